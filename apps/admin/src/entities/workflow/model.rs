@@ -34,6 +34,12 @@ pub enum StepType {
     Http,
     Notify,
     Transform,
+    WaitForEvent,
+    Approval,
+    Branch,
+    Parallel,
+    ForEach,
+    SubWorkflow,
     #[serde(other)]
     Unknown,
 }
@@ -49,6 +55,12 @@ impl std::fmt::Display for StepType {
             Self::Http => "HTTP",
             Self::Notify => "NOTIFY",
             Self::Transform => "TRANSFORM",
+            Self::WaitForEvent => "WAIT_FOR_EVENT",
+            Self::Approval => "APPROVAL",
+            Self::Branch => "BRANCH",
+            Self::Parallel => "PARALLEL",
+            Self::ForEach => "FOR_EACH",
+            Self::SubWorkflow => "SUB_WORKFLOW",
             Self::Unknown => "UNKNOWN",
         };
         write!(f, "{}", s)
@@ -83,6 +95,7 @@ pub enum ExecutionStatus {
     Completed,
     Failed,
     TimedOut,
+    Suspended,
     #[serde(other)]
     Unknown,
 }
@@ -94,6 +107,7 @@ impl std::fmt::Display for ExecutionStatus {
             Self::Completed => write!(f, "COMPLETED"),
             Self::Failed => write!(f, "FAILED"),
             Self::TimedOut => write!(f, "TIMED_OUT"),
+            Self::Suspended => write!(f, "SUSPENDED"),
             Self::Unknown => write!(f, "UNKNOWN"),
         }
     }
@@ -119,6 +133,10 @@ pub struct WorkflowStep {
     pub id: String,
     #[serde(rename = "workflowId")]
     pub workflow_id: String,
+    #[serde(rename = "parentStepId", default)]
+    pub parent_step_id: Option<String>,
+    #[serde(default)]
+    pub branch: Option<String>,
     pub position: i32,
     #[serde(rename = "stepType")]
     pub step_type: StepType,
@@ -172,6 +190,8 @@ pub struct StepExecution {
     pub id: String,
     #[serde(rename = "stepId")]
     pub step_id: String,
+    #[serde(default)]
+    pub branch: Option<String>,
     pub status: String,
     pub error: Option<String>,
     #[serde(rename = "startedAt")]
@@ -13,10 +13,10 @@ pub const WORKFLOWS_QUERY: &str =
     "query Workflows { workflows { id tenantId name status failureCount createdAt updatedAt } }";
 
 pub const WORKFLOW_QUERY: &str =
-    "query Workflow($id: UUID!) { workflow(id: $id) { id tenantId name description status triggerConfig createdBy createdAt updatedAt failureCount autoDisabledAt steps { id workflowId position stepType config onError timeoutMs } } }";
+    "query Workflow($id: UUID!) { workflow(id: $id) { id tenantId name description status triggerConfig createdBy createdAt updatedAt failureCount autoDisabledAt steps { id workflowId parentStepId branch position stepType config onError timeoutMs } } }";
 
 pub const WORKFLOW_EXECUTIONS_QUERY: &str =
-    "query WorkflowExecutions($workflowId: UUID!) { workflowExecutions(workflowId: $workflowId) { id workflowId status error startedAt completedAt stepExecutions { id stepId status error startedAt completedAt } } }";
+    "query WorkflowExecutions($workflowId: UUID!) { workflowExecutions(workflowId: $workflowId) { id workflowId status error startedAt completedAt stepExecutions { id stepId branch status error startedAt completedAt } } }";
 
 pub const CREATE_WORKFLOW_MUTATION: &str =
     "mutation CreateWorkflow($input: GqlCreateWorkflowInput!) { createWorkflow(input: $input) }";
@@ -81,6 +81,9 @@ pub struct UpdateWorkflowVars {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CreateStepInput {
     pub position: i32,
+    #[serde(rename = "parentStepId")]
+    pub parent_step_id: Option<String>,
+    pub branch: Option<String>,
     #[serde(rename = "stepType")]
     pub step_type: String,
     pub config: Value,
@@ -169,6 +172,20 @@ async fn workflow_server_context(
     Ok((app_ctx.db.clone(), auth, tenant))
 }
 
+/// Workflow service with module actions attached, for step validation and templates.
+#[cfg(feature = "ssr")]
+fn workflow_service_with_actions(
+    db: sea_orm::DatabaseConnection,
+) -> rustok_workflow::WorkflowService {
+    use leptos::prelude::expect_context;
+    use loco_rs::app::AppContext;
+
+    let app_ctx = expect_context::<AppContext>();
+    rustok_workflow::WorkflowService::new(db).with_action_runtime(
+        rustok_workflow::controllers::action_runtime_from_context(&app_ctx),
+    )
+}
+
 #[cfg(feature = "ssr")]
 fn parse_uuid_arg(value: &str, field_name: &str) -> Result<uuid::Uuid, ServerFnError> {
     uuid::Uuid::parse_str(value).map_err(|err| server_error(format!("invalid {field_name}: {err}")))
@@ -187,6 +204,12 @@ fn parse_step_type_arg(
         "HTTP" => Ok(rustok_workflow::entities::StepType::Http),
         "NOTIFY" => Ok(rustok_workflow::entities::StepType::Notify),
         "TRANSFORM" => Ok(rustok_workflow::entities::StepType::Transform),
+        "WAIT_FOR_EVENT" => Ok(rustok_workflow::entities::StepType::WaitForEvent),
+        "APPROVAL" => Ok(rustok_workflow::entities::StepType::Approval),
+        "BRANCH" => Ok(rustok_workflow::entities::StepType::Branch),
+        "PARALLEL" => Ok(rustok_workflow::entities::StepType::Parallel),
+        "FOR_EACH" => Ok(rustok_workflow::entities::StepType::ForEach),
+        "SUB_WORKFLOW" => Ok(rustok_workflow::entities::StepType::SubWorkflow),
         other => Err(server_error(format!("unsupported step type: {other}"))),
     }
 }
@@ -222,6 +245,12 @@ fn map_step_type(step_type: rustok_workflow::entities::StepType) -> StepType {
         rustok_workflow::entities::StepType::Http => StepType::Http,
         rustok_workflow::entities::StepType::Notify => StepType::Notify,
         rustok_workflow::entities::StepType::Transform => StepType::Transform,
+        rustok_workflow::entities::StepType::WaitForEvent => StepType::WaitForEvent,
+        rustok_workflow::entities::StepType::Approval => StepType::Approval,
+        rustok_workflow::entities::StepType::Branch => StepType::Branch,
+        rustok_workflow::entities::StepType::Parallel => StepType::Parallel,
+        rustok_workflow::entities::StepType::ForEach => StepType::ForEach,
+        rustok_workflow::entities::StepType::SubWorkflow => StepType::SubWorkflow,
     }
 }
 
@@ -241,6 +270,7 @@ fn map_execution_status(status: rustok_workflow::entities::ExecutionStatus) -> E
         rustok_workflow::entities::ExecutionStatus::Completed => ExecutionStatus::Completed,
         rustok_workflow::entities::ExecutionStatus::Failed => ExecutionStatus::Failed,
         rustok_workflow::entities::ExecutionStatus::TimedOut => ExecutionStatus::TimedOut,
+        rustok_workflow::entities::ExecutionStatus::Suspended => ExecutionStatus::Suspended,
     }
 }
 
@@ -262,6 +292,8 @@ fn map_workflow_step(value: rustok_workflow::WorkflowStepResponse) -> WorkflowSt
     WorkflowStep {
         id: value.id.to_string(),
         workflow_id: value.workflow_id.to_string(),
+        parent_step_id: value.parent_step_id.map(|id| id.to_string()),
+        branch: value.branch,
         position: value.position,
         step_type: map_step_type(value.step_type),
         config: value.config,
@@ -303,6 +335,7 @@ fn map_workflow_execution(value: rustok_workflow::WorkflowExecutionResponse) ->
             .map(|step| StepExecution {
                 id: step.id.to_string(),
                 step_id: step.step_id.to_string(),
+                branch: step.branch,
                 status: step.status.to_string().to_ascii_uppercase(),
                 error: step.error,
                 started_at: step.started_at.to_rfc3339(),
@@ -665,19 +698,27 @@ async fn add_step_native(
     {
         use rustok_core::Permission;
 
-        let (db, _auth, tenant) =
+        let (db, auth, tenant) =
             workflow_server_context(&[Permission::WORKFLOWS_UPDATE], "workflows:update required")
                 .await?;
         let workflow_id = parse_uuid_arg(&workflow_id, "workflow id")?;
         let step_type = parse_step_type_arg(&input.step_type)?;
         let on_error = parse_on_error_arg(&input.on_error)?;
+        let parent_step_id = input
+            .parent_step_id
+            .as_deref()
+            .map(|id| parse_uuid_arg(id, "parent step id"))
+            .transpose()?;
 
-        rustok_workflow::WorkflowService::new(db)
+        workflow_service_with_actions(db)
             .add_step(
                 tenant.id,
+                auth.security_context(),
                 workflow_id,
                 rustok_workflow::CreateWorkflowStepInput {
                     position: input.position,
+                    parent_step_id,
+                    branch: input.branch,
                     step_type,
                     config: input.config,
                     on_error,
@@ -972,8 +1013,8 @@ async fn create_from_template_native(
             workflow_server_context(&[Permission::WORKFLOWS_CREATE], "workflows:create required")
                 .await?;
 
-        rustok_workflow::WorkflowService::new(db)
-            .create_from_template(tenant.id, Some(auth.user_id), &template_id, name)
+        workflow_service_with_actions(db)
+            .create_from_template(tenant.id, auth.security_context(), &template_id, name)
             .await
             .map(|id| id.to_string())
             .map_err(|err| server_error(err.to_string()))
@@ -1071,8 +1112,8 @@ async fn restore_version_native(workflow_id: String, version: i32) -> Result<(),
                 .await?;
         let workflow_id = parse_uuid_arg(&workflow_id, "workflow id")?;
 
-        rustok_workflow::WorkflowService::new(db)
-            .restore_version(tenant.id, workflow_id, version, Some(auth.user_id))
+        workflow_service_with_actions(db)
+            .restore_version(tenant.id, workflow_id, version, auth.security_context())
             .await
             .map_err(|err| server_error(err.to_string()))
     }
@@ -62,6 +62,10 @@ pub fn ExecutionBadge(status: ExecutionStatus) -> impl IntoView {
             "Timed out",
             "bg-orange-50 text-orange-700 dark:bg-orange-900/30 dark:text-orange-400",
         ),
+        ExecutionStatus::Suspended => (
+            "Suspended",
+            "bg-amber-50 text-amber-700 dark:bg-amber-900/30 dark:text-amber-400",
+        ),
         ExecutionStatus::Unknown => ("Unknown", "bg-muted text-muted-foreground"),
     };
     view! {
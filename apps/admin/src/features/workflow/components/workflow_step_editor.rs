@@ -12,6 +12,12 @@ const STEP_TYPES: &[&str] = &[
     "HTTP",
     "NOTIFY",
     "TRANSFORM",
+    "WAIT_FOR_EVENT",
+    "APPROVAL",
+    "BRANCH",
+    "PARALLEL",
+    "FOR_EACH",
+    "SUB_WORKFLOW",
 ];
 
 #[component]
@@ -50,6 +56,8 @@ pub fn WorkflowStepEditor(
                 wf_id,
                 api::CreateStepInput {
                     position,
+                    parent_step_id: None,
+                    branch: None,
                     step_type,
                     config: serde_json::json!({}),
                     on_error,
@@ -609,7 +609,7 @@ mod tests {
             let registry = crate::modules::build_registry();
             let extensions =
                 crate::services::module_event_dispatcher::build_shared_runtime_extensions(
-                    &registry, &settings,
+                    &registry, &settings, &ctx.db,
                 );
             ctx.shared_store.insert(extensions);
         }
@@ -0,0 +1 @@
+pub use rustok_blog::controllers::feeds::*;
@@ -2,6 +2,7 @@ use axum::routing::get;
 use loco_rs::controller::Routes;
 
 pub mod comments;
+pub mod feeds;
 pub mod posts;
 
 pub fn routes() -> Routes {
@@ -0,0 +1 @@
+pub use rustok_forum::controllers::feeds::*;
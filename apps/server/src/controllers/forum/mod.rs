@@ -2,6 +2,7 @@ use axum::routing::get;
 use loco_rs::controller::Routes;
 
 pub mod categories;
+pub mod feeds;
 pub mod replies;
 pub mod topics;
 
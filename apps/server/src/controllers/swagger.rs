@@ -147,6 +147,10 @@ pub struct ApiDoc;
         crate::controllers::blog::posts::publish_post,
         crate::controllers::blog::posts::unpublish_post,
         crate::controllers::blog::comments::moderate_comment,
+        crate::controllers::blog::feeds::posts_feed,
+        crate::controllers::blog::feeds::category_feed,
+        crate::controllers::blog::feeds::tag_feed,
+        crate::controllers::blog::feeds::author_feed,
     ),
     components(
         schemas(
@@ -160,6 +164,7 @@ pub struct ApiDoc;
             rustok_blog::dto::ModerateCommentInput,
             rustok_blog::dto::ModerateCommentStatus,
             rustok_blog::state_machine::BlogPostStatus,
+            rustok_blog::controllers::feeds::FeedParams,
         )
     ),
     tags((name = "blog", description = "Blog endpoints"))
@@ -185,6 +190,8 @@ pub struct BlogApiDoc;
         crate::controllers::forum::replies::create_reply,
         crate::controllers::forum::replies::update_reply,
         crate::controllers::forum::replies::delete_reply,
+        crate::controllers::forum::feeds::topics_feed,
+        crate::controllers::forum::feeds::category_feed,
     ),
     components(
         schemas(
@@ -202,6 +209,7 @@ pub struct BlogApiDoc;
             rustok_forum::ListRepliesFilter,
             rustok_forum::ReplyResponse,
             rustok_forum::ReplyListItem,
+            rustok_forum::controllers::feeds::FeedParams,
         )
     ),
     tags((name = "forum", description = "Forum endpoints"))
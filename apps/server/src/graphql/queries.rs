@@ -72,9 +72,8 @@ fn map_content_error(err: rustok_content::ContentError) -> FieldError {
         | rustok_content::ContentError::CategoryNotFound(_)
         | rustok_content::ContentError::TranslationNotFound { .. }
         | rustok_content::ContentError::DuplicateSlug { .. }
-        | rustok_content::ContentError::ConcurrentModification { .. } => {
-            FieldError::new(err.to_string())
-        }
+        | rustok_content::ContentError::ConcurrentModification { .. }
+        | rustok_content::ContentError::UserSanctioned { .. } => FieldError::new(err.to_string()),
         rustok_content::ContentError::Database(inner) => {
             <FieldError as GraphQLError>::internal_error(&inner.to_string())
         }
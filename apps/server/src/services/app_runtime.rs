@@ -97,7 +97,7 @@ pub async fn bootstrap_app_runtime(
     };
 
     let registry = modules::build_registry();
-    let runtime_extensions = build_shared_runtime_extensions(&registry, settings);
+    let runtime_extensions = build_shared_runtime_extensions(&registry, settings, &ctx.db);
     ctx.shared_store.insert(runtime_extensions.clone());
     ctx.shared_store
         .insert(rustok_ai::SharedAiModuleRegistry(registry.clone()));
@@ -200,11 +200,18 @@ fn init_alloy_runtime(_ctx: &AppContext) {
 
 #[cfg(feature = "mod-workflow")]
 fn init_workflow_runtime(ctx: &AppContext) {
-    use rustok_workflow::WorkflowCronScheduler;
+    use rustok_workflow::{WebhookDeliveryLog, WebhookDispatcher, WorkflowCronScheduler};
     let db = ctx.db.clone();
+    let action_runtime = rustok_workflow::controllers::action_runtime_from_context(ctx);
+
+    // Webhook ingress queues rejected deliveries; the cron scheduler writes them
+    let delivery_log = WebhookDeliveryLog::new();
+    ctx.shared_store.insert(delivery_log.clone());
 
     // Start the cron scheduler
-    let scheduler = WorkflowCronScheduler::new(db);
+    let scheduler = WorkflowCronScheduler::new(db.clone())
+        .with_action_runtime(action_runtime)
+        .with_webhook_delivery_log(delivery_log);
     let handle = scheduler.start();
     tokio::spawn(async move {
         if let Err(error) = handle.await {
@@ -212,7 +219,15 @@ fn init_workflow_runtime(ctx: &AppContext) {
         }
     });
 
-    tracing::info!("Workflow runtime initialized (cron scheduler)");
+    // Send queued outbound webhook deliveries
+    let handle = WebhookDispatcher::new(db).start();
+    tokio::spawn(async move {
+        if let Err(error) = handle.await {
+            tracing::error!("Webhook dispatcher panicked: {:?}", error);
+        }
+    });
+
+    tracing::info!("Workflow runtime initialized (cron scheduler, webhook dispatcher)");
 }
 
 struct RateLimitLayers {
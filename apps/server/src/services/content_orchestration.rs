@@ -1271,6 +1271,9 @@ async fn find_or_create_blog_taxonomy_term_in_tx(
         scope_value: Set("blog".to_string()),
         canonical_key: Set(slug.to_string()),
         status: Set(TaxonomyTermStatus::Active),
+        parent_id: Set(None),
+        depth: Set(0),
+        path: Set(taxonomy_term::child_path(None, term_id)),
         created_at: Set(now.into()),
         updated_at: Set(now.into()),
     }
@@ -1783,6 +1786,9 @@ async fn find_or_create_forum_taxonomy_term_in_tx(
         scope_value: Set("forum".to_string()),
         canonical_key: Set(slug.to_string()),
         status: Set(TaxonomyTermStatus::Active),
+        parent_id: Set(None),
+        depth: Set(0),
+        path: Set(taxonomy_term::child_path(None, term_id)),
         created_at: Set(now.into()),
         updated_at: Set(now.into()),
     }
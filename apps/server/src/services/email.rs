@@ -366,6 +366,45 @@ pub fn email_service_from_ctx(
     }
 }
 
+/// Build the email runtime used by the notification center.
+///
+/// Returns `None` unless the SMTP provider is enabled; notifications then only
+/// reach the in-app inbox.
+#[cfg(feature = "mod-notifications")]
+pub fn notification_email_runtime(
+    settings: &RustokSettings,
+    db: &sea_orm::DatabaseConnection,
+) -> Option<rustok_notifications::NotificationEmailRuntime> {
+    if !settings.email.enabled || !matches!(settings.email.provider, EmailProvider::Smtp) {
+        return None;
+    }
+
+    let config = rustok_email::EmailConfig {
+        enabled: settings.email.enabled,
+        smtp: rustok_email::SmtpConfig {
+            host: settings.email.smtp.host.clone(),
+            port: settings.email.smtp.port,
+            username: settings.email.smtp.username.clone(),
+            password: settings.email.smtp.password.clone(),
+        },
+        from: settings.email.from.clone(),
+        reset_base_url: settings.email.reset_base_url.clone(),
+    };
+    let sender = match EmailService::from_config(&config) {
+        Ok(EmailService::Smtp(sender)) => *sender,
+        Ok(EmailService::Disabled) => return None,
+        Err(error) => {
+            tracing::warn!(%error, "Notification email disabled: SMTP transport failed to build");
+            return None;
+        }
+    };
+    let sender = sender.with_provider(Arc::new(rustok_notifications::NotificationEmailTemplates));
+    Some(rustok_notifications::NotificationEmailRuntime::new(
+        Arc::new(sender),
+        Arc::new(rustok_notifications::UserTableDirectory::new(db.clone())),
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
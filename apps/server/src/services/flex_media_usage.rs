@@ -0,0 +1,53 @@
+//! Where-used reporting for media referenced from flex entries.
+//!
+//! Flex entries are server-owned, so their [`MediaUsageProvider`] is
+//! registered here rather than by a module crate.
+
+use anyhow::Result as AnyResult;
+use async_trait::async_trait;
+use rustok_media::{collect_json_references, MediaReference, MediaUsageProvider};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use uuid::Uuid;
+
+use crate::models::{flex_entries, flex_entry_localized_values};
+
+#[derive(Clone, Default)]
+pub struct FlexMediaUsageProvider;
+
+#[async_trait]
+impl MediaUsageProvider for FlexMediaUsageProvider {
+    fn owner_kind(&self) -> &'static str {
+        "flex_entry"
+    }
+
+    fn owner_module_slug(&self) -> &'static str {
+        "flex"
+    }
+
+    async fn collect_references(
+        &self,
+        db: &DatabaseConnection,
+        tenant_id: Uuid,
+    ) -> AnyResult<Vec<MediaReference>> {
+        let mut references = Vec::new();
+        for entry in flex_entries::Entity::find()
+            .filter(flex_entries::Column::TenantId.eq(tenant_id))
+            .all(db)
+            .await?
+        {
+            references.extend(collect_json_references(entry.id, "data", &entry.data));
+        }
+        for value in flex_entry_localized_values::Entity::find()
+            .filter(flex_entry_localized_values::Column::TenantId.eq(tenant_id))
+            .all(db)
+            .await?
+        {
+            references.extend(collect_json_references(
+                value.entry_id,
+                &format!("data.{}", value.locale),
+                &value.data,
+            ));
+        }
+        Ok(references)
+    }
+}
@@ -1,5 +1,5 @@
 use loco_rs::app::AppContext;
-use rustok_core::events::{DispatcherConfig, EventDispatcher};
+use rustok_core::events::{DispatcherConfig, EventDispatcher, EventTransport};
 use rustok_core::{EventBus, ModuleEventListenerContext, ModuleRegistry, ModuleRuntimeExtensions};
 use rustok_index::IndexerRuntimeConfig;
 use rustok_telemetry::metrics;
@@ -15,7 +15,9 @@ pub fn spawn_module_event_dispatcher(
 ) {
     let bus = crate::services::event_bus::event_bus_from_context(ctx);
     let db = ctx.db.clone();
-    let dispatcher = build_module_event_dispatcher(registry, bus, db, extensions.as_ref());
+    let transport = ctx.shared_store.get::<Arc<dyn EventTransport>>();
+    let dispatcher =
+        build_module_event_dispatcher(registry, bus, db, extensions.as_ref(), transport);
     let handler_count = dispatcher.handler_count();
     if handler_count == 0 {
         tracing::info!("No module-owned event listeners registered in ModuleRegistry");
@@ -35,6 +37,7 @@ pub fn spawn_module_event_dispatcher(
 pub fn build_shared_runtime_extensions(
     registry: &ModuleRegistry,
     settings: &RustokSettings,
+    db: &DatabaseConnection,
 ) -> Arc<ModuleRuntimeExtensions> {
     let mut extensions = registry.build_runtime_extensions();
     let indexer_runtime = IndexerRuntimeConfig::new(
@@ -61,6 +64,18 @@ pub fn build_shared_runtime_extensions(
         settings.search.reindex.yield_every,
     );
     extensions.insert(indexer_runtime);
+    #[cfg(feature = "mod-media")]
+    rustok_media::register_media_usage_provider(
+        &mut extensions,
+        crate::services::flex_media_usage::FlexMediaUsageProvider,
+    )
+    .expect("flex media usage registration should remain unique");
+    #[cfg(feature = "mod-notifications")]
+    if let Some(runtime) = crate::services::email::notification_email_runtime(settings, db) {
+        extensions.insert(runtime);
+    }
+    #[cfg(not(feature = "mod-notifications"))]
+    let _ = db;
     Arc::new(extensions)
 }
 
@@ -69,8 +84,13 @@ pub fn build_module_event_dispatcher(
     bus: EventBus,
     db: DatabaseConnection,
     extensions: &ModuleRuntimeExtensions,
+    event_transport: Option<Arc<dyn EventTransport>>,
 ) -> EventDispatcher {
-    let listener_ctx = ModuleEventListenerContext { db, extensions };
+    let listener_ctx = ModuleEventListenerContext {
+        db,
+        extensions,
+        event_transport,
+    };
     let handlers = registry.build_event_listeners(&listener_ctx);
     let mut dispatcher = EventDispatcher::with_config(
         bus,
@@ -105,13 +125,17 @@ mod tests {
         #[cfg(feature = "mod-workflow")]
         let registry = registry.register(rustok_workflow::WorkflowModule);
         let settings = RustokSettings::default();
-        let extensions = build_shared_runtime_extensions(&registry, &settings);
-
         let db = Database::connect("sqlite::memory:")
             .await
             .expect("in-memory sqlite should connect");
-        let dispatcher =
-            build_module_event_dispatcher(&registry, EventBus::default(), db, extensions.as_ref());
+        let extensions = build_shared_runtime_extensions(&registry, &settings, &db);
+        let dispatcher = build_module_event_dispatcher(
+            &registry,
+            EventBus::default(),
+            db,
+            extensions.as_ref(),
+            None,
+        );
 
         let expected = if cfg!(feature = "mod-workflow") { 5 } else { 4 };
         assert_eq!(dispatcher.handler_count(), expected);
@@ -0,0 +1,208 @@
+//! Content Import Task
+//!
+//! Imports a WordPress WXR export or a directory of Markdown files with YAML
+//! front matter into blog, pages, media and SEO redirects.
+//!
+//! Run manually:
+//! ```text
+//! cargo loco task --name content_import tenant_id=<uuid> format=wxr path=export.xml dry_run=true
+//! cargo loco task --name content_import tenant_id=<uuid> format=markdown path=./content
+//! cargo loco task --name content_import tenant_id=<uuid> format=wxr path=export.xml job_id=<uuid>
+//! ```
+//!
+//! Source authors are matched to platform users by email. Passing the
+//! `job_id` of an earlier run resumes it: already imported entries are skipped
+//! and failed ones are retried.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use loco_rs::{
+    app::AppContext,
+    task::{Task, TaskInfo, Vars},
+    Error, Result,
+};
+use rustok_api::TenantContext;
+use rustok_core::SecurityContext;
+use rustok_import::{
+    load_markdown_dir, parse_wxr, DefaultMediaFetcher, ImportOptions, ImportService,
+    ImportSourceKind,
+};
+use rustok_media::MediaService;
+use rustok_seo::{SeoService, SeoTargetRegistry};
+use rustok_storage::StorageService;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use uuid::Uuid;
+
+use crate::models::{tenants, users};
+use crate::services::event_bus::transactional_event_bus_from_context;
+
+pub struct ContentImportTask;
+
+#[async_trait]
+impl Task for ContentImportTask {
+    fn task(&self) -> TaskInfo {
+        TaskInfo {
+            name: "content_import".to_string(),
+            detail: "Import WordPress WXR or Markdown content with dry-run and resumable jobs"
+                .to_string(),
+        }
+    }
+
+    async fn run(&self, ctx: &AppContext, vars: &Vars) -> Result<()> {
+        let tenant = resolve_tenant(ctx, vars).await?;
+        let source_kind = vars
+            .cli
+            .get("format")
+            .and_then(|raw| ImportSourceKind::parse(raw))
+            .ok_or_else(|| {
+                Error::Message("content_import requires format=wxr|markdown".to_string())
+            })?;
+        let Some(path) = vars.cli.get("path") else {
+            return Err(Error::Message(
+                "content_import requires path=<file or directory>".to_string(),
+            ));
+        };
+        let dry_run = is_flag_enabled(vars, "dry_run");
+
+        let bundle = match source_kind {
+            ImportSourceKind::Wxr => {
+                let xml = std::fs::read_to_string(path)
+                    .map_err(|error| Error::Message(format!("Failed to read {path}: {error}")))?;
+                parse_wxr(&xml)
+            }
+            ImportSourceKind::Markdown => load_markdown_dir(Path::new(path)),
+        }
+        .map_err(|error| Error::Message(format!("Failed to read import source: {error}")))?;
+
+        let options = ImportOptions {
+            locale: vars
+                .cli
+                .get("locale")
+                .cloned()
+                .unwrap_or_else(|| tenant.default_locale.clone()),
+            author_map: load_author_map(ctx, tenant.id).await?,
+            default_author_id: parse_uuid_var(vars, "default_author_id")?,
+            guest_author_id: parse_uuid_var(vars, "guest_author_id")?,
+            import_media: !is_flag_disabled(vars, "media"),
+            create_redirects: !is_flag_disabled(vars, "redirects"),
+            ..ImportOptions::default()
+        };
+
+        let event_bus = transactional_event_bus_from_context(ctx);
+        let mut service = ImportService::new(ctx.db.clone(), event_bus.clone()).with_seo(
+            SeoService::new(
+                ctx.db.clone(),
+                event_bus,
+                Arc::new(SeoTargetRegistry::default()),
+            ),
+        );
+        if source_kind == ImportSourceKind::Markdown {
+            // Relative images in Markdown resolve to `file://` URLs inside the import directory.
+            service = service
+                .with_media_fetcher(DefaultMediaFetcher::new().with_local_root(Path::new(path)));
+        }
+        if let Some(storage) = ctx.shared_store.get::<StorageService>() {
+            service = service.with_media(MediaService::new(ctx.db.clone(), storage.clone()));
+        } else {
+            tracing::warn!("StorageService not available — media will not be imported");
+        }
+
+        let security = SecurityContext::system();
+        let tenant_context = TenantContext {
+            id: tenant.id,
+            name: tenant.name,
+            slug: tenant.slug,
+            domain: tenant.domain,
+            settings: tenant.settings,
+            default_locale: tenant.default_locale,
+            is_active: tenant.is_active,
+        };
+        let job_id = parse_uuid_var(vars, "job_id")?;
+
+        let report = if dry_run {
+            service
+                .dry_run(&tenant_context, &security, job_id, &bundle, &options)
+                .await
+        } else {
+            let job_id = match job_id {
+                Some(job_id) => job_id,
+                None => {
+                    service
+                        .create_job(tenant_context.id, &security, source_kind, path)
+                        .await
+                        .map_err(|error| {
+                            Error::Message(format!("Failed to create import job: {error}"))
+                        })?
+                        .id
+                }
+            };
+            service
+                .run_job(&tenant_context, &security, job_id, &bundle, &options)
+                .await
+        }
+        .map_err(|error| Error::Message(format!("Content import failed: {error}")))?;
+
+        let payload = serde_json::to_string_pretty(&report).map_err(|error| {
+            Error::Message(format!("Failed to serialize content import report: {error}"))
+        })?;
+        println!("{payload}");
+        Ok(())
+    }
+}
+
+async fn resolve_tenant(
+    ctx: &AppContext,
+    vars: &Vars,
+) -> Result<crate::models::_entities::tenants::Model> {
+    let Some(raw_tenant_id) = vars.cli.get("tenant_id") else {
+        return Err(Error::Message(
+            "content_import requires tenant_id=<uuid>".to_string(),
+        ));
+    };
+    let tenant_id = Uuid::parse_str(raw_tenant_id)
+        .map_err(|error| Error::Message(format!("Invalid tenant_id '{raw_tenant_id}': {error}")))?;
+
+    tenants::Entity::find_by_id(&ctx.db, tenant_id)
+        .await
+        .map_err(|error| Error::Message(format!("Failed to load tenant {tenant_id}: {error}")))?
+        .ok_or_else(|| Error::Message(format!("Tenant {tenant_id} not found")))
+}
+
+async fn load_author_map(ctx: &AppContext, tenant_id: Uuid) -> Result<HashMap<String, Uuid>> {
+    let users = users::Entity::find()
+        .filter(users::Column::TenantId.eq(tenant_id))
+        .all(&ctx.db)
+        .await
+        .map_err(|error| Error::Message(format!("Failed to load users: {error}")))?;
+    Ok(users
+        .into_iter()
+        .map(|user| (user.email.to_lowercase(), user.id))
+        .collect())
+}
+
+fn parse_uuid_var(vars: &Vars, key: &str) -> Result<Option<Uuid>> {
+    vars.cli
+        .get(key)
+        .map(|raw| {
+            Uuid::parse_str(raw)
+                .map_err(|error| Error::Message(format!("Invalid {key} '{raw}': {error}")))
+        })
+        .transpose()
+}
+
+fn is_flag_enabled(vars: &Vars, key: &str) -> bool {
+    vars.cli
+        .get(key)
+        .map(|value| matches!(value.as_str(), "1" | "true" | "yes" | "on"))
+        .unwrap_or(false)
+}
+
+fn is_flag_disabled(vars: &Vars, key: &str) -> bool {
+    vars.cli
+        .get(key)
+        .map(|value| matches!(value.as_str(), "0" | "false" | "no" | "off"))
+        .unwrap_or(false)
+}
@@ -4,6 +4,10 @@
 //! confirmed missing from storage.  Safe to run in production: unknown
 //! errors are treated conservatively (record is kept).
 //!
+//! Also aborts expired direct upload sessions and discards their parts, and
+//! logs a per-tenant orphan report (unused media and references to missing
+//! media). The report is informational: unused media is never deleted here.
+//!
 //! Run manually:
 //! ```text
 //! cargo loco task --name media_cleanup
@@ -61,6 +65,17 @@ async fn run_media_cleanup(ctx: &AppContext) -> Result<()> {
     }
     let _ = storage.delete(probe).await;
 
+    let usage = ctx
+        .shared_store
+        .get::<std::sync::Arc<rustok_core::ModuleRuntimeExtensions>>()
+        .and_then(|extensions| rustok_media::media_usage_registry_from_extensions(&extensions));
+    let service =
+        rustok_media::MediaService::new(ctx.db.clone(), storage.clone()).with_usage_registry(usage);
+    match service.abort_expired_uploads().await {
+        Ok(aborted) => tracing::info!(aborted, "Aborted expired media upload sessions"),
+        Err(e) => tracing::warn!(error = %e, "Failed to abort expired media upload sessions"),
+    }
+
     // Fetch full models and use the fields we need. This avoids brittle
     // column-only type plumbing for a maintenance task.
     let records = MediaEntity::find()
@@ -111,5 +126,44 @@ async fn run_media_cleanup(ctx: &AppContext) -> Result<()> {
     }
 
     tracing::info!(scanned = total, removed, "Media cleanup complete");
+
+    report_orphans(ctx, &service).await;
     Ok(())
 }
+
+#[cfg(feature = "mod-media")]
+async fn report_orphans(ctx: &AppContext, service: &rustok_media::MediaService) {
+    use rustok_media::media::{Column as MediaCol, Entity as MediaEntity};
+    use sea_orm::{EntityTrait, QuerySelect};
+
+    let tenant_ids: Vec<uuid::Uuid> = match MediaEntity::find()
+        .select_only()
+        .column(MediaCol::TenantId)
+        .distinct()
+        .into_tuple()
+        .all(&ctx.db)
+        .await
+    {
+        Ok(tenant_ids) => tenant_ids,
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to list tenants for media orphan report");
+            return;
+        }
+    };
+
+    for tenant_id in tenant_ids {
+        match service.orphan_report(tenant_id).await {
+            Ok(report) => tracing::info!(
+                tenant_id = %tenant_id,
+                unused = report.unused.len(),
+                unused_size = report.unused_size,
+                broken_references = report.broken_references.len(),
+                indexed_usages = report.indexed_usages,
+                "Media orphan report"
+            ),
+            Err(e) => {
+                tracing::warn!(tenant_id = %tenant_id, error = %e, "Failed to build media orphan report")
+            }
+        }
+    }
+}
@@ -6,9 +6,13 @@
 use loco_rs::task::Tasks;
 
 mod cleanup;
+#[cfg(feature = "mod-import")]
+mod content_import;
 mod create_oauth_app;
 mod db_baseline;
 mod media_cleanup;
+#[cfg(feature = "mod-notifications")]
+mod notification_digest;
 #[cfg(feature = "mod-profiles")]
 mod profiles_backfill;
 mod rebuild;
@@ -17,9 +21,13 @@ mod rebuild;
 pub fn register(tasks: &mut Tasks) {
     // Maintenance tasks
     tasks.register(cleanup::CleanupTask);
+    #[cfg(feature = "mod-import")]
+    tasks.register(content_import::ContentImportTask);
     tasks.register(create_oauth_app::CreateOAuthAppTask);
     tasks.register(db_baseline::DbBaselineTask);
     tasks.register(media_cleanup::MediaCleanupTask);
+    #[cfg(feature = "mod-notifications")]
+    tasks.register(notification_digest::NotificationDigestTask);
     #[cfg(feature = "mod-profiles")]
     tasks.register(profiles_backfill::ProfilesBackfillTask);
     tasks.register(rebuild::RebuildTask);
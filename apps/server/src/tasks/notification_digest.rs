@@ -0,0 +1,54 @@
+//! Notification Digest Task
+//!
+//! Sends the daily email digest to every user with queued `daily`
+//! notification deliveries. Does nothing when SMTP email is disabled.
+//!
+//! Run manually:
+//! ```text
+//! cargo loco task --name notification_digest
+//! ```
+//! Or schedule via `scheduler.yaml`.
+
+use async_trait::async_trait;
+use loco_rs::{
+    app::AppContext,
+    task::{Task, TaskInfo, Vars},
+    Error, Result,
+};
+use rustok_notifications::DigestService;
+
+use crate::common::settings::RustokSettings;
+use crate::services::email::notification_email_runtime;
+
+pub struct NotificationDigestTask;
+
+#[async_trait]
+impl Task for NotificationDigestTask {
+    fn task(&self) -> TaskInfo {
+        TaskInfo {
+            name: "notification_digest".to_string(),
+            detail: "Send daily notification email digests".to_string(),
+        }
+    }
+
+    async fn run(&self, ctx: &AppContext, _vars: &Vars) -> Result<()> {
+        let settings = RustokSettings::from_settings(&ctx.config.settings)
+            .map_err(|error| Error::Message(error.to_string()))?;
+        let Some(email) = notification_email_runtime(&settings, &ctx.db) else {
+            tracing::info!("SMTP email disabled — notification digest is a no-op");
+            return Ok(());
+        };
+
+        let summary = DigestService::new(ctx.db.clone(), email)
+            .send_daily_digests()
+            .await
+            .map_err(|error| Error::Message(format!("Notification digest failed: {error}")))?;
+        tracing::info!(
+            recipients = summary.recipients,
+            notifications = summary.notifications,
+            failed_recipients = summary.failed_recipients,
+            "Notification digest finished"
+        );
+        Ok(())
+    }
+}
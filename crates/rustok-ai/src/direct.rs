@@ -44,6 +44,7 @@ mod direct_order_generation;
 mod direct_order_tasks;
 #[path = "direct_product_attributes.rs"]
 mod direct_product_attributes;
+pub use direct_content_moderation::AiSpamClassifier;
 use direct_domain_commerce::register_commerce_direct_handlers;
 use direct_domain_content::register_content_direct_handlers;
 use direct_domain_orders::register_order_direct_handlers;
@@ -1,14 +1,23 @@
 #![cfg(feature = "server")]
 
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use chrono::Utc;
+use rustok_content::{
+    ContentError, ContentResult, SpamAction, SpamClassifier, SpamHistory, SpamSubmission,
+    SpamVerdict,
+};
 use serde_json::json;
 
 use crate::direct::{
     explain_result, generate_content_moderation, DirectExecutionRequest, DirectExecutionResult,
     DirectTaskHandler,
 };
-use crate::model::{AiContentModerationTaskInput, DirectExecutionTarget, ToolTrace};
+use crate::model::{
+    AiContentModerationTaskInput, AiProviderConfig, DirectExecutionTarget, ToolTrace,
+};
+use crate::provider::ModelProvider;
 use crate::service::AiOperatorContext;
 use crate::{AiError, AiResult};
 use loco_rs::app::AppContext;
@@ -72,3 +81,75 @@ impl DirectTaskHandler for ContentModerationHandler {
         })
     }
 }
+
+/// Anti-spam classifier backed by the same provider prompt as [`ContentModerationHandler`].
+///
+/// `review` decisions hold the submission, `block` decisions mark it as spam. Provider failures
+/// surface as errors, which the spam pipeline logs and ignores.
+pub struct AiSpamClassifier {
+    provider: Arc<dyn ModelProvider>,
+    provider_config: AiProviderConfig,
+    system_prompt: Option<String>,
+}
+
+impl AiSpamClassifier {
+    pub fn new(provider: Arc<dyn ModelProvider>, provider_config: AiProviderConfig) -> Self {
+        Self {
+            provider,
+            provider_config,
+            system_prompt: None,
+        }
+    }
+
+    pub fn with_system_prompt(mut self, system_prompt: impl Into<String>) -> Self {
+        self.system_prompt = Some(system_prompt.into());
+        self
+    }
+}
+
+#[async_trait]
+impl SpamClassifier for AiSpamClassifier {
+    fn name(&self) -> &'static str {
+        "ai_content_moderation"
+    }
+
+    async fn classify(
+        &self,
+        submission: &SpamSubmission,
+        _history: &SpamHistory,
+    ) -> ContentResult<Option<SpamVerdict>> {
+        let input = AiContentModerationTaskInput {
+            content_id: None,
+            content_type: Some(submission.surface.as_str().to_string()),
+            title: None,
+            body: Some(submission.body.clone()),
+            locale: Some(submission.locale.clone()),
+            assistant_prompt: None,
+        };
+        let generated = generate_content_moderation(
+            &self.provider,
+            &self.provider_config,
+            self.system_prompt.as_deref(),
+            submission.locale.as_str(),
+            &input,
+        )
+        .await
+        .map_err(|error| ContentError::Validation(format!("AI moderation failed: {error}")))?;
+
+        let action = match generated.decision.trim().to_ascii_lowercase().as_str() {
+            "block" => SpamAction::Spam,
+            "review" => SpamAction::Hold,
+            _ if generated.requires_human => SpamAction::Hold,
+            _ => return Ok(None),
+        };
+        Ok(Some(SpamVerdict::new(
+            self.name(),
+            action,
+            format!(
+                "AI moderation: {} (severity {})",
+                generated.explanation.trim(),
+                generated.severity
+            ),
+        )))
+    }
+}
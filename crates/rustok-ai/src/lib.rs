@@ -18,7 +18,7 @@ pub mod streaming;
 
 #[cfg(feature = "server")]
 pub use direct::{
-    AlloyScriptAssistHandler, BlogDraftHandler, DirectExecutionRegistry, DirectExecutionRequest,
+    AiSpamClassifier, AlloyScriptAssistHandler, BlogDraftHandler, DirectExecutionRegistry, DirectExecutionRequest,
     DirectExecutionResult, DirectTaskHandler, MediaImageAssetHandler, ProductCopyHandler,
 };
 pub use error::{AiError, AiResult};
@@ -0,0 +1,136 @@
+use axum::{
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use rustok_core::feed::{format_http_date, is_not_modified};
+use rustok_core::{Feed, FeedFormat};
+use sha2::Digest;
+
+use crate::context::TenantContext;
+
+const FEED_CACHE_CONTROL: &str = "public, max-age=300";
+
+/// Renders `feed` as an HTTP response with `ETag` / `Last-Modified`
+/// validators, answering `304 Not Modified` when the request's conditional
+/// headers still match.
+pub fn feed_response(request_headers: &HeaderMap, feed: &Feed, format: FeedFormat) -> Response {
+    let body = feed.render(format);
+    let etag = feed_etag(&body);
+    let last_modified = feed.last_modified();
+
+    let mut headers = HeaderMap::new();
+    insert_header(&mut headers, header::ETAG, &etag);
+    insert_header(&mut headers, header::CACHE_CONTROL, FEED_CACHE_CONTROL);
+    insert_header(&mut headers, header::VARY, "Accept-Language");
+    if let Some(last_modified) = last_modified {
+        insert_header(
+            &mut headers,
+            header::LAST_MODIFIED,
+            &format_http_date(last_modified),
+        );
+    }
+
+    let header_str = |name: header::HeaderName| {
+        request_headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+    };
+    if is_not_modified(
+        header_str(header::IF_NONE_MATCH),
+        header_str(header::IF_MODIFIED_SINCE),
+        &etag,
+        last_modified,
+    ) {
+        return (StatusCode::NOT_MODIFIED, headers).into_response();
+    }
+
+    insert_header(&mut headers, header::CONTENT_TYPE, format.content_type());
+    (StatusCode::OK, headers, body).into_response()
+}
+
+/// Public origin used for absolute links in feeds: the tenant's domain when
+/// configured, otherwise `RUSTOK_PUBLIC_URL`.
+pub fn public_base_url(tenant: &TenantContext) -> String {
+    if let Some(domain) = tenant
+        .domain
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        if domain.starts_with("http://") || domain.starts_with("https://") {
+            return domain.trim_end_matches('/').to_string();
+        }
+        return format!("https://{}", domain.trim_end_matches('/'));
+    }
+    std::env::var("RUSTOK_PUBLIC_URL")
+        .or_else(|_| std::env::var("RUSTOK_API_URL"))
+        .unwrap_or_else(|_| "http://localhost:5150".to_string())
+        .trim_end_matches('/')
+        .to_string()
+}
+
+fn feed_etag(body: &str) -> String {
+    let digest = sha2::Sha256::digest(body.as_bytes());
+    format!("\"{}\"", &hex::encode(digest)[..32])
+}
+
+fn insert_header(headers: &mut HeaderMap, name: header::HeaderName, value: &str) {
+    if let Ok(value) = HeaderValue::from_str(value) {
+        headers.insert(name, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use rustok_core::FeedItem;
+
+    fn feed() -> Feed {
+        Feed {
+            title: "Blog".to_string(),
+            description: None,
+            home_page_url: "https://example.com/modules/blog".to_string(),
+            feed_url: "https://example.com/api/blog/feeds/atom".to_string(),
+            language: Some("en".to_string()),
+            items: vec![FeedItem::new(
+                "urn:uuid:1",
+                "First",
+                "https://example.com/modules/blog?slug=first",
+                Utc.with_ymd_and_hms(2026, 3, 1, 9, 0, 0).unwrap(),
+            )],
+        }
+    }
+
+    #[test]
+    fn answers_not_modified_for_matching_validators() {
+        let first = feed_response(&HeaderMap::new(), &feed(), FeedFormat::Atom);
+        assert_eq!(first.status(), StatusCode::OK);
+        assert_eq!(
+            first.headers()[header::CONTENT_TYPE],
+            "application/atom+xml; charset=utf-8"
+        );
+        assert_eq!(
+            first.headers()[header::LAST_MODIFIED],
+            "Sun, 01 Mar 2026 09:00:00 GMT"
+        );
+        let etag = first.headers()[header::ETAG].clone();
+
+        let mut conditional = HeaderMap::new();
+        conditional.insert(header::IF_NONE_MATCH, etag.clone());
+        let cached = feed_response(&conditional, &feed(), FeedFormat::Atom);
+        assert_eq!(cached.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(cached.headers()[header::ETAG], etag);
+
+        let rss = feed_response(&conditional, &feed(), FeedFormat::Rss);
+        assert_eq!(rss.status(), StatusCode::OK);
+
+        let mut since = HeaderMap::new();
+        since.insert(
+            header::IF_MODIFIED_SINCE,
+            HeaderValue::from_static("Sun, 01 Mar 2026 10:00:00 GMT"),
+        );
+        let by_date = feed_response(&since, &feed(), FeedFormat::JsonFeed);
+        assert_eq!(by_date.status(), StatusCode::NOT_MODIFIED);
+    }
+}
@@ -1,5 +1,7 @@
 pub mod context;
 #[cfg(feature = "server")]
+pub mod feed;
+#[cfg(feature = "server")]
 pub mod graphql;
 #[cfg(feature = "loco-adapter")]
 pub mod loco;
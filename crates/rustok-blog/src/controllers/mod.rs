@@ -2,6 +2,7 @@ use axum::routing::{get, post};
 use loco_rs::controller::Routes;
 
 pub mod comments;
+pub mod feeds;
 pub mod posts;
 
 pub fn routes() -> Routes {
@@ -17,4 +18,14 @@ pub fn routes() -> Routes {
         .add("/posts/{id}/publish", post(posts::publish_post))
         .add("/posts/{id}/unpublish", post(posts::unpublish_post))
         .add("/comments/{id}/moderate", post(comments::moderate_comment))
+        .add("/feeds/{format}", get(feeds::posts_feed))
+        .add(
+            "/feeds/categories/{category_id}/{format}",
+            get(feeds::category_feed),
+        )
+        .add("/feeds/tags/{tag}/{format}", get(feeds::tag_feed))
+        .add(
+            "/feeds/authors/{author_id}/{format}",
+            get(feeds::author_feed),
+        )
 }
@@ -79,6 +79,9 @@ pub struct CommentResponse {
     pub content: String,
     pub content_format: String,
     pub content_json: Option<Value>,
+    /// Sanitized HTML rendering of the content.
+    #[serde(default)]
+    pub content_html: String,
     pub status: String,
     pub parent_comment_id: Option<Uuid>,
     pub created_at: String,
@@ -119,6 +122,7 @@ mod tests {
             content: content.into(),
             content_format: content_format.into(),
             content_json,
+            content_html: String::new(),
             status: "pending".into(),
             parent_comment_id: None,
             created_at: "2024-01-01T00:00:00Z".into(),
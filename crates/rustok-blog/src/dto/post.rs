@@ -69,6 +69,13 @@ pub struct PostResponse {
     pub body: String,
     pub body_format: String,
     pub content_json: Option<Value>,
+    /// Sanitized HTML rendering of the body.
+    #[serde(default)]
+    pub body_html: String,
+    #[serde(default)]
+    pub word_count: u32,
+    #[serde(default)]
+    pub reading_time_minutes: u32,
     pub excerpt: Option<String>,
     pub status: BlogPostStatus,
     pub category_id: Option<Uuid>,
@@ -112,6 +119,9 @@ mod tests {
             body: body.to_string(),
             body_format: body_format.to_string(),
             content_json,
+            body_html: String::new(),
+            word_count: 0,
+            reading_time_minutes: 0,
             excerpt: None,
             status: BlogPostStatus::Draft,
             category_id: None,
@@ -186,6 +196,8 @@ pub struct PostListQuery {
     pub status: Option<BlogPostStatus>,
     pub category_id: Option<Uuid>,
     pub tag: Option<String>,
+    /// Taxonomy term id; matches posts tagged with the term or any of its descendants.
+    pub tag_id: Option<Uuid>,
     pub author_id: Option<Uuid>,
     pub search: Option<String>,
     pub locale: Option<String>,
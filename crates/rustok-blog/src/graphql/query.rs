@@ -154,6 +154,7 @@ impl BlogQuery {
         let filter = filter.unwrap_or(PostsFilter {
             status: None,
             author_id: None,
+            tag_id: None,
             locale: None,
             page: Some(1),
             per_page: Some(20),
@@ -186,6 +187,7 @@ impl BlogQuery {
                     status: filter.status.map(Into::into),
                     category_id: None,
                     tag: None,
+                    tag_id: filter.tag_id,
                     author_id: filter.author_id,
                     search: None,
                     locale: Some(locale.clone()),
@@ -293,6 +295,7 @@ async fn list_public_visible_posts(
                 status: Some(crate::BlogPostStatus::Published),
                 category_id: None,
                 tag: None,
+                tag_id: filter.tag_id,
                 author_id: filter.author_id,
                 search: None,
                 locale: Some(locale.clone()),
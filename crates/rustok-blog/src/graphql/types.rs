@@ -45,6 +45,9 @@ pub struct GqlPost {
     pub body: Option<String>,
     pub body_format: String,
     pub content_json: Option<Value>,
+    pub body_html: String,
+    pub word_count: u32,
+    pub reading_time_minutes: u32,
     pub status: GqlContentStatus,
     pub author_id: Option<Uuid>,
     pub author_profile: Option<GqlProfileSummary>,
@@ -119,6 +122,8 @@ pub struct UpdatePostInput {
 pub struct PostsFilter {
     pub status: Option<GqlContentStatus>,
     pub author_id: Option<Uuid>,
+    /// Matches posts tagged with this term or any of its descendants.
+    pub tag_id: Option<Uuid>,
     pub locale: Option<String>,
     pub page: Option<u64>,
     pub per_page: Option<u64>,
@@ -137,6 +142,9 @@ impl From<PostResponse> for GqlPost {
             body: Some(post.body),
             body_format: post.body_format,
             content_json: post.content_json,
+            body_html: post.body_html,
+            word_count: post.word_count,
+            reading_time_minutes: post.reading_time_minutes,
             status: match post.status {
                 BlogPostStatus::Draft => GqlContentStatus::Draft,
                 BlogPostStatus::Published => GqlContentStatus::Published,
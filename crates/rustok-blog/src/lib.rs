@@ -42,6 +42,7 @@
 use async_trait::async_trait;
 use rustok_core::permissions::Permission;
 use rustok_core::{MigrationSource, ModuleRuntimeExtensions, RusToKModule};
+use rustok_media::register_media_usage_provider;
 use rustok_seo_targets::register_seo_target_provider;
 use sea_orm_migration::MigrationTrait;
 
@@ -51,6 +52,7 @@ pub mod entities;
 pub mod error;
 pub mod graphql;
 pub mod locale;
+mod media_usage;
 pub mod migrations;
 mod seo_targets;
 pub mod services;
@@ -113,6 +115,8 @@ impl RusToKModule for BlogModule {
     fn register_runtime_extensions(&self, extensions: &mut ModuleRuntimeExtensions) {
         register_seo_target_provider(extensions, seo_targets::BlogSeoTargetProvider)
             .expect("blog SEO target registration should remain unique");
+        register_media_usage_provider(extensions, media_usage::BlogMediaUsageProvider)
+            .expect("blog media usage registration should remain unique");
     }
 }
 
@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+
+use anyhow::Result as AnyResult;
+use async_trait::async_trait;
+use rustok_media::{
+    collect_json_references, media_reference_target, MediaReference, MediaUsageProvider,
+};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+use uuid::Uuid;
+
+use crate::entities::{blog_post, blog_post_translation};
+
+/// Reports featured images and media referenced from post metadata.
+#[derive(Clone, Default)]
+pub struct BlogMediaUsageProvider;
+
+#[async_trait]
+impl MediaUsageProvider for BlogMediaUsageProvider {
+    fn owner_kind(&self) -> &'static str {
+        "blog_post"
+    }
+
+    fn owner_module_slug(&self) -> &'static str {
+        "blog"
+    }
+
+    async fn collect_references(
+        &self,
+        db: &DatabaseConnection,
+        tenant_id: Uuid,
+    ) -> AnyResult<Vec<MediaReference>> {
+        let posts = blog_post::Entity::find()
+            .filter(blog_post::Column::TenantId.eq(tenant_id))
+            .all(db)
+            .await?;
+        if posts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut titles = HashMap::new();
+        for translation in blog_post_translation::Entity::find()
+            .filter(blog_post_translation::Column::PostId.is_in(posts.iter().map(|post| post.id)))
+            .order_by_asc(blog_post_translation::Column::Locale)
+            .all(db)
+            .await?
+        {
+            titles
+                .entry(translation.post_id)
+                .or_insert(translation.title);
+        }
+
+        let mut references = Vec::new();
+        for post in posts {
+            let label = titles.get(&post.id).cloned();
+            let mut post_references = collect_json_references(post.id, "metadata", &post.metadata);
+            if let Some(target) = post
+                .featured_image_url
+                .as_deref()
+                .and_then(media_reference_target)
+            {
+                post_references.push(MediaReference {
+                    owner_id: post.id,
+                    field: "featured_image_url".to_string(),
+                    label: None,
+                    target,
+                });
+            }
+            references.extend(
+                post_references
+                    .into_iter()
+                    .map(|reference| reference.with_label(label.clone())),
+            );
+        }
+        Ok(references)
+    }
+}
@@ -116,6 +116,7 @@ impl SeoTargetProvider for BlogSeoTargetProvider {
                         status: Some(BlogPostStatus::Published),
                         category_id: None,
                         tag: None,
+                        tag_id: None,
                         author_id: None,
                         search: None,
                         locale: Some(request.locale.to_string()),
@@ -171,6 +172,7 @@ impl SeoTargetProvider for BlogSeoTargetProvider {
                         status: Some(BlogPostStatus::Published),
                         category_id: None,
                         tag: None,
+                        tag_id: None,
                         author_id: None,
                         search: None,
                         locale: Some(request.default_locale.to_string()),
@@ -1,21 +1,27 @@
+use std::collections::HashSet;
+
 use chrono::Utc;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set,
-    TransactionTrait,
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DatabaseTransaction, EntityTrait,
+    QueryFilter, Set, TransactionTrait,
 };
 use tracing::instrument;
 use uuid::Uuid;
 
 use rustok_comments::{
-    CommentListItem as DomainCommentListItem, CommentRecord as DomainCommentRecord,
-    CommentStatus as DomainCommentStatus, CommentsService,
+    comment as domain_comment, CommentListItem as DomainCommentListItem,
+    CommentRecord as DomainCommentRecord, CommentStatus as DomainCommentStatus, CommentsService,
     CreateCommentInput as DomainCreateCommentInput, ListCommentsFilter as DomainListCommentsFilter,
     UpdateCommentInput as DomainUpdateCommentInput,
 };
-use rustok_content::PLATFORM_FALLBACK_LOCALE;
+use rustok_content::{
+    scan_body, MentionContext, MentionService, MentionSource, QuoteReference,
+    PLATFORM_FALLBACK_LOCALE,
+};
 use rustok_core::{prepare_content_payload, Action, Resource, SecurityContext};
 use rustok_events::DomainEvent;
 use rustok_outbox::TransactionalEventBus;
+use rustok_profiles::{ProfileService, SocialGraphService};
 
 use crate::dto::{
     CommentListItem, CommentResponse, CreateCommentInput, ListCommentsFilter, ModerateCommentInput,
@@ -27,6 +33,13 @@ use crate::services::rbac::enforce_scope;
 
 const TARGET_TYPE_BLOG_POST: &str = "blog_post";
 
+/// Users and comment quotes referenced by a comment body.
+#[derive(Debug, Default)]
+struct CommentMentions {
+    users: Vec<Uuid>,
+    quotes: Vec<QuoteReference>,
+}
+
 pub struct CommentService {
     db: DatabaseConnection,
     comments: CommentsService,
@@ -50,7 +63,7 @@ impl CommentService {
         post_id: Uuid,
         input: CreateCommentInput,
     ) -> BlogResult<CommentResponse> {
-        self.ensure_post_exists(tenant_id, post_id).await?;
+        let post = self.find_post(tenant_id, post_id).await?;
 
         if security.user_id.is_none() {
             return Err(BlogError::AuthorRequired);
@@ -65,8 +78,32 @@ impl CommentService {
             "Comment content",
         )
         .map_err(BlogError::validation)?;
+        let mentions = self
+            .resolve_mentions(tenant_id, &prepared.format, &prepared.body)
+            .await?;
 
         let txn = self.db.begin().await.map_err(BlogError::from)?;
+        SocialGraphService::ensure_not_blocked_in_tx(
+            &txn,
+            tenant_id,
+            Some(post.author_id),
+            security.user_id,
+        )
+        .await?;
+        if let Some(parent_comment_id) = input.parent_comment_id {
+            let parent_author_id = domain_comment::Entity::find_by_id(parent_comment_id)
+                .filter(domain_comment::Column::TenantId.eq(tenant_id))
+                .one(&txn)
+                .await?
+                .map(|parent| parent.author_id);
+            SocialGraphService::ensure_not_blocked_in_tx(
+                &txn,
+                tenant_id,
+                parent_author_id,
+                security.user_id,
+            )
+            .await?;
+        }
         let comment_id = self
             .comments
             .create_comment_in_tx(
@@ -90,6 +127,29 @@ impl CommentService {
             .await?;
         self.publish_post_updated_event_in_tx(&txn, tenant_id, security.user_id, post_id)
             .await?;
+        self.event_bus
+            .publish_in_tx(
+                &txn,
+                tenant_id,
+                security.user_id,
+                DomainEvent::BlogCommentCreated {
+                    post_id,
+                    comment_id,
+                    parent_comment_id: input.parent_comment_id,
+                    author_id: security.user_id,
+                },
+            )
+            .await
+            .map_err(BlogError::from)?;
+        self.apply_mentions_in_tx(
+            &txn,
+            tenant_id,
+            post_id,
+            comment_id,
+            security.user_id,
+            mentions,
+        )
+        .await?;
         txn.commit().await.map_err(BlogError::from)?;
 
         let record = self
@@ -142,6 +202,7 @@ impl CommentService {
         input: UpdateCommentInput,
     ) -> BlogResult<CommentResponse> {
         let locale = input.locale.clone();
+        let mut mentions = None;
         let domain_input = if input.content.is_some()
             || input.content_json.is_some()
             || input.content_format.is_some()
@@ -154,6 +215,10 @@ impl CommentService {
                 "Comment content",
             )
             .map_err(BlogError::validation)?;
+            mentions = Some(
+                self.resolve_mentions(tenant_id, &prepared.format, &prepared.body)
+                    .await?,
+            );
 
             DomainUpdateCommentInput {
                 locale: locale.clone(),
@@ -173,6 +238,20 @@ impl CommentService {
             .update_comment(tenant_id, security, comment_id, domain_input)
             .await
             .map_err(BlogError::from)?;
+        if let Some(mentions) = mentions {
+            let post_id = Self::ensure_blog_target(&record)?;
+            let txn = self.db.begin().await.map_err(BlogError::from)?;
+            self.apply_mentions_in_tx(
+                &txn,
+                tenant_id,
+                post_id,
+                comment_id,
+                Some(record.author_id),
+                mentions,
+            )
+            .await?;
+            txn.commit().await.map_err(BlogError::from)?;
+        }
         Self::map_comment_record(record)
     }
 
@@ -247,6 +326,7 @@ impl CommentService {
             .delete_comment_in_tx(&txn, tenant_id, security.clone(), comment_id)
             .await
             .map_err(BlogError::from)?;
+        MentionService::delete_for_source_in_tx(&txn, MentionSource::Comment, comment_id).await?;
         self.adjust_post_reply_count_in_tx(&txn, tenant_id, post_id, -1)
             .await?;
         self.publish_post_updated_event_in_tx(&txn, tenant_id, security.user_id, post_id)
@@ -277,7 +357,7 @@ impl CommentService {
         filter: ListCommentsFilter,
         fallback_locale: Option<&str>,
     ) -> BlogResult<(Vec<CommentListItem>, u64)> {
-        self.ensure_post_exists(tenant_id, post_id).await?;
+        self.find_post(tenant_id, post_id).await?;
 
         let locale = filter
             .locale
@@ -309,15 +389,103 @@ impl CommentService {
         ))
     }
 
-    async fn ensure_post_exists(&self, tenant_id: Uuid, post_id: Uuid) -> BlogResult<()> {
-        let exists = blog_post::Entity::find_by_id(post_id)
+    async fn find_post(&self, tenant_id: Uuid, post_id: Uuid) -> BlogResult<blog_post::Model> {
+        blog_post::Entity::find_by_id(post_id)
             .filter(blog_post::Column::TenantId.eq(tenant_id))
             .one(&self.db)
             .await
             .map_err(BlogError::from)?
-            .is_some();
-        if !exists {
-            return Err(BlogError::post_not_found(post_id));
+            .ok_or_else(|| BlogError::post_not_found(post_id))
+    }
+
+    async fn resolve_mentions(
+        &self,
+        tenant_id: Uuid,
+        body_format: &str,
+        body: &str,
+    ) -> BlogResult<CommentMentions> {
+        let scanned = scan_body(body_format, body);
+        if scanned.is_empty() {
+            return Ok(CommentMentions::default());
+        }
+
+        let resolved = ProfileService::new(self.db.clone())
+            .resolve_handles(tenant_id, &scanned.handles)
+            .await?;
+        Ok(CommentMentions {
+            users: scanned
+                .handles
+                .iter()
+                .filter_map(|handle| resolved.get(handle).copied())
+                .collect(),
+            quotes: scanned
+                .quotes
+                .into_iter()
+                .filter(|quote| quote.source == MentionSource::Comment)
+                .collect(),
+        })
+    }
+
+    /// Stores mentions and quotes of a comment and emits `UserMentioned` for new mentions.
+    ///
+    /// Users who blocked or muted the author are not mentioned.
+    async fn apply_mentions_in_tx(
+        &self,
+        txn: &DatabaseTransaction,
+        tenant_id: Uuid,
+        post_id: Uuid,
+        comment_id: Uuid,
+        author_id: Option<Uuid>,
+        mentions: CommentMentions,
+    ) -> BlogResult<()> {
+        let mut users = mentions.users;
+        if let Some(author_id) = author_id {
+            let silenced =
+                SocialGraphService::silenced_by_in_tx(txn, tenant_id, author_id, &users).await?;
+            users.retain(|user_id| !silenced.contains(user_id));
+        }
+        let mut quotes = mentions.quotes;
+        if !quotes.is_empty() {
+            let ids: Vec<Uuid> = quotes.iter().map(|quote| quote.source_id).collect();
+            let known: HashSet<Uuid> = domain_comment::Entity::find()
+                .filter(domain_comment::Column::TenantId.eq(tenant_id))
+                .filter(domain_comment::Column::Id.is_in(ids))
+                .all(txn)
+                .await?
+                .into_iter()
+                .map(|comment| comment.id)
+                .collect();
+            quotes.retain(|quote| known.contains(&quote.source_id));
+        }
+
+        let added = MentionService::sync_in_tx(
+            txn,
+            MentionContext {
+                tenant_id,
+                source: MentionSource::Comment,
+                source_id: comment_id,
+                target_id: post_id,
+                author_id,
+            },
+            &users,
+            &quotes,
+        )
+        .await?;
+        for mentioned_user_id in added {
+            self.event_bus
+                .publish_in_tx(
+                    txn,
+                    tenant_id,
+                    author_id,
+                    DomainEvent::UserMentioned {
+                        mentioned_user_id,
+                        author_id,
+                        source_kind: MentionSource::Comment.as_str().to_string(),
+                        source_id: comment_id,
+                        target_id: post_id,
+                    },
+                )
+                .await?;
         }
         Ok(())
     }
@@ -396,6 +564,7 @@ impl CommentService {
             content: body,
             content_format: body_format,
             content_json,
+            content_html: record.body_html,
             status: comment_status_label(record.status).to_string(),
             parent_comment_id: record.parent_comment_id,
             created_at: record.created_at,
@@ -446,6 +615,7 @@ mod rich_content_tests {
             parent_comment_id: None,
             body: rich.to_string(),
             body_format: "rt_json_v1".into(),
+            body_html: String::new(),
             status: DomainCommentStatus::Pending,
             position: 1,
             created_at: "2024-01-01T00:00:00Z".into(),
@@ -20,7 +20,10 @@ use rustok_content::{
     available_locales_from, normalize_locale_code, resolve_by_locale_with_fallback,
     PLATFORM_FALLBACK_LOCALE,
 };
-use rustok_core::{prepare_content_payload, Action, Resource, SecurityContext};
+use rustok_core::{
+    prepare_content_payload, render_content, Action, FeedContentMode, FeedItem, Resource,
+    SecurityContext,
+};
 use rustok_events::DomainEvent;
 use rustok_outbox::TransactionalEventBus;
 use serde_json::Value;
@@ -34,9 +37,13 @@ use crate::services::category::CategoryService;
 use crate::services::rbac::{
     can_read_non_public_posts, enforce_create_author, enforce_owned_scope, enforce_scope,
 };
-use crate::services::tag::{find_post_ids_by_tag, load_post_tags_map, sync_post_tags_in_tx};
+use crate::services::tag::{
+    find_post_ids_by_tag, find_post_ids_by_tag_tree, load_post_tags_map, sync_post_tags_in_tx,
+};
 use crate::state_machine::BlogPostStatus;
 
+const DERIVED_EXCERPT_CHARS: usize = 280;
+
 pub struct PostService {
     db: DatabaseConnection,
     event_bus: TransactionalEventBus,
@@ -610,6 +617,13 @@ impl PostService {
             }
             select = select.filter(blog_post::Column::Id.is_in(tagged_post_ids));
         }
+        if let Some(tag_id) = query.tag_id {
+            let tagged_post_ids = find_post_ids_by_tag_tree(&self.db, tenant_id, tag_id).await?;
+            if tagged_post_ids.is_empty() {
+                return Ok(PostListResponse::new(Vec::new(), 0, &query));
+            }
+            select = select.filter(blog_post::Column::Id.is_in(tagged_post_ids));
+        }
 
         if let Some(status) = query.status {
             select = select.filter(blog_post::Column::Status.eq(status_to_storage(status)));
@@ -669,7 +683,7 @@ impl PostService {
                 slug: post.slug.clone(),
                 locale: locale.clone(),
                 effective_locale: resolved.effective_locale,
-                excerpt: translation.and_then(|item| item.excerpt.clone()),
+                excerpt: translation.and_then(translation_excerpt),
                 status: storage_to_status(&post.status)?,
                 author_id: post.author_id,
                 author_name: None,
@@ -718,6 +732,13 @@ impl PostService {
             }
             select = select.filter(blog_post::Column::Id.is_in(tagged_post_ids));
         }
+        if let Some(tag_id) = query.tag_id {
+            let tagged_post_ids = find_post_ids_by_tag_tree(&self.db, tenant_id, tag_id).await?;
+            if tagged_post_ids.is_empty() {
+                return Ok(PostListResponse::new(Vec::new(), 0, &query));
+            }
+            select = select.filter(blog_post::Column::Id.is_in(tagged_post_ids));
+        }
 
         if let Some(author_id) = query.author_id {
             select = select.filter(blog_post::Column::AuthorId.eq(author_id));
@@ -767,7 +788,7 @@ impl PostService {
                 slug: post.slug.clone(),
                 locale: locale.clone(),
                 effective_locale: resolved.effective_locale,
-                excerpt: translation.and_then(|item| item.excerpt.clone()),
+                excerpt: translation.and_then(translation_excerpt),
                 status: storage_to_status(&post.status)?,
                 author_id: post.author_id,
                 author_name: None,
@@ -788,6 +809,70 @@ impl PostService {
         Ok(PostListResponse::new(items, total, &query))
     }
 
+    /// Published, channel-visible posts as syndication entries, newest first.
+    /// `query` carries the feed scope (category, tag, author), locale and size.
+    #[instrument(skip(self))]
+    pub async fn list_public_feed_items(
+        &self,
+        tenant_id: Uuid,
+        query: PostListQuery,
+        fallback_locale: Option<&str>,
+        channel_slug: Option<&str>,
+        base_url: &str,
+        content_mode: FeedContentMode,
+    ) -> BlogResult<Vec<FeedItem>> {
+        let query = PostListQuery {
+            status: Some(BlogPostStatus::Published),
+            page: Some(1),
+            sort_by: Some("published_at".to_string()),
+            sort_order: Some("desc".to_string()),
+            ..query
+        };
+        let summaries = self
+            .list_public_visible_with_locale_fallback(
+                tenant_id,
+                query,
+                fallback_locale,
+                channel_slug,
+            )
+            .await?
+            .items;
+        let post_ids = summaries.iter().map(|post| post.id).collect::<Vec<_>>();
+        let translations_map = self.load_translations_map(&post_ids).await?;
+
+        let mut items = Vec::with_capacity(summaries.len());
+        for summary in summaries {
+            let translations = translations_map
+                .get(&summary.id)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            let Some(translation) = translations
+                .iter()
+                .find(|item| item.locale == summary.effective_locale)
+            else {
+                continue;
+            };
+            let published_at = summary.published_at.unwrap_or(summary.created_at);
+            let mut item = FeedItem::new(
+                format!("urn:uuid:{}", summary.id),
+                summary.title,
+                format!("{base_url}/modules/blog?slug={}", summary.slug),
+                published_at,
+            )
+            .with_content(
+                content_mode,
+                &render_content(&translation.body_format, &translation.body),
+                translation.excerpt.as_deref(),
+            );
+            item.updated_at = published_at.max(translation.updated_at.with_timezone(&chrono::Utc));
+            item.categories = summary.tags;
+            item.image_url = summary.featured_image_url;
+            items.push(item);
+        }
+
+        Ok(items)
+    }
+
     pub async fn get_posts_by_tag(
         &self,
         tenant_id: Uuid,
@@ -1105,6 +1190,7 @@ impl PostService {
         } else {
             None
         };
+        let rendered = render_content(&body_format, &body);
 
         Ok(PostResponse {
             id: post.id,
@@ -1121,6 +1207,9 @@ impl PostService {
             body,
             body_format,
             content_json,
+            body_html: rendered.html,
+            word_count: rendered.summary.word_count,
+            reading_time_minutes: rendered.summary.reading_time_minutes,
             excerpt: translation.and_then(|item| item.excerpt.clone()),
             status: storage_to_status(&post.status)?,
             category_id: post.category_id,
@@ -1148,6 +1237,15 @@ impl PostService {
     }
 }
 
+/// Stored excerpt, or one derived from the rendered body.
+fn translation_excerpt(translation: &blog_post_translation::Model) -> Option<String> {
+    translation.excerpt.clone().or_else(|| {
+        let excerpt = render_content(&translation.body_format, &translation.body)
+            .excerpt(DERIVED_EXCERPT_CHARS);
+        (!excerpt.is_empty()).then_some(excerpt)
+    })
+}
+
 fn resolve_translation_record<'a>(
     translations: &'a [blog_post_translation::Model],
     requested: &str,
@@ -59,6 +59,7 @@ impl TagService {
                     canonical_key: None,
                     description: None,
                     aliases: vec![],
+                    parent_id: None,
                 },
             )
             .await?)
@@ -446,6 +447,32 @@ pub(crate) async fn find_post_ids_by_tag(
         .collect())
 }
 
+/// Posts tagged with `tag_id` or any of its descendant terms.
+pub(crate) async fn find_post_ids_by_tag_tree(
+    db: &DatabaseConnection,
+    tenant_id: Uuid,
+    tag_id: Uuid,
+) -> BlogResult<Vec<Uuid>> {
+    let tag_ids = TaxonomyService::expand_descendants_in_tx(db, tenant_id, &[tag_id]).await?;
+    if tag_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let relations = blog_post_tag::Entity::find()
+        .join(JoinType::InnerJoin, blog_post_tag::Relation::Post.def())
+        .filter(blog_post::Column::TenantId.eq(tenant_id))
+        .filter(blog_post_tag::Column::TagId.is_in(tag_ids))
+        .all(db)
+        .await?;
+
+    let mut seen = HashSet::new();
+    Ok(relations
+        .into_iter()
+        .map(|relation| relation.post_id)
+        .filter(|post_id| seen.insert(*post_id))
+        .collect())
+}
+
 async fn load_translations_map(
     db: &DatabaseConnection,
     term_ids: &[Uuid],
@@ -257,6 +257,7 @@ async fn storefront_blog_native(
                     status: Some(BlogPostStatus::Published),
                     category_id: None,
                     tag: None,
+                    tag_id: None,
                     author_id: None,
                     search: None,
                     locale: Some(requested_locale),
@@ -0,0 +1,343 @@
+//! Transport tests for the public blog feed endpoints.
+
+use std::sync::Arc;
+
+use axum::body::{to_bytes, Body};
+use axum::extract::State;
+use axum::http::{header, HeaderValue, Request, StatusCode};
+use axum::middleware::{from_fn_with_state, Next};
+use axum::response::Response;
+use axum::Router;
+use loco_rs::app::{AppContext, SharedStore};
+use loco_rs::cache;
+use loco_rs::environment::Environment;
+use loco_rs::storage::{self, Storage};
+use loco_rs::tests_cfg::config::test_config;
+use rustok_api::{
+    ChannelContext, ChannelContextExtension, ChannelResolutionSource, TenantContext,
+    TenantContextExtension,
+};
+use rustok_blog::dto::CreatePostInput;
+use rustok_blog::{BlogModule, PostService};
+use rustok_channel::entities::{channel, channel_module_binding};
+use rustok_channel::{BindChannelModuleInput, ChannelService, CreateChannelInput};
+use rustok_comments::CommentsModule;
+use rustok_core::events::EventTransport;
+use rustok_core::{MigrationSource, SecurityContext, UserRole};
+use rustok_taxonomy::TaxonomyModule;
+use rustok_test_utils::{mock_transactional_event_bus, MockEventTransport};
+use sea_orm::{ConnectOptions, ConnectionTrait, Database, DatabaseConnection, Schema};
+use sea_orm_migration::SchemaManager;
+use serde_json::{json, Value};
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn feed_excludes_posts_hidden_from_the_request_channel() {
+    let fixture = Fixture::new().await;
+    fixture.create_post("Everywhere", None).await;
+    fixture
+        .create_post("Intranet only", Some(vec!["intranet".to_string()]))
+        .await;
+
+    let web = fixture.create_channel("web").await;
+    let (status, body) = fixture.get_json(Some(&web)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(titles(&body), vec!["Everywhere"]);
+
+    let intranet = fixture.create_channel("intranet").await;
+    let (_, body) = fixture.get_json(Some(&intranet)).await;
+    let mut visible = titles(&body);
+    visible.sort();
+    assert_eq!(visible, vec!["Everywhere", "Intranet only"]);
+
+    let (_, body) = fixture.get_json(None).await;
+    assert_eq!(titles(&body), vec!["Everywhere"]);
+}
+
+#[tokio::test]
+async fn feed_is_not_found_when_blog_is_disabled_for_the_channel() {
+    let fixture = Fixture::new().await;
+    let channel = fixture.create_channel("kiosk").await;
+    ChannelService::new(fixture.db.clone())
+        .bind_module(
+            channel.id,
+            BindChannelModuleInput {
+                module_slug: "blog".to_string(),
+                is_enabled: false,
+                settings: None,
+            },
+        )
+        .await
+        .expect("module binding should be stored");
+
+    let response = fixture.get("/feeds/rss", Some(&channel), &[]).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn feed_answers_not_modified_for_matching_validators() {
+    let fixture = Fixture::new().await;
+    fixture.create_post("Cached", None).await;
+
+    let first = fixture.get("/feeds/atom", None, &[]).await;
+    assert_eq!(first.status(), StatusCode::OK);
+    let etag = first.headers()[header::ETAG].clone();
+    let last_modified = first.headers()[header::LAST_MODIFIED].clone();
+
+    let by_etag = fixture
+        .get(
+            "/feeds/atom",
+            None,
+            &[(header::IF_NONE_MATCH, etag.clone())],
+        )
+        .await;
+    assert_eq!(by_etag.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(by_etag.headers()[header::ETAG], etag);
+    assert!(to_bytes(by_etag.into_body(), usize::MAX)
+        .await
+        .expect("body")
+        .is_empty());
+
+    let by_date = fixture
+        .get(
+            "/feeds/atom",
+            None,
+            &[(header::IF_MODIFIED_SINCE, last_modified)],
+        )
+        .await;
+    assert_eq!(by_date.status(), StatusCode::NOT_MODIFIED);
+
+    let stale = fixture
+        .get(
+            "/feeds/atom",
+            None,
+            &[(header::IF_NONE_MATCH, HeaderValue::from_static("\"stale\""))],
+        )
+        .await;
+    assert_eq!(stale.status(), StatusCode::OK);
+
+    fixture.create_post("Newer", None).await;
+    let changed = fixture
+        .get("/feeds/atom", None, &[(header::IF_NONE_MATCH, etag)])
+        .await;
+    assert_eq!(changed.status(), StatusCode::OK);
+}
+
+struct Fixture {
+    db: DatabaseConnection,
+    tenant: TenantContext,
+}
+
+impl Fixture {
+    async fn new() -> Self {
+        let db = setup_feed_test_db().await;
+        let tenant_id = Uuid::new_v4();
+        Self {
+            db,
+            tenant: TenantContext {
+                id: tenant_id,
+                name: "Feed Tenant".to_string(),
+                slug: format!("feed-{tenant_id}"),
+                domain: Some("blog.example.com".to_string()),
+                settings: json!({}),
+                default_locale: "en".to_string(),
+                is_active: true,
+            },
+        }
+    }
+
+    async fn create_post(&self, title: &str, channel_slugs: Option<Vec<String>>) {
+        PostService::new(self.db.clone(), mock_transactional_event_bus())
+            .create_post(
+                self.tenant.id,
+                SecurityContext::new(UserRole::Admin, Some(Uuid::new_v4())),
+                CreatePostInput {
+                    locale: "en".to_string(),
+                    title: title.to_string(),
+                    body: format!("{title} body"),
+                    body_format: "markdown".to_string(),
+                    content_json: None,
+                    excerpt: None,
+                    slug: None,
+                    publish: true,
+                    tags: vec![],
+                    category_id: None,
+                    featured_image_url: None,
+                    seo_title: None,
+                    seo_description: None,
+                    channel_slugs,
+                    metadata: None,
+                },
+            )
+            .await
+            .expect("post should be created");
+    }
+
+    async fn create_channel(&self, slug: &str) -> ChannelContext {
+        let channel = ChannelService::new(self.db.clone())
+            .create_channel(CreateChannelInput {
+                tenant_id: self.tenant.id,
+                slug: slug.to_string(),
+                name: slug.to_string(),
+                settings: None,
+            })
+            .await
+            .expect("channel should be created");
+        ChannelContext {
+            id: channel.id,
+            tenant_id: self.tenant.id,
+            slug: channel.slug,
+            name: channel.name,
+            is_active: true,
+            status: channel.status,
+            target_type: None,
+            target_value: None,
+            settings: json!({}),
+            resolution_source: ChannelResolutionSource::HeaderSlug,
+            resolution_trace: vec![],
+        }
+    }
+
+    async fn get(
+        &self,
+        uri: &str,
+        channel: Option<&ChannelContext>,
+        headers: &[(header::HeaderName, HeaderValue)],
+    ) -> Response {
+        let mut request = Request::builder().method("GET").uri(uri);
+        for (name, value) in headers {
+            request = request.header(name, value);
+        }
+        feed_router(
+            test_app_context(self.db.clone()),
+            self.tenant.clone(),
+            channel.cloned(),
+        )
+        .oneshot(request.body(Body::empty()).expect("request"))
+        .await
+        .expect("request should succeed")
+    }
+
+    async fn get_json(&self, channel: Option<&ChannelContext>) -> (StatusCode, Value) {
+        let response = self.get("/feeds/json", channel, &[]).await;
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("body");
+        (
+            status,
+            serde_json::from_slice(&body).expect("feed should be JSON"),
+        )
+    }
+}
+
+fn titles(feed: &Value) -> Vec<&str> {
+    feed["items"]
+        .as_array()
+        .expect("feed items")
+        .iter()
+        .map(|item| item["title"].as_str().expect("item title"))
+        .collect()
+}
+
+#[derive(Clone)]
+struct FeedRequestContext {
+    tenant: TenantContext,
+    channel: Option<ChannelContext>,
+}
+
+async fn inject_feed_context(
+    State(context): State<FeedRequestContext>,
+    mut req: axum::extract::Request,
+    next: Next,
+) -> Response {
+    req.extensions_mut()
+        .insert(TenantContextExtension(context.tenant));
+    if let Some(channel) = context.channel {
+        req.extensions_mut()
+            .insert(ChannelContextExtension(channel));
+    }
+    next.run(req).await
+}
+
+fn feed_router(ctx: AppContext, tenant: TenantContext, channel: Option<ChannelContext>) -> Router {
+    let mut router = Router::new();
+    for handler in rustok_blog::controllers::routes().handlers {
+        router = router.route(&handler.uri, handler.method.with_state(ctx.clone()));
+    }
+    router.layer(from_fn_with_state(
+        FeedRequestContext { tenant, channel },
+        inject_feed_context,
+    ))
+}
+
+fn test_app_context(db: DatabaseConnection) -> AppContext {
+    let shared_store = Arc::new(SharedStore::default());
+    let event_transport: Arc<dyn EventTransport> = Arc::new(MockEventTransport::new());
+    shared_store.insert(event_transport);
+
+    AppContext {
+        environment: Environment::Test,
+        db,
+        queue_provider: None,
+        config: test_config(),
+        mailer: None,
+        storage: Storage::single(storage::drivers::mem::new()).into(),
+        cache: Arc::new(cache::Cache::new(cache::drivers::null::new())),
+        shared_store,
+    }
+}
+
+async fn setup_feed_test_db() -> DatabaseConnection {
+    let db_url = format!(
+        "sqlite:file:blog_feeds_{}?mode=memory&cache=shared",
+        Uuid::new_v4()
+    );
+    let mut opts = ConnectOptions::new(db_url);
+    opts.max_connections(5)
+        .min_connections(1)
+        .sqlx_logging(false);
+    let db = Database::connect(opts)
+        .await
+        .expect("failed to connect blog feed test sqlite database");
+
+    let manager = SchemaManager::new(&db);
+    for migration in rustok_content::migrations::migrations() {
+        migration
+            .up(&manager)
+            .await
+            .expect("content migration should apply");
+    }
+    for migration in TaxonomyModule.migrations() {
+        migration
+            .up(&manager)
+            .await
+            .expect("taxonomy migration should apply");
+    }
+    for migration in BlogModule.migrations() {
+        migration
+            .up(&manager)
+            .await
+            .expect("blog migration should apply");
+    }
+    for migration in CommentsModule.migrations() {
+        migration
+            .up(&manager)
+            .await
+            .expect("comments migration should apply");
+    }
+
+    let backend = db.get_database_backend();
+    let schema = Schema::new(backend);
+    for mut statement in [
+        schema.create_table_from_entity(channel::Entity),
+        schema.create_table_from_entity(channel_module_binding::Entity),
+    ] {
+        statement.if_not_exists();
+        db.execute(backend.build(&statement))
+            .await
+            .expect("channel test table should be created");
+    }
+    db
+}
@@ -657,6 +657,12 @@ async fn setup_blog_test_db() -> DatabaseConnection {
 
 async fn ensure_blog_schema(db: &DatabaseConnection) {
     let manager = SchemaManager::new(db);
+    for migration in rustok_content::migrations::migrations() {
+        migration
+            .up(&manager)
+            .await
+            .expect("content migration should apply");
+    }
     for migration in TaxonomyModule.migrations() {
         migration
             .up(&manager)
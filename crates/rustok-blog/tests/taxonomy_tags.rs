@@ -160,6 +160,7 @@ async fn post_tag_sync_reuses_existing_global_taxonomy_term() {
                 canonical_key: None,
                 description: None,
                 aliases: vec![],
+                parent_id: None,
             },
         )
         .await
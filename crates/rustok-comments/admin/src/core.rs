@@ -324,6 +324,7 @@ mod tests {
             parent_comment_id: None,
             body: "Moderation body".to_string(),
             body_format: "plain_text".to_string(),
+            body_html: "<p>Moderation body</p>".to_string(),
             status: CommentStatus::Pending,
             position: 1,
             created_at: "2026-06-07T00:00:00Z".to_string(),
@@ -73,6 +73,9 @@ pub struct CommentRecord {
     pub parent_comment_id: Option<Uuid>,
     pub body: String,
     pub body_format: String,
+    /// Sanitized HTML rendering of the body.
+    #[serde(default)]
+    pub body_html: String,
     pub status: CommentStatus,
     pub position: i64,
     pub created_at: String,
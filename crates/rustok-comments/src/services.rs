@@ -3,14 +3,16 @@ use sea_orm::{
     ActiveModelTrait, ColumnTrait, DatabaseConnection, DatabaseTransaction, EntityTrait, JoinType,
     PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, RelationTrait, Set, TransactionTrait,
 };
-use std::{collections::HashMap, time::Instant};
+use std::{collections::HashMap, sync::Arc, time::Instant};
 use tracing::instrument;
 use uuid::Uuid;
 
 use rustok_content::{
     dto::validation::validate_body_format, normalize_locale_code, resolve_by_locale_with_fallback,
+    ModerationLogInput, ModerationLogService, ModeratorVerdict, SanctionService, SpamAction,
+    SpamDecisionLog, SpamPipeline, SpamSubmission, SpamSurface, SubmissionOrigin,
 };
-use rustok_core::{Action, PermissionScope, Resource, SecurityContext};
+use rustok_core::{render_content, Action, PermissionScope, Resource, SecurityContext};
 use rustok_telemetry::metrics;
 
 use crate::dto::{
@@ -22,6 +24,7 @@ use crate::error::{CommentsError, CommentsResult};
 
 pub struct CommentsService {
     db: DatabaseConnection,
+    spam_pipeline: Option<Arc<SpamPipeline>>,
 }
 
 const MODULE: &str = "comments";
@@ -106,15 +109,36 @@ use sea_orm::Database;
 
 impl CommentsService {
     pub fn new(db: DatabaseConnection) -> Self {
-        Self { db }
+        Self {
+            db,
+            spam_pipeline: None,
+        }
+    }
+
+    /// Screens comments from non-moderators with the given anti-spam pipeline and records
+    /// moderator status changes as feedback for it.
+    pub fn with_spam_pipeline(mut self, pipeline: Arc<SpamPipeline>) -> Self {
+        self.spam_pipeline = Some(pipeline);
+        self
     }
 
-    #[instrument(skip(self, security, input), fields(tenant_id = %tenant_id, target_type = %input.target_type, target_id = %input.target_id))]
     pub async fn create_comment(
         &self,
         tenant_id: Uuid,
         security: SecurityContext,
         input: CreateCommentInput,
+    ) -> CommentsResult<CommentRecord> {
+        self.create_comment_with_origin(tenant_id, security, input, SubmissionOrigin::default())
+            .await
+    }
+
+    #[instrument(skip(self, security, input, origin), fields(tenant_id = %tenant_id, target_type = %input.target_type, target_id = %input.target_id))]
+    pub async fn create_comment_with_origin(
+        &self,
+        tenant_id: Uuid,
+        security: SecurityContext,
+        input: CreateCommentInput,
+        origin: SubmissionOrigin,
     ) -> CommentsResult<CommentRecord> {
         record_entrypoint("create_comment");
         let started = Instant::now();
@@ -122,7 +146,7 @@ impl CommentsService {
             let locale = input.locale.clone();
             let txn = self.db.begin().await?;
             let comment_id = self
-                .create_comment_in_tx(&txn, tenant_id, security.clone(), input)
+                .create_comment_in_tx_with_origin(&txn, tenant_id, security.clone(), input, origin)
                 .await?;
             txn.commit().await?;
             self.get_comment(tenant_id, security, comment_id, &locale, None)
@@ -139,15 +163,48 @@ impl CommentsService {
         tenant_id: Uuid,
         security: SecurityContext,
         input: CreateCommentInput,
+    ) -> CommentsResult<Uuid> {
+        self.create_comment_in_tx_with_origin(
+            txn,
+            tenant_id,
+            security,
+            input,
+            SubmissionOrigin::default(),
+        )
+        .await
+    }
+
+    pub async fn create_comment_in_tx_with_origin(
+        &self,
+        txn: &DatabaseTransaction,
+        tenant_id: Uuid,
+        security: SecurityContext,
+        input: CreateCommentInput,
+        origin: SubmissionOrigin,
     ) -> CommentsResult<Uuid> {
         let author_id = self.enforce_create_scope(&security)?;
         self.validate_body(&input.body, &input.body_format)?;
+        SanctionService::ensure_can_post(txn, tenant_id, Some(author_id))
+            .await
+            .map_err(content_error)?;
 
         let thread = self
             .find_or_create_thread_in_tx(txn, tenant_id, &input.target_type, input.target_id)
             .await?;
         self.ensure_thread_is_open(&thread)?;
-        let status = self.resolve_create_status(&security, input.status)?;
+        let mut status = self.resolve_create_status(&security, input.status)?;
+        let spam_submission = self
+            .spam_pipeline
+            .as_ref()
+            .filter(|_| !self.can_moderate(&security))
+            .map(|_| SpamSubmission {
+                tenant_id,
+                surface: SpamSurface::Comment,
+                author_id: Some(author_id),
+                ip_address: origin.ip_address,
+                locale: input.locale.clone(),
+                body: input.body.clone(),
+            });
 
         if let Some(parent_comment_id) = input.parent_comment_id {
             let parent = self
@@ -165,6 +222,26 @@ impl CommentsService {
         let comment_id = Uuid::new_v4();
         let locale = normalize_locale(&input.locale)?;
 
+        if let (Some(pipeline), Some(submission)) = (&self.spam_pipeline, spam_submission) {
+            let decision = pipeline
+                .screen_and_record(txn, &submission, comment_id)
+                .await
+                .map_err(content_error)?;
+            status = match decision.action {
+                SpamAction::Allow => status,
+                SpamAction::Hold => crate::dto::CommentStatus::Pending,
+                SpamAction::Spam => crate::dto::CommentStatus::Spam,
+            };
+            if decision.action != SpamAction::Allow {
+                tracing::info!(
+                    comment_id = %comment_id,
+                    action = decision.action.as_str(),
+                    reason = decision.reason().unwrap_or_default(),
+                    "comment diverted by anti-spam pipeline"
+                );
+            }
+        }
+
         comment::ActiveModel {
             id: Set(comment_id),
             tenant_id: Set(tenant_id),
@@ -263,6 +340,9 @@ impl CommentsService {
             self.validate_body(&body, &body_format)?;
 
             let txn = self.db.begin().await?;
+            SanctionService::ensure_can_post(&txn, tenant_id, security.user_id)
+                .await
+                .map_err(content_error)?;
             self.upsert_body_in_tx(&txn, comment_id, &locale, body, body_format)
                 .await?;
 
@@ -420,7 +500,8 @@ impl CommentsService {
                         &locale,
                         fallback_locale.as_deref(),
                     )?;
-                    let preview: String = resolved.body.chars().take(200).collect();
+                    let preview =
+                        render_content(&resolved.body_format, &resolved.body).excerpt(200);
 
                     Ok(CommentListItem {
                         id: item.id,
@@ -482,10 +563,14 @@ impl CommentsService {
                 return Ok(());
             }
 
+            let txn = self.db.begin().await?;
+            let old_status = thread.status;
             let mut active: comment_thread::ActiveModel = thread.into();
             active.status = Set(status);
             active.updated_at = Set(Utc::now().into());
-            active.update(&self.db).await?;
+            let thread = active.update(&txn).await?;
+            record_thread_status_change(&txn, tenant_id, &security, &thread, old_status).await?;
+            txn.commit().await?;
             Ok(())
         }
         .await;
@@ -644,10 +729,28 @@ impl CommentsService {
 
             let existing = self.find_comment(tenant_id, comment_id, false).await?;
             if existing.status != status {
+                let txn = self.db.begin().await?;
                 let mut active: comment::ActiveModel = existing.clone().into();
                 active.status = Set(status);
                 active.updated_at = Set(Utc::now().into());
-                active.update(&self.db).await?;
+                active.update(&txn).await?;
+                ModerationLogService::record_in_tx(
+                    &txn,
+                    tenant_id,
+                    security.user_id,
+                    ModerationLogInput::new("comment.status_changed", "comment", comment_id)
+                        .with_subject(Some(existing.author_id))
+                        .with_details(serde_json::json!({
+                            "thread_id": existing.thread_id,
+                            "old_status": existing.status,
+                            "new_status": status,
+                        })),
+                )
+                .await
+                .map_err(content_error)?;
+                txn.commit().await?;
+                self.record_spam_feedback(tenant_id, &security, comment_id, status)
+                    .await?;
             }
 
             self.get_comment(
@@ -689,10 +792,14 @@ impl CommentsService {
                 return Ok(Self::map_thread_summary(thread));
             }
 
-            let mut active: comment_thread::ActiveModel = thread.clone().into();
+            let txn = self.db.begin().await?;
+            let old_status = thread.status;
+            let mut active: comment_thread::ActiveModel = thread.into();
             active.status = Set(status);
             active.updated_at = Set(Utc::now().into());
-            let thread = active.update(&self.db).await?;
+            let thread = active.update(&txn).await?;
+            record_thread_status_change(&txn, tenant_id, &security, &thread, old_status).await?;
+            txn.commit().await?;
             Ok(Self::map_thread_summary(thread))
         }
         .await;
@@ -700,6 +807,34 @@ impl CommentsService {
         result
     }
 
+    async fn record_spam_feedback(
+        &self,
+        tenant_id: Uuid,
+        security: &SecurityContext,
+        comment_id: Uuid,
+        status: crate::dto::CommentStatus,
+    ) -> CommentsResult<()> {
+        if self.spam_pipeline.is_none() {
+            return Ok(());
+        }
+        let verdict = match status {
+            crate::dto::CommentStatus::Approved => ModeratorVerdict::Ham,
+            crate::dto::CommentStatus::Spam => ModeratorVerdict::Spam,
+            crate::dto::CommentStatus::Pending | crate::dto::CommentStatus::Trash => return Ok(()),
+        };
+        SpamDecisionLog::record_moderator_verdict(
+            &self.db,
+            tenant_id,
+            SpamSurface::Comment,
+            comment_id,
+            verdict,
+            security.user_id,
+        )
+        .await
+        .map_err(content_error)?;
+        Ok(())
+    }
+
     async fn find_or_create_thread_in_tx(
         &self,
         txn: &DatabaseTransaction,
@@ -864,6 +999,7 @@ impl CommentsService {
         fallback_locale: Option<&str>,
     ) -> CommentsResult<CommentRecord> {
         let resolved = resolve_body(bodies, locale, fallback_locale)?;
+        let body_html = render_content(&resolved.body_format, &resolved.body).html;
         Ok(CommentRecord {
             id: comment.id,
             thread_id: comment.thread_id,
@@ -875,6 +1011,7 @@ impl CommentsService {
             parent_comment_id: comment.parent_comment_id,
             body: resolved.body,
             body_format: resolved.body_format,
+            body_html,
             status: comment.status,
             position: comment.position,
             created_at: comment.created_at.to_rfc3339(),
@@ -1060,11 +1197,14 @@ mod format_validation_tests {
             .await
             .expect("sqlite connection should succeed");
         let manager = SchemaManager::new(&db);
-        for migration in migrations::migrations() {
+        for migration in rustok_content::migrations::migrations()
+            .into_iter()
+            .chain(migrations::migrations())
+        {
             migration
                 .up(&manager)
                 .await
-                .expect("comments migration should apply");
+                .expect("migration should apply");
         }
         CommentsService::new(db)
     }
@@ -1131,6 +1271,131 @@ mod format_validation_tests {
         ));
     }
 
+    async fn setup_screened_comments_service() -> CommentsService {
+        let db_url = format!(
+            "sqlite:file:comments_spam_pipeline_{}?mode=memory&cache=shared",
+            Uuid::new_v4()
+        );
+        let db = Database::connect(db_url)
+            .await
+            .expect("sqlite connection should succeed");
+        let manager = SchemaManager::new(&db);
+        for migration in rustok_content::migrations::migrations()
+            .into_iter()
+            .chain(migrations::migrations())
+        {
+            migration
+                .up(&manager)
+                .await
+                .expect("migration should apply");
+        }
+        let pipeline = SpamPipeline::with_default_heuristics().with_classifier(Arc::new(
+            rustok_content::spam::BlocklistClassifier::new(["casino bonus"], Vec::<String>::new()),
+        ));
+        CommentsService::new(db).with_spam_pipeline(Arc::new(pipeline))
+    }
+
+    fn approved_comment(target_id: Uuid, body: &str) -> CreateCommentInput {
+        CreateCommentInput {
+            target_type: "blog_post".to_string(),
+            target_id,
+            locale: "en".to_string(),
+            body: body.to_string(),
+            body_format: "markdown".to_string(),
+            parent_comment_id: None,
+            status: crate::dto::CommentStatus::Approved,
+        }
+    }
+
+    #[tokio::test]
+    async fn spam_pipeline_holds_first_post_and_learns_from_approval() {
+        let service = setup_screened_comments_service().await;
+        let tenant_id = Uuid::new_v4();
+        let target_id = Uuid::new_v4();
+        let customer = SecurityContext::new(UserRole::Customer, Some(Uuid::new_v4()));
+        let moderator = SecurityContext::system();
+
+        let first = service
+            .create_comment(
+                tenant_id,
+                customer.clone(),
+                approved_comment(target_id, "hello there"),
+            )
+            .await
+            .expect("first comment should be accepted");
+        assert_eq!(first.status, crate::dto::CommentStatus::Pending);
+
+        let decision =
+            SpamDecisionLog::find(&service.db, tenant_id, SpamSurface::Comment, first.id)
+                .await
+                .expect("decision lookup should succeed")
+                .expect("decision should be recorded");
+        assert_eq!(decision.action, "hold");
+        assert!(decision.reasons.to_string().contains("first submission"));
+
+        service
+            .set_comment_status(
+                tenant_id,
+                moderator,
+                first.id,
+                crate::dto::CommentStatus::Approved,
+                "en",
+                None,
+            )
+            .await
+            .expect("moderator should approve the comment");
+
+        let links = service
+            .create_comment(
+                tenant_id,
+                customer,
+                approved_comment(
+                    target_id,
+                    "see https://a.example https://b.example https://c.example",
+                ),
+            )
+            .await
+            .expect("trusted author comment should be accepted");
+        assert_eq!(links.status, crate::dto::CommentStatus::Approved);
+    }
+
+    #[tokio::test]
+    async fn spam_pipeline_marks_blocked_content_and_skips_moderators() {
+        let service = setup_screened_comments_service().await;
+        let tenant_id = Uuid::new_v4();
+        let target_id = Uuid::new_v4();
+        let customer = SecurityContext::new(UserRole::Customer, Some(Uuid::new_v4()));
+
+        let spam = service
+            .create_comment(
+                tenant_id,
+                customer,
+                approved_comment(target_id, "Casino bonus for everyone"),
+            )
+            .await
+            .expect("spam should be stored, not rejected");
+        assert_eq!(spam.status, crate::dto::CommentStatus::Spam);
+
+        let moderated = service
+            .create_comment_with_origin(
+                tenant_id,
+                SecurityContext::new(UserRole::Admin, Some(Uuid::new_v4())),
+                approved_comment(target_id, "Casino bonus announcement"),
+                SubmissionOrigin {
+                    ip_address: Some("198.51.100.1".to_string()),
+                },
+            )
+            .await
+            .expect("moderator comment should be accepted");
+        assert_eq!(moderated.status, crate::dto::CommentStatus::Approved);
+        assert!(
+            SpamDecisionLog::find(&service.db, tenant_id, SpamSurface::Comment, moderated.id)
+                .await
+                .expect("decision lookup should succeed")
+                .is_none()
+        );
+    }
+
     #[tokio::test]
     async fn non_moderator_cannot_create_spam_or_trash_comment() {
         let service = setup_comments_service().await;
@@ -1163,6 +1428,41 @@ fn normalize_locale(locale: &str) -> CommentsResult<String> {
         .ok_or_else(|| CommentsError::Validation("Invalid locale".to_string()))
 }
 
+fn content_error(error: rustok_content::ContentError) -> CommentsError {
+    match error {
+        rustok_content::ContentError::Database(error) => CommentsError::Database(error),
+        rustok_content::ContentError::Forbidden(message) => CommentsError::Forbidden(message),
+        sanctioned @ rustok_content::ContentError::UserSanctioned { .. } => {
+            CommentsError::Forbidden(sanctioned.to_string())
+        }
+        other => CommentsError::Validation(other.to_string()),
+    }
+}
+
+async fn record_thread_status_change(
+    txn: &DatabaseTransaction,
+    tenant_id: Uuid,
+    security: &SecurityContext,
+    thread: &comment_thread::Model,
+    old_status: crate::dto::CommentThreadStatus,
+) -> CommentsResult<()> {
+    ModerationLogService::record_in_tx(
+        txn,
+        tenant_id,
+        security.user_id,
+        ModerationLogInput::new("comment.thread.status_changed", "comment_thread", thread.id)
+            .with_details(serde_json::json!({
+                "target_type": thread.target_type,
+                "target_id": thread.target_id,
+                "old_status": old_status,
+                "new_status": thread.status,
+            })),
+    )
+    .await
+    .map_err(content_error)?;
+    Ok(())
+}
+
 fn record_entrypoint(entry_point: &str) {
     metrics::record_module_entrypoint_call(MODULE, entry_point, LIBRARY_PATH);
 }
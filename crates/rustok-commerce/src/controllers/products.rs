@@ -8,6 +8,7 @@ use rustok_api::{
     loco::transactional_event_bus_from_context, AuthContext, RequestContext, TenantContext,
 };
 use rustok_core::{locale_tags_match, Permission};
+use rustok_product::product_tag_tree_condition;
 use rustok_telemetry::metrics;
 use sea_orm::{
     ColumnTrait, ConnectionTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect,
@@ -68,6 +69,13 @@ pub async fn list_products(
             search,
         ));
     }
+    if let Some(tag_id) = params.tag_id {
+        query = query.filter(
+            product_tag_tree_condition(&ctx.db, tenant.id, tag_id)
+                .await
+                .map_err(|err| Error::BadRequest(err.to_string()))?,
+        );
+    }
 
     let count_started_at = Instant::now();
     let total = query
@@ -303,6 +311,8 @@ pub struct ListProductsParams {
     pub vendor: Option<String>,
     pub product_type: Option<String>,
     pub search: Option<String>,
+    /// Taxonomy term id; matches products tagged with the term or any of its descendants.
+    pub tag_id: Option<Uuid>,
     pub locale: Option<String>,
 }
 
@@ -5,6 +5,7 @@ use rustok_api::{
 };
 use rustok_core::{locale_tags_match, Permission};
 use rustok_outbox::TransactionalEventBus;
+use rustok_product::product_tag_tree_condition;
 use rustok_telemetry::metrics;
 use sea_orm::{
     ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
@@ -1352,6 +1353,7 @@ impl CommerceQuery {
             status: None,
             vendor: None,
             search: None,
+            tag_id: None,
             page: Some(1),
             per_page: Some(20),
         });
@@ -1376,6 +1378,9 @@ impl CommerceQuery {
                 search,
             ));
         }
+        if let Some(tag_id) = filter.tag_id {
+            query = query.filter(product_tag_tree_condition(db, tenant_id, tag_id).await?);
+        }
 
         let total = query.clone().count(db).await?;
         let products = query
@@ -1519,6 +1524,7 @@ impl CommerceQuery {
             vendor: None,
             product_type: None,
             search: None,
+            tag_id: None,
             page: Some(1),
             per_page: Some(12),
         });
@@ -1547,6 +1553,9 @@ impl CommerceQuery {
                 search,
             ));
         }
+        if let Some(tag_id) = filter.tag_id {
+            query = query.filter(product_tag_tree_condition(db, tenant_id, tag_id).await?);
+        }
 
         let visible_products = query
             .order_by_desc(product::Column::PublishedAt)
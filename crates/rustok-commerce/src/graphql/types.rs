@@ -863,6 +863,8 @@ pub struct ProductsFilter {
     pub status: Option<GqlProductStatus>,
     pub vendor: Option<String>,
     pub search: Option<String>,
+    /// Matches products tagged with this term or any of its descendants.
+    pub tag_id: Option<Uuid>,
     pub page: Option<u64>,
     pub per_page: Option<u64>,
 }
@@ -872,6 +874,8 @@ pub struct StorefrontProductsFilter {
     pub vendor: Option<String>,
     pub product_type: Option<String>,
     pub search: Option<String>,
+    /// Matches products tagged with this term or any of its descendants.
+    pub tag_id: Option<Uuid>,
     pub page: Option<u64>,
     pub per_page: Option<u64>,
 }
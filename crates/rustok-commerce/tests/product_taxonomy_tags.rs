@@ -138,6 +138,7 @@ async fn product_tag_sync_reuses_existing_global_taxonomy_term() {
                 canonical_key: None,
                 description: None,
                 aliases: vec![],
+                parent_id: None,
             },
         )
         .await
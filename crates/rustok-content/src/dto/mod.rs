@@ -1,4 +1,5 @@
 pub mod category;
+pub mod moderation;
 pub mod node;
 pub mod tag;
 pub mod validation;
@@ -8,6 +9,11 @@ pub use category::{
     CategoryListItem, CategoryResponse, CreateCategoryInput, ListCategoriesFilter,
     UpdateCategoryInput,
 };
+pub use moderation::{
+    CreateReportInput, IssueSanctionInput, ListModerationLogFilter, ListReportsFilter,
+    ModerationLogEntry, ReportEntryResponse, ReportReason, ReportResponse, ReportStatus,
+    ReportTargetKind, ResolveReportInput, SanctionKind, SanctionResponse,
+};
 pub use node::*;
 pub use tag::{CreateTagInput, ListTagsFilter, TagListItem, TagResponse, UpdateTagInput};
 pub use validation_helpers::{format_single_error, format_validation_errors};
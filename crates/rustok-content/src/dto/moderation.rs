@@ -0,0 +1,286 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+/// Kind of object a user can report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportTargetKind {
+    ForumTopic,
+    ForumReply,
+    Comment,
+    Profile,
+}
+
+impl ReportTargetKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ForumTopic => "forum_topic",
+            Self::ForumReply => "forum_reply",
+            Self::Comment => "comment",
+            Self::Profile => "profile",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "forum_topic" => Some(Self::ForumTopic),
+            "forum_reply" => Some(Self::ForumReply),
+            "comment" => Some(Self::Comment),
+            "profile" => Some(Self::Profile),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportReason {
+    Spam,
+    Harassment,
+    HateSpeech,
+    Inappropriate,
+    OffTopic,
+    Other,
+}
+
+impl ReportReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Spam => "spam",
+            Self::Harassment => "harassment",
+            Self::HateSpeech => "hate_speech",
+            Self::Inappropriate => "inappropriate",
+            Self::OffTopic => "off_topic",
+            Self::Other => "other",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "spam" => Some(Self::Spam),
+            "harassment" => Some(Self::Harassment),
+            "hate_speech" => Some(Self::HateSpeech),
+            "inappropriate" => Some(Self::Inappropriate),
+            "off_topic" => Some(Self::OffTopic),
+            "other" => Some(Self::Other),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportStatus {
+    Open,
+    Resolved,
+    Dismissed,
+}
+
+impl ReportStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::Resolved => "resolved",
+            Self::Dismissed => "dismissed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "open" => Some(Self::Open),
+            "resolved" => Some(Self::Resolved),
+            "dismissed" => Some(Self::Dismissed),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateReportInput {
+    pub target_kind: ReportTargetKind,
+    pub target_id: Uuid,
+    /// Author of the reported object, resolved by the owning module rather
+    /// than supplied by the reporter.
+    #[serde(default, skip_deserializing)]
+    pub target_author_id: Option<Uuid>,
+    pub reason: ReportReason,
+    #[schema(max_length = 2000)]
+    pub details: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ResolveReportInput {
+    /// Either `resolved` (action was taken) or `dismissed` (no violation).
+    pub status: ReportStatus,
+    #[schema(max_length = 2000)]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, IntoParams)]
+pub struct ListReportsFilter {
+    pub status: Option<ReportStatus>,
+    pub target_kind: Option<ReportTargetKind>,
+    #[serde(default = "default_page")]
+    pub page: u64,
+    #[serde(default = "default_per_page")]
+    pub per_page: u64,
+}
+
+impl Default for ListReportsFilter {
+    fn default() -> Self {
+        Self {
+            status: None,
+            target_kind: None,
+            page: default_page(),
+            per_page: default_per_page(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReportEntryResponse {
+    pub reporter_id: Uuid,
+    pub reason: ReportReason,
+    pub details: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReportResponse {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub target_kind: ReportTargetKind,
+    pub target_id: Uuid,
+    pub target_author_id: Option<Uuid>,
+    pub status: ReportStatus,
+    pub report_count: i32,
+    /// Number of reports per reason, keyed by reason.
+    pub reasons: BTreeMap<String, i64>,
+    pub resolved_by: Option<Uuid>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub resolution_note: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_reported_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SanctionKind {
+    /// Recorded on the account, no restrictions.
+    Warn,
+    /// Cannot create or edit posts; can still vote, subscribe and report.
+    Mute,
+    /// Temporarily locked out of all write actions.
+    Suspend,
+    /// Permanently locked out of all write actions until revoked.
+    Ban,
+}
+
+impl SanctionKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Warn => "warn",
+            Self::Mute => "mute",
+            Self::Suspend => "suspend",
+            Self::Ban => "ban",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "warn" => Some(Self::Warn),
+            "mute" => Some(Self::Mute),
+            "suspend" => Some(Self::Suspend),
+            "ban" => Some(Self::Ban),
+            _ => None,
+        }
+    }
+
+    /// Whether the sanction blocks creating and editing content.
+    pub fn blocks_posting(self) -> bool {
+        matches!(self, Self::Mute | Self::Suspend | Self::Ban)
+    }
+
+    /// Whether the sanction blocks lightweight interactions (votes,
+    /// subscriptions, reports) as well as posting.
+    pub fn blocks_interaction(self) -> bool {
+        matches!(self, Self::Suspend | Self::Ban)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct IssueSanctionInput {
+    pub user_id: Uuid,
+    pub kind: SanctionKind,
+    #[schema(max_length = 2000)]
+    pub reason: String,
+    /// Required for `suspend`, optional for `mute`, rejected for `warn` and `ban`.
+    pub duration_seconds: Option<i64>,
+    /// Report that led to the sanction, if any.
+    pub report_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SanctionResponse {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub user_id: Uuid,
+    pub kind: SanctionKind,
+    pub reason: String,
+    pub issued_by: Option<Uuid>,
+    pub report_id: Option<Uuid>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub revoked_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub active: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, IntoParams)]
+pub struct ListModerationLogFilter {
+    pub target_kind: Option<String>,
+    pub target_id: Option<Uuid>,
+    pub actor_id: Option<Uuid>,
+    pub subject_user_id: Option<Uuid>,
+    #[serde(default = "default_page")]
+    pub page: u64,
+    #[serde(default = "default_per_page")]
+    pub per_page: u64,
+}
+
+impl Default for ListModerationLogFilter {
+    fn default() -> Self {
+        Self {
+            target_kind: None,
+            target_id: None,
+            actor_id: None,
+            subject_user_id: None,
+            page: default_page(),
+            per_page: default_per_page(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ModerationLogEntry {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub actor_id: Option<Uuid>,
+    pub action: String,
+    pub target_kind: String,
+    pub target_id: Uuid,
+    pub subject_user_id: Option<Uuid>,
+    pub details: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+fn default_page() -> u64 {
+    1
+}
+
+fn default_per_page() -> u64 {
+    20
+}
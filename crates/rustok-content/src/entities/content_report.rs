@@ -0,0 +1,34 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "content_reports")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub target_kind: String,
+    pub target_id: Uuid,
+    pub target_author_id: Option<Uuid>,
+    pub status: String,
+    pub report_count: i32,
+    pub reasons: Json,
+    pub resolved_by: Option<Uuid>,
+    pub resolved_at: Option<DateTimeWithTimeZone>,
+    pub resolution_note: Option<String>,
+    pub created_at: DateTimeWithTimeZone,
+    pub last_reported_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::content_report_entry::Entity")]
+    Entries,
+}
+
+impl Related<super::content_report_entry::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Entries.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,33 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "content_report_entries")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub report_id: Uuid,
+    pub tenant_id: Uuid,
+    pub reporter_id: Uuid,
+    pub reason: String,
+    pub details: Option<String>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::content_report::Entity",
+        from = "Column::ReportId",
+        to = "super::content_report::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Report,
+}
+
+impl Related<super::content_report::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Report.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
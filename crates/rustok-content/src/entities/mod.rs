@@ -2,18 +2,32 @@ pub mod body;
 pub mod canonical_url;
 pub mod category;
 pub mod category_translation;
+pub mod content_mention;
+pub mod content_quote;
+pub mod content_report;
+pub mod content_report_entry;
+pub mod moderation_log;
 pub mod node;
 pub mod node_translation;
 pub mod orchestration_audit_log;
 pub mod orchestration_operation;
+pub mod spam_decision;
 pub mod url_alias;
+pub mod user_sanction;
 
 pub use body::Entity as Body;
 pub use canonical_url::Entity as CanonicalUrl;
 pub use category::Entity as Category;
 pub use category_translation::Entity as CategoryTranslation;
+pub use content_mention::Entity as ContentMention;
+pub use content_quote::Entity as ContentQuote;
+pub use content_report::Entity as ContentReport;
+pub use content_report_entry::Entity as ContentReportEntry;
+pub use moderation_log::Entity as ModerationLog;
 pub use node::Entity as Node;
 pub use node_translation::Entity as NodeTranslation;
 pub use orchestration_audit_log::Entity as OrchestrationAuditLog;
 pub use orchestration_operation::Entity as OrchestrationOperation;
+pub use spam_decision::Entity as SpamDecision;
 pub use url_alias::Entity as UrlAlias;
+pub use user_sanction::Entity as UserSanction;
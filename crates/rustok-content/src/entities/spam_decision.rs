@@ -0,0 +1,25 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "content_spam_decisions")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub surface: String,
+    pub target_id: Uuid,
+    pub author_id: Option<Uuid>,
+    pub ip_address: Option<String>,
+    pub action: String,
+    pub reasons: Json,
+    pub link_domains: Json,
+    pub moderator_verdict: Option<String>,
+    pub moderator_id: Option<Uuid>,
+    pub reviewed_at: Option<DateTimeWithTimeZone>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
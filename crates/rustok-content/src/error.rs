@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use rustok_core::error::{ErrorKind, RichError};
 use sea_orm::DbErr;
 use thiserror::Error;
@@ -33,6 +34,13 @@ pub enum ContentError {
     #[error("Forbidden: {0}")]
     Forbidden(String),
 
+    #[error("User {user_id} is sanctioned ({kind})")]
+    UserSanctioned {
+        user_id: Uuid,
+        kind: String,
+        expires_at: Option<DateTime<Utc>>,
+    },
+
     #[error("Validation error: {0}")]
     Validation(String),
 
@@ -98,6 +106,27 @@ impl From<ContentError> for RichError {
             .with_error_code("CONCURRENT_MODIFICATION"),
             ContentError::Forbidden(msg) => RichError::new(ErrorKind::Forbidden, msg)
                 .with_user_message("You do not have permission to perform this action"),
+            ContentError::UserSanctioned {
+                user_id,
+                kind,
+                expires_at,
+            } => {
+                let mut rich = RichError::new(
+                    ErrorKind::Forbidden,
+                    format!(
+                        "User {} is restricted by an active {} sanction",
+                        user_id, kind
+                    ),
+                )
+                .with_user_message("Your account is currently restricted by a moderator")
+                .with_field("user_id", user_id.to_string())
+                .with_field("sanction", kind)
+                .with_error_code("USER_SANCTIONED");
+                if let Some(expires_at) = expires_at {
+                    rich = rich.with_field("expires_at", expires_at.to_rfc3339());
+                }
+                rich
+            }
             ContentError::Validation(msg) => {
                 RichError::new(ErrorKind::Validation, msg).with_user_message("Invalid input data")
             }
@@ -166,6 +195,7 @@ impl ContentError {
             ContentError::DuplicateSlug { .. } => "conflict",
             ContentError::ConcurrentModification { .. } => "conflict",
             ContentError::Forbidden(_) => "forbidden",
+            ContentError::UserSanctioned { .. } => "forbidden",
             ContentError::Validation(_) => "validation",
             ContentError::Rich(_) => "rich",
         }
@@ -7,8 +7,10 @@ pub mod dto;
 pub mod entities;
 pub mod error;
 pub mod locale;
+pub mod mentions;
 pub mod migrations;
 pub mod services;
+pub mod spam;
 pub mod state_machine;
 
 #[cfg(test)]
@@ -23,12 +25,21 @@ pub use locale::{
     available_locales_from, normalize_locale_code, resolve_by_locale,
     resolve_by_locale_with_fallback, ResolvedLocale,
 };
+pub use mentions::{
+    scan_body, MentionContext, MentionService, MentionSource, QuoteBacklink, QuoteReference,
+    ScannedBody, MAX_MENTIONS_PER_BODY,
+};
 pub use rustok_core::PLATFORM_FALLBACK_LOCALE;
 pub use services::{
     CanonicalUrlMutation, CanonicalUrlService, CategoryService, ContentOrchestrationBridge,
     ContentOrchestrationService, DemotePostToTopicInput, DemotePostToTopicOutput, MergeTopicsInput,
-    MergeTopicsOutput, OrchestrationResult, PromoteTopicToPostInput, PromoteTopicToPostOutput,
-    ResolvedContentRoute, RetiredCanonicalTarget, SplitTopicInput, SplitTopicOutput,
+    MergeTopicsOutput, ModerationLogInput, ModerationLogService, OrchestrationResult,
+    PromoteTopicToPostInput, PromoteTopicToPostOutput, ReportService, ResolvedContentRoute,
+    RetiredCanonicalTarget, SanctionService, SplitTopicInput, SplitTopicOutput,
+};
+pub use spam::{
+    ModeratorVerdict, SpamAction, SpamClassifier, SpamDecision, SpamDecisionLog, SpamHistory,
+    SpamPipeline, SpamSubmission, SpamSurface, SpamVerdict, SubmissionOrigin,
 };
 pub use state_machine::{Archived, ContentNode, Draft, Published, ToContentStatus};
 
@@ -0,0 +1,253 @@
+//! `@mentions` and structured quotes shared by forum replies and comments.
+//!
+//! [`scan_body`] extracts handles and quote references from a stored body. Callers resolve the
+//! handles to users (and apply their own visibility rules), then persist the result with
+//! [`MentionService::sync_in_tx`], which returns the users that were not mentioned before so only
+//! they get notified. Quote rows let the quoted reply or comment list who quoted it.
+
+mod parser;
+
+use std::collections::HashSet;
+
+use chrono::Utc;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::entities::{content_mention, content_quote};
+use crate::error::ContentResult;
+
+pub use parser::{scan_body, MAX_MENTIONS_PER_BODY};
+
+/// Kind of content that can mention users or be quoted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MentionSource {
+    ForumReply,
+    Comment,
+}
+
+impl MentionSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ForumReply => "forum_reply",
+            Self::Comment => "comment",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "forum_reply" => Some(Self::ForumReply),
+            "comment" => Some(Self::Comment),
+            _ => None,
+        }
+    }
+}
+
+/// Reference from a quote block back to the quoted reply or comment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct QuoteReference {
+    pub source: MentionSource,
+    pub source_id: Uuid,
+}
+
+/// Handles and quote references found in a body, in order of appearance and without duplicates.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScannedBody {
+    pub handles: Vec<String>,
+    pub quotes: Vec<QuoteReference>,
+}
+
+impl ScannedBody {
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty() && self.quotes.is_empty()
+    }
+}
+
+/// Content whose mentions and quotes are being synchronised.
+#[derive(Debug, Clone, Copy)]
+pub struct MentionContext {
+    pub tenant_id: Uuid,
+    pub source: MentionSource,
+    pub source_id: Uuid,
+    /// Topic or post the source belongs to; used to build links.
+    pub target_id: Uuid,
+    pub author_id: Option<Uuid>,
+}
+
+/// A reply or comment that quotes another one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuoteBacklink {
+    pub source: MentionSource,
+    pub source_id: Uuid,
+    pub target_id: Uuid,
+}
+
+pub struct MentionService {
+    db: DatabaseConnection,
+}
+
+impl MentionService {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Replaces the stored mentions and quotes of a source.
+    ///
+    /// Returns the users that were not mentioned by this source before. The author is never
+    /// recorded as mentioning themselves.
+    pub async fn sync_in_tx<C: ConnectionTrait>(
+        conn: &C,
+        context: MentionContext,
+        mentioned_user_ids: &[Uuid],
+        quotes: &[QuoteReference],
+    ) -> ContentResult<Vec<Uuid>> {
+        let source_kind = context.source.as_str();
+        let existing: HashSet<Uuid> = content_mention::Entity::find()
+            .filter(content_mention::Column::SourceKind.eq(source_kind))
+            .filter(content_mention::Column::SourceId.eq(context.source_id))
+            .all(conn)
+            .await?
+            .into_iter()
+            .map(|row| row.mentioned_user_id)
+            .collect();
+
+        let mut wanted = Vec::with_capacity(mentioned_user_ids.len());
+        for user_id in mentioned_user_ids {
+            if Some(*user_id) != context.author_id && !wanted.contains(user_id) {
+                wanted.push(*user_id);
+            }
+        }
+
+        let removed: Vec<Uuid> = existing
+            .iter()
+            .filter(|user_id| !wanted.contains(user_id))
+            .copied()
+            .collect();
+        if !removed.is_empty() {
+            content_mention::Entity::delete_many()
+                .filter(content_mention::Column::SourceKind.eq(source_kind))
+                .filter(content_mention::Column::SourceId.eq(context.source_id))
+                .filter(content_mention::Column::MentionedUserId.is_in(removed))
+                .exec(conn)
+                .await?;
+        }
+
+        let now = Utc::now();
+        let added: Vec<Uuid> = wanted
+            .into_iter()
+            .filter(|user_id| !existing.contains(user_id))
+            .collect();
+        if !added.is_empty() {
+            content_mention::Entity::insert_many(added.iter().map(|user_id| {
+                content_mention::ActiveModel {
+                    id: Set(Uuid::new_v4()),
+                    tenant_id: Set(context.tenant_id),
+                    source_kind: Set(source_kind.to_string()),
+                    source_id: Set(context.source_id),
+                    target_id: Set(context.target_id),
+                    author_id: Set(context.author_id),
+                    mentioned_user_id: Set(*user_id),
+                    created_at: Set(now.into()),
+                }
+            }))
+            .exec(conn)
+            .await?;
+        }
+
+        content_quote::Entity::delete_many()
+            .filter(content_quote::Column::SourceKind.eq(source_kind))
+            .filter(content_quote::Column::SourceId.eq(context.source_id))
+            .exec(conn)
+            .await?;
+        let quotes: Vec<&QuoteReference> = quotes
+            .iter()
+            .filter(|quote| {
+                !(quote.source == context.source && quote.source_id == context.source_id)
+            })
+            .collect();
+        if !quotes.is_empty() {
+            content_quote::Entity::insert_many(quotes.into_iter().map(|quote| {
+                content_quote::ActiveModel {
+                    id: Set(Uuid::new_v4()),
+                    tenant_id: Set(context.tenant_id),
+                    source_kind: Set(source_kind.to_string()),
+                    source_id: Set(context.source_id),
+                    target_id: Set(context.target_id),
+                    quoted_kind: Set(quote.source.as_str().to_string()),
+                    quoted_id: Set(quote.source_id),
+                    created_at: Set(now.into()),
+                }
+            }))
+            .exec(conn)
+            .await?;
+        }
+
+        Ok(added)
+    }
+
+    /// Drops mention and quote rows written by a deleted source.
+    pub async fn delete_for_source_in_tx<C: ConnectionTrait>(
+        conn: &C,
+        source: MentionSource,
+        source_id: Uuid,
+    ) -> ContentResult<()> {
+        content_mention::Entity::delete_many()
+            .filter(content_mention::Column::SourceKind.eq(source.as_str()))
+            .filter(content_mention::Column::SourceId.eq(source_id))
+            .exec(conn)
+            .await?;
+        content_quote::Entity::delete_many()
+            .filter(content_quote::Column::SourceKind.eq(source.as_str()))
+            .filter(content_quote::Column::SourceId.eq(source_id))
+            .exec(conn)
+            .await?;
+        Ok(())
+    }
+
+    /// Users currently mentioned by a source.
+    pub async fn mentioned_users(
+        &self,
+        tenant_id: Uuid,
+        source: MentionSource,
+        source_id: Uuid,
+    ) -> ContentResult<Vec<Uuid>> {
+        Ok(content_mention::Entity::find()
+            .filter(content_mention::Column::TenantId.eq(tenant_id))
+            .filter(content_mention::Column::SourceKind.eq(source.as_str()))
+            .filter(content_mention::Column::SourceId.eq(source_id))
+            .order_by_asc(content_mention::Column::CreatedAt)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|row| row.mentioned_user_id)
+            .collect())
+    }
+
+    /// Replies and comments that quote the given source, oldest first.
+    pub async fn quoted_by(
+        &self,
+        tenant_id: Uuid,
+        source: MentionSource,
+        source_id: Uuid,
+    ) -> ContentResult<Vec<QuoteBacklink>> {
+        Ok(content_quote::Entity::find()
+            .filter(content_quote::Column::TenantId.eq(tenant_id))
+            .filter(content_quote::Column::QuotedKind.eq(source.as_str()))
+            .filter(content_quote::Column::QuotedId.eq(source_id))
+            .order_by_asc(content_quote::Column::CreatedAt)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .filter_map(|row| {
+                Some(QuoteBacklink {
+                    source: MentionSource::parse(&row.source_kind)?,
+                    source_id: row.source_id,
+                    target_id: row.target_id,
+                })
+            })
+            .collect())
+    }
+}
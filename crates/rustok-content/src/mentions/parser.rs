@@ -0,0 +1,238 @@
+use serde_json::Value;
+use uuid::Uuid;
+
+use rustok_core::{CONTENT_FORMAT_MARKDOWN, CONTENT_FORMAT_RT_JSON_V1};
+
+use super::{MentionSource, QuoteReference, ScannedBody};
+
+/// Upper bound of distinct handles picked up from a single body.
+pub const MAX_MENTIONS_PER_BODY: usize = 20;
+
+const QUOTE_TAG_PREFIX: &str = "[quote=";
+
+/// Extracts `@handle` mentions and quote references from a stored body.
+///
+/// `body` is the persisted representation produced by `prepare_content_payload`: raw markdown or
+/// the sanitized `rt_json_v1` document serialized as JSON. Markdown quotes use
+/// `[quote=forum_reply:<uuid>]` / `[quote=comment:<uuid>]`; `rt_json_v1` quotes are `blockquote`
+/// nodes with `source_kind` and `source_id` attrs. Mentions inside code are ignored.
+pub fn scan_body(body_format: &str, body: &str) -> ScannedBody {
+    let mut scanned = ScannedBody::default();
+    match body_format {
+        CONTENT_FORMAT_MARKDOWN => scan_markdown(body, &mut scanned),
+        CONTENT_FORMAT_RT_JSON_V1 => {
+            if let Ok(payload) = serde_json::from_str::<Value>(body) {
+                if let Some(doc) = payload.get("doc") {
+                    scan_rt_json_node(doc, &mut scanned);
+                }
+            }
+        }
+        _ => {}
+    }
+    scanned
+}
+
+fn scan_markdown(body: &str, scanned: &mut ScannedBody) {
+    let mut fence: Option<&str> = None;
+    for line in body.lines() {
+        let trimmed = line.trim_start();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            continue;
+        }
+        if trimmed.starts_with("```") {
+            fence = Some("```");
+            continue;
+        }
+        if trimmed.starts_with("~~~") {
+            fence = Some("~~~");
+            continue;
+        }
+        if line.starts_with("    ") || line.starts_with('\t') {
+            continue;
+        }
+
+        // Odd segments between backticks are inline code spans.
+        for segment in line.split('`').step_by(2) {
+            scan_quote_tags(segment, scanned);
+            scan_text(segment, scanned);
+        }
+    }
+}
+
+fn scan_rt_json_node(node: &Value, scanned: &mut ScannedBody) {
+    match node.get("type").and_then(Value::as_str) {
+        Some("code_block") => return,
+        Some("mention") => {
+            if let Some(handle) = node
+                .get("attrs")
+                .and_then(|attrs| attrs.get("handle"))
+                .and_then(Value::as_str)
+            {
+                push_handle(handle, scanned);
+            }
+            return;
+        }
+        Some("blockquote") => {
+            let attrs = node.get("attrs");
+            let kind = attrs
+                .and_then(|attrs| attrs.get("source_kind"))
+                .and_then(Value::as_str)
+                .and_then(MentionSource::parse);
+            let id = attrs
+                .and_then(|attrs| attrs.get("source_id"))
+                .and_then(Value::as_str)
+                .and_then(|id| Uuid::parse_str(id).ok());
+            if let (Some(source), Some(source_id)) = (kind, id) {
+                push_quote(QuoteReference { source, source_id }, scanned);
+            }
+        }
+        Some("text") => {
+            let is_code = node
+                .get("marks")
+                .and_then(Value::as_array)
+                .is_some_and(|marks| {
+                    marks
+                        .iter()
+                        .any(|mark| mark.get("type").and_then(Value::as_str) == Some("code"))
+                });
+            if !is_code {
+                if let Some(text) = node.get("text").and_then(Value::as_str) {
+                    scan_text(text, scanned);
+                }
+            }
+            return;
+        }
+        _ => {}
+    }
+
+    if let Some(children) = node.get("content").and_then(Value::as_array) {
+        for child in children {
+            scan_rt_json_node(child, scanned);
+        }
+    }
+}
+
+fn scan_text(text: &str, scanned: &mut ScannedBody) {
+    let chars: Vec<char> = text.chars().collect();
+    let mut index = 0;
+    while index < chars.len() {
+        if chars[index] != '@' {
+            index += 1;
+            continue;
+        }
+        let preceded_by_word = index > 0 && {
+            let previous = chars[index - 1];
+            previous.is_alphanumeric() || matches!(previous, '_' | '-' | '.' | '@' | '/')
+        };
+        let start = index + 1;
+        let mut end = start;
+        while end < chars.len() && is_handle_char(chars[end]) {
+            end += 1;
+        }
+        if !preceded_by_word && end > start {
+            let handle: String = chars[start..end].iter().collect();
+            push_handle(handle.trim_end_matches('-'), scanned);
+        }
+        index = end.max(index + 1);
+    }
+}
+
+fn scan_quote_tags(text: &str, scanned: &mut ScannedBody) {
+    let mut rest = text;
+    while let Some(position) = rest.find(QUOTE_TAG_PREFIX) {
+        rest = &rest[position + QUOTE_TAG_PREFIX.len()..];
+        let Some(close) = rest.find(']') else {
+            break;
+        };
+        let reference = rest[..close].trim().trim_matches('"');
+        if let Some((kind, id)) = reference.split_once(':') {
+            if let (Some(source), Ok(source_id)) = (
+                MentionSource::parse(kind.trim()),
+                Uuid::parse_str(id.trim()),
+            ) {
+                push_quote(QuoteReference { source, source_id }, scanned);
+            }
+        }
+        rest = &rest[close + 1..];
+    }
+}
+
+fn is_handle_char(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || ch == '_' || ch == '-'
+}
+
+fn push_handle(handle: &str, scanned: &mut ScannedBody) {
+    let handle = handle.trim().trim_start_matches('@').to_ascii_lowercase();
+    if handle.is_empty()
+        || scanned.handles.len() >= MAX_MENTIONS_PER_BODY
+        || scanned.handles.contains(&handle)
+    {
+        return;
+    }
+    scanned.handles.push(handle);
+}
+
+fn push_quote(reference: QuoteReference, scanned: &mut ScannedBody) {
+    if !scanned.quotes.contains(&reference) {
+        scanned.quotes.push(reference);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn markdown_mentions_skip_code_and_emails() {
+        let body = "Hi @Alice and @bob-, see `@not_me`\nmail me at carol@example.com\n```\n@fenced\n```\n@alice again";
+        let scanned = scan_body(CONTENT_FORMAT_MARKDOWN, body);
+        assert_eq!(scanned.handles, vec!["alice", "bob"]);
+    }
+
+    #[test]
+    fn markdown_quote_tags_are_parsed() {
+        let id = Uuid::new_v4();
+        let body = format!("[quote=forum_reply:{id}]earlier[/quote]\n[quote=page:{id}]x[/quote]");
+        let scanned = scan_body(CONTENT_FORMAT_MARKDOWN, &body);
+        assert_eq!(
+            scanned.quotes,
+            vec![QuoteReference {
+                source: MentionSource::ForumReply,
+                source_id: id,
+            }]
+        );
+    }
+
+    #[test]
+    fn rt_json_mentions_and_quotes_are_parsed() {
+        let id = Uuid::new_v4();
+        let body = json!({
+            "version": "rt_json_v1",
+            "locale": "en",
+            "doc": {"type": "doc", "content": [
+                {"type": "blockquote", "attrs": {"source_kind": "comment", "source_id": id.to_string()},
+                 "content": [{"type": "paragraph", "content": [{"type": "text", "text": "@quoted"}]}]},
+                {"type": "paragraph", "content": [
+                    {"type": "mention", "attrs": {"handle": "dave"}},
+                    {"type": "text", "text": " and @erin"},
+                    {"type": "text", "text": "@code", "marks": [{"type": "code"}]}
+                ]},
+                {"type": "code_block", "content": [{"type": "text", "text": "@block"}]}
+            ]}
+        })
+        .to_string();
+        let scanned = scan_body(CONTENT_FORMAT_RT_JSON_V1, &body);
+        assert_eq!(scanned.handles, vec!["quoted", "dave", "erin"]);
+        assert_eq!(
+            scanned.quotes,
+            vec![QuoteReference {
+                source: MentionSource::Comment,
+                source_id: id,
+            }]
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ContentSpamDecisions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ContentSpamDecisions::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentSpamDecisions::TenantId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentSpamDecisions::Surface)
+                            .string_len(32)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentSpamDecisions::TargetId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ContentSpamDecisions::AuthorId).uuid())
+                    .col(ColumnDef::new(ContentSpamDecisions::IpAddress).string_len(64))
+                    .col(
+                        ColumnDef::new(ContentSpamDecisions::Action)
+                            .string_len(16)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentSpamDecisions::Reasons)
+                            .json()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentSpamDecisions::LinkDomains)
+                            .json()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ContentSpamDecisions::ModeratorVerdict).string_len(16))
+                    .col(ColumnDef::new(ContentSpamDecisions::ModeratorId).uuid())
+                    .col(
+                        ColumnDef::new(ContentSpamDecisions::ReviewedAt).timestamp_with_time_zone(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentSpamDecisions::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_content_spam_decisions_target")
+                    .table(ContentSpamDecisions::Table)
+                    .col(ContentSpamDecisions::TenantId)
+                    .col(ContentSpamDecisions::Surface)
+                    .col(ContentSpamDecisions::TargetId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_content_spam_decisions_author")
+                    .table(ContentSpamDecisions::Table)
+                    .col(ContentSpamDecisions::TenantId)
+                    .col(ContentSpamDecisions::AuthorId)
+                    .col(ContentSpamDecisions::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_content_spam_decisions_ip")
+                    .table(ContentSpamDecisions::Table)
+                    .col(ContentSpamDecisions::TenantId)
+                    .col(ContentSpamDecisions::IpAddress)
+                    .col(ContentSpamDecisions::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ContentSpamDecisions::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum ContentSpamDecisions {
+    Table,
+    Id,
+    TenantId,
+    Surface,
+    TargetId,
+    AuthorId,
+    IpAddress,
+    Action,
+    Reasons,
+    LinkDomains,
+    ModeratorVerdict,
+    ModeratorId,
+    ReviewedAt,
+    CreatedAt,
+}
@@ -0,0 +1,347 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ContentReports::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ContentReports::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ContentReports::TenantId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(ContentReports::TargetKind)
+                            .string_len(32)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ContentReports::TargetId).uuid().not_null())
+                    .col(ColumnDef::new(ContentReports::TargetAuthorId).uuid())
+                    .col(
+                        ColumnDef::new(ContentReports::Status)
+                            .string_len(16)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentReports::ReportCount)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(ContentReports::Reasons).json().not_null())
+                    .col(ColumnDef::new(ContentReports::ResolvedBy).uuid())
+                    .col(ColumnDef::new(ContentReports::ResolvedAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(ContentReports::ResolutionNote).text())
+                    .col(
+                        ColumnDef::new(ContentReports::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentReports::LastReportedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_content_reports_target")
+                    .table(ContentReports::Table)
+                    .col(ContentReports::TenantId)
+                    .col(ContentReports::TargetKind)
+                    .col(ContentReports::TargetId)
+                    .col(ContentReports::Status)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_content_reports_queue")
+                    .table(ContentReports::Table)
+                    .col(ContentReports::TenantId)
+                    .col(ContentReports::Status)
+                    .col(ContentReports::LastReportedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ContentReportEntries::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ContentReportEntries::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentReportEntries::ReportId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentReportEntries::TenantId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentReportEntries::ReporterId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentReportEntries::Reason)
+                            .string_len(32)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ContentReportEntries::Details).text())
+                    .col(
+                        ColumnDef::new(ContentReportEntries::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(ContentReportEntries::Table, ContentReportEntries::ReportId)
+                            .to(ContentReports::Table, ContentReports::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_content_report_entries_reporter")
+                    .table(ContentReportEntries::Table)
+                    .col(ContentReportEntries::ReportId)
+                    .col(ContentReportEntries::ReporterId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ContentUserSanctions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ContentUserSanctions::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentUserSanctions::TenantId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentUserSanctions::UserId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentUserSanctions::Kind)
+                            .string_len(16)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentUserSanctions::Reason)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ContentUserSanctions::IssuedBy).uuid())
+                    .col(ColumnDef::new(ContentUserSanctions::ReportId).uuid())
+                    .col(ColumnDef::new(ContentUserSanctions::ExpiresAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(ContentUserSanctions::RevokedAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(ContentUserSanctions::RevokedBy).uuid())
+                    .col(
+                        ColumnDef::new(ContentUserSanctions::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_content_user_sanctions_user")
+                    .table(ContentUserSanctions::Table)
+                    .col(ContentUserSanctions::TenantId)
+                    .col(ContentUserSanctions::UserId)
+                    .col(ContentUserSanctions::RevokedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ContentModerationLog::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ContentModerationLog::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentModerationLog::TenantId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ContentModerationLog::ActorId).uuid())
+                    .col(
+                        ColumnDef::new(ContentModerationLog::Action)
+                            .string_len(64)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentModerationLog::TargetKind)
+                            .string_len(32)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentModerationLog::TargetId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ContentModerationLog::SubjectUserId).uuid())
+                    .col(
+                        ColumnDef::new(ContentModerationLog::Details)
+                            .json()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentModerationLog::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_content_moderation_log_target")
+                    .table(ContentModerationLog::Table)
+                    .col(ContentModerationLog::TenantId)
+                    .col(ContentModerationLog::TargetKind)
+                    .col(ContentModerationLog::TargetId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_content_moderation_log_created")
+                    .table(ContentModerationLog::Table)
+                    .col(ContentModerationLog::TenantId)
+                    .col(ContentModerationLog::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ContentModerationLog::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(ContentUserSanctions::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(ContentReportEntries::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(ContentReports::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum ContentReports {
+    Table,
+    Id,
+    TenantId,
+    TargetKind,
+    TargetId,
+    TargetAuthorId,
+    Status,
+    ReportCount,
+    Reasons,
+    ResolvedBy,
+    ResolvedAt,
+    ResolutionNote,
+    CreatedAt,
+    LastReportedAt,
+}
+
+#[derive(Iden)]
+enum ContentReportEntries {
+    Table,
+    Id,
+    ReportId,
+    TenantId,
+    ReporterId,
+    Reason,
+    Details,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum ContentUserSanctions {
+    Table,
+    Id,
+    TenantId,
+    UserId,
+    Kind,
+    Reason,
+    IssuedBy,
+    ReportId,
+    ExpiresAt,
+    RevokedAt,
+    RevokedBy,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum ContentModerationLog {
+    Table,
+    Id,
+    TenantId,
+    ActorId,
+    Action,
+    TargetKind,
+    TargetId,
+    SubjectUserId,
+    Details,
+    CreatedAt,
+}
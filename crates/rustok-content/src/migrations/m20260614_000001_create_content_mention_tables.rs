@@ -0,0 +1,164 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ContentMentions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ContentMentions::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ContentMentions::TenantId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(ContentMentions::SourceKind)
+                            .string_len(32)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ContentMentions::SourceId).uuid().not_null())
+                    .col(ColumnDef::new(ContentMentions::TargetId).uuid().not_null())
+                    .col(ColumnDef::new(ContentMentions::AuthorId).uuid())
+                    .col(
+                        ColumnDef::new(ContentMentions::MentionedUserId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ContentMentions::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_content_mentions_source_user")
+                    .table(ContentMentions::Table)
+                    .col(ContentMentions::SourceKind)
+                    .col(ContentMentions::SourceId)
+                    .col(ContentMentions::MentionedUserId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_content_mentions_user")
+                    .table(ContentMentions::Table)
+                    .col(ContentMentions::TenantId)
+                    .col(ContentMentions::MentionedUserId)
+                    .col(ContentMentions::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ContentQuotes::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ContentQuotes::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ContentQuotes::TenantId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(ContentQuotes::SourceKind)
+                            .string_len(32)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ContentQuotes::SourceId).uuid().not_null())
+                    .col(ColumnDef::new(ContentQuotes::TargetId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(ContentQuotes::QuotedKind)
+                            .string_len(32)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ContentQuotes::QuotedId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(ContentQuotes::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_content_quotes_source")
+                    .table(ContentQuotes::Table)
+                    .col(ContentQuotes::SourceKind)
+                    .col(ContentQuotes::SourceId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_content_quotes_quoted")
+                    .table(ContentQuotes::Table)
+                    .col(ContentQuotes::TenantId)
+                    .col(ContentQuotes::QuotedKind)
+                    .col(ContentQuotes::QuotedId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ContentQuotes::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(ContentMentions::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum ContentMentions {
+    Table,
+    Id,
+    TenantId,
+    SourceKind,
+    SourceId,
+    TargetId,
+    AuthorId,
+    MentionedUserId,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum ContentQuotes {
+    Table,
+    Id,
+    TenantId,
+    SourceKind,
+    SourceId,
+    TargetId,
+    QuotedKind,
+    QuotedId,
+    CreatedAt,
+}
@@ -9,6 +9,9 @@ mod m20260311_000001_create_content_orchestration_tables;
 mod m20260316_000003_create_node_field_definitions;
 mod m20260317_000001_alter_categories_add_updated_at;
 mod m20260328_000001_create_content_url_tables;
+mod m20260611_000001_create_content_spam_decisions;
+mod m20260612_000001_create_content_moderation_tables;
+mod m20260614_000001_create_content_mention_tables;
 
 use sea_orm_migration::MigrationTrait;
 
@@ -23,5 +26,8 @@ pub fn migrations() -> Vec<Box<dyn MigrationTrait>> {
         Box::new(m20260316_000003_create_node_field_definitions::Migration),
         Box::new(m20260317_000001_alter_categories_add_updated_at::Migration),
         Box::new(m20260328_000001_create_content_url_tables::Migration),
+        Box::new(m20260611_000001_create_content_spam_decisions::Migration),
+        Box::new(m20260612_000001_create_content_moderation_tables::Migration),
+        Box::new(m20260614_000001_create_content_mention_tables::Migration),
     ]
 }
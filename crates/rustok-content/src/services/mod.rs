@@ -1,7 +1,10 @@
 mod canonical_url_service;
 mod category_service;
 mod content_orchestration_service;
+mod moderation_log_service;
 mod node_service;
+mod report_service;
+mod sanction_service;
 
 pub use canonical_url_service::{CanonicalUrlService, ResolvedContentRoute};
 pub use category_service::CategoryService;
@@ -11,4 +14,7 @@ pub use content_orchestration_service::{
     OrchestrationResult, PromoteTopicToPostInput, PromoteTopicToPostOutput, RetiredCanonicalTarget,
     SplitTopicInput, SplitTopicOutput,
 };
+pub use moderation_log_service::{ModerationLogInput, ModerationLogService};
 pub use node_service::NodeService;
+pub use report_service::ReportService;
+pub use sanction_service::SanctionService;
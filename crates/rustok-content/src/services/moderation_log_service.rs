@@ -0,0 +1,161 @@
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, DatabaseConnection,
+    EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use rustok_core::{Action, PermissionScope, Resource, SecurityContext};
+
+use crate::dto::moderation::{ListModerationLogFilter, ModerationLogEntry};
+use crate::entities::moderation_log;
+use crate::error::{ContentError, ContentResult};
+
+/// Resources whose `Moderate` permission grants access to the shared
+/// moderation queue, sanctions and log.
+const MODERATED_RESOURCES: [Resource; 3] = [
+    Resource::ForumTopics,
+    Resource::ForumReplies,
+    Resource::Comments,
+];
+
+pub(crate) fn is_moderator(security: &SecurityContext) -> bool {
+    MODERATED_RESOURCES
+        .iter()
+        .any(|resource| security.get_scope(*resource, Action::Moderate) == PermissionScope::All)
+}
+
+pub(crate) fn ensure_moderator(security: &SecurityContext) -> ContentResult<()> {
+    if is_moderator(security) {
+        Ok(())
+    } else {
+        Err(ContentError::forbidden("Permission denied"))
+    }
+}
+
+/// A single moderation action to append to the log.
+#[derive(Debug, Clone)]
+pub struct ModerationLogInput {
+    pub action: String,
+    pub target_kind: String,
+    pub target_id: Uuid,
+    pub subject_user_id: Option<Uuid>,
+    pub details: serde_json::Value,
+}
+
+impl ModerationLogInput {
+    pub fn new(action: impl Into<String>, target_kind: impl Into<String>, target_id: Uuid) -> Self {
+        Self {
+            action: action.into(),
+            target_kind: target_kind.into(),
+            target_id,
+            subject_user_id: None,
+            details: serde_json::json!({}),
+        }
+    }
+
+    pub fn with_subject(mut self, user_id: Option<Uuid>) -> Self {
+        self.subject_user_id = user_id;
+        self
+    }
+
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = details;
+        self
+    }
+}
+
+/// Append-only log of moderation actions across forum, comments, reports and
+/// sanctions. Entries are never updated or deleted through this service.
+pub struct ModerationLogService {
+    db: DatabaseConnection,
+}
+
+impl ModerationLogService {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Appends an entry using the caller's connection so the log row commits
+    /// together with the moderated change.
+    pub async fn record_in_tx<C>(
+        conn: &C,
+        tenant_id: Uuid,
+        actor_id: Option<Uuid>,
+        input: ModerationLogInput,
+    ) -> ContentResult<Uuid>
+    where
+        C: ConnectionTrait,
+    {
+        let id = Uuid::new_v4();
+        moderation_log::ActiveModel {
+            id: Set(id),
+            tenant_id: Set(tenant_id),
+            actor_id: Set(actor_id),
+            action: Set(input.action),
+            target_kind: Set(input.target_kind),
+            target_id: Set(input.target_id),
+            subject_user_id: Set(input.subject_user_id),
+            details: Set(input.details),
+            created_at: Set(Utc::now().into()),
+        }
+        .insert(conn)
+        .await?;
+
+        Ok(id)
+    }
+
+    #[instrument(skip(self, security))]
+    pub async fn list(
+        &self,
+        tenant_id: Uuid,
+        security: SecurityContext,
+        filter: ListModerationLogFilter,
+    ) -> ContentResult<(Vec<ModerationLogEntry>, u64)> {
+        ensure_moderator(&security)?;
+
+        let mut query =
+            moderation_log::Entity::find().filter(moderation_log::Column::TenantId.eq(tenant_id));
+        if let Some(target_kind) = filter.target_kind {
+            query = query.filter(moderation_log::Column::TargetKind.eq(target_kind));
+        }
+        if let Some(target_id) = filter.target_id {
+            query = query.filter(moderation_log::Column::TargetId.eq(target_id));
+        }
+        if let Some(actor_id) = filter.actor_id {
+            query = query.filter(moderation_log::Column::ActorId.eq(actor_id));
+        }
+        if let Some(subject_user_id) = filter.subject_user_id {
+            query = query.filter(moderation_log::Column::SubjectUserId.eq(subject_user_id));
+        }
+
+        let paginator = query
+            .order_by_desc(moderation_log::Column::CreatedAt)
+            .order_by_desc(moderation_log::Column::Id)
+            .paginate(&self.db, filter.per_page.clamp(1, 100));
+        let total = paginator.num_items().await?;
+        let items = paginator
+            .fetch_page(filter.page.max(1) - 1)
+            .await?
+            .into_iter()
+            .map(to_entry)
+            .collect();
+
+        Ok((items, total))
+    }
+}
+
+fn to_entry(model: moderation_log::Model) -> ModerationLogEntry {
+    ModerationLogEntry {
+        id: model.id,
+        tenant_id: model.tenant_id,
+        actor_id: model.actor_id,
+        action: model.action,
+        target_kind: model.target_kind,
+        target_id: model.target_id,
+        subject_user_id: model.subject_user_id,
+        details: model.details,
+        created_at: model.created_at.into(),
+    }
+}
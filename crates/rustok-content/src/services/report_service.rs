@@ -0,0 +1,302 @@
+use std::collections::BTreeMap;
+
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DatabaseConnection, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, TransactionTrait,
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use rustok_core::error::{ErrorKind, RichError};
+use rustok_core::SecurityContext;
+
+use super::moderation_log_service::{ensure_moderator, ModerationLogInput, ModerationLogService};
+use super::sanction_service::SanctionService;
+use crate::dto::moderation::{
+    CreateReportInput, ListReportsFilter, ReportEntryResponse, ReportReason, ReportResponse,
+    ReportStatus, ReportTargetKind, ResolveReportInput,
+};
+use crate::entities::{content_report, content_report_entry};
+use crate::error::{ContentError, ContentResult};
+
+const MAX_DETAILS_LEN: usize = 2000;
+
+/// User-facing abuse reports and the moderator triage queue.
+///
+/// Reports against the same target are folded into a single open queue item;
+/// each reporter is counted at most once per item.
+pub struct ReportService {
+    db: DatabaseConnection,
+}
+
+impl ReportService {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    #[instrument(skip(self, security, input))]
+    pub async fn submit(
+        &self,
+        tenant_id: Uuid,
+        security: SecurityContext,
+        input: CreateReportInput,
+    ) -> ContentResult<ReportResponse> {
+        let reporter_id = security
+            .user_id
+            .ok_or_else(|| ContentError::forbidden("Authentication required to report content"))?;
+        if input.target_kind == ReportTargetKind::Profile && input.target_id == reporter_id {
+            return Err(ContentError::validation(
+                "You cannot report your own profile",
+            ));
+        }
+        let details = input
+            .details
+            .map(|details| details.trim().to_string())
+            .filter(|details| !details.is_empty());
+        if details
+            .as_ref()
+            .is_some_and(|details| details.len() > MAX_DETAILS_LEN)
+        {
+            return Err(ContentError::validation(
+                "Report details cannot exceed 2000 characters",
+            ));
+        }
+
+        let txn = self.db.begin().await?;
+        SanctionService::ensure_can_interact(&txn, tenant_id, Some(reporter_id)).await?;
+
+        let now = Utc::now();
+        let existing = content_report::Entity::find()
+            .filter(content_report::Column::TenantId.eq(tenant_id))
+            .filter(content_report::Column::TargetKind.eq(input.target_kind.as_str()))
+            .filter(content_report::Column::TargetId.eq(input.target_id))
+            .filter(content_report::Column::Status.eq(ReportStatus::Open.as_str()))
+            .one(&txn)
+            .await?;
+        let report = match existing {
+            Some(report) => report,
+            None => {
+                content_report::ActiveModel {
+                    id: Set(Uuid::new_v4()),
+                    tenant_id: Set(tenant_id),
+                    target_kind: Set(input.target_kind.as_str().to_string()),
+                    target_id: Set(input.target_id),
+                    target_author_id: Set(input.target_author_id),
+                    status: Set(ReportStatus::Open.as_str().to_string()),
+                    report_count: Set(0),
+                    reasons: Set(serde_json::json!({})),
+                    resolved_by: Set(None),
+                    resolved_at: Set(None),
+                    resolution_note: Set(None),
+                    created_at: Set(now.into()),
+                    last_reported_at: Set(now.into()),
+                }
+                .insert(&txn)
+                .await?
+            }
+        };
+
+        let already_reported = content_report_entry::Entity::find()
+            .filter(content_report_entry::Column::ReportId.eq(report.id))
+            .filter(content_report_entry::Column::ReporterId.eq(reporter_id))
+            .one(&txn)
+            .await?
+            .is_some();
+        if already_reported {
+            txn.commit().await?;
+            return Ok(to_response(report));
+        }
+
+        content_report_entry::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            report_id: Set(report.id),
+            tenant_id: Set(tenant_id),
+            reporter_id: Set(reporter_id),
+            reason: Set(input.reason.as_str().to_string()),
+            details: Set(details),
+            created_at: Set(now.into()),
+        }
+        .insert(&txn)
+        .await?;
+
+        let mut reasons = reason_counts(&report.reasons);
+        *reasons
+            .entry(input.reason.as_str().to_string())
+            .or_default() += 1;
+        let report_count = report.report_count + 1;
+        let target_author_id = report.target_author_id.or(input.target_author_id);
+        let mut active: content_report::ActiveModel = report.into();
+        active.report_count = Set(report_count);
+        active.reasons = Set(serde_json::json!(reasons));
+        active.target_author_id = Set(target_author_id);
+        active.last_reported_at = Set(now.into());
+        let report = active.update(&txn).await?;
+        txn.commit().await?;
+
+        Ok(to_response(report))
+    }
+
+    /// Moderator triage queue. Defaults to open items, most-reported first.
+    #[instrument(skip(self, security))]
+    pub async fn list_queue(
+        &self,
+        tenant_id: Uuid,
+        security: SecurityContext,
+        filter: ListReportsFilter,
+    ) -> ContentResult<(Vec<ReportResponse>, u64)> {
+        ensure_moderator(&security)?;
+
+        let status = filter.status.unwrap_or(ReportStatus::Open);
+        let mut query = content_report::Entity::find()
+            .filter(content_report::Column::TenantId.eq(tenant_id))
+            .filter(content_report::Column::Status.eq(status.as_str()));
+        if let Some(target_kind) = filter.target_kind {
+            query = query.filter(content_report::Column::TargetKind.eq(target_kind.as_str()));
+        }
+
+        let paginator = query
+            .order_by_desc(content_report::Column::ReportCount)
+            .order_by_desc(content_report::Column::LastReportedAt)
+            .paginate(&self.db, filter.per_page.clamp(1, 100));
+        let total = paginator.num_items().await?;
+        let items = paginator
+            .fetch_page(filter.page.max(1) - 1)
+            .await?
+            .into_iter()
+            .map(to_response)
+            .collect();
+
+        Ok((items, total))
+    }
+
+    #[instrument(skip(self, security))]
+    pub async fn entries(
+        &self,
+        tenant_id: Uuid,
+        security: SecurityContext,
+        report_id: Uuid,
+    ) -> ContentResult<Vec<ReportEntryResponse>> {
+        ensure_moderator(&security)?;
+
+        let entries = content_report_entry::Entity::find()
+            .filter(content_report_entry::Column::TenantId.eq(tenant_id))
+            .filter(content_report_entry::Column::ReportId.eq(report_id))
+            .order_by_asc(content_report_entry::Column::CreatedAt)
+            .all(&self.db)
+            .await?;
+
+        Ok(entries
+            .into_iter()
+            .map(|entry| ReportEntryResponse {
+                reporter_id: entry.reporter_id,
+                reason: ReportReason::parse(&entry.reason).unwrap_or(ReportReason::Other),
+                details: entry.details,
+                created_at: entry.created_at.into(),
+            })
+            .collect())
+    }
+
+    /// Closes an open queue item as resolved or dismissed and records the
+    /// decision in the moderation log.
+    #[instrument(skip(self, security, input))]
+    pub async fn resolve(
+        &self,
+        tenant_id: Uuid,
+        security: SecurityContext,
+        report_id: Uuid,
+        input: ResolveReportInput,
+    ) -> ContentResult<ReportResponse> {
+        ensure_moderator(&security)?;
+        if input.status == ReportStatus::Open {
+            return Err(ContentError::validation(
+                "Reports can only be resolved or dismissed",
+            ));
+        }
+        let note = input
+            .note
+            .map(|note| note.trim().to_string())
+            .filter(|note| !note.is_empty());
+        if note
+            .as_ref()
+            .is_some_and(|note| note.len() > MAX_DETAILS_LEN)
+        {
+            return Err(ContentError::validation(
+                "Resolution note cannot exceed 2000 characters",
+            ));
+        }
+
+        let txn = self.db.begin().await?;
+        let report = content_report::Entity::find_by_id(report_id)
+            .filter(content_report::Column::TenantId.eq(tenant_id))
+            .one(&txn)
+            .await?
+            .ok_or_else(|| {
+                ContentError::from(
+                    RichError::new(
+                        ErrorKind::NotFound,
+                        format!("Report {} not found", report_id),
+                    )
+                    .with_error_code("REPORT_NOT_FOUND"),
+                )
+            })?;
+        if report.status != ReportStatus::Open.as_str() {
+            return Err(ContentError::validation("Report is already closed"));
+        }
+
+        let target_kind = report.target_kind.clone();
+        let target_id = report.target_id;
+        let target_author_id = report.target_author_id;
+        let report_count = report.report_count;
+        let mut active: content_report::ActiveModel = report.into();
+        active.status = Set(input.status.as_str().to_string());
+        active.resolved_by = Set(security.user_id);
+        active.resolved_at = Set(Some(Utc::now().into()));
+        active.resolution_note = Set(note.clone());
+        let report = active.update(&txn).await?;
+
+        ModerationLogService::record_in_tx(
+            &txn,
+            tenant_id,
+            security.user_id,
+            ModerationLogInput::new(
+                format!("report.{}", input.status.as_str()),
+                target_kind,
+                target_id,
+            )
+            .with_subject(target_author_id)
+            .with_details(serde_json::json!({
+                "report_id": report_id,
+                "report_count": report_count,
+                "note": note,
+            })),
+        )
+        .await?;
+        txn.commit().await?;
+
+        Ok(to_response(report))
+    }
+}
+
+fn reason_counts(value: &serde_json::Value) -> BTreeMap<String, i64> {
+    serde_json::from_value(value.clone()).unwrap_or_default()
+}
+
+fn to_response(model: content_report::Model) -> ReportResponse {
+    ReportResponse {
+        id: model.id,
+        tenant_id: model.tenant_id,
+        target_kind: ReportTargetKind::parse(&model.target_kind)
+            .unwrap_or(ReportTargetKind::Profile),
+        target_id: model.target_id,
+        target_author_id: model.target_author_id,
+        status: ReportStatus::parse(&model.status).unwrap_or(ReportStatus::Open),
+        report_count: model.report_count,
+        reasons: reason_counts(&model.reasons),
+        resolved_by: model.resolved_by,
+        resolved_at: model.resolved_at.map(Into::into),
+        resolution_note: model.resolution_note,
+        created_at: model.created_at.into(),
+        last_reported_at: model.last_reported_at.into(),
+    }
+}
@@ -0,0 +1,300 @@
+use chrono::{Duration, Utc};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, Condition, ConnectionTrait,
+    DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, TransactionTrait,
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use rustok_core::error::{ErrorKind, RichError};
+use rustok_core::SecurityContext;
+
+use super::moderation_log_service::{ensure_moderator, ModerationLogInput, ModerationLogService};
+use crate::dto::moderation::{IssueSanctionInput, SanctionKind, SanctionResponse};
+use crate::entities::user_sanction;
+use crate::error::{ContentError, ContentResult};
+
+const MAX_REASON_LEN: usize = 2000;
+
+/// Issues and enforces account sanctions (warn, mute, suspend, ban).
+///
+/// Write paths in forum and comments call [`SanctionService::ensure_can_post`]
+/// or [`SanctionService::ensure_can_interact`] before persisting anything.
+pub struct SanctionService {
+    db: DatabaseConnection,
+}
+
+impl SanctionService {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    #[instrument(skip(self, security, input))]
+    pub async fn issue(
+        &self,
+        tenant_id: Uuid,
+        security: SecurityContext,
+        input: IssueSanctionInput,
+    ) -> ContentResult<SanctionResponse> {
+        ensure_moderator(&security)?;
+
+        let reason = input.reason.trim();
+        if reason.is_empty() {
+            return Err(ContentError::validation("Sanction reason cannot be empty"));
+        }
+        if reason.len() > MAX_REASON_LEN {
+            return Err(ContentError::validation(
+                "Sanction reason cannot exceed 2000 characters",
+            ));
+        }
+        if security.user_id == Some(input.user_id) {
+            return Err(ContentError::validation(
+                "Moderators cannot sanction themselves",
+            ));
+        }
+
+        let duration = match (input.kind, input.duration_seconds) {
+            (_, Some(seconds)) if seconds <= 0 => {
+                return Err(ContentError::validation(
+                    "Sanction duration must be positive",
+                ))
+            }
+            (SanctionKind::Suspend, None) => {
+                return Err(ContentError::validation("Suspensions require a duration"))
+            }
+            (SanctionKind::Warn | SanctionKind::Ban, Some(_)) => {
+                return Err(ContentError::validation(
+                    "Warnings and bans do not take a duration",
+                ))
+            }
+            (_, seconds) => seconds.map(Duration::seconds),
+        };
+
+        let now = Utc::now();
+        let id = Uuid::new_v4();
+        let txn = self.db.begin().await?;
+        let model = user_sanction::ActiveModel {
+            id: Set(id),
+            tenant_id: Set(tenant_id),
+            user_id: Set(input.user_id),
+            kind: Set(input.kind.as_str().to_string()),
+            reason: Set(reason.to_string()),
+            issued_by: Set(security.user_id),
+            report_id: Set(input.report_id),
+            expires_at: Set(duration.map(|duration| (now + duration).into())),
+            revoked_at: Set(None),
+            revoked_by: Set(None),
+            created_at: Set(now.into()),
+        }
+        .insert(&txn)
+        .await?;
+
+        ModerationLogService::record_in_tx(
+            &txn,
+            tenant_id,
+            security.user_id,
+            ModerationLogInput::new("sanction.issued", "profile", input.user_id)
+                .with_subject(Some(input.user_id))
+                .with_details(serde_json::json!({
+                    "sanction_id": id,
+                    "kind": input.kind.as_str(),
+                    "reason": reason,
+                    "expires_at": model.expires_at,
+                    "report_id": input.report_id,
+                })),
+        )
+        .await?;
+        txn.commit().await?;
+
+        Ok(to_response(model))
+    }
+
+    #[instrument(skip(self, security))]
+    pub async fn revoke(
+        &self,
+        tenant_id: Uuid,
+        security: SecurityContext,
+        sanction_id: Uuid,
+        note: Option<String>,
+    ) -> ContentResult<SanctionResponse> {
+        ensure_moderator(&security)?;
+
+        let txn = self.db.begin().await?;
+        let sanction = user_sanction::Entity::find_by_id(sanction_id)
+            .filter(user_sanction::Column::TenantId.eq(tenant_id))
+            .one(&txn)
+            .await?
+            .ok_or_else(|| {
+                ContentError::from(
+                    RichError::new(
+                        ErrorKind::NotFound,
+                        format!("Sanction {} not found", sanction_id),
+                    )
+                    .with_error_code("SANCTION_NOT_FOUND"),
+                )
+            })?;
+        if sanction.revoked_at.is_some() {
+            return Err(ContentError::validation("Sanction is already revoked"));
+        }
+
+        let user_id = sanction.user_id;
+        let kind = sanction.kind.clone();
+        let mut active: user_sanction::ActiveModel = sanction.into();
+        active.revoked_at = Set(Some(Utc::now().into()));
+        active.revoked_by = Set(security.user_id);
+        let model = active.update(&txn).await?;
+
+        ModerationLogService::record_in_tx(
+            &txn,
+            tenant_id,
+            security.user_id,
+            ModerationLogInput::new("sanction.revoked", "profile", user_id)
+                .with_subject(Some(user_id))
+                .with_details(serde_json::json!({
+                    "sanction_id": sanction_id,
+                    "kind": kind,
+                    "note": note,
+                })),
+        )
+        .await?;
+        txn.commit().await?;
+
+        Ok(to_response(model))
+    }
+
+    /// Lists every sanction ever issued to a user. Moderators can inspect any
+    /// account; users can only see their own record.
+    #[instrument(skip(self, security))]
+    pub async fn list_for_user(
+        &self,
+        tenant_id: Uuid,
+        security: SecurityContext,
+        user_id: Uuid,
+    ) -> ContentResult<Vec<SanctionResponse>> {
+        if security.user_id != Some(user_id) {
+            ensure_moderator(&security)?;
+        }
+
+        let items = user_sanction::Entity::find()
+            .filter(user_sanction::Column::TenantId.eq(tenant_id))
+            .filter(user_sanction::Column::UserId.eq(user_id))
+            .order_by_desc(user_sanction::Column::CreatedAt)
+            .all(&self.db)
+            .await?;
+
+        Ok(items.into_iter().map(to_response).collect())
+    }
+
+    /// Sanctions that are neither revoked nor expired, most restrictive first.
+    pub async fn active_for_user<C>(
+        conn: &C,
+        tenant_id: Uuid,
+        user_id: Uuid,
+    ) -> ContentResult<Vec<user_sanction::Model>>
+    where
+        C: ConnectionTrait,
+    {
+        let now = Utc::now();
+        let mut items = user_sanction::Entity::find()
+            .filter(user_sanction::Column::TenantId.eq(tenant_id))
+            .filter(user_sanction::Column::UserId.eq(user_id))
+            .filter(user_sanction::Column::RevokedAt.is_null())
+            .filter(
+                Condition::any()
+                    .add(user_sanction::Column::ExpiresAt.is_null())
+                    .add(user_sanction::Column::ExpiresAt.gt(now)),
+            )
+            .all(conn)
+            .await?;
+        items.sort_by_key(|item| std::cmp::Reverse(severity(&item.kind)));
+
+        Ok(items)
+    }
+
+    /// Rejects users under an active mute, suspension or ban. Anonymous and
+    /// system actors (`user_id == None`) are never sanctioned.
+    pub async fn ensure_can_post<C>(
+        conn: &C,
+        tenant_id: Uuid,
+        user_id: Option<Uuid>,
+    ) -> ContentResult<()>
+    where
+        C: ConnectionTrait,
+    {
+        Self::ensure_not_blocked(conn, tenant_id, user_id, SanctionKind::blocks_posting).await
+    }
+
+    /// Rejects users under an active suspension or ban. Muted users may still
+    /// vote, subscribe and report.
+    pub async fn ensure_can_interact<C>(
+        conn: &C,
+        tenant_id: Uuid,
+        user_id: Option<Uuid>,
+    ) -> ContentResult<()>
+    where
+        C: ConnectionTrait,
+    {
+        Self::ensure_not_blocked(conn, tenant_id, user_id, SanctionKind::blocks_interaction).await
+    }
+
+    async fn ensure_not_blocked<C>(
+        conn: &C,
+        tenant_id: Uuid,
+        user_id: Option<Uuid>,
+        blocks: fn(SanctionKind) -> bool,
+    ) -> ContentResult<()>
+    where
+        C: ConnectionTrait,
+    {
+        let Some(user_id) = user_id else {
+            return Ok(());
+        };
+
+        let blocking = Self::active_for_user(conn, tenant_id, user_id)
+            .await?
+            .into_iter()
+            .find(|sanction| SanctionKind::parse(&sanction.kind).is_some_and(blocks));
+
+        match blocking {
+            Some(sanction) => Err(ContentError::UserSanctioned {
+                user_id,
+                kind: sanction.kind,
+                expires_at: sanction.expires_at.map(Into::into),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+fn severity(kind: &str) -> u8 {
+    match SanctionKind::parse(kind) {
+        Some(SanctionKind::Ban) => 3,
+        Some(SanctionKind::Suspend) => 2,
+        Some(SanctionKind::Mute) => 1,
+        Some(SanctionKind::Warn) | None => 0,
+    }
+}
+
+fn to_response(model: user_sanction::Model) -> SanctionResponse {
+    let now = Utc::now();
+    let active = model.revoked_at.is_none()
+        && model
+            .expires_at
+            .as_ref()
+            .is_none_or(|expires_at| *expires_at > now);
+
+    SanctionResponse {
+        id: model.id,
+        tenant_id: model.tenant_id,
+        user_id: model.user_id,
+        kind: SanctionKind::parse(&model.kind).unwrap_or(SanctionKind::Warn),
+        reason: model.reason,
+        issued_by: model.issued_by,
+        report_id: model.report_id,
+        expires_at: model.expires_at.map(Into::into),
+        revoked_at: model.revoked_at.map(Into::into),
+        revoked_by: model.revoked_by,
+        created_at: model.created_at.into(),
+        active,
+    }
+}
@@ -0,0 +1,478 @@
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+
+use super::{SpamAction, SpamClassifier, SpamHistory, SpamSubmission, SpamVerdict};
+use crate::error::ContentResult;
+
+/// Extracts lowercased link domains (without a leading `www.`) from free-form or rich text.
+pub fn extract_link_domains(body: &str) -> Vec<String> {
+    let lower = body.to_lowercase();
+    let mut domains = Vec::new();
+    let mut rest = lower.as_str();
+
+    while let Some((start, prefix_len)) = next_link_start(rest) {
+        let tail = &rest[start + prefix_len..];
+        let end = tail
+            .find(|ch: char| !(ch.is_alphanumeric() || matches!(ch, '.' | '-')))
+            .unwrap_or(tail.len());
+        let host = tail[..end].trim_end_matches('.');
+        let host = host.strip_prefix("www.").unwrap_or(host);
+        if host.contains('.') && !domains.iter().any(|domain| domain == host) {
+            domains.push(host.to_string());
+        }
+        rest = &tail[end..];
+    }
+
+    domains
+}
+
+fn next_link_start(value: &str) -> Option<(usize, usize)> {
+    ["https://", "http://", "www."]
+        .iter()
+        .filter_map(|prefix| value.find(prefix).map(|index| (index, prefix.len())))
+        .min_by_key(|(index, _)| *index)
+}
+
+/// Holds or rejects submissions with many links.
+#[derive(Debug, Clone)]
+pub struct LinkCountClassifier {
+    pub hold_at: usize,
+    pub spam_at: usize,
+}
+
+impl Default for LinkCountClassifier {
+    fn default() -> Self {
+        Self {
+            hold_at: 3,
+            spam_at: 8,
+        }
+    }
+}
+
+#[async_trait]
+impl SpamClassifier for LinkCountClassifier {
+    fn name(&self) -> &'static str {
+        "link_count"
+    }
+
+    async fn classify(
+        &self,
+        submission: &SpamSubmission,
+        history: &SpamHistory,
+    ) -> ContentResult<Option<SpamVerdict>> {
+        let links = count_links(&submission.body);
+        if links >= self.spam_at {
+            return Ok(Some(SpamVerdict::new(
+                self.name(),
+                SpamAction::Spam,
+                format!("contains {links} links (limit {})", self.spam_at),
+            )));
+        }
+        if links >= self.hold_at && !history.is_trusted_author() {
+            return Ok(Some(SpamVerdict::new(
+                self.name(),
+                SpamAction::Hold,
+                format!("contains {links} links"),
+            )));
+        }
+        Ok(None)
+    }
+}
+
+fn count_links(body: &str) -> usize {
+    let lower = body.to_lowercase();
+    let mut rest = lower.as_str();
+    let mut count = 0;
+    while let Some((start, prefix_len)) = next_link_start(rest) {
+        count += 1;
+        rest = &rest[start + prefix_len..];
+    }
+    count
+}
+
+/// Rejects submissions containing blocked terms or links to blocked domains.
+///
+/// Domains learned from moderator-confirmed spam are blocked in addition to the configured list.
+#[derive(Debug, Clone, Default)]
+pub struct BlocklistClassifier {
+    terms: Vec<String>,
+    domains: Vec<String>,
+}
+
+impl BlocklistClassifier {
+    pub fn new(
+        terms: impl IntoIterator<Item = impl Into<String>>,
+        domains: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self {
+            terms: normalize_entries(terms),
+            domains: normalize_entries(domains),
+        }
+    }
+}
+
+fn normalize_entries(values: impl IntoIterator<Item = impl Into<String>>) -> Vec<String> {
+    values
+        .into_iter()
+        .map(|value| value.into().trim().to_lowercase())
+        .filter(|value| !value.is_empty())
+        .collect()
+}
+
+#[async_trait]
+impl SpamClassifier for BlocklistClassifier {
+    fn name(&self) -> &'static str {
+        "blocklist"
+    }
+
+    async fn classify(
+        &self,
+        submission: &SpamSubmission,
+        history: &SpamHistory,
+    ) -> ContentResult<Option<SpamVerdict>> {
+        let body = submission.body.to_lowercase();
+        if let Some(term) = self.terms.iter().find(|term| body.contains(term.as_str())) {
+            return Ok(Some(SpamVerdict::new(
+                self.name(),
+                SpamAction::Spam,
+                format!("contains blocked term '{term}'"),
+            )));
+        }
+
+        for domain in submission.link_domains() {
+            let configured = self
+                .domains
+                .iter()
+                .any(|blocked| domain == *blocked || domain.ends_with(&format!(".{blocked}")));
+            if configured {
+                return Ok(Some(SpamVerdict::new(
+                    self.name(),
+                    SpamAction::Spam,
+                    format!("links to blocked domain '{domain}'"),
+                )));
+            }
+            if history.learned_spam_domains.contains(&domain) {
+                return Ok(Some(SpamVerdict::new(
+                    self.name(),
+                    SpamAction::Spam,
+                    format!("links to domain '{domain}' repeatedly confirmed as spam"),
+                )));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// Holds submissions when an author or IP posts faster than the configured rate.
+#[derive(Debug, Clone)]
+pub struct VelocityClassifier {
+    pub window: Duration,
+    pub max_per_author: usize,
+    pub max_per_ip: usize,
+}
+
+impl Default for VelocityClassifier {
+    fn default() -> Self {
+        Self {
+            window: Duration::minutes(1),
+            max_per_author: 5,
+            max_per_ip: 10,
+        }
+    }
+}
+
+#[async_trait]
+impl SpamClassifier for VelocityClassifier {
+    fn name(&self) -> &'static str {
+        "velocity"
+    }
+
+    async fn classify(
+        &self,
+        _submission: &SpamSubmission,
+        history: &SpamHistory,
+    ) -> ContentResult<Option<SpamVerdict>> {
+        let since = Utc::now() - self.window;
+        let by_author = history.author_submissions_since(since);
+        if by_author >= self.max_per_author {
+            return Ok(Some(SpamVerdict::new(
+                self.name(),
+                SpamAction::Hold,
+                format!(
+                    "author posted {by_author} times in the last {}s",
+                    self.window.num_seconds()
+                ),
+            )));
+        }
+        let by_ip = history.ip_submissions_since(since);
+        if by_ip >= self.max_per_ip {
+            return Ok(Some(SpamVerdict::new(
+                self.name(),
+                SpamAction::Hold,
+                format!(
+                    "IP address posted {by_ip} times in the last {}s",
+                    self.window.num_seconds()
+                ),
+            )));
+        }
+        Ok(None)
+    }
+}
+
+/// Holds the first screened submission of an author (or any anonymous submission).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FirstPostClassifier;
+
+#[async_trait]
+impl SpamClassifier for FirstPostClassifier {
+    fn name(&self) -> &'static str {
+        "first_post"
+    }
+
+    async fn classify(
+        &self,
+        submission: &SpamSubmission,
+        history: &SpamHistory,
+    ) -> ContentResult<Option<SpamVerdict>> {
+        if submission.author_id.is_none() {
+            return Ok(Some(SpamVerdict::new(
+                self.name(),
+                SpamAction::Hold,
+                "anonymous submission",
+            )));
+        }
+        if history.author_total == 0 {
+            return Ok(Some(SpamVerdict::new(
+                self.name(),
+                SpamAction::Hold,
+                "first submission from this author",
+            )));
+        }
+        Ok(None)
+    }
+}
+
+/// Learns from moderator reversals: authors whose submissions moderators confirmed as spam more
+/// often than as legitimate are rejected outright.
+#[derive(Debug, Clone)]
+pub struct AuthorReputationClassifier {
+    pub min_confirmed_spam: u64,
+}
+
+impl Default for AuthorReputationClassifier {
+    fn default() -> Self {
+        Self {
+            min_confirmed_spam: 1,
+        }
+    }
+}
+
+#[async_trait]
+impl SpamClassifier for AuthorReputationClassifier {
+    fn name(&self) -> &'static str {
+        "author_reputation"
+    }
+
+    async fn classify(
+        &self,
+        _submission: &SpamSubmission,
+        history: &SpamHistory,
+    ) -> ContentResult<Option<SpamVerdict>> {
+        if history.author_confirmed_spam >= self.min_confirmed_spam
+            && history.author_confirmed_spam > history.author_confirmed_ham
+        {
+            return Ok(Some(SpamVerdict::new(
+                self.name(),
+                SpamAction::Spam,
+                format!(
+                    "moderators confirmed {} earlier submissions from this author as spam",
+                    history.author_confirmed_spam
+                ),
+            )));
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spam::SpamSurface;
+    use uuid::Uuid;
+
+    fn submission(body: &str) -> SpamSubmission {
+        SpamSubmission {
+            tenant_id: Uuid::new_v4(),
+            surface: SpamSurface::ForumReply,
+            author_id: Some(Uuid::new_v4()),
+            ip_address: Some("203.0.113.7".to_string()),
+            locale: "en".to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    fn known_author() -> SpamHistory {
+        SpamHistory {
+            author_total: 3,
+            ..SpamHistory::default()
+        }
+    }
+
+    #[test]
+    fn extracts_unique_domains_without_www() {
+        let domains = extract_link_domains(
+            "See https://WWW.Example.com/a, http://docs.rs. and www.example.com?x=1",
+        );
+        assert_eq!(
+            domains,
+            vec!["example.com".to_string(), "docs.rs".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn link_count_holds_untrusted_authors_and_rejects_link_farms() {
+        let classifier = LinkCountClassifier::default();
+        let body = "https://a.example https://b.example https://c.example";
+
+        let verdict = classifier
+            .classify(&submission(body), &known_author())
+            .await
+            .unwrap()
+            .expect("three links should be held");
+        assert_eq!(verdict.action, SpamAction::Hold);
+
+        let trusted = SpamHistory {
+            author_confirmed_ham: 1,
+            ..known_author()
+        };
+        assert!(classifier
+            .classify(&submission(body), &trusted)
+            .await
+            .unwrap()
+            .is_none());
+
+        let farm = "https://x.example ".repeat(8);
+        let verdict = classifier
+            .classify(&submission(&farm), &trusted)
+            .await
+            .unwrap()
+            .expect("link farm should be rejected");
+        assert_eq!(verdict.action, SpamAction::Spam);
+    }
+
+    #[tokio::test]
+    async fn blocklist_matches_terms_configured_and_learned_domains() {
+        let classifier = BlocklistClassifier::new(["Casino Bonus"], ["pills.example"]);
+
+        let verdict = classifier
+            .classify(&submission("best casino bonus here"), &known_author())
+            .await
+            .unwrap()
+            .expect("blocked term");
+        assert_eq!(verdict.action, SpamAction::Spam);
+
+        assert!(classifier
+            .classify(
+                &submission("https://shop.pills.example/buy"),
+                &known_author()
+            )
+            .await
+            .unwrap()
+            .is_some());
+
+        let mut history = known_author();
+        history
+            .learned_spam_domains
+            .insert("cheap.example".to_string());
+        let verdict = classifier
+            .classify(&submission("visit https://cheap.example"), &history)
+            .await
+            .unwrap()
+            .expect("learned domain");
+        assert!(verdict.reason.contains("confirmed as spam"));
+
+        assert!(classifier
+            .classify(&submission("plain text"), &known_author())
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn velocity_holds_fast_authors_and_ips() {
+        let classifier = VelocityClassifier::default();
+        let now = Utc::now();
+
+        let history = SpamHistory {
+            author_recent: vec![now; 5],
+            ..known_author()
+        };
+        let verdict = classifier
+            .classify(&submission("hi"), &history)
+            .await
+            .unwrap()
+            .expect("author burst should be held");
+        assert_eq!(verdict.action, SpamAction::Hold);
+
+        let history = SpamHistory {
+            ip_recent: vec![now; 10],
+            ..known_author()
+        };
+        assert!(classifier
+            .classify(&submission("hi"), &history)
+            .await
+            .unwrap()
+            .is_some());
+
+        let history = SpamHistory {
+            author_recent: vec![now - Duration::minutes(10); 20],
+            ..known_author()
+        };
+        assert!(classifier
+            .classify(&submission("hi"), &history)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn first_post_and_reputation_use_history() {
+        let first = FirstPostClassifier
+            .classify(&submission("hi"), &SpamHistory::default())
+            .await
+            .unwrap()
+            .expect("first post should be held");
+        assert_eq!(first.action, SpamAction::Hold);
+        assert!(FirstPostClassifier
+            .classify(&submission("hi"), &known_author())
+            .await
+            .unwrap()
+            .is_none());
+
+        let reputation = AuthorReputationClassifier::default();
+        let history = SpamHistory {
+            author_confirmed_spam: 2,
+            author_confirmed_ham: 1,
+            ..known_author()
+        };
+        assert_eq!(
+            reputation
+                .classify(&submission("hi"), &history)
+                .await
+                .unwrap()
+                .map(|verdict| verdict.action),
+            Some(SpamAction::Spam)
+        );
+        let history = SpamHistory {
+            author_confirmed_spam: 1,
+            author_confirmed_ham: 1,
+            ..known_author()
+        };
+        assert!(reputation
+            .classify(&submission("hi"), &history)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,544 @@
+//! Pluggable anti-spam pipeline shared by comment and forum submission paths.
+//!
+//! A [`SpamPipeline`] runs a chain of [`SpamClassifier`]s against a [`SpamSubmission`] and folds
+//! their verdicts into a single [`SpamDecision`]. Decisions are persisted in
+//! `content_spam_decisions` together with their reasons; moderator reversals recorded through
+//! [`SpamDecisionLog::record_moderator_verdict`] feed back into [`SpamHistory`] so later
+//! submissions from the same author (or carrying the same link domains) are judged accordingly.
+
+mod classifiers;
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect, Set,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::entities::spam_decision;
+use crate::error::ContentResult;
+
+pub use classifiers::{
+    extract_link_domains, AuthorReputationClassifier, BlocklistClassifier, FirstPostClassifier,
+    LinkCountClassifier, VelocityClassifier,
+};
+
+/// How far back the pipeline looks when loading per-author and per-IP history.
+const HISTORY_WINDOW_HOURS: i64 = 24;
+/// Upper bound of recent submissions loaded per author or IP.
+const HISTORY_RECENT_LIMIT: u64 = 200;
+/// Upper bound of moderator-confirmed spam decisions scanned for learned link domains.
+const LEARNED_DOMAIN_SCAN_LIMIT: u64 = 500;
+/// A domain becomes a learned spam domain after this many moderator-confirmed spam posts.
+const LEARNED_DOMAIN_THRESHOLD: usize = 2;
+
+/// Submission surface screened by the pipeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpamSurface {
+    Comment,
+    ForumReply,
+}
+
+impl SpamSurface {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Comment => "comment",
+            Self::ForumReply => "forum_reply",
+        }
+    }
+}
+
+/// Outcome of a classifier or of the whole pipeline, ordered by severity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpamAction {
+    /// Publish with the status the caller asked for.
+    Allow,
+    /// Keep the submission pending until a moderator reviews it.
+    Hold,
+    /// Store the submission as spam.
+    Spam,
+}
+
+impl SpamAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Allow => "allow",
+            Self::Hold => "hold",
+            Self::Spam => "spam",
+        }
+    }
+}
+
+/// Moderator judgement recorded against an earlier pipeline decision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModeratorVerdict {
+    /// The submission is legitimate.
+    Ham,
+    /// The submission is spam.
+    Spam,
+}
+
+impl ModeratorVerdict {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ham => "ham",
+            Self::Spam => "spam",
+        }
+    }
+}
+
+/// Request-level facts that are not part of the submitted payload.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SubmissionOrigin {
+    pub ip_address: Option<String>,
+}
+
+/// Content handed to the classifiers.
+#[derive(Debug, Clone)]
+pub struct SpamSubmission {
+    pub tenant_id: Uuid,
+    pub surface: SpamSurface,
+    pub author_id: Option<Uuid>,
+    pub ip_address: Option<String>,
+    pub locale: String,
+    pub body: String,
+}
+
+impl SpamSubmission {
+    /// Domains of the links found in the body, lowercased and without `www.`.
+    pub fn link_domains(&self) -> Vec<String> {
+        extract_link_domains(&self.body)
+    }
+}
+
+/// Prior decisions relevant to a submission, loaded once per screening.
+#[derive(Debug, Clone, Default)]
+pub struct SpamHistory {
+    /// Creation times of the author's recent screened submissions, newest first.
+    pub author_recent: Vec<DateTime<Utc>>,
+    /// Creation times of recent screened submissions from the same IP, newest first.
+    pub ip_recent: Vec<DateTime<Utc>>,
+    /// Total screened submissions by the author across all surfaces.
+    pub author_total: u64,
+    /// Author submissions a moderator confirmed as legitimate.
+    pub author_confirmed_ham: u64,
+    /// Author submissions a moderator confirmed as spam.
+    pub author_confirmed_spam: u64,
+    /// Link domains that moderators repeatedly confirmed as spam.
+    pub learned_spam_domains: HashSet<String>,
+}
+
+impl SpamHistory {
+    /// Authors with at least one moderator-approved submission and no confirmed spam.
+    pub fn is_trusted_author(&self) -> bool {
+        self.author_confirmed_ham > 0 && self.author_confirmed_spam == 0
+    }
+
+    pub fn author_submissions_since(&self, since: DateTime<Utc>) -> usize {
+        self.author_recent.iter().filter(|at| **at >= since).count()
+    }
+
+    pub fn ip_submissions_since(&self, since: DateTime<Utc>) -> usize {
+        self.ip_recent.iter().filter(|at| **at >= since).count()
+    }
+}
+
+/// Single classifier opinion with the reason shown to moderators.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpamVerdict {
+    pub classifier: String,
+    pub action: SpamAction,
+    pub reason: String,
+}
+
+impl SpamVerdict {
+    pub fn new(classifier: &str, action: SpamAction, reason: impl Into<String>) -> Self {
+        Self {
+            classifier: classifier.to_string(),
+            action,
+            reason: reason.into(),
+        }
+    }
+}
+
+/// Combined pipeline result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpamDecision {
+    pub action: SpamAction,
+    pub verdicts: Vec<SpamVerdict>,
+    pub link_domains: Vec<String>,
+}
+
+impl SpamDecision {
+    fn allow(link_domains: Vec<String>) -> Self {
+        Self {
+            action: SpamAction::Allow,
+            verdicts: Vec::new(),
+            link_domains,
+        }
+    }
+
+    /// Reasons of the verdicts that produced the final action, joined for display.
+    pub fn reason(&self) -> Option<String> {
+        let reasons: Vec<&str> = self
+            .verdicts
+            .iter()
+            .filter(|verdict| verdict.action == self.action)
+            .map(|verdict| verdict.reason.as_str())
+            .collect();
+        (!reasons.is_empty()).then(|| reasons.join("; "))
+    }
+}
+
+/// A single step of the anti-spam chain.
+///
+/// Classifiers return `Ok(None)` when they have no opinion. Errors are logged and treated as
+/// "no opinion" so an unavailable external service never blocks submissions.
+#[async_trait]
+pub trait SpamClassifier: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    async fn classify(
+        &self,
+        submission: &SpamSubmission,
+        history: &SpamHistory,
+    ) -> ContentResult<Option<SpamVerdict>>;
+}
+
+/// Ordered chain of classifiers. The most severe verdict wins; a `Spam` verdict stops the chain.
+#[derive(Clone, Default)]
+pub struct SpamPipeline {
+    classifiers: Vec<Arc<dyn SpamClassifier>>,
+}
+
+impl SpamPipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pipeline with the built-in heuristics in their default configuration.
+    pub fn with_default_heuristics() -> Self {
+        Self::new()
+            .with_classifier(Arc::new(AuthorReputationClassifier::default()))
+            .with_classifier(Arc::new(BlocklistClassifier::default()))
+            .with_classifier(Arc::new(LinkCountClassifier::default()))
+            .with_classifier(Arc::new(VelocityClassifier::default()))
+            .with_classifier(Arc::new(FirstPostClassifier))
+    }
+
+    pub fn with_classifier(mut self, classifier: Arc<dyn SpamClassifier>) -> Self {
+        self.classifiers.push(classifier);
+        self
+    }
+
+    pub fn classifier_names(&self) -> Vec<&'static str> {
+        self.classifiers
+            .iter()
+            .map(|classifier| classifier.name())
+            .collect()
+    }
+
+    /// Loads the submission history and runs the classifier chain without persisting anything.
+    pub async fn screen<C: ConnectionTrait>(
+        &self,
+        conn: &C,
+        submission: &SpamSubmission,
+    ) -> ContentResult<SpamDecision> {
+        let history = SpamDecisionLog::load_history(conn, submission).await?;
+        Ok(self.classify(submission, &history).await)
+    }
+
+    /// Runs the classifier chain against already loaded history.
+    pub async fn classify(
+        &self,
+        submission: &SpamSubmission,
+        history: &SpamHistory,
+    ) -> SpamDecision {
+        let mut decision = SpamDecision::allow(submission.link_domains());
+        for classifier in &self.classifiers {
+            let verdict = match classifier.classify(submission, history).await {
+                Ok(Some(verdict)) => verdict,
+                Ok(None) => continue,
+                Err(error) => {
+                    tracing::warn!(
+                        classifier = classifier.name(),
+                        surface = submission.surface.as_str(),
+                        error = %error,
+                        "spam classifier failed; ignoring its verdict"
+                    );
+                    continue;
+                }
+            };
+            decision.action = decision.action.max(verdict.action);
+            decision.verdicts.push(verdict);
+            if decision.action == SpamAction::Spam {
+                break;
+            }
+        }
+        decision
+    }
+
+    /// Screens a submission and stores the decision for `target_id` in one step.
+    pub async fn screen_and_record<C: ConnectionTrait>(
+        &self,
+        conn: &C,
+        submission: &SpamSubmission,
+        target_id: Uuid,
+    ) -> ContentResult<SpamDecision> {
+        let decision = self.screen(conn, submission).await?;
+        SpamDecisionLog::record(conn, submission, target_id, &decision).await?;
+        Ok(decision)
+    }
+}
+
+/// Persistence for pipeline decisions and moderator feedback.
+pub struct SpamDecisionLog;
+
+impl SpamDecisionLog {
+    pub async fn record<C: ConnectionTrait>(
+        conn: &C,
+        submission: &SpamSubmission,
+        target_id: Uuid,
+        decision: &SpamDecision,
+    ) -> ContentResult<()> {
+        spam_decision::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tenant_id: Set(submission.tenant_id),
+            surface: Set(submission.surface.as_str().to_string()),
+            target_id: Set(target_id),
+            author_id: Set(submission.author_id),
+            ip_address: Set(submission.ip_address.clone()),
+            action: Set(decision.action.as_str().to_string()),
+            reasons: Set(serde_json::json!(decision.verdicts)),
+            link_domains: Set(serde_json::json!(decision.link_domains)),
+            moderator_verdict: Set(None),
+            moderator_id: Set(None),
+            reviewed_at: Set(None),
+            created_at: Set(Utc::now().into()),
+        }
+        .insert(conn)
+        .await?;
+        Ok(())
+    }
+
+    /// Stores a moderator judgement for the decision made on `target_id`.
+    ///
+    /// Returns `false` when the target was never screened by the pipeline.
+    pub async fn record_moderator_verdict<C: ConnectionTrait>(
+        conn: &C,
+        tenant_id: Uuid,
+        surface: SpamSurface,
+        target_id: Uuid,
+        verdict: ModeratorVerdict,
+        moderator_id: Option<Uuid>,
+    ) -> ContentResult<bool> {
+        let Some(existing) = Self::find(conn, tenant_id, surface, target_id).await? else {
+            return Ok(false);
+        };
+        if existing.moderator_verdict.as_deref() == Some(verdict.as_str()) {
+            return Ok(true);
+        }
+
+        let mut active: spam_decision::ActiveModel = existing.into();
+        active.moderator_verdict = Set(Some(verdict.as_str().to_string()));
+        active.moderator_id = Set(moderator_id);
+        active.reviewed_at = Set(Some(Utc::now().into()));
+        active.update(conn).await?;
+        Ok(true)
+    }
+
+    pub async fn find<C: ConnectionTrait>(
+        conn: &C,
+        tenant_id: Uuid,
+        surface: SpamSurface,
+        target_id: Uuid,
+    ) -> ContentResult<Option<spam_decision::Model>> {
+        Ok(spam_decision::Entity::find()
+            .filter(spam_decision::Column::TenantId.eq(tenant_id))
+            .filter(spam_decision::Column::Surface.eq(surface.as_str()))
+            .filter(spam_decision::Column::TargetId.eq(target_id))
+            .one(conn)
+            .await?)
+    }
+
+    pub async fn load_history<C: ConnectionTrait>(
+        conn: &C,
+        submission: &SpamSubmission,
+    ) -> ContentResult<SpamHistory> {
+        let tenant_id = submission.tenant_id;
+        let since = Utc::now() - Duration::hours(HISTORY_WINDOW_HOURS);
+        let mut history = SpamHistory::default();
+
+        if let Some(author_id) = submission.author_id {
+            let by_author = || {
+                spam_decision::Entity::find()
+                    .filter(spam_decision::Column::TenantId.eq(tenant_id))
+                    .filter(spam_decision::Column::AuthorId.eq(author_id))
+            };
+            history.author_recent = by_author()
+                .filter(spam_decision::Column::CreatedAt.gte(since))
+                .order_by_desc(spam_decision::Column::CreatedAt)
+                .limit(HISTORY_RECENT_LIMIT)
+                .all(conn)
+                .await?
+                .into_iter()
+                .map(|decision| decision.created_at.with_timezone(&Utc))
+                .collect();
+            history.author_total = by_author().count(conn).await?;
+            history.author_confirmed_ham = by_author()
+                .filter(spam_decision::Column::ModeratorVerdict.eq(ModeratorVerdict::Ham.as_str()))
+                .count(conn)
+                .await?;
+            history.author_confirmed_spam = by_author()
+                .filter(spam_decision::Column::ModeratorVerdict.eq(ModeratorVerdict::Spam.as_str()))
+                .count(conn)
+                .await?;
+        }
+
+        if let Some(ip_address) = submission.ip_address.as_deref() {
+            history.ip_recent = spam_decision::Entity::find()
+                .filter(spam_decision::Column::TenantId.eq(tenant_id))
+                .filter(spam_decision::Column::IpAddress.eq(ip_address))
+                .filter(spam_decision::Column::CreatedAt.gte(since))
+                .order_by_desc(spam_decision::Column::CreatedAt)
+                .limit(HISTORY_RECENT_LIMIT)
+                .all(conn)
+                .await?
+                .into_iter()
+                .map(|decision| decision.created_at.with_timezone(&Utc))
+                .collect();
+        }
+
+        let confirmed_spam = spam_decision::Entity::find()
+            .filter(spam_decision::Column::TenantId.eq(tenant_id))
+            .filter(spam_decision::Column::ModeratorVerdict.eq(ModeratorVerdict::Spam.as_str()))
+            .order_by_desc(spam_decision::Column::CreatedAt)
+            .limit(LEARNED_DOMAIN_SCAN_LIMIT)
+            .all(conn)
+            .await?;
+        history.learned_spam_domains = learned_spam_domains(
+            confirmed_spam
+                .into_iter()
+                .map(|decision| decision.link_domains),
+        );
+
+        Ok(history)
+    }
+}
+
+fn learned_spam_domains(link_domains: impl Iterator<Item = serde_json::Value>) -> HashSet<String> {
+    let mut counts = std::collections::HashMap::<String, usize>::new();
+    for domains in link_domains {
+        let domains: HashSet<String> = serde_json::from_value(domains).unwrap_or_default();
+        for domain in domains {
+            *counts.entry(domain).or_default() += 1;
+        }
+    }
+    counts
+        .into_iter()
+        .filter(|(_, count)| *count >= LEARNED_DOMAIN_THRESHOLD)
+        .map(|(domain, _)| domain)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FixedClassifier(&'static str, SpamAction);
+
+    #[async_trait]
+    impl SpamClassifier for FixedClassifier {
+        fn name(&self) -> &'static str {
+            self.0
+        }
+
+        async fn classify(
+            &self,
+            _submission: &SpamSubmission,
+            _history: &SpamHistory,
+        ) -> ContentResult<Option<SpamVerdict>> {
+            Ok(Some(SpamVerdict::new(self.0, self.1, self.0)))
+        }
+    }
+
+    struct FailingClassifier;
+
+    #[async_trait]
+    impl SpamClassifier for FailingClassifier {
+        fn name(&self) -> &'static str {
+            "failing"
+        }
+
+        async fn classify(
+            &self,
+            _submission: &SpamSubmission,
+            _history: &SpamHistory,
+        ) -> ContentResult<Option<SpamVerdict>> {
+            Err(crate::ContentError::Validation("unavailable".to_string()))
+        }
+    }
+
+    fn submission(body: &str) -> SpamSubmission {
+        SpamSubmission {
+            tenant_id: Uuid::new_v4(),
+            surface: SpamSurface::Comment,
+            author_id: Some(Uuid::new_v4()),
+            ip_address: None,
+            locale: "en".to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn most_severe_verdict_wins_and_spam_stops_the_chain() {
+        let pipeline = SpamPipeline::new()
+            .with_classifier(Arc::new(FixedClassifier("hold", SpamAction::Hold)))
+            .with_classifier(Arc::new(FixedClassifier("spam", SpamAction::Spam)))
+            .with_classifier(Arc::new(FixedClassifier("never", SpamAction::Allow)));
+
+        let decision = pipeline
+            .classify(&submission("hello"), &SpamHistory::default())
+            .await;
+
+        assert_eq!(decision.action, SpamAction::Spam);
+        assert_eq!(decision.verdicts.len(), 2);
+        assert_eq!(decision.reason().as_deref(), Some("spam"));
+    }
+
+    #[tokio::test]
+    async fn failing_classifier_is_ignored() {
+        let pipeline = SpamPipeline::new().with_classifier(Arc::new(FailingClassifier));
+
+        let decision = pipeline
+            .classify(&submission("hello"), &SpamHistory::default())
+            .await;
+
+        assert_eq!(decision.action, SpamAction::Allow);
+        assert!(decision.reason().is_none());
+    }
+
+    #[test]
+    fn learned_domains_require_repeated_confirmation() {
+        let learned = learned_spam_domains(
+            vec![
+                serde_json::json!(["cheap.example", "docs.rs"]),
+                serde_json::json!(["cheap.example"]),
+                serde_json::json!(["other.example"]),
+            ]
+            .into_iter(),
+        );
+
+        assert!(learned.contains("cheap.example"));
+        assert!(!learned.contains("docs.rs"));
+        assert!(!learned.contains("other.example"));
+    }
+}
@@ -0,0 +1,293 @@
+use rustok_content::{
+    ContentError, CreateReportInput, IssueSanctionInput, ListModerationLogFilter,
+    ListReportsFilter, ModerationLogService, ReportReason, ReportService, ReportStatus,
+    ReportTargetKind, ResolveReportInput, SanctionKind, SanctionService,
+};
+use rustok_core::{SecurityContext, UserRole};
+use sea_orm::{ConnectOptions, Database, DatabaseConnection};
+use sea_orm_migration::SchemaManager;
+use uuid::Uuid;
+
+async fn setup() -> DatabaseConnection {
+    let db_url = format!(
+        "sqlite:file:content_moderation_{}?mode=memory&cache=shared",
+        Uuid::new_v4()
+    );
+    let mut opts = ConnectOptions::new(db_url);
+    opts.max_connections(5)
+        .min_connections(1)
+        .sqlx_logging(false);
+    let db = Database::connect(opts)
+        .await
+        .expect("failed to connect content sqlite database");
+
+    let schema = SchemaManager::new(&db);
+    for migration in rustok_content::migrations::migrations() {
+        migration
+            .up(&schema)
+            .await
+            .expect("content migration should apply");
+    }
+    db
+}
+
+fn user() -> SecurityContext {
+    SecurityContext::new(UserRole::Customer, Some(Uuid::new_v4()))
+}
+
+fn moderator() -> SecurityContext {
+    SecurityContext::new(UserRole::Manager, Some(Uuid::new_v4()))
+}
+
+fn report(target_id: Uuid, reason: ReportReason) -> CreateReportInput {
+    CreateReportInput {
+        target_kind: ReportTargetKind::ForumReply,
+        target_id,
+        target_author_id: None,
+        reason,
+        details: None,
+    }
+}
+
+fn sanction(
+    user_id: Uuid,
+    kind: SanctionKind,
+    duration_seconds: Option<i64>,
+) -> IssueSanctionInput {
+    IssueSanctionInput {
+        user_id,
+        kind,
+        reason: "Repeated abuse".to_string(),
+        duration_seconds,
+        report_id: None,
+    }
+}
+
+#[tokio::test]
+async fn reports_are_deduplicated_per_target_and_reporter() {
+    let db = setup().await;
+    let reports = ReportService::new(db.clone());
+    let tenant_id = Uuid::new_v4();
+    let target_id = Uuid::new_v4();
+    let first_reporter = user();
+
+    reports
+        .submit(
+            tenant_id,
+            first_reporter.clone(),
+            report(target_id, ReportReason::Spam),
+        )
+        .await
+        .expect("first report should be accepted");
+    reports
+        .submit(
+            tenant_id,
+            first_reporter,
+            report(target_id, ReportReason::Harassment),
+        )
+        .await
+        .expect("repeat report should be accepted idempotently");
+    let item = reports
+        .submit(tenant_id, user(), report(target_id, ReportReason::Spam))
+        .await
+        .expect("second reporter should be accepted");
+
+    assert_eq!(item.report_count, 2);
+    assert_eq!(item.reasons.get("spam"), Some(&2));
+    assert!(!item.reasons.contains_key("harassment"));
+
+    let (queue, total) = reports
+        .list_queue(tenant_id, moderator(), ListReportsFilter::default())
+        .await
+        .expect("moderator should see the queue");
+    assert_eq!(total, 1);
+    assert_eq!(queue[0].id, item.id);
+    assert_eq!(
+        reports
+            .entries(tenant_id, moderator(), item.id)
+            .await
+            .expect("entries should load")
+            .len(),
+        2
+    );
+}
+
+#[tokio::test]
+async fn queue_and_resolution_require_moderators_and_are_logged() {
+    let db = setup().await;
+    let reports = ReportService::new(db.clone());
+    let tenant_id = Uuid::new_v4();
+    let target_id = Uuid::new_v4();
+    let moderator = moderator();
+
+    let item = reports
+        .submit(tenant_id, user(), report(target_id, ReportReason::OffTopic))
+        .await
+        .expect("report should be accepted");
+    assert!(matches!(
+        reports
+            .list_queue(tenant_id, user(), ListReportsFilter::default())
+            .await,
+        Err(ContentError::Forbidden(_))
+    ));
+
+    let resolved = reports
+        .resolve(
+            tenant_id,
+            moderator.clone(),
+            item.id,
+            ResolveReportInput {
+                status: ReportStatus::Dismissed,
+                note: Some("Not off-topic".to_string()),
+            },
+        )
+        .await
+        .expect("moderator should dismiss the report");
+    assert_eq!(resolved.status, ReportStatus::Dismissed);
+
+    let reopened = reports
+        .submit(tenant_id, user(), report(target_id, ReportReason::OffTopic))
+        .await
+        .expect("new report after dismissal should open a fresh item");
+    assert_ne!(reopened.id, item.id);
+
+    let (log, _) = ModerationLogService::new(db)
+        .list(
+            tenant_id,
+            moderator.clone(),
+            ListModerationLogFilter {
+                target_id: Some(target_id),
+                ..ListModerationLogFilter::default()
+            },
+        )
+        .await
+        .expect("moderation log should load");
+    assert_eq!(log.len(), 1);
+    assert_eq!(log[0].action, "report.dismissed");
+    assert_eq!(log[0].actor_id, moderator.user_id);
+}
+
+#[tokio::test]
+async fn sanctions_block_posting_and_interaction_by_kind() {
+    let db = setup().await;
+    let sanctions = SanctionService::new(db.clone());
+    let tenant_id = Uuid::new_v4();
+    let muted = Uuid::new_v4();
+    let banned = Uuid::new_v4();
+
+    sanctions
+        .issue(
+            tenant_id,
+            moderator(),
+            sanction(muted, SanctionKind::Mute, None),
+        )
+        .await
+        .expect("mute should be issued");
+    sanctions
+        .issue(
+            tenant_id,
+            moderator(),
+            sanction(banned, SanctionKind::Ban, None),
+        )
+        .await
+        .expect("ban should be issued");
+
+    assert!(matches!(
+        SanctionService::ensure_can_post(&db, tenant_id, Some(muted)).await,
+        Err(ContentError::UserSanctioned { ref kind, .. }) if kind == "mute"
+    ));
+    SanctionService::ensure_can_interact(&db, tenant_id, Some(muted))
+        .await
+        .expect("muted users can still interact");
+    assert!(
+        SanctionService::ensure_can_interact(&db, tenant_id, Some(banned))
+            .await
+            .is_err()
+    );
+    SanctionService::ensure_can_post(&db, Uuid::new_v4(), Some(banned))
+        .await
+        .expect("sanctions are tenant scoped");
+    SanctionService::ensure_can_post(&db, tenant_id, None)
+        .await
+        .expect("system actors are never sanctioned");
+}
+
+#[tokio::test]
+async fn suspensions_need_a_duration_and_can_be_revoked() {
+    let db = setup().await;
+    let sanctions = SanctionService::new(db.clone());
+    let tenant_id = Uuid::new_v4();
+    let target = Uuid::new_v4();
+    let moderator = moderator();
+
+    assert!(matches!(
+        sanctions
+            .issue(
+                tenant_id,
+                moderator.clone(),
+                sanction(target, SanctionKind::Suspend, None)
+            )
+            .await,
+        Err(ContentError::Validation(_))
+    ));
+    assert!(matches!(
+        sanctions
+            .issue(
+                tenant_id,
+                user(),
+                sanction(target, SanctionKind::Warn, None)
+            )
+            .await,
+        Err(ContentError::Forbidden(_))
+    ));
+
+    let issued = sanctions
+        .issue(
+            tenant_id,
+            moderator.clone(),
+            sanction(target, SanctionKind::Suspend, Some(3600)),
+        )
+        .await
+        .expect("suspension should be issued");
+    assert!(issued.active);
+    assert!(issued.expires_at.is_some());
+    assert!(
+        SanctionService::ensure_can_interact(&db, tenant_id, Some(target))
+            .await
+            .is_err()
+    );
+
+    let revoked = sanctions
+        .revoke(tenant_id, moderator.clone(), issued.id, None)
+        .await
+        .expect("suspension should be revoked");
+    assert!(!revoked.active);
+    SanctionService::ensure_can_post(&db, tenant_id, Some(target))
+        .await
+        .expect("revoked sanctions no longer apply");
+
+    let own = SecurityContext::new(UserRole::Customer, Some(target));
+    assert_eq!(
+        sanctions
+            .list_for_user(tenant_id, own, target)
+            .await
+            .expect("users can read their own record")
+            .len(),
+        1
+    );
+
+    let (log, _) = ModerationLogService::new(db)
+        .list(
+            tenant_id,
+            moderator,
+            ListModerationLogFilter {
+                subject_user_id: Some(target),
+                ..ListModerationLogFilter::default()
+            },
+        )
+        .await
+        .expect("moderation log should load");
+    let mut actions: Vec<_> = log.iter().map(|entry| entry.action.as_str()).collect();
+    actions.sort_unstable();
+    assert_eq!(actions, vec!["sanction.issued", "sanction.revoked"]);
+}
@@ -0,0 +1,161 @@
+//! HTML rendering of `grapesjs_v1` project data.
+//!
+//! Only the component tree is rendered; styles, scripts and editor-only
+//! state are ignored. Tags and attributes go through the same allowlist as
+//! raw HTML fragments.
+
+use serde_json::Value;
+
+use super::html::{
+    escape_html, is_allowed_tag, is_dropped_tag, is_void_tag, open_tag, sanitize_html_fragment,
+};
+
+const MAX_DEPTH: usize = 64;
+
+pub(crate) fn render_project(project: &Value) -> String {
+    let mut out = String::new();
+    for page in project
+        .get("pages")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let frames = page.get("frames").and_then(Value::as_array);
+        match frames {
+            Some(frames) => {
+                for frame in frames {
+                    if let Some(component) = frame.get("component") {
+                        render_component(component, 0, &mut out);
+                    }
+                }
+            }
+            None => {
+                if let Some(component) = page.get("component") {
+                    render_component(component, 0, &mut out);
+                }
+            }
+        }
+    }
+    out
+}
+
+fn render_component(component: &Value, depth: usize, out: &mut String) {
+    if depth > MAX_DEPTH {
+        return;
+    }
+    let object = match component {
+        Value::String(html) => {
+            out.push_str(&sanitize_html_fragment(html));
+            return;
+        }
+        Value::Object(object) => object,
+        _ => return,
+    };
+    let component_type = object
+        .get("type")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    if component_type == "textnode" {
+        if let Some(content) = object.get("content").and_then(Value::as_str) {
+            out.push_str(&escape_html(content));
+        }
+        return;
+    }
+
+    let tag = object
+        .get("tagName")
+        .and_then(Value::as_str)
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_else(|| default_tag(component_type).to_string());
+    if is_dropped_tag(&tag) || matches!(component_type, "script" | "iframe" | "video" | "map") {
+        return;
+    }
+
+    let mut inner = String::new();
+    if let Some(content) = object.get("content").and_then(Value::as_str) {
+        inner.push_str(&sanitize_html_fragment(content));
+    }
+    match object.get("components") {
+        Some(Value::Array(children)) => {
+            for child in children {
+                render_component(child, depth + 1, &mut inner);
+            }
+        }
+        Some(child @ Value::String(_)) => render_component(child, depth + 1, &mut inner),
+        _ => {}
+    }
+
+    // Wrappers and unknown tags contribute their content only.
+    if component_type == "wrapper" || !is_allowed_tag(&tag) {
+        out.push_str(&inner);
+        return;
+    }
+
+    let mut attributes: Vec<(String, String)> = object
+        .get("attributes")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .filter_map(|(name, value)| Some((name.to_ascii_lowercase(), value.as_str()?.to_string())))
+        .collect();
+    if tag == "img" {
+        if let Some(src) = object.get("src").and_then(Value::as_str) {
+            attributes.retain(|(name, _)| name != "src");
+            attributes.push(("src".to_string(), src.to_string()));
+        }
+    }
+    out.push_str(&open_tag(
+        &tag,
+        attributes
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str())),
+    ));
+    if !is_void_tag(&tag) {
+        out.push_str(&inner);
+        out.push_str(&format!("</{tag}>"));
+    }
+}
+
+fn default_tag(component_type: &str) -> &'static str {
+    match component_type {
+        "image" => "img",
+        "link" => "a",
+        "text" | "wrapper" | "default" | "" => "div",
+        "table" => "table",
+        "row" => "tr",
+        "cell" => "td",
+        _ => "div",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn renders_component_tree_through_allowlist() {
+        let project = json!({
+            "pages": [{
+                "frames": [{
+                    "component": {
+                        "type": "wrapper",
+                        "components": [
+                            {"type": "text", "content": "<h1>Hello <em>world</em></h1><script>x()</script>"},
+                            {"type": "image", "attributes": {"src": "/media/a.png", "onerror": "x()", "alt": "A"}},
+                            {"type": "link", "attributes": {"href": "javascript:x()"}, "components": [{"type": "textnode", "content": "go <now>"}]},
+                            {"tagName": "script", "content": "alert(1)"},
+                            {"tagName": "custom-el", "components": "<p>inside</p>"}
+                        ]
+                    }
+                }]
+            }],
+            "styles": [{"selectors": ["x"], "style": {"color": "red"}}]
+        });
+        assert_eq!(
+            render_project(&project),
+            "<div><h1>Hello <em>world</em></h1></div><img alt=\"A\" src=\"/media/a.png\">\
+             <a rel=\"nofollow noopener\">go &lt;now&gt;</a><p>inside</p>"
+        );
+    }
+}
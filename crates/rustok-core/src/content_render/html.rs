@@ -0,0 +1,657 @@
+//! HTML helpers shared by the content renderers: escaping, URL policy, an
+//! allowlist sanitizer for raw HTML fragments and HTML-to-text extraction.
+
+const ALLOWED_TAGS: &[&str] = &[
+    "a",
+    "abbr",
+    "article",
+    "b",
+    "blockquote",
+    "br",
+    "caption",
+    "code",
+    "del",
+    "div",
+    "em",
+    "figcaption",
+    "figure",
+    "footer",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "i",
+    "img",
+    "li",
+    "main",
+    "mark",
+    "ol",
+    "p",
+    "pre",
+    "s",
+    "section",
+    "small",
+    "span",
+    "strong",
+    "sub",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "tfoot",
+    "th",
+    "thead",
+    "tr",
+    "u",
+    "ul",
+];
+
+/// Tags dropped together with everything inside them.
+const DROPPED_TAGS: &[&str] = &[
+    "button", "canvas", "embed", "form", "frame", "frameset", "head", "iframe", "input", "link",
+    "math", "meta", "noscript", "object", "script", "select", "style", "svg", "template",
+    "textarea", "title", "video", "audio",
+];
+
+const VOID_TAGS: &[&str] = &["br", "hr", "img"];
+
+const BLOCK_TAGS: &[&str] = &[
+    "article",
+    "blockquote",
+    "br",
+    "div",
+    "figcaption",
+    "figure",
+    "footer",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "li",
+    "main",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "tr",
+    "ul",
+];
+
+pub(crate) fn is_allowed_tag(tag: &str) -> bool {
+    ALLOWED_TAGS.contains(&tag)
+}
+
+pub(crate) fn is_dropped_tag(tag: &str) -> bool {
+    DROPPED_TAGS.contains(&tag)
+}
+
+pub(crate) fn is_void_tag(tag: &str) -> bool {
+    VOID_TAGS.contains(&tag)
+}
+
+/// Attributes kept on an allowed tag. URL attributes are additionally checked
+/// with [`is_safe_url`].
+pub(crate) fn is_allowed_attribute(tag: &str, attribute: &str) -> bool {
+    match attribute {
+        "title" => true,
+        "href" => tag == "a",
+        "src" | "alt" | "width" | "height" => tag == "img",
+        "start" => tag == "ol",
+        "colspan" | "rowspan" => matches!(tag, "td" | "th"),
+        _ => false,
+    }
+}
+
+pub fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+/// Whether a link or image URL may be rendered: `http`, `https`, `mailto`
+/// or a relative reference. Anything with another scheme is refused, as are
+/// protocol-relative URLs (browsers read `\` as `/`). Character references
+/// are checked decoded too, so `javascript&colon;` is refused wherever the
+/// URL ends up.
+pub fn is_safe_url(url: &str) -> bool {
+    is_safe_reference(url) && (!url.contains('&') || is_safe_reference(&decode_entities(url)))
+}
+
+fn is_safe_reference(url: &str) -> bool {
+    let url = url.trim();
+    if url.is_empty() || url.replace('\\', "/").starts_with("//") {
+        return false;
+    }
+    if url.chars().any(|ch| ch.is_control()) {
+        return false;
+    }
+    match url.find(':') {
+        Some(colon) => {
+            let before = &url[..colon];
+            if before.contains(['/', '?', '#']) {
+                // The colon belongs to the path or query of a relative URL.
+                return true;
+            }
+            matches!(
+                before.to_ascii_lowercase().as_str(),
+                "http" | "https" | "mailto"
+            )
+        }
+        None => true,
+    }
+}
+
+/// Decode the character references browsers commonly see in content,
+/// including the named references used to disguise URL schemes.
+pub(crate) fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(index) = rest.find('&') {
+        out.push_str(&rest[..index]);
+        rest = &rest[index..];
+        let decoded = rest.find(';').filter(|end| *end <= 10).and_then(|end| {
+            let name = &rest[1..end];
+            let ch = match name {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                "colon" => Some(':'),
+                "Tab" => Some('\t'),
+                "NewLine" => Some('\n'),
+                "sol" => Some('/'),
+                "bsol" => Some('\\'),
+                "num" => Some('#'),
+                "quest" => Some('?'),
+                "period" => Some('.'),
+                _ => name.strip_prefix('#').and_then(|number| {
+                    let code = match number.strip_prefix(['x', 'X']) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                        None => number.parse().ok(),
+                    };
+                    code.and_then(char::from_u32)
+                }),
+            };
+            ch.map(|ch| (ch, end))
+        });
+        match decoded {
+            Some((ch, end)) => {
+                out.push(ch);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+enum Token<'a> {
+    Text(&'a str),
+    Open {
+        name: String,
+        attributes: Vec<(String, String)>,
+        self_closing: bool,
+    },
+    Close(String),
+}
+
+/// Minimal HTML tokenizer. Comments, doctypes and processing instructions are
+/// skipped; a `<` that does not start a tag is treated as text.
+fn tokenize(html: &str) -> Vec<Token<'_>> {
+    let bytes = html.as_bytes();
+    let mut tokens = Vec::new();
+    let mut text_start = 0;
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] != b'<' {
+            index += 1;
+            continue;
+        }
+        let rest = &html[index..];
+        let consumed = if rest.starts_with("<!--") {
+            Some(rest.find("-->").map_or(rest.len(), |end| end + 3))
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            Some(rest.find('>').map_or(rest.len(), |end| end + 1))
+        } else {
+            parse_tag(rest).map(|(token, len)| {
+                if text_start < index {
+                    tokens.push(Token::Text(&html[text_start..index]));
+                }
+                tokens.push(token);
+                text_start = index + len;
+                len
+            })
+        };
+        match consumed {
+            Some(len) => {
+                if text_start < index {
+                    tokens.push(Token::Text(&html[text_start..index]));
+                }
+                index += len;
+                text_start = index;
+            }
+            None => index += 1,
+        }
+    }
+    if text_start < html.len() {
+        tokens.push(Token::Text(&html[text_start..]));
+    }
+    tokens
+}
+
+fn parse_tag(rest: &str) -> Option<(Token<'static>, usize)> {
+    let bytes = rest.as_bytes();
+    let closing = bytes.get(1) == Some(&b'/');
+    let name_start = if closing { 2 } else { 1 };
+    if !bytes.get(name_start)?.is_ascii_alphabetic() {
+        return None;
+    }
+    let mut index = name_start;
+    while index < bytes.len() && (bytes[index].is_ascii_alphanumeric() || bytes[index] == b'-') {
+        index += 1;
+    }
+    let name = rest[name_start..index].to_ascii_lowercase();
+
+    let mut attributes = Vec::new();
+    let mut self_closing = false;
+    loop {
+        while index < bytes.len() && bytes[index].is_ascii_whitespace() {
+            index += 1;
+        }
+        match bytes.get(index)? {
+            b'>' => {
+                index += 1;
+                break;
+            }
+            b'/' => {
+                self_closing = true;
+                index += 1;
+                continue;
+            }
+            _ => {}
+        }
+        let attr_start = index;
+        while index < bytes.len()
+            && !bytes[index].is_ascii_whitespace()
+            && !matches!(bytes[index], b'=' | b'>' | b'/')
+        {
+            index += 1;
+        }
+        let attr_name = rest[attr_start..index].to_ascii_lowercase();
+        while index < bytes.len() && bytes[index].is_ascii_whitespace() {
+            index += 1;
+        }
+        let mut value = String::new();
+        if bytes.get(index) == Some(&b'=') {
+            index += 1;
+            while index < bytes.len() && bytes[index].is_ascii_whitespace() {
+                index += 1;
+            }
+            match bytes.get(index)? {
+                quote @ (b'"' | b'\'') => {
+                    let end = rest[index + 1..].find(*quote as char)? + index + 1;
+                    value = decode_entities(&rest[index + 1..end]);
+                    index = end + 1;
+                }
+                _ => {
+                    let value_start = index;
+                    while index < bytes.len()
+                        && !bytes[index].is_ascii_whitespace()
+                        && bytes[index] != b'>'
+                    {
+                        index += 1;
+                    }
+                    value = decode_entities(&rest[value_start..index]);
+                }
+            }
+        }
+        if !attr_name.is_empty() {
+            attributes.push((attr_name, value));
+        }
+    }
+
+    let token = if closing {
+        Token::Close(name)
+    } else {
+        Token::Open {
+            name,
+            attributes,
+            self_closing,
+        }
+    };
+    Some((token, index))
+}
+
+/// Render one allowed opening tag with its permitted attributes.
+pub(crate) fn open_tag<'a>(
+    tag: &str,
+    attributes: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> String {
+    let mut out = format!("<{tag}");
+    for (name, value) in attributes {
+        if !is_allowed_attribute(tag, name) {
+            continue;
+        }
+        if matches!(name, "href" | "src") && !is_safe_url(value) {
+            continue;
+        }
+        out.push_str(&format!(" {name}=\"{}\"", escape_html(value.trim())));
+    }
+    if tag == "a" {
+        out.push_str(" rel=\"nofollow noopener\"");
+    }
+    out.push('>');
+    out
+}
+
+/// Sanitize a raw HTML fragment against the tag and attribute allowlist.
+///
+/// Disallowed tags are unwrapped, script-like tags are removed with their
+/// content, text is re-escaped and unbalanced tags are closed.
+pub fn sanitize_html_fragment(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut open: Vec<String> = Vec::new();
+    let mut skip_until: Option<String> = None;
+
+    for token in tokenize(html) {
+        if let Some(skipped) = &skip_until {
+            if matches!(&token, Token::Close(name) if name == skipped) {
+                skip_until = None;
+            }
+            continue;
+        }
+        match token {
+            Token::Text(text) => out.push_str(&escape_html(&decode_entities(text))),
+            Token::Open {
+                name,
+                attributes,
+                self_closing,
+            } => {
+                if is_dropped_tag(&name) {
+                    if !self_closing && !is_void_tag(&name) {
+                        skip_until = Some(name);
+                    }
+                    continue;
+                }
+                if !is_allowed_tag(&name) {
+                    continue;
+                }
+                out.push_str(&open_tag(
+                    &name,
+                    attributes
+                        .iter()
+                        .map(|(name, value)| (name.as_str(), value.as_str())),
+                ));
+                if !is_void_tag(&name) {
+                    if self_closing {
+                        out.push_str(&format!("</{name}>"));
+                    } else {
+                        open.push(name);
+                    }
+                }
+            }
+            Token::Close(name) => {
+                if let Some(position) = open.iter().rposition(|tag| *tag == name) {
+                    for tag in open.drain(position..).rev() {
+                        out.push_str(&format!("</{tag}>"));
+                    }
+                }
+            }
+        }
+    }
+    for tag in open.into_iter().rev() {
+        out.push_str(&format!("</{tag}>"));
+    }
+    out
+}
+
+/// Plain text of an HTML fragment. Block-level elements become line breaks;
+/// runs of whitespace inside a line collapse to one space.
+pub fn html_to_text(html: &str) -> String {
+    let mut raw = String::with_capacity(html.len());
+    let mut skip_until: Option<String> = None;
+    for token in tokenize(html) {
+        if let Some(skipped) = &skip_until {
+            if matches!(&token, Token::Close(name) if name == skipped) {
+                skip_until = None;
+            }
+            continue;
+        }
+        match token {
+            Token::Text(text) => raw.push_str(&decode_entities(text)),
+            Token::Open {
+                name, self_closing, ..
+            } => {
+                if is_dropped_tag(&name) && !self_closing && !is_void_tag(&name) {
+                    skip_until = Some(name);
+                } else if BLOCK_TAGS.contains(&name.as_str()) {
+                    raw.push('\n');
+                } else if matches!(name.as_str(), "td" | "th") {
+                    raw.push(' ');
+                }
+            }
+            Token::Close(name) => {
+                if BLOCK_TAGS.contains(&name.as_str()) {
+                    raw.push('\n');
+                }
+            }
+        }
+    }
+
+    let mut lines = Vec::new();
+    for line in raw.lines() {
+        let line = line
+            .split(|ch: char| ch.is_whitespace())
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        if !line.is_empty() {
+            lines.push(line);
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn safe_urls_allow_web_mail_and_relative_links_only() {
+        assert!(is_safe_url("https://example.com/a?b=c:d"));
+        assert!(is_safe_url("mailto:team@example.com"));
+        assert!(is_safe_url("/media/a.png"));
+        assert!(is_safe_url("docs/page#intro"));
+        assert!(!is_safe_url("javascript:alert(1)"));
+        assert!(!is_safe_url(" JavaScript:alert(1)"));
+        assert!(!is_safe_url("data:text/html;base64,xx"));
+        assert!(!is_safe_url("//evil.example.com"));
+        assert!(!is_safe_url("/\\evil.example.com"));
+        assert!(!is_safe_url("\\\\evil.example.com"));
+    }
+
+    #[test]
+    fn sanitizer_unwraps_unknown_tags_and_drops_scripts() {
+        let html = r#"<p onclick="x()">Hi <custom>there</custom><script>alert(1)</script>
+            <a href="javascript:alert(1)">bad</a> <a href="/ok" target="_blank">ok</a>
+            <img src="/media/a.png" alt="A &amp; B" onerror="x()"><b>open"#;
+        assert_eq!(
+            sanitize_html_fragment(html),
+            "<p>Hi there\n            <a rel=\"nofollow noopener\">bad</a> \
+             <a href=\"/ok\" rel=\"nofollow noopener\">ok</a>\n            \
+             <img src=\"/media/a.png\" alt=\"A &amp; B\"><b>open</b></p>"
+        );
+        assert_eq!(sanitize_html_fragment("1 < 2 &lt; 3"), "1 &lt; 2 &lt; 3");
+    }
+
+    #[test]
+    fn text_extraction_breaks_blocks_and_decodes_entities() {
+        assert_eq!(
+            html_to_text("<h1>Title</h1><p>Fish &amp;   chips<br>to go</p><style>p{}</style>"),
+            "Title\nFish & chips\nto go"
+        );
+    }
+
+    /// Hostile fragments from common sanitizer bypasses.
+    const HOSTILE: &[&str] = &[
+        // Malformed and nested tags.
+        "<scr<script>ipt>alert(1)</script>",
+        "<<script>alert(1)//<</script>",
+        "<script>alert(1)",
+        "<script >alert(1)</script ><p>after</p>",
+        "<SCRIPT SRC=//evil.test/x.js></SCRIPT>",
+        "<p><script>alert(1)</p></script><p>x</p>",
+        "<textarea><p title=\"</textarea><img src=x onerror=alert(1)>\"></textarea>",
+        "<style><img src=x onerror=alert(1)></style>",
+        "<noscript><p title=\"</noscript><img src=x onerror=alert(1)>\"></noscript>",
+        "<xmp><img src=x onerror=alert(1)></xmp>",
+        "<a href=\"/ok\"<img src=x onerror=alert(1)>",
+        "<img src=\"/a.png\" onerror=alert(1)//>",
+        "<img/src=\"/a.png\"/onerror=alert(1)>",
+        "<p/onmouseover=alert(1)>hover</p>",
+        "<b><i><u>unclosed",
+        "</p></div><p>stray closes",
+        // Unquoted and backtick-quoted attributes.
+        "<a href=javascript:alert(1)>x</a>",
+        "<a href=`javascript:alert(1)`>x</a>",
+        "<img src=`x`onerror=`alert(1)`>",
+        "<a title=x onclick=alert(1) href=/ok>x</a>",
+        "<a href='/ok' title='\"><script>alert(1)</script>'>x</a>",
+        // Entity-encoded and whitespace-split schemes.
+        "<a href=\"&#106;avascript:alert(1)\">x</a>",
+        "<a href=\"&#x6A;&#x61;&#x76;&#x61;script:alert(1)\">x</a>",
+        "<a href=\"&#0000106;&#0000097;vascript:alert(1)\">x</a>",
+        "<a href=\"javascript&#58;alert(1)\">x</a>",
+        "<a href=\"javascript&#x3A;alert(1)\">x</a>",
+        "<a href=\"javascript&colon;alert(1)\">x</a>",
+        "<a href=\"java\tscript:alert(1)\">x</a>",
+        "<a href=\"java&#9;script:alert(1)\">x</a>",
+        "<a href=\"java&#x0A;script:alert(1)\">x</a>",
+        "<a href=\" \n javascript:alert(1)\">x</a>",
+        "<a href=\"&#1;javascript:alert(1)\">x</a>",
+        "<a href=\"JaVaScRiPt:alert(1)\">x</a>",
+        "<a href=\"vbscript:msgbox(1)\">x</a>",
+        "<a href=\"data:text/html;base64,PHNjcmlwdD5hbGVydCgxKTwvc2NyaXB0Pg==\">x</a>",
+        "<img src=\"data:image/svg+xml,<svg onload=alert(1)>\">",
+        "<a href=\"//evil.test\">x</a>",
+        "<a href=\"/\\evil.test\">x</a>",
+        "<a href=\"\\\\evil.test\">x</a>",
+        // SVG and MathML namespaces.
+        "<svg><script>alert(1)</script></svg>",
+        "<svg/onload=alert(1)>",
+        "<svg><a xlink:href=\"javascript:alert(1)\"><text>x</text></a></svg>",
+        "<svg><style><img src=x onerror=alert(1)></style></svg>",
+        "<math><mtext><table><mglyph><style><img src=x onerror=alert(1)>",
+        "<math><maction actiontype=statusline xlink:href=javascript:alert(1)>x</maction></math>",
+        "<svg><svg></svg><script>alert(1)</script></svg>",
+        // Comment and CDATA tricks.
+        "<!--<script>alert(1)</script>-->",
+        "<!-- --!><img src=x onerror=alert(1)> -->",
+        "<!--><img src=x onerror=alert(1)>-->",
+        "<!---><img src=x onerror=alert(1)>-->",
+        "<![CDATA[<script>alert(1)</script>]]>",
+        "<![CDATA[><img src=x onerror=alert(1)>]]>",
+        "<?xml version=\"1.0\"?><img src=x onerror=alert(1)>",
+        "<!DOCTYPE html><img src=x onerror=alert(1)>",
+        "<p>unterminated <!-- comment <script>alert(1)</script>",
+        // Entity-encoded markup in text stays text.
+        "&lt;script&gt;alert(1)&lt;/script&gt;",
+        "&#60;img src=x onerror=alert(1)&#62;",
+    ];
+
+    /// Check that sanitizer output only contains allowlisted markup: every
+    /// tag and attribute is allowed, URLs are safe and sanitizing again
+    /// changes nothing.
+    fn assert_inert(input: &str, output: &str) {
+        for token in tokenize(output) {
+            match token {
+                Token::Open {
+                    name, attributes, ..
+                } => {
+                    assert!(is_allowed_tag(&name), "{input:?} kept <{name}>: {output}");
+                    for (attribute, value) in attributes {
+                        assert!(
+                            is_allowed_attribute(&name, &attribute) || attribute == "rel",
+                            "{input:?} kept {attribute} on <{name}>: {output}"
+                        );
+                        if matches!(attribute.as_str(), "href" | "src") {
+                            assert!(is_safe_url(&value), "{input:?} kept {value:?}: {output}");
+                        }
+                    }
+                }
+                Token::Close(name) => {
+                    assert!(is_allowed_tag(&name), "{input:?} kept </{name}>: {output}");
+                }
+                Token::Text(_) => {}
+            }
+        }
+        let lowered = output.to_ascii_lowercase();
+        assert!(
+            !lowered.contains("<script") && !lowered.contains("<img src=x"),
+            "{input:?} left live markup: {output}"
+        );
+        assert_eq!(
+            sanitize_html_fragment(output),
+            output,
+            "{input:?} is not stable under re-sanitizing"
+        );
+    }
+
+    #[test]
+    fn sanitizer_neutralizes_hostile_fragments() {
+        for input in HOSTILE {
+            assert_inert(input, &sanitize_html_fragment(input));
+        }
+    }
+
+    #[test]
+    fn sanitizer_refuses_encoded_and_split_schemes() {
+        for input in HOSTILE.iter().filter(|input| input.starts_with("<a href")) {
+            let output = sanitize_html_fragment(input);
+            assert!(
+                !output.contains("href") || output.contains("href=\"/ok\""),
+                "{input:?} kept a link: {output}"
+            );
+        }
+        assert_eq!(
+            sanitize_html_fragment("<svg><script>alert(1)</script></svg><p>after</p>"),
+            "<p>after</p>"
+        );
+        assert_eq!(
+            sanitize_html_fragment("<!-- x --><![CDATA[y]]><b>z</b>"),
+            "<b>z</b>"
+        );
+        assert_eq!(
+            sanitize_html_fragment("<a href=`/ok` title=`t`>x</a>"),
+            "<a href=\"`/ok`\" title=\"`t`\" rel=\"nofollow noopener\">x</a>",
+            "backticks are not quotes: the value stays inside double quotes"
+        );
+    }
+}
@@ -0,0 +1,1312 @@
+//! Markdown support for the content renderer.
+//!
+//! The parser covers the CommonMark subset editors actually produce: ATX and
+//! setext headings, fenced code, block quotes, nested lists, thematic breaks
+//! and paragraphs with hard breaks; inline code, emphasis, strike-through,
+//! links, images and autolinks. Raw HTML is not interpreted and renders as
+//! text.
+
+use serde_json::{json, Map, Value};
+
+use super::html::{escape_html, is_safe_url};
+use crate::rt_json::is_allowed_url;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Block {
+    Heading(u8, Vec<Inline>),
+    Paragraph(Vec<Inline>),
+    Code {
+        language: Option<String>,
+        text: String,
+    },
+    Quote(Vec<Block>),
+    List {
+        ordered: bool,
+        start: u64,
+        tight: bool,
+        items: Vec<Vec<Block>>,
+    },
+    Rule,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Inline {
+    Text(String),
+    Code(String),
+    Strong(Vec<Inline>),
+    Emphasis(Vec<Inline>),
+    Strike(Vec<Inline>),
+    Link { href: String, content: Vec<Inline> },
+    Image { src: String, alt: String },
+    HardBreak,
+}
+
+// ── Block parsing ─────────────────────────────────────────────────────────────
+
+pub(crate) fn parse(markdown: &str) -> Vec<Block> {
+    let normalized = markdown.replace("\r\n", "\n").replace('\t', "    ");
+    let lines: Vec<&str> = normalized.lines().collect();
+    parse_blocks(&lines)
+}
+
+fn parse_blocks(lines: &[&str]) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut index = 0;
+    while index < lines.len() {
+        let line = lines[index];
+        if line.trim().is_empty() {
+            index += 1;
+            continue;
+        }
+        if let Some((fence, language)) = fence_open(line) {
+            let mut text = Vec::new();
+            index += 1;
+            while index < lines.len() && !is_fence_close(lines[index], &fence) {
+                text.push(lines[index]);
+                index += 1;
+            }
+            index += 1;
+            blocks.push(Block::Code {
+                language,
+                text: text.join("\n"),
+            });
+            continue;
+        }
+        if let Some((level, text)) = atx_heading(line) {
+            blocks.push(Block::Heading(level, parse_inlines(text)));
+            index += 1;
+            continue;
+        }
+        if is_rule(line) {
+            blocks.push(Block::Rule);
+            index += 1;
+            continue;
+        }
+        if quote_content(line).is_some() {
+            let mut inner = Vec::new();
+            while index < lines.len() {
+                match quote_content(lines[index]) {
+                    Some(content) => inner.push(content),
+                    None => break,
+                }
+                index += 1;
+            }
+            blocks.push(Block::Quote(parse_blocks(&inner)));
+            continue;
+        }
+        if let Some(marker) = list_marker(line) {
+            let (block, consumed) = parse_list(&lines[index..], marker);
+            blocks.push(block);
+            index += consumed;
+            continue;
+        }
+
+        let mut paragraph = vec![line.trim_start()];
+        index += 1;
+        let mut heading = None;
+        while index < lines.len() {
+            let next = lines[index];
+            if next.trim().is_empty() {
+                break;
+            }
+            if let Some(level) = setext_underline(next) {
+                heading = Some(level);
+                index += 1;
+                break;
+            }
+            if fence_open(next).is_some()
+                || atx_heading(next).is_some()
+                || is_rule(next)
+                || quote_content(next).is_some()
+                || list_marker(next).is_some_and(|marker| marker.interrupts_paragraph())
+            {
+                break;
+            }
+            paragraph.push(next.trim_start());
+            index += 1;
+        }
+        let text = paragraph.join("\n");
+        blocks.push(match heading {
+            Some(level) => Block::Heading(level, parse_inlines(text.trim())),
+            None => Block::Paragraph(parse_inlines(text.trim_end_matches([' ', '\\']))),
+        });
+    }
+    blocks
+}
+
+fn leading_spaces(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+fn fence_open(line: &str) -> Option<(String, Option<String>)> {
+    if leading_spaces(line) > 3 {
+        return None;
+    }
+    let trimmed = line.trim_start();
+    let fence_char = trimmed
+        .chars()
+        .next()
+        .filter(|ch| *ch == '`' || *ch == '~')?;
+    let fence_len = trimmed.chars().take_while(|ch| *ch == fence_char).count();
+    if fence_len < 3 {
+        return None;
+    }
+    let info = trimmed[fence_len..].trim();
+    if fence_char == '`' && info.contains('`') {
+        return None;
+    }
+    let language = info
+        .split_whitespace()
+        .next()
+        .map(str::to_string)
+        .filter(|language| {
+            language
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '+' | '.'))
+        });
+    Some((trimmed[..fence_len].to_string(), language))
+}
+
+fn is_fence_close(line: &str, fence: &str) -> bool {
+    let trimmed = line.trim();
+    let fence_char = fence.chars().next().unwrap_or('`');
+    leading_spaces(line) <= 3
+        && trimmed.len() >= fence.len()
+        && trimmed.chars().all(|ch| ch == fence_char)
+}
+
+fn atx_heading(line: &str) -> Option<(u8, &str)> {
+    if leading_spaces(line) > 3 {
+        return None;
+    }
+    let trimmed = line.trim();
+    let level = trimmed.chars().take_while(|ch| *ch == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let rest = &trimmed[level..];
+    if !rest.is_empty() && !rest.starts_with(' ') {
+        return None;
+    }
+    let mut text = rest.trim();
+    let without_closing = text.trim_end_matches('#');
+    if without_closing.is_empty() || without_closing.ends_with(' ') {
+        text = without_closing.trim_end();
+    }
+    Some((level as u8, text))
+}
+
+fn setext_underline(line: &str) -> Option<u8> {
+    let trimmed = line.trim();
+    if leading_spaces(line) > 3 || trimmed.is_empty() {
+        return None;
+    }
+    if trimmed.chars().all(|ch| ch == '=') {
+        Some(1)
+    } else if trimmed.chars().all(|ch| ch == '-') {
+        Some(2)
+    } else {
+        None
+    }
+}
+
+fn is_rule(line: &str) -> bool {
+    if leading_spaces(line) > 3 {
+        return false;
+    }
+    let compact: Vec<char> = line.chars().filter(|ch| !ch.is_whitespace()).collect();
+    compact.len() >= 3
+        && matches!(compact[0], '-' | '*' | '_')
+        && compact.iter().all(|ch| *ch == compact[0])
+}
+
+fn quote_content(line: &str) -> Option<&str> {
+    if leading_spaces(line) > 3 {
+        return None;
+    }
+    let rest = line.trim_start().strip_prefix('>')?;
+    Some(rest.strip_prefix(' ').unwrap_or(rest))
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ListMarker {
+    ordered: bool,
+    /// Bullet character or ordered delimiter (`.` or `)`).
+    delimiter: char,
+    number: u64,
+    /// Column where the item content starts.
+    content_indent: usize,
+    empty: bool,
+}
+
+impl ListMarker {
+    fn same_list(&self, other: &ListMarker) -> bool {
+        self.ordered == other.ordered && self.delimiter == other.delimiter
+    }
+
+    /// Only non-empty bullets and lists starting at 1 may interrupt a
+    /// paragraph, as in CommonMark.
+    fn interrupts_paragraph(&self) -> bool {
+        !self.empty && (!self.ordered || self.number == 1)
+    }
+}
+
+fn list_marker(line: &str) -> Option<ListMarker> {
+    let indent = leading_spaces(line);
+    if indent > 3 {
+        return None;
+    }
+    let trimmed = &line[indent..];
+    let (ordered, delimiter, number, marker_len) = match trimmed.chars().next()? {
+        ch @ ('-' | '*' | '+') => (false, ch, 0, 1),
+        ch if ch.is_ascii_digit() => {
+            let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+            if digits > 9 {
+                return None;
+            }
+            let delimiter = trimmed[digits..].chars().next()?;
+            if delimiter != '.' && delimiter != ')' {
+                return None;
+            }
+            (true, delimiter, trimmed[..digits].parse().ok()?, digits + 1)
+        }
+        _ => return None,
+    };
+    let after = &trimmed[marker_len..];
+    if after.is_empty() {
+        return Some(ListMarker {
+            ordered,
+            delimiter,
+            number,
+            content_indent: indent + marker_len + 1,
+            empty: true,
+        });
+    }
+    if !after.starts_with(' ') {
+        return None;
+    }
+    let spaces = leading_spaces(after).min(4);
+    Some(ListMarker {
+        ordered,
+        delimiter,
+        number,
+        content_indent: indent + marker_len + spaces,
+        empty: after.trim().is_empty(),
+    })
+}
+
+fn parse_list(lines: &[&str], first: ListMarker) -> (Block, usize) {
+    let mut items: Vec<Vec<String>> = Vec::new();
+    let mut current: Vec<String> = Vec::new();
+    let mut content_indent = first.content_indent;
+    let mut tight = true;
+    let mut pending_blank = false;
+    let mut index = 0;
+
+    while index < lines.len() {
+        let line = lines[index];
+        if line.trim().is_empty() {
+            pending_blank = true;
+            current.push(String::new());
+            index += 1;
+            continue;
+        }
+        let marker = list_marker(line).filter(|_| index == 0 || !is_rule(line));
+        let starts_item = index == 0
+            || marker.is_some_and(|marker| {
+                marker.same_list(&first) && leading_spaces(line) < content_indent
+            });
+
+        if starts_item {
+            let marker = marker.unwrap_or(first);
+            if index > 0 {
+                if pending_blank {
+                    tight = false;
+                }
+                items.push(std::mem::take(&mut current));
+            }
+            pending_blank = false;
+            content_indent = marker.content_indent;
+            current.push(if marker.empty {
+                String::new()
+            } else {
+                line[marker.content_indent..].to_string()
+            });
+            index += 1;
+            continue;
+        }
+
+        if leading_spaces(line) >= content_indent {
+            if pending_blank && current.iter().any(|line| !line.is_empty()) {
+                let nested_block = list_marker(&line[content_indent..]).is_some();
+                if !nested_block {
+                    tight = false;
+                }
+            }
+            pending_blank = false;
+            current.push(line[content_indent..].to_string());
+            index += 1;
+            continue;
+        }
+
+        // Lazy paragraph continuation.
+        let last_is_text = current.last().is_some_and(|line| !line.trim().is_empty());
+        if !pending_blank
+            && last_is_text
+            && fence_open(line).is_none()
+            && atx_heading(line).is_none()
+            && !is_rule(line)
+            && quote_content(line).is_none()
+            && list_marker(line).is_none()
+        {
+            current.push(line.trim_start().to_string());
+            index += 1;
+            continue;
+        }
+        break;
+    }
+    while current.last().is_some_and(String::is_empty) {
+        current.pop();
+        index -= 1;
+    }
+    items.push(current);
+
+    let items = items
+        .iter()
+        .map(|lines| {
+            let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+            parse_blocks(&lines)
+        })
+        .collect();
+    (
+        Block::List {
+            ordered: first.ordered,
+            start: first.number,
+            tight,
+            items,
+        },
+        index.max(1),
+    )
+}
+
+// ── Inline parsing ────────────────────────────────────────────────────────────
+
+pub(crate) fn parse_inlines(text: &str) -> Vec<Inline> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut parser = InlineParser {
+        chars: &chars,
+        out: Vec::new(),
+        buffer: String::new(),
+    };
+    parser.run(0, chars.len());
+    parser.finish()
+}
+
+struct InlineParser<'a> {
+    chars: &'a [char],
+    out: Vec<Inline>,
+    buffer: String,
+}
+
+impl InlineParser<'_> {
+    fn flush(&mut self) {
+        if !self.buffer.is_empty() {
+            self.out
+                .push(Inline::Text(std::mem::take(&mut self.buffer)));
+        }
+    }
+
+    fn push(&mut self, inline: Inline) {
+        self.flush();
+        self.out.push(inline);
+    }
+
+    fn finish(mut self) -> Vec<Inline> {
+        self.flush();
+        self.out
+    }
+
+    fn nested(&self, start: usize, end: usize) -> Vec<Inline> {
+        let mut parser = InlineParser {
+            chars: self.chars,
+            out: Vec::new(),
+            buffer: String::new(),
+        };
+        parser.run(start, end);
+        parser.finish()
+    }
+
+    fn run(&mut self, start: usize, end: usize) {
+        let chars = self.chars;
+        let mut index = start;
+        while index < end {
+            let ch = chars[index];
+            match ch {
+                '\\' if index + 1 < end => {
+                    let next = chars[index + 1];
+                    if next == '\n' {
+                        self.push(Inline::HardBreak);
+                        index += 2;
+                        continue;
+                    }
+                    if next.is_ascii_punctuation() {
+                        self.buffer.push(next);
+                        index += 2;
+                        continue;
+                    }
+                    self.buffer.push(ch);
+                    index += 1;
+                }
+                '\n' => {
+                    let trailing = self.buffer.len() - self.buffer.trim_end_matches(' ').len();
+                    self.buffer.truncate(self.buffer.len() - trailing);
+                    if trailing >= 2 {
+                        self.push(Inline::HardBreak);
+                    } else {
+                        self.buffer.push(' ');
+                    }
+                    index += 1;
+                    while index < end && chars[index] == ' ' {
+                        index += 1;
+                    }
+                }
+                '`' => {
+                    let run = count_run(chars, index, end, '`');
+                    match find_code_close(chars, index + run, end, run) {
+                        Some(close) => {
+                            let code: String = chars[index + run..close].iter().collect();
+                            let code = code.replace('\n', " ");
+                            let code = if code.len() > 2
+                                && code.starts_with(' ')
+                                && code.ends_with(' ')
+                                && !code.trim().is_empty()
+                            {
+                                code[1..code.len() - 1].to_string()
+                            } else {
+                                code
+                            };
+                            self.push(Inline::Code(code));
+                            index = close + run;
+                        }
+                        None => {
+                            self.buffer.extend(&chars[index..index + run]);
+                            index += run;
+                        }
+                    }
+                }
+                '!' if index + 1 < end && chars[index + 1] == '[' => {
+                    match self.link_parts(index + 1, end) {
+                        Some((label_end, destination, next)) => {
+                            let alt = plain_text(&self.nested(index + 2, label_end));
+                            self.push(Inline::Image {
+                                src: destination,
+                                alt,
+                            });
+                            index = next;
+                        }
+                        None => {
+                            self.buffer.push(ch);
+                            index += 1;
+                        }
+                    }
+                }
+                '[' => match self.link_parts(index, end) {
+                    Some((label_end, destination, next)) => {
+                        let content = self.nested(index + 1, label_end);
+                        self.push(Inline::Link {
+                            href: destination,
+                            content,
+                        });
+                        index = next;
+                    }
+                    None => {
+                        self.buffer.push(ch);
+                        index += 1;
+                    }
+                },
+                '<' => match autolink(chars, index, end) {
+                    Some((href, text, next)) => {
+                        self.push(Inline::Link {
+                            href,
+                            content: vec![Inline::Text(text)],
+                        });
+                        index = next;
+                    }
+                    None => {
+                        self.buffer.push(ch);
+                        index += 1;
+                    }
+                },
+                '*' | '_' | '~' => {
+                    let run = count_run(chars, index, end, ch);
+                    let width = match (ch, run) {
+                        ('~', 2) => 2,
+                        ('~', _) => 0,
+                        (_, 1) => 1,
+                        (_, _) => 2,
+                    };
+                    let opens = width > 0
+                        && index + width < end
+                        && !chars[index + width].is_whitespace()
+                        && (ch != '_' || index == 0 || !chars[index - 1].is_alphanumeric());
+                    let close = if opens {
+                        find_delimiter_close(chars, index + width, end, ch, width)
+                    } else {
+                        None
+                    };
+                    match close {
+                        Some(close) => {
+                            let content = self.nested(index + width, close);
+                            self.push(match (ch, width) {
+                                ('~', _) => Inline::Strike(content),
+                                (_, 2) => Inline::Strong(content),
+                                _ => Inline::Emphasis(content),
+                            });
+                            index = close + width;
+                        }
+                        None => {
+                            self.buffer.extend(&chars[index..index + run]);
+                            index += run;
+                        }
+                    }
+                }
+                _ => {
+                    self.buffer.push(ch);
+                    index += 1;
+                }
+            }
+        }
+    }
+
+    /// Parse `[label](destination "title")` starting at `[`. Returns the
+    /// label end, the destination and the index after the closing paren.
+    fn link_parts(&self, open: usize, end: usize) -> Option<(usize, String, usize)> {
+        let chars = self.chars;
+        let mut depth = 0usize;
+        let mut index = open;
+        let label_end = loop {
+            if index >= end {
+                return None;
+            }
+            match chars[index] {
+                '\\' => index += 1,
+                '`' => {
+                    let run = count_run(chars, index, end, '`');
+                    if let Some(close) = find_code_close(chars, index + run, end, run) {
+                        index = close + run - 1;
+                    }
+                }
+                '[' => depth += 1,
+                ']' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break index;
+                    }
+                }
+                _ => {}
+            }
+            index += 1;
+        };
+
+        let mut index = label_end + 1;
+        if chars.get(index) != Some(&'(') || index >= end {
+            return None;
+        }
+        index += 1;
+        while index < end && chars[index] == ' ' {
+            index += 1;
+        }
+        let mut destination = String::new();
+        if chars.get(index) == Some(&'<') {
+            index += 1;
+            while index < end && chars[index] != '>' {
+                if chars[index] == '\n' {
+                    return None;
+                }
+                destination.push(chars[index]);
+                index += 1;
+            }
+            index += 1;
+        } else {
+            let mut parens = 0usize;
+            while index < end && !chars[index].is_whitespace() {
+                match chars[index] {
+                    '(' => parens += 1,
+                    ')' if parens == 0 => break,
+                    ')' => parens -= 1,
+                    '\\' if index + 1 < end && chars[index + 1].is_ascii_punctuation() => {
+                        index += 1;
+                    }
+                    _ => {}
+                }
+                destination.push(chars[index]);
+                index += 1;
+            }
+        }
+        while index < end && chars[index].is_whitespace() {
+            index += 1;
+        }
+        if let Some(&quote @ ('"' | '\'' | '(')) = chars.get(index) {
+            let closing = if quote == '(' { ')' } else { quote };
+            index += 1;
+            while index < end && chars[index] != closing {
+                index += 1;
+            }
+            index += 1;
+            while index < end && chars[index].is_whitespace() {
+                index += 1;
+            }
+        }
+        if index >= end || chars[index] != ')' {
+            return None;
+        }
+        Some((label_end, destination, index + 1))
+    }
+}
+
+fn count_run(chars: &[char], start: usize, end: usize, ch: char) -> usize {
+    chars[start..end].iter().take_while(|c| **c == ch).count()
+}
+
+fn find_code_close(chars: &[char], from: usize, end: usize, run: usize) -> Option<usize> {
+    let mut index = from;
+    while index < end {
+        if chars[index] == '`' {
+            let found = count_run(chars, index, end, '`');
+            if found == run {
+                return Some(index);
+            }
+            index += found;
+        } else {
+            index += 1;
+        }
+    }
+    None
+}
+
+/// Find the closing emphasis delimiter of `width` characters. Runs of other
+/// lengths belong to nested emphasis and are skipped.
+fn find_delimiter_close(
+    chars: &[char],
+    from: usize,
+    end: usize,
+    ch: char,
+    width: usize,
+) -> Option<usize> {
+    let mut index = from;
+    while index < end {
+        match chars[index] {
+            '\\' => index += 2,
+            '`' => {
+                let run = count_run(chars, index, end, '`');
+                index = find_code_close(chars, index + run, end, run)
+                    .map_or(index + run, |close| close + run);
+            }
+            c if c == ch => {
+                let run = count_run(chars, index, end, ch);
+                let closes = !chars[index - 1].is_whitespace()
+                    && (ch != '_' || index + run >= end || !chars[index + run].is_alphanumeric());
+                // A run of three closes the inner and the outer emphasis.
+                if closes && index > from && (run == width || run == 3) {
+                    return Some(index + run - width);
+                }
+                index += run;
+            }
+            _ => index += 1,
+        }
+    }
+    None
+}
+
+fn autolink(chars: &[char], start: usize, end: usize) -> Option<(String, String, usize)> {
+    let close = (start + 1..end).find(|index| chars[*index] == '>')?;
+    let inner: String = chars[start + 1..close].iter().collect();
+    if inner.is_empty() || inner.contains(char::is_whitespace) || inner.contains('<') {
+        return None;
+    }
+    let href = if let Some((scheme, _)) = inner.split_once(':') {
+        if scheme.len() < 2 || !scheme.chars().all(|ch| ch.is_ascii_alphanumeric()) {
+            return None;
+        }
+        inner.clone()
+    } else if inner.contains('@') && !inner.starts_with('@') && !inner.ends_with('@') {
+        format!("mailto:{inner}")
+    } else {
+        return None;
+    };
+    Some((href, inner, close + 1))
+}
+
+pub(crate) fn plain_text(inlines: &[Inline]) -> String {
+    let mut out = String::new();
+    for inline in inlines {
+        match inline {
+            Inline::Text(text) | Inline::Code(text) => out.push_str(text),
+            Inline::Strong(content) | Inline::Emphasis(content) | Inline::Strike(content) => {
+                out.push_str(&plain_text(content))
+            }
+            Inline::Link { content, .. } => out.push_str(&plain_text(content)),
+            Inline::Image { alt, .. } => out.push_str(alt),
+            Inline::HardBreak => out.push('\n'),
+        }
+    }
+    out
+}
+
+// ── HTML output ───────────────────────────────────────────────────────────────
+
+pub(crate) fn to_html(blocks: &[Block]) -> String {
+    let mut out = String::new();
+    for block in blocks {
+        block_html(block, false, &mut out);
+    }
+    out
+}
+
+fn block_html(block: &Block, tight: bool, out: &mut String) {
+    match block {
+        Block::Heading(level, content) => {
+            out.push_str(&format!("<h{level}>{}</h{level}>", inlines_html(content)));
+        }
+        Block::Paragraph(content) if tight => out.push_str(&inlines_html(content)),
+        Block::Paragraph(content) => {
+            out.push_str(&format!("<p>{}</p>", inlines_html(content)));
+        }
+        Block::Code { language, text } => {
+            let class = language
+                .as_deref()
+                .map(|language| format!(" class=\"language-{}\"", escape_html(language)))
+                .unwrap_or_default();
+            out.push_str(&format!(
+                "<pre><code{class}>{}</code></pre>",
+                escape_html(text)
+            ));
+        }
+        Block::Quote(blocks) => {
+            out.push_str("<blockquote>");
+            for block in blocks {
+                block_html(block, false, out);
+            }
+            out.push_str("</blockquote>");
+        }
+        Block::List {
+            ordered,
+            start,
+            tight,
+            items,
+        } => {
+            let tag = if *ordered { "ol" } else { "ul" };
+            if *ordered && *start != 1 {
+                out.push_str(&format!("<ol start=\"{start}\">"));
+            } else {
+                out.push_str(&format!("<{tag}>"));
+            }
+            for item in items {
+                out.push_str("<li>");
+                for block in item {
+                    block_html(block, *tight, out);
+                }
+                out.push_str("</li>");
+            }
+            out.push_str(&format!("</{tag}>"));
+        }
+        Block::Rule => out.push_str("<hr>"),
+    }
+}
+
+fn inlines_html(inlines: &[Inline]) -> String {
+    let mut out = String::new();
+    for inline in inlines {
+        match inline {
+            Inline::Text(text) => out.push_str(&escape_html(text)),
+            Inline::Code(code) => out.push_str(&format!("<code>{}</code>", escape_html(code))),
+            Inline::Strong(content) => {
+                out.push_str(&format!("<strong>{}</strong>", inlines_html(content)))
+            }
+            Inline::Emphasis(content) => {
+                out.push_str(&format!("<em>{}</em>", inlines_html(content)))
+            }
+            Inline::Strike(content) => out.push_str(&format!("<s>{}</s>", inlines_html(content))),
+            Inline::Link { href, content } if is_safe_url(href) => out.push_str(&format!(
+                "<a href=\"{}\" rel=\"nofollow noopener\">{}</a>",
+                escape_html(href),
+                inlines_html(content)
+            )),
+            Inline::Link { content, .. } => out.push_str(&inlines_html(content)),
+            Inline::Image { src, alt } if is_safe_url(src) => out.push_str(&format!(
+                "<img src=\"{}\" alt=\"{}\">",
+                escape_html(src),
+                escape_html(alt)
+            )),
+            Inline::Image { alt, .. } => out.push_str(&escape_html(alt)),
+            Inline::HardBreak => out.push_str("<br>"),
+        }
+    }
+    out
+}
+
+// ── rt_json output ────────────────────────────────────────────────────────────
+
+/// Build an `rt_json_v1` document from parsed markdown. Links and images the
+/// rt_json policy would reject (relative or non-web URLs) degrade to text.
+pub(crate) fn to_rt_json_doc(blocks: &[Block]) -> Value {
+    json!({
+        "type": "doc",
+        "content": blocks.iter().map(block_node).collect::<Vec<_>>(),
+    })
+}
+
+fn block_node(block: &Block) -> Value {
+    match block {
+        Block::Heading(level, content) => json!({
+            "type": "heading",
+            "attrs": { "level": level },
+            "content": inline_nodes(content),
+        }),
+        Block::Paragraph(content) => json!({
+            "type": "paragraph",
+            "content": inline_nodes(content),
+        }),
+        Block::Code { text, .. } => {
+            let content = if text.is_empty() {
+                Vec::new()
+            } else {
+                vec![json!({ "type": "text", "text": text })]
+            };
+            json!({ "type": "code_block", "content": content })
+        }
+        Block::Quote(blocks) => json!({
+            "type": "blockquote",
+            "content": blocks.iter().map(block_node).collect::<Vec<_>>(),
+        }),
+        Block::List { ordered, items, .. } => json!({
+            "type": if *ordered { "ordered_list" } else { "bullet_list" },
+            "content": items
+                .iter()
+                .map(|item| json!({
+                    "type": "list_item",
+                    "content": item.iter().map(block_node).collect::<Vec<_>>(),
+                }))
+                .collect::<Vec<_>>(),
+        }),
+        Block::Rule => json!({ "type": "horizontal_rule" }),
+    }
+}
+
+fn inline_nodes(inlines: &[Inline]) -> Vec<Value> {
+    let mut nodes = Vec::new();
+    collect_inline_nodes(inlines, &[], &mut nodes);
+
+    // Merge neighbouring text nodes that carry the same marks.
+    let mut merged: Vec<Value> = Vec::new();
+    for node in nodes {
+        if let (Some(previous), Some(text)) =
+            (merged.last_mut(), node.get("text").and_then(Value::as_str))
+        {
+            if previous["type"] == "text" && previous.get("marks") == node.get("marks") {
+                let joined = format!("{}{text}", previous["text"].as_str().unwrap_or_default());
+                previous["text"] = Value::String(joined);
+                continue;
+            }
+        }
+        merged.push(node);
+    }
+    merged
+}
+
+fn collect_inline_nodes(inlines: &[Inline], marks: &[Value], nodes: &mut Vec<Value>) {
+    let with_mark = |mark: Value| {
+        let mut marks = marks.to_vec();
+        marks.push(mark);
+        marks
+    };
+    for inline in inlines {
+        match inline {
+            Inline::Text(text) => push_text(nodes, text, marks),
+            Inline::Code(code) => push_text(nodes, code, &with_mark(json!({ "type": "code" }))),
+            Inline::Strong(content) => {
+                collect_inline_nodes(content, &with_mark(json!({ "type": "bold" })), nodes)
+            }
+            Inline::Emphasis(content) => {
+                collect_inline_nodes(content, &with_mark(json!({ "type": "italic" })), nodes)
+            }
+            Inline::Strike(content) => {
+                collect_inline_nodes(content, &with_mark(json!({ "type": "strike" })), nodes)
+            }
+            Inline::Link { href, content } if is_allowed_url(href, true) => {
+                let mark = json!({ "type": "link", "attrs": { "href": href } });
+                collect_inline_nodes(content, &with_mark(mark), nodes)
+            }
+            Inline::Link { content, .. } => collect_inline_nodes(content, marks, nodes),
+            Inline::Image { src, .. } if is_allowed_url(src, false) => {
+                nodes.push(json!({ "type": "image", "attrs": { "src": src } }))
+            }
+            Inline::Image { alt, .. } => push_text(nodes, alt, marks),
+            Inline::HardBreak => nodes.push(json!({ "type": "hard_break" })),
+        }
+    }
+}
+
+fn push_text(nodes: &mut Vec<Value>, text: &str, marks: &[Value]) {
+    if text.is_empty() {
+        return;
+    }
+    let mut node = Map::new();
+    node.insert("type".to_string(), json!("text"));
+    node.insert("text".to_string(), json!(text));
+    if !marks.is_empty() {
+        node.insert("marks".to_string(), Value::Array(marks.to_vec()));
+    }
+    nodes.push(Value::Object(node));
+}
+
+// ── rt_json → markdown ────────────────────────────────────────────────────────
+
+/// Serialize an `rt_json_v1` payload (or a bare `doc` node) as markdown.
+pub fn rt_json_to_markdown(payload: &Value) -> String {
+    let doc = payload.get("doc").unwrap_or(payload);
+    blocks_markdown(children(doc))
+}
+
+fn children(node: &Value) -> &[Value] {
+    node.get("content")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+fn blocks_markdown(nodes: &[Value]) -> String {
+    nodes
+        .iter()
+        .map(block_markdown)
+        .filter(|block| !block.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn block_markdown(node: &Value) -> String {
+    match node.get("type").and_then(Value::as_str).unwrap_or_default() {
+        "paragraph" => inline_markdown(children(node)),
+        "heading" => {
+            let level = node
+                .get("attrs")
+                .and_then(|attrs| attrs.get("level"))
+                .and_then(Value::as_u64)
+                .unwrap_or(1)
+                .clamp(1, 6) as usize;
+            format!("{} {}", "#".repeat(level), inline_markdown(children(node)))
+        }
+        "code_block" => {
+            let text: String = children(node)
+                .iter()
+                .filter_map(|child| child.get("text").and_then(Value::as_str))
+                .collect();
+            let fence = "`".repeat(longest_run(&text, '`').max(2) + 1);
+            format!("{fence}\n{text}\n{fence}")
+        }
+        "blockquote" => prefix_lines(&blocks_markdown(children(node)), "> ", "> ")
+            .lines()
+            .map(str::trim_end)
+            .collect::<Vec<_>>()
+            .join("\n"),
+        list @ ("bullet_list" | "ordered_list") => children(node)
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let marker = if list == "ordered_list" {
+                    format!("{}. ", index + 1)
+                } else {
+                    "- ".to_string()
+                };
+                let indent = " ".repeat(marker.len());
+                let body = blocks_markdown(children(item));
+                let body = prefix_lines(&body, &marker, &indent);
+                body.lines()
+                    .map(str::trim_end)
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        "horizontal_rule" => "---".to_string(),
+        "image" | "embed" | "mention" | "text" | "hard_break" => {
+            inline_markdown(std::slice::from_ref(node))
+        }
+        _ => blocks_markdown(children(node)),
+    }
+}
+
+fn prefix_lines(text: &str, first: &str, rest: &str) -> String {
+    text.lines()
+        .enumerate()
+        .map(|(index, line)| {
+            if line.is_empty() {
+                rest.trim_end().to_string()
+            } else if index == 0 {
+                format!("{first}{line}")
+            } else {
+                format!("{rest}{line}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn inline_markdown(nodes: &[Value]) -> String {
+    let mut out = String::new();
+    for node in nodes {
+        match node.get("type").and_then(Value::as_str).unwrap_or_default() {
+            "text" => {
+                let text = node.get("text").and_then(Value::as_str).unwrap_or_default();
+                let marks: Vec<&Value> = node
+                    .get("marks")
+                    .and_then(Value::as_array)
+                    .map(|marks| marks.iter().collect())
+                    .unwrap_or_default();
+                let has = |kind: &str| marks.iter().any(|mark| mark["type"] == kind);
+                let mut piece = if has("code") {
+                    let ticks = "`".repeat(longest_run(text, '`') + 1);
+                    let pad = if text.starts_with('`') || text.ends_with('`') {
+                        " "
+                    } else {
+                        ""
+                    };
+                    format!("{ticks}{pad}{text}{pad}{ticks}")
+                } else {
+                    escape_markdown(text, out.is_empty() || out.ends_with('\n'))
+                };
+                if has("bold") {
+                    piece = format!("**{piece}**");
+                }
+                if has("italic") {
+                    piece = format!("*{piece}*");
+                }
+                if has("strike") {
+                    piece = format!("~~{piece}~~");
+                }
+                if let Some(href) = marks
+                    .iter()
+                    .find(|mark| mark["type"] == "link")
+                    .and_then(|mark| mark["attrs"]["href"].as_str())
+                {
+                    piece = format!("[{piece}]({})", markdown_destination(href));
+                }
+                out.push_str(&piece);
+            }
+            "hard_break" => out.push_str("\\\n"),
+            "image" => {
+                if let Some(src) = node["attrs"]["src"].as_str() {
+                    out.push_str(&format!("![]({})", markdown_destination(src)));
+                }
+            }
+            "embed" => {
+                if let Some(url) = node["attrs"]["url"].as_str() {
+                    out.push_str(&format!("<{url}>"));
+                }
+            }
+            "mention" => {
+                if let Some(handle) = node["attrs"]["handle"].as_str() {
+                    out.push('@');
+                    out.push_str(handle);
+                }
+            }
+            _ => out.push_str(&inline_markdown(children(node))),
+        }
+    }
+    out
+}
+
+fn escape_markdown(text: &str, line_start: bool) -> String {
+    let mut out = String::with_capacity(text.len());
+    for (index, ch) in text.chars().enumerate() {
+        let at_start = line_start && index == 0;
+        if matches!(
+            ch,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '~' | '!'
+        ) || (at_start && matches!(ch, '#' | '-' | '+' | '='))
+        {
+            out.push('\\');
+        }
+        out.push(ch);
+    }
+    if line_start {
+        // `1. text` at the start of a paragraph would become a list.
+        let digits = out.chars().take_while(char::is_ascii_digit).count();
+        if digits > 0 && matches!(out[digits..].chars().next(), Some('.' | ')')) {
+            out.insert(digits, '\\');
+        }
+    }
+    out
+}
+
+fn markdown_destination(url: &str) -> String {
+    if url.contains([' ', '(', ')', '<', '>']) {
+        format!("<{}>", url.replace('<', "%3C").replace('>', "%3E"))
+    } else {
+        url.to_string()
+    }
+}
+
+fn longest_run(text: &str, ch: char) -> usize {
+    let mut longest = 0;
+    let mut current = 0;
+    for c in text.chars() {
+        if c == ch {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+    longest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::html::sanitize_html_fragment;
+    use super::*;
+
+    fn html(markdown: &str) -> String {
+        to_html(&parse(markdown))
+    }
+
+    #[test]
+    fn renders_block_structure() {
+        assert_eq!(
+            html("# Title #\n\nSub\n---\n\n> quoted\n> **bold**\n\n***\n\n```rust\nlet x = 1 < 2;\n```"),
+            "<h1>Title</h1><h2>Sub</h2><blockquote><p>quoted\n<strong>bold</strong></p></blockquote>\
+             <hr><pre><code class=\"language-rust\">let x = 1 &lt; 2;</code></pre>"
+                .replace('\n', " ")
+        );
+    }
+
+    #[test]
+    fn renders_nested_and_loose_lists() {
+        assert_eq!(
+            html("- one\n- two\n  1. inner\n  2. more\n- three"),
+            "<ul><li>one</li><li>two<ol><li>inner</li><li>more</li></ol></li><li>three</li></ul>"
+        );
+        assert_eq!(
+            html("3. a\n\n4. b"),
+            "<ol start=\"3\"><li><p>a</p></li><li><p>b</p></li></ol>"
+        );
+    }
+
+    #[test]
+    fn renders_inline_markup_and_refuses_unsafe_urls() {
+        assert_eq!(
+            html("A *b* __c__ ~~d~~ `e*f` [g](https://x.test/a_(b)) ![h](/media/h.png)  \nnext"),
+            "<p>A <em>b</em> <strong>c</strong> <s>d</s> <code>e*f</code> \
+             <a href=\"https://x.test/a_(b)\" rel=\"nofollow noopener\">g</a> \
+             <img src=\"/media/h.png\" alt=\"h\"><br>next</p>"
+        );
+        assert_eq!(
+            html("[x](javascript:alert(1)) <script>y</script> snake_case_name"),
+            "<p>x &lt;script&gt;y&lt;/script&gt; snake_case_name</p>"
+        );
+        assert_eq!(
+            html("<https://example.com> \\*not em\\*"),
+            "<p><a href=\"https://example.com\" rel=\"nofollow noopener\">https://example.com</a> *not em*</p>"
+        );
+    }
+
+    const HOSTILE: &[&str] = &[
+        "[x](javascript&colon;alert(1))",
+        "[x](jav&#x09;ascript:alert(1))",
+        "[x](&#106;avascript:alert(1))",
+        "[x]( javascript:alert(1))",
+        "[x](<javascript:alert(1)>)",
+        "[x](JaVaScRiPt:alert(1))",
+        "[x](java\\\nscript:alert(1))",
+        "[x](data:text/html;base64,PHNjcmlwdD4=)",
+        "[x](vbscript:msgbox(1))",
+        "[x](/\\evil.test)",
+        "[x](//evil.test)",
+        "![x](javascript:alert(1))",
+        "![x\"onerror=\"alert(1)](/a.png)",
+        "[x](/a\" onclick=\"alert(1))",
+        "<javascript:alert(1)>",
+        "<JAVASCRIPT:alert(1)>",
+        "<svg><script>alert(1)</script></svg>",
+        "<math><mtext><img src=x onerror=alert(1)></mtext></math>",
+        "<!-- <img src=x onerror=alert(1)> -->",
+        "<![CDATA[<img src=x onerror=alert(1)>]]>",
+        "**<img src=x onerror=alert(1)>**",
+        "`<script>` <b onclick=alert(1)>x</b>",
+        "[<img src=x onerror=alert(1)>](https://ok.test)",
+        "[[x](javascript:alert(1))](https://ok.test)",
+    ];
+
+    #[test]
+    fn renders_hostile_markdown_inert() {
+        for input in HOSTILE {
+            let output = html(input);
+            let lowered = output.to_ascii_lowercase();
+            for live in [
+                "<script",
+                "<svg",
+                "<math",
+                "<img src=x",
+                "<!--",
+                "<![cdata",
+                "<b ",
+            ] {
+                assert!(!lowered.contains(live), "{input:?} left {live}: {output}");
+            }
+            assert!(
+                !lowered.contains("href=\"javascript") && !lowered.contains("src=\"javascript"),
+                "{input:?} kept a script URL: {output}"
+            );
+            assert_eq!(
+                sanitize_html_fragment(&output),
+                output,
+                "{input:?} rendered markup the sanitizer would change"
+            );
+        }
+        assert_eq!(html("[x](javascript&colon;alert(1))"), "<p>x</p>");
+        assert_eq!(html("[x](&#106;avascript:alert(1))"), "<p>x</p>");
+        assert_eq!(html("[x](/\\evil.test)"), "<p>x</p>");
+        assert_eq!(html("<javascript:alert(1)>"), "<p>javascript:alert(1)</p>");
+        assert_eq!(
+            html("![x\"onerror=\"alert(1)](/a.png)"),
+            "<p><img src=\"/a.png\" alt=\"x&quot;onerror=&quot;alert(1)\"></p>"
+        );
+        assert_eq!(
+            html("<!-- <b>x</b> -->"),
+            "<p>&lt;!-- &lt;b&gt;x&lt;/b&gt; --&gt;</p>"
+        );
+    }
+
+    #[test]
+    fn rt_json_round_trip_keeps_structure() {
+        let markdown =
+            "## Heading\n\nSome **bold** and *italic* [link](https://example.com) text.\n\n\
+                        - first\n- second\n\n> quote\n\n```\ncode `here`\n```\n\n---";
+        let doc = to_rt_json_doc(&parse(markdown));
+        assert_eq!(doc["content"][0]["attrs"]["level"], 2);
+        assert_eq!(
+            doc["content"][1]["content"][1],
+            json!({"type": "text", "text": "bold", "marks": [{"type": "bold"}]})
+        );
+        assert_eq!(rt_json_to_markdown(&doc), markdown);
+    }
+
+    #[test]
+    fn markdown_export_escapes_literal_syntax() {
+        let doc = json!({"type": "doc", "content": [
+            {"type": "paragraph", "content": [{"type": "text", "text": "1. *not* a list"}]},
+            {"type": "paragraph", "content": [
+                {"type": "text", "text": "hi "},
+                {"type": "mention", "attrs": {"handle": "alice"}},
+                {"type": "hard_break"},
+                {"type": "text", "text": "x", "marks": [{"type": "code"}]}
+            ]}
+        ]});
+        let markdown = rt_json_to_markdown(&doc);
+        assert_eq!(markdown, "1\\. \\*not\\* a list\n\nhi @alice\\\n`x`");
+        assert_eq!(
+            html(&markdown),
+            "<p>1. *not* a list</p><p>hi @alice<br><code>x</code></p>"
+        );
+    }
+}
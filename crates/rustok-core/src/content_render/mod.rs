@@ -0,0 +1,382 @@
+//! Unified rendering of stored content formats.
+//!
+//! [`ContentRenderer`] turns a `markdown`, `rt_json_v1` or `grapesjs_v1` body
+//! into sanitized HTML, plain text for search indexing and excerpts, and a
+//! word-count/reading-time summary. It also converts between markdown and
+//! `rt_json_v1`. Embeds and other rt_json nodes can be rendered by custom
+//! [`RtJsonNodeRenderer`]s.
+
+mod grapesjs;
+mod html;
+mod markdown;
+mod rich_text;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::content_format::{
+    normalize_content_format, CONTENT_FORMAT_GRAPESJS_V1, CONTENT_FORMAT_MARKDOWN,
+    CONTENT_FORMAT_RT_JSON_V1,
+};
+use crate::grapesjs::validate_grapesjs_project;
+use crate::rt_json::{
+    sanitize_rt_json_before_html_render, validate_and_sanitize_rt_json, RtJsonValidationConfig,
+};
+
+pub use html::{escape_html, html_to_text, is_safe_url, sanitize_html_fragment};
+pub use markdown::rt_json_to_markdown;
+
+const WORDS_PER_MINUTE: usize = 200;
+const DEFAULT_RT_JSON_LOCALE: &str = "en";
+/// Formats older content rows may still carry; rendered but never written.
+const LEGACY_FORMAT_PLAIN: &str = "plain";
+const LEGACY_FORMAT_PLAIN_TEXT: &str = "plain_text";
+const LEGACY_FORMAT_HTML: &str = "html";
+
+static DEFAULT_RENDERER: Lazy<ContentRenderer> = Lazy::new(ContentRenderer::new);
+
+/// Renders one rt_json node type, e.g. `embed`.
+pub trait RtJsonNodeRenderer: Send + Sync {
+    /// HTML for `node`; `children_html` is its already rendered content.
+    /// Returning `None` falls back to the built-in rendering.
+    fn render_html(&self, node: &Value, children_html: &str) -> Option<String>;
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentSummary {
+    pub word_count: u32,
+    pub reading_time_minutes: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RenderedContent {
+    pub format: String,
+    pub html: String,
+    pub text: String,
+    pub summary: ContentSummary,
+}
+
+impl RenderedContent {
+    /// Excerpt of the plain text, cut at a word boundary.
+    pub fn excerpt(&self, max_chars: usize) -> String {
+        excerpt(&self.text, max_chars)
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct ContentRenderer {
+    node_renderers: HashMap<String, Arc<dyn RtJsonNodeRenderer>>,
+}
+
+impl ContentRenderer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Override rendering of one rt_json node type.
+    pub fn with_node_renderer(
+        mut self,
+        node_type: impl Into<String>,
+        renderer: Arc<dyn RtJsonNodeRenderer>,
+    ) -> Self {
+        self.node_renderers.insert(node_type.into(), renderer);
+        self
+    }
+
+    pub fn render(&self, format: &str, body: &str) -> Result<RenderedContent, String> {
+        let legacy = format.trim().to_ascii_lowercase();
+        let format = match legacy.as_str() {
+            LEGACY_FORMAT_PLAIN | LEGACY_FORMAT_PLAIN_TEXT | LEGACY_FORMAT_HTML => legacy,
+            _ => normalize_content_format(Some(format))?,
+        };
+        let html = match format.as_str() {
+            LEGACY_FORMAT_PLAIN | LEGACY_FORMAT_PLAIN_TEXT => plain_text_html(body),
+            LEGACY_FORMAT_HTML => sanitize_html_fragment(body),
+            CONTENT_FORMAT_MARKDOWN => markdown::to_html(&markdown::parse(body)),
+            CONTENT_FORMAT_RT_JSON_V1 => {
+                let payload = parse_json(body, &format)?;
+                let locale = payload
+                    .get("locale")
+                    .and_then(Value::as_str)
+                    .unwrap_or(DEFAULT_RT_JSON_LOCALE);
+                let sanitized = sanitize_rt_json_before_html_render(
+                    &payload,
+                    &RtJsonValidationConfig::for_locale(locale),
+                )?;
+                rich_text::render_doc(&sanitized["doc"], &self.node_renderers)
+            }
+            _ => {
+                let project = parse_json(body, &format)?;
+                validate_grapesjs_project(&project)?;
+                grapesjs::render_project(&project)
+            }
+        };
+        let text = html_to_text(&html);
+        Ok(RenderedContent {
+            summary: summarize_text(&text),
+            format,
+            html,
+            text,
+        })
+    }
+
+    /// Like [`ContentRenderer::render`], but content that fails to render is
+    /// logged and yields an empty result. Meant for read paths where stored
+    /// content was validated on write.
+    pub fn render_or_empty(&self, format: &str, body: &str) -> RenderedContent {
+        self.render(format, body).unwrap_or_else(|error| {
+            tracing::warn!(format, error = %error, "Failed to render stored content");
+            RenderedContent {
+                format: format.to_string(),
+                ..RenderedContent::default()
+            }
+        })
+    }
+
+    pub fn render_html(&self, format: &str, body: &str) -> Result<String, String> {
+        self.render(format, body).map(|rendered| rendered.html)
+    }
+
+    pub fn render_text(&self, format: &str, body: &str) -> Result<String, String> {
+        self.render(format, body).map(|rendered| rendered.text)
+    }
+
+    /// Convert a stored body between formats. Supports markdown to
+    /// `rt_json_v1` and back; converting to the same format normalizes it.
+    pub fn convert(
+        &self,
+        from_format: &str,
+        body: &str,
+        to_format: &str,
+        locale: &str,
+    ) -> Result<String, String> {
+        let from = normalize_content_format(Some(from_format))?;
+        let to = normalize_content_format(Some(to_format))?;
+        match (from.as_str(), to.as_str()) {
+            (CONTENT_FORMAT_MARKDOWN, CONTENT_FORMAT_MARKDOWN) => Ok(body.to_string()),
+            (CONTENT_FORMAT_MARKDOWN, CONTENT_FORMAT_RT_JSON_V1) => {
+                Ok(markdown_to_rt_json(body, locale)?.to_string())
+            }
+            (CONTENT_FORMAT_RT_JSON_V1, CONTENT_FORMAT_RT_JSON_V1) => {
+                let payload = parse_json(body, &from)?;
+                let config = RtJsonValidationConfig::for_locale(locale);
+                Ok(validate_and_sanitize_rt_json(&payload, &config)?
+                    .sanitized
+                    .to_string())
+            }
+            (CONTENT_FORMAT_RT_JSON_V1, CONTENT_FORMAT_MARKDOWN) => {
+                let payload = parse_json(body, &from)?;
+                let locale = payload
+                    .get("locale")
+                    .and_then(Value::as_str)
+                    .unwrap_or(locale);
+                let sanitized = sanitize_rt_json_before_html_render(
+                    &payload,
+                    &RtJsonValidationConfig::for_locale(locale),
+                )?;
+                Ok(rt_json_to_markdown(&sanitized))
+            }
+            (CONTENT_FORMAT_GRAPESJS_V1, CONTENT_FORMAT_GRAPESJS_V1) => {
+                let project = parse_json(body, &from)?;
+                validate_grapesjs_project(&project)?;
+                Ok(project.to_string())
+            }
+            _ => Err(format!(
+                "Conversion from '{from}' to '{to}' is not supported"
+            )),
+        }
+    }
+}
+
+/// Render with the default renderer, see [`ContentRenderer::render_or_empty`].
+pub fn render_content(format: &str, body: &str) -> RenderedContent {
+    DEFAULT_RENDERER.render_or_empty(format, body)
+}
+
+/// Parse markdown into a sanitized `rt_json_v1` payload for `locale`.
+pub fn markdown_to_rt_json(markdown: &str, locale: &str) -> Result<Value, String> {
+    let payload = serde_json::json!({
+        "version": CONTENT_FORMAT_RT_JSON_V1,
+        "locale": locale,
+        "doc": markdown::to_rt_json_doc(&markdown::parse(markdown)),
+    });
+    validate_and_sanitize_rt_json(&payload, &RtJsonValidationConfig::for_locale(locale))
+        .map(|result| result.sanitized)
+}
+
+pub fn summarize_text(text: &str) -> ContentSummary {
+    let words = text.split_whitespace().count();
+    ContentSummary {
+        word_count: words as u32,
+        reading_time_minutes: words.div_ceil(WORDS_PER_MINUTE) as u32,
+    }
+}
+
+/// First `max_chars` characters of `text` with whitespace collapsed, cut at
+/// a word boundary and ellipsized when shortened.
+pub fn excerpt(text: &str, max_chars: usize) -> String {
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.chars().count() <= max_chars {
+        return collapsed;
+    }
+    let cut: String = collapsed.chars().take(max_chars).collect();
+    let cut = match cut.rfind(' ') {
+        Some(space) if space > 0 => &cut[..space],
+        _ => cut.as_str(),
+    };
+    format!(
+        "{}…",
+        cut.trim_end_matches(|ch: char| ch.is_ascii_punctuation())
+    )
+}
+
+/// Paragraphs separated by blank lines, single newlines kept as breaks.
+fn plain_text_html(body: &str) -> String {
+    body.replace("\r\n", "\n")
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|paragraph| !paragraph.is_empty())
+        .map(|paragraph| {
+            let lines: Vec<String> = paragraph.lines().map(escape_html).collect();
+            format!("<p>{}</p>", lines.join("<br>"))
+        })
+        .collect()
+}
+
+fn parse_json(body: &str, format: &str) -> Result<Value, String> {
+    serde_json::from_str(body).map_err(|_| format!("{format} body must be valid JSON"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    struct LinkEmbed;
+
+    impl RtJsonNodeRenderer for LinkEmbed {
+        fn render_html(&self, node: &Value, _children_html: &str) -> Option<String> {
+            let url = node["attrs"]["url"].as_str()?;
+            Some(format!(
+                "<a class=\"embed\" href=\"{}\">video</a>",
+                escape_html(url)
+            ))
+        }
+    }
+
+    #[test]
+    fn renders_every_format_to_html_text_and_summary() {
+        let renderer = ContentRenderer::new();
+        let markdown = renderer
+            .render("markdown", "# Hello\n\nSome *nice* words here.")
+            .expect("markdown renders");
+        assert_eq!(
+            markdown.html,
+            "<h1>Hello</h1><p>Some <em>nice</em> words here.</p>"
+        );
+        assert_eq!(markdown.text, "Hello\nSome nice words here.");
+        assert_eq!(
+            markdown.summary,
+            ContentSummary {
+                word_count: 5,
+                reading_time_minutes: 1
+            }
+        );
+
+        let rich = json!({"version": "rt_json_v1", "locale": "ru", "doc": {"type": "doc", "content": [
+            {"type": "paragraph", "content": [{"type": "text", "text": "Привет"}]},
+            {"type": "script", "content": [{"type": "text", "text": "x"}]}
+        ]}});
+        let rendered = renderer
+            .render("rt_json", &rich.to_string())
+            .expect("rt_json renders");
+        assert_eq!(rendered.format, "rt_json_v1");
+        assert_eq!(rendered.html, "<p>Привет</p>");
+
+        let project = json!({"pages": [{"frames": [{"component": {"type": "wrapper", "components": [
+            {"type": "text", "content": "<p>Built</p>"}
+        ]}}]}]});
+        assert_eq!(
+            renderer
+                .render("grapesjs_v1", &project.to_string())
+                .expect("grapesjs renders")
+                .text,
+            "Built"
+        );
+        assert_eq!(
+            renderer
+                .render("plain", "a < b\nc\n\nd")
+                .expect("plain text renders")
+                .html,
+            "<p>a &lt; b<br>c</p><p>d</p>"
+        );
+        assert!(renderer.render("rt_json_v1", "not json").is_err());
+        assert!(renderer.render("json", "{}").is_err());
+    }
+
+    #[test]
+    fn custom_node_renderers_override_embeds() {
+        let renderer = ContentRenderer::new().with_node_renderer("embed", Arc::new(LinkEmbed));
+        let rich = json!({"version": "rt_json_v1", "locale": "en", "doc": {"type": "doc", "content": [
+            {"type": "embed", "attrs": {"provider": "youtube", "url": "https://youtu.be/abc"}}
+        ]}});
+        assert_eq!(
+            renderer
+                .render_html("rt_json_v1", &rich.to_string())
+                .unwrap(),
+            "<a class=\"embed\" href=\"https://youtu.be/abc\">video</a>"
+        );
+    }
+
+    #[test]
+    fn converts_between_markdown_and_rt_json() {
+        let renderer = ContentRenderer::new();
+        let rt_json = renderer
+            .convert(
+                "markdown",
+                "Read [docs](/docs) or **[site](https://example.com)**",
+                "rt_json_v1",
+                "en",
+            )
+            .expect("markdown converts");
+        let payload: Value = serde_json::from_str(&rt_json).unwrap();
+        assert_eq!(payload["locale"], "en");
+        // Relative links are not allowed in rt_json and degrade to text.
+        assert_eq!(
+            payload["doc"]["content"][0]["content"][0]["text"],
+            "Read docs or "
+        );
+
+        assert_eq!(
+            renderer
+                .convert("rt_json_v1", &rt_json, "markdown", "en")
+                .expect("rt_json converts"),
+            "Read docs or [**site**](https://example.com)"
+        );
+        assert!(renderer
+            .convert("grapesjs_v1", "{}", "markdown", "en")
+            .is_err());
+    }
+
+    #[test]
+    fn summaries_and_excerpts() {
+        assert_eq!(summarize_text("").reading_time_minutes, 0);
+        assert_eq!(summarize_text(&"word ".repeat(401)).reading_time_minutes, 3);
+        assert_eq!(excerpt("short  text", 20), "short text");
+        assert_eq!(
+            excerpt("A longer sentence, cut here", 19),
+            "A longer sentence…"
+        );
+        assert_eq!(
+            render_content("rt_json_v1", "broken"),
+            RenderedContent {
+                format: "rt_json_v1".to_string(),
+                ..RenderedContent::default()
+            }
+        );
+    }
+}
@@ -0,0 +1,235 @@
+//! HTML rendering of sanitized `rt_json_v1` documents.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde_json::Value;
+use url::Url;
+
+use super::html::{escape_html, is_safe_url};
+use super::RtJsonNodeRenderer;
+
+pub(crate) fn render_doc(
+    doc: &Value,
+    renderers: &HashMap<String, Arc<dyn RtJsonNodeRenderer>>,
+) -> String {
+    let mut out = String::new();
+    render_node(doc, renderers, &mut out);
+    out
+}
+
+fn render_children(
+    node: &Value,
+    renderers: &HashMap<String, Arc<dyn RtJsonNodeRenderer>>,
+) -> String {
+    let mut out = String::new();
+    for child in node
+        .get("content")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        render_node(child, renderers, &mut out);
+    }
+    out
+}
+
+fn render_node(
+    node: &Value,
+    renderers: &HashMap<String, Arc<dyn RtJsonNodeRenderer>>,
+    out: &mut String,
+) {
+    let node_type = node.get("type").and_then(Value::as_str).unwrap_or_default();
+    let attrs = node.get("attrs");
+    if node_type == "text" {
+        out.push_str(&render_text(node));
+        return;
+    }
+
+    let children = render_children(node, renderers);
+    if let Some(html) = renderers
+        .get(node_type)
+        .and_then(|renderer| renderer.render_html(node, &children))
+    {
+        out.push_str(&html);
+        return;
+    }
+
+    match node_type {
+        "doc" => out.push_str(&children),
+        "paragraph" => out.push_str(&format!("<p>{children}</p>")),
+        "heading" => {
+            let level = attrs
+                .and_then(|attrs| attrs.get("level"))
+                .and_then(Value::as_u64)
+                .unwrap_or(1)
+                .clamp(1, 6);
+            out.push_str(&format!("<h{level}>{children}</h{level}>"));
+        }
+        "bullet_list" => out.push_str(&format!("<ul>{children}</ul>")),
+        "ordered_list" => out.push_str(&format!("<ol>{children}</ol>")),
+        "list_item" => out.push_str(&format!("<li>{children}</li>")),
+        "blockquote" => out.push_str(&format!("<blockquote>{children}</blockquote>")),
+        "code_block" => {
+            let code: String = node
+                .get("content")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|child| child.get("text").and_then(Value::as_str))
+                .collect();
+            out.push_str(&format!("<pre><code>{}</code></pre>", escape_html(&code)));
+        }
+        "horizontal_rule" => out.push_str("<hr>"),
+        "hard_break" => out.push_str("<br>"),
+        "mention" => {
+            if let Some(handle) = attrs
+                .and_then(|attrs| attrs.get("handle"))
+                .and_then(Value::as_str)
+            {
+                let handle = escape_html(handle);
+                out.push_str(&format!(
+                    "<span class=\"mention\" data-handle=\"{handle}\">@{handle}</span>"
+                ));
+            }
+        }
+        "image" => {
+            if let Some(src) = attrs
+                .and_then(|attrs| attrs.get("src"))
+                .and_then(Value::as_str)
+                .filter(|src| is_safe_url(src))
+            {
+                out.push_str(&format!("<img src=\"{}\" alt=\"\">", escape_html(src)));
+            }
+        }
+        "embed" => {
+            let provider = attrs
+                .and_then(|attrs| attrs.get("provider"))
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            if let Some(url) = attrs
+                .and_then(|attrs| attrs.get("url"))
+                .and_then(Value::as_str)
+            {
+                out.push_str(&render_embed(provider, url));
+            }
+        }
+        _ => out.push_str(&children),
+    }
+}
+
+fn render_text(node: &Value) -> String {
+    let text = node.get("text").and_then(Value::as_str).unwrap_or_default();
+    let mut html = escape_html(text);
+    let marks = node
+        .get("marks")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    let has = |kind: &str| marks.iter().any(|mark| mark["type"] == kind);
+    if has("code") {
+        html = format!("<code>{html}</code>");
+    }
+    if has("bold") {
+        html = format!("<strong>{html}</strong>");
+    }
+    if has("italic") {
+        html = format!("<em>{html}</em>");
+    }
+    if has("strike") {
+        html = format!("<s>{html}</s>");
+    }
+    if let Some(href) = marks
+        .iter()
+        .find(|mark| mark["type"] == "link")
+        .and_then(|mark| mark["attrs"]["href"].as_str())
+        .filter(|href| is_safe_url(href))
+    {
+        html = format!(
+            "<a href=\"{}\" rel=\"nofollow noopener\">{html}</a>",
+            escape_html(href)
+        );
+    }
+    html
+}
+
+/// Built-in embed rendering: a privacy-friendly player iframe for known
+/// video providers, a plain link otherwise.
+fn render_embed(provider: &str, url: &str) -> String {
+    match embed_player_url(provider, url) {
+        Some(player) => format!(
+            "<iframe class=\"embed embed-{provider}\" src=\"{}\" loading=\"lazy\" \
+             allowfullscreen referrerpolicy=\"strict-origin-when-cross-origin\"></iframe>",
+            escape_html(&player)
+        ),
+        None if is_safe_url(url) => {
+            let url = escape_html(url);
+            format!("<p><a href=\"{url}\" rel=\"nofollow noopener\">{url}</a></p>")
+        }
+        None => String::new(),
+    }
+}
+
+fn embed_player_url(provider: &str, raw: &str) -> Option<String> {
+    let url = Url::parse(raw).ok()?;
+    let is_id = |id: &&str| {
+        !id.is_empty()
+            && id
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+    };
+    match (provider, url.host_str()?) {
+        ("youtube", "youtu.be") => {
+            let id = url.path_segments()?.next().filter(is_id)?;
+            Some(format!("https://www.youtube-nocookie.com/embed/{id}"))
+        }
+        ("youtube", _) => {
+            let id = url
+                .query_pairs()
+                .find(|(key, _)| key == "v")
+                .map(|(_, value)| value.into_owned())
+                .or_else(|| {
+                    let mut segments = url.path_segments()?;
+                    matches!(segments.next(), Some("embed" | "shorts"))
+                        .then(|| segments.next().map(str::to_string))
+                        .flatten()
+                })?;
+            is_id(&id.as_str()).then(|| format!("https://www.youtube-nocookie.com/embed/{id}"))
+        }
+        ("vimeo", "player.vimeo.com") => Some(url.to_string()),
+        ("vimeo", _) => {
+            let id = url.path_segments()?.find(|segment| {
+                !segment.is_empty() && segment.chars().all(|ch| ch.is_ascii_digit())
+            })?;
+            Some(format!("https://player.vimeo.com/video/{id}"))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn renders_marks_mentions_and_embeds() {
+        let doc = json!({"type": "doc", "content": [
+            {"type": "paragraph", "content": [
+                {"type": "text", "text": "<hi>", "marks": [{"type": "bold"}, {"type": "link", "attrs": {"href": "https://example.com"}}]},
+                {"type": "mention", "attrs": {"handle": "alice"}}
+            ]},
+            {"type": "embed", "attrs": {"provider": "youtube", "url": "https://www.youtube.com/watch?v=dQw4w9WgXcQ"}},
+            {"type": "embed", "attrs": {"provider": "vimeo", "url": "https://vimeo.com/76979871"}}
+        ]});
+        assert_eq!(
+            render_doc(&doc, &HashMap::new()),
+            "<p><a href=\"https://example.com\" rel=\"nofollow noopener\"><strong>&lt;hi&gt;</strong></a>\
+             <span class=\"mention\" data-handle=\"alice\">@alice</span></p>\
+             <iframe class=\"embed embed-youtube\" src=\"https://www.youtube-nocookie.com/embed/dQw4w9WgXcQ\" \
+             loading=\"lazy\" allowfullscreen referrerpolicy=\"strict-origin-when-cross-origin\"></iframe>\
+             <iframe class=\"embed embed-vimeo\" src=\"https://player.vimeo.com/video/76979871\" \
+             loading=\"lazy\" allowfullscreen referrerpolicy=\"strict-origin-when-cross-origin\"></iframe>"
+        );
+    }
+}
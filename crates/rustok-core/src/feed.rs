@@ -0,0 +1,516 @@
+//! Syndication feeds: RSS 2.0, Atom 1.0 and JSON Feed 1.1.
+//!
+//! Modules map their public content to [`FeedItem`]s and pick a
+//! [`FeedFormat`]; this module owns the wire formats and the HTTP validators
+//! (`ETag` / `Last-Modified`) used for conditional GET.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::{json, Map, Value};
+
+use crate::content_render::{escape_html, RenderedContent};
+
+/// Default and upper bound for the number of entries in a feed.
+pub const DEFAULT_FEED_LIMIT: u32 = 20;
+pub const MAX_FEED_LIMIT: u32 = 100;
+
+/// Characters kept in an entry summary.
+pub const FEED_SUMMARY_CHARS: usize = 400;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedFormat {
+    Rss,
+    Atom,
+    JsonFeed,
+}
+
+impl FeedFormat {
+    /// Parses the format path segment: `rss`, `atom` or `json`, optionally
+    /// with the matching file extension (`rss.xml`, `atom.xml`, `feed.json`).
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "rss" | "rss.xml" | "rss2" => Some(Self::Rss),
+            "atom" | "atom.xml" => Some(Self::Atom),
+            "json" | "feed.json" | "jsonfeed" => Some(Self::JsonFeed),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Rss => "rss",
+            Self::Atom => "atom",
+            Self::JsonFeed => "json",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Rss => "application/rss+xml; charset=utf-8",
+            Self::Atom => "application/atom+xml; charset=utf-8",
+            Self::JsonFeed => "application/feed+json; charset=utf-8",
+        }
+    }
+}
+
+/// Whether entries carry the full rendered body or only a summary.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FeedContentMode {
+    #[default]
+    Full,
+    Excerpt,
+}
+
+impl FeedContentMode {
+    pub fn parse(value: Option<&str>) -> Option<Self> {
+        match value.map(|value| value.trim().to_ascii_lowercase()) {
+            None => Some(Self::Full),
+            Some(value) if value.is_empty() || value == "full" => Some(Self::Full),
+            Some(value) if value == "excerpt" || value == "summary" => Some(Self::Excerpt),
+            Some(_) => None,
+        }
+    }
+}
+
+/// Clamps a requested entry count to `1..=MAX_FEED_LIMIT`.
+pub fn feed_limit(requested: Option<u32>) -> u32 {
+    requested
+        .unwrap_or(DEFAULT_FEED_LIMIT)
+        .clamp(1, MAX_FEED_LIMIT)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeedItem {
+    /// Stable, globally unique entry id (e.g. `urn:uuid:…`).
+    pub id: String,
+    pub title: String,
+    pub url: String,
+    pub author: Option<String>,
+    pub published_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub categories: Vec<String>,
+    pub summary: Option<String>,
+    /// Sanitized HTML body; `None` for excerpt-only feeds.
+    pub content_html: Option<String>,
+    pub image_url: Option<String>,
+}
+
+impl FeedItem {
+    pub fn new(
+        id: impl Into<String>,
+        title: impl Into<String>,
+        url: impl Into<String>,
+        published_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            title: title.into(),
+            url: url.into(),
+            author: None,
+            published_at,
+            updated_at: published_at,
+            categories: Vec::new(),
+            summary: None,
+            content_html: None,
+            image_url: None,
+        }
+    }
+
+    /// Fills summary and body from rendered content. An explicit summary
+    /// (e.g. an authored excerpt) wins over the derived one.
+    pub fn with_content(
+        mut self,
+        mode: FeedContentMode,
+        rendered: &RenderedContent,
+        summary: Option<&str>,
+    ) -> Self {
+        let summary = summary
+            .map(str::trim)
+            .filter(|summary| !summary.is_empty())
+            .map(str::to_string)
+            .unwrap_or_else(|| rendered.excerpt(FEED_SUMMARY_CHARS));
+        self.summary = (!summary.is_empty()).then_some(summary);
+        self.content_html = match mode {
+            FeedContentMode::Full if !rendered.html.is_empty() => Some(rendered.html.clone()),
+            _ => None,
+        };
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Feed {
+    pub title: String,
+    pub description: Option<String>,
+    /// Public page the feed describes.
+    pub home_page_url: String,
+    /// Absolute URL of the feed document itself.
+    pub feed_url: String,
+    pub language: Option<String>,
+    pub items: Vec<FeedItem>,
+}
+
+impl Feed {
+    /// Most recent change across entries, used for `Last-Modified` and the
+    /// feed-level `updated` / `lastBuildDate`.
+    pub fn last_modified(&self) -> Option<DateTime<Utc>> {
+        self.items.iter().map(|item| item.updated_at).max()
+    }
+
+    pub fn render(&self, format: FeedFormat) -> String {
+        match format {
+            FeedFormat::Rss => self.render_rss(),
+            FeedFormat::Atom => self.render_atom(),
+            FeedFormat::JsonFeed => self.render_json_feed(),
+        }
+    }
+
+    fn render_rss(&self) -> String {
+        let mut out = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<rss version=\"2.0\" \
+             xmlns:atom=\"http://www.w3.org/2005/Atom\" \
+             xmlns:content=\"http://purl.org/rss/1.0/modules/content/\" \
+             xmlns:dc=\"http://purl.org/dc/elements/1.1/\">\n<channel>\n",
+        );
+        push_element(&mut out, "title", &self.title);
+        push_element(&mut out, "link", &self.home_page_url);
+        push_element(
+            &mut out,
+            "description",
+            self.description.as_deref().unwrap_or(&self.title),
+        );
+        if let Some(language) = &self.language {
+            push_element(&mut out, "language", language);
+        }
+        if let Some(updated) = self.last_modified() {
+            push_element(&mut out, "lastBuildDate", &updated.to_rfc2822());
+        }
+        out.push_str(&format!(
+            "<atom:link href=\"{}\" rel=\"self\" type=\"application/rss+xml\"/>\n",
+            escape_html(&self.feed_url)
+        ));
+        for item in &self.items {
+            out.push_str("<item>\n");
+            push_element(&mut out, "title", &item.title);
+            push_element(&mut out, "link", &item.url);
+            out.push_str(&format!(
+                "<guid isPermaLink=\"false\">{}</guid>\n",
+                escape_html(&item.id)
+            ));
+            push_element(&mut out, "pubDate", &item.published_at.to_rfc2822());
+            if let Some(author) = &item.author {
+                push_element(&mut out, "dc:creator", author);
+            }
+            for category in &item.categories {
+                push_element(&mut out, "category", category);
+            }
+            if let Some(summary) = &item.summary {
+                push_element(&mut out, "description", summary);
+            }
+            if let Some(html) = &item.content_html {
+                push_element(&mut out, "content:encoded", html);
+            }
+            if let Some(image) = &item.image_url {
+                out.push_str(&format!(
+                    "<enclosure url=\"{}\" length=\"0\" type=\"{}\"/>\n",
+                    escape_html(image),
+                    image_mime_type(image)
+                ));
+            }
+            out.push_str("</item>\n");
+        }
+        out.push_str("</channel>\n</rss>\n");
+        out
+    }
+
+    fn render_atom(&self) -> String {
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        match &self.language {
+            Some(language) => out.push_str(&format!(
+                "<feed xmlns=\"http://www.w3.org/2005/Atom\" xml:lang=\"{}\">\n",
+                escape_html(language)
+            )),
+            None => out.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n"),
+        }
+        push_element(&mut out, "id", &self.feed_url);
+        push_element(&mut out, "title", &self.title);
+        if let Some(description) = &self.description {
+            push_element(&mut out, "subtitle", description);
+        }
+        let updated = self.last_modified().unwrap_or(DateTime::UNIX_EPOCH);
+        push_element(&mut out, "updated", &rfc3339(updated));
+        push_link(&mut out, "self", &self.feed_url);
+        push_link(&mut out, "alternate", &self.home_page_url);
+        for item in &self.items {
+            out.push_str("<entry>\n");
+            push_element(&mut out, "id", &item.id);
+            push_element(&mut out, "title", &item.title);
+            push_link(&mut out, "alternate", &item.url);
+            push_element(&mut out, "published", &rfc3339(item.published_at));
+            push_element(&mut out, "updated", &rfc3339(item.updated_at));
+            if let Some(author) = &item.author {
+                out.push_str("<author>");
+                push_inline_element(&mut out, "name", author);
+                out.push_str("</author>\n");
+            }
+            for category in &item.categories {
+                out.push_str(&format!("<category term=\"{}\"/>\n", escape_html(category)));
+            }
+            if let Some(summary) = &item.summary {
+                out.push_str(&format!(
+                    "<summary type=\"text\">{}</summary>\n",
+                    escape_html(summary)
+                ));
+            }
+            if let Some(html) = &item.content_html {
+                out.push_str(&format!(
+                    "<content type=\"html\">{}</content>\n",
+                    escape_html(html)
+                ));
+            }
+            out.push_str("</entry>\n");
+        }
+        out.push_str("</feed>\n");
+        out
+    }
+
+    fn render_json_feed(&self) -> String {
+        let items = self
+            .items
+            .iter()
+            .map(|item| {
+                let mut entry = Map::new();
+                entry.insert("id".into(), json!(item.id));
+                entry.insert("url".into(), json!(item.url));
+                entry.insert("title".into(), json!(item.title));
+                match (&item.content_html, &item.summary) {
+                    (Some(html), _) => {
+                        entry.insert("content_html".into(), json!(html));
+                    }
+                    (None, summary) => {
+                        entry.insert(
+                            "content_text".into(),
+                            json!(summary.as_deref().unwrap_or_default()),
+                        );
+                    }
+                }
+                if let Some(summary) = &item.summary {
+                    entry.insert("summary".into(), json!(summary));
+                }
+                if let Some(image) = &item.image_url {
+                    entry.insert("image".into(), json!(image));
+                }
+                entry.insert("date_published".into(), json!(rfc3339(item.published_at)));
+                entry.insert("date_modified".into(), json!(rfc3339(item.updated_at)));
+                if let Some(author) = &item.author {
+                    entry.insert("authors".into(), json!([{ "name": author }]));
+                }
+                if !item.categories.is_empty() {
+                    entry.insert("tags".into(), json!(item.categories));
+                }
+                Value::Object(entry)
+            })
+            .collect::<Vec<_>>();
+
+        let mut feed = Map::new();
+        feed.insert("version".into(), json!("https://jsonfeed.org/version/1.1"));
+        feed.insert("title".into(), json!(self.title));
+        feed.insert("home_page_url".into(), json!(self.home_page_url));
+        feed.insert("feed_url".into(), json!(self.feed_url));
+        if let Some(description) = &self.description {
+            feed.insert("description".into(), json!(description));
+        }
+        if let Some(language) = &self.language {
+            feed.insert("language".into(), json!(language));
+        }
+        feed.insert("items".into(), Value::Array(items));
+        serde_json::to_string_pretty(&Value::Object(feed)).unwrap_or_default()
+    }
+}
+
+/// Formats a timestamp as an HTTP-date (RFC 9110 IMF-fixdate).
+pub fn format_http_date(value: DateTime<Utc>) -> String {
+    value.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+pub fn parse_http_date(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc2822(value.trim())
+        .ok()
+        .map(|value| value.with_timezone(&Utc))
+}
+
+/// Evaluates `If-None-Match` / `If-Modified-Since` against the current
+/// validators. `If-None-Match` takes precedence when present, as required by
+/// RFC 9110; entity tags are compared weakly.
+pub fn is_not_modified(
+    if_none_match: Option<&str>,
+    if_modified_since: Option<&str>,
+    etag: &str,
+    last_modified: Option<DateTime<Utc>>,
+) -> bool {
+    if let Some(if_none_match) = if_none_match {
+        let current = strip_weak(etag);
+        return if_none_match
+            .split(',')
+            .map(str::trim)
+            .any(|candidate| candidate == "*" || strip_weak(candidate) == current);
+    }
+
+    match (if_modified_since.and_then(parse_http_date), last_modified) {
+        (Some(since), Some(last_modified)) => last_modified.timestamp() <= since.timestamp(),
+        _ => false,
+    }
+}
+
+fn strip_weak(tag: &str) -> &str {
+    tag.trim().trim_start_matches("W/")
+}
+
+fn rfc3339(value: DateTime<Utc>) -> String {
+    value.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn push_element(out: &mut String, name: &str, text: &str) {
+    push_inline_element(out, name, text);
+    out.push('\n');
+}
+
+fn push_inline_element(out: &mut String, name: &str, text: &str) {
+    out.push_str(&format!("<{name}>{}</{name}>", escape_html(text)));
+}
+
+fn push_link(out: &mut String, rel: &str, href: &str) {
+    out.push_str(&format!(
+        "<link rel=\"{rel}\" href=\"{}\"/>\n",
+        escape_html(href)
+    ));
+}
+
+fn image_mime_type(url: &str) -> &'static str {
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    let extension = path
+        .rsplit('.')
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    match extension.as_str() {
+        "png" => "image/png",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "avif" => "image/avif",
+        "svg" => "image/svg+xml",
+        _ => "image/jpeg",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content_render::render_content;
+    use chrono::TimeZone;
+
+    fn sample_feed(mode: FeedContentMode) -> Feed {
+        let published = Utc.with_ymd_and_hms(2026, 3, 1, 9, 30, 0).unwrap();
+        let mut item = FeedItem::new(
+            "urn:uuid:00000000-0000-0000-0000-000000000001",
+            "Fish & <Chips>",
+            "https://example.com/modules/blog?slug=fish",
+            published,
+        )
+        .with_content(mode, &render_content("markdown", "Hello **world**"), None);
+        item.updated_at = published + chrono::Duration::hours(2);
+        item.author = Some("Alice".to_string());
+        item.categories = vec!["news".to_string()];
+        Feed {
+            title: "Example blog".to_string(),
+            description: None,
+            home_page_url: "https://example.com/modules/blog".to_string(),
+            feed_url: "https://example.com/api/blog/feeds/rss".to_string(),
+            language: Some("en".to_string()),
+            items: vec![item],
+        }
+    }
+
+    #[test]
+    fn parses_formats_and_modes() {
+        assert_eq!(FeedFormat::parse("RSS"), Some(FeedFormat::Rss));
+        assert_eq!(FeedFormat::parse("atom.xml"), Some(FeedFormat::Atom));
+        assert_eq!(FeedFormat::parse("feed.json"), Some(FeedFormat::JsonFeed));
+        assert_eq!(FeedFormat::parse("csv"), None);
+        assert_eq!(FeedContentMode::parse(None), Some(FeedContentMode::Full));
+        assert_eq!(
+            FeedContentMode::parse(Some("excerpt")),
+            Some(FeedContentMode::Excerpt)
+        );
+        assert_eq!(FeedContentMode::parse(Some("everything")), None);
+        assert_eq!(feed_limit(Some(1_000)), MAX_FEED_LIMIT);
+        assert_eq!(feed_limit(Some(0)), 1);
+    }
+
+    #[test]
+    fn renders_rss_with_escaped_content() {
+        let rss = sample_feed(FeedContentMode::Full).render(FeedFormat::Rss);
+        assert!(rss.contains("<title>Fish &amp; &lt;Chips&gt;</title>"));
+        assert!(rss.contains("<pubDate>Sun, 1 Mar 2026 09:30:00 +0000</pubDate>"));
+        assert!(rss.contains("<lastBuildDate>Sun, 1 Mar 2026 11:30:00 +0000</lastBuildDate>"));
+        assert!(rss.contains(
+            "<content:encoded>&lt;p&gt;Hello &lt;strong&gt;world&lt;/strong&gt;&lt;/p&gt;</content:encoded>"
+        ));
+        assert!(rss.contains("<dc:creator>Alice</dc:creator>"));
+    }
+
+    #[test]
+    fn renders_atom_excerpt_without_content() {
+        let atom = sample_feed(FeedContentMode::Excerpt).render(FeedFormat::Atom);
+        assert!(atom.contains("xml:lang=\"en\""));
+        assert!(atom.contains("<updated>2026-03-01T11:30:00Z</updated>"));
+        assert!(atom.contains("<summary type=\"text\">Hello world</summary>"));
+        assert!(!atom.contains("<content"));
+    }
+
+    #[test]
+    fn renders_json_feed() {
+        let feed: Value =
+            serde_json::from_str(&sample_feed(FeedContentMode::Full).render(FeedFormat::JsonFeed))
+                .unwrap();
+        assert_eq!(feed["version"], "https://jsonfeed.org/version/1.1");
+        assert_eq!(
+            feed["items"][0]["content_html"],
+            "<p>Hello <strong>world</strong></p>"
+        );
+        assert_eq!(feed["items"][0]["authors"][0]["name"], "Alice");
+        assert_eq!(feed["items"][0]["date_published"], "2026-03-01T09:30:00Z");
+    }
+
+    #[test]
+    fn evaluates_conditional_requests() {
+        let last_modified = Utc.with_ymd_and_hms(2026, 3, 1, 11, 30, 0).unwrap();
+        let http_date = format_http_date(last_modified);
+        assert_eq!(http_date, "Sun, 01 Mar 2026 11:30:00 GMT");
+        assert_eq!(parse_http_date(&http_date), Some(last_modified));
+
+        assert!(is_not_modified(
+            Some("W/\"abc\", \"def\""),
+            None,
+            "\"abc\"",
+            None
+        ));
+        assert!(!is_not_modified(
+            Some("\"xyz\""),
+            Some(&http_date),
+            "\"abc\"",
+            Some(last_modified)
+        ));
+        assert!(is_not_modified(
+            None,
+            Some(&http_date),
+            "\"abc\"",
+            Some(last_modified)
+        ));
+        assert!(!is_not_modified(
+            None,
+            Some("Sun, 01 Mar 2026 10:00:00 GMT"),
+            "\"abc\"",
+            Some(last_modified)
+        ));
+    }
+}
@@ -0,0 +1,294 @@
+//! Minimal JSON Schema validator for runtime-declared contracts.
+//!
+//! Covers the subset modules use to describe action inputs and outputs:
+//! `type` (single or list), `enum`, `const`, `required`, `properties`,
+//! `additionalProperties: false`, `items`, `minLength` / `maxLength`,
+//! `minimum` / `maximum`, `minItems` / `maxItems` and `format`
+//! (`uuid`, `email`, `date-time`). Unknown keywords are ignored.
+//!
+//! [`validate_json_schema_template`] additionally accepts whole-string
+//! `{{ path }}` placeholders in place of any value, so configs that are
+//! resolved at run time can be checked when they are saved.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A single schema violation, addressed by a JSON pointer-like path (`$.a.b[0]`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaViolation {
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// Validates `value` against `schema`, returning every violation found.
+pub fn validate_json_schema(schema: &Value, value: &Value) -> Vec<SchemaViolation> {
+    let mut violations = Vec::new();
+    validate_node(schema, value, "$", false, &mut violations);
+    violations
+}
+
+/// Like [`validate_json_schema`], but treats strings that consist of a single
+/// `{{ ... }}` placeholder as satisfying any schema.
+pub fn validate_json_schema_template(schema: &Value, value: &Value) -> Vec<SchemaViolation> {
+    let mut violations = Vec::new();
+    validate_node(schema, value, "$", true, &mut violations);
+    violations
+}
+
+/// Returns `true` when `value` is exactly one `{{ ... }}` placeholder.
+pub fn is_template_placeholder(value: &str) -> bool {
+    let trimmed = value.trim();
+    trimmed.len() > 4
+        && trimmed.starts_with("{{")
+        && trimmed.ends_with("}}")
+        && !trimmed[2..trimmed.len() - 2].contains("{{")
+}
+
+fn validate_node(
+    schema: &Value,
+    value: &Value,
+    path: &str,
+    allow_placeholders: bool,
+    violations: &mut Vec<SchemaViolation>,
+) {
+    let Some(schema) = schema.as_object() else {
+        return;
+    };
+    if allow_placeholders && value.as_str().is_some_and(is_template_placeholder) {
+        return;
+    }
+    let mut violation = |message: String| {
+        violations.push(SchemaViolation {
+            path: path.to_string(),
+            message,
+        })
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let types: Vec<&str> = match expected {
+            Value::String(name) => vec![name.as_str()],
+            Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !types.is_empty() && !types.iter().any(|name| matches_type(name, value)) {
+            violation(format!(
+                "expected {}, got {}",
+                types.join(" | "),
+                type_name(value)
+            ));
+            return;
+        }
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+        if !allowed.contains(value) {
+            violation(format!("must be one of {}", Value::Array(allowed.clone())));
+        }
+    }
+    if let Some(expected) = schema.get("const") {
+        if expected != value {
+            violation(format!("must equal {expected}"));
+        }
+    }
+
+    match value {
+        Value::String(text) => {
+            let length = text.chars().count() as u64;
+            if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+                if length < min {
+                    violation(format!("must be at least {min} characters"));
+                }
+            }
+            if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+                if length > max {
+                    violation(format!("must be at most {max} characters"));
+                }
+            }
+            if let Some(format) = schema.get("format").and_then(Value::as_str) {
+                if !matches_format(format, text) {
+                    violation(format!("must be a valid {format}"));
+                }
+            }
+        }
+        Value::Number(number) => {
+            let number = number.as_f64().unwrap_or_default();
+            if let Some(min) = schema.get("minimum").and_then(Value::as_f64) {
+                if number < min {
+                    violation(format!("must be >= {min}"));
+                }
+            }
+            if let Some(max) = schema.get("maximum").and_then(Value::as_f64) {
+                if number > max {
+                    violation(format!("must be <= {max}"));
+                }
+            }
+        }
+        Value::Array(items) => {
+            let length = items.len() as u64;
+            if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+                if length < min {
+                    violation(format!("must contain at least {min} items"));
+                }
+            }
+            if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+                if length > max {
+                    violation(format!("must contain at most {max} items"));
+                }
+            }
+            if let Some(item_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    validate_node(
+                        item_schema,
+                        item,
+                        &format!("{path}[{index}]"),
+                        allow_placeholders,
+                        violations,
+                    );
+                }
+            }
+        }
+        Value::Object(fields) => {
+            if let Some(required) = schema.get("required").and_then(Value::as_array) {
+                for name in required.iter().filter_map(Value::as_str) {
+                    if !fields.contains_key(name) {
+                        violation(format!("missing required property `{name}`"));
+                    }
+                }
+            }
+            let properties = schema.get("properties").and_then(Value::as_object);
+            let closed = schema.get("additionalProperties") == Some(&Value::Bool(false));
+            for (name, field) in fields {
+                match properties.and_then(|properties| properties.get(name)) {
+                    Some(field_schema) => validate_node(
+                        field_schema,
+                        field,
+                        &format!("{path}.{name}"),
+                        allow_placeholders,
+                        violations,
+                    ),
+                    None if closed => violations.push(SchemaViolation {
+                        path: path.to_string(),
+                        message: format!("unexpected property `{name}`"),
+                    }),
+                    None => {}
+                }
+            }
+        }
+        Value::Null | Value::Bool(_) => {}
+    }
+}
+
+fn matches_type(name: &str, value: &Value) -> bool {
+    match name {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        "number" => value.is_number(),
+        "integer" => {
+            value.is_i64()
+                || value.is_u64()
+                || value.as_f64().is_some_and(|number| number.fract() == 0.0)
+        }
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn matches_format(format: &str, text: &str) -> bool {
+    match format {
+        "uuid" => uuid::Uuid::parse_str(text).is_ok(),
+        "email" => email_address::EmailAddress::is_valid(text),
+        "date-time" => chrono::DateTime::parse_from_rfc3339(text).is_ok(),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "required": ["topic_id", "reason"],
+            "additionalProperties": false,
+            "properties": {
+                "topic_id": { "type": "string", "format": "uuid" },
+                "reason": { "type": "string", "minLength": 3 },
+                "priority": { "type": "integer", "minimum": 1, "maximum": 5 },
+                "tags": { "type": "array", "items": { "enum": ["vip", "spam"] } }
+            }
+        })
+    }
+
+    #[test]
+    fn accepts_matching_values() {
+        let value = json!({
+            "topic_id": "8f0c9a1e-0c3b-4f6e-9d1a-2b4c6d8e0f12",
+            "reason": "off-topic",
+            "priority": 2,
+            "tags": ["vip"]
+        });
+        assert!(validate_json_schema(&schema(), &value).is_empty());
+    }
+
+    #[test]
+    fn reports_each_violation_with_its_path() {
+        let value = json!({
+            "topic_id": "not-a-uuid",
+            "priority": 9,
+            "tags": ["vip", "other"],
+            "extra": true
+        });
+        let violations = validate_json_schema(&schema(), &value);
+        let rendered: Vec<String> = violations.iter().map(ToString::to_string).collect();
+
+        assert!(rendered.contains(&"$: missing required property `reason`".to_string()));
+        assert!(rendered.contains(&"$.topic_id: must be a valid uuid".to_string()));
+        assert!(rendered.contains(&"$.priority: must be <= 5".to_string()));
+        assert!(rendered.contains(&"$.tags[1]: must be one of [\"vip\",\"spam\"]".to_string()));
+        assert!(rendered.contains(&"$: unexpected property `extra`".to_string()));
+        assert_eq!(violations.len(), 5);
+    }
+
+    #[test]
+    fn template_mode_accepts_placeholders_only_as_whole_values() {
+        let value = json!({
+            "topic_id": "{{context.topic_id}}",
+            "reason": "{{ context.reason }}",
+            "priority": "{{context.priority}}"
+        });
+        assert!(validate_json_schema_template(&schema(), &value).is_empty());
+        assert_eq!(validate_json_schema(&schema(), &value).len(), 2);
+
+        let partial = json!({ "topic_id": "id-{{context.id}}", "reason": "ok!" });
+        assert_eq!(
+            validate_json_schema_template(&schema(), &partial),
+            vec![SchemaViolation {
+                path: "$.topic_id".to_string(),
+                message: "must be a valid uuid".to_string(),
+            }]
+        );
+    }
+}
@@ -2,18 +2,22 @@ pub mod async_utils;
 pub mod cache;
 pub mod config;
 pub mod content_format;
+pub mod content_render;
 pub mod context;
 pub mod error;
 pub mod events;
+pub mod feed;
 pub mod field_schema;
 pub mod grapesjs;
 pub mod health;
 pub mod i18n;
 pub mod id;
+pub mod json_schema;
 pub mod locale;
 pub mod metrics;
 pub mod migrations;
 pub mod module;
+pub mod module_action;
 pub mod permissions;
 pub mod rbac;
 pub mod registry;
@@ -44,6 +48,10 @@ pub use content_format::{
     normalize_content_format, prepare_content_payload, PreparedContent, CONTENT_FORMAT_GRAPESJS_V1,
     CONTENT_FORMAT_MARKDOWN, CONTENT_FORMAT_RT_JSON_V1,
 };
+pub use content_render::{
+    excerpt, markdown_to_rt_json, render_content, rt_json_to_markdown, summarize_text,
+    ContentRenderer, ContentSummary, RenderedContent, RtJsonNodeRenderer,
+};
 pub use context::{AppContext, CacheBackend, SearchBackend};
 pub use error::{
     Error, ErrorContext, ErrorKind, ErrorResponse, FieldError, Result, RichError,
@@ -55,6 +63,7 @@ pub use events::{
     HandlerBuilder, HandlerResult, MemoryTransport, ReliabilityLevel, RunningDispatcher,
     EVENT_SCHEMAS,
 };
+pub use feed::{Feed, FeedContentMode, FeedFormat, FeedItem};
 pub use field_schema::{
     create_field_definitions_table, drop_field_definitions_table, is_valid_field_key,
     is_valid_locale_key, json_field_contains, json_field_eq, json_field_exists, json_field_extract,
@@ -79,6 +88,11 @@ pub use module::{
     MigrationSource, ModuleContext, ModuleEventListenerContext, ModuleEventListenerRegistry,
     ModuleKind, ModuleRuntimeExtensions, RusToKModule,
 };
+pub use module_action::{
+    module_action_registry_from_extensions, register_module_action, ModuleAction,
+    ModuleActionContext, ModuleActionDescriptor, ModuleActionError, ModuleActionRegistry,
+    ModuleActionResult,
+};
 pub use permissions::{Action, Permission, Resource};
 pub use rbac::{PermissionScope, Rbac, SecurityContext};
 pub use registry::ModuleRegistry;
@@ -96,6 +110,7 @@ pub use security::{
     SecurityAuditResult, SecurityCategory, SecurityConfig, SecurityFinding, SecurityHeaders,
     SecurityHeadersConfig, Severity, SsrfProtection, ValidationResult,
 };
+pub use security::{read_body_limited, ResolvedUrl};
 pub use typed_error::{
     DomainError, ErrorCategory, ErrorCode, ErrorResponseBody, IntoTypedResult, TypedResult,
 };
@@ -9,7 +9,7 @@ use sea_orm_migration::MigrationTrait;
 use crate::migrations::MigrationDependencyDescriptor;
 use serde_json::Value;
 
-use crate::events::EventHandler;
+use crate::events::{EventHandler, EventTransport};
 use crate::permissions::Permission;
 
 pub struct ModuleContext<'a> {
@@ -82,6 +82,8 @@ impl ModuleRuntimeExtensions {
 pub struct ModuleEventListenerContext<'a> {
     pub db: DatabaseConnection,
     pub extensions: &'a ModuleRuntimeExtensions,
+    /// Host event transport, absent when the event runtime is not bootstrapped.
+    pub event_transport: Option<Arc<dyn EventTransport>>,
 }
 
 #[derive(Default)]
@@ -0,0 +1,329 @@
+//! Typed module actions.
+//!
+//! Modules publish invocable operations (`order.confirm`, `forum.lock_topic`, …)
+//! through [`register_module_action`] from
+//! [`RusToKModule::register_runtime_extensions`](crate::RusToKModule::register_runtime_extensions).
+//! Automation runtimes such as the workflow `action` step look them up in the
+//! shared [`ModuleActionRegistry`], validate input against the declared JSON
+//! schema and invoke them under an explicit [`SecurityContext`].
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sea_orm::DatabaseConnection;
+use serde::Serialize;
+use serde_json::Value;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::events::EventTransport;
+use crate::json_schema::{validate_json_schema, validate_json_schema_template, SchemaViolation};
+use crate::module::ModuleRuntimeExtensions;
+use crate::permissions::Permission;
+use crate::rbac::SecurityContext;
+
+/// Static description of an action: its name, contract and access requirements.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModuleActionDescriptor {
+    /// Qualified name, `<module>.<operation>`.
+    pub name: &'static str,
+    pub module_slug: &'static str,
+    pub description: &'static str,
+    /// JSON schema of the input object.
+    pub input_schema: Value,
+    /// JSON schema of the output; `Value::Null` leaves the output unchecked.
+    pub output_schema: Value,
+    /// Permissions the invoking principal must hold.
+    pub required_permissions: Vec<Permission>,
+}
+
+/// Runtime handed to an action invocation.
+#[derive(Clone)]
+pub struct ModuleActionContext {
+    pub db: DatabaseConnection,
+    pub tenant_id: Uuid,
+    /// User on whose behalf the action runs, when known.
+    pub actor_id: Option<Uuid>,
+    pub security: SecurityContext,
+    pub event_transport: Arc<dyn EventTransport>,
+}
+
+#[derive(Debug, Error)]
+pub enum ModuleActionError {
+    #[error("module action `{0}` is already registered")]
+    Duplicate(String),
+    #[error("unknown module action `{0}`")]
+    UnknownAction(String),
+    #[error("invalid input for `{action}`: {}", join_violations(.violations))]
+    InvalidInput {
+        action: String,
+        violations: Vec<SchemaViolation>,
+    },
+    #[error("invalid output from `{action}`: {}", join_violations(.violations))]
+    InvalidOutput {
+        action: String,
+        violations: Vec<SchemaViolation>,
+    },
+    #[error("`{action}` requires permission {permission}")]
+    PermissionDenied {
+        action: String,
+        permission: Permission,
+    },
+    #[error("{0}")]
+    Failed(String),
+}
+
+impl ModuleActionError {
+    pub fn failed(error: impl std::fmt::Display) -> Self {
+        Self::Failed(error.to_string())
+    }
+}
+
+fn join_violations(violations: &[SchemaViolation]) -> String {
+    violations
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+pub type ModuleActionResult<T> = Result<T, ModuleActionError>;
+
+#[async_trait]
+pub trait ModuleAction: Send + Sync {
+    fn descriptor(&self) -> ModuleActionDescriptor;
+
+    /// Runs the action. Input has already been validated against the schema
+    /// and the principal checked against `required_permissions`.
+    async fn invoke(&self, ctx: &ModuleActionContext, input: Value) -> ModuleActionResult<Value>;
+}
+
+#[derive(Clone, Default)]
+pub struct ModuleActionRegistry {
+    actions: BTreeMap<&'static str, Arc<dyn ModuleAction>>,
+}
+
+impl ModuleActionRegistry {
+    pub fn register<A>(&mut self, action: A) -> ModuleActionResult<()>
+    where
+        A: ModuleAction + 'static,
+    {
+        self.register_arc(Arc::new(action))
+    }
+
+    pub fn register_arc(&mut self, action: Arc<dyn ModuleAction>) -> ModuleActionResult<()> {
+        let name = action.descriptor().name;
+        if self.actions.contains_key(name) {
+            return Err(ModuleActionError::Duplicate(name.to_string()));
+        }
+        self.actions.insert(name, action);
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<dyn ModuleAction>> {
+        self.actions.get(name).cloned()
+    }
+
+    pub fn descriptors(&self) -> Vec<ModuleActionDescriptor> {
+        self.actions
+            .values()
+            .map(|action| action.descriptor())
+            .collect()
+    }
+
+    /// Save-time check: the action exists and `input` matches its schema,
+    /// with whole-value `{{ ... }}` placeholders accepted.
+    pub fn validate_template_input(&self, name: &str, input: &Value) -> ModuleActionResult<()> {
+        let descriptor = self.descriptor(name)?;
+        let violations = validate_json_schema_template(&descriptor.input_schema, input);
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(ModuleActionError::InvalidInput {
+                action: name.to_string(),
+                violations,
+            })
+        }
+    }
+
+    /// Checks permissions and input, invokes the action and validates its output.
+    pub async fn invoke(
+        &self,
+        name: &str,
+        ctx: &ModuleActionContext,
+        input: Value,
+    ) -> ModuleActionResult<Value> {
+        let action = self
+            .get(name)
+            .ok_or_else(|| ModuleActionError::UnknownAction(name.to_string()))?;
+        let descriptor = action.descriptor();
+
+        if let Some(permission) = descriptor
+            .required_permissions
+            .iter()
+            .find(|permission| !ctx.security.has_permission(permission))
+        {
+            return Err(ModuleActionError::PermissionDenied {
+                action: name.to_string(),
+                permission: *permission,
+            });
+        }
+
+        let violations = validate_json_schema(&descriptor.input_schema, &input);
+        if !violations.is_empty() {
+            return Err(ModuleActionError::InvalidInput {
+                action: name.to_string(),
+                violations,
+            });
+        }
+
+        let output = action.invoke(ctx, input).await?;
+        let violations = validate_json_schema(&descriptor.output_schema, &output);
+        if !violations.is_empty() {
+            return Err(ModuleActionError::InvalidOutput {
+                action: name.to_string(),
+                violations,
+            });
+        }
+        Ok(output)
+    }
+
+    fn descriptor(&self, name: &str) -> ModuleActionResult<ModuleActionDescriptor> {
+        self.get(name)
+            .map(|action| action.descriptor())
+            .ok_or_else(|| ModuleActionError::UnknownAction(name.to_string()))
+    }
+}
+
+pub fn register_module_action<A>(
+    extensions: &mut ModuleRuntimeExtensions,
+    action: A,
+) -> ModuleActionResult<()>
+where
+    A: ModuleAction + 'static,
+{
+    let registry = extensions.get_or_insert_with::<Arc<ModuleActionRegistry>, _>(|| {
+        Arc::new(ModuleActionRegistry::default())
+    });
+    Arc::make_mut(registry).register(action)
+}
+
+pub fn module_action_registry_from_extensions(
+    extensions: &ModuleRuntimeExtensions,
+) -> Option<Arc<ModuleActionRegistry>> {
+    extensions.get::<Arc<ModuleActionRegistry>>().cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::MemoryTransport;
+    use crate::permissions::{Action, Resource};
+    use crate::types::UserRole;
+    use serde_json::json;
+
+    struct LockTopic;
+
+    #[async_trait]
+    impl ModuleAction for LockTopic {
+        fn descriptor(&self) -> ModuleActionDescriptor {
+            ModuleActionDescriptor {
+                name: "forum.lock_topic",
+                module_slug: "forum",
+                description: "Lock a topic",
+                input_schema: json!({
+                    "type": "object",
+                    "required": ["topic_id"],
+                    "properties": { "topic_id": { "type": "string", "format": "uuid" } }
+                }),
+                output_schema: json!({
+                    "type": "object",
+                    "required": ["locked"],
+                    "properties": { "locked": { "type": "boolean" } }
+                }),
+                required_permissions: vec![Permission::new(
+                    Resource::ForumTopics,
+                    Action::Moderate,
+                )],
+            }
+        }
+
+        async fn invoke(
+            &self,
+            _ctx: &ModuleActionContext,
+            input: Value,
+        ) -> ModuleActionResult<Value> {
+            Ok(json!({ "locked": true, "topic_id": input["topic_id"] }))
+        }
+    }
+
+    async fn context(security: SecurityContext) -> ModuleActionContext {
+        ModuleActionContext {
+            db: sea_orm::Database::connect("sqlite::memory:")
+                .await
+                .expect("in-memory sqlite should connect"),
+            tenant_id: Uuid::new_v4(),
+            actor_id: None,
+            security,
+            event_transport: Arc::new(MemoryTransport::new()),
+        }
+    }
+
+    #[test]
+    fn registers_through_runtime_extensions_once() {
+        let mut extensions = ModuleRuntimeExtensions::default();
+        register_module_action(&mut extensions, LockTopic).expect("first registration");
+        assert!(matches!(
+            register_module_action(&mut extensions, LockTopic),
+            Err(ModuleActionError::Duplicate(name)) if name == "forum.lock_topic"
+        ));
+
+        let registry = module_action_registry_from_extensions(&extensions)
+            .expect("registry should be present");
+        assert_eq!(registry.descriptors().len(), 1);
+        assert!(registry
+            .validate_template_input("forum.lock_topic", &json!({ "topic_id": "{{context.id}}" }))
+            .is_ok());
+        assert!(matches!(
+            registry.validate_template_input("forum.lock_topic", &json!({})),
+            Err(ModuleActionError::InvalidInput { .. })
+        ));
+        assert!(matches!(
+            registry.validate_template_input("forum.delete_everything", &json!({})),
+            Err(ModuleActionError::UnknownAction(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn invoke_enforces_permissions_and_schema() {
+        let mut registry = ModuleActionRegistry::default();
+        registry.register(LockTopic).expect("registration");
+        let input = json!({ "topic_id": Uuid::new_v4() });
+
+        let customer = context(SecurityContext::new(UserRole::Customer, None)).await;
+        assert!(matches!(
+            registry
+                .invoke("forum.lock_topic", &customer, input.clone())
+                .await,
+            Err(ModuleActionError::PermissionDenied { .. })
+        ));
+
+        let admin = context(SecurityContext::new(UserRole::Admin, None)).await;
+        assert!(matches!(
+            registry
+                .invoke(
+                    "forum.lock_topic",
+                    &admin,
+                    json!({ "topic_id": "{{context.id}}" })
+                )
+                .await,
+            Err(ModuleActionError::InvalidInput { .. })
+        ));
+        let output = registry
+            .invoke("forum.lock_topic", &admin, input)
+            .await
+            .expect("admin should be able to lock topics");
+        assert_eq!(output["locked"], true);
+    }
+}
@@ -473,6 +473,13 @@ impl Permission {
     pub const WORKFLOWS_EXECUTE: Self = Self::new(Resource::Workflows, Action::Execute);
     pub const WORKFLOWS_MANAGE: Self = Self::new(Resource::Workflows, Action::Manage);
 
+    pub const WEBHOOKS_CREATE: Self = Self::new(Resource::Webhooks, Action::Create);
+    pub const WEBHOOKS_READ: Self = Self::new(Resource::Webhooks, Action::Read);
+    pub const WEBHOOKS_UPDATE: Self = Self::new(Resource::Webhooks, Action::Update);
+    pub const WEBHOOKS_DELETE: Self = Self::new(Resource::Webhooks, Action::Delete);
+    pub const WEBHOOKS_LIST: Self = Self::new(Resource::Webhooks, Action::List);
+    pub const WEBHOOKS_MANAGE: Self = Self::new(Resource::Webhooks, Action::Manage);
+
     pub const WORKFLOW_EXECUTIONS_READ: Self =
         Self::new(Resource::WorkflowExecutions, Action::Read);
     pub const WORKFLOW_EXECUTIONS_LIST: Self =
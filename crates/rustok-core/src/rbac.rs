@@ -356,6 +356,11 @@ impl SecurityContext {
         &self.permissions
     }
 
+    /// Whether the context holds `permission` directly or via `Manage` on the resource.
+    pub fn has_permission(&self, permission: &Permission) -> bool {
+        has_effective_permission_in_set(&self.permissions, permission)
+    }
+
     pub fn system() -> Self {
         Self {
             role: UserRole::SuperAdmin,
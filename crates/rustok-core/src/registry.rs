@@ -221,6 +221,7 @@ mod tests {
         let ctx = ModuleEventListenerContext {
             db,
             extensions: &extensions,
+            event_transport: None,
         };
 
         let handlers = registry.build_event_listeners(&ctx);
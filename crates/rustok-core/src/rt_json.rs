@@ -271,6 +271,38 @@ fn sanitize_attrs(node_type: &str, obj: &mut Map<String, Value>) -> Result<(), S
                 json!({"provider": provider, "url": url}),
             );
         }
+        "mention" => {
+            let handle = obj
+                .get("attrs")
+                .and_then(|attrs| attrs.get("handle"))
+                .and_then(Value::as_str)
+                .map(|handle| handle.trim().trim_start_matches('@').to_ascii_lowercase())
+                .filter(|handle| is_valid_mention_handle(handle))
+                .ok_or_else(|| "rt_json mention.attrs.handle is invalid".to_string())?;
+            obj.insert("attrs".to_string(), json!({ "handle": handle }));
+        }
+        "blockquote" => {
+            let attrs = obj.get("attrs");
+            let source_kind = attrs
+                .and_then(|attrs| attrs.get("source_kind"))
+                .and_then(Value::as_str);
+            let source_id = attrs
+                .and_then(|attrs| attrs.get("source_id"))
+                .and_then(Value::as_str);
+            match (source_kind, source_id) {
+                (Some(kind), Some(id))
+                    if is_allowed_quote_source(kind) && uuid::Uuid::parse_str(id).is_ok() =>
+                {
+                    obj.insert(
+                        "attrs".to_string(),
+                        json!({ "source_kind": kind, "source_id": id }),
+                    );
+                }
+                _ => {
+                    obj.remove("attrs");
+                }
+            }
+        }
         _ => {
             obj.remove("attrs");
         }
@@ -278,6 +310,17 @@ fn sanitize_attrs(node_type: &str, obj: &mut Map<String, Value>) -> Result<(), S
     Ok(())
 }
 
+fn is_valid_mention_handle(handle: &str) -> bool {
+    (1..=64).contains(&handle.len())
+        && handle
+            .chars()
+            .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '_' || ch == '-')
+}
+
+fn is_allowed_quote_source(kind: &str) -> bool {
+    matches!(kind, "forum_reply" | "comment")
+}
+
 fn sanitize_link_attrs(obj: &mut Map<String, Value>) -> Result<(), String> {
     let href = obj
         .get("attrs")
@@ -305,6 +348,7 @@ fn is_allowed_node(node_type: &str) -> bool {
             | "horizontal_rule"
             | "hard_break"
             | "text"
+            | "mention"
             | "image"
             | "embed"
     )
@@ -314,7 +358,7 @@ fn is_allowed_mark(mark_type: &str) -> bool {
     matches!(mark_type, "bold" | "italic" | "strike" | "code" | "link")
 }
 
-fn is_allowed_url(raw: &str, allow_mailto: bool) -> bool {
+pub(crate) fn is_allowed_url(raw: &str, allow_mailto: bool) -> bool {
     let Ok(url) = Url::parse(raw) else {
         return false;
     };
@@ -471,4 +515,40 @@ mod tests {
         let marks = &res.sanitized["doc"]["content"][0]["content"][0]["marks"];
         assert_eq!(marks.as_array().unwrap().len(), 1);
     }
+
+    #[test]
+    fn keeps_mentions_and_quote_sources() {
+        let payload = json!({
+            "version":"rt_json_v1",
+            "locale":"en",
+            "doc":{
+                "type":"doc",
+                "content":[
+                    {"type":"blockquote","attrs":{"source_kind":"forum_reply","source_id":"7f1d6d3e-8a59-4a0e-9c5a-2d2a3c1b4e5f","style":"x"},"content":[]},
+                    {"type":"blockquote","attrs":{"source_kind":"page","source_id":"nope"},"content":[]},
+                    {"type":"paragraph","content":[{"type":"mention","attrs":{"handle":"@Alice","label":"x"}}]}
+                ]
+            }
+        });
+        let res =
+            validate_and_sanitize_rt_json(&payload, &RtJsonValidationConfig::for_locale("en"))
+                .expect("valid payload");
+        let content = &res.sanitized["doc"]["content"];
+        assert_eq!(
+            content[0]["attrs"],
+            json!({"source_kind":"forum_reply","source_id":"7f1d6d3e-8a59-4a0e-9c5a-2d2a3c1b4e5f"})
+        );
+        assert!(content[1].get("attrs").is_none());
+        assert_eq!(content[2]["content"][0]["attrs"], json!({"handle":"alice"}));
+
+        let invalid = json!({
+            "version":"rt_json_v1",
+            "locale":"en",
+            "doc":{"type":"doc","content":[{"type":"mention","attrs":{"handle":"no spaces"}}]}
+        });
+        assert!(
+            validate_and_sanitize_rt_json(&invalid, &RtJsonValidationConfig::for_locale("en"))
+                .is_err()
+        );
+    }
 }
@@ -20,7 +20,9 @@ pub mod validation;
 pub use audit::{AuditEvent, AuditEventType, AuditLogger, SecurityAudit, SiemConfig};
 pub use headers::{FrameOptions, SecurityHeaders, SecurityHeadersConfig};
 pub use rate_limit::{RateLimitConfig, RateLimitResult, RateLimiter};
-pub use validation::{InputValidator, SsrfProtection, ValidationResult};
+pub use validation::{
+    read_body_limited, InputValidator, ResolvedUrl, SsrfProtection, ValidationResult,
+};
 
 use serde::{Deserialize, Serialize};
 
@@ -9,6 +9,7 @@
 //! - SSRF (Server-Side Request Forgery)
 
 use std::collections::HashSet;
+use std::net::{IpAddr, SocketAddr};
 
 use email_address::EmailAddress;
 use regex::Regex;
@@ -209,6 +210,51 @@ pub struct SsrfProtection {
     allowed_hosts: HashSet<String>,
     /// Blocked schemes
     blocked_schemes: HashSet<String>,
+    /// Whether loopback and private network targets are permitted
+    allow_private: bool,
+}
+
+/// A URL that passed SSRF validation together with the addresses its host resolved to.
+#[derive(Debug, Clone)]
+pub struct ResolvedUrl {
+    pub url: Url,
+    pub host: String,
+    pub addrs: Vec<SocketAddr>,
+}
+
+impl ResolvedUrl {
+    /// Client builder pinned to the validated addresses with redirects disabled, so neither DNS
+    /// rebinding nor a redirect can move the request to an address that was not checked.
+    pub fn client_builder(&self) -> reqwest::ClientBuilder {
+        reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .resolve_to_addrs(&self.host, &self.addrs)
+    }
+}
+
+/// Read at most `limit` bytes of a response body.
+///
+/// Returns the bytes read and whether the body was longer than `limit`.
+pub async fn read_body_limited(
+    mut response: reqwest::Response,
+    limit: usize,
+) -> reqwest::Result<(Vec<u8>, bool)> {
+    if response
+        .content_length()
+        .is_some_and(|length| length > limit as u64)
+    {
+        return Ok((Vec::new(), true));
+    }
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        let remaining = limit - body.len();
+        if chunk.len() > remaining {
+            body.extend_from_slice(&chunk[..remaining]);
+            return Ok((body, true));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok((body, false))
 }
 
 impl Default for SsrfProtection {
@@ -228,9 +274,16 @@ impl SsrfProtection {
         Self {
             allowed_hosts: HashSet::new(),
             blocked_schemes,
+            allow_private: false,
         }
     }
 
+    /// Permit loopback and private network targets (local development and tests only)
+    pub fn allow_private_networks(mut self) -> Self {
+        self.allow_private = true;
+        self
+    }
+
     /// Add allowed host
     pub fn allow_host(mut self, host: impl Into<String>) -> Self {
         self.allowed_hosts
@@ -285,15 +338,13 @@ impl SsrfProtection {
             };
         }
 
-        if let Ok(ip) = normalized_host.parse::<std::net::IpAddr>() {
-            if self.is_private_ip(&ip) {
-                return ValidationResult::Invalid {
-                    reason: "Private IP addresses are not allowed".to_string(),
-                };
+        if let Ok(ip) = normalized_host.parse::<IpAddr>() {
+            if let invalid @ ValidationResult::Invalid { .. } = self.validate_ip(&ip) {
+                return invalid;
             }
         }
 
-        if normalized_host == "localhost" {
+        if normalized_host == "localhost" && !self.allow_private {
             return ValidationResult::Invalid {
                 reason: "Localhost URLs are not allowed".to_string(),
             };
@@ -302,6 +353,54 @@ impl SsrfProtection {
         ValidationResult::Valid
     }
 
+    /// Validate an address a host resolved to
+    pub fn validate_ip(&self, ip: &IpAddr) -> ValidationResult {
+        if !self.allow_private && self.is_private_ip(ip) {
+            return ValidationResult::Invalid {
+                reason: "Private IP addresses are not allowed".to_string(),
+            };
+        }
+        ValidationResult::Valid
+    }
+
+    /// Validate `url`, resolve its host and validate every resolved address.
+    ///
+    /// Connect through [`ResolvedUrl::client_builder`] so the request goes to the checked
+    /// addresses only.
+    pub async fn resolve(&self, url: &str) -> Result<ResolvedUrl, String> {
+        if let ValidationResult::Invalid { reason } = self.validate_url(url) {
+            return Err(reason);
+        }
+        let parsed = Url::parse(url).map_err(|_| "Invalid URL format".to_string())?;
+        let host = parsed
+            .host_str()
+            .ok_or_else(|| "URL must have a host".to_string())?
+            .to_string();
+        let port = parsed
+            .port_or_known_default()
+            .ok_or_else(|| "URL must have a port".to_string())?;
+        let lookup_host = host.trim_start_matches('[').trim_end_matches(']');
+
+        let addrs: Vec<SocketAddr> = tokio::net::lookup_host((lookup_host, port))
+            .await
+            .map_err(|error| format!("Failed to resolve '{host}': {error}"))?
+            .collect();
+        if addrs.is_empty() {
+            return Err(format!("'{host}' did not resolve to any address"));
+        }
+        for addr in &addrs {
+            if let ValidationResult::Invalid { reason } = self.validate_ip(&addr.ip()) {
+                return Err(format!("'{host}' resolves to {}: {reason}", addr.ip()));
+            }
+        }
+
+        Ok(ResolvedUrl {
+            url: parsed,
+            host,
+            addrs,
+        })
+    }
+
     /// Validate every hop in an HTTP redirect chain.
     pub fn validate_redirect_chain<I, S>(&self, urls: I) -> ValidationResult
     where
@@ -349,6 +448,9 @@ impl SsrfProtection {
                     || ipv4.is_multicast()
             }
             std::net::IpAddr::V6(ipv6) => {
+                if let Some(ipv4) = ipv6.to_ipv4_mapped() {
+                    return self.is_private_ip(&IpAddr::V4(ipv4));
+                }
                 ipv6.is_loopback()
                     || ipv6.is_unspecified()
                     || ipv6.is_unique_local()
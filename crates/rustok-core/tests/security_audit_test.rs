@@ -315,6 +315,37 @@ fn test_ssrf_protection_blocks_private_ips() {
     }
 }
 
+#[test]
+fn test_ssrf_protection_blocks_ipv4_mapped_ipv6() {
+    let ssrf = SsrfProtection::new();
+
+    for url in [
+        "http://[::ffff:127.0.0.1]/",
+        "http://[::ffff:169.254.169.254]/",
+    ] {
+        assert!(
+            matches!(ssrf.validate_url(url), ValidationResult::Invalid { .. }),
+            "Mapped private IP should be blocked: {}",
+            url
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_ssrf_resolve_checks_resolved_addresses() {
+    let ssrf = SsrfProtection::new();
+    assert!(ssrf.resolve("http://localhost:8080/").await.is_err());
+    assert!(ssrf.resolve("http://169.254.169.254/latest").await.is_err());
+
+    let resolved = SsrfProtection::new()
+        .allow_private_networks()
+        .resolve("http://127.0.0.1:8080/hook")
+        .await
+        .expect("private targets are allowed when opted in");
+    assert_eq!(resolved.host, "127.0.0.1");
+    assert_eq!(resolved.addrs, vec!["127.0.0.1:8080".parse().unwrap()]);
+}
+
 #[test]
 fn test_ssrf_protection_blocks_dangerous_schemes() {
     let ssrf = SsrfProtection::new();
@@ -0,0 +1,96 @@
+use async_trait::async_trait;
+use rustok_core::{
+    ModuleAction, ModuleActionContext, ModuleActionDescriptor, ModuleActionError,
+    ModuleActionResult, Permission,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{CustomerService, UpdateCustomerInput};
+
+/// `customer.tag` — adds a tag to `metadata.tags` of a customer.
+#[derive(Clone, Default)]
+pub struct TagCustomerAction;
+
+#[derive(Deserialize)]
+struct TagCustomerInput {
+    customer_id: Uuid,
+    tag: String,
+}
+
+#[async_trait]
+impl ModuleAction for TagCustomerAction {
+    fn descriptor(&self) -> ModuleActionDescriptor {
+        ModuleActionDescriptor {
+            name: "customer.tag",
+            module_slug: "customer",
+            description: "Add a tag to a customer",
+            input_schema: json!({
+                "type": "object",
+                "required": ["customer_id", "tag"],
+                "additionalProperties": false,
+                "properties": {
+                    "customer_id": { "type": "string", "format": "uuid" },
+                    "tag": { "type": "string", "minLength": 1, "maxLength": 64 }
+                }
+            }),
+            output_schema: json!({
+                "type": "object",
+                "required": ["customer_id", "tags"],
+                "properties": {
+                    "customer_id": { "type": "string", "format": "uuid" },
+                    "tags": { "type": "array", "items": { "type": "string" } }
+                }
+            }),
+            required_permissions: vec![Permission::CUSTOMERS_UPDATE],
+        }
+    }
+
+    async fn invoke(&self, ctx: &ModuleActionContext, input: Value) -> ModuleActionResult<Value> {
+        let input: TagCustomerInput =
+            serde_json::from_value(input).map_err(ModuleActionError::failed)?;
+        let tag = input.tag.trim().to_string();
+        let service = CustomerService::new(ctx.db.clone());
+        let customer = service
+            .get_customer(ctx.tenant_id, input.customer_id)
+            .await
+            .map_err(ModuleActionError::failed)?;
+
+        let mut metadata = match customer.metadata {
+            Value::Object(fields) => fields,
+            _ => Default::default(),
+        };
+        let mut tags: Vec<String> = metadata
+            .get("tags")
+            .and_then(Value::as_array)
+            .map(|tags| {
+                tags.iter()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        if !tags.contains(&tag) {
+            tags.push(tag);
+            metadata.insert("tags".to_string(), json!(tags));
+            service
+                .update_customer(
+                    ctx.tenant_id,
+                    input.customer_id,
+                    UpdateCustomerInput {
+                        email: None,
+                        first_name: None,
+                        last_name: None,
+                        phone: None,
+                        locale: None,
+                        metadata: Some(Value::Object(metadata)),
+                    },
+                )
+                .await
+                .map_err(ModuleActionError::failed)?;
+        }
+
+        Ok(json!({ "customer_id": input.customer_id, "tags": tags }))
+    }
+}
@@ -1,9 +1,14 @@
 //! Canonical event contracts crate for RusToK.
 
+mod registry;
 mod schema;
 mod types;
 pub mod validation;
 
+pub use registry::{
+    schema_compatibility, EventSchemaRegistry, EventUpcastError, EventUpcaster,
+    SchemaCompatibility, UpcastFn, EVENT_REGISTRY, EVENT_UPCASTERS, LEGACY_EVENT_SCHEMAS,
+};
 pub use schema::{event_schema, EventSchema, FieldSchema, EVENT_SCHEMAS};
 pub use types::{DomainEvent, EventEnvelope};
 pub use validation::{EventValidationError, ValidateEvent};
@@ -0,0 +1,406 @@
+//! Versioned event schema registry and payload upcasting.
+//!
+//! [`EVENT_SCHEMAS`] holds the current schema of every event type; schemas of
+//! earlier versions stay in [`LEGACY_EVENT_SCHEMAS`] so stored payloads can be
+//! checked against the version they were written with. When a `DomainEvent`
+//! variant changes incompatibly, bump its `schema_version`, move the old schema
+//! to [`LEGACY_EVENT_SCHEMAS`] and add an [`EventUpcaster`] from the old
+//! version to [`EVENT_UPCASTERS`]. Stored envelopes are upcast to the current
+//! version before they are deserialized and dispatched
+//! ([`EventEnvelope::from_stored_json`]).
+
+use serde_json::Value;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::schema::{EventSchema, EVENT_SCHEMAS};
+use crate::types::EventEnvelope;
+
+/// Schemas of superseded event versions, kept for replaying stored events.
+pub const LEGACY_EVENT_SCHEMAS: &[EventSchema] = &[];
+
+/// Payload migrations between consecutive event versions.
+pub const EVENT_UPCASTERS: &[EventUpcaster] = &[];
+
+/// The platform registry: current and legacy schemas plus upcasters.
+pub const EVENT_REGISTRY: EventSchemaRegistry =
+    EventSchemaRegistry::new(EVENT_SCHEMAS, LEGACY_EVENT_SCHEMAS, EVENT_UPCASTERS);
+
+/// Migrates the `data` of an event from `from_version` to `from_version + 1`.
+pub type UpcastFn = fn(Value) -> Result<Value, String>;
+
+#[derive(Clone, Copy)]
+pub struct EventUpcaster {
+    pub event_type: &'static str,
+    pub from_version: u16,
+    pub upcast: UpcastFn,
+}
+
+impl std::fmt::Debug for EventUpcaster {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventUpcaster")
+            .field("event_type", &self.event_type)
+            .field("from_version", &self.from_version)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum EventUpcastError {
+    #[error("Invalid stored event envelope: {0}")]
+    InvalidEnvelope(String),
+
+    #[error("No upcaster for '{event_type}' from schema version {version}")]
+    MissingUpcaster { event_type: String, version: u16 },
+
+    #[error("Upcasting '{event_type}' from schema version {version} failed: {reason}")]
+    Failed {
+        event_type: String,
+        version: u16,
+        reason: String,
+    },
+
+    #[error("Upcast '{event_type}' payload does not match schema version {version}: {}", errors.join("; "))]
+    SchemaMismatch {
+        event_type: String,
+        version: u16,
+        errors: Vec<String>,
+    },
+}
+
+/// Differences between two versions of an event schema. Backward issues
+/// break readers of the newer version on payloads of the older one; forward
+/// issues break readers of the older version on payloads of the newer one.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaCompatibility {
+    pub backward: Vec<String>,
+    pub forward: Vec<String>,
+}
+
+impl SchemaCompatibility {
+    pub fn is_backward_compatible(&self) -> bool {
+        self.backward.is_empty()
+    }
+
+    pub fn is_forward_compatible(&self) -> bool {
+        self.forward.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.is_backward_compatible() && self.is_forward_compatible()
+    }
+}
+
+/// Compares two JSON schemas produced by [`EventSchema::to_json_schema`].
+///
+/// Adding or removing optional fields is compatible both ways. Adding a
+/// required field, or making an optional one required, is only forward
+/// compatible; removing a required field, or making it optional, is only
+/// backward compatible; changing a field type is neither.
+pub fn schema_compatibility(previous: &Value, next: &Value) -> SchemaCompatibility {
+    let mut compatibility = SchemaCompatibility::default();
+    let previous_fields = schema_fields(previous);
+    let next_fields = schema_fields(next);
+
+    for (name, (data_type, required)) in &previous_fields {
+        match next_fields.get(name) {
+            None if *required => compatibility
+                .forward
+                .push(format!("required field '{name}' was removed")),
+            None => {}
+            Some((next_type, next_required)) => {
+                if next_type != data_type {
+                    let change =
+                        format!("field '{name}' changed type from {data_type} to {next_type}");
+                    compatibility.backward.push(change.clone());
+                    compatibility.forward.push(change);
+                } else if *required && !next_required {
+                    compatibility
+                        .forward
+                        .push(format!("field '{name}' became optional"));
+                } else if !required && *next_required {
+                    compatibility
+                        .backward
+                        .push(format!("field '{name}' became required"));
+                }
+            }
+        }
+    }
+    for (name, (_, required)) in &next_fields {
+        if *required && !previous_fields.contains_key(name) {
+            compatibility
+                .backward
+                .push(format!("required field '{name}' was added"));
+        }
+    }
+
+    compatibility
+}
+
+/// `name -> (type, required)` of a JSON schema's properties.
+fn schema_fields(schema: &Value) -> std::collections::BTreeMap<String, (String, bool)> {
+    let required: Vec<&str> = schema["required"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect();
+    schema["properties"]
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(name, property)| {
+            let data_type = property["type"].as_str().unwrap_or_default().to_string();
+            (name.clone(), (data_type, required.contains(&name.as_str())))
+        })
+        .collect()
+}
+
+impl EventSchema {
+    /// Checks an event's `data` object against this schema: required fields
+    /// are present, values match their declared type and no undeclared
+    /// fields appear.
+    pub fn validate_payload(&self, data: &Value) -> Result<(), Vec<String>> {
+        let Some(object) = data.as_object() else {
+            return Err(vec!["payload must be an object".to_string()]);
+        };
+
+        let mut errors = Vec::new();
+        for field in self.fields {
+            match object.get(field.name) {
+                None | Some(Value::Null) if field.optional => {}
+                None | Some(Value::Null) => {
+                    errors.push(format!("missing required field '{}'", field.name))
+                }
+                Some(value) if !matches_type(field.data_type, value) => errors.push(format!(
+                    "field '{}' is not a valid {}",
+                    field.name, field.data_type
+                )),
+                Some(_) => {}
+            }
+        }
+        for name in object.keys() {
+            if !self.fields.iter().any(|field| field.name == name) {
+                errors.push(format!("undeclared field '{name}'"));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+fn matches_type(data_type: &str, value: &Value) -> bool {
+    match data_type {
+        "uuid" => value.as_str().is_some_and(|s| Uuid::parse_str(s).is_ok()),
+        "string" => value.is_string(),
+        "bool" => value.is_boolean(),
+        "int32" => value.as_i64().is_some_and(|n| i32::try_from(n).is_ok()),
+        "int64" => value.as_i64().is_some(),
+        "uint64" => value.is_u64(),
+        "array" => value.is_array(),
+        "array<uuid>" => value
+            .as_array()
+            .is_some_and(|items| items.iter().all(|item| matches_type("uuid", item))),
+        _ => true,
+    }
+}
+
+/// Current and legacy schemas of every event type plus the upcasters that
+/// migrate payloads between versions.
+#[derive(Clone, Copy, Debug)]
+pub struct EventSchemaRegistry {
+    current: &'static [EventSchema],
+    legacy: &'static [EventSchema],
+    upcasters: &'static [EventUpcaster],
+}
+
+impl EventSchemaRegistry {
+    pub const fn new(
+        current: &'static [EventSchema],
+        legacy: &'static [EventSchema],
+        upcasters: &'static [EventUpcaster],
+    ) -> Self {
+        Self {
+            current,
+            legacy,
+            upcasters,
+        }
+    }
+
+    pub fn current(&self, event_type: &str) -> Option<&'static EventSchema> {
+        self.current
+            .iter()
+            .find(|schema| schema.event_type == event_type)
+    }
+
+    pub fn schema(&self, event_type: &str, version: u16) -> Option<&'static EventSchema> {
+        self.current
+            .iter()
+            .chain(self.legacy)
+            .find(|schema| schema.event_type == event_type && schema.version == version)
+    }
+
+    /// Registered versions of an event type, oldest first.
+    pub fn versions(&self, event_type: &str) -> Vec<u16> {
+        let mut versions: Vec<u16> = self
+            .current
+            .iter()
+            .chain(self.legacy)
+            .filter(|schema| schema.event_type == event_type)
+            .map(|schema| schema.version)
+            .collect();
+        versions.sort_unstable();
+        versions
+    }
+
+    pub fn json_schema(&self, event_type: &str, version: u16) -> Option<Value> {
+        self.schema(event_type, version)
+            .map(EventSchema::to_json_schema)
+    }
+
+    fn upcaster(&self, event_type: &str, from_version: u16) -> Option<&'static EventUpcaster> {
+        self.upcasters.iter().find(|upcaster| {
+            upcaster.event_type == event_type && upcaster.from_version == from_version
+        })
+    }
+
+    /// Migrates `data` written with `version` to the current version of the
+    /// event type and returns it with the version reached. Payloads of the
+    /// current version, of a newer version, or of an unregistered event type
+    /// are returned unchanged.
+    pub fn upcast(
+        &self,
+        event_type: &str,
+        version: u16,
+        mut data: Value,
+    ) -> Result<(u16, Value), EventUpcastError> {
+        let Some(current) = self.current(event_type) else {
+            return Ok((version, data));
+        };
+
+        let mut version = version;
+        while version < current.version {
+            let upcaster = self.upcaster(event_type, version).ok_or_else(|| {
+                EventUpcastError::MissingUpcaster {
+                    event_type: event_type.to_string(),
+                    version,
+                }
+            })?;
+            data = (upcaster.upcast)(data).map_err(|reason| EventUpcastError::Failed {
+                event_type: event_type.to_string(),
+                version,
+                reason,
+            })?;
+            version += 1;
+
+            if let Some(schema) = self.schema(event_type, version) {
+                schema.validate_payload(&data).map_err(|errors| {
+                    EventUpcastError::SchemaMismatch {
+                        event_type: event_type.to_string(),
+                        version,
+                        errors,
+                    }
+                })?;
+            }
+        }
+
+        Ok((version, data))
+    }
+
+    /// Upcasts the event of a serialized [`EventEnvelope`] in place and sets
+    /// its `schema_version` to the version reached.
+    pub fn upcast_envelope(&self, mut envelope: Value) -> Result<Value, EventUpcastError> {
+        let event_type = envelope["event_type"]
+            .as_str()
+            .ok_or_else(|| EventUpcastError::InvalidEnvelope("missing 'event_type'".into()))?
+            .to_string();
+        let version = envelope["schema_version"]
+            .as_u64()
+            .and_then(|version| u16::try_from(version).ok())
+            .ok_or_else(|| EventUpcastError::InvalidEnvelope("missing 'schema_version'".into()))?;
+        if self
+            .current(&event_type)
+            .is_none_or(|current| version >= current.version)
+        {
+            return Ok(envelope);
+        }
+
+        let event = envelope
+            .get_mut("event")
+            .and_then(Value::as_object_mut)
+            .ok_or_else(|| EventUpcastError::InvalidEnvelope("missing 'event'".into()))?;
+        let data = event.remove("data").unwrap_or(Value::Null);
+        let (version, data) = self.upcast(&event_type, version, data)?;
+        if !data.is_null() {
+            event.insert("data".to_string(), data);
+        }
+        envelope["schema_version"] = Value::from(version);
+
+        Ok(envelope)
+    }
+
+    /// Consistency problems of the registry itself: every legacy version must
+    /// be older than the current one and reach it through upcasters, and
+    /// every upcaster must start at a registered version.
+    pub fn check(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        let mut seen = std::collections::BTreeSet::new();
+        for schema in self.current.iter().chain(self.legacy) {
+            if !seen.insert((schema.event_type, schema.version)) {
+                problems.push(format!(
+                    "schema '{}' v{} is registered twice",
+                    schema.event_type, schema.version
+                ));
+            }
+        }
+        for legacy in self.legacy {
+            let Some(current) = self.current(legacy.event_type) else {
+                problems.push(format!(
+                    "legacy schema '{}' v{} has no current schema",
+                    legacy.event_type, legacy.version
+                ));
+                continue;
+            };
+            if legacy.version >= current.version {
+                problems.push(format!(
+                    "legacy schema '{}' v{} is not older than current v{}",
+                    legacy.event_type, legacy.version, current.version
+                ));
+            }
+            for version in legacy.version..current.version {
+                if self.upcaster(legacy.event_type, version).is_none() {
+                    problems.push(format!(
+                        "'{}' has no upcaster from v{version}",
+                        legacy.event_type
+                    ));
+                }
+            }
+        }
+        for upcaster in self.upcasters {
+            if self
+                .schema(upcaster.event_type, upcaster.from_version)
+                .is_none()
+            {
+                problems.push(format!(
+                    "upcaster for '{}' starts at unregistered v{}",
+                    upcaster.event_type, upcaster.from_version
+                ));
+            }
+        }
+        problems
+    }
+}
+
+impl EventEnvelope {
+    /// Deserializes a stored envelope (outbox row, transport message) after
+    /// upcasting its event to the current schema version.
+    pub fn from_stored_json(value: Value) -> Result<Self, EventUpcastError> {
+        let value = EVENT_REGISTRY.upcast_envelope(value)?;
+        serde_json::from_value(value)
+            .map_err(|err| EventUpcastError::InvalidEnvelope(err.to_string()))
+    }
+}
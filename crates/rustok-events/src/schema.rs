@@ -105,6 +105,13 @@ const PROFILE_UPDATED_FIELDS: &[FieldSchema] = &[
     field!("locale", "string", optional),
 ];
 const USER_DELETED_FIELDS: &[FieldSchema] = &[field!("user_id", "uuid")];
+const USER_MENTIONED_FIELDS: &[FieldSchema] = &[
+    field!("mentioned_user_id", "uuid"),
+    field!("author_id", "uuid", optional),
+    field!("source_kind", "string"),
+    field!("source_id", "uuid"),
+    field!("target_id", "uuid"),
+];
 
 const PRODUCT_ID_FIELDS: &[FieldSchema] = &[field!("product_id", "uuid")];
 const VARIANT_FIELDS: &[FieldSchema] =
@@ -172,6 +179,12 @@ const BLOG_POST_ARCHIVED_FIELDS: &[FieldSchema] = &[
     field!("reason", "string", optional),
 ];
 const BLOG_POST_DELETED_FIELDS: &[FieldSchema] = &[field!("post_id", "uuid")];
+const BLOG_COMMENT_CREATED_FIELDS: &[FieldSchema] = &[
+    field!("post_id", "uuid"),
+    field!("comment_id", "uuid"),
+    field!("parent_comment_id", "uuid", optional),
+    field!("author_id", "uuid", optional),
+];
 
 const FORUM_TOPIC_CREATED_FIELDS: &[FieldSchema] = &[
     field!("topic_id", "uuid"),
@@ -195,9 +208,16 @@ const FORUM_TOPIC_PINNED_FIELDS: &[FieldSchema] = &[
     field!("is_pinned", "bool"),
     field!("moderator_id", "uuid", optional),
 ];
+const FORUM_TOPIC_SOLUTION_MARKED_FIELDS: &[FieldSchema] = &[
+    field!("topic_id", "uuid"),
+    field!("reply_id", "uuid"),
+    field!("author_id", "uuid", optional),
+    field!("moderator_id", "uuid", optional),
+];
 const FORUM_REPLY_STATUS_CHANGED_FIELDS: &[FieldSchema] = &[
     field!("reply_id", "uuid"),
     field!("topic_id", "uuid"),
+    field!("old_status", "string"),
     field!("new_status", "string"),
     field!("moderator_id", "uuid", optional),
 ];
@@ -419,6 +439,12 @@ pub const EVENT_SCHEMAS: &[EventSchema] = &[
         description: "A user was deleted.",
         fields: USER_DELETED_FIELDS,
     },
+    EventSchema {
+        event_type: "user.mentioned",
+        version: 1,
+        description: "A user was @mentioned in a forum reply or comment.",
+        fields: USER_MENTIONED_FIELDS,
+    },
     EventSchema {
         event_type: "product.created",
         version: 1,
@@ -557,6 +583,12 @@ pub const EVENT_SCHEMAS: &[EventSchema] = &[
         description: "Blog post deleted.",
         fields: BLOG_POST_DELETED_FIELDS,
     },
+    EventSchema {
+        event_type: "blog.comment.created",
+        version: 1,
+        description: "Comment added to a blog post.",
+        fields: BLOG_COMMENT_CREATED_FIELDS,
+    },
     EventSchema {
         event_type: "forum.topic.created",
         version: 1,
@@ -581,6 +613,12 @@ pub const EVENT_SCHEMAS: &[EventSchema] = &[
         description: "Forum topic pinned state changed.",
         fields: FORUM_TOPIC_PINNED_FIELDS,
     },
+    EventSchema {
+        event_type: "forum.topic.solution_marked",
+        version: 1,
+        description: "A forum reply was accepted as the topic solution.",
+        fields: FORUM_TOPIC_SOLUTION_MARKED_FIELDS,
+    },
     EventSchema {
         event_type: "forum.reply.status_changed",
         version: 1,
@@ -154,6 +154,13 @@ pub enum DomainEvent {
     UserDeleted {
         user_id: Uuid,
     },
+    UserMentioned {
+        mentioned_user_id: Uuid,
+        author_id: Option<Uuid>,
+        source_kind: String,
+        source_id: Uuid,
+        target_id: Uuid,
+    },
 
     // ════════════════════════════════════════════════════════════════
     // COMMERCE EVENTS (для будущего модуля)
@@ -267,6 +274,12 @@ pub enum DomainEvent {
     BlogPostDeleted {
         post_id: Uuid,
     },
+    BlogCommentCreated {
+        post_id: Uuid,
+        comment_id: Uuid,
+        parent_comment_id: Option<Uuid>,
+        author_id: Option<Uuid>,
+    },
 
     // ════════════════════════════════════════════════════════════════
     // FORUM EVENTS
@@ -293,6 +306,12 @@ pub enum DomainEvent {
         is_pinned: bool,
         moderator_id: Option<Uuid>,
     },
+    ForumTopicSolutionMarked {
+        topic_id: Uuid,
+        reply_id: Uuid,
+        author_id: Option<Uuid>,
+        moderator_id: Option<Uuid>,
+    },
     ForumReplyStatusChanged {
         reply_id: Uuid,
         topic_id: Uuid,
@@ -537,6 +556,7 @@ impl DomainEvent {
             Self::UserUpdated { .. } => "user.updated",
             Self::ProfileUpdated { .. } => "profile.updated",
             Self::UserDeleted { .. } => "user.deleted",
+            Self::UserMentioned { .. } => "user.mentioned",
 
             Self::ProductCreated { .. } => "product.created",
             Self::ProductUpdated { .. } => "product.updated",
@@ -564,11 +584,13 @@ impl DomainEvent {
             Self::BlogPostUpdated { .. } => "blog.post.updated",
             Self::BlogPostArchived { .. } => "blog.post.archived",
             Self::BlogPostDeleted { .. } => "blog.post.deleted",
+            Self::BlogCommentCreated { .. } => "blog.comment.created",
 
             Self::ForumTopicCreated { .. } => "forum.topic.created",
             Self::ForumTopicReplied { .. } => "forum.topic.replied",
             Self::ForumTopicStatusChanged { .. } => "forum.topic.status_changed",
             Self::ForumTopicPinned { .. } => "forum.topic.pinned",
+            Self::ForumTopicSolutionMarked { .. } => "forum.topic.solution_marked",
             Self::ForumReplyStatusChanged { .. } => "forum.reply.status_changed",
             Self::TopicPromotedToPost { .. } => "content.topic.promoted_to_post",
             Self::PostDemotedToTopic { .. } => "content.post.demoted_to_topic",
@@ -646,6 +668,7 @@ impl DomainEvent {
             Self::UserUpdated { .. } => 1,
             Self::ProfileUpdated { .. } => 1,
             Self::UserDeleted { .. } => 1,
+            Self::UserMentioned { .. } => 1,
 
             // Commerce events (v1)
             Self::ProductCreated { .. } => 1,
@@ -677,12 +700,14 @@ impl DomainEvent {
             Self::BlogPostUpdated { .. } => 1,
             Self::BlogPostArchived { .. } => 1,
             Self::BlogPostDeleted { .. } => 1,
+            Self::BlogCommentCreated { .. } => 1,
 
             // Forum events (v1)
             Self::ForumTopicCreated { .. } => 1,
             Self::ForumTopicReplied { .. } => 1,
             Self::ForumTopicStatusChanged { .. } => 1,
             Self::ForumTopicPinned { .. } => 1,
+            Self::ForumTopicSolutionMarked { .. } => 1,
             Self::ForumReplyStatusChanged { .. } => 1,
             Self::TopicPromotedToPost { .. } => 1,
             Self::PostDemotedToTopic { .. } => 1,
@@ -913,6 +938,21 @@ impl ValidateEvent for DomainEvent {
                 }
                 Ok(())
             }
+            Self::UserMentioned {
+                mentioned_user_id,
+                author_id,
+                source_kind,
+                source_id,
+                target_id,
+            } => {
+                validators::validate_not_nil_uuid("mentioned_user_id", mentioned_user_id)?;
+                validators::validate_optional_uuid("author_id", author_id)?;
+                validators::validate_not_empty("source_kind", source_kind)?;
+                validators::validate_max_length("source_kind", source_kind, 32)?;
+                validators::validate_not_nil_uuid("source_id", source_id)?;
+                validators::validate_not_nil_uuid("target_id", target_id)?;
+                Ok(())
+            }
 
             // ════════════════════════════════════════════════════════════════
             // COMMERCE EVENTS - Products
@@ -1117,6 +1157,18 @@ impl ValidateEvent for DomainEvent {
                 }
                 Ok(())
             }
+            Self::BlogCommentCreated {
+                post_id,
+                comment_id,
+                parent_comment_id,
+                author_id,
+            } => {
+                validators::validate_not_nil_uuid("post_id", post_id)?;
+                validators::validate_not_nil_uuid("comment_id", comment_id)?;
+                validators::validate_optional_uuid("parent_comment_id", parent_comment_id)?;
+                validators::validate_optional_uuid("author_id", author_id)?;
+                Ok(())
+            }
 
             // ════════════════════════════════════════════════════════════════
             // FORUM EVENTS
@@ -1173,6 +1225,18 @@ impl ValidateEvent for DomainEvent {
                 validators::validate_optional_uuid("moderator_id", moderator_id)?;
                 Ok(())
             }
+            Self::ForumTopicSolutionMarked {
+                topic_id,
+                reply_id,
+                author_id,
+                moderator_id,
+            } => {
+                validators::validate_not_nil_uuid("topic_id", topic_id)?;
+                validators::validate_not_nil_uuid("reply_id", reply_id)?;
+                validators::validate_optional_uuid("author_id", author_id)?;
+                validators::validate_optional_uuid("moderator_id", moderator_id)?;
+                Ok(())
+            }
             Self::ForumReplyStatusChanged {
                 reply_id,
                 topic_id,
@@ -1,6 +1,6 @@
 use rustok_events::{
     event_schema, DomainEvent, EventEnvelope, RootDomainEvent, RootEventEnvelope, ValidateEvent,
-    EVENT_SCHEMAS,
+    EVENT_REGISTRY, EVENT_SCHEMAS,
 };
 use uuid::Uuid;
 
@@ -75,6 +75,13 @@ fn sample_events() -> Vec<DomainEvent> {
             locale: Some("en".to_string()),
         },
         DomainEvent::UserDeleted { user_id: id(23) },
+        DomainEvent::UserMentioned {
+            mentioned_user_id: id(23),
+            author_id: Some(id(22)),
+            source_kind: "forum_reply".to_string(),
+            source_id: id(61),
+            target_id: id(60),
+        },
         DomainEvent::ProductCreated { product_id: id(24) },
         DomainEvent::ProductUpdated { product_id: id(25) },
         DomainEvent::ProductPublished { product_id: id(26) },
@@ -158,6 +165,12 @@ fn sample_events() -> Vec<DomainEvent> {
             reason: Some("scheduled_cleanup".to_string()),
         },
         DomainEvent::BlogPostDeleted { post_id: id(56) },
+        DomainEvent::BlogCommentCreated {
+            post_id: id(56),
+            comment_id: id(57),
+            parent_comment_id: Some(id(58)),
+            author_id: Some(id(59)),
+        },
         DomainEvent::ForumTopicCreated {
             topic_id: id(57),
             category_id: id(58),
@@ -180,6 +193,12 @@ fn sample_events() -> Vec<DomainEvent> {
             is_pinned: true,
             moderator_id: Some(id(66)),
         },
+        DomainEvent::ForumTopicSolutionMarked {
+            topic_id: id(68),
+            reply_id: id(67),
+            author_id: Some(id(22)),
+            moderator_id: Some(id(69)),
+        },
         DomainEvent::ForumReplyStatusChanged {
             reply_id: id(67),
             topic_id: id(68),
@@ -442,3 +461,25 @@ fn event_schema_registry_has_unique_event_types() {
         assert!(schema.version >= 1, "schema versions must start at 1");
     }
 }
+
+#[test]
+fn every_domain_event_payload_matches_its_registered_schema() {
+    for event in sample_events() {
+        let schema = EVENT_REGISTRY
+            .schema(event.event_type(), event.schema_version())
+            .expect("current schema must be registered");
+        let data = serde_json::to_value(&event).expect("event should serialize")["data"].clone();
+        if let Err(errors) = schema.validate_payload(&data) {
+            panic!(
+                "'{}' payload drifted from its schema: {}",
+                event.event_type(),
+                errors.join("; ")
+            );
+        }
+    }
+}
+
+#[test]
+fn event_schema_registry_is_consistent() {
+    assert_eq!(EVENT_REGISTRY.check(), Vec::<String>::new());
+}
@@ -0,0 +1,267 @@
+use rustok_events::{
+    schema_compatibility, DomainEvent, EventEnvelope, EventSchema, EventSchemaRegistry,
+    EventUpcastError, EventUpcaster, FieldSchema, EVENT_REGISTRY, EVENT_SCHEMAS,
+    LEGACY_EVENT_SCHEMAS,
+};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+/// Schemas of every released event version. Regenerate after a compatible
+/// change or a version bump with
+/// `RUSTOK_BLESS_EVENT_SCHEMAS=1 cargo test -p rustok-events --test schema_compatibility`.
+const PUBLISHED_SCHEMAS_PATH: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/published_event_schemas.json"
+);
+
+fn registered_schemas() -> Vec<Value> {
+    let mut schemas: Vec<&EventSchema> = EVENT_SCHEMAS.iter().chain(LEGACY_EVENT_SCHEMAS).collect();
+    schemas.sort_by_key(|schema| (schema.event_type, schema.version));
+    schemas
+        .into_iter()
+        .map(EventSchema::to_json_schema)
+        .collect()
+}
+
+#[test]
+fn registered_schemas_stay_compatible_with_published_versions() {
+    let registered = registered_schemas();
+    if std::env::var_os("RUSTOK_BLESS_EVENT_SCHEMAS").is_some() {
+        let json = serde_json::to_string_pretty(&registered).expect("schemas serialize");
+        std::fs::write(PUBLISHED_SCHEMAS_PATH, json + "\n").expect("snapshot should be written");
+        return;
+    }
+
+    let published: Vec<Value> = serde_json::from_str(
+        &std::fs::read_to_string(PUBLISHED_SCHEMAS_PATH).expect("published schemas exist"),
+    )
+    .expect("published schemas are valid JSON");
+
+    let mut problems = Vec::new();
+    for previous in &published {
+        let event_type = previous["title"].as_str().unwrap_or_default();
+        let version = previous["version"].as_u64().unwrap_or_default() as u16;
+        let Some(current) = EVENT_REGISTRY.json_schema(event_type, version) else {
+            problems.push(format!(
+                "'{event_type}' v{version} was published: keep it in LEGACY_EVENT_SCHEMAS"
+            ));
+            continue;
+        };
+        let compatibility = schema_compatibility(previous, &current);
+        if !compatibility.is_full() {
+            problems.push(format!(
+                "'{event_type}' v{version} changed incompatibly ({}): bump the variant's \
+                 schema_version and add an upcaster",
+                compatibility
+                    .backward
+                    .iter()
+                    .chain(&compatibility.forward)
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        } else if &current != previous {
+            problems.push(format!(
+                "'{event_type}' v{version} changed compatibly: re-bless the published schemas"
+            ));
+        }
+    }
+    for schema in &registered {
+        let known = published.iter().any(|previous| {
+            previous["title"] == schema["title"] && previous["version"] == schema["version"]
+        });
+        if !known {
+            problems.push(format!(
+                "'{}' v{} is not published yet: re-bless the published schemas",
+                schema["title"].as_str().unwrap_or_default(),
+                schema["version"]
+            ));
+        }
+    }
+
+    assert!(problems.is_empty(), "{}", problems.join("\n"));
+}
+
+#[test]
+fn compatibility_classifies_schema_changes() {
+    let schema = |fields: Value| {
+        let required: Vec<_> = fields
+            .as_object()
+            .unwrap()
+            .iter()
+            .filter(|(_, field)| field["nullable"].is_null())
+            .map(|(name, _)| name.clone())
+            .collect();
+        json!({ "properties": fields, "required": required })
+    };
+    let base = schema(
+        json!({ "order_id": { "type": "uuid" }, "note": { "type": "string", "nullable": true } }),
+    );
+
+    let optional_added = schema(json!({
+        "order_id": { "type": "uuid" },
+        "note": { "type": "string", "nullable": true },
+        "channel": { "type": "string", "nullable": true }
+    }));
+    assert!(schema_compatibility(&base, &optional_added).is_full());
+
+    let required_added = schema(json!({
+        "order_id": { "type": "uuid" },
+        "note": { "type": "string", "nullable": true },
+        "total": { "type": "int64" }
+    }));
+    let compatibility = schema_compatibility(&base, &required_added);
+    assert!(!compatibility.is_backward_compatible());
+    assert!(compatibility.is_forward_compatible());
+
+    let required_removed = schema(json!({ "note": { "type": "string", "nullable": true } }));
+    let compatibility = schema_compatibility(&base, &required_removed);
+    assert!(compatibility.is_backward_compatible());
+    assert!(!compatibility.is_forward_compatible());
+
+    let retyped = schema(
+        json!({ "order_id": { "type": "string" }, "note": { "type": "string", "nullable": true } }),
+    );
+    let compatibility = schema_compatibility(&base, &retyped);
+    assert!(!compatibility.is_backward_compatible());
+    assert!(!compatibility.is_forward_compatible());
+}
+
+// A `order.status_changed` whose v1 carried a single `status` field.
+const ORDER_STATUS_CHANGED_V1: EventSchema = EventSchema {
+    event_type: "order.status_changed",
+    version: 1,
+    description: "An order status changed.",
+    fields: &[
+        FieldSchema {
+            name: "order_id",
+            data_type: "uuid",
+            optional: false,
+        },
+        FieldSchema {
+            name: "status",
+            data_type: "string",
+            optional: false,
+        },
+    ],
+};
+
+const ORDER_STATUS_CHANGED_V2: EventSchema = EventSchema {
+    event_type: "order.status_changed",
+    version: 2,
+    description: "An order status changed.",
+    fields: &[
+        FieldSchema {
+            name: "order_id",
+            data_type: "uuid",
+            optional: false,
+        },
+        FieldSchema {
+            name: "old_status",
+            data_type: "string",
+            optional: false,
+        },
+        FieldSchema {
+            name: "new_status",
+            data_type: "string",
+            optional: false,
+        },
+    ],
+};
+
+fn split_status(mut data: Value) -> Result<Value, String> {
+    let status = data
+        .as_object_mut()
+        .and_then(|data| data.remove("status"))
+        .ok_or("missing 'status'")?;
+    data["old_status"] = json!("unknown");
+    data["new_status"] = status;
+    Ok(data)
+}
+
+fn drop_everything(_: Value) -> Result<Value, String> {
+    Ok(json!({}))
+}
+
+const REGISTRY: EventSchemaRegistry = EventSchemaRegistry::new(
+    &[ORDER_STATUS_CHANGED_V2],
+    &[ORDER_STATUS_CHANGED_V1],
+    &[EventUpcaster {
+        event_type: "order.status_changed",
+        from_version: 1,
+        upcast: split_status,
+    }],
+);
+
+#[test]
+fn stored_payloads_are_upcast_to_the_current_version() {
+    assert!(REGISTRY.check().is_empty());
+    assert_eq!(REGISTRY.versions("order.status_changed"), vec![1, 2]);
+    assert!(!schema_compatibility(
+        &ORDER_STATUS_CHANGED_V1.to_json_schema(),
+        &ORDER_STATUS_CHANGED_V2.to_json_schema()
+    )
+    .is_backward_compatible());
+
+    let order_id = Uuid::new_v4();
+    let stored = json!({
+        "event_type": "order.status_changed",
+        "schema_version": 1,
+        "event": { "type": "OrderStatusChanged", "data": { "order_id": order_id, "status": "paid" } }
+    });
+    let upcast = REGISTRY
+        .upcast_envelope(stored)
+        .expect("v1 payload upcasts");
+    assert_eq!(upcast["schema_version"], 2);
+    assert_eq!(
+        upcast["event"]["data"],
+        json!({ "order_id": order_id, "old_status": "unknown", "new_status": "paid" })
+    );
+
+    // Current payloads pass through untouched.
+    assert_eq!(
+        REGISTRY.upcast_envelope(upcast.clone()).expect("v2 passes"),
+        upcast
+    );
+
+    let broken = EventSchemaRegistry::new(
+        &[ORDER_STATUS_CHANGED_V2],
+        &[ORDER_STATUS_CHANGED_V1],
+        &[EventUpcaster {
+            event_type: "order.status_changed",
+            from_version: 1,
+            upcast: drop_everything,
+        }],
+    );
+    assert!(matches!(
+        broken.upcast(
+            "order.status_changed",
+            1,
+            json!({ "order_id": order_id, "status": "paid" })
+        ),
+        Err(EventUpcastError::SchemaMismatch { version: 2, .. })
+    ));
+
+    let missing =
+        EventSchemaRegistry::new(&[ORDER_STATUS_CHANGED_V2], &[ORDER_STATUS_CHANGED_V1], &[]);
+    assert_eq!(missing.check().len(), 1);
+    assert!(matches!(
+        missing.upcast("order.status_changed", 1, json!({})),
+        Err(EventUpcastError::MissingUpcaster { version: 1, .. })
+    ));
+}
+
+#[test]
+fn stored_envelopes_of_the_current_version_deserialize() {
+    let envelope = EventEnvelope::new(
+        Uuid::new_v4(),
+        None,
+        DomainEvent::OrderCompleted {
+            order_id: Uuid::new_v4(),
+        },
+    );
+    let stored = serde_json::to_value(&envelope).expect("envelope serializes");
+    let restored = EventEnvelope::from_stored_json(stored).expect("envelope deserializes");
+    assert_eq!(restored.event, envelope.event);
+    assert_eq!(restored.schema_version, envelope.schema_version);
+}
@@ -0,0 +1,62 @@
+use async_trait::async_trait;
+use rustok_core::{
+    ModuleAction, ModuleActionContext, ModuleActionDescriptor, ModuleActionError,
+    ModuleActionResult, Permission,
+};
+use rustok_outbox::TransactionalEventBus;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::services::ModerationService;
+
+/// `forum.lock_topic` — locks a topic so it no longer accepts replies.
+#[derive(Clone, Default)]
+pub struct LockTopicAction;
+
+#[derive(Deserialize)]
+struct LockTopicInput {
+    topic_id: Uuid,
+}
+
+#[async_trait]
+impl ModuleAction for LockTopicAction {
+    fn descriptor(&self) -> ModuleActionDescriptor {
+        ModuleActionDescriptor {
+            name: "forum.lock_topic",
+            module_slug: "forum",
+            description: "Lock a forum topic so it no longer accepts replies",
+            input_schema: json!({
+                "type": "object",
+                "required": ["topic_id"],
+                "additionalProperties": false,
+                "properties": {
+                    "topic_id": { "type": "string", "format": "uuid" }
+                }
+            }),
+            output_schema: json!({
+                "type": "object",
+                "required": ["topic_id", "locked"],
+                "properties": {
+                    "topic_id": { "type": "string", "format": "uuid" },
+                    "locked": { "type": "boolean" }
+                }
+            }),
+            required_permissions: vec![Permission::FORUM_TOPICS_MODERATE],
+        }
+    }
+
+    async fn invoke(&self, ctx: &ModuleActionContext, input: Value) -> ModuleActionResult<Value> {
+        let input: LockTopicInput =
+            serde_json::from_value(input).map_err(ModuleActionError::failed)?;
+        ModerationService::new(
+            ctx.db.clone(),
+            TransactionalEventBus::new(ctx.event_transport.clone()),
+        )
+        .lock_topic(ctx.tenant_id, input.topic_id, ctx.security.clone())
+        .await
+        .map_err(ModuleActionError::failed)?;
+
+        Ok(json!({ "topic_id": input.topic_id, "locked": true }))
+    }
+}
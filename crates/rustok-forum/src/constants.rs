@@ -19,3 +19,14 @@ pub mod reply_status {
     pub const FLAGGED: &str = "flagged";
     pub const DELETED: &str = "deleted";
 }
+
+/// Sources of reputation ledger entries.
+pub mod reputation_source {
+    pub const TOPIC_VOTE: &str = "topic_vote";
+    pub const REPLY_VOTE: &str = "reply_vote";
+    pub const SOLUTION: &str = "solution";
+    pub const REPLY_REJECTED: &str = "reply_rejected";
+}
+
+/// `source` of badges the forum awards on profiles.
+pub const PROFILE_BADGE_SOURCE: &str = "forum";
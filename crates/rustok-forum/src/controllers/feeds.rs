@@ -0,0 +1,200 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    response::Response,
+};
+use loco_rs::{app::AppContext, Error, Result};
+use rustok_api::{
+    feed::{feed_response, public_base_url},
+    loco::transactional_event_bus_from_context,
+    RequestContext, TenantContext,
+};
+use rustok_channel::ChannelService;
+use rustok_core::feed::feed_limit;
+use rustok_core::{Feed, FeedContentMode, FeedFormat, SecurityContext};
+use serde::Deserialize;
+use url::Url;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::{CategoryService, TopicService};
+
+const MODULE_SLUG: &str = "forum";
+
+#[derive(Debug, Clone, Default, Deserialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Query)]
+pub struct FeedParams {
+    /// Requested locale; defaults to the request locale.
+    pub locale: Option<String>,
+    /// `full` (default) or `excerpt`.
+    pub content: Option<String>,
+    /// Number of entries, 1..=100 (default 20).
+    pub limit: Option<u32>,
+}
+
+/// Latest forum topics feed (RSS 2.0, Atom or JSON Feed)
+#[utoipa::path(
+    get,
+    path = "/api/forum/feeds/{format}",
+    tag = "forum",
+    params(
+        ("format" = String, Path, description = "Feed format: rss, atom or json"),
+        FeedParams
+    ),
+    responses(
+        (status = 200, description = "Feed document"),
+        (status = 304, description = "Feed not modified"),
+        (status = 400, description = "Unknown feed format or content mode"),
+        (status = 404, description = "Forum is not enabled for the channel")
+    )
+)]
+pub async fn topics_feed(
+    State(ctx): State<AppContext>,
+    tenant: TenantContext,
+    request_context: RequestContext,
+    headers: HeaderMap,
+    Path(format): Path<String>,
+    Query(params): Query<FeedParams>,
+) -> Result<Response> {
+    render_feed(
+        &ctx,
+        &tenant,
+        &request_context,
+        &headers,
+        &format,
+        params,
+        None,
+    )
+    .await
+}
+
+/// Forum category topics feed
+#[utoipa::path(
+    get,
+    path = "/api/forum/feeds/categories/{category_id}/{format}",
+    tag = "forum",
+    params(
+        ("category_id" = Uuid, Path, description = "Category ID"),
+        ("format" = String, Path, description = "Feed format: rss, atom or json"),
+        FeedParams
+    ),
+    responses(
+        (status = 200, description = "Feed document"),
+        (status = 304, description = "Feed not modified"),
+        (status = 400, description = "Unknown feed format or content mode"),
+        (status = 404, description = "Category not found or forum is not enabled for the channel")
+    )
+)]
+pub async fn category_feed(
+    State(ctx): State<AppContext>,
+    tenant: TenantContext,
+    request_context: RequestContext,
+    headers: HeaderMap,
+    Path((category_id, format)): Path<(Uuid, String)>,
+    Query(params): Query<FeedParams>,
+) -> Result<Response> {
+    render_feed(
+        &ctx,
+        &tenant,
+        &request_context,
+        &headers,
+        &format,
+        params,
+        Some(category_id),
+    )
+    .await
+}
+
+async fn render_feed(
+    ctx: &AppContext,
+    tenant: &TenantContext,
+    request_context: &RequestContext,
+    headers: &HeaderMap,
+    format: &str,
+    params: FeedParams,
+    category_id: Option<Uuid>,
+) -> Result<Response> {
+    let format = FeedFormat::parse(format)
+        .ok_or_else(|| Error::BadRequest(format!("Unknown feed format: {format}")))?;
+    let content_mode = FeedContentMode::parse(params.content.as_deref())
+        .ok_or_else(|| Error::BadRequest("content must be `full` or `excerpt`".to_string()))?;
+    ensure_channel_enabled(ctx, request_context).await?;
+
+    let locale = params
+        .locale
+        .unwrap_or_else(|| request_context.locale.clone());
+    let event_bus = transactional_event_bus_from_context(ctx);
+    let mut feed_segments = ["api", "forum", "feeds"].map(String::from).to_vec();
+    let mut title = format!("{} · Forum", tenant.name);
+    if let Some(category_id) = category_id {
+        let category = CategoryService::new(ctx.db.clone())
+            .get_with_locale_fallback(
+                tenant.id,
+                SecurityContext::system(),
+                category_id,
+                &locale,
+                Some(tenant.default_locale.as_str()),
+            )
+            .await
+            .map_err(|_| Error::NotFound)?;
+        title.push_str(&format!(" · {}", category.name));
+        feed_segments.extend(["categories".to_string(), category_id.to_string()]);
+    }
+    feed_segments.push(format.as_str().to_string());
+
+    let base_url = public_base_url(tenant);
+    let items = TopicService::new(ctx.db.clone(), event_bus)
+        .list_public_feed_items(
+            tenant.id,
+            category_id,
+            &locale,
+            Some(tenant.default_locale.as_str()),
+            request_context.channel_slug.as_deref(),
+            u64::from(feed_limit(params.limit)),
+            &base_url,
+            content_mode,
+        )
+        .await
+        .map_err(|err| Error::BadRequest(err.to_string()))?;
+
+    let home_page_url = match category_id {
+        Some(category_id) => format!("{base_url}/modules/forum?category={category_id}"),
+        None => format!("{base_url}/modules/forum"),
+    };
+    let feed = Feed {
+        title,
+        description: None,
+        home_page_url,
+        feed_url: absolute_url(&base_url, &feed_segments),
+        language: Some(locale),
+        items,
+    };
+    Ok(feed_response(headers, &feed, format))
+}
+
+fn absolute_url(base_url: &str, segments: &[String]) -> String {
+    match Url::parse(base_url) {
+        Ok(mut url) => {
+            if let Ok(mut path) = url.path_segments_mut() {
+                path.pop_if_empty().extend(segments);
+            }
+            url.to_string()
+        }
+        Err(_) => format!("{base_url}/{}", segments.join("/")),
+    }
+}
+
+async fn ensure_channel_enabled(ctx: &AppContext, request_context: &RequestContext) -> Result<()> {
+    let Some(channel_id) = request_context.channel_id else {
+        return Ok(());
+    };
+    let enabled = ChannelService::new(ctx.db.clone())
+        .is_module_enabled(channel_id, MODULE_SLUG)
+        .await
+        .map_err(|err| Error::string(&format!("Channel module check failed: {err}")))?;
+    if enabled {
+        Ok(())
+    } else {
+        Err(Error::NotFound)
+    }
+}
@@ -2,6 +2,7 @@ use axum::routing::get;
 use loco_rs::controller::Routes;
 
 pub mod categories;
+pub mod feeds;
 pub mod replies;
 pub mod topics;
 pub mod users;
@@ -79,4 +80,9 @@ pub fn routes() -> Routes {
             axum::routing::post(widgets::validate_widget_props),
         )
         .add("/users/{user_id}/stats", get(users::get_user_stats))
+        .add("/feeds/{format}", get(feeds::topics_feed))
+        .add(
+            "/feeds/categories/{category_id}/{format}",
+            get(feeds::category_feed),
+        )
 }
@@ -1,11 +1,13 @@
 pub mod category;
 pub mod reply;
+pub mod reputation;
 pub mod topic;
 pub mod user_stats;
 pub mod widget;
 
 pub use category::*;
 pub use reply::*;
+pub use reputation::*;
 pub use topic::*;
 pub use user_stats::*;
 pub use widget::*;
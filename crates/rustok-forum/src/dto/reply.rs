@@ -55,6 +55,9 @@ pub struct ReplyResponse {
     pub content: String,
     pub content_format: String,
     pub content_json: Option<Value>,
+    /// Sanitized HTML rendering of the content.
+    #[serde(default)]
+    pub content_html: String,
     pub status: String,
     pub vote_score: i32,
     pub current_user_vote: Option<i32>,
@@ -101,6 +104,7 @@ mod tests {
             content: content.into(),
             content_format: content_format.into(),
             content_json,
+            content_html: String::new(),
             status: "approved".into(),
             vote_score: 0,
             current_user_vote: None,
@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Thresholds a member must reach to earn a badge; every configured threshold must hold.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct BadgeRule {
+    pub min_reputation: Option<i32>,
+    pub min_topics: Option<i32>,
+    pub min_replies: Option<i32>,
+    pub min_solutions: Option<i32>,
+}
+
+impl BadgeRule {
+    pub fn is_empty(&self) -> bool {
+        self.min_reputation.is_none()
+            && self.min_topics.is_none()
+            && self.min_replies.is_none()
+            && self.min_solutions.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateBadgeInput {
+    pub slug: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub icon: Option<String>,
+    pub rule: BadgeRule,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BadgeResponse {
+    pub id: Uuid,
+    pub slug: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub icon: Option<String>,
+    pub rule: BadgeRule,
+    pub created_at: String,
+}
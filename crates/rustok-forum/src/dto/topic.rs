@@ -36,6 +36,8 @@ pub struct UpdateTopicInput {
 #[derive(Debug, Clone, Serialize, Deserialize, Default, ToSchema, IntoParams)]
 pub struct ListTopicsFilter {
     pub category_id: Option<Uuid>,
+    /// Taxonomy term id; matches topics tagged with the term or any of its descendants.
+    pub tag_id: Option<Uuid>,
     pub status: Option<String>,
     pub locale: Option<String>,
     #[serde(default = "default_page")]
@@ -70,6 +72,9 @@ pub struct TopicResponse {
     pub body: String,
     pub body_format: String,
     pub content_json: Option<Value>,
+    /// Sanitized HTML rendering of the body.
+    #[serde(default)]
+    pub body_html: String,
     pub metadata: Value,
     pub status: String,
     pub tags: Vec<String>,
@@ -80,6 +85,7 @@ pub struct TopicResponse {
     pub solution_reply_id: Option<Uuid>,
     pub is_pinned: bool,
     pub is_locked: bool,
+    pub is_wiki: bool,
     pub reply_count: i32,
     pub created_at: String,
     pub updated_at: String,
@@ -105,6 +111,7 @@ pub struct TopicListItem {
     pub solution_reply_id: Option<Uuid>,
     pub is_pinned: bool,
     pub is_locked: bool,
+    pub is_wiki: bool,
     pub reply_count: i32,
     pub created_at: String,
 }
@@ -133,6 +140,7 @@ mod tests {
             body: body.into(),
             body_format: body_format.into(),
             content_json,
+            body_html: String::new(),
             metadata: json!({}),
             status: "open".into(),
             tags: vec![],
@@ -143,6 +151,7 @@ mod tests {
             solution_reply_id: None,
             is_pinned: false,
             is_locked: false,
+            is_wiki: false,
             reply_count: 0,
             created_at: "2024-01-01T00:00:00Z".into(),
             updated_at: "2024-01-01T00:00:00Z".into(),
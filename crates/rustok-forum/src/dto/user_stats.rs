@@ -8,5 +8,8 @@ pub struct ForumUserStatsResponse {
     pub topic_count: i32,
     pub reply_count: i32,
     pub solution_count: i32,
+    pub reputation: i32,
+    /// Trust level derived from `reputation`: `new`, `basic`, `member`, `regular` or `leader`.
+    pub trust_level: String,
     pub updated_at: String,
 }
@@ -14,6 +14,7 @@ pub struct Model {
     pub metadata: Json,
     pub is_pinned: bool,
     pub is_locked: bool,
+    pub is_wiki: bool,
     pub reply_count: i32,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
@@ -12,6 +12,7 @@ pub struct Model {
     pub topic_count: i32,
     pub reply_count: i32,
     pub solution_count: i32,
+    pub reputation: i32,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
 }
@@ -1,11 +1,13 @@
 //! SeaORM entities for forum-owned persistence.
 
+pub mod forum_badge;
 pub mod forum_category;
 pub mod forum_category_subscription;
 pub mod forum_category_translation;
 pub mod forum_reply;
 pub mod forum_reply_body;
 pub mod forum_reply_vote;
+pub mod forum_reputation_event;
 pub mod forum_solution;
 pub mod forum_topic;
 pub mod forum_topic_channel_access;
@@ -63,6 +63,21 @@ impl From<rustok_taxonomy::TaxonomyError> for ForumError {
             rustok_taxonomy::TaxonomyError::TermNotFound(term_id) => {
                 Self::Validation(format!("Taxonomy term not found: {term_id}"))
             }
+            error @ rustok_taxonomy::TaxonomyError::HierarchyCycle(_) => {
+                Self::Validation(error.to_string())
+            }
+        }
+    }
+}
+
+impl From<rustok_profiles::ProfileError> for ForumError {
+    fn from(value: rustok_profiles::ProfileError) -> Self {
+        match value {
+            rustok_profiles::ProfileError::Database(err) => Self::Database(err),
+            blocked @ rustok_profiles::ProfileError::RelationBlocked(_) => {
+                Self::Forbidden(blocked.to_string())
+            }
+            other => Self::Validation(other.to_string()),
         }
     }
 }
@@ -8,6 +8,11 @@ use rustok_api::graphql::PageInfo;
 ))]
 #[graphql(concrete(name = "ForumTopicConnection", params(crate::graphql::GqlForumTopic)))]
 #[graphql(concrete(name = "ForumReplyConnection", params(crate::graphql::GqlForumReply)))]
+#[graphql(concrete(name = "ForumReportConnection", params(crate::graphql::GqlForumReport)))]
+#[graphql(concrete(
+    name = "ForumModerationLogConnection",
+    params(crate::graphql::GqlForumModerationLogEntry)
+))]
 pub struct ListConnection<T>
 where
     T: async_graphql::OutputType,
@@ -3,6 +3,10 @@ use rustok_api::{
     graphql::{require_module_enabled, GraphQLError},
     has_any_effective_permission, AuthContext,
 };
+use rustok_content::{
+    CreateReportInput, IssueSanctionInput, ReportReason, ReportService, ReportStatus,
+    ReportTargetKind, ResolveReportInput, SanctionKind, SanctionService,
+};
 use rustok_core::{Permission, CONTENT_FORMAT_MARKDOWN};
 use rustok_outbox::TransactionalEventBus;
 use rustok_profiles::{
@@ -370,6 +374,7 @@ impl ForumMutation {
             author_profile,
             content: reply.content,
             content_format: reply.content_format,
+            content_html: reply.content_html,
             status: reply.status,
             vote_score: reply.vote_score,
             current_user_vote: reply.current_user_vote,
@@ -518,6 +523,7 @@ impl ForumMutation {
             author_profile,
             content: reply.content,
             content_format: reply.content_format,
+            content_html: reply.content_html,
             status: reply.status,
             vote_score: reply.vote_score,
             current_user_vote: reply.current_user_vote,
@@ -578,6 +584,7 @@ impl ForumMutation {
             author_profile,
             content: reply.content,
             content_format: reply.content_format,
+            content_html: reply.content_html,
             status: reply.status,
             vote_score: reply.vote_score,
             current_user_vote: reply.current_user_vote,
@@ -683,6 +690,108 @@ impl ForumMutation {
         Ok(map_topic(topic, author_profile))
     }
 
+    async fn set_forum_topic_wiki(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Uuid,
+        topic_id: Uuid,
+        is_wiki: bool,
+        locale: Option<String>,
+    ) -> Result<GqlForumTopic> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let event_bus = ctx.data::<TransactionalEventBus>()?;
+        let auth = require_forum_permission(
+            ctx,
+            &[
+                Permission::FORUM_TOPICS_UPDATE,
+                Permission::FORUM_TOPICS_MODERATE,
+            ],
+            "Permission denied: forum_topics:update or forum_topics:moderate required",
+        )?;
+        let tenant = ctx.data::<rustok_api::TenantContext>()?;
+        let resolved_locale = locale.unwrap_or_else(|| tenant.default_locale.clone());
+
+        crate::ModerationService::new(db.clone(), event_bus.clone())
+            .set_topic_wiki(tenant_id, topic_id, auth.security_context(), is_wiki)
+            .await?;
+
+        let topic = TopicService::new(db.clone(), event_bus.clone())
+            .get_with_locale_fallback(
+                tenant_id,
+                auth.security_context(),
+                topic_id,
+                resolved_locale.as_str(),
+                Some(tenant.default_locale.as_str()),
+            )
+            .await?;
+        let author_profile = load_author_profile(
+            ctx,
+            db,
+            tenant_id,
+            topic.author_id,
+            topic.effective_locale.as_str(),
+        )
+        .await?;
+
+        Ok(map_topic(topic, author_profile))
+    }
+
+    async fn create_forum_badge(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Uuid,
+        input: CreateForumBadgeInput,
+    ) -> Result<GqlForumBadge> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let auth = require_forum_permission(
+            ctx,
+            &[Permission::FORUM_CATEGORIES_MANAGE],
+            "Permission denied: forum_categories:manage required",
+        )?;
+
+        let badge = crate::ReputationService::new(db.clone())
+            .create_badge(
+                tenant_id,
+                auth.security_context(),
+                crate::CreateBadgeInput {
+                    slug: input.slug,
+                    name: input.name,
+                    description: input.description,
+                    icon: input.icon,
+                    rule: crate::BadgeRule {
+                        min_reputation: input.min_reputation,
+                        min_topics: input.min_topics,
+                        min_replies: input.min_replies,
+                        min_solutions: input.min_solutions,
+                    },
+                },
+            )
+            .await?;
+        Ok(badge.into())
+    }
+
+    async fn delete_forum_badge(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Uuid,
+        badge_id: Uuid,
+    ) -> Result<bool> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let auth = require_forum_permission(
+            ctx,
+            &[Permission::FORUM_CATEGORIES_MANAGE],
+            "Permission denied: forum_categories:manage required",
+        )?;
+
+        crate::ReputationService::new(db.clone())
+            .delete_badge(tenant_id, auth.security_context(), badge_id)
+            .await?;
+        Ok(true)
+    }
+
     async fn create_forum_category(
         &self,
         ctx: &Context<'_>,
@@ -732,6 +841,144 @@ impl ForumMutation {
             is_subscribed: category.is_subscribed,
         })
     }
+
+    async fn report_forum_content(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Uuid,
+        input: ReportForumContentInput,
+    ) -> Result<GqlForumReport> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let event_bus = ctx.data::<TransactionalEventBus>()?;
+        let auth = ctx
+            .data::<AuthContext>()
+            .map_err(|_| <FieldError as GraphQLError>::unauthenticated())?;
+        let target_kind = ReportTargetKind::parse(&input.target_kind).ok_or_else(|| {
+            <FieldError as GraphQLError>::bad_user_input("Unknown report target kind")
+        })?;
+        let reason = ReportReason::parse(&input.reason)
+            .ok_or_else(|| <FieldError as GraphQLError>::bad_user_input("Unknown report reason"))?;
+
+        let target_author_id = match target_kind {
+            ReportTargetKind::ForumTopic => {
+                TopicService::new(db.clone(), event_bus.clone())
+                    .find_topic(tenant_id, input.target_id)
+                    .await?
+                    .author_id
+            }
+            ReportTargetKind::ForumReply => {
+                ReplyService::new(db.clone(), event_bus.clone())
+                    .find_reply(tenant_id, input.target_id)
+                    .await?
+                    .author_id
+            }
+            ReportTargetKind::Profile => Some(input.target_id),
+            ReportTargetKind::Comment => None,
+        };
+
+        let report = ReportService::new(db.clone())
+            .submit(
+                tenant_id,
+                auth.security_context(),
+                CreateReportInput {
+                    target_kind,
+                    target_id: input.target_id,
+                    target_author_id,
+                    reason,
+                    details: input.details,
+                },
+            )
+            .await?;
+
+        Ok(report.into())
+    }
+
+    async fn resolve_forum_report(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Uuid,
+        report_id: Uuid,
+        input: ResolveForumReportInput,
+    ) -> Result<GqlForumReport> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let auth = require_forum_moderator(ctx)?;
+        let status = ReportStatus::parse(&input.status)
+            .ok_or_else(|| <FieldError as GraphQLError>::bad_user_input("Unknown report status"))?;
+
+        let report = ReportService::new(db.clone())
+            .resolve(
+                tenant_id,
+                auth.security_context(),
+                report_id,
+                ResolveReportInput {
+                    status,
+                    note: input.note,
+                },
+            )
+            .await?;
+
+        Ok(report.into())
+    }
+
+    async fn issue_forum_sanction(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Uuid,
+        input: IssueForumSanctionInput,
+    ) -> Result<GqlForumSanction> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let auth = require_forum_moderator(ctx)?;
+        let kind = SanctionKind::parse(&input.kind)
+            .ok_or_else(|| <FieldError as GraphQLError>::bad_user_input("Unknown sanction kind"))?;
+
+        let sanction = SanctionService::new(db.clone())
+            .issue(
+                tenant_id,
+                auth.security_context(),
+                IssueSanctionInput {
+                    user_id: input.user_id,
+                    kind,
+                    reason: input.reason,
+                    duration_seconds: input.duration_seconds,
+                    report_id: input.report_id,
+                },
+            )
+            .await?;
+
+        Ok(sanction.into())
+    }
+
+    async fn revoke_forum_sanction(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Uuid,
+        sanction_id: Uuid,
+        note: Option<String>,
+    ) -> Result<GqlForumSanction> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let auth = require_forum_moderator(ctx)?;
+
+        let sanction = SanctionService::new(db.clone())
+            .revoke(tenant_id, auth.security_context(), sanction_id, note)
+            .await?;
+
+        Ok(sanction.into())
+    }
+}
+
+fn require_forum_moderator(ctx: &Context<'_>) -> Result<AuthContext> {
+    require_forum_permission(
+        ctx,
+        &[
+            Permission::FORUM_TOPICS_MODERATE,
+            Permission::FORUM_REPLIES_MODERATE,
+        ],
+        "Permission denied: forum_topics:moderate or forum_replies:moderate required",
+    )
 }
 
 fn require_forum_permission(
@@ -799,6 +1046,7 @@ fn map_topic(
         slug: topic.slug,
         body: topic.body,
         body_format: topic.body_format,
+        body_html: topic.body_html,
         metadata: topic.metadata,
         status: topic.status,
         tags: topic.tags,
@@ -809,6 +1057,7 @@ fn map_topic(
         solution_reply_id: topic.solution_reply_id,
         is_pinned: topic.is_pinned,
         is_locked: topic.is_locked,
+        is_wiki: topic.is_wiki,
         reply_count: topic.reply_count,
         created_at: topic.created_at,
         updated_at: topic.updated_at,
@@ -4,6 +4,10 @@ use rustok_api::{
     has_any_effective_permission, AuthContext, RequestContext, TenantContext,
 };
 use rustok_channel::ChannelService;
+use rustok_content::{
+    ListModerationLogFilter, ListReportsFilter, ModerationLogService, ReportService, ReportStatus,
+    ReportTargetKind, SanctionService,
+};
 use rustok_core::{Permission, SecurityContext};
 use rustok_outbox::TransactionalEventBus;
 use rustok_profiles::{
@@ -100,6 +104,7 @@ impl ForumQuery {
         ctx: &Context<'_>,
         tenant_id: Uuid,
         category_id: Option<Uuid>,
+        tag_id: Option<Uuid>,
         locale: Option<String>,
         #[graphql(default)] pagination: PaginationInput,
     ) -> Result<ForumTopicConnection> {
@@ -119,6 +124,7 @@ impl ForumQuery {
         let locale = resolve_graphql_locale(ctx, locale.as_deref());
         let filter = crate::ListTopicsFilter {
             category_id,
+            tag_id,
             status: None,
             locale: Some(locale.clone()),
             page: (offset / limit + 1) as u64,
@@ -281,10 +287,27 @@ impl ForumQuery {
             topic_count: stats.topic_count,
             reply_count: stats.reply_count,
             solution_count: stats.solution_count,
+            reputation: stats.reputation,
+            trust_level: stats.trust_level,
             updated_at: stats.updated_at,
         })
     }
 
+    async fn forum_badges(&self, ctx: &Context<'_>, tenant_id: Uuid) -> Result<Vec<GqlForumBadge>> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        require_forum_permission(
+            ctx,
+            &[Permission::FORUM_TOPICS_READ],
+            "Permission denied: forum_topics:read required",
+        )?;
+
+        let badges = crate::ReputationService::new(db.clone())
+            .list_badges(tenant_id)
+            .await?;
+        Ok(badges.into_iter().map(Into::into).collect())
+    }
+
     async fn forum_widget_catalog(&self, ctx: &Context<'_>) -> Result<GqlForumWidgetCatalog> {
         require_module_enabled(ctx, MODULE_SLUG).await?;
         require_forum_permission(
@@ -360,6 +383,7 @@ impl ForumQuery {
         ctx: &Context<'_>,
         tenant_id: Option<Uuid>,
         category_id: Option<Uuid>,
+        tag_id: Option<Uuid>,
         locale: Option<String>,
         #[graphql(default)] pagination: PaginationInput,
     ) -> Result<ForumTopicConnection> {
@@ -375,6 +399,7 @@ impl ForumQuery {
         let locale = resolve_graphql_locale(ctx, locale.as_deref());
         let filter = crate::ListTopicsFilter {
             category_id,
+            tag_id,
             status: None,
             locale: Some(locale.clone()),
             page: (offset / limit + 1) as u64,
@@ -597,6 +622,124 @@ impl ForumQuery {
             limit,
         ))
     }
+
+    async fn forum_reports(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Uuid,
+        status: Option<String>,
+        target_kind: Option<String>,
+        #[graphql(default)] pagination: PaginationInput,
+    ) -> Result<ForumReportConnection> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let auth = require_forum_permission(
+            ctx,
+            &[
+                Permission::FORUM_TOPICS_MODERATE,
+                Permission::FORUM_REPLIES_MODERATE,
+            ],
+            "Permission denied: forum_topics:moderate or forum_replies:moderate required",
+        )?;
+        let status = status
+            .map(|value| {
+                ReportStatus::parse(&value).ok_or_else(|| {
+                    <FieldError as GraphQLError>::bad_user_input("Unknown report status")
+                })
+            })
+            .transpose()?;
+        let target_kind = target_kind
+            .map(|value| {
+                ReportTargetKind::parse(&value).ok_or_else(|| {
+                    <FieldError as GraphQLError>::bad_user_input("Unknown report target kind")
+                })
+            })
+            .transpose()?;
+
+        let (offset, limit) = pagination.normalize()?;
+        let (reports, total) = ReportService::new(db.clone())
+            .list_queue(
+                tenant_id,
+                auth.security_context(),
+                ListReportsFilter {
+                    status,
+                    target_kind,
+                    page: (offset / limit + 1) as u64,
+                    per_page: limit as u64,
+                },
+            )
+            .await?;
+
+        Ok(ForumReportConnection::new(
+            reports.into_iter().map(Into::into).collect(),
+            total as i64,
+            offset,
+            limit,
+        ))
+    }
+
+    async fn forum_user_sanctions(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Vec<GqlForumSanction>> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let auth = ctx
+            .data::<AuthContext>()
+            .map_err(|_| <FieldError as GraphQLError>::unauthenticated())?;
+
+        let sanctions = SanctionService::new(db.clone())
+            .list_for_user(tenant_id, auth.security_context(), user_id)
+            .await?;
+
+        Ok(sanctions.into_iter().map(Into::into).collect())
+    }
+
+    async fn forum_moderation_log(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Uuid,
+        target_kind: Option<String>,
+        target_id: Option<Uuid>,
+        subject_user_id: Option<Uuid>,
+        #[graphql(default)] pagination: PaginationInput,
+    ) -> Result<ForumModerationLogConnection> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let auth = require_forum_permission(
+            ctx,
+            &[
+                Permission::FORUM_TOPICS_MODERATE,
+                Permission::FORUM_REPLIES_MODERATE,
+            ],
+            "Permission denied: forum_topics:moderate or forum_replies:moderate required",
+        )?;
+
+        let (offset, limit) = pagination.normalize()?;
+        let (entries, total) = ModerationLogService::new(db.clone())
+            .list(
+                tenant_id,
+                auth.security_context(),
+                ListModerationLogFilter {
+                    target_kind,
+                    target_id,
+                    actor_id: None,
+                    subject_user_id,
+                    page: (offset / limit + 1) as u64,
+                    per_page: limit as u64,
+                },
+            )
+            .await?;
+
+        Ok(ForumModerationLogConnection::new(
+            entries.into_iter().map(Into::into).collect(),
+            total as i64,
+            offset,
+            limit,
+        ))
+    }
 }
 
 fn require_forum_permission(
@@ -657,6 +800,7 @@ fn map_topic_list_item(
         slug: topic.slug,
         body: String::new(),
         body_format: "markdown".to_string(),
+        body_html: String::new(),
         metadata: topic.metadata,
         status: topic.status,
         tags: Vec::new(),
@@ -667,6 +811,7 @@ fn map_topic_list_item(
         solution_reply_id: topic.solution_reply_id,
         is_pinned: topic.is_pinned,
         is_locked: topic.is_locked,
+        is_wiki: topic.is_wiki,
         reply_count: topic.reply_count,
         created_at: topic.created_at,
         updated_at: String::new(),
@@ -690,6 +835,7 @@ fn map_topic_response(
         slug: topic.slug,
         body: topic.body,
         body_format: topic.body_format,
+        body_html: topic.body_html,
         metadata: topic.metadata,
         status: topic.status,
         tags: topic.tags,
@@ -700,6 +846,7 @@ fn map_topic_response(
         solution_reply_id: topic.solution_reply_id,
         is_pinned: topic.is_pinned,
         is_locked: topic.is_locked,
+        is_wiki: topic.is_wiki,
         reply_count: topic.reply_count,
         created_at: topic.created_at,
         updated_at: topic.updated_at,
@@ -720,6 +867,7 @@ fn map_reply_response(
         author_profile,
         content: reply.content,
         content_format: reply.content_format,
+        content_html: reply.content_html,
         status: reply.status,
         vote_score: reply.vote_score,
         current_user_vote: reply.current_user_vote,
@@ -958,6 +1106,12 @@ mod tests {
 
     async fn ensure_forum_query_schema(db: &DatabaseConnection) {
         let manager = SchemaManager::new(db);
+        for migration in rustok_content::migrations::migrations() {
+            migration
+                .up(&manager)
+                .await
+                .expect("content migration should apply");
+        }
         for migration in migrations::migrations() {
             migration
                 .up(&manager)
@@ -1190,6 +1344,7 @@ mod tests {
             SecurityContext::system(),
             crate::ListTopicsFilter {
                 category_id: Some(category.id),
+                tag_id: None,
                 status: None,
                 locale: Some("en".to_string()),
                 page: 1,
@@ -36,6 +36,7 @@ pub struct GqlForumTopic {
     pub slug: String,
     pub body: String,
     pub body_format: String,
+    pub body_html: String,
     pub metadata: Value,
     pub status: String,
     pub tags: Vec<String>,
@@ -46,6 +47,7 @@ pub struct GqlForumTopic {
     pub solution_reply_id: Option<Uuid>,
     pub is_pinned: bool,
     pub is_locked: bool,
+    pub is_wiki: bool,
     pub reply_count: i32,
     pub created_at: String,
     pub updated_at: String,
@@ -62,6 +64,7 @@ pub struct GqlForumReply {
     pub author_profile: Option<GqlProfileSummary>,
     pub content: String,
     pub content_format: String,
+    pub content_html: String,
     pub status: String,
     pub vote_score: i32,
     pub current_user_vote: Option<i32>,
@@ -77,9 +80,54 @@ pub struct GqlForumUserStats {
     pub topic_count: i32,
     pub reply_count: i32,
     pub solution_count: i32,
+    pub reputation: i32,
+    pub trust_level: String,
     pub updated_at: String,
 }
 
+#[derive(Clone, Debug, SimpleObject)]
+pub struct GqlForumBadge {
+    pub id: Uuid,
+    pub slug: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub icon: Option<String>,
+    pub min_reputation: Option<i32>,
+    pub min_topics: Option<i32>,
+    pub min_replies: Option<i32>,
+    pub min_solutions: Option<i32>,
+    pub created_at: String,
+}
+
+impl From<crate::BadgeResponse> for GqlForumBadge {
+    fn from(value: crate::BadgeResponse) -> Self {
+        Self {
+            id: value.id,
+            slug: value.slug,
+            name: value.name,
+            description: value.description,
+            icon: value.icon,
+            min_reputation: value.rule.min_reputation,
+            min_topics: value.rule.min_topics,
+            min_replies: value.rule.min_replies,
+            min_solutions: value.rule.min_solutions,
+            created_at: value.created_at,
+        }
+    }
+}
+
+#[derive(InputObject)]
+pub struct CreateForumBadgeInput {
+    pub slug: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub icon: Option<String>,
+    pub min_reputation: Option<i32>,
+    pub min_topics: Option<i32>,
+    pub min_replies: Option<i32>,
+    pub min_solutions: Option<i32>,
+}
+
 #[derive(Clone, Debug, SimpleObject)]
 pub struct GqlForumWidgetCatalog {
     pub catalog_version: String,
@@ -171,3 +219,125 @@ pub struct CreateForumCategoryInput {
 pub type ForumCategoryConnection = ListConnection<GqlForumCategory>;
 pub type ForumTopicConnection = ListConnection<GqlForumTopic>;
 pub type ForumReplyConnection = ListConnection<GqlForumReply>;
+pub type ForumReportConnection = ListConnection<GqlForumReport>;
+pub type ForumModerationLogConnection = ListConnection<GqlForumModerationLogEntry>;
+
+#[derive(Clone, Debug, SimpleObject)]
+pub struct GqlForumReport {
+    pub id: Uuid,
+    pub target_kind: String,
+    pub target_id: Uuid,
+    pub target_author_id: Option<Uuid>,
+    pub status: String,
+    pub report_count: i32,
+    pub reasons: Value,
+    pub resolved_by: Option<Uuid>,
+    pub resolved_at: Option<String>,
+    pub resolution_note: Option<String>,
+    pub created_at: String,
+    pub last_reported_at: String,
+}
+
+#[derive(Clone, Debug, SimpleObject)]
+pub struct GqlForumSanction {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub kind: String,
+    pub reason: String,
+    pub issued_by: Option<Uuid>,
+    pub report_id: Option<Uuid>,
+    pub expires_at: Option<String>,
+    pub revoked_at: Option<String>,
+    pub active: bool,
+    pub created_at: String,
+}
+
+#[derive(Clone, Debug, SimpleObject)]
+pub struct GqlForumModerationLogEntry {
+    pub id: Uuid,
+    pub actor_id: Option<Uuid>,
+    pub action: String,
+    pub target_kind: String,
+    pub target_id: Uuid,
+    pub subject_user_id: Option<Uuid>,
+    pub details: Value,
+    pub created_at: String,
+}
+
+#[derive(InputObject)]
+pub struct ReportForumContentInput {
+    /// One of `forum_topic`, `forum_reply`, `comment`, `profile`.
+    pub target_kind: String,
+    pub target_id: Uuid,
+    /// One of `spam`, `harassment`, `hate_speech`, `inappropriate`, `off_topic`, `other`.
+    pub reason: String,
+    pub details: Option<String>,
+}
+
+#[derive(InputObject)]
+pub struct ResolveForumReportInput {
+    /// Either `resolved` or `dismissed`.
+    pub status: String,
+    pub note: Option<String>,
+}
+
+#[derive(InputObject)]
+pub struct IssueForumSanctionInput {
+    pub user_id: Uuid,
+    /// One of `warn`, `mute`, `suspend`, `ban`.
+    pub kind: String,
+    pub reason: String,
+    pub duration_seconds: Option<i64>,
+    pub report_id: Option<Uuid>,
+}
+
+impl From<rustok_content::ReportResponse> for GqlForumReport {
+    fn from(report: rustok_content::ReportResponse) -> Self {
+        Self {
+            id: report.id,
+            target_kind: report.target_kind.as_str().to_string(),
+            target_id: report.target_id,
+            target_author_id: report.target_author_id,
+            status: report.status.as_str().to_string(),
+            report_count: report.report_count,
+            reasons: serde_json::json!(report.reasons),
+            resolved_by: report.resolved_by,
+            resolved_at: report.resolved_at.map(|value| value.to_rfc3339()),
+            resolution_note: report.resolution_note,
+            created_at: report.created_at.to_rfc3339(),
+            last_reported_at: report.last_reported_at.to_rfc3339(),
+        }
+    }
+}
+
+impl From<rustok_content::SanctionResponse> for GqlForumSanction {
+    fn from(sanction: rustok_content::SanctionResponse) -> Self {
+        Self {
+            id: sanction.id,
+            user_id: sanction.user_id,
+            kind: sanction.kind.as_str().to_string(),
+            reason: sanction.reason,
+            issued_by: sanction.issued_by,
+            report_id: sanction.report_id,
+            expires_at: sanction.expires_at.map(|value| value.to_rfc3339()),
+            revoked_at: sanction.revoked_at.map(|value| value.to_rfc3339()),
+            active: sanction.active,
+            created_at: sanction.created_at.to_rfc3339(),
+        }
+    }
+}
+
+impl From<rustok_content::ModerationLogEntry> for GqlForumModerationLogEntry {
+    fn from(entry: rustok_content::ModerationLogEntry) -> Self {
+        Self {
+            id: entry.id,
+            actor_id: entry.actor_id,
+            action: entry.action,
+            target_kind: entry.target_kind,
+            target_id: entry.target_id,
+            subject_user_id: entry.subject_user_id,
+            details: entry.details,
+            created_at: entry.created_at.to_rfc3339(),
+        }
+    }
+}
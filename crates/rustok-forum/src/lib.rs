@@ -1,9 +1,10 @@
 use async_trait::async_trait;
 use rustok_core::permissions::Permission;
-use rustok_core::{MigrationSource, ModuleRuntimeExtensions, RusToKModule};
+use rustok_core::{register_module_action, MigrationSource, ModuleRuntimeExtensions, RusToKModule};
 use rustok_seo_targets::register_seo_target_provider;
 use sea_orm_migration::MigrationTrait;
 
+mod actions;
 pub mod constants;
 pub mod controllers;
 pub mod dto;
@@ -22,8 +23,8 @@ pub use entities::*;
 pub use error::{ForumError, ForumResult};
 pub use graphql::{ForumMutation, ForumQuery};
 pub use services::{
-    CategoryService, ForumWidgetContractService, ModerationService, ReplyService,
-    SubscriptionService, TopicService, UserStatsService, VoteService,
+    CategoryService, ForumWidgetContractService, ModerationService, ReplyService, ReputationPolicy,
+    ReputationService, SubscriptionService, TopicService, UserStatsService, VoteService,
 };
 pub use state_machine::{ReplyStatus, TopicStatus};
 
@@ -81,6 +82,8 @@ impl RusToKModule for ForumModule {
             .expect("forum category SEO target registration should remain unique");
         register_seo_target_provider(extensions, seo_targets::ForumTopicSeoTargetProvider)
             .expect("forum topic SEO target registration should remain unique");
+        register_module_action(extensions, actions::LockTopicAction)
+            .expect("forum.lock_topic action registration should remain unique");
     }
 }
 
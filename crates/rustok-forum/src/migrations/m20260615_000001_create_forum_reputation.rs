@@ -0,0 +1,224 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ForumUserStats::Table)
+                    .add_column(
+                        ColumnDef::new(ForumUserStats::Reputation)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ForumTopics::Table)
+                    .add_column(
+                        ColumnDef::new(ForumTopics::IsWiki)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ForumReputationEvents::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ForumReputationEvents::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ForumReputationEvents::TenantId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ForumReputationEvents::UserId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ForumReputationEvents::SourceKind)
+                            .string_len(32)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ForumReputationEvents::SourceId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ForumReputationEvents::ActorId).uuid())
+                    .col(
+                        ColumnDef::new(ForumReputationEvents::Points)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ForumReputationEvents::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ForumReputationEvents::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_forum_reputation_events_source")
+                    .table(ForumReputationEvents::Table)
+                    .col(ForumReputationEvents::TenantId)
+                    .col(ForumReputationEvents::SourceKind)
+                    .col(ForumReputationEvents::SourceId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_forum_reputation_events_user")
+                    .table(ForumReputationEvents::Table)
+                    .col(ForumReputationEvents::TenantId)
+                    .col(ForumReputationEvents::UserId)
+                    .col(ForumReputationEvents::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ForumBadges::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ForumBadges::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ForumBadges::TenantId).uuid().not_null())
+                    .col(ColumnDef::new(ForumBadges::Slug).string_len(64).not_null())
+                    .col(ColumnDef::new(ForumBadges::Name).string_len(128).not_null())
+                    .col(ColumnDef::new(ForumBadges::Description).text())
+                    .col(ColumnDef::new(ForumBadges::Icon).string_len(64))
+                    .col(ColumnDef::new(ForumBadges::Rule).json_binary().not_null())
+                    .col(
+                        ColumnDef::new(ForumBadges::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ForumBadges::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_forum_badges_tenant_slug")
+                    .table(ForumBadges::Table)
+                    .col(ForumBadges::TenantId)
+                    .col(ForumBadges::Slug)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ForumBadges::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(ForumReputationEvents::Table).to_owned())
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ForumTopics::Table)
+                    .drop_column(ForumTopics::IsWiki)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(ForumUserStats::Table)
+                    .drop_column(ForumUserStats::Reputation)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum ForumUserStats {
+    Table,
+    Reputation,
+}
+
+#[derive(DeriveIden)]
+enum ForumTopics {
+    Table,
+    IsWiki,
+}
+
+#[derive(DeriveIden)]
+enum ForumReputationEvents {
+    Table,
+    Id,
+    TenantId,
+    UserId,
+    SourceKind,
+    SourceId,
+    ActorId,
+    Points,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum ForumBadges {
+    Table,
+    Id,
+    TenantId,
+    Slug,
+    Name,
+    Description,
+    Icon,
+    Rule,
+    CreatedAt,
+    UpdatedAt,
+}
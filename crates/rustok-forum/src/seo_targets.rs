@@ -283,6 +283,7 @@ impl SeoTargetProvider for ForumTopicSeoTargetProvider {
                     SecurityContext::system(),
                     ListTopicsFilter {
                         category_id: None,
+                        tag_id: None,
                         status: None,
                         locale: Some(request.locale.to_string()),
                         page: page_number,
@@ -334,6 +335,7 @@ impl SeoTargetProvider for ForumTopicSeoTargetProvider {
                     SecurityContext::system(),
                     ListTopicsFilter {
                         category_id: None,
+                        tag_id: None,
                         status: Some(topic_status::OPEN.to_string()),
                         locale: Some(request.default_locale.to_string()),
                         page: page_number,
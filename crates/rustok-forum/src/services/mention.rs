@@ -0,0 +1,179 @@
+//! Mention and quote handling for forum replies.
+
+use std::collections::HashSet;
+
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, DatabaseTransaction, EntityTrait, QueryFilter, QuerySelect,
+};
+use uuid::Uuid;
+
+use rustok_content::{scan_body, MentionContext, MentionService, MentionSource, QuoteReference};
+use rustok_events::DomainEvent;
+use rustok_outbox::TransactionalEventBus;
+use rustok_profiles::{ProfileService, SocialGraphService};
+
+use crate::entities::{
+    forum_reply, forum_topic, forum_topic_channel_access, forum_topic_subscription,
+};
+use crate::error::ForumResult;
+
+/// Users and quotes referenced by a reply body, resolved before the write transaction starts.
+#[derive(Debug, Default)]
+pub(crate) struct ReplyMentions {
+    users: Vec<Uuid>,
+    quotes: Vec<QuoteReference>,
+}
+
+impl ReplyMentions {
+    pub(crate) async fn resolve(
+        db: &DatabaseConnection,
+        tenant_id: Uuid,
+        body_format: &str,
+        body: &str,
+    ) -> ForumResult<Self> {
+        let scanned = scan_body(body_format, body);
+        if scanned.is_empty() {
+            return Ok(Self::default());
+        }
+
+        let resolved = ProfileService::new(db.clone())
+            .resolve_handles(tenant_id, &scanned.handles)
+            .await?;
+        Ok(Self {
+            users: scanned
+                .handles
+                .iter()
+                .filter_map(|handle| resolved.get(handle).copied())
+                .collect(),
+            quotes: scanned
+                .quotes
+                .into_iter()
+                .filter(|quote| quote.source == MentionSource::ForumReply)
+                .collect(),
+        })
+    }
+
+    /// Stores the reply's mentions and quotes and emits `UserMentioned` for newly mentioned users.
+    ///
+    /// Topics restricted to channels via `forum_topic_channel_access` only ping users that
+    /// already take part in the topic (author, repliers, subscribers), since channel access is
+    /// not tracked per user. Users who blocked or muted the author are not mentioned. Quotes of
+    /// replies from other tenants are dropped.
+    pub(crate) async fn apply_in_tx(
+        self,
+        txn: &DatabaseTransaction,
+        event_bus: &TransactionalEventBus,
+        topic: &forum_topic::Model,
+        reply_id: Uuid,
+        author_id: Option<Uuid>,
+        notify: bool,
+    ) -> ForumResult<()> {
+        let tenant_id = topic.tenant_id;
+        let mut users = self.users;
+        if let Some(author_id) = author_id {
+            let silenced =
+                SocialGraphService::silenced_by_in_tx(txn, tenant_id, author_id, &users).await?;
+            users.retain(|user_id| !silenced.contains(user_id));
+        }
+        let users = if users.is_empty() || !is_channel_restricted(txn, topic.id).await? {
+            users
+        } else {
+            let participants = topic_participants(txn, topic, &users).await?;
+            users
+                .into_iter()
+                .filter(|user_id| participants.contains(user_id))
+                .collect()
+        };
+
+        let quotes = if self.quotes.is_empty() {
+            self.quotes
+        } else {
+            let ids: Vec<Uuid> = self.quotes.iter().map(|quote| quote.source_id).collect();
+            let known: HashSet<Uuid> = forum_reply::Entity::find()
+                .filter(forum_reply::Column::TenantId.eq(tenant_id))
+                .filter(forum_reply::Column::Id.is_in(ids))
+                .all(txn)
+                .await?
+                .into_iter()
+                .map(|reply| reply.id)
+                .collect();
+            self.quotes
+                .into_iter()
+                .filter(|quote| known.contains(&quote.source_id))
+                .collect()
+        };
+
+        let added = MentionService::sync_in_tx(
+            txn,
+            MentionContext {
+                tenant_id,
+                source: MentionSource::ForumReply,
+                source_id: reply_id,
+                target_id: topic.id,
+                author_id,
+            },
+            &users,
+            &quotes,
+        )
+        .await?;
+
+        if notify {
+            for mentioned_user_id in added {
+                event_bus
+                    .publish_in_tx(
+                        txn,
+                        tenant_id,
+                        author_id,
+                        DomainEvent::UserMentioned {
+                            mentioned_user_id,
+                            author_id,
+                            source_kind: MentionSource::ForumReply.as_str().to_string(),
+                            source_id: reply_id,
+                            target_id: topic.id,
+                        },
+                    )
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+async fn is_channel_restricted(txn: &DatabaseTransaction, topic_id: Uuid) -> ForumResult<bool> {
+    Ok(forum_topic_channel_access::Entity::find()
+        .filter(forum_topic_channel_access::Column::TopicId.eq(topic_id))
+        .one(txn)
+        .await?
+        .is_some())
+}
+
+/// Which of `candidates` take part in the topic as its author, a replier or a subscriber.
+async fn topic_participants(
+    txn: &DatabaseTransaction,
+    topic: &forum_topic::Model,
+    candidates: &[Uuid],
+) -> ForumResult<HashSet<Uuid>> {
+    let mut participants: HashSet<Uuid> = forum_reply::Entity::find()
+        .select_only()
+        .column(forum_reply::Column::AuthorId)
+        .distinct()
+        .filter(forum_reply::Column::TopicId.eq(topic.id))
+        .filter(forum_reply::Column::AuthorId.is_in(candidates.iter().copied()))
+        .into_tuple::<Uuid>()
+        .all(txn)
+        .await?
+        .into_iter()
+        .collect();
+    participants.extend(topic.author_id);
+    participants.extend(
+        forum_topic_subscription::Entity::find()
+            .select_only()
+            .column(forum_topic_subscription::Column::UserId)
+            .filter(forum_topic_subscription::Column::TopicId.eq(topic.id))
+            .filter(forum_topic_subscription::Column::UserId.is_in(candidates.iter().copied()))
+            .into_tuple::<Uuid>()
+            .all(txn)
+            .await?,
+    );
+    Ok(participants)
+}
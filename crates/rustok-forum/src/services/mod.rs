@@ -1,7 +1,9 @@
 pub mod category;
+mod mention;
 pub mod moderation;
 mod rbac;
 pub mod reply;
+pub mod reputation;
 pub mod subscription;
 pub mod topic;
 pub mod user_stats;
@@ -11,6 +13,7 @@ pub mod widget_contract;
 pub use category::CategoryService;
 pub use moderation::ModerationService;
 pub use reply::ReplyService;
+pub use reputation::{ReputationPolicy, ReputationService};
 pub use subscription::SubscriptionService;
 pub use topic::TopicService;
 pub use user_stats::UserStatsService;
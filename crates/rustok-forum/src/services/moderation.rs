@@ -1,20 +1,27 @@
+use std::sync::Arc;
+
 use chrono::Utc;
 use sea_orm::{
-    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
-    TransactionTrait,
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, DatabaseConnection,
+    EntityTrait, QueryFilter, TransactionTrait,
 };
 use tracing::instrument;
 use uuid::Uuid;
 
+use rustok_content::{
+    ModerationLogInput, ModerationLogService, ModeratorVerdict, SpamDecisionLog, SpamPipeline,
+    SpamSurface,
+};
 use rustok_core::{Action, Resource, SecurityContext};
 use rustok_events::DomainEvent;
 use rustok_outbox::TransactionalEventBus;
 
-use crate::constants::reply_status;
+use crate::constants::{reply_status, reputation_source};
 use crate::entities::forum_solution;
 use crate::error::ForumError;
 use crate::error::ForumResult;
 use crate::services::rbac::{enforce_owned_scope, enforce_scope};
+use crate::services::reputation::{ReputationPolicy, ReputationService};
 use crate::services::user_stats::UserStatsService;
 use crate::services::{ReplyService, TopicService};
 use crate::state_machine::{ReplyStatus, TopicStatus};
@@ -22,11 +29,30 @@ use crate::state_machine::{ReplyStatus, TopicStatus};
 pub struct ModerationService {
     db: DatabaseConnection,
     event_bus: TransactionalEventBus,
+    spam_pipeline: Option<Arc<SpamPipeline>>,
+    reputation_policy: ReputationPolicy,
 }
 
 impl ModerationService {
     pub fn new(db: DatabaseConnection, event_bus: TransactionalEventBus) -> Self {
-        Self { db, event_bus }
+        Self {
+            db,
+            event_bus,
+            spam_pipeline: None,
+            reputation_policy: ReputationPolicy::default(),
+        }
+    }
+
+    /// Points credited for accepted solutions and deducted for rejected replies.
+    pub fn with_reputation_policy(mut self, policy: ReputationPolicy) -> Self {
+        self.reputation_policy = policy;
+        self
+    }
+
+    /// Records approve/reject decisions as feedback for the anti-spam pipeline.
+    pub fn with_spam_pipeline(mut self, pipeline: Arc<SpamPipeline>) -> Self {
+        self.spam_pipeline = Some(pipeline);
+        self
     }
 
     #[instrument(skip(self, security))]
@@ -90,6 +116,13 @@ impl ModerationService {
         enforce_scope(&security, Resource::ForumTopics, Action::Moderate)?;
         let txn = self.db.begin().await?;
         TopicService::set_pinned_in_tx(&txn, tenant_id, topic_id, true).await?;
+        record_action(
+            &txn,
+            tenant_id,
+            &security,
+            ModerationLogInput::new("forum.topic.pinned", "forum_topic", topic_id),
+        )
+        .await?;
         self.event_bus
             .publish_in_tx(
                 &txn,
@@ -116,6 +149,13 @@ impl ModerationService {
         enforce_scope(&security, Resource::ForumTopics, Action::Moderate)?;
         let txn = self.db.begin().await?;
         TopicService::set_pinned_in_tx(&txn, tenant_id, topic_id, false).await?;
+        record_action(
+            &txn,
+            tenant_id,
+            &security,
+            ModerationLogInput::new("forum.topic.unpinned", "forum_topic", topic_id),
+        )
+        .await?;
         self.event_bus
             .publish_in_tx(
                 &txn,
@@ -142,6 +182,13 @@ impl ModerationService {
         enforce_scope(&security, Resource::ForumTopics, Action::Moderate)?;
         let txn = self.db.begin().await?;
         TopicService::set_locked_in_tx(&txn, tenant_id, topic_id, true).await?;
+        record_action(
+            &txn,
+            tenant_id,
+            &security,
+            ModerationLogInput::new("forum.topic.locked", "forum_topic", topic_id),
+        )
+        .await?;
         txn.commit().await?;
         Ok(())
     }
@@ -156,6 +203,13 @@ impl ModerationService {
         enforce_scope(&security, Resource::ForumTopics, Action::Moderate)?;
         let txn = self.db.begin().await?;
         TopicService::set_locked_in_tx(&txn, tenant_id, topic_id, false).await?;
+        record_action(
+            &txn,
+            tenant_id,
+            &security,
+            ModerationLogInput::new("forum.topic.unlocked", "forum_topic", topic_id),
+        )
+        .await?;
         txn.commit().await?;
         Ok(())
     }
@@ -196,6 +250,35 @@ impl ModerationService {
             .await
     }
 
+    /// Turns a topic into a wiki that trusted members may edit, or back. Allowed for the topic
+    /// author and moderators.
+    #[instrument(skip(self, security))]
+    pub async fn set_topic_wiki(
+        &self,
+        tenant_id: Uuid,
+        topic_id: Uuid,
+        security: SecurityContext,
+        is_wiki: bool,
+    ) -> ForumResult<()> {
+        let topic_service = TopicService::new(self.db.clone(), self.event_bus.clone());
+        let topic = topic_service.find_topic(tenant_id, topic_id).await?;
+        enforce_topic_owner_or_moderator(&security, topic.author_id)?;
+
+        let txn = self.db.begin().await?;
+        TopicService::set_wiki_in_tx(&txn, tenant_id, topic_id, is_wiki).await?;
+        record_action(
+            &txn,
+            tenant_id,
+            &security,
+            ModerationLogInput::new("forum.topic.wiki_changed", "forum_topic", topic_id)
+                .with_subject(topic.author_id)
+                .with_details(serde_json::json!({ "is_wiki": is_wiki })),
+        )
+        .await?;
+        txn.commit().await?;
+        Ok(())
+    }
+
     #[instrument(skip(self, security))]
     pub async fn mark_solution(
         &self,
@@ -207,7 +290,7 @@ impl ModerationService {
         let topic_service = TopicService::new(self.db.clone(), self.event_bus.clone());
         let reply_service = ReplyService::new(self.db.clone(), self.event_bus.clone());
         let topic = topic_service.find_topic(tenant_id, topic_id).await?;
-        enforce_solution_scope(&security, topic.author_id)?;
+        enforce_topic_owner_or_moderator(&security, topic.author_id)?;
         let reply = reply_service.find_reply(tenant_id, reply_id).await?;
         if reply.topic_id != topic_id {
             return Err(ForumError::Validation(
@@ -256,7 +339,58 @@ impl ModerationService {
             .await?;
             UserStatsService::adjust_solution_count_in_tx(&txn, tenant_id, reply.author_id, 1)
                 .await?;
+            ReputationService::record_in_tx(
+                &txn,
+                tenant_id,
+                previous_solution_author_id,
+                reputation_source::SOLUTION,
+                topic_id,
+                None,
+                0,
+            )
+            .await?;
+            // Askers answering their own question do not earn solution points.
+            let points = if reply.author_id == topic.author_id {
+                0
+            } else {
+                self.reputation_policy.accepted_solution
+            };
+            ReputationService::record_in_tx(
+                &txn,
+                tenant_id,
+                reply.author_id,
+                reputation_source::SOLUTION,
+                topic_id,
+                None,
+                points,
+            )
+            .await?;
+            self.event_bus
+                .publish_in_tx(
+                    &txn,
+                    tenant_id,
+                    security.user_id,
+                    DomainEvent::ForumTopicSolutionMarked {
+                        topic_id,
+                        reply_id,
+                        author_id: reply.author_id,
+                        moderator_id: security.user_id,
+                    },
+                )
+                .await?;
         }
+        record_action(
+            &txn,
+            tenant_id,
+            &security,
+            ModerationLogInput::new("forum.topic.solution_marked", "forum_topic", topic_id)
+                .with_subject(reply.author_id)
+                .with_details(serde_json::json!({
+                    "reply_id": reply_id,
+                    "previous_reply_id": previous_solution_reply_id,
+                })),
+        )
+        .await?;
         txn.commit().await?;
         Ok(())
     }
@@ -270,7 +404,7 @@ impl ModerationService {
     ) -> ForumResult<()> {
         let topic_service = TopicService::new(self.db.clone(), self.event_bus.clone());
         let topic = topic_service.find_topic(tenant_id, topic_id).await?;
-        enforce_solution_scope(&security, topic.author_id)?;
+        enforce_topic_owner_or_moderator(&security, topic.author_id)?;
 
         let txn = self.db.begin().await?;
         let solution_author_id = if let Some(solution) =
@@ -290,6 +424,24 @@ impl ModerationService {
             .await?;
         UserStatsService::adjust_solution_count_in_tx(&txn, tenant_id, solution_author_id, -1)
             .await?;
+        ReputationService::record_in_tx(
+            &txn,
+            tenant_id,
+            solution_author_id,
+            reputation_source::SOLUTION,
+            topic_id,
+            None,
+            0,
+        )
+        .await?;
+        record_action(
+            &txn,
+            tenant_id,
+            &security,
+            ModerationLogInput::new("forum.topic.solution_cleared", "forum_topic", topic_id)
+                .with_subject(solution_author_id),
+        )
+        .await?;
         txn.commit().await?;
         Ok(())
     }
@@ -313,6 +465,51 @@ impl ModerationService {
         let new_status = target.as_str().to_string();
 
         ReplyService::set_status_in_tx(&txn, tenant_id, reply_id, &new_status).await?;
+        let penalty = if target == ReplyStatus::Rejected {
+            self.reputation_policy.rejected_reply
+        } else {
+            0
+        };
+        ReputationService::record_in_tx(
+            &txn,
+            tenant_id,
+            reply.author_id,
+            reputation_source::REPLY_REJECTED,
+            reply_id,
+            None,
+            penalty,
+        )
+        .await?;
+        record_action(
+            &txn,
+            tenant_id,
+            &security,
+            ModerationLogInput::new("forum.reply.status_changed", "forum_reply", reply_id)
+                .with_subject(reply.author_id)
+                .with_details(serde_json::json!({
+                    "topic_id": topic_id,
+                    "old_status": old_status,
+                    "new_status": new_status,
+                })),
+        )
+        .await?;
+
+        let spam_feedback = match target {
+            ReplyStatus::Approved => Some(ModeratorVerdict::Ham),
+            ReplyStatus::Rejected => Some(ModeratorVerdict::Spam),
+            _ => None,
+        };
+        if let (Some(_), Some(verdict)) = (&self.spam_pipeline, spam_feedback) {
+            SpamDecisionLog::record_moderator_verdict(
+                &txn,
+                tenant_id,
+                SpamSurface::ForumReply,
+                reply_id,
+                verdict,
+                security.user_id,
+            )
+            .await?;
+        }
 
         self.event_bus
             .publish_in_tx(
@@ -350,6 +547,18 @@ impl ModerationService {
 
         let txn = self.db.begin().await?;
         TopicService::set_status_in_tx(&txn, tenant_id, topic_id, &new_status).await?;
+        record_action(
+            &txn,
+            tenant_id,
+            &security,
+            ModerationLogInput::new("forum.topic.status_changed", "forum_topic", topic_id)
+                .with_subject(topic.author_id)
+                .with_details(serde_json::json!({
+                    "old_status": old_status,
+                    "new_status": new_status,
+                })),
+        )
+        .await?;
         self.event_bus
             .publish_in_tx(
                 &txn,
@@ -368,7 +577,20 @@ impl ModerationService {
     }
 }
 
-fn enforce_solution_scope(
+async fn record_action<C>(
+    conn: &C,
+    tenant_id: Uuid,
+    security: &SecurityContext,
+    input: ModerationLogInput,
+) -> ForumResult<()>
+where
+    C: ConnectionTrait,
+{
+    ModerationLogService::record_in_tx(conn, tenant_id, security.user_id, input).await?;
+    Ok(())
+}
+
+fn enforce_topic_owner_or_moderator(
     security: &SecurityContext,
     topic_author_id: Option<Uuid>,
 ) -> ForumResult<()> {
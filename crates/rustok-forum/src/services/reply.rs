@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use chrono::Utc;
 use sea_orm::{
@@ -9,11 +10,17 @@ use tracing::instrument;
 use uuid::Uuid;
 
 use rustok_content::{
-    normalize_locale_code, resolve_by_locale_with_fallback, PLATFORM_FALLBACK_LOCALE,
+    normalize_locale_code, resolve_by_locale_with_fallback, MentionService, MentionSource,
+    SanctionService, SpamAction, SpamPipeline, SpamSubmission, SpamSurface, SubmissionOrigin,
+    PLATFORM_FALLBACK_LOCALE,
+};
+use rustok_core::{
+    prepare_content_payload, render_content, Action, PermissionScope, Resource, SecurityContext,
 };
-use rustok_core::{prepare_content_payload, Action, Resource, SecurityContext};
 use rustok_events::DomainEvent;
 use rustok_outbox::TransactionalEventBus;
+use rustok_profiles::SocialGraphService;
+use rustok_rbac::{TrustCapability, TrustLevelPolicy};
 
 use crate::constants::{reply_status, topic_status};
 use crate::dto::{
@@ -21,7 +28,9 @@ use crate::dto::{
 };
 use crate::entities::{forum_reply, forum_reply_body, forum_solution};
 use crate::error::{ForumError, ForumResult};
+use crate::services::mention::ReplyMentions;
 use crate::services::rbac::{enforce_owned_scope, enforce_scope};
+use crate::services::reputation::ReputationService;
 use crate::services::user_stats::UserStatsService;
 use crate::services::vote::{VoteService, VoteSummary};
 use crate::services::{CategoryService, TopicService};
@@ -29,24 +38,62 @@ use crate::services::{CategoryService, TopicService};
 pub struct ReplyService {
     db: DatabaseConnection,
     event_bus: TransactionalEventBus,
+    spam_pipeline: Option<Arc<SpamPipeline>>,
+    trust_policy: TrustLevelPolicy,
 }
 
 impl ReplyService {
     pub fn new(db: DatabaseConnection, event_bus: TransactionalEventBus) -> Self {
-        Self { db, event_bus }
+        Self {
+            db,
+            event_bus,
+            spam_pipeline: None,
+            trust_policy: TrustLevelPolicy::default(),
+        }
+    }
+
+    /// Trust levels required to post links and to bypass moderated categories.
+    pub fn with_trust_policy(mut self, policy: TrustLevelPolicy) -> Self {
+        self.trust_policy = policy;
+        self
+    }
+
+    /// Screens replies from non-moderators with the given anti-spam pipeline.
+    pub fn with_spam_pipeline(mut self, pipeline: Arc<SpamPipeline>) -> Self {
+        self.spam_pipeline = Some(pipeline);
+        self
     }
 
-    #[instrument(skip(self, security, input))]
     pub async fn create(
         &self,
         tenant_id: Uuid,
         security: SecurityContext,
         topic_id: Uuid,
         input: CreateReplyInput,
+    ) -> ForumResult<ReplyResponse> {
+        self.create_with_origin(
+            tenant_id,
+            security,
+            topic_id,
+            input,
+            SubmissionOrigin::default(),
+        )
+        .await
+    }
+
+    #[instrument(skip(self, security, input, origin))]
+    pub async fn create_with_origin(
+        &self,
+        tenant_id: Uuid,
+        security: SecurityContext,
+        topic_id: Uuid,
+        input: CreateReplyInput,
+        origin: SubmissionOrigin,
     ) -> ForumResult<ReplyResponse> {
         enforce_scope(&security, Resource::ForumReplies, Action::Create)?;
         let locale = normalize_locale(&input.locale)?;
         let txn = self.db.begin().await?;
+        SanctionService::ensure_can_post(&txn, tenant_id, security.user_id).await?;
         let topic = TopicService::find_topic_in_tx(&txn, tenant_id, topic_id).await?;
         let category =
             CategoryService::find_category_in_tx(&txn, tenant_id, topic.category_id).await?;
@@ -57,6 +104,13 @@ impl ReplyService {
         if topic.status == topic_status::ARCHIVED {
             return Err(ForumError::TopicArchived);
         }
+        SocialGraphService::ensure_not_blocked_in_tx(
+            &txn,
+            tenant_id,
+            topic.author_id,
+            security.user_id,
+        )
+        .await?;
 
         let prepared_body = prepare_content_payload(
             Some(&input.content_format),
@@ -66,6 +120,21 @@ impl ReplyService {
             "Reply content",
         )
         .map_err(ForumError::Validation)?;
+        ReputationService::ensure_can_post_links_in_tx(
+            &txn,
+            &self.trust_policy,
+            tenant_id,
+            &security,
+            &prepared_body.body,
+        )
+        .await?;
+        let mentions = ReplyMentions::resolve(
+            &self.db,
+            tenant_id,
+            &prepared_body.format,
+            &prepared_body.body,
+        )
+        .await?;
 
         if let Some(parent_reply_id) = input.parent_reply_id {
             let parent = Self::find_reply_in_tx(&txn, tenant_id, parent_reply_id).await?;
@@ -74,10 +143,55 @@ impl ReplyService {
                     "Parent reply belongs to another topic".to_string(),
                 ));
             }
+            SocialGraphService::ensure_not_blocked_in_tx(
+                &txn,
+                tenant_id,
+                parent.author_id,
+                security.user_id,
+            )
+            .await?;
         }
 
         let position = Self::next_position_in_tx(&txn, topic_id).await?;
         let reply_id = Uuid::new_v4();
+        let mut status = if category.moderated
+            && !ReputationService::allows_in_tx(
+                &txn,
+                &self.trust_policy,
+                tenant_id,
+                &security,
+                TrustCapability::SkipModeration,
+            )
+            .await?
+        {
+            reply_status::PENDING
+        } else {
+            reply_status::APPROVED
+        };
+        if let Some(pipeline) = self.spam_pipeline.as_ref().filter(|_| {
+            matches!(
+                security.get_scope(Resource::ForumReplies, Action::Moderate),
+                PermissionScope::None
+            )
+        }) {
+            let submission = SpamSubmission {
+                tenant_id,
+                surface: SpamSurface::ForumReply,
+                author_id: security.user_id,
+                ip_address: origin.ip_address,
+                locale: locale.clone(),
+                body: prepared_body.body.clone(),
+            };
+            let decision = pipeline
+                .screen_and_record(&txn, &submission, reply_id)
+                .await?;
+            status = match decision.action {
+                SpamAction::Allow => status,
+                SpamAction::Hold => reply_status::PENDING,
+                SpamAction::Spam => reply_status::REJECTED,
+            };
+        }
+
         let now = Utc::now();
         forum_reply::ActiveModel {
             id: Set(reply_id),
@@ -85,12 +199,7 @@ impl ReplyService {
             topic_id: Set(topic_id),
             author_id: Set(security.user_id),
             parent_reply_id: Set(input.parent_reply_id),
-            status: Set(if category.moderated {
-                reply_status::PENDING
-            } else {
-                reply_status::APPROVED
-            }
-            .to_string()),
+            status: Set(status.to_string()),
             position: Set(position),
             created_at: Set(now.into()),
             updated_at: Set(now.into()),
@@ -126,6 +235,16 @@ impl ReplyService {
                 },
             )
             .await?;
+        mentions
+            .apply_in_tx(
+                &txn,
+                &self.event_bus,
+                &topic,
+                reply_id,
+                security.user_id,
+                status != reply_status::REJECTED,
+            )
+            .await?;
 
         txn.commit().await?;
         self.get(tenant_id, security, reply_id, &locale).await
@@ -203,8 +322,35 @@ impl ReplyService {
             "Reply content",
         )
         .map_err(ForumError::Validation)?;
+        let mentions = ReplyMentions::resolve(
+            &self.db,
+            tenant_id,
+            &prepared_body.format,
+            &prepared_body.body,
+        )
+        .await?;
 
         let txn = self.db.begin().await?;
+        SanctionService::ensure_can_post(&txn, tenant_id, security.user_id).await?;
+        ReputationService::ensure_can_post_links_in_tx(
+            &txn,
+            &self.trust_policy,
+            tenant_id,
+            &security,
+            &prepared_body.body,
+        )
+        .await?;
+        let topic = TopicService::find_topic_in_tx(&txn, tenant_id, existing.topic_id).await?;
+        mentions
+            .apply_in_tx(
+                &txn,
+                &self.event_bus,
+                &topic,
+                reply_id,
+                existing.author_id,
+                existing.status != reply_status::REJECTED,
+            )
+            .await?;
         self.upsert_body_in_tx(
             &txn,
             reply_id,
@@ -243,6 +389,7 @@ impl ReplyService {
         forum_reply::Entity::delete_by_id(reply_id)
             .exec(&txn)
             .await?;
+        MentionService::delete_for_source_in_tx(&txn, MentionSource::ForumReply, reply_id).await?;
         let topic =
             TopicService::adjust_reply_count_in_tx(&txn, tenant_id, reply.topic_id, -1).await?;
         CategoryService::adjust_counters_in_tx(&txn, tenant_id, topic.category_id, 0, -1).await?;
@@ -299,11 +446,10 @@ impl ReplyService {
             .map(|reply| {
                 let bodies = bodies_map.get(&reply.id).cloned().unwrap_or_default();
                 let resolved = resolve_reply_body(&bodies, &locale, fallback_locale.as_deref());
-                let content = resolved
+                let preview = resolved
                     .item
-                    .map(|body| body.body.clone())
+                    .map(|body| render_content(&body.body_format, &body.body).excerpt(200))
                     .unwrap_or_default();
-                let preview: String = content.chars().take(200).collect();
                 ReplyListItem {
                     id: reply.id,
                     locale: locale.clone(),
@@ -588,6 +734,7 @@ fn to_reply_response(
     } else {
         None
     };
+    let content_html = render_content(&content_format, &content).html;
 
     ReplyResponse {
         id: reply.id,
@@ -599,6 +746,7 @@ fn to_reply_response(
         content,
         content_format,
         content_json,
+        content_html,
         status: reply.status,
         vote_score: vote_summary.score,
         current_user_vote: vote_summary.current_user_vote,
@@ -656,6 +804,12 @@ mod tests {
 
     async fn ensure_forum_schema(db: &DatabaseConnection) {
         let manager = SchemaManager::new(db);
+        for migration in rustok_content::migrations::migrations() {
+            migration
+                .up(&manager)
+                .await
+                .expect("content migration should apply");
+        }
         for migration in migrations::migrations() {
             migration
                 .up(&manager)
@@ -0,0 +1,382 @@
+//! Reputation ledger, trust levels and rule-based badges.
+//!
+//! Votes received, accepted solutions and moderation outcomes write ledger rows in the same
+//! transaction as the change that caused them. Each row holds the points a user currently owes to
+//! one source, so changing or withdrawing a vote rewrites the row instead of appending to it. The
+//! running total lives in `forum_user_stats.reputation`; trust levels are derived from it through
+//! [`TrustLevelPolicy`] and gate capabilities via `rustok-rbac`.
+
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, DatabaseConnection,
+    DatabaseTransaction, EntityTrait, QueryFilter, QueryOrder,
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use rustok_core::{Action, Permission, Resource, SecurityContext};
+use rustok_profiles::{AwardProfileBadgeInput, ProfileService};
+use rustok_rbac::{check_trust_capability, TrustCapability, TrustLevel, TrustLevelPolicy};
+
+use crate::constants::PROFILE_BADGE_SOURCE;
+use crate::dto::{BadgeResponse, BadgeRule, CreateBadgeInput};
+use crate::entities::{forum_badge, forum_reputation_event, forum_user_stat};
+use crate::error::{ForumError, ForumResult};
+use crate::services::rbac::enforce_scope;
+
+const MAX_BADGE_SLUG_LENGTH: usize = 64;
+const MAX_BADGE_NAME_LENGTH: usize = 128;
+
+/// Points granted per reputation source, plus the trust level thresholds built on top of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReputationPolicy {
+    pub topic_upvote: i32,
+    pub topic_downvote: i32,
+    pub reply_upvote: i32,
+    pub reply_downvote: i32,
+    pub accepted_solution: i32,
+    pub rejected_reply: i32,
+    pub trust: TrustLevelPolicy,
+}
+
+impl Default for ReputationPolicy {
+    fn default() -> Self {
+        Self {
+            topic_upvote: 5,
+            topic_downvote: -1,
+            reply_upvote: 10,
+            reply_downvote: -2,
+            accepted_solution: 15,
+            rejected_reply: -10,
+            trust: TrustLevelPolicy::default(),
+        }
+    }
+}
+
+impl ReputationPolicy {
+    pub(crate) fn topic_vote_points(&self, value: i32) -> i32 {
+        if value > 0 {
+            self.topic_upvote
+        } else {
+            self.topic_downvote
+        }
+    }
+
+    pub(crate) fn reply_vote_points(&self, value: i32) -> i32 {
+        if value > 0 {
+            self.reply_upvote
+        } else {
+            self.reply_downvote
+        }
+    }
+}
+
+pub struct ReputationService {
+    db: DatabaseConnection,
+    policy: ReputationPolicy,
+}
+
+impl ReputationService {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self {
+            db,
+            policy: ReputationPolicy::default(),
+        }
+    }
+
+    pub fn with_policy(mut self, policy: ReputationPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub async fn reputation(&self, tenant_id: Uuid, user_id: Uuid) -> ForumResult<i32> {
+        Self::reputation_in_tx(&self.db, tenant_id, Some(user_id)).await
+    }
+
+    pub async fn trust_level(&self, tenant_id: Uuid, user_id: Uuid) -> ForumResult<TrustLevel> {
+        Ok(self
+            .policy
+            .trust
+            .level_for(self.reputation(tenant_id, user_id).await?))
+    }
+
+    #[instrument(skip(self, security, input))]
+    pub async fn create_badge(
+        &self,
+        tenant_id: Uuid,
+        security: SecurityContext,
+        input: CreateBadgeInput,
+    ) -> ForumResult<BadgeResponse> {
+        enforce_scope(&security, Resource::ForumCategories, Action::Manage)?;
+        let slug = input.slug.trim().to_ascii_lowercase();
+        if slug.is_empty()
+            || slug.len() > MAX_BADGE_SLUG_LENGTH
+            || !slug
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+        {
+            return Err(ForumError::Validation(
+                "Badge slug must be 1-64 characters of [a-z0-9_-]".to_string(),
+            ));
+        }
+        let name = input.name.trim().to_string();
+        if name.is_empty() || name.chars().count() > MAX_BADGE_NAME_LENGTH {
+            return Err(ForumError::Validation(
+                "Badge name must be 1-128 characters".to_string(),
+            ));
+        }
+        if input.rule.is_empty() {
+            return Err(ForumError::Validation(
+                "Badge rule must set at least one threshold".to_string(),
+            ));
+        }
+
+        let duplicate = forum_badge::Entity::find()
+            .filter(forum_badge::Column::TenantId.eq(tenant_id))
+            .filter(forum_badge::Column::Slug.eq(slug.as_str()))
+            .one(&self.db)
+            .await?;
+        if duplicate.is_some() {
+            return Err(ForumError::Validation(format!(
+                "Badge slug already exists: {slug}"
+            )));
+        }
+
+        let now = Utc::now();
+        let badge = forum_badge::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tenant_id: Set(tenant_id),
+            slug: Set(slug),
+            name: Set(name),
+            description: Set(input.description),
+            icon: Set(input.icon),
+            rule: Set(serde_json::to_value(&input.rule)
+                .map_err(|err| ForumError::Validation(err.to_string()))?),
+            created_at: Set(now.into()),
+            updated_at: Set(now.into()),
+        }
+        .insert(&self.db)
+        .await?;
+        Ok(to_badge_response(badge))
+    }
+
+    pub async fn list_badges(&self, tenant_id: Uuid) -> ForumResult<Vec<BadgeResponse>> {
+        Ok(forum_badge::Entity::find()
+            .filter(forum_badge::Column::TenantId.eq(tenant_id))
+            .order_by_asc(forum_badge::Column::CreatedAt)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(to_badge_response)
+            .collect())
+    }
+
+    /// Deletes a badge definition. Badges already shown on profiles are kept.
+    #[instrument(skip(self, security))]
+    pub async fn delete_badge(
+        &self,
+        tenant_id: Uuid,
+        security: SecurityContext,
+        badge_id: Uuid,
+    ) -> ForumResult<()> {
+        enforce_scope(&security, Resource::ForumCategories, Action::Manage)?;
+        let result = forum_badge::Entity::delete_many()
+            .filter(forum_badge::Column::TenantId.eq(tenant_id))
+            .filter(forum_badge::Column::Id.eq(badge_id))
+            .exec(&self.db)
+            .await?;
+        if result.rows_affected == 0 {
+            return Err(ForumError::Validation(format!(
+                "Badge not found: {badge_id}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Sets the points `user_id` holds from one source and updates their running total.
+    ///
+    /// `actor_id` is part of the ledger key: votes pass the voter, while solutions and moderation
+    /// outcomes pass `None` so re-marking by someone else rewrites the same row. Zero points
+    /// remove the row, and users never earn points from their own votes.
+    pub(crate) async fn record_in_tx(
+        txn: &DatabaseTransaction,
+        tenant_id: Uuid,
+        user_id: Option<Uuid>,
+        source_kind: &str,
+        source_id: Uuid,
+        actor_id: Option<Uuid>,
+        points: i32,
+    ) -> ForumResult<()> {
+        let Some(user_id) = user_id else {
+            return Ok(());
+        };
+        let points = if actor_id == Some(user_id) { 0 } else { points };
+
+        let mut query = forum_reputation_event::Entity::find()
+            .filter(forum_reputation_event::Column::TenantId.eq(tenant_id))
+            .filter(forum_reputation_event::Column::UserId.eq(user_id))
+            .filter(forum_reputation_event::Column::SourceKind.eq(source_kind))
+            .filter(forum_reputation_event::Column::SourceId.eq(source_id));
+        query = match actor_id {
+            Some(actor_id) => query.filter(forum_reputation_event::Column::ActorId.eq(actor_id)),
+            None => query.filter(forum_reputation_event::Column::ActorId.is_null()),
+        };
+        let existing = query.one(txn).await?;
+        let delta = points - existing.as_ref().map_or(0, |entry| entry.points);
+        if delta == 0 {
+            return Ok(());
+        }
+
+        let now = Utc::now();
+        match existing {
+            Some(existing) if points == 0 => {
+                forum_reputation_event::Entity::delete_by_id(existing.id)
+                    .exec(txn)
+                    .await?;
+            }
+            Some(existing) => {
+                let mut active: forum_reputation_event::ActiveModel = existing.into();
+                active.points = Set(points);
+                active.updated_at = Set(now.into());
+                active.update(txn).await?;
+            }
+            None => {
+                forum_reputation_event::ActiveModel {
+                    id: Set(Uuid::new_v4()),
+                    tenant_id: Set(tenant_id),
+                    user_id: Set(user_id),
+                    source_kind: Set(source_kind.to_string()),
+                    source_id: Set(source_id),
+                    actor_id: Set(actor_id),
+                    points: Set(points),
+                    created_at: Set(now.into()),
+                    updated_at: Set(now.into()),
+                }
+                .insert(txn)
+                .await?;
+            }
+        }
+
+        crate::services::UserStatsService::adjust_reputation_in_tx(
+            txn,
+            tenant_id,
+            Some(user_id),
+            delta,
+        )
+        .await
+    }
+
+    pub(crate) async fn reputation_in_tx<C: ConnectionTrait>(
+        conn: &C,
+        tenant_id: Uuid,
+        user_id: Option<Uuid>,
+    ) -> ForumResult<i32> {
+        let Some(user_id) = user_id else {
+            return Ok(0);
+        };
+        Ok(forum_user_stat::Entity::find_by_id((tenant_id, user_id))
+            .one(conn)
+            .await?
+            .map_or(0, |stat| stat.reputation))
+    }
+
+    /// Whether the acting user may use a trust-gated capability.
+    pub(crate) async fn allows_in_tx<C: ConnectionTrait>(
+        conn: &C,
+        policy: &TrustLevelPolicy,
+        tenant_id: Uuid,
+        security: &SecurityContext,
+        capability: TrustCapability,
+    ) -> ForumResult<bool> {
+        let permissions: Vec<Permission> = security.permissions().iter().copied().collect();
+        let reputation = Self::reputation_in_tx(conn, tenant_id, security.user_id).await?;
+        Ok(check_trust_capability(&permissions, reputation, capability, policy).allowed)
+    }
+
+    /// Rejects bodies with external links from members below the `PostLinks` trust level.
+    pub(crate) async fn ensure_can_post_links_in_tx<C: ConnectionTrait>(
+        conn: &C,
+        policy: &TrustLevelPolicy,
+        tenant_id: Uuid,
+        security: &SecurityContext,
+        body: &str,
+    ) -> ForumResult<()> {
+        if !contains_link(body)
+            || Self::allows_in_tx(
+                conn,
+                policy,
+                tenant_id,
+                security,
+                TrustCapability::PostLinks,
+            )
+            .await?
+        {
+            return Ok(());
+        }
+        Err(ForumError::forbidden(
+            "Trust level is too low to post links",
+        ))
+    }
+
+    /// Awards every tenant badge whose rule the given stats now satisfy.
+    pub(crate) async fn award_badges_in_tx(
+        txn: &DatabaseTransaction,
+        stats: &forum_user_stat::Model,
+    ) -> ForumResult<()> {
+        let badges = forum_badge::Entity::find()
+            .filter(forum_badge::Column::TenantId.eq(stats.tenant_id))
+            .all(txn)
+            .await?;
+        for badge in badges {
+            let Ok(rule) = serde_json::from_value::<BadgeRule>(badge.rule.clone()) else {
+                continue;
+            };
+            if rule.is_empty() || !rule_matches(&rule, stats) {
+                continue;
+            }
+            ProfileService::award_badge_in_tx(
+                txn,
+                stats.tenant_id,
+                stats.user_id,
+                AwardProfileBadgeInput {
+                    source: PROFILE_BADGE_SOURCE.to_string(),
+                    key: badge.slug,
+                    name: badge.name,
+                    description: badge.description,
+                    icon: badge.icon,
+                },
+            )
+            .await?;
+        }
+        Ok(())
+    }
+}
+
+/// Whether a body links to an external resource.
+fn contains_link(body: &str) -> bool {
+    let lowered = body.to_ascii_lowercase();
+    lowered.contains("http://") || lowered.contains("https://") || lowered.contains("www.")
+}
+
+fn rule_matches(rule: &BadgeRule, stats: &forum_user_stat::Model) -> bool {
+    [
+        (rule.min_reputation, stats.reputation),
+        (rule.min_topics, stats.topic_count),
+        (rule.min_replies, stats.reply_count),
+        (rule.min_solutions, stats.solution_count),
+    ]
+    .into_iter()
+    .all(|(threshold, value)| threshold.is_none_or(|threshold| value >= threshold))
+}
+
+fn to_badge_response(badge: forum_badge::Model) -> BadgeResponse {
+    BadgeResponse {
+        id: badge.id,
+        slug: badge.slug,
+        name: badge.name,
+        description: badge.description,
+        icon: badge.icon,
+        rule: serde_json::from_value(badge.rule).unwrap_or_default(),
+        created_at: badge.created_at.to_rfc3339(),
+    }
+}
@@ -8,6 +8,7 @@ use sea_orm::{
 use tracing::instrument;
 use uuid::Uuid;
 
+use rustok_content::SanctionService;
 use rustok_core::{Action, Resource, SecurityContext};
 
 use crate::entities::{
@@ -37,6 +38,7 @@ impl SubscriptionService {
         self.find_category(tenant_id, category_id).await?;
 
         let txn = self.db.begin().await?;
+        SanctionService::ensure_can_interact(&txn, tenant_id, Some(user_id)).await?;
         let existing = forum_category_subscription::Entity::find()
             .filter(forum_category_subscription::Column::TenantId.eq(tenant_id))
             .filter(forum_category_subscription::Column::CategoryId.eq(category_id))
@@ -93,6 +95,7 @@ impl SubscriptionService {
         self.find_topic(tenant_id, topic_id).await?;
 
         let txn = self.db.begin().await?;
+        SanctionService::ensure_can_interact(&txn, tenant_id, Some(user_id)).await?;
         let existing = forum_topic_subscription::Entity::find()
             .filter(forum_topic_subscription::Column::TenantId.eq(tenant_id))
             .filter(forum_topic_subscription::Column::TopicId.eq(topic_id))
@@ -26,7 +26,7 @@ use sea_orm::{
     ActiveModelTrait,
     ActiveValue::Set,
     ColumnTrait, Condition, DatabaseConnection, DatabaseTransaction, EntityTrait, PaginatorTrait,
-    QueryFilter, QueryOrder, Select, TransactionTrait,
+    QueryFilter, QueryOrder, QuerySelect, Select, TransactionTrait,
 };
 use serde_json::Value;
 use tracing::instrument;
@@ -34,12 +34,16 @@ use uuid::Uuid;
 
 use rustok_content::{
     available_locales_from, normalize_locale_code, resolve_by_locale_with_fallback,
-    PLATFORM_FALLBACK_LOCALE,
+    SanctionService, PLATFORM_FALLBACK_LOCALE,
 };
 use rustok_core::field_schema::{CustomFieldsSchema, FieldDefinition, FieldType, ValidationRule};
-use rustok_core::{prepare_content_payload, Action, Resource, SecurityContext};
+use rustok_core::{
+    prepare_content_payload, render_content, Action, FeedContentMode, FeedItem, Resource,
+    SecurityContext,
+};
 use rustok_events::DomainEvent;
 use rustok_outbox::TransactionalEventBus;
+use rustok_rbac::{TrustCapability, TrustLevelPolicy};
 use rustok_taxonomy::{TaxonomyService, TaxonomyTermKind};
 
 use crate::constants::topic_status;
@@ -53,6 +57,7 @@ use crate::entities::{
 use crate::error::{ForumError, ForumResult};
 use crate::services::category::CategoryService;
 use crate::services::rbac::{enforce_owned_scope, enforce_scope};
+use crate::services::reputation::ReputationService;
 use crate::services::subscription::SubscriptionService;
 use crate::services::user_stats::UserStatsService;
 use crate::services::vote::{VoteService, VoteSummary};
@@ -73,11 +78,22 @@ fn map_flex_cleanup_error(error: rustok_core::field_schema::FlexError) -> ForumE
 pub struct TopicService {
     db: DatabaseConnection,
     event_bus: TransactionalEventBus,
+    trust_policy: TrustLevelPolicy,
 }
 
 impl TopicService {
     pub fn new(db: DatabaseConnection, event_bus: TransactionalEventBus) -> Self {
-        Self { db, event_bus }
+        Self {
+            db,
+            event_bus,
+            trust_policy: TrustLevelPolicy::default(),
+        }
+    }
+
+    /// Trust levels required to post links and to edit other members' wiki topics.
+    pub fn with_trust_policy(mut self, policy: TrustLevelPolicy) -> Self {
+        self.trust_policy = policy;
+        self
     }
 
     #[instrument(skip(self, security, input))]
@@ -104,6 +120,15 @@ impl TopicService {
             .await?;
 
         let txn = self.db.begin().await?;
+        SanctionService::ensure_can_post(&txn, tenant_id, security.user_id).await?;
+        ReputationService::ensure_can_post_links_in_tx(
+            &txn,
+            &self.trust_policy,
+            tenant_id,
+            &security,
+            &prepared_body.body,
+        )
+        .await?;
         CategoryService::ensure_exists_in_tx(&txn, tenant_id, input.category_id).await?;
 
         let now = Utc::now();
@@ -120,6 +145,7 @@ impl TopicService {
                 .unwrap_or_else(|| serde_json::json!({}))),
             is_pinned: Set(false),
             is_locked: Set(false),
+            is_wiki: Set(false),
             reply_count: Set(0),
             created_at: Set(now.into()),
             updated_at: Set(now.into()),
@@ -254,12 +280,30 @@ impl TopicService {
     ) -> ForumResult<TopicResponse> {
         let locale = normalize_locale(&input.locale)?;
         let topic = self.find_topic(tenant_id, topic_id).await?;
-        enforce_owned_scope(
+        if let Err(error) = enforce_owned_scope(
             &security,
             Resource::ForumTopics,
             Action::Update,
             topic.author_id,
-        )?;
+        ) {
+            // Trusted members may edit the title and body of someone else's wiki topic.
+            let content_only =
+                input.metadata.is_none() && input.tags.is_none() && input.channel_slugs.is_none();
+            if !(topic.is_wiki
+                && content_only
+                && security.user_id.is_some()
+                && ReputationService::allows_in_tx(
+                    &self.db,
+                    &self.trust_policy,
+                    tenant_id,
+                    &security,
+                    TrustCapability::EditOthersWikis,
+                )
+                .await?)
+            {
+                return Err(error);
+            }
+        }
         let prepared_custom_fields = if let Some(metadata) = input.metadata.clone() {
             Some(
                 self.prepare_topic_custom_fields_for_update(
@@ -275,6 +319,21 @@ impl TopicService {
             None
         };
         let txn = self.db.begin().await?;
+        SanctionService::ensure_can_post(&txn, tenant_id, security.user_id).await?;
+        let submitted_body = input
+            .content_json
+            .as_ref()
+            .map(Value::to_string)
+            .or_else(|| input.body.clone())
+            .unwrap_or_default();
+        ReputationService::ensure_can_post_links_in_tx(
+            &txn,
+            &self.trust_policy,
+            tenant_id,
+            &security,
+            &submitted_body,
+        )
+        .await?;
         let normalized_tags = input.tags.as_ref().map(|tags| normalize_tags(tags));
 
         let mut active: forum_topic::ActiveModel = topic.into();
@@ -428,6 +487,11 @@ impl TopicService {
         if let Some(status) = filter.status {
             select = select.filter(forum_topic::Column::Status.eq(status));
         }
+        if let Some(tag_id) = filter.tag_id {
+            select = self
+                .apply_tag_tree_filter(select, tenant_id, tag_id)
+                .await?;
+        }
 
         let paginator = select
             .order_by_desc(forum_topic::Column::IsPinned)
@@ -472,6 +536,11 @@ impl TopicService {
         if let Some(category_id) = filter.category_id {
             select = select.filter(forum_topic::Column::CategoryId.eq(category_id));
         }
+        if let Some(tag_id) = filter.tag_id {
+            select = self
+                .apply_tag_tree_filter(select, tenant_id, tag_id)
+                .await?;
+        }
         select = apply_public_topic_channel_filter(select, channel_slug);
 
         let paginator = select
@@ -494,6 +563,76 @@ impl TopicService {
         Ok((items, total))
     }
 
+    /// Open, channel-visible topics as syndication entries, newest first.
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(skip(self))]
+    pub async fn list_public_feed_items(
+        &self,
+        tenant_id: Uuid,
+        category_id: Option<Uuid>,
+        locale: &str,
+        fallback_locale: Option<&str>,
+        channel_slug: Option<&str>,
+        limit: u64,
+        base_url: &str,
+        content_mode: FeedContentMode,
+    ) -> ForumResult<Vec<FeedItem>> {
+        let locale = normalize_locale(locale)?;
+        let fallback_locale = fallback_locale.map(normalize_locale).transpose()?;
+
+        let mut select = forum_topic::Entity::find()
+            .filter(forum_topic::Column::TenantId.eq(tenant_id))
+            .filter(forum_topic::Column::Status.eq(topic_status::OPEN));
+        if let Some(category_id) = category_id {
+            select = select.filter(forum_topic::Column::CategoryId.eq(category_id));
+        }
+        select = apply_public_topic_channel_filter(select, channel_slug);
+        let topics = select
+            .order_by_desc(forum_topic::Column::CreatedAt)
+            .limit(limit.max(1))
+            .all(&self.db)
+            .await?;
+
+        let topic_ids: Vec<Uuid> = topics.iter().map(|topic| topic.id).collect();
+        let translations_by_topic_id = self.load_translations_map_for_topics(&topic_ids).await?;
+
+        let mut items = Vec::with_capacity(topics.len());
+        for topic in topics {
+            let localized = translations_by_topic_id
+                .get(&topic.id)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            let resolved = resolve_by_locale_with_fallback(
+                localized,
+                &locale,
+                fallback_locale.as_deref(),
+                |translation| translation.locale.as_str(),
+            );
+            let Some(translation) = resolved.item else {
+                continue;
+            };
+            let published_at = topic.created_at.with_timezone(&Utc);
+            let mut item = FeedItem::new(
+                format!("urn:uuid:{}", topic.id),
+                translation.title.clone(),
+                format!(
+                    "{base_url}/modules/forum?category={}&topic={}",
+                    topic.category_id, topic.id
+                ),
+                published_at,
+            )
+            .with_content(
+                content_mode,
+                &render_content(&translation.body_format, &translation.body),
+                None,
+            );
+            item.updated_at = published_at.max(translation.updated_at.with_timezone(&Utc));
+            items.push(item);
+        }
+
+        Ok(items)
+    }
+
     pub(crate) async fn find_topic(
         &self,
         tenant_id: Uuid,
@@ -561,6 +700,20 @@ impl TopicService {
         Ok(())
     }
 
+    pub(crate) async fn set_wiki_in_tx(
+        txn: &DatabaseTransaction,
+        tenant_id: Uuid,
+        topic_id: Uuid,
+        is_wiki: bool,
+    ) -> ForumResult<()> {
+        let topic = Self::find_topic_in_tx(txn, tenant_id, topic_id).await?;
+        let mut active: forum_topic::ActiveModel = topic.into();
+        active.is_wiki = Set(is_wiki);
+        active.updated_at = Set(Utc::now().into());
+        active.update(txn).await?;
+        Ok(())
+    }
+
     pub(crate) async fn set_status_in_tx(
         txn: &DatabaseTransaction,
         tenant_id: Uuid,
@@ -767,6 +920,20 @@ impl TopicService {
         Ok(())
     }
 
+    async fn apply_tag_tree_filter(
+        &self,
+        select: Select<forum_topic::Entity>,
+        tenant_id: Uuid,
+        tag_id: Uuid,
+    ) -> ForumResult<Select<forum_topic::Entity>> {
+        let term_ids =
+            TaxonomyService::expand_descendants_in_tx(&self.db, tenant_id, &[tag_id]).await?;
+        Ok(select.filter(
+            Expr::col((forum_topic::Entity, forum_topic::Column::Id))
+                .in_subquery(tagged_topics_subquery(term_ids)),
+        ))
+    }
+
     async fn hydrate_topic_list_items(
         &self,
         tenant_id: Uuid,
@@ -846,6 +1013,7 @@ impl TopicService {
                 solution_reply_id: solution_reply_ids.get(&topic.id).copied(),
                 is_pinned: topic.is_pinned,
                 is_locked: topic.is_locked,
+                is_wiki: topic.is_wiki,
                 reply_count: topic.reply_count,
                 created_at: topic.created_at.to_rfc3339(),
             });
@@ -1079,6 +1247,7 @@ fn to_topic_response(
     } else {
         None
     };
+    let body_html = render_content(&body_format, &body).html;
 
     TopicResponse {
         id: topic.id,
@@ -1101,6 +1270,7 @@ fn to_topic_response(
         body,
         body_format,
         content_json,
+        body_html,
         metadata: parts.metadata,
         status: topic.status,
         tags: parts.tags,
@@ -1111,6 +1281,7 @@ fn to_topic_response(
         solution_reply_id: parts.solution_reply_id,
         is_pinned: topic.is_pinned,
         is_locked: topic.is_locked,
+        is_wiki: topic.is_wiki,
         reply_count: topic.reply_count,
         created_at: topic.created_at.to_rfc3339(),
         updated_at: topic.updated_at.to_rfc3339(),
@@ -1168,6 +1339,16 @@ fn normalize_channel_slugs(channel_slugs: &[String]) -> Vec<String> {
     normalized
 }
 
+fn tagged_topics_subquery(term_ids: Vec<Uuid>) -> SelectStatement {
+    Query::select()
+        .column(forum_topic_tag::Column::TopicId)
+        .from(forum_topic_tag::Entity)
+        .and_where(
+            Expr::col((forum_topic_tag::Entity, forum_topic_tag::Column::TermId)).is_in(term_ids),
+        )
+        .to_owned()
+}
+
 fn apply_public_topic_channel_filter(
     select: Select<forum_topic::Entity>,
     channel_slug: Option<&str>,
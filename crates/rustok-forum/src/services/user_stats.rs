@@ -6,19 +6,39 @@ use tracing::instrument;
 use uuid::Uuid;
 
 use rustok_core::{Action, Resource, SecurityContext};
+use rustok_rbac::TrustLevelPolicy;
 
 use crate::dto::ForumUserStatsResponse;
 use crate::entities::forum_user_stat;
 use crate::error::ForumResult;
 use crate::services::rbac::enforce_scope;
+use crate::services::reputation::ReputationService;
+
+#[derive(Debug, Clone, Copy, Default)]
+struct StatDeltas {
+    topics: i32,
+    replies: i32,
+    solutions: i32,
+    reputation: i32,
+}
 
 pub struct UserStatsService {
     db: DatabaseConnection,
+    trust_policy: TrustLevelPolicy,
 }
 
 impl UserStatsService {
     pub fn new(db: DatabaseConnection) -> Self {
-        Self { db }
+        Self {
+            db,
+            trust_policy: TrustLevelPolicy::default(),
+        }
+    }
+
+    /// Thresholds used to report the user's trust level.
+    pub fn with_trust_policy(mut self, policy: TrustLevelPolicy) -> Self {
+        self.trust_policy = policy;
+        self
     }
 
     #[instrument(skip(self, security))]
@@ -39,6 +59,12 @@ impl UserStatsService {
                 topic_count: row.topic_count,
                 reply_count: row.reply_count,
                 solution_count: row.solution_count,
+                reputation: row.reputation,
+                trust_level: self
+                    .trust_policy
+                    .level_for(row.reputation)
+                    .as_str()
+                    .to_string(),
                 updated_at: row.updated_at.to_rfc3339(),
             },
             None => ForumUserStatsResponse {
@@ -46,6 +72,8 @@ impl UserStatsService {
                 topic_count: 0,
                 reply_count: 0,
                 solution_count: 0,
+                reputation: 0,
+                trust_level: self.trust_policy.level_for(0).as_str().to_string(),
                 updated_at: Utc::now().to_rfc3339(),
             },
         })
@@ -57,7 +85,16 @@ impl UserStatsService {
         user_id: Option<Uuid>,
         delta: i32,
     ) -> ForumResult<()> {
-        Self::adjust_counts_in_tx(txn, tenant_id, user_id, delta, 0, 0).await
+        Self::adjust_counts_in_tx(
+            txn,
+            tenant_id,
+            user_id,
+            StatDeltas {
+                topics: delta,
+                ..StatDeltas::default()
+            },
+        )
+        .await
     }
 
     pub(crate) async fn adjust_reply_count_in_tx(
@@ -66,7 +103,16 @@ impl UserStatsService {
         user_id: Option<Uuid>,
         delta: i32,
     ) -> ForumResult<()> {
-        Self::adjust_counts_in_tx(txn, tenant_id, user_id, 0, delta, 0).await
+        Self::adjust_counts_in_tx(
+            txn,
+            tenant_id,
+            user_id,
+            StatDeltas {
+                replies: delta,
+                ..StatDeltas::default()
+            },
+        )
+        .await
     }
 
     pub(crate) async fn adjust_solution_count_in_tx(
@@ -75,16 +121,42 @@ impl UserStatsService {
         user_id: Option<Uuid>,
         delta: i32,
     ) -> ForumResult<()> {
-        Self::adjust_counts_in_tx(txn, tenant_id, user_id, 0, 0, delta).await
+        Self::adjust_counts_in_tx(
+            txn,
+            tenant_id,
+            user_id,
+            StatDeltas {
+                solutions: delta,
+                ..StatDeltas::default()
+            },
+        )
+        .await
     }
 
+    pub(crate) async fn adjust_reputation_in_tx(
+        txn: &DatabaseTransaction,
+        tenant_id: Uuid,
+        user_id: Option<Uuid>,
+        delta: i32,
+    ) -> ForumResult<()> {
+        Self::adjust_counts_in_tx(
+            txn,
+            tenant_id,
+            user_id,
+            StatDeltas {
+                reputation: delta,
+                ..StatDeltas::default()
+            },
+        )
+        .await
+    }
+
+    /// Applies counter deltas and awards any badges the new totals unlock.
     async fn adjust_counts_in_tx(
         txn: &DatabaseTransaction,
         tenant_id: Uuid,
         user_id: Option<Uuid>,
-        topic_delta: i32,
-        reply_delta: i32,
-        solution_delta: i32,
+        deltas: StatDeltas,
     ) -> ForumResult<()> {
         let Some(user_id) = user_id else {
             return Ok(());
@@ -95,45 +167,40 @@ impl UserStatsService {
             .one(txn)
             .await?;
 
-        match existing {
+        let stats = match existing {
             Some(existing) => {
+                let topic_count = (existing.topic_count + deltas.topics).max(0);
+                let reply_count = (existing.reply_count + deltas.replies).max(0);
+                let solution_count = (existing.solution_count + deltas.solutions).max(0);
+                let reputation = existing.reputation + deltas.reputation;
                 let mut active: forum_user_stat::ActiveModel = existing.into();
-                let current_topic = match active.topic_count.clone() {
-                    sea_orm::ActiveValue::Set(value) => value,
-                    sea_orm::ActiveValue::Unchanged(value) => value,
-                    sea_orm::ActiveValue::NotSet => 0,
-                };
-                let current_reply = match active.reply_count.clone() {
-                    sea_orm::ActiveValue::Set(value) => value,
-                    sea_orm::ActiveValue::Unchanged(value) => value,
-                    sea_orm::ActiveValue::NotSet => 0,
-                };
-                let current_solution = match active.solution_count.clone() {
-                    sea_orm::ActiveValue::Set(value) => value,
-                    sea_orm::ActiveValue::Unchanged(value) => value,
-                    sea_orm::ActiveValue::NotSet => 0,
-                };
-                active.topic_count = Set((current_topic + topic_delta).max(0));
-                active.reply_count = Set((current_reply + reply_delta).max(0));
-                active.solution_count = Set((current_solution + solution_delta).max(0));
+                active.topic_count = Set(topic_count);
+                active.reply_count = Set(reply_count);
+                active.solution_count = Set(solution_count);
+                active.reputation = Set(reputation);
                 active.updated_at = Set(now.into());
-                active.update(txn).await?;
+                active.update(txn).await?
             }
             None => {
                 forum_user_stat::ActiveModel {
                     tenant_id: Set(tenant_id),
                     user_id: Set(user_id),
-                    topic_count: Set(topic_delta.max(0)),
-                    reply_count: Set(reply_delta.max(0)),
-                    solution_count: Set(solution_delta.max(0)),
+                    topic_count: Set(deltas.topics.max(0)),
+                    reply_count: Set(deltas.replies.max(0)),
+                    solution_count: Set(deltas.solutions.max(0)),
+                    reputation: Set(deltas.reputation),
                     created_at: Set(now.into()),
                     updated_at: Set(now.into()),
                 }
                 .insert(txn)
-                .await?;
+                .await?
             }
-        }
+        };
 
+        if deltas.topics > 0 || deltas.replies > 0 || deltas.solutions > 0 || deltas.reputation > 0
+        {
+            ReputationService::award_badges_in_tx(txn, &stats).await?;
+        }
         Ok(())
     }
 
@@ -8,12 +8,14 @@ use sea_orm::{
 use tracing::instrument;
 use uuid::Uuid;
 
+use rustok_content::SanctionService;
 use rustok_core::{Action, Resource, SecurityContext};
 
-use crate::constants::{reply_status, topic_status};
+use crate::constants::{reply_status, reputation_source, topic_status};
 use crate::entities::{forum_reply, forum_reply_vote, forum_topic, forum_topic_vote};
 use crate::error::{ForumError, ForumResult};
 use crate::services::rbac::enforce_scope;
+use crate::services::reputation::{ReputationPolicy, ReputationService};
 
 #[derive(Debug, Clone, Copy, Default)]
 pub struct VoteSummary {
@@ -23,11 +25,21 @@ pub struct VoteSummary {
 
 pub struct VoteService {
     db: DatabaseConnection,
+    reputation_policy: ReputationPolicy,
 }
 
 impl VoteService {
     pub fn new(db: DatabaseConnection) -> Self {
-        Self { db }
+        Self {
+            db,
+            reputation_policy: ReputationPolicy::default(),
+        }
+    }
+
+    /// Points credited to authors for the votes they receive.
+    pub fn with_reputation_policy(mut self, policy: ReputationPolicy) -> Self {
+        self.reputation_policy = policy;
+        self
     }
 
     #[instrument(skip(self, security))]
@@ -50,8 +62,19 @@ impl VoteService {
         }
 
         let txn = self.db.begin().await?;
+        SanctionService::ensure_can_interact(&txn, tenant_id, Some(user_id)).await?;
         self.upsert_topic_vote_in_tx(&txn, tenant_id, topic_id, user_id, value)
             .await?;
+        ReputationService::record_in_tx(
+            &txn,
+            tenant_id,
+            topic.author_id,
+            reputation_source::TOPIC_VOTE,
+            topic_id,
+            Some(user_id),
+            self.reputation_policy.topic_vote_points(value),
+        )
+        .await?;
         txn.commit().await?;
         Ok(())
     }
@@ -65,7 +88,7 @@ impl VoteService {
     ) -> ForumResult<()> {
         enforce_scope(&security, Resource::ForumTopics, Action::Read)?;
         let user_id = require_authenticated_user(&security)?;
-        self.find_topic(tenant_id, topic_id).await?;
+        let topic = self.find_topic(tenant_id, topic_id).await?;
 
         let txn = self.db.begin().await?;
         forum_topic_vote::Entity::delete_many()
@@ -74,6 +97,16 @@ impl VoteService {
             .filter(forum_topic_vote::Column::UserId.eq(user_id))
             .exec(&txn)
             .await?;
+        ReputationService::record_in_tx(
+            &txn,
+            tenant_id,
+            topic.author_id,
+            reputation_source::TOPIC_VOTE,
+            topic_id,
+            Some(user_id),
+            0,
+        )
+        .await?;
         txn.commit().await?;
         Ok(())
     }
@@ -98,8 +131,19 @@ impl VoteService {
         }
 
         let txn = self.db.begin().await?;
+        SanctionService::ensure_can_interact(&txn, tenant_id, Some(user_id)).await?;
         self.upsert_reply_vote_in_tx(&txn, tenant_id, reply_id, user_id, value)
             .await?;
+        ReputationService::record_in_tx(
+            &txn,
+            tenant_id,
+            reply.author_id,
+            reputation_source::REPLY_VOTE,
+            reply_id,
+            Some(user_id),
+            self.reputation_policy.reply_vote_points(value),
+        )
+        .await?;
         txn.commit().await?;
         Ok(())
     }
@@ -113,7 +157,7 @@ impl VoteService {
     ) -> ForumResult<()> {
         enforce_scope(&security, Resource::ForumReplies, Action::Read)?;
         let user_id = require_authenticated_user(&security)?;
-        self.find_reply(tenant_id, reply_id).await?;
+        let reply = self.find_reply(tenant_id, reply_id).await?;
 
         let txn = self.db.begin().await?;
         forum_reply_vote::Entity::delete_many()
@@ -122,6 +166,16 @@ impl VoteService {
             .filter(forum_reply_vote::Column::UserId.eq(user_id))
             .exec(&txn)
             .await?;
+        ReputationService::record_in_tx(
+            &txn,
+            tenant_id,
+            reply.author_id,
+            reputation_source::REPLY_VOTE,
+            reply_id,
+            Some(user_id),
+            0,
+        )
+        .await?;
         txn.commit().await?;
         Ok(())
     }
@@ -44,6 +44,12 @@ async fn setup() -> (
 ) {
     let db = setup_forum_test_db().await;
     let schema = SchemaManager::new(&db);
+    for migration in rustok_content::migrations::migrations() {
+        migration
+            .up(&schema)
+            .await
+            .expect("content migration should apply");
+    }
     for migration in TaxonomyModule.migrations() {
         migration
             .up(&schema)
@@ -281,6 +287,7 @@ async fn topic_list_exposes_requested_effective_and_available_locales() {
             admin,
             ListTopicsFilter {
                 category_id: Some(category.id),
+                tag_id: None,
                 status: None,
                 locale: Some("fr-FR".to_string()),
                 page: 1,
@@ -0,0 +1,348 @@
+use std::sync::Arc;
+
+use rustok_content::{MentionService, MentionSource};
+use rustok_core::{MemoryTransport, MigrationSource, SecurityContext, UserRole};
+use rustok_events::{DomainEvent, EventEnvelope};
+use rustok_forum::{
+    CategoryService, CreateCategoryInput, CreateReplyInput, CreateTopicInput, ForumError,
+    ForumModule, ReplyService, SubscriptionService, TopicService, UpdateReplyInput,
+};
+use rustok_outbox::TransactionalEventBus;
+use rustok_profiles::{
+    ProfileBlockKind, ProfileService, ProfileVisibility, ProfilesModule, SocialGraphService,
+    UpsertProfileInput,
+};
+use rustok_taxonomy::TaxonomyModule;
+use sea_orm::{ConnectOptions, Database, DatabaseConnection};
+use sea_orm_migration::SchemaManager;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+struct Fixture {
+    db: DatabaseConnection,
+    event_bus: TransactionalEventBus,
+    events: broadcast::Receiver<EventEnvelope>,
+    tenant_id: Uuid,
+    category_id: Uuid,
+    author: SecurityContext,
+    alice: Uuid,
+    bob: Uuid,
+}
+
+async fn setup() -> Fixture {
+    let db_url = format!(
+        "sqlite:file:forum_mentions_{}?mode=memory&cache=shared",
+        Uuid::new_v4()
+    );
+    let mut opts = ConnectOptions::new(db_url);
+    opts.max_connections(5)
+        .min_connections(1)
+        .sqlx_logging(false);
+    let db = Database::connect(opts)
+        .await
+        .expect("failed to connect forum sqlite database");
+
+    let schema = SchemaManager::new(&db);
+    for migration in rustok_content::migrations::migrations()
+        .into_iter()
+        .chain(TaxonomyModule.migrations())
+        .chain(ProfilesModule.migrations())
+        .chain(ForumModule.migrations())
+    {
+        migration.up(&schema).await.expect("migration should apply");
+    }
+
+    let transport = MemoryTransport::new();
+    let events = transport.subscribe();
+    let event_bus = TransactionalEventBus::new(Arc::new(transport));
+    let tenant_id = Uuid::new_v4();
+    let author = SecurityContext::new(UserRole::Admin, Some(Uuid::new_v4()));
+
+    let profiles = ProfileService::new(db.clone());
+    let alice = Uuid::new_v4();
+    let bob = Uuid::new_v4();
+    for (user_id, handle) in [(alice, "alice"), (bob, "bob")] {
+        profiles
+            .upsert_profile(
+                tenant_id,
+                user_id,
+                UpsertProfileInput {
+                    handle: handle.to_string(),
+                    display_name: handle.to_string(),
+                    bio: None,
+                    tags: vec![],
+                    avatar_media_id: None,
+                    banner_media_id: None,
+                    preferred_locale: None,
+                    visibility: ProfileVisibility::Public,
+                },
+                Some("en"),
+            )
+            .await
+            .expect("profile should be created");
+    }
+
+    let category = CategoryService::new(db.clone())
+        .create(
+            tenant_id,
+            author.clone(),
+            CreateCategoryInput {
+                locale: "en".to_string(),
+                name: "General".to_string(),
+                slug: "general".to_string(),
+                description: None,
+                icon: None,
+                color: None,
+                parent_id: None,
+                position: Some(0),
+                moderated: false,
+            },
+        )
+        .await
+        .expect("category should be created");
+
+    Fixture {
+        db,
+        event_bus,
+        events,
+        tenant_id,
+        category_id: category.id,
+        author,
+        alice,
+        bob,
+    }
+}
+
+async fn create_topic(fixture: &Fixture, channel_slugs: Option<Vec<String>>) -> Uuid {
+    TopicService::new(fixture.db.clone(), fixture.event_bus.clone())
+        .create(
+            fixture.tenant_id,
+            fixture.author.clone(),
+            CreateTopicInput {
+                locale: "en".to_string(),
+                category_id: fixture.category_id,
+                title: "Welcome".to_string(),
+                slug: None,
+                body: "Say hello".to_string(),
+                body_format: "markdown".to_string(),
+                content_json: None,
+                metadata: serde_json::json!({}),
+                tags: vec![],
+                channel_slugs,
+            },
+        )
+        .await
+        .expect("topic should be created")
+        .id
+}
+
+fn reply(content: &str) -> CreateReplyInput {
+    CreateReplyInput {
+        locale: "en".to_string(),
+        content: content.to_string(),
+        content_format: "markdown".to_string(),
+        content_json: None,
+        parent_reply_id: None,
+    }
+}
+
+fn mentioned_users(events: &mut broadcast::Receiver<EventEnvelope>) -> Vec<Uuid> {
+    let mut users = Vec::new();
+    while let Ok(envelope) = events.try_recv() {
+        if let DomainEvent::UserMentioned {
+            mentioned_user_id, ..
+        } = envelope.event
+        {
+            users.push(mentioned_user_id);
+        }
+    }
+    users
+}
+
+#[tokio::test]
+async fn replies_ping_new_mentions_once_and_record_quotes() {
+    let mut fixture = setup().await;
+    let topic_id = create_topic(&fixture, None).await;
+    let replies = ReplyService::new(fixture.db.clone(), fixture.event_bus.clone());
+    let replier = SecurityContext::new(UserRole::Customer, Some(Uuid::new_v4()));
+    mentioned_users(&mut fixture.events);
+
+    let first = replies
+        .create(
+            fixture.tenant_id,
+            fixture.author.clone(),
+            topic_id,
+            reply("Hey @Alice, @ghost and `@bob`"),
+        )
+        .await
+        .expect("reply should be created");
+    assert_eq!(mentioned_users(&mut fixture.events), vec![fixture.alice]);
+
+    replies
+        .update(
+            fixture.tenant_id,
+            first.id,
+            fixture.author.clone(),
+            UpdateReplyInput {
+                locale: "en".to_string(),
+                content: Some("Hey @alice and @bob".to_string()),
+                content_format: None,
+                content_json: None,
+            },
+        )
+        .await
+        .expect("reply should be updated");
+    assert_eq!(
+        mentioned_users(&mut fixture.events),
+        vec![fixture.bob],
+        "editing only pings newly added mentions"
+    );
+
+    let second = replies
+        .create(
+            fixture.tenant_id,
+            replier,
+            topic_id,
+            reply(&format!(
+                "[quote=forum_reply:{}]Hey[/quote]\nAgreed",
+                first.id
+            )),
+        )
+        .await
+        .expect("quoting reply should be created");
+
+    let mentions = MentionService::new(fixture.db.clone());
+    let mut stored = mentions
+        .mentioned_users(fixture.tenant_id, MentionSource::ForumReply, first.id)
+        .await
+        .unwrap();
+    stored.sort();
+    let mut expected = vec![fixture.alice, fixture.bob];
+    expected.sort();
+    assert_eq!(stored, expected);
+
+    let backlinks = mentions
+        .quoted_by(fixture.tenant_id, MentionSource::ForumReply, first.id)
+        .await
+        .unwrap();
+    assert_eq!(backlinks.len(), 1);
+    assert_eq!(backlinks[0].source_id, second.id);
+    assert_eq!(backlinks[0].target_id, topic_id);
+}
+
+#[tokio::test]
+async fn channel_restricted_topics_only_ping_participants() {
+    let mut fixture = setup().await;
+    let topic_id = create_topic(&fixture, Some(vec!["members".to_string()])).await;
+    let replies = ReplyService::new(fixture.db.clone(), fixture.event_bus.clone());
+    SubscriptionService::new(fixture.db.clone())
+        .set_topic_subscription(
+            fixture.tenant_id,
+            topic_id,
+            SecurityContext::new(UserRole::Customer, Some(fixture.bob)),
+        )
+        .await
+        .expect("subscription should be stored");
+    mentioned_users(&mut fixture.events);
+
+    replies
+        .create(
+            fixture.tenant_id,
+            fixture.author.clone(),
+            topic_id,
+            reply("@alice @bob have a look"),
+        )
+        .await
+        .expect("reply should be created");
+
+    assert_eq!(
+        mentioned_users(&mut fixture.events),
+        vec![fixture.bob],
+        "alice has no known access to the restricted topic"
+    );
+}
+
+#[tokio::test]
+async fn blocks_stop_replies_and_blocks_or_mutes_stop_mentions() {
+    let mut fixture = setup().await;
+    let topic_id = create_topic(&fixture, None).await;
+    let replies = ReplyService::new(fixture.db.clone(), fixture.event_bus.clone());
+    let graph = SocialGraphService::new(fixture.db.clone());
+    let author_id = fixture.author.user_id.unwrap();
+    let bob = SecurityContext::new(UserRole::Customer, Some(fixture.bob));
+    mentioned_users(&mut fixture.events);
+
+    let bobs_reply = replies
+        .create(fixture.tenant_id, bob.clone(), topic_id, reply("First!"))
+        .await
+        .expect("reply should be created");
+    graph
+        .block(
+            fixture.tenant_id,
+            fixture.alice,
+            author_id,
+            ProfileBlockKind::Mute,
+        )
+        .await
+        .unwrap();
+    graph
+        .block(
+            fixture.tenant_id,
+            fixture.bob,
+            author_id,
+            ProfileBlockKind::Block,
+        )
+        .await
+        .unwrap();
+
+    let answer = replies
+        .create(
+            fixture.tenant_id,
+            fixture.author.clone(),
+            topic_id,
+            CreateReplyInput {
+                parent_reply_id: None,
+                ..reply("@alice @bob thanks")
+            },
+        )
+        .await
+        .expect("blocked users can still be discussed");
+    assert!(mentioned_users(&mut fixture.events).is_empty());
+    assert!(MentionService::new(fixture.db.clone())
+        .mentioned_users(fixture.tenant_id, MentionSource::ForumReply, answer.id)
+        .await
+        .unwrap()
+        .is_empty());
+
+    let error = replies
+        .create(
+            fixture.tenant_id,
+            fixture.author.clone(),
+            topic_id,
+            CreateReplyInput {
+                parent_reply_id: Some(bobs_reply.id),
+                ..reply("Replying to bob")
+            },
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ForumError::Forbidden(_)));
+
+    graph
+        .block(
+            fixture.tenant_id,
+            author_id,
+            fixture.bob,
+            ProfileBlockKind::Block,
+        )
+        .await
+        .unwrap();
+    let error = replies
+        .create(fixture.tenant_id, bob, topic_id, reply("Let me in"))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(error, ForumError::Forbidden(_)),
+        "users blocked by the topic author cannot reply"
+    );
+}
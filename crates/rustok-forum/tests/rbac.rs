@@ -37,6 +37,12 @@ async fn setup() -> (
 ) {
     let db = setup_forum_test_db().await;
     let schema = SchemaManager::new(&db);
+    for migration in rustok_content::migrations::migrations() {
+        migration
+            .up(&schema)
+            .await
+            .expect("content migration should apply");
+    }
     for migration in TaxonomyModule.migrations() {
         migration
             .up(&schema)
@@ -198,6 +204,7 @@ async fn customer_permissions_are_enforced_in_forum_services() {
             customer,
             ListTopicsFilter {
                 category_id: Some(category.id),
+                tag_id: None,
                 status: None,
                 locale: Some("en".to_string()),
                 page: 1,
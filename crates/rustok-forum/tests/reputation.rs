@@ -0,0 +1,424 @@
+use std::sync::Arc;
+
+use rustok_core::{MemoryTransport, MigrationSource, SecurityContext, UserRole};
+use rustok_events::EventEnvelope;
+use rustok_forum::{
+    BadgeRule, CategoryService, CreateBadgeInput, CreateCategoryInput, CreateReplyInput,
+    CreateTopicInput, ForumError, ForumModule, ModerationService, ReplyService, ReputationService,
+    TopicService, UpdateTopicInput, UserStatsService, VoteService,
+};
+use rustok_outbox::TransactionalEventBus;
+use rustok_profiles::{ProfileService, ProfilesModule};
+use rustok_rbac::{TrustLevel, TrustLevelPolicy};
+use rustok_taxonomy::TaxonomyModule;
+use sea_orm::{ConnectOptions, Database, DatabaseConnection};
+use sea_orm_migration::SchemaManager;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+struct Fixture {
+    db: DatabaseConnection,
+    event_bus: TransactionalEventBus,
+    tenant_id: Uuid,
+    admin: SecurityContext,
+    _events: broadcast::Receiver<EventEnvelope>,
+}
+
+async fn setup() -> Fixture {
+    let db_url = format!(
+        "sqlite:file:forum_reputation_{}?mode=memory&cache=shared",
+        Uuid::new_v4()
+    );
+    let mut opts = ConnectOptions::new(db_url);
+    opts.max_connections(5)
+        .min_connections(1)
+        .sqlx_logging(false);
+    let db = Database::connect(opts)
+        .await
+        .expect("failed to connect forum sqlite database");
+
+    let schema = SchemaManager::new(&db);
+    for migration in rustok_content::migrations::migrations()
+        .into_iter()
+        .chain(TaxonomyModule.migrations())
+        .chain(ProfilesModule.migrations())
+        .chain(ForumModule.migrations())
+    {
+        migration.up(&schema).await.expect("migration should apply");
+    }
+
+    let transport = MemoryTransport::new();
+    let events = transport.subscribe();
+    Fixture {
+        db,
+        event_bus: TransactionalEventBus::new(Arc::new(transport)),
+        _events: events,
+        tenant_id: Uuid::new_v4(),
+        admin: SecurityContext::new(UserRole::Admin, Some(Uuid::new_v4())),
+    }
+}
+
+fn customer() -> SecurityContext {
+    SecurityContext::new(UserRole::Customer, Some(Uuid::new_v4()))
+}
+
+async fn create_category(fixture: &Fixture, slug: &str, moderated: bool) -> Uuid {
+    CategoryService::new(fixture.db.clone())
+        .create(
+            fixture.tenant_id,
+            fixture.admin.clone(),
+            CreateCategoryInput {
+                locale: "en".to_string(),
+                name: slug.to_string(),
+                slug: slug.to_string(),
+                description: None,
+                icon: None,
+                color: None,
+                parent_id: None,
+                position: Some(0),
+                moderated,
+            },
+        )
+        .await
+        .expect("category should be created")
+        .id
+}
+
+fn topic_input(category_id: Uuid, body: &str) -> CreateTopicInput {
+    CreateTopicInput {
+        locale: "en".to_string(),
+        category_id,
+        title: "Question".to_string(),
+        slug: None,
+        body: body.to_string(),
+        body_format: "markdown".to_string(),
+        content_json: None,
+        metadata: serde_json::json!({}),
+        tags: vec![],
+        channel_slugs: None,
+    }
+}
+
+fn reply_input(content: &str) -> CreateReplyInput {
+    CreateReplyInput {
+        locale: "en".to_string(),
+        content: content.to_string(),
+        content_format: "markdown".to_string(),
+        content_json: None,
+        parent_reply_id: None,
+    }
+}
+
+async fn reputation(fixture: &Fixture, security: &SecurityContext) -> i32 {
+    ReputationService::new(fixture.db.clone())
+        .reputation(fixture.tenant_id, security.user_id.unwrap())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn votes_solutions_and_moderation_feed_the_ledger_and_award_badges() {
+    let fixture = setup().await;
+    let category_id = create_category(&fixture, "general", false).await;
+    let topics = TopicService::new(fixture.db.clone(), fixture.event_bus.clone());
+    let replies = ReplyService::new(fixture.db.clone(), fixture.event_bus.clone());
+    let votes = VoteService::new(fixture.db.clone());
+    let moderation = ModerationService::new(fixture.db.clone(), fixture.event_bus.clone());
+    let reputation_service = ReputationService::new(fixture.db.clone());
+    let author = customer();
+    let voter = customer();
+
+    reputation_service
+        .create_badge(
+            fixture.tenant_id,
+            fixture.admin.clone(),
+            CreateBadgeInput {
+                slug: "Helper".to_string(),
+                name: "Helper".to_string(),
+                description: Some("Had an answer accepted".to_string()),
+                icon: None,
+                rule: BadgeRule {
+                    min_solutions: Some(1),
+                    min_reputation: Some(20),
+                    ..BadgeRule::default()
+                },
+            },
+        )
+        .await
+        .expect("badge should be created");
+    let forbidden = reputation_service
+        .create_badge(
+            fixture.tenant_id,
+            author.clone(),
+            CreateBadgeInput {
+                slug: "self-made".to_string(),
+                name: "Self made".to_string(),
+                description: None,
+                icon: None,
+                rule: BadgeRule {
+                    min_topics: Some(0),
+                    ..BadgeRule::default()
+                },
+            },
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(forbidden, ForumError::Forbidden(_)));
+
+    let topic = topics
+        .create(
+            fixture.tenant_id,
+            fixture.admin.clone(),
+            topic_input(category_id, "How?"),
+        )
+        .await
+        .unwrap();
+    let answer = replies
+        .create(
+            fixture.tenant_id,
+            author.clone(),
+            topic.id,
+            reply_input("Like this"),
+        )
+        .await
+        .unwrap();
+
+    votes
+        .set_reply_vote(fixture.tenant_id, answer.id, voter.clone(), 1)
+        .await
+        .unwrap();
+    assert_eq!(reputation(&fixture, &author).await, 10);
+    votes
+        .set_reply_vote(fixture.tenant_id, answer.id, voter.clone(), -1)
+        .await
+        .unwrap();
+    assert_eq!(
+        reputation(&fixture, &author).await,
+        -2,
+        "changing a vote rewrites its ledger entry"
+    );
+    votes
+        .set_reply_vote(fixture.tenant_id, answer.id, author.clone(), 1)
+        .await
+        .unwrap();
+    assert_eq!(
+        reputation(&fixture, &author).await,
+        -2,
+        "self votes do not count"
+    );
+    votes
+        .set_reply_vote(fixture.tenant_id, answer.id, voter.clone(), 1)
+        .await
+        .unwrap();
+
+    moderation
+        .mark_solution(
+            fixture.tenant_id,
+            topic.id,
+            answer.id,
+            fixture.admin.clone(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(reputation(&fixture, &author).await, 25);
+    let badges = ProfileService::new(fixture.db.clone())
+        .list_badges(fixture.tenant_id, author.user_id.unwrap())
+        .await
+        .unwrap();
+    assert_eq!(badges.len(), 1);
+    assert_eq!(badges[0].source, "forum");
+    assert_eq!(badges[0].key, "helper");
+
+    votes
+        .clear_reply_vote(fixture.tenant_id, answer.id, voter.clone())
+        .await
+        .unwrap();
+    assert_eq!(reputation(&fixture, &author).await, 15);
+
+    let spam = replies
+        .create(
+            fixture.tenant_id,
+            author.clone(),
+            topic.id,
+            reply_input("Buy now"),
+        )
+        .await
+        .unwrap();
+    moderation
+        .reject_reply(fixture.tenant_id, spam.id, topic.id, fixture.admin.clone())
+        .await
+        .unwrap();
+    assert_eq!(reputation(&fixture, &author).await, 5);
+    moderation
+        .approve_reply(fixture.tenant_id, spam.id, topic.id, fixture.admin.clone())
+        .await
+        .unwrap();
+    assert_eq!(
+        reputation(&fixture, &author).await,
+        15,
+        "restoring a rejected reply removes the penalty"
+    );
+
+    moderation
+        .clear_solution(fixture.tenant_id, topic.id, fixture.admin.clone())
+        .await
+        .unwrap();
+    let stats = UserStatsService::new(fixture.db.clone())
+        .get(fixture.tenant_id, author.clone(), author.user_id.unwrap())
+        .await
+        .unwrap();
+    assert_eq!(stats.reputation, 0);
+    assert_eq!(stats.trust_level, "new");
+    assert_eq!(
+        ProfileService::new(fixture.db.clone())
+            .list_badges(fixture.tenant_id, author.user_id.unwrap())
+            .await
+            .unwrap()
+            .len(),
+        1,
+        "earned badges are kept"
+    );
+}
+
+#[tokio::test]
+async fn trust_levels_gate_links_moderation_and_wiki_edits() {
+    let fixture = setup().await;
+    let open_category = create_category(&fixture, "general", false).await;
+    let moderated_category = create_category(&fixture, "support", true).await;
+    let policy = TrustLevelPolicy {
+        basic_reputation: 10,
+        member_reputation: 10,
+        regular_reputation: 10,
+        ..TrustLevelPolicy::default()
+    };
+    let topics = TopicService::new(fixture.db.clone(), fixture.event_bus.clone())
+        .with_trust_policy(policy.clone());
+    let replies = ReplyService::new(fixture.db.clone(), fixture.event_bus.clone())
+        .with_trust_policy(policy.clone());
+    let votes = VoteService::new(fixture.db.clone());
+    let newcomer = customer();
+
+    let support_topic = topics
+        .create(
+            fixture.tenant_id,
+            fixture.admin.clone(),
+            topic_input(moderated_category, "See https://docs.example.com"),
+        )
+        .await
+        .expect("moderators may always post links");
+
+    let error = replies
+        .create(
+            fixture.tenant_id,
+            newcomer.clone(),
+            support_topic.id,
+            reply_input("Try https://spam.example.com"),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ForumError::Forbidden(_)));
+    let held = replies
+        .create(
+            fixture.tenant_id,
+            newcomer.clone(),
+            support_topic.id,
+            reply_input("Plain answer"),
+        )
+        .await
+        .unwrap();
+    assert_eq!(held.status, "pending");
+
+    let wiki = topics
+        .create(
+            fixture.tenant_id,
+            fixture.admin.clone(),
+            topic_input(open_category, "Community notes"),
+        )
+        .await
+        .unwrap();
+    ModerationService::new(fixture.db.clone(), fixture.event_bus.clone())
+        .set_topic_wiki(fixture.tenant_id, wiki.id, fixture.admin.clone(), true)
+        .await
+        .unwrap();
+    let edit = |title: &str| UpdateTopicInput {
+        locale: "en".to_string(),
+        title: Some(title.to_string()),
+        ..UpdateTopicInput::default()
+    };
+    let error = topics
+        .update(
+            fixture.tenant_id,
+            wiki.id,
+            newcomer.clone(),
+            edit("Vandalised"),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ForumError::Forbidden(_)));
+
+    let own_topic = topics
+        .create(
+            fixture.tenant_id,
+            newcomer.clone(),
+            topic_input(open_category, "My first topic"),
+        )
+        .await
+        .unwrap();
+    for _ in 0..2 {
+        votes
+            .set_topic_vote(fixture.tenant_id, own_topic.id, customer(), 1)
+            .await
+            .unwrap();
+    }
+    assert_eq!(
+        ReputationService::new(fixture.db.clone())
+            .with_policy(rustok_forum::ReputationPolicy {
+                trust: policy.clone(),
+                ..Default::default()
+            })
+            .trust_level(fixture.tenant_id, newcomer.user_id.unwrap())
+            .await
+            .unwrap(),
+        TrustLevel::Regular
+    );
+
+    let linked = replies
+        .create(
+            fixture.tenant_id,
+            newcomer.clone(),
+            support_topic.id,
+            reply_input("Docs: https://docs.example.com"),
+        )
+        .await
+        .expect("trusted members may post links");
+    assert_eq!(linked.status, "approved", "trusted members skip moderation");
+
+    let updated = topics
+        .update(
+            fixture.tenant_id,
+            wiki.id,
+            newcomer.clone(),
+            edit("Community notes v2"),
+        )
+        .await
+        .expect("trusted members may edit wiki topics");
+    assert_eq!(updated.title, "Community notes v2");
+    assert!(updated.is_wiki);
+
+    let error = topics
+        .update(
+            fixture.tenant_id,
+            wiki.id,
+            newcomer,
+            UpdateTopicInput {
+                tags: Some(vec!["hijack".to_string()]),
+                ..edit("Community notes v3")
+            },
+        )
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(error, ForumError::Forbidden(_)),
+        "wiki editors may only change the title and body"
+    );
+}
@@ -0,0 +1,243 @@
+use std::sync::Arc;
+
+use rustok_content::{
+    ContentError, IssueSanctionInput, ListModerationLogFilter, ModerationLogService, SanctionKind,
+    SanctionService,
+};
+use rustok_core::{MemoryTransport, MigrationSource, SecurityContext, UserRole};
+use rustok_events::EventEnvelope;
+use rustok_forum::{
+    CategoryService, CreateCategoryInput, CreateReplyInput, CreateTopicInput, ForumError,
+    ForumModule, ModerationService, ReplyService, TopicService, VoteService,
+};
+use rustok_outbox::TransactionalEventBus;
+use rustok_profiles::ProfilesModule;
+use rustok_taxonomy::TaxonomyModule;
+use sea_orm::{ConnectOptions, Database, DatabaseConnection};
+use sea_orm_migration::SchemaManager;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+struct Fixture {
+    db: DatabaseConnection,
+    event_bus: TransactionalEventBus,
+    tenant_id: Uuid,
+    topic_id: Uuid,
+    moderator: SecurityContext,
+    _events: broadcast::Receiver<EventEnvelope>,
+}
+
+async fn setup() -> Fixture {
+    let db_url = format!(
+        "sqlite:file:forum_sanctions_{}?mode=memory&cache=shared",
+        Uuid::new_v4()
+    );
+    let mut opts = ConnectOptions::new(db_url);
+    opts.max_connections(5)
+        .min_connections(1)
+        .sqlx_logging(false);
+    let db = Database::connect(opts)
+        .await
+        .expect("failed to connect forum sqlite database");
+
+    let schema = SchemaManager::new(&db);
+    for migration in rustok_content::migrations::migrations()
+        .into_iter()
+        .chain(TaxonomyModule.migrations())
+        .chain(ProfilesModule.migrations())
+        .chain(ForumModule.migrations())
+    {
+        migration.up(&schema).await.expect("migration should apply");
+    }
+
+    let transport = MemoryTransport::new();
+    let events = transport.subscribe();
+    let event_bus = TransactionalEventBus::new(Arc::new(transport));
+    let tenant_id = Uuid::new_v4();
+    let moderator = SecurityContext::new(UserRole::Admin, Some(Uuid::new_v4()));
+
+    let category = CategoryService::new(db.clone())
+        .create(
+            tenant_id,
+            moderator.clone(),
+            CreateCategoryInput {
+                locale: "en".to_string(),
+                name: "General".to_string(),
+                slug: "general".to_string(),
+                description: None,
+                icon: None,
+                color: None,
+                parent_id: None,
+                position: Some(0),
+                moderated: false,
+            },
+        )
+        .await
+        .expect("category should be created");
+    let topic = TopicService::new(db.clone(), event_bus.clone())
+        .create(
+            tenant_id,
+            moderator.clone(),
+            CreateTopicInput {
+                locale: "en".to_string(),
+                category_id: category.id,
+                title: "Welcome".to_string(),
+                slug: Some("welcome".to_string()),
+                body: "Say hello".to_string(),
+                body_format: "markdown".to_string(),
+                content_json: None,
+                metadata: serde_json::json!({}),
+                tags: vec![],
+                channel_slugs: None,
+            },
+        )
+        .await
+        .expect("topic should be created");
+
+    Fixture {
+        db,
+        event_bus,
+        tenant_id,
+        topic_id: topic.id,
+        moderator,
+        _events: events,
+    }
+}
+
+fn reply(content: &str) -> CreateReplyInput {
+    CreateReplyInput {
+        locale: "en".to_string(),
+        content: content.to_string(),
+        content_format: "markdown".to_string(),
+        content_json: None,
+        parent_reply_id: None,
+    }
+}
+
+async fn sanction(fixture: &Fixture, user_id: Uuid, kind: SanctionKind, duration: Option<i64>) {
+    SanctionService::new(fixture.db.clone())
+        .issue(
+            fixture.tenant_id,
+            fixture.moderator.clone(),
+            IssueSanctionInput {
+                user_id,
+                kind,
+                reason: "Abusive replies".to_string(),
+                duration_seconds: duration,
+                report_id: None,
+            },
+        )
+        .await
+        .expect("sanction should be issued");
+}
+
+fn is_sanctioned(error: &ForumError) -> bool {
+    matches!(
+        error,
+        ForumError::Content(ContentError::UserSanctioned { .. })
+    )
+}
+
+#[tokio::test]
+async fn muted_users_cannot_post_but_can_still_vote() {
+    let fixture = setup().await;
+    let user_id = Uuid::new_v4();
+    let user = SecurityContext::new(UserRole::Customer, Some(user_id));
+    let replies = ReplyService::new(fixture.db.clone(), fixture.event_bus.clone());
+
+    replies
+        .create(
+            fixture.tenant_id,
+            user.clone(),
+            fixture.topic_id,
+            reply("Before the mute"),
+        )
+        .await
+        .expect("reply should be accepted before the mute");
+    sanction(&fixture, user_id, SanctionKind::Mute, None).await;
+
+    let error = replies
+        .create(
+            fixture.tenant_id,
+            user.clone(),
+            fixture.topic_id,
+            reply("After the mute"),
+        )
+        .await
+        .expect_err("muted users cannot reply");
+    assert!(is_sanctioned(&error));
+
+    VoteService::new(fixture.db.clone())
+        .set_topic_vote(fixture.tenant_id, fixture.topic_id, user, 1)
+        .await
+        .expect("muted users can still vote");
+}
+
+#[tokio::test]
+async fn suspended_users_cannot_vote() {
+    let fixture = setup().await;
+    let user_id = Uuid::new_v4();
+    let user = SecurityContext::new(UserRole::Customer, Some(user_id));
+    sanction(&fixture, user_id, SanctionKind::Suspend, Some(3600)).await;
+
+    let error = VoteService::new(fixture.db.clone())
+        .set_topic_vote(fixture.tenant_id, fixture.topic_id, user, 1)
+        .await
+        .expect_err("suspended users cannot vote");
+    assert!(is_sanctioned(&error));
+}
+
+#[tokio::test]
+async fn moderator_actions_are_written_to_the_moderation_log() {
+    let fixture = setup().await;
+    let author_id = Uuid::new_v4();
+    let author = SecurityContext::new(UserRole::Customer, Some(author_id));
+    let created = ReplyService::new(fixture.db.clone(), fixture.event_bus.clone())
+        .create(
+            fixture.tenant_id,
+            author,
+            fixture.topic_id,
+            reply("Offensive reply"),
+        )
+        .await
+        .expect("reply should be accepted");
+
+    let moderation = ModerationService::new(fixture.db.clone(), fixture.event_bus.clone());
+    moderation
+        .hide_reply(
+            fixture.tenant_id,
+            created.id,
+            fixture.topic_id,
+            fixture.moderator.clone(),
+        )
+        .await
+        .expect("moderator should hide the reply");
+    moderation
+        .lock_topic(
+            fixture.tenant_id,
+            fixture.topic_id,
+            fixture.moderator.clone(),
+        )
+        .await
+        .expect("moderator should lock the topic");
+
+    let (entries, total) = ModerationLogService::new(fixture.db.clone())
+        .list(
+            fixture.tenant_id,
+            fixture.moderator.clone(),
+            ListModerationLogFilter::default(),
+        )
+        .await
+        .expect("moderation log should load");
+    assert_eq!(total, 2);
+    let hidden = entries
+        .iter()
+        .find(|entry| entry.action == "forum.reply.status_changed")
+        .expect("hide should be logged");
+    assert_eq!(hidden.target_id, created.id);
+    assert_eq!(hidden.subject_user_id, Some(author_id));
+    assert_eq!(hidden.details["new_status"], "hidden");
+    assert!(entries
+        .iter()
+        .any(|entry| entry.action == "forum.topic.locked" && entry.target_id == fixture.topic_id));
+}
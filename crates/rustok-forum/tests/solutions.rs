@@ -36,6 +36,12 @@ async fn setup() -> (
 ) {
     let db = setup_forum_test_db().await;
     let schema = SchemaManager::new(&db);
+    for migration in rustok_content::migrations::migrations() {
+        migration
+            .up(&schema)
+            .await
+            .expect("content migration should apply");
+    }
     for migration in TaxonomyModule.migrations() {
         migration
             .up(&schema)
@@ -147,6 +153,7 @@ async fn mark_and_clear_solution_updates_topic_and_reply_read_paths() {
             customer.clone(),
             ListTopicsFilter {
                 category_id: Some(category.id),
+                tag_id: None,
                 status: None,
                 locale: Some("en".to_string()),
                 page: 1,
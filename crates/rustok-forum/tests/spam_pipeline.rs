@@ -0,0 +1,254 @@
+use std::sync::Arc;
+
+use rustok_content::{
+    spam::BlocklistClassifier, SpamDecisionLog, SpamPipeline, SpamSurface, SubmissionOrigin,
+};
+use rustok_core::{MemoryTransport, MigrationSource, SecurityContext, UserRole};
+use rustok_events::EventEnvelope;
+use rustok_forum::{
+    CategoryService, CreateCategoryInput, CreateReplyInput, CreateTopicInput, ForumModule,
+    ModerationService, ReplyService, TopicService,
+};
+use rustok_outbox::TransactionalEventBus;
+use rustok_profiles::ProfilesModule;
+use rustok_rbac::{TrustLevel, TrustLevelPolicy};
+use rustok_taxonomy::TaxonomyModule;
+use sea_orm::{ConnectOptions, Database, DatabaseConnection};
+use sea_orm_migration::SchemaManager;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+struct Fixture {
+    db: DatabaseConnection,
+    replies: ReplyService,
+    moderation: ModerationService,
+    tenant_id: Uuid,
+    topic_id: Uuid,
+    _events: broadcast::Receiver<EventEnvelope>,
+}
+
+async fn setup() -> Fixture {
+    let db_url = format!(
+        "sqlite:file:forum_spam_{}?mode=memory&cache=shared",
+        Uuid::new_v4()
+    );
+    let mut opts = ConnectOptions::new(db_url);
+    opts.max_connections(5)
+        .min_connections(1)
+        .sqlx_logging(false);
+    let db = Database::connect(opts)
+        .await
+        .expect("failed to connect forum sqlite database");
+
+    let schema = SchemaManager::new(&db);
+    for migration in rustok_content::migrations::migrations()
+        .into_iter()
+        .chain(TaxonomyModule.migrations())
+        .chain(ProfilesModule.migrations())
+        .chain(ForumModule.migrations())
+    {
+        migration.up(&schema).await.expect("migration should apply");
+    }
+
+    let transport = MemoryTransport::new();
+    let events = transport.subscribe();
+    let event_bus = TransactionalEventBus::new(Arc::new(transport));
+    let pipeline = Arc::new(
+        SpamPipeline::with_default_heuristics().with_classifier(Arc::new(
+            BlocklistClassifier::new(["casino bonus"], ["pills.example"]),
+        )),
+    );
+    let tenant_id = Uuid::new_v4();
+    let admin = SecurityContext::new(UserRole::Admin, Some(Uuid::new_v4()));
+
+    let category = CategoryService::new(db.clone())
+        .create(
+            tenant_id,
+            admin.clone(),
+            CreateCategoryInput {
+                locale: "en".to_string(),
+                name: "General".to_string(),
+                slug: "general".to_string(),
+                description: None,
+                icon: None,
+                color: None,
+                parent_id: None,
+                position: Some(0),
+                moderated: false,
+            },
+        )
+        .await
+        .expect("category should be created");
+    let topic = TopicService::new(db.clone(), event_bus.clone())
+        .create(
+            tenant_id,
+            admin,
+            CreateTopicInput {
+                locale: "en".to_string(),
+                category_id: category.id,
+                title: "Welcome".to_string(),
+                slug: Some("welcome".to_string()),
+                body: "Say hello".to_string(),
+                body_format: "markdown".to_string(),
+                content_json: None,
+                metadata: serde_json::json!({}),
+                tags: vec![],
+                channel_slugs: None,
+            },
+        )
+        .await
+        .expect("topic should be created");
+
+    Fixture {
+        replies: ReplyService::new(db.clone(), event_bus.clone())
+            .with_spam_pipeline(pipeline.clone())
+            // Links are left to the spam pipeline here rather than to trust levels.
+            .with_trust_policy(TrustLevelPolicy {
+                post_links: TrustLevel::New,
+                ..TrustLevelPolicy::default()
+            }),
+        moderation: ModerationService::new(db.clone(), event_bus).with_spam_pipeline(pipeline),
+        db,
+        tenant_id,
+        topic_id: topic.id,
+        _events: events,
+    }
+}
+
+fn reply(content: &str) -> CreateReplyInput {
+    CreateReplyInput {
+        locale: "en".to_string(),
+        content: content.to_string(),
+        content_format: "markdown".to_string(),
+        content_json: None,
+        parent_reply_id: None,
+    }
+}
+
+#[tokio::test]
+async fn first_reply_is_held_until_moderator_approves_it() {
+    let fixture = setup().await;
+    let customer = SecurityContext::new(UserRole::Customer, Some(Uuid::new_v4()));
+    let moderator = SecurityContext::new(UserRole::Admin, Some(Uuid::new_v4()));
+
+    let first = fixture
+        .replies
+        .create_with_origin(
+            fixture.tenant_id,
+            customer.clone(),
+            fixture.topic_id,
+            reply("Hello everyone"),
+            SubmissionOrigin {
+                ip_address: Some("203.0.113.10".to_string()),
+            },
+        )
+        .await
+        .expect("reply should be accepted");
+    assert_eq!(first.status, "pending");
+
+    fixture
+        .moderation
+        .approve_reply(fixture.tenant_id, first.id, fixture.topic_id, moderator)
+        .await
+        .expect("moderator should approve the reply");
+    let decision = SpamDecisionLog::find(
+        &fixture.db,
+        fixture.tenant_id,
+        SpamSurface::ForumReply,
+        first.id,
+    )
+    .await
+    .expect("decision lookup should succeed")
+    .expect("decision should be recorded");
+    assert_eq!(decision.action, "hold");
+    assert_eq!(decision.moderator_verdict.as_deref(), Some("ham"));
+    assert_eq!(decision.ip_address.as_deref(), Some("203.0.113.10"));
+
+    let second = fixture
+        .replies
+        .create(
+            fixture.tenant_id,
+            customer,
+            fixture.topic_id,
+            reply("Thanks for the welcome"),
+        )
+        .await
+        .expect("reply should be accepted");
+    assert_eq!(second.status, "approved");
+}
+
+#[tokio::test]
+async fn blocked_replies_are_rejected_and_confirmed_spam_marks_the_author() {
+    let fixture = setup().await;
+    let spammer = SecurityContext::new(UserRole::Customer, Some(Uuid::new_v4()));
+    let moderator = SecurityContext::new(UserRole::Admin, Some(Uuid::new_v4()));
+
+    let blocked = fixture
+        .replies
+        .create(
+            fixture.tenant_id,
+            spammer.clone(),
+            fixture.topic_id,
+            reply("Cheap meds at https://shop.pills.example"),
+        )
+        .await
+        .expect("spam should be stored, not refused");
+    assert_eq!(blocked.status, "rejected");
+
+    let held = fixture
+        .replies
+        .create(
+            fixture.tenant_id,
+            spammer.clone(),
+            fixture.topic_id,
+            reply("https://a.example https://b.example https://c.example"),
+        )
+        .await
+        .expect("reply should be accepted");
+    assert_eq!(held.status, "pending");
+
+    fixture
+        .moderation
+        .reject_reply(fixture.tenant_id, held.id, fixture.topic_id, moderator)
+        .await
+        .expect("moderator should reject the reply");
+
+    let next = fixture
+        .replies
+        .create(
+            fixture.tenant_id,
+            spammer,
+            fixture.topic_id,
+            reply("Innocent looking text"),
+        )
+        .await
+        .expect("reply should be accepted");
+    assert_eq!(next.status, "rejected");
+}
+
+#[tokio::test]
+async fn moderators_bypass_the_pipeline() {
+    let fixture = setup().await;
+    let moderator = SecurityContext::new(UserRole::Admin, Some(Uuid::new_v4()));
+
+    let created = fixture
+        .replies
+        .create(
+            fixture.tenant_id,
+            moderator,
+            fixture.topic_id,
+            reply("Casino bonus rules are pinned above"),
+        )
+        .await
+        .expect("moderator reply should be accepted");
+    assert_eq!(created.status, "approved");
+    assert!(SpamDecisionLog::find(
+        &fixture.db,
+        fixture.tenant_id,
+        SpamSurface::ForumReply,
+        created.id,
+    )
+    .await
+    .expect("decision lookup should succeed")
+    .is_none());
+}
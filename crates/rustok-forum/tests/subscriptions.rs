@@ -35,6 +35,12 @@ async fn setup() -> (
 ) {
     let db = setup_forum_test_db().await;
     let schema = SchemaManager::new(&db);
+    for migration in rustok_content::migrations::migrations() {
+        migration
+            .up(&schema)
+            .await
+            .expect("content migration should apply");
+    }
     for migration in TaxonomyModule.migrations() {
         migration
             .up(&schema)
@@ -139,6 +145,7 @@ async fn category_and_topic_subscriptions_round_trip_through_read_paths() {
             viewer.clone(),
             ListTopicsFilter {
                 category_id: Some(category.id),
+                tag_id: None,
                 status: None,
                 locale: Some("en".to_string()),
                 page: 1,
@@ -3,7 +3,8 @@ use std::sync::Arc;
 use rustok_core::{MemoryTransport, MigrationSource, SecurityContext, UserRole};
 use rustok_forum::{
     entities::{forum_topic, forum_topic_tag},
-    CategoryService, CreateCategoryInput, CreateTopicInput, ForumModule, TopicService,
+    CategoryService, CreateCategoryInput, CreateTopicInput, ForumModule, ListTopicsFilter,
+    TopicService,
 };
 use rustok_outbox::TransactionalEventBus;
 use rustok_taxonomy::{
@@ -40,6 +41,12 @@ async fn setup() -> (
 ) {
     let db = setup_forum_test_db().await;
     let schema = SchemaManager::new(&db);
+    for migration in rustok_content::migrations::migrations() {
+        migration
+            .up(&schema)
+            .await
+            .expect("content migration should apply");
+    }
     for migration in TaxonomyModule.migrations() {
         migration
             .up(&schema)
@@ -166,6 +173,7 @@ async fn topic_tag_sync_reuses_existing_global_taxonomy_term() {
                 canonical_key: None,
                 description: None,
                 aliases: vec![],
+                parent_id: None,
             },
         )
         .await
@@ -214,3 +222,85 @@ async fn topic_tag_sync_reuses_existing_global_taxonomy_term() {
     assert_eq!(forum_scoped_terms.len(), 1);
     assert_eq!(forum_scoped_terms[0].canonical_key, "backend");
 }
+
+#[tokio::test]
+async fn topic_list_tag_filter_matches_descendant_terms() {
+    let (db, event_bus, _events, tenant_id) = setup().await;
+    let category_service = CategoryService::new(db.clone());
+    let topic_service = TopicService::new(db.clone(), event_bus);
+    let taxonomy_service = TaxonomyService::new(db.clone());
+    let admin = SecurityContext::new(UserRole::Admin, Some(Uuid::new_v4()));
+
+    let mut term_ids = Vec::new();
+    for name in ["languages", "rust"] {
+        let term_id = taxonomy_service
+            .create_term(
+                tenant_id,
+                admin.clone(),
+                CreateTaxonomyTermInput {
+                    kind: TaxonomyTermKind::Tag,
+                    scope_type: TaxonomyScopeType::Global,
+                    scope_value: None,
+                    locale: "en".to_string(),
+                    name: name.to_string(),
+                    slug: None,
+                    canonical_key: None,
+                    description: None,
+                    aliases: vec![],
+                    parent_id: term_ids.last().copied(),
+                },
+            )
+            .await
+            .expect("term should be created");
+        term_ids.push(term_id);
+    }
+
+    let category = create_category(&category_service, tenant_id, admin.clone()).await;
+    let mut topic_ids = Vec::new();
+    for tag in ["rust", "languages", "backend"] {
+        let topic = topic_service
+            .create(
+                tenant_id,
+                admin.clone(),
+                CreateTopicInput {
+                    locale: "en".to_string(),
+                    category_id: category.id,
+                    title: format!("About {tag}"),
+                    slug: None,
+                    body: "Body".to_string(),
+                    body_format: "markdown".to_string(),
+                    content_json: None,
+                    metadata: serde_json::json!({}),
+                    tags: vec![tag.to_string()],
+                    channel_slugs: None,
+                },
+            )
+            .await
+            .expect("topic should be created");
+        topic_ids.push(topic.id);
+    }
+
+    let list = |tag_id| {
+        topic_service.list(
+            tenant_id,
+            admin.clone(),
+            ListTopicsFilter {
+                tag_id: Some(tag_id),
+                page: 1,
+                per_page: 20,
+                ..ListTopicsFilter::default()
+            },
+        )
+    };
+    let (topics, total) = list(term_ids[0]).await.expect("topics should list");
+    assert_eq!(total, 2);
+    let mut listed = topics.into_iter().map(|topic| topic.id).collect::<Vec<_>>();
+    listed.sort();
+    let mut expected = topic_ids[..2].to_vec();
+    expected.sort();
+    assert_eq!(listed, expected);
+
+    let (topics, total) = list(term_ids[1]).await.expect("topics should list");
+    assert_eq!(total, 1);
+    assert_eq!(topics[0].id, topic_ids[0]);
+}
@@ -6,6 +6,7 @@ use rustok_forum::{
     ModerationService, ReplyService, TopicService, UserStatsService,
 };
 use rustok_outbox::TransactionalEventBus;
+use rustok_profiles::ProfilesModule;
 use rustok_taxonomy::TaxonomyModule;
 use sea_orm::{ConnectOptions, Database, DatabaseConnection};
 use sea_orm_migration::SchemaManager;
@@ -35,12 +36,24 @@ async fn setup() -> (
 ) {
     let db = setup_forum_test_db().await;
     let schema = SchemaManager::new(&db);
+    for migration in rustok_content::migrations::migrations() {
+        migration
+            .up(&schema)
+            .await
+            .expect("content migration should apply");
+    }
     for migration in TaxonomyModule.migrations() {
         migration
             .up(&schema)
             .await
             .expect("taxonomy migration should apply");
     }
+    for migration in ProfilesModule.migrations() {
+        migration
+            .up(&schema)
+            .await
+            .expect("profiles migration should apply");
+    }
     let module = ForumModule;
     for migration in module.migrations() {
         migration
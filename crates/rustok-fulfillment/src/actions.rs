@@ -0,0 +1,82 @@
+use async_trait::async_trait;
+use rustok_core::{
+    ModuleAction, ModuleActionContext, ModuleActionDescriptor, ModuleActionError,
+    ModuleActionResult, Permission,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::{CreateFulfillmentInput, FulfillmentService};
+
+/// `fulfillment.create` — opens a pending fulfillment for a whole order.
+#[derive(Clone, Default)]
+pub struct CreateFulfillmentAction;
+
+#[derive(Deserialize)]
+struct CreateFulfillmentActionInput {
+    order_id: Uuid,
+    shipping_option_id: Option<Uuid>,
+    customer_id: Option<Uuid>,
+    carrier: Option<String>,
+    tracking_number: Option<String>,
+}
+
+#[async_trait]
+impl ModuleAction for CreateFulfillmentAction {
+    fn descriptor(&self) -> ModuleActionDescriptor {
+        ModuleActionDescriptor {
+            name: "fulfillment.create",
+            module_slug: "fulfillment",
+            description: "Create a fulfillment for an order",
+            input_schema: json!({
+                "type": "object",
+                "required": ["order_id"],
+                "additionalProperties": false,
+                "properties": {
+                    "order_id": { "type": "string", "format": "uuid" },
+                    "shipping_option_id": { "type": ["string", "null"], "format": "uuid" },
+                    "customer_id": { "type": ["string", "null"], "format": "uuid" },
+                    "carrier": { "type": ["string", "null"], "maxLength": 100 },
+                    "tracking_number": { "type": ["string", "null"], "maxLength": 100 }
+                }
+            }),
+            output_schema: json!({
+                "type": "object",
+                "required": ["fulfillment_id", "order_id", "status"],
+                "properties": {
+                    "fulfillment_id": { "type": "string", "format": "uuid" },
+                    "order_id": { "type": "string", "format": "uuid" },
+                    "status": { "type": "string" }
+                }
+            }),
+            required_permissions: vec![Permission::FULFILLMENTS_CREATE],
+        }
+    }
+
+    async fn invoke(&self, ctx: &ModuleActionContext, input: Value) -> ModuleActionResult<Value> {
+        let input: CreateFulfillmentActionInput =
+            serde_json::from_value(input).map_err(ModuleActionError::failed)?;
+        let fulfillment = FulfillmentService::new(ctx.db.clone())
+            .create_fulfillment(
+                ctx.tenant_id,
+                CreateFulfillmentInput {
+                    order_id: input.order_id,
+                    shipping_option_id: input.shipping_option_id,
+                    customer_id: input.customer_id,
+                    carrier: input.carrier,
+                    tracking_number: input.tracking_number,
+                    items: None,
+                    metadata: json!({ "source": "workflow" }),
+                },
+            )
+            .await
+            .map_err(ModuleActionError::failed)?;
+
+        Ok(json!({
+            "fulfillment_id": fulfillment.id,
+            "order_id": fulfillment.order_id,
+            "status": fulfillment.status,
+        }))
+    }
+}
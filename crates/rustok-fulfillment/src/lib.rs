@@ -1,8 +1,9 @@
 use async_trait::async_trait;
 use rustok_core::permissions::Permission;
-use rustok_core::{MigrationSource, RusToKModule};
+use rustok_core::{register_module_action, MigrationSource, ModuleRuntimeExtensions, RusToKModule};
 use sea_orm_migration::MigrationTrait;
 
+mod actions;
 pub mod dto;
 pub mod entities;
 pub mod error;
@@ -44,6 +45,11 @@ impl RusToKModule for FulfillmentModule {
             Permission::FULFILLMENTS_MANAGE,
         ]
     }
+
+    fn register_runtime_extensions(&self, extensions: &mut ModuleRuntimeExtensions) {
+        register_module_action(extensions, actions::CreateFulfillmentAction)
+            .expect("fulfillment.create action registration should remain unique");
+    }
 }
 
 impl MigrationSource for FulfillmentModule {
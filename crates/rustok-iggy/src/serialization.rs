@@ -22,7 +22,8 @@ impl EventSerializer for JsonSerializer {
     }
 
     fn deserialize(&self, payload: &[u8]) -> Result<EventEnvelope> {
-        Ok(serde_json::from_slice(payload)?)
+        EventEnvelope::from_stored_json(serde_json::from_slice(payload)?)
+            .map_err(|err| rustok_core::Error::Validation(err.to_string()))
     }
 }
 
@@ -0,0 +1,185 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::source::ImportSourceKind;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportEntityKind {
+    Author,
+    Category,
+    Tag,
+    Media,
+    Post,
+    Page,
+    Comment,
+    Redirect,
+}
+
+impl ImportEntityKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Author => "author",
+            Self::Category => "category",
+            Self::Tag => "tag",
+            Self::Media => "media",
+            Self::Post => "post",
+            Self::Page => "page",
+            Self::Comment => "comment",
+            Self::Redirect => "redirect",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportAction {
+    /// Dry run: the entry would be created.
+    Planned,
+    Imported,
+    /// Resume: the ledger already records this entry as imported.
+    AlreadyImported,
+    Skipped,
+    Failed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportJobStatus {
+    Running,
+    Completed,
+    /// The run finished but some entries failed; running the job again retries them.
+    Failed,
+}
+
+impl ImportJobStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Running => "running",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "completed" => Self::Completed,
+            "failed" => Self::Failed,
+            _ => Self::Running,
+        }
+    }
+}
+
+/// How a bundle is mapped onto the tenant.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportOptions {
+    pub locale: String,
+    /// Source author login or email (lowercase) → platform user id.
+    pub author_map: HashMap<String, Uuid>,
+    /// Author of items whose source author is not mapped. Falls back to the
+    /// importing user.
+    pub default_author_id: Option<Uuid>,
+    /// Author of guest comments. Guest comments are skipped when unset.
+    pub guest_author_id: Option<Uuid>,
+    pub import_media: bool,
+    pub create_redirects: bool,
+    pub redirect_status_code: i32,
+    pub channel_slugs: Option<Vec<String>>,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self {
+            locale: "en".to_string(),
+            author_map: HashMap::new(),
+            default_author_id: None,
+            guest_author_id: None,
+            import_media: true,
+            create_redirects: true,
+            redirect_status_code: 301,
+            channel_slugs: None,
+        }
+    }
+}
+
+impl ImportOptions {
+    pub(crate) fn mapped_author(&self, login: Option<&str>, email: Option<&str>) -> Option<Uuid> {
+        [login, email]
+            .into_iter()
+            .flatten()
+            .find_map(|key| self.author_map.get(&key.trim().to_lowercase()).copied())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportCounts {
+    pub planned: usize,
+    pub imported: usize,
+    pub already_imported: usize,
+    pub skipped: usize,
+    pub failed: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportReportEntry {
+    pub kind: ImportEntityKind,
+    pub source_id: String,
+    pub action: ImportAction,
+    pub target_id: Option<Uuid>,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportReport {
+    pub job_id: Option<Uuid>,
+    pub dry_run: bool,
+    pub counts: BTreeMap<ImportEntityKind, ImportCounts>,
+    pub entries: Vec<ImportReportEntry>,
+    pub warnings: Vec<String>,
+}
+
+impl ImportReport {
+    pub fn counts_for(&self, kind: ImportEntityKind) -> ImportCounts {
+        self.counts.get(&kind).copied().unwrap_or_default()
+    }
+
+    pub fn has_failures(&self) -> bool {
+        self.counts.values().any(|counts| counts.failed > 0)
+    }
+
+    pub(crate) fn push(&mut self, entry: ImportReportEntry) {
+        let counts = self.counts.entry(entry.kind).or_default();
+        match entry.action {
+            ImportAction::Planned => counts.planned += 1,
+            ImportAction::Imported => counts.imported += 1,
+            ImportAction::AlreadyImported => counts.already_imported += 1,
+            ImportAction::Skipped => counts.skipped += 1,
+            ImportAction::Failed => counts.failed += 1,
+        }
+        self.entries.push(entry);
+    }
+
+    pub(crate) fn warn(&mut self, warning: impl Into<String>) {
+        let warning = warning.into();
+        if !self.warnings.contains(&warning) {
+            self.warnings.push(warning);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportJobRecord {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub source_kind: ImportSourceKind,
+    pub source_name: String,
+    pub status: ImportJobStatus,
+    pub summary: BTreeMap<ImportEntityKind, ImportCounts>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,39 @@
+use sea_orm::entity::prelude::*;
+
+/// Progress ledger entry: one row per source entity and kind within a job.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "import_job_items")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub job_id: Uuid,
+    pub tenant_id: Uuid,
+    pub kind: String,
+    pub source_id: String,
+    /// `imported`, `skipped` or `failed`; only `imported` entries are skipped on resume.
+    pub status: String,
+    pub target_id: Option<Uuid>,
+    /// Kind-specific reference to the created entity (slug, media URL, redirect target).
+    pub target_ref: Option<String>,
+    pub message: Option<String>,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::import_job::Entity",
+        from = "Column::JobId",
+        to = "super::import_job::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Job,
+}
+
+impl Related<super::import_job::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Job.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
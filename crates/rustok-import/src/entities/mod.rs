@@ -0,0 +1,5 @@
+pub mod import_job;
+pub mod import_job_item;
+
+pub use import_job::Entity as ImportJob;
+pub use import_job_item::Entity as ImportJobItem;
@@ -0,0 +1,46 @@
+use thiserror::Error;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum ImportError {
+    #[error("Import job not found: {0}")]
+    JobNotFound(Uuid),
+
+    #[error("Invalid import source: {0}")]
+    Parse(String),
+
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
+    #[error("Validation error: {0}")]
+    Validation(String),
+
+    #[error("Media fetch failed: {0}")]
+    Fetch(String),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Blog error: {0}")]
+    Blog(#[from] rustok_blog::BlogError),
+
+    #[error("Pages error: {0}")]
+    Pages(#[from] rustok_pages::PagesError),
+
+    #[error("Media error: {0}")]
+    Media(#[from] rustok_media::MediaError),
+
+    #[error("SEO error: {0}")]
+    Seo(#[from] rustok_seo::SeoError),
+
+    #[error("Profile error: {0}")]
+    Profile(#[from] rustok_profiles::error::ProfileError),
+
+    #[error("Database error: {0}")]
+    Database(#[from] sea_orm::DbErr),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+pub type ImportResult<T> = Result<T, ImportError>;
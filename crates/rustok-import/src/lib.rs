@@ -0,0 +1,78 @@
+//! Content import for RusToK.
+//!
+//! Reads WordPress WXR exports ([`parse_wxr`]) and directories of Markdown
+//! files with YAML front matter ([`load_markdown_dir`]) into a source-neutral
+//! [`ImportBundle`], then [`ImportService`] creates authors' profiles,
+//! categories, tags, media, blog posts, pages, comments and SEO redirects
+//! through the owning modules' services.
+//!
+//! Every run belongs to an import job (`import_jobs`). Each imported entry is
+//! recorded in the job ledger (`import_job_items`), so an interrupted or
+//! partially failed job can be run again and only the remaining entries are
+//! processed. [`ImportService::dry_run`] produces the same report without
+//! writing anything.
+
+use async_trait::async_trait;
+use rustok_core::{MigrationSource, RusToKModule};
+use sea_orm_migration::MigrationTrait;
+
+pub mod dto;
+pub mod entities;
+pub mod error;
+pub mod markdown;
+pub mod migrations;
+pub mod services;
+pub mod source;
+pub mod wxr;
+
+pub use dto::*;
+pub use error::{ImportError, ImportResult};
+pub use markdown::{load_markdown_dir, parse_markdown_document};
+pub use services::{DefaultMediaFetcher, FetchedMedia, ImportService, MediaFetcher};
+pub use source::*;
+pub use wxr::parse_wxr;
+
+pub struct ImportModule;
+
+#[async_trait]
+impl RusToKModule for ImportModule {
+    fn slug(&self) -> &'static str {
+        "import"
+    }
+
+    fn name(&self) -> &'static str {
+        "Import"
+    }
+
+    fn description(&self) -> &'static str {
+        "WordPress WXR and Markdown content import with dry-run reports and resumable jobs"
+    }
+
+    fn version(&self) -> &'static str {
+        env!("CARGO_PKG_VERSION")
+    }
+
+    fn dependencies(&self) -> &[&'static str] {
+        &["blog", "pages", "media", "seo", "profiles"]
+    }
+}
+
+impl MigrationSource for ImportModule {
+    fn migrations(&self) -> Vec<Box<dyn MigrationTrait>> {
+        migrations::migrations()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn module_metadata() {
+        let module = ImportModule;
+        assert_eq!(module.slug(), "import");
+        assert_eq!(module.name(), "Import");
+        assert_eq!(module.version(), env!("CARGO_PKG_VERSION"));
+        assert!(!module.migrations().is_empty());
+    }
+}
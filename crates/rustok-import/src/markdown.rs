@@ -0,0 +1,316 @@
+//! Markdown reader for directories of files with YAML front matter.
+//!
+//! Front-matter keys follow the common static-site generator conventions
+//! (Jekyll, Hugo, Eleventy): `title`, `slug`, `date`, `draft`/`published`,
+//! `type`/`layout`, `author`, `categories`, `tags`, `excerpt`/`description`,
+//! `image`, `permalink` and `aliases`/`redirect_from`.
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Deserialize;
+
+use crate::error::{ImportError, ImportResult};
+use crate::source::{
+    image_references, slugify, ImportAttachment, ImportBundle, ImportCategory, ImportItem,
+    ImportItemKind,
+};
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    #[default]
+    None,
+    One(String),
+    Many(Vec<String>),
+}
+
+impl OneOrMany {
+    fn into_vec(self) -> Vec<String> {
+        match self {
+            Self::None => Vec::new(),
+            Self::One(value) => value
+                .split(',')
+                .map(|part| part.trim().to_string())
+                .filter(|part| !part.is_empty())
+                .collect(),
+            Self::Many(values) => values,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct FrontMatter {
+    title: Option<String>,
+    slug: Option<String>,
+    date: Option<String>,
+    draft: Option<bool>,
+    published: Option<bool>,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    layout: Option<String>,
+    author: Option<String>,
+    categories: OneOrMany,
+    tags: OneOrMany,
+    excerpt: Option<String>,
+    description: Option<String>,
+    summary: Option<String>,
+    image: Option<String>,
+    featured_image: Option<String>,
+    permalink: Option<String>,
+    url: Option<String>,
+    aliases: OneOrMany,
+    redirect_from: OneOrMany,
+}
+
+/// Split a document into its front matter and body.
+fn split_front_matter(text: &str) -> (Option<&str>, &str) {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let Some(rest) = text
+        .strip_prefix("---\n")
+        .or_else(|| text.strip_prefix("---\r\n"))
+    else {
+        return (None, text);
+    };
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return (Some(&rest[..offset]), &rest[offset + line.len()..]);
+        }
+        offset += line.len();
+    }
+    (None, text)
+}
+
+fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|value| value.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(value.get(..10)?, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+                .map(|value| value.and_utc())
+        })
+}
+
+/// Parse one Markdown document. `source_id` is its path relative to the
+/// import root and becomes the fallback slug.
+pub fn parse_markdown_document(source_id: &str, text: &str) -> ImportResult<ImportItem> {
+    let (front_matter, body) = split_front_matter(text);
+    let front_matter: FrontMatter = match front_matter {
+        Some(raw) if !raw.trim().is_empty() => serde_yaml::from_str(raw)
+            .map_err(|error| ImportError::Parse(format!("{source_id}: {error}")))?,
+        _ => FrontMatter::default(),
+    };
+
+    let stem = Path::new(source_id)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or(source_id);
+    let kind = match front_matter
+        .kind
+        .as_deref()
+        .or(front_matter.layout.as_deref())
+        .map(str::to_ascii_lowercase)
+        .as_deref()
+    {
+        Some("page") => ImportItemKind::Page,
+        _ => ImportItemKind::Post,
+    };
+    let published = front_matter
+        .published
+        .unwrap_or(!front_matter.draft.unwrap_or(false));
+
+    let mut legacy_urls = front_matter.aliases.into_vec();
+    legacy_urls.extend(front_matter.redirect_from.into_vec());
+    legacy_urls.extend(front_matter.permalink.or(front_matter.url));
+
+    Ok(ImportItem {
+        source_id: source_id.to_string(),
+        kind,
+        title: front_matter.title.unwrap_or_else(|| stem.to_string()),
+        slug: front_matter.slug.or_else(|| Some(slugify(stem))),
+        body: body.trim_start_matches(['\r', '\n']).to_string(),
+        excerpt: front_matter
+            .excerpt
+            .or(front_matter.description)
+            .or(front_matter.summary),
+        published,
+        published_at: front_matter.date.as_deref().and_then(parse_date),
+        author: front_matter.author,
+        categories: front_matter
+            .categories
+            .into_vec()
+            .iter()
+            .map(|name| slugify(name))
+            .collect(),
+        tags: front_matter.tags.into_vec(),
+        legacy_urls,
+        featured_image: front_matter.featured_image.or(front_matter.image),
+        comments: Vec::new(),
+    })
+}
+
+/// Read every `*.md`/`*.markdown` file below `root`.
+///
+/// Categories are derived from the names used in front matter. Relative image
+/// references are resolved against the document and imported as `file://`
+/// attachments; references that resolve outside `root` are skipped.
+pub fn load_markdown_dir(root: &Path) -> ImportResult<ImportBundle> {
+    let canonical_root = root.canonicalize()?;
+    let mut files = Vec::new();
+    collect_markdown_files(root, &mut files)?;
+    files.sort();
+
+    let mut bundle = ImportBundle::default();
+    for path in files {
+        let relative = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .components()
+            .map(|part| part.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        let text = std::fs::read_to_string(&path)?;
+        let item = parse_markdown_document(&relative, &text)?;
+
+        for name in read_category_names(&text) {
+            let slug = slugify(&name);
+            if !slug.is_empty() && !bundle.categories.iter().any(|known| known.slug == slug) {
+                bundle.categories.push(ImportCategory {
+                    slug,
+                    name,
+                    parent_slug: None,
+                    description: None,
+                });
+            }
+        }
+
+        let base = path.parent().unwrap_or(root);
+        let references = image_references(&item.body)
+            .into_iter()
+            .chain(item.featured_image.clone());
+        for reference in references {
+            if let Some(url) = attachment_url(base, &canonical_root, &reference) {
+                bundle.push_attachment(ImportAttachment {
+                    source_id: reference.clone(),
+                    url,
+                    reference,
+                    title: None,
+                });
+            } else if !reference.contains("://") && !reference.starts_with("data:") {
+                bundle.notices.push(format!(
+                    "{relative}: skipped image `{reference}` not found inside the import directory"
+                ));
+            }
+        }
+        bundle.items.push(item);
+    }
+    Ok(bundle)
+}
+
+fn collect_markdown_files(dir: &Path, files: &mut Vec<PathBuf>) -> ImportResult<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_markdown_files(&path, files)?;
+        } else if path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| {
+                ext.eq_ignore_ascii_case("md") || ext.eq_ignore_ascii_case("markdown")
+            })
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn read_category_names(text: &str) -> Vec<String> {
+    split_front_matter(text)
+        .0
+        .and_then(|raw| serde_yaml::from_str::<FrontMatter>(raw).ok())
+        .map(|front_matter| front_matter.categories.into_vec())
+        .unwrap_or_default()
+}
+
+/// `root` must be canonical: a reference is only imported when its canonical
+/// path stays inside it, so `../` and symlinks cannot reach other files.
+fn attachment_url(base: &Path, root: &Path, reference: &str) -> Option<String> {
+    if reference.starts_with("http://") || reference.starts_with("https://") {
+        return Some(reference.to_string());
+    }
+    if reference.contains("://") || reference.starts_with("data:") {
+        return None;
+    }
+    let path = match reference.strip_prefix('/') {
+        Some(site_relative) => root.join(site_relative),
+        None => base.join(reference),
+    };
+    let path = path
+        .canonicalize()
+        .ok()
+        .filter(|path| path.starts_with(root))?;
+    url::Url::from_file_path(path).ok().map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_front_matter_and_body() {
+        let item = parse_markdown_document(
+            "posts/hello-world.md",
+            "---\ntitle: Hello\ndate: 2021-05-06\ntags: rust, web\ncategories: [Release Notes]\naliases:\n  - /old/hello/\ndraft: true\n---\n\n# Hi\n",
+        )
+        .unwrap();
+        assert_eq!(item.title, "Hello");
+        assert_eq!(item.slug.as_deref(), Some("hello-world"));
+        assert_eq!(item.kind, ImportItemKind::Post);
+        assert!(!item.published);
+        assert_eq!(item.tags, vec!["rust", "web"]);
+        assert_eq!(item.categories, vec!["release-notes"]);
+        assert_eq!(item.legacy_urls, vec!["/old/hello/"]);
+        assert_eq!(item.body, "# Hi\n");
+        assert!(item.published_at.is_some());
+    }
+
+    #[test]
+    fn documents_without_front_matter_become_posts() {
+        let item = parse_markdown_document("about.md", "Just text").unwrap();
+        assert_eq!(item.title, "about");
+        assert_eq!(item.body, "Just text");
+        assert!(item.published);
+    }
+
+    #[test]
+    fn page_layout_is_detected() {
+        let item = parse_markdown_document("about.md", "---\nlayout: page\n---\nx").unwrap();
+        assert_eq!(item.kind, ImportItemKind::Page);
+    }
+
+    #[test]
+    fn image_references_stay_inside_the_root() {
+        let dir = std::env::temp_dir().join(format!("rustok-markdown-{}", uuid::Uuid::new_v4()));
+        let root = dir.join("site");
+        std::fs::create_dir_all(root.join("posts")).unwrap();
+        std::fs::write(root.join("posts/logo.png"), b"png").unwrap();
+        std::fs::write(dir.join("secret.png"), b"secret").unwrap();
+        std::fs::write(
+            root.join("posts/hello.md"),
+            "![a](logo.png) ![b](../../secret.png) ![c](/../secret.png)",
+        )
+        .unwrap();
+
+        let bundle = load_markdown_dir(&root).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(bundle.attachments.len(), 1);
+        assert_eq!(bundle.attachments[0].reference, "logo.png");
+        assert_eq!(bundle.notices.len(), 2);
+    }
+}
@@ -0,0 +1,158 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ImportJobs::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ImportJobs::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ImportJobs::TenantId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(ImportJobs::SourceKind)
+                            .string_len(32)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ImportJobs::SourceName)
+                            .string_len(1024)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ImportJobs::Status).string_len(32).not_null())
+                    .col(ColumnDef::new(ImportJobs::Summary).json().not_null())
+                    .col(ColumnDef::new(ImportJobs::CreatedBy).uuid())
+                    .col(
+                        ColumnDef::new(ImportJobs::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ImportJobs::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ImportJobs::FinishedAt).timestamp_with_time_zone())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_import_jobs_tenant")
+                    .table(ImportJobs::Table)
+                    .col(ImportJobs::TenantId)
+                    .col(ImportJobs::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ImportJobItems::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ImportJobItems::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ImportJobItems::JobId).uuid().not_null())
+                    .col(ColumnDef::new(ImportJobItems::TenantId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(ImportJobItems::Kind)
+                            .string_len(32)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ImportJobItems::SourceId)
+                            .string_len(1024)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ImportJobItems::Status)
+                            .string_len(32)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ImportJobItems::TargetId).uuid())
+                    .col(ColumnDef::new(ImportJobItems::TargetRef).string_len(2048))
+                    .col(ColumnDef::new(ImportJobItems::Message).text())
+                    .col(
+                        ColumnDef::new(ImportJobItems::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(ImportJobItems::Table, ImportJobItems::JobId)
+                            .to(ImportJobs::Table, ImportJobs::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_import_job_items_source")
+                    .table(ImportJobItems::Table)
+                    .col(ImportJobItems::JobId)
+                    .col(ImportJobItems::Kind)
+                    .col(ImportJobItems::SourceId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ImportJobItems::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(ImportJobs::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ImportJobs {
+    Table,
+    Id,
+    TenantId,
+    SourceKind,
+    SourceName,
+    Status,
+    Summary,
+    CreatedBy,
+    CreatedAt,
+    UpdatedAt,
+    FinishedAt,
+}
+
+#[derive(DeriveIden)]
+enum ImportJobItems {
+    Table,
+    Id,
+    JobId,
+    TenantId,
+    Kind,
+    SourceId,
+    Status,
+    TargetId,
+    TargetRef,
+    Message,
+    UpdatedAt,
+}
@@ -0,0 +1,7 @@
+mod m20260616_000003_create_import_tables;
+
+use sea_orm_migration::MigrationTrait;
+
+pub fn migrations() -> Vec<Box<dyn MigrationTrait>> {
+    vec![Box::new(m20260616_000003_create_import_tables::Migration)]
+}
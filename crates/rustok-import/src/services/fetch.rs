@@ -0,0 +1,273 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use rustok_core::{read_body_limited, SsrfProtection};
+use rustok_media::DEFAULT_MAX_SIZE;
+
+use crate::error::{ImportError, ImportResult};
+
+/// A downloaded media file ready for `MediaService::upload`.
+pub struct FetchedMedia {
+    pub file_name: String,
+    pub content_type: String,
+    pub data: Bytes,
+}
+
+/// Downloads files referenced by an import source.
+#[async_trait]
+pub trait MediaFetcher: Send + Sync {
+    async fn fetch(&self, url: &str) -> ImportResult<FetchedMedia>;
+}
+
+/// Fetches `http(s)://` URLs with `reqwest` and reads `file://` URLs from disk.
+///
+/// Remote URLs go through [`SsrfProtection`]: the host is resolved once, every
+/// address is checked and the request is pinned to those addresses with
+/// redirects disabled. `file://` URLs are only read below the directory set with
+/// [`DefaultMediaFetcher::with_local_root`]. Files larger than `max_size` are
+/// rejected in both cases.
+#[derive(Clone)]
+pub struct DefaultMediaFetcher {
+    ssrf: SsrfProtection,
+    local_root: Option<PathBuf>,
+    max_size: u64,
+    timeout: Duration,
+}
+
+impl Default for DefaultMediaFetcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DefaultMediaFetcher {
+    pub fn new() -> Self {
+        Self {
+            ssrf: SsrfProtection::new(),
+            local_root: None,
+            max_size: DEFAULT_MAX_SIZE,
+            timeout: Duration::from_secs(30),
+        }
+    }
+
+    /// Allow `file://` URLs below `root` (the Markdown import directory).
+    pub fn with_local_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.local_root = Some(root.into());
+        self
+    }
+
+    pub fn with_ssrf_protection(mut self, ssrf: SsrfProtection) -> Self {
+        self.ssrf = ssrf;
+        self
+    }
+
+    pub fn with_max_size(mut self, max_size: u64) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    async fn read_local(&self, url: &str, parsed: &url::Url) -> ImportResult<Bytes> {
+        let Some(root) = &self.local_root else {
+            return Err(ImportError::Fetch(format!(
+                "{url}: local files are not allowed for this import"
+            )));
+        };
+        let path = parsed
+            .to_file_path()
+            .map_err(|_| ImportError::Fetch(format!("{url}: invalid file path")))?;
+        let root = tokio::fs::canonicalize(root).await?;
+        let path = tokio::fs::canonicalize(path).await?;
+        if !path.starts_with(&root) {
+            return Err(ImportError::Fetch(format!(
+                "{url}: outside the import directory"
+            )));
+        }
+        if tokio::fs::metadata(&path).await?.len() > self.max_size {
+            return Err(self.too_large(url));
+        }
+        Ok(Bytes::from(tokio::fs::read(path).await?))
+    }
+
+    async fn download(&self, url: &str, guessed: String) -> ImportResult<(String, Bytes)> {
+        let fetch_error = |error: reqwest::Error| ImportError::Fetch(format!("{url}: {error}"));
+        let resolved = self
+            .ssrf
+            .resolve(url)
+            .await
+            .map_err(|reason| ImportError::Fetch(format!("{url}: {reason}")))?;
+        let client = resolved
+            .client_builder()
+            .timeout(self.timeout)
+            .build()
+            .map_err(fetch_error)?;
+        let response = client
+            .get(resolved.url)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(fetch_error)?;
+        // Redirects are not followed, so a 3xx lands here instead of at an unchecked host.
+        if !response.status().is_success() {
+            return Err(ImportError::Fetch(format!(
+                "{url}: unexpected status {}",
+                response.status()
+            )));
+        }
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.split(';').next().unwrap_or(value).trim().to_string())
+            .filter(|value| !value.is_empty() && value != "application/octet-stream")
+            .unwrap_or(guessed);
+        let limit = usize::try_from(self.max_size).unwrap_or(usize::MAX);
+        let (data, truncated) = read_body_limited(response, limit)
+            .await
+            .map_err(fetch_error)?;
+        if truncated {
+            return Err(self.too_large(url));
+        }
+        Ok((content_type, Bytes::from(data)))
+    }
+
+    fn too_large(&self, url: &str) -> ImportError {
+        ImportError::Fetch(format!("{url}: larger than {} bytes", self.max_size))
+    }
+}
+
+#[async_trait]
+impl MediaFetcher for DefaultMediaFetcher {
+    async fn fetch(&self, url: &str) -> ImportResult<FetchedMedia> {
+        let parsed =
+            url::Url::parse(url).map_err(|error| ImportError::Fetch(format!("{url}: {error}")))?;
+        let file_name = parsed
+            .path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .filter(|name| !name.is_empty())
+            .unwrap_or("file")
+            .to_string();
+        let guessed = mime_guess::from_path(&file_name)
+            .first_or_octet_stream()
+            .essence_str()
+            .to_string();
+
+        match parsed.scheme() {
+            "file" => Ok(FetchedMedia {
+                data: self.read_local(url, &parsed).await?,
+                file_name,
+                content_type: guessed,
+            }),
+            "http" | "https" => {
+                let (content_type, data) = self.download(url, guessed).await?;
+                Ok(FetchedMedia {
+                    file_name,
+                    content_type,
+                    data,
+                })
+            }
+            scheme => Err(ImportError::Fetch(format!(
+                "{url}: unsupported scheme `{scheme}`"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("rustok-fetch-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("site")).unwrap();
+        dir
+    }
+
+    fn file_url(path: &std::path::Path) -> String {
+        url::Url::from_file_path(path).unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn local_files_are_read_only_below_the_root() {
+        let dir = temp_dir();
+        std::fs::write(dir.join("site/logo.png"), b"png").unwrap();
+        std::fs::write(dir.join("secret.txt"), b"secret").unwrap();
+
+        let fetcher = DefaultMediaFetcher::new().with_local_root(dir.join("site"));
+        let fetched = fetcher
+            .fetch(&file_url(&dir.join("site/logo.png")))
+            .await
+            .unwrap();
+        assert_eq!(fetched.content_type, "image/png");
+        assert_eq!(&fetched.data[..], b"png");
+
+        let outside = fetcher.fetch(&file_url(&dir.join("secret.txt"))).await;
+        let without_root = DefaultMediaFetcher::new()
+            .fetch(&file_url(&dir.join("site/logo.png")))
+            .await;
+        let too_large = fetcher
+            .clone()
+            .with_max_size(2)
+            .fetch(&file_url(&dir.join("site/logo.png")))
+            .await;
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(matches!(outside, Err(ImportError::Fetch(_))));
+        assert!(matches!(without_root, Err(ImportError::Fetch(_))));
+        assert!(matches!(too_large, Err(ImportError::Fetch(_))));
+    }
+
+    #[tokio::test]
+    async fn private_network_urls_are_rejected() {
+        let fetcher = DefaultMediaFetcher::new();
+        for url in [
+            "http://127.0.0.1/logo.png",
+            "http://localhost:8080/logo.png",
+            "http://169.254.169.254/latest/meta-data",
+            "http://[::ffff:10.0.0.1]/logo.png",
+            "ftp://old.test/logo.png",
+        ] {
+            assert!(
+                matches!(fetcher.fetch(url).await, Err(ImportError::Fetch(_))),
+                "{url} should be rejected"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn remote_bodies_are_capped() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut request = [0u8; 1024];
+                let _ = socket.read(&mut request).await;
+                let body = vec![b'x'; 64];
+                let head = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: image/png\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+                    body.len()
+                );
+                let _ = socket.write_all(head.as_bytes()).await;
+                let _ = socket.write_all(&body).await;
+            }
+        });
+
+        let url = format!("http://{addr}/logo.png");
+        let fetcher = DefaultMediaFetcher::new()
+            .with_ssrf_protection(SsrfProtection::new().allow_private_networks());
+        let fetched = fetcher.fetch(&url).await.unwrap();
+        assert_eq!(fetched.data.len(), 64);
+        assert!(matches!(
+            fetcher.with_max_size(16).fetch(&url).await,
+            Err(ImportError::Fetch(_))
+        ));
+    }
+}
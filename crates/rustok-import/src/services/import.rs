@@ -0,0 +1,1155 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use chrono::Utc;
+use rustok_api::TenantContext;
+use rustok_blog::{
+    CategoryService, CommentService, CreateCategoryInput, CreateCommentInput, CreatePostInput,
+    CreateTagInput, ListCategoriesFilter, ListTagsFilter, ModerateCommentInput,
+    ModerateCommentStatus, PostService, TagService,
+};
+use rustok_core::{Action, PermissionScope, Resource, SecurityContext};
+use rustok_media::{MediaService, UploadInput};
+use rustok_outbox::TransactionalEventBus;
+use rustok_pages::dto::{CreatePageInput, PageBodyInput, PageTranslationInput};
+use rustok_pages::services::PageService;
+use rustok_profiles::{ProfileService, ProfileVisibility};
+use rustok_seo::{SeoRedirectInput, SeoRedirectMatchType, SeoService};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
+};
+use serde_json::json;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::dto::{
+    ImportAction, ImportEntityKind, ImportJobRecord, ImportJobStatus, ImportOptions, ImportReport,
+    ImportReportEntry,
+};
+use crate::entities::{import_job, import_job_item};
+use crate::error::{ImportError, ImportResult};
+use crate::services::fetch::{DefaultMediaFetcher, MediaFetcher};
+use crate::source::{
+    legacy_path, slugify, ImportBundle, ImportCategory, ImportComment, ImportItem, ImportItemKind,
+    ImportSourceKind,
+};
+
+const BLOG_ROUTE_PREFIX: &str = "/modules/blog?slug=";
+const PAGES_ROUTE_PREFIX: &str = "/modules/pages?slug=";
+const LIST_PAGE_SIZE: u64 = 100;
+const MAX_CATEGORY_DEPTH: usize = 16;
+
+type LedgerKey = (ImportEntityKind, String);
+
+/// Outcome of a successfully imported entry.
+#[derive(Default)]
+struct Settled {
+    target_id: Option<Uuid>,
+    target_ref: Option<String>,
+    message: Option<String>,
+}
+
+struct Run<'a> {
+    tenant: &'a TenantContext,
+    security: &'a SecurityContext,
+    options: &'a ImportOptions,
+    /// Set for real runs; dry runs read the ledger but never write it.
+    job_id: Option<Uuid>,
+    ledger: HashMap<LedgerKey, import_job_item::Model>,
+    report: ImportReport,
+}
+
+impl Run<'_> {
+    fn dry_run(&self) -> bool {
+        self.report.dry_run
+    }
+
+    fn imported(&self, kind: ImportEntityKind, source_id: &str) -> Option<import_job_item::Model> {
+        self.ledger
+            .get(&(kind, source_id.to_string()))
+            .filter(|entry| entry.status == "imported")
+            .cloned()
+    }
+
+    fn entry(
+        &mut self,
+        kind: ImportEntityKind,
+        source_id: &str,
+        action: ImportAction,
+        target_id: Option<Uuid>,
+        message: Option<String>,
+    ) {
+        self.report.push(ImportReportEntry {
+            kind,
+            source_id: source_id.to_string(),
+            action,
+            target_id,
+            message,
+        });
+    }
+
+    fn already(&mut self, kind: ImportEntityKind, entry: &import_job_item::Model) {
+        self.entry(
+            kind,
+            &entry.source_id,
+            ImportAction::AlreadyImported,
+            entry.target_id,
+            None,
+        );
+    }
+
+    fn skip(&mut self, kind: ImportEntityKind, source_id: &str, message: impl Into<String>) {
+        self.entry(
+            kind,
+            source_id,
+            ImportAction::Skipped,
+            None,
+            Some(message.into()),
+        );
+    }
+
+    fn plan(&mut self, kind: ImportEntityKind, source_id: &str, message: Option<String>) {
+        self.entry(kind, source_id, ImportAction::Planned, None, message);
+    }
+
+    fn acting_as(&self, user_id: Uuid) -> SecurityContext {
+        let mut security = self.security.clone();
+        security.user_id = Some(user_id);
+        security
+    }
+}
+
+/// Imports an [`ImportBundle`] into blog, pages, media and SEO redirects.
+///
+/// Every created entity is recorded in the job ledger (`import_job_items`), so
+/// running the same job again skips finished entries and retries failed ones.
+pub struct ImportService {
+    db: DatabaseConnection,
+    event_bus: TransactionalEventBus,
+    media: Option<Arc<MediaService>>,
+    seo: Option<Arc<SeoService>>,
+    fetcher: Arc<dyn MediaFetcher>,
+}
+
+impl ImportService {
+    pub fn new(db: DatabaseConnection, event_bus: TransactionalEventBus) -> Self {
+        Self {
+            db,
+            event_bus,
+            media: None,
+            seo: None,
+            fetcher: Arc::new(DefaultMediaFetcher::default()),
+        }
+    }
+
+    /// Upload referenced media through `MediaService`; without it bodies keep
+    /// their original media URLs.
+    pub fn with_media(mut self, media: MediaService) -> Self {
+        self.media = Some(Arc::new(media));
+        self
+    }
+
+    pub fn with_media_fetcher(mut self, fetcher: impl MediaFetcher + 'static) -> Self {
+        self.fetcher = Arc::new(fetcher);
+        self
+    }
+
+    /// Register legacy URLs as redirects; without it redirects are skipped.
+    pub fn with_seo(mut self, seo: SeoService) -> Self {
+        self.seo = Some(Arc::new(seo));
+        self
+    }
+
+    #[instrument(skip(self, security))]
+    pub async fn create_job(
+        &self,
+        tenant_id: Uuid,
+        security: &SecurityContext,
+        source_kind: ImportSourceKind,
+        source_name: &str,
+    ) -> ImportResult<ImportJobRecord> {
+        let can_manage = [Resource::BlogPosts, Resource::Pages]
+            .into_iter()
+            .any(|resource| {
+                matches!(
+                    security.get_scope(resource, Action::Manage),
+                    PermissionScope::All
+                )
+            });
+        if !can_manage {
+            return Err(ImportError::Forbidden(
+                "Importing content requires blog or pages manage permission".to_string(),
+            ));
+        }
+
+        let now = Utc::now().fixed_offset();
+        let model = import_job::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tenant_id: Set(tenant_id),
+            source_kind: Set(source_kind.as_str().to_string()),
+            source_name: Set(source_name.to_string()),
+            status: Set(ImportJobStatus::Running.as_str().to_string()),
+            summary: Set(json!({})),
+            created_by: Set(security.user_id),
+            created_at: Set(now),
+            updated_at: Set(now),
+            finished_at: Set(None),
+        }
+        .insert(&self.db)
+        .await?;
+        map_job(model)
+    }
+
+    pub async fn get_job(&self, tenant_id: Uuid, job_id: Uuid) -> ImportResult<ImportJobRecord> {
+        map_job(self.find_job(tenant_id, job_id).await?)
+    }
+
+    pub async fn list_jobs(&self, tenant_id: Uuid) -> ImportResult<Vec<ImportJobRecord>> {
+        import_job::Entity::find()
+            .filter(import_job::Column::TenantId.eq(tenant_id))
+            .order_by_desc(import_job::Column::CreatedAt)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(map_job)
+            .collect()
+    }
+
+    /// Report what a run would do without writing anything. With `job_id`
+    /// the report accounts for entries the job has already imported.
+    #[instrument(skip(self, tenant, security, bundle, options))]
+    pub async fn dry_run(
+        &self,
+        tenant: &TenantContext,
+        security: &SecurityContext,
+        job_id: Option<Uuid>,
+        bundle: &ImportBundle,
+        options: &ImportOptions,
+    ) -> ImportResult<ImportReport> {
+        let ledger = match job_id {
+            Some(job_id) => {
+                self.find_job(tenant.id, job_id).await?;
+                self.load_ledger(job_id).await?
+            }
+            None => HashMap::new(),
+        };
+        let mut run = Run {
+            tenant,
+            security,
+            options,
+            job_id: None,
+            ledger,
+            report: ImportReport {
+                job_id,
+                dry_run: true,
+                ..ImportReport::default()
+            },
+        };
+        self.execute(&mut run, bundle).await?;
+        Ok(run.report)
+    }
+
+    /// Import `bundle` under `job_id`. Safe to call again with the same job
+    /// after a failure or interruption.
+    #[instrument(skip(self, tenant, security, bundle, options))]
+    pub async fn run_job(
+        &self,
+        tenant: &TenantContext,
+        security: &SecurityContext,
+        job_id: Uuid,
+        bundle: &ImportBundle,
+        options: &ImportOptions,
+    ) -> ImportResult<ImportReport> {
+        let job = self.find_job(tenant.id, job_id).await?;
+        let mut running: import_job::ActiveModel = job.into();
+        running.status = Set(ImportJobStatus::Running.as_str().to_string());
+        running.updated_at = Set(Utc::now().fixed_offset());
+        let job = running.update(&self.db).await?;
+
+        let mut run = Run {
+            tenant,
+            security,
+            options,
+            job_id: Some(job_id),
+            ledger: self.load_ledger(job_id).await?,
+            report: ImportReport {
+                job_id: Some(job_id),
+                ..ImportReport::default()
+            },
+        };
+        self.execute(&mut run, bundle).await?;
+
+        let status = if run.report.has_failures() {
+            ImportJobStatus::Failed
+        } else {
+            ImportJobStatus::Completed
+        };
+        let now = Utc::now().fixed_offset();
+        let mut finished: import_job::ActiveModel = job.into();
+        finished.status = Set(status.as_str().to_string());
+        finished.summary = Set(serde_json::to_value(&run.report.counts)?);
+        finished.updated_at = Set(now);
+        finished.finished_at = Set(Some(now));
+        finished.update(&self.db).await?;
+        Ok(run.report)
+    }
+
+    async fn execute(&self, run: &mut Run<'_>, bundle: &ImportBundle) -> ImportResult<()> {
+        for notice in &bundle.notices {
+            run.report.warn(notice.clone());
+        }
+        let author_emails = bundle
+            .authors
+            .iter()
+            .filter_map(|author| {
+                author
+                    .email
+                    .clone()
+                    .map(|email| (author.login.to_lowercase(), email))
+            })
+            .collect::<HashMap<_, _>>();
+
+        self.import_authors(run, bundle).await?;
+        let categories = self.import_categories(run, &bundle.categories).await?;
+        self.import_tags(run, bundle).await?;
+        let media_urls = self.import_media(run, bundle).await?;
+
+        for item in &bundle.items {
+            let author = run.options.mapped_author(
+                item.author.as_deref(),
+                item.author
+                    .as_deref()
+                    .and_then(|login| author_emails.get(&login.to_lowercase()))
+                    .map(String::as_str),
+            );
+            let target = self
+                .import_item(run, item, author, &categories, &media_urls)
+                .await?;
+            self.import_comments(run, item, target.as_ref().map(|(id, _)| *id))
+                .await?;
+            self.import_redirects(run, item, target.map(|(_, slug)| slug))
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn import_authors(&self, run: &mut Run<'_>, bundle: &ImportBundle) -> ImportResult<()> {
+        let profiles = ProfileService::new(self.db.clone());
+        for author in &bundle.authors {
+            let kind = ImportEntityKind::Author;
+            if let Some(done) = run.imported(kind, &author.login) {
+                run.already(kind, &done);
+                continue;
+            }
+            let Some(user_id) = run
+                .options
+                .mapped_author(Some(&author.login), author.email.as_deref())
+            else {
+                let fallback = if run.options.default_author_id.is_some() {
+                    "content falls back to the default author"
+                } else {
+                    "content falls back to the importing user"
+                };
+                run.skip(
+                    kind,
+                    &author.login,
+                    format!("not mapped to a platform user; {fallback}"),
+                );
+                continue;
+            };
+            if run.dry_run() {
+                run.entry(
+                    kind,
+                    &author.login,
+                    ImportAction::Planned,
+                    Some(user_id),
+                    None,
+                );
+                continue;
+            }
+            let result = profiles
+                .backfill_profile(
+                    run.tenant.id,
+                    user_id,
+                    author.email.as_deref().unwrap_or(&author.login),
+                    author.display_name.as_deref(),
+                    Some(run.options.locale.as_str()),
+                    ProfileVisibility::Public,
+                    Some(run.tenant.default_locale.as_str()),
+                )
+                .await
+                .map(|result| Settled {
+                    target_id: Some(user_id),
+                    target_ref: Some(result.profile.handle),
+                    message: Some(if result.created {
+                        "profile created".to_string()
+                    } else {
+                        "profile already existed".to_string()
+                    }),
+                })
+                .map_err(ImportError::from);
+            self.settle(run, kind, &author.login, result).await?;
+        }
+        Ok(())
+    }
+
+    async fn import_categories(
+        &self,
+        run: &mut Run<'_>,
+        categories: &[ImportCategory],
+    ) -> ImportResult<HashMap<String, Uuid>> {
+        let service = CategoryService::new(self.db.clone());
+        let mut ids = HashMap::new();
+        if categories.is_empty() {
+            return Ok(ids);
+        }
+
+        let mut page = 1;
+        loop {
+            let (items, total) = service
+                .list(
+                    run.tenant.id,
+                    run.security.clone(),
+                    ListCategoriesFilter {
+                        locale: Some(run.options.locale.clone()),
+                        page,
+                        per_page: LIST_PAGE_SIZE,
+                    },
+                )
+                .await?;
+            let fetched = items.len() as u64;
+            ids.extend(items.into_iter().map(|item| (item.slug, item.id)));
+            if fetched == 0 || page * LIST_PAGE_SIZE >= total {
+                break;
+            }
+            page += 1;
+        }
+
+        for category in parents_first(categories) {
+            let kind = ImportEntityKind::Category;
+            if let Some(done) = run.imported(kind, &category.slug) {
+                if let Some(target_id) = done.target_id {
+                    ids.insert(category.slug.clone(), target_id);
+                }
+                run.already(kind, &done);
+                continue;
+            }
+            if let Some(existing) = ids.get(&category.slug).copied() {
+                run.entry(
+                    kind,
+                    &category.slug,
+                    ImportAction::Skipped,
+                    Some(existing),
+                    Some("category already exists".to_string()),
+                );
+                continue;
+            }
+            if run.dry_run() {
+                run.plan(kind, &category.slug, None);
+                continue;
+            }
+            let parent_id = category
+                .parent_slug
+                .as_ref()
+                .and_then(|parent| ids.get(parent).copied());
+            let result = service
+                .create(
+                    run.tenant.id,
+                    run.security.clone(),
+                    CreateCategoryInput {
+                        locale: run.options.locale.clone(),
+                        name: category.name.clone(),
+                        slug: Some(category.slug.clone()),
+                        description: category.description.clone(),
+                        parent_id,
+                        position: None,
+                        settings: json!({}),
+                    },
+                )
+                .await
+                .map(|id| Settled {
+                    target_id: Some(id),
+                    ..Settled::default()
+                })
+                .map_err(ImportError::from);
+            if let Some(settled) = self.settle(run, kind, &category.slug, result).await? {
+                ids.extend(settled.target_id.map(|id| (category.slug.clone(), id)));
+            }
+        }
+        Ok(ids)
+    }
+
+    async fn import_tags(&self, run: &mut Run<'_>, bundle: &ImportBundle) -> ImportResult<()> {
+        let names = bundle.tag_names();
+        if names.is_empty() {
+            return Ok(());
+        }
+        let service = TagService::new(self.db.clone());
+        let mut existing = HashSet::new();
+        let mut page = 1;
+        loop {
+            let (items, total) = service
+                .list_tags(
+                    run.tenant.id,
+                    run.security.clone(),
+                    ListTagsFilter {
+                        locale: Some(run.options.locale.clone()),
+                        page,
+                        per_page: LIST_PAGE_SIZE,
+                    },
+                )
+                .await?;
+            let fetched = items.len() as u64;
+            existing.extend(items.into_iter().map(|item| item.name.to_lowercase()));
+            if fetched == 0 || page * LIST_PAGE_SIZE >= total {
+                break;
+            }
+            page += 1;
+        }
+
+        for name in names {
+            let kind = ImportEntityKind::Tag;
+            if let Some(done) = run.imported(kind, &name) {
+                run.already(kind, &done);
+            } else if existing.contains(&name.to_lowercase()) {
+                run.skip(kind, &name, "tag already exists");
+            } else if run.dry_run() {
+                run.plan(kind, &name, None);
+            } else {
+                let result = service
+                    .create_tag(
+                        run.tenant.id,
+                        run.security.clone(),
+                        CreateTagInput {
+                            locale: run.options.locale.clone(),
+                            name: name.clone(),
+                            slug: None,
+                        },
+                    )
+                    .await
+                    .map(|id| Settled {
+                        target_id: Some(id),
+                        ..Settled::default()
+                    })
+                    .map_err(ImportError::from);
+                self.settle(run, kind, &name, result).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns attachment reference → new public URL for imported media.
+    async fn import_media(
+        &self,
+        run: &mut Run<'_>,
+        bundle: &ImportBundle,
+    ) -> ImportResult<HashMap<String, String>> {
+        let mut urls = HashMap::new();
+        if bundle.attachments.is_empty() {
+            return Ok(urls);
+        }
+        if !run.options.import_media {
+            run.report
+                .warn("media import is disabled; bodies keep their original media URLs");
+            return Ok(urls);
+        }
+
+        for attachment in &bundle.attachments {
+            let kind = ImportEntityKind::Media;
+            if let Some(done) = run.imported(kind, &attachment.source_id) {
+                if let Some(url) = done.target_ref.clone() {
+                    urls.insert(attachment.reference.clone(), url.clone());
+                    urls.insert(attachment.source_id.clone(), url);
+                }
+                run.already(kind, &done);
+                continue;
+            }
+            let Some(media) = self.media.as_ref() else {
+                run.report
+                    .warn("no media service is configured; bodies keep their original media URLs");
+                run.skip(kind, &attachment.source_id, "media service unavailable");
+                continue;
+            };
+            if run.dry_run() {
+                run.plan(kind, &attachment.source_id, Some(attachment.url.clone()));
+                continue;
+            }
+            let result = match self.fetcher.fetch(&attachment.url).await {
+                Ok(file) => media
+                    .upload(UploadInput {
+                        tenant_id: run.tenant.id,
+                        uploaded_by: run.security.user_id,
+                        original_name: file.file_name,
+                        content_type: file.content_type,
+                        data: file.data,
+                    })
+                    .await
+                    .map(|item| Settled {
+                        target_id: Some(item.id),
+                        target_ref: Some(item.public_url),
+                        message: None,
+                    })
+                    .map_err(ImportError::from),
+                Err(error) => Err(error),
+            };
+            if let Some(url) = self
+                .settle(run, kind, &attachment.source_id, result)
+                .await?
+                .and_then(|settled| settled.target_ref)
+            {
+                urls.insert(attachment.reference.clone(), url.clone());
+                urls.insert(attachment.source_id.clone(), url);
+            }
+        }
+        Ok(urls)
+    }
+
+    /// Returns the created (or previously imported) entity id and slug.
+    async fn import_item(
+        &self,
+        run: &mut Run<'_>,
+        item: &ImportItem,
+        mapped_author: Option<Uuid>,
+        categories: &HashMap<String, Uuid>,
+        media_urls: &HashMap<String, String>,
+    ) -> ImportResult<Option<(Uuid, String)>> {
+        let kind = match item.kind {
+            ImportItemKind::Post => ImportEntityKind::Post,
+            ImportItemKind::Page => ImportEntityKind::Page,
+        };
+        if let Some(done) = run.imported(kind, &item.source_id) {
+            run.already(kind, &done);
+            return Ok(done.target_id.zip(done.target_ref));
+        }
+
+        let Some(author_id) = mapped_author
+            .or(run.options.default_author_id)
+            .or(run.security.user_id)
+        else {
+            let result = Err(ImportError::Validation(
+                "no author: map the source author or set a default author".to_string(),
+            ));
+            self.settle(run, kind, &item.source_id, result).await?;
+            return Ok(None);
+        };
+        if run.dry_run() {
+            run.entry(
+                kind,
+                &item.source_id,
+                ImportAction::Planned,
+                None,
+                Some(format!(
+                    "{} by {author_id}",
+                    item_slug(item).unwrap_or_default()
+                )),
+            );
+            return Ok(None);
+        }
+
+        let body = rewrite_media_urls(&item.body, media_urls);
+        let acting = run.acting_as(author_id);
+        let locale = run.options.locale.clone();
+        let result = match item.kind {
+            ImportItemKind::Post => {
+                let posts = PostService::new(self.db.clone(), self.event_bus.clone());
+                let created = posts
+                    .create_post(
+                        run.tenant.id,
+                        acting.clone(),
+                        CreatePostInput {
+                            locale: locale.clone(),
+                            title: item.title.clone(),
+                            body,
+                            body_format: "markdown".to_string(),
+                            content_json: None,
+                            excerpt: item.excerpt.clone(),
+                            slug: item_slug(item),
+                            publish: item.published,
+                            tags: item.tags.clone(),
+                            category_id: item
+                                .categories
+                                .iter()
+                                .find_map(|slug| categories.get(slug).copied()),
+                            featured_image_url: item.featured_image.as_ref().and_then(|image| {
+                                media_urls
+                                    .get(image)
+                                    .cloned()
+                                    .or_else(|| image.starts_with("http").then(|| image.clone()))
+                            }),
+                            seo_title: None,
+                            seo_description: None,
+                            channel_slugs: run.options.channel_slugs.clone(),
+                            metadata: Some(import_metadata(item)),
+                        },
+                    )
+                    .await;
+                match created {
+                    Ok(post_id) => posts
+                        .get_post(run.tenant.id, acting, post_id, &locale)
+                        .await
+                        .map(|post| (post_id, post.slug))
+                        .map_err(ImportError::from),
+                    Err(error) => Err(error.into()),
+                }
+            }
+            ImportItemKind::Page => PageService::new(self.db.clone(), self.event_bus.clone())
+                .create(
+                    run.tenant.id,
+                    acting,
+                    CreatePageInput {
+                        translations: vec![PageTranslationInput {
+                            locale: locale.clone(),
+                            title: item.title.clone(),
+                            slug: item_slug(item),
+                            meta_title: None,
+                            meta_description: item.excerpt.clone(),
+                        }],
+                        template: None,
+                        body: Some(PageBodyInput {
+                            locale,
+                            content: body,
+                            format: Some("markdown".to_string()),
+                            content_json: None,
+                        }),
+                        blocks: None,
+                        channel_slugs: run.options.channel_slugs.clone(),
+                        publish: item.published,
+                    },
+                )
+                .await
+                .map(|page| {
+                    let slug = page
+                        .translation
+                        .and_then(|translation| translation.slug)
+                        .unwrap_or_default();
+                    (page.id, slug)
+                })
+                .map_err(ImportError::from),
+        };
+        let settled = self
+            .settle(
+                run,
+                kind,
+                &item.source_id,
+                result.map(|(id, slug)| Settled {
+                    target_id: Some(id),
+                    target_ref: Some(slug),
+                    message: None,
+                }),
+            )
+            .await?;
+        Ok(settled.and_then(|settled| settled.target_id.zip(settled.target_ref)))
+    }
+
+    async fn import_comments(
+        &self,
+        run: &mut Run<'_>,
+        item: &ImportItem,
+        post_id: Option<Uuid>,
+    ) -> ImportResult<()> {
+        let service = CommentService::new(self.db.clone(), self.event_bus.clone());
+        let mut created = HashMap::new();
+        for comment in parents_first_comments(&item.comments) {
+            let kind = ImportEntityKind::Comment;
+            let source_id = format!("{}/{}", item.source_id, comment.source_id);
+            if let Some(done) = run.imported(kind, &source_id) {
+                created.extend(done.target_id.map(|id| (comment.source_id.clone(), id)));
+                run.already(kind, &done);
+                continue;
+            }
+            if item.kind == ImportItemKind::Page {
+                run.skip(kind, &source_id, "pages do not support comments");
+                continue;
+            }
+            let mapped = run
+                .options
+                .mapped_author(None, comment.author_email.as_deref());
+            let Some(author_id) = mapped.or(run.options.guest_author_id) else {
+                run.skip(
+                    kind,
+                    &source_id,
+                    "guest comment and no guest author is configured",
+                );
+                continue;
+            };
+            let message = mapped.is_none().then(|| {
+                format!(
+                    "attributed to the guest author; originally by {}",
+                    comment.author_name.as_deref().unwrap_or("anonymous")
+                )
+            });
+            if run.dry_run() {
+                run.plan(kind, &source_id, message);
+                continue;
+            }
+            let Some(post_id) = post_id else {
+                run.skip(kind, &source_id, "post was not imported");
+                continue;
+            };
+
+            let parent_comment_id = comment
+                .parent_source_id
+                .as_ref()
+                .and_then(|parent| created.get(parent).copied());
+            let result = service
+                .create_comment(
+                    run.tenant.id,
+                    run.acting_as(author_id),
+                    post_id,
+                    CreateCommentInput {
+                        locale: run.options.locale.clone(),
+                        content: comment.body.clone(),
+                        content_format: "markdown".to_string(),
+                        content_json: None,
+                        parent_comment_id,
+                    },
+                )
+                .await;
+            let result = match result {
+                Ok(response) if comment.approved => service
+                    .moderate_comment(
+                        run.tenant.id,
+                        response.id,
+                        run.security.clone(),
+                        ModerateCommentInput {
+                            status: ModerateCommentStatus::Approved,
+                            locale: Some(run.options.locale.clone()),
+                        },
+                        None,
+                    )
+                    .await
+                    .map(|response| response.id),
+                Ok(response) => Ok(response.id),
+                Err(error) => Err(error),
+            }
+            .map(|id| Settled {
+                target_id: Some(id),
+                target_ref: None,
+                message,
+            })
+            .map_err(ImportError::from);
+            if let Some(id) = self
+                .settle(run, kind, &source_id, result)
+                .await?
+                .and_then(|settled| settled.target_id)
+            {
+                created.insert(comment.source_id.clone(), id);
+            }
+        }
+        Ok(())
+    }
+
+    async fn import_redirects(
+        &self,
+        run: &mut Run<'_>,
+        item: &ImportItem,
+        slug: Option<String>,
+    ) -> ImportResult<()> {
+        if !run.options.create_redirects {
+            return Ok(());
+        }
+        let prefix = match item.kind {
+            ImportItemKind::Post => BLOG_ROUTE_PREFIX,
+            ImportItemKind::Page => PAGES_ROUTE_PREFIX,
+        };
+        let mut seen = HashSet::new();
+        let paths = item
+            .legacy_urls
+            .iter()
+            .filter_map(|url| legacy_path(url))
+            .filter(|path| seen.insert(path.clone()))
+            .collect::<Vec<_>>();
+
+        for path in paths {
+            let kind = ImportEntityKind::Redirect;
+            if let Some(done) = run.imported(kind, &path) {
+                run.already(kind, &done);
+                continue;
+            }
+            let Some(seo) = self.seo.as_ref() else {
+                run.report
+                    .warn("no SEO service is configured; legacy URLs are not redirected");
+                run.skip(kind, &path, "SEO service unavailable");
+                continue;
+            };
+            if run.dry_run() {
+                let target = slug.clone().or_else(|| item_slug(item)).unwrap_or_default();
+                run.plan(kind, &path, Some(format!("{prefix}{target}")));
+                continue;
+            }
+            let Some(slug) = slug.as_deref().filter(|slug| !slug.is_empty()) else {
+                run.skip(kind, &path, "entry was not imported");
+                continue;
+            };
+            let target_url = format!("{prefix}{slug}");
+            if path == target_url {
+                continue;
+            }
+            let result = seo
+                .upsert_redirect(
+                    run.tenant,
+                    SeoRedirectInput {
+                        id: None,
+                        match_type: SeoRedirectMatchType::Exact,
+                        source_pattern: path.clone(),
+                        target_url: target_url.clone(),
+                        status_code: run.options.redirect_status_code,
+                        expires_at: None,
+                        is_active: true,
+                    },
+                )
+                .await
+                .map(|record| Settled {
+                    target_id: Some(record.id),
+                    target_ref: Some(target_url),
+                    message: None,
+                })
+                .map_err(ImportError::from);
+            self.settle(run, kind, &path, result).await?;
+        }
+        Ok(())
+    }
+
+    /// Report an attempted entry and record it in the job ledger. Per-entry
+    /// failures are reported, not propagated, so one bad entry does not stop
+    /// the job.
+    async fn settle(
+        &self,
+        run: &mut Run<'_>,
+        kind: ImportEntityKind,
+        source_id: &str,
+        result: ImportResult<Settled>,
+    ) -> ImportResult<Option<Settled>> {
+        let (status, action, settled, message) = match result {
+            Ok(settled) => {
+                let message = settled.message.clone();
+                ("imported", ImportAction::Imported, Some(settled), message)
+            }
+            Err(error) => {
+                tracing::warn!(kind = kind.as_str(), source_id, %error, "import entry failed");
+                (
+                    "failed",
+                    ImportAction::Failed,
+                    None,
+                    Some(error.to_string()),
+                )
+            }
+        };
+        let target_id = settled.as_ref().and_then(|settled| settled.target_id);
+        if let Some(job_id) = run.job_id {
+            let target_ref = settled
+                .as_ref()
+                .and_then(|settled| settled.target_ref.clone());
+            let entry = self
+                .record(
+                    run.tenant.id,
+                    job_id,
+                    kind,
+                    source_id,
+                    status,
+                    target_id,
+                    target_ref,
+                    message.clone(),
+                    run.ledger.get(&(kind, source_id.to_string())),
+                )
+                .await?;
+            run.ledger.insert((kind, source_id.to_string()), entry);
+        }
+        run.entry(kind, source_id, action, target_id, message);
+        Ok(settled)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn record(
+        &self,
+        tenant_id: Uuid,
+        job_id: Uuid,
+        kind: ImportEntityKind,
+        source_id: &str,
+        status: &str,
+        target_id: Option<Uuid>,
+        target_ref: Option<String>,
+        message: Option<String>,
+        existing: Option<&import_job_item::Model>,
+    ) -> ImportResult<import_job_item::Model> {
+        let now = Utc::now().fixed_offset();
+        let model = match existing {
+            Some(existing) => {
+                let mut active: import_job_item::ActiveModel = existing.clone().into();
+                active.status = Set(status.to_string());
+                active.target_id = Set(target_id);
+                active.target_ref = Set(target_ref);
+                active.message = Set(message);
+                active.updated_at = Set(now);
+                active.update(&self.db).await?
+            }
+            None => {
+                import_job_item::ActiveModel {
+                    id: Set(Uuid::new_v4()),
+                    job_id: Set(job_id),
+                    tenant_id: Set(tenant_id),
+                    kind: Set(kind.as_str().to_string()),
+                    source_id: Set(source_id.to_string()),
+                    status: Set(status.to_string()),
+                    target_id: Set(target_id),
+                    target_ref: Set(target_ref),
+                    message: Set(message),
+                    updated_at: Set(now),
+                }
+                .insert(&self.db)
+                .await?
+            }
+        };
+        Ok(model)
+    }
+
+    async fn find_job(&self, tenant_id: Uuid, job_id: Uuid) -> ImportResult<import_job::Model> {
+        import_job::Entity::find_by_id(job_id)
+            .filter(import_job::Column::TenantId.eq(tenant_id))
+            .one(&self.db)
+            .await?
+            .ok_or(ImportError::JobNotFound(job_id))
+    }
+
+    async fn load_ledger(
+        &self,
+        job_id: Uuid,
+    ) -> ImportResult<HashMap<LedgerKey, import_job_item::Model>> {
+        let entries = import_job_item::Entity::find()
+            .filter(import_job_item::Column::JobId.eq(job_id))
+            .all(&self.db)
+            .await?;
+        Ok(entries
+            .into_iter()
+            .filter_map(|entry| {
+                let kind = serde_json::from_value::<ImportEntityKind>(json!(entry.kind)).ok()?;
+                Some(((kind, entry.source_id.clone()), entry))
+            })
+            .collect())
+    }
+}
+
+fn map_job(model: import_job::Model) -> ImportResult<ImportJobRecord> {
+    Ok(ImportJobRecord {
+        id: model.id,
+        tenant_id: model.tenant_id,
+        source_kind: ImportSourceKind::parse(&model.source_kind).ok_or_else(|| {
+            ImportError::Validation(format!("unknown source kind `{}`", model.source_kind))
+        })?,
+        source_name: model.source_name,
+        status: ImportJobStatus::parse(&model.status),
+        summary: serde_json::from_value(model.summary).unwrap_or_default(),
+        created_by: model.created_by,
+        created_at: model.created_at.with_timezone(&Utc),
+        updated_at: model.updated_at.with_timezone(&Utc),
+        finished_at: model.finished_at.map(|value| value.with_timezone(&Utc)),
+    })
+}
+
+fn item_slug(item: &ImportItem) -> Option<String> {
+    item.slug
+        .clone()
+        .filter(|slug| !slug.trim().is_empty())
+        .or_else(|| Some(slugify(&item.title)).filter(|slug| !slug.is_empty()))
+}
+
+fn import_metadata(item: &ImportItem) -> serde_json::Value {
+    json!({
+        "import": {
+            "source_id": item.source_id,
+            "published_at": item.published_at,
+            "legacy_urls": item.legacy_urls,
+        }
+    })
+}
+
+fn rewrite_media_urls(body: &str, urls: &HashMap<String, String>) -> String {
+    // Longest references first so a URL is never partially replaced by a prefix of it.
+    let mut references = urls.keys().collect::<Vec<_>>();
+    references.sort_by_key(|reference| std::cmp::Reverse(reference.len()));
+    references
+        .into_iter()
+        .filter(|reference| !reference.is_empty())
+        .fold(body.to_string(), |body, reference| {
+            body.replace(reference.as_str(), &urls[reference])
+        })
+}
+
+fn parents_first(categories: &[ImportCategory]) -> Vec<&ImportCategory> {
+    let by_slug = categories
+        .iter()
+        .map(|category| (category.slug.as_str(), category))
+        .collect::<HashMap<_, _>>();
+    let depth = |category: &ImportCategory| {
+        let mut depth = 0;
+        let mut parent = category.parent_slug.as_deref();
+        while let Some(slug) = parent.filter(|_| depth < MAX_CATEGORY_DEPTH) {
+            depth += 1;
+            parent = by_slug
+                .get(slug)
+                .and_then(|parent| parent.parent_slug.as_deref());
+        }
+        depth
+    };
+    let mut ordered = categories.iter().collect::<Vec<_>>();
+    ordered.sort_by_key(|category| depth(category));
+    ordered
+}
+
+fn parents_first_comments(comments: &[ImportComment]) -> Vec<&ImportComment> {
+    let ids = comments
+        .iter()
+        .map(|comment| comment.source_id.as_str())
+        .collect::<HashSet<_>>();
+    let mut placed = HashSet::new();
+    let mut ordered = Vec::with_capacity(comments.len());
+    while ordered.len() < comments.len() {
+        let before = ordered.len();
+        for comment in comments {
+            if placed.contains(comment.source_id.as_str()) {
+                continue;
+            }
+            let ready = match comment.parent_source_id.as_deref() {
+                Some(parent) => !ids.contains(parent) || placed.contains(parent),
+                None => true,
+            };
+            if ready {
+                placed.insert(comment.source_id.as_str());
+                ordered.push(comment);
+            }
+        }
+        if ordered.len() == before {
+            // Parent cycle: keep the remaining comments in source order.
+            ordered.extend(
+                comments
+                    .iter()
+                    .filter(|comment| !placed.contains(comment.source_id.as_str())),
+            );
+            break;
+        }
+    }
+    ordered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rewrite_prefers_longest_reference() {
+        let urls = HashMap::from([
+            (
+                "https://a.test/x.png".to_string(),
+                "/media/1.png".to_string(),
+            ),
+            (
+                "https://a.test/x.png?v=2".to_string(),
+                "/media/2.png".to_string(),
+            ),
+        ]);
+        assert_eq!(
+            rewrite_media_urls("<img src=\"https://a.test/x.png?v=2\">", &urls),
+            "<img src=\"/media/2.png\">"
+        );
+    }
+}
@@ -0,0 +1,5 @@
+mod fetch;
+mod import;
+
+pub use fetch::{DefaultMediaFetcher, FetchedMedia, MediaFetcher};
+pub use import::ImportService;
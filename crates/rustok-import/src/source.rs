@@ -0,0 +1,226 @@
+//! Source-neutral representation of imported content.
+//!
+//! Both the WXR and the Markdown readers produce an [`ImportBundle`]; the
+//! import service only ever works with this model.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportSourceKind {
+    Wxr,
+    Markdown,
+}
+
+impl ImportSourceKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Wxr => "wxr",
+            Self::Markdown => "markdown",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "wxr" | "wordpress" => Some(Self::Wxr),
+            "markdown" | "md" => Some(Self::Markdown),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportBundle {
+    pub authors: Vec<ImportAuthor>,
+    pub categories: Vec<ImportCategory>,
+    /// Tag names declared by the source in addition to the ones used by items.
+    pub tags: Vec<String>,
+    pub attachments: Vec<ImportAttachment>,
+    pub items: Vec<ImportItem>,
+    /// Source entries the reader ignored, with the reason.
+    pub notices: Vec<String>,
+}
+
+impl ImportBundle {
+    /// Every distinct tag name, declared or used, in first-seen order.
+    pub fn tag_names(&self) -> Vec<String> {
+        let mut seen = std::collections::HashSet::new();
+        self.tags
+            .iter()
+            .chain(self.items.iter().flat_map(|item| item.tags.iter()))
+            .map(|name| name.trim())
+            .filter(|name| !name.is_empty() && seen.insert(name.to_lowercase()))
+            .map(str::to_string)
+            .collect()
+    }
+
+    pub(crate) fn push_attachment(&mut self, attachment: ImportAttachment) {
+        if !self
+            .attachments
+            .iter()
+            .any(|existing| existing.reference == attachment.reference)
+        {
+            self.attachments.push(attachment);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportAuthor {
+    pub login: String,
+    pub email: Option<String>,
+    pub display_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportCategory {
+    pub slug: String,
+    pub name: String,
+    pub parent_slug: Option<String>,
+    pub description: Option<String>,
+}
+
+/// A media file referenced by the source.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportAttachment {
+    pub source_id: String,
+    /// Where to download the file from (`http(s)://` or `file://`).
+    pub url: String,
+    /// The string that refers to the file inside item bodies; replaced with
+    /// the new media URL on import.
+    pub reference: String,
+    pub title: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportItemKind {
+    Post,
+    Page,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportItem {
+    pub source_id: String,
+    pub kind: ImportItemKind,
+    pub title: String,
+    pub slug: Option<String>,
+    pub body: String,
+    pub excerpt: Option<String>,
+    pub published: bool,
+    pub published_at: Option<DateTime<Utc>>,
+    /// Author login (WXR) or front-matter `author`.
+    pub author: Option<String>,
+    /// Category slugs; blog posts keep the first one.
+    pub categories: Vec<String>,
+    pub tags: Vec<String>,
+    /// Old URLs or paths that should redirect to the imported entry.
+    pub legacy_urls: Vec<String>,
+    /// Attachment `source_id` or `reference` of the featured image.
+    pub featured_image: Option<String>,
+    pub comments: Vec<ImportComment>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportComment {
+    pub source_id: String,
+    pub parent_source_id: Option<String>,
+    pub author_name: Option<String>,
+    pub author_email: Option<String>,
+    pub body: String,
+    pub approved: bool,
+}
+
+/// Lowercase ASCII slug with `-` separators.
+pub fn slugify(value: &str) -> String {
+    let mut slug = String::with_capacity(value.len());
+    for ch in value.trim().chars() {
+        if ch.is_ascii_alphanumeric() {
+            slug.push(ch.to_ascii_lowercase());
+        } else if !slug.ends_with('-') && !slug.is_empty() {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Image references found in HTML `src` attributes and Markdown image links.
+pub fn image_references(body: &str) -> Vec<String> {
+    let mut references = Vec::new();
+    for marker in ["src=\"", "src='"] {
+        let quote = marker.chars().last().unwrap_or('"');
+        let mut rest = body;
+        while let Some(start) = rest.find(marker) {
+            rest = &rest[start + marker.len()..];
+            if let Some(end) = rest.find(quote) {
+                references.push(rest[..end].to_string());
+                rest = &rest[end..];
+            }
+        }
+    }
+    let mut rest = body;
+    while let Some(start) = rest.find("![") {
+        rest = &rest[start + 2..];
+        let Some(link_start) = rest.find("](") else {
+            break;
+        };
+        rest = &rest[link_start + 2..];
+        if let Some(end) = rest.find(')') {
+            let target = rest[..end].split_whitespace().next().unwrap_or_default();
+            references.push(target.trim_matches(['<', '>']).to_string());
+            rest = &rest[end..];
+        }
+    }
+    references.retain(|reference| !reference.trim().is_empty());
+    references.dedup();
+    references
+}
+
+/// Path and query of a legacy URL, suitable as an exact redirect source.
+pub fn legacy_path(value: &str) -> Option<String> {
+    let value = value.trim();
+    if value.starts_with('/') {
+        return Some(value.to_string()).filter(|path| path != "/");
+    }
+    let parsed = url::Url::parse(value).ok()?;
+    let mut path = parsed.path().to_string();
+    if let Some(query) = parsed.query() {
+        path.push('?');
+        path.push_str(query);
+    }
+    Some(path).filter(|path| path != "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugify_collapses_separators() {
+        assert_eq!(slugify("  Hello, World!  "), "hello-world");
+        assert_eq!(slugify("Rust & Tokio"), "rust-tokio");
+    }
+
+    #[test]
+    fn image_references_cover_html_and_markdown() {
+        let body = r#"<img src="https://a.test/x.png"> ![Alt](./y.jpg "title") <img src='z.gif'>"#;
+        assert_eq!(
+            image_references(body),
+            vec!["https://a.test/x.png", "z.gif", "./y.jpg"]
+        );
+    }
+
+    #[test]
+    fn legacy_path_keeps_query() {
+        assert_eq!(
+            legacy_path("https://old.test/?p=12").as_deref(),
+            Some("/?p=12")
+        );
+        assert_eq!(
+            legacy_path("https://old.test/2020/01/hello/").as_deref(),
+            Some("/2020/01/hello/")
+        );
+        assert_eq!(legacy_path("https://old.test/"), None);
+    }
+}
@@ -0,0 +1,354 @@
+//! WordPress eXtended RSS (WXR) reader.
+//!
+//! Elements are matched by namespace family rather than exact URI so exports
+//! from WXR 1.0, 1.1 and 1.2 are all accepted.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use roxmltree::{Document, Node};
+
+use crate::error::{ImportError, ImportResult};
+use crate::source::{
+    image_references, ImportAttachment, ImportAuthor, ImportBundle, ImportCategory, ImportComment,
+    ImportItem, ImportItemKind,
+};
+
+const CONTENT_NS: &str = "http://purl.org/rss/1.0/modules/content/";
+const DC_NS: &str = "http://purl.org/dc/elements/1.1/";
+const WP_NS_PREFIX: &str = "http://wordpress.org/export/";
+
+#[derive(Clone, Copy)]
+enum Ns {
+    None,
+    Wp,
+    Excerpt,
+    Content,
+    Dc,
+}
+
+fn is(node: Node<'_, '_>, ns: Ns, name: &str) -> bool {
+    if !node.is_element() || node.tag_name().name() != name {
+        return false;
+    }
+    let uri = node.tag_name().namespace();
+    match ns {
+        Ns::None => uri.is_none(),
+        Ns::Wp => {
+            uri.is_some_and(|uri| uri.starts_with(WP_NS_PREFIX) && !uri.ends_with("/excerpt/"))
+        }
+        Ns::Excerpt => {
+            uri.is_some_and(|uri| uri.starts_with(WP_NS_PREFIX) && uri.ends_with("/excerpt/"))
+        }
+        Ns::Content => uri == Some(CONTENT_NS),
+        Ns::Dc => uri == Some(DC_NS),
+    }
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, ns: Ns, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|child| is(*child, ns, name))
+}
+
+fn text(node: Node<'_, '_>, ns: Ns, name: &str) -> Option<String> {
+    child(node, ns, name)
+        .map(|child| {
+            child
+                .children()
+                .filter_map(|part| part.text())
+                .collect::<String>()
+        })
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Parse a WXR export into an [`ImportBundle`].
+pub fn parse_wxr(xml: &str) -> ImportResult<ImportBundle> {
+    let document = Document::parse(xml).map_err(|error| ImportError::Parse(error.to_string()))?;
+    let channel = document
+        .root_element()
+        .children()
+        .find(|node| is(*node, Ns::None, "channel"))
+        .ok_or_else(|| ImportError::Parse("WXR document has no <channel>".to_string()))?;
+
+    let mut bundle = ImportBundle::default();
+    let mut attachment_urls = HashMap::new();
+    let mut items = Vec::new();
+
+    for node in channel.children().filter(|node| node.is_element()) {
+        if is(node, Ns::Wp, "author") {
+            if let Some(login) = text(node, Ns::Wp, "author_login") {
+                bundle.authors.push(ImportAuthor {
+                    login,
+                    email: text(node, Ns::Wp, "author_email"),
+                    display_name: text(node, Ns::Wp, "author_display_name"),
+                });
+            }
+        } else if is(node, Ns::Wp, "category") {
+            if let Some(slug) = text(node, Ns::Wp, "category_nicename") {
+                bundle.categories.push(ImportCategory {
+                    name: text(node, Ns::Wp, "cat_name").unwrap_or_else(|| slug.clone()),
+                    slug,
+                    parent_slug: text(node, Ns::Wp, "category_parent"),
+                    description: text(node, Ns::Wp, "category_description"),
+                });
+            }
+        } else if is(node, Ns::Wp, "tag") {
+            if let Some(name) =
+                text(node, Ns::Wp, "tag_name").or_else(|| text(node, Ns::Wp, "tag_slug"))
+            {
+                bundle.tags.push(name);
+            }
+        } else if is(node, Ns::None, "item") {
+            let post_type = text(node, Ns::Wp, "post_type").unwrap_or_else(|| "post".to_string());
+            let post_id = text(node, Ns::Wp, "post_id").unwrap_or_default();
+            if post_type == "attachment" {
+                if let Some(url) = text(node, Ns::Wp, "attachment_url") {
+                    if !is_remote(&url) {
+                        bundle.notices.push(format!(
+                            "attachment {post_id}: skipped non-http(s) url `{url}`"
+                        ));
+                        continue;
+                    }
+                    attachment_urls.insert(post_id.clone(), url.clone());
+                    bundle.push_attachment(ImportAttachment {
+                        source_id: post_id,
+                        reference: url.clone(),
+                        url,
+                        title: text(node, Ns::None, "title"),
+                    });
+                }
+            } else {
+                items.push((node, post_type, post_id));
+            }
+        }
+    }
+
+    for (node, post_type, post_id) in items {
+        let kind = match post_type.as_str() {
+            "post" => ImportItemKind::Post,
+            "page" => ImportItemKind::Page,
+            other => {
+                bundle
+                    .notices
+                    .push(format!("item {post_id}: unsupported post type `{other}`"));
+                continue;
+            }
+        };
+        let status = text(node, Ns::Wp, "status").unwrap_or_else(|| "draft".to_string());
+        if matches!(status.as_str(), "trash" | "auto-draft" | "inherit") {
+            bundle
+                .notices
+                .push(format!("item {post_id}: skipped `{status}` entry"));
+            continue;
+        }
+
+        let body = text(node, Ns::Content, "encoded").unwrap_or_default();
+        for reference in image_references(&body) {
+            if is_remote(&reference) {
+                bundle.push_attachment(ImportAttachment {
+                    source_id: reference.clone(),
+                    url: reference.clone(),
+                    reference,
+                    title: None,
+                });
+            }
+        }
+
+        let mut categories = Vec::new();
+        let mut tags = Vec::new();
+        for term in node
+            .children()
+            .filter(|child| is(*child, Ns::None, "category"))
+        {
+            let name = term.text().unwrap_or_default().trim().to_string();
+            match term.attribute("domain") {
+                Some("category") => categories.push(
+                    term.attribute("nicename")
+                        .map(str::to_string)
+                        .unwrap_or(name),
+                ),
+                Some("post_tag") if !name.is_empty() => tags.push(name),
+                _ => {}
+            }
+        }
+
+        let mut legacy_urls = Vec::new();
+        legacy_urls.extend(text(node, Ns::None, "link"));
+        legacy_urls.extend(text(node, Ns::None, "guid"));
+        legacy_urls.dedup();
+
+        let featured_image = node
+            .children()
+            .filter(|child| is(*child, Ns::Wp, "postmeta"))
+            .find(|meta| text(*meta, Ns::Wp, "meta_key").as_deref() == Some("_thumbnail_id"))
+            .and_then(|meta| text(meta, Ns::Wp, "meta_value"))
+            .and_then(|thumbnail_id| attachment_urls.get(&thumbnail_id).cloned());
+
+        let comments = node
+            .children()
+            .filter(|child| is(*child, Ns::Wp, "comment"))
+            .filter_map(|comment| parse_comment(comment, &post_id, &mut bundle.notices))
+            .collect();
+
+        bundle.items.push(ImportItem {
+            source_id: post_id,
+            kind,
+            title: text(node, Ns::None, "title").unwrap_or_default(),
+            slug: text(node, Ns::Wp, "post_name"),
+            body,
+            excerpt: text(node, Ns::Excerpt, "encoded"),
+            published: status == "publish",
+            published_at: text(node, Ns::Wp, "post_date_gmt").and_then(|value| parse_gmt(&value)),
+            author: text(node, Ns::Dc, "creator"),
+            categories,
+            tags,
+            legacy_urls,
+            featured_image,
+            comments,
+        });
+    }
+
+    Ok(bundle)
+}
+
+fn parse_comment(
+    node: Node<'_, '_>,
+    post_id: &str,
+    notices: &mut Vec<String>,
+) -> Option<ImportComment> {
+    let comment_id = text(node, Ns::Wp, "comment_id").unwrap_or_default();
+    let comment_type = text(node, Ns::Wp, "comment_type").unwrap_or_default();
+    if !matches!(comment_type.as_str(), "" | "comment") {
+        notices.push(format!(
+            "item {post_id}: skipped {comment_type} {comment_id}"
+        ));
+        return None;
+    }
+    let approved = text(node, Ns::Wp, "comment_approved").unwrap_or_default();
+    if matches!(approved.as_str(), "spam" | "trash") {
+        notices.push(format!(
+            "item {post_id}: skipped {approved} comment {comment_id}"
+        ));
+        return None;
+    }
+    Some(ImportComment {
+        source_id: comment_id,
+        parent_source_id: text(node, Ns::Wp, "comment_parent").filter(|parent| parent != "0"),
+        author_name: text(node, Ns::Wp, "comment_author"),
+        author_email: text(node, Ns::Wp, "comment_author_email"),
+        body: text(node, Ns::Wp, "comment_content").unwrap_or_default(),
+        approved: approved == "1",
+    })
+}
+
+/// Only `http(s)` URLs are fetched: a crafted export must not be able to point
+/// the importer at `file://` paths on the server.
+fn is_remote(url: &str) -> bool {
+    url::Url::parse(url).is_ok_and(|url| matches!(url.scheme(), "http" | "https"))
+}
+
+fn parse_gmt(value: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+        .ok()
+        .filter(|value| value.and_utc().timestamp() > 0)
+        .map(|value| value.and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPORT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0"
+    xmlns:excerpt="http://wordpress.org/export/1.2/excerpt/"
+    xmlns:content="http://purl.org/rss/1.0/modules/content/"
+    xmlns:dc="http://purl.org/dc/elements/1.1/"
+    xmlns:wp="http://wordpress.org/export/1.2/">
+<channel>
+  <wp:author><wp:author_login><![CDATA[alice]]></wp:author_login><wp:author_email>alice@old.test</wp:author_email><wp:author_display_name><![CDATA[Alice]]></wp:author_display_name></wp:author>
+  <wp:category><wp:category_nicename>news</wp:category_nicename><wp:category_parent></wp:category_parent><wp:cat_name><![CDATA[News]]></wp:cat_name></wp:category>
+  <wp:tag><wp:tag_slug>rust</wp:tag_slug><wp:tag_name><![CDATA[Rust]]></wp:tag_name></wp:tag>
+  <item>
+    <title>logo</title>
+    <wp:post_id>7</wp:post_id>
+    <wp:post_type>attachment</wp:post_type>
+    <wp:attachment_url>https://old.test/wp-content/uploads/logo.png</wp:attachment_url>
+  </item>
+  <item>
+    <title>Hello</title>
+    <link>https://old.test/2020/01/hello/</link>
+    <guid isPermaLink="false">https://old.test/?p=12</guid>
+    <dc:creator><![CDATA[alice]]></dc:creator>
+    <content:encoded><![CDATA[<p>Hi <img src="https://old.test/wp-content/uploads/logo.png"></p>]]></content:encoded>
+    <excerpt:encoded><![CDATA[Short]]></excerpt:encoded>
+    <wp:post_id>12</wp:post_id>
+    <wp:post_date_gmt>2020-01-02 03:04:05</wp:post_date_gmt>
+    <wp:post_name>hello</wp:post_name>
+    <wp:status>publish</wp:status>
+    <wp:post_type>post</wp:post_type>
+    <category domain="category" nicename="news"><![CDATA[News]]></category>
+    <category domain="post_tag" nicename="rust"><![CDATA[Rust]]></category>
+    <wp:postmeta><wp:meta_key>_thumbnail_id</wp:meta_key><wp:meta_value>7</wp:meta_value></wp:postmeta>
+    <wp:comment><wp:comment_id>3</wp:comment_id><wp:comment_author>Guest</wp:comment_author><wp:comment_content>Nice</wp:comment_content><wp:comment_approved>1</wp:comment_approved><wp:comment_parent>0</wp:comment_parent></wp:comment>
+    <wp:comment><wp:comment_id>4</wp:comment_id><wp:comment_content>Buy</wp:comment_content><wp:comment_approved>spam</wp:comment_approved></wp:comment>
+  </item>
+  <item>
+    <title>Menu</title>
+    <wp:post_id>20</wp:post_id>
+    <wp:post_type>nav_menu_item</wp:post_type>
+  </item>
+</channel>
+</rss>"#;
+
+    #[test]
+    fn parses_authors_terms_items_and_comments() {
+        let bundle = parse_wxr(EXPORT).unwrap();
+        assert_eq!(bundle.authors[0].login, "alice");
+        assert_eq!(bundle.categories[0].slug, "news");
+        assert_eq!(bundle.tags, vec!["Rust"]);
+        assert_eq!(bundle.attachments.len(), 1, "body images reuse attachments");
+        assert_eq!(bundle.items.len(), 1);
+
+        let post = &bundle.items[0];
+        assert_eq!(post.kind, ImportItemKind::Post);
+        assert_eq!(post.slug.as_deref(), Some("hello"));
+        assert!(post.published);
+        assert_eq!(post.author.as_deref(), Some("alice"));
+        assert_eq!(post.categories, vec!["news"]);
+        assert_eq!(post.tags, vec!["Rust"]);
+        assert_eq!(post.legacy_urls.len(), 2);
+        assert_eq!(
+            post.featured_image.as_deref(),
+            Some("https://old.test/wp-content/uploads/logo.png")
+        );
+        assert_eq!(post.comments.len(), 1);
+        assert!(post.comments[0].approved);
+        assert_eq!(bundle.notices.len(), 2);
+    }
+
+    #[test]
+    fn skips_attachments_with_local_urls() {
+        let export = EXPORT.replace(
+            "https://old.test/wp-content/uploads/logo.png</wp:attachment_url>",
+            "file:///etc/passwd</wp:attachment_url>",
+        );
+        let bundle = parse_wxr(&export).unwrap();
+        assert!(bundle
+            .attachments
+            .iter()
+            .all(|attachment| attachment.url.starts_with("https://")));
+        assert_eq!(bundle.items[0].featured_image, None);
+        assert!(bundle
+            .notices
+            .iter()
+            .any(|notice| notice.contains("file:///etc/passwd")));
+    }
+
+    #[test]
+    fn rejects_documents_without_channel() {
+        assert!(matches!(
+            parse_wxr("<rss></rss>"),
+            Err(ImportError::Parse(_))
+        ));
+    }
+}
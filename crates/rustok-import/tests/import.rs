@@ -0,0 +1,326 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use rustok_api::TenantContext;
+use rustok_blog::{BlogModule, ListTagsFilter, PostService, TagService};
+use rustok_comments::CommentsModule;
+use rustok_core::{MemoryTransport, MigrationSource, SecurityContext, UserRole};
+use rustok_import::{
+    parse_wxr, FetchedMedia, ImportAction, ImportEntityKind, ImportError, ImportJobStatus,
+    ImportModule, ImportOptions, ImportResult, ImportService, ImportSourceKind, MediaFetcher,
+};
+use rustok_media::{MediaModule, MediaService};
+use rustok_outbox::TransactionalEventBus;
+use rustok_pages::PagesModule;
+use rustok_profiles::ProfilesModule;
+use rustok_seo::{SeoModule, SeoService, SeoTargetRegistry};
+use rustok_storage::local::LocalStorage;
+use rustok_storage::StorageService;
+use rustok_taxonomy::TaxonomyModule;
+use sea_orm::{
+    ConnectOptions, ConnectionTrait, Database, DatabaseConnection, DbBackend, Statement,
+};
+use sea_orm_migration::SchemaManager;
+use uuid::Uuid;
+
+const EXPORT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0"
+    xmlns:excerpt="http://wordpress.org/export/1.2/excerpt/"
+    xmlns:content="http://purl.org/rss/1.0/modules/content/"
+    xmlns:dc="http://purl.org/dc/elements/1.1/"
+    xmlns:wp="http://wordpress.org/export/1.2/">
+<channel>
+    <title>Old Blog</title>
+    <link>https://old.example.com</link>
+    <wp:author>
+        <wp:author_login><![CDATA[alice]]></wp:author_login>
+        <wp:author_email><![CDATA[alice@example.com]]></wp:author_email>
+        <wp:author_display_name><![CDATA[Alice]]></wp:author_display_name>
+    </wp:author>
+    <wp:category>
+        <wp:category_nicename><![CDATA[news]]></wp:category_nicename>
+        <wp:category_parent><![CDATA[]]></wp:category_parent>
+        <wp:cat_name><![CDATA[News]]></wp:cat_name>
+    </wp:category>
+    <wp:tag>
+        <wp:tag_slug><![CDATA[rust]]></wp:tag_slug>
+        <wp:tag_name><![CDATA[Rust]]></wp:tag_name>
+    </wp:tag>
+    <item>
+        <title>Hello World</title>
+        <link>https://old.example.com/2020/01/hello-world/</link>
+        <guid isPermaLink="false">https://old.example.com/?p=1</guid>
+        <dc:creator><![CDATA[alice]]></dc:creator>
+        <content:encoded><![CDATA[<p>Welcome</p><img src="https://old.example.com/wp-content/uploads/cat.png">]]></content:encoded>
+        <excerpt:encoded><![CDATA[Intro]]></excerpt:encoded>
+        <wp:post_id>1</wp:post_id>
+        <wp:post_date_gmt><![CDATA[2020-01-02 10:00:00]]></wp:post_date_gmt>
+        <wp:post_name><![CDATA[hello-world]]></wp:post_name>
+        <wp:status><![CDATA[publish]]></wp:status>
+        <wp:post_type><![CDATA[post]]></wp:post_type>
+        <category domain="category" nicename="news"><![CDATA[News]]></category>
+        <category domain="post_tag" nicename="rust"><![CDATA[Rust]]></category>
+        <wp:comment>
+            <wp:comment_id>7</wp:comment_id>
+            <wp:comment_author><![CDATA[Visitor]]></wp:comment_author>
+            <wp:comment_author_email><![CDATA[visitor@example.com]]></wp:comment_author_email>
+            <wp:comment_content><![CDATA[Nice post]]></wp:comment_content>
+            <wp:comment_approved><![CDATA[1]]></wp:comment_approved>
+            <wp:comment_type><![CDATA[comment]]></wp:comment_type>
+            <wp:comment_parent>0</wp:comment_parent>
+        </wp:comment>
+    </item>
+    <item>
+        <title>About</title>
+        <link>https://old.example.com/about/</link>
+        <dc:creator><![CDATA[bob]]></dc:creator>
+        <content:encoded><![CDATA[About us]]></content:encoded>
+        <wp:post_id>2</wp:post_id>
+        <wp:post_name><![CDATA[about]]></wp:post_name>
+        <wp:status><![CDATA[publish]]></wp:status>
+        <wp:post_type><![CDATA[page]]></wp:post_type>
+    </item>
+</channel>
+</rss>"#;
+
+struct StubFetcher;
+
+#[async_trait]
+impl MediaFetcher for StubFetcher {
+    async fn fetch(&self, url: &str) -> ImportResult<FetchedMedia> {
+        if !url.ends_with("cat.png") {
+            return Err(ImportError::Fetch(format!("unexpected url {url}")));
+        }
+        Ok(FetchedMedia {
+            file_name: "cat.png".to_string(),
+            content_type: "image/png".to_string(),
+            data: Bytes::from_static(b"\x89PNG\r\n\x1a\nimage"),
+        })
+    }
+}
+
+#[tokio::test]
+async fn wxr_import_dry_run_then_resumable_run() {
+    let db = setup_db().await;
+    let transport = MemoryTransport::new();
+    let _receiver = transport.subscribe();
+    let event_bus = TransactionalEventBus::new(Arc::new(transport));
+    let storage_dir = std::env::temp_dir().join(format!("rustok-import-{}", Uuid::new_v4()));
+    let media = MediaService::new(
+        db.clone(),
+        StorageService::new(LocalStorage::new(&storage_dir, "/media")),
+    );
+    let seo = SeoService::new(
+        db.clone(),
+        event_bus.clone(),
+        Arc::new(SeoTargetRegistry::default()),
+    );
+    let service = ImportService::new(db.clone(), event_bus.clone())
+        .with_media(media)
+        .with_media_fetcher(StubFetcher)
+        .with_seo(seo);
+
+    let tenant = tenant_context(Uuid::new_v4());
+    let admin = SecurityContext::new(UserRole::Admin, Some(Uuid::new_v4()));
+    let alice = Uuid::new_v4();
+    let guest = Uuid::new_v4();
+    let mut options = ImportOptions {
+        guest_author_id: Some(guest),
+        ..ImportOptions::default()
+    };
+    options.author_map.insert("alice".to_string(), alice);
+
+    let bundle = parse_wxr(EXPORT).expect("export should parse");
+    let job = service
+        .create_job(tenant.id, &admin, ImportSourceKind::Wxr, "old-blog.xml")
+        .await
+        .expect("admin can create import jobs");
+
+    let plan = service
+        .dry_run(&tenant, &admin, Some(job.id), &bundle, &options)
+        .await
+        .expect("dry run");
+    assert!(plan.dry_run);
+    assert_eq!(plan.counts_for(ImportEntityKind::Post).planned, 1);
+    assert_eq!(plan.counts_for(ImportEntityKind::Page).planned, 1);
+    assert_eq!(plan.counts_for(ImportEntityKind::Media).planned, 1);
+    assert_eq!(plan.counts_for(ImportEntityKind::Comment).planned, 1);
+    assert!(plan.counts_for(ImportEntityKind::Redirect).planned >= 2);
+    assert_eq!(plan.counts_for(ImportEntityKind::Author).planned, 1);
+    let tags = TagService::new(db.clone())
+        .list_tags(
+            tenant.id,
+            admin.clone(),
+            ListTagsFilter {
+                locale: Some("en".to_string()),
+                page: 1,
+                per_page: 10,
+            },
+        )
+        .await
+        .expect("list tags");
+    assert_eq!(tags.1, 0, "dry run must not write");
+
+    let report = service
+        .run_job(&tenant, &admin, job.id, &bundle, &options)
+        .await
+        .expect("import run");
+    assert!(!report.has_failures(), "{:?}", report.entries);
+    assert_eq!(report.counts_for(ImportEntityKind::Post).imported, 1);
+    assert_eq!(report.counts_for(ImportEntityKind::Page).imported, 1);
+    assert_eq!(report.counts_for(ImportEntityKind::Category).imported, 1);
+    assert_eq!(report.counts_for(ImportEntityKind::Tag).imported, 1);
+    assert_eq!(report.counts_for(ImportEntityKind::Media).imported, 1);
+    assert_eq!(report.counts_for(ImportEntityKind::Comment).imported, 1);
+    assert!(report.counts_for(ImportEntityKind::Redirect).imported >= 2);
+
+    let post_id = report
+        .entries
+        .iter()
+        .find(|entry| entry.kind == ImportEntityKind::Post)
+        .and_then(|entry| entry.target_id)
+        .expect("post id");
+    let post = PostService::new(db.clone(), event_bus.clone())
+        .get_post(tenant.id, admin.clone(), post_id, "en")
+        .await
+        .expect("imported post");
+    assert_eq!(post.slug, "hello-world");
+    assert_eq!(post.author_id, alice);
+    assert!(post.body.contains("/media/"));
+    assert!(!post.body.contains("wp-content/uploads"));
+
+    let redirect = seo_redirect_target(&db, "/2020/01/hello-world/").await;
+    assert_eq!(redirect.as_deref(), Some("/modules/blog?slug=hello-world"));
+
+    let record = service.get_job(tenant.id, job.id).await.expect("job");
+    assert_eq!(record.status, ImportJobStatus::Completed);
+    assert!(record.finished_at.is_some());
+
+    let resumed = service
+        .run_job(&tenant, &admin, job.id, &bundle, &options)
+        .await
+        .expect("second run");
+    assert!(resumed
+        .entries
+        .iter()
+        .filter(
+            |entry| entry.kind != ImportEntityKind::Author || entry.action != ImportAction::Skipped
+        )
+        .all(|entry| entry.action == ImportAction::AlreadyImported));
+    assert_eq!(
+        resumed.counts_for(ImportEntityKind::Post).already_imported,
+        1
+    );
+
+    let _ = std::fs::remove_dir_all(storage_dir);
+}
+
+#[tokio::test]
+async fn creating_jobs_requires_manage_permission() {
+    let db = setup_db().await;
+    let event_bus = TransactionalEventBus::new(Arc::new(MemoryTransport::new()));
+    let service = ImportService::new(db, event_bus);
+    let customer = SecurityContext::new(UserRole::Customer, Some(Uuid::new_v4()));
+
+    let error = service
+        .create_job(
+            Uuid::new_v4(),
+            &customer,
+            ImportSourceKind::Markdown,
+            "docs",
+        )
+        .await
+        .expect_err("customers cannot import");
+    assert!(matches!(error, ImportError::Forbidden(_)));
+}
+
+async fn seo_redirect_target(db: &DatabaseConnection, source: &str) -> Option<String> {
+    db.query_one(Statement::from_sql_and_values(
+        DbBackend::Sqlite,
+        "SELECT target_url FROM seo_redirects WHERE source_pattern = ?",
+        [source.into()],
+    ))
+    .await
+    .expect("query redirects")
+    .map(|row| row.try_get::<String>("", "target_url").expect("target_url"))
+}
+
+fn tenant_context(id: Uuid) -> TenantContext {
+    TenantContext {
+        id,
+        name: "Import Tenant".to_string(),
+        slug: "import".to_string(),
+        domain: None,
+        settings: serde_json::json!({}),
+        default_locale: "en".to_string(),
+        is_active: true,
+    }
+}
+
+async fn setup_db() -> DatabaseConnection {
+    let db_url = format!(
+        "sqlite:file:import_{}?mode=memory&cache=shared",
+        Uuid::new_v4()
+    );
+    let mut opts = ConnectOptions::new(db_url);
+    opts.max_connections(5)
+        .min_connections(1)
+        .sqlx_logging(false);
+    let db = Database::connect(opts)
+        .await
+        .expect("failed to connect import test sqlite database");
+
+    for sql in [
+        "CREATE TABLE tenant_modules (
+            id TEXT PRIMARY KEY,
+            tenant_id TEXT NOT NULL,
+            module_slug TEXT NOT NULL,
+            enabled INTEGER NOT NULL,
+            settings TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        )",
+        "CREATE TABLE media (
+            id TEXT PRIMARY KEY,
+            tenant_id TEXT NOT NULL,
+            uploaded_by TEXT NULL,
+            filename TEXT NOT NULL,
+            original_name TEXT NOT NULL,
+            mime_type TEXT NOT NULL,
+            size INTEGER NOT NULL,
+            storage_path TEXT NOT NULL,
+            storage_driver TEXT NOT NULL,
+            width INTEGER NULL,
+            height INTEGER NULL,
+            metadata TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        )",
+    ] {
+        db.execute(Statement::from_string(DbBackend::Sqlite, sql.to_string()))
+            .await
+            .expect("create host table");
+    }
+
+    let manager = SchemaManager::new(&db);
+    let modules: [&dyn MigrationSource; 8] = [
+        &TaxonomyModule,
+        &ProfilesModule,
+        &CommentsModule,
+        &BlogModule,
+        &PagesModule,
+        &SeoModule,
+        &MediaModule,
+        &ImportModule,
+    ];
+    for migration in rustok_content::migrations::migrations()
+        .into_iter()
+        .chain(modules.iter().flat_map(|module| module.migrations()))
+    {
+        migration
+            .up(&manager)
+            .await
+            .expect("migration should apply");
+    }
+    db
+}
@@ -31,6 +31,7 @@ async fn index_module_registers_registry_owned_event_listeners() {
     let ctx = ModuleEventListenerContext {
         db,
         extensions: &extensions,
+        event_transport: None,
     };
 
     let handlers = registry.build_event_listeners(&ctx);
@@ -1,17 +1,31 @@
+use std::sync::Arc;
+
 use axum::{
-    extract::{Multipart, Path, Query, State},
-    http::StatusCode,
+    extract::{DefaultBodyLimit, Multipart, Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
-use loco_rs::{app::AppContext, controller::Routes, Error, Result};
+use bytes::Bytes;
+use loco_rs::{
+    app::AppContext,
+    controller::{ErrorDetail, Routes},
+    Error, Result,
+};
 use rustok_api::{AuthContext, TenantContext};
-use rustok_storage::StorageService;
+use rustok_core::ModuleRuntimeExtensions;
+use rustok_storage::{PresignedUpload, StorageError, StorageService, UploadTarget, UploadedPart};
 use rustok_telemetry::metrics;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::{
-    dto::{MediaItem, MediaTranslationItem, UpsertTranslationInput},
+    dto::{
+        CreateMediaFolderInput, CreateUploadInput, MediaDerivativeItem, MediaFolderItem, MediaItem,
+        MediaOrphanReport, MediaSearchFilter, MediaTranslationItem, MediaUsageItem,
+        UploadSessionItem, UpsertTranslationInput, MULTIPART_THRESHOLD,
+    },
+    usage::media_usage_registry_from_extensions,
     MediaError, MediaService, UploadInput,
 };
 
@@ -21,6 +35,14 @@ fn storage_from_ctx(ctx: &AppContext) -> Result<StorageService> {
         .ok_or(Error::InternalServerError)
 }
 
+fn service_from_ctx(ctx: &AppContext) -> Result<MediaService> {
+    let usage = ctx
+        .shared_store
+        .get::<Arc<ModuleRuntimeExtensions>>()
+        .and_then(|extensions| media_usage_registry_from_extensions(&extensions));
+    Ok(MediaService::new(ctx.db.clone(), storage_from_ctx(ctx)?).with_usage_registry(usage))
+}
+
 fn media_error(error: MediaError) -> Error {
     match error {
         MediaError::NotFound(_) => Error::NotFound,
@@ -31,6 +53,28 @@ fn media_error(error: MediaError) -> Error {
         MediaError::FileTooLarge { size, max } => {
             Error::BadRequest(format!("File too large: {size} bytes (max {max} bytes)"))
         }
+        MediaError::ContentTypeMismatch { .. } | MediaError::UnrecognizedContent(_) => {
+            Error::BadRequest(error.to_string())
+        }
+        MediaError::Image(message) => Error::Message(message),
+        MediaError::InvalidPreset(message) => Error::BadRequest(message),
+        MediaError::PresetNotFound(_) => Error::NotFound,
+        MediaError::UploadNotFound(_) | MediaError::FolderNotFound(_) => Error::NotFound,
+        MediaError::InvalidFolder(message) | MediaError::InvalidTag(message) => {
+            Error::BadRequest(message)
+        }
+        MediaError::InUse { .. } => Error::CustomError(
+            StatusCode::CONFLICT,
+            ErrorDetail::new("media_in_use", &error.to_string()),
+        ),
+        MediaError::UsageProvider { .. } => Error::Message(error.to_string()),
+        MediaError::InvalidUpload(message) => Error::BadRequest(message),
+        MediaError::Storage(StorageError::InvalidSignature(message)) => {
+            Error::Unauthorized(message)
+        }
+        MediaError::Storage(StorageError::Unsupported(operation)) => {
+            Error::BadRequest(format!("Not supported by the storage backend: {operation}"))
+        }
         MediaError::Storage(error) => Error::Message(error.to_string()),
         MediaError::Db(error) => Error::Message(error.to_string()),
     }
@@ -42,6 +86,13 @@ pub struct ListParams {
     pub limit: u64,
     #[serde(default)]
     pub offset: u64,
+    /// Filename, title or alt text search.
+    pub q: Option<String>,
+    pub folder_id: Option<Uuid>,
+    #[serde(default)]
+    pub unfiled: bool,
+    pub tag_id: Option<Uuid>,
+    pub mime_prefix: Option<String>,
 }
 
 fn default_limit() -> u64 {
@@ -61,8 +112,7 @@ pub async fn upload(
     auth: AuthContext,
     mut multipart: Multipart,
 ) -> Result<(StatusCode, Json<MediaItem>)> {
-    let storage = storage_from_ctx(&ctx)?;
-    let service = MediaService::new(ctx.db.clone(), storage);
+    let service = service_from_ctx(&ctx)?;
 
     while let Some(field) = multipart
         .next_field()
@@ -105,18 +155,24 @@ pub async fn upload(
     ))
 }
 
-/// List media assets for the current tenant.
+/// List media assets for the current tenant, optionally filtered.
 pub async fn list(
     State(ctx): State<AppContext>,
     tenant: TenantContext,
     _auth: AuthContext,
     Query(params): Query<ListParams>,
 ) -> Result<Json<MediaListResponse>> {
-    let storage = storage_from_ctx(&ctx)?;
-    let service = MediaService::new(ctx.db.clone(), storage);
+    let service = service_from_ctx(&ctx)?;
     let limit = params.limit.clamp(1, 100);
+    let filter = MediaSearchFilter {
+        query: params.q,
+        folder_id: params.folder_id,
+        unfiled: params.unfiled,
+        tag_id: params.tag_id,
+        mime_prefix: params.mime_prefix,
+    };
     let (items, total) = service
-        .list(tenant.id, limit, params.offset)
+        .search(tenant.id, filter, limit, params.offset)
         .await
         .map_err(media_error)?;
 
@@ -130,26 +186,246 @@ pub async fn get_media(
     _auth: AuthContext,
     Path(id): Path<Uuid>,
 ) -> Result<Json<MediaItem>> {
-    let storage = storage_from_ctx(&ctx)?;
-    let service = MediaService::new(ctx.db.clone(), storage);
+    let service = service_from_ctx(&ctx)?;
     let item = service.get(tenant.id, id).await.map_err(media_error)?;
     Ok(Json(item))
 }
 
-/// Delete a media asset.
+#[derive(Deserialize)]
+pub struct DeleteParams {
+    /// Delete even when the asset is still used.
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// Delete a media asset. Assets that are still used are refused with
+/// `409 Conflict` unless `force=true`, which returns the usages left behind.
 pub async fn delete_media(
     State(ctx): State<AppContext>,
     tenant: TenantContext,
     _auth: AuthContext,
     Path(id): Path<Uuid>,
-) -> Result<StatusCode> {
-    let storage = storage_from_ctx(&ctx)?;
-    let service = MediaService::new(ctx.db.clone(), storage);
-    service.delete(tenant.id, id).await.map_err(media_error)?;
+    Query(params): Query<DeleteParams>,
+) -> Result<Response> {
+    let service = service_from_ctx(&ctx)?;
+    let response = if params.force {
+        let usages = service
+            .force_delete(tenant.id, id)
+            .await
+            .map_err(media_error)?;
+        Json(usages).into_response()
+    } else {
+        service.delete(tenant.id, id).await.map_err(media_error)?;
+        StatusCode::NO_CONTENT.into_response()
+    };
     metrics::record_media_delete(&tenant.id.to_string());
+    Ok(response)
+}
+
+/// Where a media asset is used.
+pub async fn get_usages(
+    State(ctx): State<AppContext>,
+    tenant: TenantContext,
+    _auth: AuthContext,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<MediaUsageItem>>> {
+    let service = service_from_ctx(&ctx)?;
+    let usages = service
+        .where_used(tenant.id, id)
+        .await
+        .map_err(media_error)?;
+    Ok(Json(usages))
+}
+
+/// Refresh the where-used index and report unused media and broken
+/// references.
+pub async fn orphan_report(
+    State(ctx): State<AppContext>,
+    tenant: TenantContext,
+    _auth: AuthContext,
+) -> Result<Json<MediaOrphanReport>> {
+    let service = service_from_ctx(&ctx)?;
+    let report = service
+        .orphan_report(tenant.id)
+        .await
+        .map_err(media_error)?;
+    Ok(Json(report))
+}
+
+#[derive(Deserialize)]
+pub struct TagsParams {
+    pub locale: String,
+}
+
+#[derive(Deserialize)]
+pub struct SetTagsBody {
+    pub locale: String,
+    pub tags: Vec<String>,
+}
+
+/// Tags of a media asset in a locale.
+pub async fn get_tags(
+    State(ctx): State<AppContext>,
+    tenant: TenantContext,
+    _auth: AuthContext,
+    Path(id): Path<Uuid>,
+    Query(params): Query<TagsParams>,
+) -> Result<Json<Vec<String>>> {
+    let service = service_from_ctx(&ctx)?;
+    let tags = service
+        .tags(tenant.id, id, &params.locale)
+        .await
+        .map_err(media_error)?;
+    Ok(Json(tags))
+}
+
+/// Replace the tags of a media asset.
+pub async fn set_tags(
+    State(ctx): State<AppContext>,
+    tenant: TenantContext,
+    _auth: AuthContext,
+    Path(id): Path<Uuid>,
+    Json(body): Json<SetTagsBody>,
+) -> Result<Json<Vec<String>>> {
+    let service = service_from_ctx(&ctx)?;
+    let tags = service
+        .set_tags(tenant.id, id, &body.locale, &body.tags)
+        .await
+        .map_err(media_error)?;
+    Ok(Json(tags))
+}
+
+#[derive(Deserialize)]
+pub struct MoveBody {
+    /// Target folder; `null` moves to the library root.
+    pub folder_id: Option<Uuid>,
+}
+
+/// Move a media asset into a folder.
+pub async fn move_media(
+    State(ctx): State<AppContext>,
+    tenant: TenantContext,
+    _auth: AuthContext,
+    Path(id): Path<Uuid>,
+    Json(body): Json<MoveBody>,
+) -> Result<Json<MediaItem>> {
+    let service = service_from_ctx(&ctx)?;
+    let item = service
+        .move_media(tenant.id, id, body.folder_id)
+        .await
+        .map_err(media_error)?;
+    Ok(Json(item))
+}
+
+#[derive(Deserialize)]
+pub struct FolderListParams {
+    /// Parent folder; root folders when omitted.
+    pub parent_id: Option<Uuid>,
+}
+
+/// List subfolders of a folder, or root folders.
+pub async fn list_folders(
+    State(ctx): State<AppContext>,
+    tenant: TenantContext,
+    _auth: AuthContext,
+    Query(params): Query<FolderListParams>,
+) -> Result<Json<Vec<MediaFolderItem>>> {
+    let service = service_from_ctx(&ctx)?;
+    let folders = service
+        .list_folders(tenant.id, params.parent_id)
+        .await
+        .map_err(media_error)?;
+    Ok(Json(folders))
+}
+
+/// Create a media folder.
+pub async fn create_folder(
+    State(ctx): State<AppContext>,
+    tenant: TenantContext,
+    _auth: AuthContext,
+    Json(body): Json<CreateMediaFolderInput>,
+) -> Result<(StatusCode, Json<MediaFolderItem>)> {
+    let service = service_from_ctx(&ctx)?;
+    let folder = service
+        .create_folder(tenant.id, body)
+        .await
+        .map_err(media_error)?;
+    Ok((StatusCode::CREATED, Json(folder)))
+}
+
+#[derive(Deserialize)]
+pub struct RenameFolderBody {
+    pub name: String,
+}
+
+/// Rename a media folder.
+pub async fn rename_folder(
+    State(ctx): State<AppContext>,
+    tenant: TenantContext,
+    _auth: AuthContext,
+    Path(id): Path<Uuid>,
+    Json(body): Json<RenameFolderBody>,
+) -> Result<Json<MediaFolderItem>> {
+    let service = service_from_ctx(&ctx)?;
+    let folder = service
+        .rename_folder(tenant.id, id, &body.name)
+        .await
+        .map_err(media_error)?;
+    Ok(Json(folder))
+}
+
+#[derive(Deserialize)]
+pub struct MoveFolderBody {
+    /// New parent folder; `null` moves to the library root.
+    pub parent_id: Option<Uuid>,
+}
+
+/// Move a media folder under another folder.
+pub async fn move_folder(
+    State(ctx): State<AppContext>,
+    tenant: TenantContext,
+    _auth: AuthContext,
+    Path(id): Path<Uuid>,
+    Json(body): Json<MoveFolderBody>,
+) -> Result<Json<MediaFolderItem>> {
+    let service = service_from_ctx(&ctx)?;
+    let folder = service
+        .move_folder(tenant.id, id, body.parent_id)
+        .await
+        .map_err(media_error)?;
+    Ok(Json(folder))
+}
+
+/// Delete an empty media folder.
+pub async fn delete_folder(
+    State(ctx): State<AppContext>,
+    tenant: TenantContext,
+    _auth: AuthContext,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode> {
+    let service = service_from_ctx(&ctx)?;
+    service
+        .delete_folder(tenant.id, id)
+        .await
+        .map_err(media_error)?;
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Get the derivative for an image preset, rendering it on demand.
+pub async fn get_derivative(
+    State(ctx): State<AppContext>,
+    tenant: TenantContext,
+    _auth: AuthContext,
+    Path((id, preset)): Path<(Uuid, String)>,
+) -> Result<Json<MediaDerivativeItem>> {
+    let service = service_from_ctx(&ctx)?;
+    let derivative = service
+        .derivative(tenant.id, id, &preset)
+        .await
+        .map_err(media_error)?;
+    Ok(Json(derivative))
+}
+
 /// Upsert localized media metadata for a locale.
 pub async fn upsert_translation(
     State(ctx): State<AppContext>,
@@ -158,8 +434,7 @@ pub async fn upsert_translation(
     Path((id, locale)): Path<(Uuid, String)>,
     Json(body): Json<UpsertTranslationInput>,
 ) -> Result<Json<MediaTranslationItem>> {
-    let storage = storage_from_ctx(&ctx)?;
-    let service = MediaService::new(ctx.db.clone(), storage);
+    let service = service_from_ctx(&ctx)?;
     let translation = service
         .upsert_translation(tenant.id, id, UpsertTranslationInput { locale, ..body })
         .await
@@ -168,12 +443,207 @@ pub async fn upsert_translation(
     Ok(Json(translation))
 }
 
+#[derive(Deserialize)]
+pub struct CreateUploadBody {
+    pub original_name: String,
+    pub content_type: String,
+    pub size: u64,
+}
+
+/// Start a direct upload session.
+pub async fn create_upload(
+    State(ctx): State<AppContext>,
+    tenant: TenantContext,
+    auth: AuthContext,
+    Json(body): Json<CreateUploadBody>,
+) -> Result<(StatusCode, Json<UploadSessionItem>)> {
+    let service = service_from_ctx(&ctx)?;
+    let session = service
+        .create_upload(CreateUploadInput {
+            tenant_id: tenant.id,
+            uploaded_by: Some(auth.user_id),
+            original_name: body.original_name,
+            content_type: body.content_type,
+            size: body.size,
+        })
+        .await
+        .map_err(media_error)?;
+    Ok((StatusCode::CREATED, Json(session)))
+}
+
+/// Get an upload session with the parts received so far.
+pub async fn get_upload(
+    State(ctx): State<AppContext>,
+    tenant: TenantContext,
+    _auth: AuthContext,
+    Path(id): Path<Uuid>,
+) -> Result<Json<UploadSessionItem>> {
+    let service = service_from_ctx(&ctx)?;
+    let session = service
+        .upload_session(tenant.id, id)
+        .await
+        .map_err(media_error)?;
+    Ok(Json(session))
+}
+
+/// Abort an upload session and discard its content.
+pub async fn abort_upload(
+    State(ctx): State<AppContext>,
+    tenant: TenantContext,
+    _auth: AuthContext,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode> {
+    let service = service_from_ctx(&ctx)?;
+    service
+        .abort_upload(tenant.id, id)
+        .await
+        .map_err(media_error)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Upload the whole content of a single-request session through the API.
+pub async fn upload_content(
+    State(ctx): State<AppContext>,
+    tenant: TenantContext,
+    _auth: AuthContext,
+    Path(id): Path<Uuid>,
+    body: Bytes,
+) -> Result<StatusCode> {
+    let service = service_from_ctx(&ctx)?;
+    service
+        .upload_content(tenant.id, id, body)
+        .await
+        .map_err(media_error)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Upload one part of a multipart session through the API.
+pub async fn upload_part(
+    State(ctx): State<AppContext>,
+    tenant: TenantContext,
+    _auth: AuthContext,
+    Path((id, part_number)): Path<(Uuid, u32)>,
+    body: Bytes,
+) -> Result<Json<UploadedPart>> {
+    let service = service_from_ctx(&ctx)?;
+    let part = service
+        .upload_part(tenant.id, id, part_number, body)
+        .await
+        .map_err(media_error)?;
+    Ok(Json(part))
+}
+
+/// Presign a request for one part, or `null` when parts must be proxied.
+pub async fn presign_part(
+    State(ctx): State<AppContext>,
+    tenant: TenantContext,
+    _auth: AuthContext,
+    Path((id, part_number)): Path<(Uuid, u32)>,
+) -> Result<Json<Option<PresignedUpload>>> {
+    let service = service_from_ctx(&ctx)?;
+    let presigned = service
+        .presign_upload_part(tenant.id, id, part_number)
+        .await
+        .map_err(media_error)?;
+    Ok(Json(presigned))
+}
+
+/// Finalize an upload session into a media asset.
+pub async fn complete_upload(
+    State(ctx): State<AppContext>,
+    tenant: TenantContext,
+    _auth: AuthContext,
+    Path(id): Path<Uuid>,
+) -> Result<(StatusCode, Json<MediaItem>)> {
+    let service = service_from_ctx(&ctx)?;
+    let item = service
+        .complete_upload(tenant.id, id)
+        .await
+        .map_err(media_error)?;
+    metrics::record_media_upload(&tenant.id.to_string(), &item.mime_type, item.size as u64);
+    Ok((StatusCode::CREATED, Json(item)))
+}
+
+#[derive(Deserialize)]
+pub struct DirectUploadParams {
+    pub expires: i64,
+    pub signature: String,
+    pub upload_id: Option<String>,
+    pub part_number: Option<u32>,
+}
+
+/// Receive a presigned upload for backends without native presigning. The
+/// signature in the query is the credential, so no session is required.
+pub async fn direct_upload(
+    State(ctx): State<AppContext>,
+    Path(path): Path<String>,
+    Query(params): Query<DirectUploadParams>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Response> {
+    let service = service_from_ctx(&ctx)?;
+    let target = match (params.upload_id, params.part_number) {
+        (Some(upload_id), Some(part_number)) => UploadTarget::part(path, upload_id, part_number),
+        (None, None) => UploadTarget::object(path),
+        _ => {
+            return Err(Error::BadRequest(
+                "`upload_id` and `part_number` must be given together".to_string(),
+            ))
+        }
+    };
+    let part = service
+        .receive_direct_upload(
+            target,
+            headers
+                .get(header::CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or_default(),
+            params.expires,
+            &params.signature,
+            body,
+        )
+        .await
+        .map_err(media_error)?;
+
+    Ok(match part {
+        Some(part) => (
+            StatusCode::OK,
+            [(header::ETAG, format!("\"{}\"", part.etag))],
+        )
+            .into_response(),
+        None => StatusCode::OK.into_response(),
+    })
+}
+
 pub fn routes() -> Routes {
-    use axum::routing::{get, put};
+    use axum::routing::{get, post, put};
+
+    let body_limit = || DefaultBodyLimit::max(MULTIPART_THRESHOLD as usize);
 
     Routes::new()
         .prefix("api/media")
         .add("/", get(list).post(upload))
+        .add("/orphans", get(orphan_report))
+        .add("/folders", get(list_folders).post(create_folder))
+        .add("/folders/{id}", put(rename_folder).delete(delete_folder))
+        .add("/folders/{id}/parent", put(move_folder))
+        .add("/uploads", post(create_upload))
+        .add("/uploads/{id}", get(get_upload).delete(abort_upload))
+        .add(
+            "/uploads/{id}/content",
+            put(upload_content).layer(body_limit()),
+        )
+        .add(
+            "/uploads/{id}/parts/{part_number}",
+            put(upload_part).layer(body_limit()),
+        )
+        .add("/uploads/{id}/parts/{part_number}/url", post(presign_part))
+        .add("/uploads/{id}/complete", post(complete_upload))
+        .add("/direct/{*path}", put(direct_upload).layer(body_limit()))
         .add("/{id}", get(get_media).delete(delete_media))
+        .add("/{id}/folder", put(move_media))
+        .add("/{id}/tags", get(get_tags).put(set_tags))
+        .add("/{id}/usages", get(get_usages))
+        .add("/{id}/derivatives/{preset}", get(get_derivative))
         .add("/{id}/translations/{locale}", put(upsert_translation))
 }
@@ -0,0 +1,619 @@
+//! Image derivative presets and rendering.
+//!
+//! A preset describes one rendition of an uploaded image: target box, fit
+//! mode, output format and quality. Rendering is pure (bytes in, bytes out);
+//! `MediaService` stores the result next to the original and tracks it in
+//! `media_derivatives`.
+
+use std::io::Cursor;
+
+use image::codecs::avif::AvifEncoder;
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::PngEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageReader, Rgb, RgbImage};
+use serde::{Deserialize, Serialize};
+
+use crate::dto::MediaDerivativeItem;
+use crate::error::{MediaError, Result};
+
+/// Largest edge a preset may request.
+pub const MAX_PRESET_DIMENSION: u32 = 8192;
+
+/// Quality used for lossy formats when a preset does not set one.
+pub const DEFAULT_QUALITY: u8 = 80;
+
+/// AVIF encoder speed (1 = slowest/best, 10 = fastest).
+const AVIF_SPEED: u8 = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageFit {
+    /// Scale down to fit inside the box, keeping the aspect ratio.
+    Contain,
+    /// Fill the box exactly, cropping around the focal point.
+    Cover,
+}
+
+impl ImageFit {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Contain => "contain",
+            Self::Cover => "cover",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "contain" => Some(Self::Contain),
+            "cover" => Some(Self::Cover),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageFormat {
+    Webp,
+    Avif,
+    Jpeg,
+    Png,
+}
+
+impl ImageFormat {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Webp => "webp",
+            Self::Avif => "avif",
+            Self::Jpeg => "jpeg",
+            Self::Png => "png",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "webp" => Some(Self::Webp),
+            "avif" => Some(Self::Avif),
+            "jpeg" | "jpg" => Some(Self::Jpeg),
+            "png" => Some(Self::Png),
+            _ => None,
+        }
+    }
+
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            Self::Webp => "image/webp",
+            Self::Avif => "image/avif",
+            Self::Jpeg => "image/jpeg",
+            Self::Png => "image/png",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Jpeg => "jpg",
+            other => other.as_str(),
+        }
+    }
+
+    /// Whether the encoder takes a quality setting. WebP is always encoded
+    /// losslessly and PNG has no quality setting.
+    pub fn is_lossy(self) -> bool {
+        matches!(self, Self::Jpeg | Self::Avif)
+    }
+}
+
+/// Point of interest kept inside cropped derivatives, as fractions of the
+/// image size (`0.0..=1.0`, origin top-left).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FocalPoint {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl FocalPoint {
+    pub const CENTER: Self = Self { x: 0.5, y: 0.5 };
+
+    pub fn new(x: f32, y: f32) -> Result<Self> {
+        if !(0.0..=1.0).contains(&x) || !(0.0..=1.0).contains(&y) {
+            return Err(MediaError::InvalidPreset(
+                "focal point coordinates must be between 0 and 1".to_string(),
+            ));
+        }
+        Ok(Self { x, y })
+    }
+
+    /// Read the focal point stored in media metadata.
+    pub fn from_metadata(metadata: &serde_json::Value) -> Option<Self> {
+        serde_json::from_value(metadata.get("focal_point")?.clone()).ok()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImagePreset {
+    pub name: String,
+    pub width: u32,
+    /// Required for [`ImageFit::Cover`]; `None` bounds only the width.
+    pub height: Option<u32>,
+    pub fit: ImageFit,
+    pub format: ImageFormat,
+    /// Encoder quality, `1..=100`, for lossy formats (JPEG and AVIF);
+    /// defaults to [`DEFAULT_QUALITY`]. Must be unset for WebP and PNG.
+    pub quality: Option<u8>,
+    /// Render on upload rather than on first request.
+    pub eager: bool,
+}
+
+impl ImagePreset {
+    pub fn validate(&self) -> Result<()> {
+        let valid_name = !self.name.is_empty()
+            && self.name.len() <= 64
+            && self
+                .name
+                .bytes()
+                .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-' || b == b'_');
+        if !valid_name {
+            return Err(MediaError::InvalidPreset(format!(
+                "`{}`: name must be 1-64 characters of a-z, 0-9, `-` or `_`",
+                self.name
+            )));
+        }
+        let in_range = |value: u32| (1..=MAX_PRESET_DIMENSION).contains(&value);
+        if !in_range(self.width) || !self.height.is_none_or(in_range) {
+            return Err(MediaError::InvalidPreset(format!(
+                "`{}`: dimensions must be between 1 and {MAX_PRESET_DIMENSION}",
+                self.name
+            )));
+        }
+        if self.fit == ImageFit::Cover && self.height.is_none() {
+            return Err(MediaError::InvalidPreset(format!(
+                "`{}`: cover fit requires a height",
+                self.name
+            )));
+        }
+        if let Some(quality) = self.quality {
+            if !self.format.is_lossy() {
+                return Err(MediaError::InvalidPreset(format!(
+                    "`{}`: {} is encoded losslessly and takes no quality",
+                    self.name,
+                    self.format.as_str()
+                )));
+            }
+            if !(1..=100).contains(&quality) {
+                return Err(MediaError::InvalidPreset(format!(
+                    "`{}`: quality must be between 1 and 100",
+                    self.name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Quality passed to the encoder, `None` for lossless formats.
+    pub fn encoder_quality(&self) -> Option<u8> {
+        self.format
+            .is_lossy()
+            .then(|| self.quality.unwrap_or(DEFAULT_QUALITY))
+    }
+
+    /// Settings a rendered derivative depends on. Stored with the derivative
+    /// so preset or focal point changes can be detected.
+    pub fn fingerprint(&self, focal_point: Option<FocalPoint>) -> String {
+        let mut fingerprint = format!(
+            "{}x{}:{}:{}",
+            self.width,
+            self.height.unwrap_or(0),
+            self.fit.as_str(),
+            self.format.as_str()
+        );
+        if let Some(quality) = self.encoder_quality() {
+            fingerprint.push_str(&format!(":q{quality}"));
+        }
+        if self.fit == ImageFit::Cover {
+            let focal = focal_point.unwrap_or(FocalPoint::CENTER);
+            fingerprint.push_str(&format!(":f{:.3},{:.3}", focal.x, focal.y));
+        }
+        fingerprint
+    }
+
+    /// Whether derivatives keep the original aspect ratio and can therefore
+    /// be offered as `srcset` candidates for the original.
+    pub fn preserves_aspect_ratio(&self) -> bool {
+        self.fit == ImageFit::Contain
+    }
+}
+
+/// Presets every tenant starts with. Tenants can override them by name.
+pub fn default_presets() -> Vec<ImagePreset> {
+    vec![
+        ImagePreset {
+            name: "thumbnail".to_string(),
+            width: 320,
+            height: Some(320),
+            fit: ImageFit::Cover,
+            format: ImageFormat::Jpeg,
+            quality: Some(80),
+            eager: true,
+        },
+        ImagePreset {
+            name: "card".to_string(),
+            width: 800,
+            height: Some(450),
+            fit: ImageFit::Cover,
+            format: ImageFormat::Jpeg,
+            quality: Some(82),
+            eager: true,
+        },
+        ImagePreset {
+            name: "hero".to_string(),
+            width: 1920,
+            height: None,
+            fit: ImageFit::Contain,
+            format: ImageFormat::Jpeg,
+            quality: Some(85),
+            eager: true,
+        },
+    ]
+}
+
+#[derive(Debug, Clone)]
+pub struct RenderedImage {
+    pub data: bytes::Bytes,
+    pub width: u32,
+    pub height: u32,
+    pub mime_type: &'static str,
+}
+
+/// Whether derivatives can be rendered from this MIME type.
+pub fn is_raster_image(mime_type: &str) -> bool {
+    matches!(
+        mime_type,
+        "image/jpeg"
+            | "image/png"
+            | "image/webp"
+            | "image/gif"
+            | "image/avif"
+            | "image/bmp"
+            | "image/tiff"
+    )
+}
+
+/// Read the pixel size from the image header without decoding it.
+pub fn image_dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    ImageReader::new(Cursor::new(data))
+        .with_guessed_format()
+        .ok()?
+        .into_dimensions()
+        .ok()
+}
+
+/// Render `data` with `preset`. Images are never upscaled.
+pub fn render_derivative(
+    data: &[u8],
+    preset: &ImagePreset,
+    focal_point: Option<FocalPoint>,
+) -> Result<RenderedImage> {
+    let image = ImageReader::new(Cursor::new(data))
+        .with_guessed_format()
+        .map_err(|error| MediaError::Image(error.to_string()))?
+        .decode()
+        .map_err(|error| MediaError::Image(error.to_string()))?;
+
+    let image = match preset.fit {
+        ImageFit::Contain => contain(image, preset.width, preset.height),
+        ImageFit::Cover => cover(
+            image,
+            preset.width,
+            preset.height.unwrap_or(preset.width),
+            focal_point.unwrap_or(FocalPoint::CENTER),
+        ),
+    };
+    let (width, height) = (image.width(), image.height());
+    let data = encode(image, preset.format, preset.encoder_quality())?;
+    Ok(RenderedImage {
+        data: bytes::Bytes::from(data),
+        width,
+        height,
+        mime_type: preset.format.mime_type(),
+    })
+}
+
+fn contain(image: DynamicImage, max_width: u32, max_height: Option<u32>) -> DynamicImage {
+    let (width, height) = (image.width() as f64, image.height() as f64);
+    let scale = (max_width as f64 / width)
+        .min(max_height.map_or(f64::INFINITY, |max| max as f64 / height))
+        .min(1.0);
+    if scale >= 1.0 {
+        return image;
+    }
+    let target_width = ((width * scale).round() as u32).max(1);
+    let target_height = ((height * scale).round() as u32).max(1);
+    image.resize_exact(target_width, target_height, FilterType::Lanczos3)
+}
+
+fn cover(image: DynamicImage, width: u32, height: u32, focal: FocalPoint) -> DynamicImage {
+    let (source_width, source_height) = (image.width() as f64, image.height() as f64);
+    let target_ratio = width as f64 / height as f64;
+
+    // Largest window with the target aspect ratio, centred on the focal point
+    // as far as the image edges allow.
+    let (crop_width, crop_height) = if source_width / source_height > target_ratio {
+        (source_height * target_ratio, source_height)
+    } else {
+        (source_width, source_width / target_ratio)
+    };
+    let left = (focal.x as f64 * source_width - crop_width / 2.0)
+        .clamp(0.0, source_width - crop_width)
+        .round() as u32;
+    let top = (focal.y as f64 * source_height - crop_height / 2.0)
+        .clamp(0.0, source_height - crop_height)
+        .round() as u32;
+    let crop_width = (crop_width.round() as u32).max(1);
+    let crop_height = (crop_height.round() as u32).max(1);
+    let cropped = image.crop_imm(left, top, crop_width, crop_height);
+
+    if crop_width <= width {
+        return cropped;
+    }
+    cropped.resize_exact(width, height, FilterType::Lanczos3)
+}
+
+fn encode(image: DynamicImage, format: ImageFormat, quality: Option<u8>) -> Result<Vec<u8>> {
+    let mut buffer = Vec::new();
+    let has_alpha = image.color().has_alpha();
+    let quality = quality.unwrap_or(DEFAULT_QUALITY);
+    let result = match format {
+        ImageFormat::Webp => {
+            let image = if has_alpha {
+                DynamicImage::ImageRgba8(image.to_rgba8())
+            } else {
+                DynamicImage::ImageRgb8(image.to_rgb8())
+            };
+            image.write_with_encoder(WebPEncoder::new_lossless(&mut buffer))
+        }
+        ImageFormat::Avif => {
+            let image = DynamicImage::ImageRgba8(image.to_rgba8());
+            image.write_with_encoder(AvifEncoder::new_with_speed_quality(
+                &mut buffer,
+                AVIF_SPEED,
+                quality,
+            ))
+        }
+        ImageFormat::Jpeg => {
+            let image = if has_alpha {
+                flatten_on_white(&image)
+            } else {
+                image.to_rgb8()
+            };
+            DynamicImage::ImageRgb8(image)
+                .write_with_encoder(JpegEncoder::new_with_quality(&mut buffer, quality))
+        }
+        ImageFormat::Png => image.write_with_encoder(PngEncoder::new(&mut buffer)),
+    };
+    result.map_err(|error| MediaError::Image(error.to_string()))?;
+    Ok(buffer)
+}
+
+/// JPEG has no alpha channel: blend transparent areas onto white instead of
+/// letting them turn black.
+fn flatten_on_white(image: &DynamicImage) -> RgbImage {
+    let rgba = image.to_rgba8();
+    RgbImage::from_fn(rgba.width(), rgba.height(), |x, y| {
+        let [r, g, b, a] = rgba.get_pixel(x, y).0;
+        let blend = |channel: u8| {
+            ((u16::from(channel) * u16::from(a) + 255 * u16::from(255 - a)) / 255) as u8
+        };
+        Rgb([blend(r), blend(g), blend(b)])
+    })
+}
+
+/// Build an HTML `srcset` value from `(url, width)` candidates, smallest
+/// first, one candidate per width.
+pub fn build_srcset<'a>(candidates: impl IntoIterator<Item = (&'a str, i32)>) -> Option<String> {
+    let mut candidates = candidates
+        .into_iter()
+        .filter(|(url, width)| *width > 0 && !url.is_empty())
+        .collect::<Vec<_>>();
+    candidates.sort_by_key(|(_, width)| *width);
+    candidates.dedup_by_key(|(_, width)| *width);
+    if candidates.is_empty() {
+        return None;
+    }
+    Some(
+        candidates
+            .into_iter()
+            .map(|(url, width)| format!("{url} {width}w"))
+            .collect::<Vec<_>>()
+            .join(", "),
+    )
+}
+
+/// `srcset` of an original image and its aspect-preserving derivatives.
+pub fn responsive_srcset(
+    original_url: &str,
+    original_width: Option<i32>,
+    derivatives: &[MediaDerivativeItem],
+) -> Option<String> {
+    let responsive = derivatives
+        .iter()
+        .filter(|derivative| derivative.responsive)
+        .collect::<Vec<_>>();
+    if responsive.is_empty() {
+        return None;
+    }
+    build_srcset(
+        responsive
+            .into_iter()
+            .map(|derivative| (derivative.public_url.as_str(), derivative.width))
+            .chain(original_width.map(|width| (original_url, width))),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgba, RgbaImage};
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let image = RgbImage::from_fn(width, height, |x, _| {
+            if x < width / 2 {
+                Rgb([255, 0, 0])
+            } else {
+                Rgb([0, 0, 255])
+            }
+        });
+        let mut buffer = Vec::new();
+        DynamicImage::ImageRgb8(image)
+            .write_with_encoder(PngEncoder::new(&mut buffer))
+            .unwrap();
+        buffer
+    }
+
+    fn preset(fit: ImageFit, width: u32, height: Option<u32>, format: ImageFormat) -> ImagePreset {
+        ImagePreset {
+            name: "test".to_string(),
+            width,
+            height,
+            fit,
+            format,
+            quality: format.is_lossy().then_some(80),
+            eager: true,
+        }
+    }
+
+    #[test]
+    fn contain_scales_down_without_upscaling() {
+        let source = png(400, 200);
+        let rendered = render_derivative(
+            &source,
+            &preset(ImageFit::Contain, 100, None, ImageFormat::Webp),
+            None,
+        )
+        .unwrap();
+        assert_eq!((rendered.width, rendered.height), (100, 50));
+        assert_eq!(rendered.mime_type, "image/webp");
+        assert_eq!(image_dimensions(&rendered.data), Some((100, 50)));
+
+        let rendered = render_derivative(
+            &source,
+            &preset(ImageFit::Contain, 1000, Some(1000), ImageFormat::Png),
+            None,
+        )
+        .unwrap();
+        assert_eq!((rendered.width, rendered.height), (400, 200));
+    }
+
+    #[test]
+    fn cover_crops_around_focal_point() {
+        let source = png(400, 200);
+        let square = preset(ImageFit::Cover, 50, Some(50), ImageFormat::Jpeg);
+
+        let left =
+            render_derivative(&source, &square, Some(FocalPoint::new(0.0, 0.5).unwrap())).unwrap();
+        assert_eq!((left.width, left.height), (50, 50));
+        let decoded = image::load_from_memory(&left.data).unwrap().to_rgb8();
+        let pixel = decoded.get_pixel(25, 25);
+        assert!(pixel[0] > 200 && pixel[2] < 60, "left crop keeps red half");
+
+        let right =
+            render_derivative(&source, &square, Some(FocalPoint::new(1.0, 0.5).unwrap())).unwrap();
+        let decoded = image::load_from_memory(&right.data).unwrap().to_rgb8();
+        let pixel = decoded.get_pixel(25, 25);
+        assert!(
+            pixel[2] > 200 && pixel[0] < 60,
+            "right crop keeps blue half"
+        );
+    }
+
+    #[test]
+    fn fingerprint_tracks_focal_point_only_for_cover() {
+        let cover = preset(ImageFit::Cover, 10, Some(10), ImageFormat::Webp);
+        let contain = preset(ImageFit::Contain, 10, None, ImageFormat::Webp);
+        let focal = FocalPoint::new(0.2, 0.8).ok();
+        assert_ne!(cover.fingerprint(None), cover.fingerprint(focal));
+        assert_eq!(contain.fingerprint(None), contain.fingerprint(focal));
+    }
+
+    #[test]
+    fn validate_rejects_bad_presets() {
+        assert!(preset(ImageFit::Cover, 10, None, ImageFormat::Webp)
+            .validate()
+            .is_err());
+        let mut invalid_name = preset(ImageFit::Contain, 10, None, ImageFormat::Webp);
+        invalid_name.name = "Hero Image".to_string();
+        assert!(invalid_name.validate().is_err());
+        assert!(default_presets()
+            .iter()
+            .all(|preset| preset.validate().is_ok()));
+
+        let mut lossless_quality = preset(ImageFit::Contain, 10, None, ImageFormat::Webp);
+        lossless_quality.quality = Some(80);
+        assert!(lossless_quality.validate().is_err());
+        let mut out_of_range = preset(ImageFit::Contain, 10, None, ImageFormat::Jpeg);
+        out_of_range.quality = Some(0);
+        assert!(out_of_range.validate().is_err());
+    }
+
+    #[test]
+    fn default_presets_are_smaller_than_the_source() {
+        // Photo-like content: a gradient with noise, which PNG compresses poorly.
+        let mut seed = 0x2545_f491_u32;
+        let image = RgbImage::from_fn(1200, 800, |x, y| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            let noise = (seed >> 24) as u8 / 8;
+            Rgb([
+                (x * 255 / 1200) as u8 / 2 + noise,
+                (y * 255 / 800) as u8 / 2 + noise,
+                128 + noise,
+            ])
+        });
+        let mut source = Vec::new();
+        DynamicImage::ImageRgb8(image)
+            .write_with_encoder(PngEncoder::new(&mut source))
+            .unwrap();
+
+        for preset in default_presets() {
+            let rendered = render_derivative(&source, &preset, None).unwrap();
+            assert_eq!(rendered.mime_type, "image/jpeg");
+            assert!(
+                rendered.data.len() < source.len(),
+                "`{}` is {} bytes, source {}",
+                preset.name,
+                rendered.data.len(),
+                source.len()
+            );
+        }
+    }
+
+    #[test]
+    fn jpeg_flattens_transparency_onto_white() {
+        let image = RgbaImage::from_pixel(8, 8, Rgba([0, 0, 0, 0]));
+        let mut source = Vec::new();
+        DynamicImage::ImageRgba8(image)
+            .write_with_encoder(PngEncoder::new(&mut source))
+            .unwrap();
+        let rendered = render_derivative(
+            &source,
+            &preset(ImageFit::Contain, 8, None, ImageFormat::Jpeg),
+            None,
+        )
+        .unwrap();
+        let decoded = image::load_from_memory(&rendered.data).unwrap().to_rgb8();
+        assert!(decoded
+            .get_pixel(4, 4)
+            .0
+            .iter()
+            .all(|channel| *channel > 240));
+    }
+
+    #[test]
+    fn srcset_orders_and_dedups_candidates() {
+        assert_eq!(
+            build_srcset([("/b.webp", 800), ("/a.webp", 320), ("/c.webp", 800)]).as_deref(),
+            Some("/a.webp 320w, /b.webp 800w")
+        );
+        assert_eq!(build_srcset([("/a.webp", 0)]), None);
+    }
+}
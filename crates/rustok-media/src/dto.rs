@@ -1,3 +1,6 @@
+use rustok_storage::{PresignedUpload, UploadedPart};
+
+use crate::usage::MediaReferenceTarget;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -23,6 +26,10 @@ pub struct MediaItem {
     pub public_url: String,
     pub width: Option<i32>,
     pub height: Option<i32>,
+    pub duration_ms: Option<i64>,
+    pub page_count: Option<i32>,
+    pub content_hash: Option<String>,
+    pub folder_id: Option<Uuid>,
     pub metadata: serde_json::Value,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
@@ -45,6 +52,155 @@ pub struct MediaTranslationItem {
     pub caption: Option<String>,
 }
 
+/// A stored rendition of a media image for one preset.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediaDerivativeItem {
+    pub media_id: Uuid,
+    pub preset: String,
+    pub public_url: String,
+    pub mime_type: String,
+    pub width: i32,
+    pub height: i32,
+    pub size: i64,
+    /// Whether the derivative keeps the original's aspect ratio and can be
+    /// used as a `srcset` candidate.
+    pub responsive: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediaFolderItem {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub name: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateMediaFolderInput {
+    pub name: String,
+    #[serde(default)]
+    pub parent_id: Option<Uuid>,
+}
+
+/// Media library search. All set criteria must match.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MediaSearchFilter {
+    /// Case-insensitive match on the original filename or any localized
+    /// title / alt text.
+    #[serde(default)]
+    pub query: Option<String>,
+    #[serde(default)]
+    pub folder_id: Option<Uuid>,
+    /// Only media outside any folder; ignored when `folder_id` is set.
+    #[serde(default)]
+    pub unfiled: bool,
+    /// Taxonomy term ID of a media tag.
+    #[serde(default)]
+    pub tag_id: Option<Uuid>,
+    /// MIME type prefix such as `image/`.
+    #[serde(default)]
+    pub mime_prefix: Option<String>,
+}
+
+/// A place where a media asset is used, from the where-used index.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediaUsageItem {
+    pub media_id: Uuid,
+    pub owner_kind: String,
+    pub owner_id: Uuid,
+    pub field: String,
+    pub label: Option<String>,
+    pub indexed_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// A content reference that does not resolve to any media of the tenant.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediaBrokenReference {
+    pub owner_kind: String,
+    pub owner_id: Uuid,
+    pub field: String,
+    pub label: Option<String>,
+    pub target: MediaReferenceTarget,
+}
+
+/// Result of refreshing the where-used index of a tenant.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediaOrphanReport {
+    /// Media not referenced by any registered provider.
+    pub unused: Vec<MediaItem>,
+    /// Total bytes held by `unused`.
+    pub unused_size: i64,
+    pub broken_references: Vec<MediaBrokenReference>,
+    pub indexed_usages: u64,
+    /// Provider owner kinds consulted; media used only elsewhere shows up
+    /// as unused.
+    pub owner_kinds: Vec<String>,
+    pub generated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Start a direct-to-storage upload.
+#[derive(Debug, Clone)]
+pub struct CreateUploadInput {
+    pub tenant_id: Uuid,
+    pub uploaded_by: Option<Uuid>,
+    pub original_name: String,
+    pub content_type: String,
+    /// Exact size of the file in bytes.
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UploadSessionStatus {
+    Pending,
+    Completed,
+    Aborted,
+}
+
+impl UploadSessionStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Completed => "completed",
+            Self::Aborted => "aborted",
+        }
+    }
+
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "completed" => Self::Completed,
+            "aborted" => Self::Aborted,
+            _ => Self::Pending,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadSessionItem {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub original_name: String,
+    pub content_type: String,
+    pub size: i64,
+    pub status: UploadSessionStatus,
+    /// Multipart sessions upload `part_count` parts of `part_size` bytes
+    /// (the last one may be shorter).
+    pub multipart: bool,
+    pub part_size: Option<i64>,
+    pub part_count: Option<u32>,
+    /// Direct upload request for single-request sessions. `None` when the
+    /// storage backend cannot presign; upload through the API instead.
+    pub upload_url: Option<PresignedUpload>,
+    /// Parts the backend has received, for resuming multipart uploads.
+    pub parts: Vec<UploadedPart>,
+    pub media_id: Option<Uuid>,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, Default)]
 pub struct MediaImageDescriptor {
     pub url: String,
@@ -148,6 +304,15 @@ pub const ALLOWED_MIME_PREFIXES: &[&str] = &["image/", "video/", "audio/", "appl
 
 pub const DEFAULT_MAX_SIZE: u64 = 50 * 1024 * 1024;
 
+/// Largest file accepted through a direct-to-storage upload session.
+pub const MAX_DIRECT_UPLOAD_SIZE: u64 = 5 * 1024 * 1024 * 1024;
+
+/// Direct uploads above this size use multipart sessions.
+pub const MULTIPART_THRESHOLD: u64 = 64 * 1024 * 1024;
+
+/// Part size of multipart sessions (S3 requires at least 5 MiB).
+pub const UPLOAD_PART_SIZE: u64 = 16 * 1024 * 1024;
+
 #[cfg(test)]
 mod tests {
     use super::MediaImageDescriptor;
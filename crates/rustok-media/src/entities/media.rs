@@ -16,6 +16,12 @@ pub struct Model {
     pub storage_driver: String,
     pub width: Option<i32>,
     pub height: Option<i32>,
+    pub duration_ms: Option<i64>,
+    pub page_count: Option<i32>,
+    /// SHA-256 of the uploaded bytes; duplicate uploads resolve to the
+    /// existing row of the same tenant.
+    pub content_hash: Option<String>,
+    pub folder_id: Option<Uuid>,
     pub metadata: Json,
     pub created_at: DateTimeWithTimeZone,
 }
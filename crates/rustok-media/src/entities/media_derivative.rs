@@ -0,0 +1,26 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "media_derivatives")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub media_id: Uuid,
+    pub preset: String,
+    /// Preset settings (and focal point) the derivative was rendered with;
+    /// a mismatch marks the derivative stale.
+    pub fingerprint: String,
+    pub storage_path: String,
+    pub mime_type: String,
+    pub width: i32,
+    pub height: i32,
+    pub size: i64,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,32 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A direct-to-storage upload in progress. Parts are tracked by the storage
+/// backend itself; the row records what the finalize step needs.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "media_uploads")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub uploaded_by: Option<Uuid>,
+    pub original_name: String,
+    /// Client-declared type; checked against the content on completion.
+    pub content_type: String,
+    /// Declared size in bytes; the stored object must match it.
+    pub size: i64,
+    pub storage_path: String,
+    /// Backend multipart upload ID, `None` for single-request uploads.
+    pub multipart_upload_id: Option<String>,
+    pub part_size: Option<i64>,
+    pub status: String,
+    pub media_id: Option<Uuid>,
+    pub expires_at: DateTimeWithTimeZone,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,26 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// One reference to a media asset from content owned by another module, as
+/// last collected by the registered usage providers.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "media_usages")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub media_id: Uuid,
+    /// Provider owner kind, e.g. `blog_post` or `profile`.
+    pub owner_kind: String,
+    pub owner_id: Uuid,
+    /// Field or JSON path holding the reference.
+    pub field: String,
+    /// Human-readable owner label, such as a post title.
+    pub label: Option<String>,
+    pub indexed_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
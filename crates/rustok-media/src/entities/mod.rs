@@ -1,2 +1,8 @@
 pub mod media;
+pub mod media_derivative;
+pub mod media_folder;
+pub mod media_image_preset;
+pub mod media_tag;
 pub mod media_translation;
+pub mod media_upload;
+pub mod media_usage;
@@ -15,6 +15,42 @@ pub enum MediaError {
     #[error("File too large: {size} bytes (max {max} bytes)")]
     FileTooLarge { size: u64, max: u64 },
 
+    #[error("Declared content type {declared} does not match detected {detected}")]
+    ContentTypeMismatch { declared: String, detected: String },
+
+    #[error("Unrecognized file content (declared {0})")]
+    UnrecognizedContent(String),
+
+    #[error("Upload session not found: {0}")]
+    UploadNotFound(Uuid),
+
+    #[error("Invalid upload: {0}")]
+    InvalidUpload(String),
+
+    #[error("Image processing failed: {0}")]
+    Image(String),
+
+    #[error("Invalid image preset: {0}")]
+    InvalidPreset(String),
+
+    #[error("Image preset not found: {0}")]
+    PresetNotFound(String),
+
+    #[error("Media folder not found: {0}")]
+    FolderNotFound(Uuid),
+
+    #[error("Invalid media folder: {0}")]
+    InvalidFolder(String),
+
+    #[error("Invalid media tag: {0}")]
+    InvalidTag(String),
+
+    #[error("Media {id} is still used in {usages} place(s)")]
+    InUse { id: Uuid, usages: usize },
+
+    #[error("Media usage provider `{owner_kind}` failed: {message}")]
+    UsageProvider { owner_kind: String, message: String },
+
     #[error("Storage error: {0}")]
     Storage(#[from] rustok_storage::StorageError),
 
@@ -22,4 +58,21 @@ pub enum MediaError {
     Db(#[from] sea_orm::DbErr),
 }
 
+impl From<rustok_taxonomy::TaxonomyError> for MediaError {
+    fn from(value: rustok_taxonomy::TaxonomyError) -> Self {
+        match value {
+            rustok_taxonomy::TaxonomyError::Database(err) => Self::Db(err),
+            rustok_taxonomy::TaxonomyError::Validation(message)
+            | rustok_taxonomy::TaxonomyError::DuplicateCanonicalKey(message)
+            | rustok_taxonomy::TaxonomyError::DuplicateSlug(message)
+            | rustok_taxonomy::TaxonomyError::DuplicateAlias(message)
+            | rustok_taxonomy::TaxonomyError::Forbidden(message) => Self::InvalidTag(message),
+            error @ (rustok_taxonomy::TaxonomyError::TermNotFound(_)
+            | rustok_taxonomy::TaxonomyError::HierarchyCycle(_)) => {
+                Self::InvalidTag(error.to_string())
+            }
+        }
+    }
+}
+
 pub type Result<T> = std::result::Result<T, MediaError>;
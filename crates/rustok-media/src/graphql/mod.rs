@@ -2,8 +2,29 @@ mod mutation;
 mod query;
 mod types;
 
+use std::sync::Arc;
+
+use async_graphql::{Context, Result};
+use rustok_core::ModuleRuntimeExtensions;
+use rustok_storage::StorageService;
+use sea_orm::DatabaseConnection;
+
+use crate::usage::media_usage_registry_from_extensions;
+use crate::MediaService;
+
 pub use mutation::MediaMutation;
 pub use query::MediaQuery;
 pub use types::*;
 
 pub(crate) const MODULE_SLUG: &str = "media";
+
+/// Media service with the usage providers registered in the schema's runtime
+/// extensions, so deletes and reports see references from other modules.
+pub(crate) fn media_service(ctx: &Context<'_>) -> Result<MediaService> {
+    let db = ctx.data::<DatabaseConnection>()?;
+    let storage = ctx.data::<StorageService>()?;
+    let registry = ctx
+        .data_opt::<Arc<ModuleRuntimeExtensions>>()
+        .and_then(|extensions| media_usage_registry_from_extensions(extensions));
+    Ok(MediaService::new(db.clone(), storage.clone()).with_usage_registry(registry))
+}
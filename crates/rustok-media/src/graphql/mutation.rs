@@ -1,25 +1,28 @@
 use async_graphql::{Context, Object, Result};
 use rustok_api::graphql::require_module_enabled;
-use rustok_storage::StorageService;
-use sea_orm::DatabaseConnection;
 use uuid::Uuid;
 
-use crate::{dto::UpsertTranslationInput, MediaService};
+use crate::{
+    dto::{CreateMediaFolderInput, UpsertTranslationInput},
+    FocalPoint, ImagePreset,
+};
 
-use super::{GqlMediaTranslation, UpsertMediaTranslationInput, MODULE_SLUG};
+use super::{
+    media_service, FocalPointInput, GqlImagePresetUpdate, GqlMediaDerivative, GqlMediaFolder,
+    GqlMediaItem, GqlMediaTranslation, GqlMediaUsage, ImagePresetInput,
+    UpsertMediaTranslationInput, MODULE_SLUG,
+};
 
 #[derive(Default)]
 pub struct MediaMutation;
 
 #[Object]
 impl MediaMutation {
-    /// Delete a media asset and remove it from storage.
+    /// Delete a media asset and remove it from storage. Fails while the asset
+    /// is still used; see `forceDeleteMedia`.
     async fn delete_media(&self, ctx: &Context<'_>, tenant_id: Uuid, id: Uuid) -> Result<bool> {
         require_module_enabled(ctx, MODULE_SLUG).await?;
-        let db = ctx.data::<DatabaseConnection>()?;
-        let storage = ctx.data::<StorageService>()?;
-
-        let service = MediaService::new(db.clone(), storage.clone());
+        let service = media_service(ctx)?;
         service
             .delete(tenant_id, id)
             .await
@@ -36,10 +39,7 @@ impl MediaMutation {
         input: UpsertMediaTranslationInput,
     ) -> Result<GqlMediaTranslation> {
         require_module_enabled(ctx, MODULE_SLUG).await?;
-        let db = ctx.data::<DatabaseConnection>()?;
-        let storage = ctx.data::<StorageService>()?;
-
-        let service = MediaService::new(db.clone(), storage.clone());
+        let service = media_service(ctx)?;
         let translation = service
             .upsert_translation(
                 tenant_id,
@@ -56,4 +56,200 @@ impl MediaMutation {
 
         Ok(translation.into())
     }
+
+    /// Create or replace an image preset and re-render derivatives produced
+    /// with the previous settings.
+    async fn upsert_media_image_preset(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Uuid,
+        input: ImagePresetInput,
+    ) -> Result<GqlImagePresetUpdate> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let service = media_service(ctx)?;
+        let (preset, regenerated) = service
+            .upsert_image_preset(tenant_id, ImagePreset::try_from(input)?)
+            .await
+            .map_err(|error| async_graphql::Error::new(error.to_string()))?;
+
+        Ok(GqlImagePresetUpdate {
+            preset: preset.into(),
+            regenerated: regenerated as i64,
+        })
+    }
+
+    /// Delete a tenant image preset. Built-in presets revert to defaults.
+    async fn delete_media_image_preset(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Uuid,
+        name: String,
+    ) -> Result<bool> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let service = media_service(ctx)?;
+        service
+            .delete_image_preset(tenant_id, &name)
+            .await
+            .map_err(|error| async_graphql::Error::new(error.to_string()))
+    }
+
+    /// Set or clear the focal point used for cropped derivatives.
+    async fn set_media_focal_point(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Uuid,
+        media_id: Uuid,
+        focal_point: Option<FocalPointInput>,
+    ) -> Result<GqlMediaItem> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let focal_point = focal_point.map(FocalPoint::try_from).transpose()?;
+        let service = media_service(ctx)?;
+        let item = service
+            .set_focal_point(tenant_id, media_id, focal_point)
+            .await
+            .map_err(|error| async_graphql::Error::new(error.to_string()))?;
+
+        Ok(item.into())
+    }
+
+    /// Re-render all derivatives of a media asset.
+    async fn regenerate_media_derivatives(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Uuid,
+        media_id: Uuid,
+    ) -> Result<Vec<GqlMediaDerivative>> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let service = media_service(ctx)?;
+        let derivatives = service
+            .regenerate_derivatives(tenant_id, media_id)
+            .await
+            .map_err(|error| async_graphql::Error::new(error.to_string()))?;
+
+        Ok(derivatives.into_iter().map(Into::into).collect())
+    }
+
+    /// Delete a media asset even when it is still used. Returns the usages
+    /// left pointing at the deleted asset.
+    async fn force_delete_media(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Uuid,
+        id: Uuid,
+    ) -> Result<Vec<GqlMediaUsage>> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let service = media_service(ctx)?;
+        let usages = service
+            .force_delete(tenant_id, id)
+            .await
+            .map_err(|error| async_graphql::Error::new(error.to_string()))?;
+
+        Ok(usages.into_iter().map(Into::into).collect())
+    }
+
+    /// Replace the tags of a media asset. Unknown tags are created in the
+    /// taxonomy.
+    async fn set_media_tags(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Uuid,
+        media_id: Uuid,
+        locale: String,
+        tags: Vec<String>,
+    ) -> Result<Vec<String>> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let service = media_service(ctx)?;
+        service
+            .set_tags(tenant_id, media_id, &locale, &tags)
+            .await
+            .map_err(|error| async_graphql::Error::new(error.to_string()))
+    }
+
+    /// Move a media asset into a folder, or to the library root when
+    /// `folderId` is omitted.
+    async fn move_media(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Uuid,
+        media_id: Uuid,
+        folder_id: Option<Uuid>,
+    ) -> Result<GqlMediaItem> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let service = media_service(ctx)?;
+        let item = service
+            .move_media(tenant_id, media_id, folder_id)
+            .await
+            .map_err(|error| async_graphql::Error::new(error.to_string()))?;
+
+        Ok(item.into())
+    }
+
+    async fn create_media_folder(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Uuid,
+        name: String,
+        parent_id: Option<Uuid>,
+    ) -> Result<GqlMediaFolder> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let service = media_service(ctx)?;
+        let folder = service
+            .create_folder(tenant_id, CreateMediaFolderInput { name, parent_id })
+            .await
+            .map_err(|error| async_graphql::Error::new(error.to_string()))?;
+
+        Ok(folder.into())
+    }
+
+    async fn rename_media_folder(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Uuid,
+        id: Uuid,
+        name: String,
+    ) -> Result<GqlMediaFolder> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let service = media_service(ctx)?;
+        let folder = service
+            .rename_folder(tenant_id, id, &name)
+            .await
+            .map_err(|error| async_graphql::Error::new(error.to_string()))?;
+
+        Ok(folder.into())
+    }
+
+    /// Move a folder below another one, or to the root when `parentId` is
+    /// omitted.
+    async fn move_media_folder(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Uuid,
+        id: Uuid,
+        parent_id: Option<Uuid>,
+    ) -> Result<GqlMediaFolder> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let service = media_service(ctx)?;
+        let folder = service
+            .move_folder(tenant_id, id, parent_id)
+            .await
+            .map_err(|error| async_graphql::Error::new(error.to_string()))?;
+
+        Ok(folder.into())
+    }
+
+    /// Delete an empty folder.
+    async fn delete_media_folder(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Uuid,
+        id: Uuid,
+    ) -> Result<bool> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let service = media_service(ctx)?;
+        service
+            .delete_folder(tenant_id, id)
+            .await
+            .map_err(|error| async_graphql::Error::new(error.to_string()))?;
+        Ok(true)
+    }
 }
@@ -1,33 +1,31 @@
 use async_graphql::{Context, Object, Result};
 use rustok_api::graphql::{require_module_enabled, PaginationInput};
-use rustok_storage::StorageService;
-use sea_orm::DatabaseConnection;
 use uuid::Uuid;
 
-use crate::MediaService;
-
-use super::{GqlMediaItem, GqlMediaList, GqlMediaTranslation, MODULE_SLUG};
+use super::{
+    media_service, GqlImagePreset, GqlMediaDerivative, GqlMediaFolder, GqlMediaItem, GqlMediaList,
+    GqlMediaOrphanReport, GqlMediaTranslation, GqlMediaUsage, MediaSearchInput, MODULE_SLUG,
+};
 
 #[derive(Default)]
 pub struct MediaQuery;
 
 #[Object]
 impl MediaQuery {
-    /// List media assets for a tenant.
+    /// List media assets for a tenant, optionally filtered by text, folder,
+    /// tag or MIME type.
     async fn media(
         &self,
         ctx: &Context<'_>,
         tenant_id: Uuid,
         #[graphql(default)] pagination: PaginationInput,
+        #[graphql(default)] filter: MediaSearchInput,
     ) -> Result<GqlMediaList> {
         require_module_enabled(ctx, MODULE_SLUG).await?;
-        let db = ctx.data::<DatabaseConnection>()?;
-        let storage = ctx.data::<StorageService>()?;
-
-        let service = MediaService::new(db.clone(), storage.clone());
+        let service = media_service(ctx)?;
         let (offset, limit) = pagination.normalize()?;
         let (items, total) = service
-            .list(tenant_id, limit as u64, offset as u64)
+            .search(tenant_id, filter.into(), limit as u64, offset as u64)
             .await
             .map_err(|error| async_graphql::Error::new(error.to_string()))?;
 
@@ -45,10 +43,7 @@ impl MediaQuery {
         id: Uuid,
     ) -> Result<Option<GqlMediaItem>> {
         require_module_enabled(ctx, MODULE_SLUG).await?;
-        let db = ctx.data::<DatabaseConnection>()?;
-        let storage = ctx.data::<StorageService>()?;
-
-        let service = MediaService::new(db.clone(), storage.clone());
+        let service = media_service(ctx)?;
         match service.get(tenant_id, id).await {
             Ok(item) => Ok(Some(item.into())),
             Err(crate::MediaError::NotFound(_)) => Ok(None),
@@ -64,10 +59,7 @@ impl MediaQuery {
         media_id: Uuid,
     ) -> Result<Vec<GqlMediaTranslation>> {
         require_module_enabled(ctx, MODULE_SLUG).await?;
-        let db = ctx.data::<DatabaseConnection>()?;
-        let storage = ctx.data::<StorageService>()?;
-
-        let service = MediaService::new(db.clone(), storage.clone());
+        let service = media_service(ctx)?;
         let translations = service
             .get_translations(tenant_id, media_id)
             .await
@@ -75,4 +67,107 @@ impl MediaQuery {
 
         Ok(translations.into_iter().map(Into::into).collect())
     }
+
+    /// Effective image presets for a tenant: built-in defaults merged with
+    /// tenant overrides and custom presets.
+    async fn media_image_presets(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Uuid,
+    ) -> Result<Vec<GqlImagePreset>> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let service = media_service(ctx)?;
+        let presets = service
+            .image_presets(tenant_id)
+            .await
+            .map_err(|error| async_graphql::Error::new(error.to_string()))?;
+
+        Ok(presets.into_iter().map(Into::into).collect())
+    }
+
+    /// Get the derivative of a media asset for a preset, rendering it on
+    /// demand when it is missing or stale.
+    async fn media_derivative(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Uuid,
+        media_id: Uuid,
+        preset: String,
+    ) -> Result<GqlMediaDerivative> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let service = media_service(ctx)?;
+        let derivative = service
+            .derivative(tenant_id, media_id, &preset)
+            .await
+            .map_err(|error| async_graphql::Error::new(error.to_string()))?;
+
+        Ok(derivative.into())
+    }
+
+    /// Folders directly below `parentId`, or root folders when it is omitted.
+    async fn media_folders(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Uuid,
+        parent_id: Option<Uuid>,
+    ) -> Result<Vec<GqlMediaFolder>> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let service = media_service(ctx)?;
+        let folders = service
+            .list_folders(tenant_id, parent_id)
+            .await
+            .map_err(|error| async_graphql::Error::new(error.to_string()))?;
+
+        Ok(folders.into_iter().map(Into::into).collect())
+    }
+
+    /// Tag names of a media asset in the requested locale.
+    async fn media_tags(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Uuid,
+        media_id: Uuid,
+        locale: String,
+    ) -> Result<Vec<String>> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let service = media_service(ctx)?;
+        service
+            .tags(tenant_id, media_id, &locale)
+            .await
+            .map_err(|error| async_graphql::Error::new(error.to_string()))
+    }
+
+    /// Places that use a media asset, from the where-used index.
+    async fn media_usages(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Uuid,
+        media_id: Uuid,
+    ) -> Result<Vec<GqlMediaUsage>> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let service = media_service(ctx)?;
+        let usages = service
+            .where_used(tenant_id, media_id)
+            .await
+            .map_err(|error| async_graphql::Error::new(error.to_string()))?;
+
+        Ok(usages.into_iter().map(Into::into).collect())
+    }
+
+    /// Refresh the where-used index and report unused media and references
+    /// to missing media.
+    async fn media_orphan_report(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Uuid,
+    ) -> Result<GqlMediaOrphanReport> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let service = media_service(ctx)?;
+        let report = service
+            .orphan_report(tenant_id)
+            .await
+            .map_err(|error| async_graphql::Error::new(error.to_string()))?;
+
+        Ok(report.into())
+    }
 }
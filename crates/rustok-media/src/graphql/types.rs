@@ -1,10 +1,20 @@
-use async_graphql::{InputObject, SimpleObject};
+use async_graphql::{ComplexObject, Context, Enum, InputObject, Result, SimpleObject};
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
-use crate::dto::{MediaItem, MediaTranslationItem};
+use crate::derivatives::{
+    default_presets, responsive_srcset, FocalPoint, ImageFit, ImageFormat, ImagePreset,
+};
+use crate::dto::{
+    MediaBrokenReference, MediaDerivativeItem, MediaFolderItem, MediaItem, MediaOrphanReport,
+    MediaSearchFilter, MediaTranslationItem, MediaUsageItem,
+};
+use crate::usage::MediaReferenceTarget;
+
+use super::media_service;
 
 #[derive(SimpleObject, Clone, Debug)]
+#[graphql(complex)]
 pub struct GqlMediaItem {
     pub id: Uuid,
     pub tenant_id: Uuid,
@@ -17,6 +27,10 @@ pub struct GqlMediaItem {
     pub public_url: String,
     pub width: Option<i32>,
     pub height: Option<i32>,
+    pub duration_ms: Option<i64>,
+    pub page_count: Option<i32>,
+    pub content_hash: Option<String>,
+    pub folder_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -34,11 +48,48 @@ impl From<MediaItem> for GqlMediaItem {
             public_url: item.public_url,
             width: item.width,
             height: item.height,
+            duration_ms: item.duration_ms,
+            page_count: item.page_count,
+            content_hash: item.content_hash,
+            folder_id: item.folder_id,
             created_at: item.created_at,
         }
     }
 }
 
+#[ComplexObject]
+impl GqlMediaItem {
+    /// Rendered image derivatives, narrowest first.
+    async fn derivatives(&self, ctx: &Context<'_>) -> Result<Vec<GqlMediaDerivative>> {
+        Ok(self
+            .load_derivatives(ctx)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+
+    /// Responsive `srcset` built from aspect-preserving derivatives and the
+    /// original. `null` for non-images and images without such derivatives.
+    async fn srcset(&self, ctx: &Context<'_>) -> Result<Option<String>> {
+        let derivatives = self.load_derivatives(ctx).await?;
+        Ok(responsive_srcset(
+            &self.public_url,
+            self.width,
+            &derivatives,
+        ))
+    }
+}
+
+impl GqlMediaItem {
+    async fn load_derivatives(&self, ctx: &Context<'_>) -> Result<Vec<MediaDerivativeItem>> {
+        media_service(ctx)?
+            .list_derivatives(self.tenant_id, self.id)
+            .await
+            .map_err(|error| async_graphql::Error::new(error.to_string()))
+    }
+}
+
 #[derive(SimpleObject, Clone, Debug)]
 pub struct GqlMediaList {
     pub items: Vec<GqlMediaItem>,
@@ -75,3 +126,304 @@ pub struct UpsertMediaTranslationInput {
     pub alt_text: Option<String>,
     pub caption: Option<String>,
 }
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum GqlImageFit {
+    Contain,
+    Cover,
+}
+
+impl From<ImageFit> for GqlImageFit {
+    fn from(fit: ImageFit) -> Self {
+        match fit {
+            ImageFit::Contain => Self::Contain,
+            ImageFit::Cover => Self::Cover,
+        }
+    }
+}
+
+impl From<GqlImageFit> for ImageFit {
+    fn from(fit: GqlImageFit) -> Self {
+        match fit {
+            GqlImageFit::Contain => Self::Contain,
+            GqlImageFit::Cover => Self::Cover,
+        }
+    }
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum GqlImageFormat {
+    Webp,
+    Avif,
+    Jpeg,
+    Png,
+}
+
+impl From<ImageFormat> for GqlImageFormat {
+    fn from(format: ImageFormat) -> Self {
+        match format {
+            ImageFormat::Webp => Self::Webp,
+            ImageFormat::Avif => Self::Avif,
+            ImageFormat::Jpeg => Self::Jpeg,
+            ImageFormat::Png => Self::Png,
+        }
+    }
+}
+
+impl From<GqlImageFormat> for ImageFormat {
+    fn from(format: GqlImageFormat) -> Self {
+        match format {
+            GqlImageFormat::Webp => Self::Webp,
+            GqlImageFormat::Avif => Self::Avif,
+            GqlImageFormat::Jpeg => Self::Jpeg,
+            GqlImageFormat::Png => Self::Png,
+        }
+    }
+}
+
+#[derive(SimpleObject, Clone, Debug)]
+pub struct GqlImagePreset {
+    pub name: String,
+    pub width: i32,
+    pub height: Option<i32>,
+    pub fit: GqlImageFit,
+    pub format: GqlImageFormat,
+    /// Encoder quality; unset for lossless formats.
+    pub quality: Option<i32>,
+    pub eager: bool,
+    /// Whether the preset is one of the built-in defaults.
+    pub builtin: bool,
+}
+
+impl From<ImagePreset> for GqlImagePreset {
+    fn from(preset: ImagePreset) -> Self {
+        let builtin = default_presets()
+            .iter()
+            .any(|default| default.name == preset.name);
+        Self {
+            name: preset.name,
+            width: preset.width as i32,
+            height: preset.height.map(|height| height as i32),
+            fit: preset.fit.into(),
+            format: preset.format.into(),
+            quality: preset.quality.map(i32::from),
+            eager: preset.eager,
+            builtin,
+        }
+    }
+}
+
+#[derive(SimpleObject, Clone, Debug)]
+pub struct GqlImagePresetUpdate {
+    pub preset: GqlImagePreset,
+    /// Number of derivatives re-rendered with the new settings.
+    pub regenerated: i64,
+}
+
+#[derive(SimpleObject, Clone, Debug)]
+pub struct GqlMediaDerivative {
+    pub media_id: Uuid,
+    pub preset: String,
+    pub public_url: String,
+    pub mime_type: String,
+    pub width: i32,
+    pub height: i32,
+    pub size: i64,
+    pub responsive: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<MediaDerivativeItem> for GqlMediaDerivative {
+    fn from(item: MediaDerivativeItem) -> Self {
+        Self {
+            media_id: item.media_id,
+            preset: item.preset,
+            public_url: item.public_url,
+            mime_type: item.mime_type,
+            width: item.width,
+            height: item.height,
+            size: item.size,
+            responsive: item.responsive,
+            created_at: item.created_at,
+        }
+    }
+}
+
+#[derive(InputObject, Clone, Debug)]
+pub struct ImagePresetInput {
+    pub name: String,
+    pub width: i32,
+    pub height: Option<i32>,
+    pub fit: GqlImageFit,
+    pub format: GqlImageFormat,
+    /// Encoder quality for JPEG and AVIF; must be omitted for WebP and PNG.
+    pub quality: Option<i32>,
+    #[graphql(default = true)]
+    pub eager: bool,
+}
+
+impl TryFrom<ImagePresetInput> for ImagePreset {
+    type Error = async_graphql::Error;
+
+    fn try_from(input: ImagePresetInput) -> Result<Self> {
+        let dimension = |value: i32| {
+            u32::try_from(value)
+                .map_err(|_| async_graphql::Error::new("dimensions must be positive"))
+        };
+        Ok(Self {
+            name: input.name,
+            width: dimension(input.width)?,
+            height: input.height.map(dimension).transpose()?,
+            fit: input.fit.into(),
+            format: input.format.into(),
+            quality: input
+                .quality
+                .map(u8::try_from)
+                .transpose()
+                .map_err(|_| async_graphql::Error::new("quality must be between 1 and 100"))?,
+            eager: input.eager,
+        })
+    }
+}
+
+#[derive(InputObject, Clone, Copy, Debug)]
+pub struct FocalPointInput {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl TryFrom<FocalPointInput> for FocalPoint {
+    type Error = async_graphql::Error;
+
+    fn try_from(input: FocalPointInput) -> Result<Self> {
+        FocalPoint::new(input.x, input.y)
+            .map_err(|error| async_graphql::Error::new(error.to_string()))
+    }
+}
+
+#[derive(InputObject, Clone, Debug, Default)]
+pub struct MediaSearchInput {
+    /// Matches the original file name, alt text and title.
+    pub query: Option<String>,
+    pub folder_id: Option<Uuid>,
+    /// Only media outside any folder. Ignored when `folder_id` is set.
+    #[graphql(default)]
+    pub unfiled: bool,
+    pub tag_id: Option<Uuid>,
+    /// MIME type prefix such as `image/`.
+    pub mime_prefix: Option<String>,
+}
+
+impl From<MediaSearchInput> for MediaSearchFilter {
+    fn from(input: MediaSearchInput) -> Self {
+        Self {
+            query: input.query,
+            folder_id: input.folder_id,
+            unfiled: input.unfiled,
+            tag_id: input.tag_id,
+            mime_prefix: input.mime_prefix,
+        }
+    }
+}
+
+#[derive(SimpleObject, Clone, Debug)]
+pub struct GqlMediaFolder {
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<MediaFolderItem> for GqlMediaFolder {
+    fn from(folder: MediaFolderItem) -> Self {
+        Self {
+            id: folder.id,
+            tenant_id: folder.tenant_id,
+            parent_id: folder.parent_id,
+            name: folder.name,
+            created_at: folder.created_at,
+            updated_at: folder.updated_at,
+        }
+    }
+}
+
+#[derive(SimpleObject, Clone, Debug)]
+pub struct GqlMediaUsage {
+    pub media_id: Uuid,
+    pub owner_kind: String,
+    pub owner_id: Uuid,
+    pub field: String,
+    pub label: Option<String>,
+    pub indexed_at: DateTime<Utc>,
+}
+
+impl From<MediaUsageItem> for GqlMediaUsage {
+    fn from(usage: MediaUsageItem) -> Self {
+        Self {
+            media_id: usage.media_id,
+            owner_kind: usage.owner_kind,
+            owner_id: usage.owner_id,
+            field: usage.field,
+            label: usage.label,
+            indexed_at: usage.indexed_at,
+        }
+    }
+}
+
+#[derive(SimpleObject, Clone, Debug)]
+pub struct GqlMediaBrokenReference {
+    pub owner_kind: String,
+    pub owner_id: Uuid,
+    pub field: String,
+    pub label: Option<String>,
+    /// Media ID the reference points at, when it is an ID reference.
+    pub target_id: Option<Uuid>,
+    /// URL the reference points at, when it is a URL reference.
+    pub target_url: Option<String>,
+}
+
+impl From<MediaBrokenReference> for GqlMediaBrokenReference {
+    fn from(reference: MediaBrokenReference) -> Self {
+        let (target_id, target_url) = match reference.target {
+            MediaReferenceTarget::Id(id) => (Some(id), None),
+            MediaReferenceTarget::Url(url) => (None, Some(url)),
+        };
+        Self {
+            owner_kind: reference.owner_kind,
+            owner_id: reference.owner_id,
+            field: reference.field,
+            label: reference.label,
+            target_id,
+            target_url,
+        }
+    }
+}
+
+#[derive(SimpleObject, Clone, Debug)]
+pub struct GqlMediaOrphanReport {
+    pub unused: Vec<GqlMediaItem>,
+    pub unused_size: i64,
+    pub broken_references: Vec<GqlMediaBrokenReference>,
+    pub indexed_usages: i64,
+    pub owner_kinds: Vec<String>,
+    pub generated_at: DateTime<Utc>,
+}
+
+impl From<MediaOrphanReport> for GqlMediaOrphanReport {
+    fn from(report: MediaOrphanReport) -> Self {
+        Self {
+            unused: report.unused.into_iter().map(Into::into).collect(),
+            unused_size: report.unused_size,
+            broken_references: report
+                .broken_references
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            indexed_usages: report.indexed_usages as i64,
+            owner_kinds: report.owner_kinds,
+            generated_at: report.generated_at,
+        }
+    }
+}
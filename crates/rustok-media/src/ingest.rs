@@ -0,0 +1,831 @@
+//! Upload ingestion: content sniffing, metadata extraction and privacy
+//! stripping.
+//!
+//! Nothing here trusts the client-supplied content type. The MIME type stored
+//! for an upload is the one detected from its leading bytes, and the declared
+//! type only has to agree with it. Everything is pure (bytes in, values out)
+//! so `MediaService` can run it before touching storage.
+
+use sha2::Digest;
+
+use crate::derivatives::{image_dimensions, is_raster_image};
+use crate::error::{MediaError, Result};
+
+/// How `MediaService::upload` treats incoming files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IngestOptions {
+    /// Remove EXIF (including GPS), XMP and text metadata from JPEG, PNG and
+    /// WebP images before storing them. The EXIF orientation is kept.
+    pub strip_metadata: bool,
+    /// Resolve an upload whose content hash matches an existing asset of the
+    /// same tenant to that asset instead of storing another copy.
+    pub deduplicate: bool,
+}
+
+impl Default for IngestOptions {
+    fn default() -> Self {
+        Self {
+            strip_metadata: true,
+            deduplicate: true,
+        }
+    }
+}
+
+/// Technical metadata read from file headers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExtractedMetadata {
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub duration_ms: Option<i64>,
+    pub page_count: Option<i32>,
+}
+
+/// ISO base media file format (`ftyp`) types.
+const ISO_BMFF_TYPES: &[&str] = &[
+    "video/mp4",
+    "audio/mp4",
+    "video/quicktime",
+    "video/3gpp",
+    "video/x-m4v",
+];
+
+/// Matroska/EBML types.
+const MATROSKA_TYPES: &[&str] = &["video/webm", "audio/webm", "video/x-matroska"];
+
+const OGG_TYPES: &[&str] = &["audio/ogg", "video/ogg", "application/ogg"];
+
+/// Container families whose brand bytes do not reliably tell audio from
+/// video. Within a family the declared type is trusted.
+const MIME_FAMILIES: &[&[&str]] = &[ISO_BMFF_TYPES, MATROSKA_TYPES, OGG_TYPES];
+
+/// Detect the MIME type of `data` from its magic bytes.
+pub fn sniff_mime_type(data: &[u8]) -> Option<&'static str> {
+    let starts = |magic: &[u8]| data.starts_with(magic);
+    let riff = |form: &[u8]| starts(b"RIFF") && data.get(8..12) == Some(form);
+
+    if starts(b"\xFF\xD8\xFF") {
+        return Some("image/jpeg");
+    }
+    if starts(b"\x89PNG\r\n\x1a\n") {
+        return Some("image/png");
+    }
+    if starts(b"GIF87a") || starts(b"GIF89a") {
+        return Some("image/gif");
+    }
+    if riff(b"WEBP") {
+        return Some("image/webp");
+    }
+    if riff(b"WAVE") {
+        return Some("audio/wav");
+    }
+    if riff(b"AVI ") {
+        return Some("video/x-msvideo");
+    }
+    if starts(b"II*\0") || starts(b"MM\0*") {
+        return Some("image/tiff");
+    }
+    if starts(b"\0\0\x01\0") && data.len() > 6 {
+        return Some("image/x-icon");
+    }
+    if starts(b"BM") && data.len() > 14 {
+        return Some("image/bmp");
+    }
+    if data.get(4..8) == Some(b"ftyp") {
+        return Some(match data.get(8..12) {
+            Some(b"avif" | b"avis") => "image/avif",
+            Some(b"heic" | b"heix" | b"mif1" | b"msf1") => "image/heic",
+            Some(b"M4A " | b"M4B ") => "audio/mp4",
+            Some(b"qt  ") => "video/quicktime",
+            Some(brand) if brand.starts_with(b"3g") => "video/3gpp",
+            _ => "video/mp4",
+        });
+    }
+    if starts(b"\x1A\x45\xDF\xA3") {
+        let header = &data[..data.len().min(64)];
+        return Some(if contains(header, b"webm") {
+            "video/webm"
+        } else {
+            "video/x-matroska"
+        });
+    }
+    if starts(b"OggS") {
+        let header = &data[..data.len().min(64)];
+        return Some(if contains(header, b"theora") {
+            "video/ogg"
+        } else {
+            "audio/ogg"
+        });
+    }
+    if starts(b"fLaC") {
+        return Some("audio/flac");
+    }
+    if starts(b"ID3") {
+        return Some("audio/mpeg");
+    }
+    if let [0xFF, second, ..] = data {
+        if second & 0xF6 == 0xF0 {
+            return Some("audio/aac");
+        }
+        if second & 0xE0 == 0xE0 {
+            return Some("audio/mpeg");
+        }
+    }
+    if starts(b"%PDF-") {
+        return Some("application/pdf");
+    }
+    if looks_like_svg(data) {
+        return Some("image/svg+xml");
+    }
+    None
+}
+
+/// Resolve the MIME type to store for an upload.
+///
+/// A generic declared type (`application/octet-stream` or empty) takes the
+/// detected type; otherwise the declared type must agree with the bytes.
+pub fn resolve_mime_type(declared: &str, data: &[u8]) -> Result<String> {
+    let declared = canonical_mime_type(declared);
+    let Some(detected) = sniff_mime_type(data) else {
+        return Err(MediaError::UnrecognizedContent(declared));
+    };
+    if declared.is_empty() || declared == "application/octet-stream" || declared == detected {
+        return Ok(detected.to_string());
+    }
+    let same_family = MIME_FAMILIES
+        .iter()
+        .any(|family| family.contains(&detected) && family.contains(&declared.as_str()));
+    if same_family {
+        return Ok(declared);
+    }
+    Err(MediaError::ContentTypeMismatch {
+        declared,
+        detected: detected.to_string(),
+    })
+}
+
+/// Lowercase a MIME type, drop its parameters and map common aliases.
+pub fn canonical_mime_type(mime_type: &str) -> String {
+    let essence = mime_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    let canonical = match essence.as_str() {
+        "image/jpg" | "image/pjpeg" => "image/jpeg",
+        "image/x-png" => "image/png",
+        "image/x-ms-bmp" => "image/bmp",
+        "image/vnd.microsoft.icon" => "image/x-icon",
+        "audio/mp3" | "audio/x-mpeg" | "audio/mpeg3" => "audio/mpeg",
+        "audio/x-wav" | "audio/wave" | "audio/vnd.wave" => "audio/wav",
+        "audio/x-flac" => "audio/flac",
+        "audio/x-m4a" | "audio/m4a" => "audio/mp4",
+        "audio/x-aac" | "audio/aacp" => "audio/aac",
+        "video/avi" | "video/msvideo" => "video/x-msvideo",
+        _ => return essence,
+    };
+    canonical.to_string()
+}
+
+/// SHA-256 of the uploaded bytes, hex encoded.
+pub fn content_hash(data: &[u8]) -> String {
+    hex::encode(sha2::Sha256::digest(data))
+}
+
+/// Incremental [`content_hash`] for objects read in chunks.
+#[derive(Default)]
+pub struct ContentHasher(sha2::Sha256);
+
+impl ContentHasher {
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.0.update(chunk);
+    }
+
+    pub fn finish(self) -> String {
+        hex::encode(self.0.finalize())
+    }
+}
+
+/// Read dimensions, duration and page count from the file headers. Fields
+/// the format does not carry, or that cannot be parsed, stay `None`.
+pub fn extract_metadata(mime_type: &str, data: &[u8]) -> ExtractedMetadata {
+    let mut metadata = ExtractedMetadata::default();
+    if is_raster_image(mime_type) {
+        if let Some((width, height)) = image_dimensions(data) {
+            metadata.width = Some(width);
+            metadata.height = Some(height);
+        }
+        return metadata;
+    }
+    match mime_type {
+        "application/pdf" => metadata.page_count = pdf_page_count(data),
+        "audio/wav" => metadata.duration_ms = wav_duration_ms(data),
+        "audio/flac" => metadata.duration_ms = flac_duration_ms(data),
+        mime if ISO_BMFF_TYPES.contains(&mime) => {
+            if let Some(info) = mp4_info(data) {
+                metadata.duration_ms = info.duration_ms;
+                metadata.width = info.width;
+                metadata.height = info.height;
+            }
+        }
+        mime if MATROSKA_TYPES.contains(&mime) => {
+            if let Some(info) = matroska_info(data) {
+                metadata.duration_ms = info.duration_ms;
+                metadata.width = info.width;
+                metadata.height = info.height;
+            }
+        }
+        _ => {}
+    }
+    metadata
+}
+
+/// Whether [`strip_image_metadata`] handles this type.
+pub fn has_strippable_metadata(mime_type: &str) -> bool {
+    matches!(mime_type, "image/jpeg" | "image/png" | "image/webp")
+}
+
+/// Remove privacy-sensitive metadata from JPEG, PNG and WebP images. Other
+/// types, and files that do not parse cleanly, are returned unchanged.
+pub fn strip_image_metadata(mime_type: &str, data: bytes::Bytes) -> bytes::Bytes {
+    let stripped = match mime_type {
+        "image/jpeg" => strip_jpeg(&data),
+        "image/png" => strip_png(&data),
+        "image/webp" => strip_webp(&data),
+        _ => None,
+    };
+    stripped.map_or(data, bytes::Bytes::from)
+}
+
+// ── Sniffing helpers ──────────────────────────────────────────────────────────
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+fn looks_like_svg(data: &[u8]) -> bool {
+    let head = &data[..data.len().min(1024)];
+    let head = head.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(head);
+    let start = head
+        .iter()
+        .position(|byte| !byte.is_ascii_whitespace())
+        .unwrap_or(head.len());
+    let head = &head[start..];
+    (head.starts_with(b"<svg") || head.starts_with(b"<?xml") || head.starts_with(b"<!--"))
+        && contains(head, b"<svg")
+}
+
+// ── Metadata extraction ───────────────────────────────────────────────────────
+
+struct StreamInfo {
+    duration_ms: Option<i64>,
+    width: Option<u32>,
+    height: Option<u32>,
+}
+
+fn be_u16(data: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(data.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn be_u32(data: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(data.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn be_u64(data: &[u8], at: usize) -> Option<u64> {
+    Some(u64::from_be_bytes(data.get(at..at + 8)?.try_into().ok()?))
+}
+
+fn le_u32(data: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(data.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn scaled_ms(units: u64, per_second: u64) -> Option<i64> {
+    if per_second == 0 {
+        return None;
+    }
+    i64::try_from(u128::from(units) * 1000 / u128::from(per_second)).ok()
+}
+
+/// Count `/Type /Page` objects. PDFs that keep their page tree in compressed
+/// object streams report `None`.
+fn pdf_page_count(data: &[u8]) -> Option<i32> {
+    let mut count = 0;
+    let mut rest = data;
+    while let Some(at) = rest.windows(5).position(|window| window == b"/Type") {
+        rest = &rest[at + 5..];
+        let value_at = rest
+            .iter()
+            .position(|byte| !byte.is_ascii_whitespace())
+            .unwrap_or(rest.len());
+        let value = &rest[value_at..];
+        if value.starts_with(b"/Page") && !value.get(5).is_some_and(u8::is_ascii_alphanumeric) {
+            count += 1;
+        }
+    }
+    (count > 0).then_some(count)
+}
+
+fn wav_duration_ms(data: &[u8]) -> Option<i64> {
+    let mut byte_rate = None;
+    let mut offset = 12;
+    while offset + 8 <= data.len() {
+        let id = &data[offset..offset + 4];
+        let size = le_u32(data, offset + 4)? as usize;
+        match id {
+            b"fmt " => byte_rate = le_u32(data, offset + 16),
+            b"data" => return scaled_ms(size as u64, u64::from(byte_rate?)),
+            _ => {}
+        }
+        offset = offset.checked_add(8 + size + size % 2)?;
+    }
+    None
+}
+
+fn flac_duration_ms(data: &[u8]) -> Option<i64> {
+    // STREAMINFO is always the first metadata block, right after `fLaC` and
+    // its 4-byte block header.
+    let info = data.get(8..42)?;
+    let sample_rate =
+        (u32::from(info[10]) << 12) | (u32::from(info[11]) << 4) | (u32::from(info[12]) >> 4);
+    let total_samples = (u64::from(info[13] & 0x0F) << 32) | u64::from(be_u32(info, 14)?);
+    if total_samples == 0 {
+        return None;
+    }
+    scaled_ms(total_samples, u64::from(sample_rate))
+}
+
+/// Iterate ISO-BMFF boxes in `data` as `(type, body)` pairs.
+fn mp4_boxes(data: &[u8]) -> impl Iterator<Item = (&[u8], &[u8])> {
+    let mut offset = 0usize;
+    std::iter::from_fn(move || {
+        let size = be_u32(data, offset)? as usize;
+        let kind = data.get(offset + 4..offset + 8)?;
+        let (header, size) = match size {
+            0 => (8, data.len() - offset),
+            1 => (16, usize::try_from(be_u64(data, offset + 8)?).ok()?),
+            size => (8, size),
+        };
+        if size < header {
+            return None;
+        }
+        let body = data.get(offset + header..offset.checked_add(size)?)?;
+        offset += size;
+        Some((kind, body))
+    })
+}
+
+fn mp4_info(data: &[u8]) -> Option<StreamInfo> {
+    let (_, moov) = mp4_boxes(data).find(|(kind, _)| *kind == b"moov")?;
+    let mut info = StreamInfo {
+        duration_ms: None,
+        width: None,
+        height: None,
+    };
+    for (kind, body) in mp4_boxes(moov) {
+        match kind {
+            b"mvhd" => {
+                info.duration_ms = match body.first()? {
+                    1 => scaled_ms(be_u64(body, 24)?, u64::from(be_u32(body, 20)?)),
+                    _ => scaled_ms(u64::from(be_u32(body, 16)?), u64::from(be_u32(body, 12)?)),
+                };
+            }
+            b"trak" if info.width.is_none() => {
+                let Some((_, tkhd)) = mp4_boxes(body).find(|(kind, _)| *kind == b"tkhd") else {
+                    continue;
+                };
+                let at = if tkhd.first() == Some(&1) { 88 } else { 76 };
+                // 16.16 fixed point; audio tracks report zero.
+                let width = be_u32(tkhd, at).map(|value| value >> 16);
+                let height = be_u32(tkhd, at + 4).map(|value| value >> 16);
+                if let (Some(width @ 1..), Some(height @ 1..)) = (width, height) {
+                    info.width = Some(width);
+                    info.height = Some(height);
+                }
+            }
+            _ => {}
+        }
+    }
+    Some(info)
+}
+
+/// Read an EBML variable-length integer. Returns the value (with the length
+/// marker removed unless `keep_marker`) and its encoded length.
+fn ebml_vint(data: &[u8], keep_marker: bool) -> Option<(u64, usize)> {
+    let first = *data.first()?;
+    let length = first.leading_zeros() as usize + 1;
+    if length > 8 {
+        return None;
+    }
+    let mut value = if keep_marker {
+        u64::from(first)
+    } else {
+        u64::from(first) & (0xFF >> length)
+    };
+    for byte in data.get(1..length)? {
+        value = (value << 8) | u64::from(*byte);
+    }
+    Some((value, length))
+}
+
+/// Iterate EBML elements in `data` as `(id, body)` pairs. An element with an
+/// unknown size extends to the end of `data`.
+fn ebml_elements(data: &[u8]) -> impl Iterator<Item = (u64, &[u8])> {
+    let mut offset = 0usize;
+    std::iter::from_fn(move || {
+        let (id, id_length) = ebml_vint(data.get(offset..)?, true)?;
+        let (size, size_length) = ebml_vint(data.get(offset + id_length..)?, false)?;
+        let start = offset + id_length + size_length;
+        let unknown = size == (1u64 << (7 * size_length)) - 1;
+        let end = if unknown {
+            data.len()
+        } else {
+            start
+                .checked_add(usize::try_from(size).ok()?)?
+                .min(data.len())
+        };
+        offset = end;
+        Some((id, data.get(start..end)?))
+    })
+}
+
+fn ebml_uint(body: &[u8]) -> Option<u64> {
+    (body.len() <= 8).then(|| {
+        body.iter()
+            .fold(0u64, |value, byte| (value << 8) | u64::from(*byte))
+    })
+}
+
+fn ebml_float(body: &[u8]) -> Option<f64> {
+    match body.len() {
+        4 => Some(f64::from(f32::from_be_bytes(body.try_into().ok()?))),
+        8 => Some(f64::from_be_bytes(body.try_into().ok()?)),
+        _ => None,
+    }
+}
+
+fn matroska_info(data: &[u8]) -> Option<StreamInfo> {
+    const SEGMENT: u64 = 0x1853_8067;
+    const INFO: u64 = 0x1549_A966;
+    const TIMECODE_SCALE: u64 = 0x2A_D7B1;
+    const DURATION: u64 = 0x4489;
+    const TRACKS: u64 = 0x1654_AE6B;
+    const TRACK_ENTRY: u64 = 0xAE;
+    const VIDEO: u64 = 0xE0;
+    const PIXEL_WIDTH: u64 = 0xB0;
+    const PIXEL_HEIGHT: u64 = 0xBA;
+    const CLUSTER: u64 = 0x1F43_B675;
+
+    let (_, segment) = ebml_elements(data).find(|(id, _)| *id == SEGMENT)?;
+    let mut info = StreamInfo {
+        duration_ms: None,
+        width: None,
+        height: None,
+    };
+    for (id, body) in ebml_elements(segment) {
+        match id {
+            INFO => {
+                let mut scale = 1_000_000u64;
+                let mut duration = None;
+                for (id, body) in ebml_elements(body) {
+                    match id {
+                        TIMECODE_SCALE => scale = ebml_uint(body).unwrap_or(scale),
+                        DURATION => duration = ebml_float(body),
+                        _ => {}
+                    }
+                }
+                info.duration_ms = duration
+                    .filter(|duration| duration.is_finite() && *duration >= 0.0)
+                    .map(|duration| (duration * scale as f64 / 1_000_000.0).round() as i64);
+            }
+            TRACKS => {
+                let video = ebml_elements(body)
+                    .filter(|(id, _)| *id == TRACK_ENTRY)
+                    .find_map(|(_, entry)| ebml_elements(entry).find(|(id, _)| *id == VIDEO));
+                if let Some((_, video)) = video {
+                    for (id, body) in ebml_elements(video) {
+                        let value = ebml_uint(body).and_then(|value| u32::try_from(value).ok());
+                        match id {
+                            PIXEL_WIDTH => info.width = value,
+                            PIXEL_HEIGHT => info.height = value,
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            // Headers precede the media data; nothing useful follows.
+            CLUSTER => break,
+            _ => {}
+        }
+    }
+    Some(info)
+}
+
+// ── Metadata stripping ────────────────────────────────────────────────────────
+
+const EXIF_HEADER: &[u8] = b"Exif\0\0";
+const XMP_HEADER: &[u8] = b"http://ns.adobe.com/xap/1.0/";
+const EXTENDED_XMP_HEADER: &[u8] = b"http://ns.adobe.com/xmp/extension/";
+const PHOTOSHOP_HEADER: &[u8] = b"Photoshop 3.0";
+
+/// Drop EXIF, XMP and Photoshop (IPTC) segments, re-adding a minimal EXIF
+/// block when the original carried a non-default orientation.
+fn strip_jpeg(data: &[u8]) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(data.len());
+    out.extend_from_slice(data.get(..2)?);
+    let mut orientation = None;
+    let mut exif_at = None;
+    let mut offset = 2;
+    let mut changed = false;
+    loop {
+        if data.get(offset) != Some(&0xFF) {
+            return None;
+        }
+        let marker = *data.get(offset + 1)?;
+        // Standalone markers carry no length.
+        if marker == 0x01 || (0xD0..=0xD7).contains(&marker) || marker == 0xFF {
+            out.push(0xFF);
+            offset += if marker == 0xFF { 1 } else { 2 };
+            if marker != 0xFF {
+                out.push(marker);
+            }
+            continue;
+        }
+        let length = be_u16(data, offset + 2)? as usize;
+        let end = offset.checked_add(2 + length)?;
+        let payload = data.get(offset + 4..end)?;
+        let drop = match marker {
+            0xE1 if payload.starts_with(EXIF_HEADER) => {
+                orientation = orientation.or_else(|| exif_orientation(&payload[6..]));
+                exif_at = exif_at.or(Some(out.len()));
+                true
+            }
+            0xE1 => payload.starts_with(XMP_HEADER) || payload.starts_with(EXTENDED_XMP_HEADER),
+            0xED => payload.starts_with(PHOTOSHOP_HEADER),
+            0xFE => true,
+            _ => false,
+        };
+        if drop {
+            changed = true;
+        } else {
+            if marker == 0xDA {
+                // Start of scan: entropy-coded data runs to the end.
+                out.extend_from_slice(&data[offset..]);
+                if let (Some(at), Some(orientation)) =
+                    (exif_at, orientation.filter(|value| *value != 1))
+                {
+                    out.splice(at..at, orientation_segment(orientation));
+                }
+                return changed.then_some(out);
+            }
+            out.extend_from_slice(&data[offset..end]);
+        }
+        offset = end;
+    }
+}
+
+/// Read the orientation tag from IFD0 of a TIFF-structured EXIF payload.
+fn exif_orientation(tiff: &[u8]) -> Option<u16> {
+    let little_endian = match tiff.get(..2)? {
+        b"II" => true,
+        b"MM" => false,
+        _ => return None,
+    };
+    let u16_at = |at: usize| -> Option<u16> {
+        let bytes: [u8; 2] = tiff.get(at..at + 2)?.try_into().ok()?;
+        Some(if little_endian {
+            u16::from_le_bytes(bytes)
+        } else {
+            u16::from_be_bytes(bytes)
+        })
+    };
+    let u32_at = |at: usize| -> Option<u32> {
+        let bytes: [u8; 4] = tiff.get(at..at + 4)?.try_into().ok()?;
+        Some(if little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    };
+    let ifd = u32_at(4)? as usize;
+    let entries = u16_at(ifd)? as usize;
+    (0..entries)
+        .map(|index| ifd + 2 + index * 12)
+        .find(|entry| u16_at(*entry) == Some(0x0112))
+        .and_then(|entry| u16_at(entry + 8))
+        .filter(|value| (1..=8).contains(value))
+}
+
+/// An APP1 segment holding an EXIF block with only the orientation.
+fn orientation_segment(orientation: u16) -> Vec<u8> {
+    let mut tiff = Vec::with_capacity(26);
+    tiff.extend_from_slice(b"MM\0*");
+    tiff.extend_from_slice(&8u32.to_be_bytes());
+    tiff.extend_from_slice(&1u16.to_be_bytes());
+    tiff.extend_from_slice(&0x0112u16.to_be_bytes());
+    tiff.extend_from_slice(&3u16.to_be_bytes());
+    tiff.extend_from_slice(&1u32.to_be_bytes());
+    tiff.extend_from_slice(&orientation.to_be_bytes());
+    tiff.extend_from_slice(&[0, 0]);
+    tiff.extend_from_slice(&0u32.to_be_bytes());
+
+    let length = (2 + EXIF_HEADER.len() + tiff.len()) as u16;
+    let mut segment = vec![0xFF, 0xE1];
+    segment.extend_from_slice(&length.to_be_bytes());
+    segment.extend_from_slice(EXIF_HEADER);
+    segment.extend_from_slice(&tiff);
+    segment
+}
+
+/// Drop `eXIf`, textual and timestamp chunks.
+fn strip_png(data: &[u8]) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(data.len());
+    out.extend_from_slice(data.get(..8)?);
+    let mut offset = 8;
+    let mut changed = false;
+    while offset < data.len() {
+        let length = be_u32(data, offset)? as usize;
+        let end = offset.checked_add(12 + length)?;
+        let chunk = data.get(offset..end)?;
+        match &chunk[4..8] {
+            b"eXIf" | b"tEXt" | b"iTXt" | b"zTXt" | b"tIME" => changed = true,
+            _ => out.extend_from_slice(chunk),
+        }
+        offset = end;
+    }
+    changed.then_some(out)
+}
+
+/// Drop `EXIF` and `XMP ` chunks and clear their flags in `VP8X`.
+fn strip_webp(data: &[u8]) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(data.len());
+    out.extend_from_slice(data.get(..12)?);
+    let mut offset = 12;
+    let mut changed = false;
+    while offset < data.len() {
+        let size = le_u32(data, offset + 4)? as usize;
+        let end = offset.checked_add(8 + size + size % 2)?.min(data.len());
+        let chunk = data.get(offset..end)?;
+        match &chunk[..4] {
+            b"EXIF" | b"XMP " => changed = true,
+            b"VP8X" => {
+                let flags_at = out.len() + 8;
+                out.extend_from_slice(chunk);
+                if let Some(flags) = out.get_mut(flags_at) {
+                    *flags &= !0x0C;
+                }
+            }
+            _ => out.extend_from_slice(chunk),
+        }
+        offset = end;
+    }
+    if !changed {
+        return None;
+    }
+    let riff_size = u32::try_from(out.len() - 8).ok()?;
+    out[4..8].copy_from_slice(&riff_size.to_le_bytes());
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jpeg_with_exif(orientation: u16) -> Vec<u8> {
+        // Little-endian TIFF with orientation and a GPS IFD pointer.
+        let mut tiff = Vec::new();
+        tiff.extend_from_slice(b"II*\0");
+        tiff.extend_from_slice(&8u32.to_le_bytes());
+        tiff.extend_from_slice(&2u16.to_le_bytes());
+        tiff.extend_from_slice(&0x0112u16.to_le_bytes());
+        tiff.extend_from_slice(&3u16.to_le_bytes());
+        tiff.extend_from_slice(&1u32.to_le_bytes());
+        tiff.extend_from_slice(&orientation.to_le_bytes());
+        tiff.extend_from_slice(&[0, 0]);
+        tiff.extend_from_slice(&0x8825u16.to_le_bytes());
+        tiff.extend_from_slice(&4u16.to_le_bytes());
+        tiff.extend_from_slice(&1u32.to_le_bytes());
+        tiff.extend_from_slice(&38u32.to_le_bytes());
+        tiff.extend_from_slice(&0u32.to_le_bytes());
+        tiff.extend_from_slice(b"GPS-52.5200N-13.4050E");
+
+        let mut jpeg = vec![0xFF, 0xD8];
+        jpeg.extend_from_slice(&[0xFF, 0xE0, 0x00, 0x06]);
+        jpeg.extend_from_slice(b"JFIF");
+        let length = (2 + EXIF_HEADER.len() + tiff.len()) as u16;
+        jpeg.extend_from_slice(&[0xFF, 0xE1]);
+        jpeg.extend_from_slice(&length.to_be_bytes());
+        jpeg.extend_from_slice(EXIF_HEADER);
+        jpeg.extend_from_slice(&tiff);
+        jpeg.extend_from_slice(&[0xFF, 0xDA, 0x00, 0x02, 0x12, 0x34, 0xFF, 0xD9]);
+        jpeg
+    }
+
+    #[test]
+    fn sniffing_ignores_declared_type() {
+        assert_eq!(sniff_mime_type(b"\x89PNG\r\n\x1a\nrest"), Some("image/png"));
+        assert_eq!(sniff_mime_type(b"%PDF-1.7\n"), Some("application/pdf"));
+        assert_eq!(
+            sniff_mime_type(b"\0\0\0\x18ftypisom\0\0\0\0"),
+            Some("video/mp4")
+        );
+        assert_eq!(
+            sniff_mime_type(b"  <svg xmlns=\"http://www.w3.org/2000/svg\"/>"),
+            Some("image/svg+xml")
+        );
+        assert_eq!(sniff_mime_type(b"MZ\x90\0 not media"), None);
+    }
+
+    #[test]
+    fn resolve_rejects_mismatched_declarations() {
+        let png = b"\x89PNG\r\n\x1a\nrest";
+        assert_eq!(resolve_mime_type("image/png", png).unwrap(), "image/png");
+        assert_eq!(
+            resolve_mime_type("application/octet-stream", png).unwrap(),
+            "image/png"
+        );
+        assert!(matches!(
+            resolve_mime_type("image/jpeg", png),
+            Err(MediaError::ContentTypeMismatch { .. })
+        ));
+        assert!(matches!(
+            resolve_mime_type("image/png", b"<html><script>"),
+            Err(MediaError::UnrecognizedContent(_))
+        ));
+        assert_eq!(
+            resolve_mime_type("audio/mp4", b"\0\0\0\x18ftypisom\0\0\0\0").unwrap(),
+            "audio/mp4"
+        );
+        assert_eq!(
+            resolve_mime_type("image/JPG; charset=binary", &jpeg_with_exif(1)).unwrap(),
+            "image/jpeg"
+        );
+    }
+
+    #[test]
+    fn jpeg_exif_is_stripped_but_orientation_survives() {
+        let original = jpeg_with_exif(6);
+        let stripped = strip_image_metadata("image/jpeg", bytes::Bytes::from(original.clone()));
+        assert!(!contains(&stripped, b"GPS-52"));
+        assert!(contains(&stripped, b"JFIF"));
+        assert!(stripped.ends_with(&[0xFF, 0xDA, 0x00, 0x02, 0x12, 0x34, 0xFF, 0xD9]));
+        let exif_at = stripped
+            .windows(EXIF_HEADER.len())
+            .position(|window| window == EXIF_HEADER)
+            .expect("orientation segment");
+        assert_eq!(exif_orientation(&stripped[exif_at + 6..]), Some(6));
+
+        let upright = strip_image_metadata("image/jpeg", bytes::Bytes::from(jpeg_with_exif(1)));
+        assert!(!contains(&upright, EXIF_HEADER));
+    }
+
+    #[test]
+    fn malformed_images_are_left_untouched() {
+        let data = bytes::Bytes::from_static(b"\xFF\xD8\xFF\xE1\xFF\xFFtruncated");
+        assert_eq!(strip_image_metadata("image/jpeg", data.clone()), data);
+    }
+
+    #[test]
+    fn wav_and_pdf_metadata_is_extracted() {
+        let mut wav = Vec::new();
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&0u32.to_le_bytes());
+        wav.extend_from_slice(b"WAVEfmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&[1, 0, 1, 0]);
+        wav.extend_from_slice(&8000u32.to_le_bytes());
+        wav.extend_from_slice(&16000u32.to_le_bytes());
+        wav.extend_from_slice(&[2, 0, 16, 0]);
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&24000u32.to_le_bytes());
+        assert_eq!(extract_metadata("audio/wav", &wav).duration_ms, Some(1500));
+
+        let pdf = b"%PDF-1.4\n1 0 obj << /Type /Pages /Count 2 >>\n\
+            2 0 obj << /Type /Page >>\n3 0 obj << /Type/Page /Parent 1 0 R >>";
+        assert_eq!(extract_metadata("application/pdf", pdf).page_count, Some(2));
+    }
+
+    #[test]
+    fn mp4_duration_and_size_are_extracted() {
+        fn mp4_box(kind: &[u8], body: &[u8]) -> Vec<u8> {
+            let mut out = ((body.len() + 8) as u32).to_be_bytes().to_vec();
+            out.extend_from_slice(kind);
+            out.extend_from_slice(body);
+            out
+        }
+        let mut mvhd = vec![0u8; 100];
+        mvhd[12..16].copy_from_slice(&1000u32.to_be_bytes());
+        mvhd[16..20].copy_from_slice(&12_345u32.to_be_bytes());
+        let mut tkhd = vec![0u8; 84];
+        tkhd[76..80].copy_from_slice(&(1280u32 << 16).to_be_bytes());
+        tkhd[80..84].copy_from_slice(&(720u32 << 16).to_be_bytes());
+        let trak = mp4_box(b"trak", &mp4_box(b"tkhd", &tkhd));
+        let moov = mp4_box(b"moov", &[mp4_box(b"mvhd", &mvhd), trak].concat());
+        let file = [mp4_box(b"ftyp", b"isom\0\0\0\0"), moov].concat();
+
+        let metadata = extract_metadata("video/mp4", &file);
+        assert_eq!(metadata.duration_ms, Some(12_345));
+        assert_eq!((metadata.width, metadata.height), (Some(1280), Some(720)));
+    }
+}
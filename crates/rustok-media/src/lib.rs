@@ -1,23 +1,37 @@
 pub mod controllers;
+pub mod derivatives;
 pub mod dto;
 pub mod entities;
 pub mod error;
 pub mod graphql;
+pub mod ingest;
+pub mod migrations;
 pub mod service;
+pub mod usage;
 
 use async_trait::async_trait;
 use rustok_core::permissions::{Action, Permission, Resource};
 use rustok_core::{MigrationSource, RusToKModule};
 use sea_orm_migration::MigrationTrait;
 
+pub use derivatives::{FocalPoint, ImageFit, ImageFormat, ImagePreset};
 pub use dto::{
-    MediaImageDescriptor, MediaItem, MediaTranslationItem, UploadInput, UpsertTranslationInput,
-    ALLOWED_MIME_PREFIXES, DEFAULT_MAX_SIZE,
+    CreateMediaFolderInput, CreateUploadInput, MediaBrokenReference, MediaDerivativeItem,
+    MediaFolderItem, MediaImageDescriptor, MediaItem, MediaOrphanReport, MediaSearchFilter,
+    MediaTranslationItem, MediaUsageItem, UploadInput, UploadSessionItem, UploadSessionStatus,
+    UpsertTranslationInput, ALLOWED_MIME_PREFIXES, DEFAULT_MAX_SIZE, MAX_DIRECT_UPLOAD_SIZE,
+    MULTIPART_THRESHOLD, UPLOAD_PART_SIZE,
 };
 pub use entities::*;
 pub use error::{MediaError, Result};
 pub use graphql::{MediaMutation, MediaQuery};
+pub use ingest::IngestOptions;
 pub use service::MediaService;
+pub use usage::{
+    collect_json_references, media_reference_target, media_usage_registry_from_extensions,
+    register_media_usage_provider, MediaReference, MediaReferenceTarget, MediaUsageProvider,
+    MediaUsageRegistry, MediaUsageRegistryError,
+};
 
 pub struct MediaModule;
 
@@ -39,6 +53,10 @@ impl RusToKModule for MediaModule {
         env!("CARGO_PKG_VERSION")
     }
 
+    fn dependencies(&self) -> &[&'static str] {
+        &["taxonomy"]
+    }
+
     fn permissions(&self) -> Vec<Permission> {
         vec![
             Permission::new(Resource::Media, Action::Create),
@@ -53,6 +71,10 @@ impl RusToKModule for MediaModule {
 
 impl MigrationSource for MediaModule {
     fn migrations(&self) -> Vec<Box<dyn MigrationTrait>> {
-        Vec::new()
+        migrations::migrations()
+    }
+
+    fn migration_dependencies(&self) -> Vec<rustok_core::MigrationDependencyDescriptor> {
+        migrations::migration_dependencies()
     }
 }
@@ -0,0 +1,196 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(MediaImagePresets::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(MediaImagePresets::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(MediaImagePresets::TenantId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MediaImagePresets::Name)
+                            .string_len(64)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MediaImagePresets::Width)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(MediaImagePresets::Height).integer())
+                    .col(
+                        ColumnDef::new(MediaImagePresets::Fit)
+                            .string_len(16)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MediaImagePresets::Format)
+                            .string_len(16)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(MediaImagePresets::Quality).integer())
+                    .col(
+                        ColumnDef::new(MediaImagePresets::Eager)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .col(
+                        ColumnDef::new(MediaImagePresets::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MediaImagePresets::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_media_image_presets_tenant_name")
+                    .table(MediaImagePresets::Table)
+                    .col(MediaImagePresets::TenantId)
+                    .col(MediaImagePresets::Name)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(MediaDerivatives::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(MediaDerivatives::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(MediaDerivatives::TenantId).uuid().not_null())
+                    .col(ColumnDef::new(MediaDerivatives::MediaId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(MediaDerivatives::Preset)
+                            .string_len(64)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MediaDerivatives::Fingerprint)
+                            .string_len(128)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MediaDerivatives::StoragePath)
+                            .string_len(1024)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MediaDerivatives::MimeType)
+                            .string_len(64)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(MediaDerivatives::Width).integer().not_null())
+                    .col(
+                        ColumnDef::new(MediaDerivatives::Height)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MediaDerivatives::Size)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MediaDerivatives::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_media_derivatives_media_preset")
+                    .table(MediaDerivatives::Table)
+                    .col(MediaDerivatives::MediaId)
+                    .col(MediaDerivatives::Preset)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_media_derivatives_tenant_preset")
+                    .table(MediaDerivatives::Table)
+                    .col(MediaDerivatives::TenantId)
+                    .col(MediaDerivatives::Preset)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(MediaDerivatives::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(MediaImagePresets::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum MediaImagePresets {
+    Table,
+    Id,
+    TenantId,
+    Name,
+    Width,
+    Height,
+    Fit,
+    Format,
+    Quality,
+    Eager,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+enum MediaDerivatives {
+    Table,
+    Id,
+    TenantId,
+    MediaId,
+    Preset,
+    Fingerprint,
+    StoragePath,
+    MimeType,
+    Width,
+    Height,
+    Size,
+    CreatedAt,
+}
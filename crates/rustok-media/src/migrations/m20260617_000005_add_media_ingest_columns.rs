@@ -0,0 +1,67 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // One column per statement: SQLite cannot combine ALTER TABLE actions.
+        for column in [
+            ColumnDef::new(Media::DurationMs).big_integer().to_owned(),
+            ColumnDef::new(Media::PageCount).integer().to_owned(),
+            ColumnDef::new(Media::ContentHash).string_len(64).to_owned(),
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Media::Table)
+                        .add_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_media_tenant_content_hash")
+                    .table(Media::Table)
+                    .col(Media::TenantId)
+                    .col(Media::ContentHash)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_media_tenant_content_hash")
+                    .table(Media::Table)
+                    .to_owned(),
+            )
+            .await?;
+        for column in [Media::ContentHash, Media::PageCount, Media::DurationMs] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Media::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum Media {
+    Table,
+    TenantId,
+    DurationMs,
+    PageCount,
+    ContentHash,
+}
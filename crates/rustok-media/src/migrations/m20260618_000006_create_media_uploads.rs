@@ -0,0 +1,112 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(MediaUploads::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(MediaUploads::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(MediaUploads::TenantId).uuid().not_null())
+                    .col(ColumnDef::new(MediaUploads::UploadedBy).uuid())
+                    .col(
+                        ColumnDef::new(MediaUploads::OriginalName)
+                            .string_len(512)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MediaUploads::ContentType)
+                            .string_len(255)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(MediaUploads::Size).big_integer().not_null())
+                    .col(
+                        ColumnDef::new(MediaUploads::StoragePath)
+                            .string_len(1024)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(MediaUploads::MultipartUploadId).string_len(1024))
+                    .col(ColumnDef::new(MediaUploads::PartSize).big_integer())
+                    .col(
+                        ColumnDef::new(MediaUploads::Status)
+                            .string_len(16)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(MediaUploads::MediaId).uuid())
+                    .col(
+                        ColumnDef::new(MediaUploads::ExpiresAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MediaUploads::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MediaUploads::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_media_uploads_status_expires")
+                    .table(MediaUploads::Table)
+                    .col(MediaUploads::Status)
+                    .col(MediaUploads::ExpiresAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_media_uploads_storage_path")
+                    .table(MediaUploads::Table)
+                    .col(MediaUploads::StoragePath)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(MediaUploads::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum MediaUploads {
+    Table,
+    Id,
+    TenantId,
+    UploadedBy,
+    OriginalName,
+    ContentType,
+    Size,
+    StoragePath,
+    MultipartUploadId,
+    PartSize,
+    Status,
+    MediaId,
+    ExpiresAt,
+    CreatedAt,
+    UpdatedAt,
+}
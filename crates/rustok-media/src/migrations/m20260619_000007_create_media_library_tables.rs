@@ -0,0 +1,271 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(MediaFolders::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(MediaFolders::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(MediaFolders::TenantId).uuid().not_null())
+                    .col(ColumnDef::new(MediaFolders::ParentId).uuid())
+                    .col(
+                        ColumnDef::new(MediaFolders::Name)
+                            .string_len(255)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MediaFolders::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(MediaFolders::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_media_folders_parent")
+                            .from(MediaFolders::Table, MediaFolders::ParentId)
+                            .to(MediaFolders::Table, MediaFolders::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_media_folders_tenant_parent")
+                    .table(MediaFolders::Table)
+                    .col(MediaFolders::TenantId)
+                    .col(MediaFolders::ParentId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Media::Table)
+                    .add_column(ColumnDef::new(Media::FolderId).uuid())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_media_tenant_folder")
+                    .table(Media::Table)
+                    .col(Media::TenantId)
+                    .col(Media::FolderId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(MediaTags::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(MediaTags::MediaId).uuid().not_null())
+                    .col(ColumnDef::new(MediaTags::TermId).uuid().not_null())
+                    .col(ColumnDef::new(MediaTags::TenantId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(MediaTags::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(MediaTags::MediaId)
+                            .col(MediaTags::TermId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_media_tags_media")
+                            .from(MediaTags::Table, MediaTags::MediaId)
+                            .to(Media::Table, Media::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_media_tags_term")
+                            .from(MediaTags::Table, MediaTags::TermId)
+                            .to(TaxonomyTerms::Table, TaxonomyTerms::Id)
+                            .on_update(ForeignKeyAction::Cascade)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_media_tags_tenant_term")
+                    .table(MediaTags::Table)
+                    .col(MediaTags::TenantId)
+                    .col(MediaTags::TermId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(MediaUsages::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(MediaUsages::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(MediaUsages::TenantId).uuid().not_null())
+                    .col(ColumnDef::new(MediaUsages::MediaId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(MediaUsages::OwnerKind)
+                            .string_len(64)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(MediaUsages::OwnerId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(MediaUsages::Field)
+                            .string_len(255)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(MediaUsages::Label).string_len(255))
+                    .col(
+                        ColumnDef::new(MediaUsages::IndexedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_media_usages_media")
+                            .from(MediaUsages::Table, MediaUsages::MediaId)
+                            .to(Media::Table, Media::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_media_usages_tenant_media")
+                    .table(MediaUsages::Table)
+                    .col(MediaUsages::TenantId)
+                    .col(MediaUsages::MediaId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_media_usages_owner")
+                    .table(MediaUsages::Table)
+                    .col(MediaUsages::TenantId)
+                    .col(MediaUsages::OwnerKind)
+                    .col(MediaUsages::OwnerId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(MediaUsages::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(MediaTags::Table).to_owned())
+            .await?;
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_media_tenant_folder")
+                    .table(Media::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Media::Table)
+                    .drop_column(Media::FolderId)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(MediaFolders::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Media {
+    Table,
+    Id,
+    TenantId,
+    FolderId,
+}
+
+#[derive(Iden)]
+enum MediaFolders {
+    Table,
+    Id,
+    TenantId,
+    ParentId,
+    Name,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+enum MediaTags {
+    Table,
+    MediaId,
+    TermId,
+    TenantId,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum MediaUsages {
+    Table,
+    Id,
+    TenantId,
+    MediaId,
+    OwnerKind,
+    OwnerId,
+    Field,
+    Label,
+    IndexedAt,
+}
+
+#[derive(Iden)]
+enum TaxonomyTerms {
+    Table,
+    Id,
+}
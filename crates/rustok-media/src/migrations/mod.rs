@@ -0,0 +1,23 @@
+mod m20260616_000004_create_media_derivative_tables;
+mod m20260617_000005_add_media_ingest_columns;
+mod m20260618_000006_create_media_uploads;
+mod m20260619_000007_create_media_library_tables;
+
+use rustok_core::MigrationDependencyDescriptor;
+use sea_orm_migration::MigrationTrait;
+
+pub fn migrations() -> Vec<Box<dyn MigrationTrait>> {
+    vec![
+        Box::new(m20260616_000004_create_media_derivative_tables::Migration),
+        Box::new(m20260617_000005_add_media_ingest_columns::Migration),
+        Box::new(m20260618_000006_create_media_uploads::Migration),
+        Box::new(m20260619_000007_create_media_library_tables::Migration),
+    ]
+}
+
+pub fn migration_dependencies() -> Vec<MigrationDependencyDescriptor> {
+    vec![MigrationDependencyDescriptor::new(
+        "m20260619_000007_create_media_library_tables",
+        vec!["m20260329_000001_create_taxonomy_tables"],
+    )]
+}
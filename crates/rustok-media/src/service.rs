@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use chrono::Utc;
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
@@ -9,6 +11,7 @@ use rustok_core::generate_id;
 use rustok_storage::StorageService;
 
 use crate::{
+    derivatives::is_raster_image,
     dto::{
         MediaItem, MediaTranslationItem, UploadInput, UpsertTranslationInput,
         ALLOWED_MIME_PREFIXES, DEFAULT_MAX_SIZE,
@@ -20,29 +23,83 @@ use crate::{
         },
     },
     error::{MediaError, Result},
+    ingest::{
+        content_hash, extract_metadata, resolve_mime_type, strip_image_metadata, ExtractedMetadata,
+        IngestOptions,
+    },
+    usage::MediaUsageRegistry,
 };
 
+mod derivatives;
+mod library;
+mod uploads;
+mod usage;
+
+fn ensure_allowed_mime_type(mime_type: &str) -> Result<()> {
+    if !ALLOWED_MIME_PREFIXES
+        .iter()
+        .any(|p| mime_type.starts_with(p))
+    {
+        return Err(MediaError::UnsupportedMimeType(mime_type.to_string()));
+    }
+    Ok(())
+}
+
+/// An object already in storage, ready to be recorded as media.
+struct StoredObject {
+    tenant_id: Uuid,
+    uploaded_by: Option<Uuid>,
+    original_name: String,
+    mime_type: String,
+    path: String,
+    size: u64,
+    extracted: ExtractedMetadata,
+    content_hash: Option<String>,
+}
+
 pub struct MediaService {
     db: DatabaseConnection,
     storage: StorageService,
+    ingest: IngestOptions,
+    usage: Option<Arc<MediaUsageRegistry>>,
 }
 
 impl MediaService {
     pub fn new(db: DatabaseConnection, storage: StorageService) -> Self {
-        Self { db, storage }
+        Self {
+            db,
+            storage,
+            ingest: IngestOptions::default(),
+            usage: None,
+        }
+    }
+
+    pub fn with_ingest_options(mut self, ingest: IngestOptions) -> Self {
+        self.ingest = ingest;
+        self
+    }
+
+    /// Providers feeding the where-used index; see [`crate::usage`].
+    pub fn with_usage_registry(mut self, registry: Option<Arc<MediaUsageRegistry>>) -> Self {
+        self.usage = registry;
+        self
     }
 
     // ── Upload ────────────────────────────────────────────────────────────────
 
     /// Validate, store, and record a new media upload.
+    ///
+    /// The stored MIME type is detected from the file content and the
+    /// declared type must agree with it. Dimensions, duration and page count
+    /// are read from the file headers, and image metadata is stripped unless
+    /// disabled via [`IngestOptions`]. Re-uploading content the tenant
+    /// already has returns the existing asset.
+    ///
+    /// Raster images get eager preset derivatives rendered; a derivative
+    /// failure is logged and does not fail the upload, since derivatives can
+    /// be rendered on demand later.
     pub async fn upload(&self, input: UploadInput) -> Result<MediaItem> {
         // Validation
-        if !ALLOWED_MIME_PREFIXES
-            .iter()
-            .any(|p| input.content_type.starts_with(p))
-        {
-            return Err(MediaError::UnsupportedMimeType(input.content_type.clone()));
-        }
         let size = input.data.len() as u64;
         if size > DEFAULT_MAX_SIZE {
             return Err(MediaError::FileTooLarge {
@@ -50,52 +107,45 @@ impl MediaService {
                 max: DEFAULT_MAX_SIZE,
             });
         }
+        let mime_type = resolve_mime_type(&input.content_type, &input.data)?;
+        ensure_allowed_mime_type(&mime_type)?;
 
-        // Generate storage path and persist to backend
-        let path = StorageService::generate_path(input.tenant_id, &input.original_name);
-        let uploaded = self
-            .storage
-            .store(&path, input.data, &input.content_type)
-            .await?;
-
-        // Sanitise filename (keep extension + uuid)
-        let filename = std::path::Path::new(&path)
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or(&path)
-            .to_string();
-
-        let id = generate_id();
-        let now = Utc::now().fixed_offset();
+        let hash = content_hash(&input.data);
+        if let Some(existing) = self.find_duplicate(input.tenant_id, &hash).await? {
+            return Ok(self.to_item(existing));
+        }
 
-        let active = MediaActiveModel {
-            id: Set(id),
-            tenant_id: Set(input.tenant_id),
-            uploaded_by: Set(input.uploaded_by),
-            filename: Set(filename),
-            original_name: Set(input.original_name.clone()),
-            mime_type: Set(input.content_type.clone()),
-            size: Set(uploaded.size as i64),
-            storage_path: Set(path.clone()),
-            storage_driver: Set(self.storage.backend_name().to_string()),
-            width: Set(None),
-            height: Set(None),
-            metadata: Set(serde_json::json!({})),
-            created_at: Set(now),
+        let extracted = extract_metadata(&mime_type, &input.data);
+        let data = if self.ingest.strip_metadata {
+            strip_image_metadata(&mime_type, input.data)
+        } else {
+            input.data
         };
 
-        let model = active.insert(&self.db).await?;
-        Ok(self.to_item(model))
+        // Generate storage path and persist to backend
+        let path = StorageService::generate_path(input.tenant_id, &input.original_name);
+        let uploaded = self.storage.store(&path, data.clone(), &mime_type).await?;
+
+        self.record_media(
+            StoredObject {
+                tenant_id: input.tenant_id,
+                uploaded_by: input.uploaded_by,
+                original_name: input.original_name,
+                mime_type,
+                path,
+                size: uploaded.size,
+                extracted,
+                content_hash: Some(hash),
+            },
+            Some(&data),
+        )
+        .await
     }
 
     // ── Queries ───────────────────────────────────────────────────────────────
 
     pub async fn get(&self, tenant_id: Uuid, id: Uuid) -> Result<MediaItem> {
-        let model = MediaEntity::find_by_id(id)
-            .filter(MediaCol::TenantId.eq(tenant_id))
-            .one(&self.db)
-            .await?
-            .ok_or(MediaError::NotFound(id))?;
+        let model = self.find_media(tenant_id, id).await?;
         Ok(self.to_item(model))
     }
 
@@ -115,29 +165,6 @@ impl MediaService {
         Ok((items.into_iter().map(|m| self.to_item(m)).collect(), total))
     }
 
-    // ── Delete ────────────────────────────────────────────────────────────────
-
-    pub async fn delete(&self, tenant_id: Uuid, id: Uuid) -> Result<()> {
-        let model = MediaEntity::find_by_id(id)
-            .filter(MediaCol::TenantId.eq(tenant_id))
-            .one(&self.db)
-            .await?
-            .ok_or(MediaError::NotFound(id))?;
-
-        // Best-effort storage cleanup — log but don't fail on storage errors
-        if let Err(e) = self.storage.delete(&model.storage_path).await {
-            tracing::warn!(
-                media_id = %id,
-                path = %model.storage_path,
-                error = %e,
-                "Failed to delete media object from storage; DB record will still be removed"
-            );
-        }
-
-        MediaEntity::delete_by_id(id).exec(&self.db).await?;
-        Ok(())
-    }
-
     // ── Translations ──────────────────────────────────────────────────────────
 
     pub async fn upsert_translation(
@@ -209,6 +236,74 @@ impl MediaService {
 
     // ── Private ───────────────────────────────────────────────────────────────
 
+    /// Existing media of the tenant with the same content, when
+    /// deduplication is enabled.
+    async fn find_duplicate(&self, tenant_id: Uuid, hash: &str) -> Result<Option<media::Model>> {
+        if !self.ingest.deduplicate {
+            return Ok(None);
+        }
+        let existing = MediaEntity::find()
+            .filter(MediaCol::TenantId.eq(tenant_id))
+            .filter(MediaCol::ContentHash.eq(hash))
+            .one(&self.db)
+            .await?;
+        if let Some(existing) = &existing {
+            tracing::debug!(
+                media_id = %existing.id,
+                "Duplicate upload resolved to existing media"
+            );
+        }
+        Ok(existing)
+    }
+
+    /// Insert the media row for an object already in storage and render
+    /// eager derivatives from `source` when it is a raster image.
+    async fn record_media(&self, stored: StoredObject, source: Option<&[u8]>) -> Result<MediaItem> {
+        // Sanitise filename (keep extension + uuid)
+        let filename = std::path::Path::new(&stored.path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(&stored.path)
+            .to_string();
+
+        let id = generate_id();
+        let now = Utc::now().fixed_offset();
+
+        let active = MediaActiveModel {
+            id: Set(id),
+            tenant_id: Set(stored.tenant_id),
+            uploaded_by: Set(stored.uploaded_by),
+            filename: Set(filename),
+            original_name: Set(stored.original_name),
+            mime_type: Set(stored.mime_type),
+            size: Set(stored.size as i64),
+            storage_path: Set(stored.path),
+            storage_driver: Set(self.storage.backend_name().to_string()),
+            width: Set(stored.extracted.width.map(|width| width as i32)),
+            height: Set(stored.extracted.height.map(|height| height as i32)),
+            duration_ms: Set(stored.extracted.duration_ms),
+            page_count: Set(stored.extracted.page_count),
+            content_hash: Set(stored.content_hash),
+            folder_id: Set(None),
+            metadata: Set(serde_json::json!({})),
+            created_at: Set(now),
+        };
+
+        let model = active.insert(&self.db).await?;
+        if let Some(source) =
+            source.filter(|_| is_raster_image(&model.mime_type) && stored.extracted.width.is_some())
+        {
+            if let Err(error) = self.render_eager_derivatives(&model, source).await {
+                tracing::warn!(
+                    media_id = %model.id,
+                    error = %error,
+                    "Failed to render image derivatives on upload"
+                );
+            }
+        }
+        Ok(self.to_item(model))
+    }
+
     fn to_item(&self, m: media::Model) -> MediaItem {
         let public_url = self.storage.public_url(&m.storage_path);
         MediaItem {
@@ -224,6 +319,10 @@ impl MediaService {
             public_url,
             width: m.width,
             height: m.height,
+            duration_ms: m.duration_ms,
+            page_count: m.page_count,
+            content_hash: m.content_hash,
+            folder_id: m.folder_id,
             metadata: m.metadata,
             created_at: m.created_at.with_timezone(&Utc),
         }
@@ -0,0 +1,508 @@
+use chrono::Utc;
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, Set};
+use uuid::Uuid;
+
+use rustok_core::generate_id;
+
+use super::MediaService;
+use crate::{
+    derivatives::{
+        default_presets, is_raster_image, render_derivative, FocalPoint, ImageFit, ImageFormat,
+        ImagePreset,
+    },
+    dto::{MediaDerivativeItem, MediaItem},
+    entities::{
+        media::{self, Column as MediaCol, Entity as MediaEntity},
+        media_derivative::{
+            self, ActiveModel as DerivativeActiveModel, Column as DerivativeCol,
+            Entity as DerivativeEntity,
+        },
+        media_image_preset::{
+            self, ActiveModel as PresetActiveModel, Column as PresetCol, Entity as PresetEntity,
+        },
+    },
+    error::{MediaError, Result},
+};
+
+impl MediaService {
+    // ── Presets ───────────────────────────────────────────────────────────────
+
+    /// Effective presets for a tenant: the built-in defaults overridden by
+    /// tenant presets with the same name, followed by custom presets.
+    pub async fn image_presets(&self, tenant_id: Uuid) -> Result<Vec<ImagePreset>> {
+        let overrides = PresetEntity::find()
+            .filter(PresetCol::TenantId.eq(tenant_id))
+            .order_by_asc(PresetCol::Name)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(preset_from_model)
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut presets = default_presets();
+        for preset in overrides {
+            match presets.iter_mut().find(|known| known.name == preset.name) {
+                Some(known) => *known = preset,
+                None => presets.push(preset),
+            }
+        }
+        Ok(presets)
+    }
+
+    pub async fn image_preset(&self, tenant_id: Uuid, name: &str) -> Result<ImagePreset> {
+        self.image_presets(tenant_id)
+            .await?
+            .into_iter()
+            .find(|preset| preset.name == name)
+            .ok_or_else(|| MediaError::PresetNotFound(name.to_string()))
+    }
+
+    /// Create or replace a tenant preset and re-render derivatives that were
+    /// produced with the previous settings. Returns the preset and the number
+    /// of derivatives rendered.
+    pub async fn upsert_image_preset(
+        &self,
+        tenant_id: Uuid,
+        preset: ImagePreset,
+    ) -> Result<(ImagePreset, u64)> {
+        preset.validate()?;
+        let now = Utc::now().fixed_offset();
+        let existing = PresetEntity::find()
+            .filter(PresetCol::TenantId.eq(tenant_id))
+            .filter(PresetCol::Name.eq(&preset.name))
+            .one(&self.db)
+            .await?;
+
+        match existing {
+            Some(existing) => {
+                let mut active: PresetActiveModel = existing.into();
+                active.width = Set(preset.width as i32);
+                active.height = Set(preset.height.map(|height| height as i32));
+                active.fit = Set(preset.fit.as_str().to_string());
+                active.format = Set(preset.format.as_str().to_string());
+                active.quality = Set(preset.quality.map(i32::from));
+                active.eager = Set(preset.eager);
+                active.updated_at = Set(now);
+                active.update(&self.db).await?;
+            }
+            None => {
+                PresetActiveModel {
+                    id: Set(generate_id()),
+                    tenant_id: Set(tenant_id),
+                    name: Set(preset.name.clone()),
+                    width: Set(preset.width as i32),
+                    height: Set(preset.height.map(|height| height as i32)),
+                    fit: Set(preset.fit.as_str().to_string()),
+                    format: Set(preset.format.as_str().to_string()),
+                    quality: Set(preset.quality.map(i32::from)),
+                    eager: Set(preset.eager),
+                    created_at: Set(now),
+                    updated_at: Set(now),
+                }
+                .insert(&self.db)
+                .await?;
+            }
+        }
+
+        let regenerated = self.regenerate_preset(tenant_id, &preset).await?;
+        Ok((preset, regenerated))
+    }
+
+    /// Remove a tenant preset. Built-in presets revert to their defaults and
+    /// are re-rendered; derivatives of custom presets are deleted.
+    pub async fn delete_image_preset(&self, tenant_id: Uuid, name: &str) -> Result<bool> {
+        let result = PresetEntity::delete_many()
+            .filter(PresetCol::TenantId.eq(tenant_id))
+            .filter(PresetCol::Name.eq(name))
+            .exec(&self.db)
+            .await?;
+        if result.rows_affected == 0 {
+            return Ok(false);
+        }
+
+        match default_presets()
+            .into_iter()
+            .find(|preset| preset.name == name)
+        {
+            Some(default) => {
+                self.regenerate_preset(tenant_id, &default).await?;
+            }
+            None => {
+                let derivatives = DerivativeEntity::find()
+                    .filter(DerivativeCol::TenantId.eq(tenant_id))
+                    .filter(DerivativeCol::Preset.eq(name))
+                    .all(&self.db)
+                    .await?;
+                self.delete_derivatives(derivatives).await?;
+            }
+        }
+        Ok(true)
+    }
+
+    // ── Derivatives ───────────────────────────────────────────────────────────
+
+    pub async fn list_derivatives(
+        &self,
+        tenant_id: Uuid,
+        media_id: Uuid,
+    ) -> Result<Vec<MediaDerivativeItem>> {
+        let _ = self.get(tenant_id, media_id).await?;
+        let presets = self.image_presets(tenant_id).await?;
+        let derivatives = DerivativeEntity::find()
+            .filter(DerivativeCol::TenantId.eq(tenant_id))
+            .filter(DerivativeCol::MediaId.eq(media_id))
+            .order_by_asc(DerivativeCol::Width)
+            .all(&self.db)
+            .await?;
+        Ok(derivatives
+            .into_iter()
+            .map(|derivative| {
+                let responsive = presets
+                    .iter()
+                    .find(|preset| preset.name == derivative.preset)
+                    .is_some_and(ImagePreset::preserves_aspect_ratio);
+                self.to_derivative_item(derivative, responsive)
+            })
+            .collect())
+    }
+
+    /// Return the derivative for `preset`, rendering it first if it is
+    /// missing or was rendered with different settings.
+    pub async fn derivative(
+        &self,
+        tenant_id: Uuid,
+        media_id: Uuid,
+        preset: &str,
+    ) -> Result<MediaDerivativeItem> {
+        let media = self.find_media(tenant_id, media_id).await?;
+        let preset = self.image_preset(tenant_id, preset).await?;
+        if !is_raster_image(&media.mime_type) {
+            return Err(MediaError::UnsupportedMimeType(media.mime_type));
+        }
+        let focal_point = FocalPoint::from_metadata(&media.metadata);
+        let existing = self.find_derivative(media.id, &preset.name).await?;
+        let derivative = match existing {
+            Some(existing) if existing.fingerprint == preset.fingerprint(focal_point) => existing,
+            existing => {
+                let source = self.storage.read(&media.storage_path).await?;
+                self.render_and_store(&media, &source, &preset, existing)
+                    .await?
+            }
+        };
+        Ok(self.to_derivative_item(derivative, preset.preserves_aspect_ratio()))
+    }
+
+    /// Re-render every eager preset and every derivative that already exists
+    /// for the media item.
+    pub async fn regenerate_derivatives(
+        &self,
+        tenant_id: Uuid,
+        media_id: Uuid,
+    ) -> Result<Vec<MediaDerivativeItem>> {
+        let media = self.find_media(tenant_id, media_id).await?;
+        if !is_raster_image(&media.mime_type) {
+            return Err(MediaError::UnsupportedMimeType(media.mime_type));
+        }
+        let presets = self.image_presets(tenant_id).await?;
+        let source = self.storage.read(&media.storage_path).await?;
+        for preset in &presets {
+            let existing = self.find_derivative(media.id, &preset.name).await?;
+            if preset.eager || existing.is_some() {
+                self.render_and_store(&media, &source, preset, existing)
+                    .await?;
+            }
+        }
+        self.list_derivatives(tenant_id, media_id).await
+    }
+
+    /// Store the focal point used by `cover` presets and re-render the
+    /// cropped derivatives. `None` resets it to the image centre.
+    pub async fn set_focal_point(
+        &self,
+        tenant_id: Uuid,
+        media_id: Uuid,
+        focal_point: Option<FocalPoint>,
+    ) -> Result<MediaItem> {
+        let media = self.find_media(tenant_id, media_id).await?;
+        let mut metadata = match media.metadata.clone() {
+            serde_json::Value::Object(map) => map,
+            _ => serde_json::Map::new(),
+        };
+        match focal_point {
+            Some(focal_point) => {
+                metadata.insert(
+                    "focal_point".to_string(),
+                    serde_json::to_value(focal_point)
+                        .map_err(|error| MediaError::Image(error.to_string()))?,
+                );
+            }
+            None => {
+                metadata.remove("focal_point");
+            }
+        }
+        let mut active: media::ActiveModel = media.into();
+        active.metadata = Set(serde_json::Value::Object(metadata));
+        let media = active.update(&self.db).await?;
+
+        if is_raster_image(&media.mime_type) {
+            let presets = self.image_presets(tenant_id).await?;
+            let mut source = None;
+            for preset in presets
+                .iter()
+                .filter(|preset| preset.fit == ImageFit::Cover)
+            {
+                let existing = self.find_derivative(media.id, &preset.name).await?;
+                if !preset.eager && existing.is_none() {
+                    continue;
+                }
+                if source.is_none() {
+                    source = Some(self.storage.read(&media.storage_path).await?);
+                }
+                if let Some(source) = source.as_ref() {
+                    self.render_and_store(&media, source, preset, existing)
+                        .await?;
+                }
+            }
+        }
+        Ok(self.to_item(media))
+    }
+
+    // ── Internal ──────────────────────────────────────────────────────────────
+
+    /// Render eager presets for a freshly uploaded image.
+    pub(super) async fn render_eager_derivatives(
+        &self,
+        media: &media::Model,
+        source: &[u8],
+    ) -> Result<()> {
+        for preset in self.image_presets(media.tenant_id).await? {
+            if preset.eager {
+                self.render_and_store(media, source, &preset, None).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Delete derivative rows together with their storage objects.
+    pub(super) async fn delete_derivatives(
+        &self,
+        derivatives: Vec<media_derivative::Model>,
+    ) -> Result<()> {
+        for derivative in derivatives {
+            if let Err(error) = self.storage.delete(&derivative.storage_path).await {
+                tracing::warn!(
+                    media_id = %derivative.media_id,
+                    preset = %derivative.preset,
+                    path = %derivative.storage_path,
+                    error = %error,
+                    "Failed to delete media derivative from storage"
+                );
+            }
+            DerivativeEntity::delete_by_id(derivative.id)
+                .exec(&self.db)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn regenerate_preset(&self, tenant_id: Uuid, preset: &ImagePreset) -> Result<u64> {
+        let existing = DerivativeEntity::find()
+            .filter(DerivativeCol::TenantId.eq(tenant_id))
+            .filter(DerivativeCol::Preset.eq(&preset.name))
+            .all(&self.db)
+            .await?;
+        let mut existing = existing
+            .into_iter()
+            .map(|derivative| (derivative.media_id, derivative))
+            .collect::<std::collections::HashMap<_, _>>();
+
+        let mut media_query = MediaEntity::find().filter(MediaCol::TenantId.eq(tenant_id));
+        if !preset.eager {
+            media_query = media_query.filter(MediaCol::Id.is_in(existing.keys().copied()));
+        }
+        let media_items = media_query.all(&self.db).await?;
+
+        let mut rendered = 0;
+        for media in media_items {
+            if !is_raster_image(&media.mime_type) {
+                continue;
+            }
+            let current = existing.remove(&media.id);
+            let focal_point = FocalPoint::from_metadata(&media.metadata);
+            if current
+                .as_ref()
+                .is_some_and(|current| current.fingerprint == preset.fingerprint(focal_point))
+            {
+                continue;
+            }
+            let source = match self.storage.read(&media.storage_path).await {
+                Ok(source) => source,
+                Err(error) => {
+                    tracing::warn!(
+                        media_id = %media.id,
+                        preset = %preset.name,
+                        error = %error,
+                        "Skipping derivative: original could not be read"
+                    );
+                    continue;
+                }
+            };
+            match self
+                .render_and_store(&media, &source, preset, current)
+                .await
+            {
+                Ok(_) => rendered += 1,
+                Err(MediaError::Image(error)) => tracing::warn!(
+                    media_id = %media.id,
+                    preset = %preset.name,
+                    error = %error,
+                    "Skipping derivative: image could not be rendered"
+                ),
+                Err(error) => return Err(error),
+            }
+        }
+        Ok(rendered)
+    }
+
+    async fn render_and_store(
+        &self,
+        media: &media::Model,
+        source: &[u8],
+        preset: &ImagePreset,
+        existing: Option<media_derivative::Model>,
+    ) -> Result<media_derivative::Model> {
+        let focal_point = FocalPoint::from_metadata(&media.metadata);
+        let rendered = {
+            let source = source.to_vec();
+            let preset = preset.clone();
+            tokio::task::spawn_blocking(move || render_derivative(&source, &preset, focal_point))
+                .await
+                .map_err(|error| MediaError::Image(error.to_string()))??
+        };
+
+        let path = derivative_path(&media.storage_path, &preset.name, preset.format);
+        let uploaded = self
+            .storage
+            .store(&path, rendered.data, rendered.mime_type)
+            .await?;
+
+        let model = match existing {
+            Some(existing) => {
+                let previous_path = existing.storage_path.clone();
+                let mut active: DerivativeActiveModel = existing.into();
+                active.fingerprint = Set(preset.fingerprint(focal_point));
+                active.storage_path = Set(path);
+                active.mime_type = Set(rendered.mime_type.to_string());
+                active.width = Set(rendered.width as i32);
+                active.height = Set(rendered.height as i32);
+                active.size = Set(uploaded.size as i64);
+                active.created_at = Set(Utc::now().fixed_offset());
+                let model = active.update(&self.db).await?;
+                if let Err(error) = self.storage.delete(&previous_path).await {
+                    tracing::warn!(
+                        media_id = %media.id,
+                        path = %previous_path,
+                        error = %error,
+                        "Failed to delete superseded media derivative"
+                    );
+                }
+                model
+            }
+            None => {
+                DerivativeActiveModel {
+                    id: Set(generate_id()),
+                    tenant_id: Set(media.tenant_id),
+                    media_id: Set(media.id),
+                    preset: Set(preset.name.clone()),
+                    fingerprint: Set(preset.fingerprint(focal_point)),
+                    storage_path: Set(path),
+                    mime_type: Set(rendered.mime_type.to_string()),
+                    width: Set(rendered.width as i32),
+                    height: Set(rendered.height as i32),
+                    size: Set(uploaded.size as i64),
+                    created_at: Set(Utc::now().fixed_offset()),
+                }
+                .insert(&self.db)
+                .await?
+            }
+        };
+        Ok(model)
+    }
+
+    pub(super) async fn find_media(&self, tenant_id: Uuid, media_id: Uuid) -> Result<media::Model> {
+        MediaEntity::find_by_id(media_id)
+            .filter(MediaCol::TenantId.eq(tenant_id))
+            .one(&self.db)
+            .await?
+            .ok_or(MediaError::NotFound(media_id))
+    }
+
+    async fn find_derivative(
+        &self,
+        media_id: Uuid,
+        preset: &str,
+    ) -> Result<Option<media_derivative::Model>> {
+        Ok(DerivativeEntity::find()
+            .filter(DerivativeCol::MediaId.eq(media_id))
+            .filter(DerivativeCol::Preset.eq(preset))
+            .one(&self.db)
+            .await?)
+    }
+
+    fn to_derivative_item(
+        &self,
+        derivative: media_derivative::Model,
+        responsive: bool,
+    ) -> MediaDerivativeItem {
+        MediaDerivativeItem {
+            media_id: derivative.media_id,
+            preset: derivative.preset,
+            public_url: self.storage.public_url(&derivative.storage_path),
+            mime_type: derivative.mime_type,
+            width: derivative.width,
+            height: derivative.height,
+            size: derivative.size,
+            responsive,
+            created_at: derivative.created_at.with_timezone(&Utc),
+        }
+    }
+}
+
+/// Derivatives live next to the original: `<dir>/<stem>.<preset>-<rev>.<ext>`.
+/// The random revision keeps CDN caches from serving a superseded rendition.
+fn derivative_path(original: &str, preset: &str, format: ImageFormat) -> String {
+    let stem = original
+        .rsplit_once('.')
+        .filter(|(stem, _)| !stem.ends_with('/'))
+        .map_or(original, |(stem, _)| stem);
+    let revision = Uuid::new_v4().simple().to_string();
+    format!("{stem}.{preset}-{}.{}", &revision[..8], format.extension())
+}
+
+fn preset_from_model(model: media_image_preset::Model) -> Result<ImagePreset> {
+    let invalid = |field: &str, value: &str| {
+        MediaError::InvalidPreset(format!("`{}`: unknown {field} `{value}`", model.name))
+    };
+    Ok(ImagePreset {
+        fit: ImageFit::parse(&model.fit).ok_or_else(|| invalid("fit", &model.fit))?,
+        format: ImageFormat::parse(&model.format)
+            .ok_or_else(|| invalid("format", &model.format))?,
+        name: model.name,
+        width: model.width.max(1) as u32,
+        height: model.height.map(|height| height.max(1) as u32),
+        quality: model.quality.map(|quality| quality.clamp(1, 100) as u8),
+        eager: model.eager,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derivative_path_sits_next_to_original() {
+        let path = derivative_path("t/2026/10/abc.jpg", "thumbnail", ImageFormat::Webp);
+        assert!(path.starts_with("t/2026/10/abc.thumbnail-"));
+        assert!(path.ends_with(".webp"));
+    }
+}
@@ -0,0 +1,411 @@
+use std::collections::HashMap;
+
+use chrono::Utc;
+use sea_orm::{
+    sea_query::{Expr, Func, LikeExpr, Query, SimpleExpr},
+    ActiveModelTrait, ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set, TransactionTrait,
+};
+use uuid::Uuid;
+
+use rustok_core::generate_id;
+use rustok_taxonomy::{TaxonomyService, TaxonomyTermKind};
+
+use super::MediaService;
+use crate::{
+    dto::{CreateMediaFolderInput, MediaFolderItem, MediaItem, MediaSearchFilter},
+    entities::{
+        media::{ActiveModel as MediaActiveModel, Column as MediaCol, Entity as MediaEntity},
+        media_folder::{
+            self, ActiveModel as FolderActiveModel, Column as FolderCol, Entity as FolderEntity,
+        },
+        media_tag::{ActiveModel as TagActiveModel, Column as TagCol, Entity as TagEntity},
+        media_translation::{Column as TransCol, Entity as TransEntity},
+    },
+    error::{MediaError, Result},
+};
+
+/// Taxonomy module scope of media tags.
+const TAG_SCOPE_VALUE: &str = "media";
+
+const MAX_FOLDER_NAME_LEN: usize = 255;
+
+impl MediaService {
+    // ── Folders ───────────────────────────────────────────────────────────────
+
+    pub async fn create_folder(
+        &self,
+        tenant_id: Uuid,
+        input: CreateMediaFolderInput,
+    ) -> Result<MediaFolderItem> {
+        let name = normalize_folder_name(&input.name)?;
+        if let Some(parent_id) = input.parent_id {
+            self.find_folder(tenant_id, parent_id).await?;
+        }
+        self.ensure_unique_folder_name(tenant_id, input.parent_id, &name, None)
+            .await?;
+
+        let now = Utc::now().fixed_offset();
+        let folder = FolderActiveModel {
+            id: Set(generate_id()),
+            tenant_id: Set(tenant_id),
+            parent_id: Set(input.parent_id),
+            name: Set(name),
+            created_at: Set(now),
+            updated_at: Set(now),
+        }
+        .insert(&self.db)
+        .await?;
+        Ok(to_folder_item(folder))
+    }
+
+    /// Direct subfolders of `parent_id`, or root folders when `None`.
+    pub async fn list_folders(
+        &self,
+        tenant_id: Uuid,
+        parent_id: Option<Uuid>,
+    ) -> Result<Vec<MediaFolderItem>> {
+        let query = FolderEntity::find().filter(FolderCol::TenantId.eq(tenant_id));
+        let query = match parent_id {
+            Some(parent_id) => query.filter(FolderCol::ParentId.eq(parent_id)),
+            None => query.filter(FolderCol::ParentId.is_null()),
+        };
+        Ok(query
+            .order_by_asc(FolderCol::Name)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(to_folder_item)
+            .collect())
+    }
+
+    pub async fn rename_folder(
+        &self,
+        tenant_id: Uuid,
+        id: Uuid,
+        name: &str,
+    ) -> Result<MediaFolderItem> {
+        let folder = self.find_folder(tenant_id, id).await?;
+        let name = normalize_folder_name(name)?;
+        self.ensure_unique_folder_name(tenant_id, folder.parent_id, &name, Some(id))
+            .await?;
+
+        let mut active: FolderActiveModel = folder.into();
+        active.name = Set(name);
+        active.updated_at = Set(Utc::now().fixed_offset());
+        Ok(to_folder_item(active.update(&self.db).await?))
+    }
+
+    /// Move a folder under `parent_id`, or to the root when `None`.
+    pub async fn move_folder(
+        &self,
+        tenant_id: Uuid,
+        id: Uuid,
+        parent_id: Option<Uuid>,
+    ) -> Result<MediaFolderItem> {
+        let folder = self.find_folder(tenant_id, id).await?;
+
+        // Walk up from the new parent; reaching the folder itself means the
+        // move would create a cycle.
+        let mut ancestor = parent_id;
+        while let Some(ancestor_id) = ancestor {
+            if ancestor_id == id {
+                return Err(MediaError::InvalidFolder(
+                    "a folder cannot be moved into itself or a subfolder".to_string(),
+                ));
+            }
+            ancestor = self.find_folder(tenant_id, ancestor_id).await?.parent_id;
+        }
+        self.ensure_unique_folder_name(tenant_id, parent_id, &folder.name, Some(id))
+            .await?;
+
+        let mut active: FolderActiveModel = folder.into();
+        active.parent_id = Set(parent_id);
+        active.updated_at = Set(Utc::now().fixed_offset());
+        Ok(to_folder_item(active.update(&self.db).await?))
+    }
+
+    /// Delete an empty folder. Folders holding media or subfolders are
+    /// refused so assets are never moved or lost implicitly.
+    pub async fn delete_folder(&self, tenant_id: Uuid, id: Uuid) -> Result<()> {
+        self.find_folder(tenant_id, id).await?;
+        let subfolders = FolderEntity::find()
+            .filter(FolderCol::TenantId.eq(tenant_id))
+            .filter(FolderCol::ParentId.eq(id))
+            .count(&self.db)
+            .await?;
+        let media = MediaEntity::find()
+            .filter(MediaCol::TenantId.eq(tenant_id))
+            .filter(MediaCol::FolderId.eq(id))
+            .count(&self.db)
+            .await?;
+        if subfolders > 0 || media > 0 {
+            return Err(MediaError::InvalidFolder(format!(
+                "folder is not empty ({media} media, {subfolders} subfolders)"
+            )));
+        }
+        FolderEntity::delete_by_id(id).exec(&self.db).await?;
+        Ok(())
+    }
+
+    /// Move a media asset into a folder, or out of any folder when `None`.
+    pub async fn move_media(
+        &self,
+        tenant_id: Uuid,
+        id: Uuid,
+        folder_id: Option<Uuid>,
+    ) -> Result<MediaItem> {
+        let model = self.find_media(tenant_id, id).await?;
+        if let Some(folder_id) = folder_id {
+            self.find_folder(tenant_id, folder_id).await?;
+        }
+        let mut active: MediaActiveModel = model.into();
+        active.folder_id = Set(folder_id);
+        Ok(self.to_item(active.update(&self.db).await?))
+    }
+
+    // ── Tags ──────────────────────────────────────────────────────────────────
+
+    /// Replace the tags of a media asset. Tags are module-scoped taxonomy
+    /// terms, created on first use in `locale`.
+    pub async fn set_tags(
+        &self,
+        tenant_id: Uuid,
+        media_id: Uuid,
+        locale: &str,
+        tags: &[String],
+    ) -> Result<Vec<String>> {
+        self.find_media(tenant_id, media_id).await?;
+        let tags = normalize_tag_names(tags);
+
+        let txn = self.db.begin().await?;
+        TagEntity::delete_many()
+            .filter(TagCol::MediaId.eq(media_id))
+            .exec(&txn)
+            .await?;
+        if !tags.is_empty() {
+            let term_ids = TaxonomyService::new(self.db.clone())
+                .ensure_terms_for_module_in_tx(
+                    &txn,
+                    tenant_id,
+                    TaxonomyTermKind::Tag,
+                    TAG_SCOPE_VALUE,
+                    locale,
+                    &tags,
+                )
+                .await?;
+            let now = Utc::now();
+            for (index, term_id) in term_ids.into_iter().enumerate() {
+                TagActiveModel {
+                    media_id: Set(media_id),
+                    term_id: Set(term_id),
+                    tenant_id: Set(tenant_id),
+                    // Preserve caller-provided tag order in read paths that sort by created_at.
+                    created_at: Set((now + chrono::Duration::microseconds(index as i64)).into()),
+                }
+                .insert(&txn)
+                .await?;
+            }
+        }
+        txn.commit().await?;
+
+        self.tags(tenant_id, media_id, locale).await
+    }
+
+    /// Tag names of a media asset, localized to `locale`.
+    pub async fn tags(&self, tenant_id: Uuid, media_id: Uuid, locale: &str) -> Result<Vec<String>> {
+        Ok(self
+            .tags_by_term(tenant_id, media_id, locale)
+            .await?
+            .into_iter()
+            .map(|(_, name)| name)
+            .collect())
+    }
+
+    /// Tag term IDs and localized names of a media asset, in tag order.
+    pub async fn tags_by_term(
+        &self,
+        tenant_id: Uuid,
+        media_id: Uuid,
+        locale: &str,
+    ) -> Result<Vec<(Uuid, String)>> {
+        let term_ids: Vec<Uuid> = TagEntity::find()
+            .filter(TagCol::TenantId.eq(tenant_id))
+            .filter(TagCol::MediaId.eq(media_id))
+            .order_by_asc(TagCol::CreatedAt)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|tag| tag.term_id)
+            .collect();
+        if term_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut names: HashMap<Uuid, String> = TaxonomyService::new(self.db.clone())
+            .resolve_term_names(tenant_id, &term_ids, locale, None)
+            .await?;
+        Ok(term_ids
+            .into_iter()
+            .filter_map(|term_id| names.remove(&term_id).map(|name| (term_id, name)))
+            .collect())
+    }
+
+    // ── Search ────────────────────────────────────────────────────────────────
+
+    pub async fn search(
+        &self,
+        tenant_id: Uuid,
+        filter: MediaSearchFilter,
+        limit: u64,
+        offset: u64,
+    ) -> Result<(Vec<MediaItem>, u64)> {
+        let mut query = MediaEntity::find().filter(MediaCol::TenantId.eq(tenant_id));
+
+        if let Some(text) = filter
+            .query
+            .as_deref()
+            .map(str::trim)
+            .filter(|text| !text.is_empty())
+        {
+            let pattern = like_pattern(text);
+            let translated = Query::select()
+                .column(TransCol::MediaId)
+                .from(TransEntity)
+                .cond_where(
+                    Condition::any()
+                        .add(lower_like(TransCol::AltText, &pattern))
+                        .add(lower_like(TransCol::Title, &pattern)),
+                )
+                .to_owned();
+            query = query.filter(
+                Condition::any()
+                    .add(lower_like(MediaCol::OriginalName, &pattern))
+                    .add(MediaCol::Id.in_subquery(translated)),
+            );
+        }
+
+        query = match filter.folder_id {
+            Some(folder_id) => query.filter(MediaCol::FolderId.eq(folder_id)),
+            None if filter.unfiled => query.filter(MediaCol::FolderId.is_null()),
+            None => query,
+        };
+
+        if let Some(tag_id) = filter.tag_id {
+            let tagged = Query::select()
+                .column(TagCol::MediaId)
+                .from(TagEntity)
+                .and_where(TagCol::TenantId.eq(tenant_id))
+                .and_where(TagCol::TermId.eq(tag_id))
+                .to_owned();
+            query = query.filter(MediaCol::Id.in_subquery(tagged));
+        }
+
+        if let Some(prefix) = filter
+            .mime_prefix
+            .as_deref()
+            .map(str::trim)
+            .filter(|prefix| !prefix.is_empty())
+        {
+            query = query.filter(MediaCol::MimeType.starts_with(prefix.to_ascii_lowercase()));
+        }
+
+        let query = query.order_by_desc(MediaCol::CreatedAt);
+        let total = query.clone().count(&self.db).await?;
+        let items = query.limit(limit).offset(offset).all(&self.db).await?;
+        Ok((items.into_iter().map(|m| self.to_item(m)).collect(), total))
+    }
+
+    // ── Internal ──────────────────────────────────────────────────────────────
+
+    async fn find_folder(&self, tenant_id: Uuid, id: Uuid) -> Result<media_folder::Model> {
+        FolderEntity::find_by_id(id)
+            .filter(FolderCol::TenantId.eq(tenant_id))
+            .one(&self.db)
+            .await?
+            .ok_or(MediaError::FolderNotFound(id))
+    }
+
+    async fn ensure_unique_folder_name(
+        &self,
+        tenant_id: Uuid,
+        parent_id: Option<Uuid>,
+        name: &str,
+        except: Option<Uuid>,
+    ) -> Result<()> {
+        let query = FolderEntity::find().filter(FolderCol::TenantId.eq(tenant_id));
+        let query = match parent_id {
+            Some(parent_id) => query.filter(FolderCol::ParentId.eq(parent_id)),
+            None => query.filter(FolderCol::ParentId.is_null()),
+        };
+        let taken =
+            query.all(&self.db).await?.into_iter().any(|sibling| {
+                Some(sibling.id) != except && sibling.name.eq_ignore_ascii_case(name)
+            });
+        if taken {
+            return Err(MediaError::InvalidFolder(format!(
+                "a folder named `{name}` already exists here"
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn to_folder_item(folder: media_folder::Model) -> MediaFolderItem {
+    MediaFolderItem {
+        id: folder.id,
+        tenant_id: folder.tenant_id,
+        parent_id: folder.parent_id,
+        name: folder.name,
+        created_at: folder.created_at.with_timezone(&Utc),
+        updated_at: folder.updated_at.with_timezone(&Utc),
+    }
+}
+
+fn normalize_folder_name(name: &str) -> Result<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(MediaError::InvalidFolder(
+            "folder name must not be empty".to_string(),
+        ));
+    }
+    if name.len() > MAX_FOLDER_NAME_LEN || name.contains('/') {
+        return Err(MediaError::InvalidFolder(format!(
+            "folder name must be at most {MAX_FOLDER_NAME_LEN} bytes without `/`"
+        )));
+    }
+    Ok(name.to_string())
+}
+
+fn normalize_tag_names(tag_names: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag_name in tag_names {
+        let trimmed = tag_name.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if !normalized
+            .iter()
+            .any(|existing| existing.eq_ignore_ascii_case(trimmed))
+        {
+            normalized.push(trimmed.to_string());
+        }
+    }
+    normalized
+}
+
+/// `%text%` with LIKE wildcards in `text` escaped, lowercased for
+/// [`lower_like`].
+fn like_pattern(text: &str) -> String {
+    let escaped = text
+        .to_lowercase()
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{escaped}%")
+}
+
+/// Case-insensitive `LIKE` that behaves the same on PostgreSQL and SQLite.
+fn lower_like(column: impl sea_orm::sea_query::IntoColumnRef, pattern: &str) -> SimpleExpr {
+    Expr::expr(Func::lower(Expr::col(column))).like(LikeExpr::new(pattern).escape('\\'))
+}
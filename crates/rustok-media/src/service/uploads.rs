@@ -0,0 +1,605 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set};
+use uuid::Uuid;
+
+use rustok_core::generate_id;
+use rustok_storage::{
+    PresignedUpload, StorageError, StorageService, UploadContent, UploadTarget, UploadedPart,
+};
+
+use super::{ensure_allowed_mime_type, MediaService, StoredObject};
+use crate::{
+    dto::{
+        CreateUploadInput, MediaItem, UploadSessionItem, UploadSessionStatus, DEFAULT_MAX_SIZE,
+        MAX_DIRECT_UPLOAD_SIZE, MULTIPART_THRESHOLD, UPLOAD_PART_SIZE,
+    },
+    entities::media_upload::{
+        self, ActiveModel as UploadActiveModel, Column as UploadCol, Entity as UploadEntity,
+    },
+    error::{MediaError, Result},
+    ingest::{
+        canonical_mime_type, content_hash, extract_metadata, has_strippable_metadata,
+        resolve_mime_type, strip_image_metadata, ContentHasher,
+    },
+};
+
+/// How long a session accepts uploads before it is aborted.
+const SESSION_TTL_HOURS: i64 = 24;
+
+/// Lifetime of presigned upload URLs.
+const PRESIGN_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Bytes read from the start of objects too large to read whole, for type
+/// detection and header metadata.
+const HEADER_PROBE_SIZE: u64 = 1024 * 1024;
+
+/// Bytes read per request when hashing objects too large to read whole.
+const HASH_CHUNK_SIZE: u64 = 8 * 1024 * 1024;
+
+impl MediaService {
+    // ── Direct uploads ────────────────────────────────────────────────────────
+
+    /// Start a direct-to-storage upload. Files above
+    /// [`MULTIPART_THRESHOLD`] get a multipart session; smaller ones a single
+    /// presigned request. Nothing is recorded as media until
+    /// [`MediaService::complete_upload`].
+    pub async fn create_upload(&self, input: CreateUploadInput) -> Result<UploadSessionItem> {
+        let content_type = canonical_mime_type(&input.content_type);
+        ensure_allowed_mime_type(&content_type)?;
+        if input.size == 0 {
+            return Err(MediaError::InvalidUpload("file is empty".to_string()));
+        }
+        if input.size > MAX_DIRECT_UPLOAD_SIZE {
+            return Err(MediaError::FileTooLarge {
+                size: input.size,
+                max: MAX_DIRECT_UPLOAD_SIZE,
+            });
+        }
+        self.ensure_strippable_size(&content_type, input.size)?;
+
+        let path = StorageService::generate_path(input.tenant_id, &input.original_name);
+        let multipart = input.size > MULTIPART_THRESHOLD;
+        let multipart_upload_id = if multipart {
+            Some(
+                self.storage
+                    .create_multipart_upload(&path, &content_type)
+                    .await?,
+            )
+        } else {
+            None
+        };
+
+        let now = Utc::now();
+        let model = UploadActiveModel {
+            id: Set(generate_id()),
+            tenant_id: Set(input.tenant_id),
+            uploaded_by: Set(input.uploaded_by),
+            original_name: Set(input.original_name),
+            content_type: Set(content_type),
+            size: Set(input.size as i64),
+            storage_path: Set(path),
+            multipart_upload_id: Set(multipart_upload_id),
+            part_size: Set(multipart.then_some(UPLOAD_PART_SIZE as i64)),
+            status: Set(UploadSessionStatus::Pending.as_str().to_string()),
+            media_id: Set(None),
+            expires_at: Set((now + chrono::Duration::hours(SESSION_TTL_HOURS)).fixed_offset()),
+            created_at: Set(now.fixed_offset()),
+            updated_at: Set(now.fixed_offset()),
+        }
+        .insert(&self.db)
+        .await?;
+
+        self.to_session_item(model).await
+    }
+
+    /// Current state of an upload session, including the parts received so
+    /// far so interrupted multipart uploads can resume.
+    pub async fn upload_session(&self, tenant_id: Uuid, id: Uuid) -> Result<UploadSessionItem> {
+        let session = self.find_session(tenant_id, id).await?;
+        self.to_session_item(session).await
+    }
+
+    /// Presigned request for one part of a multipart session, or `None` when
+    /// the backend cannot presign and the part must go through
+    /// [`MediaService::upload_part`].
+    pub async fn presign_upload_part(
+        &self,
+        tenant_id: Uuid,
+        id: Uuid,
+        part_number: u32,
+    ) -> Result<Option<PresignedUpload>> {
+        let session = self.pending_session(tenant_id, id).await?;
+        let upload_id = multipart_upload_id(&session)?;
+        let part_size = expected_part_size(&session, part_number)?;
+        Ok(self
+            .storage
+            .presigned_upload(
+                &UploadTarget::part(&session.storage_path, upload_id, part_number),
+                &UploadContent::new(&session.content_type, part_size),
+                PRESIGN_TTL,
+            )
+            .await?)
+    }
+
+    /// Upload one part of a multipart session through the API.
+    pub async fn upload_part(
+        &self,
+        tenant_id: Uuid,
+        id: Uuid,
+        part_number: u32,
+        data: bytes::Bytes,
+    ) -> Result<UploadedPart> {
+        let session = self.pending_session(tenant_id, id).await?;
+        self.write_part(&session, part_number, data).await
+    }
+
+    /// Upload the content of a single-request session through the API.
+    pub async fn upload_content(
+        &self,
+        tenant_id: Uuid,
+        id: Uuid,
+        data: bytes::Bytes,
+    ) -> Result<()> {
+        let session = self.pending_session(tenant_id, id).await?;
+        self.write_content(&session, data).await
+    }
+
+    /// Accept a signed direct upload for backends that route presigned
+    /// requests through the application (the local driver). The signature
+    /// covers the declared content type and size, so `content_type` and the
+    /// length of `data` must match what was presigned. Returns the part for
+    /// multipart targets.
+    pub async fn receive_direct_upload(
+        &self,
+        target: UploadTarget,
+        content_type: &str,
+        expires_at: i64,
+        signature: &str,
+        data: bytes::Bytes,
+    ) -> Result<Option<UploadedPart>> {
+        self.storage.verify_presigned_upload(
+            &target,
+            &UploadContent::new(content_type, data.len() as u64),
+            expires_at,
+            signature,
+        )?;
+        let session = UploadEntity::find()
+            .filter(UploadCol::StoragePath.eq(&target.path))
+            .one(&self.db)
+            .await?
+            .ok_or_else(|| MediaError::InvalidUpload("no upload session for path".to_string()))?;
+        let session = ensure_pending(session)?;
+
+        match target.part {
+            Some((upload_id, part_number)) => {
+                if session.multipart_upload_id.as_deref() != Some(upload_id.as_str()) {
+                    return Err(MediaError::InvalidUpload(
+                        "upload ID does not match the session".to_string(),
+                    ));
+                }
+                self.write_part(&session, part_number, data).await.map(Some)
+            }
+            None => self.write_content(&session, data).await.map(|()| None),
+        }
+    }
+
+    /// Assemble the uploaded parts, validate the stored content like a
+    /// regular upload and create the media row.
+    ///
+    /// Objects up to [`DEFAULT_MAX_SIZE`] are read whole and have their
+    /// metadata stripped. Larger objects are probed at the start and hashed
+    /// in chunks; images whose metadata would be stripped are rejected above
+    /// that size, since stripping needs the whole file in memory. Duplicates
+    /// resolve to the existing asset either way. Content that fails
+    /// validation is deleted and the session aborted.
+    pub async fn complete_upload(&self, tenant_id: Uuid, id: Uuid) -> Result<MediaItem> {
+        let session = self.pending_session(tenant_id, id).await?;
+
+        if let Some(upload_id) = &session.multipart_upload_id {
+            let parts = self
+                .storage
+                .list_parts(&session.storage_path, upload_id)
+                .await?;
+            let expected = part_count(&session);
+            let complete = parts.len() == expected as usize
+                && parts
+                    .iter()
+                    .zip(1..)
+                    .all(|(part, number)| part.part_number == number);
+            if !complete {
+                return Err(MediaError::InvalidUpload(format!(
+                    "expected parts 1..={expected}, received {}",
+                    parts.len()
+                )));
+            }
+            self.storage
+                .complete_multipart_upload(&session.storage_path, upload_id, &parts)
+                .await?;
+        }
+
+        let size = match self.storage.head(&session.storage_path).await {
+            Ok(info) => info.size,
+            Err(StorageError::NotFound(_)) => {
+                return Err(MediaError::InvalidUpload(
+                    "no content has been uploaded".to_string(),
+                ));
+            }
+            Err(error) => return Err(error.into()),
+        };
+        if size != session.size as u64 {
+            self.discard_session(&session).await;
+            self.set_session_status(session, UploadSessionStatus::Aborted, None)
+                .await?;
+            return Err(MediaError::InvalidUpload(format!(
+                "stored size {size} does not match declared size"
+            )));
+        }
+
+        match self.ingest_stored(&session, size).await {
+            Ok(item) => {
+                self.set_session_status(session, UploadSessionStatus::Completed, Some(item.id))
+                    .await?;
+                Ok(item)
+            }
+            Err(
+                error @ (MediaError::ContentTypeMismatch { .. }
+                | MediaError::UnrecognizedContent(_)
+                | MediaError::UnsupportedMimeType(_)
+                | MediaError::FileTooLarge { .. }),
+            ) => {
+                self.discard_session(&session).await;
+                self.set_session_status(session, UploadSessionStatus::Aborted, None)
+                    .await?;
+                Err(error)
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Abort a pending session and discard anything uploaded for it.
+    pub async fn abort_upload(&self, tenant_id: Uuid, id: Uuid) -> Result<()> {
+        let session = self.find_session(tenant_id, id).await?;
+        if UploadSessionStatus::parse(&session.status) != UploadSessionStatus::Pending {
+            return Err(MediaError::InvalidUpload(format!(
+                "upload is {}",
+                session.status
+            )));
+        }
+        self.discard_session(&session).await;
+        self.set_session_status(session, UploadSessionStatus::Aborted, None)
+            .await
+    }
+
+    /// Abort sessions of all tenants whose TTL has passed. Returns how many
+    /// were aborted.
+    pub async fn abort_expired_uploads(&self) -> Result<u64> {
+        let expired = UploadEntity::find()
+            .filter(UploadCol::Status.eq(UploadSessionStatus::Pending.as_str()))
+            .filter(UploadCol::ExpiresAt.lt(Utc::now().fixed_offset()))
+            .all(&self.db)
+            .await?;
+        let mut aborted = 0;
+        for session in expired {
+            self.discard_session(&session).await;
+            self.set_session_status(session, UploadSessionStatus::Aborted, None)
+                .await?;
+            aborted += 1;
+        }
+        Ok(aborted)
+    }
+
+    // ── Internal ──────────────────────────────────────────────────────────────
+
+    async fn ingest_stored(&self, session: &media_upload::Model, size: u64) -> Result<MediaItem> {
+        let path = session.storage_path.clone();
+        if size > DEFAULT_MAX_SIZE {
+            let head = self.storage.read_range(&path, 0, HEADER_PROBE_SIZE).await?;
+            let mime_type = resolve_mime_type(&session.content_type, &head)?;
+            ensure_allowed_mime_type(&mime_type)?;
+            self.ensure_strippable_size(&mime_type, size)?;
+            let hash = self.stored_content_hash(&path, size).await?;
+            if let Some(existing) = self.take_duplicate(session.tenant_id, &hash, &path).await? {
+                return Ok(existing);
+            }
+            let mut extracted = extract_metadata(&mime_type, &head);
+            // Pages are counted across the whole file; a prefix undercounts.
+            extracted.page_count = None;
+            return self
+                .record_media(
+                    StoredObject {
+                        tenant_id: session.tenant_id,
+                        uploaded_by: session.uploaded_by,
+                        original_name: session.original_name.clone(),
+                        mime_type,
+                        path,
+                        size,
+                        extracted,
+                        content_hash: Some(hash),
+                    },
+                    None,
+                )
+                .await;
+        }
+
+        let data = self.storage.read(&path).await?;
+        let mime_type = resolve_mime_type(&session.content_type, &data)?;
+        ensure_allowed_mime_type(&mime_type)?;
+        let hash = content_hash(&data);
+        if let Some(existing) = self.take_duplicate(session.tenant_id, &hash, &path).await? {
+            return Ok(existing);
+        }
+
+        let extracted = extract_metadata(&mime_type, &data);
+        let mut size = size;
+        let data = if self.ingest.strip_metadata {
+            let stripped = strip_image_metadata(&mime_type, data.clone());
+            if stripped != data {
+                size = self
+                    .storage
+                    .store(&path, stripped.clone(), &mime_type)
+                    .await?
+                    .size;
+            }
+            stripped
+        } else {
+            data
+        };
+
+        self.record_media(
+            StoredObject {
+                tenant_id: session.tenant_id,
+                uploaded_by: session.uploaded_by,
+                original_name: session.original_name.clone(),
+                mime_type,
+                path,
+                size,
+                extracted,
+                content_hash: Some(hash),
+            },
+            Some(&data),
+        )
+        .await
+    }
+
+    /// Metadata stripping reads the whole file, so strippable images are
+    /// limited to [`DEFAULT_MAX_SIZE`] while stripping is enabled.
+    fn ensure_strippable_size(&self, mime_type: &str, size: u64) -> Result<()> {
+        if self.ingest.strip_metadata
+            && has_strippable_metadata(mime_type)
+            && size > DEFAULT_MAX_SIZE
+        {
+            return Err(MediaError::FileTooLarge {
+                size,
+                max: DEFAULT_MAX_SIZE,
+            });
+        }
+        Ok(())
+    }
+
+    async fn stored_content_hash(&self, path: &str, size: u64) -> Result<String> {
+        let mut hasher = ContentHasher::default();
+        let mut offset = 0;
+        while offset < size {
+            let chunk = self
+                .storage
+                .read_range(path, offset, HASH_CHUNK_SIZE.min(size - offset))
+                .await?;
+            if chunk.is_empty() {
+                return Err(MediaError::InvalidUpload(
+                    "stored object is shorter than its size".to_string(),
+                ));
+            }
+            hasher.update(&chunk);
+            offset += chunk.len() as u64;
+        }
+        Ok(hasher.finish())
+    }
+
+    /// Resolve a completed upload to existing media with the same content,
+    /// deleting the uploaded copy.
+    async fn take_duplicate(
+        &self,
+        tenant_id: Uuid,
+        hash: &str,
+        path: &str,
+    ) -> Result<Option<MediaItem>> {
+        let Some(existing) = self.find_duplicate(tenant_id, hash).await? else {
+            return Ok(None);
+        };
+        if let Err(error) = self.storage.delete(path).await {
+            tracing::warn!(
+                path = %path,
+                error = %error,
+                "Failed to delete duplicate direct upload from storage"
+            );
+        }
+        Ok(Some(self.to_item(existing)))
+    }
+
+    async fn write_part(
+        &self,
+        session: &media_upload::Model,
+        part_number: u32,
+        data: bytes::Bytes,
+    ) -> Result<UploadedPart> {
+        let upload_id = multipart_upload_id(session)?;
+        let expected = expected_part_size(session, part_number)?;
+        if data.len() as u64 != expected {
+            return Err(MediaError::InvalidUpload(format!(
+                "part {part_number} must be {expected} bytes, got {}",
+                data.len()
+            )));
+        }
+        Ok(self
+            .storage
+            .upload_part(&session.storage_path, upload_id, part_number, data)
+            .await?)
+    }
+
+    async fn write_content(&self, session: &media_upload::Model, data: bytes::Bytes) -> Result<()> {
+        if session.multipart_upload_id.is_some() {
+            return Err(MediaError::InvalidUpload(
+                "multipart uploads are sent in parts".to_string(),
+            ));
+        }
+        if data.len() as i64 != session.size {
+            return Err(MediaError::InvalidUpload(format!(
+                "content must be {} bytes, got {}",
+                session.size,
+                data.len()
+            )));
+        }
+        self.storage
+            .store(&session.storage_path, data, &session.content_type)
+            .await?;
+        Ok(())
+    }
+
+    /// Best-effort removal of everything stored for a session.
+    async fn discard_session(&self, session: &media_upload::Model) {
+        let result = match &session.multipart_upload_id {
+            Some(upload_id) => {
+                let aborted = self
+                    .storage
+                    .abort_multipart_upload(&session.storage_path, upload_id)
+                    .await;
+                // A completed multipart upload has become a regular object.
+                match self.storage.delete(&session.storage_path).await {
+                    Ok(()) => aborted,
+                    Err(error) => Err(error),
+                }
+            }
+            None => self.storage.delete(&session.storage_path).await,
+        };
+        if let Err(error) = result {
+            tracing::warn!(
+                upload_id = %session.id,
+                path = %session.storage_path,
+                error = %error,
+                "Failed to discard direct upload from storage"
+            );
+        }
+    }
+
+    async fn set_session_status(
+        &self,
+        session: media_upload::Model,
+        status: UploadSessionStatus,
+        media_id: Option<Uuid>,
+    ) -> Result<()> {
+        let mut active: UploadActiveModel = session.into();
+        active.status = Set(status.as_str().to_string());
+        active.media_id = Set(media_id);
+        active.updated_at = Set(Utc::now().fixed_offset());
+        active.update(&self.db).await?;
+        Ok(())
+    }
+
+    async fn find_session(&self, tenant_id: Uuid, id: Uuid) -> Result<media_upload::Model> {
+        UploadEntity::find_by_id(id)
+            .filter(UploadCol::TenantId.eq(tenant_id))
+            .one(&self.db)
+            .await?
+            .ok_or(MediaError::UploadNotFound(id))
+    }
+
+    async fn pending_session(&self, tenant_id: Uuid, id: Uuid) -> Result<media_upload::Model> {
+        ensure_pending(self.find_session(tenant_id, id).await?)
+    }
+
+    async fn to_session_item(&self, session: media_upload::Model) -> Result<UploadSessionItem> {
+        let status = UploadSessionStatus::parse(&session.status);
+        let pending = status == UploadSessionStatus::Pending;
+        let (upload_url, parts) = match &session.multipart_upload_id {
+            Some(upload_id) if pending => {
+                let parts = match self
+                    .storage
+                    .list_parts(&session.storage_path, upload_id)
+                    .await
+                {
+                    Ok(parts) => parts,
+                    Err(StorageError::NotFound(_)) => Vec::new(),
+                    Err(error) => return Err(error.into()),
+                };
+                (None, parts)
+            }
+            None if pending => {
+                let upload_url = self
+                    .storage
+                    .presigned_upload(
+                        &UploadTarget::object(&session.storage_path),
+                        &UploadContent::new(&session.content_type, session.size as u64),
+                        PRESIGN_TTL,
+                    )
+                    .await?;
+                (upload_url, Vec::new())
+            }
+            _ => (None, Vec::new()),
+        };
+
+        Ok(UploadSessionItem {
+            id: session.id,
+            tenant_id: session.tenant_id,
+            multipart: session.multipart_upload_id.is_some(),
+            part_count: session
+                .multipart_upload_id
+                .is_some()
+                .then(|| part_count(&session)),
+            part_size: session.part_size,
+            original_name: session.original_name,
+            content_type: session.content_type,
+            size: session.size,
+            status,
+            upload_url,
+            parts,
+            media_id: session.media_id,
+            expires_at: session.expires_at.with_timezone(&Utc),
+            created_at: session.created_at.with_timezone(&Utc),
+        })
+    }
+}
+
+fn ensure_pending(session: media_upload::Model) -> Result<media_upload::Model> {
+    if UploadSessionStatus::parse(&session.status) != UploadSessionStatus::Pending {
+        return Err(MediaError::InvalidUpload(format!(
+            "upload is {}",
+            session.status
+        )));
+    }
+    if session.expires_at < Utc::now().fixed_offset() {
+        return Err(MediaError::InvalidUpload(
+            "upload session expired".to_string(),
+        ));
+    }
+    Ok(session)
+}
+
+fn multipart_upload_id(session: &media_upload::Model) -> Result<&str> {
+    session
+        .multipart_upload_id
+        .as_deref()
+        .ok_or_else(|| MediaError::InvalidUpload("not a multipart upload".to_string()))
+}
+
+fn part_count(session: &media_upload::Model) -> u32 {
+    let part_size = session.part_size.unwrap_or(session.size).max(1);
+    ((session.size + part_size - 1) / part_size) as u32
+}
+
+/// Every part but the last is exactly `part_size` bytes.
+fn expected_part_size(session: &media_upload::Model, part_number: u32) -> Result<u64> {
+    let count = part_count(session);
+    if part_number == 0 || part_number > count {
+        return Err(MediaError::InvalidUpload(format!(
+            "part number must be between 1 and {count}"
+        )));
+    }
+    let part_size = session.part_size.unwrap_or(session.size) as u64;
+    Ok(if part_number == count {
+        session.size as u64 - part_size * u64::from(count - 1)
+    } else {
+        part_size
+    })
+}
@@ -0,0 +1,321 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::Utc;
+use sea_orm::{
+    sea_query::Query, ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set, TransactionTrait,
+};
+use uuid::Uuid;
+
+use rustok_core::generate_id;
+
+use super::MediaService;
+use crate::{
+    dto::{MediaBrokenReference, MediaItem, MediaOrphanReport, MediaUsageItem},
+    entities::{
+        media::{self, Column as MediaCol, Entity as MediaEntity},
+        media_derivative::{Column as DerivativeCol, Entity as DerivativeEntity},
+        media_tag::{Column as TagCol, Entity as TagEntity},
+        media_usage::{
+            self, ActiveModel as UsageActiveModel, Column as UsageCol, Entity as UsageEntity,
+        },
+    },
+    error::{MediaError, Result},
+    usage::{url_path, MediaReferenceTarget},
+};
+
+/// Outcome of collecting references from all providers.
+struct UsageRefresh {
+    indexed: u64,
+    broken: Vec<MediaBrokenReference>,
+}
+
+impl MediaService {
+    // ── Where-used ────────────────────────────────────────────────────────────
+
+    /// Places that use a media asset, from the where-used index.
+    pub async fn where_used(&self, tenant_id: Uuid, media_id: Uuid) -> Result<Vec<MediaUsageItem>> {
+        self.find_media(tenant_id, media_id).await?;
+        self.indexed_usages(tenant_id, media_id).await
+    }
+
+    /// Rebuild the where-used index of a tenant from the registered usage
+    /// providers. Returns the number of indexed references.
+    pub async fn reindex_usages(&self, tenant_id: Uuid) -> Result<u64> {
+        Ok(self.refresh_usages(tenant_id).await?.indexed)
+    }
+
+    /// Refresh the where-used index and report media nothing references and
+    /// references that point at missing media.
+    pub async fn orphan_report(&self, tenant_id: Uuid) -> Result<MediaOrphanReport> {
+        let refresh = self.refresh_usages(tenant_id).await?;
+        let used = Query::select()
+            .column(UsageCol::MediaId)
+            .from(UsageEntity)
+            .and_where(UsageCol::TenantId.eq(tenant_id))
+            .to_owned();
+        let unused: Vec<MediaItem> = MediaEntity::find()
+            .filter(MediaCol::TenantId.eq(tenant_id))
+            .filter(MediaCol::Id.not_in_subquery(used))
+            .order_by_asc(MediaCol::CreatedAt)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|model| self.to_item(model))
+            .collect();
+
+        Ok(MediaOrphanReport {
+            unused_size: unused.iter().map(|item| item.size).sum(),
+            unused,
+            broken_references: refresh.broken,
+            indexed_usages: refresh.indexed,
+            owner_kinds: self
+                .usage
+                .as_ref()
+                .map(|registry| {
+                    registry
+                        .owner_kinds()
+                        .into_iter()
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            generated_at: Utc::now(),
+        })
+    }
+
+    // ── Delete ────────────────────────────────────────────────────────────────
+
+    /// Delete a media asset unless it is still used somewhere.
+    ///
+    /// With usage providers configured the tenant's where-used index is
+    /// refreshed first, so the check reflects current content.
+    pub async fn delete(&self, tenant_id: Uuid, id: Uuid) -> Result<()> {
+        let model = self.find_media(tenant_id, id).await?;
+        let usages = self.current_usages(tenant_id, id).await?;
+        if !usages.is_empty() {
+            return Err(MediaError::InUse {
+                id,
+                usages: usages.len(),
+            });
+        }
+        self.remove_media(model).await
+    }
+
+    /// Delete a media asset even when it is still used. Returns the usages
+    /// left pointing at the deleted asset.
+    pub async fn force_delete(&self, tenant_id: Uuid, id: Uuid) -> Result<Vec<MediaUsageItem>> {
+        let model = self.find_media(tenant_id, id).await?;
+        let usages = self.current_usages(tenant_id, id).await?;
+        if !usages.is_empty() {
+            tracing::warn!(
+                media_id = %id,
+                usages = usages.len(),
+                "Deleting media that is still referenced"
+            );
+        }
+        self.remove_media(model).await?;
+        Ok(usages)
+    }
+
+    // ── Internal ──────────────────────────────────────────────────────────────
+
+    async fn current_usages(&self, tenant_id: Uuid, media_id: Uuid) -> Result<Vec<MediaUsageItem>> {
+        if self.usage.is_some() {
+            self.refresh_usages(tenant_id).await?;
+        }
+        self.indexed_usages(tenant_id, media_id).await
+    }
+
+    async fn indexed_usages(&self, tenant_id: Uuid, media_id: Uuid) -> Result<Vec<MediaUsageItem>> {
+        Ok(UsageEntity::find()
+            .filter(UsageCol::TenantId.eq(tenant_id))
+            .filter(UsageCol::MediaId.eq(media_id))
+            .order_by_asc(UsageCol::OwnerKind)
+            .order_by_asc(UsageCol::OwnerId)
+            .order_by_asc(UsageCol::Field)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(to_usage_item)
+            .collect())
+    }
+
+    async fn remove_media(&self, model: media::Model) -> Result<()> {
+        let id = model.id;
+        // Best-effort storage cleanup — log but don't fail on storage errors
+        if let Err(e) = self.storage.delete(&model.storage_path).await {
+            tracing::warn!(
+                media_id = %id,
+                path = %model.storage_path,
+                error = %e,
+                "Failed to delete media object from storage; DB record will still be removed"
+            );
+        }
+
+        let derivatives = DerivativeEntity::find()
+            .filter(DerivativeCol::MediaId.eq(id))
+            .all(&self.db)
+            .await?;
+        self.delete_derivatives(derivatives).await?;
+
+        TagEntity::delete_many()
+            .filter(TagCol::MediaId.eq(id))
+            .exec(&self.db)
+            .await?;
+        UsageEntity::delete_many()
+            .filter(UsageCol::MediaId.eq(id))
+            .exec(&self.db)
+            .await?;
+        MediaEntity::delete_by_id(id).exec(&self.db).await?;
+        Ok(())
+    }
+
+    /// Replace the tenant's where-used index with the references currently
+    /// reported by the providers. Without providers the index is left as is.
+    async fn refresh_usages(&self, tenant_id: Uuid) -> Result<UsageRefresh> {
+        let Some(registry) = self.usage.clone() else {
+            return Ok(UsageRefresh {
+                indexed: 0,
+                broken: Vec::new(),
+            });
+        };
+
+        let mut collected = Vec::new();
+        for provider in registry.providers() {
+            let references = provider
+                .collect_references(&self.db, tenant_id)
+                .await
+                .map_err(|error| MediaError::UsageProvider {
+                    owner_kind: provider.owner_kind().to_string(),
+                    message: error.to_string(),
+                })?;
+            collected.extend(
+                references
+                    .into_iter()
+                    .map(|reference| (provider.owner_kind(), reference)),
+            );
+        }
+
+        let resolver = self.reference_resolver(tenant_id).await?;
+        let now = Utc::now().fixed_offset();
+        let mut seen = HashSet::new();
+        let mut rows = Vec::new();
+        let mut broken = Vec::new();
+        for (owner_kind, reference) in collected {
+            match resolver.resolve(&reference.target) {
+                Some(media_id) => {
+                    if seen.insert((
+                        media_id,
+                        owner_kind,
+                        reference.owner_id,
+                        reference.field.clone(),
+                    )) {
+                        rows.push(UsageActiveModel {
+                            id: Set(generate_id()),
+                            tenant_id: Set(tenant_id),
+                            media_id: Set(media_id),
+                            owner_kind: Set(owner_kind.to_string()),
+                            owner_id: Set(reference.owner_id),
+                            field: Set(reference.field),
+                            label: Set(reference.label),
+                            indexed_at: Set(now),
+                        });
+                    }
+                }
+                None => broken.push(MediaBrokenReference {
+                    owner_kind: owner_kind.to_string(),
+                    owner_id: reference.owner_id,
+                    field: reference.field,
+                    label: reference.label,
+                    target: reference.target,
+                }),
+            }
+        }
+
+        let indexed = rows.len() as u64;
+        let txn = self.db.begin().await?;
+        UsageEntity::delete_many()
+            .filter(UsageCol::TenantId.eq(tenant_id))
+            .exec(&txn)
+            .await?;
+        for row in rows {
+            row.insert(&txn).await?;
+        }
+        txn.commit().await?;
+
+        Ok(UsageRefresh { indexed, broken })
+    }
+
+    async fn reference_resolver(&self, tenant_id: Uuid) -> Result<ReferenceResolver> {
+        let media: Vec<(Uuid, String)> = MediaEntity::find()
+            .select_only()
+            .column(MediaCol::Id)
+            .column(MediaCol::StoragePath)
+            .filter(MediaCol::TenantId.eq(tenant_id))
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+        let derivatives: Vec<(Uuid, String)> = DerivativeEntity::find()
+            .select_only()
+            .column(DerivativeCol::MediaId)
+            .column(DerivativeCol::StoragePath)
+            .filter(DerivativeCol::TenantId.eq(tenant_id))
+            .into_tuple()
+            .all(&self.db)
+            .await?;
+
+        let ids = media.iter().map(|(id, _)| *id).collect();
+        let paths = media
+            .into_iter()
+            .chain(derivatives)
+            .map(|(id, path)| (path.trim_start_matches('/').to_string(), id))
+            .collect();
+        Ok(ReferenceResolver { ids, paths })
+    }
+}
+
+/// Maps reference targets to media IDs of one tenant.
+struct ReferenceResolver {
+    ids: HashSet<Uuid>,
+    /// Storage paths of originals and derivatives.
+    paths: HashMap<String, Uuid>,
+}
+
+impl ReferenceResolver {
+    fn resolve(&self, target: &MediaReferenceTarget) -> Option<Uuid> {
+        match target {
+            MediaReferenceTarget::Id(id) => self.ids.contains(id).then_some(*id),
+            MediaReferenceTarget::Url(url) => {
+                let path = url_path(url).trim_start_matches('/');
+                // API URLs carry the media ID, e.g. `/api/media/{id}`.
+                let by_id = path
+                    .split('/')
+                    .filter_map(|segment| Uuid::parse_str(segment).ok())
+                    .find(|id| self.ids.contains(id));
+                by_id.or_else(|| {
+                    // Public URLs end with the storage path below a
+                    // backend-specific prefix; try every suffix.
+                    let mut candidate = path;
+                    loop {
+                        if let Some(id) = self.paths.get(candidate) {
+                            return Some(*id);
+                        }
+                        candidate = candidate.split_once('/')?.1;
+                    }
+                })
+            }
+        }
+    }
+}
+
+fn to_usage_item(usage: media_usage::Model) -> MediaUsageItem {
+    MediaUsageItem {
+        media_id: usage.media_id,
+        owner_kind: usage.owner_kind,
+        owner_id: usage.owner_id,
+        field: usage.field,
+        label: usage.label,
+        indexed_at: usage.indexed_at.with_timezone(&Utc),
+    }
+}
@@ -0,0 +1,316 @@
+//! Where-used tracking for media assets.
+//!
+//! Modules that reference media from their own content register a
+//! [`MediaUsageProvider`] in the module runtime extensions. The media service
+//! collects references from every provider into the `media_usages` index,
+//! which backs where-used lookups, delete protection and orphan reports.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use anyhow::Result as AnyResult;
+use async_trait::async_trait;
+use rustok_core::ModuleRuntimeExtensions;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+use uuid::Uuid;
+
+/// How content points at a media asset.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum MediaReferenceTarget {
+    Id(Uuid),
+    /// A public URL of the asset or one of its derivatives.
+    Url(String),
+}
+
+/// One reference from module-owned content to a media asset.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MediaReference {
+    pub owner_id: Uuid,
+    /// Field or JSON path holding the reference, e.g. `featured_image_url`.
+    pub field: String,
+    /// Human-readable owner label, such as a post title.
+    pub label: Option<String>,
+    pub target: MediaReferenceTarget,
+}
+
+impl MediaReference {
+    pub fn id(owner_id: Uuid, field: impl Into<String>, media_id: Uuid) -> Self {
+        Self {
+            owner_id,
+            field: field.into(),
+            label: None,
+            target: MediaReferenceTarget::Id(media_id),
+        }
+    }
+
+    pub fn url(owner_id: Uuid, field: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            owner_id,
+            field: field.into(),
+            label: None,
+            target: MediaReferenceTarget::Url(url.into()),
+        }
+    }
+
+    pub fn with_label(mut self, label: Option<String>) -> Self {
+        self.label = label.filter(|label| !label.trim().is_empty());
+        self
+    }
+}
+
+#[derive(Debug, Error, Clone, Eq, PartialEq)]
+pub enum MediaUsageRegistryError {
+    #[error("media usage provider `{0}` is already registered")]
+    DuplicateOwnerKind(&'static str),
+}
+
+#[async_trait]
+pub trait MediaUsageProvider: Send + Sync {
+    /// Stable kind of the referencing content, e.g. `blog_post`.
+    fn owner_kind(&self) -> &'static str;
+
+    fn owner_module_slug(&self) -> &'static str;
+
+    /// Every media reference in the tenant's content of this kind.
+    async fn collect_references(
+        &self,
+        db: &DatabaseConnection,
+        tenant_id: Uuid,
+    ) -> AnyResult<Vec<MediaReference>>;
+}
+
+#[derive(Clone, Default)]
+pub struct MediaUsageRegistry {
+    providers: BTreeMap<&'static str, Arc<dyn MediaUsageProvider>>,
+}
+
+impl MediaUsageRegistry {
+    pub fn register<P>(&mut self, provider: P) -> Result<(), MediaUsageRegistryError>
+    where
+        P: MediaUsageProvider + 'static,
+    {
+        self.register_arc(Arc::new(provider))
+    }
+
+    pub fn register_arc(
+        &mut self,
+        provider: Arc<dyn MediaUsageProvider>,
+    ) -> Result<(), MediaUsageRegistryError> {
+        let owner_kind = provider.owner_kind();
+        if self.providers.contains_key(owner_kind) {
+            return Err(MediaUsageRegistryError::DuplicateOwnerKind(owner_kind));
+        }
+        self.providers.insert(owner_kind, provider);
+        Ok(())
+    }
+
+    pub fn providers(&self) -> impl Iterator<Item = &Arc<dyn MediaUsageProvider>> {
+        self.providers.values()
+    }
+
+    pub fn owner_kinds(&self) -> Vec<&'static str> {
+        self.providers.keys().copied().collect()
+    }
+}
+
+pub fn register_media_usage_provider<P>(
+    extensions: &mut ModuleRuntimeExtensions,
+    provider: P,
+) -> Result<(), MediaUsageRegistryError>
+where
+    P: MediaUsageProvider + 'static,
+{
+    let registry = extensions.get_or_insert_with::<Arc<MediaUsageRegistry>, _>(|| {
+        Arc::new(MediaUsageRegistry::default())
+    });
+    Arc::make_mut(registry).register(provider)
+}
+
+pub fn media_usage_registry_from_extensions(
+    extensions: &ModuleRuntimeExtensions,
+) -> Option<Arc<MediaUsageRegistry>> {
+    extensions.get::<Arc<MediaUsageRegistry>>().cloned()
+}
+
+/// Interpret a string value as a media reference: a media URL (any URL with
+/// a `media` path segment) or a bare media ID.
+pub fn media_reference_target(value: &str) -> Option<MediaReferenceTarget> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    if let Ok(id) = Uuid::parse_str(value) {
+        return Some(MediaReferenceTarget::Id(id));
+    }
+    url_path(value)
+        .split('/')
+        .any(|segment| segment == "media")
+        .then(|| MediaReferenceTarget::Url(value.to_string()))
+}
+
+/// Collect media references from arbitrary JSON such as flex entry data or
+/// page block payloads. String values under keys ending in `media_id` count
+/// as IDs; other strings count when they look like media URLs. Fields are
+/// reported as JSON paths below `root`.
+pub fn collect_json_references(owner_id: Uuid, root: &str, value: &Value) -> Vec<MediaReference> {
+    let mut references = Vec::new();
+    walk_json(owner_id, root, None, value, &mut references);
+    references
+}
+
+fn walk_json(
+    owner_id: Uuid,
+    path: &str,
+    key: Option<&str>,
+    value: &Value,
+    references: &mut Vec<MediaReference>,
+) {
+    match value {
+        Value::String(text) => {
+            let is_id_key = key.is_some_and(|key| {
+                let key = key.to_ascii_lowercase();
+                key.ends_with("media_id") || key.ends_with("mediaid")
+            });
+            let target = if is_id_key {
+                Uuid::parse_str(text.trim())
+                    .ok()
+                    .map(MediaReferenceTarget::Id)
+            } else {
+                media_reference_target(text)
+                    .filter(|target| matches!(target, MediaReferenceTarget::Url(_)))
+            };
+            if let Some(target) = target {
+                references.push(MediaReference {
+                    owner_id,
+                    field: path.to_string(),
+                    label: None,
+                    target,
+                });
+            }
+        }
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                walk_json(owner_id, &format!("{path}[{index}]"), key, item, references);
+            }
+        }
+        Value::Object(map) => {
+            for (child_key, child) in map {
+                let child_path = if path.is_empty() {
+                    child_key.clone()
+                } else {
+                    format!("{path}.{child_key}")
+                };
+                walk_json(owner_id, &child_path, Some(child_key), child, references);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Path part of a relative or absolute URL, without query or fragment.
+pub(crate) fn url_path(url: &str) -> &str {
+    let url = url.split(['?', '#']).next().unwrap_or(url);
+    match url.split_once("://") {
+        Some((_, rest)) => rest.find('/').map_or("", |index| &rest[index..]),
+        None => url,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    struct DummyProvider(&'static str);
+
+    #[async_trait]
+    impl MediaUsageProvider for DummyProvider {
+        fn owner_kind(&self) -> &'static str {
+            self.0
+        }
+
+        fn owner_module_slug(&self) -> &'static str {
+            "dummy"
+        }
+
+        async fn collect_references(
+            &self,
+            _db: &DatabaseConnection,
+            _tenant_id: Uuid,
+        ) -> AnyResult<Vec<MediaReference>> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[test]
+    fn registry_rejects_duplicate_owner_kinds() {
+        let mut extensions = ModuleRuntimeExtensions::default();
+        register_media_usage_provider(&mut extensions, DummyProvider("blog_post")).unwrap();
+        register_media_usage_provider(&mut extensions, DummyProvider("page")).unwrap();
+        assert_eq!(
+            register_media_usage_provider(&mut extensions, DummyProvider("page")),
+            Err(MediaUsageRegistryError::DuplicateOwnerKind("page"))
+        );
+
+        let registry = media_usage_registry_from_extensions(&extensions).unwrap();
+        assert_eq!(registry.owner_kinds(), vec!["blog_post", "page"]);
+    }
+
+    #[test]
+    fn reference_targets_recognise_media_urls_and_ids() {
+        let id = Uuid::new_v4();
+        assert_eq!(
+            media_reference_target(&id.to_string()),
+            Some(MediaReferenceTarget::Id(id))
+        );
+        assert_eq!(
+            media_reference_target("https://cdn.example.com/media/t/a.png?v=2"),
+            Some(MediaReferenceTarget::Url(
+                "https://cdn.example.com/media/t/a.png?v=2".to_string()
+            ))
+        );
+        assert_eq!(media_reference_target("https://example.com/about"), None);
+        assert_eq!(media_reference_target("multimedia guide"), None);
+        assert_eq!(url_path("https://cdn.example.com"), "");
+    }
+
+    #[test]
+    fn json_references_report_paths() {
+        let owner = Uuid::new_v4();
+        let media_id = Uuid::new_v4();
+        let references = collect_json_references(
+            owner,
+            "data",
+            &json!({
+                "hero": { "image": "/media/t/hero.png", "title": "Welcome" },
+                "gallery": ["/media/t/1.png", "https://example.com/x.png"],
+                "cover_media_id": media_id.to_string(),
+                "count": 3
+            }),
+        );
+
+        let mut fields: Vec<_> = references
+            .iter()
+            .map(|reference| (reference.field.as_str(), &reference.target))
+            .collect();
+        fields.sort_by_key(|(field, _)| *field);
+        assert_eq!(
+            fields,
+            vec![
+                ("data.cover_media_id", &MediaReferenceTarget::Id(media_id)),
+                (
+                    "data.gallery[0]",
+                    &MediaReferenceTarget::Url("/media/t/1.png".to_string())
+                ),
+                (
+                    "data.hero.image",
+                    &MediaReferenceTarget::Url("/media/t/hero.png".to_string())
+                ),
+            ]
+        );
+    }
+}
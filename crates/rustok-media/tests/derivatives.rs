@@ -0,0 +1,297 @@
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use bytes::Bytes;
+use rustok_core::MigrationSource;
+use rustok_media::{
+    derivatives::{image_dimensions, responsive_srcset},
+    FocalPoint, ImageFit, ImageFormat, ImagePreset, MediaError, MediaModule, MediaService,
+    UploadInput,
+};
+use rustok_storage::local::LocalStorage;
+use rustok_storage::StorageService;
+use rustok_taxonomy::TaxonomyModule;
+use sea_orm::{
+    ConnectOptions, ConnectionTrait, Database, DatabaseConnection, DbBackend, Statement,
+};
+use sea_orm_migration::SchemaManager;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn upload_renders_presets_and_cleans_up_with_parent() {
+    let db = setup_db().await;
+    let storage_dir = std::env::temp_dir().join(format!("rustok-media-{}", Uuid::new_v4()));
+    let service = MediaService::new(
+        db.clone(),
+        StorageService::new(LocalStorage::new(&storage_dir, "/media")),
+    );
+    let tenant_id = Uuid::new_v4();
+
+    let item = service
+        .upload(UploadInput {
+            tenant_id,
+            uploaded_by: None,
+            original_name: "landscape.png".to_string(),
+            content_type: "image/png".to_string(),
+            data: png(1200, 600),
+        })
+        .await
+        .expect("upload should succeed");
+    assert_eq!(item.width, Some(1200));
+    assert_eq!(item.height, Some(600));
+
+    let derivatives = service
+        .list_derivatives(tenant_id, item.id)
+        .await
+        .expect("derivatives should list");
+    let mut presets = derivatives
+        .iter()
+        .map(|derivative| derivative.preset.as_str())
+        .collect::<Vec<_>>();
+    presets.sort_unstable();
+    assert_eq!(presets, ["card", "hero", "thumbnail"]);
+
+    let thumbnail = find(&derivatives, "thumbnail");
+    assert_eq!((thumbnail.width, thumbnail.height), (320, 320));
+    assert_eq!(thumbnail.mime_type, "image/jpeg");
+    assert!(!thumbnail.responsive);
+    let stored = stored_file(&storage_dir, &thumbnail.public_url);
+    assert_eq!(
+        image_dimensions(&std::fs::read(&stored).expect("thumbnail file")),
+        Some((320, 320))
+    );
+
+    // The hero preset never upscales, so it matches the original width.
+    let hero = find(&derivatives, "hero");
+    assert_eq!((hero.width, hero.height), (1200, 600));
+    assert!(hero.responsive);
+    assert_eq!(
+        responsive_srcset(&item.public_url, item.width, &derivatives),
+        Some(format!("{} 1200w", hero.public_url))
+    );
+
+    // Changing a preset re-renders existing derivatives.
+    let (_, regenerated) = service
+        .upsert_image_preset(
+            tenant_id,
+            ImagePreset {
+                name: "hero".to_string(),
+                width: 600,
+                height: None,
+                fit: ImageFit::Contain,
+                format: ImageFormat::Jpeg,
+                quality: Some(70),
+                eager: true,
+            },
+        )
+        .await
+        .expect("preset should update");
+    assert_eq!(regenerated, 1);
+    let derivatives = service
+        .list_derivatives(tenant_id, item.id)
+        .await
+        .expect("derivatives should list");
+    let hero_resized = find(&derivatives, "hero");
+    assert_eq!((hero_resized.width, hero_resized.height), (600, 300));
+    assert_eq!(hero_resized.mime_type, "image/jpeg");
+    assert!(!stored_file(&storage_dir, &hero.public_url).exists());
+    assert_eq!(
+        responsive_srcset(&item.public_url, item.width, &derivatives),
+        Some(format!(
+            "{} 600w, {} 1200w",
+            hero_resized.public_url, item.public_url
+        ))
+    );
+
+    // Moving the focal point re-renders cropped presets only.
+    let card = find(&derivatives, "card").clone();
+    service
+        .set_focal_point(
+            tenant_id,
+            item.id,
+            Some(FocalPoint::new(0.9, 0.5).expect("valid focal point")),
+        )
+        .await
+        .expect("focal point should update");
+    let derivatives = service
+        .list_derivatives(tenant_id, item.id)
+        .await
+        .expect("derivatives should list");
+    assert_ne!(find(&derivatives, "card").public_url, card.public_url);
+    assert_eq!(
+        find(&derivatives, "hero").public_url,
+        hero_resized.public_url
+    );
+
+    // Custom lazy presets render on first request.
+    service
+        .upsert_image_preset(
+            tenant_id,
+            ImagePreset {
+                name: "square-avif".to_string(),
+                width: 64,
+                height: Some(64),
+                fit: ImageFit::Cover,
+                format: ImageFormat::Avif,
+                quality: Some(50),
+                eager: false,
+            },
+        )
+        .await
+        .expect("custom preset should be created");
+    let avif = service
+        .derivative(tenant_id, item.id, "square-avif")
+        .await
+        .expect("derivative should render on demand");
+    assert_eq!(
+        (avif.width, avif.height, avif.mime_type.as_str()),
+        (64, 64, "image/avif")
+    );
+    assert!(matches!(
+        service.derivative(tenant_id, item.id, "missing").await,
+        Err(MediaError::PresetNotFound(_))
+    ));
+
+    let paths = service
+        .list_derivatives(tenant_id, item.id)
+        .await
+        .expect("derivatives should list")
+        .iter()
+        .map(|derivative| stored_file(&storage_dir, &derivative.public_url))
+        .collect::<Vec<_>>();
+    assert_eq!(paths.len(), 4);
+    service
+        .delete(tenant_id, item.id)
+        .await
+        .expect("media should delete");
+    assert!(paths.iter().all(|path| !path.exists()));
+    let remaining = db
+        .query_one(Statement::from_string(
+            DbBackend::Sqlite,
+            "SELECT COUNT(*) AS count FROM media_derivatives".to_string(),
+        ))
+        .await
+        .expect("count derivatives")
+        .expect("count row")
+        .try_get::<i64>("", "count")
+        .expect("count value");
+    assert_eq!(remaining, 0);
+
+    let _ = std::fs::remove_dir_all(&storage_dir);
+}
+
+#[tokio::test]
+async fn non_image_uploads_have_no_derivatives() {
+    let db = setup_db().await;
+    let storage_dir = std::env::temp_dir().join(format!("rustok-media-{}", Uuid::new_v4()));
+    let service = MediaService::new(
+        db,
+        StorageService::new(LocalStorage::new(&storage_dir, "/media")),
+    );
+    let tenant_id = Uuid::new_v4();
+
+    let item = service
+        .upload(UploadInput {
+            tenant_id,
+            uploaded_by: None,
+            original_name: "notes.pdf".to_string(),
+            content_type: "application/pdf".to_string(),
+            data: Bytes::from_static(b"%PDF-1.4"),
+        })
+        .await
+        .expect("upload should succeed");
+    assert_eq!(item.width, None);
+    assert!(service
+        .list_derivatives(tenant_id, item.id)
+        .await
+        .expect("derivatives should list")
+        .is_empty());
+    assert!(matches!(
+        service.derivative(tenant_id, item.id, "thumbnail").await,
+        Err(MediaError::UnsupportedMimeType(_))
+    ));
+
+    let _ = std::fs::remove_dir_all(&storage_dir);
+}
+
+fn find<'a>(
+    derivatives: &'a [rustok_media::MediaDerivativeItem],
+    preset: &str,
+) -> &'a rustok_media::MediaDerivativeItem {
+    derivatives
+        .iter()
+        .find(|derivative| derivative.preset == preset)
+        .unwrap_or_else(|| panic!("missing {preset} derivative"))
+}
+
+fn stored_file(storage_dir: &std::path::Path, public_url: &str) -> PathBuf {
+    storage_dir.join(public_url.trim_start_matches("/media/"))
+}
+
+fn png(width: u32, height: u32) -> Bytes {
+    let image = image::RgbImage::from_fn(width, height, |x, y| {
+        image::Rgb([(x % 256) as u8, (y % 256) as u8, 128])
+    });
+    let mut out = Cursor::new(Vec::new());
+    image
+        .write_to(&mut out, image::ImageFormat::Png)
+        .expect("encode png");
+    Bytes::from(out.into_inner())
+}
+
+async fn setup_db() -> DatabaseConnection {
+    let db_url = format!(
+        "sqlite:file:media_{}?mode=memory&cache=shared",
+        Uuid::new_v4()
+    );
+    let mut opts = ConnectOptions::new(db_url);
+    opts.max_connections(5)
+        .min_connections(1)
+        .sqlx_logging(false);
+    let db = Database::connect(opts)
+        .await
+        .expect("failed to connect media test sqlite database");
+
+    for sql in [
+        "CREATE TABLE media (
+            id TEXT PRIMARY KEY,
+            tenant_id TEXT NOT NULL,
+            uploaded_by TEXT NULL,
+            filename TEXT NOT NULL,
+            original_name TEXT NOT NULL,
+            mime_type TEXT NOT NULL,
+            size INTEGER NOT NULL,
+            storage_path TEXT NOT NULL,
+            storage_driver TEXT NOT NULL,
+            width INTEGER NULL,
+            height INTEGER NULL,
+            metadata TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        )",
+        "CREATE TABLE media_translations (
+            id TEXT PRIMARY KEY,
+            media_id TEXT NOT NULL,
+            locale TEXT NOT NULL,
+            title TEXT NULL,
+            alt_text TEXT NULL,
+            caption TEXT NULL
+        )",
+    ] {
+        db.execute(Statement::from_string(DbBackend::Sqlite, sql.to_string()))
+            .await
+            .expect("create host table");
+    }
+
+    let manager = SchemaManager::new(&db);
+    for migration in TaxonomyModule
+        .migrations()
+        .into_iter()
+        .chain(MediaModule.migrations())
+    {
+        migration
+            .up(&manager)
+            .await
+            .expect("migration should apply");
+    }
+    db
+}
@@ -0,0 +1,251 @@
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use bytes::Bytes;
+use rustok_core::MigrationSource;
+use rustok_media::{IngestOptions, MediaError, MediaModule, MediaService, UploadInput};
+use rustok_storage::local::LocalStorage;
+use rustok_storage::StorageService;
+use rustok_taxonomy::TaxonomyModule;
+use sea_orm::{
+    ConnectOptions, ConnectionTrait, Database, DatabaseConnection, DbBackend, Statement,
+};
+use sea_orm_migration::SchemaManager;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn upload_detects_type_and_rejects_mismatches() {
+    let (service, storage_dir) = setup_service().await;
+    let tenant_id = Uuid::new_v4();
+
+    let item = service
+        .upload(upload(
+            tenant_id,
+            "scan.bin",
+            "application/octet-stream",
+            png(),
+        ))
+        .await
+        .expect("generic declared type should take the detected type");
+    assert_eq!(item.mime_type, "image/png");
+    assert_eq!((item.width, item.height), (Some(64), Some(32)));
+    assert_eq!(item.content_hash.as_deref().map(str::len), Some(64));
+
+    let result = service
+        .upload(upload(tenant_id, "photo.jpg", "image/jpeg", png()))
+        .await;
+    assert!(matches!(
+        result,
+        Err(MediaError::ContentTypeMismatch { ref declared, ref detected })
+            if declared == "image/jpeg" && detected == "image/png"
+    ));
+
+    let result = service
+        .upload(upload(
+            tenant_id,
+            "page.png",
+            "image/png",
+            Bytes::from_static(b"<html><script>alert(1)</script></html>"),
+        ))
+        .await;
+    assert!(matches!(result, Err(MediaError::UnrecognizedContent(_))));
+
+    let pdf = service
+        .upload(upload(
+            tenant_id,
+            "brochure.pdf",
+            "application/pdf",
+            Bytes::from_static(
+                b"%PDF-1.4\n1 0 obj << /Type /Pages /Count 3 >>\n\
+                  2 0 obj << /Type /Page >>\n3 0 obj << /Type /Page >>\n\
+                  4 0 obj << /Type /Page >>\n%%EOF",
+            ),
+        ))
+        .await
+        .expect("pdf upload should succeed");
+    assert_eq!(pdf.page_count, Some(3));
+    assert_eq!(pdf.width, None);
+
+    let _ = std::fs::remove_dir_all(&storage_dir);
+}
+
+#[tokio::test]
+async fn duplicate_uploads_resolve_to_the_existing_asset() {
+    let (service, storage_dir) = setup_service().await;
+    let tenant_id = Uuid::new_v4();
+
+    let first = service
+        .upload(upload(tenant_id, "a.png", "image/png", png()))
+        .await
+        .expect("first upload");
+    let second = service
+        .upload(upload(tenant_id, "copy-of-a.png", "image/png", png()))
+        .await
+        .expect("duplicate upload");
+    assert_eq!(first.id, second.id);
+    assert_eq!(second.original_name, "a.png");
+    let (_, total) = service.list(tenant_id, 10, 0).await.expect("list");
+    assert_eq!(total, 1);
+
+    // Hashes are scoped per tenant.
+    let other = service
+        .upload(upload(Uuid::new_v4(), "a.png", "image/png", png()))
+        .await
+        .expect("other tenant upload");
+    assert_ne!(other.id, first.id);
+
+    // Deleting the asset lets the same content be uploaded again.
+    service.delete(tenant_id, first.id).await.expect("delete");
+    let again = service
+        .upload(upload(tenant_id, "a.png", "image/png", png()))
+        .await
+        .expect("re-upload after delete");
+    assert_ne!(again.id, first.id);
+
+    let _ = std::fs::remove_dir_all(&storage_dir);
+}
+
+#[tokio::test]
+async fn image_metadata_is_stripped_unless_disabled() {
+    let (service, storage_dir) = setup_service().await;
+    let tenant_id = Uuid::new_v4();
+
+    let item = service
+        .upload(upload(tenant_id, "geo.jpg", "image/jpeg", jpeg_with_gps()))
+        .await
+        .expect("jpeg upload");
+    let stored = std::fs::read(stored_file(&storage_dir, &item.public_url)).expect("stored file");
+    assert!(!contains(&stored, b"GPS-52.5200N"));
+    assert_eq!(item.size as usize, stored.len());
+    image::load_from_memory(&stored).expect("stripped jpeg still decodes");
+
+    let keep = service.with_ingest_options(IngestOptions {
+        strip_metadata: false,
+        deduplicate: false,
+    });
+    let item = keep
+        .upload(upload(tenant_id, "geo.jpg", "image/jpeg", jpeg_with_gps()))
+        .await
+        .expect("jpeg upload keeping metadata");
+    let stored = std::fs::read(stored_file(&storage_dir, &item.public_url)).expect("stored file");
+    assert!(contains(&stored, b"GPS-52.5200N"));
+
+    let _ = std::fs::remove_dir_all(&storage_dir);
+}
+
+fn upload(tenant_id: Uuid, name: &str, content_type: &str, data: Bytes) -> UploadInput {
+    UploadInput {
+        tenant_id,
+        uploaded_by: None,
+        original_name: name.to_string(),
+        content_type: content_type.to_string(),
+        data,
+    }
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+fn stored_file(storage_dir: &Path, public_url: &str) -> PathBuf {
+    storage_dir.join(public_url.trim_start_matches("/media/"))
+}
+
+fn png() -> Bytes {
+    let image = image::RgbImage::from_fn(64, 32, |x, y| image::Rgb([x as u8, y as u8, 200]));
+    let mut out = Cursor::new(Vec::new());
+    image
+        .write_to(&mut out, image::ImageFormat::Png)
+        .expect("encode png");
+    Bytes::from(out.into_inner())
+}
+
+/// A real JPEG with an EXIF APP1 segment carrying a GPS marker string.
+fn jpeg_with_gps() -> Bytes {
+    let image = image::RgbImage::from_fn(16, 16, |x, y| image::Rgb([x as u8 * 8, y as u8 * 8, 0]));
+    let mut encoded = Cursor::new(Vec::new());
+    image
+        .write_to(&mut encoded, image::ImageFormat::Jpeg)
+        .expect("encode jpeg");
+    let encoded = encoded.into_inner();
+
+    let mut tiff = b"MM\0*\0\0\0\x08\0\0\0\0\0\0".to_vec();
+    tiff.extend_from_slice(b"GPS-52.5200N-13.4050E");
+    let mut segment = vec![0xFF, 0xE1];
+    segment.extend_from_slice(&((2 + 6 + tiff.len()) as u16).to_be_bytes());
+    segment.extend_from_slice(b"Exif\0\0");
+    segment.extend_from_slice(&tiff);
+
+    let mut jpeg = encoded[..2].to_vec();
+    jpeg.extend_from_slice(&segment);
+    jpeg.extend_from_slice(&encoded[2..]);
+    Bytes::from(jpeg)
+}
+
+async fn setup_service() -> (MediaService, PathBuf) {
+    let storage_dir = std::env::temp_dir().join(format!("rustok-media-{}", Uuid::new_v4()));
+    let service = MediaService::new(
+        setup_db().await,
+        StorageService::new(LocalStorage::new(&storage_dir, "/media")),
+    );
+    (service, storage_dir)
+}
+
+async fn setup_db() -> DatabaseConnection {
+    let db_url = format!(
+        "sqlite:file:media_ingest_{}?mode=memory&cache=shared",
+        Uuid::new_v4()
+    );
+    let mut opts = ConnectOptions::new(db_url);
+    opts.max_connections(5)
+        .min_connections(1)
+        .sqlx_logging(false);
+    let db = Database::connect(opts)
+        .await
+        .expect("failed to connect media test sqlite database");
+
+    for sql in [
+        "CREATE TABLE media (
+            id TEXT PRIMARY KEY,
+            tenant_id TEXT NOT NULL,
+            uploaded_by TEXT NULL,
+            filename TEXT NOT NULL,
+            original_name TEXT NOT NULL,
+            mime_type TEXT NOT NULL,
+            size INTEGER NOT NULL,
+            storage_path TEXT NOT NULL,
+            storage_driver TEXT NOT NULL,
+            width INTEGER NULL,
+            height INTEGER NULL,
+            metadata TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        )",
+        "CREATE TABLE media_translations (
+            id TEXT PRIMARY KEY,
+            media_id TEXT NOT NULL,
+            locale TEXT NOT NULL,
+            title TEXT NULL,
+            alt_text TEXT NULL,
+            caption TEXT NULL
+        )",
+    ] {
+        db.execute(Statement::from_string(DbBackend::Sqlite, sql.to_string()))
+            .await
+            .expect("create host table");
+    }
+
+    let manager = SchemaManager::new(&db);
+    for migration in TaxonomyModule
+        .migrations()
+        .into_iter()
+        .chain(MediaModule.migrations())
+    {
+        migration
+            .up(&manager)
+            .await
+            .expect("migration should apply");
+    }
+    db
+}
@@ -0,0 +1,353 @@
+use std::io::Cursor;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use rustok_core::MigrationSource;
+use rustok_media::{
+    CreateMediaFolderInput, MediaError, MediaModule, MediaReference, MediaReferenceTarget,
+    MediaSearchFilter, MediaService, MediaUsageProvider, MediaUsageRegistry, UploadInput,
+};
+use rustok_storage::local::LocalStorage;
+use rustok_storage::StorageService;
+use rustok_taxonomy::TaxonomyModule;
+use sea_orm::{
+    ConnectOptions, ConnectionTrait, Database, DatabaseConnection, DbBackend, Statement,
+};
+use sea_orm_migration::SchemaManager;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn folders_tags_and_search_organise_the_library() {
+    let (service, storage_dir) = setup_service(None).await;
+    let tenant_id = Uuid::new_v4();
+
+    let photos = service
+        .create_folder(tenant_id, folder("Photos", None))
+        .await
+        .expect("create folder");
+    let events = service
+        .create_folder(tenant_id, folder("Events", Some(photos.id)))
+        .await
+        .expect("create subfolder");
+    assert!(matches!(
+        service
+            .create_folder(tenant_id, folder(" photos ", None))
+            .await,
+        Err(MediaError::InvalidFolder(_))
+    ));
+    assert!(matches!(
+        service
+            .move_folder(tenant_id, photos.id, Some(events.id))
+            .await,
+        Err(MediaError::InvalidFolder(_))
+    ));
+
+    let hero = upload(&service, tenant_id, "Summer-Hero.png", 10).await;
+    let logo = upload(&service, tenant_id, "logo.png", 20).await;
+    let hero = service
+        .move_media(tenant_id, hero.id, Some(events.id))
+        .await
+        .expect("move media");
+    assert_eq!(hero.folder_id, Some(events.id));
+    assert!(matches!(
+        service.delete_folder(tenant_id, events.id).await,
+        Err(MediaError::InvalidFolder(_))
+    ));
+
+    let tags = service
+        .set_tags(
+            tenant_id,
+            logo.id,
+            "en",
+            &[
+                "Brand".to_string(),
+                "brand".to_string(),
+                "Print".to_string(),
+            ],
+        )
+        .await
+        .expect("set tags");
+    assert_eq!(tags, vec!["Brand".to_string(), "Print".to_string()]);
+    let (brand_id, _) = service
+        .tags_by_term(tenant_id, logo.id, "en")
+        .await
+        .expect("tags by term")
+        .into_iter()
+        .find(|(_, name)| name == "Brand")
+        .expect("brand term");
+
+    assert_eq!(
+        search(
+            &service,
+            tenant_id,
+            MediaSearchFilter {
+                query: Some("hero".to_string()),
+                ..Default::default()
+            }
+        )
+        .await,
+        vec![hero.id]
+    );
+    assert_eq!(
+        search(
+            &service,
+            tenant_id,
+            MediaSearchFilter {
+                folder_id: Some(events.id),
+                ..Default::default()
+            }
+        )
+        .await,
+        vec![hero.id]
+    );
+    assert_eq!(
+        search(
+            &service,
+            tenant_id,
+            MediaSearchFilter {
+                unfiled: true,
+                ..Default::default()
+            }
+        )
+        .await,
+        vec![logo.id]
+    );
+    assert_eq!(
+        search(
+            &service,
+            tenant_id,
+            MediaSearchFilter {
+                tag_id: Some(brand_id),
+                mime_prefix: Some("image/".to_string()),
+                ..Default::default()
+            }
+        )
+        .await,
+        vec![logo.id]
+    );
+
+    service
+        .move_media(tenant_id, hero.id, None)
+        .await
+        .expect("move to root");
+    service
+        .delete_folder(tenant_id, photos.id)
+        .await
+        .expect_err("folder with subfolders stays");
+    service
+        .delete_folder(tenant_id, events.id)
+        .await
+        .expect("delete empty folder");
+    assert!(service
+        .list_folders(tenant_id, Some(photos.id))
+        .await
+        .expect("list folders")
+        .is_empty());
+
+    let _ = std::fs::remove_dir_all(&storage_dir);
+}
+
+#[tokio::test]
+async fn delete_is_refused_while_media_is_used() {
+    let references = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let mut registry = MediaUsageRegistry::default();
+    registry
+        .register(FixedProvider(references.clone()))
+        .expect("register provider");
+    let (service, storage_dir) = setup_service(Some(Arc::new(registry))).await;
+    let tenant_id = Uuid::new_v4();
+
+    let used = upload(&service, tenant_id, "used.png", 10).await;
+    let unused = upload(&service, tenant_id, "unused.png", 20).await;
+    let post_id = Uuid::new_v4();
+    let missing = Uuid::new_v4();
+    *references.lock().unwrap() = vec![
+        MediaReference::id(post_id, "cover_media_id", used.id)
+            .with_label(Some("Launch".to_string())),
+        MediaReference::url(
+            post_id,
+            "body.image",
+            format!("https://cdn.example.com{}?w=640", used.public_url),
+        ),
+        MediaReference::id(post_id, "gallery[0]", missing),
+    ];
+
+    assert!(matches!(
+        service.delete(tenant_id, used.id).await,
+        Err(MediaError::InUse { usages: 2, .. })
+    ));
+    let usages = service
+        .where_used(tenant_id, used.id)
+        .await
+        .expect("where used");
+    assert_eq!(
+        usages
+            .iter()
+            .map(|usage| (usage.owner_kind.as_str(), usage.field.as_str()))
+            .collect::<Vec<_>>(),
+        vec![("post", "body.image"), ("post", "cover_media_id")]
+    );
+
+    let report = service.orphan_report(tenant_id).await.expect("report");
+    assert_eq!(
+        report.unused.iter().map(|item| item.id).collect::<Vec<_>>(),
+        vec![unused.id]
+    );
+    assert_eq!(report.unused_size, unused.size);
+    assert_eq!(report.indexed_usages, 2);
+    assert_eq!(report.owner_kinds, vec!["post".to_string()]);
+    assert_eq!(report.broken_references.len(), 1);
+    assert_eq!(
+        report.broken_references[0].target,
+        MediaReferenceTarget::Id(missing)
+    );
+
+    service
+        .delete(tenant_id, unused.id)
+        .await
+        .expect("unused media deletes");
+    let left = service
+        .force_delete(tenant_id, used.id)
+        .await
+        .expect("force delete");
+    assert_eq!(left.len(), 2);
+    assert!(matches!(
+        service.get(tenant_id, used.id).await,
+        Err(MediaError::NotFound(_))
+    ));
+
+    let _ = std::fs::remove_dir_all(&storage_dir);
+}
+
+struct FixedProvider(Arc<std::sync::Mutex<Vec<MediaReference>>>);
+
+#[async_trait]
+impl MediaUsageProvider for FixedProvider {
+    fn owner_kind(&self) -> &'static str {
+        "post"
+    }
+
+    fn owner_module_slug(&self) -> &'static str {
+        "test"
+    }
+
+    async fn collect_references(
+        &self,
+        _db: &DatabaseConnection,
+        _tenant_id: Uuid,
+    ) -> anyhow::Result<Vec<MediaReference>> {
+        Ok(self.0.lock().unwrap().clone())
+    }
+}
+
+async fn search(service: &MediaService, tenant_id: Uuid, filter: MediaSearchFilter) -> Vec<Uuid> {
+    let (items, total) = service
+        .search(tenant_id, filter, 20, 0)
+        .await
+        .expect("search");
+    assert_eq!(total as usize, items.len());
+    items.into_iter().map(|item| item.id).collect()
+}
+
+fn folder(name: &str, parent_id: Option<Uuid>) -> CreateMediaFolderInput {
+    CreateMediaFolderInput {
+        name: name.to_string(),
+        parent_id,
+    }
+}
+
+async fn upload(
+    service: &MediaService,
+    tenant_id: Uuid,
+    name: &str,
+    shade: u8,
+) -> rustok_media::MediaItem {
+    service
+        .upload(UploadInput {
+            tenant_id,
+            uploaded_by: None,
+            original_name: name.to_string(),
+            content_type: "image/png".to_string(),
+            data: png(shade),
+        })
+        .await
+        .expect("upload")
+}
+
+fn png(shade: u8) -> Bytes {
+    let image = image::RgbImage::from_fn(32, 16, |x, y| image::Rgb([x as u8, y as u8, shade]));
+    let mut out = Cursor::new(Vec::new());
+    image
+        .write_to(&mut out, image::ImageFormat::Png)
+        .expect("encode png");
+    Bytes::from(out.into_inner())
+}
+
+async fn setup_service(registry: Option<Arc<MediaUsageRegistry>>) -> (MediaService, PathBuf) {
+    let storage_dir = std::env::temp_dir().join(format!("rustok-media-{}", Uuid::new_v4()));
+    let service = MediaService::new(
+        setup_db().await,
+        StorageService::new(LocalStorage::new(&storage_dir, "/media")),
+    )
+    .with_usage_registry(registry);
+    (service, storage_dir)
+}
+
+async fn setup_db() -> DatabaseConnection {
+    let db_url = format!(
+        "sqlite:file:media_library_{}?mode=memory&cache=shared",
+        Uuid::new_v4()
+    );
+    let mut opts = ConnectOptions::new(db_url);
+    opts.max_connections(5)
+        .min_connections(1)
+        .sqlx_logging(false);
+    let db = Database::connect(opts)
+        .await
+        .expect("failed to connect media test sqlite database");
+
+    for sql in [
+        "CREATE TABLE media (
+            id TEXT PRIMARY KEY,
+            tenant_id TEXT NOT NULL,
+            uploaded_by TEXT NULL,
+            filename TEXT NOT NULL,
+            original_name TEXT NOT NULL,
+            mime_type TEXT NOT NULL,
+            size INTEGER NOT NULL,
+            storage_path TEXT NOT NULL,
+            storage_driver TEXT NOT NULL,
+            width INTEGER NULL,
+            height INTEGER NULL,
+            metadata TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        )",
+        "CREATE TABLE media_translations (
+            id TEXT PRIMARY KEY,
+            media_id TEXT NOT NULL,
+            locale TEXT NOT NULL,
+            title TEXT NULL,
+            alt_text TEXT NULL,
+            caption TEXT NULL
+        )",
+    ] {
+        db.execute(Statement::from_string(DbBackend::Sqlite, sql.to_string()))
+            .await
+            .expect("create host table");
+    }
+
+    let manager = SchemaManager::new(&db);
+    for migration in TaxonomyModule
+        .migrations()
+        .into_iter()
+        .chain(MediaModule.migrations())
+    {
+        migration
+            .up(&manager)
+            .await
+            .expect("migration should apply");
+    }
+    db
+}
@@ -0,0 +1,371 @@
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use bytes::Bytes;
+use rustok_core::MigrationSource;
+use rustok_media::ingest::content_hash;
+use rustok_media::{
+    CreateUploadInput, MediaError, MediaModule, MediaService, UploadSessionStatus,
+    MULTIPART_THRESHOLD, UPLOAD_PART_SIZE,
+};
+use rustok_storage::local::LocalStorage;
+use rustok_storage::{StorageService, UploadTarget};
+use rustok_taxonomy::TaxonomyModule;
+use sea_orm::{
+    ConnectOptions, ConnectionTrait, Database, DatabaseConnection, DbBackend, Statement,
+};
+use sea_orm_migration::SchemaManager;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn presigned_upload_completes_into_media() {
+    let (service, storage_dir) = setup_service().await;
+    let tenant_id = Uuid::new_v4();
+    let data = png();
+
+    let session = service
+        .create_upload(create(
+            tenant_id,
+            "hero.png",
+            "image/png",
+            data.len() as u64,
+        ))
+        .await
+        .expect("create upload");
+    assert!(!session.multipart);
+    assert_eq!(session.status, UploadSessionStatus::Pending);
+    let presigned = session
+        .upload_url
+        .expect("local storage presigns with a secret");
+    assert_eq!(presigned.method, "PUT");
+
+    assert_eq!(
+        presigned.headers,
+        vec![("content-type".to_string(), "image/png".to_string())]
+    );
+
+    let (path, expires, signature) = parse_presigned(&presigned.url);
+    let forged = service
+        .receive_direct_upload(
+            UploadTarget::object(path.clone()),
+            "image/png",
+            expires,
+            "00",
+            data.clone(),
+        )
+        .await;
+    assert!(matches!(forged, Err(MediaError::Storage(_))));
+    // The signature is bound to the declared type and size.
+    for (content_type, body) in [
+        ("text/html", data.clone()),
+        ("image/png", data.slice(..data.len() - 1)),
+    ] {
+        let rebound = service
+            .receive_direct_upload(
+                UploadTarget::object(path.clone()),
+                content_type,
+                expires,
+                &signature,
+                body,
+            )
+            .await;
+        assert!(matches!(rebound, Err(MediaError::Storage(_))));
+    }
+
+    let part = service
+        .receive_direct_upload(
+            UploadTarget::object(path),
+            "image/png",
+            expires,
+            &signature,
+            data,
+        )
+        .await
+        .expect("signed upload");
+    assert!(part.is_none());
+
+    let item = service
+        .complete_upload(tenant_id, session.id)
+        .await
+        .expect("complete upload");
+    assert_eq!(item.mime_type, "image/png");
+    assert_eq!((item.width, item.height), (Some(64), Some(32)));
+    assert!(item.content_hash.is_some());
+
+    let session = service
+        .upload_session(tenant_id, session.id)
+        .await
+        .expect("session");
+    assert_eq!(session.status, UploadSessionStatus::Completed);
+    assert_eq!(session.media_id, Some(item.id));
+    assert!(matches!(
+        service.complete_upload(tenant_id, session.id).await,
+        Err(MediaError::InvalidUpload(_))
+    ));
+
+    let _ = std::fs::remove_dir_all(&storage_dir);
+}
+
+#[tokio::test]
+async fn multipart_upload_resumes_and_completes() {
+    let (service, storage_dir) = setup_service().await;
+    let tenant_id = Uuid::new_v4();
+    let mut content = b"\0\0\0\x18ftypisom\0\0\x02\0isomiso2".to_vec();
+    content.resize((MULTIPART_THRESHOLD + 1024 * 1024) as usize, 0);
+    let content = Bytes::from(content);
+
+    // Images this large cannot have their metadata stripped.
+    assert!(matches!(
+        service
+            .create_upload(create(
+                tenant_id,
+                "big.png",
+                "image/png",
+                content.len() as u64
+            ))
+            .await,
+        Err(MediaError::FileTooLarge { .. })
+    ));
+
+    let session = service
+        .create_upload(create(
+            tenant_id,
+            "big.mp4",
+            "video/mp4",
+            content.len() as u64,
+        ))
+        .await
+        .expect("create upload");
+    assert!(session.multipart);
+    assert_eq!(session.part_count, Some(5));
+    let part_size = UPLOAD_PART_SIZE as usize;
+    let chunk = |number: usize| {
+        content.slice((number - 1) * part_size..(number * part_size).min(content.len()))
+    };
+
+    for number in [1, 2] {
+        service
+            .upload_part(tenant_id, session.id, number as u32, chunk(number))
+            .await
+            .expect("proxied part");
+    }
+    assert!(matches!(
+        service
+            .upload_part(tenant_id, session.id, 3, content.slice(0..10))
+            .await,
+        Err(MediaError::InvalidUpload(_))
+    ));
+    assert!(matches!(
+        service.complete_upload(tenant_id, session.id).await,
+        Err(MediaError::InvalidUpload(_))
+    ));
+
+    // Resume: the session reports what the backend already holds.
+    let resumed = service
+        .upload_session(tenant_id, session.id)
+        .await
+        .expect("session");
+    let received: Vec<u32> = resumed.parts.iter().map(|part| part.part_number).collect();
+    assert_eq!(received, vec![1, 2]);
+
+    for number in 3..=5 {
+        let presigned = service
+            .presign_upload_part(tenant_id, session.id, number as u32)
+            .await
+            .expect("presign part")
+            .expect("local storage presigns with a secret");
+        let (path, expires, signature) = parse_presigned(&presigned.url);
+        let upload_id = query_param(&presigned.url, "upload_id").to_string();
+        let part = service
+            .receive_direct_upload(
+                UploadTarget::part(path, upload_id, number as u32),
+                "video/mp4",
+                expires,
+                &signature,
+                chunk(number),
+            )
+            .await
+            .expect("signed part")
+            .expect("part upload returns the part");
+        assert_eq!(part.part_number, number as u32);
+    }
+
+    let item = service
+        .complete_upload(tenant_id, session.id)
+        .await
+        .expect("complete upload");
+    assert_eq!(item.size as usize, content.len());
+    assert_eq!(item.mime_type, "video/mp4");
+    // Objects too large to read whole are hashed in chunks.
+    assert_eq!(item.content_hash, Some(content_hash(&content)));
+
+    let _ = std::fs::remove_dir_all(&storage_dir);
+}
+
+#[tokio::test]
+async fn mismatched_or_aborted_uploads_are_discarded() {
+    let (service, storage_dir) = setup_service().await;
+    let tenant_id = Uuid::new_v4();
+
+    let session = service
+        .create_upload(create(tenant_id, "a.png", "image/png", 10))
+        .await
+        .expect("create upload");
+    assert!(matches!(
+        service.upload_content(tenant_id, session.id, png()).await,
+        Err(MediaError::InvalidUpload(_))
+    ));
+    assert!(matches!(
+        service.complete_upload(tenant_id, session.id).await,
+        Err(MediaError::InvalidUpload(_))
+    ));
+    service
+        .abort_upload(tenant_id, session.id)
+        .await
+        .expect("abort upload");
+    let aborted = service
+        .upload_session(tenant_id, session.id)
+        .await
+        .expect("session");
+    assert_eq!(aborted.status, UploadSessionStatus::Aborted);
+    assert!(aborted.upload_url.is_none());
+
+    // Content that is not what it claims to be never becomes media.
+    let html = Bytes::from_static(b"<html><script>alert(1)</script></html>");
+    let session = service
+        .create_upload(create(tenant_id, "b.png", "image/png", html.len() as u64))
+        .await
+        .expect("create upload");
+    service
+        .upload_content(tenant_id, session.id, html)
+        .await
+        .expect("upload content");
+    assert!(matches!(
+        service.complete_upload(tenant_id, session.id).await,
+        Err(MediaError::UnrecognizedContent(_))
+    ));
+    let (_, total) = service.list(tenant_id, 10, 0).await.expect("list");
+    assert_eq!(total, 0);
+
+    assert!(matches!(
+        service
+            .create_upload(create(tenant_id, "c.exe", "application/x-msdownload", 10))
+            .await,
+        Err(MediaError::UnsupportedMimeType(_))
+    ));
+    assert!(matches!(
+        service.upload_session(Uuid::new_v4(), session.id).await,
+        Err(MediaError::UploadNotFound(_))
+    ));
+
+    let _ = std::fs::remove_dir_all(&storage_dir);
+}
+
+fn create(tenant_id: Uuid, name: &str, content_type: &str, size: u64) -> CreateUploadInput {
+    CreateUploadInput {
+        tenant_id,
+        uploaded_by: None,
+        original_name: name.to_string(),
+        content_type: content_type.to_string(),
+        size,
+    }
+}
+
+/// Split a presigned local URL into its storage path, expiry and signature.
+fn parse_presigned(url: &str) -> (String, i64, String) {
+    let path = url
+        .trim_start_matches("/api/media/direct/")
+        .split('?')
+        .next()
+        .expect("path")
+        .to_string();
+    let expires = query_param(url, "expires").parse().expect("expires");
+    (path, expires, query_param(url, "signature").to_string())
+}
+
+fn query_param<'a>(url: &'a str, name: &str) -> &'a str {
+    url.split_once('?')
+        .expect("query")
+        .1
+        .split('&')
+        .find_map(|pair| pair.strip_prefix(name)?.strip_prefix('='))
+        .expect("query parameter")
+}
+
+fn png() -> Bytes {
+    let image = image::RgbImage::from_fn(64, 32, |x, y| image::Rgb([x as u8, y as u8, 200]));
+    let mut out = Cursor::new(Vec::new());
+    image
+        .write_to(&mut out, image::ImageFormat::Png)
+        .expect("encode png");
+    Bytes::from(out.into_inner())
+}
+
+async fn setup_service() -> (MediaService, PathBuf) {
+    let storage_dir = std::env::temp_dir().join(format!("rustok-media-{}", Uuid::new_v4()));
+    let service = MediaService::new(
+        setup_db().await,
+        StorageService::new(
+            LocalStorage::new(&storage_dir, "/media")
+                .with_upload_signing("test-secret", "/api/media/direct"),
+        ),
+    );
+    (service, storage_dir)
+}
+
+async fn setup_db() -> DatabaseConnection {
+    let db_url = format!(
+        "sqlite:file:media_uploads_{}?mode=memory&cache=shared",
+        Uuid::new_v4()
+    );
+    let mut opts = ConnectOptions::new(db_url);
+    opts.max_connections(5)
+        .min_connections(1)
+        .sqlx_logging(false);
+    let db = Database::connect(opts)
+        .await
+        .expect("failed to connect media test sqlite database");
+
+    for sql in [
+        "CREATE TABLE media (
+            id TEXT PRIMARY KEY,
+            tenant_id TEXT NOT NULL,
+            uploaded_by TEXT NULL,
+            filename TEXT NOT NULL,
+            original_name TEXT NOT NULL,
+            mime_type TEXT NOT NULL,
+            size INTEGER NOT NULL,
+            storage_path TEXT NOT NULL,
+            storage_driver TEXT NOT NULL,
+            width INTEGER NULL,
+            height INTEGER NULL,
+            metadata TEXT NOT NULL,
+            created_at TEXT NOT NULL
+        )",
+        "CREATE TABLE media_translations (
+            id TEXT PRIMARY KEY,
+            media_id TEXT NOT NULL,
+            locale TEXT NOT NULL,
+            title TEXT NULL,
+            alt_text TEXT NULL,
+            caption TEXT NULL
+        )",
+    ] {
+        db.execute(Statement::from_string(DbBackend::Sqlite, sql.to_string()))
+            .await
+            .expect("create host table");
+    }
+
+    let manager = SchemaManager::new(&db);
+    for migration in TaxonomyModule
+        .migrations()
+        .into_iter()
+        .chain(MediaModule.migrations())
+    {
+        migration
+            .up(&manager)
+            .await
+            .expect("migration should apply");
+    }
+    db
+}
@@ -0,0 +1,198 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Notification kinds produced by the built-in recipient resolvers.
+pub mod kinds {
+    /// A reply was posted in a forum topic the recipient follows or started.
+    pub const FORUM_TOPIC_REPLY: &str = "forum.topic.reply";
+    /// A topic was created in a forum category the recipient follows.
+    pub const FORUM_CATEGORY_TOPIC: &str = "forum.category.topic";
+    /// Someone commented on the recipient's blog post.
+    pub const BLOG_POST_COMMENT: &str = "blog.post.comment";
+    /// Someone replied to the recipient's blog comment.
+    pub const BLOG_COMMENT_REPLY: &str = "blog.comment.reply";
+    /// The recipient was @mentioned in a forum reply or comment.
+    pub const USER_MENTION: &str = "user.mention";
+    /// The status of one of the recipient's orders changed.
+    pub const ORDER_STATUS_CHANGED: &str = "order.status_changed";
+}
+
+/// Preference key that applies to every kind without an explicit preference.
+pub const DEFAULT_PREFERENCE_KIND: &str = "*";
+
+/// How a user wants notification emails delivered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmailDelivery {
+    /// One email per notification, sent as soon as it is created.
+    Immediate,
+    /// Notifications are collected and sent as one daily digest.
+    Daily,
+    /// No email.
+    Off,
+}
+
+impl EmailDelivery {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Immediate => "immediate",
+            Self::Daily => "daily",
+            Self::Off => "off",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "immediate" => Some(Self::Immediate),
+            "daily" => Some(Self::Daily),
+            "off" => Some(Self::Off),
+            _ => None,
+        }
+    }
+}
+
+/// A notification produced by a recipient resolver, before preferences are
+/// applied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NotificationDraft {
+    pub recipient_id: Uuid,
+    pub kind: String,
+    pub title: String,
+    pub body: Option<String>,
+    pub link: Option<String>,
+    pub actor_id: Option<Uuid>,
+    pub subject_id: Option<Uuid>,
+    pub locale: String,
+    pub payload: serde_json::Value,
+}
+
+impl NotificationDraft {
+    pub fn new(recipient_id: Uuid, kind: impl Into<String>, title: impl Into<String>) -> Self {
+        Self {
+            recipient_id,
+            kind: kind.into(),
+            title: title.into(),
+            body: None,
+            link: None,
+            actor_id: None,
+            subject_id: None,
+            locale: "en".to_string(),
+            payload: serde_json::json!({}),
+        }
+    }
+
+    pub fn with_body(mut self, body: impl Into<String>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+
+    pub fn with_link(mut self, link: impl Into<String>) -> Self {
+        self.link = Some(link.into());
+        self
+    }
+
+    pub fn with_actor(mut self, actor_id: Option<Uuid>) -> Self {
+        self.actor_id = actor_id;
+        self
+    }
+
+    pub fn with_subject(mut self, subject_id: Uuid) -> Self {
+        self.subject_id = Some(subject_id);
+        self
+    }
+
+    pub fn with_locale(mut self, locale: impl Into<String>) -> Self {
+        self.locale = locale.into();
+        self
+    }
+
+    pub fn with_payload(mut self, payload: serde_json::Value) -> Self {
+        self.payload = payload;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationRecord {
+    pub id: Uuid,
+    pub kind: String,
+    pub title: String,
+    pub body: Option<String>,
+    pub link: Option<String>,
+    pub actor_id: Option<Uuid>,
+    pub subject_id: Option<Uuid>,
+    pub payload: serde_json::Value,
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl NotificationRecord {
+    pub fn is_read(&self) -> bool {
+        self.read_at.is_some()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListNotificationsFilter {
+    #[serde(default)]
+    pub unread_only: bool,
+    pub kind: Option<String>,
+    #[serde(default = "default_page")]
+    pub page: u64,
+    #[serde(default = "default_per_page")]
+    pub per_page: u64,
+}
+
+impl Default for ListNotificationsFilter {
+    fn default() -> Self {
+        Self {
+            unread_only: false,
+            kind: None,
+            page: default_page(),
+            per_page: default_per_page(),
+        }
+    }
+}
+
+/// Effective delivery preference for one notification kind.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationPreferenceRecord {
+    /// Notification kind, or `*` for the user-wide default.
+    pub kind: String,
+    pub in_app: bool,
+    pub email: EmailDelivery,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateNotificationPreferenceInput {
+    /// Notification kind, or `*` to change the user-wide default.
+    pub kind: String,
+    pub in_app: bool,
+    pub email: EmailDelivery,
+}
+
+/// Outcome of delivering the drafts of one event.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DispatchSummary {
+    pub stored: usize,
+    pub emailed: usize,
+    pub queued_for_digest: usize,
+    pub skipped: usize,
+}
+
+/// Outcome of a daily digest run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DigestRunSummary {
+    pub recipients: usize,
+    pub notifications: usize,
+    pub failed_recipients: usize,
+}
+
+fn default_page() -> u64 {
+    1
+}
+
+fn default_per_page() -> u64 {
+    20
+}
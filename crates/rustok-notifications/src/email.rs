@@ -0,0 +1,196 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use rustok_email::template::render_tera_string;
+use rustok_email::{EmailTemplateProvider, RenderedEmail, TransactionalEmailSender};
+use sea_orm::sea_query::{Alias, Expr, Query};
+use sea_orm::{ConnectionTrait, DatabaseConnection};
+use uuid::Uuid;
+
+use crate::error::NotificationsResult;
+
+/// Template for a single notification email.
+pub const TEMPLATE_NOTIFICATION: &str = "notifications/notification";
+/// Template for the daily digest email.
+pub const TEMPLATE_DAILY_DIGEST: &str = "notifications/daily_digest";
+
+/// Where to send notification email for a user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecipientAddress {
+    pub email: String,
+}
+
+/// Resolves user ids to email addresses.
+#[async_trait]
+pub trait RecipientDirectory: Send + Sync {
+    async fn lookup(
+        &self,
+        tenant_id: Uuid,
+        user_id: Uuid,
+    ) -> NotificationsResult<Option<RecipientAddress>>;
+}
+
+/// Looks recipients up in the platform `users` table.
+pub struct UserTableDirectory {
+    db: DatabaseConnection,
+}
+
+impl UserTableDirectory {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl RecipientDirectory for UserTableDirectory {
+    async fn lookup(
+        &self,
+        tenant_id: Uuid,
+        user_id: Uuid,
+    ) -> NotificationsResult<Option<RecipientAddress>> {
+        let query = Query::select()
+            .column(Alias::new("email"))
+            .from(Alias::new("users"))
+            .and_where(Expr::col(Alias::new("id")).eq(user_id))
+            .and_where(Expr::col(Alias::new("tenant_id")).eq(tenant_id))
+            .to_owned();
+        let row = self
+            .db
+            .query_one(self.db.get_database_backend().build(&query))
+            .await?;
+
+        Ok(row
+            .and_then(|row| row.try_get::<String>("", "email").ok())
+            .filter(|email| !email.trim().is_empty())
+            .map(|email| RecipientAddress { email }))
+    }
+}
+
+/// Email fan-out for notifications. Without it the notification center only
+/// writes to the in-app inbox.
+#[derive(Clone)]
+pub struct NotificationEmailRuntime {
+    sender: Arc<dyn TransactionalEmailSender>,
+    directory: Arc<dyn RecipientDirectory>,
+}
+
+impl NotificationEmailRuntime {
+    pub fn new(
+        sender: Arc<dyn TransactionalEmailSender>,
+        directory: Arc<dyn RecipientDirectory>,
+    ) -> Self {
+        Self { sender, directory }
+    }
+
+    /// Sends `template_id` to the user. Returns `false` when the user has no
+    /// email address on file.
+    pub(crate) async fn send(
+        &self,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        template_id: &str,
+        locale: &str,
+        vars: &serde_json::Value,
+    ) -> NotificationsResult<bool> {
+        let Some(address) = self.directory.lookup(tenant_id, user_id).await? else {
+            return Ok(false);
+        };
+        self.sender
+            .send_transactional(template_id, locale, &address.email, vars)
+            .await?;
+        Ok(true)
+    }
+}
+
+/// Email templates for the `notifications/*` namespace.
+pub struct NotificationEmailTemplates;
+
+impl EmailTemplateProvider for NotificationEmailTemplates {
+    fn namespace(&self) -> &str {
+        "notifications"
+    }
+
+    fn render(
+        &self,
+        template_id: &str,
+        locale: &str,
+        vars: &serde_json::Value,
+    ) -> Option<rustok_email::error::Result<RenderedEmail>> {
+        let russian = locale.starts_with("ru");
+        let (subject, text, html) = match template_id {
+            TEMPLATE_NOTIFICATION if russian => (
+                "{{ title }}",
+                "{{ title }}\n{% if body %}\n{{ body }}\n{% endif %}{% if link %}\nОткрыть: {{ link }}\n{% endif %}",
+                "<p><strong>{{ title }}</strong></p>{% if body %}<p>{{ body }}</p>{% endif %}{% if link %}<p><a href=\"{{ link }}\">Открыть</a></p>{% endif %}",
+            ),
+            TEMPLATE_NOTIFICATION => (
+                "{{ title }}",
+                "{{ title }}\n{% if body %}\n{{ body }}\n{% endif %}{% if link %}\nOpen: {{ link }}\n{% endif %}",
+                "<p><strong>{{ title }}</strong></p>{% if body %}<p>{{ body }}</p>{% endif %}{% if link %}<p><a href=\"{{ link }}\">Open</a></p>{% endif %}",
+            ),
+            TEMPLATE_DAILY_DIGEST if russian => (
+                "Ваши уведомления за день: {{ count }}",
+                "Новых уведомлений: {{ count }}\n{% for item in items %}\n- {{ item.title }}{% if item.link %} ({{ item.link }}){% endif %}{% endfor %}\n",
+                "<p>Новых уведомлений: {{ count }}</p><ul>{% for item in items %}<li>{% if item.link %}<a href=\"{{ item.link }}\">{{ item.title }}</a>{% else %}{{ item.title }}{% endif %}</li>{% endfor %}</ul>",
+            ),
+            TEMPLATE_DAILY_DIGEST => (
+                "Your daily notification digest ({{ count }})",
+                "You have {{ count }} new notifications.\n{% for item in items %}\n- {{ item.title }}{% if item.link %} ({{ item.link }}){% endif %}{% endfor %}\n",
+                "<p>You have {{ count }} new notifications.</p><ul>{% for item in items %}<li>{% if item.link %}<a href=\"{{ item.link }}\">{{ item.title }}</a>{% else %}{{ item.title }}{% endif %}</li>{% endfor %}</ul>",
+            ),
+            _ => return None,
+        };
+
+        Some(render_parts(subject, text, html, vars))
+    }
+}
+
+fn render_parts(
+    subject: &str,
+    text: &str,
+    html: &str,
+    vars: &serde_json::Value,
+) -> rustok_email::error::Result<RenderedEmail> {
+    Ok(RenderedEmail {
+        subject: render_tera_string(subject, vars)?,
+        text: render_tera_string(text, vars)?,
+        html: render_tera_string(html, vars)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digest_template_lists_items_per_locale() {
+        let vars = serde_json::json!({
+            "count": 2,
+            "items": [
+                { "title": "New reply in \"Welcome\"", "link": "/modules/forum?topic=1" },
+                { "title": "Order shipped", "link": null },
+            ],
+        });
+
+        let en = NotificationEmailTemplates
+            .render(TEMPLATE_DAILY_DIGEST, "en", &vars)
+            .expect("digest template should be handled")
+            .expect("digest should render");
+        assert!(en.subject.contains('2'));
+        assert!(en.html.contains("href=\"/modules/forum?topic=1\""));
+        assert!(en.text.contains("Order shipped"));
+
+        let ru = NotificationEmailTemplates
+            .render(TEMPLATE_DAILY_DIGEST, "ru-RU", &vars)
+            .expect("digest template should be handled")
+            .expect("digest should render");
+        assert!(ru.subject.starts_with("Ваши"));
+    }
+
+    #[test]
+    fn unknown_templates_are_left_to_other_providers() {
+        assert!(NotificationEmailTemplates
+            .render("forum/new_reply", "en", &serde_json::json!({}))
+            .is_none());
+    }
+}
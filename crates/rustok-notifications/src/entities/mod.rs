@@ -0,0 +1,7 @@
+pub mod notification;
+pub mod notification_email_delivery;
+pub mod notification_preference;
+
+pub use notification::Entity as Notification;
+pub use notification_email_delivery::Entity as NotificationEmailDelivery;
+pub use notification_preference::Entity as NotificationPreference;
@@ -0,0 +1,38 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "notifications")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub recipient_id: Uuid,
+    pub kind: String,
+    pub title: String,
+    pub body: Option<String>,
+    pub link: Option<String>,
+    pub actor_id: Option<Uuid>,
+    pub subject_id: Option<Uuid>,
+    pub event_id: Option<Uuid>,
+    pub locale: String,
+    pub payload: Json,
+    /// Whether the notification is shown in the in-app inbox. Email-only
+    /// notifications are still stored so digests can render them.
+    pub in_app: bool,
+    pub read_at: Option<DateTimeWithTimeZone>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::notification_email_delivery::Entity")]
+    EmailDeliveries,
+}
+
+impl Related<super::notification_email_delivery::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::EmailDeliveries.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
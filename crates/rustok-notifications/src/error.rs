@@ -0,0 +1,25 @@
+use thiserror::Error;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum NotificationsError {
+    #[error("Notification not found: {0}")]
+    NotFound(Uuid),
+
+    #[error("Validation error: {0}")]
+    Validation(String),
+
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
+    #[error("Email delivery failed: {0}")]
+    Email(#[from] rustok_email::EmailError),
+
+    #[error("Database error: {0}")]
+    Database(#[from] sea_orm::DbErr),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+pub type NotificationsResult<T> = Result<T, NotificationsError>;
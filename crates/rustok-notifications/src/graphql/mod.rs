@@ -0,0 +1,36 @@
+mod mutation;
+mod query;
+mod types;
+
+use async_graphql::{Context, FieldError, Result};
+use rustok_api::{graphql::GraphQLError, AuthContext};
+
+use crate::NotificationsError;
+
+pub use mutation::NotificationsMutation;
+pub use query::NotificationsQuery;
+pub use types::*;
+
+pub(crate) const MODULE_SLUG: &str = "notifications";
+
+/// Notifications are always scoped to the calling user.
+pub(crate) fn require_user(ctx: &Context<'_>) -> Result<AuthContext> {
+    ctx.data::<AuthContext>()
+        .cloned()
+        .map_err(|_| <FieldError as GraphQLError>::unauthenticated())
+}
+
+pub(crate) fn map_notifications_error(error: NotificationsError) -> FieldError {
+    match error {
+        NotificationsError::NotFound(_) => {
+            <FieldError as GraphQLError>::not_found(&error.to_string())
+        }
+        NotificationsError::Validation(message) => {
+            <FieldError as GraphQLError>::bad_user_input(&message)
+        }
+        NotificationsError::Forbidden(message) => {
+            <FieldError as GraphQLError>::permission_denied(&message)
+        }
+        other => <FieldError as GraphQLError>::internal_error(&other.to_string()),
+    }
+}
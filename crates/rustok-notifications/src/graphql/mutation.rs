@@ -0,0 +1,57 @@
+use async_graphql::{Context, Object, Result};
+use rustok_api::{graphql::require_module_enabled, TenantContext};
+use sea_orm::DatabaseConnection;
+use uuid::Uuid;
+
+use crate::services::{NotificationService, PreferenceService};
+
+use super::{map_notifications_error, require_user, types::*, MODULE_SLUG};
+
+#[derive(Default)]
+pub struct NotificationsMutation;
+
+#[Object]
+impl NotificationsMutation {
+    async fn mark_notification_read(&self, ctx: &Context<'_>, id: Uuid) -> Result<GqlNotification> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let tenant = ctx.data::<TenantContext>()?;
+        let auth = require_user(ctx)?;
+
+        let record = NotificationService::new(db.clone())
+            .mark_read(tenant.id, auth.user_id, id)
+            .await
+            .map_err(map_notifications_error)?;
+        Ok(record.into())
+    }
+
+    /// Marks the whole inbox as read and returns how many entries changed.
+    async fn mark_all_notifications_read(&self, ctx: &Context<'_>) -> Result<u64> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let tenant = ctx.data::<TenantContext>()?;
+        let auth = require_user(ctx)?;
+
+        NotificationService::new(db.clone())
+            .mark_all_read(tenant.id, auth.user_id)
+            .await
+            .map_err(map_notifications_error)
+    }
+
+    async fn update_notification_preference(
+        &self,
+        ctx: &Context<'_>,
+        input: GqlUpdateNotificationPreferenceInput,
+    ) -> Result<GqlNotificationPreference> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let tenant = ctx.data::<TenantContext>()?;
+        let auth = require_user(ctx)?;
+
+        let record = PreferenceService::new(db.clone())
+            .update(tenant.id, auth.user_id, input.into())
+            .await
+            .map_err(map_notifications_error)?;
+        Ok(record.into())
+    }
+}
@@ -0,0 +1,82 @@
+use async_graphql::{Context, Object, Result};
+use rustok_api::{graphql::require_module_enabled, TenantContext};
+use sea_orm::DatabaseConnection;
+
+use crate::dto::ListNotificationsFilter;
+use crate::services::{NotificationService, PreferenceService};
+
+use super::{map_notifications_error, require_user, types::*, MODULE_SLUG};
+
+#[derive(Default)]
+pub struct NotificationsQuery;
+
+#[Object]
+impl NotificationsQuery {
+    /// The current user's in-app inbox, newest first.
+    async fn notifications(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default)] unread_only: bool,
+        kind: Option<String>,
+        #[graphql(default = 1)] page: u64,
+        #[graphql(default = 20)] per_page: u64,
+    ) -> Result<GqlNotificationPage> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let tenant = ctx.data::<TenantContext>()?;
+        let auth = require_user(ctx)?;
+
+        let service = NotificationService::new(db.clone());
+        let (items, total) = service
+            .list(
+                tenant.id,
+                auth.user_id,
+                ListNotificationsFilter {
+                    unread_only,
+                    kind,
+                    page,
+                    per_page,
+                },
+            )
+            .await
+            .map_err(map_notifications_error)?;
+        let unread_count = service
+            .unread_count(tenant.id, auth.user_id)
+            .await
+            .map_err(map_notifications_error)?;
+
+        Ok(GqlNotificationPage {
+            items: items.into_iter().map(Into::into).collect(),
+            total,
+            unread_count,
+        })
+    }
+
+    async fn notification_unread_count(&self, ctx: &Context<'_>) -> Result<u64> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let tenant = ctx.data::<TenantContext>()?;
+        let auth = require_user(ctx)?;
+
+        NotificationService::new(db.clone())
+            .unread_count(tenant.id, auth.user_id)
+            .await
+            .map_err(map_notifications_error)
+    }
+
+    async fn notification_preferences(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Vec<GqlNotificationPreference>> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let tenant = ctx.data::<TenantContext>()?;
+        let auth = require_user(ctx)?;
+
+        let preferences = PreferenceService::new(db.clone())
+            .list(tenant.id, auth.user_id)
+            .await
+            .map_err(map_notifications_error)?;
+        Ok(preferences.into_iter().map(Into::into).collect())
+    }
+}
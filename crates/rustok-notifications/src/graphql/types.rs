@@ -0,0 +1,111 @@
+use async_graphql::{Enum, InputObject, SimpleObject};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::dto::{
+    EmailDelivery, NotificationPreferenceRecord, NotificationRecord,
+    UpdateNotificationPreferenceInput,
+};
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum GqlEmailDelivery {
+    Immediate,
+    Daily,
+    Off,
+}
+
+impl From<EmailDelivery> for GqlEmailDelivery {
+    fn from(value: EmailDelivery) -> Self {
+        match value {
+            EmailDelivery::Immediate => Self::Immediate,
+            EmailDelivery::Daily => Self::Daily,
+            EmailDelivery::Off => Self::Off,
+        }
+    }
+}
+
+impl From<GqlEmailDelivery> for EmailDelivery {
+    fn from(value: GqlEmailDelivery) -> Self {
+        match value {
+            GqlEmailDelivery::Immediate => Self::Immediate,
+            GqlEmailDelivery::Daily => Self::Daily,
+            GqlEmailDelivery::Off => Self::Off,
+        }
+    }
+}
+
+#[derive(SimpleObject, Clone, Debug)]
+pub struct GqlNotification {
+    pub id: Uuid,
+    pub kind: String,
+    pub title: String,
+    pub body: Option<String>,
+    pub link: Option<String>,
+    pub actor_id: Option<Uuid>,
+    pub subject_id: Option<Uuid>,
+    pub payload: serde_json::Value,
+    pub is_read: bool,
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<NotificationRecord> for GqlNotification {
+    fn from(record: NotificationRecord) -> Self {
+        Self {
+            is_read: record.is_read(),
+            id: record.id,
+            kind: record.kind,
+            title: record.title,
+            body: record.body,
+            link: record.link,
+            actor_id: record.actor_id,
+            subject_id: record.subject_id,
+            payload: record.payload,
+            read_at: record.read_at,
+            created_at: record.created_at,
+        }
+    }
+}
+
+#[derive(SimpleObject, Clone, Debug)]
+pub struct GqlNotificationPage {
+    pub items: Vec<GqlNotification>,
+    pub total: u64,
+    pub unread_count: u64,
+}
+
+#[derive(SimpleObject, Clone, Debug)]
+pub struct GqlNotificationPreference {
+    /// Notification kind, or `*` for the user-wide default.
+    pub kind: String,
+    pub in_app: bool,
+    pub email: GqlEmailDelivery,
+}
+
+impl From<NotificationPreferenceRecord> for GqlNotificationPreference {
+    fn from(record: NotificationPreferenceRecord) -> Self {
+        Self {
+            kind: record.kind,
+            in_app: record.in_app,
+            email: record.email.into(),
+        }
+    }
+}
+
+#[derive(InputObject, Clone, Debug)]
+pub struct GqlUpdateNotificationPreferenceInput {
+    /// Notification kind, or `*` to change the user-wide default.
+    pub kind: String,
+    pub in_app: bool,
+    pub email: GqlEmailDelivery,
+}
+
+impl From<GqlUpdateNotificationPreferenceInput> for UpdateNotificationPreferenceInput {
+    fn from(input: GqlUpdateNotificationPreferenceInput) -> Self {
+        Self {
+            kind: input.kind,
+            in_app: input.in_app,
+            email: input.email.into(),
+        }
+    }
+}
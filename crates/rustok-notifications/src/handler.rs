@@ -0,0 +1,90 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use rustok_core::events::{EventEnvelope, EventHandler, HandlerResult};
+use sea_orm::DatabaseConnection;
+use tracing::debug;
+
+use crate::email::NotificationEmailRuntime;
+use crate::resolvers::{default_resolvers, RecipientResolver};
+use crate::services::NotificationService;
+
+/// Consumes domain events, resolves recipients and delivers notifications.
+pub struct NotificationEventHandler {
+    db: DatabaseConnection,
+    service: NotificationService,
+    resolvers: Vec<Arc<dyn RecipientResolver>>,
+}
+
+impl NotificationEventHandler {
+    /// Creates a handler with the built-in resolvers and in-app delivery only.
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self {
+            service: NotificationService::new(db.clone()),
+            db,
+            resolvers: default_resolvers(),
+        }
+    }
+
+    pub fn with_email(mut self, email: NotificationEmailRuntime) -> Self {
+        self.service = self.service.with_email(email);
+        self
+    }
+
+    pub fn with_resolver(mut self, resolver: Arc<dyn RecipientResolver>) -> Self {
+        self.resolvers.push(resolver);
+        self
+    }
+}
+
+#[async_trait]
+impl EventHandler for NotificationEventHandler {
+    fn name(&self) -> &'static str {
+        "notifications"
+    }
+
+    fn handles(&self, event: &rustok_core::events::DomainEvent) -> bool {
+        self.resolvers
+            .iter()
+            .any(|resolver| resolver.handles(event))
+    }
+
+    async fn handle(&self, envelope: &EventEnvelope) -> HandlerResult {
+        let mut drafts = Vec::new();
+        for resolver in &self.resolvers {
+            if !resolver.handles(&envelope.event) {
+                continue;
+            }
+            let resolved = resolver
+                .resolve(&self.db, envelope)
+                .await
+                .map_err(|error| {
+                    rustok_core::Error::External(format!(
+                        "Notification resolver {} failed: {error}",
+                        resolver.name()
+                    ))
+                })?;
+            drafts.extend(resolved);
+        }
+        if drafts.is_empty() {
+            return Ok(());
+        }
+
+        let summary = self
+            .service
+            .dispatch(envelope.tenant_id, Some(envelope.id), drafts)
+            .await
+            .map_err(|error| {
+                rustok_core::Error::External(format!("Notification dispatch failed: {error}"))
+            })?;
+        debug!(
+            event_type = %envelope.event_type,
+            stored = summary.stored,
+            emailed = summary.emailed,
+            queued = summary.queued_for_digest,
+            skipped = summary.skipped,
+            "Notifications dispatched"
+        );
+        Ok(())
+    }
+}
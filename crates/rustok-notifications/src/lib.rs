@@ -0,0 +1,93 @@
+//! Notification center for RusToK.
+//!
+//! Consumes domain events (forum replies and new topics, blog comments, order
+//! status changes), resolves recipients and delivers notifications:
+//!
+//! - in-app inbox with read/unread state (`notifications`);
+//! - email via `rustok_email::TransactionalEmailSender`, either immediately or
+//!   as a daily digest (`notification_email_deliveries`);
+//! - per-user channel preferences per notification kind
+//!   (`notification_preferences`).
+//!
+//! Recipients are resolved by [`RecipientResolver`] implementations; hosts can
+//! add their own through [`NotificationEventHandler::with_resolver`].
+
+use async_trait::async_trait;
+use rustok_core::{
+    MigrationSource, ModuleEventListenerContext, ModuleEventListenerRegistry, RusToKModule,
+};
+use sea_orm_migration::MigrationTrait;
+
+pub mod dto;
+pub mod email;
+pub mod entities;
+pub mod error;
+pub mod graphql;
+pub mod handler;
+pub mod migrations;
+pub mod resolvers;
+pub mod services;
+
+pub use dto::*;
+pub use email::{
+    NotificationEmailRuntime, NotificationEmailTemplates, RecipientAddress, RecipientDirectory,
+    UserTableDirectory, TEMPLATE_DAILY_DIGEST, TEMPLATE_NOTIFICATION,
+};
+pub use error::{NotificationsError, NotificationsResult};
+pub use graphql::{NotificationsMutation, NotificationsQuery};
+pub use handler::NotificationEventHandler;
+pub use resolvers::{default_resolvers, RecipientResolver};
+pub use services::{DigestService, NotificationService, PreferenceService, DEFAULT_EMAIL_DELIVERY};
+
+pub struct NotificationsModule;
+
+#[async_trait]
+impl RusToKModule for NotificationsModule {
+    fn slug(&self) -> &'static str {
+        "notifications"
+    }
+
+    fn name(&self) -> &'static str {
+        "Notifications"
+    }
+
+    fn description(&self) -> &'static str {
+        "In-app notification inbox, delivery preferences and email digests"
+    }
+
+    fn version(&self) -> &'static str {
+        env!("CARGO_PKG_VERSION")
+    }
+
+    fn register_event_listeners(
+        &self,
+        registry: &mut ModuleEventListenerRegistry,
+        ctx: &ModuleEventListenerContext<'_>,
+    ) {
+        let mut handler = NotificationEventHandler::new(ctx.db.clone());
+        if let Some(email) = ctx.extensions.get::<NotificationEmailRuntime>() {
+            handler = handler.with_email(email.clone());
+        }
+        registry.register(handler);
+    }
+}
+
+impl MigrationSource for NotificationsModule {
+    fn migrations(&self) -> Vec<Box<dyn MigrationTrait>> {
+        migrations::migrations()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn module_metadata() {
+        let module = NotificationsModule;
+        assert_eq!(module.slug(), "notifications");
+        assert_eq!(module.name(), "Notifications");
+        assert_eq!(module.version(), env!("CARGO_PKG_VERSION"));
+        assert!(!module.migrations().is_empty());
+    }
+}
@@ -0,0 +1,292 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Notifications::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Notifications::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Notifications::TenantId).uuid().not_null())
+                    .col(ColumnDef::new(Notifications::RecipientId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(Notifications::Kind)
+                            .string_len(64)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Notifications::Title)
+                            .string_len(255)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Notifications::Body).text())
+                    .col(ColumnDef::new(Notifications::Link).string_len(1024))
+                    .col(ColumnDef::new(Notifications::ActorId).uuid())
+                    .col(ColumnDef::new(Notifications::SubjectId).uuid())
+                    .col(ColumnDef::new(Notifications::EventId).uuid())
+                    .col(
+                        ColumnDef::new(Notifications::Locale)
+                            .string_len(32)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Notifications::Payload).json().not_null())
+                    .col(
+                        ColumnDef::new(Notifications::InApp)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .col(ColumnDef::new(Notifications::ReadAt).timestamp_with_time_zone())
+                    .col(
+                        ColumnDef::new(Notifications::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_notifications_inbox")
+                    .table(Notifications::Table)
+                    .col(Notifications::TenantId)
+                    .col(Notifications::RecipientId)
+                    .col(Notifications::InApp)
+                    .col(Notifications::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_notifications_event_recipient")
+                    .table(Notifications::Table)
+                    .col(Notifications::EventId)
+                    .col(Notifications::RecipientId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(NotificationPreferences::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(NotificationPreferences::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationPreferences::TenantId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationPreferences::UserId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationPreferences::Kind)
+                            .string_len(64)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationPreferences::InApp)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationPreferences::Email)
+                            .string_len(16)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationPreferences::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_notification_preferences_user_kind")
+                    .table(NotificationPreferences::Table)
+                    .col(NotificationPreferences::TenantId)
+                    .col(NotificationPreferences::UserId)
+                    .col(NotificationPreferences::Kind)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(NotificationEmailDeliveries::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(NotificationEmailDeliveries::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationEmailDeliveries::TenantId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationEmailDeliveries::NotificationId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationEmailDeliveries::RecipientId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationEmailDeliveries::Mode)
+                            .string_len(16)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationEmailDeliveries::Status)
+                            .string_len(16)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationEmailDeliveries::Attempts)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(NotificationEmailDeliveries::LastError).text())
+                    .col(
+                        ColumnDef::new(NotificationEmailDeliveries::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(NotificationEmailDeliveries::SentAt)
+                            .timestamp_with_time_zone(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                NotificationEmailDeliveries::Table,
+                                NotificationEmailDeliveries::NotificationId,
+                            )
+                            .to(Notifications::Table, Notifications::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_notification_email_deliveries_pending")
+                    .table(NotificationEmailDeliveries::Table)
+                    .col(NotificationEmailDeliveries::Status)
+                    .col(NotificationEmailDeliveries::Mode)
+                    .col(NotificationEmailDeliveries::TenantId)
+                    .col(NotificationEmailDeliveries::RecipientId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(NotificationEmailDeliveries::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(NotificationPreferences::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Notifications::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum Notifications {
+    Table,
+    Id,
+    TenantId,
+    RecipientId,
+    Kind,
+    Title,
+    Body,
+    Link,
+    ActorId,
+    SubjectId,
+    EventId,
+    Locale,
+    Payload,
+    InApp,
+    ReadAt,
+    CreatedAt,
+}
+
+#[derive(Iden)]
+enum NotificationPreferences {
+    Table,
+    Id,
+    TenantId,
+    UserId,
+    Kind,
+    InApp,
+    Email,
+    UpdatedAt,
+}
+
+#[derive(Iden)]
+enum NotificationEmailDeliveries {
+    Table,
+    Id,
+    TenantId,
+    NotificationId,
+    RecipientId,
+    Mode,
+    Status,
+    Attempts,
+    LastError,
+    CreatedAt,
+    SentAt,
+}
@@ -0,0 +1,9 @@
+mod m20260613_000001_create_notifications_tables;
+
+use sea_orm_migration::MigrationTrait;
+
+pub fn migrations() -> Vec<Box<dyn MigrationTrait>> {
+    vec![Box::new(
+        m20260613_000001_create_notifications_tables::Migration,
+    )]
+}
@@ -0,0 +1,452 @@
+//! Recipient resolvers turn a domain event into notification drafts.
+//!
+//! The built-in resolvers read the owning modules' tables directly (the same
+//! approach `rustok-index` uses) so the notification center does not depend
+//! on the forum, blog or commerce crates.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use rustok_events::{DomainEvent, EventEnvelope};
+use sea_orm::sea_query::{Alias, Expr, Order, Query, SelectStatement};
+use sea_orm::{ConnectionTrait, DatabaseConnection};
+use uuid::Uuid;
+
+use crate::dto::{kinds, NotificationDraft};
+use crate::error::NotificationsResult;
+
+/// Resolves who should be notified about an event.
+#[async_trait]
+pub trait RecipientResolver: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn handles(&self, event: &DomainEvent) -> bool;
+
+    async fn resolve(
+        &self,
+        db: &DatabaseConnection,
+        envelope: &EventEnvelope,
+    ) -> NotificationsResult<Vec<NotificationDraft>>;
+}
+
+/// Resolvers registered by [`crate::NotificationEventHandler::new`].
+pub fn default_resolvers() -> Vec<Arc<dyn RecipientResolver>> {
+    vec![
+        Arc::new(ForumReplyResolver),
+        Arc::new(ForumTopicResolver),
+        Arc::new(BlogCommentResolver),
+        Arc::new(MentionResolver),
+        Arc::new(OrderStatusResolver),
+    ]
+}
+
+/// Notifies topic subscribers and the topic author about new replies.
+pub struct ForumReplyResolver;
+
+#[async_trait]
+impl RecipientResolver for ForumReplyResolver {
+    fn name(&self) -> &'static str {
+        "forum_reply"
+    }
+
+    fn handles(&self, event: &DomainEvent) -> bool {
+        matches!(event, DomainEvent::ForumTopicReplied { .. })
+    }
+
+    async fn resolve(
+        &self,
+        db: &DatabaseConnection,
+        envelope: &EventEnvelope,
+    ) -> NotificationsResult<Vec<NotificationDraft>> {
+        let DomainEvent::ForumTopicReplied {
+            topic_id,
+            reply_id,
+            author_id,
+        } = &envelope.event
+        else {
+            return Ok(Vec::new());
+        };
+        let tenant_id = envelope.tenant_id;
+
+        let topic = Query::select()
+            .columns([Alias::new("category_id"), Alias::new("author_id")])
+            .from(Alias::new("forum_topics"))
+            .and_where(Expr::col(Alias::new("id")).eq(*topic_id))
+            .and_where(Expr::col(Alias::new("tenant_id")).eq(tenant_id))
+            .to_owned();
+        let Some(topic) = db
+            .query_one(db.get_database_backend().build(&topic))
+            .await?
+        else {
+            return Ok(Vec::new());
+        };
+        let category_id: Uuid = topic.try_get("", "category_id")?;
+        let topic_author: Option<Uuid> = topic.try_get("", "author_id")?;
+
+        let subscribers = Query::select()
+            .column(Alias::new("user_id"))
+            .from(Alias::new("forum_topic_subscriptions"))
+            .and_where(Expr::col(Alias::new("topic_id")).eq(*topic_id))
+            .and_where(Expr::col(Alias::new("tenant_id")).eq(tenant_id))
+            .to_owned();
+        let mut recipients = select_uuids(db, &subscribers, "user_id").await?;
+        recipients.extend(topic_author);
+
+        let (locale, title) = topic_title(db, *topic_id, None).await?;
+        let actor = envelope.actor_id.or(*author_id);
+        let link = format!("/modules/forum?category={category_id}&topic={topic_id}");
+
+        Ok(recipients
+            .into_iter()
+            .map(|recipient| {
+                NotificationDraft::new(
+                    recipient,
+                    kinds::FORUM_TOPIC_REPLY,
+                    format!("New reply in \"{title}\""),
+                )
+                .with_link(link.clone())
+                .with_actor(actor)
+                .with_subject(*topic_id)
+                .with_locale(locale.clone())
+                .with_payload(serde_json::json!({
+                    "topic_id": topic_id,
+                    "reply_id": reply_id,
+                }))
+            })
+            .collect())
+    }
+}
+
+/// Notifies category subscribers about new topics.
+pub struct ForumTopicResolver;
+
+#[async_trait]
+impl RecipientResolver for ForumTopicResolver {
+    fn name(&self) -> &'static str {
+        "forum_topic"
+    }
+
+    fn handles(&self, event: &DomainEvent) -> bool {
+        matches!(event, DomainEvent::ForumTopicCreated { .. })
+    }
+
+    async fn resolve(
+        &self,
+        db: &DatabaseConnection,
+        envelope: &EventEnvelope,
+    ) -> NotificationsResult<Vec<NotificationDraft>> {
+        let DomainEvent::ForumTopicCreated {
+            topic_id,
+            category_id,
+            author_id,
+            locale,
+        } = &envelope.event
+        else {
+            return Ok(Vec::new());
+        };
+
+        let subscribers = Query::select()
+            .column(Alias::new("user_id"))
+            .from(Alias::new("forum_category_subscriptions"))
+            .and_where(Expr::col(Alias::new("category_id")).eq(*category_id))
+            .and_where(Expr::col(Alias::new("tenant_id")).eq(envelope.tenant_id))
+            .to_owned();
+        let recipients = select_uuids(db, &subscribers, "user_id").await?;
+        if recipients.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let (locale, title) = topic_title(db, *topic_id, Some(locale)).await?;
+        let actor = envelope.actor_id.or(*author_id);
+        let link = format!("/modules/forum?category={category_id}&topic={topic_id}");
+
+        Ok(recipients
+            .into_iter()
+            .map(|recipient| {
+                NotificationDraft::new(
+                    recipient,
+                    kinds::FORUM_CATEGORY_TOPIC,
+                    format!("New topic: {title}"),
+                )
+                .with_link(link.clone())
+                .with_actor(actor)
+                .with_subject(*topic_id)
+                .with_locale(locale.clone())
+                .with_payload(serde_json::json!({
+                    "topic_id": topic_id,
+                    "category_id": category_id,
+                }))
+            })
+            .collect())
+    }
+}
+
+/// Notifies the parent comment author about replies, or the post author about
+/// new top-level comments.
+pub struct BlogCommentResolver;
+
+#[async_trait]
+impl RecipientResolver for BlogCommentResolver {
+    fn name(&self) -> &'static str {
+        "blog_comment"
+    }
+
+    fn handles(&self, event: &DomainEvent) -> bool {
+        matches!(event, DomainEvent::BlogCommentCreated { .. })
+    }
+
+    async fn resolve(
+        &self,
+        db: &DatabaseConnection,
+        envelope: &EventEnvelope,
+    ) -> NotificationsResult<Vec<NotificationDraft>> {
+        let DomainEvent::BlogCommentCreated {
+            post_id,
+            comment_id,
+            parent_comment_id,
+            author_id,
+        } = &envelope.event
+        else {
+            return Ok(Vec::new());
+        };
+        let tenant_id = envelope.tenant_id;
+
+        let post = Query::select()
+            .columns([Alias::new("author_id"), Alias::new("slug")])
+            .from(Alias::new("blog_posts"))
+            .and_where(Expr::col(Alias::new("id")).eq(*post_id))
+            .and_where(Expr::col(Alias::new("tenant_id")).eq(tenant_id))
+            .to_owned();
+        let Some(post) = db.query_one(db.get_database_backend().build(&post)).await? else {
+            return Ok(Vec::new());
+        };
+        let slug: String = post.try_get("", "slug")?;
+
+        let (kind, recipient) = match parent_comment_id {
+            Some(parent_id) => {
+                let parent = Query::select()
+                    .column(Alias::new("author_id"))
+                    .from(Alias::new("comments"))
+                    .and_where(Expr::col(Alias::new("id")).eq(*parent_id))
+                    .and_where(Expr::col(Alias::new("tenant_id")).eq(tenant_id))
+                    .to_owned();
+                let Some(author) = select_uuids(db, &parent, "author_id").await?.pop() else {
+                    return Ok(Vec::new());
+                };
+                (kinds::BLOG_COMMENT_REPLY, author)
+            }
+            None => (kinds::BLOG_POST_COMMENT, post.try_get("", "author_id")?),
+        };
+
+        let title = match kind {
+            kinds::BLOG_COMMENT_REPLY => "New reply to your comment".to_string(),
+            _ => "New comment on your post".to_string(),
+        };
+        Ok(vec![NotificationDraft::new(recipient, kind, title)
+            .with_link(format!("/modules/blog?slug={slug}"))
+            .with_actor(envelope.actor_id.or(*author_id))
+            .with_subject(*post_id)
+            .with_payload(serde_json::json!({
+                "post_id": post_id,
+                "comment_id": comment_id,
+            }))])
+    }
+}
+
+/// Notifies users @mentioned in forum replies and blog comments.
+pub struct MentionResolver;
+
+#[async_trait]
+impl RecipientResolver for MentionResolver {
+    fn name(&self) -> &'static str {
+        "mention"
+    }
+
+    fn handles(&self, event: &DomainEvent) -> bool {
+        matches!(event, DomainEvent::UserMentioned { .. })
+    }
+
+    async fn resolve(
+        &self,
+        db: &DatabaseConnection,
+        envelope: &EventEnvelope,
+    ) -> NotificationsResult<Vec<NotificationDraft>> {
+        let DomainEvent::UserMentioned {
+            mentioned_user_id,
+            author_id,
+            source_kind,
+            source_id,
+            target_id,
+        } = &envelope.event
+        else {
+            return Ok(Vec::new());
+        };
+        let tenant_id = envelope.tenant_id;
+
+        let (title, link, locale) = match source_kind.as_str() {
+            "forum_reply" => {
+                let topic = Query::select()
+                    .column(Alias::new("category_id"))
+                    .from(Alias::new("forum_topics"))
+                    .and_where(Expr::col(Alias::new("id")).eq(*target_id))
+                    .and_where(Expr::col(Alias::new("tenant_id")).eq(tenant_id))
+                    .to_owned();
+                let Some(category_id) = select_uuids(db, &topic, "category_id").await?.pop() else {
+                    return Ok(Vec::new());
+                };
+                let (locale, title) = topic_title(db, *target_id, None).await?;
+                (
+                    format!("You were mentioned in \"{title}\""),
+                    Some(format!(
+                        "/modules/forum?category={category_id}&topic={target_id}"
+                    )),
+                    Some(locale),
+                )
+            }
+            "comment" => {
+                let post = Query::select()
+                    .column(Alias::new("slug"))
+                    .from(Alias::new("blog_posts"))
+                    .and_where(Expr::col(Alias::new("id")).eq(*target_id))
+                    .and_where(Expr::col(Alias::new("tenant_id")).eq(tenant_id))
+                    .to_owned();
+                let link = match db.query_one(db.get_database_backend().build(&post)).await? {
+                    Some(row) => Some(format!(
+                        "/modules/blog?slug={}",
+                        row.try_get::<String>("", "slug")?
+                    )),
+                    None => None,
+                };
+                ("You were mentioned in a comment".to_string(), link, None)
+            }
+            _ => return Ok(Vec::new()),
+        };
+
+        let mut draft = NotificationDraft::new(*mentioned_user_id, kinds::USER_MENTION, title)
+            .with_actor(envelope.actor_id.or(*author_id))
+            .with_subject(*source_id)
+            .with_payload(serde_json::json!({
+                "source_kind": source_kind,
+                "source_id": source_id,
+                "target_id": target_id,
+            }));
+        if let Some(link) = link {
+            draft = draft.with_link(link);
+        }
+        if let Some(locale) = locale {
+            draft = draft.with_locale(locale);
+        }
+        Ok(vec![draft])
+    }
+}
+
+/// Notifies the customer's user account about order status changes.
+pub struct OrderStatusResolver;
+
+#[async_trait]
+impl RecipientResolver for OrderStatusResolver {
+    fn name(&self) -> &'static str {
+        "order_status"
+    }
+
+    fn handles(&self, event: &DomainEvent) -> bool {
+        matches!(event, DomainEvent::OrderStatusChanged { .. })
+    }
+
+    async fn resolve(
+        &self,
+        db: &DatabaseConnection,
+        envelope: &EventEnvelope,
+    ) -> NotificationsResult<Vec<NotificationDraft>> {
+        let DomainEvent::OrderStatusChanged {
+            order_id,
+            old_status,
+            new_status,
+        } = &envelope.event
+        else {
+            return Ok(Vec::new());
+        };
+
+        let owner = Query::select()
+            .column((Alias::new("customers"), Alias::new("user_id")))
+            .from(Alias::new("orders"))
+            .inner_join(
+                Alias::new("customers"),
+                Expr::col((Alias::new("customers"), Alias::new("id")))
+                    .equals((Alias::new("orders"), Alias::new("customer_id"))),
+            )
+            .and_where(Expr::col((Alias::new("orders"), Alias::new("id"))).eq(*order_id))
+            .and_where(
+                Expr::col((Alias::new("orders"), Alias::new("tenant_id"))).eq(envelope.tenant_id),
+            )
+            .to_owned();
+        let Some(recipient) = select_uuids(db, &owner, "user_id").await?.pop() else {
+            return Ok(Vec::new());
+        };
+
+        Ok(vec![NotificationDraft::new(
+            recipient,
+            kinds::ORDER_STATUS_CHANGED,
+            format!("Your order status changed to {new_status}"),
+        )
+        .with_actor(envelope.actor_id)
+        .with_subject(*order_id)
+        .with_payload(serde_json::json!({
+            "order_id": order_id,
+            "old_status": old_status,
+            "new_status": new_status,
+        }))])
+    }
+}
+
+/// Returns the non-null uuid values of `column`, without duplicates.
+async fn select_uuids(
+    db: &DatabaseConnection,
+    query: &SelectStatement,
+    column: &str,
+) -> NotificationsResult<Vec<Uuid>> {
+    let rows = db.query_all(db.get_database_backend().build(query)).await?;
+    let mut values = Vec::with_capacity(rows.len());
+    for row in rows {
+        if let Some(value) = row.try_get::<Option<Uuid>>("", column)? {
+            if !values.contains(&value) {
+                values.push(value);
+            }
+        }
+    }
+    Ok(values)
+}
+
+/// Picks the topic translation in `preferred` locale, or the oldest one.
+async fn topic_title(
+    db: &DatabaseConnection,
+    topic_id: Uuid,
+    preferred: Option<&str>,
+) -> NotificationsResult<(String, String)> {
+    let query = Query::select()
+        .columns([Alias::new("locale"), Alias::new("title")])
+        .from(Alias::new("forum_topic_translations"))
+        .and_where(Expr::col(Alias::new("topic_id")).eq(topic_id))
+        .order_by(Alias::new("created_at"), Order::Asc)
+        .to_owned();
+    let rows = db
+        .query_all(db.get_database_backend().build(&query))
+        .await?;
+    let mut translations = Vec::with_capacity(rows.len());
+    for row in rows {
+        translations.push((
+            row.try_get::<String>("", "locale")?,
+            row.try_get::<String>("", "title")?,
+        ));
+    }
+
+    let chosen = preferred
+        .and_then(|locale| {
+            translations
+                .iter()
+                .find(|(candidate, _)| candidate == locale)
+        })
+        .or_else(|| translations.first())
+        .cloned();
+    Ok(chosen.unwrap_or_else(|| ("en".to_string(), "a topic".to_string())))
+}
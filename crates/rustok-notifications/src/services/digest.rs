@@ -0,0 +1,154 @@
+use std::collections::BTreeMap;
+
+use chrono::Utc;
+use sea_orm::{
+    sea_query::Expr, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+};
+use tracing::{instrument, warn};
+use uuid::Uuid;
+
+use super::inbox::{DELIVERY_FAILED, DELIVERY_PENDING, DELIVERY_SENT, DELIVERY_SKIPPED};
+use crate::dto::{DigestRunSummary, EmailDelivery};
+use crate::email::{NotificationEmailRuntime, TEMPLATE_DAILY_DIGEST};
+use crate::entities::{notification, notification_email_delivery};
+use crate::error::NotificationsResult;
+
+/// Digest emails list at most this many notifications; the rest stay in the
+/// inbox.
+const MAX_DIGEST_ITEMS: usize = 50;
+/// Failed digest deliveries are retried on the next run until this many
+/// attempts were made.
+const MAX_DIGEST_ATTEMPTS: i32 = 3;
+
+/// Sends the daily digest for every recipient with queued `daily`
+/// deliveries. Intended to be run once a day by the host scheduler.
+pub struct DigestService {
+    db: DatabaseConnection,
+    email: NotificationEmailRuntime,
+}
+
+impl DigestService {
+    pub fn new(db: DatabaseConnection, email: NotificationEmailRuntime) -> Self {
+        Self { db, email }
+    }
+
+    #[instrument(skip(self))]
+    pub async fn send_daily_digests(&self) -> NotificationsResult<DigestRunSummary> {
+        let pending = notification_email_delivery::Entity::find()
+            .filter(notification_email_delivery::Column::Status.eq(DELIVERY_PENDING))
+            .filter(notification_email_delivery::Column::Mode.eq(EmailDelivery::Daily.as_str()))
+            .order_by_asc(notification_email_delivery::Column::CreatedAt)
+            .find_also_related(notification::Entity)
+            .all(&self.db)
+            .await?;
+
+        let mut batches: BTreeMap<(Uuid, Uuid), Vec<_>> = BTreeMap::new();
+        for (delivery, notification) in pending {
+            let Some(notification) = notification else {
+                continue;
+            };
+            batches
+                .entry((delivery.tenant_id, delivery.recipient_id))
+                .or_default()
+                .push((delivery.id, notification));
+        }
+
+        let mut summary = DigestRunSummary::default();
+        for ((tenant_id, recipient_id), batch) in batches {
+            let delivery_ids: Vec<Uuid> = batch.iter().map(|(id, _)| *id).collect();
+            let locale = batch
+                .last()
+                .map(|(_, notification)| notification.locale.clone())
+                .unwrap_or_else(|| "en".to_string());
+            let items: Vec<_> = batch
+                .iter()
+                .rev()
+                .take(MAX_DIGEST_ITEMS)
+                .map(|(_, notification)| {
+                    serde_json::json!({
+                        "title": notification.title,
+                        "body": notification.body,
+                        "link": notification.link,
+                        "kind": notification.kind,
+                    })
+                })
+                .collect();
+            let vars = serde_json::json!({
+                "count": batch.len(),
+                "items": items,
+            });
+
+            summary.recipients += 1;
+            summary.notifications += batch.len();
+            let (status, error) = match self
+                .email
+                .send(
+                    tenant_id,
+                    recipient_id,
+                    TEMPLATE_DAILY_DIGEST,
+                    &locale,
+                    &vars,
+                )
+                .await
+            {
+                Ok(true) => (DELIVERY_SENT, None),
+                Ok(false) => (DELIVERY_SKIPPED, None),
+                Err(error) => {
+                    warn!(
+                        tenant_id = %tenant_id,
+                        recipient_id = %recipient_id,
+                        error = %error,
+                        "Notification digest delivery failed"
+                    );
+                    summary.failed_recipients += 1;
+                    (DELIVERY_PENDING, Some(error.to_string()))
+                }
+            };
+            self.finish(&delivery_ids, status, error).await?;
+        }
+
+        Ok(summary)
+    }
+
+    async fn finish(
+        &self,
+        delivery_ids: &[Uuid],
+        status: &str,
+        error: Option<String>,
+    ) -> NotificationsResult<()> {
+        let now = chrono::DateTime::<chrono::FixedOffset>::from(Utc::now());
+        notification_email_delivery::Entity::update_many()
+            .col_expr(
+                notification_email_delivery::Column::Status,
+                Expr::value(status),
+            )
+            .col_expr(
+                notification_email_delivery::Column::Attempts,
+                Expr::col(notification_email_delivery::Column::Attempts).add(1),
+            )
+            .col_expr(
+                notification_email_delivery::Column::LastError,
+                Expr::value(error),
+            )
+            .col_expr(
+                notification_email_delivery::Column::SentAt,
+                Expr::value((status == DELIVERY_SENT).then_some(now)),
+            )
+            .filter(notification_email_delivery::Column::Id.is_in(delivery_ids.iter().copied()))
+            .exec(&self.db)
+            .await?;
+
+        if status == DELIVERY_PENDING {
+            notification_email_delivery::Entity::update_many()
+                .col_expr(
+                    notification_email_delivery::Column::Status,
+                    Expr::value(DELIVERY_FAILED),
+                )
+                .filter(notification_email_delivery::Column::Id.is_in(delivery_ids.iter().copied()))
+                .filter(notification_email_delivery::Column::Attempts.gte(MAX_DIGEST_ATTEMPTS))
+                .exec(&self.db)
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,318 @@
+use std::collections::HashSet;
+
+use chrono::Utc;
+use sea_orm::{
+    sea_query::Expr, ActiveModelTrait, ActiveValue::Set, ColumnTrait, DatabaseConnection,
+    EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+};
+use tracing::{instrument, warn};
+use uuid::Uuid;
+
+use super::preferences::PreferenceService;
+use crate::dto::{
+    DispatchSummary, EmailDelivery, ListNotificationsFilter, NotificationDraft, NotificationRecord,
+};
+use crate::email::{NotificationEmailRuntime, TEMPLATE_NOTIFICATION};
+use crate::entities::{notification, notification_email_delivery};
+use crate::error::{NotificationsError, NotificationsResult};
+
+pub(crate) const DELIVERY_PENDING: &str = "pending";
+pub(crate) const DELIVERY_SENT: &str = "sent";
+pub(crate) const DELIVERY_FAILED: &str = "failed";
+pub(crate) const DELIVERY_SKIPPED: &str = "skipped";
+
+const MAX_TITLE_LEN: usize = 255;
+
+/// In-app inbox and delivery fan-out.
+///
+/// `dispatch` applies each recipient's preferences: the notification is
+/// stored (visible in the inbox only when `in_app` is enabled), and email is
+/// either sent right away or queued for the daily digest.
+#[derive(Clone)]
+pub struct NotificationService {
+    db: DatabaseConnection,
+    email: Option<NotificationEmailRuntime>,
+}
+
+impl NotificationService {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db, email: None }
+    }
+
+    pub fn with_email(mut self, email: NotificationEmailRuntime) -> Self {
+        self.email = Some(email);
+        self
+    }
+
+    /// Delivers the drafts produced for one event. Re-delivering the same
+    /// `event_id` is a no-op for recipients that already have the
+    /// notification, so handler retries do not duplicate inbox entries.
+    #[instrument(skip(self, drafts), fields(drafts = drafts.len()))]
+    pub async fn dispatch(
+        &self,
+        tenant_id: Uuid,
+        event_id: Option<Uuid>,
+        drafts: Vec<NotificationDraft>,
+    ) -> NotificationsResult<DispatchSummary> {
+        let mut summary = DispatchSummary::default();
+        let mut seen = HashSet::new();
+
+        for draft in drafts {
+            if draft.actor_id == Some(draft.recipient_id) || !seen.insert(draft.recipient_id) {
+                summary.skipped += 1;
+                continue;
+            }
+            if let Some(event_id) = event_id {
+                let delivered = notification::Entity::find()
+                    .filter(notification::Column::EventId.eq(event_id))
+                    .filter(notification::Column::RecipientId.eq(draft.recipient_id))
+                    .one(&self.db)
+                    .await?
+                    .is_some();
+                if delivered {
+                    summary.skipped += 1;
+                    continue;
+                }
+            }
+
+            let preference =
+                PreferenceService::effective(&self.db, tenant_id, draft.recipient_id, &draft.kind)
+                    .await?;
+            let email = if self.email.is_some() {
+                preference.email
+            } else {
+                EmailDelivery::Off
+            };
+            if !preference.in_app && email == EmailDelivery::Off {
+                summary.skipped += 1;
+                continue;
+            }
+
+            let stored = self
+                .store(tenant_id, event_id, &draft, preference.in_app)
+                .await?;
+            summary.stored += 1;
+
+            match email {
+                EmailDelivery::Off => {}
+                EmailDelivery::Daily => {
+                    self.record_delivery(&stored, EmailDelivery::Daily, DELIVERY_PENDING, None)
+                        .await?;
+                    summary.queued_for_digest += 1;
+                }
+                EmailDelivery::Immediate => {
+                    if self.send_immediate(&stored).await? {
+                        summary.emailed += 1;
+                    }
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn list(
+        &self,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        filter: ListNotificationsFilter,
+    ) -> NotificationsResult<(Vec<NotificationRecord>, u64)> {
+        let mut query = inbox_query(tenant_id, user_id);
+        if filter.unread_only {
+            query = query.filter(notification::Column::ReadAt.is_null());
+        }
+        if let Some(kind) = filter.kind.as_deref() {
+            query = query.filter(notification::Column::Kind.eq(kind));
+        }
+
+        let paginator = query
+            .order_by_desc(notification::Column::CreatedAt)
+            .paginate(&self.db, filter.per_page.clamp(1, 100));
+        let total = paginator.num_items().await?;
+        let items = paginator
+            .fetch_page(filter.page.max(1) - 1)
+            .await?
+            .into_iter()
+            .map(to_record)
+            .collect();
+
+        Ok((items, total))
+    }
+
+    #[instrument(skip(self))]
+    pub async fn unread_count(&self, tenant_id: Uuid, user_id: Uuid) -> NotificationsResult<u64> {
+        Ok(inbox_query(tenant_id, user_id)
+            .filter(notification::Column::ReadAt.is_null())
+            .count(&self.db)
+            .await?)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn mark_read(
+        &self,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        notification_id: Uuid,
+    ) -> NotificationsResult<NotificationRecord> {
+        let existing = inbox_query(tenant_id, user_id)
+            .filter(notification::Column::Id.eq(notification_id))
+            .one(&self.db)
+            .await?
+            .ok_or(NotificationsError::NotFound(notification_id))?;
+        if existing.read_at.is_some() {
+            return Ok(to_record(existing));
+        }
+
+        let mut active: notification::ActiveModel = existing.into();
+        active.read_at = Set(Some(Utc::now().into()));
+        Ok(to_record(active.update(&self.db).await?))
+    }
+
+    /// Marks every unread inbox entry as read and returns how many changed.
+    #[instrument(skip(self))]
+    pub async fn mark_all_read(&self, tenant_id: Uuid, user_id: Uuid) -> NotificationsResult<u64> {
+        let result = notification::Entity::update_many()
+            .col_expr(
+                notification::Column::ReadAt,
+                Expr::value(Some(chrono::DateTime::<chrono::FixedOffset>::from(
+                    Utc::now(),
+                ))),
+            )
+            .filter(notification::Column::TenantId.eq(tenant_id))
+            .filter(notification::Column::RecipientId.eq(user_id))
+            .filter(notification::Column::InApp.eq(true))
+            .filter(notification::Column::ReadAt.is_null())
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    async fn store(
+        &self,
+        tenant_id: Uuid,
+        event_id: Option<Uuid>,
+        draft: &NotificationDraft,
+        in_app: bool,
+    ) -> NotificationsResult<notification::Model> {
+        let title: String = draft.title.chars().take(MAX_TITLE_LEN).collect();
+        Ok(notification::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tenant_id: Set(tenant_id),
+            recipient_id: Set(draft.recipient_id),
+            kind: Set(draft.kind.clone()),
+            title: Set(title),
+            body: Set(draft.body.clone()),
+            link: Set(draft.link.clone()),
+            actor_id: Set(draft.actor_id),
+            subject_id: Set(draft.subject_id),
+            event_id: Set(event_id),
+            locale: Set(draft.locale.clone()),
+            payload: Set(draft.payload.clone()),
+            in_app: Set(in_app),
+            read_at: Set(None),
+            created_at: Set(Utc::now().into()),
+        }
+        .insert(&self.db)
+        .await?)
+    }
+
+    /// Sends one notification email. Delivery failures are recorded rather
+    /// than propagated: the inbox entry already exists and retrying the
+    /// whole event would not resend it.
+    async fn send_immediate(&self, stored: &notification::Model) -> NotificationsResult<bool> {
+        let Some(email) = self.email.as_ref() else {
+            return Ok(false);
+        };
+        let vars = serde_json::json!({
+            "title": stored.title,
+            "body": stored.body,
+            "link": stored.link,
+            "kind": stored.kind,
+        });
+        match email
+            .send(
+                stored.tenant_id,
+                stored.recipient_id,
+                TEMPLATE_NOTIFICATION,
+                &stored.locale,
+                &vars,
+            )
+            .await
+        {
+            Ok(true) => {
+                self.record_delivery(stored, EmailDelivery::Immediate, DELIVERY_SENT, None)
+                    .await?;
+                Ok(true)
+            }
+            Ok(false) => {
+                self.record_delivery(stored, EmailDelivery::Immediate, DELIVERY_SKIPPED, None)
+                    .await?;
+                Ok(false)
+            }
+            Err(error) => {
+                warn!(
+                    notification_id = %stored.id,
+                    error = %error,
+                    "Notification email delivery failed"
+                );
+                self.record_delivery(
+                    stored,
+                    EmailDelivery::Immediate,
+                    DELIVERY_FAILED,
+                    Some(error.to_string()),
+                )
+                .await?;
+                Ok(false)
+            }
+        }
+    }
+
+    async fn record_delivery(
+        &self,
+        stored: &notification::Model,
+        mode: EmailDelivery,
+        status: &str,
+        last_error: Option<String>,
+    ) -> NotificationsResult<()> {
+        let now = Utc::now();
+        let attempted = status != DELIVERY_PENDING;
+        notification_email_delivery::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tenant_id: Set(stored.tenant_id),
+            notification_id: Set(stored.id),
+            recipient_id: Set(stored.recipient_id),
+            mode: Set(mode.as_str().to_string()),
+            status: Set(status.to_string()),
+            attempts: Set(i32::from(attempted)),
+            last_error: Set(last_error),
+            created_at: Set(now.into()),
+            sent_at: Set((status == DELIVERY_SENT).then(|| now.into())),
+        }
+        .insert(&self.db)
+        .await?;
+        Ok(())
+    }
+}
+
+fn inbox_query(tenant_id: Uuid, user_id: Uuid) -> sea_orm::Select<notification::Entity> {
+    notification::Entity::find()
+        .filter(notification::Column::TenantId.eq(tenant_id))
+        .filter(notification::Column::RecipientId.eq(user_id))
+        .filter(notification::Column::InApp.eq(true))
+}
+
+fn to_record(model: notification::Model) -> NotificationRecord {
+    NotificationRecord {
+        id: model.id,
+        kind: model.kind,
+        title: model.title,
+        body: model.body,
+        link: model.link,
+        actor_id: model.actor_id,
+        subject_id: model.subject_id,
+        payload: model.payload,
+        read_at: model.read_at.map(Into::into),
+        created_at: model.created_at.into(),
+    }
+}
@@ -0,0 +1,7 @@
+mod digest;
+mod inbox;
+mod preferences;
+
+pub use digest::DigestService;
+pub use inbox::NotificationService;
+pub use preferences::{PreferenceService, DEFAULT_EMAIL_DELIVERY};
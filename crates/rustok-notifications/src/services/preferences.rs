@@ -0,0 +1,148 @@
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, DatabaseConnection,
+    EntityTrait, QueryFilter, QueryOrder,
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::dto::{
+    EmailDelivery, NotificationPreferenceRecord, UpdateNotificationPreferenceInput,
+    DEFAULT_PREFERENCE_KIND,
+};
+use crate::entities::notification_preference;
+use crate::error::{NotificationsError, NotificationsResult};
+
+/// Email delivery used when the user has not configured anything.
+pub const DEFAULT_EMAIL_DELIVERY: EmailDelivery = EmailDelivery::Daily;
+
+const MAX_KIND_LEN: usize = 64;
+
+/// Per-user delivery preferences, keyed by notification kind with a `*`
+/// fallback row.
+pub struct PreferenceService {
+    db: DatabaseConnection,
+}
+
+impl PreferenceService {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Stored preferences for the user, always including the effective `*`
+    /// default first.
+    #[instrument(skip(self))]
+    pub async fn list(
+        &self,
+        tenant_id: Uuid,
+        user_id: Uuid,
+    ) -> NotificationsResult<Vec<NotificationPreferenceRecord>> {
+        let rows = notification_preference::Entity::find()
+            .filter(notification_preference::Column::TenantId.eq(tenant_id))
+            .filter(notification_preference::Column::UserId.eq(user_id))
+            .order_by_asc(notification_preference::Column::Kind)
+            .all(&self.db)
+            .await?;
+
+        let mut records: Vec<_> = rows.into_iter().map(to_record).collect();
+        if !records
+            .iter()
+            .any(|record| record.kind == DEFAULT_PREFERENCE_KIND)
+        {
+            records.insert(0, default_record(DEFAULT_PREFERENCE_KIND));
+        }
+        Ok(records)
+    }
+
+    #[instrument(skip(self, input))]
+    pub async fn update(
+        &self,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        input: UpdateNotificationPreferenceInput,
+    ) -> NotificationsResult<NotificationPreferenceRecord> {
+        let kind = input.kind.trim().to_string();
+        if kind.is_empty() || kind.len() > MAX_KIND_LEN {
+            return Err(NotificationsError::Validation(
+                "Notification kind must be between 1 and 64 characters".to_string(),
+            ));
+        }
+
+        let existing = notification_preference::Entity::find()
+            .filter(notification_preference::Column::TenantId.eq(tenant_id))
+            .filter(notification_preference::Column::UserId.eq(user_id))
+            .filter(notification_preference::Column::Kind.eq(kind.as_str()))
+            .one(&self.db)
+            .await?;
+        let now = Utc::now();
+        let model = match existing {
+            Some(existing) => {
+                let mut active: notification_preference::ActiveModel = existing.into();
+                active.in_app = Set(input.in_app);
+                active.email = Set(input.email.as_str().to_string());
+                active.updated_at = Set(now.into());
+                active.update(&self.db).await?
+            }
+            None => {
+                notification_preference::ActiveModel {
+                    id: Set(Uuid::new_v4()),
+                    tenant_id: Set(tenant_id),
+                    user_id: Set(user_id),
+                    kind: Set(kind),
+                    in_app: Set(input.in_app),
+                    email: Set(input.email.as_str().to_string()),
+                    updated_at: Set(now.into()),
+                }
+                .insert(&self.db)
+                .await?
+            }
+        };
+
+        Ok(to_record(model))
+    }
+
+    /// Resolves the preference that applies to `kind`: the kind-specific row,
+    /// then the user's `*` row, then the platform default.
+    pub async fn effective<C>(
+        conn: &C,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        kind: &str,
+    ) -> NotificationsResult<NotificationPreferenceRecord>
+    where
+        C: ConnectionTrait,
+    {
+        let rows = notification_preference::Entity::find()
+            .filter(notification_preference::Column::TenantId.eq(tenant_id))
+            .filter(notification_preference::Column::UserId.eq(user_id))
+            .filter(notification_preference::Column::Kind.is_in([kind, DEFAULT_PREFERENCE_KIND]))
+            .all(conn)
+            .await?;
+
+        let specific = rows.iter().find(|row| row.kind == kind);
+        let fallback = rows.iter().find(|row| row.kind == DEFAULT_PREFERENCE_KIND);
+        Ok(match specific.or(fallback) {
+            Some(row) => NotificationPreferenceRecord {
+                kind: kind.to_string(),
+                ..to_record(row.clone())
+            },
+            None => default_record(kind),
+        })
+    }
+}
+
+fn default_record(kind: &str) -> NotificationPreferenceRecord {
+    NotificationPreferenceRecord {
+        kind: kind.to_string(),
+        in_app: true,
+        email: DEFAULT_EMAIL_DELIVERY,
+    }
+}
+
+fn to_record(model: notification_preference::Model) -> NotificationPreferenceRecord {
+    NotificationPreferenceRecord {
+        email: EmailDelivery::parse(&model.email).unwrap_or(DEFAULT_EMAIL_DELIVERY),
+        kind: model.kind,
+        in_app: model.in_app,
+    }
+}
@@ -0,0 +1,457 @@
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use rustok_core::events::EventHandler;
+use rustok_core::{MemoryTransport, MigrationSource, SecurityContext, UserRole};
+use rustok_email::TransactionalEmailSender;
+use rustok_forum::{
+    CategoryService, CreateCategoryInput, CreateReplyInput, CreateTopicInput, ForumModule,
+    ReplyService, SubscriptionService, TopicService,
+};
+use rustok_notifications::{
+    kinds, DigestService, EmailDelivery, ListNotificationsFilter, NotificationEmailRuntime,
+    NotificationEventHandler, NotificationService, NotificationsModule, NotificationsResult,
+    PreferenceService, RecipientAddress, RecipientDirectory, UpdateNotificationPreferenceInput,
+    TEMPLATE_DAILY_DIGEST, TEMPLATE_NOTIFICATION,
+};
+use rustok_outbox::TransactionalEventBus;
+use rustok_profiles::{ProfileService, ProfileVisibility, ProfilesModule, UpsertProfileInput};
+use rustok_taxonomy::TaxonomyModule;
+use sea_orm::{ConnectOptions, Database, DatabaseConnection};
+use sea_orm_migration::SchemaManager;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+#[derive(Default)]
+struct RecordingSender {
+    sent: Mutex<Vec<(String, String)>>,
+}
+
+#[async_trait]
+impl TransactionalEmailSender for RecordingSender {
+    async fn send_transactional(
+        &self,
+        template_id: &str,
+        _locale: &str,
+        to: &str,
+        _vars: &serde_json::Value,
+    ) -> rustok_email::error::Result<()> {
+        self.sent
+            .lock()
+            .unwrap()
+            .push((template_id.to_string(), to.to_string()));
+        Ok(())
+    }
+}
+
+struct FakeDirectory;
+
+#[async_trait]
+impl RecipientDirectory for FakeDirectory {
+    async fn lookup(
+        &self,
+        _tenant_id: Uuid,
+        user_id: Uuid,
+    ) -> NotificationsResult<Option<RecipientAddress>> {
+        Ok(Some(RecipientAddress {
+            email: format!("{user_id}@example.test"),
+        }))
+    }
+}
+
+struct Forum {
+    db: DatabaseConnection,
+    event_bus: TransactionalEventBus,
+    events: broadcast::Receiver<rustok_events::EventEnvelope>,
+    tenant_id: Uuid,
+    author: SecurityContext,
+    topic_id: Uuid,
+}
+
+async fn setup() -> Forum {
+    let db_url = format!(
+        "sqlite:file:notifications_{}?mode=memory&cache=shared",
+        Uuid::new_v4()
+    );
+    let mut opts = ConnectOptions::new(db_url);
+    opts.max_connections(5)
+        .min_connections(1)
+        .sqlx_logging(false);
+    let db = Database::connect(opts)
+        .await
+        .expect("failed to connect notifications sqlite database");
+
+    let schema = SchemaManager::new(&db);
+    let migrations = rustok_content::migrations::migrations()
+        .into_iter()
+        .chain(TaxonomyModule.migrations())
+        .chain(ProfilesModule.migrations())
+        .chain(ForumModule.migrations())
+        .chain(NotificationsModule.migrations());
+    for migration in migrations {
+        migration.up(&schema).await.expect("migration should apply");
+    }
+
+    let transport = MemoryTransport::new();
+    let events = transport.subscribe();
+    let event_bus = TransactionalEventBus::new(Arc::new(transport));
+    let tenant_id = Uuid::new_v4();
+    let author = SecurityContext::new(UserRole::Admin, Some(Uuid::new_v4()));
+
+    let category = CategoryService::new(db.clone())
+        .create(
+            tenant_id,
+            author.clone(),
+            CreateCategoryInput {
+                locale: "en".to_string(),
+                name: "General".to_string(),
+                slug: "general".to_string(),
+                description: None,
+                icon: None,
+                color: None,
+                parent_id: None,
+                position: Some(0),
+                moderated: false,
+            },
+        )
+        .await
+        .expect("category should be created");
+    let topic = TopicService::new(db.clone(), event_bus.clone())
+        .create(
+            tenant_id,
+            author.clone(),
+            CreateTopicInput {
+                locale: "en".to_string(),
+                category_id: category.id,
+                title: "Welcome".to_string(),
+                slug: Some("welcome".to_string()),
+                body: "Say hello".to_string(),
+                body_format: "markdown".to_string(),
+                content_json: None,
+                metadata: serde_json::json!({}),
+                tags: vec![],
+                channel_slugs: None,
+            },
+        )
+        .await
+        .expect("topic should be created");
+
+    let mut forum = Forum {
+        db,
+        event_bus,
+        events,
+        tenant_id,
+        author,
+        topic_id: topic.id,
+    };
+    drain(&mut forum.events);
+    forum
+}
+
+fn drain(
+    events: &mut broadcast::Receiver<rustok_events::EventEnvelope>,
+) -> Vec<rustok_events::EventEnvelope> {
+    let mut drained = Vec::new();
+    while let Ok(envelope) = events.try_recv() {
+        drained.push(envelope);
+    }
+    drained
+}
+
+async fn reply_as(forum: &mut Forum, user: &SecurityContext) -> rustok_events::EventEnvelope {
+    ReplyService::new(forum.db.clone(), forum.event_bus.clone())
+        .create(
+            forum.tenant_id,
+            user.clone(),
+            forum.topic_id,
+            CreateReplyInput {
+                locale: "en".to_string(),
+                content: "Hello there".to_string(),
+                content_format: "markdown".to_string(),
+                content_json: None,
+                parent_reply_id: None,
+            },
+        )
+        .await
+        .expect("reply should be created");
+
+    drain(&mut forum.events)
+        .into_iter()
+        .find(|envelope| envelope.event_type == "forum.topic.replied")
+        .expect("reply should publish forum.topic.replied")
+}
+
+#[tokio::test]
+async fn forum_reply_notifies_subscribers_and_topic_author_but_not_the_replier() {
+    let mut forum = setup().await;
+    let subscriber = SecurityContext::new(UserRole::Customer, Some(Uuid::new_v4()));
+    let replier = SecurityContext::new(UserRole::Customer, Some(Uuid::new_v4()));
+    SubscriptionService::new(forum.db.clone())
+        .set_topic_subscription(forum.tenant_id, forum.topic_id, subscriber.clone())
+        .await
+        .expect("subscription should be stored");
+    SubscriptionService::new(forum.db.clone())
+        .set_topic_subscription(forum.tenant_id, forum.topic_id, replier.clone())
+        .await
+        .expect("subscription should be stored");
+
+    let envelope = reply_as(&mut forum, &replier).await;
+    let handler = NotificationEventHandler::new(forum.db.clone());
+    assert!(handler.handles(&envelope.event));
+    handler.handle(&envelope).await.expect("handler should run");
+
+    let service = NotificationService::new(forum.db.clone());
+    for user in [&subscriber, &forum.author] {
+        let (items, total) = service
+            .list(
+                forum.tenant_id,
+                user.user_id.unwrap(),
+                ListNotificationsFilter::default(),
+            )
+            .await
+            .expect("inbox should load");
+        assert_eq!(total, 1);
+        assert_eq!(items[0].kind, kinds::FORUM_TOPIC_REPLY);
+        assert_eq!(items[0].title, "New reply in \"Welcome\"");
+        assert_eq!(items[0].actor_id, replier.user_id);
+        assert!(items[0]
+            .link
+            .as_deref()
+            .unwrap()
+            .ends_with(&format!("topic={}", forum.topic_id)));
+    }
+    let replier_unread = service
+        .unread_count(forum.tenant_id, replier.user_id.unwrap())
+        .await
+        .expect("count should load");
+    assert_eq!(replier_unread, 0);
+
+    handler
+        .handle(&envelope)
+        .await
+        .expect("redelivery should succeed");
+    let subscriber_unread = service
+        .unread_count(forum.tenant_id, subscriber.user_id.unwrap())
+        .await
+        .expect("count should load");
+    assert_eq!(
+        subscriber_unread, 1,
+        "redelivery must not duplicate entries"
+    );
+}
+
+#[tokio::test]
+async fn mentions_notify_the_mentioned_user_with_a_topic_link() {
+    let mut forum = setup().await;
+    let mentioned = Uuid::new_v4();
+    ProfileService::new(forum.db.clone())
+        .upsert_profile(
+            forum.tenant_id,
+            mentioned,
+            UpsertProfileInput {
+                handle: "alice".to_string(),
+                display_name: "Alice".to_string(),
+                bio: None,
+                tags: vec![],
+                avatar_media_id: None,
+                banner_media_id: None,
+                preferred_locale: None,
+                visibility: ProfileVisibility::Public,
+            },
+            Some("en"),
+        )
+        .await
+        .expect("profile should be created");
+
+    let replier = SecurityContext::new(UserRole::Customer, Some(Uuid::new_v4()));
+    ReplyService::new(forum.db.clone(), forum.event_bus.clone())
+        .create(
+            forum.tenant_id,
+            replier.clone(),
+            forum.topic_id,
+            CreateReplyInput {
+                locale: "en".to_string(),
+                content: "Thoughts, @alice?".to_string(),
+                content_format: "markdown".to_string(),
+                content_json: None,
+                parent_reply_id: None,
+            },
+        )
+        .await
+        .expect("reply should be created");
+    let envelope = drain(&mut forum.events)
+        .into_iter()
+        .find(|envelope| envelope.event_type == "user.mentioned")
+        .expect("reply should publish user.mentioned");
+
+    let handler = NotificationEventHandler::new(forum.db.clone());
+    handler.handle(&envelope).await.expect("handler should run");
+
+    let (items, total) = NotificationService::new(forum.db.clone())
+        .list(
+            forum.tenant_id,
+            mentioned,
+            ListNotificationsFilter::default(),
+        )
+        .await
+        .expect("inbox should load");
+    assert_eq!(total, 1);
+    assert_eq!(items[0].kind, kinds::USER_MENTION);
+    assert_eq!(items[0].title, "You were mentioned in \"Welcome\"");
+    assert_eq!(items[0].actor_id, replier.user_id);
+    assert!(items[0]
+        .link
+        .as_deref()
+        .unwrap()
+        .ends_with(&format!("topic={}", forum.topic_id)));
+}
+
+#[tokio::test]
+async fn inbox_tracks_read_state() {
+    let mut forum = setup().await;
+    let author_id = forum.author.user_id.unwrap();
+    let handler = NotificationEventHandler::new(forum.db.clone());
+    for _ in 0..3 {
+        let replier = SecurityContext::new(UserRole::Customer, Some(Uuid::new_v4()));
+        let envelope = reply_as(&mut forum, &replier).await;
+        handler.handle(&envelope).await.expect("handler should run");
+    }
+
+    let service = NotificationService::new(forum.db.clone());
+    assert_eq!(
+        service
+            .unread_count(forum.tenant_id, author_id)
+            .await
+            .unwrap(),
+        3
+    );
+
+    let (items, _) = service
+        .list(
+            forum.tenant_id,
+            author_id,
+            ListNotificationsFilter::default(),
+        )
+        .await
+        .unwrap();
+    let read = service
+        .mark_read(forum.tenant_id, author_id, items[0].id)
+        .await
+        .expect("own notification should be marked read");
+    assert!(read.is_read());
+    assert!(service
+        .mark_read(forum.tenant_id, Uuid::new_v4(), items[1].id)
+        .await
+        .is_err());
+
+    let (unread, total) = service
+        .list(
+            forum.tenant_id,
+            author_id,
+            ListNotificationsFilter {
+                unread_only: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!((unread.len(), total), (2, 2));
+
+    assert_eq!(
+        service
+            .mark_all_read(forum.tenant_id, author_id)
+            .await
+            .unwrap(),
+        2
+    );
+    assert_eq!(
+        service
+            .unread_count(forum.tenant_id, author_id)
+            .await
+            .unwrap(),
+        0
+    );
+}
+
+#[tokio::test]
+async fn preferences_route_email_immediately_or_into_the_daily_digest() {
+    let mut forum = setup().await;
+    let author_id = forum.author.user_id.unwrap();
+    let subscriber = SecurityContext::new(UserRole::Customer, Some(Uuid::new_v4()));
+    let subscriber_id = subscriber.user_id.unwrap();
+    SubscriptionService::new(forum.db.clone())
+        .set_topic_subscription(forum.tenant_id, forum.topic_id, subscriber)
+        .await
+        .unwrap();
+
+    let preferences = PreferenceService::new(forum.db.clone());
+    preferences
+        .update(
+            forum.tenant_id,
+            author_id,
+            UpdateNotificationPreferenceInput {
+                kind: kinds::FORUM_TOPIC_REPLY.to_string(),
+                in_app: false,
+                email: EmailDelivery::Immediate,
+            },
+        )
+        .await
+        .expect("preference should be stored");
+    let listed = preferences.list(forum.tenant_id, author_id).await.unwrap();
+    assert_eq!(listed[0].kind, "*");
+    assert_eq!(listed[1].email, EmailDelivery::Immediate);
+
+    let sender = Arc::new(RecordingSender::default());
+    let runtime = NotificationEmailRuntime::new(sender.clone(), Arc::new(FakeDirectory));
+    let handler = NotificationEventHandler::new(forum.db.clone()).with_email(runtime.clone());
+    for _ in 0..2 {
+        let replier = SecurityContext::new(UserRole::Customer, Some(Uuid::new_v4()));
+        let envelope = reply_as(&mut forum, &replier).await;
+        handler.handle(&envelope).await.unwrap();
+    }
+
+    let service = NotificationService::new(forum.db.clone());
+    assert_eq!(
+        service
+            .unread_count(forum.tenant_id, author_id)
+            .await
+            .unwrap(),
+        0,
+        "in-app delivery is disabled for the author"
+    );
+    assert_eq!(
+        service
+            .unread_count(forum.tenant_id, subscriber_id)
+            .await
+            .unwrap(),
+        2
+    );
+    {
+        let sent = sender.sent.lock().unwrap();
+        assert_eq!(sent.len(), 2);
+        assert!(sent
+            .iter()
+            .all(|(template, to)| template == TEMPLATE_NOTIFICATION
+                && to == &format!("{author_id}@example.test")));
+    }
+
+    let digest = DigestService::new(forum.db.clone(), runtime);
+    let summary = digest.send_daily_digests().await.unwrap();
+    assert_eq!(summary.recipients, 1);
+    assert_eq!(summary.notifications, 2);
+    assert_eq!(summary.failed_recipients, 0);
+    {
+        let sent = sender.sent.lock().unwrap();
+        assert_eq!(sent.len(), 3);
+        assert_eq!(
+            sent[2],
+            (
+                TEMPLATE_DAILY_DIGEST.to_string(),
+                format!("{subscriber_id}@example.test")
+            )
+        );
+    }
+
+    let again = digest.send_daily_digests().await.unwrap();
+    assert_eq!(again.recipients, 0, "digest deliveries are sent once");
+}
@@ -0,0 +1,66 @@
+use async_trait::async_trait;
+use rustok_core::{
+    ModuleAction, ModuleActionContext, ModuleActionDescriptor, ModuleActionError,
+    ModuleActionResult, Permission,
+};
+use rustok_outbox::TransactionalEventBus;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::OrderService;
+
+/// `order.confirm` — moves a pending order to `confirmed`.
+#[derive(Clone, Default)]
+pub struct ConfirmOrderAction;
+
+#[derive(Deserialize)]
+struct ConfirmOrderInput {
+    order_id: Uuid,
+}
+
+#[async_trait]
+impl ModuleAction for ConfirmOrderAction {
+    fn descriptor(&self) -> ModuleActionDescriptor {
+        ModuleActionDescriptor {
+            name: "order.confirm",
+            module_slug: "order",
+            description: "Confirm a pending order",
+            input_schema: json!({
+                "type": "object",
+                "required": ["order_id"],
+                "additionalProperties": false,
+                "properties": {
+                    "order_id": { "type": "string", "format": "uuid" }
+                }
+            }),
+            output_schema: json!({
+                "type": "object",
+                "required": ["order_id", "status"],
+                "properties": {
+                    "order_id": { "type": "string", "format": "uuid" },
+                    "status": { "type": "string" }
+                }
+            }),
+            required_permissions: vec![Permission::ORDERS_UPDATE],
+        }
+    }
+
+    async fn invoke(&self, ctx: &ModuleActionContext, input: Value) -> ModuleActionResult<Value> {
+        let input: ConfirmOrderInput =
+            serde_json::from_value(input).map_err(ModuleActionError::failed)?;
+        let order = OrderService::new(
+            ctx.db.clone(),
+            TransactionalEventBus::new(ctx.event_transport.clone()),
+        )
+        .confirm_order(
+            ctx.tenant_id,
+            ctx.actor_id.unwrap_or_else(Uuid::nil),
+            input.order_id,
+        )
+        .await
+        .map_err(ModuleActionError::failed)?;
+
+        Ok(json!({ "order_id": order.id, "status": order.status }))
+    }
+}
@@ -8,7 +8,6 @@ use sea_orm::{
     ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter,
     QueryOrder, QuerySelect, Set, TransactionTrait,
 };
-use serde_json::from_value;
 use uuid::Uuid;
 
 use rustok_core::events::EventTransport;
@@ -181,7 +180,8 @@ impl OutboxRelay {
     async fn process_claimed_event(&self, model: &entity::Model) -> Result<()> {
         let started = Instant::now();
         let event_id = model.id;
-        let envelope: EventEnvelope = from_value(model.payload.clone())?;
+        let envelope = EventEnvelope::from_stored_json(model.payload.clone())
+            .map_err(|err| Error::Validation(err.to_string()))?;
 
         let publish_result = self.target.publish(envelope).await;
         let elapsed_ms = started.elapsed().as_millis() as u64;
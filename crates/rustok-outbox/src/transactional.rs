@@ -16,6 +16,11 @@ impl TransactionalEventBus {
         Self { transport }
     }
 
+    /// Underlying transport, for runtimes that build their own buses from it.
+    pub fn transport(&self) -> Arc<dyn EventTransport> {
+        self.transport.clone()
+    }
+
     pub async fn publish_in_tx<C>(
         &self,
         txn: &C,
@@ -237,8 +237,13 @@ impl BlockPayload {
 pub struct CreateBlockInput {
     pub block_type: BlockType,
     pub position: i32,
+    /// Inline payload. Ignored when the block references a global block.
+    #[serde(default)]
     pub data: serde_json::Value,
     pub translations: Option<Vec<BlockTranslationInput>>,
+    /// Reference to a reusable global block whose content is rendered in place.
+    #[serde(default)]
+    pub global_block_id: Option<Uuid>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -264,4 +269,5 @@ pub struct BlockResponse {
     pub position: i32,
     pub data: serde_json::Value,
     pub translations: Option<Vec<BlockTranslationInput>>,
+    pub global_block_id: Option<Uuid>,
 }
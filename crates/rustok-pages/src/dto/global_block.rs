@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{BlockTranslationInput, BlockType};
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CreateGlobalBlockInput {
+    /// Stable tenant-unique key, e.g. `footer-cta`.
+    pub key: String,
+    pub name: String,
+    pub block_type: BlockType,
+    pub data: serde_json::Value,
+    pub translations: Option<Vec<BlockTranslationInput>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct UpdateGlobalBlockInput {
+    pub name: Option<String>,
+    pub data: Option<serde_json::Value>,
+    pub translations: Option<Vec<BlockTranslationInput>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GlobalBlockResponse {
+    pub id: Uuid,
+    pub key: String,
+    pub name: String,
+    pub block_type: BlockType,
+    pub data: serde_json::Value,
+    pub translations: Option<Vec<BlockTranslationInput>>,
+    pub usage_count: u64,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// A page block that renders a global block.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GlobalBlockUsage {
+    pub page_id: Uuid,
+    pub block_id: Uuid,
+    pub position: i32,
+}
@@ -1,14 +1,20 @@
 // DTOs for pages-related requests/responses.
 pub mod block;
+pub mod global_block;
 pub mod menu;
 pub mod page;
+pub mod template;
 
 pub use block::{
     BlockPayload, BlockResponse, BlockTranslationInput, BlockType, CreateBlockInput,
     UpdateBlockInput,
 };
+pub use global_block::{
+    CreateGlobalBlockInput, GlobalBlockResponse, GlobalBlockUsage, UpdateGlobalBlockInput,
+};
 pub use menu::{CreateMenuInput, MenuItemInput, MenuItemResponse, MenuLocation, MenuResponse};
 pub use page::{
     CreatePageInput, ListPagesFilter, PageBodyInput, PageBodyResponse, PageListItem, PageResponse,
     PageTranslationInput, PageTranslationResponse, UpdatePageInput,
 };
+pub use template::{CreatePageTemplateInput, PageTemplateResponse, UpdatePageTemplateInput};
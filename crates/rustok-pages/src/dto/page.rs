@@ -97,6 +97,9 @@ pub struct PageBodyResponse {
     pub content: String,
     pub format: String,
     pub content_json: Option<Value>,
+    /// Sanitized HTML rendering of the content.
+    #[serde(default)]
+    pub html: String,
     pub updated_at: String,
 }
 
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::CreateBlockInput;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CreatePageTemplateInput {
+    /// Matches `CreatePageInput::template`.
+    pub slug: String,
+    pub name: String,
+    pub description: Option<String>,
+    /// Block structure copied onto pages created from this template.
+    #[serde(default)]
+    pub blocks: Vec<CreateBlockInput>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct UpdatePageTemplateInput {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub blocks: Option<Vec<CreateBlockInput>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PageTemplateResponse {
+    pub id: Uuid,
+    pub slug: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub blocks: Vec<CreateBlockInput>,
+    pub created_at: String,
+    pub updated_at: String,
+}
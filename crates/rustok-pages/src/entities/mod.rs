@@ -8,6 +8,8 @@ pub mod page;
 pub mod page_block;
 pub mod page_body;
 pub mod page_channel_visibility;
+pub mod page_global_block;
+pub mod page_template;
 pub mod page_translation;
 
 pub use menu::Entity as Menu;
@@ -15,3 +17,5 @@ pub use menu_item::Entity as MenuItem;
 pub use page::Entity as Page;
 pub use page_block::Entity as Block;
 pub use page_channel_visibility::Entity as PageChannelVisibility;
+pub use page_global_block::Entity as GlobalBlock;
+pub use page_template::Entity as PageTemplate;
@@ -13,6 +13,7 @@ pub struct Model {
     pub position: i32,
     pub data: Json,
     pub translations: Option<Json>,
+    pub global_block_id: Option<Uuid>,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
 }
@@ -25,6 +26,12 @@ pub enum Relation {
         to = "super::page::Column::Id"
     )]
     Page,
+    #[sea_orm(
+        belongs_to = "super::page_global_block::Entity",
+        from = "Column::GlobalBlockId",
+        to = "super::page_global_block::Column::Id"
+    )]
+    GlobalBlock,
 }
 
 impl Related<super::page::Entity> for Entity {
@@ -33,4 +40,10 @@ impl Related<super::page::Entity> for Entity {
     }
 }
 
+impl Related<super::page_global_block::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::GlobalBlock.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
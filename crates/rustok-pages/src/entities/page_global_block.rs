@@ -0,0 +1,32 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "page_global_blocks")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub key: String,
+    pub name: String,
+    pub block_type: String,
+    pub data: Json,
+    pub translations: Option<Json>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(has_many = "super::page_block::Entity")]
+    PageBlocks,
+}
+
+impl Related<super::page_block::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PageBlocks.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
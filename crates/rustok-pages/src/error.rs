@@ -24,6 +24,15 @@ pub enum PagesError {
     #[error("Menu not found: {0}")]
     MenuNotFound(Uuid),
 
+    #[error("Global block not found: {0}")]
+    GlobalBlockNotFound(Uuid),
+
+    #[error("Page template not found: {0}")]
+    TemplateNotFound(Uuid),
+
+    #[error("Global block {block_id} is still used by {usages} page block(s)")]
+    GlobalBlockInUse { block_id: Uuid, usages: u64 },
+
     #[error("Duplicate slug: {slug} already exists for locale {locale}")]
     DuplicateSlug { slug: String, locale: String },
 
@@ -116,6 +125,30 @@ impl From<PagesError> for RichError {
                     .with_field("menu_id", id.to_string())
                     .with_error_code("MENU_NOT_FOUND")
             }
+            PagesError::GlobalBlockNotFound(id) => RichError::new(
+                ErrorKind::NotFound,
+                format!("Global block {} not found", id),
+            )
+            .with_user_message("The requested global block does not exist")
+            .with_field("global_block_id", id.to_string())
+            .with_error_code("GLOBAL_BLOCK_NOT_FOUND"),
+            PagesError::TemplateNotFound(id) => RichError::new(
+                ErrorKind::NotFound,
+                format!("Page template {} not found", id),
+            )
+            .with_user_message("The requested page template does not exist")
+            .with_field("template_id", id.to_string())
+            .with_error_code("PAGE_TEMPLATE_NOT_FOUND"),
+            PagesError::GlobalBlockInUse { block_id, usages } => RichError::new(
+                ErrorKind::Conflict,
+                format!("Global block {block_id} is still used by {usages} page block(s)"),
+            )
+            .with_user_message(
+                "This block is used on other pages. Detach it from those pages or force deletion.",
+            )
+            .with_field("global_block_id", block_id.to_string())
+            .with_field("usages", usages.to_string())
+            .with_error_code("GLOBAL_BLOCK_IN_USE"),
             PagesError::DuplicateSlug { slug, locale } => RichError::new(
                 ErrorKind::Conflict,
                 format!("Slug '{}' already exists for locale '{}'", slug, locale),
@@ -164,6 +197,21 @@ impl PagesError {
         PagesError::MenuNotFound(menu_id)
     }
 
+    /// Create a global block not found error
+    pub fn global_block_not_found(block_id: Uuid) -> Self {
+        PagesError::GlobalBlockNotFound(block_id)
+    }
+
+    /// Create a page template not found error
+    pub fn template_not_found(template_id: Uuid) -> Self {
+        PagesError::TemplateNotFound(template_id)
+    }
+
+    /// Create a global block in use error
+    pub fn global_block_in_use(block_id: Uuid, usages: u64) -> Self {
+        PagesError::GlobalBlockInUse { block_id, usages }
+    }
+
     /// Create a duplicate slug error
     pub fn duplicate_slug(slug: impl Into<String>, locale: impl Into<String>) -> Self {
         PagesError::DuplicateSlug {
@@ -232,6 +280,18 @@ mod tests {
         assert!(rich.fields.contains_key("menu_id"));
     }
 
+    #[test]
+    fn test_global_block_in_use_conversion() {
+        let id = Uuid::new_v4();
+        let err = PagesError::global_block_in_use(id, 3);
+        let rich: RichError = err.into();
+
+        assert_eq!(rich.kind, ErrorKind::Conflict);
+        assert_eq!(rich.status_code, 409);
+        assert_eq!(rich.error_code, Some("GLOBAL_BLOCK_IN_USE".to_string()));
+        assert_eq!(rich.fields.get("usages"), Some(&"3".to_string()));
+    }
+
     #[test]
     fn test_duplicate_slug_conversion() {
         let err = PagesError::duplicate_slug("my-page", "en");
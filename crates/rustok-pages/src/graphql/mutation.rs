@@ -9,8 +9,10 @@ use sea_orm::DatabaseConnection;
 use uuid::Uuid;
 
 use crate::{
-    BlockService, BlockTranslationInput, BlockType, CreateBlockInput, CreatePageInput,
-    PageBodyInput, PageService, PageTranslationInput, UpdateBlockInput, UpdatePageInput,
+    BlockService, BlockTranslationInput, BlockType, CreateBlockInput, CreateGlobalBlockInput,
+    CreatePageInput, CreatePageTemplateInput, GlobalBlockService, PageBodyInput, PageService,
+    PageTemplateService, PageTranslationInput, UpdateBlockInput, UpdateGlobalBlockInput,
+    UpdatePageInput, UpdatePageTemplateInput,
 };
 
 use super::types::*;
@@ -303,9 +305,190 @@ impl PagesMutation {
 
         Ok(true)
     }
+
+    async fn create_global_block(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateGqlGlobalBlockInput,
+        tenant_id: Option<Uuid>,
+    ) -> Result<GqlGlobalBlock> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let event_bus = ctx.data::<TransactionalEventBus>()?;
+        let auth = require_pages_permission(ctx, Permission::PAGES_CREATE)?;
+        let tenant = ctx.data::<rustok_api::TenantContext>()?;
+        let tenant_id = tenant_id.unwrap_or(tenant.id);
+
+        let service = GlobalBlockService::new(db.clone(), event_bus.clone());
+        let block = service
+            .create(
+                tenant_id,
+                auth.security_context(),
+                CreateGlobalBlockInput {
+                    key: input.key,
+                    name: input.name,
+                    block_type: parse_block_type(&input.block_type)?,
+                    data: input.data,
+                    translations: input.translations.map(map_block_translations),
+                },
+            )
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))?;
+
+        Ok(block.into())
+    }
+
+    async fn update_global_block(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+        input: UpdateGqlGlobalBlockInput,
+        tenant_id: Option<Uuid>,
+    ) -> Result<GqlGlobalBlock> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let event_bus = ctx.data::<TransactionalEventBus>()?;
+        let auth = require_pages_permission(ctx, Permission::PAGES_UPDATE)?;
+        let tenant = ctx.data::<rustok_api::TenantContext>()?;
+        let tenant_id = tenant_id.unwrap_or(tenant.id);
+
+        let service = GlobalBlockService::new(db.clone(), event_bus.clone());
+        let block = service
+            .update(
+                tenant_id,
+                auth.security_context(),
+                id,
+                UpdateGlobalBlockInput {
+                    name: input.name,
+                    data: input.data,
+                    translations: input.translations.map(map_block_translations),
+                },
+            )
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))?;
+
+        Ok(block.into())
+    }
+
+    /// Fails with `GLOBAL_BLOCK_IN_USE` while pages reference the block unless `force` is set.
+    async fn delete_global_block(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+        force: Option<bool>,
+        tenant_id: Option<Uuid>,
+    ) -> Result<bool> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let event_bus = ctx.data::<TransactionalEventBus>()?;
+        let auth = require_pages_permission(ctx, Permission::PAGES_DELETE)?;
+        let tenant = ctx.data::<rustok_api::TenantContext>()?;
+        let tenant_id = tenant_id.unwrap_or(tenant.id);
+
+        let service = GlobalBlockService::new(db.clone(), event_bus.clone());
+        service
+            .delete(
+                tenant_id,
+                auth.security_context(),
+                id,
+                force.unwrap_or(false),
+            )
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))?;
+
+        Ok(true)
+    }
+
+    async fn create_page_template(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateGqlPageTemplateInput,
+        tenant_id: Option<Uuid>,
+    ) -> Result<GqlPageTemplate> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let event_bus = ctx.data::<TransactionalEventBus>()?;
+        let auth = require_pages_permission(ctx, Permission::PAGES_CREATE)?;
+        let tenant = ctx.data::<rustok_api::TenantContext>()?;
+        let tenant_id = tenant_id.unwrap_or(tenant.id);
+
+        let service = PageTemplateService::new(db.clone(), event_bus.clone());
+        let template = service
+            .create(
+                tenant_id,
+                auth.security_context(),
+                CreatePageTemplateInput {
+                    slug: input.slug,
+                    name: input.name,
+                    description: input.description,
+                    blocks: map_create_block_inputs(input.blocks.unwrap_or_default())?,
+                },
+            )
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))?;
+
+        Ok(template.into())
+    }
+
+    async fn update_page_template(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+        input: UpdateGqlPageTemplateInput,
+        tenant_id: Option<Uuid>,
+    ) -> Result<GqlPageTemplate> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let event_bus = ctx.data::<TransactionalEventBus>()?;
+        let auth = require_pages_permission(ctx, Permission::PAGES_UPDATE)?;
+        let tenant = ctx.data::<rustok_api::TenantContext>()?;
+        let tenant_id = tenant_id.unwrap_or(tenant.id);
+
+        let service = PageTemplateService::new(db.clone(), event_bus.clone());
+        let template = service
+            .update(
+                tenant_id,
+                auth.security_context(),
+                id,
+                UpdatePageTemplateInput {
+                    name: input.name,
+                    description: input.description,
+                    blocks: input.blocks.map(map_create_block_inputs).transpose()?,
+                },
+            )
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))?;
+
+        Ok(template.into())
+    }
+
+    async fn delete_page_template(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+        tenant_id: Option<Uuid>,
+    ) -> Result<bool> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let event_bus = ctx.data::<TransactionalEventBus>()?;
+        let auth = require_pages_permission(ctx, Permission::PAGES_DELETE)?;
+        let tenant = ctx.data::<rustok_api::TenantContext>()?;
+        let tenant_id = tenant_id.unwrap_or(tenant.id);
+
+        let service = PageTemplateService::new(db.clone(), event_bus.clone());
+        service
+            .delete(tenant_id, auth.security_context(), id)
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))?;
+
+        Ok(true)
+    }
 }
 
-fn require_pages_permission(ctx: &Context<'_>, permission: Permission) -> Result<AuthContext> {
+pub(super) fn require_pages_permission(
+    ctx: &Context<'_>,
+    permission: Permission,
+) -> Result<AuthContext> {
     let auth = ctx
         .data::<AuthContext>()
         .map_err(|_| <FieldError as GraphQLError>::unauthenticated())?
@@ -324,11 +507,16 @@ fn map_create_block_input(input: CreateGqlBlockInput) -> Result<CreateBlockInput
     Ok(CreateBlockInput {
         block_type: parse_block_type(&input.block_type)?,
         position: input.position,
-        data: input.data,
+        data: input.data.unwrap_or_default(),
         translations: input.translations.map(map_block_translations),
+        global_block_id: input.global_block_id,
     })
 }
 
+fn map_create_block_inputs(inputs: Vec<CreateGqlBlockInput>) -> Result<Vec<CreateBlockInput>> {
+    inputs.into_iter().map(map_create_block_input).collect()
+}
+
 fn map_block_translations(
     translations: Vec<GqlBlockTranslationInput>,
 ) -> Vec<BlockTranslationInput> {
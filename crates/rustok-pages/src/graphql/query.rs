@@ -4,7 +4,7 @@ use rustok_api::{
     TenantContext,
 };
 use rustok_channel::ChannelService;
-use rustok_core::SecurityContext;
+use rustok_core::{Permission, SecurityContext};
 use rustok_outbox::TransactionalEventBus;
 use rustok_telemetry::metrics;
 use sea_orm::DatabaseConnection;
@@ -12,8 +12,9 @@ use std::time::Instant;
 use uuid::Uuid;
 
 use crate::services::page::is_page_visible_for_channel;
-use crate::PageService;
+use crate::{GlobalBlockService, PageService, PageTemplateService};
 
+use super::mutation::require_pages_permission;
 use super::types::*;
 
 const MODULE_SLUG: &str = "pages";
@@ -180,6 +181,71 @@ impl PagesQuery {
 
         Ok(GqlPageList { items, total })
     }
+
+    async fn global_blocks(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Option<Uuid>,
+    ) -> Result<Vec<GqlGlobalBlock>> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let event_bus = ctx.data::<TransactionalEventBus>()?;
+        let auth = require_pages_permission(ctx, Permission::PAGES_LIST)?;
+        let tenant = ctx.data::<TenantContext>()?;
+        let tenant_id = tenant_id.unwrap_or(tenant.id);
+
+        let service = GlobalBlockService::new(db.clone(), event_bus.clone());
+        let blocks = service
+            .list(tenant_id, auth.security_context())
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))?;
+
+        Ok(blocks.into_iter().map(Into::into).collect())
+    }
+
+    /// Page blocks that render the global block; shown before deleting it.
+    async fn global_block_usages(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+        tenant_id: Option<Uuid>,
+    ) -> Result<Vec<GqlGlobalBlockUsage>> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let event_bus = ctx.data::<TransactionalEventBus>()?;
+        let auth = require_pages_permission(ctx, Permission::PAGES_READ)?;
+        let tenant = ctx.data::<TenantContext>()?;
+        let tenant_id = tenant_id.unwrap_or(tenant.id);
+
+        let service = GlobalBlockService::new(db.clone(), event_bus.clone());
+        let usages = service
+            .usages(tenant_id, auth.security_context(), id)
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))?;
+
+        Ok(usages.into_iter().map(Into::into).collect())
+    }
+
+    async fn page_templates(
+        &self,
+        ctx: &Context<'_>,
+        tenant_id: Option<Uuid>,
+    ) -> Result<Vec<GqlPageTemplate>> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let event_bus = ctx.data::<TransactionalEventBus>()?;
+        let auth = require_pages_permission(ctx, Permission::PAGES_LIST)?;
+        let tenant = ctx.data::<TenantContext>()?;
+        let tenant_id = tenant_id.unwrap_or(tenant.id);
+
+        let service = PageTemplateService::new(db.clone(), event_bus.clone());
+        let templates = service
+            .list(tenant_id, auth.security_context())
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))?;
+
+        Ok(templates.into_iter().map(Into::into).collect())
+    }
 }
 
 fn auth_context_to_security(ctx: &Context<'_>) -> SecurityContext {
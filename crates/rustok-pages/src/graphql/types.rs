@@ -36,6 +36,7 @@ pub struct GqlPageBody {
     pub content: String,
     pub format: String,
     pub content_json: Option<Value>,
+    pub html: String,
     pub updated_at: String,
 }
 
@@ -98,14 +99,17 @@ pub struct GqlBlock {
     pub position: i32,
     pub data: Value,
     pub translations: Option<Vec<GqlBlockTranslation>>,
+    pub global_block_id: Option<Uuid>,
 }
 
 #[derive(Clone, Debug, InputObject)]
 pub struct CreateGqlBlockInput {
     pub block_type: String,
     pub position: i32,
-    pub data: Value,
+    /// Inline payload; omitted when `global_block_id` references a global block.
+    pub data: Option<Value>,
     pub translations: Option<Vec<GqlBlockTranslationInput>>,
+    pub global_block_id: Option<Uuid>,
 }
 
 #[derive(Clone, Debug, InputObject)]
@@ -132,6 +136,77 @@ pub struct ReorderBlocksInput {
     pub block_ids: Vec<Uuid>,
 }
 
+#[derive(Clone, Debug, SimpleObject)]
+pub struct GqlGlobalBlock {
+    pub id: Uuid,
+    pub key: String,
+    pub name: String,
+    pub block_type: String,
+    pub data: Value,
+    pub translations: Option<Vec<GqlBlockTranslation>>,
+    pub usage_count: u64,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Clone, Debug, SimpleObject)]
+pub struct GqlGlobalBlockUsage {
+    pub page_id: Uuid,
+    pub block_id: Uuid,
+    pub position: i32,
+}
+
+#[derive(Clone, Debug, InputObject)]
+pub struct CreateGqlGlobalBlockInput {
+    pub key: String,
+    pub name: String,
+    pub block_type: String,
+    pub data: Value,
+    pub translations: Option<Vec<GqlBlockTranslationInput>>,
+}
+
+#[derive(Clone, Debug, InputObject)]
+pub struct UpdateGqlGlobalBlockInput {
+    pub name: Option<String>,
+    pub data: Option<Value>,
+    pub translations: Option<Vec<GqlBlockTranslationInput>>,
+}
+
+#[derive(Clone, Debug, SimpleObject)]
+pub struct GqlPageTemplate {
+    pub id: Uuid,
+    pub slug: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub blocks: Vec<GqlTemplateBlock>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Clone, Debug, SimpleObject)]
+pub struct GqlTemplateBlock {
+    pub block_type: String,
+    pub position: i32,
+    pub data: Value,
+    pub translations: Option<Vec<GqlBlockTranslation>>,
+    pub global_block_id: Option<Uuid>,
+}
+
+#[derive(Clone, Debug, InputObject)]
+pub struct CreateGqlPageTemplateInput {
+    pub slug: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub blocks: Option<Vec<CreateGqlBlockInput>>,
+}
+
+#[derive(Clone, Debug, InputObject)]
+pub struct UpdateGqlPageTemplateInput {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub blocks: Option<Vec<CreateGqlBlockInput>>,
+}
+
 #[derive(InputObject)]
 pub struct ListGqlPagesFilter {
     pub locale: Option<String>,
@@ -181,6 +256,7 @@ impl From<crate::PageBodyResponse> for GqlPageBody {
             content: r.content,
             format: r.format,
             content_json: r.content_json,
+            html: r.html,
             updated_at: r.updated_at,
         }
     }
@@ -207,19 +283,72 @@ impl From<crate::BlockResponse> for GqlBlock {
             block_type: block_type_str(&r.block_type),
             position: r.position,
             data: r.data,
-            translations: r.translations.map(|items| {
-                items
-                    .into_iter()
-                    .map(|translation| GqlBlockTranslation {
-                        locale: translation.locale,
-                        data: translation.data,
-                    })
-                    .collect()
-            }),
+            translations: r.translations.map(map_block_translations),
+            global_block_id: r.global_block_id,
         }
     }
 }
 
+impl From<crate::GlobalBlockResponse> for GqlGlobalBlock {
+    fn from(r: crate::GlobalBlockResponse) -> Self {
+        Self {
+            id: r.id,
+            key: r.key,
+            name: r.name,
+            block_type: block_type_str(&r.block_type),
+            data: r.data,
+            translations: r.translations.map(map_block_translations),
+            usage_count: r.usage_count,
+            created_at: r.created_at,
+            updated_at: r.updated_at,
+        }
+    }
+}
+
+impl From<crate::GlobalBlockUsage> for GqlGlobalBlockUsage {
+    fn from(r: crate::GlobalBlockUsage) -> Self {
+        Self {
+            page_id: r.page_id,
+            block_id: r.block_id,
+            position: r.position,
+        }
+    }
+}
+
+impl From<crate::PageTemplateResponse> for GqlPageTemplate {
+    fn from(r: crate::PageTemplateResponse) -> Self {
+        Self {
+            id: r.id,
+            slug: r.slug,
+            name: r.name,
+            description: r.description,
+            blocks: r
+                .blocks
+                .into_iter()
+                .map(|block| GqlTemplateBlock {
+                    block_type: block_type_str(&block.block_type),
+                    position: block.position,
+                    data: block.data,
+                    translations: block.translations.map(map_block_translations),
+                    global_block_id: block.global_block_id,
+                })
+                .collect(),
+            created_at: r.created_at,
+            updated_at: r.updated_at,
+        }
+    }
+}
+
+fn map_block_translations(items: Vec<crate::BlockTranslationInput>) -> Vec<GqlBlockTranslation> {
+    items
+        .into_iter()
+        .map(|translation| GqlBlockTranslation {
+            locale: translation.locale,
+            data: translation.data,
+        })
+        .collect()
+}
+
 fn block_type_str(block_type: &crate::BlockType) -> String {
     use crate::BlockType;
     match block_type {
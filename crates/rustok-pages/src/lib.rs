@@ -1,6 +1,7 @@
 //! Pages module for RusToK platform.
 //!
-//! The module owns storage for pages, page blocks, menus, and menu items.
+//! The module owns storage for pages, page blocks, reusable global blocks, page templates,
+//! menus, and menu items.
 //!
 //! # Example
 //!
@@ -36,6 +37,7 @@ pub mod dto;
 pub mod entities;
 pub mod error;
 pub mod graphql;
+mod media_usage;
 pub mod migrations;
 mod seo_targets;
 pub mod services;
@@ -44,11 +46,14 @@ pub use dto::*;
 pub use entities::{Block, Menu, Page};
 pub use error::{PagesError, PagesResult};
 pub use graphql::{PagesMutation, PagesQuery};
-pub use services::{BlockService, MenuService, PageService};
+pub use services::{
+    BlockService, GlobalBlockService, MenuService, PageService, PageTemplateService,
+};
 
 use async_trait::async_trait;
 use rustok_core::permissions::{Action, Permission, Resource};
 use rustok_core::{MigrationSource, ModuleRuntimeExtensions, RusToKModule};
+use rustok_media::register_media_usage_provider;
 use rustok_seo_targets::register_seo_target_provider;
 use sea_orm_migration::MigrationTrait;
 
@@ -92,6 +97,10 @@ impl RusToKModule for PagesModule {
     fn register_runtime_extensions(&self, extensions: &mut ModuleRuntimeExtensions) {
         register_seo_target_provider(extensions, seo_targets::PagesSeoTargetProvider)
             .expect("pages SEO target registration should remain unique");
+        register_media_usage_provider(extensions, media_usage::PagesMediaUsageProvider)
+            .expect("pages media usage registration should remain unique");
+        register_media_usage_provider(extensions, media_usage::GlobalBlockMediaUsageProvider)
+            .expect("global block media usage registration should remain unique");
     }
 }
 
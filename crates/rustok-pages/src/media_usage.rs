@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+
+use anyhow::Result as AnyResult;
+use async_trait::async_trait;
+use rustok_media::{collect_json_references, MediaReference, MediaUsageProvider};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+use uuid::Uuid;
+
+use crate::entities::{page, page_block, page_body, page_global_block, page_translation};
+
+/// Reports media referenced from page metadata, blocks and JSON bodies.
+#[derive(Clone, Default)]
+pub struct PagesMediaUsageProvider;
+
+#[async_trait]
+impl MediaUsageProvider for PagesMediaUsageProvider {
+    fn owner_kind(&self) -> &'static str {
+        "page"
+    }
+
+    fn owner_module_slug(&self) -> &'static str {
+        "pages"
+    }
+
+    async fn collect_references(
+        &self,
+        db: &DatabaseConnection,
+        tenant_id: Uuid,
+    ) -> AnyResult<Vec<MediaReference>> {
+        let pages = page::Entity::find()
+            .filter(page::Column::TenantId.eq(tenant_id))
+            .all(db)
+            .await?;
+        if pages.is_empty() {
+            return Ok(Vec::new());
+        }
+        let page_ids: Vec<Uuid> = pages.iter().map(|page| page.id).collect();
+
+        let mut titles = HashMap::new();
+        for translation in page_translation::Entity::find()
+            .filter(page_translation::Column::TenantId.eq(tenant_id))
+            .order_by_asc(page_translation::Column::Locale)
+            .all(db)
+            .await?
+        {
+            titles
+                .entry(translation.page_id)
+                .or_insert(translation.title);
+        }
+
+        let mut references = Vec::new();
+        for page in &pages {
+            references.extend(collect_json_references(page.id, "metadata", &page.metadata));
+        }
+        for block in page_block::Entity::find()
+            .filter(page_block::Column::TenantId.eq(tenant_id))
+            .all(db)
+            .await?
+        {
+            let root = format!("blocks.{}", block.id);
+            references.extend(collect_json_references(
+                block.page_id,
+                &format!("{root}.data"),
+                &block.data,
+            ));
+            if let Some(translations) = &block.translations {
+                references.extend(collect_json_references(
+                    block.page_id,
+                    &format!("{root}.translations"),
+                    translations,
+                ));
+            }
+        }
+        for body in page_body::Entity::find()
+            .filter(page_body::Column::PageId.is_in(page_ids))
+            .all(db)
+            .await?
+        {
+            // Rich-text bodies are stored as JSON documents; plain formats
+            // carry no structured references.
+            if let Ok(document) = serde_json::from_str(&body.content) {
+                references.extend(collect_json_references(
+                    body.page_id,
+                    &format!("body.{}", body.locale),
+                    &document,
+                ));
+            }
+        }
+
+        Ok(references
+            .into_iter()
+            .map(|reference| {
+                let label = titles.get(&reference.owner_id).cloned();
+                reference.with_label(label)
+            })
+            .collect())
+    }
+}
+
+/// Reports media referenced from reusable global blocks.
+#[derive(Clone, Default)]
+pub struct GlobalBlockMediaUsageProvider;
+
+#[async_trait]
+impl MediaUsageProvider for GlobalBlockMediaUsageProvider {
+    fn owner_kind(&self) -> &'static str {
+        "page_global_block"
+    }
+
+    fn owner_module_slug(&self) -> &'static str {
+        "pages"
+    }
+
+    async fn collect_references(
+        &self,
+        db: &DatabaseConnection,
+        tenant_id: Uuid,
+    ) -> AnyResult<Vec<MediaReference>> {
+        let mut references = Vec::new();
+        for block in page_global_block::Entity::find()
+            .filter(page_global_block::Column::TenantId.eq(tenant_id))
+            .all(db)
+            .await?
+        {
+            let mut block_references = collect_json_references(block.id, "data", &block.data);
+            if let Some(translations) = &block.translations {
+                block_references.extend(collect_json_references(
+                    block.id,
+                    "translations",
+                    translations,
+                ));
+            }
+            references.extend(
+                block_references
+                    .into_iter()
+                    .map(|reference| reference.with_label(Some(block.name.clone()))),
+            );
+        }
+        Ok(references)
+    }
+}
@@ -0,0 +1,203 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PageGlobalBlocks::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PageGlobalBlocks::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(PageGlobalBlocks::TenantId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(PageGlobalBlocks::Key)
+                            .string_len(128)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(PageGlobalBlocks::Name).text().not_null())
+                    .col(
+                        ColumnDef::new(PageGlobalBlocks::BlockType)
+                            .string_len(64)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(PageGlobalBlocks::Data)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(PageGlobalBlocks::Translations).json_binary())
+                    .col(
+                        ColumnDef::new(PageGlobalBlocks::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(PageGlobalBlocks::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_page_global_blocks_tenant_key")
+                    .table(PageGlobalBlocks::Table)
+                    .col(PageGlobalBlocks::TenantId)
+                    .col(PageGlobalBlocks::Key)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PageBlocks::Table)
+                    .add_column(ColumnDef::new(PageBlocks::GlobalBlockId).uuid())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_page_blocks_tenant_global_block")
+                    .table(PageBlocks::Table)
+                    .col(PageBlocks::TenantId)
+                    .col(PageBlocks::GlobalBlockId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(PageTemplates::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PageTemplates::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(PageTemplates::TenantId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(PageTemplates::Slug)
+                            .string_len(128)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(PageTemplates::Name).text().not_null())
+                    .col(ColumnDef::new(PageTemplates::Description).text())
+                    .col(
+                        ColumnDef::new(PageTemplates::Blocks)
+                            .json_binary()
+                            .not_null()
+                            .default("[]"),
+                    )
+                    .col(
+                        ColumnDef::new(PageTemplates::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        ColumnDef::new(PageTemplates::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_page_templates_tenant_slug")
+                    .table(PageTemplates::Table)
+                    .col(PageTemplates::TenantId)
+                    .col(PageTemplates::Slug)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PageTemplates::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_page_blocks_tenant_global_block")
+                    .table(PageBlocks::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(PageBlocks::Table)
+                    .drop_column(PageBlocks::GlobalBlockId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(PageGlobalBlocks::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum PageGlobalBlocks {
+    Table,
+    Id,
+    TenantId,
+    Key,
+    Name,
+    BlockType,
+    Data,
+    Translations,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum PageBlocks {
+    Table,
+    TenantId,
+    GlobalBlockId,
+}
+
+#[derive(DeriveIden)]
+enum PageTemplates {
+    Table,
+    Id,
+    TenantId,
+    Slug,
+    Name,
+    Description,
+    Blocks,
+    CreatedAt,
+    UpdatedAt,
+}
@@ -1,5 +1,6 @@
 mod m20260328_000001_create_pages_tables;
 mod m20260329_000001_create_page_channel_visibility_table;
+mod m20260610_000001_create_page_global_blocks_and_templates;
 
 use sea_orm_migration::MigrationTrait;
 
@@ -7,5 +8,6 @@ pub fn migrations() -> Vec<Box<dyn MigrationTrait>> {
     vec![
         Box::new(m20260328_000001_create_pages_tables::Migration),
         Box::new(m20260329_000001_create_page_channel_visibility_table::Migration),
+        Box::new(m20260610_000001_create_page_global_blocks_and_templates::Migration),
     ]
 }
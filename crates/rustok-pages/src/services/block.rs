@@ -13,8 +13,11 @@ use rustok_core::{Action, Resource, SecurityContext};
 use rustok_outbox::TransactionalEventBus;
 
 use crate::dto::*;
-use crate::entities::{page, page_block};
+use crate::entities::{page, page_block, page_global_block};
 use crate::error::{PagesError, PagesResult};
+use crate::services::global_block::{
+    find_global_block, find_global_block_for_reference, load_global_blocks_by_id,
+};
 use crate::services::rbac::{can_read_non_public_pages, enforce_owned_scope, enforce_scope};
 
 pub struct BlockService {
@@ -41,32 +44,40 @@ impl BlockService {
             Action::Update,
             parent_page.author_id,
         )?;
-        let data = validate_and_sanitize_block_data(&input.block_type, input.data)?;
-        let translations = sanitize_translations(&input.block_type, input.translations)?;
+        let global_block = match input.global_block_id {
+            Some(global_block_id) => Some(
+                find_global_block_for_reference(txn, tenant_id, global_block_id, &input.block_type)
+                    .await?,
+            ),
+            None => None,
+        };
+        let (data, translations) = if global_block.is_some() {
+            (serde_json::json!({}), None)
+        } else {
+            (
+                validate_and_sanitize_block_data(&input.block_type, input.data)?,
+                sanitize_translations(&input.block_type, input.translations)?,
+            )
+        };
         let now = Utc::now();
         let block_id = Uuid::new_v4();
 
-        page_block::ActiveModel {
+        let model = page_block::ActiveModel {
             id: Set(block_id),
             page_id: Set(page_id),
             tenant_id: Set(tenant_id),
             block_type: Set(block_type_str(&input.block_type).to_string()),
             position: Set(input.position),
-            data: Set(data.clone()),
-            translations: Set(translations.clone().map(|items| serde_json::json!(items))),
+            data: Set(data),
+            translations: Set(translations.map(|items| serde_json::json!(items))),
+            global_block_id: Set(input.global_block_id),
             created_at: Set(now.into()),
             updated_at: Set(now.into()),
         }
         .insert(txn)
         .await?;
 
-        Ok(BlockResponse {
-            id: block_id,
-            block_type: input.block_type,
-            position: input.position,
-            data,
-            translations,
-        })
+        Self::model_to_block(model, global_block.as_ref())
     }
 
     pub async fn delete_all_for_page_in_tx(
@@ -127,18 +138,24 @@ impl BlockService {
             .ok_or_else(|| PagesError::block_not_found(block_id))
     }
 
-    fn model_to_block(model: page_block::Model) -> PagesResult<BlockResponse> {
+    fn model_to_block(
+        model: page_block::Model,
+        global_block: Option<&page_global_block::Model>,
+    ) -> PagesResult<BlockResponse> {
         let block_type = block_type_from_str(&model.block_type)?;
-        let translations = model
-            .translations
-            .and_then(|value| serde_json::from_value(value).ok());
+        let (data, translations) = match global_block {
+            Some(global_block) => (global_block.data.clone(), global_block.translations.clone()),
+            None => (model.data, model.translations),
+        };
+        let translations = translations.and_then(|value| serde_json::from_value(value).ok());
 
         Ok(BlockResponse {
             id: model.id,
             block_type,
             position: model.position,
-            data: model.data,
+            data,
             translations,
+            global_block_id: model.global_block_id,
         })
     }
 
@@ -172,9 +189,18 @@ impl BlockService {
             return Err(PagesError::forbidden("Permission denied"));
         }
         let blocks = self.list_models_for_page(tenant_id, page_id).await?;
+        let global_blocks = load_global_blocks_by_id(
+            &self.db,
+            tenant_id,
+            blocks.iter().filter_map(|block| block.global_block_id),
+        )
+        .await?;
         let mut responses = Vec::with_capacity(blocks.len());
         for block in blocks {
-            responses.push(Self::model_to_block(block)?);
+            let global_block = block
+                .global_block_id
+                .and_then(|global_block_id| global_blocks.get(&global_block_id));
+            responses.push(Self::model_to_block(block, global_block)?);
         }
         Ok(responses)
     }
@@ -197,6 +223,22 @@ impl BlockService {
             Action::Update,
             parent_page.author_id,
         )?;
+        if let Some(global_block_id) = existing.global_block_id {
+            if input.data.is_some() || input.translations.is_some() {
+                return Err(PagesError::validation(
+                    "Blocks referencing a global block are edited through the global block",
+                ));
+            }
+            let global_block = find_global_block(&self.db, tenant_id, global_block_id).await?;
+            let mut active: page_block::ActiveModel = existing.into();
+            if let Some(position) = input.position {
+                active.position = Set(position);
+            }
+            active.updated_at = Set(Utc::now().into());
+            let block = active.update(&self.db).await?;
+            return Self::model_to_block(block, Some(&global_block));
+        }
+
         let block_type = block_type_from_str(&existing.block_type)?;
 
         let mut data = existing.data.clone();
@@ -226,6 +268,7 @@ impl BlockService {
             position: block.position,
             data,
             translations,
+            global_block_id: None,
         })
     }
 
@@ -326,7 +369,7 @@ fn storage_to_status(status: &str) -> PagesResult<ContentStatus> {
     })
 }
 
-fn block_type_str(block_type: &BlockType) -> &'static str {
+pub(crate) fn block_type_str(block_type: &BlockType) -> &'static str {
     match block_type {
         BlockType::Hero => "hero",
         BlockType::Text => "text",
@@ -346,7 +389,7 @@ fn block_type_str(block_type: &BlockType) -> &'static str {
     }
 }
 
-fn block_type_from_str(value: &str) -> PagesResult<BlockType> {
+pub(crate) fn block_type_from_str(value: &str) -> PagesResult<BlockType> {
     Ok(match value {
         "hero" => BlockType::Hero,
         "text" => BlockType::Text,
@@ -371,7 +414,7 @@ fn block_type_from_str(value: &str) -> PagesResult<BlockType> {
     })
 }
 
-fn sanitize_translations(
+pub(crate) fn sanitize_translations(
     block_type: &BlockType,
     translations: Option<Vec<BlockTranslationInput>>,
 ) -> PagesResult<Option<Vec<BlockTranslationInput>>> {
@@ -391,7 +434,10 @@ fn sanitize_translations(
         .transpose()
 }
 
-fn validate_and_sanitize_block_data(block_type: &BlockType, data: Value) -> PagesResult<Value> {
+pub(crate) fn validate_and_sanitize_block_data(
+    block_type: &BlockType,
+    data: Value,
+) -> PagesResult<Value> {
     let payload = BlockPayload::from_block_type(block_type, data)
         .map_err(|err| PagesError::validation(format!("Invalid block payload: {err}")))?;
 
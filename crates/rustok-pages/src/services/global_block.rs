@@ -0,0 +1,404 @@
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, DatabaseConnection,
+    EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+};
+use std::collections::HashMap;
+use tracing::instrument;
+use uuid::Uuid;
+
+use rustok_core::{Action, DomainEvent, Resource, SecurityContext};
+use rustok_outbox::TransactionalEventBus;
+
+use crate::dto::*;
+use crate::entities::{page_block, page_global_block};
+use crate::error::{PagesError, PagesResult};
+use crate::services::block::{
+    block_type_from_str, block_type_str, sanitize_translations, validate_and_sanitize_block_data,
+};
+use crate::services::page::PAGE_KIND;
+use crate::services::rbac::enforce_scope;
+
+/// Reusable block definitions rendered in place by any number of page blocks.
+pub struct GlobalBlockService {
+    db: DatabaseConnection,
+    event_bus: TransactionalEventBus,
+}
+
+impl GlobalBlockService {
+    pub fn new(db: DatabaseConnection, event_bus: TransactionalEventBus) -> Self {
+        Self { db, event_bus }
+    }
+
+    #[instrument(skip(self, input))]
+    pub async fn create(
+        &self,
+        tenant_id: Uuid,
+        security: SecurityContext,
+        input: CreateGlobalBlockInput,
+    ) -> PagesResult<GlobalBlockResponse> {
+        enforce_scope(&security, Resource::Pages, Action::Create)?;
+        let key = normalize_global_block_key(&input.key)?;
+        let name = normalize_name(&input.name)?;
+        let data = validate_and_sanitize_block_data(&input.block_type, input.data)?;
+        let translations = sanitize_translations(&input.block_type, input.translations)?;
+
+        let existing = page_global_block::Entity::find()
+            .filter(page_global_block::Column::TenantId.eq(tenant_id))
+            .filter(page_global_block::Column::Key.eq(key.as_str()))
+            .one(&self.db)
+            .await?;
+        if existing.is_some() {
+            return Err(PagesError::validation(format!(
+                "Global block key '{key}' already exists"
+            )));
+        }
+
+        let now = Utc::now();
+        let model = page_global_block::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tenant_id: Set(tenant_id),
+            key: Set(key),
+            name: Set(name),
+            block_type: Set(block_type_str(&input.block_type).to_string()),
+            data: Set(data),
+            translations: Set(translations.map(|items| serde_json::json!(items))),
+            created_at: Set(now.into()),
+            updated_at: Set(now.into()),
+        }
+        .insert(&self.db)
+        .await?;
+
+        global_block_response(model, 0)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get(
+        &self,
+        tenant_id: Uuid,
+        security: SecurityContext,
+        global_block_id: Uuid,
+    ) -> PagesResult<GlobalBlockResponse> {
+        enforce_scope(&security, Resource::Pages, Action::Read)?;
+        let model = find_global_block(&self.db, tenant_id, global_block_id).await?;
+        let usage_count = self.count_usages(tenant_id, model.id).await?;
+        global_block_response(model, usage_count)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn list(
+        &self,
+        tenant_id: Uuid,
+        security: SecurityContext,
+    ) -> PagesResult<Vec<GlobalBlockResponse>> {
+        enforce_scope(&security, Resource::Pages, Action::List)?;
+        let models = page_global_block::Entity::find()
+            .filter(page_global_block::Column::TenantId.eq(tenant_id))
+            .order_by_asc(page_global_block::Column::Key)
+            .all(&self.db)
+            .await?;
+
+        let usage_counts: HashMap<Uuid, i64> = page_block::Entity::find()
+            .select_only()
+            .column(page_block::Column::GlobalBlockId)
+            .column_as(page_block::Column::Id.count(), "usage_count")
+            .filter(page_block::Column::TenantId.eq(tenant_id))
+            .filter(page_block::Column::GlobalBlockId.is_not_null())
+            .group_by(page_block::Column::GlobalBlockId)
+            .into_tuple::<(Uuid, i64)>()
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .collect();
+
+        models
+            .into_iter()
+            .map(|model| {
+                let usage_count = usage_counts.get(&model.id).copied().unwrap_or(0);
+                global_block_response(model, usage_count as u64)
+            })
+            .collect()
+    }
+
+    /// Updates the shared definition; every referencing page block picks up the change and
+    /// each referencing page gets a `node.updated` event so caches and indexes are refreshed.
+    #[instrument(skip(self, input))]
+    pub async fn update(
+        &self,
+        tenant_id: Uuid,
+        security: SecurityContext,
+        global_block_id: Uuid,
+        input: UpdateGlobalBlockInput,
+    ) -> PagesResult<GlobalBlockResponse> {
+        enforce_scope(&security, Resource::Pages, Action::Update)?;
+        let existing = find_global_block(&self.db, tenant_id, global_block_id).await?;
+        let block_type = block_type_from_str(&existing.block_type)?;
+
+        let mut active: page_global_block::ActiveModel = existing.into();
+        if let Some(name) = input.name {
+            active.name = Set(normalize_name(&name)?);
+        }
+        if let Some(data) = input.data {
+            active.data = Set(validate_and_sanitize_block_data(&block_type, data)?);
+        }
+        if let Some(translations) = input.translations {
+            active.translations = Set(sanitize_translations(&block_type, Some(translations))?
+                .map(|items| serde_json::json!(items)));
+        }
+        active.updated_at = Set(Utc::now().into());
+
+        let txn = self.db.begin().await?;
+        let model = active.update(&txn).await?;
+        let page_ids = referencing_page_ids(&txn, tenant_id, model.id).await?;
+        self.publish_pages_updated(&txn, tenant_id, &security, &page_ids)
+            .await?;
+        txn.commit().await?;
+
+        let usage_count = self.count_usages(tenant_id, model.id).await?;
+        global_block_response(model, usage_count)
+    }
+
+    /// Lists page blocks that render the global block.
+    #[instrument(skip(self))]
+    pub async fn usages(
+        &self,
+        tenant_id: Uuid,
+        security: SecurityContext,
+        global_block_id: Uuid,
+    ) -> PagesResult<Vec<GlobalBlockUsage>> {
+        enforce_scope(&security, Resource::Pages, Action::Read)?;
+        find_global_block(&self.db, tenant_id, global_block_id).await?;
+        let blocks = page_block::Entity::find()
+            .filter(page_block::Column::TenantId.eq(tenant_id))
+            .filter(page_block::Column::GlobalBlockId.eq(global_block_id))
+            .order_by_asc(page_block::Column::PageId)
+            .order_by_asc(page_block::Column::Position)
+            .all(&self.db)
+            .await?;
+
+        Ok(blocks
+            .into_iter()
+            .map(|block| GlobalBlockUsage {
+                page_id: block.page_id,
+                block_id: block.id,
+                position: block.position,
+            })
+            .collect())
+    }
+
+    /// Deletes a global block.
+    ///
+    /// Without `force` the call fails with [`PagesError::GlobalBlockInUse`] while pages still
+    /// reference the block. With `force` referencing page blocks are detached and keep an inline
+    /// copy of the current content, so no page loses its block; the affected pages get a
+    /// `node.updated` event.
+    #[instrument(skip(self))]
+    pub async fn delete(
+        &self,
+        tenant_id: Uuid,
+        security: SecurityContext,
+        global_block_id: Uuid,
+        force: bool,
+    ) -> PagesResult<()> {
+        enforce_scope(&security, Resource::Pages, Action::Delete)?;
+        let txn = self.db.begin().await?;
+        let global_block = find_global_block(&txn, tenant_id, global_block_id).await?;
+        let references = page_block::Entity::find()
+            .filter(page_block::Column::TenantId.eq(tenant_id))
+            .filter(page_block::Column::GlobalBlockId.eq(global_block_id))
+            .all(&txn)
+            .await?;
+
+        if !references.is_empty() && !force {
+            return Err(PagesError::global_block_in_use(
+                global_block_id,
+                references.len() as u64,
+            ));
+        }
+
+        let mut page_ids: Vec<Uuid> = references.iter().map(|block| block.page_id).collect();
+        page_ids.sort_unstable();
+        page_ids.dedup();
+
+        let now = Utc::now();
+        for block in references {
+            let mut active: page_block::ActiveModel = block.into();
+            active.data = Set(global_block.data.clone());
+            active.translations = Set(global_block.translations.clone());
+            active.global_block_id = Set(None);
+            active.updated_at = Set(now.into());
+            active.update(&txn).await?;
+        }
+
+        page_global_block::Entity::delete_by_id(global_block_id)
+            .exec(&txn)
+            .await?;
+        self.publish_pages_updated(&txn, tenant_id, &security, &page_ids)
+            .await?;
+        txn.commit().await?;
+        Ok(())
+    }
+
+    async fn publish_pages_updated<C: ConnectionTrait>(
+        &self,
+        txn: &C,
+        tenant_id: Uuid,
+        security: &SecurityContext,
+        page_ids: &[Uuid],
+    ) -> PagesResult<()> {
+        for page_id in page_ids {
+            self.event_bus
+                .publish_in_tx(
+                    txn,
+                    tenant_id,
+                    security.user_id,
+                    DomainEvent::NodeUpdated {
+                        node_id: *page_id,
+                        kind: PAGE_KIND.to_string(),
+                    },
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn count_usages(&self, tenant_id: Uuid, global_block_id: Uuid) -> PagesResult<u64> {
+        Ok(page_block::Entity::find()
+            .filter(page_block::Column::TenantId.eq(tenant_id))
+            .filter(page_block::Column::GlobalBlockId.eq(global_block_id))
+            .count(&self.db)
+            .await?)
+    }
+}
+
+async fn referencing_page_ids<C: ConnectionTrait>(
+    conn: &C,
+    tenant_id: Uuid,
+    global_block_id: Uuid,
+) -> PagesResult<Vec<Uuid>> {
+    Ok(page_block::Entity::find()
+        .select_only()
+        .column(page_block::Column::PageId)
+        .distinct()
+        .filter(page_block::Column::TenantId.eq(tenant_id))
+        .filter(page_block::Column::GlobalBlockId.eq(global_block_id))
+        .order_by_asc(page_block::Column::PageId)
+        .into_tuple::<Uuid>()
+        .all(conn)
+        .await?)
+}
+
+pub(crate) async fn find_global_block<C: ConnectionTrait>(
+    conn: &C,
+    tenant_id: Uuid,
+    global_block_id: Uuid,
+) -> PagesResult<page_global_block::Model> {
+    page_global_block::Entity::find_by_id(global_block_id)
+        .filter(page_global_block::Column::TenantId.eq(tenant_id))
+        .one(conn)
+        .await?
+        .ok_or_else(|| PagesError::global_block_not_found(global_block_id))
+}
+
+/// Loads a global block that a page block is about to reference and checks the declared type.
+pub(crate) async fn find_global_block_for_reference<C: ConnectionTrait>(
+    conn: &C,
+    tenant_id: Uuid,
+    global_block_id: Uuid,
+    block_type: &BlockType,
+) -> PagesResult<page_global_block::Model> {
+    let global_block = find_global_block(conn, tenant_id, global_block_id).await?;
+    if global_block.block_type != block_type_str(block_type) {
+        return Err(PagesError::validation(format!(
+            "Global block {global_block_id} is of type '{}', not '{}'",
+            global_block.block_type,
+            block_type_str(block_type)
+        )));
+    }
+    Ok(global_block)
+}
+
+pub(crate) async fn load_global_blocks_by_id<C: ConnectionTrait>(
+    conn: &C,
+    tenant_id: Uuid,
+    ids: impl IntoIterator<Item = Uuid>,
+) -> PagesResult<HashMap<Uuid, page_global_block::Model>> {
+    let mut ids: Vec<Uuid> = ids.into_iter().collect();
+    ids.sort_unstable();
+    ids.dedup();
+    if ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    Ok(page_global_block::Entity::find()
+        .filter(page_global_block::Column::TenantId.eq(tenant_id))
+        .filter(page_global_block::Column::Id.is_in(ids))
+        .all(conn)
+        .await?
+        .into_iter()
+        .map(|model| (model.id, model))
+        .collect())
+}
+
+fn global_block_response(
+    model: page_global_block::Model,
+    usage_count: u64,
+) -> PagesResult<GlobalBlockResponse> {
+    Ok(GlobalBlockResponse {
+        id: model.id,
+        key: model.key,
+        name: model.name,
+        block_type: block_type_from_str(&model.block_type)?,
+        data: model.data,
+        translations: model
+            .translations
+            .and_then(|value| serde_json::from_value(value).ok()),
+        usage_count,
+        created_at: model.created_at.to_rfc3339(),
+        updated_at: model.updated_at.to_rfc3339(),
+    })
+}
+
+fn normalize_global_block_key(value: &str) -> PagesResult<String> {
+    let key = value.trim().to_lowercase();
+    if key.is_empty() {
+        return Err(PagesError::validation("Global block key must not be empty"));
+    }
+    if key.len() > 128
+        || !key
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.'))
+    {
+        return Err(PagesError::validation(
+            "Global block key may contain only latin letters, digits, '-', '_' and '.'",
+        ));
+    }
+    Ok(key)
+}
+
+pub(crate) fn normalize_name(value: &str) -> PagesResult<String> {
+    let name = value.trim();
+    if name.is_empty() {
+        return Err(PagesError::validation("Name must not be empty"));
+    }
+    Ok(name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn global_block_key_is_normalized() {
+        assert_eq!(
+            normalize_global_block_key("  Footer-CTA ").unwrap(),
+            "footer-cta"
+        );
+    }
+
+    #[test]
+    fn global_block_key_rejects_spaces_and_empty_values() {
+        assert!(normalize_global_block_key("   ").is_err());
+        assert!(normalize_global_block_key("footer cta").is_err());
+    }
+}
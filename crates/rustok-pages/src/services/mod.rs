@@ -1,9 +1,13 @@
 // Service layer for pages operations.
 pub mod block;
+pub mod global_block;
 pub mod menu;
 pub mod page;
 mod rbac;
+pub mod template;
 
 pub use block::BlockService;
+pub use global_block::GlobalBlockService;
 pub use menu::MenuService;
 pub use page::PageService;
+pub use template::PageTemplateService;
@@ -14,8 +14,8 @@ use rustok_content::{
     available_locales_from, normalize_locale_code, resolve_by_locale_with_fallback,
 };
 use rustok_core::{
-    normalize_content_format, prepare_content_payload, Action, Resource, SecurityContext,
-    CONTENT_FORMAT_GRAPESJS_V1, CONTENT_FORMAT_RT_JSON_V1,
+    normalize_content_format, prepare_content_payload, render_content, Action, Resource,
+    SecurityContext, CONTENT_FORMAT_GRAPESJS_V1, CONTENT_FORMAT_RT_JSON_V1,
 };
 use rustok_events::DomainEvent;
 use rustok_outbox::TransactionalEventBus;
@@ -27,10 +27,11 @@ use crate::error::{
     FEATURE_BUILDER_PROPERTIES_ENABLED, FEATURE_BUILDER_PUBLISH_ENABLED,
 };
 use crate::services::rbac::{can_read_non_public_pages, enforce_owned_scope, enforce_scope};
+use crate::services::template::load_template_blocks;
 use crate::services::BlockService;
 use rustok_tenant::entities::tenant_module;
 
-const PAGE_KIND: &str = "page";
+pub(crate) const PAGE_KIND: &str = "page";
 const PLATFORM_FALLBACK_LOCALE: &str = "en";
 
 struct PageResponseParts {
@@ -122,7 +123,7 @@ impl PageService {
             tenant_id: Set(tenant_id),
             author_id: Set(security.user_id),
             status: Set(status_to_storage(&initial_status).to_string()),
-            template: Set(template),
+            template: Set(template.clone()),
             metadata: Set(metadata),
             created_at: Set(now.into()),
             updated_at: Set(now.into()),
@@ -142,7 +143,11 @@ impl PageService {
         self.replace_channel_visibility_in_tx(&txn, tenant_id, page_id, &channel_slugs)
             .await?;
         self.upsert_body_in_tx(&txn, page_id, body, now).await?;
-        if let Some(blocks) = input.blocks {
+        let blocks = match input.blocks {
+            Some(blocks) => Some(blocks),
+            None => load_template_blocks(&txn, tenant_id, &template.trim().to_lowercase()).await?,
+        };
+        if let Some(blocks) = blocks {
             for block in blocks {
                 BlockService::create_in_tx(&txn, tenant_id, security.clone(), page_id, block)
                     .await?;
@@ -1303,6 +1308,7 @@ fn page_body_response(body: &page_body::Model) -> PageBodyResponse {
         content: body.content.clone(),
         format: body.format.clone(),
         content_json,
+        html: render_content(&body.format, &body.content).html,
         updated_at: body.updated_at.to_string(),
     }
 }
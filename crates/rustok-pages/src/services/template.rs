@@ -0,0 +1,234 @@
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, DatabaseConnection,
+    EntityTrait, QueryFilter, QueryOrder,
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use rustok_core::{Action, Resource, SecurityContext};
+use rustok_outbox::TransactionalEventBus;
+
+use crate::dto::*;
+use crate::entities::page_template;
+use crate::error::{PagesError, PagesResult};
+use crate::services::block::{sanitize_translations, validate_and_sanitize_block_data};
+use crate::services::global_block::{find_global_block_for_reference, normalize_name};
+use crate::services::rbac::enforce_scope;
+
+/// Page templates pre-populate the block structure of pages created with a matching
+/// `CreatePageInput::template` slug.
+pub struct PageTemplateService {
+    db: DatabaseConnection,
+}
+
+impl PageTemplateService {
+    pub fn new(db: DatabaseConnection, event_bus: TransactionalEventBus) -> Self {
+        let _ = event_bus;
+        Self { db }
+    }
+
+    #[instrument(skip(self, input))]
+    pub async fn create(
+        &self,
+        tenant_id: Uuid,
+        security: SecurityContext,
+        input: CreatePageTemplateInput,
+    ) -> PagesResult<PageTemplateResponse> {
+        enforce_scope(&security, Resource::Pages, Action::Create)?;
+        let slug = normalize_template_slug(&input.slug)?;
+        let name = normalize_name(&input.name)?;
+        let blocks = prepare_template_blocks(&self.db, tenant_id, input.blocks).await?;
+
+        if find_template_by_slug(&self.db, tenant_id, &slug)
+            .await?
+            .is_some()
+        {
+            return Err(PagesError::validation(format!(
+                "Page template '{slug}' already exists"
+            )));
+        }
+
+        let now = Utc::now();
+        let model = page_template::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tenant_id: Set(tenant_id),
+            slug: Set(slug),
+            name: Set(name),
+            description: Set(normalize_description(input.description)),
+            blocks: Set(serde_json::json!(blocks)),
+            created_at: Set(now.into()),
+            updated_at: Set(now.into()),
+        }
+        .insert(&self.db)
+        .await?;
+
+        template_response(model)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get(
+        &self,
+        tenant_id: Uuid,
+        security: SecurityContext,
+        template_id: Uuid,
+    ) -> PagesResult<PageTemplateResponse> {
+        enforce_scope(&security, Resource::Pages, Action::Read)?;
+        template_response(self.find_template(tenant_id, template_id).await?)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn list(
+        &self,
+        tenant_id: Uuid,
+        security: SecurityContext,
+    ) -> PagesResult<Vec<PageTemplateResponse>> {
+        enforce_scope(&security, Resource::Pages, Action::List)?;
+        page_template::Entity::find()
+            .filter(page_template::Column::TenantId.eq(tenant_id))
+            .order_by_asc(page_template::Column::Slug)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(template_response)
+            .collect()
+    }
+
+    /// Updates the template. Pages created earlier keep their own copy of the blocks.
+    #[instrument(skip(self, input))]
+    pub async fn update(
+        &self,
+        tenant_id: Uuid,
+        security: SecurityContext,
+        template_id: Uuid,
+        input: UpdatePageTemplateInput,
+    ) -> PagesResult<PageTemplateResponse> {
+        enforce_scope(&security, Resource::Pages, Action::Update)?;
+        let existing = self.find_template(tenant_id, template_id).await?;
+
+        let mut active: page_template::ActiveModel = existing.into();
+        if let Some(name) = input.name {
+            active.name = Set(normalize_name(&name)?);
+        }
+        if input.description.is_some() {
+            active.description = Set(normalize_description(input.description));
+        }
+        if let Some(blocks) = input.blocks {
+            let blocks = prepare_template_blocks(&self.db, tenant_id, blocks).await?;
+            active.blocks = Set(serde_json::json!(blocks));
+        }
+        active.updated_at = Set(Utc::now().into());
+
+        template_response(active.update(&self.db).await?)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn delete(
+        &self,
+        tenant_id: Uuid,
+        security: SecurityContext,
+        template_id: Uuid,
+    ) -> PagesResult<()> {
+        enforce_scope(&security, Resource::Pages, Action::Delete)?;
+        let template = self.find_template(tenant_id, template_id).await?;
+        page_template::Entity::delete_by_id(template.id)
+            .exec(&self.db)
+            .await?;
+        Ok(())
+    }
+
+    async fn find_template(
+        &self,
+        tenant_id: Uuid,
+        template_id: Uuid,
+    ) -> PagesResult<page_template::Model> {
+        page_template::Entity::find_by_id(template_id)
+            .filter(page_template::Column::TenantId.eq(tenant_id))
+            .one(&self.db)
+            .await?
+            .ok_or_else(|| PagesError::template_not_found(template_id))
+    }
+}
+
+/// Returns the block blueprint of the tenant template with the given slug, if one exists.
+pub(crate) async fn load_template_blocks<C: ConnectionTrait>(
+    conn: &C,
+    tenant_id: Uuid,
+    slug: &str,
+) -> PagesResult<Option<Vec<CreateBlockInput>>> {
+    let Some(template) = find_template_by_slug(conn, tenant_id, slug).await? else {
+        return Ok(None);
+    };
+    decode_template_blocks(template.blocks).map(Some)
+}
+
+async fn find_template_by_slug<C: ConnectionTrait>(
+    conn: &C,
+    tenant_id: Uuid,
+    slug: &str,
+) -> PagesResult<Option<page_template::Model>> {
+    Ok(page_template::Entity::find()
+        .filter(page_template::Column::TenantId.eq(tenant_id))
+        .filter(page_template::Column::Slug.eq(slug))
+        .one(conn)
+        .await?)
+}
+
+async fn prepare_template_blocks<C: ConnectionTrait>(
+    conn: &C,
+    tenant_id: Uuid,
+    blocks: Vec<CreateBlockInput>,
+) -> PagesResult<Vec<CreateBlockInput>> {
+    let mut prepared = Vec::with_capacity(blocks.len());
+    for block in blocks {
+        if let Some(global_block_id) = block.global_block_id {
+            find_global_block_for_reference(conn, tenant_id, global_block_id, &block.block_type)
+                .await?;
+            prepared.push(CreateBlockInput {
+                data: serde_json::json!({}),
+                translations: None,
+                ..block
+            });
+        } else {
+            prepared.push(CreateBlockInput {
+                data: validate_and_sanitize_block_data(&block.block_type, block.data)?,
+                translations: sanitize_translations(&block.block_type, block.translations)?,
+                ..block
+            });
+        }
+    }
+    Ok(prepared)
+}
+
+fn decode_template_blocks(value: serde_json::Value) -> PagesResult<Vec<CreateBlockInput>> {
+    serde_json::from_value(value)
+        .map_err(|err| PagesError::validation(format!("Invalid stored template blocks: {err}")))
+}
+
+fn template_response(model: page_template::Model) -> PagesResult<PageTemplateResponse> {
+    Ok(PageTemplateResponse {
+        id: model.id,
+        slug: model.slug,
+        name: model.name,
+        description: model.description,
+        blocks: decode_template_blocks(model.blocks)?,
+        created_at: model.created_at.to_rfc3339(),
+        updated_at: model.updated_at.to_rfc3339(),
+    })
+}
+
+fn normalize_template_slug(value: &str) -> PagesResult<String> {
+    let slug = value.trim().to_lowercase();
+    if slug.is_empty() || slug.len() > 128 {
+        return Err(PagesError::validation(
+            "Page template slug must be between 1 and 128 characters",
+        ));
+    }
+    Ok(slug)
+}
+
+fn normalize_description(value: Option<String>) -> Option<String> {
+    value
+        .map(|description| description.trim().to_string())
+        .filter(|description| !description.is_empty())
+}
@@ -0,0 +1,397 @@
+use std::sync::Arc;
+
+use rustok_core::{DomainEvent, MigrationSource, SecurityContext};
+use rustok_outbox::TransactionalEventBus;
+use rustok_pages::dto::{
+    BlockTranslationInput, BlockType, CreateBlockInput, CreateGlobalBlockInput, CreatePageInput,
+    CreatePageTemplateInput, PageTranslationInput, UpdateGlobalBlockInput,
+};
+use rustok_pages::services::{BlockService, GlobalBlockService, PageService, PageTemplateService};
+use rustok_pages::{PagesError, PagesModule};
+use rustok_test_utils::{db::setup_test_db, helpers::admin_context, MockEventTransport};
+use sea_orm_migration::SchemaManager;
+use uuid::Uuid;
+
+struct Services {
+    pages: PageService,
+    blocks: BlockService,
+    global_blocks: GlobalBlockService,
+    templates: PageTemplateService,
+    events: Arc<MockEventTransport>,
+}
+
+async fn setup() -> (Services, Uuid, SecurityContext) {
+    let db = setup_test_db().await;
+    let module = PagesModule;
+    let schema = SchemaManager::new(&db);
+    for migration in module.migrations() {
+        migration
+            .up(&schema)
+            .await
+            .expect("failed to apply pages migrations");
+    }
+
+    let events = Arc::new(MockEventTransport::new());
+    let event_bus = TransactionalEventBus::new(events.clone());
+    (
+        Services {
+            pages: PageService::new(db.clone(), event_bus.clone()),
+            blocks: BlockService::new(db.clone(), event_bus.clone()),
+            global_blocks: GlobalBlockService::new(db.clone(), event_bus.clone()),
+            templates: PageTemplateService::new(db, event_bus),
+            events,
+        },
+        Uuid::new_v4(),
+        admin_context(),
+    )
+}
+
+fn page_input(
+    slug: &str,
+    template: &str,
+    blocks: Option<Vec<CreateBlockInput>>,
+) -> CreatePageInput {
+    CreatePageInput {
+        translations: vec![PageTranslationInput {
+            locale: "en".to_string(),
+            title: slug.to_string(),
+            slug: Some(slug.to_string()),
+            meta_title: None,
+            meta_description: None,
+        }],
+        template: Some(template.to_string()),
+        body: None,
+        blocks,
+        channel_slugs: None,
+        publish: false,
+    }
+}
+
+fn global_reference(global_block_id: Uuid, position: i32) -> CreateBlockInput {
+    CreateBlockInput {
+        block_type: BlockType::Cta,
+        position,
+        data: serde_json::Value::Null,
+        translations: None,
+        global_block_id: Some(global_block_id),
+    }
+}
+
+fn updated_nodes(events: &MockEventTransport) -> Vec<Uuid> {
+    let mut node_ids: Vec<Uuid> = events
+        .events_of_type("NodeUpdated")
+        .into_iter()
+        .filter_map(|event| match event {
+            DomainEvent::NodeUpdated { node_id, .. } => Some(node_id),
+            _ => None,
+        })
+        .collect();
+    node_ids.sort_unstable();
+    node_ids
+}
+
+fn footer_cta() -> CreateGlobalBlockInput {
+    CreateGlobalBlockInput {
+        key: "footer-cta".to_string(),
+        name: "Footer CTA".to_string(),
+        block_type: BlockType::Cta,
+        data: serde_json::json!({
+            "title": "Join us",
+            "button_label": "Sign up",
+            "button_url": "https://example.com/signup"
+        }),
+        translations: Some(vec![BlockTranslationInput {
+            locale: "ru".to_string(),
+            data: serde_json::json!({
+                "title": "Присоединяйтесь",
+                "button_label": "Регистрация",
+                "button_url": "https://example.com/signup"
+            }),
+        }]),
+    }
+}
+
+#[tokio::test]
+async fn global_block_edits_propagate_to_every_referencing_page() {
+    let (services, tenant_id, security) = setup().await;
+    let global = services
+        .global_blocks
+        .create(tenant_id, security.clone(), footer_cta())
+        .await
+        .expect("global block should be created");
+
+    let first = services
+        .pages
+        .create(
+            tenant_id,
+            security.clone(),
+            page_input(
+                "first",
+                "default",
+                Some(vec![global_reference(global.id, 0)]),
+            ),
+        )
+        .await
+        .expect("first page should be created");
+    let second = services
+        .pages
+        .create(
+            tenant_id,
+            security.clone(),
+            page_input(
+                "second",
+                "default",
+                Some(vec![global_reference(global.id, 0)]),
+            ),
+        )
+        .await
+        .expect("second page should be created");
+
+    assert_eq!(first.blocks[0].global_block_id, Some(global.id));
+    assert_eq!(first.blocks[0].data["title"], "Join us");
+    assert_eq!(
+        first.blocks[0].translations.as_ref().unwrap()[0].locale,
+        "ru"
+    );
+
+    services.events.clear();
+    services
+        .global_blocks
+        .update(
+            tenant_id,
+            security.clone(),
+            global.id,
+            UpdateGlobalBlockInput {
+                data: Some(serde_json::json!({
+                    "title": "Join the community",
+                    "button_label": "Sign up",
+                    "button_url": "https://example.com/signup"
+                })),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("global block should be updated");
+
+    let mut expected = vec![first.id, second.id];
+    expected.sort_unstable();
+    assert_eq!(updated_nodes(&services.events), expected);
+
+    for page_id in [first.id, second.id] {
+        let blocks = services
+            .blocks
+            .list_for_page(tenant_id, security.clone(), page_id)
+            .await
+            .expect("blocks should be listed");
+        assert_eq!(blocks[0].data["title"], "Join the community");
+    }
+
+    let refreshed = services
+        .global_blocks
+        .get(tenant_id, security.clone(), global.id)
+        .await
+        .expect("global block should be readable");
+    assert_eq!(refreshed.usage_count, 2);
+
+    let unused = services
+        .global_blocks
+        .create(
+            tenant_id,
+            security.clone(),
+            CreateGlobalBlockInput {
+                key: "header-cta".to_string(),
+                ..footer_cta()
+            },
+        )
+        .await
+        .expect("second global block should be created");
+    let listed = services
+        .global_blocks
+        .list(tenant_id, security)
+        .await
+        .expect("global blocks should be listed");
+    let usage = |id: Uuid| {
+        listed
+            .iter()
+            .find(|block| block.id == id)
+            .unwrap()
+            .usage_count
+    };
+    assert_eq!(usage(global.id), 2);
+    assert_eq!(usage(unused.id), 0);
+}
+
+#[tokio::test]
+async fn deleting_used_global_block_requires_force_and_detaches_copies() {
+    let (services, tenant_id, security) = setup().await;
+    let global = services
+        .global_blocks
+        .create(tenant_id, security.clone(), footer_cta())
+        .await
+        .expect("global block should be created");
+    let page = services
+        .pages
+        .create(
+            tenant_id,
+            security.clone(),
+            page_input(
+                "landing",
+                "default",
+                Some(vec![global_reference(global.id, 0)]),
+            ),
+        )
+        .await
+        .expect("page should be created");
+
+    let usages = services
+        .global_blocks
+        .usages(tenant_id, security.clone(), global.id)
+        .await
+        .expect("usages should be listed");
+    assert_eq!(usages.len(), 1);
+    assert_eq!(usages[0].page_id, page.id);
+
+    let err = services
+        .global_blocks
+        .delete(tenant_id, security.clone(), global.id, false)
+        .await
+        .expect_err("used global block must not be deleted silently");
+    assert!(matches!(
+        err,
+        PagesError::GlobalBlockInUse { usages: 1, .. }
+    ));
+
+    services.events.clear();
+    services
+        .global_blocks
+        .delete(tenant_id, security.clone(), global.id, true)
+        .await
+        .expect("forced delete should succeed");
+    assert_eq!(updated_nodes(&services.events), vec![page.id]);
+
+    let blocks = services
+        .blocks
+        .list_for_page(tenant_id, security, page.id)
+        .await
+        .expect("blocks should be listed");
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks[0].global_block_id, None);
+    assert_eq!(blocks[0].data["title"], "Join us");
+}
+
+#[tokio::test]
+async fn page_template_prepopulates_blocks_for_new_pages() {
+    let (services, tenant_id, security) = setup().await;
+    let global = services
+        .global_blocks
+        .create(tenant_id, security.clone(), footer_cta())
+        .await
+        .expect("global block should be created");
+    services
+        .templates
+        .create(
+            tenant_id,
+            security.clone(),
+            CreatePageTemplateInput {
+                slug: "landing".to_string(),
+                name: "Landing".to_string(),
+                description: None,
+                blocks: vec![
+                    CreateBlockInput {
+                        block_type: BlockType::Hero,
+                        position: 0,
+                        data: serde_json::json!({ "title": "  Headline  " }),
+                        translations: None,
+                        global_block_id: None,
+                    },
+                    global_reference(global.id, 1),
+                ],
+            },
+        )
+        .await
+        .expect("template should be created");
+
+    let page = services
+        .pages
+        .create(
+            tenant_id,
+            security.clone(),
+            page_input("promo", "landing", None),
+        )
+        .await
+        .expect("page should be created from template");
+    assert_eq!(page.blocks.len(), 2);
+    assert_eq!(page.blocks[0].data["title"], "Headline");
+    assert_eq!(page.blocks[1].global_block_id, Some(global.id));
+
+    let explicit = services
+        .pages
+        .create(
+            tenant_id,
+            security,
+            page_input("explicit", "landing", Some(Vec::new())),
+        )
+        .await
+        .expect("explicit blocks should override the template");
+    assert!(explicit.blocks.is_empty());
+}
+
+#[tokio::test]
+async fn referencing_block_rejects_type_mismatch_and_inline_edits() {
+    let (services, tenant_id, security) = setup().await;
+    let global = services
+        .global_blocks
+        .create(tenant_id, security.clone(), footer_cta())
+        .await
+        .expect("global block should be created");
+    let page = services
+        .pages
+        .create(
+            tenant_id,
+            security.clone(),
+            page_input("about", "default", None),
+        )
+        .await
+        .expect("page should be created");
+
+    let err = services
+        .blocks
+        .create(
+            tenant_id,
+            security.clone(),
+            page.id,
+            CreateBlockInput {
+                block_type: BlockType::Hero,
+                ..global_reference(global.id, 0)
+            },
+        )
+        .await
+        .expect_err("type mismatch must be rejected");
+    assert!(matches!(err, PagesError::Validation(_)));
+
+    let block = services
+        .blocks
+        .create(
+            tenant_id,
+            security.clone(),
+            page.id,
+            global_reference(global.id, 0),
+        )
+        .await
+        .expect("reference should be created");
+    let err = services
+        .blocks
+        .update(
+            tenant_id,
+            security,
+            block.id,
+            rustok_pages::dto::UpdateBlockInput {
+                position: None,
+                data: Some(serde_json::json!({ "title": "local" })),
+                translations: None,
+            },
+        )
+        .await
+        .expect_err("inline edits of a reference must be rejected");
+    assert!(matches!(err, PagesError::Validation(_)));
+}
@@ -62,6 +62,7 @@ fn legacy_text_block(text: &str, position: i32) -> CreateBlockInput {
             "text": text,
         }),
         translations: None,
+        global_block_id: None,
     }
 }
 
@@ -155,6 +155,7 @@ async fn create_block(
                 position: 0,
                 data: serde_json::json!({ "text": "hello" }),
                 translations: None,
+                global_block_id: None,
             },
         )
         .await
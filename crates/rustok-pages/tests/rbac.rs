@@ -173,6 +173,7 @@ async fn customer_cannot_mutate_blocks_or_menus() {
                 position: 0,
                 data: serde_json::json!({ "text": "nope" }),
                 translations: None,
+                global_block_id: None,
             },
         )
         .await
@@ -0,0 +1,71 @@
+use async_trait::async_trait;
+use rustok_core::{
+    ModuleAction, ModuleActionContext, ModuleActionDescriptor, ModuleActionError,
+    ModuleActionResult, Permission,
+};
+use rustok_outbox::TransactionalEventBus;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::CatalogService;
+
+/// `product.publish` — moves a product to `active` and stamps `published_at`.
+#[derive(Clone, Default)]
+pub struct PublishProductAction;
+
+#[derive(Deserialize)]
+struct PublishProductInput {
+    product_id: Uuid,
+}
+
+#[async_trait]
+impl ModuleAction for PublishProductAction {
+    fn descriptor(&self) -> ModuleActionDescriptor {
+        ModuleActionDescriptor {
+            name: "product.publish",
+            module_slug: "product",
+            description: "Publish a product to the storefront",
+            input_schema: json!({
+                "type": "object",
+                "required": ["product_id"],
+                "additionalProperties": false,
+                "properties": {
+                    "product_id": { "type": "string", "format": "uuid" }
+                }
+            }),
+            output_schema: json!({
+                "type": "object",
+                "required": ["product_id", "status"],
+                "properties": {
+                    "product_id": { "type": "string", "format": "uuid" },
+                    "status": { "type": "string" },
+                    "published_at": { "type": ["string", "null"], "format": "date-time" }
+                }
+            }),
+            required_permissions: vec![Permission::PRODUCTS_UPDATE],
+        }
+    }
+
+    async fn invoke(&self, ctx: &ModuleActionContext, input: Value) -> ModuleActionResult<Value> {
+        let input: PublishProductInput =
+            serde_json::from_value(input).map_err(ModuleActionError::failed)?;
+        let product = CatalogService::new(
+            ctx.db.clone(),
+            TransactionalEventBus::new(ctx.event_transport.clone()),
+        )
+        .publish_product(
+            ctx.tenant_id,
+            ctx.actor_id.unwrap_or_else(Uuid::nil),
+            input.product_id,
+        )
+        .await
+        .map_err(ModuleActionError::failed)?;
+
+        Ok(json!({
+            "product_id": product.id,
+            "status": product.status.to_string(),
+            "published_at": product.published_at,
+        }))
+    }
+}
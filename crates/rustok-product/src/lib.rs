@@ -1,15 +1,20 @@
 use async_trait::async_trait;
 use rustok_core::permissions::Permission;
-use rustok_core::{MigrationSource, ModuleRuntimeExtensions, RusToKModule};
+use rustok_core::{register_module_action, MigrationSource, ModuleRuntimeExtensions, RusToKModule};
+use rustok_media::register_media_usage_provider;
 use rustok_seo_targets::register_seo_target_provider;
 use sea_orm_migration::MigrationTrait;
 
+mod actions;
 pub mod entities;
+mod media_usage;
 pub mod migrations;
 mod seo_targets;
 pub mod services;
 
-pub use services::{CatalogService, StorefrontProductList, StorefrontProductListItem};
+pub use services::{
+    product_tag_tree_condition, CatalogService, StorefrontProductList, StorefrontProductListItem,
+};
 
 pub struct ProductModule;
 
@@ -49,6 +54,10 @@ impl RusToKModule for ProductModule {
     fn register_runtime_extensions(&self, extensions: &mut ModuleRuntimeExtensions) {
         register_seo_target_provider(extensions, seo_targets::ProductSeoTargetProvider)
             .expect("product SEO target registration should remain unique");
+        register_media_usage_provider(extensions, media_usage::ProductMediaUsageProvider)
+            .expect("product media usage registration should remain unique");
+        register_module_action(extensions, actions::PublishProductAction)
+            .expect("product.publish action registration should remain unique");
     }
 }
 
@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+
+use anyhow::Result as AnyResult;
+use async_trait::async_trait;
+use rustok_commerce_foundation::entities::{product, product_image, product_translation};
+use rustok_media::{collect_json_references, MediaReference, MediaUsageProvider};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder};
+use uuid::Uuid;
+
+/// Reports product gallery images and media referenced from product metadata.
+#[derive(Clone, Default)]
+pub struct ProductMediaUsageProvider;
+
+#[async_trait]
+impl MediaUsageProvider for ProductMediaUsageProvider {
+    fn owner_kind(&self) -> &'static str {
+        "product"
+    }
+
+    fn owner_module_slug(&self) -> &'static str {
+        "product"
+    }
+
+    async fn collect_references(
+        &self,
+        db: &DatabaseConnection,
+        tenant_id: Uuid,
+    ) -> AnyResult<Vec<MediaReference>> {
+        let products = product::Entity::find()
+            .filter(product::Column::TenantId.eq(tenant_id))
+            .all(db)
+            .await?;
+        if products.is_empty() {
+            return Ok(Vec::new());
+        }
+        let product_ids: Vec<Uuid> = products.iter().map(|product| product.id).collect();
+
+        let mut titles = HashMap::new();
+        for translation in product_translation::Entity::find()
+            .filter(product_translation::Column::ProductId.is_in(product_ids.clone()))
+            .order_by_asc(product_translation::Column::Locale)
+            .all(db)
+            .await?
+        {
+            titles
+                .entry(translation.product_id)
+                .or_insert(translation.title);
+        }
+
+        let mut references = Vec::new();
+        for product in &products {
+            references.extend(collect_json_references(
+                product.id,
+                "metadata",
+                &product.metadata,
+            ));
+        }
+        for image in product_image::Entity::find()
+            .filter(product_image::Column::ProductId.is_in(product_ids))
+            .all(db)
+            .await?
+        {
+            references.push(MediaReference::id(
+                image.product_id,
+                format!("images.{}", image.id),
+                image.media_id,
+            ));
+        }
+
+        Ok(references
+            .into_iter()
+            .map(|reference| {
+                let label = titles.get(&reference.owner_id).cloned();
+                reference.with_label(label)
+            })
+            .collect())
+    }
+}
@@ -4,6 +4,7 @@ use flex::{
     prepare_attached_values_update, resolve_attached_payload,
 };
 use sea_orm::{
+    sea_query::{Expr, Query, SimpleExpr},
     ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DatabaseTransaction,
     EntityTrait, QueryFilter, QueryOrder, Set, TransactionTrait,
 };
@@ -2148,6 +2149,27 @@ impl CatalogService {
     }
 }
 
+/// Condition matching products tagged with `tag_id` or any of its descendant terms.
+pub async fn product_tag_tree_condition<C: ConnectionTrait>(
+    conn: &C,
+    tenant_id: Uuid,
+    tag_id: Uuid,
+) -> CommerceResult<SimpleExpr> {
+    let term_ids = TaxonomyService::expand_descendants_in_tx(conn, tenant_id, &[tag_id])
+        .await
+        .map_err(|error| CommerceError::Validation(error.to_string()))?;
+    Ok(
+        Expr::col((entities::product::Entity, entities::product::Column::Id)).in_subquery(
+            Query::select()
+                .column(product_tag::Column::ProductId)
+                .from(product_tag::Entity)
+                .and_where(Expr::col(product_tag::Column::TenantId).eq(tenant_id))
+                .and_where(Expr::col(product_tag::Column::TermId).is_in(term_ids))
+                .to_owned(),
+        ),
+    )
+}
+
 async fn load_product_custom_fields_schema<C>(
     db: &C,
     tenant_id: Uuid,
@@ -1,3 +1,5 @@
 pub mod catalog;
 
-pub use catalog::{CatalogService, StorefrontProductList, StorefrontProductListItem};
+pub use catalog::{
+    product_tag_tree_condition, CatalogService, StorefrontProductList, StorefrontProductListItem,
+};
@@ -0,0 +1,451 @@
+//! Per-user activity feed assembled from domain events.
+//!
+//! [`ProfileActivityHandler`] records published blog posts, new forum topics and accepted
+//! solutions into `profile_activities`, copying the channel restrictions of the underlying
+//! content. It reads the owning modules' tables directly, like the notification resolvers, so
+//! profiles do not depend on the blog or forum crates.
+//!
+//! [`ProfileActivityService`] serves two feeds with keyset pagination over
+//! `(occurred_at, id)`: a single profile's timeline and a viewer's home feed built from the
+//! profiles they follow. Both honour [`ProfileVisibility`], blocks and mutes, and the channel of
+//! the current request.
+
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use rustok_core::events::{EventEnvelope, EventHandler, HandlerResult};
+use rustok_events::DomainEvent;
+use sea_orm::sea_query::{Alias, Expr, Query};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, EntityTrait,
+    QueryFilter, QueryOrder, QuerySelect, Set,
+};
+use uuid::Uuid;
+
+use crate::dto::{
+    activity_kinds, ProfileActivity, ProfileActivityPage, ProfileActivityQuery, ProfileStatus,
+    ProfileVisibility,
+};
+use crate::entities::{profile, profile_activity, profile_follow};
+use crate::social::SocialGraphService;
+use crate::{ProfileError, ProfileResult};
+
+const DEFAULT_FEED_LIMIT: u64 = 20;
+const MAX_FEED_LIMIT: u64 = 100;
+
+#[derive(Clone)]
+pub struct ProfileActivityService {
+    db: DatabaseConnection,
+}
+
+impl ProfileActivityService {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Timeline of a single user as seen by `viewer_id` (`None` for anonymous visitors).
+    ///
+    /// Returns an empty page when the profile's visibility hides it from the viewer or either
+    /// side blocked the other.
+    pub async fn profile_feed(
+        &self,
+        tenant_id: Uuid,
+        viewer_id: Option<Uuid>,
+        user_id: Uuid,
+        query: ProfileActivityQuery,
+    ) -> ProfileResult<ProfileActivityPage> {
+        let owner = profile::Entity::find_by_id(user_id)
+            .filter(profile::Column::TenantId.eq(tenant_id))
+            .one(&self.db)
+            .await?
+            .ok_or(ProfileError::ProfileNotFound(user_id))?;
+
+        if viewer_id != Some(user_id) {
+            if owner.status != ProfileStatus::Active {
+                return Err(ProfileError::ProfileNotFound(user_id));
+            }
+            let follows = match viewer_id {
+                Some(viewer_id) => {
+                    SocialGraphService::is_following_in_tx(&self.db, tenant_id, viewer_id, user_id)
+                        .await?
+                }
+                None => false,
+            };
+            if !visible_to(owner.visibility, viewer_id.is_some(), follows) {
+                return Ok(ProfileActivityPage::default());
+            }
+            if let Some(viewer_id) = viewer_id {
+                let hidden =
+                    SocialGraphService::hidden_from_in_tx(&self.db, tenant_id, viewer_id).await?;
+                if hidden.contains(&user_id) {
+                    return Ok(ProfileActivityPage::default());
+                }
+            }
+        }
+
+        self.page(tenant_id, &[user_id], query).await
+    }
+
+    /// Activity of the viewer and the profiles they follow, newest first.
+    ///
+    /// Private, hidden and blocked profiles are left out, as are users the viewer muted.
+    pub async fn home_feed(
+        &self,
+        tenant_id: Uuid,
+        viewer_id: Uuid,
+        query: ProfileActivityQuery,
+    ) -> ProfileResult<ProfileActivityPage> {
+        let followees: Vec<Uuid> = profile_follow::Entity::find()
+            .filter(profile_follow::Column::TenantId.eq(tenant_id))
+            .filter(profile_follow::Column::FollowerId.eq(viewer_id))
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|follow| follow.followee_id)
+            .collect();
+        let hidden = SocialGraphService::hidden_from_in_tx(&self.db, tenant_id, viewer_id).await?;
+
+        let mut actors = vec![viewer_id];
+        if !followees.is_empty() {
+            actors.extend(
+                profile::Entity::find()
+                    .filter(profile::Column::TenantId.eq(tenant_id))
+                    .filter(profile::Column::UserId.is_in(followees))
+                    .filter(profile::Column::Status.eq(ProfileStatus::Active))
+                    .filter(profile::Column::Visibility.ne(ProfileVisibility::Private))
+                    .all(&self.db)
+                    .await?
+                    .into_iter()
+                    .map(|profile| profile.user_id)
+                    .filter(|user_id| !hidden.contains(user_id)),
+            );
+        }
+
+        self.page(tenant_id, &actors, query).await
+    }
+
+    /// Records an activity once per source event.
+    pub async fn record<C: ConnectionTrait>(
+        conn: &C,
+        tenant_id: Uuid,
+        event_id: Uuid,
+        draft: ActivityDraft,
+    ) -> ProfileResult<()> {
+        let existing = profile_activity::Entity::find()
+            .filter(profile_activity::Column::EventId.eq(event_id))
+            .one(conn)
+            .await?;
+        if existing.is_some() {
+            return Ok(());
+        }
+
+        profile_activity::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tenant_id: Set(tenant_id),
+            actor_id: Set(draft.actor_id),
+            kind: Set(draft.kind.to_string()),
+            subject_id: Set(draft.subject_id),
+            target_id: Set(draft.target_id),
+            channel_slugs: Set(serde_json::json!(draft.channel_slugs)),
+            event_id: Set(event_id),
+            occurred_at: Set(draft.occurred_at.into()),
+        }
+        .insert(conn)
+        .await?;
+        Ok(())
+    }
+
+    /// Removes the activities of a subject, e.g. when a post is unpublished.
+    pub async fn remove_for_subject<C: ConnectionTrait>(
+        conn: &C,
+        tenant_id: Uuid,
+        kind: &str,
+        subject_id: Uuid,
+    ) -> ProfileResult<()> {
+        profile_activity::Entity::delete_many()
+            .filter(profile_activity::Column::TenantId.eq(tenant_id))
+            .filter(profile_activity::Column::Kind.eq(kind))
+            .filter(profile_activity::Column::SubjectId.eq(subject_id))
+            .exec(conn)
+            .await?;
+        Ok(())
+    }
+
+    async fn page(
+        &self,
+        tenant_id: Uuid,
+        actors: &[Uuid],
+        query: ProfileActivityQuery,
+    ) -> ProfileResult<ProfileActivityPage> {
+        let limit = query
+            .limit
+            .unwrap_or(DEFAULT_FEED_LIMIT)
+            .clamp(1, MAX_FEED_LIMIT);
+        let channel_slug = normalize_channel_slug(query.channel_slug.as_deref());
+        let mut position = match query.after {
+            Some(cursor) => {
+                let anchor = profile_activity::Entity::find_by_id(cursor)
+                    .filter(profile_activity::Column::TenantId.eq(tenant_id))
+                    .one(&self.db)
+                    .await?
+                    .ok_or(ProfileError::InvalidCursor(cursor))?;
+                Some((anchor.occurred_at, anchor.id))
+            }
+            None => None,
+        };
+
+        // Channel restrictions are filtered after loading, so keep reading batches until the
+        // page is full and one more visible item proves there is a next page.
+        let batch_size = limit + 1;
+        let mut items = Vec::new();
+        let mut has_more = false;
+        'batches: loop {
+            let mut select = profile_activity::Entity::find()
+                .filter(profile_activity::Column::TenantId.eq(tenant_id))
+                .filter(profile_activity::Column::ActorId.is_in(actors.to_vec()));
+            if let Some((occurred_at, id)) = position {
+                select = select.filter(
+                    Condition::any()
+                        .add(profile_activity::Column::OccurredAt.lt(occurred_at))
+                        .add(
+                            Condition::all()
+                                .add(profile_activity::Column::OccurredAt.eq(occurred_at))
+                                .add(profile_activity::Column::Id.lt(id)),
+                        ),
+                );
+            }
+            let batch = select
+                .order_by_desc(profile_activity::Column::OccurredAt)
+                .order_by_desc(profile_activity::Column::Id)
+                .limit(batch_size)
+                .all(&self.db)
+                .await?;
+            let exhausted = (batch.len() as u64) < batch_size;
+
+            for activity in batch {
+                position = Some((activity.occurred_at, activity.id));
+                if !channel_visible(&activity.channel_slugs, channel_slug.as_deref()) {
+                    continue;
+                }
+                if items.len() as u64 == limit {
+                    has_more = true;
+                    break 'batches;
+                }
+                items.push(map_activity(activity));
+            }
+            if exhausted {
+                break;
+            }
+        }
+
+        let next_cursor = if has_more {
+            items.last().map(|item: &ProfileActivity| item.id)
+        } else {
+            None
+        };
+        Ok(ProfileActivityPage { items, next_cursor })
+    }
+}
+
+/// Activity derived from a domain event, before it is stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActivityDraft {
+    pub actor_id: Uuid,
+    pub kind: &'static str,
+    pub subject_id: Uuid,
+    pub target_id: Option<Uuid>,
+    pub channel_slugs: Vec<String>,
+    pub occurred_at: chrono::DateTime<Utc>,
+}
+
+/// Turns content events into profile activities.
+pub struct ProfileActivityHandler {
+    db: DatabaseConnection,
+}
+
+impl ProfileActivityHandler {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    async fn draft(&self, envelope: &EventEnvelope) -> ProfileResult<Option<ActivityDraft>> {
+        let tenant_id = envelope.tenant_id;
+        let draft = match &envelope.event {
+            DomainEvent::BlogPostPublished { post_id, author_id } => {
+                let Some(actor_id) = author_id.or(envelope.actor_id) else {
+                    return Ok(None);
+                };
+                ActivityDraft {
+                    actor_id,
+                    kind: activity_kinds::BLOG_POST_PUBLISHED,
+                    subject_id: *post_id,
+                    target_id: None,
+                    channel_slugs: channel_slugs(
+                        &self.db,
+                        "blog_post_channel_visibility",
+                        "post_id",
+                        *post_id,
+                    )
+                    .await?,
+                    occurred_at: envelope.timestamp,
+                }
+            }
+            DomainEvent::ForumTopicCreated {
+                topic_id,
+                author_id,
+                ..
+            } => {
+                let Some(actor_id) = *author_id else {
+                    return Ok(None);
+                };
+                ActivityDraft {
+                    actor_id,
+                    kind: activity_kinds::FORUM_TOPIC_CREATED,
+                    subject_id: *topic_id,
+                    target_id: None,
+                    channel_slugs: channel_slugs(
+                        &self.db,
+                        "forum_topic_channel_access",
+                        "topic_id",
+                        *topic_id,
+                    )
+                    .await?,
+                    occurred_at: envelope.timestamp,
+                }
+            }
+            DomainEvent::ForumTopicSolutionMarked {
+                topic_id,
+                reply_id,
+                author_id,
+                ..
+            } => {
+                let Some(actor_id) = *author_id else {
+                    return Ok(None);
+                };
+                ActivityDraft {
+                    actor_id,
+                    kind: activity_kinds::FORUM_SOLUTION_ACCEPTED,
+                    subject_id: *reply_id,
+                    target_id: Some(*topic_id),
+                    channel_slugs: channel_slugs(
+                        &self.db,
+                        "forum_topic_channel_access",
+                        "topic_id",
+                        *topic_id,
+                    )
+                    .await?,
+                    occurred_at: envelope.timestamp,
+                }
+            }
+            DomainEvent::BlogPostUnpublished { post_id }
+            | DomainEvent::BlogPostDeleted { post_id } => {
+                ProfileActivityService::remove_for_subject(
+                    &self.db,
+                    tenant_id,
+                    activity_kinds::BLOG_POST_PUBLISHED,
+                    *post_id,
+                )
+                .await?;
+                return Ok(None);
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(draft))
+    }
+}
+
+#[async_trait]
+impl EventHandler for ProfileActivityHandler {
+    fn name(&self) -> &'static str {
+        "profile_activity"
+    }
+
+    fn handles(&self, event: &DomainEvent) -> bool {
+        matches!(
+            event,
+            DomainEvent::BlogPostPublished { .. }
+                | DomainEvent::BlogPostUnpublished { .. }
+                | DomainEvent::BlogPostDeleted { .. }
+                | DomainEvent::ForumTopicCreated { .. }
+                | DomainEvent::ForumTopicSolutionMarked { .. }
+        )
+    }
+
+    async fn handle(&self, envelope: &EventEnvelope) -> HandlerResult {
+        let map_error = |error: ProfileError| {
+            rustok_core::Error::External(format!("Profile activity recording failed: {error}"))
+        };
+        if let Some(draft) = self.draft(envelope).await.map_err(map_error)? {
+            ProfileActivityService::record(&self.db, envelope.tenant_id, envelope.id, draft)
+                .await
+                .map_err(map_error)?;
+        }
+        Ok(())
+    }
+}
+
+fn visible_to(visibility: ProfileVisibility, authenticated: bool, follows: bool) -> bool {
+    match visibility {
+        ProfileVisibility::Public => true,
+        ProfileVisibility::Authenticated => authenticated,
+        ProfileVisibility::FollowersOnly => follows,
+        ProfileVisibility::Private => false,
+    }
+}
+
+async fn channel_slugs(
+    db: &DatabaseConnection,
+    table: &str,
+    column: &str,
+    id: Uuid,
+) -> ProfileResult<Vec<String>> {
+    let query = Query::select()
+        .column(Alias::new("channel_slug"))
+        .from(Alias::new(table))
+        .and_where(Expr::col(Alias::new(column)).eq(id))
+        .to_owned();
+    let rows = db
+        .query_all(db.get_database_backend().build(&query))
+        .await?;
+    let mut slugs = rows
+        .into_iter()
+        .map(|row| row.try_get::<String>("", "channel_slug"))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .filter_map(|slug| normalize_channel_slug(Some(slug.as_str())))
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+    slugs.sort();
+    Ok(slugs)
+}
+
+fn channel_visible(channel_slugs: &serde_json::Value, channel_slug: Option<&str>) -> bool {
+    let restricted: Vec<&str> = channel_slugs
+        .as_array()
+        .map(|items| items.iter().filter_map(|item| item.as_str()).collect())
+        .unwrap_or_default();
+    if restricted.is_empty() {
+        return true;
+    }
+    channel_slug.is_some_and(|channel_slug| restricted.contains(&channel_slug))
+}
+
+fn normalize_channel_slug(channel_slug: Option<&str>) -> Option<String> {
+    channel_slug
+        .map(str::trim)
+        .filter(|slug| !slug.is_empty())
+        .map(|slug| slug.to_ascii_lowercase())
+}
+
+fn map_activity(activity: profile_activity::Model) -> ProfileActivity {
+    ProfileActivity {
+        id: activity.id,
+        actor_id: activity.actor_id,
+        kind: activity.kind,
+        subject_id: activity.subject_id,
+        target_id: activity.target_id,
+        occurred_at: activity.occurred_at.with_timezone(&Utc),
+    }
+}
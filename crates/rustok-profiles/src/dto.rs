@@ -71,3 +71,96 @@ pub struct UpsertProfileInput {
     pub preferred_locale: Option<String>,
     pub visibility: ProfileVisibility,
 }
+
+/// Badge shown on a profile; awarded by the module named in `source`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileBadge {
+    pub source: String,
+    pub key: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub icon: Option<String>,
+    pub awarded_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AwardProfileBadgeInput {
+    pub source: String,
+    pub key: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub icon: Option<String>,
+}
+
+/// How a user shields themselves from another user.
+///
+/// `Block` also removes follows in both directions and stops the blocked user from following,
+/// replying to or commenting on the blocker's content. `Mute` only hides the muted user's
+/// activity and mentions from the muting user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+#[serde(rename_all = "snake_case")]
+pub enum ProfileBlockKind {
+    #[sea_orm(string_value = "block")]
+    Block,
+    #[sea_orm(string_value = "mute")]
+    Mute,
+}
+
+impl std::fmt::Display for ProfileBlockKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Block => write!(f, "block"),
+            Self::Mute => write!(f, "mute"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileBlock {
+    pub target_user_id: Uuid,
+    pub kind: ProfileBlockKind,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileFollowCounts {
+    pub followers: u64,
+    pub following: u64,
+}
+
+/// Activity kinds recorded from domain events.
+pub mod activity_kinds {
+    pub const BLOG_POST_PUBLISHED: &str = "blog_post_published";
+    pub const FORUM_TOPIC_CREATED: &str = "forum_topic_created";
+    pub const FORUM_SOLUTION_ACCEPTED: &str = "forum_solution_accepted";
+}
+
+/// One entry of a user's activity feed.
+///
+/// `subject_id` is the post, topic or reply the activity is about; `target_id` is the enclosing
+/// topic for solutions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileActivity {
+    pub id: Uuid,
+    pub actor_id: Uuid,
+    pub kind: String,
+    pub subject_id: Uuid,
+    pub target_id: Option<Uuid>,
+    pub occurred_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileActivityQuery {
+    /// Channel of the current request; activities restricted to other channels are skipped.
+    pub channel_slug: Option<String>,
+    /// `next_cursor` of the previous page.
+    pub after: Option<Uuid>,
+    pub limit: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileActivityPage {
+    pub items: Vec<ProfileActivity>,
+    pub next_cursor: Option<Uuid>,
+}
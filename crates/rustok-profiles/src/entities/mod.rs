@@ -2,6 +2,10 @@ use crate::dto::{ProfileStatus, ProfileVisibility};
 use uuid::Uuid;
 
 pub mod profile;
+pub mod profile_activity;
+pub mod profile_badge;
+pub mod profile_block;
+pub mod profile_follow;
 pub mod profile_tag;
 pub mod profile_translation;
 
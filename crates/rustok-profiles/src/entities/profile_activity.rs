@@ -0,0 +1,23 @@
+use sea_orm::entity::prelude::*;
+use uuid::Uuid;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "profile_activities")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub actor_id: Uuid,
+    pub kind: String,
+    pub subject_id: Uuid,
+    pub target_id: Option<Uuid>,
+    /// Channels the underlying content is restricted to; empty means every channel.
+    pub channel_slugs: Json,
+    pub event_id: Uuid,
+    pub occurred_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
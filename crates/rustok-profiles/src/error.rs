@@ -28,6 +28,12 @@ pub enum ProfileError {
     ProfileByHandleNotFound(String),
     #[error("profile handle already exists: {0}")]
     DuplicateHandle(String),
+    #[error("users cannot follow or block themselves")]
+    SelfRelation,
+    #[error("interaction with user {0} is blocked")]
+    RelationBlocked(Uuid),
+    #[error("activity feed cursor {0} is unknown")]
+    InvalidCursor(Uuid),
     #[error("profile validation failed: {0}")]
     Validation(String),
     #[error(transparent)]
@@ -46,6 +52,9 @@ impl From<rustok_taxonomy::TaxonomyError> for ProfileError {
             rustok_taxonomy::TaxonomyError::TermNotFound(term_id) => {
                 Self::Validation(format!("taxonomy term not found: {term_id}"))
             }
+            error @ rustok_taxonomy::TaxonomyError::HierarchyCycle(_) => {
+                Self::Validation(error.to_string())
+            }
         }
     }
 }
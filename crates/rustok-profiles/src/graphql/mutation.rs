@@ -7,7 +7,7 @@ use rustok_events::DomainEvent;
 use rustok_outbox::TransactionalEventBus;
 use sea_orm::DatabaseConnection;
 
-use crate::{ProfileError, ProfileService};
+use crate::{ProfileError, ProfileService, SocialGraphService};
 
 use super::{types::*, MODULE_SLUG};
 
@@ -168,6 +168,61 @@ impl ProfilesMutation {
 
         Ok(profile.into())
     }
+
+    /// Returns `true` when the follow is new.
+    async fn follow_profile(&self, ctx: &Context<'_>, user_id: uuid::Uuid) -> Result<bool> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let auth = require_auth(ctx)?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let tenant = ctx.data::<TenantContext>()?;
+
+        SocialGraphService::new(db.clone())
+            .follow(tenant.id, auth.user_id, user_id)
+            .await
+            .map_err(map_profile_error)
+    }
+
+    async fn unfollow_profile(&self, ctx: &Context<'_>, user_id: uuid::Uuid) -> Result<bool> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let auth = require_auth(ctx)?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let tenant = ctx.data::<TenantContext>()?;
+
+        SocialGraphService::new(db.clone())
+            .unfollow(tenant.id, auth.user_id, user_id)
+            .await
+            .map_err(map_profile_error)
+    }
+
+    async fn block_profile(
+        &self,
+        ctx: &Context<'_>,
+        user_id: uuid::Uuid,
+        kind: GqlProfileBlockKind,
+    ) -> Result<bool> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let auth = require_auth(ctx)?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let tenant = ctx.data::<TenantContext>()?;
+
+        SocialGraphService::new(db.clone())
+            .block(tenant.id, auth.user_id, user_id, kind.into())
+            .await
+            .map_err(map_profile_error)?;
+        Ok(true)
+    }
+
+    async fn unblock_profile(&self, ctx: &Context<'_>, user_id: uuid::Uuid) -> Result<bool> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let auth = require_auth(ctx)?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let tenant = ctx.data::<TenantContext>()?;
+
+        SocialGraphService::new(db.clone())
+            .unblock(tenant.id, auth.user_id, user_id)
+            .await
+            .map_err(map_profile_error)
+    }
 }
 
 fn require_auth(ctx: &Context<'_>) -> Result<AuthContext> {
@@ -207,12 +262,17 @@ fn map_profile_error(err: ProfileError) -> async_graphql::Error {
         | ProfileError::ReservedHandle(_)
         | ProfileError::InvalidLocale(_)
         | ProfileError::Validation(_)
+        | ProfileError::SelfRelation
+        | ProfileError::InvalidCursor(_)
         | ProfileError::DuplicateHandle(_) => {
             <FieldError as GraphQLError>::bad_user_input(&err.to_string())
         }
         ProfileError::ProfileNotFound(_) | ProfileError::ProfileByHandleNotFound(_) => {
             <FieldError as GraphQLError>::not_found(&err.to_string())
         }
+        ProfileError::RelationBlocked(_) => {
+            <FieldError as GraphQLError>::permission_denied(&err.to_string())
+        }
         ProfileError::Database(_) => <FieldError as GraphQLError>::internal_error(&err.to_string()),
     }
 }
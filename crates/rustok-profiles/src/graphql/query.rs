@@ -1,12 +1,15 @@
 use async_graphql::{dataloader::DataLoader, Context, FieldError, Object, Result};
 use rustok_api::{
     graphql::{require_module_enabled, resolve_graphql_locale, GraphQLError},
-    AuthContext, TenantContext,
+    AuthContext, RequestContext, TenantContext,
 };
 use sea_orm::DatabaseConnection;
 use uuid::Uuid;
 
-use crate::{ProfileError, ProfileService, ProfileSummaryLoader, ProfileSummaryLoaderKey};
+use crate::{
+    ProfileActivityQuery, ProfileActivityService, ProfileError, ProfileService,
+    ProfileSummaryLoader, ProfileSummaryLoaderKey, SocialGraphService,
+};
 
 use super::{types::*, MODULE_SLUG};
 
@@ -111,6 +114,89 @@ impl ProfilesQuery {
             Err(err) => Err(map_profile_error(err)),
         }
     }
+
+    /// Activity of one profile, filtered by its visibility and the request channel.
+    async fn profile_activity(
+        &self,
+        ctx: &Context<'_>,
+        user_id: Uuid,
+        after: Option<Uuid>,
+        first: Option<u64>,
+    ) -> Result<GqlProfileActivityPage> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let tenant = ctx.data::<TenantContext>()?;
+        let viewer_id = ctx.data_opt::<AuthContext>().map(|auth| auth.user_id);
+
+        let page = ProfileActivityService::new(db.clone())
+            .profile_feed(
+                tenant.id,
+                viewer_id,
+                user_id,
+                activity_query(ctx, after, first),
+            )
+            .await
+            .map_err(map_profile_error)?;
+        Ok(page.into())
+    }
+
+    /// Activity of the current user and the profiles they follow.
+    async fn my_activity_feed(
+        &self,
+        ctx: &Context<'_>,
+        after: Option<Uuid>,
+        first: Option<u64>,
+    ) -> Result<GqlProfileActivityPage> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let auth = require_auth(ctx)?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let tenant = ctx.data::<TenantContext>()?;
+
+        let page = ProfileActivityService::new(db.clone())
+            .home_feed(tenant.id, auth.user_id, activity_query(ctx, after, first))
+            .await
+            .map_err(map_profile_error)?;
+        Ok(page.into())
+    }
+
+    async fn my_profile_blocks(&self, ctx: &Context<'_>) -> Result<Vec<GqlProfileBlock>> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let auth = require_auth(ctx)?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let tenant = ctx.data::<TenantContext>()?;
+
+        let blocks = SocialGraphService::new(db.clone())
+            .list_blocks(tenant.id, auth.user_id)
+            .await
+            .map_err(map_profile_error)?;
+        Ok(blocks.into_iter().map(Into::into).collect())
+    }
+
+    async fn is_following_profile(&self, ctx: &Context<'_>, user_id: Uuid) -> Result<bool> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let auth = require_auth(ctx)?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let tenant = ctx.data::<TenantContext>()?;
+
+        SocialGraphService::new(db.clone())
+            .is_following(tenant.id, auth.user_id, user_id)
+            .await
+            .map_err(map_profile_error)
+    }
+}
+
+fn activity_query(
+    ctx: &Context<'_>,
+    after: Option<Uuid>,
+    first: Option<u64>,
+) -> ProfileActivityQuery {
+    ProfileActivityQuery {
+        channel_slug: ctx
+            .data_opt::<RequestContext>()
+            .and_then(|request| request.channel_slug.clone()),
+        after,
+        limit: first,
+    }
 }
 
 fn require_auth(ctx: &Context<'_>) -> Result<AuthContext> {
@@ -130,12 +216,17 @@ fn map_profile_error(err: ProfileError) -> async_graphql::Error {
         | ProfileError::ReservedHandle(_)
         | ProfileError::InvalidLocale(_)
         | ProfileError::Validation(_)
+        | ProfileError::SelfRelation
+        | ProfileError::InvalidCursor(_)
         | ProfileError::DuplicateHandle(_) => {
             <FieldError as GraphQLError>::bad_user_input(&err.to_string())
         }
         ProfileError::ProfileNotFound(_) | ProfileError::ProfileByHandleNotFound(_) => {
             <FieldError as GraphQLError>::not_found(&err.to_string())
         }
+        ProfileError::RelationBlocked(_) => {
+            <FieldError as GraphQLError>::permission_denied(&err.to_string())
+        }
         ProfileError::Database(_) => <FieldError as GraphQLError>::internal_error(&err.to_string()),
     }
 }
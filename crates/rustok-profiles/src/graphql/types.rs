@@ -1,7 +1,12 @@
-use async_graphql::{Enum, InputObject, SimpleObject};
+use async_graphql::{ComplexObject, Context, Enum, InputObject, Result, SimpleObject};
+use sea_orm::DatabaseConnection;
 use uuid::Uuid;
 
-use crate::{ProfileRecord, ProfileStatus, ProfileSummary, ProfileVisibility, UpsertProfileInput};
+use crate::{
+    ProfileActivity, ProfileActivityPage, ProfileBadge, ProfileBlock, ProfileBlockKind,
+    ProfileRecord, ProfileService, ProfileStatus, ProfileSummary, ProfileVisibility,
+    SocialGraphService, UpsertProfileInput,
+};
 
 #[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
 pub enum GqlProfileVisibility {
@@ -51,6 +56,7 @@ impl From<ProfileStatus> for GqlProfileStatus {
 }
 
 #[derive(SimpleObject, Debug, Clone)]
+#[graphql(complex)]
 pub struct GqlProfile {
     pub tenant_id: Uuid,
     pub user_id: Uuid,
@@ -83,6 +89,59 @@ impl From<ProfileRecord> for GqlProfile {
     }
 }
 
+#[ComplexObject]
+impl GqlProfile {
+    async fn badges(&self, ctx: &Context<'_>) -> Result<Vec<GqlProfileBadge>> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        let badges = ProfileService::new(db.clone())
+            .list_badges(self.tenant_id, self.user_id)
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))?;
+        Ok(badges.into_iter().map(Into::into).collect())
+    }
+
+    async fn followers_count(&self, ctx: &Context<'_>) -> Result<u64> {
+        Ok(self.follow_counts(ctx).await?.followers)
+    }
+
+    async fn following_count(&self, ctx: &Context<'_>) -> Result<u64> {
+        Ok(self.follow_counts(ctx).await?.following)
+    }
+}
+
+impl GqlProfile {
+    async fn follow_counts(&self, ctx: &Context<'_>) -> Result<crate::ProfileFollowCounts> {
+        let db = ctx.data::<DatabaseConnection>()?;
+        SocialGraphService::new(db.clone())
+            .follow_counts(self.tenant_id, self.user_id)
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))
+    }
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct GqlProfileBadge {
+    pub source: String,
+    pub key: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub icon: Option<String>,
+    pub awarded_at: String,
+}
+
+impl From<ProfileBadge> for GqlProfileBadge {
+    fn from(value: ProfileBadge) -> Self {
+        Self {
+            source: value.source,
+            key: value.key,
+            name: value.name,
+            description: value.description,
+            icon: value.icon,
+            awarded_at: value.awarded_at.to_rfc3339(),
+        }
+    }
+}
+
 #[derive(SimpleObject, Debug, Clone)]
 pub struct GqlProfileSummary {
     pub user_id: Uuid,
@@ -146,3 +205,82 @@ pub struct GqlUpdateMyProfileMediaInput {
     pub avatar_media_id: Option<Uuid>,
     pub banner_media_id: Option<Uuid>,
 }
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum GqlProfileBlockKind {
+    Block,
+    Mute,
+}
+
+impl From<ProfileBlockKind> for GqlProfileBlockKind {
+    fn from(value: ProfileBlockKind) -> Self {
+        match value {
+            ProfileBlockKind::Block => Self::Block,
+            ProfileBlockKind::Mute => Self::Mute,
+        }
+    }
+}
+
+impl From<GqlProfileBlockKind> for ProfileBlockKind {
+    fn from(value: GqlProfileBlockKind) -> Self {
+        match value {
+            GqlProfileBlockKind::Block => Self::Block,
+            GqlProfileBlockKind::Mute => Self::Mute,
+        }
+    }
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct GqlProfileBlock {
+    pub target_user_id: Uuid,
+    pub kind: GqlProfileBlockKind,
+    pub created_at: String,
+}
+
+impl From<ProfileBlock> for GqlProfileBlock {
+    fn from(value: ProfileBlock) -> Self {
+        Self {
+            target_user_id: value.target_user_id,
+            kind: value.kind.into(),
+            created_at: value.created_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct GqlProfileActivity {
+    pub id: Uuid,
+    pub actor_id: Uuid,
+    pub kind: String,
+    pub subject_id: Uuid,
+    pub target_id: Option<Uuid>,
+    pub occurred_at: String,
+}
+
+impl From<ProfileActivity> for GqlProfileActivity {
+    fn from(value: ProfileActivity) -> Self {
+        Self {
+            id: value.id,
+            actor_id: value.actor_id,
+            kind: value.kind,
+            subject_id: value.subject_id,
+            target_id: value.target_id,
+            occurred_at: value.occurred_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(SimpleObject, Debug, Clone)]
+pub struct GqlProfileActivityPage {
+    pub items: Vec<GqlProfileActivity>,
+    pub next_cursor: Option<Uuid>,
+}
+
+impl From<ProfileActivityPage> for GqlProfileActivityPage {
+    fn from(value: ProfileActivityPage) -> Self {
+        Self {
+            items: value.items.into_iter().map(Into::into).collect(),
+            next_cursor: value.next_cursor,
+        }
+    }
+}
@@ -1,23 +1,36 @@
 use async_trait::async_trait;
 use rustok_core::permissions::Permission;
-use rustok_core::{MigrationSource, RusToKModule};
+use rustok_core::{
+    MigrationSource, ModuleEventListenerContext, ModuleEventListenerRegistry,
+    ModuleRuntimeExtensions, RusToKModule,
+};
+use rustok_media::register_media_usage_provider;
 use sea_orm_migration::MigrationTrait;
 
+pub mod activity;
 pub mod dto;
 pub mod entities;
 pub mod error;
 pub mod graphql;
 pub mod loader;
+mod media_usage;
 pub mod migrations;
 pub mod reader;
 pub mod services;
-
-pub use dto::{ProfileStatus, ProfileSummary, ProfileVisibility, UpsertProfileInput};
+pub mod social;
+
+pub use activity::{ActivityDraft, ProfileActivityHandler, ProfileActivityService};
+pub use dto::{
+    activity_kinds, AwardProfileBadgeInput, ProfileActivity, ProfileActivityPage,
+    ProfileActivityQuery, ProfileBadge, ProfileBlock, ProfileBlockKind, ProfileFollowCounts,
+    ProfileStatus, ProfileSummary, ProfileVisibility, UpsertProfileInput,
+};
 pub use entities::ProfileRecord;
 pub use error::{ProfileError, ProfileResult};
 pub use loader::{ProfileSummaryLoader, ProfileSummaryLoaderKey};
 pub use reader::ProfilesReader;
 pub use services::{ProfileBackfillResult, ProfileService};
+pub use social::SocialGraphService;
 
 pub struct ProfilesModule;
 
@@ -53,6 +66,19 @@ impl RusToKModule for ProfilesModule {
             Permission::PROFILES_MANAGE,
         ]
     }
+
+    fn register_event_listeners(
+        &self,
+        registry: &mut ModuleEventListenerRegistry,
+        ctx: &ModuleEventListenerContext<'_>,
+    ) {
+        registry.register(ProfileActivityHandler::new(ctx.db.clone()));
+    }
+
+    fn register_runtime_extensions(&self, extensions: &mut ModuleRuntimeExtensions) {
+        register_media_usage_provider(extensions, media_usage::ProfileMediaUsageProvider)
+            .expect("profile media usage registration should remain unique");
+    }
 }
 
 impl MigrationSource for ProfilesModule {
@@ -0,0 +1,55 @@
+use anyhow::Result as AnyResult;
+use async_trait::async_trait;
+use rustok_media::{MediaReference, MediaUsageProvider};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use uuid::Uuid;
+
+use crate::entities::profile;
+
+/// Reports profile avatars and banners.
+#[derive(Clone, Default)]
+pub struct ProfileMediaUsageProvider;
+
+#[async_trait]
+impl MediaUsageProvider for ProfileMediaUsageProvider {
+    fn owner_kind(&self) -> &'static str {
+        "profile"
+    }
+
+    fn owner_module_slug(&self) -> &'static str {
+        "profiles"
+    }
+
+    async fn collect_references(
+        &self,
+        db: &DatabaseConnection,
+        tenant_id: Uuid,
+    ) -> AnyResult<Vec<MediaReference>> {
+        let profiles = profile::Entity::find()
+            .filter(profile::Column::TenantId.eq(tenant_id))
+            .filter(
+                profile::Column::AvatarMediaId
+                    .is_not_null()
+                    .or(profile::Column::BannerMediaId.is_not_null()),
+            )
+            .all(db)
+            .await?;
+
+        let mut references = Vec::new();
+        for profile in profiles {
+            let fields = [
+                ("avatar_media_id", profile.avatar_media_id),
+                ("banner_media_id", profile.banner_media_id),
+            ];
+            for (field, media_id) in fields {
+                if let Some(media_id) = media_id {
+                    references.push(
+                        MediaReference::id(profile.user_id, field, media_id)
+                            .with_label(Some(profile.display_name.clone())),
+                    );
+                }
+            }
+        }
+        Ok(references)
+    }
+}
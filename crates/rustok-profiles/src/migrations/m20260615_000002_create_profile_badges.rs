@@ -0,0 +1,85 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProfileBadges::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ProfileBadges::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ProfileBadges::TenantId).uuid().not_null())
+                    .col(ColumnDef::new(ProfileBadges::UserId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(ProfileBadges::Source)
+                            .string_len(32)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ProfileBadges::BadgeKey)
+                            .string_len(64)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ProfileBadges::Name)
+                            .string_len(128)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ProfileBadges::Description).text())
+                    .col(ColumnDef::new(ProfileBadges::Icon).string_len(64))
+                    .col(
+                        ColumnDef::new(ProfileBadges::AwardedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_profile_badges_user_badge")
+                    .table(ProfileBadges::Table)
+                    .col(ProfileBadges::TenantId)
+                    .col(ProfileBadges::UserId)
+                    .col(ProfileBadges::Source)
+                    .col(ProfileBadges::BadgeKey)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ProfileBadges::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ProfileBadges {
+    Table,
+    Id,
+    TenantId,
+    UserId,
+    Source,
+    BadgeKey,
+    Name,
+    Description,
+    Icon,
+    AwardedAt,
+}
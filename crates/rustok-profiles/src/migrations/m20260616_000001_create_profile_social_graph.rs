@@ -0,0 +1,240 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProfileFollows::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ProfileFollows::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ProfileFollows::TenantId).uuid().not_null())
+                    .col(ColumnDef::new(ProfileFollows::FollowerId).uuid().not_null())
+                    .col(ColumnDef::new(ProfileFollows::FolloweeId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(ProfileFollows::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_profile_follows_pair")
+                    .table(ProfileFollows::Table)
+                    .col(ProfileFollows::TenantId)
+                    .col(ProfileFollows::FollowerId)
+                    .col(ProfileFollows::FolloweeId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_profile_follows_followee")
+                    .table(ProfileFollows::Table)
+                    .col(ProfileFollows::TenantId)
+                    .col(ProfileFollows::FolloweeId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProfileBlocks::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ProfileBlocks::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ProfileBlocks::TenantId).uuid().not_null())
+                    .col(ColumnDef::new(ProfileBlocks::UserId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(ProfileBlocks::TargetUserId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ProfileBlocks::Kind)
+                            .string_len(16)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ProfileBlocks::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_profile_blocks_pair")
+                    .table(ProfileBlocks::Table)
+                    .col(ProfileBlocks::TenantId)
+                    .col(ProfileBlocks::UserId)
+                    .col(ProfileBlocks::TargetUserId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_profile_blocks_target")
+                    .table(ProfileBlocks::Table)
+                    .col(ProfileBlocks::TenantId)
+                    .col(ProfileBlocks::TargetUserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProfileActivities::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ProfileActivities::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ProfileActivities::TenantId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ProfileActivities::ActorId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(ProfileActivities::Kind)
+                            .string_len(64)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ProfileActivities::SubjectId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ProfileActivities::TargetId).uuid())
+                    .col(
+                        ColumnDef::new(ProfileActivities::ChannelSlugs)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ProfileActivities::EventId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(ProfileActivities::OccurredAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_profile_activities_event")
+                    .table(ProfileActivities::Table)
+                    .col(ProfileActivities::EventId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_profile_activities_actor")
+                    .table(ProfileActivities::Table)
+                    .col(ProfileActivities::TenantId)
+                    .col(ProfileActivities::ActorId)
+                    .col(ProfileActivities::OccurredAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_profile_activities_subject")
+                    .table(ProfileActivities::Table)
+                    .col(ProfileActivities::TenantId)
+                    .col(ProfileActivities::SubjectId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ProfileActivities::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(ProfileBlocks::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(ProfileFollows::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ProfileFollows {
+    Table,
+    Id,
+    TenantId,
+    FollowerId,
+    FolloweeId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum ProfileBlocks {
+    Table,
+    Id,
+    TenantId,
+    UserId,
+    TargetUserId,
+    Kind,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum ProfileActivities {
+    Table,
+    Id,
+    TenantId,
+    ActorId,
+    Kind,
+    SubjectId,
+    TargetId,
+    ChannelSlugs,
+    EventId,
+    OccurredAt,
+}
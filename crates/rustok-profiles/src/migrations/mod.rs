@@ -1,5 +1,7 @@
 mod m20260326_000001_create_profiles_tables;
 mod m20260330_000002_create_profile_tags;
+mod m20260615_000002_create_profile_badges;
+mod m20260616_000001_create_profile_social_graph;
 
 use sea_orm_migration::MigrationTrait;
 
@@ -7,5 +9,7 @@ pub fn migrations() -> Vec<Box<dyn MigrationTrait>> {
     vec![
         Box::new(m20260326_000001_create_profiles_tables::Migration),
         Box::new(m20260330_000002_create_profile_tags::Migration),
+        Box::new(m20260615_000002_create_profile_badges::Migration),
+        Box::new(m20260616_000001_create_profile_social_graph::Migration),
     ]
 }
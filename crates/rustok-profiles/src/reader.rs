@@ -30,6 +30,12 @@ pub trait ProfilesReader: Send + Sync {
         requested_locale: Option<&str>,
         tenant_default_locale: Option<&str>,
     ) -> ProfileResult<ProfileRecord>;
+
+    async fn resolve_handles(
+        &self,
+        tenant_id: Uuid,
+        handles: &[String],
+    ) -> ProfileResult<HashMap<String, Uuid>>;
 }
 
 #[async_trait]
@@ -88,4 +94,12 @@ impl ProfilesReader for ProfileService {
         )
         .await
     }
+
+    async fn resolve_handles(
+        &self,
+        tenant_id: Uuid,
+        handles: &[String],
+    ) -> ProfileResult<HashMap<String, Uuid>> {
+        ProfileService::resolve_handles(self, tenant_id, handles).await
+    }
 }
@@ -9,7 +9,10 @@ use std::collections::HashMap;
 use tracing::instrument;
 use uuid::Uuid;
 
-use crate::dto::{ProfileStatus, ProfileSummary, ProfileVisibility, UpsertProfileInput};
+use crate::dto::{
+    AwardProfileBadgeInput, ProfileBadge, ProfileStatus, ProfileSummary, ProfileVisibility,
+    UpsertProfileInput,
+};
 use crate::entities::{self, ProfileRecord};
 use crate::error::{ProfileError, ProfileResult};
 
@@ -431,6 +434,103 @@ impl ProfileService {
         ))
     }
 
+    /// Maps mention handles to the users owning active profiles.
+    ///
+    /// Invalid or unknown handles are skipped; keys are normalized handles.
+    pub async fn resolve_handles(
+        &self,
+        tenant_id: Uuid,
+        handles: &[String],
+    ) -> ProfileResult<HashMap<String, Uuid>> {
+        let normalized: Vec<String> = handles
+            .iter()
+            .filter_map(|handle| Self::normalize_handle(handle).ok())
+            .collect();
+        if normalized.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        Ok(entities::profile::Entity::find()
+            .filter(entities::profile::Column::TenantId.eq(tenant_id))
+            .filter(entities::profile::Column::Handle.is_in(normalized))
+            .filter(entities::profile::Column::Status.eq(ProfileStatus::Active))
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|profile| (profile.handle, profile.user_id))
+            .collect())
+    }
+
+    /// Awards a badge to a user unless they already hold it.
+    ///
+    /// Returns `true` when the badge was newly awarded. Badges do not require an existing profile
+    /// row, so they show up once the user creates one.
+    pub async fn award_badge_in_tx<C: ConnectionTrait>(
+        conn: &C,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        input: AwardProfileBadgeInput,
+    ) -> ProfileResult<bool> {
+        let source = input.source.trim();
+        let key = input.key.trim();
+        let name = input.name.trim();
+        if source.is_empty() || key.is_empty() || name.is_empty() {
+            return Err(ProfileError::Validation(
+                "badge source, key and name must not be empty".to_string(),
+            ));
+        }
+
+        let existing = entities::profile_badge::Entity::find()
+            .filter(entities::profile_badge::Column::TenantId.eq(tenant_id))
+            .filter(entities::profile_badge::Column::UserId.eq(user_id))
+            .filter(entities::profile_badge::Column::Source.eq(source))
+            .filter(entities::profile_badge::Column::BadgeKey.eq(key))
+            .one(conn)
+            .await?;
+        if existing.is_some() {
+            return Ok(false);
+        }
+
+        entities::profile_badge::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tenant_id: Set(tenant_id),
+            user_id: Set(user_id),
+            source: Set(source.to_string()),
+            badge_key: Set(key.to_string()),
+            name: Set(name.to_string()),
+            description: Set(input.description),
+            icon: Set(input.icon),
+            awarded_at: Set(Utc::now().into()),
+        }
+        .insert(conn)
+        .await?;
+        Ok(true)
+    }
+
+    /// Badges held by a user, oldest award first.
+    pub async fn list_badges(
+        &self,
+        tenant_id: Uuid,
+        user_id: Uuid,
+    ) -> ProfileResult<Vec<ProfileBadge>> {
+        Ok(entities::profile_badge::Entity::find()
+            .filter(entities::profile_badge::Column::TenantId.eq(tenant_id))
+            .filter(entities::profile_badge::Column::UserId.eq(user_id))
+            .order_by_asc(entities::profile_badge::Column::AwardedAt)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|badge| ProfileBadge {
+                source: badge.source,
+                key: badge.badge_key,
+                name: badge.name,
+                description: badge.description,
+                icon: badge.icon,
+                awarded_at: badge.awarded_at.with_timezone(&Utc),
+            })
+            .collect())
+    }
+
     pub async fn get_profile_summary(
         &self,
         tenant_id: Uuid,
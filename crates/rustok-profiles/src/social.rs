@@ -0,0 +1,361 @@
+//! Follows, blocks and mutes between users of a tenant.
+
+use std::collections::HashSet;
+
+use chrono::Utc;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait,
+};
+use uuid::Uuid;
+
+use crate::dto::{ProfileBlock, ProfileBlockKind, ProfileFollowCounts};
+use crate::entities::{profile, profile_block, profile_follow};
+use crate::{ProfileError, ProfileResult};
+
+const MAX_FOLLOW_PAGE: u64 = 100;
+
+#[derive(Clone)]
+pub struct SocialGraphService {
+    db: DatabaseConnection,
+}
+
+impl SocialGraphService {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Follows another user's profile. Returns `true` when the follow is new.
+    pub async fn follow(
+        &self,
+        tenant_id: Uuid,
+        follower_id: Uuid,
+        followee_id: Uuid,
+    ) -> ProfileResult<bool> {
+        if follower_id == followee_id {
+            return Err(ProfileError::SelfRelation);
+        }
+        let followee_exists = profile::Entity::find_by_id(followee_id)
+            .filter(profile::Column::TenantId.eq(tenant_id))
+            .one(&self.db)
+            .await?
+            .is_some();
+        if !followee_exists {
+            return Err(ProfileError::ProfileNotFound(followee_id));
+        }
+        if Self::has_blocked_in_tx(&self.db, tenant_id, followee_id, follower_id).await?
+            || Self::has_blocked_in_tx(&self.db, tenant_id, follower_id, followee_id).await?
+        {
+            return Err(ProfileError::RelationBlocked(followee_id));
+        }
+        if self
+            .is_following(tenant_id, follower_id, followee_id)
+            .await?
+        {
+            return Ok(false);
+        }
+
+        profile_follow::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            tenant_id: Set(tenant_id),
+            follower_id: Set(follower_id),
+            followee_id: Set(followee_id),
+            created_at: Set(Utc::now().into()),
+        }
+        .insert(&self.db)
+        .await?;
+        Ok(true)
+    }
+
+    /// Returns `true` when a follow was removed.
+    pub async fn unfollow(
+        &self,
+        tenant_id: Uuid,
+        follower_id: Uuid,
+        followee_id: Uuid,
+    ) -> ProfileResult<bool> {
+        let result = profile_follow::Entity::delete_many()
+            .filter(profile_follow::Column::TenantId.eq(tenant_id))
+            .filter(profile_follow::Column::FollowerId.eq(follower_id))
+            .filter(profile_follow::Column::FolloweeId.eq(followee_id))
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected > 0)
+    }
+
+    pub async fn is_following(
+        &self,
+        tenant_id: Uuid,
+        follower_id: Uuid,
+        followee_id: Uuid,
+    ) -> ProfileResult<bool> {
+        Self::is_following_in_tx(&self.db, tenant_id, follower_id, followee_id).await
+    }
+
+    pub async fn follow_counts(
+        &self,
+        tenant_id: Uuid,
+        user_id: Uuid,
+    ) -> ProfileResult<ProfileFollowCounts> {
+        let followers = profile_follow::Entity::find()
+            .filter(profile_follow::Column::TenantId.eq(tenant_id))
+            .filter(profile_follow::Column::FolloweeId.eq(user_id))
+            .count(&self.db)
+            .await?;
+        let following = profile_follow::Entity::find()
+            .filter(profile_follow::Column::TenantId.eq(tenant_id))
+            .filter(profile_follow::Column::FollowerId.eq(user_id))
+            .count(&self.db)
+            .await?;
+        Ok(ProfileFollowCounts {
+            followers,
+            following,
+        })
+    }
+
+    /// Users following `user_id`, newest first.
+    pub async fn list_followers(
+        &self,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        offset: u64,
+        limit: u64,
+    ) -> ProfileResult<Vec<Uuid>> {
+        Ok(profile_follow::Entity::find()
+            .filter(profile_follow::Column::TenantId.eq(tenant_id))
+            .filter(profile_follow::Column::FolloweeId.eq(user_id))
+            .order_by_desc(profile_follow::Column::CreatedAt)
+            .offset(offset)
+            .limit(limit.clamp(1, MAX_FOLLOW_PAGE))
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|follow| follow.follower_id)
+            .collect())
+    }
+
+    /// Users `user_id` follows, newest first.
+    pub async fn list_following(
+        &self,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        offset: u64,
+        limit: u64,
+    ) -> ProfileResult<Vec<Uuid>> {
+        Ok(profile_follow::Entity::find()
+            .filter(profile_follow::Column::TenantId.eq(tenant_id))
+            .filter(profile_follow::Column::FollowerId.eq(user_id))
+            .order_by_desc(profile_follow::Column::CreatedAt)
+            .offset(offset)
+            .limit(limit.clamp(1, MAX_FOLLOW_PAGE))
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|follow| follow.followee_id)
+            .collect())
+    }
+
+    /// Blocks or mutes another user, replacing any earlier relation to them.
+    ///
+    /// Blocking also drops follows in both directions.
+    pub async fn block(
+        &self,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        target_user_id: Uuid,
+        kind: ProfileBlockKind,
+    ) -> ProfileResult<()> {
+        if user_id == target_user_id {
+            return Err(ProfileError::SelfRelation);
+        }
+
+        let txn = self.db.begin().await?;
+        let existing = profile_block::Entity::find()
+            .filter(profile_block::Column::TenantId.eq(tenant_id))
+            .filter(profile_block::Column::UserId.eq(user_id))
+            .filter(profile_block::Column::TargetUserId.eq(target_user_id))
+            .one(&txn)
+            .await?;
+        match existing {
+            Some(existing) if existing.kind == kind => {}
+            Some(existing) => {
+                let mut active: profile_block::ActiveModel = existing.into();
+                active.kind = Set(kind);
+                active.update(&txn).await?;
+            }
+            None => {
+                profile_block::ActiveModel {
+                    id: Set(Uuid::new_v4()),
+                    tenant_id: Set(tenant_id),
+                    user_id: Set(user_id),
+                    target_user_id: Set(target_user_id),
+                    kind: Set(kind),
+                    created_at: Set(Utc::now().into()),
+                }
+                .insert(&txn)
+                .await?;
+            }
+        }
+
+        if kind == ProfileBlockKind::Block {
+            profile_follow::Entity::delete_many()
+                .filter(profile_follow::Column::TenantId.eq(tenant_id))
+                .filter(
+                    Condition::any()
+                        .add(
+                            Condition::all()
+                                .add(profile_follow::Column::FollowerId.eq(user_id))
+                                .add(profile_follow::Column::FolloweeId.eq(target_user_id)),
+                        )
+                        .add(
+                            Condition::all()
+                                .add(profile_follow::Column::FollowerId.eq(target_user_id))
+                                .add(profile_follow::Column::FolloweeId.eq(user_id)),
+                        ),
+                )
+                .exec(&txn)
+                .await?;
+        }
+        txn.commit().await?;
+        Ok(())
+    }
+
+    /// Removes a block or mute. Returns `true` when one existed.
+    pub async fn unblock(
+        &self,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        target_user_id: Uuid,
+    ) -> ProfileResult<bool> {
+        let result = profile_block::Entity::delete_many()
+            .filter(profile_block::Column::TenantId.eq(tenant_id))
+            .filter(profile_block::Column::UserId.eq(user_id))
+            .filter(profile_block::Column::TargetUserId.eq(target_user_id))
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected > 0)
+    }
+
+    /// Users blocked or muted by `user_id`, newest first.
+    pub async fn list_blocks(
+        &self,
+        tenant_id: Uuid,
+        user_id: Uuid,
+    ) -> ProfileResult<Vec<ProfileBlock>> {
+        Ok(profile_block::Entity::find()
+            .filter(profile_block::Column::TenantId.eq(tenant_id))
+            .filter(profile_block::Column::UserId.eq(user_id))
+            .order_by_desc(profile_block::Column::CreatedAt)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|block| ProfileBlock {
+                target_user_id: block.target_user_id,
+                kind: block.kind,
+                created_at: block.created_at.with_timezone(&Utc),
+            })
+            .collect())
+    }
+
+    pub async fn is_following_in_tx<C: ConnectionTrait>(
+        conn: &C,
+        tenant_id: Uuid,
+        follower_id: Uuid,
+        followee_id: Uuid,
+    ) -> ProfileResult<bool> {
+        Ok(profile_follow::Entity::find()
+            .filter(profile_follow::Column::TenantId.eq(tenant_id))
+            .filter(profile_follow::Column::FollowerId.eq(follower_id))
+            .filter(profile_follow::Column::FolloweeId.eq(followee_id))
+            .one(conn)
+            .await?
+            .is_some())
+    }
+
+    /// Whether `user_id` has blocked (not merely muted) `target_user_id`.
+    pub async fn has_blocked_in_tx<C: ConnectionTrait>(
+        conn: &C,
+        tenant_id: Uuid,
+        user_id: Uuid,
+        target_user_id: Uuid,
+    ) -> ProfileResult<bool> {
+        Ok(profile_block::Entity::find()
+            .filter(profile_block::Column::TenantId.eq(tenant_id))
+            .filter(profile_block::Column::UserId.eq(user_id))
+            .filter(profile_block::Column::TargetUserId.eq(target_user_id))
+            .filter(profile_block::Column::Kind.eq(ProfileBlockKind::Block))
+            .one(conn)
+            .await?
+            .is_some())
+    }
+
+    /// Fails with [`ProfileError::RelationBlocked`] when `owner_id` has blocked `actor_id`.
+    ///
+    /// Used by content modules before letting `actor_id` reply to or comment on `owner_id`'s
+    /// content. Anonymous owners or actors are never blocked.
+    pub async fn ensure_not_blocked_in_tx<C: ConnectionTrait>(
+        conn: &C,
+        tenant_id: Uuid,
+        owner_id: Option<Uuid>,
+        actor_id: Option<Uuid>,
+    ) -> ProfileResult<()> {
+        let (Some(owner_id), Some(actor_id)) = (owner_id, actor_id) else {
+            return Ok(());
+        };
+        if owner_id != actor_id
+            && Self::has_blocked_in_tx(conn, tenant_id, owner_id, actor_id).await?
+        {
+            return Err(ProfileError::RelationBlocked(owner_id));
+        }
+        Ok(())
+    }
+
+    /// Users among `candidates` who blocked or muted `author_id`; mentions should not reach them.
+    pub async fn silenced_by_in_tx<C: ConnectionTrait>(
+        conn: &C,
+        tenant_id: Uuid,
+        author_id: Uuid,
+        candidates: &[Uuid],
+    ) -> ProfileResult<HashSet<Uuid>> {
+        if candidates.is_empty() {
+            return Ok(HashSet::new());
+        }
+        Ok(profile_block::Entity::find()
+            .filter(profile_block::Column::TenantId.eq(tenant_id))
+            .filter(profile_block::Column::TargetUserId.eq(author_id))
+            .filter(profile_block::Column::UserId.is_in(candidates.to_vec()))
+            .all(conn)
+            .await?
+            .into_iter()
+            .map(|block| block.user_id)
+            .collect())
+    }
+
+    /// Users `viewer_id` should not see activity from: everyone they blocked or muted and
+    /// everyone who blocked them.
+    pub(crate) async fn hidden_from_in_tx<C: ConnectionTrait>(
+        conn: &C,
+        tenant_id: Uuid,
+        viewer_id: Uuid,
+    ) -> ProfileResult<HashSet<Uuid>> {
+        let mut hidden: HashSet<Uuid> = profile_block::Entity::find()
+            .filter(profile_block::Column::TenantId.eq(tenant_id))
+            .filter(profile_block::Column::UserId.eq(viewer_id))
+            .all(conn)
+            .await?
+            .into_iter()
+            .map(|block| block.target_user_id)
+            .collect();
+        hidden.extend(
+            profile_block::Entity::find()
+                .filter(profile_block::Column::TenantId.eq(tenant_id))
+                .filter(profile_block::Column::TargetUserId.eq(viewer_id))
+                .filter(profile_block::Column::Kind.eq(ProfileBlockKind::Block))
+                .all(conn)
+                .await?
+                .into_iter()
+                .map(|block| block.user_id),
+        );
+        Ok(hidden)
+    }
+}
@@ -1,6 +1,6 @@
 use async_graphql::dataloader::DataLoader;
 use chrono::Utc;
-use rustok_profiles::dto::{ProfileVisibility, UpsertProfileInput};
+use rustok_profiles::dto::{AwardProfileBadgeInput, ProfileVisibility, UpsertProfileInput};
 use rustok_profiles::entities;
 use rustok_profiles::error::ProfileError;
 use rustok_profiles::services::ProfileService;
@@ -110,6 +110,37 @@ async fn get_profile_by_handle_normalizes_lookup() {
     assert_eq!(fetched.handle, "creator-one");
 }
 
+#[tokio::test]
+async fn resolve_handles_skips_unknown_invalid_and_other_tenant_handles() {
+    let service = setup().await;
+    let tenant_id = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+
+    service
+        .upsert_profile(tenant_id, user_id, profile_input(), Some("en"))
+        .await
+        .unwrap();
+    service
+        .upsert_profile(Uuid::new_v4(), Uuid::new_v4(), profile_input(), Some("en"))
+        .await
+        .unwrap();
+
+    let resolved = ProfilesReader::resolve_handles(
+        &service,
+        tenant_id,
+        &[
+            "Creator-One".to_string(),
+            "missing".to_string(),
+            "no spaces".to_string(),
+        ],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(resolved.len(), 1);
+    assert_eq!(resolved.get("creator-one"), Some(&user_id));
+}
+
 #[tokio::test]
 async fn duplicate_handle_is_rejected_per_tenant() {
     let service = setup().await;
@@ -536,3 +567,39 @@ async fn backfill_profile_uses_suffix_and_skips_existing_profile() {
     assert_eq!(repeat.profile.preferred_locale.as_deref(), Some("en"));
     assert_eq!(repeat.profile.visibility, ProfileVisibility::Public);
 }
+
+#[tokio::test]
+async fn badges_are_awarded_once_per_source_and_key() {
+    let (db, service) = setup_with_db().await;
+    let tenant_id = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+    let input = AwardProfileBadgeInput {
+        source: "forum".to_string(),
+        key: "helper".to_string(),
+        name: "Helper".to_string(),
+        description: Some("Solved a topic".to_string()),
+        icon: None,
+    };
+
+    assert!(
+        ProfileService::award_badge_in_tx(&db, tenant_id, user_id, input.clone())
+            .await
+            .unwrap()
+    );
+    assert!(
+        !ProfileService::award_badge_in_tx(&db, tenant_id, user_id, input.clone())
+            .await
+            .unwrap()
+    );
+    assert!(
+        ProfileService::award_badge_in_tx(&db, Uuid::new_v4(), user_id, input)
+            .await
+            .unwrap()
+    );
+
+    let badges = service.list_badges(tenant_id, user_id).await.unwrap();
+    assert_eq!(badges.len(), 1);
+    assert_eq!(badges[0].source, "forum");
+    assert_eq!(badges[0].key, "helper");
+    assert_eq!(badges[0].description.as_deref(), Some("Solved a topic"));
+}
@@ -0,0 +1,254 @@
+use chrono::{Duration, Utc};
+use rustok_core::events::EventHandler;
+use rustok_events::{DomainEvent, EventEnvelope};
+use rustok_profiles::dto::{ProfileVisibility, UpsertProfileInput};
+use rustok_profiles::error::ProfileError;
+use rustok_profiles::{
+    activity_kinds, ProfileActivityHandler, ProfileActivityQuery, ProfileActivityService,
+    ProfileBlockKind, ProfileService, SocialGraphService,
+};
+use sea_orm::{ConnectionTrait, DatabaseConnection};
+use uuid::Uuid;
+
+mod support;
+
+async fn create_profile(db: &DatabaseConnection, tenant_id: Uuid, handle: &str) -> Uuid {
+    let user_id = Uuid::new_v4();
+    ProfileService::new(db.clone())
+        .upsert_profile(
+            tenant_id,
+            user_id,
+            UpsertProfileInput {
+                handle: handle.to_string(),
+                display_name: handle.to_string(),
+                bio: None,
+                tags: vec![],
+                avatar_media_id: None,
+                banner_media_id: None,
+                preferred_locale: None,
+                visibility: ProfileVisibility::Public,
+            },
+            Some("en"),
+        )
+        .await
+        .expect("profile should be created");
+    user_id
+}
+
+fn query(limit: u64, after: Option<Uuid>, channel_slug: Option<&str>) -> ProfileActivityQuery {
+    ProfileActivityQuery {
+        channel_slug: channel_slug.map(str::to_string),
+        after,
+        limit: Some(limit),
+    }
+}
+
+#[tokio::test]
+async fn follows_blocks_and_mutes() {
+    let db = support::setup_profiles_test_db().await;
+    let tenant_id = Uuid::new_v4();
+    let alice = create_profile(&db, tenant_id, "alice").await;
+    let bob = create_profile(&db, tenant_id, "bob").await;
+    let carol = create_profile(&db, tenant_id, "carol").await;
+    let graph = SocialGraphService::new(db.clone());
+
+    assert!(graph.follow(tenant_id, bob, alice).await.unwrap());
+    assert!(!graph.follow(tenant_id, bob, alice).await.unwrap());
+    assert!(graph.follow(tenant_id, alice, bob).await.unwrap());
+    assert!(matches!(
+        graph.follow(tenant_id, alice, alice).await,
+        Err(ProfileError::SelfRelation)
+    ));
+    assert!(matches!(
+        graph.follow(tenant_id, alice, Uuid::new_v4()).await,
+        Err(ProfileError::ProfileNotFound(_))
+    ));
+    let counts = graph.follow_counts(tenant_id, alice).await.unwrap();
+    assert_eq!((counts.followers, counts.following), (1, 1));
+    assert_eq!(
+        graph.list_followers(tenant_id, alice, 0, 10).await.unwrap(),
+        vec![bob]
+    );
+
+    graph
+        .block(tenant_id, alice, bob, ProfileBlockKind::Block)
+        .await
+        .unwrap();
+    assert!(
+        !graph.is_following(tenant_id, bob, alice).await.unwrap(),
+        "blocking drops follows in both directions"
+    );
+    assert!(!graph.is_following(tenant_id, alice, bob).await.unwrap());
+    assert!(matches!(
+        graph.follow(tenant_id, bob, alice).await,
+        Err(ProfileError::RelationBlocked(_))
+    ));
+    assert!(matches!(
+        SocialGraphService::ensure_not_blocked_in_tx(&db, tenant_id, Some(alice), Some(bob)).await,
+        Err(ProfileError::RelationBlocked(_))
+    ));
+    SocialGraphService::ensure_not_blocked_in_tx(&db, tenant_id, Some(bob), Some(alice))
+        .await
+        .expect("the blocker can still interact");
+
+    graph.follow(tenant_id, carol, alice).await.unwrap();
+    graph
+        .block(tenant_id, carol, alice, ProfileBlockKind::Mute)
+        .await
+        .unwrap();
+    assert!(
+        graph.is_following(tenant_id, carol, alice).await.unwrap(),
+        "muting keeps follows"
+    );
+    let silenced = SocialGraphService::silenced_by_in_tx(&db, tenant_id, alice, &[bob, carol])
+        .await
+        .unwrap();
+    assert_eq!(silenced.into_iter().collect::<Vec<_>>(), vec![carol]);
+
+    let blocks = graph.list_blocks(tenant_id, carol).await.unwrap();
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks[0].kind, ProfileBlockKind::Mute);
+    assert!(graph.unblock(tenant_id, alice, bob).await.unwrap());
+    assert!(graph.follow(tenant_id, bob, alice).await.unwrap());
+}
+
+#[tokio::test]
+async fn activity_feed_respects_visibility_channels_and_cursor() {
+    let db = support::setup_profiles_test_db().await;
+    db.execute_unprepared(
+        "CREATE TABLE forum_topic_channel_access (topic_id uuid NOT NULL, channel_slug varchar NOT NULL)",
+    )
+    .await
+    .unwrap();
+    let tenant_id = Uuid::new_v4();
+    let alice = create_profile(&db, tenant_id, "alice").await;
+    let bob = create_profile(&db, tenant_id, "bob").await;
+    let handler = ProfileActivityHandler::new(db.clone());
+    let feed = ProfileActivityService::new(db.clone());
+
+    let restricted_topic = Uuid::new_v4();
+    db.execute(sea_orm::Statement::from_sql_and_values(
+        db.get_database_backend(),
+        "INSERT INTO forum_topic_channel_access (topic_id, channel_slug) VALUES ($1, $2)",
+        [restricted_topic.into(), "members".into()],
+    ))
+    .await
+    .unwrap();
+
+    let start = Utc::now() - Duration::minutes(10);
+    let topics = [Uuid::new_v4(), restricted_topic, Uuid::new_v4()];
+    for (minute, topic_id) in topics.iter().enumerate() {
+        let mut envelope = EventEnvelope::new(
+            tenant_id,
+            Some(alice),
+            DomainEvent::ForumTopicCreated {
+                topic_id: *topic_id,
+                category_id: Uuid::new_v4(),
+                author_id: Some(alice),
+                locale: "en".to_string(),
+            },
+        );
+        envelope.timestamp = start + Duration::minutes(minute as i64);
+        handler.handle(&envelope).await.unwrap();
+        handler
+            .handle(&envelope)
+            .await
+            .expect("redelivered events are ignored");
+    }
+    let mut solution = EventEnvelope::new(
+        tenant_id,
+        Some(bob),
+        DomainEvent::ForumTopicSolutionMarked {
+            topic_id: topics[0],
+            reply_id: Uuid::new_v4(),
+            author_id: Some(alice),
+            moderator_id: Some(bob),
+        },
+    );
+    solution.timestamp = start + Duration::minutes(5);
+    handler.handle(&solution).await.unwrap();
+
+    let first = feed
+        .profile_feed(tenant_id, None, alice, query(2, None, None))
+        .await
+        .unwrap();
+    assert_eq!(first.items.len(), 2);
+    assert_eq!(first.items[0].kind, activity_kinds::FORUM_SOLUTION_ACCEPTED);
+    assert_eq!(first.items[0].target_id, Some(topics[0]));
+    assert_eq!(first.items[1].subject_id, topics[2]);
+    let second = feed
+        .profile_feed(tenant_id, None, alice, query(2, first.next_cursor, None))
+        .await
+        .unwrap();
+    assert_eq!(
+        second
+            .items
+            .iter()
+            .map(|item| item.subject_id)
+            .collect::<Vec<_>>(),
+        vec![topics[0]],
+        "activities restricted to other channels are skipped"
+    );
+    assert_eq!(second.next_cursor, None);
+
+    let members = feed
+        .profile_feed(tenant_id, None, alice, query(10, None, Some("Members")))
+        .await
+        .unwrap();
+    assert_eq!(members.items.len(), 4);
+
+    ProfileService::new(db.clone())
+        .update_profile_visibility(tenant_id, alice, ProfileVisibility::FollowersOnly, None)
+        .await
+        .unwrap();
+    assert!(feed
+        .profile_feed(tenant_id, None, alice, query(10, None, None))
+        .await
+        .unwrap()
+        .items
+        .is_empty());
+    assert!(feed
+        .profile_feed(tenant_id, Some(bob), alice, query(10, None, None))
+        .await
+        .unwrap()
+        .items
+        .is_empty());
+    let graph = SocialGraphService::new(db.clone());
+    graph.follow(tenant_id, bob, alice).await.unwrap();
+    assert_eq!(
+        feed.profile_feed(tenant_id, Some(bob), alice, query(10, None, None))
+            .await
+            .unwrap()
+            .items
+            .len(),
+        3
+    );
+    assert_eq!(
+        feed.home_feed(tenant_id, bob, query(10, None, None))
+            .await
+            .unwrap()
+            .items
+            .len(),
+        3
+    );
+
+    graph
+        .block(tenant_id, bob, alice, ProfileBlockKind::Mute)
+        .await
+        .unwrap();
+    assert!(feed
+        .home_feed(tenant_id, bob, query(10, None, None))
+        .await
+        .unwrap()
+        .items
+        .is_empty());
+    assert_eq!(
+        feed.profile_feed(tenant_id, Some(alice), alice, query(10, None, None))
+            .await
+            .unwrap()
+            .items
+            .len(),
+        3,
+        "owners always see their own timeline"
+    );
+}
@@ -34,6 +34,9 @@ pub use services::relation_permission_resolver::{
     resolve_permissions_with_cache, PermissionCache, RelationPermissionStore,
 };
 pub use services::runtime_permission_resolver::{RoleAssignmentStore, RuntimePermissionResolver};
+pub use services::trust_policy::{
+    check_trust_capability, TrustCapability, TrustLevel, TrustLevelPolicy,
+};
 
 use async_trait::async_trait;
 use rustok_core::module::{HealthStatus, MigrationSource, ModuleKind, RusToKModule};
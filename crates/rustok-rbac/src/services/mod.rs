@@ -9,3 +9,4 @@ pub mod permission_policy;
 pub mod permission_resolver;
 pub mod relation_permission_resolver;
 pub mod runtime_permission_resolver;
+pub mod trust_policy;
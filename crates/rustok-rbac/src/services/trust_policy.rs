@@ -0,0 +1,182 @@
+use rustok_core::Permission;
+use serde::{Deserialize, Serialize};
+
+use crate::services::permission_policy::{check_permission, PermissionCheckOutcome};
+
+/// Reputation-derived trust level of a community member.
+///
+/// Levels are ordered: every capability unlocked at a level stays unlocked at higher levels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrustLevel {
+    New,
+    Basic,
+    Member,
+    Regular,
+    Leader,
+}
+
+impl TrustLevel {
+    pub const ALL: [Self; 5] = [
+        Self::New,
+        Self::Basic,
+        Self::Member,
+        Self::Regular,
+        Self::Leader,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::New => "new",
+            Self::Basic => "basic",
+            Self::Member => "member",
+            Self::Regular => "regular",
+            Self::Leader => "leader",
+        }
+    }
+}
+
+/// Capability that members unlock by trust level instead of by role.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrustCapability {
+    PostLinks,
+    EditOthersWikis,
+    SkipModeration,
+}
+
+impl TrustCapability {
+    /// Role permission that grants the capability regardless of trust level.
+    ///
+    /// Skipping moderation has no bypass: moderated categories hold staff posts too until the
+    /// author has earned the trust level.
+    pub fn bypass_permission(&self) -> Option<Permission> {
+        match self {
+            Self::PostLinks => Some(Permission::FORUM_REPLIES_MODERATE),
+            Self::EditOthersWikis => Some(Permission::FORUM_TOPICS_MODERATE),
+            Self::SkipModeration => None,
+        }
+    }
+}
+
+/// Reputation thresholds for each trust level and the level each capability requires.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TrustLevelPolicy {
+    pub basic_reputation: i32,
+    pub member_reputation: i32,
+    pub regular_reputation: i32,
+    pub leader_reputation: i32,
+    pub post_links: TrustLevel,
+    pub edit_others_wikis: TrustLevel,
+    pub skip_moderation: TrustLevel,
+}
+
+impl Default for TrustLevelPolicy {
+    fn default() -> Self {
+        Self {
+            basic_reputation: 10,
+            member_reputation: 50,
+            regular_reputation: 250,
+            leader_reputation: 1000,
+            post_links: TrustLevel::Basic,
+            edit_others_wikis: TrustLevel::Member,
+            skip_moderation: TrustLevel::Regular,
+        }
+    }
+}
+
+impl TrustLevelPolicy {
+    pub fn level_for(&self, reputation: i32) -> TrustLevel {
+        if reputation >= self.leader_reputation {
+            TrustLevel::Leader
+        } else if reputation >= self.regular_reputation {
+            TrustLevel::Regular
+        } else if reputation >= self.member_reputation {
+            TrustLevel::Member
+        } else if reputation >= self.basic_reputation {
+            TrustLevel::Basic
+        } else {
+            TrustLevel::New
+        }
+    }
+
+    pub fn required_level(&self, capability: TrustCapability) -> TrustLevel {
+        match capability {
+            TrustCapability::PostLinks => self.post_links,
+            TrustCapability::EditOthersWikis => self.edit_others_wikis,
+            TrustCapability::SkipModeration => self.skip_moderation,
+        }
+    }
+}
+
+/// Checks a trust capability: allowed when the member's level is high enough or their role
+/// carries the capability's bypass permission. A denial reports the bypass permission as missing.
+pub fn check_trust_capability(
+    user_permissions: &[Permission],
+    reputation: i32,
+    capability: TrustCapability,
+    policy: &TrustLevelPolicy,
+) -> PermissionCheckOutcome {
+    if policy.level_for(reputation) >= policy.required_level(capability) {
+        return PermissionCheckOutcome {
+            allowed: true,
+            missing_permissions: Vec::new(),
+        };
+    }
+
+    match capability.bypass_permission() {
+        Some(permission) => check_permission(user_permissions, &permission),
+        None => PermissionCheckOutcome {
+            allowed: false,
+            missing_permissions: Vec::new(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels_follow_reputation_thresholds() {
+        let policy = TrustLevelPolicy::default();
+        assert_eq!(policy.level_for(-5), TrustLevel::New);
+        assert_eq!(policy.level_for(10), TrustLevel::Basic);
+        assert_eq!(policy.level_for(249), TrustLevel::Member);
+        assert_eq!(policy.level_for(250), TrustLevel::Regular);
+        assert_eq!(policy.level_for(5000), TrustLevel::Leader);
+    }
+
+    #[test]
+    fn capability_is_granted_by_level_or_bypass_permission() {
+        let policy = TrustLevelPolicy::default();
+        let denied = check_trust_capability(&[], 0, TrustCapability::PostLinks, &policy);
+        assert!(!denied.allowed);
+        assert_eq!(
+            denied.missing_permissions,
+            vec![Permission::FORUM_REPLIES_MODERATE]
+        );
+
+        assert!(check_trust_capability(&[], 10, TrustCapability::PostLinks, &policy).allowed);
+        assert!(!check_trust_capability(&[], 10, TrustCapability::SkipModeration, &policy).allowed);
+        assert!(
+            check_trust_capability(
+                &[Permission::FORUM_REPLIES_MODERATE],
+                0,
+                TrustCapability::PostLinks,
+                &policy,
+            )
+            .allowed
+        );
+        assert!(
+            !check_trust_capability(
+                &[Permission::FORUM_REPLIES_MODERATE],
+                0,
+                TrustCapability::SkipModeration,
+                &policy,
+            )
+            .allowed
+        );
+    }
+}
@@ -5,7 +5,7 @@ use sea_orm::{
 use std::time::{Duration, Instant};
 use uuid::Uuid;
 
-use rustok_core::{Error, Result};
+use rustok_core::{render_content, Error, Result};
 use rustok_telemetry::metrics;
 
 #[derive(Clone)]
@@ -494,8 +494,66 @@ impl SearchProjector {
             "#
         );
 
-        let stmt = Statement::from_sql_and_values(DbBackend::Postgres, sql, values);
+        let stmt = Statement::from_sql_and_values(DbBackend::Postgres, sql, values.clone());
         conn.execute(stmt).await.map_err(Error::Database)?;
+        self.render_content_document_bodies_in(conn, &where_clause, values)
+            .await
+    }
+
+    /// Replace raw stored bodies (markdown, rt_json, GrapesJS project JSON)
+    /// of freshly upserted content documents with their rendered plain text.
+    async fn render_content_document_bodies_in<C>(
+        &self,
+        conn: &C,
+        where_clause: &str,
+        values: Vec<sea_orm::Value>,
+    ) -> Result<()>
+    where
+        C: ConnectionTrait,
+    {
+        let sql = format!(
+            r#"
+            SELECT
+                CONCAT('node:', n.id::text, ':', nt.locale) AS document_key,
+                nt.excerpt,
+                b.body,
+                b.format
+            FROM nodes n
+            JOIN node_translations nt
+                ON nt.node_id = n.id
+            JOIN bodies b
+                ON b.node_id = n.id AND b.locale = nt.locale
+            {where_clause}
+            "#
+        );
+        let rows = conn
+            .query_all(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                sql,
+                values,
+            ))
+            .await
+            .map_err(Error::Database)?;
+
+        for row in rows {
+            let document_key: String = row.try_get("", "document_key").map_err(Error::Database)?;
+            let excerpt: Option<String> = row.try_get("", "excerpt").map_err(Error::Database)?;
+            let body: String = row.try_get("", "body").map_err(Error::Database)?;
+            let format: String = row.try_get("", "format").map_err(Error::Database)?;
+
+            let text = render_content(&format, &body).text;
+            if text == body {
+                continue;
+            }
+            let indexed_body = [excerpt.unwrap_or_default(), text].join("\n\n");
+            conn.execute(Statement::from_sql_and_values(
+                DbBackend::Postgres,
+                "UPDATE search_documents SET body = $1 WHERE document_key = $2",
+                vec![indexed_body.into(), document_key.into()],
+            ))
+            .await
+            .map_err(Error::Database)?;
+        }
         Ok(())
     }
 
@@ -667,6 +667,12 @@ mod tests {
 
     async fn run_forum_migrations(db: &DatabaseConnection) {
         let manager = SchemaManager::new(db);
+        for migration in rustok_content::migrations::migrations() {
+            migration
+                .up(&manager)
+                .await
+                .expect("content migration should apply");
+        }
         for migration in forum_migrations::migrations() {
             migration
                 .up(&manager)
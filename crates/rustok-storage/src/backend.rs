@@ -1,4 +1,6 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 
 use crate::error::Result;
 
@@ -13,6 +15,75 @@ pub struct UploadedObject {
     pub size: u64,
 }
 
+/// Identifies the object, or the part of a multipart upload, a presigned
+/// upload URL writes to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadTarget {
+    pub path: String,
+    /// Multipart upload ID and 1-based part number when targeting a part.
+    pub part: Option<(String, u32)>,
+}
+
+impl UploadTarget {
+    pub fn object(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            part: None,
+        }
+    }
+
+    pub fn part(path: impl Into<String>, upload_id: impl Into<String>, part_number: u32) -> Self {
+        Self {
+            path: path.into(),
+            part: Some((upload_id.into(), part_number)),
+        }
+    }
+}
+
+/// Declared content of a direct upload. Presigned requests are bound to it,
+/// so a URL cannot be reused for a different type or size.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadContent {
+    pub content_type: String,
+    /// Exact number of bytes the request carries.
+    pub content_length: u64,
+}
+
+impl UploadContent {
+    pub fn new(content_type: impl Into<String>, content_length: u64) -> Self {
+        Self {
+            content_type: content_type.into(),
+            content_length,
+        }
+    }
+}
+
+/// A request the client can send to upload bytes without passing them
+/// through the application server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresignedUpload {
+    pub url: String,
+    pub method: String,
+    /// Headers the client must send unchanged for the signature to match.
+    pub headers: Vec<(String, String)>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// A part of a multipart upload that the backend has received.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UploadedPart {
+    /// 1-based part number.
+    pub part_number: u32,
+    pub etag: String,
+    pub size: u64,
+}
+
+/// Metadata of a stored object.
+#[derive(Debug, Clone)]
+pub struct ObjectInfo {
+    pub size: u64,
+}
+
 /// Contract every storage driver must implement.
 #[async_trait]
 pub trait StorageBackend: Send + Sync {
@@ -39,6 +110,59 @@ pub trait StorageBackend: Send + Sync {
         expires_in: std::time::Duration,
     ) -> Result<Option<String>>;
 
+    /// Size and existence check without reading the object.
+    async fn head(&self, path: &str) -> Result<ObjectInfo>;
+
+    /// Read up to `length` bytes starting at `offset`.
+    async fn read_range(&self, path: &str, offset: u64, length: u64) -> Result<bytes::Bytes>;
+
+    /// Build a direct upload request for `target`, or `None` when the backend
+    /// is not configured for direct uploads.
+    async fn presigned_upload(
+        &self,
+        target: &UploadTarget,
+        content: &UploadContent,
+        expires_in: std::time::Duration,
+    ) -> Result<Option<PresignedUpload>>;
+
+    /// Check a signature produced by [`StorageBackend::presigned_upload`] for
+    /// backends whose direct uploads are received by the application itself.
+    /// Backends that verify their own signatures return
+    /// [`crate::StorageError::Unsupported`].
+    fn verify_presigned_upload(
+        &self,
+        target: &UploadTarget,
+        content: &UploadContent,
+        expires_at: i64,
+        signature: &str,
+    ) -> Result<()>;
+
+    /// Start a multipart upload to `path` and return its upload ID.
+    async fn create_multipart_upload(&self, path: &str, content_type: &str) -> Result<String>;
+
+    /// Store one part. Re-uploading a part number replaces it.
+    async fn upload_part(
+        &self,
+        path: &str,
+        upload_id: &str,
+        part_number: u32,
+        data: bytes::Bytes,
+    ) -> Result<UploadedPart>;
+
+    /// Parts received so far, ordered by part number.
+    async fn list_parts(&self, path: &str, upload_id: &str) -> Result<Vec<UploadedPart>>;
+
+    /// Assemble `parts` into the object at `path`.
+    async fn complete_multipart_upload(
+        &self,
+        path: &str,
+        upload_id: &str,
+        parts: &[UploadedPart],
+    ) -> Result<UploadedObject>;
+
+    /// Discard a multipart upload and its parts. Idempotent.
+    async fn abort_multipart_upload(&self, path: &str, upload_id: &str) -> Result<()>;
+
     /// Resolve the public URL for a stored path.
     fn public_url(&self, path: &str) -> String;
 
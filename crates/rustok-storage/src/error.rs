@@ -11,6 +11,12 @@ pub enum StorageError {
     #[error("Object not found: {0}")]
     NotFound(String),
 
+    #[error("Invalid upload signature: {0}")]
+    InvalidSignature(String),
+
+    #[error("Operation not supported by the {0} backend")]
+    Unsupported(&'static str),
+
     #[error("Backend error: {0}")]
     Backend(String),
 }
@@ -5,7 +5,10 @@ pub mod local;
 pub mod s3;
 pub mod service;
 
-pub use backend::{StorageBackend, UploadedObject};
+pub use backend::{
+    ObjectInfo, PresignedUpload, StorageBackend, UploadContent, UploadTarget, UploadedObject,
+    UploadedPart,
+};
 pub use error::{Result, StorageError};
 pub use local::LocalStorageConfig;
 #[cfg(feature = "s3")]
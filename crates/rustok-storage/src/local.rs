@@ -1,22 +1,51 @@
 use std::path::PathBuf;
 
 use async_trait::async_trait;
+use hmac::{Hmac, KeyInit, Mac};
+use sha2::{Digest, Sha256};
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
 use tracing::instrument;
 
 use crate::{
-    backend::{StorageBackend, UploadedObject},
+    backend::{
+        ObjectInfo, PresignedUpload, StorageBackend, UploadContent, UploadTarget, UploadedObject,
+        UploadedPart,
+    },
     error::{Result, StorageError},
 };
 
+/// Directory under `base_dir` holding in-progress multipart uploads.
+const MULTIPART_DIR: &str = ".multipart";
+
 /// Local-filesystem storage driver.
 ///
 /// Files are stored under `base_dir/<path>`.  Public URLs are constructed as
 /// `<base_url>/<path>` — configure `base_url` to point at a static-file
 /// server route (e.g. `/media`).
+///
+/// Direct uploads are the local equivalent of S3 presigned PUTs: URLs under
+/// `upload_base_url` carry an HMAC signature and expiry, and the application
+/// route serving them checks it with
+/// [`StorageBackend::verify_presigned_upload`] before writing.
 #[derive(Clone, Debug)]
 pub struct LocalStorage {
     base_dir: PathBuf,
     base_url: String,
+    upload_signing: Option<UploadSigning>,
+}
+
+#[derive(Clone)]
+struct UploadSigning {
+    secret: Vec<u8>,
+    base_url: String,
+}
+
+impl std::fmt::Debug for UploadSigning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UploadSigning")
+            .field("base_url", &self.base_url)
+            .finish_non_exhaustive()
+    }
 }
 
 impl LocalStorage {
@@ -24,9 +53,55 @@ impl LocalStorage {
         Self {
             base_dir: base_dir.into(),
             base_url: base_url.into().trim_end_matches('/').to_owned(),
+            upload_signing: None,
         }
     }
 
+    /// Enable signed direct uploads to `upload_base_url`.
+    pub fn with_upload_signing(
+        mut self,
+        secret: impl Into<Vec<u8>>,
+        upload_base_url: impl Into<String>,
+    ) -> Self {
+        self.upload_signing = Some(UploadSigning {
+            secret: secret.into(),
+            base_url: upload_base_url.into().trim_end_matches('/').to_owned(),
+        });
+        self
+    }
+
+    fn signature(
+        secret: &[u8],
+        target: &UploadTarget,
+        content: &UploadContent,
+        expires_at: i64,
+    ) -> Result<Hmac<Sha256>> {
+        let (upload_id, part_number) = match &target.part {
+            Some((upload_id, part_number)) => (upload_id.as_str(), part_number.to_string()),
+            None => ("", String::new()),
+        };
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret)
+            .map_err(|error| StorageError::Backend(error.to_string()))?;
+        mac.update(
+            format!(
+                "PUT\n{}\n{upload_id}\n{part_number}\n{}\n{}\n{expires_at}",
+                target.path.trim_start_matches('/'),
+                content.content_type,
+                content.content_length
+            )
+            .as_bytes(),
+        );
+        Ok(mac)
+    }
+
+    fn multipart_dir(&self, upload_id: &str) -> Result<PathBuf> {
+        // Upload IDs are generated here; anything else is a forged path.
+        if upload_id.len() != 32 || !upload_id.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(StorageError::InvalidPath(upload_id.to_string()));
+        }
+        Ok(self.base_dir.join(MULTIPART_DIR).join(upload_id))
+    }
+
     fn resolve(&self, path: &str) -> Result<PathBuf> {
         // Guard against path traversal
         if path.contains("..") {
@@ -36,6 +111,16 @@ impl LocalStorage {
     }
 }
 
+/// Part files are named `<part_number>.<etag>` so listing needs no index.
+fn part_file_name(part_number: u32, etag: &str) -> String {
+    format!("{part_number:05}.{etag}")
+}
+
+fn parse_part_file_name(name: &str) -> Option<(u32, String)> {
+    let (number, etag) = name.split_once('.')?;
+    Some((number.parse().ok()?, etag.to_string()))
+}
+
 #[async_trait]
 impl StorageBackend for LocalStorage {
     #[instrument(skip(self, data), fields(path, size = data.len()))]
@@ -88,6 +173,207 @@ impl StorageBackend for LocalStorage {
         Ok(None)
     }
 
+    #[instrument(skip(self), fields(path))]
+    async fn head(&self, path: &str) -> Result<ObjectInfo> {
+        let dest = self.resolve(path)?;
+        match tokio::fs::metadata(&dest).await {
+            Ok(metadata) if metadata.is_file() => Ok(ObjectInfo {
+                size: metadata.len(),
+            }),
+            Ok(_) => Err(StorageError::NotFound(path.to_string())),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                Err(StorageError::NotFound(path.to_string()))
+            }
+            Err(error) => Err(StorageError::Io(error)),
+        }
+    }
+
+    #[instrument(skip(self), fields(path))]
+    async fn read_range(&self, path: &str, offset: u64, length: u64) -> Result<bytes::Bytes> {
+        let dest = self.resolve(path)?;
+        let mut file = match tokio::fs::File::open(&dest).await {
+            Ok(file) => file,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                return Err(StorageError::NotFound(path.to_string()));
+            }
+            Err(error) => return Err(StorageError::Io(error)),
+        };
+        file.seek(std::io::SeekFrom::Start(offset)).await?;
+        let mut buffer = Vec::new();
+        file.take(length).read_to_end(&mut buffer).await?;
+        Ok(bytes::Bytes::from(buffer))
+    }
+
+    async fn presigned_upload(
+        &self,
+        target: &UploadTarget,
+        content: &UploadContent,
+        expires_in: std::time::Duration,
+    ) -> Result<Option<PresignedUpload>> {
+        let Some(signing) = &self.upload_signing else {
+            return Ok(None);
+        };
+        self.resolve(&target.path)?;
+        let expires_at = chrono::Utc::now()
+            + chrono::Duration::from_std(expires_in)
+                .map_err(|error| StorageError::Backend(error.to_string()))?;
+        let signature = Self::signature(&signing.secret, target, content, expires_at.timestamp())?;
+        let mut url = format!(
+            "{}/{}?expires={}&signature={}",
+            signing.base_url,
+            target.path.trim_start_matches('/'),
+            expires_at.timestamp(),
+            hex::encode(signature.finalize().into_bytes())
+        );
+        if let Some((upload_id, part_number)) = &target.part {
+            url.push_str(&format!("&upload_id={upload_id}&part_number={part_number}"));
+        }
+        Ok(Some(PresignedUpload {
+            url,
+            method: "PUT".to_string(),
+            headers: vec![("content-type".to_string(), content.content_type.clone())],
+            expires_at,
+        }))
+    }
+
+    fn verify_presigned_upload(
+        &self,
+        target: &UploadTarget,
+        content: &UploadContent,
+        expires_at: i64,
+        signature: &str,
+    ) -> Result<()> {
+        let Some(signing) = &self.upload_signing else {
+            return Err(StorageError::Unsupported("local"));
+        };
+        if expires_at < chrono::Utc::now().timestamp() {
+            return Err(StorageError::InvalidSignature(
+                "upload URL expired".to_string(),
+            ));
+        }
+        let signature = hex::decode(signature)
+            .map_err(|_| StorageError::InvalidSignature("malformed signature".to_string()))?;
+        Self::signature(&signing.secret, target, content, expires_at)?
+            .verify_slice(&signature)
+            .map_err(|_| StorageError::InvalidSignature("signature mismatch".to_string()))
+    }
+
+    #[instrument(skip(self), fields(path))]
+    async fn create_multipart_upload(&self, path: &str, _content_type: &str) -> Result<String> {
+        self.resolve(path)?;
+        let upload_id = uuid::Uuid::new_v4().simple().to_string();
+        tokio::fs::create_dir_all(self.multipart_dir(&upload_id)?).await?;
+        Ok(upload_id)
+    }
+
+    #[instrument(skip(self, data), fields(path, upload_id, part_number, size = data.len()))]
+    async fn upload_part(
+        &self,
+        path: &str,
+        upload_id: &str,
+        part_number: u32,
+        data: bytes::Bytes,
+    ) -> Result<UploadedPart> {
+        let dir = self.multipart_dir(upload_id)?;
+        if !tokio::fs::try_exists(&dir).await? {
+            return Err(StorageError::NotFound(format!(
+                "{path} (upload {upload_id})"
+            )));
+        }
+        let etag = hex::encode(&Sha256::digest(&data)[..16]);
+        for part in self.list_parts(path, upload_id).await? {
+            if part.part_number == part_number {
+                tokio::fs::remove_file(dir.join(part_file_name(part_number, &part.etag))).await?;
+            }
+        }
+        tokio::fs::write(dir.join(part_file_name(part_number, &etag)), &data).await?;
+        Ok(UploadedPart {
+            part_number,
+            etag,
+            size: data.len() as u64,
+        })
+    }
+
+    async fn list_parts(&self, path: &str, upload_id: &str) -> Result<Vec<UploadedPart>> {
+        let dir = self.multipart_dir(upload_id)?;
+        let mut entries = match tokio::fs::read_dir(&dir).await {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                return Err(StorageError::NotFound(format!(
+                    "{path} (upload {upload_id})"
+                )));
+            }
+            Err(error) => return Err(StorageError::Io(error)),
+        };
+        let mut parts = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let Some((part_number, etag)) =
+                entry.file_name().to_str().and_then(parse_part_file_name)
+            else {
+                continue;
+            };
+            parts.push(UploadedPart {
+                part_number,
+                etag,
+                size: entry.metadata().await?.len(),
+            });
+        }
+        parts.sort_by_key(|part| part.part_number);
+        Ok(parts)
+    }
+
+    #[instrument(skip(self, parts), fields(path, upload_id, parts = parts.len()))]
+    async fn complete_multipart_upload(
+        &self,
+        path: &str,
+        upload_id: &str,
+        parts: &[UploadedPart],
+    ) -> Result<UploadedObject> {
+        let dir = self.multipart_dir(upload_id)?;
+        let received = self.list_parts(path, upload_id).await?;
+        for part in parts {
+            if !received
+                .iter()
+                .any(|stored| stored.part_number == part.part_number && stored.etag == part.etag)
+            {
+                return Err(StorageError::Backend(format!(
+                    "part {} of upload {upload_id} was not received",
+                    part.part_number
+                )));
+            }
+        }
+
+        let dest = self.resolve(path)?;
+        if let Some(parent) = dest.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let mut output = tokio::fs::File::create(&dest).await?;
+        let mut size = 0;
+        for part in parts {
+            let mut input =
+                tokio::fs::File::open(dir.join(part_file_name(part.part_number, &part.etag)))
+                    .await?;
+            size += tokio::io::copy(&mut input, &mut output).await?;
+        }
+        output.sync_all().await?;
+        tokio::fs::remove_dir_all(&dir).await?;
+
+        Ok(UploadedObject {
+            path: path.to_string(),
+            public_url: self.public_url(path),
+            size,
+        })
+    }
+
+    #[instrument(skip(self), fields(path, upload_id))]
+    async fn abort_multipart_upload(&self, _path: &str, upload_id: &str) -> Result<()> {
+        match tokio::fs::remove_dir_all(self.multipart_dir(upload_id)?).await {
+            Ok(()) => Ok(()),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(error) => Err(StorageError::Io(error)),
+        }
+    }
+
     fn public_url(&self, path: &str) -> String {
         format!("{}/{}", self.base_url, path.trim_start_matches('/'))
     }
@@ -104,6 +390,17 @@ pub struct LocalStorageConfig {
     pub base_dir: String,
     /// URL prefix exposed to clients (e.g. `/media` or `https://cdn.example.com/media`).
     pub base_url: String,
+    /// Secret for signing direct upload URLs. Direct uploads are disabled
+    /// when unset.
+    #[serde(default)]
+    pub upload_signing_secret: Option<String>,
+    /// Application route that receives signed direct uploads.
+    #[serde(default = "default_upload_base_url")]
+    pub upload_base_url: String,
+}
+
+fn default_upload_base_url() -> String {
+    "/api/media/direct".into()
 }
 
 impl Default for LocalStorageConfig {
@@ -111,12 +408,156 @@ impl Default for LocalStorageConfig {
         Self {
             base_dir: "storage/media".into(),
             base_url: "/media".into(),
+            upload_signing_secret: None,
+            upload_base_url: default_upload_base_url(),
         }
     }
 }
 
 impl LocalStorageConfig {
     pub fn build(&self) -> LocalStorage {
-        LocalStorage::new(&self.base_dir, &self.base_url)
+        let storage = LocalStorage::new(&self.base_dir, &self.base_url);
+        match self
+            .upload_signing_secret
+            .as_deref()
+            .filter(|secret| !secret.is_empty())
+        {
+            Some(secret) => storage.with_upload_signing(secret, &self.upload_base_url),
+            None => storage,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn storage() -> (LocalStorage, PathBuf) {
+        let dir = std::env::temp_dir().join(format!("rustok-storage-{}", uuid::Uuid::new_v4()));
+        (
+            LocalStorage::new(&dir, "/media").with_upload_signing("secret", "/api/media/direct"),
+            dir,
+        )
+    }
+
+    fn query_param<'a>(url: &'a str, name: &str) -> &'a str {
+        url.split(['?', '&'])
+            .find_map(|pair| pair.strip_prefix(name)?.strip_prefix('='))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn presigned_uploads_verify_only_their_own_target() {
+        let (storage, _) = storage();
+        let target = UploadTarget::object("t/a.png");
+        let content = UploadContent::new("image/png", 128);
+        let presigned = storage
+            .presigned_upload(&target, &content, std::time::Duration::from_secs(60))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(presigned.url.starts_with("/api/media/direct/t/a.png?"));
+        assert_eq!(
+            presigned.headers,
+            vec![("content-type".to_string(), "image/png".to_string())]
+        );
+        let expires: i64 = query_param(&presigned.url, "expires").parse().unwrap();
+        let signature = query_param(&presigned.url, "signature");
+
+        storage
+            .verify_presigned_upload(&target, &content, expires, signature)
+            .unwrap();
+        for (other_target, other_content, other_expires) in [
+            (UploadTarget::object("t/b.png"), content.clone(), expires),
+            (target.clone(), content.clone(), expires + 1),
+            (
+                target.clone(),
+                UploadContent::new("image/png", 129),
+                expires,
+            ),
+            (
+                target.clone(),
+                UploadContent::new("text/html", 128),
+                expires,
+            ),
+        ] {
+            assert!(matches!(
+                storage.verify_presigned_upload(
+                    &other_target,
+                    &other_content,
+                    other_expires,
+                    signature
+                ),
+                Err(StorageError::InvalidSignature(_))
+            ));
+        }
+        assert!(LocalStorage::new("unused", "/media")
+            .presigned_upload(&target, &content, std::time::Duration::from_secs(60))
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn multipart_upload_assembles_parts_in_order() {
+        let (storage, dir) = storage();
+        let upload_id = storage
+            .create_multipart_upload("t/video.mp4", "video/mp4")
+            .await
+            .unwrap();
+        storage
+            .upload_part(
+                "t/video.mp4",
+                &upload_id,
+                2,
+                bytes::Bytes::from_static(b"world"),
+            )
+            .await
+            .unwrap();
+        storage
+            .upload_part(
+                "t/video.mp4",
+                &upload_id,
+                1,
+                bytes::Bytes::from_static(b"stale"),
+            )
+            .await
+            .unwrap();
+        // Re-uploading a part replaces it.
+        storage
+            .upload_part(
+                "t/video.mp4",
+                &upload_id,
+                1,
+                bytes::Bytes::from_static(b"hello "),
+            )
+            .await
+            .unwrap();
+
+        let parts = storage.list_parts("t/video.mp4", &upload_id).await.unwrap();
+        assert_eq!(
+            parts
+                .iter()
+                .map(|part| part.part_number)
+                .collect::<Vec<_>>(),
+            [1, 2]
+        );
+        let object = storage
+            .complete_multipart_upload("t/video.mp4", &upload_id, &parts)
+            .await
+            .unwrap();
+        assert_eq!(object.size, 11);
+        assert_eq!(
+            storage.read("t/video.mp4").await.unwrap(),
+            bytes::Bytes::from_static(b"hello world")
+        );
+        assert_eq!(
+            storage.read_range("t/video.mp4", 6, 100).await.unwrap(),
+            bytes::Bytes::from_static(b"world")
+        );
+        assert!(storage.list_parts("t/video.mp4", &upload_id).await.is_err());
+        assert!(storage.multipart_dir("../../etc").is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
     }
 }
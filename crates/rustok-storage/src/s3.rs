@@ -6,10 +6,14 @@ use aws_sdk_s3::config::{Builder as S3ConfigBuilder, Credentials, Region};
 use aws_sdk_s3::operation::get_object::GetObjectOutput;
 use aws_sdk_s3::presigning::PresigningConfig;
 use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::types::{CompletedMultipartUpload, CompletedPart};
 use aws_sdk_s3::Client;
 
 use crate::{
-    backend::{StorageBackend, UploadedObject},
+    backend::{
+        ObjectInfo, PresignedUpload, StorageBackend, UploadContent, UploadTarget, UploadedObject,
+        UploadedPart,
+    },
     error::{Result, StorageError},
 };
 
@@ -107,6 +111,10 @@ impl S3Storage {
         })
     }
 
+    fn backend_error(error: impl std::fmt::Display) -> StorageError {
+        StorageError::Backend(error.to_string())
+    }
+
     async fn collect_bytes(output: GetObjectOutput) -> Result<bytes::Bytes> {
         output
             .body
@@ -188,6 +196,218 @@ impl StorageBackend for S3Storage {
         Ok(Some(request.uri().to_string()))
     }
 
+    async fn head(&self, path: &str) -> Result<ObjectInfo> {
+        let key = self.object_key(path)?;
+        let output = self
+            .client
+            .head_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|error| match error.as_service_error() {
+                Some(service) if service.is_not_found() => StorageError::NotFound(path.to_string()),
+                _ => Self::backend_error(error),
+            })?;
+        Ok(ObjectInfo {
+            size: output.content_length().unwrap_or_default().max(0) as u64,
+        })
+    }
+
+    async fn read_range(&self, path: &str, offset: u64, length: u64) -> Result<bytes::Bytes> {
+        if length == 0 {
+            return Ok(bytes::Bytes::new());
+        }
+        let key = self.object_key(path)?;
+        let output = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .range(format!("bytes={}-{}", offset, offset + length - 1))
+            .send()
+            .await
+            .map_err(Self::backend_error)?;
+        Self::collect_bytes(output).await
+    }
+
+    async fn presigned_upload(
+        &self,
+        target: &UploadTarget,
+        content: &UploadContent,
+        expires_in: std::time::Duration,
+    ) -> Result<Option<PresignedUpload>> {
+        let key = self.object_key(&target.path)?;
+        let config = PresigningConfig::expires_in(expires_in).map_err(Self::backend_error)?;
+        let request = match &target.part {
+            Some((upload_id, part_number)) => self
+                .client
+                .upload_part()
+                .bucket(&self.bucket)
+                .key(key)
+                .upload_id(upload_id)
+                .part_number(*part_number as i32)
+                .content_length(content.content_length as i64)
+                .presigned(config)
+                .await
+                .map_err(Self::backend_error)?,
+            None => self
+                .client
+                .put_object()
+                .bucket(&self.bucket)
+                .key(key)
+                .content_type(&content.content_type)
+                .content_length(content.content_length as i64)
+                .presigned(config)
+                .await
+                .map_err(Self::backend_error)?,
+        };
+        Ok(Some(PresignedUpload {
+            url: request.uri().to_string(),
+            method: request.method().to_string(),
+            headers: request
+                .headers()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            expires_at: chrono::Utc::now()
+                + chrono::Duration::from_std(expires_in).map_err(Self::backend_error)?,
+        }))
+    }
+
+    fn verify_presigned_upload(
+        &self,
+        _target: &UploadTarget,
+        _content: &UploadContent,
+        _expires_at: i64,
+        _signature: &str,
+    ) -> Result<()> {
+        // S3 checks its own presigned requests.
+        Err(StorageError::Unsupported("s3"))
+    }
+
+    async fn create_multipart_upload(&self, path: &str, content_type: &str) -> Result<String> {
+        let key = self.object_key(path)?;
+        let output = self
+            .client
+            .create_multipart_upload()
+            .bucket(&self.bucket)
+            .key(key)
+            .content_type(content_type)
+            .send()
+            .await
+            .map_err(Self::backend_error)?;
+        output
+            .upload_id()
+            .map(ToString::to_string)
+            .ok_or_else(|| Self::backend_error("S3 returned no multipart upload ID"))
+    }
+
+    async fn upload_part(
+        &self,
+        path: &str,
+        upload_id: &str,
+        part_number: u32,
+        data: bytes::Bytes,
+    ) -> Result<UploadedPart> {
+        let key = self.object_key(path)?;
+        let size = data.len() as u64;
+        let output = self
+            .client
+            .upload_part()
+            .bucket(&self.bucket)
+            .key(key)
+            .upload_id(upload_id)
+            .part_number(part_number as i32)
+            .body(ByteStream::from(data))
+            .send()
+            .await
+            .map_err(Self::backend_error)?;
+        Ok(UploadedPart {
+            part_number,
+            etag: output.e_tag().unwrap_or_default().to_string(),
+            size,
+        })
+    }
+
+    async fn list_parts(&self, path: &str, upload_id: &str) -> Result<Vec<UploadedPart>> {
+        let key = self.object_key(path)?;
+        let mut parts = Vec::new();
+        let mut marker = None;
+        loop {
+            let output = self
+                .client
+                .list_parts()
+                .bucket(&self.bucket)
+                .key(&key)
+                .upload_id(upload_id)
+                .set_part_number_marker(marker)
+                .send()
+                .await
+                .map_err(Self::backend_error)?;
+            parts.extend(output.parts().iter().map(|part| UploadedPart {
+                part_number: part.part_number().unwrap_or_default().max(0) as u32,
+                etag: part.e_tag().unwrap_or_default().to_string(),
+                size: part.size().unwrap_or_default().max(0) as u64,
+            }));
+            if !output.is_truncated().unwrap_or(false) {
+                break;
+            }
+            marker = output.next_part_number_marker().map(ToString::to_string);
+        }
+        parts.sort_by_key(|part| part.part_number);
+        Ok(parts)
+    }
+
+    async fn complete_multipart_upload(
+        &self,
+        path: &str,
+        upload_id: &str,
+        parts: &[UploadedPart],
+    ) -> Result<UploadedObject> {
+        let key = self.object_key(path)?;
+        let completed = CompletedMultipartUpload::builder()
+            .set_parts(Some(
+                parts
+                    .iter()
+                    .map(|part| {
+                        CompletedPart::builder()
+                            .part_number(part.part_number as i32)
+                            .e_tag(&part.etag)
+                            .build()
+                    })
+                    .collect(),
+            ))
+            .build();
+        self.client
+            .complete_multipart_upload()
+            .bucket(&self.bucket)
+            .key(key)
+            .upload_id(upload_id)
+            .multipart_upload(completed)
+            .send()
+            .await
+            .map_err(Self::backend_error)?;
+
+        Ok(UploadedObject {
+            path: path.to_string(),
+            public_url: self.public_url(path),
+            size: parts.iter().map(|part| part.size).sum(),
+        })
+    }
+
+    async fn abort_multipart_upload(&self, path: &str, upload_id: &str) -> Result<()> {
+        let key = self.object_key(path)?;
+        self.client
+            .abort_multipart_upload()
+            .bucket(&self.bucket)
+            .key(key)
+            .upload_id(upload_id)
+            .send()
+            .await
+            .map_err(Self::backend_error)?;
+        Ok(())
+    }
+
     fn public_url(&self, path: &str) -> String {
         let key = match self.object_key(path) {
             Ok(key) => key,
@@ -221,3 +441,265 @@ pub struct S3StorageConfig {
     #[serde(default)]
     pub key_prefix: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::sync::{Arc, Mutex};
+
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    async fn storage(endpoint_url: &str) -> S3Storage {
+        S3Storage::from_config(&S3StorageConfig {
+            bucket: "media".to_string(),
+            region: Some("us-east-1".to_string()),
+            endpoint_url: Some(endpoint_url.to_string()),
+            access_key_id: Some("access".to_string()),
+            secret_access_key: Some("secret".to_string()),
+            public_base_url: None,
+            key_prefix: Some("files".to_string()),
+        })
+        .await
+        .unwrap()
+    }
+
+    fn query_param<'a>(url: &'a str, name: &str) -> Option<&'a str> {
+        url.split_once('?')?
+            .1
+            .split('&')
+            .find_map(|pair| pair.strip_prefix(name)?.strip_prefix('='))
+    }
+
+    #[tokio::test]
+    async fn presigned_uploads_sign_type_and_length() {
+        let storage = storage("http://127.0.0.1:9000").await;
+        let content = UploadContent::new("image/png", 1024);
+        let expires_in = std::time::Duration::from_secs(60);
+
+        let object = storage
+            .presigned_upload(&UploadTarget::object("t/a.png"), &content, expires_in)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(object.method, "PUT");
+        assert!(object
+            .url
+            .starts_with("http://127.0.0.1:9000/media/files/t/a.png?"));
+        assert_eq!(query_param(&object.url, "X-Amz-Expires"), Some("60"));
+        assert_eq!(
+            query_param(&object.url, "X-Amz-SignedHeaders"),
+            Some("content-length%3Bcontent-type%3Bhost")
+        );
+        assert!(object
+            .headers
+            .contains(&("content-type".to_string(), "image/png".to_string())));
+        assert!(object
+            .headers
+            .contains(&("content-length".to_string(), "1024".to_string())));
+
+        let part = storage
+            .presigned_upload(
+                &UploadTarget::part("t/a.png", "upload-1", 3),
+                &content,
+                expires_in,
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(query_param(&part.url, "partNumber"), Some("3"));
+        assert_eq!(query_param(&part.url, "uploadId"), Some("upload-1"));
+        assert_eq!(
+            query_param(&part.url, "X-Amz-SignedHeaders"),
+            Some("content-length%3Bhost")
+        );
+        assert_ne!(
+            query_param(&part.url, "X-Amz-Signature"),
+            query_param(&object.url, "X-Amz-Signature")
+        );
+
+        assert!(matches!(
+            storage.verify_presigned_upload(&UploadTarget::object("t/a.png"), &content, 0, ""),
+            Err(StorageError::Unsupported("s3"))
+        ));
+        assert!(matches!(
+            storage
+                .presigned_upload(&UploadTarget::object("../a.png"), &content, expires_in)
+                .await,
+            Err(StorageError::InvalidPath(_))
+        ));
+    }
+
+    /// Requests the mock S3 endpoint received, as `METHOD path?query`.
+    type Requests = Arc<Mutex<Vec<String>>>;
+
+    /// Minimal S3 endpoint covering the multipart upload calls.
+    async fn mock_s3() -> (String, Requests) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Requests::default();
+        let parts = Arc::new(Mutex::new(BTreeMap::<u32, usize>::new()));
+        let log = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                let (log, parts) = (log.clone(), parts.clone());
+                tokio::spawn(async move {
+                    let mut socket = BufReader::new(socket);
+                    while let Some((target, body)) = read_request(&mut socket).await {
+                        let response = respond(&target, body, &parts);
+                        log.lock().unwrap().push(target);
+                        if socket.get_mut().write_all(&response).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+        (endpoint, requests)
+    }
+
+    async fn read_request(
+        socket: &mut BufReader<tokio::net::TcpStream>,
+    ) -> Option<(String, Vec<u8>)> {
+        let mut line = String::new();
+        socket.read_line(&mut line).await.ok()?;
+        let mut words = line.split_whitespace();
+        let target = format!("{} {}", words.next()?, words.next()?);
+        let mut length = 0;
+        loop {
+            let mut header = String::new();
+            socket.read_line(&mut header).await.ok()?;
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    length = value.trim().parse().ok()?;
+                }
+            }
+        }
+        let mut body = vec![0; length];
+        socket.read_exact(&mut body).await.ok()?;
+        Some((target, body))
+    }
+
+    fn respond(target: &str, body: Vec<u8>, parts: &Mutex<BTreeMap<u32, usize>>) -> Vec<u8> {
+        let param = |name| query_param(target, name);
+        let (etag, xml) = match (target.split(' ').next(), param("partNumber")) {
+            (Some("POST"), _) if target.contains("uploads") => (
+                None,
+                "<InitiateMultipartUploadResult><Bucket>media</Bucket><Key>files/t/v.mp4</Key>\
+                 <UploadId>upload-1</UploadId></InitiateMultipartUploadResult>"
+                    .to_string(),
+            ),
+            (Some("PUT"), Some(number)) => {
+                let number: u32 = number.parse().unwrap();
+                parts.lock().unwrap().insert(number, body.len());
+                (Some(format!("\"etag-{number}\"")), String::new())
+            }
+            (Some("GET"), _) => {
+                let listed: String = parts
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .map(|(number, size)| {
+                        format!(
+                            "<Part><PartNumber>{number}</PartNumber><ETag>\"etag-{number}\"</ETag>\
+                             <Size>{size}</Size></Part>"
+                        )
+                    })
+                    .collect();
+                (
+                    None,
+                    format!(
+                        "<ListPartsResult><Bucket>media</Bucket><Key>files/t/v.mp4</Key>\
+                         <UploadId>upload-1</UploadId><IsTruncated>false</IsTruncated>{listed}\
+                         </ListPartsResult>"
+                    ),
+                )
+            }
+            (Some("POST"), _) => {
+                let completed = String::from_utf8(body).unwrap();
+                assert!(
+                    completed.find("etag-1") < completed.find("etag-2"),
+                    "parts are completed in order: {completed}"
+                );
+                (
+                    None,
+                    "<CompleteMultipartUploadResult><Bucket>media</Bucket>\
+                     <Key>files/t/v.mp4</Key><ETag>\"done\"</ETag></CompleteMultipartUploadResult>"
+                        .to_string(),
+                )
+            }
+            _ => (None, String::new()),
+        };
+        let status = if target.starts_with("DELETE") {
+            "204 No Content"
+        } else {
+            "200 OK"
+        };
+        let etag = etag.map_or(String::new(), |etag| format!("etag: {etag}\r\n"));
+        format!(
+            "HTTP/1.1 {status}\r\ncontent-type: application/xml\r\n{etag}content-length: {}\r\n\r\n{xml}",
+            xml.len()
+        )
+        .into_bytes()
+    }
+
+    #[tokio::test]
+    async fn multipart_upload_round_trip() {
+        let (endpoint, requests) = mock_s3().await;
+        let storage = storage(&endpoint).await;
+
+        let upload_id = storage
+            .create_multipart_upload("t/v.mp4", "video/mp4")
+            .await
+            .unwrap();
+        assert_eq!(upload_id, "upload-1");
+        // Parts may arrive out of order; listing and completion sort them.
+        for (number, size) in [(2, 3), (1, 5)] {
+            let part = storage
+                .upload_part(
+                    "t/v.mp4",
+                    &upload_id,
+                    number,
+                    bytes::Bytes::from(vec![7; size]),
+                )
+                .await
+                .unwrap();
+            assert_eq!(part.etag, format!("\"etag-{number}\""));
+            assert_eq!(part.size, size as u64);
+        }
+
+        let parts = storage.list_parts("t/v.mp4", &upload_id).await.unwrap();
+        assert_eq!(
+            parts
+                .iter()
+                .map(|part| (part.part_number, part.size))
+                .collect::<Vec<_>>(),
+            vec![(1, 5), (2, 3)]
+        );
+        let object = storage
+            .complete_multipart_upload("t/v.mp4", &upload_id, &parts)
+            .await
+            .unwrap();
+        assert_eq!(object.size, 8);
+        storage
+            .abort_multipart_upload("t/v.mp4", &upload_id)
+            .await
+            .unwrap();
+
+        let requests = requests.lock().unwrap();
+        let methods: Vec<_> = requests
+            .iter()
+            .map(|request| request.split(' ').next().unwrap())
+            .collect();
+        assert_eq!(methods, vec!["POST", "PUT", "PUT", "GET", "POST", "DELETE"]);
+        assert!(requests
+            .iter()
+            .all(|request| request.contains(" /media/files/t/v.mp4?")));
+    }
+}
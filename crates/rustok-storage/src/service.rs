@@ -6,7 +6,10 @@ use uuid::Uuid;
 #[cfg(feature = "s3")]
 use crate::s3::{S3Storage, S3StorageConfig};
 use crate::{
-    backend::{StorageBackend, UploadedObject},
+    backend::{
+        ObjectInfo, PresignedUpload, StorageBackend, UploadContent, UploadTarget, UploadedObject,
+        UploadedPart,
+    },
     error::Result,
     local::LocalStorageConfig,
 };
@@ -73,6 +76,67 @@ impl StorageService {
         self.0.private_download_url(path, expires_in).await
     }
 
+    pub async fn head(&self, path: &str) -> Result<ObjectInfo> {
+        self.0.head(path).await
+    }
+
+    pub async fn read_range(&self, path: &str, offset: u64, length: u64) -> Result<bytes::Bytes> {
+        self.0.read_range(path, offset, length).await
+    }
+
+    pub async fn presigned_upload(
+        &self,
+        target: &UploadTarget,
+        content: &UploadContent,
+        expires_in: std::time::Duration,
+    ) -> Result<Option<PresignedUpload>> {
+        self.0.presigned_upload(target, content, expires_in).await
+    }
+
+    pub fn verify_presigned_upload(
+        &self,
+        target: &UploadTarget,
+        content: &UploadContent,
+        expires_at: i64,
+        signature: &str,
+    ) -> Result<()> {
+        self.0
+            .verify_presigned_upload(target, content, expires_at, signature)
+    }
+
+    pub async fn create_multipart_upload(&self, path: &str, content_type: &str) -> Result<String> {
+        self.0.create_multipart_upload(path, content_type).await
+    }
+
+    pub async fn upload_part(
+        &self,
+        path: &str,
+        upload_id: &str,
+        part_number: u32,
+        data: bytes::Bytes,
+    ) -> Result<UploadedPart> {
+        self.0.upload_part(path, upload_id, part_number, data).await
+    }
+
+    pub async fn list_parts(&self, path: &str, upload_id: &str) -> Result<Vec<UploadedPart>> {
+        self.0.list_parts(path, upload_id).await
+    }
+
+    pub async fn complete_multipart_upload(
+        &self,
+        path: &str,
+        upload_id: &str,
+        parts: &[UploadedPart],
+    ) -> Result<UploadedObject> {
+        self.0
+            .complete_multipart_upload(path, upload_id, parts)
+            .await
+    }
+
+    pub async fn abort_multipart_upload(&self, path: &str, upload_id: &str) -> Result<()> {
+        self.0.abort_multipart_upload(path, upload_id).await
+    }
+
     pub fn public_url(&self, path: &str) -> String {
         self.0.public_url(path)
     }
@@ -69,6 +69,8 @@ pub struct CreateTaxonomyTermInput {
     pub description: Option<String>,
     #[serde(default)]
     pub aliases: Vec<String>,
+    #[serde(default)]
+    pub parent_id: Option<Uuid>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -90,6 +92,8 @@ pub struct TaxonomyTermResponse {
     pub scope_value: Option<String>,
     pub canonical_key: String,
     pub status: TaxonomyTermStatus,
+    pub parent_id: Option<Uuid>,
+    pub depth: i32,
     pub requested_locale: String,
     pub effective_locale: String,
     pub available_locales: Vec<String>,
@@ -109,6 +113,8 @@ pub struct TaxonomyTermListItem {
     pub scope_value: Option<String>,
     pub canonical_key: String,
     pub status: TaxonomyTermStatus,
+    pub parent_id: Option<Uuid>,
+    pub depth: i32,
     pub requested_locale: String,
     pub effective_locale: String,
     pub available_locales: Vec<String>,
@@ -118,12 +124,27 @@ pub struct TaxonomyTermListItem {
     pub created_at: DateTime<Utc>,
 }
 
+/// One localized step of a term's breadcrumb trail, ordered from the root.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaxonomyBreadcrumb {
+    pub id: Uuid,
+    pub depth: i32,
+    pub effective_locale: String,
+    pub name: String,
+    pub slug: String,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub struct ListTaxonomyTermsFilter {
     pub kind: Option<TaxonomyTermKind>,
     pub scope_type: Option<TaxonomyScopeType>,
     pub scope_value: Option<String>,
     pub status: Option<TaxonomyTermStatus>,
+    /// Only direct children of this term.
+    pub parent_id: Option<Uuid>,
+    /// Only root terms; ignored when `parent_id` is set.
+    #[serde(default)]
+    pub roots_only: bool,
     pub locale: Option<String>,
     pub page: Option<u64>,
     pub per_page: Option<u64>,
@@ -15,6 +15,10 @@ pub struct Model {
     pub scope_value: String,
     pub canonical_key: String,
     pub status: TaxonomyTermStatus,
+    pub parent_id: Option<Uuid>,
+    pub depth: i32,
+    /// Materialized ancestor path ending with the term itself, e.g. `/{root}/{child}/`.
+    pub path: String,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
 }
@@ -40,3 +44,18 @@ impl Related<super::taxonomy_term_alias::Entity> for Entity {
 }
 
 impl ActiveModelBehavior for ActiveModel {}
+
+/// Materialized path for a term placed under `parent_path` (or at the root when `None`).
+pub fn child_path(parent_path: Option<&str>, id: Uuid) -> String {
+    format!("{}{id}/", parent_path.unwrap_or("/"))
+}
+
+impl Model {
+    /// Ids from the root down to this term, parsed from [`Model::path`].
+    pub fn ancestor_ids(&self) -> Vec<Uuid> {
+        self.path
+            .split('/')
+            .filter_map(|segment| Uuid::parse_str(segment).ok())
+            .collect()
+    }
+}
@@ -18,6 +18,9 @@ pub enum TaxonomyError {
     #[error("Alias already exists in this scope: {0}")]
     DuplicateAlias(String),
 
+    #[error("Moving term {0} there would create a cycle")]
+    HierarchyCycle(Uuid),
+
     #[error("Forbidden: {0}")]
     Forbidden(String),
 
@@ -10,8 +10,9 @@ pub mod migrations;
 pub mod services;
 
 pub use dto::{
-    CreateTaxonomyTermInput, ListTaxonomyTermsFilter, TaxonomyScopeType, TaxonomyTermKind,
-    TaxonomyTermListItem, TaxonomyTermResponse, TaxonomyTermStatus, UpdateTaxonomyTermInput,
+    CreateTaxonomyTermInput, ListTaxonomyTermsFilter, TaxonomyBreadcrumb, TaxonomyScopeType,
+    TaxonomyTermKind, TaxonomyTermListItem, TaxonomyTermResponse, TaxonomyTermStatus,
+    UpdateTaxonomyTermInput,
 };
 pub use error::{TaxonomyError, TaxonomyResult};
 pub use services::TaxonomyService;
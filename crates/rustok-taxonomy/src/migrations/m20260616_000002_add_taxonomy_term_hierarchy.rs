@@ -0,0 +1,136 @@
+use sea_orm_migration::prelude::*;
+use uuid::Uuid;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TaxonomyTerms::Table)
+                    .add_column(ColumnDef::new(TaxonomyTerms::ParentId).uuid().null())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TaxonomyTerms::Table)
+                    .add_column(
+                        ColumnDef::new(TaxonomyTerms::Depth)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TaxonomyTerms::Table)
+                    .add_column(
+                        ColumnDef::new(TaxonomyTerms::Path)
+                            .string_len(1024)
+                            .not_null()
+                            .default(""),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Every existing term becomes a root whose path is just its own id.
+        let connection = manager.get_connection();
+        let backend = manager.get_database_backend();
+        let rows = connection
+            .query_all(
+                backend.build(
+                    Query::select()
+                        .column(TaxonomyTerms::Id)
+                        .from(TaxonomyTerms::Table),
+                ),
+            )
+            .await?;
+        for row in rows {
+            let id: Uuid = row.try_get("", "id")?;
+            connection
+                .execute(
+                    backend.build(
+                        Query::update()
+                            .table(TaxonomyTerms::Table)
+                            .value(TaxonomyTerms::Path, format!("/{id}/"))
+                            .and_where(Expr::col(TaxonomyTerms::Id).eq(id)),
+                    ),
+                )
+                .await?;
+        }
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_taxonomy_terms_parent")
+                    .table(TaxonomyTerms::Table)
+                    .col(TaxonomyTerms::TenantId)
+                    .col(TaxonomyTerms::ParentId)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_taxonomy_terms_path")
+                    .table(TaxonomyTerms::Table)
+                    .col(TaxonomyTerms::TenantId)
+                    .col(TaxonomyTerms::Path)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_taxonomy_terms_path")
+                    .table(TaxonomyTerms::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_taxonomy_terms_parent")
+                    .table(TaxonomyTerms::Table)
+                    .to_owned(),
+            )
+            .await?;
+        for column in [
+            TaxonomyTerms::Path,
+            TaxonomyTerms::Depth,
+            TaxonomyTerms::ParentId,
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(TaxonomyTerms::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum TaxonomyTerms {
+    Table,
+    Id,
+    TenantId,
+    ParentId,
+    Depth,
+    Path,
+}
@@ -1,7 +1,11 @@
 mod m20260329_000001_create_taxonomy_tables;
+mod m20260616_000002_add_taxonomy_term_hierarchy;
 
 use sea_orm_migration::MigrationTrait;
 
 pub fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-    vec![Box::new(m20260329_000001_create_taxonomy_tables::Migration)]
+    vec![
+        Box::new(m20260329_000001_create_taxonomy_tables::Migration),
+        Box::new(m20260616_000002_add_taxonomy_term_hierarchy::Migration),
+    ]
 }
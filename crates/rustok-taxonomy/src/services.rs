@@ -2,9 +2,9 @@ use std::collections::{HashMap, HashSet};
 
 use chrono::Utc;
 use sea_orm::{
-    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DatabaseConnection, DatabaseTransaction,
-    EntityTrait, JoinType, ModelTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect,
-    RelationTrait, TransactionTrait,
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, Condition, ConnectionTrait,
+    DatabaseConnection, DatabaseTransaction, EntityTrait, JoinType, ModelTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, RelationTrait, TransactionTrait,
 };
 use tracing::instrument;
 use uuid::Uuid;
@@ -16,12 +16,16 @@ use rustok_content::{
 use rustok_core::{Action, PermissionScope, Resource, SecurityContext};
 
 use crate::dto::{
-    CreateTaxonomyTermInput, ListTaxonomyTermsFilter, TaxonomyScopeType, TaxonomyTermKind,
-    TaxonomyTermListItem, TaxonomyTermResponse, TaxonomyTermStatus, UpdateTaxonomyTermInput,
+    CreateTaxonomyTermInput, ListTaxonomyTermsFilter, TaxonomyBreadcrumb, TaxonomyScopeType,
+    TaxonomyTermKind, TaxonomyTermListItem, TaxonomyTermResponse, TaxonomyTermStatus,
+    UpdateTaxonomyTermInput,
 };
 use crate::entities::{taxonomy_term, taxonomy_term_alias, taxonomy_term_translation};
 use crate::error::{TaxonomyError, TaxonomyResult};
 
+/// Deepest level a term may sit at; roots have depth 0.
+pub const MAX_TERM_DEPTH: i32 = 16;
+
 pub struct TaxonomyService {
     db: DatabaseConnection,
 }
@@ -68,6 +72,20 @@ impl TaxonomyService {
         let aliases = normalize_aliases(&input.aliases);
         let txn = self.db.begin().await?;
 
+        let parent = match input.parent_id {
+            Some(parent_id) => {
+                let parent = find_term_in_tx(&txn, tenant_id, parent_id).await?;
+                ensure_parent_compatible(&parent, input.kind, input.scope_type, &scope_value)?;
+                if parent.depth + 1 > MAX_TERM_DEPTH {
+                    return Err(TaxonomyError::validation(format!(
+                        "Terms cannot be nested deeper than {MAX_TERM_DEPTH} levels"
+                    )));
+                }
+                Some(parent)
+            }
+            None => None,
+        };
+
         self.ensure_canonical_key_available_in_tx(
             &txn,
             tenant_id,
@@ -111,6 +129,12 @@ impl TaxonomyService {
             scope_value: Set(scope_value.clone()),
             canonical_key: Set(canonical_key),
             status: Set(TaxonomyTermStatus::Active),
+            parent_id: Set(parent.as_ref().map(|parent| parent.id)),
+            depth: Set(parent.as_ref().map_or(0, |parent| parent.depth + 1)),
+            path: Set(taxonomy_term::child_path(
+                parent.as_ref().map(|parent| parent.path.as_str()),
+                term_id,
+            )),
             created_at: Set(now.into()),
             updated_at: Set(now.into()),
         }
@@ -325,10 +349,153 @@ impl TaxonomyService {
     ) -> TaxonomyResult<()> {
         enforce_scope(&security, Resource::Taxonomy, Action::Delete)?;
         let term = self.find_term(tenant_id, term_id).await?;
-        term.delete(&self.db).await?;
+        let txn = self.db.begin().await?;
+
+        // Children move up to the deleted term's parent instead of being orphaned.
+        let new_parent = match term.parent_id {
+            Some(parent_id) => Some(find_term_in_tx(&txn, tenant_id, parent_id).await?),
+            None => None,
+        };
+        let children = taxonomy_term::Entity::find()
+            .filter(taxonomy_term::Column::TenantId.eq(tenant_id))
+            .filter(taxonomy_term::Column::ParentId.eq(term_id))
+            .all(&txn)
+            .await?;
+        for child in children {
+            move_subtree_in_tx(&txn, child, new_parent.as_ref()).await?;
+        }
+
+        term.delete(&txn).await?;
+        txn.commit().await?;
+        Ok(())
+    }
+
+    /// Moves a term (with its whole subtree) under `parent_id`, or to the root when `None`.
+    #[instrument(skip(self, security))]
+    pub async fn move_term(
+        &self,
+        tenant_id: Uuid,
+        term_id: Uuid,
+        security: SecurityContext,
+        parent_id: Option<Uuid>,
+    ) -> TaxonomyResult<()> {
+        enforce_scope(&security, Resource::Taxonomy, Action::Update)?;
+
+        let txn = self.db.begin().await?;
+        let term = find_term_in_tx(&txn, tenant_id, term_id).await?;
+        if term.parent_id == parent_id {
+            return Ok(());
+        }
+        let parent = match parent_id {
+            Some(parent_id) => {
+                let parent = find_term_in_tx(&txn, tenant_id, parent_id).await?;
+                if parent.path.starts_with(&term.path) {
+                    return Err(TaxonomyError::HierarchyCycle(term_id));
+                }
+                ensure_parent_compatible(&parent, term.kind, term.scope_type, &term.scope_value)?;
+                Some(parent)
+            }
+            None => None,
+        };
+
+        move_subtree_in_tx(&txn, term, parent.as_ref()).await?;
+        txn.commit().await?;
         Ok(())
     }
 
+    /// Localized trail from the root down to (and including) `term_id`.
+    #[instrument(skip(self, security))]
+    pub async fn get_breadcrumbs(
+        &self,
+        tenant_id: Uuid,
+        security: SecurityContext,
+        term_id: Uuid,
+        locale: &str,
+        fallback_locale: Option<&str>,
+    ) -> TaxonomyResult<Vec<TaxonomyBreadcrumb>> {
+        enforce_scope(&security, Resource::Taxonomy, Action::Read)?;
+
+        let locale = normalize_locale(locale)?;
+        let fallback_locale = fallback_locale.map(normalize_locale).transpose()?;
+        let term = self.find_term(tenant_id, term_id).await?;
+        let ancestor_ids = term.ancestor_ids();
+        let mut ancestors = taxonomy_term::Entity::find()
+            .filter(taxonomy_term::Column::TenantId.eq(tenant_id))
+            .filter(taxonomy_term::Column::Id.is_in(ancestor_ids))
+            .all(&self.db)
+            .await?;
+        ancestors.sort_by_key(|ancestor| ancestor.depth);
+
+        let ids = ancestors
+            .iter()
+            .map(|ancestor| ancestor.id)
+            .collect::<Vec<_>>();
+        let translations_by_term = self.load_translations_map(&ids).await?;
+        Ok(ancestors
+            .into_iter()
+            .map(|ancestor| {
+                let translations = translations_by_term
+                    .get(&ancestor.id)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                let resolved = resolve_by_locale_with_fallback(
+                    translations,
+                    &locale,
+                    fallback_locale.as_deref(),
+                    |translation| translation.locale.as_str(),
+                );
+                TaxonomyBreadcrumb {
+                    id: ancestor.id,
+                    depth: ancestor.depth,
+                    effective_locale: resolved.effective_locale,
+                    name: resolved
+                        .item
+                        .map(|translation| translation.name.clone())
+                        .unwrap_or_else(|| ancestor.canonical_key.clone()),
+                    slug: resolved
+                        .item
+                        .map(|translation| translation.slug.clone())
+                        .unwrap_or_else(|| ancestor.canonical_key.clone()),
+                }
+            })
+            .collect())
+    }
+
+    /// Expands `term_ids` with every descendant, for "term or any child" filters in
+    /// consuming modules. Unknown ids and ids of other tenants are dropped.
+    pub async fn expand_descendants_in_tx<C: ConnectionTrait>(
+        conn: &C,
+        tenant_id: Uuid,
+        term_ids: &[Uuid],
+    ) -> TaxonomyResult<Vec<Uuid>> {
+        if term_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let roots = taxonomy_term::Entity::find()
+            .filter(taxonomy_term::Column::TenantId.eq(tenant_id))
+            .filter(taxonomy_term::Column::Id.is_in(term_ids.to_vec()))
+            .all(conn)
+            .await?;
+        if roots.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let subtree = roots.iter().fold(Condition::any(), |condition, root| {
+            condition.add(taxonomy_term::Column::Path.starts_with(&root.path))
+        });
+        let mut seen = HashSet::new();
+        Ok(taxonomy_term::Entity::find()
+            .filter(taxonomy_term::Column::TenantId.eq(tenant_id))
+            .filter(subtree)
+            .order_by_asc(taxonomy_term::Column::Depth)
+            .all(conn)
+            .await?
+            .into_iter()
+            .map(|term| term.id)
+            .filter(|id| seen.insert(*id))
+            .collect())
+    }
+
     #[instrument(skip(self, security))]
     pub async fn list_terms(
         &self,
@@ -359,6 +526,11 @@ impl TaxonomyService {
         if let Some(status) = filter.status {
             select = select.filter(taxonomy_term::Column::Status.eq(status));
         }
+        if let Some(parent_id) = filter.parent_id {
+            select = select.filter(taxonomy_term::Column::ParentId.eq(parent_id));
+        } else if filter.roots_only {
+            select = select.filter(taxonomy_term::Column::ParentId.is_null());
+        }
 
         let paginator = select
             .order_by_asc(taxonomy_term::Column::Kind)
@@ -397,6 +569,8 @@ impl TaxonomyService {
                     scope_value: decode_scope_value(term.scope_type, &term.scope_value),
                     canonical_key: term.canonical_key,
                     status: term.status,
+                    parent_id: term.parent_id,
+                    depth: term.depth,
                     requested_locale: locale.clone(),
                     effective_locale: resolved.effective_locale,
                     available_locales: available_locales_from(&translations, |translation| {
@@ -902,6 +1076,9 @@ impl TaxonomyService {
             scope_value: Set(module_scope.to_string()),
             canonical_key: Set(normalized_slug.to_string()),
             status: Set(TaxonomyTermStatus::Active),
+            parent_id: Set(None),
+            depth: Set(0),
+            path: Set(taxonomy_term::child_path(None, term_id)),
             created_at: Set(now.into()),
             updated_at: Set(now.into()),
         }
@@ -1020,6 +1197,8 @@ fn build_term_response(
         scope_value: decode_scope_value(term.scope_type, &term.scope_value),
         canonical_key: term.canonical_key,
         status: term.status,
+        parent_id: term.parent_id,
+        depth: term.depth,
         requested_locale: locale.to_string(),
         effective_locale: resolved.effective_locale,
         available_locales: available_locales_from(&translations, |translation| {
@@ -1042,6 +1221,76 @@ fn build_term_response(
     }
 }
 
+async fn find_term_in_tx<C: ConnectionTrait>(
+    conn: &C,
+    tenant_id: Uuid,
+    term_id: Uuid,
+) -> TaxonomyResult<taxonomy_term::Model> {
+    taxonomy_term::Entity::find_by_id(term_id)
+        .filter(taxonomy_term::Column::TenantId.eq(tenant_id))
+        .one(conn)
+        .await?
+        .ok_or(TaxonomyError::TermNotFound(term_id))
+}
+
+fn ensure_parent_compatible(
+    parent: &taxonomy_term::Model,
+    kind: TaxonomyTermKind,
+    scope_type: TaxonomyScopeType,
+    scope_value: &str,
+) -> TaxonomyResult<()> {
+    if parent.kind != kind || parent.scope_type != scope_type || parent.scope_value != scope_value {
+        return Err(TaxonomyError::validation(
+            "Parent term must share the kind and scope of its children",
+        ));
+    }
+    Ok(())
+}
+
+/// Re-roots `term` and everything below it under `new_parent`, rewriting paths and depths.
+async fn move_subtree_in_tx(
+    txn: &DatabaseTransaction,
+    term: taxonomy_term::Model,
+    new_parent: Option<&taxonomy_term::Model>,
+) -> TaxonomyResult<()> {
+    let new_path =
+        taxonomy_term::child_path(new_parent.map(|parent| parent.path.as_str()), term.id);
+    let new_depth = new_parent.map_or(0, |parent| parent.depth + 1);
+    let depth_delta = new_depth - term.depth;
+
+    let subtree = taxonomy_term::Entity::find()
+        .filter(taxonomy_term::Column::TenantId.eq(term.tenant_id))
+        .filter(taxonomy_term::Column::Path.starts_with(&term.path))
+        .all(txn)
+        .await?;
+    let deepest = subtree
+        .iter()
+        .map(|node| node.depth)
+        .max()
+        .unwrap_or(term.depth);
+    if deepest + depth_delta > MAX_TERM_DEPTH {
+        return Err(TaxonomyError::validation(format!(
+            "Terms cannot be nested deeper than {MAX_TERM_DEPTH} levels"
+        )));
+    }
+
+    let now = Utc::now();
+    for node in subtree {
+        let path = format!("{new_path}{}", &node.path[term.path.len()..]);
+        let depth = node.depth + depth_delta;
+        let is_root = node.id == term.id;
+        let mut active: taxonomy_term::ActiveModel = node.into();
+        if is_root {
+            active.parent_id = Set(new_parent.map(|parent| parent.id));
+        }
+        active.path = Set(path);
+        active.depth = Set(depth);
+        active.updated_at = Set(now.into());
+        active.update(txn).await?;
+    }
+    Ok(())
+}
+
 fn enforce_scope(
     security: &SecurityContext,
     resource: Resource,
@@ -1208,6 +1457,7 @@ mod tests {
                     canonical_key: None,
                     description: Some("Systems language".to_string()),
                     aliases: vec!["rust-lang".to_string(), "rust language".to_string()],
+                    parent_id: None,
                 },
             )
             .await
@@ -1250,6 +1500,7 @@ mod tests {
                     canonical_key: None,
                     description: None,
                     aliases: vec![],
+                    parent_id: None,
                 },
             )
             .await
@@ -1269,6 +1520,7 @@ mod tests {
                     canonical_key: None,
                     description: None,
                     aliases: vec![],
+                    parent_id: None,
                 },
             )
             .await
@@ -1295,6 +1547,7 @@ mod tests {
                     canonical_key: None,
                     description: None,
                     aliases: vec![],
+                    parent_id: None,
                 },
             )
             .await
@@ -1314,6 +1567,7 @@ mod tests {
                     canonical_key: Some("zig".to_string()),
                     description: None,
                     aliases: vec![],
+                    parent_id: None,
                 },
             )
             .await
@@ -1342,6 +1596,7 @@ mod tests {
                     canonical_key: None,
                     description: Some("Systems language".to_string()),
                     aliases: vec!["rust language".to_string()],
+                    parent_id: None,
                 },
             )
             .await
@@ -1400,6 +1655,7 @@ mod tests {
                     canonical_key: None,
                     description: None,
                     aliases: vec![],
+                    parent_id: None,
                 },
             )
             .await
@@ -1419,6 +1675,7 @@ mod tests {
                     canonical_key: None,
                     description: None,
                     aliases: vec![],
+                    parent_id: None,
                 },
             )
             .await
@@ -1441,4 +1698,158 @@ mod tests {
         assert_eq!(resolved.id, module_term_id);
         assert_ne!(resolved.id, global_term_id);
     }
+
+    async fn create_global(
+        service: &TaxonomyService,
+        tenant_id: Uuid,
+        name: &str,
+        parent_id: Option<Uuid>,
+    ) -> TaxonomyResult<Uuid> {
+        service
+            .create_term(
+                tenant_id,
+                admin(),
+                CreateTaxonomyTermInput {
+                    kind: TaxonomyTermKind::Tag,
+                    scope_type: TaxonomyScopeType::Global,
+                    scope_value: None,
+                    locale: "en".to_string(),
+                    name: name.to_string(),
+                    slug: None,
+                    canonical_key: None,
+                    description: None,
+                    aliases: vec![],
+                    parent_id,
+                },
+            )
+            .await
+    }
+
+    #[tokio::test]
+    async fn hierarchy_supports_breadcrumbs_moves_and_subtree_expansion() {
+        let (db, service) = setup().await;
+        let tenant_id = Uuid::new_v4();
+        let languages = create_global(&service, tenant_id, "Languages", None)
+            .await
+            .unwrap();
+        let systems = create_global(&service, tenant_id, "Systems", Some(languages))
+            .await
+            .unwrap();
+        let rust = create_global(&service, tenant_id, "Rust", Some(systems))
+            .await
+            .unwrap();
+        let web = create_global(&service, tenant_id, "Web", None)
+            .await
+            .unwrap();
+
+        let module_child = service
+            .create_term(
+                tenant_id,
+                admin(),
+                CreateTaxonomyTermInput {
+                    kind: TaxonomyTermKind::Tag,
+                    scope_type: TaxonomyScopeType::Module,
+                    scope_value: Some("blog".to_string()),
+                    locale: "en".to_string(),
+                    name: "Async".to_string(),
+                    slug: None,
+                    canonical_key: None,
+                    description: None,
+                    aliases: vec![],
+                    parent_id: Some(rust),
+                },
+            )
+            .await;
+        assert!(matches!(module_child, Err(TaxonomyError::Validation(_))));
+
+        service
+            .update_term(
+                tenant_id,
+                systems,
+                admin(),
+                UpdateTaxonomyTermInput {
+                    locale: "ru".to_string(),
+                    name: Some("Системные".to_string()),
+                    ..UpdateTaxonomyTermInput::default()
+                },
+            )
+            .await
+            .unwrap();
+        let crumbs = service
+            .get_breadcrumbs(tenant_id, admin(), rust, "ru", Some("en"))
+            .await
+            .unwrap();
+        assert_eq!(
+            crumbs
+                .iter()
+                .map(|crumb| (crumb.name.as_str(), crumb.depth))
+                .collect::<Vec<_>>(),
+            vec![("Languages", 0), ("Системные", 1), ("Rust", 2)]
+        );
+        assert_eq!(crumbs[1].effective_locale, "ru");
+
+        assert_eq!(
+            TaxonomyService::expand_descendants_in_tx(&db, tenant_id, &[languages])
+                .await
+                .unwrap(),
+            vec![languages, systems, rust]
+        );
+        assert!(matches!(
+            service
+                .move_term(tenant_id, languages, admin(), Some(rust))
+                .await,
+            Err(TaxonomyError::HierarchyCycle(_))
+        ));
+
+        service
+            .move_term(tenant_id, systems, admin(), Some(web))
+            .await
+            .unwrap();
+        let moved = service
+            .get_term(tenant_id, admin(), rust, "en", None)
+            .await
+            .unwrap();
+        assert_eq!(moved.depth, 2);
+        assert_eq!(
+            TaxonomyService::expand_descendants_in_tx(&db, tenant_id, &[web])
+                .await
+                .unwrap(),
+            vec![web, systems, rust]
+        );
+        assert_eq!(
+            TaxonomyService::expand_descendants_in_tx(&db, tenant_id, &[languages])
+                .await
+                .unwrap(),
+            vec![languages]
+        );
+
+        service
+            .delete_term(tenant_id, systems, admin())
+            .await
+            .unwrap();
+        let orphan = service
+            .get_term(tenant_id, admin(), rust, "en", None)
+            .await
+            .unwrap();
+        assert_eq!(
+            orphan.parent_id,
+            Some(web),
+            "children move up to the grandparent"
+        );
+        assert_eq!(orphan.depth, 1);
+        let (roots, total) = service
+            .list_terms(
+                tenant_id,
+                admin(),
+                ListTaxonomyTermsFilter {
+                    roots_only: true,
+                    ..ListTaxonomyTermsFilter::default()
+                },
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(total, 2);
+        assert!(roots.iter().all(|term| term.parent_id.is_none()));
+    }
 }
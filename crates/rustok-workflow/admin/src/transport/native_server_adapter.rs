@@ -85,7 +85,10 @@ pub async fn create_from_template_native(
 
         let app_ctx = expect_context::<AppContext>();
         rustok_workflow::WorkflowService::new(app_ctx.db.clone())
-            .create_from_template(tenant.id, Some(auth.user_id), &template_id, name)
+            .with_action_runtime(rustok_workflow::controllers::action_runtime_from_context(
+                &app_ctx,
+            ))
+            .create_from_template(tenant.id, auth.security_context(), &template_id, name)
             .await
             .map(|id| id.to_string())
             .map_err(|err| ServerFnError::new(err.to_string()))
@@ -7,7 +7,7 @@ use rustok_api::{has_any_effective_permission, AuthContext, TenantContext};
 use rustok_core::Permission;
 use uuid::Uuid;
 
-use crate::{WorkflowExecutionResponse, WorkflowService};
+use crate::{RerunExecutionInput, WorkflowExecutionResponse, WorkflowWebhookDeliveryResponse};
 
 pub async fn list_executions(
     State(ctx): State<AppContext>,
@@ -21,7 +21,7 @@ pub async fn list_executions(
         "Permission denied: workflow_executions:list required",
     )?;
 
-    let service = WorkflowService::new(ctx.db.clone());
+    let service = super::workflow_service(&ctx);
     let executions = service
         .list_executions(tenant.id, workflow_id)
         .await
@@ -29,6 +29,27 @@ pub async fn list_executions(
     Ok(Json(executions))
 }
 
+/// Recent accepted and rejected webhook requests of a workflow.
+pub async fn list_webhook_deliveries(
+    State(ctx): State<AppContext>,
+    tenant: TenantContext,
+    auth: AuthContext,
+    Path(workflow_id): Path<Uuid>,
+) -> Result<Json<Vec<WorkflowWebhookDeliveryResponse>>> {
+    ensure_execution_permission(
+        &auth,
+        &[Permission::WORKFLOW_EXECUTIONS_LIST],
+        "Permission denied: workflow_executions:list required",
+    )?;
+
+    let service = super::workflow_service(&ctx);
+    let deliveries = service
+        .list_webhook_deliveries(tenant.id, workflow_id)
+        .await
+        .map_err(|err| Error::BadRequest(err.to_string()))?;
+    Ok(Json(deliveries))
+}
+
 pub async fn get_execution(
     State(ctx): State<AppContext>,
     tenant: TenantContext,
@@ -41,7 +62,7 @@ pub async fn get_execution(
         "Permission denied: workflow_executions:read required",
     )?;
 
-    let service = WorkflowService::new(ctx.db.clone());
+    let service = super::workflow_service(&ctx);
     let execution = service
         .get_execution(tenant.id, execution_id)
         .await
@@ -52,6 +73,32 @@ pub async fn get_execution(
     Ok(Json(execution))
 }
 
+/// Resumes a failed execution from its failing step, or re-runs a finished
+/// one from `step_id`.
+pub async fn rerun_execution(
+    State(ctx): State<AppContext>,
+    tenant: TenantContext,
+    auth: AuthContext,
+    Path(execution_id): Path<Uuid>,
+    Json(input): Json<RerunExecutionInput>,
+) -> Result<Json<WorkflowExecutionResponse>> {
+    ensure_execution_permission(
+        &auth,
+        &[Permission::WORKFLOWS_EXECUTE],
+        "Permission denied: workflows:execute required",
+    )?;
+
+    let service = super::workflow_service(&ctx);
+    let execution = service
+        .rerun_execution(tenant.id, execution_id, input.step_id)
+        .await
+        .map_err(|err| match err {
+            crate::WorkflowError::ExecutionNotFound(_) => Error::NotFound,
+            other => Error::BadRequest(other.to_string()),
+        })?;
+    Ok(Json(execution))
+}
+
 fn ensure_execution_permission(
     auth: &AuthContext,
     permissions: &[Permission],
@@ -1,15 +1,22 @@
+use std::sync::Arc;
+
 use axum::routing::{get, post, put};
-use loco_rs::controller::Routes;
+use loco_rs::{app::AppContext, controller::Routes};
+use rustok_core::{events::EventTransport, ModuleRuntimeExtensions};
+
+use crate::{WebhookDeliveryLog, WorkflowActionRuntime, WorkflowService};
 
 pub mod executions;
 pub mod steps;
 pub mod webhook;
+pub mod webhook_subscriptions;
 pub mod workflows;
 
 pub fn routes() -> Routes {
     Routes::new()
         .prefix("api/workflows")
         .add("/", get(workflows::list).post(workflows::create))
+        .add("/actions", get(workflows::list_actions))
         .add(
             "/{id}",
             get(workflows::get)
@@ -19,6 +26,15 @@ pub fn routes() -> Routes {
         .add("/{id}/activate", post(workflows::activate))
         .add("/{id}/pause", post(workflows::pause))
         .add("/{id}/trigger", post(workflows::trigger_manual))
+        .add("/{id}/dry-run", post(workflows::dry_run))
+        .add(
+            "/{id}/webhook-secret",
+            post(workflows::rotate_webhook_secret),
+        )
+        .add(
+            "/{id}/webhook-deliveries",
+            get(executions::list_webhook_deliveries),
+        )
         .add("/{id}/steps", post(steps::add_step))
         .add(
             "/{id}/steps/{step_id}",
@@ -26,6 +42,32 @@ pub fn routes() -> Routes {
         )
         .add("/{id}/executions", get(executions::list_executions))
         .add("/executions/{execution_id}", get(executions::get_execution))
+        .add(
+            "/executions/{execution_id}/rerun",
+            post(executions::rerun_execution),
+        )
+        .add(
+            "/webhook-subscriptions",
+            get(webhook_subscriptions::list).post(webhook_subscriptions::create),
+        )
+        .add(
+            "/webhook-subscriptions/{subscription_id}",
+            get(webhook_subscriptions::get)
+                .put(webhook_subscriptions::update)
+                .delete(webhook_subscriptions::delete_subscription),
+        )
+        .add(
+            "/webhook-subscriptions/{subscription_id}/secret",
+            post(webhook_subscriptions::rotate_secret),
+        )
+        .add(
+            "/webhook-subscriptions/{subscription_id}/deliveries",
+            get(webhook_subscriptions::list_deliveries),
+        )
+        .add(
+            "/webhook-deliveries/{delivery_id}/redeliver",
+            post(webhook_subscriptions::redeliver),
+        )
 }
 
 pub fn webhook_routes() -> Routes {
@@ -33,3 +75,20 @@ pub fn webhook_routes() -> Routes {
         .prefix("webhooks")
         .add("/{tenant_slug}/{webhook_slug}", post(webhook::receive))
 }
+
+/// Module actions available to `action` steps; `None` until the host has
+/// bootstrapped both runtime extensions and the event transport.
+pub fn action_runtime_from_context(ctx: &AppContext) -> Option<WorkflowActionRuntime> {
+    let extensions = ctx.shared_store.get::<Arc<ModuleRuntimeExtensions>>()?;
+    let transport = ctx.shared_store.get::<Arc<dyn EventTransport>>()?;
+    WorkflowActionRuntime::from_extensions(&extensions, transport)
+}
+
+pub(crate) fn workflow_service(ctx: &AppContext) -> WorkflowService {
+    let service =
+        WorkflowService::new(ctx.db.clone()).with_action_runtime(action_runtime_from_context(ctx));
+    match ctx.shared_store.get::<WebhookDeliveryLog>() {
+        Some(log) => service.with_webhook_delivery_log(log),
+        None => service,
+    }
+}
@@ -7,7 +7,7 @@ use rustok_api::{has_any_effective_permission, AuthContext, TenantContext};
 use rustok_core::Permission;
 use uuid::Uuid;
 
-use crate::{CreateWorkflowStepInput, UpdateWorkflowStepInput, WorkflowService};
+use crate::{CreateWorkflowStepInput, UpdateWorkflowStepInput};
 
 pub async fn add_step(
     State(ctx): State<AppContext>,
@@ -18,9 +18,9 @@ pub async fn add_step(
 ) -> Result<Json<serde_json::Value>> {
     ensure_workflow_permission(&auth)?;
 
-    let service = WorkflowService::new(ctx.db.clone());
+    let service = super::workflow_service(&ctx);
     let step_id = service
-        .add_step(tenant.id, id, input)
+        .add_step(tenant.id, auth.security_context(), id, input)
         .await
         .map_err(|err| Error::BadRequest(err.to_string()))?;
     Ok(Json(serde_json::json!({ "id": step_id })))
@@ -35,9 +35,9 @@ pub async fn update_step(
 ) -> Result<Json<serde_json::Value>> {
     ensure_workflow_permission(&auth)?;
 
-    let service = WorkflowService::new(ctx.db.clone());
+    let service = super::workflow_service(&ctx);
     service
-        .update_step(tenant.id, id, step_id, input)
+        .update_step(tenant.id, auth.security_context(), id, step_id, input)
         .await
         .map_err(|err| Error::BadRequest(err.to_string()))?;
     Ok(Json(serde_json::json!({ "ok": true })))
@@ -51,7 +51,7 @@ pub async fn delete_step(
 ) -> Result<Json<serde_json::Value>> {
     ensure_workflow_permission(&auth)?;
 
-    let service = WorkflowService::new(ctx.db.clone());
+    let service = super::workflow_service(&ctx);
     service
         .delete_step(tenant.id, id, step_id)
         .await
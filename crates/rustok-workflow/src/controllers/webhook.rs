@@ -6,16 +6,18 @@ use axum::{
 };
 use loco_rs::{app::AppContext, Error, Result};
 use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
-use serde_json::Value;
-use tracing::{info, warn};
+use tracing::info;
 
-use crate::WorkflowService;
+use crate::services::webhook::WebhookRequest;
+use crate::WorkflowError;
 
 #[derive(serde::Serialize)]
 pub struct WebhookResponse {
     pub executions: Vec<uuid::Uuid>,
 }
 
+/// Receives a signed webhook. See [`crate::services::webhook`] for the
+/// `X-Webhook-Timestamp` / `X-Webhook-Id` / `X-Webhook-Signature` contract.
 pub async fn receive(
     State(ctx): State<AppContext>,
     Path((tenant_slug, webhook_slug)): Path<(String, String)>,
@@ -29,29 +31,27 @@ pub async fn receive(
         .map_err(|err| Error::BadRequest(err.to_string()))?
         .ok_or_else(|| Error::BadRequest(format!("Tenant not found: {tenant_slug}")))?;
 
-    let payload: Value = serde_json::from_slice(&body)
-        .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&body).into_owned()));
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    let request = WebhookRequest {
+        timestamp: header("x-webhook-timestamp"),
+        delivery_id: header("x-webhook-id"),
+        signature: header("x-webhook-signature"),
+        body: body.to_vec(),
+    };
 
-    if let Some(signature) = headers.get("x-webhook-signature") {
-        info!(
-            tenant_slug = %tenant_slug,
-            webhook_slug = %webhook_slug,
-            signature = ?signature,
-            "Workflow webhook received with signature"
-        );
-    } else {
-        warn!(
-            tenant_slug = %tenant_slug,
-            webhook_slug = %webhook_slug,
-            "Workflow webhook received without X-Webhook-Signature"
-        );
-    }
-
-    let service = WorkflowService::new(ctx.db.clone());
+    let service = super::workflow_service(&ctx);
     let executions = service
-        .trigger_by_webhook(tenant.id, &webhook_slug, payload)
+        .receive_webhook(tenant.id, &webhook_slug, request)
         .await
-        .map_err(|err| Error::BadRequest(err.to_string()))?;
+        .map_err(|err| match err {
+            WorkflowError::WebhookRejected(_) => Error::Unauthorized(err.to_string()),
+            _ => Error::BadRequest(err.to_string()),
+        })?;
 
     info!(
         tenant_slug = %tenant_slug,
@@ -0,0 +1,191 @@
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use loco_rs::{app::AppContext, Error, Result};
+use rustok_api::{has_any_effective_permission, AuthContext, TenantContext};
+use rustok_core::Permission;
+use uuid::Uuid;
+
+use crate::{
+    CreateWebhookSubscriptionInput, UpdateWebhookSubscriptionInput, WebhookSecretResponse,
+    WebhookSubscriptionDeliveryResponse, WebhookSubscriptionResponse, WebhookSubscriptionService,
+    WorkflowError,
+};
+
+pub async fn list(
+    State(ctx): State<AppContext>,
+    tenant: TenantContext,
+    auth: AuthContext,
+) -> Result<Json<Vec<WebhookSubscriptionResponse>>> {
+    ensure_webhook_permission(
+        &auth,
+        &[Permission::WEBHOOKS_LIST],
+        "Permission denied: webhooks:list required",
+    )?;
+
+    let subscriptions = WebhookSubscriptionService::new(ctx.db.clone())
+        .list(tenant.id)
+        .await
+        .map_err(map_error)?;
+    Ok(Json(subscriptions))
+}
+
+pub async fn get(
+    State(ctx): State<AppContext>,
+    tenant: TenantContext,
+    auth: AuthContext,
+    Path(subscription_id): Path<Uuid>,
+) -> Result<Json<WebhookSubscriptionResponse>> {
+    ensure_webhook_permission(
+        &auth,
+        &[Permission::WEBHOOKS_READ],
+        "Permission denied: webhooks:read required",
+    )?;
+
+    let subscription = WebhookSubscriptionService::new(ctx.db.clone())
+        .get(tenant.id, subscription_id)
+        .await
+        .map_err(map_error)?;
+    Ok(Json(subscription))
+}
+
+/// Create a subscription. The signing secret is only returned here.
+pub async fn create(
+    State(ctx): State<AppContext>,
+    tenant: TenantContext,
+    auth: AuthContext,
+    Json(input): Json<CreateWebhookSubscriptionInput>,
+) -> Result<Json<WebhookSubscriptionResponse>> {
+    ensure_webhook_permission(
+        &auth,
+        &[Permission::WEBHOOKS_CREATE],
+        "Permission denied: webhooks:create required",
+    )?;
+
+    let subscription = WebhookSubscriptionService::new(ctx.db.clone())
+        .create(tenant.id, Some(auth.user_id), input)
+        .await
+        .map_err(map_error)?;
+    Ok(Json(subscription))
+}
+
+pub async fn update(
+    State(ctx): State<AppContext>,
+    tenant: TenantContext,
+    auth: AuthContext,
+    Path(subscription_id): Path<Uuid>,
+    Json(input): Json<UpdateWebhookSubscriptionInput>,
+) -> Result<Json<WebhookSubscriptionResponse>> {
+    ensure_webhook_permission(
+        &auth,
+        &[Permission::WEBHOOKS_UPDATE],
+        "Permission denied: webhooks:update required",
+    )?;
+
+    let subscription = WebhookSubscriptionService::new(ctx.db.clone())
+        .update(tenant.id, subscription_id, input)
+        .await
+        .map_err(map_error)?;
+    Ok(Json(subscription))
+}
+
+pub async fn delete_subscription(
+    State(ctx): State<AppContext>,
+    tenant: TenantContext,
+    auth: AuthContext,
+    Path(subscription_id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>> {
+    ensure_webhook_permission(
+        &auth,
+        &[Permission::WEBHOOKS_DELETE],
+        "Permission denied: webhooks:delete required",
+    )?;
+
+    WebhookSubscriptionService::new(ctx.db.clone())
+        .delete(tenant.id, subscription_id)
+        .await
+        .map_err(map_error)?;
+    Ok(Json(serde_json::json!({ "ok": true })))
+}
+
+/// Generate a new signing secret; deliveries sent afterwards use it.
+pub async fn rotate_secret(
+    State(ctx): State<AppContext>,
+    tenant: TenantContext,
+    auth: AuthContext,
+    Path(subscription_id): Path<Uuid>,
+) -> Result<Json<WebhookSecretResponse>> {
+    ensure_webhook_permission(
+        &auth,
+        &[Permission::WEBHOOKS_UPDATE],
+        "Permission denied: webhooks:update required",
+    )?;
+
+    let secret = WebhookSubscriptionService::new(ctx.db.clone())
+        .rotate_secret(tenant.id, subscription_id)
+        .await
+        .map_err(map_error)?;
+    Ok(Json(WebhookSecretResponse { secret }))
+}
+
+/// Delivery log of a subscription with the captured request and response.
+pub async fn list_deliveries(
+    State(ctx): State<AppContext>,
+    tenant: TenantContext,
+    auth: AuthContext,
+    Path(subscription_id): Path<Uuid>,
+) -> Result<Json<Vec<WebhookSubscriptionDeliveryResponse>>> {
+    ensure_webhook_permission(
+        &auth,
+        &[Permission::WEBHOOKS_READ],
+        "Permission denied: webhooks:read required",
+    )?;
+
+    let deliveries = WebhookSubscriptionService::new(ctx.db.clone())
+        .list_deliveries(tenant.id, subscription_id)
+        .await
+        .map_err(map_error)?;
+    Ok(Json(deliveries))
+}
+
+/// Queue a delivery again, e.g. after it was dead-lettered.
+pub async fn redeliver(
+    State(ctx): State<AppContext>,
+    tenant: TenantContext,
+    auth: AuthContext,
+    Path(delivery_id): Path<Uuid>,
+) -> Result<Json<WebhookSubscriptionDeliveryResponse>> {
+    ensure_webhook_permission(
+        &auth,
+        &[Permission::WEBHOOKS_MANAGE],
+        "Permission denied: webhooks:manage required",
+    )?;
+
+    let delivery = WebhookSubscriptionService::new(ctx.db.clone())
+        .redeliver(tenant.id, delivery_id)
+        .await
+        .map_err(map_error)?;
+    Ok(Json(delivery))
+}
+
+fn map_error(err: WorkflowError) -> Error {
+    match err {
+        WorkflowError::SubscriptionNotFound(_) | WorkflowError::DeliveryNotFound(_) => {
+            Error::NotFound
+        }
+        other => Error::BadRequest(other.to_string()),
+    }
+}
+
+fn ensure_webhook_permission(
+    auth: &AuthContext,
+    permissions: &[Permission],
+    message: &str,
+) -> Result<()> {
+    if !has_any_effective_permission(&auth.permissions, permissions) {
+        return Err(Error::Unauthorized(message.to_string()));
+    }
+
+    Ok(())
+}
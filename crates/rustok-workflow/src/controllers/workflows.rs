@@ -4,14 +4,14 @@ use axum::{
 };
 use loco_rs::{app::AppContext, Error, Result};
 use rustok_api::{has_any_effective_permission, AuthContext, TenantContext};
-use rustok_core::Permission;
+use rustok_core::{ModuleActionDescriptor, Permission};
 use serde::Deserialize;
 use serde_json::Value;
 use uuid::Uuid;
 
 use crate::{
-    entities::WorkflowStatus, CreateWorkflowInput, UpdateWorkflowInput, WorkflowResponse,
-    WorkflowService, WorkflowSummary,
+    entities::WorkflowStatus, CreateWorkflowInput, DryRunWorkflowInput, UpdateWorkflowInput,
+    WebhookSecretResponse, WorkflowDryRunResponse, WorkflowResponse, WorkflowSummary,
 };
 
 pub async fn list(
@@ -25,7 +25,7 @@ pub async fn list(
         "Permission denied: workflows:list required",
     )?;
 
-    let service = WorkflowService::new(ctx.db.clone());
+    let service = super::workflow_service(&ctx);
     let workflows = service
         .list(tenant.id)
         .await
@@ -45,7 +45,7 @@ pub async fn get(
         "Permission denied: workflows:read required",
     )?;
 
-    let service = WorkflowService::new(ctx.db.clone());
+    let service = super::workflow_service(&ctx);
     let workflow = service
         .get(tenant.id, id)
         .await
@@ -65,7 +65,7 @@ pub async fn create(
         "Permission denied: workflows:create required",
     )?;
 
-    let service = WorkflowService::new(ctx.db.clone());
+    let service = super::workflow_service(&ctx);
     let id = service
         .create(tenant.id, Some(auth.user_id), input)
         .await
@@ -86,7 +86,7 @@ pub async fn update(
         "Permission denied: workflows:update required",
     )?;
 
-    let service = WorkflowService::new(ctx.db.clone());
+    let service = super::workflow_service(&ctx);
     service
         .update(tenant.id, id, Some(auth.user_id), input)
         .await
@@ -106,7 +106,7 @@ pub async fn delete_workflow(
         "Permission denied: workflows:delete required",
     )?;
 
-    let service = WorkflowService::new(ctx.db.clone());
+    let service = super::workflow_service(&ctx);
     service
         .delete(tenant.id, id)
         .await
@@ -126,7 +126,7 @@ pub async fn activate(
         "Permission denied: workflows:update required",
     )?;
 
-    let service = WorkflowService::new(ctx.db.clone());
+    let service = super::workflow_service(&ctx);
     service
         .update(
             tenant.id,
@@ -154,7 +154,7 @@ pub async fn pause(
         "Permission denied: workflows:update required",
     )?;
 
-    let service = WorkflowService::new(ctx.db.clone());
+    let service = super::workflow_service(&ctx);
     service
         .update(
             tenant.id,
@@ -191,7 +191,7 @@ pub async fn trigger_manual(
         "Permission denied: workflows:execute required",
     )?;
 
-    let service = WorkflowService::new(ctx.db.clone());
+    let service = super::workflow_service(&ctx);
     let execution_id = service
         .trigger_manual(
             tenant.id,
@@ -205,6 +205,72 @@ pub async fn trigger_manual(
     Ok(Json(serde_json::json!({ "execution_id": execution_id })))
 }
 
+/// Runs the workflow (or one of its versions) against a sample payload
+/// without persisting anything; side-effecting steps are mocked.
+pub async fn dry_run(
+    State(ctx): State<AppContext>,
+    tenant: TenantContext,
+    auth: AuthContext,
+    Path(id): Path<Uuid>,
+    Json(input): Json<DryRunWorkflowInput>,
+) -> Result<Json<WorkflowDryRunResponse>> {
+    ensure_workflow_permission(
+        &auth,
+        &[Permission::WORKFLOWS_EXECUTE],
+        "Permission denied: workflows:execute required",
+    )?;
+
+    let service = super::workflow_service(&ctx);
+    let response = service
+        .dry_run(tenant.id, id, Some(auth.user_id), input)
+        .await
+        .map_err(|err| match err {
+            crate::WorkflowError::NotFound(_) => Error::NotFound,
+            other => Error::BadRequest(other.to_string()),
+        })?;
+    Ok(Json(response))
+}
+
+/// Generate a new webhook signing secret; the previous one stays valid for a
+/// grace period. The secret is only returned here.
+pub async fn rotate_webhook_secret(
+    State(ctx): State<AppContext>,
+    tenant: TenantContext,
+    auth: AuthContext,
+    Path(id): Path<Uuid>,
+) -> Result<Json<WebhookSecretResponse>> {
+    ensure_workflow_permission(
+        &auth,
+        &[Permission::WORKFLOWS_UPDATE],
+        "Permission denied: workflows:update required",
+    )?;
+
+    let service = super::workflow_service(&ctx);
+    let secret = service
+        .rotate_webhook_secret(tenant.id, id)
+        .await
+        .map_err(|err| Error::BadRequest(err.to_string()))?;
+    Ok(Json(WebhookSecretResponse { secret }))
+}
+
+/// Module actions that `action` steps can invoke, with their input schemas.
+pub async fn list_actions(
+    State(ctx): State<AppContext>,
+    auth: AuthContext,
+) -> Result<Json<Vec<ModuleActionDescriptor>>> {
+    ensure_workflow_permission(
+        &auth,
+        &[Permission::WORKFLOWS_READ, Permission::WORKFLOWS_LIST],
+        "Permission denied: workflows:read required",
+    )?;
+
+    Ok(Json(
+        super::action_runtime_from_context(&ctx)
+            .map(|runtime| runtime.descriptors())
+            .unwrap_or_default(),
+    ))
+}
+
 fn ensure_workflow_permission(
     auth: &AuthContext,
     permissions: &[Permission],
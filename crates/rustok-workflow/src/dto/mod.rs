@@ -2,7 +2,10 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::entities::{ExecutionStatus, OnError, StepExecutionStatus, StepType, WorkflowStatus};
+use crate::entities::{
+    ApprovalStatus, ExecutionStatus, OnError, StepExecutionStatus, StepType,
+    SubscriptionDeliveryStatus, WebhookDeliveryStatus, WebhookSubscriptionStatus, WorkflowStatus,
+};
 
 // ── Workflow DTOs ──────────────────────────────────────────────────────────────
 
@@ -80,6 +83,10 @@ pub struct WorkflowVersionDetail {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateWorkflowStepInput {
     pub position: i32,
+    /// Control-flow step (`branch`, `parallel`, `for_each`) to nest the step under
+    pub parent_step_id: Option<Uuid>,
+    /// Branch of the parent: a `branch` case or a `parallel` lane name
+    pub branch: Option<String>,
     pub step_type: StepType,
     pub config: serde_json::Value,
     pub on_error: OnError,
@@ -89,6 +96,8 @@ pub struct CreateWorkflowStepInput {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpdateWorkflowStepInput {
     pub position: Option<i32>,
+    pub parent_step_id: Option<Uuid>,
+    pub branch: Option<String>,
     pub step_type: Option<StepType>,
     pub config: Option<serde_json::Value>,
     pub on_error: Option<OnError>,
@@ -99,6 +108,8 @@ pub struct UpdateWorkflowStepInput {
 pub struct WorkflowStepResponse {
     pub id: Uuid,
     pub workflow_id: Uuid,
+    pub parent_step_id: Option<Uuid>,
+    pub branch: Option<String>,
     pub position: i32,
     pub step_type: StepType,
     pub config: serde_json::Value,
@@ -127,6 +138,7 @@ pub struct WorkflowStepExecutionResponse {
     pub id: Uuid,
     pub execution_id: Uuid,
     pub step_id: Uuid,
+    pub branch: Option<String>,
     pub status: StepExecutionStatus,
     pub input: serde_json::Value,
     pub output: serde_json::Value,
@@ -135,6 +147,151 @@ pub struct WorkflowStepExecutionResponse {
     pub completed_at: Option<DateTime<Utc>>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RerunExecutionInput {
+    /// Step to re-run from; without it the execution resumes from the step
+    /// that failed.
+    pub step_id: Option<Uuid>,
+}
+
+// ── Dry-run DTOs ───────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DryRunWorkflowInput {
+    /// Version to run; the current steps when omitted
+    pub version: Option<i32>,
+    /// Sample trigger payload, placed under `payload` like a manual trigger
+    #[serde(default)]
+    pub payload: serde_json::Value,
+    /// Results of mocked steps by step id, stored where the real step stores
+    /// its result (`http_response`, the action `output_key`, `alloy_result`)
+    #[serde(default)]
+    pub mocks: std::collections::HashMap<Uuid, serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowDryRunResponse {
+    pub workflow_id: Uuid,
+    pub version: Option<i32>,
+    /// `completed`, `failed`, or `suspended` when a step would wait
+    pub status: ExecutionStatus,
+    pub context: serde_json::Value,
+    pub error: Option<String>,
+    pub steps: Vec<WorkflowDryRunStep>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowDryRunStep {
+    /// Differs from the dry-run workflow for steps of a sub-workflow
+    pub workflow_id: Uuid,
+    pub step_id: Uuid,
+    pub step_type: StepType,
+    pub branch: Option<String>,
+    pub status: StepExecutionStatus,
+    /// Whether the step's side effect was replaced by a mock
+    pub mocked: bool,
+    pub input: serde_json::Value,
+    pub output: serde_json::Value,
+    pub error: Option<String>,
+}
+
+// ── Approval DTOs ──────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowApprovalResponse {
+    pub id: Uuid,
+    pub workflow_id: Uuid,
+    pub execution_id: Uuid,
+    pub step_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub assignee_user_ids: Vec<Uuid>,
+    pub assignee_roles: Vec<String>,
+    pub status: ApprovalStatus,
+    pub escalation_level: i32,
+    pub deadline_at: Option<DateTime<Utc>>,
+    pub decided_by: Option<Uuid>,
+    pub comment: Option<String>,
+    pub decided_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+// ── Webhook DTOs ───────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowWebhookDeliveryResponse {
+    pub id: Uuid,
+    pub workflow_id: Option<Uuid>,
+    pub webhook_slug: String,
+    pub delivery_id: Option<String>,
+    pub status: WebhookDeliveryStatus,
+    pub reason: Option<String>,
+    pub execution_ids: Vec<Uuid>,
+    pub received_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookSecretResponse {
+    pub secret: String,
+}
+
+// ── Webhook subscription DTOs ──────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateWebhookSubscriptionInput {
+    pub name: String,
+    /// `http` or `https` endpoint receiving the events
+    pub url: String,
+    /// Event types to deliver: exact types (`order.paid`) or prefixes (`order.*`, `*`)
+    pub event_types: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateWebhookSubscriptionInput {
+    pub name: Option<String>,
+    pub url: Option<String>,
+    pub event_types: Option<Vec<String>>,
+    /// `active` re-enables a disabled subscription and resets its failure count
+    pub status: Option<WebhookSubscriptionStatus>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookSubscriptionResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub url: String,
+    pub event_types: Vec<String>,
+    pub status: WebhookSubscriptionStatus,
+    pub consecutive_failures: i32,
+    pub disabled_at: Option<DateTime<Utc>>,
+    pub disabled_reason: Option<String>,
+    /// Signing secret; only returned when the subscription is created
+    pub secret: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookSubscriptionDeliveryResponse {
+    pub id: Uuid,
+    pub subscription_id: Uuid,
+    pub event_id: Uuid,
+    pub event_type: String,
+    pub status: SubscriptionDeliveryStatus,
+    pub attempts: i32,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub request_headers: serde_json::Value,
+    pub request_body: Option<String>,
+    pub response_status: Option<i32>,
+    pub response_body: Option<String>,
+    pub error: Option<String>,
+    pub duration_ms: Option<i64>,
+    pub last_attempt_at: Option<DateTime<Utc>>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
 // ── Trigger config helpers ─────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
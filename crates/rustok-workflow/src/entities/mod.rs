@@ -1,12 +1,30 @@
+pub mod webhook_subscription;
+pub mod webhook_subscription_delivery;
 pub mod workflow;
+pub mod workflow_approval;
 pub mod workflow_execution;
 pub mod workflow_step;
 pub mod workflow_step_execution;
+pub mod workflow_suspension;
 pub mod workflow_version;
+pub mod workflow_webhook_delivery;
 
+pub use webhook_subscription::{
+    ActiveModel as WebhookSubscriptionActiveModel, Entity as WebhookSubscriptionEntity,
+    Model as WebhookSubscription, WebhookSubscriptionStatus,
+};
+pub use webhook_subscription_delivery::{
+    ActiveModel as WebhookSubscriptionDeliveryActiveModel,
+    Entity as WebhookSubscriptionDeliveryEntity, Model as WebhookSubscriptionDelivery,
+    SubscriptionDeliveryStatus,
+};
 pub use workflow::{
     ActiveModel as WorkflowActiveModel, Entity as WorkflowEntity, Model as Workflow, WorkflowStatus,
 };
+pub use workflow_approval::{
+    ActiveModel as WorkflowApprovalActiveModel, ApprovalStatus, Entity as WorkflowApprovalEntity,
+    Model as WorkflowApproval,
+};
 pub use workflow_execution::{
     ActiveModel as WorkflowExecutionActiveModel, Entity as WorkflowExecutionEntity,
     ExecutionStatus, Model as WorkflowExecution,
@@ -19,7 +37,15 @@ pub use workflow_step_execution::{
     ActiveModel as WorkflowStepExecutionActiveModel, Entity as WorkflowStepExecutionEntity,
     Model as WorkflowStepExecution, StepExecutionStatus,
 };
+pub use workflow_suspension::{
+    ActiveModel as WorkflowSuspensionActiveModel, Entity as WorkflowSuspensionEntity,
+    Model as WorkflowSuspension,
+};
 pub use workflow_version::{
     ActiveModel as WorkflowVersionActiveModel, Entity as WorkflowVersionEntity,
     Model as WorkflowVersion,
 };
+pub use workflow_webhook_delivery::{
+    ActiveModel as WorkflowWebhookDeliveryActiveModel, Entity as WorkflowWebhookDeliveryEntity,
+    Model as WorkflowWebhookDelivery, WebhookDeliveryStatus,
+};
@@ -0,0 +1,51 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(32))")]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookSubscriptionStatus {
+    #[sea_orm(string_value = "active")]
+    Active,
+    /// Disabled by a tenant admin or automatically after repeated failures;
+    /// no new deliveries are queued and pending ones are held.
+    #[sea_orm(string_value = "disabled")]
+    Disabled,
+}
+
+impl std::fmt::Display for WebhookSubscriptionStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Active => write!(f, "active"),
+            Self::Disabled => write!(f, "disabled"),
+        }
+    }
+}
+
+/// A tenant-managed endpoint that receives domain events as signed webhooks.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "webhook_subscriptions")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub name: String,
+    pub url: String,
+    /// Event type patterns: exact types or a `*`-suffixed prefix
+    pub event_types: Json,
+    /// HMAC-SHA256 secret signing every delivery (X-Webhook-Signature header)
+    pub secret: String,
+    pub status: WebhookSubscriptionStatus,
+    /// Failed attempts since the last successful delivery
+    pub consecutive_failures: i32,
+    pub disabled_at: Option<DateTimeWithTimeZone>,
+    pub disabled_reason: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,62 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(32))")]
+#[serde(rename_all = "snake_case")]
+pub enum SubscriptionDeliveryStatus {
+    /// Waiting for its first attempt or a retry at `next_attempt_at`.
+    #[sea_orm(string_value = "pending")]
+    Pending,
+    #[sea_orm(string_value = "delivered")]
+    Delivered,
+    /// Every attempt failed; only a manual redelivery sends it again.
+    #[sea_orm(string_value = "dead_letter")]
+    DeadLetter,
+}
+
+impl std::fmt::Display for SubscriptionDeliveryStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pending => write!(f, "pending"),
+            Self::Delivered => write!(f, "delivered"),
+            Self::DeadLetter => write!(f, "dead_letter"),
+        }
+    }
+}
+
+/// One domain event queued for one webhook subscription, with the request
+/// and response of its latest attempt.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "webhook_subscription_deliveries")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub subscription_id: Uuid,
+    /// Outbox event id; unique per subscription so a re-dispatched event is
+    /// queued once
+    pub event_id: Uuid,
+    pub event_type: String,
+    /// Document sent as the request body
+    pub payload: Json,
+    pub status: SubscriptionDeliveryStatus,
+    pub attempts: i32,
+    pub next_attempt_at: Option<DateTimeWithTimeZone>,
+    pub request_headers: Json,
+    pub request_body: Option<String>,
+    pub response_status: Option<i32>,
+    /// Truncated response body
+    pub response_body: Option<String>,
+    /// Transport error or non-2xx summary of the latest attempt
+    pub error: Option<String>,
+    pub duration_ms: Option<i64>,
+    pub last_attempt_at: Option<DateTimeWithTimeZone>,
+    pub delivered_at: Option<DateTimeWithTimeZone>,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -48,6 +48,9 @@ pub struct Model {
     pub webhook_slug: Option<String>,
     /// HMAC-SHA256 secret for verifying webhook payloads (X-Webhook-Signature header)
     pub webhook_secret: Option<String>,
+    /// Secret replaced by the last rotation; still accepted for a grace period
+    pub webhook_previous_secret: Option<String>,
+    pub webhook_secret_rotated_at: Option<DateTimeWithTimeZone>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -0,0 +1,78 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(32))")]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalStatus {
+    #[sea_orm(string_value = "pending")]
+    Pending,
+    #[sea_orm(string_value = "approved")]
+    Approved,
+    #[sea_orm(string_value = "rejected")]
+    Rejected,
+    /// Auto-rejected because the final deadline passed without a decision
+    #[sea_orm(string_value = "expired")]
+    Expired,
+}
+
+impl std::fmt::Display for ApprovalStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pending => write!(f, "pending"),
+            Self::Approved => write!(f, "approved"),
+            Self::Rejected => write!(f, "rejected"),
+            Self::Expired => write!(f, "expired"),
+        }
+    }
+}
+
+/// A human approval task created by an `approval` step.
+///
+/// One row per suspended step execution; escalation reassigns the same row.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "workflow_approvals")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    pub workflow_id: Uuid,
+    pub execution_id: Uuid,
+    pub step_id: Uuid,
+    #[sea_orm(unique)]
+    pub step_execution_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    /// User ids allowed to decide
+    pub assignee_user_ids: Json,
+    /// RBAC roles (`admin`, `manager`, …) allowed to decide
+    pub assignee_roles: Json,
+    pub status: ApprovalStatus,
+    /// Number of times the task was escalated to other assignees
+    pub escalation_level: i32,
+    /// Escalation or auto-rejection happens at this instant
+    pub deadline_at: Option<DateTimeWithTimeZone>,
+    pub decided_by: Option<Uuid>,
+    pub comment: Option<String>,
+    pub decided_at: Option<DateTimeWithTimeZone>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::workflow_execution::Entity",
+        from = "Column::ExecutionId",
+        to = "super::workflow_execution::Column::Id"
+    )]
+    Execution,
+}
+
+impl Related<super::workflow_execution::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Execution.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -13,6 +13,9 @@ pub enum ExecutionStatus {
     Failed,
     #[sea_orm(string_value = "timed_out")]
     TimedOut,
+    /// Parked on a durable delay or event wait; see `workflow_suspensions`.
+    #[sea_orm(string_value = "suspended")]
+    Suspended,
 }
 
 impl std::fmt::Display for ExecutionStatus {
@@ -22,6 +25,7 @@ impl std::fmt::Display for ExecutionStatus {
             Self::Completed => write!(f, "completed"),
             Self::Failed => write!(f, "failed"),
             Self::TimedOut => write!(f, "timed_out"),
+            Self::Suspended => write!(f, "suspended"),
         }
     }
 }
@@ -21,6 +21,33 @@ pub enum StepType {
     Notify,
     #[sea_orm(string_value = "transform")]
     Transform,
+    #[sea_orm(string_value = "wait_for_event")]
+    WaitForEvent,
+    #[sea_orm(string_value = "approval")]
+    Approval,
+    #[sea_orm(string_value = "branch")]
+    Branch,
+    #[sea_orm(string_value = "parallel")]
+    Parallel,
+    #[sea_orm(string_value = "for_each")]
+    ForEach,
+    #[sea_orm(string_value = "sub_workflow")]
+    SubWorkflow,
+}
+
+impl StepType {
+    /// Control-flow steps are run by the engine itself; `branch`, `parallel`
+    /// and `for_each` own child steps via `parent_step_id`.
+    pub fn is_control_flow(&self) -> bool {
+        matches!(
+            self,
+            Self::Branch | Self::Parallel | Self::ForEach | Self::SubWorkflow
+        )
+    }
+
+    pub fn has_children(&self) -> bool {
+        matches!(self, Self::Branch | Self::Parallel | Self::ForEach)
+    }
 }
 
 impl std::fmt::Display for StepType {
@@ -34,6 +61,12 @@ impl std::fmt::Display for StepType {
             Self::Http => write!(f, "http"),
             Self::Notify => write!(f, "notify"),
             Self::Transform => write!(f, "transform"),
+            Self::WaitForEvent => write!(f, "wait_for_event"),
+            Self::Approval => write!(f, "approval"),
+            Self::Branch => write!(f, "branch"),
+            Self::Parallel => write!(f, "parallel"),
+            Self::ForEach => write!(f, "for_each"),
+            Self::SubWorkflow => write!(f, "sub_workflow"),
         }
     }
 }
@@ -66,6 +99,11 @@ pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
     pub id: Uuid,
     pub workflow_id: Uuid,
+    /// Control-flow step this step belongs to; `None` for the top-level sequence
+    pub parent_step_id: Option<Uuid>,
+    /// Branch of the parent the step runs in (`then`, a switch case, a parallel lane)
+    pub branch: Option<String>,
+    /// Order within the parent branch (or the top-level sequence)
     pub position: i32,
     pub step_type: StepType,
     /// Step-specific configuration as JSONB
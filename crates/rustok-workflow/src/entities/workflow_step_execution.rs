@@ -15,6 +15,8 @@ pub enum StepExecutionStatus {
     Failed,
     #[sea_orm(string_value = "skipped")]
     Skipped,
+    #[sea_orm(string_value = "waiting")]
+    Waiting,
 }
 
 impl std::fmt::Display for StepExecutionStatus {
@@ -25,6 +27,7 @@ impl std::fmt::Display for StepExecutionStatus {
             Self::Completed => write!(f, "completed"),
             Self::Failed => write!(f, "failed"),
             Self::Skipped => write!(f, "skipped"),
+            Self::Waiting => write!(f, "waiting"),
         }
     }
 }
@@ -36,6 +39,10 @@ pub struct Model {
     pub id: Uuid,
     pub execution_id: Uuid,
     pub step_id: Uuid,
+    /// Branch the step ran in: the chosen case for steps inside a `branch`,
+    /// the lane for `parallel`, `item[N]` for a `for_each` iteration;
+    /// `None` for the top-level sequence
+    pub branch: Option<String>,
     pub status: StepExecutionStatus,
     pub input: Json,
     pub output: Json,
@@ -0,0 +1,43 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A suspended execution waiting for a timer and/or a correlated domain event.
+///
+/// The row exists only while the execution is parked; whoever deletes it
+/// first owns the resume.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "workflow_suspensions")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub execution_id: Uuid,
+    pub tenant_id: Uuid,
+    pub workflow_id: Uuid,
+    /// Step the execution is parked on
+    pub step_id: Uuid,
+    pub step_execution_id: Uuid,
+    /// Resume at this instant (delay end or wait timeout)
+    pub resume_at: Option<DateTimeWithTimeZone>,
+    /// Resume early when an event of this type arrives
+    pub event_type: Option<String>,
+    /// Event paths and the values they must equal, e.g. `{"payload.order_id": "…"}`
+    pub correlation: Json,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::workflow_execution::Entity",
+        from = "Column::ExecutionId",
+        to = "super::workflow_execution::Column::Id"
+    )]
+    Execution,
+}
+
+impl Related<super::workflow_execution::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Execution.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,50 @@
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(32))")]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookDeliveryStatus {
+    #[sea_orm(string_value = "accepted")]
+    Accepted,
+    #[sea_orm(string_value = "rejected")]
+    Rejected,
+}
+
+impl std::fmt::Display for WebhookDeliveryStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Accepted => write!(f, "accepted"),
+            Self::Rejected => write!(f, "rejected"),
+        }
+    }
+}
+
+/// One incoming webhook request, accepted or rejected, kept for diagnostics
+/// and replay protection.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "workflow_webhook_deliveries")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    pub tenant_id: Uuid,
+    /// `None` when the slug matched no workflow
+    pub workflow_id: Option<Uuid>,
+    pub webhook_slug: String,
+    /// Sender's `X-Webhook-Id`
+    pub delivery_id: Option<String>,
+    /// Delivery id of accepted requests; unique per workflow so a replayed
+    /// delivery cannot be accepted twice
+    pub replay_key: Option<String>,
+    pub status: WebhookDeliveryStatus,
+    /// Why the request was rejected
+    pub reason: Option<String>,
+    /// Executions started by an accepted request
+    pub execution_ids: Json,
+    pub received_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
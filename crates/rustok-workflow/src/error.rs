@@ -12,6 +12,30 @@ pub enum WorkflowError {
     #[error("Workflow execution not found: {0}")]
     ExecutionNotFound(Uuid),
 
+    #[error("Workflow version not found: {0}")]
+    VersionNotFound(i32),
+
+    #[error("Workflow execution {0} cannot be re-run: {1}")]
+    NotRerunnable(Uuid, String),
+
+    #[error("Webhook subscription not found: {0}")]
+    SubscriptionNotFound(Uuid),
+
+    #[error("Webhook delivery not found: {0}")]
+    DeliveryNotFound(Uuid),
+
+    #[error("Invalid webhook subscription: {0}")]
+    InvalidSubscription(String),
+
+    #[error("Workflow approval not found: {0}")]
+    ApprovalNotFound(Uuid),
+
+    #[error("Workflow approval {0} is already closed")]
+    ApprovalClosed(Uuid),
+
+    #[error("Not an assignee of workflow approval {0}")]
+    NotApprover(Uuid),
+
     #[error("Workflow is not active (status: {0})")]
     NotActive(String),
 
@@ -27,6 +51,15 @@ pub enum WorkflowError {
     #[error("Invalid step config: {0}")]
     InvalidStepConfig(String),
 
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
+
+    #[error("Webhook rejected: {0}")]
+    WebhookRejected(String),
+
+    #[error("Invalid webhook payload: {0}")]
+    InvalidWebhookPayload(String),
+
     #[error("Database error: {0}")]
     Database(#[from] sea_orm::DbErr),
 
@@ -2,9 +2,15 @@ mod mutation;
 mod query;
 mod types;
 
+use std::sync::Arc;
+
 use async_graphql::{Context, FieldError, Result};
 use rustok_api::{graphql::GraphQLError, has_any_effective_permission, AuthContext};
-use rustok_core::Permission;
+use rustok_core::{ModuleRuntimeExtensions, Permission};
+use rustok_outbox::TransactionalEventBus;
+use sea_orm::DatabaseConnection;
+
+use crate::{WorkflowActionRuntime, WorkflowService};
 
 pub use mutation::WorkflowMutation;
 pub use query::WorkflowQuery;
@@ -12,15 +18,32 @@ pub use types::*;
 
 pub(crate) const MODULE_SLUG: &str = "workflow";
 
+/// `WorkflowService` with module actions attached when the schema carries
+/// runtime extensions and an event bus.
+pub(crate) fn workflow_service(ctx: &Context<'_>, db: &DatabaseConnection) -> WorkflowService {
+    let action_runtime = ctx
+        .data_opt::<Arc<ModuleRuntimeExtensions>>()
+        .zip(ctx.data_opt::<TransactionalEventBus>())
+        .and_then(|(extensions, event_bus)| {
+            WorkflowActionRuntime::from_extensions(extensions, event_bus.transport())
+        });
+    WorkflowService::new(db.clone()).with_action_runtime(action_runtime)
+}
+
+/// The caller's auth context; approval tasks are guarded by assignment
+/// rather than by a workflow permission.
+pub(crate) fn require_workflow_auth(ctx: &Context<'_>) -> Result<AuthContext> {
+    ctx.data::<AuthContext>()
+        .cloned()
+        .map_err(|_| <FieldError as GraphQLError>::unauthenticated())
+}
+
 pub(crate) fn require_workflow_permission(
     ctx: &Context<'_>,
     permissions: &[Permission],
     message: &str,
 ) -> Result<AuthContext> {
-    let auth = ctx
-        .data::<AuthContext>()
-        .map_err(|_| <FieldError as GraphQLError>::unauthenticated())?
-        .clone();
+    let auth = require_workflow_auth(ctx)?;
 
     if !has_any_effective_permission(&auth.permissions, permissions) {
         return Err(<FieldError as GraphQLError>::permission_denied(message));
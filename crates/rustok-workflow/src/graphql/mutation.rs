@@ -1,5 +1,6 @@
-use async_graphql::{Context, Object, Result};
-use rustok_api::graphql::require_module_enabled;
+use async_graphql::{Context, FieldError, Object, Result};
+use rustok_api::graphql::{require_module_enabled, GraphQLError};
+use rustok_api::infer_user_role_from_permissions;
 use rustok_core::Permission;
 use sea_orm::DatabaseConnection;
 use serde_json::Value;
@@ -7,11 +8,14 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::{
-    entities::WorkflowStatus, CreateWorkflowInput, CreateWorkflowStepInput, UpdateWorkflowInput,
-    UpdateWorkflowStepInput, WorkflowService,
+    entities::WorkflowStatus, CreateWebhookSubscriptionInput, CreateWorkflowInput,
+    CreateWorkflowStepInput, DryRunWorkflowInput, UpdateWebhookSubscriptionInput,
+    UpdateWorkflowInput, UpdateWorkflowStepInput, WebhookSubscriptionService,
 };
 
-use super::{require_workflow_permission, types::*, MODULE_SLUG};
+use super::{
+    require_workflow_auth, require_workflow_permission, types::*, workflow_service, MODULE_SLUG,
+};
 
 #[derive(Default)]
 pub struct WorkflowMutation;
@@ -32,7 +36,7 @@ impl WorkflowMutation {
             "Permission denied: workflows:create required",
         )?;
 
-        let service = WorkflowService::new(db.clone());
+        let service = workflow_service(ctx, db);
         service
             .create(
                 tenant.id,
@@ -63,7 +67,7 @@ impl WorkflowMutation {
             "Permission denied: workflows:update required",
         )?;
 
-        let service = WorkflowService::new(db.clone());
+        let service = workflow_service(ctx, db);
         service
             .update(
                 tenant.id,
@@ -93,7 +97,7 @@ impl WorkflowMutation {
             "Permission denied: workflows:delete required",
         )?;
 
-        let service = WorkflowService::new(db.clone());
+        let service = workflow_service(ctx, db);
         service
             .delete(tenant.id, id)
             .await
@@ -112,7 +116,7 @@ impl WorkflowMutation {
             "Permission denied: workflows:update required",
         )?;
 
-        let service = WorkflowService::new(db.clone());
+        let service = workflow_service(ctx, db);
         service
             .update(
                 tenant.id,
@@ -129,6 +133,25 @@ impl WorkflowMutation {
         Ok(true)
     }
 
+    /// Generate a new webhook signing secret and return it; the replaced
+    /// secret stays valid for a grace period.
+    async fn rotate_workflow_webhook_secret(&self, ctx: &Context<'_>, id: Uuid) -> Result<String> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let tenant = ctx.data::<rustok_api::TenantContext>()?;
+        require_workflow_permission(
+            ctx,
+            &[Permission::WORKFLOWS_UPDATE],
+            "Permission denied: workflows:update required",
+        )?;
+
+        let service = workflow_service(ctx, db);
+        service
+            .rotate_webhook_secret(tenant.id, id)
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))
+    }
+
     async fn pause_workflow(&self, ctx: &Context<'_>, id: Uuid) -> Result<bool> {
         require_module_enabled(ctx, MODULE_SLUG).await?;
         let db = ctx.data::<DatabaseConnection>()?;
@@ -139,7 +162,7 @@ impl WorkflowMutation {
             "Permission denied: workflows:update required",
         )?;
 
-        let service = WorkflowService::new(db.clone());
+        let service = workflow_service(ctx, db);
         service
             .update(
                 tenant.id,
@@ -172,7 +195,7 @@ impl WorkflowMutation {
             "Permission denied: workflows:execute required",
         )?;
 
-        let service = WorkflowService::new(db.clone());
+        let service = workflow_service(ctx, db);
         service
             .trigger_manual(
                 tenant.id,
@@ -185,6 +208,96 @@ impl WorkflowMutation {
             .map_err(|err| async_graphql::Error::new(err.to_string()))
     }
 
+    /// Run a workflow, or one of its versions, against a sample payload
+    /// without persisting anything. `mocks` maps step ids to the results of
+    /// mocked side-effecting steps.
+    async fn dry_run_workflow(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+        version: Option<i32>,
+        payload: Option<Value>,
+        mocks: Option<Value>,
+    ) -> Result<GqlWorkflowDryRun> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let tenant = ctx.data::<rustok_api::TenantContext>()?;
+        let auth = require_workflow_permission(
+            ctx,
+            &[Permission::WORKFLOWS_EXECUTE],
+            "Permission denied: workflows:execute required",
+        )?;
+
+        let mocks = match mocks {
+            Some(mocks) => serde_json::from_value(mocks).map_err(|err| {
+                async_graphql::Error::new(format!("mocks must map step ids to values: {err}"))
+            })?,
+            None => Default::default(),
+        };
+        let service = workflow_service(ctx, db);
+        service
+            .dry_run(
+                tenant.id,
+                id,
+                Some(auth.user_id),
+                DryRunWorkflowInput {
+                    version,
+                    payload: payload.unwrap_or_default(),
+                    mocks,
+                },
+            )
+            .await
+            .map(Into::into)
+            .map_err(|err| async_graphql::Error::new(err.to_string()))
+    }
+
+    /// Resume a failed execution from its failing step or, with `step_id`,
+    /// re-run a finished execution from that step.
+    async fn rerun_workflow_execution(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+        step_id: Option<Uuid>,
+    ) -> Result<GqlWorkflowExecution> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let tenant = ctx.data::<rustok_api::TenantContext>()?;
+        require_workflow_permission(
+            ctx,
+            &[Permission::WORKFLOWS_EXECUTE],
+            "Permission denied: workflows:execute required",
+        )?;
+
+        let service = workflow_service(ctx, db);
+        service
+            .rerun_execution(tenant.id, id, step_id)
+            .await
+            .map(Into::into)
+            .map_err(|err| async_graphql::Error::new(err.to_string()))
+    }
+
+    /// Approve a pending approval task assigned to the caller and resume
+    /// its execution.
+    async fn approve_workflow_approval(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+        comment: Option<String>,
+    ) -> Result<GqlWorkflowApproval> {
+        decide_workflow_approval(ctx, id, true, comment).await
+    }
+
+    /// Reject a pending approval task assigned to the caller and resume its
+    /// execution.
+    async fn reject_workflow_approval(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+        comment: Option<String>,
+    ) -> Result<GqlWorkflowApproval> {
+        decide_workflow_approval(ctx, id, false, comment).await
+    }
+
     async fn add_workflow_step(
         &self,
         ctx: &Context<'_>,
@@ -194,19 +307,22 @@ impl WorkflowMutation {
         require_module_enabled(ctx, MODULE_SLUG).await?;
         let db = ctx.data::<DatabaseConnection>()?;
         let tenant = ctx.data::<rustok_api::TenantContext>()?;
-        require_workflow_permission(
+        let auth = require_workflow_permission(
             ctx,
             &[Permission::WORKFLOWS_UPDATE],
             "Permission denied: workflows:update required",
         )?;
 
-        let service = WorkflowService::new(db.clone());
+        let service = workflow_service(ctx, db);
         service
             .add_step(
                 tenant.id,
+                auth.security_context(),
                 workflow_id,
                 CreateWorkflowStepInput {
                     position: input.position,
+                    parent_step_id: input.parent_step_id,
+                    branch: input.branch,
                     step_type: input.step_type.into(),
                     config: input.config,
                     on_error: input.on_error.into(),
@@ -227,20 +343,23 @@ impl WorkflowMutation {
         require_module_enabled(ctx, MODULE_SLUG).await?;
         let db = ctx.data::<DatabaseConnection>()?;
         let tenant = ctx.data::<rustok_api::TenantContext>()?;
-        require_workflow_permission(
+        let auth = require_workflow_permission(
             ctx,
             &[Permission::WORKFLOWS_UPDATE],
             "Permission denied: workflows:update required",
         )?;
 
-        let service = WorkflowService::new(db.clone());
+        let service = workflow_service(ctx, db);
         service
             .update_step(
                 tenant.id,
+                auth.security_context(),
                 workflow_id,
                 step_id,
                 UpdateWorkflowStepInput {
                     position: input.position,
+                    parent_step_id: input.parent_step_id,
+                    branch: input.branch,
                     step_type: input.step_type.map(Into::into),
                     config: input.config,
                     on_error: input.on_error.map(Into::into),
@@ -268,7 +387,7 @@ impl WorkflowMutation {
             "Permission denied: workflows:update required",
         )?;
 
-        let service = WorkflowService::new(db.clone());
+        let service = workflow_service(ctx, db);
         service
             .delete_step(tenant.id, workflow_id, step_id)
             .await
@@ -292,9 +411,9 @@ impl WorkflowMutation {
             "Permission denied: workflows:create required",
         )?;
 
-        let service = WorkflowService::new(db.clone());
+        let service = workflow_service(ctx, db);
         service
-            .create_from_template(tenant.id, Some(auth.user_id), &template_id, name)
+            .create_from_template(tenant.id, auth.security_context(), &template_id, name)
             .await
             .map_err(|err| async_graphql::Error::new(err.to_string()))
     }
@@ -314,9 +433,9 @@ impl WorkflowMutation {
             "Permission denied: workflows:update required",
         )?;
 
-        let service = WorkflowService::new(db.clone());
+        let service = workflow_service(ctx, db);
         service
-            .restore_version(tenant.id, workflow_id, version, Some(auth.user_id))
+            .restore_version(tenant.id, workflow_id, version, auth.security_context())
             .await
             .map_err(|err| async_graphql::Error::new(err.to_string()))?;
 
@@ -352,7 +471,7 @@ impl WorkflowMutation {
             .cloned()
             .unwrap_or_else(|| serde_json::json!({ "type": "manual" }));
 
-        let service = WorkflowService::new(db.clone());
+        let service = workflow_service(ctx, db);
         service
             .create(
                 tenant.id,
@@ -374,6 +493,128 @@ impl WorkflowMutation {
             .await
             .map_err(|err| async_graphql::Error::new(err.to_string()))
     }
+
+    /// Create a webhook subscription; the signing secret is only returned here.
+    async fn create_webhook_subscription(
+        &self,
+        ctx: &Context<'_>,
+        input: GqlCreateWebhookSubscriptionInput,
+    ) -> Result<GqlWebhookSubscription> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let tenant = ctx.data::<rustok_api::TenantContext>()?;
+        let auth = require_workflow_permission(
+            ctx,
+            &[Permission::WEBHOOKS_CREATE],
+            "Permission denied: webhooks:create required",
+        )?;
+
+        WebhookSubscriptionService::new(db.clone())
+            .create(
+                tenant.id,
+                Some(auth.user_id),
+                CreateWebhookSubscriptionInput {
+                    name: input.name,
+                    url: input.url,
+                    event_types: input.event_types,
+                },
+            )
+            .await
+            .map(Into::into)
+            .map_err(|err| async_graphql::Error::new(err.to_string()))
+    }
+
+    async fn update_webhook_subscription(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+        input: GqlUpdateWebhookSubscriptionInput,
+    ) -> Result<GqlWebhookSubscription> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let tenant = ctx.data::<rustok_api::TenantContext>()?;
+        require_workflow_permission(
+            ctx,
+            &[Permission::WEBHOOKS_UPDATE],
+            "Permission denied: webhooks:update required",
+        )?;
+
+        WebhookSubscriptionService::new(db.clone())
+            .update(
+                tenant.id,
+                id,
+                UpdateWebhookSubscriptionInput {
+                    name: input.name,
+                    url: input.url,
+                    event_types: input.event_types,
+                    status: input.status.map(Into::into),
+                },
+            )
+            .await
+            .map(Into::into)
+            .map_err(|err| async_graphql::Error::new(err.to_string()))
+    }
+
+    async fn delete_webhook_subscription(&self, ctx: &Context<'_>, id: Uuid) -> Result<bool> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let tenant = ctx.data::<rustok_api::TenantContext>()?;
+        require_workflow_permission(
+            ctx,
+            &[Permission::WEBHOOKS_DELETE],
+            "Permission denied: webhooks:delete required",
+        )?;
+
+        WebhookSubscriptionService::new(db.clone())
+            .delete(tenant.id, id)
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))?;
+
+        Ok(true)
+    }
+
+    /// Generate a new signing secret for a webhook subscription and return it.
+    async fn rotate_webhook_subscription_secret(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+    ) -> Result<String> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let tenant = ctx.data::<rustok_api::TenantContext>()?;
+        require_workflow_permission(
+            ctx,
+            &[Permission::WEBHOOKS_UPDATE],
+            "Permission denied: webhooks:update required",
+        )?;
+
+        WebhookSubscriptionService::new(db.clone())
+            .rotate_secret(tenant.id, id)
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))
+    }
+
+    /// Queue a webhook delivery again with a fresh attempt budget.
+    async fn redeliver_webhook(
+        &self,
+        ctx: &Context<'_>,
+        delivery_id: Uuid,
+    ) -> Result<GqlWebhookSubscriptionDelivery> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let tenant = ctx.data::<rustok_api::TenantContext>()?;
+        require_workflow_permission(
+            ctx,
+            &[Permission::WEBHOOKS_MANAGE],
+            "Permission denied: webhooks:manage required",
+        )?;
+
+        WebhookSubscriptionService::new(db.clone())
+            .redeliver(tenant.id, delivery_id)
+            .await
+            .map(Into::into)
+            .map_err(|err| async_graphql::Error::new(err.to_string()))
+    }
 }
 
 fn default_generated_workflow(description: &str) -> serde_json::Value {
@@ -384,3 +625,28 @@ fn default_generated_workflow(description: &str) -> serde_json::Value {
         "steps": []
     })
 }
+
+async fn decide_workflow_approval(
+    ctx: &Context<'_>,
+    id: Uuid,
+    approved: bool,
+    comment: Option<String>,
+) -> Result<GqlWorkflowApproval> {
+    require_module_enabled(ctx, MODULE_SLUG).await?;
+    let db = ctx.data::<DatabaseConnection>()?;
+    let tenant = ctx.data::<rustok_api::TenantContext>()?;
+    let auth = require_workflow_auth(ctx)?;
+    let role = infer_user_role_from_permissions(&auth.permissions);
+
+    let service = workflow_service(ctx, db);
+    match service
+        .decide_approval(tenant.id, id, auth.user_id, &role, approved, comment)
+        .await
+    {
+        Ok(approval) => Ok(approval.into()),
+        Err(err @ crate::WorkflowError::NotApprover(_)) => Err(
+            <FieldError as GraphQLError>::permission_denied(&err.to_string()),
+        ),
+        Err(err) => Err(async_graphql::Error::new(err.to_string())),
+    }
+}
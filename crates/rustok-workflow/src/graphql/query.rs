@@ -1,12 +1,16 @@
 use async_graphql::{Context, Object, Result};
-use rustok_api::{graphql::require_module_enabled, TenantContext};
+use rustok_api::{
+    graphql::require_module_enabled, infer_user_role_from_permissions, TenantContext,
+};
 use rustok_core::Permission;
 use sea_orm::DatabaseConnection;
 use uuid::Uuid;
 
-use crate::WorkflowService;
+use crate::WebhookSubscriptionService;
 
-use super::{require_workflow_permission, types::*, MODULE_SLUG};
+use super::{
+    require_workflow_auth, require_workflow_permission, types::*, workflow_service, MODULE_SLUG,
+};
 
 #[derive(Default)]
 pub struct WorkflowQuery;
@@ -24,7 +28,7 @@ impl WorkflowQuery {
             "Permission denied: workflows:list required",
         )?;
 
-        let service = WorkflowService::new(db.clone());
+        let service = workflow_service(ctx, db);
         let workflows = service
             .list(tenant.id)
             .await
@@ -44,7 +48,7 @@ impl WorkflowQuery {
             "Permission denied: workflows:read required",
         )?;
 
-        let service = WorkflowService::new(db.clone());
+        let service = workflow_service(ctx, db);
         match service.get(tenant.id, id).await {
             Ok(workflow) => Ok(Some(workflow.into())),
             Err(crate::WorkflowError::NotFound(_)) => Ok(None),
@@ -67,7 +71,7 @@ impl WorkflowQuery {
             "Permission denied: workflow_executions:list required",
         )?;
 
-        let service = WorkflowService::new(db.clone());
+        let service = workflow_service(ctx, db);
         let executions = service
             .list_executions(tenant.id, workflow_id)
             .await
@@ -76,6 +80,31 @@ impl WorkflowQuery {
         Ok(executions.into_iter().map(Into::into).collect())
     }
 
+    /// Recent accepted and rejected webhook requests of a workflow.
+    async fn workflow_webhook_deliveries(
+        &self,
+        ctx: &Context<'_>,
+        workflow_id: Uuid,
+    ) -> Result<Vec<GqlWorkflowWebhookDelivery>> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let tenant = ctx.data::<TenantContext>()?;
+
+        require_workflow_permission(
+            ctx,
+            &[Permission::WORKFLOW_EXECUTIONS_LIST],
+            "Permission denied: workflow_executions:list required",
+        )?;
+
+        let service = workflow_service(ctx, db);
+        let deliveries = service
+            .list_webhook_deliveries(tenant.id, workflow_id)
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))?;
+
+        Ok(deliveries.into_iter().map(Into::into).collect())
+    }
+
     async fn workflow_execution(
         &self,
         ctx: &Context<'_>,
@@ -91,7 +120,7 @@ impl WorkflowQuery {
             "Permission denied: workflow_executions:read required",
         )?;
 
-        let service = WorkflowService::new(db.clone());
+        let service = workflow_service(ctx, db);
         match service.get_execution(tenant.id, execution_id).await {
             Ok(execution) => Ok(Some(execution.into())),
             Err(crate::WorkflowError::ExecutionNotFound(_)) => Ok(None),
@@ -99,6 +128,40 @@ impl WorkflowQuery {
         }
     }
 
+    /// Approval tasks of the tenant. With `assignedToMe` only tasks the
+    /// caller may decide are returned and no workflow permission is needed.
+    async fn workflow_approvals(
+        &self,
+        ctx: &Context<'_>,
+        status: Option<GqlApprovalStatus>,
+        assigned_to_me: Option<bool>,
+    ) -> Result<Vec<GqlWorkflowApproval>> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let tenant = ctx.data::<TenantContext>()?;
+
+        let assignee = if assigned_to_me.unwrap_or(false) {
+            let auth = require_workflow_auth(ctx)?;
+            let role = infer_user_role_from_permissions(&auth.permissions);
+            Some((auth.user_id, role))
+        } else {
+            require_workflow_permission(
+                ctx,
+                &[Permission::WORKFLOW_EXECUTIONS_LIST],
+                "Permission denied: workflow_executions:list required",
+            )?;
+            None
+        };
+
+        let service = workflow_service(ctx, db);
+        let approvals = service
+            .list_approvals(tenant.id, status.map(Into::into), assignee)
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))?;
+
+        Ok(approvals.into_iter().map(Into::into).collect())
+    }
+
     async fn workflow_templates(&self, ctx: &Context<'_>) -> Result<Vec<GqlWorkflowTemplate>> {
         require_module_enabled(ctx, MODULE_SLUG).await?;
 
@@ -120,7 +183,7 @@ impl WorkflowQuery {
             "Permission denied: workflows:read required",
         )?;
 
-        let service = WorkflowService::new(db.clone());
+        let service = workflow_service(ctx, db);
         let versions = service
             .list_versions(tenant.id, workflow_id)
             .await
@@ -145,10 +208,81 @@ impl WorkflowQuery {
             "Permission denied: workflows:read required",
         )?;
 
-        let service = WorkflowService::new(db.clone());
+        let service = workflow_service(ctx, db);
         match service.get_version(tenant.id, workflow_id, version).await {
             Ok(version) => Ok(Some(version.into())),
             Err(_) => Ok(None),
         }
     }
+
+    async fn webhook_subscriptions(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<Vec<GqlWebhookSubscription>> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let tenant = ctx.data::<TenantContext>()?;
+
+        require_workflow_permission(
+            ctx,
+            &[Permission::WEBHOOKS_LIST],
+            "Permission denied: webhooks:list required",
+        )?;
+
+        let subscriptions = WebhookSubscriptionService::new(db.clone())
+            .list(tenant.id)
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))?;
+
+        Ok(subscriptions.into_iter().map(Into::into).collect())
+    }
+
+    async fn webhook_subscription(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+    ) -> Result<Option<GqlWebhookSubscription>> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let tenant = ctx.data::<TenantContext>()?;
+
+        require_workflow_permission(
+            ctx,
+            &[Permission::WEBHOOKS_READ],
+            "Permission denied: webhooks:read required",
+        )?;
+
+        match WebhookSubscriptionService::new(db.clone())
+            .get(tenant.id, id)
+            .await
+        {
+            Ok(subscription) => Ok(Some(subscription.into())),
+            Err(crate::WorkflowError::SubscriptionNotFound(_)) => Ok(None),
+            Err(err) => Err(async_graphql::Error::new(err.to_string())),
+        }
+    }
+
+    /// Delivery log of a webhook subscription (most recent first).
+    async fn webhook_subscription_deliveries(
+        &self,
+        ctx: &Context<'_>,
+        subscription_id: Uuid,
+    ) -> Result<Vec<GqlWebhookSubscriptionDelivery>> {
+        require_module_enabled(ctx, MODULE_SLUG).await?;
+        let db = ctx.data::<DatabaseConnection>()?;
+        let tenant = ctx.data::<TenantContext>()?;
+
+        require_workflow_permission(
+            ctx,
+            &[Permission::WEBHOOKS_READ],
+            "Permission denied: webhooks:read required",
+        )?;
+
+        let deliveries = WebhookSubscriptionService::new(db.clone())
+            .list_deliveries(tenant.id, subscription_id)
+            .await
+            .map_err(|err| async_graphql::Error::new(err.to_string()))?;
+
+        Ok(deliveries.into_iter().map(Into::into).collect())
+    }
 }
@@ -2,11 +2,16 @@ use async_graphql::{Enum, InputObject, SimpleObject};
 use serde_json::Value;
 use uuid::Uuid;
 
-use crate::entities::{ExecutionStatus, OnError, StepExecutionStatus, StepType, WorkflowStatus};
+use crate::entities::{
+    ApprovalStatus, ExecutionStatus, OnError, StepExecutionStatus, StepType,
+    SubscriptionDeliveryStatus, WebhookDeliveryStatus, WebhookSubscriptionStatus, WorkflowStatus,
+};
 use crate::templates::WorkflowTemplate;
 use crate::{
-    WorkflowExecutionResponse, WorkflowResponse, WorkflowStepExecutionResponse,
-    WorkflowStepResponse, WorkflowSummary, WorkflowVersionDetail, WorkflowVersionSummary,
+    WebhookSubscriptionDeliveryResponse, WebhookSubscriptionResponse, WorkflowApprovalResponse,
+    WorkflowDryRunResponse, WorkflowDryRunStep, WorkflowExecutionResponse, WorkflowResponse,
+    WorkflowStepExecutionResponse, WorkflowStepResponse, WorkflowSummary, WorkflowVersionDetail,
+    WorkflowVersionSummary, WorkflowWebhookDeliveryResponse,
 };
 
 #[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
@@ -49,6 +54,12 @@ pub enum GqlStepType {
     Http,
     Notify,
     Transform,
+    WaitForEvent,
+    Approval,
+    Branch,
+    Parallel,
+    ForEach,
+    SubWorkflow,
 }
 
 impl From<StepType> for GqlStepType {
@@ -62,6 +73,12 @@ impl From<StepType> for GqlStepType {
             StepType::Http => Self::Http,
             StepType::Notify => Self::Notify,
             StepType::Transform => Self::Transform,
+            StepType::WaitForEvent => Self::WaitForEvent,
+            StepType::Approval => Self::Approval,
+            StepType::Branch => Self::Branch,
+            StepType::Parallel => Self::Parallel,
+            StepType::ForEach => Self::ForEach,
+            StepType::SubWorkflow => Self::SubWorkflow,
         }
     }
 }
@@ -77,6 +94,12 @@ impl From<GqlStepType> for StepType {
             GqlStepType::Http => Self::Http,
             GqlStepType::Notify => Self::Notify,
             GqlStepType::Transform => Self::Transform,
+            GqlStepType::WaitForEvent => Self::WaitForEvent,
+            GqlStepType::Approval => Self::Approval,
+            GqlStepType::Branch => Self::Branch,
+            GqlStepType::Parallel => Self::Parallel,
+            GqlStepType::ForEach => Self::ForEach,
+            GqlStepType::SubWorkflow => Self::SubWorkflow,
         }
     }
 }
@@ -114,6 +137,7 @@ pub enum GqlExecutionStatus {
     Completed,
     Failed,
     TimedOut,
+    Suspended,
 }
 
 impl From<ExecutionStatus> for GqlExecutionStatus {
@@ -123,6 +147,7 @@ impl From<ExecutionStatus> for GqlExecutionStatus {
             ExecutionStatus::Completed => Self::Completed,
             ExecutionStatus::Failed => Self::Failed,
             ExecutionStatus::TimedOut => Self::TimedOut,
+            ExecutionStatus::Suspended => Self::Suspended,
         }
     }
 }
@@ -134,6 +159,7 @@ pub enum GqlStepExecutionStatus {
     Completed,
     Failed,
     Skipped,
+    Waiting,
 }
 
 impl From<StepExecutionStatus> for GqlStepExecutionStatus {
@@ -144,6 +170,7 @@ impl From<StepExecutionStatus> for GqlStepExecutionStatus {
             StepExecutionStatus::Completed => Self::Completed,
             StepExecutionStatus::Failed => Self::Failed,
             StepExecutionStatus::Skipped => Self::Skipped,
+            StepExecutionStatus::Waiting => Self::Waiting,
         }
     }
 }
@@ -179,6 +206,8 @@ impl From<WorkflowSummary> for GqlWorkflowSummary {
 pub struct GqlWorkflowStep {
     pub id: Uuid,
     pub workflow_id: Uuid,
+    pub parent_step_id: Option<Uuid>,
+    pub branch: Option<String>,
     pub position: i32,
     pub step_type: GqlStepType,
     pub config: Value,
@@ -191,6 +220,8 @@ impl From<WorkflowStepResponse> for GqlWorkflowStep {
         Self {
             id: step.id,
             workflow_id: step.workflow_id,
+            parent_step_id: step.parent_step_id,
+            branch: step.branch,
             position: step.position,
             step_type: step.step_type.into(),
             config: step.config,
@@ -242,6 +273,7 @@ pub struct GqlStepExecution {
     pub id: Uuid,
     pub execution_id: Uuid,
     pub step_id: Uuid,
+    pub branch: Option<String>,
     pub status: GqlStepExecutionStatus,
     pub input: Value,
     pub output: Value,
@@ -256,6 +288,7 @@ impl From<WorkflowStepExecutionResponse> for GqlStepExecution {
             id: step.id,
             execution_id: step.execution_id,
             step_id: step.step_id,
+            branch: step.branch,
             status: step.status.into(),
             input: step.input,
             output: step.output,
@@ -301,6 +334,306 @@ impl From<WorkflowExecutionResponse> for GqlWorkflowExecution {
     }
 }
 
+#[derive(SimpleObject)]
+pub struct GqlDryRunStep {
+    pub workflow_id: Uuid,
+    pub step_id: Uuid,
+    pub step_type: GqlStepType,
+    pub branch: Option<String>,
+    pub status: GqlStepExecutionStatus,
+    pub mocked: bool,
+    pub input: Value,
+    pub output: Value,
+    pub error: Option<String>,
+}
+
+impl From<WorkflowDryRunStep> for GqlDryRunStep {
+    fn from(step: WorkflowDryRunStep) -> Self {
+        Self {
+            workflow_id: step.workflow_id,
+            step_id: step.step_id,
+            step_type: step.step_type.into(),
+            branch: step.branch,
+            status: step.status.into(),
+            mocked: step.mocked,
+            input: step.input,
+            output: step.output,
+            error: step.error,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct GqlWorkflowDryRun {
+    pub workflow_id: Uuid,
+    pub version: Option<i32>,
+    pub status: GqlExecutionStatus,
+    pub context: Value,
+    pub error: Option<String>,
+    pub steps: Vec<GqlDryRunStep>,
+}
+
+impl From<WorkflowDryRunResponse> for GqlWorkflowDryRun {
+    fn from(dry_run: WorkflowDryRunResponse) -> Self {
+        Self {
+            workflow_id: dry_run.workflow_id,
+            version: dry_run.version,
+            status: dry_run.status.into(),
+            context: dry_run.context,
+            error: dry_run.error,
+            steps: dry_run.steps.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum GqlApprovalStatus {
+    Pending,
+    Approved,
+    Rejected,
+    Expired,
+}
+
+impl From<ApprovalStatus> for GqlApprovalStatus {
+    fn from(status: ApprovalStatus) -> Self {
+        match status {
+            ApprovalStatus::Pending => Self::Pending,
+            ApprovalStatus::Approved => Self::Approved,
+            ApprovalStatus::Rejected => Self::Rejected,
+            ApprovalStatus::Expired => Self::Expired,
+        }
+    }
+}
+
+impl From<GqlApprovalStatus> for ApprovalStatus {
+    fn from(status: GqlApprovalStatus) -> Self {
+        match status {
+            GqlApprovalStatus::Pending => Self::Pending,
+            GqlApprovalStatus::Approved => Self::Approved,
+            GqlApprovalStatus::Rejected => Self::Rejected,
+            GqlApprovalStatus::Expired => Self::Expired,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct GqlWorkflowApproval {
+    pub id: Uuid,
+    pub workflow_id: Uuid,
+    pub execution_id: Uuid,
+    pub step_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub assignee_user_ids: Vec<Uuid>,
+    pub assignee_roles: Vec<String>,
+    pub status: GqlApprovalStatus,
+    pub escalation_level: i32,
+    pub deadline_at: Option<String>,
+    pub decided_by: Option<Uuid>,
+    pub comment: Option<String>,
+    pub decided_at: Option<String>,
+    pub created_at: String,
+}
+
+impl From<WorkflowApprovalResponse> for GqlWorkflowApproval {
+    fn from(approval: WorkflowApprovalResponse) -> Self {
+        Self {
+            id: approval.id,
+            workflow_id: approval.workflow_id,
+            execution_id: approval.execution_id,
+            step_id: approval.step_id,
+            title: approval.title,
+            description: approval.description,
+            assignee_user_ids: approval.assignee_user_ids,
+            assignee_roles: approval.assignee_roles,
+            status: approval.status.into(),
+            escalation_level: approval.escalation_level,
+            deadline_at: approval.deadline_at.map(|value| value.to_rfc3339()),
+            decided_by: approval.decided_by,
+            comment: approval.comment,
+            decided_at: approval.decided_at.map(|value| value.to_rfc3339()),
+            created_at: approval.created_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum GqlWebhookDeliveryStatus {
+    Accepted,
+    Rejected,
+}
+
+impl From<WebhookDeliveryStatus> for GqlWebhookDeliveryStatus {
+    fn from(status: WebhookDeliveryStatus) -> Self {
+        match status {
+            WebhookDeliveryStatus::Accepted => Self::Accepted,
+            WebhookDeliveryStatus::Rejected => Self::Rejected,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct GqlWorkflowWebhookDelivery {
+    pub id: Uuid,
+    pub workflow_id: Option<Uuid>,
+    pub webhook_slug: String,
+    pub delivery_id: Option<String>,
+    pub status: GqlWebhookDeliveryStatus,
+    pub reason: Option<String>,
+    pub execution_ids: Vec<Uuid>,
+    pub received_at: String,
+}
+
+impl From<WorkflowWebhookDeliveryResponse> for GqlWorkflowWebhookDelivery {
+    fn from(delivery: WorkflowWebhookDeliveryResponse) -> Self {
+        Self {
+            id: delivery.id,
+            workflow_id: delivery.workflow_id,
+            webhook_slug: delivery.webhook_slug,
+            delivery_id: delivery.delivery_id,
+            status: delivery.status.into(),
+            reason: delivery.reason,
+            execution_ids: delivery.execution_ids,
+            received_at: delivery.received_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum GqlWebhookSubscriptionStatus {
+    Active,
+    Disabled,
+}
+
+impl From<WebhookSubscriptionStatus> for GqlWebhookSubscriptionStatus {
+    fn from(status: WebhookSubscriptionStatus) -> Self {
+        match status {
+            WebhookSubscriptionStatus::Active => Self::Active,
+            WebhookSubscriptionStatus::Disabled => Self::Disabled,
+        }
+    }
+}
+
+impl From<GqlWebhookSubscriptionStatus> for WebhookSubscriptionStatus {
+    fn from(status: GqlWebhookSubscriptionStatus) -> Self {
+        match status {
+            GqlWebhookSubscriptionStatus::Active => Self::Active,
+            GqlWebhookSubscriptionStatus::Disabled => Self::Disabled,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct GqlWebhookSubscription {
+    pub id: Uuid,
+    pub name: String,
+    pub url: String,
+    pub event_types: Vec<String>,
+    pub status: GqlWebhookSubscriptionStatus,
+    pub consecutive_failures: i32,
+    pub disabled_at: Option<String>,
+    pub disabled_reason: Option<String>,
+    /// Signing secret; only set in the response to `createWebhookSubscription`
+    pub secret: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<WebhookSubscriptionResponse> for GqlWebhookSubscription {
+    fn from(subscription: WebhookSubscriptionResponse) -> Self {
+        Self {
+            id: subscription.id,
+            name: subscription.name,
+            url: subscription.url,
+            event_types: subscription.event_types,
+            status: subscription.status.into(),
+            consecutive_failures: subscription.consecutive_failures,
+            disabled_at: subscription.disabled_at.map(|value| value.to_rfc3339()),
+            disabled_reason: subscription.disabled_reason,
+            secret: subscription.secret,
+            created_by: subscription.created_by,
+            created_at: subscription.created_at.to_rfc3339(),
+            updated_at: subscription.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq, Debug)]
+pub enum GqlSubscriptionDeliveryStatus {
+    Pending,
+    Delivered,
+    DeadLetter,
+}
+
+impl From<SubscriptionDeliveryStatus> for GqlSubscriptionDeliveryStatus {
+    fn from(status: SubscriptionDeliveryStatus) -> Self {
+        match status {
+            SubscriptionDeliveryStatus::Pending => Self::Pending,
+            SubscriptionDeliveryStatus::Delivered => Self::Delivered,
+            SubscriptionDeliveryStatus::DeadLetter => Self::DeadLetter,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct GqlWebhookSubscriptionDelivery {
+    pub id: Uuid,
+    pub subscription_id: Uuid,
+    pub event_id: Uuid,
+    pub event_type: String,
+    pub status: GqlSubscriptionDeliveryStatus,
+    pub attempts: i32,
+    pub next_attempt_at: Option<String>,
+    pub request_headers: Value,
+    pub request_body: Option<String>,
+    pub response_status: Option<i32>,
+    pub response_body: Option<String>,
+    pub error: Option<String>,
+    pub duration_ms: Option<i64>,
+    pub last_attempt_at: Option<String>,
+    pub delivered_at: Option<String>,
+    pub created_at: String,
+}
+
+impl From<WebhookSubscriptionDeliveryResponse> for GqlWebhookSubscriptionDelivery {
+    fn from(delivery: WebhookSubscriptionDeliveryResponse) -> Self {
+        Self {
+            id: delivery.id,
+            subscription_id: delivery.subscription_id,
+            event_id: delivery.event_id,
+            event_type: delivery.event_type,
+            status: delivery.status.into(),
+            attempts: delivery.attempts,
+            next_attempt_at: delivery.next_attempt_at.map(|value| value.to_rfc3339()),
+            request_headers: delivery.request_headers,
+            request_body: delivery.request_body,
+            response_status: delivery.response_status,
+            response_body: delivery.response_body,
+            error: delivery.error,
+            duration_ms: delivery.duration_ms,
+            last_attempt_at: delivery.last_attempt_at.map(|value| value.to_rfc3339()),
+            delivered_at: delivery.delivered_at.map(|value| value.to_rfc3339()),
+            created_at: delivery.created_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(InputObject)]
+pub struct GqlCreateWebhookSubscriptionInput {
+    pub name: String,
+    pub url: String,
+    pub event_types: Vec<String>,
+}
+
+#[derive(InputObject)]
+pub struct GqlUpdateWebhookSubscriptionInput {
+    pub name: Option<String>,
+    pub url: Option<String>,
+    pub event_types: Option<Vec<String>>,
+    pub status: Option<GqlWebhookSubscriptionStatus>,
+}
+
 #[derive(InputObject)]
 pub struct GqlCreateWorkflowInput {
     pub name: String,
@@ -321,6 +654,8 @@ pub struct GqlUpdateWorkflowInput {
 #[derive(InputObject)]
 pub struct GqlCreateStepInput {
     pub position: i32,
+    pub parent_step_id: Option<Uuid>,
+    pub branch: Option<String>,
     pub step_type: GqlStepType,
     pub config: Value,
     pub on_error: GqlOnError,
@@ -330,6 +665,8 @@ pub struct GqlCreateStepInput {
 #[derive(InputObject)]
 pub struct GqlUpdateStepInput {
     pub position: Option<i32>,
+    pub parent_step_id: Option<Uuid>,
+    pub branch: Option<String>,
     pub step_type: Option<GqlStepType>,
     pub config: Option<Value>,
     pub on_error: Option<GqlOnError>,
@@ -17,6 +17,25 @@
 //! - Linear step execution via `WorkflowEngine`
 //! - Event trigger via `WorkflowTriggerHandler`
 //! - Basic steps: `action`, `emit_event`, `condition`
+//! - `action` steps invoke module actions published through
+//!   `rustok_core::register_module_action`
+//! - `condition` / `branch` configs and trigger `filter`s are boolean
+//!   expressions (`steps::Expression`) checked when saved
+//! - `transform` steps reshape the execution context declaratively
+//! - long `delay` and `wait_for_event` steps suspend executions durably in
+//!   `workflow_suspensions`; timers are resumed by `WorkflowCronScheduler`,
+//!   events by `WorkflowTriggerHandler`
+//! - `approval` steps open tasks in `workflow_approvals` and resume on an
+//!   assignee's decision or at the deadline
+//! - webhooks are verified by HMAC signature, timestamp and delivery id
+//!   (`services::webhook`) and recorded in `workflow_webhook_deliveries`
+//! - `branch`, `parallel`, `for_each` and `sub_workflow` steps run their child
+//!   steps (`parent_step_id` / `branch`) as a graph
+//! - finished executions can be resumed from the failing step or re-run from
+//!   any step; dry runs execute a workflow version with mocked side effects
+//! - outbound webhook subscriptions receive matching domain events as signed
+//!   deliveries (`WebhookSubscriptionHandler` queues, `WebhookDispatcher`
+//!   sends with retries, dead letters and endpoint auto-disabling)
 
 use async_trait::async_trait;
 use rustok_core::permissions::{Action, Permission, Resource};
@@ -41,9 +60,14 @@ pub use error::{WorkflowError, WorkflowResult};
 pub use graphql::{WorkflowMutation, WorkflowQuery};
 pub use migration::{WorkflowPhase4Migration, WorkflowsMigration};
 pub use services::{
-    WorkflowCronScheduler, WorkflowEngine, WorkflowService, WorkflowTriggerHandler,
+    WebhookDeliveryLog, WebhookDispatcher, WebhookDispatcherConfig, WebhookSubscriptionHandler,
+    WebhookSubscriptionService, WorkflowCronScheduler, WorkflowEngine, WorkflowService,
+    WorkflowTriggerHandler,
+};
+pub use steps::{
+    AlloyScriptStep, ApprovalStep, NotificationSender, NotifyStep, ScriptRunner, TransformStep,
+    WaitForEventStep, WorkflowActionRuntime,
 };
-pub use steps::{AlloyScriptStep, NotificationSender, NotifyStep, ScriptRunner};
 pub use templates::{WorkflowTemplate, BUILTIN_TEMPLATES};
 
 pub struct WorkflowModule;
@@ -77,6 +101,12 @@ impl RusToKModule for WorkflowModule {
             Permission::new(Resource::Workflows, Action::Manage),
             Permission::new(Resource::WorkflowExecutions, Action::Read),
             Permission::new(Resource::WorkflowExecutions, Action::List),
+            Permission::new(Resource::Webhooks, Action::Create),
+            Permission::new(Resource::Webhooks, Action::Read),
+            Permission::new(Resource::Webhooks, Action::Update),
+            Permission::new(Resource::Webhooks, Action::Delete),
+            Permission::new(Resource::Webhooks, Action::List),
+            Permission::new(Resource::Webhooks, Action::Manage),
         ]
     }
 
@@ -85,7 +115,13 @@ impl RusToKModule for WorkflowModule {
         registry: &mut ModuleEventListenerRegistry,
         ctx: &ModuleEventListenerContext<'_>,
     ) {
-        registry.register(WorkflowTriggerHandler::new(ctx.db.clone()));
+        let action_runtime = ctx.event_transport.clone().and_then(|transport| {
+            WorkflowActionRuntime::from_extensions(ctx.extensions, transport)
+        });
+        registry.register(
+            WorkflowTriggerHandler::new(ctx.db.clone()).with_action_runtime(action_runtime),
+        );
+        registry.register(WebhookSubscriptionHandler::new(ctx.db.clone()));
     }
 }
 
@@ -117,5 +153,8 @@ mod tests {
         assert!(perms
             .iter()
             .any(|p| p.resource == Resource::WorkflowExecutions && p.action == Action::List));
+        assert!(perms
+            .iter()
+            .any(|p| p.resource == Resource::Webhooks && p.action == Action::Manage));
     }
 }
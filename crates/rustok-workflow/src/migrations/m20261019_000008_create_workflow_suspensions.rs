@@ -0,0 +1,110 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(WorkflowSuspensions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(WorkflowSuspensions::ExecutionId)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(WorkflowSuspensions::TenantId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WorkflowSuspensions::WorkflowId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WorkflowSuspensions::StepId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WorkflowSuspensions::StepExecutionId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(WorkflowSuspensions::ResumeAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(WorkflowSuspensions::EventType).string_len(128))
+                    .col(
+                        ColumnDef::new(WorkflowSuspensions::Correlation)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WorkflowSuspensions::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_workflow_suspensions_execution_id")
+                            .from(WorkflowSuspensions::Table, WorkflowSuspensions::ExecutionId)
+                            .to(WorkflowExecutions::Table, WorkflowExecutions::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_workflow_suspensions_resume_at")
+                    .table(WorkflowSuspensions::Table)
+                    .col(WorkflowSuspensions::ResumeAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_workflow_suspensions_tenant_event")
+                    .table(WorkflowSuspensions::Table)
+                    .col(WorkflowSuspensions::TenantId)
+                    .col(WorkflowSuspensions::EventType)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(WorkflowSuspensions::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum WorkflowSuspensions {
+    Table,
+    ExecutionId,
+    TenantId,
+    WorkflowId,
+    StepId,
+    StepExecutionId,
+    ResumeAt,
+    EventType,
+    Correlation,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum WorkflowExecutions {
+    Table,
+    Id,
+}
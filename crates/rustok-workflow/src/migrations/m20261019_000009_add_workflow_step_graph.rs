@@ -0,0 +1,95 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(WorkflowSteps::Table)
+                    .add_column_if_not_exists(ColumnDef::new(WorkflowSteps::ParentStepId).uuid())
+                    .add_column_if_not_exists(ColumnDef::new(WorkflowSteps::Branch).string_len(64))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("fk_workflow_steps_parent_step_id")
+                    .from(WorkflowSteps::Table, WorkflowSteps::ParentStepId)
+                    .to(WorkflowSteps::Table, WorkflowSteps::Id)
+                    .on_delete(ForeignKeyAction::Cascade)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_workflow_steps_parent_step_id")
+                    .table(WorkflowSteps::Table)
+                    .col(WorkflowSteps::ParentStepId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(WorkflowStepExecutions::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(WorkflowStepExecutions::Branch).string_len(64),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(WorkflowStepExecutions::Table)
+                    .drop_column(WorkflowStepExecutions::Branch)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_foreign_key(
+                ForeignKey::drop()
+                    .name("fk_workflow_steps_parent_step_id")
+                    .table(WorkflowSteps::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(WorkflowSteps::Table)
+                    .drop_column(WorkflowSteps::Branch)
+                    .drop_column(WorkflowSteps::ParentStepId)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum WorkflowSteps {
+    Table,
+    Id,
+    ParentStepId,
+    Branch,
+}
+
+#[derive(DeriveIden)]
+enum WorkflowStepExecutions {
+    Table,
+    Branch,
+}
@@ -0,0 +1,141 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(WorkflowApprovals::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(WorkflowApprovals::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(WorkflowApprovals::TenantId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WorkflowApprovals::WorkflowId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WorkflowApprovals::ExecutionId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(WorkflowApprovals::StepId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(WorkflowApprovals::StepExecutionId)
+                            .uuid()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(WorkflowApprovals::Title)
+                            .string_len(255)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(WorkflowApprovals::Description).text())
+                    .col(
+                        ColumnDef::new(WorkflowApprovals::AssigneeUserIds)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WorkflowApprovals::AssigneeRoles)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WorkflowApprovals::Status)
+                            .string_len(32)
+                            .not_null()
+                            .default("pending"),
+                    )
+                    .col(
+                        ColumnDef::new(WorkflowApprovals::EscalationLevel)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(WorkflowApprovals::DeadlineAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(WorkflowApprovals::DecidedBy).uuid())
+                    .col(ColumnDef::new(WorkflowApprovals::Comment).text())
+                    .col(ColumnDef::new(WorkflowApprovals::DecidedAt).timestamp_with_time_zone())
+                    .col(
+                        ColumnDef::new(WorkflowApprovals::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WorkflowApprovals::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_workflow_approvals_execution_id")
+                            .from(WorkflowApprovals::Table, WorkflowApprovals::ExecutionId)
+                            .to(WorkflowExecutions::Table, WorkflowExecutions::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_workflow_approvals_tenant_status")
+                    .table(WorkflowApprovals::Table)
+                    .col(WorkflowApprovals::TenantId)
+                    .col(WorkflowApprovals::Status)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(WorkflowApprovals::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum WorkflowApprovals {
+    Table,
+    Id,
+    TenantId,
+    WorkflowId,
+    ExecutionId,
+    StepId,
+    StepExecutionId,
+    Title,
+    Description,
+    AssigneeUserIds,
+    AssigneeRoles,
+    Status,
+    EscalationLevel,
+    DeadlineAt,
+    DecidedBy,
+    Comment,
+    DecidedAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum WorkflowExecutions {
+    Table,
+    Id,
+}
@@ -0,0 +1,144 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Workflows::Table)
+                    .add_column_if_not_exists(
+                        ColumnDef::new(Workflows::WebhookPreviousSecret).string_len(128),
+                    )
+                    .add_column_if_not_exists(
+                        ColumnDef::new(Workflows::WebhookSecretRotatedAt)
+                            .timestamp_with_time_zone(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(WorkflowWebhookDeliveries::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(WorkflowWebhookDeliveries::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(WorkflowWebhookDeliveries::TenantId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(WorkflowWebhookDeliveries::WorkflowId).uuid())
+                    .col(
+                        ColumnDef::new(WorkflowWebhookDeliveries::WebhookSlug)
+                            .string_len(128)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(WorkflowWebhookDeliveries::DeliveryId).string_len(128))
+                    .col(ColumnDef::new(WorkflowWebhookDeliveries::ReplayKey).string_len(128))
+                    .col(
+                        ColumnDef::new(WorkflowWebhookDeliveries::Status)
+                            .string_len(32)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(WorkflowWebhookDeliveries::Reason).text())
+                    .col(
+                        ColumnDef::new(WorkflowWebhookDeliveries::ExecutionIds)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WorkflowWebhookDeliveries::ReceivedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("uidx_workflow_webhook_deliveries_replay_key")
+                    .table(WorkflowWebhookDeliveries::Table)
+                    .col(WorkflowWebhookDeliveries::WorkflowId)
+                    .col(WorkflowWebhookDeliveries::ReplayKey)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_workflow_webhook_deliveries_tenant_received_at")
+                    .table(WorkflowWebhookDeliveries::Table)
+                    .col(WorkflowWebhookDeliveries::TenantId)
+                    .col(WorkflowWebhookDeliveries::ReceivedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        // Retention purge runs across tenants
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_workflow_webhook_deliveries_received_at")
+                    .table(WorkflowWebhookDeliveries::Table)
+                    .col(WorkflowWebhookDeliveries::ReceivedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(WorkflowWebhookDeliveries::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Workflows::Table)
+                    .drop_column(Workflows::WebhookPreviousSecret)
+                    .drop_column(Workflows::WebhookSecretRotatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Workflows {
+    Table,
+    WebhookPreviousSecret,
+    WebhookSecretRotatedAt,
+}
+
+#[derive(DeriveIden)]
+enum WorkflowWebhookDeliveries {
+    Table,
+    Id,
+    TenantId,
+    WorkflowId,
+    WebhookSlug,
+    DeliveryId,
+    ReplayKey,
+    Status,
+    Reason,
+    ExecutionIds,
+    ReceivedAt,
+}
@@ -0,0 +1,254 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(WebhookSubscriptions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(WebhookSubscriptions::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookSubscriptions::TenantId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookSubscriptions::Name)
+                            .string_len(255)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookSubscriptions::Url)
+                            .string_len(2048)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookSubscriptions::EventTypes)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookSubscriptions::Secret)
+                            .string_len(128)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookSubscriptions::Status)
+                            .string_len(32)
+                            .not_null()
+                            .default("active"),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookSubscriptions::ConsecutiveFailures)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookSubscriptions::DisabledAt).timestamp_with_time_zone(),
+                    )
+                    .col(ColumnDef::new(WebhookSubscriptions::DisabledReason).text())
+                    .col(ColumnDef::new(WebhookSubscriptions::CreatedBy).uuid())
+                    .col(
+                        ColumnDef::new(WebhookSubscriptions::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookSubscriptions::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_webhook_subscriptions_tenant_status")
+                    .table(WebhookSubscriptions::Table)
+                    .col(WebhookSubscriptions::TenantId)
+                    .col(WebhookSubscriptions::Status)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(WebhookSubscriptionDeliveries::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(WebhookSubscriptionDeliveries::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookSubscriptionDeliveries::TenantId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookSubscriptionDeliveries::SubscriptionId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookSubscriptionDeliveries::EventId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookSubscriptionDeliveries::EventType)
+                            .string_len(128)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookSubscriptionDeliveries::Payload)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookSubscriptionDeliveries::Status)
+                            .string_len(32)
+                            .not_null()
+                            .default("pending"),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookSubscriptionDeliveries::Attempts)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookSubscriptionDeliveries::NextAttemptAt)
+                            .timestamp_with_time_zone(),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookSubscriptionDeliveries::RequestHeaders)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(WebhookSubscriptionDeliveries::RequestBody).text())
+                    .col(ColumnDef::new(WebhookSubscriptionDeliveries::ResponseStatus).integer())
+                    .col(ColumnDef::new(WebhookSubscriptionDeliveries::ResponseBody).text())
+                    .col(ColumnDef::new(WebhookSubscriptionDeliveries::Error).text())
+                    .col(ColumnDef::new(WebhookSubscriptionDeliveries::DurationMs).big_integer())
+                    .col(
+                        ColumnDef::new(WebhookSubscriptionDeliveries::LastAttemptAt)
+                            .timestamp_with_time_zone(),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookSubscriptionDeliveries::DeliveredAt)
+                            .timestamp_with_time_zone(),
+                    )
+                    .col(
+                        ColumnDef::new(WebhookSubscriptionDeliveries::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_webhook_subscription_deliveries_subscription_id")
+                            .from(
+                                WebhookSubscriptionDeliveries::Table,
+                                WebhookSubscriptionDeliveries::SubscriptionId,
+                            )
+                            .to(WebhookSubscriptions::Table, WebhookSubscriptions::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("uidx_webhook_subscription_deliveries_event")
+                    .table(WebhookSubscriptionDeliveries::Table)
+                    .col(WebhookSubscriptionDeliveries::SubscriptionId)
+                    .col(WebhookSubscriptionDeliveries::EventId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_webhook_subscription_deliveries_due")
+                    .table(WebhookSubscriptionDeliveries::Table)
+                    .col(WebhookSubscriptionDeliveries::Status)
+                    .col(WebhookSubscriptionDeliveries::NextAttemptAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(WebhookSubscriptionDeliveries::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(WebhookSubscriptions::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum WebhookSubscriptions {
+    Table,
+    Id,
+    TenantId,
+    Name,
+    Url,
+    EventTypes,
+    Secret,
+    Status,
+    ConsecutiveFailures,
+    DisabledAt,
+    DisabledReason,
+    CreatedBy,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum WebhookSubscriptionDeliveries {
+    Table,
+    Id,
+    TenantId,
+    SubscriptionId,
+    EventId,
+    EventType,
+    Payload,
+    Status,
+    Attempts,
+    NextAttemptAt,
+    RequestHeaders,
+    RequestBody,
+    ResponseStatus,
+    ResponseBody,
+    Error,
+    DurationMs,
+    LastAttemptAt,
+    DeliveredAt,
+    CreatedAt,
+}
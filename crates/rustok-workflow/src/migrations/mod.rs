@@ -1,5 +1,10 @@
 mod m20260316_000006_create_workflows;
 mod m20260316_000007_alter_workflows_add_failure_tracking;
+mod m20261019_000008_create_workflow_suspensions;
+mod m20261019_000009_add_workflow_step_graph;
+mod m20261019_000010_create_workflow_approvals;
+mod m20261019_000011_add_webhook_verification;
+mod m20261019_000012_create_webhook_subscriptions;
 
 use sea_orm_migration::MigrationTrait;
 
@@ -7,5 +12,10 @@ pub fn migrations() -> Vec<Box<dyn MigrationTrait>> {
     vec![
         Box::new(m20260316_000006_create_workflows::Migration),
         Box::new(m20260316_000007_alter_workflows_add_failure_tracking::Migration),
+        Box::new(m20261019_000008_create_workflow_suspensions::Migration),
+        Box::new(m20261019_000009_add_workflow_step_graph::Migration),
+        Box::new(m20261019_000010_create_workflow_approvals::Migration),
+        Box::new(m20261019_000011_add_webhook_verification::Migration),
+        Box::new(m20261019_000012_create_webhook_subscriptions::Migration),
     ]
 }
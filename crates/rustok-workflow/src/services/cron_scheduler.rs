@@ -8,16 +8,27 @@ use tokio::task::JoinHandle;
 use tracing::{error, info, warn};
 
 use crate::entities::{workflow, WorkflowEntity, WorkflowStatus};
+use crate::services::webhook::WebhookDeliveryLog;
 use crate::services::{WorkflowEngine, WorkflowService};
+use crate::steps::{ResumeSignal, WorkflowActionRuntime};
+
+/// Maximum number of due suspensions resumed per tick.
+const RESUME_BATCH_SIZE: u64 = 100;
+
+/// How often rejected webhook deliveries are written and old ones purged.
+const WEBHOOK_DELIVERY_FLUSH_SECS: u64 = 60;
 
 /// Polls active workflows with cron triggers and fires them on schedule.
 ///
 /// Each workflow with `{"type": "cron", "expression": "0 * * * * *"}` trigger
-/// is checked every second against its cron expression.
+/// is checked every second against its cron expression. The same tick resumes
+/// executions suspended by long `delay` steps or timed-out event waits. Once a
+/// minute the scheduler also flushes the webhook delivery log.
 pub struct WorkflowCronScheduler {
     db: DatabaseConnection,
     engine: Arc<WorkflowEngine>,
     service: Arc<WorkflowService>,
+    delivery_log: WebhookDeliveryLog,
 }
 
 impl WorkflowCronScheduler {
@@ -28,9 +39,33 @@ impl WorkflowCronScheduler {
             db,
             engine,
             service,
+            delivery_log: WebhookDeliveryLog::new(),
         }
     }
 
+    /// Runs `action` steps against the given module actions.
+    pub fn with_action_runtime(mut self, runtime: Option<WorkflowActionRuntime>) -> Self {
+        self.engine =
+            Arc::new(WorkflowEngine::new(self.db.clone()).with_action_runtime(runtime.clone()));
+        self.service = Arc::new(
+            WorkflowService::new(self.db.clone())
+                .with_action_runtime(runtime)
+                .with_webhook_delivery_log(self.delivery_log.clone()),
+        );
+        self
+    }
+
+    /// Flushes the log that webhook ingress queues rejected deliveries in.
+    pub fn with_webhook_delivery_log(mut self, log: WebhookDeliveryLog) -> Self {
+        self.service = Arc::new(
+            WorkflowService::new(self.db.clone())
+                .with_action_runtime(self.service.action_runtime().cloned())
+                .with_webhook_delivery_log(log.clone()),
+        );
+        self.delivery_log = log;
+        self
+    }
+
     /// Start the cron scheduler as a background task.
     /// Returns a handle that can be aborted to stop the scheduler.
     pub fn start(self) -> JoinHandle<()> {
@@ -42,17 +77,47 @@ impl WorkflowCronScheduler {
 
     async fn run(&self) {
         let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(1));
+        let mut flush_interval = tokio::time::interval(tokio::time::Duration::from_secs(
+            WEBHOOK_DELIVERY_FLUSH_SECS,
+        ));
         loop {
-            interval.tick().await;
-            if let Err(e) = self.tick().await {
-                error!(error = %e, "CronScheduler tick error");
+            tokio::select! {
+                _ = interval.tick() => {
+                    if let Err(e) = self.tick().await {
+                        error!(error = %e, "CronScheduler tick error");
+                    }
+                }
+                _ = flush_interval.tick() => {
+                    if let Err(e) = self.service.flush_webhook_deliveries().await {
+                        error!(error = %e, "Webhook delivery flush error");
+                    }
+                }
             }
         }
     }
 
+    /// Resumes executions whose durable delay or wait timeout has passed.
+    async fn resume_due(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        for suspension in self.engine.due_suspensions(now, RESUME_BATCH_SIZE).await? {
+            let engine = self.engine.clone();
+            tokio::spawn(async move {
+                let execution_id = suspension.execution_id;
+                if let Err(e) = engine.resume(suspension, ResumeSignal::Timer).await {
+                    error!(execution_id = %execution_id, error = %e, "Workflow resume failed");
+                }
+            });
+        }
+        Ok(())
+    }
+
     async fn tick(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let now = chrono::Utc::now();
 
+        self.resume_due(now).await?;
+
         let workflows = WorkflowEntity::find()
             .filter(workflow::Column::Status.eq(WorkflowStatus::Active.to_string()))
             .all(&self.db)
@@ -1,21 +1,38 @@
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use futures::future::{join_all, BoxFuture};
+use futures::stream::{self, StreamExt};
 use sea_orm::sea_query::Expr;
-use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
-use serde_json::Value;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, Order, QueryFilter, QueryOrder,
+    QuerySelect, Set, TransactionTrait,
+};
+use serde_json::{json, Value};
 use tracing::{error, info, instrument, warn};
 use uuid::Uuid;
 
+use super::graph::StepGraph;
+use crate::dto::{WorkflowDryRunResponse, WorkflowDryRunStep};
 use crate::entities::{
-    workflow_execution, workflow_step_execution, ExecutionStatus, OnError, StepExecutionStatus,
-    WorkflowExecutionActiveModel, WorkflowStepExecutionActiveModel,
+    workflow, workflow_approval, workflow_execution, workflow_step, workflow_step_execution,
+    workflow_suspension, ApprovalStatus, ExecutionStatus, OnError, StepExecutionStatus, StepType,
+    WorkflowApprovalActiveModel, WorkflowApprovalEntity, WorkflowEntity, WorkflowExecution,
+    WorkflowExecutionActiveModel, WorkflowExecutionEntity, WorkflowStatus, WorkflowStepEntity,
+    WorkflowStepExecutionActiveModel, WorkflowStepExecutionEntity, WorkflowSuspension,
+    WorkflowSuspensionActiveModel, WorkflowSuspensionEntity,
+};
+use crate::error::{WorkflowError, WorkflowResult};
+use crate::steps::action::DEFAULT_ACTION_OUTPUT_KEY;
+use crate::steps::approval::ApprovalConfig;
+use crate::steps::control::{
+    BranchConfig, ForEachConfig, ParallelConfig, SubWorkflowConfig, MAX_SUB_WORKFLOW_DEPTH,
 };
-use crate::error::WorkflowResult;
 use crate::steps::{
-    ActionStep, AlloyScriptStep, ConditionStep, DelayStep, EmitEventStep, HttpStep, NotifyStep,
-    StepContext, WorkflowStep,
+    event_matches, lookup_template_path, resolve_templates, ActionStep, AlloyScriptStep,
+    ApprovalStep, ConditionStep, DelayStep, EmitEventStep, HttpStep, NotifyStep, ResumeSignal,
+    StepContext, StepOutput, TransformStep, WaitForEventStep, WorkflowActionRuntime, WorkflowStep,
 };
 
 /// Registry of available step executors, keyed by step type string.
@@ -23,18 +40,108 @@ type StepRegistry = HashMap<String, Arc<dyn WorkflowStep>>;
 
 fn default_registry() -> StepRegistry {
     let mut map: StepRegistry = HashMap::new();
-    map.insert("action".into(), Arc::new(ActionStep));
+    map.insert("action".into(), Arc::new(ActionStep::stub()));
     map.insert("emit_event".into(), Arc::new(EmitEventStep));
     map.insert("condition".into(), Arc::new(ConditionStep));
     map.insert("delay".into(), Arc::new(DelayStep));
     map.insert("http".into(), Arc::new(HttpStep::new()));
     map.insert("alloy_script".into(), Arc::new(AlloyScriptStep::stub()));
     map.insert("notify".into(), Arc::new(NotifyStep::stub()));
-    map.insert("transform".into(), Arc::new(ActionStep)); // placeholder
+    map.insert("transform".into(), Arc::new(TransformStep));
+    map.insert("wait_for_event".into(), Arc::new(WaitForEventStep));
+    map.insert("approval".into(), Arc::new(ApprovalStep));
     map
 }
 
-/// Executes workflow step chains linearly, persisting execution logs.
+/// A failed attempt of a step, before its `on_error` policy applies.
+struct StepFailure {
+    step_execution_id: Uuid,
+    input: Value,
+    message: String,
+}
+
+/// How a sequence of steps ended.
+enum Flow {
+    /// Every step ran; carries the resulting context.
+    Continue(StepContext),
+    /// A step signalled stop; the execution completes early.
+    Stop(StepContext),
+    /// A step failed and its `on_error` did not skip it.
+    Fail(StepContext, String),
+    /// A step parked the execution in `workflow_suspensions`.
+    Suspended,
+}
+
+/// Where a sequence of steps runs.
+#[derive(Clone, Copy)]
+struct Scope<'a> {
+    graph: &'a StepGraph,
+    workflow_id: Uuid,
+    execution_id: Uuid,
+    /// Sub-workflow nesting level of the execution.
+    depth: usize,
+    /// Whether steps may suspend. Lanes of `parallel`, `for_each`
+    /// iterations and sub-workflows cannot be resumed on their own.
+    durable: bool,
+    /// Set during a dry run: nothing is persisted and side-effecting steps
+    /// are mocked.
+    dry_run: Option<&'a DryRun>,
+}
+
+/// Step types whose side effects are replaced by a mock during a dry run.
+/// `delay` is mocked so that a dry run never sleeps.
+const DRY_RUN_MOCKED: [StepType; 6] = [
+    StepType::Action,
+    StepType::EmitEvent,
+    StepType::Http,
+    StepType::Notify,
+    StepType::AlloyScript,
+    StepType::Delay,
+];
+
+/// Collects the step results of a dry run in the order the steps finished.
+struct DryRun {
+    mocks: HashMap<Uuid, Value>,
+    steps: Mutex<Vec<WorkflowDryRunStep>>,
+}
+
+impl DryRun {
+    #[allow(clippy::too_many_arguments)]
+    fn record(
+        &self,
+        workflow_id: Uuid,
+        step: &crate::entities::WorkflowStep,
+        branch: Option<&str>,
+        status: StepExecutionStatus,
+        input: Value,
+        output: Value,
+        error: Option<String>,
+    ) {
+        let record = WorkflowDryRunStep {
+            workflow_id,
+            step_id: step.id,
+            step_type: step.step_type.clone(),
+            branch: branch.map(str::to_string),
+            status,
+            mocked: DRY_RUN_MOCKED.contains(&step.step_type),
+            input,
+            output,
+            error,
+        };
+        self.steps
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(record);
+    }
+}
+
+/// Executes workflow step graphs, persisting execution logs.
+///
+/// Control-flow steps (`branch`, `parallel`, `for_each`, `sub_workflow`)
+/// are run by the engine itself. Steps may suspend the execution; it is then
+/// resumed from the suspended step by [`WorkflowEngine::resume`]. Finished
+/// executions are re-run in place by [`WorkflowEngine::retry`] and
+/// [`WorkflowEngine::rerun_from`].
 pub struct WorkflowEngine {
     db: DatabaseConnection,
     steps: StepRegistry,
@@ -48,6 +155,16 @@ impl WorkflowEngine {
         }
     }
 
+    /// Attaches module actions to the `action` step; without a runtime the
+    /// step fails at execution time.
+    pub fn with_action_runtime(mut self, runtime: Option<WorkflowActionRuntime>) -> Self {
+        if let Some(runtime) = runtime {
+            let step = ActionStep::new(self.db.clone(), runtime);
+            self.register_step(step);
+        }
+        self
+    }
+
     pub fn register_step<S: WorkflowStep + 'static>(&mut self, step: S) {
         self.steps.insert(step.step_type().into(), Arc::new(step));
     }
@@ -62,215 +179,1204 @@ impl WorkflowEngine {
         steps: Vec<crate::entities::WorkflowStep>,
         initial_context: Value,
     ) -> WorkflowResult<Uuid> {
-        let execution_id = Uuid::new_v4();
-        let now = Utc::now().fixed_offset();
+        let (execution_id, _) = self
+            .start_execution(
+                workflow_id,
+                tenant_id,
+                trigger_event_id,
+                steps,
+                initial_context,
+                0,
+            )
+            .await?;
+        Ok(execution_id)
+    }
 
-        // Create execution record
-        let execution = WorkflowExecutionActiveModel {
-            id: Set(execution_id),
-            workflow_id: Set(workflow_id),
-            tenant_id: Set(tenant_id),
-            trigger_event_id: Set(trigger_event_id),
-            status: Set(ExecutionStatus::Running),
-            context: Set(initial_context.clone()),
-            error: Set(None),
-            started_at: Set(now),
-            completed_at: Set(None),
+    /// Resume a suspended execution once its timer fired or its event arrived.
+    ///
+    /// Returns `false` when another worker already claimed the suspension.
+    #[instrument(skip(self, suspension, signal), fields(execution_id = %suspension.execution_id))]
+    pub async fn resume(
+        &self,
+        suspension: WorkflowSuspension,
+        signal: ResumeSignal,
+    ) -> WorkflowResult<bool> {
+        let claimed = WorkflowSuspensionEntity::delete_by_id(suspension.execution_id)
+            .exec(&self.db)
+            .await?
+            .rows_affected
+            == 1;
+        if !claimed {
+            return Ok(false);
+        }
+
+        let execution = WorkflowExecutionEntity::find_by_id(suspension.execution_id)
+            .one(&self.db)
+            .await?
+            .ok_or(WorkflowError::ExecutionNotFound(suspension.execution_id))?;
+        let graph = self.load_graph(execution.workflow_id).await?;
+
+        let Some(step) = graph.get(suspension.step_id) else {
+            let message = "Suspended step no longer exists".to_string();
+            warn!(step_id = %suspension.step_id, "{message}");
+            self.finish_step_execution(
+                suspension.step_execution_id,
+                StepExecutionStatus::Failed,
+                Value::Null,
+                Value::Null,
+                Some(message.clone()),
+            )
+            .await?;
+            self.finish_execution(
+                execution.id,
+                ExecutionStatus::Failed,
+                execution.context,
+                Some(message),
+            )
+            .await?;
+            return Ok(true);
         };
-        execution.insert(&self.db).await?;
 
-        info!(
-            execution_id = %execution_id,
-            steps = steps.len(),
-            "Starting workflow execution"
-        );
+        info!(step_id = %suspension.step_id, "Resuming suspended workflow execution");
+        self.settle_approval(step, suspension.step_execution_id, &signal)
+            .await?;
+        workflow_execution::Entity::update_many()
+            .col_expr(
+                workflow_execution::Column::Status,
+                Expr::value(ExecutionStatus::Running.to_string()),
+            )
+            .filter(workflow_execution::Column::Id.eq(execution.id))
+            .exec(&self.db)
+            .await?;
+
+        let scope = Scope {
+            graph: &graph,
+            workflow_id: execution.workflow_id,
+            execution_id: execution.id,
+            depth: 0,
+            durable: true,
+            dry_run: None,
+        };
+        let context =
+            StepContext::new(execution.context).for_execution(execution.tenant_id, execution.id);
+        let flow = self
+            .run_from(
+                scope,
+                step,
+                context,
+                Some((suspension.step_execution_id, signal)),
+            )
+            .await?;
+
+        self.finish(execution.id, &flow).await?;
+        Ok(true)
+    }
+
+    /// Resume a failed or timed-out execution from the step that failed,
+    /// with the context that step received. The workflow's current steps are
+    /// used, so a fixed step config takes effect.
+    ///
+    /// Returns `false` when the execution changed status concurrently.
+    #[instrument(skip(self, execution), fields(execution_id = %execution.id))]
+    pub async fn retry(&self, execution: WorkflowExecution) -> WorkflowResult<bool> {
+        if !matches!(
+            execution.status,
+            ExecutionStatus::Failed | ExecutionStatus::TimedOut
+        ) {
+            return Err(WorkflowError::NotRerunnable(
+                execution.id,
+                format!(
+                    "only failed executions can be retried (status: {})",
+                    execution.status
+                ),
+            ));
+        }
+        let graph = self.load_graph(execution.workflow_id).await?;
+
+        // The innermost failed step that can be restarted on its own: steps
+        // inside `parallel` or `for_each` restart with their enclosing step.
+        let failed = WorkflowStepExecutionEntity::find()
+            .filter(workflow_step_execution::Column::ExecutionId.eq(execution.id))
+            .filter(workflow_step_execution::Column::Status.eq(StepExecutionStatus::Failed))
+            .order_by(workflow_step_execution::Column::StartedAt, Order::Desc)
+            .all(&self.db)
+            .await?;
+        let Some((step, input)) = failed.into_iter().find_map(|record| {
+            graph
+                .get(record.step_id)
+                .filter(|step| restartable(&graph, step).is_ok())
+                .map(|step| (step, record.input))
+        }) else {
+            return Err(WorkflowError::NotRerunnable(
+                execution.id,
+                "no failed step of the current workflow to resume from".into(),
+            ));
+        };
 
-        let mut context = StepContext::new(initial_context);
-        let mut failed = false;
-        let mut failure_msg = String::new();
-
-        'steps: for step in &steps {
-            let step_execution_id = Uuid::new_v4();
-            let step_input = context.data.clone();
-
-            // Record step as running
-            let step_exec = WorkflowStepExecutionActiveModel {
-                id: Set(step_execution_id),
-                execution_id: Set(execution_id),
-                step_id: Set(step.id),
-                status: Set(StepExecutionStatus::Running),
-                input: Set(step_input.clone()),
-                output: Set(Value::Null),
+        self.restart(&execution, &graph, step, input).await
+    }
+
+    /// Re-run a finished execution from `step_id` with the context that step
+    /// received when it last ran, discarding what happened after it.
+    ///
+    /// Returns `false` when the execution changed status concurrently.
+    #[instrument(skip(self, execution), fields(execution_id = %execution.id))]
+    pub async fn rerun_from(
+        &self,
+        execution: WorkflowExecution,
+        step_id: Uuid,
+    ) -> WorkflowResult<bool> {
+        if matches!(
+            execution.status,
+            ExecutionStatus::Running | ExecutionStatus::Suspended
+        ) {
+            return Err(WorkflowError::NotRerunnable(
+                execution.id,
+                format!("execution is still {}", execution.status),
+            ));
+        }
+        let graph = self.load_graph(execution.workflow_id).await?;
+        let step = graph
+            .get(step_id)
+            .ok_or(WorkflowError::StepNotFound(step_id))?;
+        restartable(&graph, step)
+            .map_err(|message| WorkflowError::NotRerunnable(execution.id, message))?;
+
+        let input = WorkflowStepExecutionEntity::find()
+            .filter(workflow_step_execution::Column::ExecutionId.eq(execution.id))
+            .filter(workflow_step_execution::Column::StepId.eq(step_id))
+            .order_by(workflow_step_execution::Column::StartedAt, Order::Desc)
+            .one(&self.db)
+            .await?
+            .map(|record| record.input)
+            .ok_or_else(|| {
+                WorkflowError::NotRerunnable(
+                    execution.id,
+                    format!("step {step_id} did not run in this execution"),
+                )
+            })?;
+
+        self.restart(&execution, &graph, step, input).await
+    }
+
+    /// Run `steps` against `initial_context` without persisting anything.
+    /// Side-effecting steps are mocked: they report their resolved config and
+    /// store the result from `mocks` (by step id) where the real step stores
+    /// its own. A step that would suspend ends the run as `suspended`.
+    #[instrument(skip(self, steps, initial_context, mocks), fields(workflow_id = %workflow_id))]
+    pub async fn dry_run(
+        &self,
+        workflow_id: Uuid,
+        tenant_id: Uuid,
+        steps: Vec<crate::entities::WorkflowStep>,
+        initial_context: Value,
+        mocks: HashMap<Uuid, Value>,
+    ) -> WorkflowResult<WorkflowDryRunResponse> {
+        let dry_run = DryRun {
+            mocks,
+            steps: Mutex::new(Vec::new()),
+        };
+        let graph = StepGraph::new(steps);
+        let scope = Scope {
+            graph: &graph,
+            workflow_id,
+            execution_id: Uuid::nil(),
+            depth: 0,
+            durable: true,
+            dry_run: Some(&dry_run),
+        };
+        let context = StepContext::new(initial_context).for_execution(tenant_id, Uuid::nil());
+        let flow = self
+            .run_sequence(scope, graph.sequence(None, None), None, context, None)
+            .await?;
+
+        let (status, context, error) = match flow {
+            Flow::Continue(context) | Flow::Stop(context) => {
+                (ExecutionStatus::Completed, context.data, None)
+            }
+            Flow::Fail(context, message) => (ExecutionStatus::Failed, context.data, Some(message)),
+            Flow::Suspended => (ExecutionStatus::Suspended, Value::Null, None),
+        };
+        Ok(WorkflowDryRunResponse {
+            workflow_id,
+            version: None,
+            status,
+            context,
+            error,
+            steps: dry_run
+                .steps
+                .into_inner()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()),
+        })
+    }
+
+    /// Claims a finished execution back into `running` and runs it from
+    /// `step` with `context`.
+    async fn restart(
+        &self,
+        execution: &WorkflowExecution,
+        graph: &StepGraph,
+        step: &crate::entities::WorkflowStep,
+        context: Value,
+    ) -> WorkflowResult<bool> {
+        if execution.context.pointer("/trigger/type") == Some(&json!("sub_workflow")) {
+            return Err(WorkflowError::NotRerunnable(
+                execution.id,
+                "sub-workflow executions run as part of their parent".into(),
+            ));
+        }
+
+        let claimed = workflow_execution::Entity::update_many()
+            .col_expr(
+                workflow_execution::Column::Status,
+                Expr::value(ExecutionStatus::Running.to_string()),
+            )
+            .col_expr(
+                workflow_execution::Column::Error,
+                Expr::value(Option::<String>::None),
+            )
+            .col_expr(
+                workflow_execution::Column::CompletedAt,
+                Expr::value(Option::<chrono::DateTime<chrono::FixedOffset>>::None),
+            )
+            .filter(workflow_execution::Column::Id.eq(execution.id))
+            .filter(workflow_execution::Column::Status.eq(execution.status.clone()))
+            .exec(&self.db)
+            .await?
+            .rows_affected
+            == 1;
+        if !claimed {
+            return Ok(false);
+        }
+
+        info!(step_id = %step.id, "Re-running workflow execution from step");
+        let scope = Scope {
+            graph,
+            workflow_id: execution.workflow_id,
+            execution_id: execution.id,
+            depth: 0,
+            durable: true,
+            dry_run: None,
+        };
+        let context = StepContext::new(context).for_execution(execution.tenant_id, execution.id);
+        let flow = self.run_from(scope, step, context, None).await?;
+
+        self.finish(execution.id, &flow).await?;
+        Ok(true)
+    }
+
+    /// Runs `step` and the rest of its sequence, then — for a step inside a
+    /// `branch` — the steps after each enclosing branch step up to the top
+    /// level.
+    async fn run_from<'a>(
+        &'a self,
+        scope: Scope<'a>,
+        step: &'a crate::entities::WorkflowStep,
+        context: StepContext,
+        resume: Option<(Uuid, ResumeSignal)>,
+    ) -> WorkflowResult<Flow> {
+        let graph = scope.graph;
+        let mut flow = self
+            .run_sequence(
+                scope,
+                graph.sequence_from(step),
+                step.branch.clone(),
+                context,
+                resume,
+            )
+            .await?;
+
+        let mut current = step;
+        while let Some(parent) = current.parent_step_id.and_then(|id| graph.get(id)) {
+            let context = match flow {
+                Flow::Continue(context) => context,
+                other => {
+                    flow = other;
+                    break;
+                }
+            };
+            if parent.step_type != StepType::Branch {
+                flow = Flow::Fail(
+                    context,
+                    format!("Cannot resume inside a {} step", parent.step_type),
+                );
+                break;
+            }
+            flow = self
+                .run_sequence(
+                    scope,
+                    graph.sequence_after(parent),
+                    parent.branch.clone(),
+                    context,
+                    None,
+                )
+                .await?;
+            current = parent;
+        }
+
+        Ok(flow)
+    }
+
+    async fn load_graph(&self, workflow_id: Uuid) -> WorkflowResult<StepGraph> {
+        let steps = WorkflowStepEntity::find()
+            .filter(workflow_step::Column::WorkflowId.eq(workflow_id))
+            .order_by(workflow_step::Column::Position, Order::Asc)
+            .all(&self.db)
+            .await?;
+        Ok(StepGraph::new(steps))
+    }
+
+    /// Suspensions whose `resume_at` has passed, oldest first.
+    pub async fn due_suspensions(
+        &self,
+        now: DateTime<Utc>,
+        limit: u64,
+    ) -> WorkflowResult<Vec<WorkflowSuspension>> {
+        Ok(WorkflowSuspensionEntity::find()
+            .filter(workflow_suspension::Column::ResumeAt.lte(now.fixed_offset()))
+            .order_by(workflow_suspension::Column::ResumeAt, Order::Asc)
+            .limit(limit)
+            .all(&self.db)
+            .await?)
+    }
+
+    /// Suspensions of `tenant_id` waiting for `event_type` whose correlation
+    /// matches `event` (shaped like the trigger context `event` object).
+    pub async fn event_suspensions(
+        &self,
+        tenant_id: Uuid,
+        event_type: &str,
+        event: &Value,
+    ) -> WorkflowResult<Vec<WorkflowSuspension>> {
+        let waiting = WorkflowSuspensionEntity::find()
+            .filter(workflow_suspension::Column::TenantId.eq(tenant_id))
+            .filter(workflow_suspension::Column::EventType.eq(event_type))
+            .all(&self.db)
+            .await?;
+        Ok(waiting
+            .into_iter()
+            .filter(|suspension| {
+                suspension
+                    .correlation
+                    .as_object()
+                    .is_some_and(|correlation| event_matches(correlation, event))
+            })
+            .collect())
+    }
+
+    /// Creates the execution record and runs the top-level sequence.
+    /// Sub-workflow executions (`depth > 0`) run inside their parent step and
+    /// therefore cannot suspend.
+    fn start_execution<'a>(
+        &'a self,
+        workflow_id: Uuid,
+        tenant_id: Uuid,
+        trigger_event_id: Option<Uuid>,
+        steps: Vec<crate::entities::WorkflowStep>,
+        initial_context: Value,
+        depth: usize,
+    ) -> BoxFuture<'a, WorkflowResult<(Uuid, Flow)>> {
+        Box::pin(async move {
+            let execution_id = Uuid::new_v4();
+            let now = Utc::now().fixed_offset();
+
+            // Create execution record
+            let execution = WorkflowExecutionActiveModel {
+                id: Set(execution_id),
+                workflow_id: Set(workflow_id),
+                tenant_id: Set(tenant_id),
+                trigger_event_id: Set(trigger_event_id),
+                status: Set(ExecutionStatus::Running),
+                context: Set(initial_context.clone()),
                 error: Set(None),
-                started_at: Set(Utc::now().fixed_offset()),
+                started_at: Set(now),
                 completed_at: Set(None),
             };
-            step_exec.insert(&self.db).await?;
+            execution.insert(&self.db).await?;
 
-            let step_type_str = step.step_type.to_string();
-            let executor = match self.steps.get(&step_type_str) {
-                Some(e) => e.clone(),
-                None => {
-                    warn!(step_type = %step_type_str, "No executor registered for step type");
-                    self.finish_step_execution(
+            info!(
+                execution_id = %execution_id,
+                steps = steps.len(),
+                depth = depth,
+                "Starting workflow execution"
+            );
+
+            let graph = StepGraph::new(steps);
+            let scope = Scope {
+                graph: &graph,
+                workflow_id,
+                execution_id,
+                depth,
+                durable: depth == 0,
+                dry_run: None,
+            };
+            let context = StepContext::new(initial_context).for_execution(tenant_id, execution_id);
+            let flow = self
+                .run_sequence(scope, graph.sequence(None, None), None, context, None)
+                .await?;
+            self.finish(execution_id, &flow).await?;
+
+            Ok((execution_id, flow))
+        })
+    }
+
+    /// Runs `steps` in order under the `branch` label. With `resume`, the
+    /// first step is the suspended one: its existing step execution is
+    /// finished through [`WorkflowStep::resume`] instead of a fresh `execute`.
+    fn run_sequence<'a>(
+        &'a self,
+        scope: Scope<'a>,
+        steps: Vec<&'a crate::entities::WorkflowStep>,
+        branch: Option<String>,
+        mut context: StepContext,
+        mut resume: Option<(Uuid, ResumeSignal)>,
+    ) -> BoxFuture<'a, WorkflowResult<Flow>> {
+        Box::pin(async move {
+            for step in steps {
+                match self
+                    .run_step(scope, step, branch.as_deref(), context, resume.take())
+                    .await?
+                {
+                    Flow::Continue(next) => context = next,
+                    flow => return Ok(flow),
+                }
+            }
+            Ok(Flow::Continue(context))
+        })
+    }
+
+    async fn run_step(
+        &self,
+        scope: Scope<'_>,
+        step: &crate::entities::WorkflowStep,
+        branch: Option<&str>,
+        context: StepContext,
+        resume: Option<(Uuid, ResumeSignal)>,
+    ) -> WorkflowResult<Flow> {
+        let step_input = context.data.clone();
+        let (step_execution_id, mut signal) = match resume {
+            Some((step_execution_id, signal)) => (step_execution_id, Some(signal)),
+            None if scope.dry_run.is_some() => (Uuid::new_v4(), None),
+            None => {
+                // Record step as running
+                let step_execution_id = Uuid::new_v4();
+                WorkflowStepExecutionActiveModel {
+                    id: Set(step_execution_id),
+                    execution_id: Set(scope.execution_id),
+                    step_id: Set(step.id),
+                    branch: Set(branch.map(str::to_string)),
+                    status: Set(StepExecutionStatus::Running),
+                    input: Set(step_input.clone()),
+                    output: Set(Value::Null),
+                    error: Set(None),
+                    started_at: Set(Utc::now().fixed_offset()),
+                    completed_at: Set(None),
+                }
+                .insert(&self.db)
+                .await?;
+                (step_execution_id, None)
+            }
+        };
+
+        // A branch step only routes: its children run as part of the
+        // enclosing sequence, so their stop, failure and suspension apply there.
+        if step.step_type == StepType::Branch {
+            let selected =
+                BranchConfig::parse(&step.config).and_then(|config| config.select(&context.data));
+            return match selected {
+                Ok(selected) => {
+                    info!(step_id = %step.id, branch = %selected, "Branch selected");
+                    self.record_step(
+                        scope,
+                        step,
+                        branch,
                         step_execution_id,
-                        StepExecutionStatus::Failed,
+                        StepExecutionStatus::Completed,
                         step_input,
-                        Value::Null,
-                        Some(format!("Unknown step type: {step_type_str}")),
+                        json!({ "branch": selected }),
+                        None,
                     )
                     .await?;
-
-                    match step.on_error {
-                        OnError::Stop => {
-                            failed = true;
-                            failure_msg = format!("Unknown step type: {step_type_str}");
-                            break 'steps;
-                        }
-                        OnError::Skip => continue 'steps,
-                        OnError::Retry => {
-                            // Retry once (simple implementation)
-                            failed = true;
-                            failure_msg = format!("Unknown step type: {step_type_str}");
-                            break 'steps;
-                        }
-                    }
+                    let body = scope.graph.sequence(Some(step.id), Some(&selected));
+                    self.run_sequence(scope, body, Some(selected), context, None)
+                        .await
+                }
+                Err(err) => {
+                    error!(step_id = %step.id, error = %err, "Branch selection failed");
+                    let failure = StepFailure {
+                        step_execution_id,
+                        input: step_input,
+                        message: err.to_string(),
+                    };
+                    self.fail_step(scope, step, branch, context, failure).await
                 }
             };
+        }
+
+        let executor = if step.step_type.is_control_flow() {
+            None
+        } else {
+            let step_type_str = step.step_type.to_string();
+            match self.steps.get(&step_type_str) {
+                Some(executor) => Some(executor.clone()),
+                None => {
+                    warn!(step_type = %step_type_str, "No executor registered for step type");
+                    let failure = StepFailure {
+                        step_execution_id,
+                        input: step_input,
+                        message: format!("Unknown step type: {step_type_str}"),
+                    };
+                    return self.fail_step(scope, step, branch, context, failure).await;
+                }
+            }
+        };
 
-            let result = executor.execute(&step.config, context.clone()).await;
+        // Retry with exponential backoff
+        // Configurable via step config: { "max_retries": 3, "retry_base_ms": 1000 }
+        let max_retries = match step.on_error {
+            _ if scope.dry_run.is_some() => 0,
+            OnError::Retry => step
+                .config
+                .get("max_retries")
+                .and_then(Value::as_u64)
+                .unwrap_or(3) as u32,
+            OnError::Stop | OnError::Skip => 0,
+        };
+        let retry_base_ms = step
+            .config
+            .get("retry_base_ms")
+            .and_then(Value::as_u64)
+            .unwrap_or(1000);
+        let mut attempt = 0;
+        let mock = scope
+            .dry_run
+            .filter(|_| DRY_RUN_MOCKED.contains(&step.step_type));
+
+        loop {
+            let result = match (&executor, signal.take()) {
+                _ if mock.is_some() => Ok(mock_step(
+                    step,
+                    context.clone(),
+                    mock.and_then(|dry_run| dry_run.mocks.get(&step.id)),
+                )),
+                (Some(executor), Some(signal)) => {
+                    executor.resume(&step.config, context.clone(), signal).await
+                }
+                (Some(executor), None) => executor.execute(&step.config, context.clone()).await,
+                (None, _) => self.run_control(scope, step, context.clone()).await,
+            };
+            let result = result.and_then(|output| {
+                if output.suspension.is_some() && !scope.durable {
+                    Err(WorkflowError::StepFailed(format!(
+                        "{}: cannot suspend inside parallel, for_each or a sub-workflow",
+                        step.step_type
+                    )))
+                } else {
+                    Ok(output)
+                }
+            });
 
             match result {
+                Ok(output) if output.suspension.is_some() && scope.dry_run.is_some() => {
+                    self.record_step(
+                        scope,
+                        step,
+                        branch,
+                        step_execution_id,
+                        StepExecutionStatus::Waiting,
+                        step_input,
+                        output.data,
+                        None,
+                    )
+                    .await?;
+                    return Ok(Flow::Suspended);
+                }
+                Ok(output) if output.suspension.is_some() => {
+                    self.suspend(
+                        scope.workflow_id,
+                        scope.execution_id,
+                        step.id,
+                        step_execution_id,
+                        output,
+                    )
+                    .await?;
+                    return Ok(Flow::Suspended);
+                }
                 Ok(output) => {
-                    self.finish_step_execution(
+                    self.record_step(
+                        scope,
+                        step,
+                        branch,
                         step_execution_id,
                         StepExecutionStatus::Completed,
                         step_input,
-                        output.data.clone(),
+                        output.data,
                         None,
                     )
                     .await?;
-
-                    context = output.context;
-
                     if !output.should_continue {
                         info!(step_id = %step.id, "Step signalled stop — halting execution");
-                        break 'steps;
+                        return Ok(Flow::Stop(output.context));
                     }
+                    return Ok(Flow::Continue(output.context));
+                }
+                Err(err) if attempt < max_retries => {
+                    attempt += 1;
+                    let backoff = retry_base_ms * (2u64.pow(attempt - 1));
+                    warn!(
+                        step_id = %step.id,
+                        attempt = attempt,
+                        backoff_ms = backoff,
+                        error = %err,
+                        "Retrying step after failure"
+                    );
+                    tokio::time::sleep(tokio::time::Duration::from_millis(backoff)).await;
                 }
                 Err(err) => {
                     let err_msg = err.to_string();
                     error!(step_id = %step.id, error = %err_msg, "Step failed");
-
-                    self.finish_step_execution(
+                    let failure = StepFailure {
                         step_execution_id,
-                        StepExecutionStatus::Failed,
-                        step_input.clone(),
-                        Value::Null,
-                        Some(err_msg.clone()),
-                    )
-                    .await?;
+                        input: step_input,
+                        message: err_msg,
+                    };
+                    return self.fail_step(scope, step, branch, context, failure).await;
+                }
+            }
+        }
+    }
 
-                    match step.on_error {
-                        OnError::Stop => {
-                            failed = true;
-                            failure_msg = err_msg;
-                            break 'steps;
-                        }
-                        OnError::Skip => {
-                            // Update step status to skipped and continue
-                            self.update_step_status(
-                                step_execution_id,
-                                StepExecutionStatus::Skipped,
-                            )
-                            .await?;
-                            continue 'steps;
-                        }
-                        OnError::Retry => {
-                            // Retry with exponential backoff
-                            // Configurable via step config: { "max_retries": 3, "retry_base_ms": 1000 }
-                            let max_retries = step
-                                .config
-                                .get("max_retries")
-                                .and_then(Value::as_u64)
-                                .unwrap_or(3) as u32;
-                            let retry_base_ms = step
-                                .config
-                                .get("retry_base_ms")
-                                .and_then(Value::as_u64)
-                                .unwrap_or(1000);
-
-                            let mut last_err = err_msg.clone();
-                            let mut succeeded = false;
-
-                            for attempt in 1..=max_retries {
-                                let backoff = retry_base_ms * (2u64.pow(attempt - 1));
-                                warn!(
-                                    step_id = %step.id,
-                                    attempt = attempt,
-                                    backoff_ms = backoff,
-                                    "Retrying step after failure"
-                                );
-                                tokio::time::sleep(tokio::time::Duration::from_millis(backoff))
-                                    .await;
-
-                                match executor.execute(&step.config, context.clone()).await {
-                                    Ok(out) => {
-                                        self.finish_step_execution(
-                                            step_execution_id,
-                                            StepExecutionStatus::Completed,
-                                            step_input.clone(),
-                                            out.data,
-                                            None,
-                                        )
-                                        .await?;
-                                        context = out.context;
-                                        succeeded = true;
-                                        break;
-                                    }
-                                    Err(retry_err) => {
-                                        last_err = retry_err.to_string();
-                                    }
-                                }
-                            }
-
-                            if !succeeded {
-                                failed = true;
-                                failure_msg = last_err.clone();
-                                self.finish_step_execution(
-                                    step_execution_id,
-                                    StepExecutionStatus::Failed,
-                                    step_input,
-                                    Value::Null,
-                                    Some(failure_msg.clone()),
-                                )
-                                .await?;
-                                break 'steps;
-                            }
-                        }
-                    }
+    /// Records a failed step and applies its `on_error` policy.
+    async fn fail_step(
+        &self,
+        scope: Scope<'_>,
+        step: &crate::entities::WorkflowStep,
+        branch: Option<&str>,
+        context: StepContext,
+        failure: StepFailure,
+    ) -> WorkflowResult<Flow> {
+        let StepFailure {
+            step_execution_id,
+            input,
+            message,
+        } = failure;
+        let skip = step.on_error == OnError::Skip;
+
+        match scope.dry_run {
+            Some(dry_run) => {
+                let status = if skip {
+                    StepExecutionStatus::Skipped
+                } else {
+                    StepExecutionStatus::Failed
+                };
+                dry_run.record(
+                    scope.workflow_id,
+                    step,
+                    branch,
+                    status,
+                    input,
+                    Value::Null,
+                    Some(message.clone()),
+                );
+            }
+            None => {
+                self.finish_step_execution(
+                    step_execution_id,
+                    StepExecutionStatus::Failed,
+                    Value::Null,
+                    Value::Null,
+                    Some(message.clone()),
+                )
+                .await?;
+                if skip {
+                    self.update_step_status(step_execution_id, StepExecutionStatus::Skipped)
+                        .await?;
                 }
             }
         }
 
-        // Finalize execution
-        let final_status = if failed {
-            ExecutionStatus::Failed
+        if skip {
+            Ok(Flow::Continue(context))
         } else {
-            ExecutionStatus::Completed
+            Ok(Flow::Fail(context, message))
+        }
+    }
+
+    /// Stores the outcome of a step in its step execution or, during a dry
+    /// run, in the dry-run report.
+    #[allow(clippy::too_many_arguments)]
+    async fn record_step(
+        &self,
+        scope: Scope<'_>,
+        step: &crate::entities::WorkflowStep,
+        branch: Option<&str>,
+        step_execution_id: Uuid,
+        status: StepExecutionStatus,
+        input: Value,
+        output: Value,
+        error: Option<String>,
+    ) -> WorkflowResult<()> {
+        match scope.dry_run {
+            Some(dry_run) => {
+                dry_run.record(
+                    scope.workflow_id,
+                    step,
+                    branch,
+                    status,
+                    input,
+                    output,
+                    error,
+                );
+                Ok(())
+            }
+            None => {
+                self.finish_step_execution(step_execution_id, status, input, output, error)
+                    .await
+            }
+        }
+    }
+
+    async fn run_control(
+        &self,
+        scope: Scope<'_>,
+        step: &crate::entities::WorkflowStep,
+        context: StepContext,
+    ) -> WorkflowResult<StepOutput> {
+        match step.step_type {
+            StepType::Parallel => self.run_parallel(scope, step, context).await,
+            StepType::ForEach => self.run_for_each(scope, step, context).await,
+            StepType::SubWorkflow => self.run_sub_workflow(scope, step, context).await,
+            ref other => Err(WorkflowError::StepFailed(format!(
+                "Unknown step type: {other}"
+            ))),
+        }
+    }
+
+    /// Runs every lane of a `parallel` step concurrently and joins them.
+    async fn run_parallel(
+        &self,
+        scope: Scope<'_>,
+        step: &crate::entities::WorkflowStep,
+        context: StepContext,
+    ) -> WorkflowResult<StepOutput> {
+        let config = ParallelConfig::parse(&step.config)?;
+        let lanes = scope.graph.lanes(step.id);
+        let required = config.join.required(lanes.len());
+        if required > lanes.len() {
+            return Err(WorkflowError::InvalidStepConfig(format!(
+                "parallel: join requires {required} lanes but the step has {}",
+                lanes.len()
+            )));
+        }
+
+        let lane_scope = Scope {
+            durable: false,
+            ..scope
+        };
+        let results = join_all(lanes.iter().map(|lane| {
+            self.run_sequence(
+                lane_scope,
+                scope.graph.sequence(Some(step.id), Some(lane)),
+                Some(lane.clone()),
+                context.clone(),
+                None,
+            )
+        }))
+        .await;
+
+        let mut joined = context.clone();
+        let mut succeeded = Vec::new();
+        let mut failed = serde_json::Map::new();
+        for (lane, result) in lanes.into_iter().zip(results) {
+            match result? {
+                Flow::Continue(lane_context) | Flow::Stop(lane_context) => {
+                    merge_changes(&mut joined.data, &context.data, lane_context.data);
+                    succeeded.push(lane);
+                }
+                Flow::Fail(_, message) => {
+                    failed.insert(lane, Value::String(message));
+                }
+                Flow::Suspended => {
+                    failed.insert(lane, Value::String("lane suspended".into()));
+                }
+            }
+        }
+
+        if succeeded.len() < required {
+            let errors = failed
+                .iter()
+                .map(|(lane, message)| format!("{lane}: {}", message.as_str().unwrap_or_default()))
+                .collect::<Vec<_>>()
+                .join("; ");
+            return Err(WorkflowError::StepFailed(format!(
+                "parallel: {} lane(s) succeeded, join requires {required} ({errors})",
+                succeeded.len()
+            )));
+        }
+
+        Ok(StepOutput::continue_with(
+            joined,
+            json!({ "succeeded": succeeded, "failed": failed }),
+        ))
+    }
+
+    /// Runs the body of a `for_each` step once per item, `concurrency` at a time.
+    async fn run_for_each(
+        &self,
+        scope: Scope<'_>,
+        step: &crate::entities::WorkflowStep,
+        context: StepContext,
+    ) -> WorkflowResult<StepOutput> {
+        let config = ForEachConfig::parse(&step.config)?;
+        let items = config.resolve_items(&context.data)?;
+        let count = items.len();
+        let body = scope.graph.sequence(Some(step.id), None);
+        let iteration_scope = Scope {
+            durable: false,
+            ..scope
         };
 
-        self.finish_execution(
-            execution_id,
-            final_status,
-            context.data,
-            if failed { Some(failure_msg) } else { None },
-        )
+        let results: Vec<WorkflowResult<Flow>> =
+            stream::iter(items.into_iter().enumerate().map(|(index, item)| {
+                let mut iteration = context.clone();
+                iteration.set(config.item_key.clone(), item);
+                iteration.set(config.index_key.clone(), json!(index));
+                self.run_sequence(
+                    iteration_scope,
+                    body.clone(),
+                    Some(format!("item[{index}]")),
+                    iteration,
+                    None,
+                )
+            }))
+            .buffered(config.concurrency)
+            .collect()
+            .await;
+
+        let mut collected = Vec::with_capacity(count);
+        for (index, result) in results.into_iter().enumerate() {
+            match result? {
+                Flow::Continue(iteration) | Flow::Stop(iteration) => {
+                    let path = config.collect.as_deref().unwrap_or(&config.item_key);
+                    collected.push(
+                        lookup_template_path(path, &iteration.data)
+                            .cloned()
+                            .unwrap_or(Value::Null),
+                    );
+                }
+                Flow::Fail(_, message) => {
+                    return Err(WorkflowError::StepFailed(format!(
+                        "for_each: item {index} failed: {message}"
+                    )))
+                }
+                Flow::Suspended => {
+                    return Err(WorkflowError::StepFailed(format!(
+                        "for_each: item {index} suspended"
+                    )))
+                }
+            }
+        }
+
+        let mut new_context = context;
+        new_context.set(config.output_key.clone(), Value::Array(collected));
+        Ok(StepOutput::continue_with(
+            new_context,
+            json!({ "items": count }),
+        ))
+    }
+
+    /// Runs another workflow of the tenant as a child execution.
+    async fn run_sub_workflow(
+        &self,
+        scope: Scope<'_>,
+        step: &crate::entities::WorkflowStep,
+        context: StepContext,
+    ) -> WorkflowResult<StepOutput> {
+        let config = SubWorkflowConfig::parse(&step.config)?;
+        if scope.depth >= MAX_SUB_WORKFLOW_DEPTH {
+            return Err(WorkflowError::StepFailed(format!(
+                "sub_workflow: nesting exceeds {MAX_SUB_WORKFLOW_DEPTH} levels"
+            )));
+        }
+        let tenant_id = context.tenant_id.ok_or_else(|| {
+            WorkflowError::StepFailed("sub_workflow: step context has no tenant".into())
+        })?;
+        let child = WorkflowEntity::find_by_id(config.workflow_id)
+            .filter(workflow::Column::TenantId.eq(tenant_id))
+            .one(&self.db)
+            .await?
+            .filter(|child| child.status != WorkflowStatus::Archived)
+            .ok_or_else(|| {
+                WorkflowError::StepFailed(format!(
+                    "sub_workflow: workflow {} not found",
+                    config.workflow_id
+                ))
+            })?;
+
+        let input = resolve_templates(&config.input, &context.data);
+        check_schema(&child.trigger_config, "input_schema", &input)?;
+
+        let steps = WorkflowStepEntity::find()
+            .filter(workflow_step::Column::WorkflowId.eq(child.id))
+            .all(&self.db)
+            .await?;
+        let child_context = json!({
+            "trigger": {
+                "type": "sub_workflow",
+                "workflow_id": scope.workflow_id,
+                "execution_id": scope.execution_id,
+                "step_id": step.id,
+            },
+            "input": input,
+        });
+        let (child_execution_id, flow) = match scope.dry_run {
+            // A dry run walks the child's steps without an execution record.
+            Some(_) => {
+                let graph = StepGraph::new(steps);
+                let child_scope = Scope {
+                    graph: &graph,
+                    workflow_id: child.id,
+                    depth: scope.depth + 1,
+                    durable: false,
+                    ..scope
+                };
+                let context = StepContext::new(child_context).for_execution(tenant_id, Uuid::nil());
+                let flow = self
+                    .run_sequence(child_scope, graph.sequence(None, None), None, context, None)
+                    .await?;
+                (Uuid::nil(), flow)
+            }
+            None => {
+                self.start_execution(
+                    child.id,
+                    tenant_id,
+                    None,
+                    steps,
+                    child_context,
+                    scope.depth + 1,
+                )
+                .await?
+            }
+        };
+
+        let output = match flow {
+            Flow::Continue(child_context) | Flow::Stop(child_context) => {
+                child_context.get("output").cloned().unwrap_or(Value::Null)
+            }
+            Flow::Fail(_, message) => {
+                return Err(WorkflowError::StepFailed(format!(
+                    "sub_workflow: execution {child_execution_id} failed: {message}"
+                )))
+            }
+            Flow::Suspended => {
+                return Err(WorkflowError::StepFailed(format!(
+                    "sub_workflow: execution {child_execution_id} suspended"
+                )))
+            }
+        };
+        check_schema(&child.trigger_config, "output_schema", &output)?;
+
+        let mut new_context = context;
+        new_context.set(config.output_key.clone(), output.clone());
+        Ok(StepOutput::continue_with(
+            new_context,
+            json!({ "execution_id": child_execution_id, "output": output }),
+        ))
+    }
+
+    /// Stores the final state of a finished (not suspended) execution.
+    async fn finish(&self, execution_id: Uuid, flow: &Flow) -> WorkflowResult<()> {
+        let (status, context, error) = match flow {
+            Flow::Suspended => return Ok(()),
+            Flow::Continue(context) | Flow::Stop(context) => {
+                (ExecutionStatus::Completed, context.data.clone(), None)
+            }
+            Flow::Fail(context, message) => (
+                ExecutionStatus::Failed,
+                context.data.clone(),
+                Some(message.clone()),
+            ),
+        };
+        let success = error.is_none();
+
+        self.finish_execution(execution_id, status, context, error)
+            .await?;
+
+        info!(execution_id = %execution_id, success = success, "Workflow execution finished");
+
+        Ok(())
+    }
+
+    /// Parks the execution: the step stays `waiting`, the context is saved
+    /// and a suspension row records what resumes it.
+    async fn suspend(
+        &self,
+        workflow_id: Uuid,
+        execution_id: Uuid,
+        step_id: Uuid,
+        step_execution_id: Uuid,
+        output: StepOutput,
+    ) -> WorkflowResult<()> {
+        let StepOutput {
+            context,
+            data,
+            suspension,
+            ..
+        } = output;
+        let Some(suspension) = suspension else {
+            return Err(WorkflowError::StepFailed(
+                "step output carries no suspension".into(),
+            ));
+        };
+        let tenant_id = context.tenant_id.ok_or_else(|| {
+            WorkflowError::StepFailed("suspended step context has no tenant".into())
+        })?;
+        let (event_type, correlation) = match suspension.wait_for {
+            Some(wait) => (Some(wait.event_type), Value::Object(wait.correlation)),
+            None => (None, Value::Object(Default::default())),
+        };
+
+        let txn = self.db.begin().await?;
+        workflow_step_execution::Entity::update_many()
+            .col_expr(
+                workflow_step_execution::Column::Status,
+                Expr::value(StepExecutionStatus::Waiting.to_string()),
+            )
+            .col_expr(workflow_step_execution::Column::Output, Expr::value(data))
+            .filter(workflow_step_execution::Column::Id.eq(step_execution_id))
+            .exec(&txn)
+            .await?;
+        WorkflowSuspensionActiveModel {
+            execution_id: Set(execution_id),
+            tenant_id: Set(tenant_id),
+            workflow_id: Set(workflow_id),
+            step_id: Set(step_id),
+            step_execution_id: Set(step_execution_id),
+            resume_at: Set(suspension.resume_at.map(|at| at.fixed_offset())),
+            event_type: Set(event_type),
+            correlation: Set(correlation),
+            created_at: Set(Utc::now().fixed_offset()),
+        }
+        .insert(&txn)
         .await?;
+        if let Some(request) = suspension.approval {
+            let now = Utc::now().fixed_offset();
+            let deadline_at = suspension.resume_at.map(|at| at.fixed_offset());
+            let existing = WorkflowApprovalEntity::find()
+                .filter(workflow_approval::Column::StepExecutionId.eq(step_execution_id))
+                .one(&txn)
+                .await?;
+            match existing {
+                // Escalation reassigns the open task.
+                Some(existing) => {
+                    let mut model: WorkflowApprovalActiveModel = existing.into();
+                    model.assignee_user_ids = Set(json!(request.assignee_user_ids));
+                    model.assignee_roles = Set(json!(request.assignee_roles));
+                    model.escalation_level = Set(request.escalation_level);
+                    model.deadline_at = Set(deadline_at);
+                    model.status = Set(ApprovalStatus::Pending);
+                    model.updated_at = Set(now);
+                    model.update(&txn).await?;
+                }
+                None => {
+                    WorkflowApprovalActiveModel {
+                        id: Set(Uuid::new_v4()),
+                        tenant_id: Set(tenant_id),
+                        workflow_id: Set(workflow_id),
+                        execution_id: Set(execution_id),
+                        step_id: Set(step_id),
+                        step_execution_id: Set(step_execution_id),
+                        title: Set(request.title),
+                        description: Set(request.description),
+                        assignee_user_ids: Set(json!(request.assignee_user_ids)),
+                        assignee_roles: Set(json!(request.assignee_roles)),
+                        status: Set(ApprovalStatus::Pending),
+                        escalation_level: Set(request.escalation_level),
+                        deadline_at: Set(deadline_at),
+                        decided_by: Set(None),
+                        comment: Set(None),
+                        decided_at: Set(None),
+                        created_at: Set(now),
+                        updated_at: Set(now),
+                    }
+                    .insert(&txn)
+                    .await?;
+                }
+            }
+        }
+        workflow_execution::Entity::update_many()
+            .col_expr(
+                workflow_execution::Column::Status,
+                Expr::value(ExecutionStatus::Suspended.to_string()),
+            )
+            .col_expr(
+                workflow_execution::Column::Context,
+                Expr::value(context.data),
+            )
+            .filter(workflow_execution::Column::Id.eq(execution_id))
+            .exec(&txn)
+            .await?;
+        txn.commit().await?;
+
+        info!(
+            execution_id = %execution_id,
+            step_id = %step_id,
+            resume_at = ?suspension.resume_at,
+            "Workflow execution suspended"
+        );
+        Ok(())
+    }
 
-        info!(execution_id = %execution_id, success = !failed, "Workflow execution finished");
+    /// Closes the pending approval task of a resumed `approval` step with
+    /// the decision or, at the deadline, as expired. A deadline that
+    /// escalates leaves the task open; the step reassigns it when it
+    /// suspends again.
+    async fn settle_approval(
+        &self,
+        step: &crate::entities::WorkflowStep,
+        step_execution_id: Uuid,
+        signal: &ResumeSignal,
+    ) -> WorkflowResult<()> {
+        if step.step_type != StepType::Approval {
+            return Ok(());
+        }
+        let Some(approval) = WorkflowApprovalEntity::find()
+            .filter(workflow_approval::Column::StepExecutionId.eq(step_execution_id))
+            .filter(workflow_approval::Column::Status.eq(ApprovalStatus::Pending))
+            .one(&self.db)
+            .await?
+        else {
+            return Ok(());
+        };
 
-        Ok(execution_id)
+        let now = Utc::now().fixed_offset();
+        let escalation_level = approval.escalation_level;
+        let mut model: WorkflowApprovalActiveModel = approval.into();
+        match signal {
+            ResumeSignal::Decision(decision) => {
+                model.status = Set(if decision.approved {
+                    ApprovalStatus::Approved
+                } else {
+                    ApprovalStatus::Rejected
+                });
+                model.decided_by = Set(Some(decision.decided_by));
+                model.comment = Set(decision.comment.clone());
+                model.decided_at = Set(Some(decision.decided_at.fixed_offset()));
+            }
+            ResumeSignal::Timer => {
+                let escalates = ApprovalConfig::parse(&step.config)
+                    .is_ok_and(|config| config.escalates(escalation_level));
+                if escalates {
+                    return Ok(());
+                }
+                model.status = Set(ApprovalStatus::Expired);
+                model.decided_at = Set(Some(now));
+            }
+            ResumeSignal::Event(_) => return Ok(()),
+        }
+        model.updated_at = Set(now);
+        model.update(&self.db).await?;
+        Ok(())
     }
 
     async fn finish_step_execution(
@@ -347,3 +1453,80 @@ impl WorkflowEngine {
         Ok(())
     }
 }
+
+/// Stand-in for a side-effecting step during a dry run: reports the resolved
+/// config and stores `mock`, if given, where the real step stores its result.
+fn mock_step(
+    step: &crate::entities::WorkflowStep,
+    mut context: StepContext,
+    mock: Option<&Value>,
+) -> StepOutput {
+    let request = resolve_templates(&step.config, &context.data);
+    let result_key = match step.step_type {
+        StepType::Action => Some(
+            step.config
+                .get("output_key")
+                .and_then(Value::as_str)
+                .unwrap_or(DEFAULT_ACTION_OUTPUT_KEY),
+        ),
+        StepType::Http => Some("http_response"),
+        StepType::AlloyScript => Some("alloy_result"),
+        _ => None,
+    };
+    if let (Some(key), Some(mock)) = (result_key, mock) {
+        context.set(key, mock.clone());
+    }
+    StepOutput::continue_with(
+        context,
+        json!({ "mocked": true, "request": request, "result": mock }),
+    )
+}
+
+/// Whether `step` can be restarted on its own: it must not be nested in a
+/// `parallel`, `for_each` or `sub_workflow` step.
+fn restartable(graph: &StepGraph, step: &crate::entities::WorkflowStep) -> Result<(), String> {
+    let mut current = step;
+    while let Some(parent) = current.parent_step_id.and_then(|id| graph.get(id)) {
+        if parent.step_type != StepType::Branch {
+            return Err(format!(
+                "step {} is inside a {} step; re-run from {} instead",
+                step.id, parent.step_type, parent.id
+            ));
+        }
+        current = parent;
+    }
+    Ok(())
+}
+
+/// Copies top-level keys that a parallel lane changed relative to `base`
+/// into `target`.
+fn merge_changes(target: &mut Value, base: &Value, lane: Value) {
+    let (Value::Object(target), Value::Object(lane)) = (target, lane) else {
+        return;
+    };
+    for (key, value) in lane {
+        if base.get(&key) != Some(&value) {
+            target.insert(key, value);
+        }
+    }
+}
+
+/// Validates a sub-workflow input or output against the schema declared
+/// under `key` in the child's `trigger_config`, if any.
+fn check_schema(trigger_config: &Value, key: &str, value: &Value) -> WorkflowResult<()> {
+    let Some(schema) = trigger_config.get(key) else {
+        return Ok(());
+    };
+    let violations = rustok_core::json_schema::validate_json_schema(schema, value);
+    if violations.is_empty() {
+        return Ok(());
+    }
+    Err(WorkflowError::StepFailed(format!(
+        "sub_workflow: value does not match {key}: {}",
+        violations
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("; ")
+    )))
+}
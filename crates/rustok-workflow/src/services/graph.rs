@@ -0,0 +1,80 @@
+use std::collections::{HashMap, HashSet};
+
+use uuid::Uuid;
+
+use crate::entities::WorkflowStep;
+
+/// Steps of one workflow arranged as a tree: the top-level sequence plus the
+/// children of each control-flow step, grouped by branch.
+///
+/// Steps are kept in `position` order; steps whose parent is missing are
+/// unreachable and never run.
+pub(crate) struct StepGraph {
+    steps: Vec<WorkflowStep>,
+    index: HashMap<Uuid, usize>,
+}
+
+impl StepGraph {
+    pub fn new(mut steps: Vec<WorkflowStep>) -> Self {
+        steps.sort_by_key(|step| step.position);
+        let index = steps
+            .iter()
+            .enumerate()
+            .map(|(idx, step)| (step.id, idx))
+            .collect();
+        Self { steps, index }
+    }
+
+    pub fn get(&self, id: Uuid) -> Option<&WorkflowStep> {
+        self.index.get(&id).map(|idx| &self.steps[*idx])
+    }
+
+    /// Steps of `parent` (or the top level) in `branch`, in order.
+    pub fn sequence(&self, parent: Option<Uuid>, branch: Option<&str>) -> Vec<&WorkflowStep> {
+        self.steps
+            .iter()
+            .filter(|step| step.parent_step_id == parent && step.branch.as_deref() == branch)
+            .collect()
+    }
+
+    /// `step` and the steps after it in its own sequence.
+    pub fn sequence_from(&self, step: &WorkflowStep) -> Vec<&WorkflowStep> {
+        self.sequence(step.parent_step_id, step.branch.as_deref())
+            .into_iter()
+            .skip_while(|sibling| sibling.id != step.id)
+            .collect()
+    }
+
+    /// Steps after `step` in its own sequence.
+    pub fn sequence_after(&self, step: &WorkflowStep) -> Vec<&WorkflowStep> {
+        self.sequence_from(step).into_iter().skip(1).collect()
+    }
+
+    /// Distinct branch names used by the children of `parent`, in order of
+    /// first appearance.
+    pub fn lanes(&self, parent: Uuid) -> Vec<String> {
+        let mut seen = HashSet::new();
+        self.steps
+            .iter()
+            .filter(|step| step.parent_step_id == Some(parent))
+            .filter_map(|step| step.branch.clone())
+            .filter(|branch| seen.insert(branch.clone()))
+            .collect()
+    }
+
+    /// Whether `ancestor` is `step` itself or one of its parents.
+    pub fn is_ancestor(&self, ancestor: Uuid, step: Uuid) -> bool {
+        let mut current = Some(step);
+        let mut visited = HashSet::new();
+        while let Some(id) = current {
+            if id == ancestor {
+                return true;
+            }
+            if !visited.insert(id) {
+                return false;
+            }
+            current = self.get(id).and_then(|step| step.parent_step_id);
+        }
+        false
+    }
+}
@@ -1,9 +1,18 @@
 pub mod cron_scheduler;
 pub mod engine;
+pub(crate) mod graph;
 pub mod trigger_handler;
+pub mod webhook;
+pub mod webhook_dispatcher;
+pub mod webhook_subscriptions;
 pub mod workflow_service;
 
 pub use cron_scheduler::WorkflowCronScheduler;
 pub use engine::WorkflowEngine;
 pub use trigger_handler::WorkflowTriggerHandler;
+pub use webhook::WebhookDeliveryLog;
+pub use webhook_dispatcher::{
+    WebhookDispatcher, WebhookDispatcherConfig, WebhookSubscriptionHandler,
+};
+pub use webhook_subscriptions::WebhookSubscriptionService;
 pub use workflow_service::WorkflowService;
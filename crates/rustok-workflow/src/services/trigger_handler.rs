@@ -3,14 +3,17 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
 use serde_json::json;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 use rustok_core::events::{EventEnvelope, EventHandler, HandlerResult};
 
 use crate::entities::{workflow, WorkflowEntity, WorkflowStatus};
+use crate::error::{WorkflowError, WorkflowResult};
 use crate::services::{WorkflowEngine, WorkflowService};
+use crate::steps::{Expression, ResumeSignal, WorkflowActionRuntime};
 
-/// Subscribes to all domain events and triggers matching active workflows.
+/// Subscribes to all domain events, triggers matching active workflows and
+/// resumes executions waiting for the event.
 pub struct WorkflowTriggerHandler {
     db: DatabaseConnection,
     engine: Arc<WorkflowEngine>,
@@ -27,6 +30,14 @@ impl WorkflowTriggerHandler {
             service,
         }
     }
+
+    /// Runs `action` steps against the given module actions.
+    pub fn with_action_runtime(mut self, runtime: Option<WorkflowActionRuntime>) -> Self {
+        self.engine =
+            Arc::new(WorkflowEngine::new(self.db.clone()).with_action_runtime(runtime.clone()));
+        self.service = Arc::new(WorkflowService::new(self.db.clone()).with_action_runtime(runtime));
+        self
+    }
 }
 
 #[async_trait]
@@ -43,6 +54,26 @@ impl EventHandler for WorkflowTriggerHandler {
     async fn handle(&self, envelope: &EventEnvelope) -> HandlerResult {
         let event_type = &envelope.event_type;
         let tenant_id = envelope.tenant_id;
+        let event = event_context(envelope);
+
+        // Resume executions suspended on a `wait_for_event` step for this event
+        let waiting = self
+            .engine
+            .event_suspensions(tenant_id, event_type, &event)
+            .await
+            .map_err(|e| {
+                rustok_core::Error::External(format!("DB error in WorkflowTriggerHandler: {e}"))
+            })?;
+        for suspension in waiting {
+            let engine = self.engine.clone();
+            let signal = ResumeSignal::Event(event.clone());
+            tokio::spawn(async move {
+                let execution_id = suspension.execution_id;
+                if let Err(e) = engine.resume(suspension, signal).await {
+                    error!(execution_id = %execution_id, error = %e, "Workflow resume failed");
+                }
+            });
+        }
 
         // Find all active workflows for this tenant with an event trigger matching this event type
         let workflows = WorkflowEntity::find()
@@ -54,11 +85,15 @@ impl EventHandler for WorkflowTriggerHandler {
                 rustok_core::Error::External(format!("DB error in WorkflowTriggerHandler: {e}"))
             })?;
 
+        // Build initial context from the event envelope
+        let initial_context = json!({ "event": event });
+
         let matching: Vec<_> = workflows
             .into_iter()
             .filter(|w| {
                 // Check if trigger_config matches: {"type": "event", "event_type": "<pattern>"}
                 matches_event_trigger(&w.trigger_config, event_type)
+                    && passes_trigger_filter(w.id, &w.trigger_config, &initial_context)
             })
             .collect();
 
@@ -73,17 +108,6 @@ impl EventHandler for WorkflowTriggerHandler {
             "Triggering workflows for event"
         );
 
-        // Build initial context from the event envelope
-        let initial_context = json!({
-            "event": {
-                "id": envelope.id,
-                "type": envelope.event_type,
-                "tenant_id": envelope.tenant_id,
-                "timestamp": envelope.timestamp,
-                "actor_id": envelope.actor_id,
-            }
-        });
-
         for workflow in matching {
             let workflow_id = workflow.id;
             let steps = match self.service.load_steps(workflow_id).await {
@@ -112,19 +136,119 @@ impl EventHandler for WorkflowTriggerHandler {
     }
 }
 
+/// The `event` object exposed to workflows: envelope metadata plus the
+/// event's own fields under `payload`.
+fn event_context(envelope: &EventEnvelope) -> serde_json::Value {
+    let payload = serde_json::to_value(&envelope.event)
+        .ok()
+        .and_then(|event| event.get("data").cloned())
+        .unwrap_or(serde_json::Value::Null);
+    json!({
+        "id": envelope.id,
+        "type": envelope.event_type,
+        "tenant_id": envelope.tenant_id,
+        "timestamp": envelope.timestamp,
+        "actor_id": envelope.actor_id,
+        "payload": payload,
+    })
+}
+
 fn matches_event_trigger(trigger_config: &serde_json::Value, event_type: &str) -> bool {
     let trigger_type = trigger_config.get("type").and_then(|v| v.as_str());
     if trigger_type != Some("event") {
         return false;
     }
     match trigger_config.get("event_type").and_then(|v| v.as_str()) {
-        Some(pattern) => {
-            if let Some(prefix) = pattern.strip_suffix('*') {
-                event_type.starts_with(prefix)
-            } else {
-                event_type == pattern
-            }
-        }
+        Some(pattern) => event_type_matches(pattern, event_type),
         None => false,
     }
 }
+
+/// A `*`-suffixed pattern matches by prefix, anything else exactly.
+pub(crate) fn event_type_matches(pattern: &str, event_type: &str) -> bool {
+    if let Some(prefix) = pattern.strip_suffix('*') {
+        event_type.starts_with(prefix)
+    } else {
+        event_type == pattern
+    }
+}
+
+/// Checks the optional `filter` expression of a trigger config.
+pub(crate) fn validate_trigger_filter(trigger_config: &serde_json::Value) -> WorkflowResult<()> {
+    match trigger_config.get("filter") {
+        None => Ok(()),
+        Some(serde_json::Value::String(source)) => Expression::compile(source)
+            .map(|_| ())
+            .map_err(|err| WorkflowError::InvalidTriggerConfig(format!("filter: {err}"))),
+        Some(_) => Err(WorkflowError::InvalidTriggerConfig(
+            "filter: must be an expression string".into(),
+        )),
+    }
+}
+
+/// Evaluates the trigger's `filter` expression against the initial execution
+/// context; a filter that fails to compile or evaluate does not fire.
+pub(crate) fn passes_trigger_filter(
+    workflow_id: uuid::Uuid,
+    trigger_config: &serde_json::Value,
+    context: &serde_json::Value,
+) -> bool {
+    let Some(source) = trigger_config.get("filter") else {
+        return true;
+    };
+    let result = source
+        .as_str()
+        .ok_or_else(|| "must be an expression string".to_string())
+        .and_then(Expression::compile)
+        .and_then(|filter| filter.evaluate(context));
+    result.unwrap_or_else(|err| {
+        warn!(workflow_id = %workflow_id, error = %err, "Workflow trigger filter failed");
+        false
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trigger_filter_gates_matching_events() {
+        let trigger = json!({
+            "type": "event",
+            "event_type": "order.*",
+            "filter": "event.payload.new_status == 'paid' and event.payload.total >= 100"
+        });
+        assert!(validate_trigger_filter(&trigger).is_ok());
+        assert!(matches_event_trigger(&trigger, "order.status_changed"));
+
+        let workflow_id = uuid::Uuid::new_v4();
+        let event = |status: &str, total: u32| json!({ "event": { "payload": { "new_status": status, "total": total } } });
+        assert!(passes_trigger_filter(
+            workflow_id,
+            &trigger,
+            &event("paid", 120)
+        ));
+        assert!(!passes_trigger_filter(
+            workflow_id,
+            &trigger,
+            &event("paid", 20)
+        ));
+        assert!(!passes_trigger_filter(
+            workflow_id,
+            &trigger,
+            &event("shipped", 120)
+        ));
+        assert!(passes_trigger_filter(
+            workflow_id,
+            &json!({ "type": "event" }),
+            &json!({})
+        ));
+
+        for filter in [json!("event.payload.total >"), json!(true)] {
+            assert!(matches!(
+                validate_trigger_filter(&json!({ "type": "event", "filter": filter })),
+                Err(WorkflowError::InvalidTriggerConfig(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,251 @@
+//! Signature verification for inbound workflow webhooks.
+//!
+//! Senders sign `"{timestamp}.{body}"` with HMAC-SHA256 using the workflow's
+//! webhook secret and send the hex digest in `X-Webhook-Signature`
+//! (optionally prefixed with `sha256=`), the Unix timestamp in
+//! `X-Webhook-Timestamp` and a unique delivery id in `X-Webhook-Id`.
+
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, KeyInit, Mac};
+use sha2::Sha256;
+
+use crate::entities::{Workflow, WorkflowWebhookDeliveryActiveModel};
+
+/// Requests whose timestamp differs from the server clock by more than this
+/// are rejected as stale.
+pub const TIMESTAMP_TOLERANCE_SECS: i64 = 300;
+
+/// How long the secret replaced by a rotation keeps verifying signatures.
+pub const PREVIOUS_SECRET_GRACE_HOURS: i64 = 24;
+
+/// Delivery records older than this are purged.
+pub const DELIVERY_RETENTION_DAYS: i64 = 30;
+
+/// Rejected deliveries held in memory between two flushes; further
+/// rejections are only counted.
+pub const MAX_PENDING_REJECTIONS: usize = 1_000;
+
+const SECRET_LEN: usize = 48;
+const MAX_DELIVERY_ID_LEN: usize = 128;
+
+/// Signature-related parts of an inbound webhook request.
+#[derive(Debug, Clone, Default)]
+pub struct WebhookRequest {
+    pub timestamp: Option<String>,
+    pub delivery_id: Option<String>,
+    pub signature: Option<String>,
+    pub body: Vec<u8>,
+}
+
+/// Rejected deliveries waiting for `WorkflowService::flush_webhook_deliveries`.
+///
+/// Unauthenticated callers must not be able to make every request write a
+/// row, so the request path only queues rejections here and the periodic
+/// flush writes them in one batch. Clones share the same queue.
+#[derive(Clone, Default)]
+pub struct WebhookDeliveryLog {
+    pending: Arc<Mutex<PendingRejections>>,
+}
+
+#[derive(Default)]
+struct PendingRejections {
+    deliveries: Vec<WorkflowWebhookDeliveryActiveModel>,
+    dropped: u64,
+}
+
+impl WebhookDeliveryLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn push(&self, delivery: WorkflowWebhookDeliveryActiveModel) {
+        let mut pending = self.pending.lock().expect("webhook delivery log poisoned");
+        if pending.deliveries.len() < MAX_PENDING_REJECTIONS {
+            pending.deliveries.push(delivery);
+        } else {
+            pending.dropped += 1;
+        }
+    }
+
+    /// Takes the queued rejections and the number dropped since the last call.
+    pub(crate) fn take(&self) -> (Vec<WorkflowWebhookDeliveryActiveModel>, u64) {
+        let mut pending = self.pending.lock().expect("webhook delivery log poisoned");
+        let dropped = std::mem::take(&mut pending.dropped);
+        (std::mem::take(&mut pending.deliveries), dropped)
+    }
+}
+
+pub fn generate_secret() -> String {
+    rustok_core::utils::random_string(SECRET_LEN)
+}
+
+fn mac(secret: &str, timestamp: i64, body: &[u8]) -> Hmac<Sha256> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    mac
+}
+
+/// Hex-encoded HMAC-SHA256 of `"{timestamp}.{body}"`.
+pub fn sign(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    hex::encode(mac(secret, timestamp, body).finalize().into_bytes())
+}
+
+/// Constant-time check of a `sha256=<hex>` or bare hex signature.
+pub fn signature_matches(secret: &str, timestamp: i64, body: &[u8], signature: &str) -> bool {
+    let signature = signature.trim();
+    let signature = signature.strip_prefix("sha256=").unwrap_or(signature);
+    let Ok(signature) = hex::decode(signature) else {
+        return false;
+    };
+    mac(secret, timestamp, body)
+        .verify_slice(&signature)
+        .is_ok()
+}
+
+/// Checks timestamp freshness, delivery id and signature against the
+/// workflow's current secret and, during the grace period after a rotation,
+/// the previous one. Returns the validated delivery id.
+pub fn verify(
+    workflow: &Workflow,
+    request: &WebhookRequest,
+    now: DateTime<Utc>,
+) -> Result<String, String> {
+    let Some(secret) = workflow.webhook_secret.as_deref() else {
+        return Err("webhook secret is not configured".to_string());
+    };
+
+    let timestamp = request
+        .timestamp
+        .as_deref()
+        .ok_or("missing X-Webhook-Timestamp header")?
+        .trim()
+        .parse::<i64>()
+        .map_err(|_| "X-Webhook-Timestamp must be a Unix timestamp in seconds")?;
+    if (now.timestamp() - timestamp).abs() > TIMESTAMP_TOLERANCE_SECS {
+        return Err(format!(
+            "timestamp is outside the {TIMESTAMP_TOLERANCE_SECS}s tolerance"
+        ));
+    }
+
+    let delivery_id = request
+        .delivery_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .ok_or("missing X-Webhook-Id header")?;
+    if delivery_id.len() > MAX_DELIVERY_ID_LEN {
+        return Err(format!(
+            "X-Webhook-Id is longer than {MAX_DELIVERY_ID_LEN} characters"
+        ));
+    }
+
+    let signature = request
+        .signature
+        .as_deref()
+        .ok_or("missing X-Webhook-Signature header")?;
+
+    let previous_secret = workflow.webhook_previous_secret.as_deref().filter(|_| {
+        workflow
+            .webhook_secret_rotated_at
+            .is_some_and(|rotated_at| {
+                now - rotated_at.with_timezone(&Utc) < Duration::hours(PREVIOUS_SECRET_GRACE_HOURS)
+            })
+    });
+
+    let valid = std::iter::once(secret)
+        .chain(previous_secret)
+        .any(|secret| signature_matches(secret, timestamp, &request.body, signature));
+    if !valid {
+        return Err("signature mismatch".to_string());
+    }
+
+    Ok(delivery_id.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::WorkflowStatus;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    fn workflow(secret: &str) -> Workflow {
+        let now = Utc::now().fixed_offset();
+        Workflow {
+            id: Uuid::new_v4(),
+            tenant_id: Uuid::new_v4(),
+            name: "hook".to_string(),
+            description: None,
+            status: WorkflowStatus::Active,
+            trigger_config: json!({ "type": "webhook" }),
+            created_by: None,
+            created_at: now,
+            updated_at: now,
+            failure_count: 0,
+            auto_disabled_at: None,
+            webhook_slug: Some("hook".to_string()),
+            webhook_secret: Some(secret.to_string()),
+            webhook_previous_secret: None,
+            webhook_secret_rotated_at: None,
+        }
+    }
+
+    fn request(secret: &str, timestamp: i64, body: &[u8]) -> WebhookRequest {
+        WebhookRequest {
+            timestamp: Some(timestamp.to_string()),
+            delivery_id: Some("evt_1".to_string()),
+            signature: Some(format!("sha256={}", sign(secret, timestamp, body))),
+            body: body.to_vec(),
+        }
+    }
+
+    #[test]
+    fn accepts_fresh_signed_request() {
+        let now = Utc::now();
+        let request = request("s3cret", now.timestamp(), br#"{"a":1}"#);
+
+        assert_eq!(
+            verify(&workflow("s3cret"), &request, now),
+            Ok("evt_1".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_tampered_body_and_stale_timestamp() {
+        let now = Utc::now();
+        let mut tampered = request("s3cret", now.timestamp(), br#"{"a":1}"#);
+        tampered.body = br#"{"a":2}"#.to_vec();
+        assert_eq!(
+            verify(&workflow("s3cret"), &tampered, now),
+            Err("signature mismatch".to_string())
+        );
+
+        let stale = request(
+            "s3cret",
+            now.timestamp() - TIMESTAMP_TOLERANCE_SECS - 1,
+            b"{}",
+        );
+        assert!(verify(&workflow("s3cret"), &stale, now)
+            .unwrap_err()
+            .contains("tolerance"));
+    }
+
+    #[test]
+    fn previous_secret_only_valid_during_grace_period() {
+        let now = Utc::now();
+        let mut rotated = workflow("new");
+        rotated.webhook_previous_secret = Some("old".to_string());
+        rotated.webhook_secret_rotated_at = Some((now - Duration::hours(1)).fixed_offset());
+        let signed_with_old = request("old", now.timestamp(), b"{}");
+        assert!(verify(&rotated, &signed_with_old, now).is_ok());
+
+        rotated.webhook_secret_rotated_at =
+            Some((now - Duration::hours(PREVIOUS_SECRET_GRACE_HOURS + 1)).fixed_offset());
+        assert!(verify(&rotated, &signed_with_old, now).is_err());
+    }
+}
@@ -0,0 +1,490 @@
+//! Outbound webhook deliveries for tenant webhook subscriptions.
+//!
+//! [`WebhookSubscriptionHandler`] queues one delivery per matching active
+//! subscription for every domain event relayed from the outbox;
+//! [`WebhookDispatcher`] sends due deliveries as signed `POST` requests.
+//!
+//! Receivers verify `X-Webhook-Signature: sha256=<hex>`, the HMAC-SHA256 of
+//! `"{X-Webhook-Timestamp}.{body}"` under the subscription secret — the same
+//! scheme inbound workflow webhooks accept. `X-Webhook-Id` is the delivery id
+//! and stays the same across retries, so receivers can deduplicate.
+
+use std::cmp;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set,
+};
+use serde_json::{json, Value};
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use rustok_core::events::{EventEnvelope, EventHandler, HandlerResult};
+use rustok_core::{read_body_limited, SsrfProtection};
+
+use crate::entities::{
+    webhook_subscription, webhook_subscription_delivery, SubscriptionDeliveryStatus,
+    WebhookSubscription, WebhookSubscriptionActiveModel, WebhookSubscriptionDelivery,
+    WebhookSubscriptionDeliveryActiveModel, WebhookSubscriptionDeliveryEntity,
+    WebhookSubscriptionEntity, WebhookSubscriptionStatus,
+};
+use crate::error::WorkflowResult;
+use crate::services::trigger_handler::event_type_matches;
+use crate::services::webhook;
+
+/// Response bodies are read up to this many bytes; the rest is discarded.
+const RESPONSE_BODY_LIMIT: usize = 4096;
+
+#[derive(Clone, Debug)]
+pub struct WebhookDispatcherConfig {
+    pub batch_size: u64,
+    pub poll_interval: Duration,
+    pub request_timeout: Duration,
+    /// Attempts before a delivery moves to the dead-letter state
+    pub max_attempts: i32,
+    pub backoff_base: Duration,
+    pub backoff_max: Duration,
+    /// Consecutive failed attempts after which a subscription is disabled
+    pub auto_disable_after: i32,
+}
+
+impl Default for WebhookDispatcherConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 50,
+            poll_interval: Duration::from_secs(1),
+            request_timeout: Duration::from_secs(10),
+            max_attempts: 8,
+            backoff_base: Duration::from_secs(30),
+            backoff_max: Duration::from_secs(6 * 60 * 60),
+            auto_disable_after: 25,
+        }
+    }
+}
+
+/// Subscribes to all domain events and queues deliveries for the tenant's
+/// active subscriptions whose event types match.
+pub struct WebhookSubscriptionHandler {
+    db: DatabaseConnection,
+}
+
+impl WebhookSubscriptionHandler {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self { db }
+    }
+
+    /// Queue the event for every matching subscription. An event dispatched
+    /// again (e.g. after a relay retry) is queued once per subscription.
+    pub async fn enqueue(&self, envelope: &EventEnvelope) -> WorkflowResult<usize> {
+        let subscriptions = WebhookSubscriptionEntity::find()
+            .filter(webhook_subscription::Column::TenantId.eq(envelope.tenant_id))
+            .filter(
+                webhook_subscription::Column::Status
+                    .eq(WebhookSubscriptionStatus::Active.to_string()),
+            )
+            .all(&self.db)
+            .await?;
+        let matching: Vec<_> = subscriptions
+            .into_iter()
+            .filter(|subscription| subscribes_to(subscription, &envelope.event_type))
+            .collect();
+        if matching.is_empty() {
+            return Ok(0);
+        }
+
+        let payload = event_payload(envelope);
+        let now = Utc::now().fixed_offset();
+        let deliveries =
+            matching
+                .iter()
+                .map(|subscription| WebhookSubscriptionDeliveryActiveModel {
+                    id: Set(Uuid::new_v4()),
+                    tenant_id: Set(envelope.tenant_id),
+                    subscription_id: Set(subscription.id),
+                    event_id: Set(envelope.id),
+                    event_type: Set(envelope.event_type.clone()),
+                    payload: Set(payload.clone()),
+                    status: Set(SubscriptionDeliveryStatus::Pending),
+                    attempts: Set(0),
+                    next_attempt_at: Set(Some(now)),
+                    request_headers: Set(json!({})),
+                    request_body: Set(None),
+                    response_status: Set(None),
+                    response_body: Set(None),
+                    error: Set(None),
+                    duration_ms: Set(None),
+                    last_attempt_at: Set(None),
+                    delivered_at: Set(None),
+                    created_at: Set(now),
+                });
+        let inserted = WebhookSubscriptionDeliveryEntity::insert_many(deliveries)
+            .on_conflict(
+                OnConflict::columns([
+                    webhook_subscription_delivery::Column::SubscriptionId,
+                    webhook_subscription_delivery::Column::EventId,
+                ])
+                .do_nothing()
+                .to_owned(),
+            )
+            .exec_without_returning(&self.db)
+            .await?;
+
+        Ok(inserted as usize)
+    }
+}
+
+#[async_trait]
+impl EventHandler for WebhookSubscriptionHandler {
+    fn name(&self) -> &'static str {
+        "WebhookSubscriptionHandler"
+    }
+
+    fn handles(&self, _event: &rustok_core::events::DomainEvent) -> bool {
+        // Subscribe to all events — filtering is done in handle() by matching event_types
+        true
+    }
+
+    async fn handle(&self, envelope: &EventEnvelope) -> HandlerResult {
+        self.enqueue(envelope).await.map(|_| ()).map_err(|e| {
+            rustok_core::Error::External(format!("DB error in WebhookSubscriptionHandler: {e}"))
+        })
+    }
+}
+
+/// Result of one delivery attempt.
+struct Attempt {
+    headers: Value,
+    body: String,
+    response_status: Option<i32>,
+    response_body: Option<String>,
+    error: Option<String>,
+    duration_ms: i64,
+}
+
+impl Attempt {
+    fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Polls due deliveries and sends them, retrying failures with exponential
+/// backoff until they are delivered or dead-lettered.
+///
+/// Every attempt resolves the subscription host through [`SsrfProtection`]
+/// and connects only to the checked addresses with redirects disabled, so
+/// neither DNS rebinding nor a redirect reaches an internal address.
+pub struct WebhookDispatcher {
+    db: DatabaseConnection,
+    ssrf: SsrfProtection,
+    config: WebhookDispatcherConfig,
+}
+
+impl WebhookDispatcher {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self::with_config(db, WebhookDispatcherConfig::default())
+    }
+
+    pub fn with_config(db: DatabaseConnection, config: WebhookDispatcherConfig) -> Self {
+        Self {
+            db,
+            ssrf: SsrfProtection::new(),
+            config,
+        }
+    }
+
+    pub fn with_ssrf_protection(mut self, ssrf: SsrfProtection) -> Self {
+        self.ssrf = ssrf;
+        self
+    }
+
+    /// Start the dispatcher as a background task.
+    /// Returns a handle that can be aborted to stop the dispatcher.
+    pub fn start(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            info!("WebhookDispatcher started");
+            let mut interval = tokio::time::interval(self.config.poll_interval);
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.deliver_due(Utc::now()).await {
+                    error!(error = %e, "WebhookDispatcher tick error");
+                }
+            }
+        })
+    }
+
+    /// Send up to one batch of deliveries due at `now`. Returns how many
+    /// attempts were made.
+    pub async fn deliver_due(&self, now: DateTime<Utc>) -> WorkflowResult<usize> {
+        let due = WebhookSubscriptionDeliveryEntity::find()
+            .filter(
+                webhook_subscription_delivery::Column::Status
+                    .eq(SubscriptionDeliveryStatus::Pending.to_string()),
+            )
+            .filter(webhook_subscription_delivery::Column::NextAttemptAt.lte(now.fixed_offset()))
+            .order_by_asc(webhook_subscription_delivery::Column::NextAttemptAt)
+            .limit(self.config.batch_size)
+            .all(&self.db)
+            .await?;
+
+        let mut attempted = 0;
+        for delivery in due {
+            if !self.claim(&delivery, now).await? {
+                continue;
+            }
+            let subscription = WebhookSubscriptionEntity::find_by_id(delivery.subscription_id)
+                .one(&self.db)
+                .await?;
+            let Some(subscription) = subscription else {
+                continue;
+            };
+            if subscription.status != WebhookSubscriptionStatus::Active {
+                // Held until the subscription is re-enabled.
+                let mut held: WebhookSubscriptionDeliveryActiveModel = delivery.into();
+                held.next_attempt_at = Set(None);
+                held.update(&self.db).await?;
+                continue;
+            }
+
+            let attempt = self.send(&subscription, &delivery, now).await;
+            self.record(subscription, delivery, attempt, now).await?;
+            attempted += 1;
+        }
+        Ok(attempted)
+    }
+
+    /// Lease the delivery so that concurrent dispatchers skip it; the lease
+    /// expires with the request timeout if this process dies mid-attempt.
+    async fn claim(
+        &self,
+        delivery: &WebhookSubscriptionDelivery,
+        now: DateTime<Utc>,
+    ) -> WorkflowResult<bool> {
+        let lease_until = now + self.config.request_timeout * 2;
+        let claimed = WebhookSubscriptionDeliveryEntity::update_many()
+            .col_expr(
+                webhook_subscription_delivery::Column::NextAttemptAt,
+                Expr::value(lease_until.fixed_offset()),
+            )
+            .filter(webhook_subscription_delivery::Column::Id.eq(delivery.id))
+            .filter(
+                webhook_subscription_delivery::Column::Status
+                    .eq(SubscriptionDeliveryStatus::Pending.to_string()),
+            )
+            .filter(
+                webhook_subscription_delivery::Column::NextAttemptAt.eq(delivery.next_attempt_at),
+            )
+            .exec(&self.db)
+            .await?;
+        Ok(claimed.rows_affected == 1)
+    }
+
+    async fn send(
+        &self,
+        subscription: &WebhookSubscription,
+        delivery: &WebhookSubscriptionDelivery,
+        now: DateTime<Utc>,
+    ) -> Attempt {
+        let body = delivery.payload.to_string();
+        let timestamp = now.timestamp();
+        let signature = webhook::sign(&subscription.secret, timestamp, body.as_bytes());
+        let headers = [
+            ("Content-Type", "application/json".to_string()),
+            ("X-Webhook-Id", delivery.id.to_string()),
+            ("X-Webhook-Event", delivery.event_type.clone()),
+            ("X-Webhook-Timestamp", timestamp.to_string()),
+            ("X-Webhook-Signature", format!("sha256={signature}")),
+        ];
+
+        let started = Instant::now();
+        let (response_status, response_body, error) =
+            match self.post(&subscription.url, &headers, body.clone()).await {
+                // Redirects are not followed, so a 3xx counts as a failure.
+                Ok((status, text)) => (
+                    Some(i32::from(status.as_u16())),
+                    Some(text),
+                    (!status.is_success()).then(|| format!("endpoint returned {status}")),
+                ),
+                Err(e) => (None, None, Some(e)),
+            };
+
+        Attempt {
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), Value::String(value.clone())))
+                .collect::<serde_json::Map<_, _>>()
+                .into(),
+            body,
+            response_status,
+            response_body,
+            error,
+            duration_ms: started.elapsed().as_millis() as i64,
+        }
+    }
+
+    /// Resolve and check the endpoint, then send the request pinned to the
+    /// checked addresses. Returns the status and the capped response body.
+    async fn post(
+        &self,
+        url: &str,
+        headers: &[(&str, String)],
+        body: String,
+    ) -> Result<(reqwest::StatusCode, String), String> {
+        let resolved = self.ssrf.resolve(url).await?;
+        let client = resolved
+            .client_builder()
+            .timeout(self.config.request_timeout)
+            .user_agent(concat!("RusTok-Webhooks/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .map_err(|e| e.to_string())?;
+        let mut request = client.post(resolved.url).body(body);
+        for (name, value) in headers {
+            request = request.header(*name, value);
+        }
+        let response = request.send().await.map_err(|e| e.to_string())?;
+        let status = response.status();
+        let (text, _) = read_body_limited(response, RESPONSE_BODY_LIMIT)
+            .await
+            .unwrap_or_default();
+        Ok((status, String::from_utf8_lossy(&text).into_owned()))
+    }
+
+    /// Store the attempt, schedule the retry or dead-letter the delivery and
+    /// keep the subscription's failure streak.
+    async fn record(
+        &self,
+        subscription: WebhookSubscription,
+        delivery: WebhookSubscriptionDelivery,
+        attempt: Attempt,
+        now: DateTime<Utc>,
+    ) -> WorkflowResult<()> {
+        let now_tz = now.fixed_offset();
+        let attempts = delivery.attempts + 1;
+        let delivery_id = delivery.id;
+        let succeeded = attempt.succeeded();
+
+        let mut model: WebhookSubscriptionDeliveryActiveModel = delivery.into();
+        model.attempts = Set(attempts);
+        model.last_attempt_at = Set(Some(now_tz));
+        model.request_headers = Set(attempt.headers);
+        model.request_body = Set(Some(attempt.body));
+        model.response_status = Set(attempt.response_status);
+        model.response_body = Set(attempt.response_body);
+        model.duration_ms = Set(Some(attempt.duration_ms));
+        if succeeded {
+            model.status = Set(SubscriptionDeliveryStatus::Delivered);
+            model.next_attempt_at = Set(None);
+            model.delivered_at = Set(Some(now_tz));
+            model.error = Set(None);
+        } else if attempts >= self.config.max_attempts {
+            model.status = Set(SubscriptionDeliveryStatus::DeadLetter);
+            model.next_attempt_at = Set(None);
+            model.error = Set(attempt.error.clone());
+            warn!(
+                delivery_id = %delivery_id,
+                subscription_id = %subscription.id,
+                attempts,
+                "Webhook delivery moved to dead letter"
+            );
+        } else {
+            model.next_attempt_at =
+                Set(Some((now + self.backoff_duration(attempts)).fixed_offset()));
+            model.error = Set(attempt.error.clone());
+        }
+        model.update(&self.db).await?;
+
+        if succeeded {
+            if subscription.consecutive_failures > 0 {
+                let mut model: WebhookSubscriptionActiveModel = subscription.into();
+                model.consecutive_failures = Set(0);
+                model.update(&self.db).await?;
+            }
+            return Ok(());
+        }
+
+        let failures = subscription.consecutive_failures + 1;
+        let subscription_id = subscription.id;
+        let mut model: WebhookSubscriptionActiveModel = subscription.into();
+        model.consecutive_failures = Set(failures);
+        if failures >= self.config.auto_disable_after {
+            model.status = Set(WebhookSubscriptionStatus::Disabled);
+            model.disabled_at = Set(Some(now_tz));
+            model.disabled_reason = Set(Some(format!(
+                "disabled after {failures} consecutive failed deliveries"
+            )));
+            model.updated_at = Set(now_tz);
+            warn!(
+                subscription_id = %subscription_id,
+                failures,
+                "Webhook subscription auto-disabled after consecutive failures"
+            );
+        }
+        model.update(&self.db).await?;
+        Ok(())
+    }
+
+    fn backoff_duration(&self, attempts: i32) -> chrono::Duration {
+        let attempt = attempts.saturating_sub(1) as u32;
+        let factor = 2u128.pow(cmp::min(attempt, 16));
+        let millis = self.config.backoff_base.as_millis().saturating_mul(factor);
+        let bounded = cmp::min(millis, self.config.backoff_max.as_millis()) as i64;
+        chrono::Duration::milliseconds(bounded)
+    }
+}
+
+/// Whether the subscription's `event_types` cover `event_type`.
+fn subscribes_to(subscription: &WebhookSubscription, event_type: &str) -> bool {
+    subscription
+        .event_types
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .any(|pattern| event_type_matches(pattern, event_type))
+}
+
+/// The delivered document: envelope metadata plus the event's own fields
+/// under `data`.
+fn event_payload(envelope: &EventEnvelope) -> Value {
+    let data = serde_json::to_value(&envelope.event)
+        .ok()
+        .and_then(|event| event.get("data").cloned())
+        .unwrap_or(Value::Null);
+    json!({
+        "id": envelope.id,
+        "type": envelope.event_type,
+        "schema_version": envelope.schema_version,
+        "tenant_id": envelope.tenant_id,
+        "timestamp": envelope.timestamp,
+        "actor_id": envelope.actor_id,
+        "correlation_id": envelope.correlation_id,
+        "data": data,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn backoff_doubles_per_attempt_up_to_the_cap() {
+        let db = sea_orm::Database::connect("sqlite::memory:").await.unwrap();
+        let dispatcher = WebhookDispatcher::with_config(
+            db,
+            WebhookDispatcherConfig {
+                backoff_base: Duration::from_secs(30),
+                backoff_max: Duration::from_secs(300),
+                ..Default::default()
+            },
+        );
+        let seconds: Vec<_> = (1..=6)
+            .map(|attempts| dispatcher.backoff_duration(attempts).num_seconds())
+            .collect();
+        assert_eq!(seconds, vec![30, 60, 120, 240, 300, 300]);
+        assert_eq!(dispatcher.backoff_duration(i32::MAX).num_seconds(), 300);
+    }
+}
@@ -0,0 +1,377 @@
+use chrono::{DateTime, Utc};
+use rustok_core::{SsrfProtection, ValidationResult};
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, Order, QueryFilter, QueryOrder,
+    QuerySelect, Set,
+};
+use uuid::Uuid;
+
+use crate::dto::{
+    CreateWebhookSubscriptionInput, UpdateWebhookSubscriptionInput,
+    WebhookSubscriptionDeliveryResponse, WebhookSubscriptionResponse,
+};
+use crate::entities::{
+    webhook_subscription, webhook_subscription_delivery, SubscriptionDeliveryStatus,
+    WebhookSubscription, WebhookSubscriptionActiveModel, WebhookSubscriptionDelivery,
+    WebhookSubscriptionDeliveryActiveModel, WebhookSubscriptionDeliveryEntity,
+    WebhookSubscriptionEntity, WebhookSubscriptionStatus,
+};
+use crate::error::{WorkflowError, WorkflowResult};
+use crate::services::webhook;
+
+const MAX_EVENT_TYPES: usize = 50;
+
+/// Tenant-managed outbound webhook subscriptions and their delivery log.
+///
+/// Subscription URLs pointing at loopback, private or link-local addresses
+/// are refused on save; `WebhookDispatcher` checks the resolved addresses
+/// again before every request.
+pub struct WebhookSubscriptionService {
+    db: DatabaseConnection,
+    ssrf: SsrfProtection,
+}
+
+impl WebhookSubscriptionService {
+    pub fn new(db: DatabaseConnection) -> Self {
+        Self {
+            db,
+            ssrf: SsrfProtection::new(),
+        }
+    }
+
+    pub fn with_ssrf_protection(mut self, ssrf: SsrfProtection) -> Self {
+        self.ssrf = ssrf;
+        self
+    }
+
+    // ── Subscriptions ──────────────────────────────────────────────────────────
+
+    /// Create an active subscription with a fresh signing secret. The secret
+    /// is only returned here and by [`Self::rotate_secret`].
+    pub async fn create(
+        &self,
+        tenant_id: Uuid,
+        actor_id: Option<Uuid>,
+        input: CreateWebhookSubscriptionInput,
+    ) -> WorkflowResult<WebhookSubscriptionResponse> {
+        let name = validate_name(&input.name)?;
+        validate_url(&input.url, &self.ssrf)?;
+        validate_event_types(&input.event_types)?;
+
+        let now = Utc::now().fixed_offset();
+        let secret = webhook::generate_secret();
+        let model = WebhookSubscriptionActiveModel {
+            id: Set(Uuid::new_v4()),
+            tenant_id: Set(tenant_id),
+            name: Set(name),
+            url: Set(input.url.trim().to_string()),
+            event_types: Set(serde_json::to_value(&input.event_types)?),
+            secret: Set(secret.clone()),
+            status: Set(WebhookSubscriptionStatus::Active),
+            consecutive_failures: Set(0),
+            disabled_at: Set(None),
+            disabled_reason: Set(None),
+            created_by: Set(actor_id),
+            created_at: Set(now),
+            updated_at: Set(now),
+        }
+        .insert(&self.db)
+        .await?;
+
+        let mut response = subscription_to_response(model);
+        response.secret = Some(secret);
+        Ok(response)
+    }
+
+    pub async fn list(&self, tenant_id: Uuid) -> WorkflowResult<Vec<WebhookSubscriptionResponse>> {
+        let subscriptions = WebhookSubscriptionEntity::find()
+            .filter(webhook_subscription::Column::TenantId.eq(tenant_id))
+            .order_by(webhook_subscription::Column::CreatedAt, Order::Asc)
+            .all(&self.db)
+            .await?;
+
+        Ok(subscriptions
+            .into_iter()
+            .map(subscription_to_response)
+            .collect())
+    }
+
+    pub async fn get(
+        &self,
+        tenant_id: Uuid,
+        id: Uuid,
+    ) -> WorkflowResult<WebhookSubscriptionResponse> {
+        self.find(tenant_id, id).await.map(subscription_to_response)
+    }
+
+    /// Update a subscription. Setting the status back to `active` re-enables
+    /// an auto-disabled subscription and resets its failure count.
+    pub async fn update(
+        &self,
+        tenant_id: Uuid,
+        id: Uuid,
+        input: UpdateWebhookSubscriptionInput,
+    ) -> WorkflowResult<WebhookSubscriptionResponse> {
+        let existing = self.find(tenant_id, id).await?;
+        let was_active = existing.status == WebhookSubscriptionStatus::Active;
+        let now = Utc::now().fixed_offset();
+        let mut model: WebhookSubscriptionActiveModel = existing.into();
+
+        if let Some(name) = input.name {
+            model.name = Set(validate_name(&name)?);
+        }
+        if let Some(url) = input.url {
+            validate_url(&url, &self.ssrf)?;
+            model.url = Set(url.trim().to_string());
+        }
+        if let Some(event_types) = input.event_types {
+            validate_event_types(&event_types)?;
+            model.event_types = Set(serde_json::to_value(&event_types)?);
+        }
+        match input.status {
+            Some(WebhookSubscriptionStatus::Active) if !was_active => {
+                model.status = Set(WebhookSubscriptionStatus::Active);
+                model.consecutive_failures = Set(0);
+                model.disabled_at = Set(None);
+                model.disabled_reason = Set(None);
+            }
+            Some(WebhookSubscriptionStatus::Disabled) if was_active => {
+                model.status = Set(WebhookSubscriptionStatus::Disabled);
+                model.disabled_at = Set(Some(now));
+                model.disabled_reason = Set(Some("disabled manually".into()));
+            }
+            _ => {}
+        }
+        model.updated_at = Set(now);
+        let updated = model.update(&self.db).await?;
+
+        if !was_active && updated.status == WebhookSubscriptionStatus::Active {
+            // Release the deliveries held while the subscription was disabled.
+            WebhookSubscriptionDeliveryEntity::update_many()
+                .col_expr(
+                    webhook_subscription_delivery::Column::NextAttemptAt,
+                    Expr::value(now),
+                )
+                .filter(webhook_subscription_delivery::Column::SubscriptionId.eq(id))
+                .filter(
+                    webhook_subscription_delivery::Column::Status
+                        .eq(SubscriptionDeliveryStatus::Pending.to_string()),
+                )
+                .filter(webhook_subscription_delivery::Column::NextAttemptAt.is_null())
+                .exec(&self.db)
+                .await?;
+        }
+
+        Ok(subscription_to_response(updated))
+    }
+
+    /// Delete a subscription together with its delivery log.
+    pub async fn delete(&self, tenant_id: Uuid, id: Uuid) -> WorkflowResult<()> {
+        let existing = self.find(tenant_id, id).await?;
+        WebhookSubscriptionDeliveryEntity::delete_many()
+            .filter(webhook_subscription_delivery::Column::SubscriptionId.eq(id))
+            .exec(&self.db)
+            .await?;
+        let model: WebhookSubscriptionActiveModel = existing.into();
+        model.delete(&self.db).await?;
+        Ok(())
+    }
+
+    /// Replace the signing secret; deliveries sent from now on use the new one.
+    pub async fn rotate_secret(&self, tenant_id: Uuid, id: Uuid) -> WorkflowResult<String> {
+        let existing = self.find(tenant_id, id).await?;
+        let secret = webhook::generate_secret();
+        let mut model: WebhookSubscriptionActiveModel = existing.into();
+        model.secret = Set(secret.clone());
+        model.updated_at = Set(Utc::now().fixed_offset());
+        model.update(&self.db).await?;
+        Ok(secret)
+    }
+
+    // ── Deliveries ─────────────────────────────────────────────────────────────
+
+    /// List recent deliveries of a subscription (most recent first, limit 100).
+    pub async fn list_deliveries(
+        &self,
+        tenant_id: Uuid,
+        subscription_id: Uuid,
+    ) -> WorkflowResult<Vec<WebhookSubscriptionDeliveryResponse>> {
+        self.find(tenant_id, subscription_id).await?;
+        let deliveries = WebhookSubscriptionDeliveryEntity::find()
+            .filter(webhook_subscription_delivery::Column::TenantId.eq(tenant_id))
+            .filter(webhook_subscription_delivery::Column::SubscriptionId.eq(subscription_id))
+            .order_by(
+                webhook_subscription_delivery::Column::CreatedAt,
+                Order::Desc,
+            )
+            .limit(100)
+            .all(&self.db)
+            .await?;
+
+        Ok(deliveries.into_iter().map(delivery_to_response).collect())
+    }
+
+    /// Queue a delivery again with a fresh attempt budget, e.g. after fixing
+    /// the receiving endpoint of a dead-lettered delivery.
+    pub async fn redeliver(
+        &self,
+        tenant_id: Uuid,
+        delivery_id: Uuid,
+    ) -> WorkflowResult<WebhookSubscriptionDeliveryResponse> {
+        let delivery = WebhookSubscriptionDeliveryEntity::find_by_id(delivery_id)
+            .filter(webhook_subscription_delivery::Column::TenantId.eq(tenant_id))
+            .one(&self.db)
+            .await?
+            .ok_or(WorkflowError::DeliveryNotFound(delivery_id))?;
+
+        let mut model: WebhookSubscriptionDeliveryActiveModel = delivery.into();
+        model.status = Set(SubscriptionDeliveryStatus::Pending);
+        model.attempts = Set(0);
+        model.next_attempt_at = Set(Some(Utc::now().fixed_offset()));
+        Ok(delivery_to_response(model.update(&self.db).await?))
+    }
+
+    async fn find(&self, tenant_id: Uuid, id: Uuid) -> WorkflowResult<WebhookSubscription> {
+        WebhookSubscriptionEntity::find_by_id(id)
+            .filter(webhook_subscription::Column::TenantId.eq(tenant_id))
+            .one(&self.db)
+            .await?
+            .ok_or(WorkflowError::SubscriptionNotFound(id))
+    }
+}
+
+fn validate_name(name: &str) -> WorkflowResult<String> {
+    let name = name.trim();
+    if name.is_empty() || name.len() > 255 {
+        return Err(WorkflowError::InvalidSubscription(
+            "name must be 1-255 characters".into(),
+        ));
+    }
+    Ok(name.to_string())
+}
+
+fn validate_url(url: &str, ssrf: &SsrfProtection) -> WorkflowResult<()> {
+    let parsed = reqwest::Url::parse(url.trim())
+        .map_err(|err| WorkflowError::InvalidSubscription(format!("url: {err}")))?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+        return Err(WorkflowError::InvalidSubscription(
+            "url must be an absolute http or https URL".into(),
+        ));
+    }
+    if url.len() > 2048 {
+        return Err(WorkflowError::InvalidSubscription(
+            "url is longer than 2048 characters".into(),
+        ));
+    }
+    if let ValidationResult::Invalid { reason } = ssrf.validate_url(url.trim()) {
+        return Err(WorkflowError::InvalidSubscription(format!("url: {reason}")));
+    }
+    Ok(())
+}
+
+/// Event types are exact names (`order.paid`) or prefixes ending in `*`
+/// (`order.*`, `*` for everything).
+fn validate_event_types(event_types: &[String]) -> WorkflowResult<()> {
+    if event_types.is_empty() || event_types.len() > MAX_EVENT_TYPES {
+        return Err(WorkflowError::InvalidSubscription(format!(
+            "event_types must list 1-{MAX_EVENT_TYPES} event types"
+        )));
+    }
+    for pattern in event_types {
+        let valid = !pattern.trim().is_empty()
+            && pattern.trim() == pattern
+            && !pattern.trim_end_matches('*').contains('*')
+            && pattern.matches('*').count() <= 1;
+        if !valid {
+            return Err(WorkflowError::InvalidSubscription(format!(
+                "event type '{pattern}' must be an event type or a prefix ending in '*'"
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn subscription_to_response(s: WebhookSubscription) -> WebhookSubscriptionResponse {
+    WebhookSubscriptionResponse {
+        id: s.id,
+        name: s.name,
+        url: s.url,
+        event_types: serde_json::from_value(s.event_types).unwrap_or_default(),
+        status: s.status,
+        consecutive_failures: s.consecutive_failures,
+        disabled_at: s.disabled_at.map(DateTime::from),
+        disabled_reason: s.disabled_reason,
+        secret: None,
+        created_by: s.created_by,
+        created_at: s.created_at.into(),
+        updated_at: s.updated_at.into(),
+    }
+}
+
+fn delivery_to_response(d: WebhookSubscriptionDelivery) -> WebhookSubscriptionDeliveryResponse {
+    WebhookSubscriptionDeliveryResponse {
+        id: d.id,
+        subscription_id: d.subscription_id,
+        event_id: d.event_id,
+        event_type: d.event_type,
+        status: d.status,
+        attempts: d.attempts,
+        next_attempt_at: d.next_attempt_at.map(DateTime::from),
+        request_headers: d.request_headers,
+        request_body: d.request_body,
+        response_status: d.response_status,
+        response_body: d.response_body,
+        error: d.error,
+        duration_ms: d.duration_ms,
+        last_attempt_at: d.last_attempt_at.map(DateTime::from),
+        delivered_at: d.delivered_at.map(DateTime::from),
+        created_at: d.created_at.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn event_type_patterns_allow_only_a_trailing_wildcard() {
+        let patterns = |list: &[&str]| list.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+        assert!(validate_event_types(&patterns(&["order.paid", "product.*", "*"])).is_ok());
+        for invalid in [
+            &[][..],
+            &[""],
+            &["order.*.paid"],
+            &["or*er*"],
+            &[" order.paid"],
+        ] {
+            assert!(matches!(
+                validate_event_types(&patterns(invalid)),
+                Err(WorkflowError::InvalidSubscription(_))
+            ));
+        }
+
+        let ssrf = SsrfProtection::new();
+        assert!(validate_url("https://hooks.example.com/rustok", &ssrf).is_ok());
+        for invalid in [
+            "ftp://example.com",
+            "/relative",
+            "not a url",
+            "http://127.0.0.1:8080/hooks",
+            "http://localhost/hooks",
+            "http://169.254.169.254/latest/meta-data",
+            "http://10.0.0.5/hooks",
+            "http://[::1]/hooks",
+        ] {
+            assert!(
+                validate_url(invalid, &ssrf).is_err(),
+                "{invalid} should be rejected"
+            );
+        }
+        assert!(validate_url(
+            "http://127.0.0.1:8080/hooks",
+            &SsrfProtection::new().allow_private_networks()
+        )
+        .is_ok());
+    }
+}
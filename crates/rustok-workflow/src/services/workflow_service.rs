@@ -1,32 +1,156 @@
 use chrono::Utc;
+use rustok_core::{SecurityContext, UserRole};
 #[allow(unused_imports)]
 use sea_orm::sea_query::Expr;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, Order, QueryFilter, QueryOrder,
-    QuerySelect, Set,
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel, Order,
+    QueryFilter, QueryOrder, QuerySelect, Set, SqlErr,
 };
 use uuid::Uuid;
 
 use crate::dto::{
-    CreateWorkflowInput, CreateWorkflowStepInput, UpdateWorkflowInput, UpdateWorkflowStepInput,
+    CreateWorkflowInput, CreateWorkflowStepInput, DryRunWorkflowInput, UpdateWorkflowInput,
+    UpdateWorkflowStepInput, WorkflowApprovalResponse, WorkflowDryRunResponse,
     WorkflowExecutionResponse, WorkflowResponse, WorkflowStepExecutionResponse,
     WorkflowStepResponse, WorkflowSummary, WorkflowVersionDetail, WorkflowVersionSummary,
+    WorkflowWebhookDeliveryResponse,
 };
 use crate::entities::{
-    workflow, workflow_execution, workflow_step, workflow_step_execution, workflow_version,
-    WorkflowActiveModel, WorkflowEntity, WorkflowExecutionEntity, WorkflowStatus,
-    WorkflowStepActiveModel, WorkflowStepEntity, WorkflowStepExecutionEntity,
-    WorkflowVersionActiveModel, WorkflowVersionEntity,
+    workflow, workflow_approval, workflow_execution, workflow_step, workflow_step_execution,
+    workflow_suspension, workflow_version, workflow_webhook_delivery, ApprovalStatus, StepType,
+    WebhookDeliveryStatus, Workflow, WorkflowActiveModel, WorkflowApprovalEntity, WorkflowEntity,
+    WorkflowExecutionEntity, WorkflowStatus, WorkflowStepActiveModel, WorkflowStepEntity,
+    WorkflowStepExecutionEntity, WorkflowSuspensionEntity, WorkflowVersionActiveModel,
+    WorkflowVersionEntity, WorkflowWebhookDeliveryActiveModel, WorkflowWebhookDeliveryEntity,
 };
 use crate::error::{WorkflowError, WorkflowResult};
+use crate::services::graph::StepGraph;
+use crate::services::trigger_handler::{passes_trigger_filter, validate_trigger_filter};
+use crate::services::webhook::{self, WebhookDeliveryLog, WebhookRequest};
+use crate::services::WorkflowEngine;
+use crate::steps::control::{validate_child_branch, validate_control_config, SubWorkflowConfig};
+use crate::steps::{
+    ApprovalDecision, ApprovalStep, ConditionStep, ResumeSignal, TransformStep, WaitForEventStep,
+    WorkflowActionRuntime,
+};
 
 pub struct WorkflowService {
     db: DatabaseConnection,
+    action_runtime: Option<WorkflowActionRuntime>,
+    delivery_log: WebhookDeliveryLog,
 }
 
 impl WorkflowService {
     pub fn new(db: DatabaseConnection) -> Self {
-        Self { db }
+        Self {
+            db,
+            action_runtime: None,
+            delivery_log: WebhookDeliveryLog::new(),
+        }
+    }
+
+    /// Queue that rejected webhook deliveries are recorded in until
+    /// [`Self::flush_webhook_deliveries`] writes them.
+    pub fn with_webhook_delivery_log(mut self, log: WebhookDeliveryLog) -> Self {
+        self.delivery_log = log;
+        self
+    }
+
+    /// Module actions used to validate `action` steps on save and to run them.
+    pub fn with_action_runtime(mut self, runtime: Option<WorkflowActionRuntime>) -> Self {
+        self.action_runtime = runtime;
+        self
+    }
+
+    pub fn action_runtime(&self) -> Option<&WorkflowActionRuntime> {
+        self.action_runtime.as_ref()
+    }
+
+    fn engine(&self) -> WorkflowEngine {
+        WorkflowEngine::new(self.db.clone()).with_action_runtime(self.action_runtime.clone())
+    }
+
+    /// Rejects step configs that cannot run: an `action` step must name an
+    /// action, and when module actions are attached its input must match the
+    /// action's schema and `author` must hold the action's permissions;
+    /// `condition`, `transform`, `wait_for_event`, `approval` and
+    /// control-flow configs must parse.
+    fn validate_step_config(
+        &self,
+        step_type: &StepType,
+        config: &serde_json::Value,
+        author: &SecurityContext,
+    ) -> WorkflowResult<()> {
+        match step_type {
+            StepType::Action => {}
+            StepType::Transform => return TransformStep::validate_config(config),
+            StepType::WaitForEvent => return WaitForEventStep::validate_config(config),
+            StepType::Approval => return ApprovalStep::validate_config(config),
+            StepType::Condition => return ConditionStep::validate_config(config),
+            step_type if step_type.is_control_flow() => {
+                return validate_control_config(step_type, config)
+            }
+            _ => return Ok(()),
+        }
+        match &self.action_runtime {
+            Some(runtime) => runtime.validate_config(config, author),
+            None => config
+                .get("action")
+                .and_then(serde_json::Value::as_str)
+                .filter(|name| !name.trim().is_empty())
+                .map(|_| ())
+                .ok_or_else(|| WorkflowError::InvalidStepConfig("action: missing 'action'".into())),
+        }
+    }
+
+    /// Checks where a step sits in the graph: top-level steps have no branch,
+    /// nested steps need a control-flow parent of the same workflow with a
+    /// matching branch and must not end up inside themselves. A
+    /// `sub_workflow` step must call another workflow of the tenant.
+    #[allow(clippy::too_many_arguments)]
+    async fn validate_step_placement(
+        &self,
+        tenant_id: Uuid,
+        workflow_id: Uuid,
+        step_id: Option<Uuid>,
+        step_type: &StepType,
+        config: &serde_json::Value,
+        parent_step_id: Option<Uuid>,
+        branch: Option<&str>,
+    ) -> WorkflowResult<()> {
+        if *step_type == StepType::SubWorkflow {
+            let target = SubWorkflowConfig::parse(config)?.workflow_id;
+            let exists = target != workflow_id
+                && WorkflowEntity::find_by_id(target)
+                    .filter(workflow::Column::TenantId.eq(tenant_id))
+                    .one(&self.db)
+                    .await?
+                    .is_some();
+            if !exists {
+                return Err(WorkflowError::InvalidStepConfig(format!(
+                    "sub_workflow: workflow {target} is not another workflow of this tenant"
+                )));
+            }
+        }
+
+        let Some(parent_step_id) = parent_step_id else {
+            return match branch {
+                None => Ok(()),
+                Some(_) => Err(WorkflowError::InvalidStepConfig(
+                    "top-level steps take no 'branch'".into(),
+                )),
+            };
+        };
+        let graph = StepGraph::new(self.load_steps(workflow_id).await?);
+        let parent = graph
+            .get(parent_step_id)
+            .ok_or(WorkflowError::StepNotFound(parent_step_id))?;
+        if step_id.is_some_and(|step_id| graph.is_ancestor(step_id, parent_step_id)) {
+            return Err(WorkflowError::InvalidStepConfig(
+                "a step cannot be nested inside itself".into(),
+            ));
+        }
+        validate_child_branch(&parent.step_type, &parent.config, branch)
     }
 
     // ── Workflows ──────────────────────────────────────────────────────────────
@@ -37,8 +161,12 @@ impl WorkflowService {
         actor_id: Option<Uuid>,
         input: CreateWorkflowInput,
     ) -> WorkflowResult<Uuid> {
-        let now = Utc::now().fixed_offset();
+        validate_trigger_filter(&input.trigger_config)?;
         let id = Uuid::new_v4();
+        if let Some(slug) = &input.webhook_slug {
+            self.ensure_webhook_slug_free(tenant_id, id, slug).await?;
+        }
+        let now = Utc::now().fixed_offset();
 
         let model = WorkflowActiveModel {
             id: Set(id),
@@ -54,6 +182,8 @@ impl WorkflowService {
             auto_disabled_at: Set(None),
             webhook_slug: Set(input.webhook_slug),
             webhook_secret: Set(None),
+            webhook_previous_secret: Set(None),
+            webhook_secret_rotated_at: Set(None),
         };
         model.insert(&self.db).await?;
 
@@ -124,6 +254,16 @@ impl WorkflowService {
             .one(&self.db)
             .await?
             .ok_or(WorkflowError::NotFound(id))?;
+        if let Some(trigger_config) = &input.trigger_config {
+            validate_trigger_filter(trigger_config)?;
+        }
+        if let Some(slug) = input
+            .webhook_slug
+            .as_deref()
+            .filter(|slug| !slug.is_empty())
+        {
+            self.ensure_webhook_slug_free(tenant_id, id, slug).await?;
+        }
 
         // Save version snapshot before applying the update
         self.save_version_internal(id, actor_id, &existing).await?;
@@ -151,6 +291,28 @@ impl WorkflowService {
         Ok(())
     }
 
+    /// A webhook slug addresses exactly one workflow of the tenant.
+    async fn ensure_webhook_slug_free(
+        &self,
+        tenant_id: Uuid,
+        workflow_id: Uuid,
+        slug: &str,
+    ) -> WorkflowResult<()> {
+        let taken = WorkflowEntity::find()
+            .filter(workflow::Column::TenantId.eq(tenant_id))
+            .filter(workflow::Column::WebhookSlug.eq(slug))
+            .filter(workflow::Column::Id.ne(workflow_id))
+            .one(&self.db)
+            .await?;
+        match taken {
+            Some(other) => Err(WorkflowError::InvalidTriggerConfig(format!(
+                "webhook slug '{slug}' is already used by workflow {}",
+                other.id
+            ))),
+            None => Ok(()),
+        }
+    }
+
     pub async fn delete(&self, tenant_id: Uuid, id: Uuid) -> WorkflowResult<()> {
         let existing = WorkflowEntity::find_by_id(id)
             .filter(workflow::Column::TenantId.eq(tenant_id))
@@ -169,6 +331,7 @@ impl WorkflowService {
     pub async fn add_step(
         &self,
         tenant_id: Uuid,
+        security: SecurityContext,
         workflow_id: Uuid,
         input: CreateWorkflowStepInput,
     ) -> WorkflowResult<Uuid> {
@@ -178,11 +341,24 @@ impl WorkflowService {
             .one(&self.db)
             .await?
             .ok_or(WorkflowError::NotFound(workflow_id))?;
+        self.validate_step_config(&input.step_type, &input.config, &security)?;
+        self.validate_step_placement(
+            tenant_id,
+            workflow_id,
+            None,
+            &input.step_type,
+            &input.config,
+            input.parent_step_id,
+            input.branch.as_deref(),
+        )
+        .await?;
 
         let step_id = Uuid::new_v4();
         let model = WorkflowStepActiveModel {
             id: Set(step_id),
             workflow_id: Set(workflow_id),
+            parent_step_id: Set(input.parent_step_id),
+            branch: Set(input.branch),
             position: Set(input.position),
             step_type: Set(input.step_type),
             config: Set(input.config),
@@ -197,6 +373,7 @@ impl WorkflowService {
     pub async fn update_step(
         &self,
         tenant_id: Uuid,
+        security: SecurityContext,
         workflow_id: Uuid,
         step_id: Uuid,
         input: UpdateWorkflowStepInput,
@@ -213,11 +390,34 @@ impl WorkflowService {
             .one(&self.db)
             .await?
             .ok_or(WorkflowError::StepNotFound(step_id))?;
+        if input.step_type.is_some() || input.config.is_some() {
+            self.validate_step_config(
+                input.step_type.as_ref().unwrap_or(&existing.step_type),
+                input.config.as_ref().unwrap_or(&existing.config),
+                &security,
+            )?;
+        }
+        self.validate_step_placement(
+            tenant_id,
+            workflow_id,
+            Some(step_id),
+            input.step_type.as_ref().unwrap_or(&existing.step_type),
+            input.config.as_ref().unwrap_or(&existing.config),
+            input.parent_step_id.or(existing.parent_step_id),
+            input.branch.as_deref().or(existing.branch.as_deref()),
+        )
+        .await?;
 
         let mut model: WorkflowStepActiveModel = existing.into();
         if let Some(pos) = input.position {
             model.position = Set(pos);
         }
+        if let Some(parent_step_id) = input.parent_step_id {
+            model.parent_step_id = Set(Some(parent_step_id));
+        }
+        if let Some(branch) = input.branch {
+            model.branch = Set(Some(branch));
+        }
         if let Some(step_type) = input.step_type {
             model.step_type = Set(step_type);
         }
@@ -300,7 +500,7 @@ impl WorkflowService {
             "payload": payload
         });
 
-        let engine = crate::services::WorkflowEngine::new(self.db.clone());
+        let engine = self.engine();
         let execution_id = engine
             .execute(workflow_id, tenant_id, None, steps, initial_context)
             .await?;
@@ -405,46 +605,409 @@ impl WorkflowService {
         Ok(execution_to_response(exec, step_execs))
     }
 
+    // ── Execution recovery ─────────────────────────────────────────────────────
+
+    /// Re-run a finished execution in place. Without `step_id` a failed
+    /// execution resumes from its failing step; with it the execution
+    /// re-runs from that step. Either way the step starts from the context
+    /// it received when it last ran, and the workflow's current steps are
+    /// used.
+    pub async fn rerun_execution(
+        &self,
+        tenant_id: Uuid,
+        execution_id: Uuid,
+        step_id: Option<Uuid>,
+    ) -> WorkflowResult<WorkflowExecutionResponse> {
+        let execution = WorkflowExecutionEntity::find_by_id(execution_id)
+            .filter(workflow_execution::Column::TenantId.eq(tenant_id))
+            .one(&self.db)
+            .await?
+            .ok_or(WorkflowError::ExecutionNotFound(execution_id))?;
+
+        let engine = self.engine();
+        let claimed = match step_id {
+            Some(step_id) => engine.rerun_from(execution, step_id).await?,
+            None => engine.retry(execution).await?,
+        };
+        if !claimed {
+            return Err(WorkflowError::NotRerunnable(
+                execution_id,
+                "the execution changed status concurrently".into(),
+            ));
+        }
+
+        self.get_execution(tenant_id, execution_id).await
+    }
+
+    /// Run the current steps or a saved version against a sample payload
+    /// without persisting anything. Side-effecting steps (`action`, `http`,
+    /// `notify`, `emit_event`, `alloy_script`) and delays are mocked.
+    pub async fn dry_run(
+        &self,
+        tenant_id: Uuid,
+        workflow_id: Uuid,
+        actor_id: Option<Uuid>,
+        input: DryRunWorkflowInput,
+    ) -> WorkflowResult<WorkflowDryRunResponse> {
+        WorkflowEntity::find_by_id(workflow_id)
+            .filter(workflow::Column::TenantId.eq(tenant_id))
+            .one(&self.db)
+            .await?
+            .ok_or(WorkflowError::NotFound(workflow_id))?;
+
+        let steps = match input.version {
+            Some(version) => {
+                let ver = WorkflowVersionEntity::find()
+                    .filter(workflow_version::Column::WorkflowId.eq(workflow_id))
+                    .filter(workflow_version::Column::Version.eq(version))
+                    .one(&self.db)
+                    .await?
+                    .ok_or(WorkflowError::VersionNotFound(version))?;
+                ver.snapshot
+                    .get("steps")
+                    .and_then(|v| v.as_array())
+                    .map(|steps| snapshot_steps(workflow_id, steps))
+                    .unwrap_or_default()
+            }
+            None => self.load_steps(workflow_id).await?,
+        };
+
+        let initial_context = serde_json::json!({
+            "trigger": { "type": "dry_run", "actor_id": actor_id },
+            "payload": input.payload
+        });
+
+        let mut response = self
+            .engine()
+            .dry_run(workflow_id, tenant_id, steps, initial_context, input.mocks)
+            .await?;
+        response.version = input.version;
+        Ok(response)
+    }
+
+    // ── Approvals ──────────────────────────────────────────────────────────────
+
+    /// List approval tasks of the tenant (most recent first, limit 100),
+    /// optionally only those with `status` or those `assignee` may decide.
+    pub async fn list_approvals(
+        &self,
+        tenant_id: Uuid,
+        status: Option<ApprovalStatus>,
+        assignee: Option<(Uuid, UserRole)>,
+    ) -> WorkflowResult<Vec<WorkflowApprovalResponse>> {
+        let mut query = WorkflowApprovalEntity::find()
+            .filter(workflow_approval::Column::TenantId.eq(tenant_id));
+        if let Some(status) = status {
+            query = query.filter(workflow_approval::Column::Status.eq(status));
+        }
+        let approvals = query
+            .order_by(workflow_approval::Column::CreatedAt, Order::Desc)
+            .all(&self.db)
+            .await?;
+
+        Ok(approvals
+            .into_iter()
+            .map(approval_to_response)
+            .filter(|approval| {
+                assignee
+                    .as_ref()
+                    .is_none_or(|(user_id, role)| is_assignee(approval, *user_id, role))
+            })
+            .take(100)
+            .collect())
+    }
+
+    /// Get a single approval task by id (tenant-scoped).
+    pub async fn get_approval(
+        &self,
+        tenant_id: Uuid,
+        approval_id: Uuid,
+    ) -> WorkflowResult<WorkflowApprovalResponse> {
+        WorkflowApprovalEntity::find_by_id(approval_id)
+            .filter(workflow_approval::Column::TenantId.eq(tenant_id))
+            .one(&self.db)
+            .await?
+            .map(approval_to_response)
+            .ok_or(WorkflowError::ApprovalNotFound(approval_id))
+    }
+
+    /// Approve or reject a pending approval task and resume its execution.
+    ///
+    /// Only an assignee may decide: a listed user or a user whose RBAC role
+    /// is listed.
+    pub async fn decide_approval(
+        &self,
+        tenant_id: Uuid,
+        approval_id: Uuid,
+        actor_id: Uuid,
+        actor_role: &UserRole,
+        approved: bool,
+        comment: Option<String>,
+    ) -> WorkflowResult<WorkflowApprovalResponse> {
+        let approval = self.get_approval(tenant_id, approval_id).await?;
+        if approval.status != ApprovalStatus::Pending {
+            return Err(WorkflowError::ApprovalClosed(approval_id));
+        }
+        if !is_assignee(&approval, actor_id, actor_role) {
+            return Err(WorkflowError::NotApprover(approval_id));
+        }
+
+        let suspension = WorkflowSuspensionEntity::find_by_id(approval.execution_id)
+            .filter(workflow_suspension::Column::StepId.eq(approval.step_id))
+            .one(&self.db)
+            .await?
+            .ok_or(WorkflowError::ApprovalClosed(approval_id))?;
+        let decision = ApprovalDecision {
+            approved,
+            decided_by: actor_id,
+            comment: comment.filter(|comment| !comment.trim().is_empty()),
+            decided_at: Utc::now(),
+        };
+        // Claiming the suspension races with the deadline timer; the loser
+        // sees the task as closed.
+        if !self
+            .engine()
+            .resume(suspension, ResumeSignal::Decision(decision))
+            .await?
+        {
+            return Err(WorkflowError::ApprovalClosed(approval_id));
+        }
+
+        self.get_approval(tenant_id, approval_id).await
+    }
+
     // ── Webhook trigger ────────────────────────────────────────────────────────
 
-    /// Trigger all active workflows with a matching webhook slug.
-    /// Returns a list of spawned execution IDs.
-    pub async fn trigger_by_webhook(
+    /// Verify an inbound webhook request and trigger the workflow it targets.
+    ///
+    /// The request must carry a fresh timestamp, a delivery id not seen
+    /// before for this workflow and a valid HMAC signature; the payload must
+    /// match `trigger_config.payload_schema` when one is declared. Only the
+    /// workflow whose secret verified the signature runs. Accepted attempts
+    /// are recorded in `workflow_webhook_deliveries` right away, rejected
+    /// ones are queued for [`Self::flush_webhook_deliveries`].
+    pub async fn receive_webhook(
         &self,
         tenant_id: Uuid,
         webhook_slug: &str,
-        payload: serde_json::Value,
+        request: WebhookRequest,
     ) -> WorkflowResult<Vec<Uuid>> {
-        let matching = WorkflowEntity::find()
+        let now = Utc::now();
+        // Slugs are unique per tenant on save; rows that predate that check
+        // are told apart by their secrets.
+        let candidates = WorkflowEntity::find()
             .filter(workflow::Column::TenantId.eq(tenant_id))
-            .filter(workflow::Column::Status.eq(WorkflowStatus::Active.to_string()))
             .filter(workflow::Column::WebhookSlug.eq(webhook_slug))
+            .order_by(workflow::Column::CreatedAt, Order::Asc)
             .all(&self.db)
             .await?;
+        let mut verified = None;
+        let mut rejection = None;
+        for candidate in candidates {
+            match webhook::verify(&candidate, &request, now) {
+                Ok(delivery_id) => {
+                    verified = Some((candidate, delivery_id));
+                    break;
+                }
+                Err(reason) => {
+                    rejection.get_or_insert((candidate.id, reason));
+                }
+            }
+        }
 
-        if matching.is_empty() {
-            return Ok(vec![]);
+        let mut delivery = WorkflowWebhookDeliveryActiveModel {
+            id: Set(Uuid::new_v4()),
+            tenant_id: Set(tenant_id),
+            workflow_id: Set(None),
+            webhook_slug: Set(webhook_slug.to_string()),
+            delivery_id: Set(request
+                .delivery_id
+                .as_deref()
+                .map(|id| id.trim().chars().take(128).collect())),
+            replay_key: Set(None),
+            status: Set(WebhookDeliveryStatus::Rejected),
+            reason: Set(None),
+            execution_ids: Set(serde_json::json!([])),
+            received_at: Set(now.fixed_offset()),
+        };
+
+        let Some((workflow, delivery_id)) = verified else {
+            let reason = match rejection {
+                Some((workflow_id, reason)) => {
+                    delivery.workflow_id = Set(Some(workflow_id));
+                    reason
+                }
+                None => "unknown webhook".to_string(),
+            };
+            return Err(self.reject_webhook(delivery, WorkflowError::WebhookRejected(reason)));
+        };
+        delivery.workflow_id = Set(Some(workflow.id));
+
+        let payload: serde_json::Value =
+            serde_json::from_slice(&request.body).unwrap_or_else(|_| {
+                serde_json::Value::String(String::from_utf8_lossy(&request.body).into_owned())
+            });
+        if let Some(schema) = workflow.trigger_config.get("payload_schema") {
+            let violations = rustok_core::json_schema::validate_json_schema(schema, &payload);
+            if !violations.is_empty() {
+                let reason = violations
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                return Err(
+                    self.reject_webhook(delivery, WorkflowError::InvalidWebhookPayload(reason))
+                );
+            }
         }
 
-        let engine = std::sync::Arc::new(crate::services::WorkflowEngine::new(self.db.clone()));
-        let initial_context = serde_json::json!({
-            "webhook": { "slug": webhook_slug, "payload": payload }
-        });
+        // The unique replay key claims the delivery id, so concurrent
+        // replays cannot both get through.
+        let mut claim = delivery.clone();
+        claim.status = Set(WebhookDeliveryStatus::Accepted);
+        claim.replay_key = Set(Some(delivery_id));
+        let claimed = match claim.insert(&self.db).await {
+            Ok(claimed) => claimed,
+            Err(err) if matches!(err.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) => {
+                return Err(self.reject_webhook(
+                    delivery,
+                    WorkflowError::WebhookRejected("delivery id was already received".into()),
+                ));
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut claimed: WorkflowWebhookDeliveryActiveModel = claimed.into();
+        match self.trigger_webhook_workflow(&workflow, payload).await {
+            Ok(execution_ids) => {
+                claimed.execution_ids = Set(serde_json::json!(execution_ids));
+                claimed.update(&self.db).await?;
+                Ok(execution_ids)
+            }
+            Err(err) => {
+                // Release the delivery id so the sender can retry
+                claimed.status = Set(WebhookDeliveryStatus::Rejected);
+                claimed.replay_key = Set(None);
+                claimed.reason = Set(Some(err.to_string()));
+                claimed.update(&self.db).await?;
+                Err(err)
+            }
+        }
+    }
 
-        let mut execution_ids = Vec::new();
+    fn reject_webhook(
+        &self,
+        mut delivery: WorkflowWebhookDeliveryActiveModel,
+        err: WorkflowError,
+    ) -> WorkflowError {
+        tracing::warn!(
+            webhook_slug = ?delivery.webhook_slug,
+            error = %err,
+            "Workflow webhook rejected"
+        );
+        delivery.reason = Set(Some(err.to_string()));
+        self.delivery_log.push(delivery);
+        err
+    }
 
-        for wf in matching {
-            let wf_id = wf.id;
-            let steps = self.load_steps(wf_id).await?;
-            let ctx = initial_context.clone();
-            let engine = engine.clone();
+    /// Write the queued rejected deliveries and purge delivery records older
+    /// than [`webhook::DELIVERY_RETENTION_DAYS`]. Run periodically by
+    /// `WorkflowCronScheduler`.
+    pub async fn flush_webhook_deliveries(&self) -> WorkflowResult<()> {
+        let (rejected, dropped) = self.delivery_log.take();
+        if dropped > 0 {
+            tracing::warn!(
+                dropped,
+                "Rejected webhook deliveries were not recorded: queue was full"
+            );
+        }
+        if !rejected.is_empty() {
+            WorkflowWebhookDeliveryEntity::insert_many(rejected)
+                .exec(&self.db)
+                .await?;
+        }
 
-            let execution_id = engine.execute(wf_id, tenant_id, None, steps, ctx).await?;
+        let cutoff = Utc::now() - chrono::Duration::days(webhook::DELIVERY_RETENTION_DAYS);
+        WorkflowWebhookDeliveryEntity::delete_many()
+            .filter(workflow_webhook_delivery::Column::ReceivedAt.lt(cutoff.fixed_offset()))
+            .exec(&self.db)
+            .await?;
+        Ok(())
+    }
 
-            execution_ids.push(execution_id);
+    /// Generate a new webhook secret. The replaced secret keeps verifying
+    /// signatures for [`webhook::PREVIOUS_SECRET_GRACE_HOURS`] so senders can
+    /// switch over without dropped deliveries.
+    pub async fn rotate_webhook_secret(
+        &self,
+        tenant_id: Uuid,
+        workflow_id: Uuid,
+    ) -> WorkflowResult<String> {
+        let existing = WorkflowEntity::find_by_id(workflow_id)
+            .filter(workflow::Column::TenantId.eq(tenant_id))
+            .one(&self.db)
+            .await?
+            .ok_or(WorkflowError::NotFound(workflow_id))?;
+
+        let secret = webhook::generate_secret();
+        let now = Utc::now().fixed_offset();
+        let previous = existing.webhook_secret.clone();
+        let mut model: WorkflowActiveModel = existing.into();
+        model.webhook_secret_rotated_at = Set(previous.as_ref().map(|_| now));
+        model.webhook_previous_secret = Set(previous);
+        model.webhook_secret = Set(Some(secret.clone()));
+        model.updated_at = Set(now);
+        model.update(&self.db).await?;
+
+        Ok(secret)
+    }
+
+    /// List recent webhook deliveries of a workflow (most recent first, limit 100).
+    pub async fn list_webhook_deliveries(
+        &self,
+        tenant_id: Uuid,
+        workflow_id: Uuid,
+    ) -> WorkflowResult<Vec<WorkflowWebhookDeliveryResponse>> {
+        let deliveries = WorkflowWebhookDeliveryEntity::find()
+            .filter(workflow_webhook_delivery::Column::TenantId.eq(tenant_id))
+            .filter(workflow_webhook_delivery::Column::WorkflowId.eq(workflow_id))
+            .order_by(workflow_webhook_delivery::Column::ReceivedAt, Order::Desc)
+            .limit(100)
+            .all(&self.db)
+            .await?;
+
+        Ok(deliveries.into_iter().map(delivery_to_response).collect())
+    }
+
+    /// Run a verified webhook workflow if it is active and its trigger
+    /// `filter`, if any, holds for the payload. Returns the execution IDs.
+    async fn trigger_webhook_workflow(
+        &self,
+        workflow: &Workflow,
+        payload: serde_json::Value,
+    ) -> WorkflowResult<Vec<Uuid>> {
+        if workflow.status != WorkflowStatus::Active {
+            return Ok(vec![]);
+        }
+        let initial_context = serde_json::json!({
+            "webhook": { "slug": workflow.webhook_slug, "payload": payload }
+        });
+        if !passes_trigger_filter(workflow.id, &workflow.trigger_config, &initial_context) {
+            return Ok(vec![]);
         }
 
-        Ok(execution_ids)
+        let steps = self.load_steps(workflow.id).await?;
+        let execution_id = self
+            .engine()
+            .execute(
+                workflow.id,
+                workflow.tenant_id,
+                None,
+                steps,
+                initial_context,
+            )
+            .await?;
+        Ok(vec![execution_id])
     }
 
     // ── Versioning ─────────────────────────────────────────────────────────────
@@ -485,6 +1048,8 @@ impl WorkflowService {
             "webhook_slug": wf.webhook_slug,
             "steps": steps.iter().map(|s| serde_json::json!({
                 "id": s.id,
+                "parent_step_id": s.parent_step_id,
+                "branch": s.branch,
                 "position": s.position,
                 "step_type": s.step_type,
                 "config": s.config,
@@ -584,13 +1149,14 @@ impl WorkflowService {
     }
 
     /// Restore a workflow to a previously saved version.
-    /// Saves a new version (the current state) before overwriting.
+    /// Saves a new version (the current state) before overwriting. Restored
+    /// steps are validated against `security` like newly added ones.
     pub async fn restore_version(
         &self,
         tenant_id: Uuid,
         workflow_id: Uuid,
         version: i32,
-        actor_id: Option<Uuid>,
+        security: SecurityContext,
     ) -> WorkflowResult<()> {
         let existing = WorkflowEntity::find_by_id(workflow_id)
             .filter(workflow::Column::TenantId.eq(tenant_id))
@@ -606,9 +1172,17 @@ impl WorkflowService {
             .ok_or_else(|| WorkflowError::StepNotFound(Uuid::nil()))?;
 
         let snapshot = &ver.snapshot;
+        let steps = snapshot
+            .get("steps")
+            .and_then(|v| v.as_array())
+            .map(|steps| snapshot_steps(workflow_id, steps))
+            .unwrap_or_default();
+        for step in &steps {
+            self.validate_step_config(&step.step_type, &step.config, &security)?;
+        }
 
         // Save current state as a new version before restoring
-        self.save_version_internal(workflow_id, actor_id, &existing)
+        self.save_version_internal(workflow_id, security.user_id, &existing)
             .await?;
 
         // Apply snapshot
@@ -632,40 +1206,43 @@ impl WorkflowService {
             .exec(&self.db)
             .await?;
 
-        if let Some(steps) = snapshot.get("steps").and_then(|v| v.as_array()) {
-            for step in steps {
-                let step_id = step
-                    .get("id")
-                    .and_then(|v| v.as_str())
-                    .and_then(|s| s.parse::<Uuid>().ok())
-                    .unwrap_or_else(Uuid::new_v4);
-
-                let position = step.get("position").and_then(|v| v.as_i64()).unwrap_or(0) as i32;
-                let step_type: crate::entities::StepType = step
-                    .get("step_type")
-                    .and_then(|v| serde_json::from_value(v.clone()).ok())
-                    .unwrap_or(crate::entities::StepType::Action);
-                let config = step
-                    .get("config")
-                    .cloned()
-                    .unwrap_or_else(|| serde_json::json!({}));
-                let on_error: crate::entities::OnError = step
-                    .get("on_error")
-                    .and_then(|v| serde_json::from_value(v.clone()).ok())
-                    .unwrap_or(crate::entities::OnError::Stop);
-                let timeout_ms = step.get("timeout_ms").and_then(|v| v.as_i64());
-
-                let am = WorkflowStepActiveModel {
-                    id: Set(step_id),
-                    workflow_id: Set(workflow_id),
-                    position: Set(position),
-                    step_type: Set(step_type),
-                    config: Set(config),
-                    on_error: Set(on_error),
-                    timeout_ms: Set(timeout_ms),
-                };
-                am.insert(&self.db).await?;
+        let mut pending: Vec<WorkflowStepActiveModel> = steps
+            .into_iter()
+            .map(|step| step.into_active_model().reset_all())
+            .collect();
+
+        // Parents go in before their children; steps whose parent is not
+        // part of the snapshot are restored at the top level.
+        let known: std::collections::HashSet<Uuid> = pending
+            .iter()
+            .filter_map(|step| step.id.clone().take())
+            .collect();
+        let mut inserted = std::collections::HashSet::new();
+        while !pending.is_empty() {
+            let (ready, rest): (Vec<_>, Vec<_>) =
+                pending.into_iter().partition(|step| {
+                    match step.parent_step_id.clone().take().flatten() {
+                        Some(parent) => inserted.contains(&parent) || !known.contains(&parent),
+                        None => true,
+                    }
+                });
+            if ready.is_empty() {
+                return Err(WorkflowError::InvalidStepConfig(
+                    "version snapshot contains a step cycle".into(),
+                ));
+            }
+            for mut step in ready {
+                if let Some(parent) = step.parent_step_id.clone().take().flatten() {
+                    if !known.contains(&parent) {
+                        step.parent_step_id = Set(None);
+                        step.branch = Set(None);
+                    }
+                }
+                let id = step.id.clone().take();
+                step.insert(&self.db).await?;
+                inserted.extend(id);
             }
+            pending = rest;
         }
 
         Ok(())
@@ -677,7 +1254,7 @@ impl WorkflowService {
     pub async fn create_from_template(
         &self,
         tenant_id: Uuid,
-        actor_id: Option<Uuid>,
+        security: SecurityContext,
         template_id: &str,
         name: String,
     ) -> WorkflowResult<Uuid> {
@@ -685,11 +1262,14 @@ impl WorkflowService {
             .iter()
             .find(|t| t.id == template_id)
             .ok_or_else(|| WorkflowError::NotFound(Uuid::nil()))?;
+        for step in &template.steps {
+            self.validate_step_config(&step.step_type, &step.config, &security)?;
+        }
 
         let workflow_id = self
             .create(
                 tenant_id,
-                actor_id,
+                security.user_id,
                 CreateWorkflowInput {
                     name,
                     description: Some(template.description.to_string()),
@@ -702,9 +1282,12 @@ impl WorkflowService {
         for (i, step) in template.steps.iter().enumerate() {
             self.add_step(
                 tenant_id,
+                security.clone(),
                 workflow_id,
                 crate::dto::CreateWorkflowStepInput {
                     position: i as i32,
+                    parent_step_id: None,
+                    branch: None,
                     step_type: step.step_type.clone(),
                     config: step.config.clone(),
                     on_error: step.on_error.clone(),
@@ -730,6 +1313,46 @@ impl WorkflowService {
     }
 }
 
+/// Steps of a version snapshot as step models of `workflow_id`.
+fn snapshot_steps(
+    workflow_id: Uuid,
+    steps: &[serde_json::Value],
+) -> Vec<crate::entities::WorkflowStep> {
+    steps
+        .iter()
+        .map(|step| crate::entities::WorkflowStep {
+            id: step
+                .get("id")
+                .and_then(|v| v.as_str())
+                .and_then(|s| s.parse::<Uuid>().ok())
+                .unwrap_or_else(Uuid::new_v4),
+            workflow_id,
+            parent_step_id: step
+                .get("parent_step_id")
+                .and_then(|v| v.as_str())
+                .and_then(|s| s.parse::<Uuid>().ok()),
+            branch: step
+                .get("branch")
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
+            position: step.get("position").and_then(|v| v.as_i64()).unwrap_or(0) as i32,
+            step_type: step
+                .get("step_type")
+                .and_then(|v| serde_json::from_value(v.clone()).ok())
+                .unwrap_or(crate::entities::StepType::Action),
+            config: step
+                .get("config")
+                .cloned()
+                .unwrap_or_else(|| serde_json::json!({})),
+            on_error: step
+                .get("on_error")
+                .and_then(|v| serde_json::from_value(v.clone()).ok())
+                .unwrap_or(crate::entities::OnError::Stop),
+            timeout_ms: step.get("timeout_ms").and_then(|v| v.as_i64()),
+        })
+        .collect()
+}
+
 fn execution_to_response(
     exec: crate::entities::WorkflowExecution,
     step_execs: Vec<crate::entities::WorkflowStepExecution>,
@@ -760,6 +1383,7 @@ fn step_execution_to_response(
         id: s.id,
         execution_id: s.execution_id,
         step_id: s.step_id,
+        branch: s.branch,
         status: s.status,
         input: s.input,
         output: s.output,
@@ -769,10 +1393,56 @@ fn step_execution_to_response(
     }
 }
 
+fn approval_to_response(a: crate::entities::WorkflowApproval) -> WorkflowApprovalResponse {
+    use chrono::DateTime;
+    WorkflowApprovalResponse {
+        id: a.id,
+        workflow_id: a.workflow_id,
+        execution_id: a.execution_id,
+        step_id: a.step_id,
+        title: a.title,
+        description: a.description,
+        assignee_user_ids: serde_json::from_value(a.assignee_user_ids).unwrap_or_default(),
+        assignee_roles: serde_json::from_value(a.assignee_roles).unwrap_or_default(),
+        status: a.status,
+        escalation_level: a.escalation_level,
+        deadline_at: a.deadline_at.map(DateTime::from),
+        decided_by: a.decided_by,
+        comment: a.comment,
+        decided_at: a.decided_at.map(DateTime::from),
+        created_at: DateTime::from(a.created_at),
+    }
+}
+
+fn delivery_to_response(
+    d: crate::entities::WorkflowWebhookDelivery,
+) -> WorkflowWebhookDeliveryResponse {
+    WorkflowWebhookDeliveryResponse {
+        id: d.id,
+        workflow_id: d.workflow_id,
+        webhook_slug: d.webhook_slug,
+        delivery_id: d.delivery_id,
+        status: d.status,
+        reason: d.reason,
+        execution_ids: serde_json::from_value(d.execution_ids).unwrap_or_default(),
+        received_at: d.received_at.into(),
+    }
+}
+
+fn is_assignee(approval: &WorkflowApprovalResponse, user_id: Uuid, role: &UserRole) -> bool {
+    approval.assignee_user_ids.contains(&user_id)
+        || approval
+            .assignee_roles
+            .iter()
+            .any(|assigned| *assigned == role.to_string())
+}
+
 fn step_to_response(s: crate::entities::WorkflowStep) -> WorkflowStepResponse {
     WorkflowStepResponse {
         id: s.id,
         workflow_id: s.workflow_id,
+        parent_step_id: s.parent_step_id,
+        branch: s.branch,
         position: s.position,
         step_type: s.step_type,
         config: s.config,
@@ -1,13 +1,118 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
+use rustok_core::events::EventTransport;
+use rustok_core::{
+    module_action_registry_from_extensions, ModuleActionContext, ModuleActionDescriptor,
+    ModuleActionError, ModuleActionRegistry, ModuleRuntimeExtensions, Permission, SecurityContext,
+    UserRole,
+};
+use sea_orm::DatabaseConnection;
 use serde_json::Value;
 use tracing::info;
 
-use super::{StepContext, StepOutput, WorkflowStep};
-use crate::error::WorkflowResult;
+use super::{resolve_templates, StepContext, StepOutput, WorkflowStep};
+use crate::error::{WorkflowError, WorkflowResult};
+
+/// Context key used for the action output when the config has no `output_key`.
+pub const DEFAULT_ACTION_OUTPUT_KEY: &str = "action_result";
+
+/// Principal that a workflow action runs under: the tenant admin role limited
+/// to the action's `required_permissions`, no user attached. Step authors are
+/// checked against the same permissions when the step is saved.
+pub fn workflow_action_principal(required_permissions: &[Permission]) -> SecurityContext {
+    SecurityContext::from_permissions(UserRole::Admin, None, required_permissions.iter().copied())
+}
 
-/// Action step — logs the action config and passes context through.
-/// In a full implementation this would call registered module service methods.
-pub struct ActionStep;
+/// Module actions published through runtime extensions plus the event
+/// transport they publish domain events to.
+#[derive(Clone)]
+pub struct WorkflowActionRuntime {
+    actions: Arc<ModuleActionRegistry>,
+    event_transport: Arc<dyn EventTransport>,
+}
+
+impl WorkflowActionRuntime {
+    pub fn new(
+        actions: Arc<ModuleActionRegistry>,
+        event_transport: Arc<dyn EventTransport>,
+    ) -> Self {
+        Self {
+            actions,
+            event_transport,
+        }
+    }
+
+    /// Builds the runtime when at least one module registered an action.
+    pub fn from_extensions(
+        extensions: &ModuleRuntimeExtensions,
+        event_transport: Arc<dyn EventTransport>,
+    ) -> Option<Self> {
+        module_action_registry_from_extensions(extensions)
+            .map(|actions| Self::new(actions, event_transport))
+    }
+
+    pub fn descriptors(&self) -> Vec<ModuleActionDescriptor> {
+        self.actions.descriptors()
+    }
+
+    /// Save-time validation of an `action` step config against the action's
+    /// input schema; `{{ ... }}` placeholders are accepted as whole values.
+    /// The author must hold every permission the action requires.
+    pub fn validate_config(&self, config: &Value, author: &SecurityContext) -> WorkflowResult<()> {
+        let action = action_name(config)?;
+        self.actions
+            .validate_template_input(action, &action_input(config))
+            .map_err(|error| WorkflowError::InvalidStepConfig(format!("action: {error}")))?;
+        let required = self.required_permissions(action);
+        match required
+            .iter()
+            .find(|permission| !author.has_permission(permission))
+        {
+            Some(permission) => Err(WorkflowError::PermissionDenied(format!(
+                "action '{action}' requires {permission}"
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    fn required_permissions(&self, action: &str) -> Vec<Permission> {
+        self.actions
+            .get(action)
+            .map(|action| action.descriptor().required_permissions)
+            .unwrap_or_default()
+    }
+}
+
+/// Action step — invokes a module action from the [`ModuleActionRegistry`].
+///
+/// Step config format:
+/// ```json
+/// {
+///   "action": "forum.lock_topic",
+///   "input": { "topic_id": "{{context.topic_id}}" },
+///   "output_key": "locked_topic"
+/// }
+/// ```
+///
+/// `input` placeholders are resolved from the step context; the action output
+/// is stored under `output_key` (default `action_result`).
+pub struct ActionStep {
+    runtime: Option<(DatabaseConnection, WorkflowActionRuntime)>,
+}
+
+impl ActionStep {
+    pub fn new(db: DatabaseConnection, runtime: WorkflowActionRuntime) -> Self {
+        Self {
+            runtime: Some((db, runtime)),
+        }
+    }
+
+    /// Creates a stub step that fails when no action runtime is attached.
+    pub fn stub() -> Self {
+        Self { runtime: None }
+    }
+}
 
 #[async_trait]
 impl WorkflowStep for ActionStep {
@@ -16,16 +121,215 @@ impl WorkflowStep for ActionStep {
     }
 
     async fn execute(&self, config: &Value, context: StepContext) -> WorkflowResult<StepOutput> {
-        let action = config
-            .get("action")
+        let action = action_name(config)?;
+        let output_key = config
+            .get("output_key")
             .and_then(Value::as_str)
-            .unwrap_or("unknown");
+            .unwrap_or(DEFAULT_ACTION_OUTPUT_KEY);
+
+        let (db, runtime) = self.runtime.as_ref().ok_or_else(|| {
+            WorkflowError::StepFailed(format!(
+                "action: no module action runtime registered for '{action}'"
+            ))
+        })?;
+        let tenant_id = context.tenant_id.ok_or_else(|| {
+            WorkflowError::StepFailed("action: step context has no tenant".into())
+        })?;
 
         info!(action = action, "Executing action step");
 
+        let input = resolve_templates(&action_input(config), &context.data);
+        let action_context = ModuleActionContext {
+            db: db.clone(),
+            tenant_id,
+            actor_id: None,
+            security: workflow_action_principal(&runtime.required_permissions(action)),
+            event_transport: runtime.event_transport.clone(),
+        };
+        let output = runtime
+            .actions
+            .invoke(action, &action_context, input)
+            .await
+            .map_err(|error| match error {
+                ModuleActionError::UnknownAction(_) | ModuleActionError::InvalidInput { .. } => {
+                    WorkflowError::InvalidStepConfig(format!("action: {error}"))
+                }
+                other => WorkflowError::StepFailed(format!("action '{action}': {other}")),
+            })?;
+
+        let mut new_context = context;
+        new_context.set(output_key, output.clone());
+
         Ok(StepOutput::continue_with(
-            context,
-            serde_json::json!({ "action": action, "status": "ok" }),
+            new_context,
+            serde_json::json!({ "action": action, "output": output }),
         ))
     }
 }
+
+fn action_name(config: &Value) -> WorkflowResult<&str> {
+    config
+        .get("action")
+        .and_then(Value::as_str)
+        .filter(|name| !name.trim().is_empty())
+        .ok_or_else(|| WorkflowError::InvalidStepConfig("action: missing 'action'".into()))
+}
+
+/// `input` object of the config; `params` is accepted for older configs.
+fn action_input(config: &Value) -> Value {
+    config
+        .get("input")
+        .or_else(|| config.get("params"))
+        .cloned()
+        .unwrap_or_else(|| Value::Object(Default::default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustok_core::events::MemoryTransport;
+    use rustok_core::{ModuleAction, ModuleActionResult};
+    use serde_json::json;
+    use uuid::Uuid;
+
+    struct TagCustomer;
+
+    #[async_trait]
+    impl ModuleAction for TagCustomer {
+        fn descriptor(&self) -> ModuleActionDescriptor {
+            ModuleActionDescriptor {
+                name: "customer.tag",
+                module_slug: "customer",
+                description: "Tag a customer",
+                input_schema: json!({
+                    "type": "object",
+                    "required": ["customer_id", "tag"],
+                    "properties": {
+                        "customer_id": { "type": "string", "format": "uuid" },
+                        "tag": { "type": "string", "minLength": 1 }
+                    }
+                }),
+                output_schema: Value::Null,
+                required_permissions: vec![Permission::CUSTOMERS_UPDATE],
+            }
+        }
+
+        async fn invoke(
+            &self,
+            ctx: &ModuleActionContext,
+            input: Value,
+        ) -> ModuleActionResult<Value> {
+            Ok(json!({
+                "tenant_id": ctx.tenant_id,
+                "customer_id": input["customer_id"],
+                "tags": [input["tag"]],
+                "can_delete": ctx.security.has_permission(&Permission::CUSTOMERS_DELETE)
+            }))
+        }
+    }
+
+    fn runtime() -> WorkflowActionRuntime {
+        let mut registry = ModuleActionRegistry::default();
+        registry.register(TagCustomer).expect("registration");
+        WorkflowActionRuntime::new(Arc::new(registry), Arc::new(MemoryTransport::new()))
+    }
+
+    fn author(permissions: &[Permission]) -> SecurityContext {
+        SecurityContext::from_permissions(
+            UserRole::Manager,
+            Some(Uuid::new_v4()),
+            permissions.iter().copied(),
+        )
+    }
+
+    #[test]
+    fn validates_config_at_save_time() {
+        let runtime = runtime();
+        let editor = author(&[Permission::CUSTOMERS_UPDATE]);
+        assert!(runtime
+            .validate_config(
+                &json!({
+                    "action": "customer.tag",
+                    "input": { "customer_id": "{{context.customer_id}}", "tag": "vip" }
+                }),
+                &editor
+            )
+            .is_ok());
+        assert!(matches!(
+            runtime.validate_config(
+                &json!({ "action": "customer.tag", "input": { "tag": "" } }),
+                &editor
+            ),
+            Err(WorkflowError::InvalidStepConfig(_))
+        ));
+        assert!(matches!(
+            runtime.validate_config(
+                &json!({ "action": "customer.delete", "input": {} }),
+                &editor
+            ),
+            Err(WorkflowError::InvalidStepConfig(_))
+        ));
+        assert!(matches!(
+            runtime.validate_config(&json!({ "input": {} }), &editor),
+            Err(WorkflowError::InvalidStepConfig(_))
+        ));
+    }
+
+    #[test]
+    fn authors_need_the_action_permissions() {
+        let config = json!({
+            "action": "customer.tag",
+            "input": { "customer_id": "{{context.customer_id}}", "tag": "vip" }
+        });
+        let runtime = runtime();
+        assert!(matches!(
+            runtime.validate_config(&config, &author(&[Permission::WORKFLOWS_UPDATE])),
+            Err(WorkflowError::PermissionDenied(_))
+        ));
+        assert!(runtime
+            .validate_config(&config, &author(&[Permission::CUSTOMERS_MANAGE]))
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn stores_typed_output_under_output_key() {
+        let tenant_id = Uuid::new_v4();
+        let customer_id = Uuid::new_v4();
+        let db = sea_orm::Database::connect("sqlite::memory:")
+            .await
+            .expect("in-memory sqlite should connect");
+        let context = StepContext::new(json!({ "customer_id": customer_id }))
+            .for_execution(tenant_id, Uuid::new_v4());
+
+        let output = ActionStep::new(db, runtime())
+            .execute(
+                &json!({
+                    "action": "customer.tag",
+                    "input": { "customer_id": "{{context.customer_id}}", "tag": "vip" },
+                    "output_key": "tagged"
+                }),
+                context,
+            )
+            .await
+            .expect("action should run");
+
+        assert_eq!(
+            output.context.get("tagged"),
+            Some(&json!({
+                "tenant_id": tenant_id,
+                "customer_id": customer_id,
+                "tags": ["vip"],
+                "can_delete": false
+            }))
+        );
+        assert_eq!(output.data["action"], "customer.tag");
+    }
+
+    #[tokio::test]
+    async fn stub_fails_without_runtime() {
+        let result = ActionStep::stub()
+            .execute(&json!({ "action": "customer.tag" }), StepContext::default())
+            .await;
+        assert!(matches!(result, Err(WorkflowError::StepFailed(_))));
+    }
+}
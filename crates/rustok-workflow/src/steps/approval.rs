@@ -0,0 +1,467 @@
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use rustok_core::UserRole;
+use serde_json::{json, Value};
+use tracing::info;
+use uuid::Uuid;
+
+use super::{
+    resolve_template_string, ApprovalRequest, ResumeSignal, StepContext, StepOutput,
+    StepSuspension, WorkflowStep,
+};
+use crate::error::{WorkflowError, WorkflowResult};
+
+/// Context key used for the approval result when the config has no `output_key`.
+pub const DEFAULT_APPROVAL_OUTPUT_KEY: &str = "approval";
+const MAX_APPROVAL_DEADLINE_MS: u64 = 366 * 24 * 60 * 60 * 1000; // one year
+const MAX_TITLE_LEN: usize = 255;
+
+/// What happens when the deadline passes without a decision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnDeadline {
+    /// Auto-reject the task.
+    Reject,
+    /// Reassign the task to `escalate_to` once; its deadline then auto-rejects.
+    Escalate,
+}
+
+/// What a rejection (or auto-rejection) does to the execution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnReject {
+    Stop,
+    Fail,
+    Continue,
+}
+
+/// Who may decide an approval task.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Assignees {
+    pub user_ids: Vec<Uuid>,
+    pub roles: Vec<UserRole>,
+}
+
+impl Assignees {
+    fn parse(config: &Value, key: &str) -> WorkflowResult<Self> {
+        let assignees = config
+            .get(key)
+            .and_then(Value::as_object)
+            .ok_or_else(|| invalid(format!("approval: '{key}' must be an object")))?;
+        let list = |field: &str| -> WorkflowResult<Vec<&str>> {
+            match assignees.get(field) {
+                None => Ok(Vec::new()),
+                Some(Value::Array(items)) => items
+                    .iter()
+                    .map(|item| {
+                        item.as_str().ok_or_else(|| {
+                            invalid(format!("approval: '{key}.{field}' must hold strings"))
+                        })
+                    })
+                    .collect(),
+                Some(_) => Err(invalid(format!(
+                    "approval: '{key}.{field}' must be an array"
+                ))),
+            }
+        };
+        let user_ids = list("users")?
+            .into_iter()
+            .map(|id| {
+                Uuid::parse_str(id)
+                    .map_err(|_| invalid(format!("approval: '{id}' in '{key}' is not a user id")))
+            })
+            .collect::<WorkflowResult<Vec<_>>>()?;
+        let roles = list("roles")?
+            .into_iter()
+            .map(|role| {
+                UserRole::from_str(role)
+                    .map_err(|_| invalid(format!("approval: unknown role '{role}' in '{key}'")))
+            })
+            .collect::<WorkflowResult<Vec<_>>>()?;
+        if user_ids.is_empty() && roles.is_empty() {
+            return Err(invalid(format!(
+                "approval: '{key}' needs at least one user or role"
+            )));
+        }
+        Ok(Self { user_ids, roles })
+    }
+
+    /// Whether `user_id`, acting with `role`, may decide.
+    pub fn includes(&self, user_id: Uuid, role: &UserRole) -> bool {
+        self.user_ids.contains(&user_id) || self.roles.contains(role)
+    }
+}
+
+/// Parsed `approval` step config.
+///
+/// ```json
+/// {
+///   "title": "Refund for order {{context.order.number}}",
+///   "description": "Amount: {{context.refund.amount}}",
+///   "assignees": { "users": ["<uuid>"], "roles": ["manager"] },
+///   "deadline_ms": 86400000,
+///   "on_deadline": "escalate",
+///   "escalate_to": { "roles": ["admin"] },
+///   "escalation_deadline_ms": 43200000,
+///   "on_reject": "stop",
+///   "output_key": "refund_approval"
+/// }
+/// ```
+/// Without `deadline_ms` the task waits indefinitely. `on_deadline` is
+/// `reject` (default) or `escalate`, which needs `escalate_to`; the
+/// escalated task auto-rejects after `escalation_deadline_ms` (default:
+/// `deadline_ms`). `on_reject` is `stop` (default), `fail` or `continue`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApprovalConfig {
+    pub title: String,
+    pub description: Option<String>,
+    pub assignees: Assignees,
+    pub deadline_ms: Option<u64>,
+    pub on_deadline: OnDeadline,
+    pub escalate_to: Option<Assignees>,
+    pub escalation_deadline_ms: Option<u64>,
+    pub on_reject: OnReject,
+    pub output_key: String,
+}
+
+impl ApprovalConfig {
+    pub fn parse(config: &Value) -> WorkflowResult<Self> {
+        let title = config
+            .get("title")
+            .and_then(Value::as_str)
+            .filter(|title| !title.trim().is_empty() && title.len() <= MAX_TITLE_LEN)
+            .ok_or_else(|| {
+                invalid(format!(
+                    "approval: 'title' must be a non-empty string up to {MAX_TITLE_LEN} bytes"
+                ))
+            })?
+            .to_string();
+        let description = match config.get("description") {
+            None | Some(Value::Null) => None,
+            Some(Value::String(description)) => Some(description.clone()),
+            Some(_) => return Err(invalid("approval: 'description' must be a string")),
+        };
+        let on_deadline = match config.get("on_deadline").map(Value::as_str) {
+            None | Some(Some("reject")) => OnDeadline::Reject,
+            Some(Some("escalate")) => OnDeadline::Escalate,
+            Some(_) => {
+                return Err(invalid(
+                    "approval: 'on_deadline' must be 'reject' or 'escalate'",
+                ))
+            }
+        };
+        let deadline_ms = deadline(config, "deadline_ms")?;
+        let escalate_to = match on_deadline {
+            OnDeadline::Escalate => {
+                if deadline_ms.is_none() {
+                    return Err(invalid(
+                        "approval: 'on_deadline: escalate' needs 'deadline_ms'",
+                    ));
+                }
+                Some(Assignees::parse(config, "escalate_to")?)
+            }
+            OnDeadline::Reject => None,
+        };
+        let on_reject = match config.get("on_reject").map(Value::as_str) {
+            None | Some(Some("stop")) => OnReject::Stop,
+            Some(Some("fail")) => OnReject::Fail,
+            Some(Some("continue")) => OnReject::Continue,
+            Some(_) => {
+                return Err(invalid(
+                    "approval: 'on_reject' must be 'stop', 'fail' or 'continue'",
+                ))
+            }
+        };
+
+        Ok(Self {
+            title,
+            description,
+            assignees: Assignees::parse(config, "assignees")?,
+            deadline_ms,
+            on_deadline,
+            escalate_to,
+            escalation_deadline_ms: deadline(config, "escalation_deadline_ms")?,
+            on_reject,
+            output_key: config
+                .get("output_key")
+                .and_then(Value::as_str)
+                .unwrap_or(DEFAULT_APPROVAL_OUTPUT_KEY)
+                .to_string(),
+        })
+    }
+
+    /// Whether the deadline of a task at `escalation_level` escalates it
+    /// rather than auto-rejecting it.
+    pub fn escalates(&self, escalation_level: i32) -> bool {
+        self.on_deadline == OnDeadline::Escalate && escalation_level == 0
+    }
+
+    /// Assignees of a task at `escalation_level`.
+    pub fn assignees_at(&self, escalation_level: i32) -> &Assignees {
+        match (&self.escalate_to, escalation_level) {
+            (Some(escalate_to), level) if level > 0 => escalate_to,
+            _ => &self.assignees,
+        }
+    }
+
+    fn suspension(&self, context: &StepContext, escalation_level: i32) -> StepSuspension {
+        let deadline_ms = if escalation_level > 0 {
+            self.escalation_deadline_ms.or(self.deadline_ms)
+        } else {
+            self.deadline_ms
+        };
+        let assignees = self.assignees_at(escalation_level);
+        let render = |text: &str| match resolve_template_string(text, &context.data) {
+            Value::String(text) => text,
+            Value::Null => String::new(),
+            other => other.to_string(),
+        };
+        let mut title = render(&self.title);
+        if title.len() > MAX_TITLE_LEN {
+            let end = (0..=MAX_TITLE_LEN)
+                .rev()
+                .find(|idx| title.is_char_boundary(*idx))
+                .unwrap_or(0);
+            title.truncate(end);
+        }
+
+        StepSuspension {
+            resume_at: deadline_ms
+                .map(|deadline_ms| Utc::now() + chrono::Duration::milliseconds(deadline_ms as i64)),
+            wait_for: None,
+            approval: Some(ApprovalRequest {
+                title,
+                description: self.description.as_deref().map(render),
+                assignee_user_ids: assignees.user_ids.clone(),
+                assignee_roles: assignees.roles.iter().map(ToString::to_string).collect(),
+                escalation_level,
+            }),
+        }
+    }
+}
+
+/// Approval step — suspends the execution until an assignee approves or
+/// rejects the approval task it opens, escalating or auto-rejecting at the
+/// deadline. See [`ApprovalConfig`] for the config format.
+///
+/// While waiting, `output_key` holds `{"status": "pending", ...}`; once
+/// decided it holds the decision (`status`, `approved`, `decided_by`,
+/// `comment`, `decided_at`, `escalation_level`), which is also the step's
+/// output in the execution log.
+pub struct ApprovalStep;
+
+impl ApprovalStep {
+    /// Save-time validation of the config shape.
+    pub fn validate_config(config: &Value) -> WorkflowResult<()> {
+        ApprovalConfig::parse(config).map(|_| ())
+    }
+
+    fn suspend(
+        config: &ApprovalConfig,
+        mut context: StepContext,
+        escalation_level: i32,
+    ) -> StepOutput {
+        let suspension = config.suspension(&context, escalation_level);
+        let pending = json!({
+            "status": "pending",
+            "escalation_level": escalation_level,
+            "deadline_at": suspension.resume_at,
+        });
+        context.set(config.output_key.clone(), pending.clone());
+        StepOutput::suspend_with(context, pending, suspension)
+    }
+
+    fn settle(
+        config: &ApprovalConfig,
+        mut context: StepContext,
+        result: Value,
+    ) -> WorkflowResult<StepOutput> {
+        context.set(config.output_key.clone(), result.clone());
+        if result["approved"] == true {
+            return Ok(StepOutput::continue_with(context, result));
+        }
+        match config.on_reject {
+            OnReject::Stop => Ok(StepOutput::stop_with(context, result)),
+            OnReject::Continue => Ok(StepOutput::continue_with(context, result)),
+            OnReject::Fail => Err(WorkflowError::StepFailed(format!(
+                "approval: '{}' was {}",
+                config.title, result["status"]
+            ))),
+        }
+    }
+}
+
+#[async_trait]
+impl WorkflowStep for ApprovalStep {
+    fn step_type(&self) -> &'static str {
+        "approval"
+    }
+
+    async fn execute(&self, config: &Value, context: StepContext) -> WorkflowResult<StepOutput> {
+        let config = ApprovalConfig::parse(config)?;
+        info!(title = %config.title, "Requesting approval");
+        Ok(Self::suspend(&config, context, 0))
+    }
+
+    async fn resume(
+        &self,
+        config: &Value,
+        context: StepContext,
+        signal: ResumeSignal,
+    ) -> WorkflowResult<StepOutput> {
+        let config = ApprovalConfig::parse(config)?;
+        let escalation_level = context
+            .get(&config.output_key)
+            .and_then(|pending| pending.get("escalation_level"))
+            .and_then(Value::as_i64)
+            .unwrap_or(0) as i32;
+
+        match signal {
+            ResumeSignal::Decision(decision) => {
+                let status = if decision.approved {
+                    "approved"
+                } else {
+                    "rejected"
+                };
+                info!(status = status, decided_by = %decision.decided_by, "Approval decided");
+                let result = json!({
+                    "status": status,
+                    "approved": decision.approved,
+                    "decided_by": decision.decided_by,
+                    "comment": decision.comment,
+                    "decided_at": decision.decided_at,
+                    "escalation_level": escalation_level,
+                });
+                Self::settle(&config, context, result)
+            }
+            ResumeSignal::Timer if config.escalates(escalation_level) => {
+                info!(title = %config.title, "Approval deadline passed, escalating");
+                Ok(Self::suspend(&config, context, escalation_level + 1))
+            }
+            ResumeSignal::Timer => {
+                info!(title = %config.title, "Approval deadline passed, auto-rejecting");
+                let result = json!({
+                    "status": "expired",
+                    "approved": false,
+                    "decided_by": null,
+                    "comment": null,
+                    "decided_at": Utc::now(),
+                    "escalation_level": escalation_level,
+                });
+                Self::settle(&config, context, result)
+            }
+            ResumeSignal::Event(_) => Err(WorkflowError::StepFailed(
+                "approval: cannot be resumed by an event".into(),
+            )),
+        }
+    }
+}
+
+fn deadline(config: &Value, key: &str) -> WorkflowResult<Option<u64>> {
+    match config.get(key) {
+        None => Ok(None),
+        Some(value) => value
+            .as_u64()
+            .filter(|deadline_ms| (1..=MAX_APPROVAL_DEADLINE_MS).contains(deadline_ms))
+            .map(Some)
+            .ok_or_else(|| {
+                invalid(format!(
+                    "approval: '{key}' must be an integer between 1 and {MAX_APPROVAL_DEADLINE_MS}"
+                ))
+            }),
+    }
+}
+
+fn invalid(message: impl Into<String>) -> WorkflowError {
+    WorkflowError::InvalidStepConfig(message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::steps::ApprovalDecision;
+
+    fn config() -> Value {
+        json!({
+            "title": "Refund {{context.order}}",
+            "assignees": { "roles": ["manager"] },
+            "deadline_ms": 3_600_000,
+            "on_deadline": "escalate",
+            "escalate_to": { "roles": ["admin"] },
+            "output_key": "refund"
+        })
+    }
+
+    #[tokio::test]
+    async fn escalates_once_then_auto_rejects() {
+        let output = ApprovalStep
+            .execute(&config(), StepContext::new(json!({ "order": "A-1" })))
+            .await
+            .expect("approval should suspend");
+        let request = output
+            .suspension
+            .as_ref()
+            .and_then(|suspension| suspension.approval.clone())
+            .expect("step should open an approval task");
+        assert_eq!(request.title, "Refund A-1");
+        assert_eq!(request.assignee_roles, vec!["manager"]);
+
+        let escalated = ApprovalStep
+            .resume(&config(), output.context, ResumeSignal::Timer)
+            .await
+            .expect("deadline should escalate");
+        let request = escalated
+            .suspension
+            .as_ref()
+            .and_then(|suspension| suspension.approval.clone())
+            .expect("escalation should reassign the task");
+        assert_eq!(request.escalation_level, 1);
+        assert_eq!(request.assignee_roles, vec!["admin"]);
+
+        let expired = ApprovalStep
+            .resume(&config(), escalated.context, ResumeSignal::Timer)
+            .await
+            .expect("second deadline should auto-reject");
+        assert!(expired.suspension.is_none());
+        assert!(!expired.should_continue);
+        assert_eq!(expired.context.get("refund").unwrap()["status"], "expired");
+    }
+
+    #[tokio::test]
+    async fn decision_is_recorded_under_output_key() {
+        let decided_by = Uuid::new_v4();
+        let output = ApprovalStep
+            .resume(
+                &config(),
+                StepContext::new(json!({})),
+                ResumeSignal::Decision(ApprovalDecision {
+                    approved: true,
+                    decided_by,
+                    comment: Some("ok".into()),
+                    decided_at: Utc::now(),
+                }),
+            )
+            .await
+            .expect("decision should resume");
+        assert!(output.should_continue);
+        let result = output.context.get("refund").unwrap();
+        assert_eq!(result["status"], "approved");
+        assert_eq!(result["decided_by"], json!(decided_by));
+        assert_eq!(result["comment"], "ok");
+    }
+
+    #[test]
+    fn validates_config() {
+        assert!(ApprovalStep::validate_config(&config()).is_ok());
+        for config in [
+            json!({ "assignees": { "roles": ["manager"] } }),
+            json!({ "title": "t", "assignees": {} }),
+            json!({ "title": "t", "assignees": { "roles": ["owner"] } }),
+            json!({ "title": "t", "assignees": { "users": ["not-a-uuid"] } }),
+            json!({ "title": "t", "assignees": { "roles": ["admin"] }, "on_deadline": "escalate", "deadline_ms": 1 }),
+            json!({ "title": "t", "assignees": { "roles": ["admin"] }, "on_reject": "retry" }),
+        ] {
+            assert!(ApprovalStep::validate_config(&config).is_err(), "{config}");
+        }
+    }
+}
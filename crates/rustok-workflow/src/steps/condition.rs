@@ -2,24 +2,36 @@ use async_trait::async_trait;
 use serde_json::Value;
 use tracing::debug;
 
+use super::expression::Expression;
 use super::{StepContext, StepOutput, WorkflowStep};
 use crate::error::{WorkflowError, WorkflowResult};
 
-/// Condition step — evaluates a simple equality condition against the context.
+/// Condition step — evaluates a condition against the context.
 ///
 /// Config format:
 /// ```json
 /// {
-///   "field": "event.status",
-///   "operator": "eq",
-///   "value": "paid",
+///   "expression": "order.total >= 100 and order.status in ['paid', 'shipped']",
 ///   "stop_on_false": true
 /// }
 /// ```
 ///
-/// Supported operators: "eq", "ne", "exists", "not_exists"
+/// See [`Expression`] for the expression syntax. The single-field form is
+/// still accepted:
+/// ```json
+/// { "field": "event.status", "operator": "eq", "value": "paid" }
+/// ```
+/// with operators "eq", "ne", "exists", "not_exists".
 pub struct ConditionStep;
 
+impl ConditionStep {
+    /// Save-time validation: the expression parses and type-checks, or the
+    /// field form names a field and a known operator.
+    pub fn validate_config(config: &Value) -> WorkflowResult<()> {
+        validate_condition(config)
+    }
+}
+
 #[async_trait]
 impl WorkflowStep for ConditionStep {
     fn step_type(&self) -> &'static str {
@@ -27,50 +39,22 @@ impl WorkflowStep for ConditionStep {
     }
 
     async fn execute(&self, config: &Value, context: StepContext) -> WorkflowResult<StepOutput> {
-        let field = config
-            .get("field")
-            .and_then(Value::as_str)
-            .ok_or_else(|| WorkflowError::InvalidStepConfig("condition: missing 'field'".into()))?;
-
-        let operator = config
-            .get("operator")
-            .and_then(Value::as_str)
-            .unwrap_or("eq");
-
         let stop_on_false = config
             .get("stop_on_false")
             .and_then(Value::as_bool)
             .unwrap_or(true);
 
-        let actual = resolve_field(field, &context.data);
-
-        let result = match operator {
-            "eq" => {
-                let expected = config.get("value");
-                actual == expected
+        let condition = Condition::parse(config)?;
+        let result = condition.evaluate(&context.data)?;
+        let output = match &condition {
+            Condition::Expression(expression) => {
+                serde_json::json!({ "expression": expression.source(), "result": result })
             }
-            "ne" => {
-                let expected = config.get("value");
-                actual != expected
-            }
-            "exists" => actual.is_some(),
-            "not_exists" => actual.is_none(),
-            op => {
-                return Err(WorkflowError::InvalidStepConfig(format!(
-                    "condition: unknown operator '{op}'"
-                )))
+            Condition::Field { field, .. } => {
+                serde_json::json!({ "field": field, "result": result })
             }
         };
 
-        debug!(
-            field = field,
-            operator = operator,
-            result = result,
-            "Condition evaluated"
-        );
-
-        let output = serde_json::json!({ "field": field, "result": result });
-
         if result || !stop_on_false {
             Ok(StepOutput::continue_with(context, output))
         } else {
@@ -79,6 +63,93 @@ impl WorkflowStep for ConditionStep {
     }
 }
 
+/// A condition config: an expression (`"..."` or `{"expression": "..."}`)
+/// or the `{"field", "operator", "value"}` form.
+enum Condition<'a> {
+    Expression(Expression),
+    Field {
+        field: &'a str,
+        operator: &'a str,
+        value: Option<&'a Value>,
+    },
+}
+
+impl<'a> Condition<'a> {
+    fn parse(config: &'a Value) -> WorkflowResult<Self> {
+        let source = match config {
+            Value::String(source) => Some(source),
+            _ => match config.get("expression") {
+                Some(Value::String(source)) => Some(source),
+                Some(_) => {
+                    return Err(WorkflowError::InvalidStepConfig(
+                        "condition: 'expression' must be a string".into(),
+                    ))
+                }
+                None => None,
+            },
+        };
+        if let Some(source) = source {
+            return Expression::compile(source)
+                .map(Self::Expression)
+                .map_err(|err| WorkflowError::InvalidStepConfig(format!("condition: {err}")));
+        }
+
+        let field = config.get("field").and_then(Value::as_str).ok_or_else(|| {
+            WorkflowError::InvalidStepConfig("condition: missing 'expression' or 'field'".into())
+        })?;
+        let operator = config
+            .get("operator")
+            .and_then(Value::as_str)
+            .unwrap_or("eq");
+        if !matches!(operator, "eq" | "ne" | "exists" | "not_exists") {
+            return Err(WorkflowError::InvalidStepConfig(format!(
+                "condition: unknown operator '{operator}'"
+            )));
+        }
+        Ok(Self::Field {
+            field,
+            operator,
+            value: config.get("value"),
+        })
+    }
+
+    fn evaluate(&self, data: &Value) -> WorkflowResult<bool> {
+        let result = match self {
+            Self::Expression(expression) => expression
+                .evaluate(data)
+                .map_err(|err| WorkflowError::StepFailed(format!("condition: {err}")))?,
+            Self::Field {
+                field,
+                operator,
+                value,
+            } => {
+                let actual = resolve_field(field, data);
+                match *operator {
+                    "eq" => actual == *value,
+                    "ne" => actual != *value,
+                    "exists" => actual.is_some(),
+                    _ => actual.is_none(),
+                }
+            }
+        };
+
+        debug!(result = result, "Condition evaluated");
+
+        Ok(result)
+    }
+}
+
+/// Checks that a condition config compiles, without evaluating it.
+pub(crate) fn validate_condition(config: &Value) -> WorkflowResult<()> {
+    Condition::parse(config).map(|_| ())
+}
+
+/// Evaluates a condition config against `data`.
+/// Shared by the `condition` step and `branch` steps.
+pub(crate) fn evaluate_condition(config: &Value, data: &Value) -> WorkflowResult<bool> {
+    Condition::parse(config)?.evaluate(data)
+}
+
 /// Resolves a dot-notation path like "event.status" against a JSON value.
 fn resolve_field<'a>(path: &str, data: &'a Value) -> Option<&'a Value> {
     path.split('.')
@@ -0,0 +1,456 @@
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::condition::{evaluate_condition, validate_condition};
+use super::{lookup_template_path, resolve_template_string};
+use crate::entities::StepType;
+use crate::error::{WorkflowError, WorkflowResult};
+
+/// Branch taken by an `if` branch step when the condition holds.
+pub const THEN_BRANCH: &str = "then";
+/// Branch taken by an `if` branch step when the condition fails.
+pub const ELSE_BRANCH: &str = "else";
+/// Fallback branch of a `switch` branch step without an explicit `default`.
+pub const DEFAULT_CASE: &str = "default";
+
+pub const MAX_FOR_EACH_ITEMS: usize = 1_000;
+pub const MAX_FOR_EACH_CONCURRENCY: usize = 16;
+/// Nesting limit for sub-workflow calls, guarding against call cycles.
+pub const MAX_SUB_WORKFLOW_DEPTH: usize = 5;
+const MAX_BRANCH_LABEL_LEN: usize = 64;
+
+/// `branch` step — routes execution into one of its child branches.
+///
+/// If/else:
+/// ```json
+/// { "when": "order.total == 0 or order.status == 'free'" }
+/// ```
+/// runs the `then` or `else` children; `when` takes any `condition` step
+/// config. Switch:
+/// ```json
+/// { "switch": "order.status", "cases": ["paid", "refunded"], "default": "other" }
+/// ```
+/// runs the children whose branch equals the value at `switch`, or the
+/// `default` branch.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BranchConfig {
+    If {
+        condition: Value,
+    },
+    Switch {
+        field: String,
+        cases: Vec<String>,
+        default: String,
+    },
+}
+
+impl BranchConfig {
+    pub fn parse(config: &Value) -> WorkflowResult<Self> {
+        match (config.get("when"), config.get("switch")) {
+            (Some(condition), None) => {
+                validate_condition(condition)?;
+                Ok(Self::If {
+                    condition: condition.clone(),
+                })
+            }
+            (None, Some(field)) => {
+                let field = field
+                    .as_str()
+                    .filter(|field| !field.trim().is_empty())
+                    .ok_or_else(|| invalid("branch: 'switch' must be a field path"))?;
+                let cases = config
+                    .get("cases")
+                    .and_then(Value::as_array)
+                    .filter(|cases| !cases.is_empty())
+                    .ok_or_else(|| invalid("branch: 'cases' must be a non-empty array"))?
+                    .iter()
+                    .map(|case| case_label(case).and_then(|label| validate_label(&label)))
+                    .collect::<WorkflowResult<Vec<_>>>()?;
+                let default = match config.get("default") {
+                    None => DEFAULT_CASE.to_string(),
+                    Some(Value::String(default)) => validate_label(default)?,
+                    Some(_) => return Err(invalid("branch: 'default' must be a string")),
+                };
+                Ok(Self::Switch {
+                    field: field.to_string(),
+                    cases,
+                    default,
+                })
+            }
+            _ => Err(invalid(
+                "branch: exactly one of 'when' or 'switch' is required",
+            )),
+        }
+    }
+
+    /// Every branch name this step can route to.
+    pub fn labels(&self) -> Vec<String> {
+        match self {
+            Self::If { .. } => vec![THEN_BRANCH.to_string(), ELSE_BRANCH.to_string()],
+            Self::Switch { cases, default, .. } => {
+                let mut labels = cases.clone();
+                labels.push(default.clone());
+                labels
+            }
+        }
+    }
+
+    /// Picks the branch to run for the current context.
+    pub fn select(&self, data: &Value) -> WorkflowResult<String> {
+        match self {
+            Self::If { condition } => Ok(if evaluate_condition(condition, data)? {
+                THEN_BRANCH.to_string()
+            } else {
+                ELSE_BRANCH.to_string()
+            }),
+            Self::Switch {
+                field,
+                cases,
+                default,
+            } => {
+                let selected = lookup_template_path(field, data)
+                    .and_then(|value| case_label(value).ok())
+                    .filter(|label| cases.contains(label));
+                Ok(selected.unwrap_or_else(|| default.clone()))
+            }
+        }
+    }
+}
+
+/// How many `parallel` lanes must succeed for the step to succeed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Join {
+    All,
+    Any,
+    AtLeast(usize),
+}
+
+impl Join {
+    /// Successful lanes required out of `lanes`.
+    pub fn required(self, lanes: usize) -> usize {
+        match self {
+            Self::All => lanes,
+            Self::Any => lanes.min(1),
+            Self::AtLeast(count) => count,
+        }
+    }
+}
+
+/// `parallel` step — runs every child lane concurrently on a copy of the
+/// context, then joins.
+///
+/// ```json
+/// { "join": "all" }
+/// ```
+/// `join` is `all` (default), `any` or the number of lanes that must
+/// succeed. Lanes are the distinct `branch` names of the children. Top-level
+/// context keys changed by successful lanes are merged back in lane order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParallelConfig {
+    pub join: Join,
+}
+
+impl ParallelConfig {
+    pub fn parse(config: &Value) -> WorkflowResult<Self> {
+        let join = match config.get("join") {
+            None => Join::All,
+            Some(Value::String(join)) if join == "all" => Join::All,
+            Some(Value::String(join)) if join == "any" => Join::Any,
+            Some(Value::Number(count)) => count
+                .as_u64()
+                .filter(|count| *count >= 1)
+                .map(|count| Join::AtLeast(count as usize))
+                .ok_or_else(|| invalid("parallel: numeric 'join' must be at least 1"))?,
+            Some(_) => {
+                return Err(invalid(
+                    "parallel: 'join' must be 'all', 'any' or a lane count",
+                ))
+            }
+        };
+        Ok(Self { join })
+    }
+}
+
+/// `for_each` step — runs its children once per item of an array.
+///
+/// ```json
+/// {
+///   "items": "order.lines",
+///   "item_key": "line",
+///   "concurrency": 4,
+///   "collect": "line.total",
+///   "output_key": "line_totals"
+/// }
+/// ```
+/// Each iteration sees the context plus `item_key` (default `item`) and
+/// `index_key` (default `index`). The value at `collect` (default: the item
+/// after the body ran) of every iteration is stored in order under
+/// `output_key` (default `for_each_results`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForEachConfig {
+    pub items: String,
+    pub item_key: String,
+    pub index_key: String,
+    pub concurrency: usize,
+    pub collect: Option<String>,
+    pub output_key: String,
+}
+
+impl ForEachConfig {
+    pub fn parse(config: &Value) -> WorkflowResult<Self> {
+        let items = config
+            .get("items")
+            .and_then(Value::as_str)
+            .filter(|items| !items.trim().is_empty())
+            .ok_or_else(|| invalid("for_each: 'items' must be a path or placeholder"))?;
+        let concurrency = match config.get("concurrency") {
+            None => 1,
+            Some(value) => value
+                .as_u64()
+                .filter(|count| (1..=MAX_FOR_EACH_CONCURRENCY as u64).contains(count))
+                .ok_or_else(|| {
+                    invalid(format!(
+                        "for_each: 'concurrency' must be between 1 and {MAX_FOR_EACH_CONCURRENCY}"
+                    ))
+                })? as usize,
+        };
+        Ok(Self {
+            items: items.to_string(),
+            item_key: string_key(config, "item_key", "item")?,
+            index_key: string_key(config, "index_key", "index")?,
+            concurrency,
+            collect: match config.get("collect") {
+                None => None,
+                Some(Value::String(path)) => Some(path.clone()),
+                Some(_) => return Err(invalid("for_each: 'collect' must be a path")),
+            },
+            output_key: string_key(config, "output_key", "for_each_results")?,
+        })
+    }
+
+    /// The array to iterate, taken from a `{{context.*}}` placeholder or a
+    /// plain dot path.
+    pub fn resolve_items(&self, data: &Value) -> WorkflowResult<Vec<Value>> {
+        let items = if self.items.contains("{{") {
+            resolve_template_string(&self.items, data)
+        } else {
+            lookup_template_path(&self.items, data)
+                .cloned()
+                .unwrap_or(Value::Null)
+        };
+        let Value::Array(items) = items else {
+            return Err(WorkflowError::StepFailed(format!(
+                "for_each: '{}' is not an array",
+                self.items
+            )));
+        };
+        if items.len() > MAX_FOR_EACH_ITEMS {
+            return Err(WorkflowError::StepFailed(format!(
+                "for_each: {} items exceed the limit of {MAX_FOR_EACH_ITEMS}",
+                items.len()
+            )));
+        }
+        Ok(items)
+    }
+}
+
+/// `sub_workflow` step — runs another workflow of the same tenant as a
+/// child execution and waits for its result.
+///
+/// ```json
+/// {
+///   "workflow_id": "…",
+///   "input": { "order_id": "{{context.order.id}}" },
+///   "output_key": "fraud_check"
+/// }
+/// ```
+/// The child starts with `{"trigger": {...}, "input": <input>}` and returns
+/// whatever it leaves under `output`. When the child's `trigger_config`
+/// declares `input_schema` / `output_schema`, both sides are validated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubWorkflowConfig {
+    pub workflow_id: Uuid,
+    pub input: Value,
+    pub output_key: String,
+}
+
+impl SubWorkflowConfig {
+    pub fn parse(config: &Value) -> WorkflowResult<Self> {
+        let workflow_id = config
+            .get("workflow_id")
+            .and_then(Value::as_str)
+            .and_then(|id| id.parse::<Uuid>().ok())
+            .ok_or_else(|| invalid("sub_workflow: 'workflow_id' must be a UUID"))?;
+        let input = match config.get("input") {
+            None => Value::Object(Default::default()),
+            Some(input @ Value::Object(_)) => input.clone(),
+            Some(_) => return Err(invalid("sub_workflow: 'input' must be an object")),
+        };
+        Ok(Self {
+            workflow_id,
+            input,
+            output_key: string_key(config, "output_key", "sub_workflow")?,
+        })
+    }
+}
+
+/// Save-time validation for control-flow step configs.
+pub fn validate_control_config(step_type: &StepType, config: &Value) -> WorkflowResult<()> {
+    match step_type {
+        StepType::Branch => BranchConfig::parse(config).map(drop),
+        StepType::Parallel => ParallelConfig::parse(config).map(drop),
+        StepType::ForEach => ForEachConfig::parse(config).map(drop),
+        StepType::SubWorkflow => SubWorkflowConfig::parse(config).map(drop),
+        _ => Ok(()),
+    }
+}
+
+/// Checks that a child step may sit in `branch` of a parent of `parent_type`:
+/// `branch` children need one of the declared branches, `parallel` children
+/// name their lane, `for_each` children have no branch.
+pub fn validate_child_branch(
+    parent_type: &StepType,
+    parent_config: &Value,
+    branch: Option<&str>,
+) -> WorkflowResult<()> {
+    match (parent_type, branch) {
+        (StepType::Branch, Some(branch)) => {
+            let labels = BranchConfig::parse(parent_config)?.labels();
+            if labels.iter().any(|label| label == branch) {
+                Ok(())
+            } else {
+                Err(invalid(format!(
+                    "branch '{branch}' is not one of the parent's branches: {}",
+                    labels.join(", ")
+                )))
+            }
+        }
+        (StepType::Parallel, Some(branch)) => validate_label(branch).map(drop),
+        (StepType::ForEach, None) => Ok(()),
+        (StepType::ForEach, Some(_)) => Err(invalid("for_each children take no 'branch'")),
+        (StepType::Branch | StepType::Parallel, None) => Err(invalid(format!(
+            "children of a {parent_type} step require a 'branch'"
+        ))),
+        (parent_type, _) => Err(invalid(format!(
+            "{parent_type} steps cannot have child steps"
+        ))),
+    }
+}
+
+fn case_label(value: &Value) -> WorkflowResult<String> {
+    match value {
+        Value::String(label) => Ok(label.clone()),
+        Value::Number(number) => Ok(number.to_string()),
+        Value::Bool(flag) => Ok(flag.to_string()),
+        _ => Err(invalid(
+            "branch: cases must be strings, numbers or booleans",
+        )),
+    }
+}
+
+fn validate_label(label: &str) -> WorkflowResult<String> {
+    if label.trim().is_empty() || label.len() > MAX_BRANCH_LABEL_LEN {
+        return Err(invalid(format!(
+            "branch names must be 1-{MAX_BRANCH_LABEL_LEN} characters"
+        )));
+    }
+    Ok(label.to_string())
+}
+
+fn string_key(config: &Value, key: &str, default: &str) -> WorkflowResult<String> {
+    match config.get(key) {
+        None => Ok(default.to_string()),
+        Some(Value::String(value)) if !value.trim().is_empty() => Ok(value.clone()),
+        Some(_) => Err(invalid(format!("'{key}' must be a non-empty string"))),
+    }
+}
+
+fn invalid(message: impl Into<String>) -> WorkflowError {
+    WorkflowError::InvalidStepConfig(message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn branch_selects_if_and_switch_cases() {
+        let branch = BranchConfig::parse(
+            &json!({ "when": { "field": "order.status", "operator": "eq", "value": "paid" } }),
+        )
+        .unwrap();
+        assert_eq!(
+            branch
+                .select(&json!({ "order": { "status": "paid" } }))
+                .unwrap(),
+            THEN_BRANCH
+        );
+        assert_eq!(branch.select(&json!({})).unwrap(), ELSE_BRANCH);
+
+        let switch = BranchConfig::parse(
+            &json!({ "switch": "order.tier", "cases": ["gold", 2], "default": "other" }),
+        )
+        .unwrap();
+        assert_eq!(switch.labels(), vec!["gold", "2", "other"]);
+        assert_eq!(
+            switch.select(&json!({ "order": { "tier": 2 } })).unwrap(),
+            "2"
+        );
+        assert_eq!(
+            switch
+                .select(&json!({ "order": { "tier": "bronze" } }))
+                .unwrap(),
+            "other"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_control_configs() {
+        for (step_type, config) in [
+            (StepType::Branch, json!({})),
+            (StepType::Branch, json!({ "when": { "operator": "eq" } })),
+            (StepType::Branch, json!({ "switch": "a", "cases": [] })),
+            (StepType::Parallel, json!({ "join": 0 })),
+            (StepType::Parallel, json!({ "join": "most" })),
+            (
+                StepType::ForEach,
+                json!({ "items": "lines", "concurrency": 100 }),
+            ),
+            (StepType::SubWorkflow, json!({ "workflow_id": "nope" })),
+        ] {
+            assert!(
+                validate_control_config(&step_type, &config).is_err(),
+                "{step_type} accepted {config}"
+            );
+        }
+    }
+
+    #[test]
+    fn validates_child_branches_against_parent() {
+        let switch = json!({ "switch": "status", "cases": ["paid"] });
+        assert!(validate_child_branch(&StepType::Branch, &switch, Some("paid")).is_ok());
+        assert!(validate_child_branch(&StepType::Branch, &switch, Some(DEFAULT_CASE)).is_ok());
+        assert!(validate_child_branch(&StepType::Branch, &switch, Some("refunded")).is_err());
+        assert!(validate_child_branch(&StepType::Parallel, &json!({}), None).is_err());
+        assert!(validate_child_branch(&StepType::ForEach, &json!({}), Some("body")).is_err());
+        assert!(validate_child_branch(&StepType::Http, &json!({}), None).is_err());
+    }
+
+    #[test]
+    fn for_each_resolves_items_from_path_or_placeholder() {
+        let data = json!({ "order": { "lines": [1, 2] } });
+        let by_path = ForEachConfig::parse(&json!({ "items": "order.lines" })).unwrap();
+        assert_eq!(
+            by_path.resolve_items(&data).unwrap(),
+            vec![json!(1), json!(2)]
+        );
+        let by_template =
+            ForEachConfig::parse(&json!({ "items": "{{context.order.lines}}" })).unwrap();
+        assert_eq!(by_template.resolve_items(&data).unwrap().len(), 2);
+        assert!(ForEachConfig::parse(&json!({ "items": "order" }))
+            .unwrap()
+            .resolve_items(&data)
+            .is_err());
+    }
+}
@@ -1,23 +1,31 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use serde_json::Value;
 use tracing::info;
 
-use super::{StepContext, StepOutput, WorkflowStep};
+use super::{
+    resolve_templates, ResumeSignal, StepContext, StepOutput, StepSuspension, WorkflowStep,
+};
 use crate::error::{WorkflowError, WorkflowResult};
 
-/// Delay step — pauses execution for a specified number of milliseconds.
+/// Delay step — pauses execution for a duration or until a point in time.
 ///
-/// For short delays (< 60s) this uses tokio::time::sleep.
-/// Per architecture, longer delays should use scheduled events via EventTransport
-/// (planned for Phase 4).
+/// Short delays (up to 60s) sleep in-process. Longer ones suspend the
+/// execution durably: the engine persists its position and context, and the
+/// cron scheduler resumes it once the delay has passed, surviving restarts.
 ///
 /// Step config format:
 /// ```json
-/// { "delay_ms": 5000 }
+/// { "delay_ms": 86400000 }
+/// ```
+/// or
+/// ```json
+/// { "until": "{{context.cart.reminder_at}}" }
 /// ```
 pub struct DelayStep;
 
 const MAX_INLINE_DELAY_MS: u64 = 60_000; // 60 seconds
+const MAX_DURABLE_DELAY_MS: u64 = 366 * 24 * 60 * 60 * 1000; // one year
 
 #[async_trait]
 impl WorkflowStep for DelayStep {
@@ -26,25 +34,69 @@ impl WorkflowStep for DelayStep {
     }
 
     async fn execute(&self, config: &Value, context: StepContext) -> WorkflowResult<StepOutput> {
-        let delay_ms = config
-            .get("delay_ms")
-            .and_then(Value::as_u64)
-            .ok_or_else(|| WorkflowError::InvalidStepConfig("delay: missing 'delay_ms'".into()))?;
+        let now = Utc::now();
+        let delay_ms = match (config.get("delay_ms"), config.get("until")) {
+            (Some(delay_ms), None) => delay_ms.as_u64().ok_or_else(|| {
+                WorkflowError::InvalidStepConfig(
+                    "delay: 'delay_ms' must be a positive integer".into(),
+                )
+            })?,
+            (None, Some(until)) => {
+                let until = resolve_templates(until, &context.data);
+                let until = until
+                    .as_str()
+                    .and_then(|until| DateTime::parse_from_rfc3339(until).ok())
+                    .ok_or_else(|| {
+                        WorkflowError::StepFailed(format!(
+                            "delay: 'until' must resolve to an RFC 3339 timestamp, got {until}"
+                        ))
+                    })?;
+                (until.with_timezone(&Utc) - now).num_milliseconds().max(0) as u64
+            }
+            _ => {
+                return Err(WorkflowError::InvalidStepConfig(
+                    "delay: exactly one of 'delay_ms' or 'until' is required".into(),
+                ))
+            }
+        };
 
-        if delay_ms > MAX_INLINE_DELAY_MS {
+        if delay_ms > MAX_DURABLE_DELAY_MS {
             return Err(WorkflowError::InvalidStepConfig(format!(
-                "delay: delay_ms={delay_ms} exceeds max inline delay {MAX_INLINE_DELAY_MS}ms. \
-                 Use EventTransport-based scheduling for long delays."
+                "delay: delay_ms={delay_ms} exceeds max delay {MAX_DURABLE_DELAY_MS}ms"
             )));
         }
 
-        info!(delay_ms = delay_ms, "Executing delay step");
+        if delay_ms <= MAX_INLINE_DELAY_MS {
+            info!(delay_ms = delay_ms, "Executing delay step");
+            tokio::time::sleep(std::time::Duration::from_millis(delay_ms)).await;
+            return Ok(StepOutput::continue_with(
+                context,
+                serde_json::json!({ "delayed_ms": delay_ms }),
+            ));
+        }
 
-        tokio::time::sleep(std::time::Duration::from_millis(delay_ms)).await;
+        let resume_at = now + chrono::Duration::milliseconds(delay_ms as i64);
+        info!(delay_ms = delay_ms, resume_at = %resume_at, "Suspending execution for delay");
+        Ok(StepOutput::suspend_with(
+            context,
+            serde_json::json!({ "delayed_ms": delay_ms, "resume_at": resume_at }),
+            StepSuspension {
+                resume_at: Some(resume_at),
+                wait_for: None,
+                approval: None,
+            },
+        ))
+    }
 
+    async fn resume(
+        &self,
+        _config: &Value,
+        context: StepContext,
+        _signal: ResumeSignal,
+    ) -> WorkflowResult<StepOutput> {
         Ok(StepOutput::continue_with(
             context,
-            serde_json::json!({ "delayed_ms": delay_ms }),
+            serde_json::json!({ "resumed_at": Utc::now() }),
         ))
     }
 }
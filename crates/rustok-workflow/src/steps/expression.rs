@@ -0,0 +1,1202 @@
+//! Boolean expression language for conditions and trigger filters.
+//!
+//! ```text
+//! order.total >= 100 and order.status in ['paid', 'shipped']
+//! not exists(order.coupon) or len(order.lines) > 3
+//! event.payload.email matches '@example\.com$'
+//! date(order.created_at) < now() - days(7)
+//! ```
+//!
+//! Bare identifiers are dot paths into the execution context (`context.`
+//! prefix optional, array items by index: `order.lines.0.sku`). Literals are
+//! numbers, `'...'` / `"..."` strings, `true`, `false`, `null` and `[...]`
+//! lists. Operators, loosest first: `or` / `||`, `and` / `&&`, `not` / `!`,
+//! comparisons (`==`, `!=`, `<`, `<=`, `>`, `>=`, `in`, `not in`,
+//! `contains`, `matches`), `+` / `-`, `*` / `/` / `%`, unary `-`.
+//!
+//! Strings compare with dates as dates; `+` / `-` work on numbers, dates and
+//! durations (`days()`, `hours()`, `minutes()`) and `+` also concatenates
+//! strings. A missing path is `null`: arithmetic on `null` stays `null`,
+//! ordering comparisons with `null` are false and `null` is falsy.
+//!
+//! Expressions are parsed and type-checked by [`Expression::compile`] when
+//! the step or trigger is saved. Evaluation is side-effect free; `matches`
+//! takes a literal pattern compiled with the linear-time `regex` engine.
+
+use std::cmp::Ordering;
+
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use regex::{Regex, RegexBuilder};
+use serde_json::{Map, Value};
+
+use super::lookup_template_path;
+use super::transform::parse_date;
+
+/// Longest accepted expression source, in bytes.
+pub const MAX_EXPRESSION_LEN: usize = 2_000;
+/// Deepest accepted nesting of parentheses, lists, calls and prefix operators.
+const MAX_NESTING: usize = 32;
+const MAX_REGEX_SIZE: usize = 1 << 20;
+const KEYWORDS: [&str; 9] = [
+    "and", "or", "not", "in", "contains", "matches", "true", "false", "null",
+];
+const SYMBOLS: [&str; 19] = [
+    "==", "!=", "<=", ">=", "&&", "||", "<", ">", "!", "+", "-", "*", "/", "%", "(", ")", "[", "]",
+    ",",
+];
+
+/// A compiled boolean expression.
+#[derive(Debug, Clone)]
+pub struct Expression {
+    source: String,
+    root: Expr,
+}
+
+impl Expression {
+    /// Parses and type-checks `source`. Fails on syntax errors, unknown
+    /// functions, operands of the wrong type and non-boolean results.
+    pub fn compile(source: &str) -> Result<Self, String> {
+        if source.trim().is_empty() {
+            return Err("expression is empty".to_string());
+        }
+        if source.len() > MAX_EXPRESSION_LEN {
+            return Err(format!(
+                "expression is longer than {MAX_EXPRESSION_LEN} bytes"
+            ));
+        }
+
+        let mut parser = Parser {
+            tokens: tokenize(source)?,
+            pos: 0,
+            depth: 0,
+        };
+        let root = parser.parse_expr()?;
+        if *parser.peek() != Token::End {
+            return Err(parser.unexpected());
+        }
+
+        check_boolean(&root, "expression")?;
+        Ok(Self {
+            source: source.to_string(),
+            root,
+        })
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Evaluates the expression against the context data; `null` counts as
+    /// `false`.
+    pub fn evaluate(&self, data: &Value) -> Result<bool, String> {
+        let scope = Scope {
+            data,
+            now: Utc::now(),
+        };
+        truthy(scope.eval(&self.root)?)
+    }
+}
+
+// ── Lexer ──────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    String(String),
+    Ident(String),
+    Symbol(&'static str),
+    End,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Self::Number(number) => format!("number {number}"),
+            Self::String(text) => format!("string '{text}'"),
+            Self::Ident(name) => format!("'{name}'"),
+            Self::Symbol(symbol) => format!("'{symbol}'"),
+            Self::End => "end of expression".to_string(),
+        }
+    }
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, String> {
+    let chars: Vec<(usize, char)> = source.char_indices().collect();
+    let char_at = |index: usize| chars.get(index).map(|(_, c)| *c);
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while let Some(&(offset, c)) = chars.get(i) {
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit() {
+            let mut end = i;
+            while char_at(end).is_some_and(|c| c.is_ascii_digit()) {
+                end += 1;
+            }
+            if char_at(end) == Some('.') && char_at(end + 1).is_some_and(|c| c.is_ascii_digit()) {
+                end += 1;
+                while char_at(end).is_some_and(|c| c.is_ascii_digit()) {
+                    end += 1;
+                }
+            }
+            let text: String = chars[i..end].iter().map(|(_, c)| c).collect();
+            let number = text
+                .parse::<f64>()
+                .map_err(|_| format!("invalid number '{text}' at offset {offset}"))?;
+            tokens.push((Token::Number(number), offset));
+            i = end;
+        } else if c.is_alphabetic() || c == '_' {
+            let mut end = i;
+            loop {
+                while char_at(end).is_some_and(is_ident_char) {
+                    end += 1;
+                }
+                if char_at(end) == Some('.') && char_at(end + 1).is_some_and(is_ident_char) {
+                    end += 1;
+                } else {
+                    break;
+                }
+            }
+            let name: String = chars[i..end].iter().map(|(_, c)| c).collect();
+            tokens.push((Token::Ident(name), offset));
+            i = end;
+        } else if c == '\'' || c == '"' {
+            let mut text = String::new();
+            let mut end = i + 1;
+            loop {
+                let Some(next) = char_at(end) else {
+                    return Err(format!("unterminated string at offset {offset}"));
+                };
+                end += 1;
+                if next == c {
+                    break;
+                }
+                if next == '\\' {
+                    let escaped = char_at(end)
+                        .ok_or_else(|| format!("unterminated string at offset {offset}"))?;
+                    end += 1;
+                    // Unknown escapes are kept so regex patterns like '\.' survive
+                    match escaped {
+                        'n' => text.push('\n'),
+                        't' => text.push('\t'),
+                        '\\' | '\'' | '"' => text.push(escaped),
+                        other => {
+                            text.push('\\');
+                            text.push(other);
+                        }
+                    }
+                } else {
+                    text.push(next);
+                }
+            }
+            tokens.push((Token::String(text), offset));
+            i = end;
+        } else {
+            let symbol = SYMBOLS
+                .iter()
+                .find(|symbol| source[offset..].starts_with(**symbol))
+                .ok_or_else(|| format!("unexpected character '{c}' at offset {offset}"))?;
+            tokens.push((Token::Symbol(symbol), offset));
+            i += symbol.len();
+        }
+    }
+
+    tokens.push((Token::End, source.len()));
+    Ok(tokens)
+}
+
+// ── Parser ─────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArithOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+impl ArithOp {
+    fn symbol(self) -> &'static str {
+        match self {
+            Self::Add => "+",
+            Self::Sub => "-",
+            Self::Mul => "*",
+            Self::Div => "/",
+            Self::Rem => "%",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Literal(Val),
+    Path(String),
+    List(Vec<Expr>),
+    Not(Box<Expr>),
+    Neg(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Compare(CompareOp, Box<Expr>, Box<Expr>),
+    /// `item in collection`, `collection contains item`
+    In {
+        item: Box<Expr>,
+        collection: Box<Expr>,
+        negated: bool,
+    },
+    Matches(Box<Expr>, Regex),
+    Arith(ArithOp, Box<Expr>, Box<Expr>),
+    Call(Func, Vec<Expr>),
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.pos].0
+    }
+
+    fn peek_at(&self, ahead: usize) -> &Token {
+        let index = (self.pos + ahead).min(self.tokens.len() - 1);
+        &self.tokens[index].0
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.tokens[self.pos].0.clone();
+        if self.pos + 1 < self.tokens.len() {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn unexpected(&self) -> String {
+        let (token, offset) = &self.tokens[self.pos];
+        format!("unexpected {} at offset {offset}", token.describe())
+    }
+
+    fn eat_symbol(&mut self, symbol: &str) -> bool {
+        let found = matches!(self.peek(), Token::Symbol(found) if *found == symbol);
+        if found {
+            self.advance();
+        }
+        found
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = matches!(self.peek(), Token::Ident(name) if name == keyword);
+        if found {
+            self.advance();
+        }
+        found
+    }
+
+    fn expect_symbol(&mut self, symbol: &str) -> Result<(), String> {
+        if self.eat_symbol(symbol) {
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn nested<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<T, String>,
+    ) -> Result<T, String> {
+        self.depth += 1;
+        if self.depth > MAX_NESTING {
+            return Err(format!(
+                "expression is nested deeper than {MAX_NESTING} levels"
+            ));
+        }
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn parse_expr(&mut self) -> Result<Expr, String> {
+        let mut left = self.parse_and()?;
+        while self.eat_symbol("||") || self.eat_keyword("or") {
+            let right = self.parse_and()?;
+            left = Expr::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, String> {
+        let mut left = self.parse_not()?;
+        while self.eat_symbol("&&") || self.eat_keyword("and") {
+            let right = self.parse_not()?;
+            left = Expr::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_not(&mut self) -> Result<Expr, String> {
+        if self.eat_symbol("!") || self.eat_keyword("not") {
+            let inner = self.nested(Self::parse_not)?;
+            return Ok(Expr::Not(Box::new(inner)));
+        }
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expr, String> {
+        let left = self.parse_additive()?;
+        let compare = match self.peek() {
+            Token::Symbol("==") => Some(CompareOp::Eq),
+            Token::Symbol("!=") => Some(CompareOp::Ne),
+            Token::Symbol("<") => Some(CompareOp::Lt),
+            Token::Symbol("<=") => Some(CompareOp::Le),
+            Token::Symbol(">") => Some(CompareOp::Gt),
+            Token::Symbol(">=") => Some(CompareOp::Ge),
+            _ => None,
+        };
+        if let Some(op) = compare {
+            self.advance();
+            let right = self.parse_additive()?;
+            return Ok(Expr::Compare(op, Box::new(left), Box::new(right)));
+        }
+
+        let keyword = match self.peek() {
+            Token::Ident(name) => name.clone(),
+            _ => return Ok(left),
+        };
+        match keyword.as_str() {
+            "in" => {
+                self.advance();
+                let collection = self.parse_additive()?;
+                Ok(Expr::In {
+                    item: Box::new(left),
+                    collection: Box::new(collection),
+                    negated: false,
+                })
+            }
+            "not" if matches!(self.peek_at(1), Token::Ident(name) if name == "in") => {
+                self.advance();
+                self.advance();
+                let collection = self.parse_additive()?;
+                Ok(Expr::In {
+                    item: Box::new(left),
+                    collection: Box::new(collection),
+                    negated: true,
+                })
+            }
+            "contains" => {
+                self.advance();
+                let item = self.parse_additive()?;
+                Ok(Expr::In {
+                    item: Box::new(item),
+                    collection: Box::new(left),
+                    negated: false,
+                })
+            }
+            "matches" => {
+                self.advance();
+                let Expr::Literal(Val::String(pattern)) = self.parse_additive()? else {
+                    return Err("'matches' takes a string literal pattern".to_string());
+                };
+                let regex = RegexBuilder::new(&pattern)
+                    .size_limit(MAX_REGEX_SIZE)
+                    .build()
+                    .map_err(|err| format!("invalid pattern '{pattern}': {err}"))?;
+                Ok(Expr::Matches(Box::new(left), regex))
+            }
+            _ => Ok(left),
+        }
+    }
+
+    fn parse_additive(&mut self) -> Result<Expr, String> {
+        let mut left = self.parse_multiplicative()?;
+        loop {
+            let op = if self.eat_symbol("+") {
+                ArithOp::Add
+            } else if self.eat_symbol("-") {
+                ArithOp::Sub
+            } else {
+                return Ok(left);
+            };
+            let right = self.parse_multiplicative()?;
+            left = Expr::Arith(op, Box::new(left), Box::new(right));
+        }
+    }
+
+    fn parse_multiplicative(&mut self) -> Result<Expr, String> {
+        let mut left = self.parse_unary()?;
+        loop {
+            let op = if self.eat_symbol("*") {
+                ArithOp::Mul
+            } else if self.eat_symbol("/") {
+                ArithOp::Div
+            } else if self.eat_symbol("%") {
+                ArithOp::Rem
+            } else {
+                return Ok(left);
+            };
+            let right = self.parse_unary()?;
+            left = Expr::Arith(op, Box::new(left), Box::new(right));
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, String> {
+        if self.eat_symbol("-") {
+            let inner = self.nested(Self::parse_unary)?;
+            return Ok(Expr::Neg(Box::new(inner)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, String> {
+        match self.peek().clone() {
+            Token::Number(number) => {
+                self.advance();
+                Ok(Expr::Literal(Val::Number(number)))
+            }
+            Token::String(text) => {
+                self.advance();
+                Ok(Expr::Literal(Val::String(text)))
+            }
+            Token::Ident(name) => {
+                let literal = match name.as_str() {
+                    "true" => Some(Val::Bool(true)),
+                    "false" => Some(Val::Bool(false)),
+                    "null" => Some(Val::Null),
+                    keyword if KEYWORDS.contains(&keyword) => return Err(self.unexpected()),
+                    _ => None,
+                };
+                self.advance();
+                if let Some(literal) = literal {
+                    return Ok(Expr::Literal(literal));
+                }
+                if !self.eat_symbol("(") {
+                    return Ok(Expr::Path(name));
+                }
+                let func =
+                    Func::parse(&name).ok_or_else(|| format!("unknown function '{name}'"))?;
+                let args = self.nested(|parser| parser.parse_items(")"))?;
+                let arity = func.params().len();
+                if args.len() != arity {
+                    return Err(format!(
+                        "{}() takes {arity} argument(s), got {}",
+                        func.name(),
+                        args.len()
+                    ));
+                }
+                Ok(Expr::Call(func, args))
+            }
+            Token::Symbol("(") => {
+                self.advance();
+                let inner = self.nested(Self::parse_expr)?;
+                self.expect_symbol(")")?;
+                Ok(inner)
+            }
+            Token::Symbol("[") => {
+                self.advance();
+                Ok(Expr::List(self.nested(|parser| parser.parse_items("]"))?))
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    /// Comma-separated expressions up to and including `close`.
+    fn parse_items(&mut self, close: &str) -> Result<Vec<Expr>, String> {
+        let mut items = Vec::new();
+        if self.eat_symbol(close) {
+            return Ok(items);
+        }
+        loop {
+            items.push(self.parse_expr()?);
+            if !self.eat_symbol(",") {
+                self.expect_symbol(close)?;
+                return Ok(items);
+            }
+        }
+    }
+}
+
+// ── Functions ──────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Func {
+    Len,
+    Now,
+    Date,
+    Days,
+    Hours,
+    Minutes,
+    Lower,
+    Upper,
+    Trim,
+    Number,
+    String,
+    Exists,
+    Abs,
+    Round,
+}
+
+impl Func {
+    const ALL: [Func; 14] = [
+        Self::Len,
+        Self::Now,
+        Self::Date,
+        Self::Days,
+        Self::Hours,
+        Self::Minutes,
+        Self::Lower,
+        Self::Upper,
+        Self::Trim,
+        Self::Number,
+        Self::String,
+        Self::Exists,
+        Self::Abs,
+        Self::Round,
+    ];
+
+    fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|func| func.name() == name)
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Len => "len",
+            Self::Now => "now",
+            Self::Date => "date",
+            Self::Days => "days",
+            Self::Hours => "hours",
+            Self::Minutes => "minutes",
+            Self::Lower => "lower",
+            Self::Upper => "upper",
+            Self::Trim => "trim",
+            Self::Number => "number",
+            Self::String => "string",
+            Self::Exists => "exists",
+            Self::Abs => "abs",
+            Self::Round => "round",
+        }
+    }
+
+    /// Accepted types of each parameter; `Ty::Any` accepts every type.
+    fn params(self) -> &'static [&'static [Ty]] {
+        match self {
+            Self::Now => &[],
+            Self::Len => &[&[Ty::String, Ty::List, Ty::Object, Ty::Null]],
+            Self::Date => &[&[Ty::String, Ty::Number, Ty::Date, Ty::Null]],
+            Self::Days | Self::Hours | Self::Minutes => &[&[Ty::Number]],
+            Self::Lower | Self::Upper | Self::Trim => &[&[Ty::String, Ty::Null]],
+            Self::Number => &[&[Ty::String, Ty::Number, Ty::Null]],
+            Self::Abs | Self::Round => &[&[Ty::Number, Ty::Null]],
+            Self::String | Self::Exists => &[&[Ty::Any]],
+        }
+    }
+
+    fn result(self) -> Ty {
+        match self {
+            Self::Len | Self::Number | Self::Abs | Self::Round => Ty::Number,
+            Self::Now | Self::Date => Ty::Date,
+            Self::Days | Self::Hours | Self::Minutes => Ty::Duration,
+            Self::Lower | Self::Upper | Self::Trim | Self::String => Ty::String,
+            Self::Exists => Ty::Bool,
+        }
+    }
+
+    fn call(self, mut args: Vec<Val>, now: DateTime<Utc>) -> Result<Val, String> {
+        let arg = if args.is_empty() {
+            Val::Null
+        } else {
+            args.swap_remove(0)
+        };
+        let mismatch = |arg: &Val| format!("{}() does not accept {}", self.name(), arg.ty().name());
+        Ok(match (self, arg) {
+            (Self::Now, _) => Val::Date(now),
+            (Self::Exists, arg) => Val::Bool(arg != Val::Null),
+            (Self::String, arg) => Val::String(arg.display()),
+            (Self::Len, Val::Null) => Val::Number(0.0),
+            (_, Val::Null) => Val::Null,
+            (Self::Len, Val::String(text)) => Val::Number(text.chars().count() as f64),
+            (Self::Len, Val::List(items)) => Val::Number(items.len() as f64),
+            (Self::Len, Val::Object(map)) => Val::Number(map.len() as f64),
+            (Self::Date, arg) => Val::Date(
+                as_date(&arg).ok_or_else(|| format!("date(): {} is not a date", arg.display()))?,
+            ),
+            (Self::Days, Val::Number(n)) => duration(n, 86_400_000.0)?,
+            (Self::Hours, Val::Number(n)) => duration(n, 3_600_000.0)?,
+            (Self::Minutes, Val::Number(n)) => duration(n, 60_000.0)?,
+            (Self::Lower, Val::String(text)) => Val::String(text.to_lowercase()),
+            (Self::Upper, Val::String(text)) => Val::String(text.to_uppercase()),
+            (Self::Trim, Val::String(text)) => Val::String(text.trim().to_string()),
+            (Self::Number, Val::Number(n)) => Val::Number(n),
+            (Self::Number, Val::String(text)) => Val::Number(
+                text.trim()
+                    .parse()
+                    .map_err(|_| format!("number(): '{text}' is not a number"))?,
+            ),
+            (Self::Abs, Val::Number(n)) => Val::Number(n.abs()),
+            (Self::Round, Val::Number(n)) => Val::Number(n.round()),
+            (_, arg) => return Err(mismatch(&arg)),
+        })
+    }
+}
+
+fn duration(amount: f64, unit_ms: f64) -> Result<Val, String> {
+    let millis = amount * unit_ms;
+    if !millis.is_finite() || millis.abs() >= i64::MAX as f64 {
+        return Err("duration out of range".to_string());
+    }
+    Duration::try_milliseconds(millis as i64)
+        .map(Val::Duration)
+        .ok_or_else(|| "duration out of range".to_string())
+}
+
+// ── Type check ─────────────────────────────────────────────────────────────────
+
+/// Static type of an expression; paths are `Any` until evaluated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Ty {
+    Any,
+    Null,
+    Bool,
+    Number,
+    String,
+    Date,
+    Duration,
+    List,
+    Object,
+}
+
+impl Ty {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Any => "any",
+            Self::Null => "null",
+            Self::Bool => "boolean",
+            Self::Number => "number",
+            Self::String => "string",
+            Self::Date => "date",
+            Self::Duration => "duration",
+            Self::List => "list",
+            Self::Object => "object",
+        }
+    }
+
+    fn is_one_of(self, accepted: &[Ty]) -> bool {
+        self == Ty::Any || accepted.contains(&Ty::Any) || accepted.contains(&self)
+    }
+}
+
+/// Checks that `expr` can produce a boolean (or `null`).
+fn check_boolean(expr: &Expr, context: &str) -> Result<(), String> {
+    let ty = match (check(expr)?, expr) {
+        // Arithmetic over paths is typed `any` but never boolean
+        (Ty::Any, Expr::Arith(..) | Expr::Neg(..)) => Ty::Number,
+        (ty, _) => ty,
+    };
+    if ty.is_one_of(&[Ty::Bool, Ty::Null]) {
+        Ok(())
+    } else {
+        Err(format!("{context} must be boolean, found {}", ty.name()))
+    }
+}
+
+fn check(expr: &Expr) -> Result<Ty, String> {
+    let operand = |expr: &Expr, accepted: &[Ty], context: &str| -> Result<Ty, String> {
+        let ty = check(expr)?;
+        if ty.is_one_of(accepted) {
+            Ok(ty)
+        } else {
+            Err(format!("{context} does not accept {}", ty.name()))
+        }
+    };
+
+    match expr {
+        Expr::Literal(value) => Ok(value.ty()),
+        Expr::Path(_) => Ok(Ty::Any),
+        Expr::List(items) => {
+            for item in items {
+                check(item)?;
+            }
+            Ok(Ty::List)
+        }
+        Expr::Not(inner) => {
+            check_boolean(inner, "operand of 'not'")?;
+            Ok(Ty::Bool)
+        }
+        Expr::And(left, right) | Expr::Or(left, right) => {
+            let context = if matches!(expr, Expr::And(..)) {
+                "operand of 'and'"
+            } else {
+                "operand of 'or'"
+            };
+            check_boolean(left, context)?;
+            check_boolean(right, context)?;
+            Ok(Ty::Bool)
+        }
+        Expr::Neg(inner) => match operand(inner, &[Ty::Number, Ty::Duration, Ty::Null], "'-'")? {
+            Ty::Null => Ok(Ty::Null),
+            ty => Ok(ty),
+        },
+        Expr::Compare(op, left, right) => {
+            let (left, right) = (check(left)?, check(right)?);
+            let known = left != Ty::Any && right != Ty::Any;
+            if matches!(op, CompareOp::Eq | CompareOp::Ne) {
+                let dates = [Ty::Date, Ty::String];
+                let comparable = left == right
+                    || left == Ty::Null
+                    || right == Ty::Null
+                    || (dates.contains(&left) && dates.contains(&right));
+                if known && !comparable {
+                    return Err(format!(
+                        "comparing {} with {} is always false",
+                        left.name(),
+                        right.name()
+                    ));
+                }
+            } else {
+                let orderable = [Ty::Number, Ty::String, Ty::Date, Ty::Duration];
+                for ty in [left, right] {
+                    if !ty.is_one_of(&orderable) {
+                        return Err(format!("cannot order {}", ty.name()));
+                    }
+                }
+                let dates = [Ty::Date, Ty::String];
+                if known && left != right && !(dates.contains(&left) && dates.contains(&right)) {
+                    return Err(format!(
+                        "cannot compare {} with {}",
+                        left.name(),
+                        right.name()
+                    ));
+                }
+            }
+            Ok(Ty::Bool)
+        }
+        Expr::In {
+            item, collection, ..
+        } => {
+            let collection = operand(
+                collection,
+                &[Ty::List, Ty::String, Ty::Object, Ty::Null],
+                "'in' / 'contains'",
+            )?;
+            if matches!(collection, Ty::String | Ty::Object) {
+                operand(item, &[Ty::String, Ty::Null], "'in' / 'contains'")?;
+            } else {
+                check(item)?;
+            }
+            Ok(Ty::Bool)
+        }
+        Expr::Matches(inner, _) => {
+            operand(inner, &[Ty::String, Ty::Null], "'matches'")?;
+            Ok(Ty::Bool)
+        }
+        Expr::Arith(op, left, right) => {
+            let (left, right) = (check(left)?, check(right)?);
+            arith_type(*op, left, right).ok_or_else(|| {
+                format!(
+                    "cannot apply '{}' to {} and {}",
+                    op.symbol(),
+                    left.name(),
+                    right.name()
+                )
+            })
+        }
+        Expr::Call(func, args) => {
+            for (arg, accepted) in args.iter().zip(func.params()) {
+                operand(arg, accepted, &format!("{}()", func.name()))?;
+            }
+            Ok(func.result())
+        }
+    }
+}
+
+fn arith_type(op: ArithOp, left: Ty, right: Ty) -> Option<Ty> {
+    use Ty::*;
+    if [left, right].iter().any(|ty| matches!(ty, Any | Null)) {
+        let allowed: &[Ty] = match op {
+            ArithOp::Add => &[Number, String, Date, Duration],
+            ArithOp::Sub => &[Number, Date, Duration],
+            _ => &[Number],
+        };
+        return [left, right]
+            .iter()
+            .all(|ty| matches!(ty, Any | Null) || allowed.contains(ty))
+            .then_some(Any);
+    }
+    match (op, left, right) {
+        (_, Number, Number) => Some(Number),
+        (ArithOp::Add, String, String) => Some(String),
+        (ArithOp::Add | ArithOp::Sub, Date, Duration) | (ArithOp::Add, Duration, Date) => {
+            Some(Date)
+        }
+        (ArithOp::Sub, Date, Date) => Some(Duration),
+        (ArithOp::Add | ArithOp::Sub, Duration, Duration) => Some(Duration),
+        _ => None,
+    }
+}
+
+// ── Evaluation ─────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+enum Val {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Date(DateTime<Utc>),
+    Duration(Duration),
+    List(Vec<Val>),
+    Object(Map<String, Value>),
+}
+
+impl Val {
+    fn from_json(value: &Value) -> Self {
+        match value {
+            Value::Null => Self::Null,
+            Value::Bool(flag) => Self::Bool(*flag),
+            Value::Number(number) => number.as_f64().map_or(Self::Null, Self::Number),
+            Value::String(text) => Self::String(text.clone()),
+            Value::Array(items) => Self::List(items.iter().map(Self::from_json).collect()),
+            Value::Object(map) => Self::Object(map.clone()),
+        }
+    }
+
+    fn ty(&self) -> Ty {
+        match self {
+            Self::Null => Ty::Null,
+            Self::Bool(_) => Ty::Bool,
+            Self::Number(_) => Ty::Number,
+            Self::String(_) => Ty::String,
+            Self::Date(_) => Ty::Date,
+            Self::Duration(_) => Ty::Duration,
+            Self::List(_) => Ty::List,
+            Self::Object(_) => Ty::Object,
+        }
+    }
+
+    fn display(&self) -> String {
+        match self {
+            Self::Null => String::new(),
+            Self::Bool(flag) => flag.to_string(),
+            Self::Number(number) if number.fract() == 0.0 && number.abs() < 1e15 => {
+                (*number as i64).to_string()
+            }
+            Self::Number(number) => number.to_string(),
+            Self::String(text) => text.clone(),
+            Self::Date(date) => date.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+            Self::Duration(duration) => format!("{}s", duration.num_seconds()),
+            Self::List(items) => format!(
+                "[{}]",
+                items
+                    .iter()
+                    .map(Self::display)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Self::Object(map) => Value::Object(map.clone()).to_string(),
+        }
+    }
+}
+
+fn as_date(value: &Val) -> Option<DateTime<Utc>> {
+    match value {
+        Val::Date(date) => Some(*date),
+        Val::String(text) => parse_date(&Value::String(text.clone())),
+        Val::Number(number) => DateTime::from_timestamp(*number as i64, 0),
+        _ => None,
+    }
+}
+
+fn truthy(value: Val) -> Result<bool, String> {
+    match value {
+        Val::Bool(flag) => Ok(flag),
+        Val::Null => Ok(false),
+        other => Err(format!("expected boolean, found {}", other.ty().name())),
+    }
+}
+
+fn equals(left: &Val, right: &Val) -> bool {
+    match (left, right) {
+        (Val::Date(date), Val::String(_)) | (Val::String(_), Val::Date(date)) => {
+            as_date(if matches!(left, Val::Date(_)) {
+                right
+            } else {
+                left
+            }) == Some(*date)
+        }
+        (Val::List(left), Val::List(right)) => {
+            left.len() == right.len() && left.iter().zip(right).all(|(l, r)| equals(l, r))
+        }
+        (left, right) => left == right,
+    }
+}
+
+/// `None` when either side is `null`.
+fn order(left: &Val, right: &Val) -> Result<Option<Ordering>, String> {
+    let not_a_date = |value: &Val| format!("{} is not a date", value.display());
+    Ok(match (left, right) {
+        (Val::Null, _) | (_, Val::Null) => None,
+        (Val::Number(left), Val::Number(right)) => left.partial_cmp(right),
+        (Val::String(left), Val::String(right)) => Some(left.cmp(right)),
+        (Val::Duration(left), Val::Duration(right)) => Some(left.cmp(right)),
+        (Val::Date(date), other) => {
+            Some(date.cmp(&as_date(other).ok_or_else(|| not_a_date(other))?))
+        }
+        (other, Val::Date(date)) => {
+            Some(as_date(other).ok_or_else(|| not_a_date(other))?.cmp(date))
+        }
+        (left, right) => {
+            return Err(format!(
+                "cannot compare {} with {}",
+                left.ty().name(),
+                right.ty().name()
+            ))
+        }
+    })
+}
+
+fn contains(collection: &Val, item: &Val) -> Result<bool, String> {
+    match (collection, item) {
+        (Val::Null, _) => Ok(false),
+        (Val::List(items), item) => Ok(items.iter().any(|candidate| equals(candidate, item))),
+        (Val::String(_) | Val::Object(_), Val::Null) => Ok(false),
+        (Val::String(text), Val::String(part)) => Ok(text.contains(part.as_str())),
+        (Val::Object(map), Val::String(key)) => Ok(map.contains_key(key)),
+        (collection, item) => Err(format!(
+            "cannot look up {} in {}",
+            item.ty().name(),
+            collection.ty().name()
+        )),
+    }
+}
+
+fn arith(op: ArithOp, left: Val, right: Val) -> Result<Val, String> {
+    if left == Val::Null || right == Val::Null {
+        return Ok(Val::Null);
+    }
+    // Strings next to a duration or date are read as dates
+    let (left, right) = match (&left, &right) {
+        (Val::String(_), Val::Duration(_) | Val::Date(_)) => match as_date(&left) {
+            Some(date) => (Val::Date(date), right),
+            None => (left, right),
+        },
+        (Val::Date(_), Val::String(_)) => match as_date(&right) {
+            Some(date) => (left, Val::Date(date)),
+            None => (left, right),
+        },
+        _ => (left, right),
+    };
+    let out_of_range = || "date out of range".to_string();
+
+    Ok(match (op, left, right) {
+        (ArithOp::Add, Val::Number(l), Val::Number(r)) => Val::Number(l + r),
+        (ArithOp::Sub, Val::Number(l), Val::Number(r)) => Val::Number(l - r),
+        (ArithOp::Mul, Val::Number(l), Val::Number(r)) => Val::Number(l * r),
+        (ArithOp::Div | ArithOp::Rem, Val::Number(_), Val::Number(0.0)) => {
+            return Err("division by zero".to_string())
+        }
+        (ArithOp::Div, Val::Number(l), Val::Number(r)) => Val::Number(l / r),
+        (ArithOp::Rem, Val::Number(l), Val::Number(r)) => Val::Number(l % r),
+        (ArithOp::Add, Val::String(l), Val::String(r)) => Val::String(l + &r),
+        (ArithOp::Add, Val::Date(date), Val::Duration(delta))
+        | (ArithOp::Add, Val::Duration(delta), Val::Date(date)) => {
+            Val::Date(date.checked_add_signed(delta).ok_or_else(out_of_range)?)
+        }
+        (ArithOp::Sub, Val::Date(date), Val::Duration(delta)) => {
+            Val::Date(date.checked_sub_signed(delta).ok_or_else(out_of_range)?)
+        }
+        (ArithOp::Sub, Val::Date(l), Val::Date(r)) => Val::Duration(l - r),
+        (ArithOp::Add, Val::Duration(l), Val::Duration(r)) => {
+            Val::Duration(l.checked_add(&r).ok_or_else(out_of_range)?)
+        }
+        (ArithOp::Sub, Val::Duration(l), Val::Duration(r)) => {
+            Val::Duration(l.checked_sub(&r).ok_or_else(out_of_range)?)
+        }
+        (op, left, right) => {
+            return Err(format!(
+                "cannot apply '{}' to {} and {}",
+                op.symbol(),
+                left.ty().name(),
+                right.ty().name()
+            ))
+        }
+    })
+}
+
+struct Scope<'a> {
+    data: &'a Value,
+    now: DateTime<Utc>,
+}
+
+impl Scope<'_> {
+    fn eval(&self, expr: &Expr) -> Result<Val, String> {
+        Ok(match expr {
+            Expr::Literal(value) => value.clone(),
+            Expr::Path(path) => {
+                lookup_template_path(path, self.data).map_or(Val::Null, Val::from_json)
+            }
+            Expr::List(items) => Val::List(
+                items
+                    .iter()
+                    .map(|item| self.eval(item))
+                    .collect::<Result<_, _>>()?,
+            ),
+            Expr::Not(inner) => Val::Bool(!truthy(self.eval(inner)?)?),
+            Expr::And(left, right) => {
+                Val::Bool(truthy(self.eval(left)?)? && truthy(self.eval(right)?)?)
+            }
+            Expr::Or(left, right) => {
+                Val::Bool(truthy(self.eval(left)?)? || truthy(self.eval(right)?)?)
+            }
+            Expr::Neg(inner) => match self.eval(inner)? {
+                Val::Null => Val::Null,
+                Val::Number(number) => Val::Number(-number),
+                Val::Duration(duration) => Val::Duration(-duration),
+                other => return Err(format!("cannot negate {}", other.ty().name())),
+            },
+            Expr::Compare(op, left, right) => {
+                let (left, right) = (self.eval(left)?, self.eval(right)?);
+                Val::Bool(match op {
+                    CompareOp::Eq => equals(&left, &right),
+                    CompareOp::Ne => !equals(&left, &right),
+                    CompareOp::Lt => order(&left, &right)? == Some(Ordering::Less),
+                    CompareOp::Le => {
+                        matches!(
+                            order(&left, &right)?,
+                            Some(Ordering::Less | Ordering::Equal)
+                        )
+                    }
+                    CompareOp::Gt => order(&left, &right)? == Some(Ordering::Greater),
+                    CompareOp::Ge => matches!(
+                        order(&left, &right)?,
+                        Some(Ordering::Greater | Ordering::Equal)
+                    ),
+                })
+            }
+            Expr::In {
+                item,
+                collection,
+                negated,
+            } => {
+                let found = contains(&self.eval(collection)?, &self.eval(item)?)?;
+                Val::Bool(found != *negated)
+            }
+            Expr::Matches(inner, regex) => match self.eval(inner)? {
+                Val::Null => Val::Bool(false),
+                Val::String(text) => Val::Bool(regex.is_match(&text)),
+                other => return Err(format!("'matches' does not accept {}", other.ty().name())),
+            },
+            Expr::Arith(op, left, right) => arith(*op, self.eval(left)?, self.eval(right)?)?,
+            Expr::Call(func, args) => {
+                let args = args
+                    .iter()
+                    .map(|arg| self.eval(arg))
+                    .collect::<Result<Vec<_>, _>>()?;
+                func.call(args, self.now)?
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn eval(source: &str, data: &Value) -> Result<bool, String> {
+        Expression::compile(source)?.evaluate(data)
+    }
+
+    #[test]
+    fn evaluates_comparisons_collections_and_logic() {
+        let data = json!({
+            "order": {
+                "total": 120.5,
+                "status": "paid",
+                "email": "Ann@Example.com",
+                "tags": ["vip", "gift"],
+                "lines": [{ "sku": "a-1" }, { "sku": "b-2" }],
+                "created_at": "2020-01-01T00:00:00Z"
+            }
+        });
+
+        for source in [
+            "order.total >= 100 and order.status in ['paid', 'shipped']",
+            "context.order.total * 2 - 1 == 240",
+            "order.tags contains 'vip' && !(order.status == 'refunded')",
+            "'gift' in order.tags and 'pai' in order.status and 'total' in order",
+            "order.status not in ['refunded', 'cancelled']",
+            "lower(order.email) matches '^ann@example\\.com$'",
+            "len(order.lines) == 2 and order.lines.1.sku == 'b-2'",
+            "order.created_at < now() - days(7)",
+            "date(order.created_at) + hours(1) > '2020-01-01T00:30:00Z'",
+            "not exists(order.coupon) or order.coupon.code == 'X'",
+            "order.missing > 5 or order.missing == null",
+        ] {
+            assert_eq!(eval(source, &data), Ok(true), "{source}");
+        }
+
+        for source in [
+            "order.total < 100",
+            "order.missing > 5",
+            "order.missing and true",
+            "order.status matches '^ship'",
+        ] {
+            assert_eq!(eval(source, &data), Ok(false), "{source}");
+        }
+    }
+
+    #[test]
+    fn rejects_syntax_and_type_errors_at_compile_time() {
+        for source in [
+            "",
+            "order.total >",
+            "order.total > 1 )",
+            "(order.total > 1",
+            "'unterminated",
+            "order.total # 1",
+            "frobnicate(order.total)",
+            "len()",
+            "order.total + 1",
+            "1 > 'one'",
+            "'a' == 1",
+            "true > false",
+            "len(5) > 1",
+            "days('x') > days(1)",
+            "order.status matches order.pattern",
+            "order.status matches '('",
+            "5 in 'text'",
+            "'a' + 1 == 'a1'",
+        ] {
+            assert!(
+                Expression::compile(source).is_err(),
+                "{source} should be rejected"
+            );
+        }
+        assert!(Expression::compile(&"(".repeat(40)).is_err());
+        assert!(Expression::compile(&"a".repeat(MAX_EXPRESSION_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn reports_type_mismatches_found_at_run_time() {
+        let data = json!({ "name": "Ann", "count": 3 });
+        assert!(eval("name > 3", &data).is_err());
+        assert!(eval("count / 0 > 1", &data).is_err());
+        assert!(eval("name and true", &data).is_err());
+        assert!(eval("date(name) > now()", &data).is_err());
+    }
+}
@@ -1,23 +1,34 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use serde_json::Value;
+use uuid::Uuid;
 
 use crate::error::WorkflowResult;
 
 pub mod action;
 pub mod alloy_script;
+pub mod approval;
 pub mod condition;
+pub mod control;
 pub mod delay;
 pub mod emit_event;
+pub mod expression;
 pub mod http;
 pub mod notify;
+pub mod transform;
+pub mod wait_for_event;
 
-pub use action::ActionStep;
+pub use action::{workflow_action_principal, ActionStep, WorkflowActionRuntime};
 pub use alloy_script::{AlloyScriptStep, ScriptRunner};
+pub use approval::ApprovalStep;
 pub use condition::ConditionStep;
 pub use delay::DelayStep;
 pub use emit_event::EmitEventStep;
+pub use expression::Expression;
 pub use http::HttpStep;
 pub use notify::{NotificationSender, NotifyStep};
+pub use transform::TransformStep;
+pub use wait_for_event::WaitForEventStep;
 
 /// Context passed between workflow steps during execution.
 /// Steps can read from and write to the context.
@@ -25,11 +36,24 @@ pub use notify::{NotificationSender, NotifyStep};
 pub struct StepContext {
     /// Data available to steps — starts with trigger event payload, enriched by each step
     pub data: Value,
+    /// Tenant the execution belongs to; set by the engine
+    pub tenant_id: Option<Uuid>,
+    /// Current execution id; set by the engine
+    pub execution_id: Option<Uuid>,
 }
 
 impl StepContext {
     pub fn new(data: Value) -> Self {
-        Self { data }
+        Self {
+            data,
+            ..Default::default()
+        }
+    }
+
+    pub fn for_execution(mut self, tenant_id: Uuid, execution_id: Uuid) -> Self {
+        self.tenant_id = Some(tenant_id);
+        self.execution_id = Some(execution_id);
+        self
     }
 
     pub fn get(&self, key: &str) -> Option<&Value> {
@@ -43,6 +67,68 @@ impl StepContext {
     }
 }
 
+/// Resolves `{{context.path}}` placeholders in string leaves of `value`.
+///
+/// A string that is a single placeholder takes the referenced JSON value
+/// (keeping its type, `null` when missing); placeholders embedded in longer
+/// strings are interpolated as text.
+pub(crate) fn resolve_templates(value: &Value, data: &Value) -> Value {
+    match value {
+        Value::String(text) => resolve_template_string(text, data),
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| resolve_templates(item, data))
+                .collect(),
+        ),
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(key, item)| (key.clone(), resolve_templates(item, data)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+pub(crate) fn resolve_template_string(text: &str, data: &Value) -> Value {
+    if rustok_core::json_schema::is_template_placeholder(text) {
+        let trimmed = text.trim();
+        let path = &trimmed[2..trimmed.len() - 2];
+        return lookup_template_path(path, data)
+            .cloned()
+            .unwrap_or(Value::Null);
+    }
+
+    let mut rendered = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+        rendered.push_str(&rest[..start]);
+        let path = &rest[start + 2..start + end];
+        match lookup_template_path(path, data) {
+            Some(Value::String(value)) => rendered.push_str(value),
+            Some(Value::Null) | None => {}
+            Some(value) => rendered.push_str(&value.to_string()),
+        }
+        rest = &rest[start + end + 2..];
+    }
+    rendered.push_str(rest);
+    Value::String(rendered)
+}
+
+pub(crate) fn lookup_template_path<'a>(path: &str, data: &'a Value) -> Option<&'a Value> {
+    let path = path.trim();
+    let path = path.strip_prefix("context.").unwrap_or(path);
+    path.split('.')
+        .try_fold(data, |current, key| match current {
+            Value::Array(items) => key.parse::<usize>().ok().and_then(|index| items.get(index)),
+            _ => current.get(key),
+        })
+}
+
 /// Output produced by a single step execution.
 #[derive(Debug, Clone)]
 pub struct StepOutput {
@@ -52,6 +138,75 @@ pub struct StepOutput {
     pub data: Value,
     /// Whether execution should continue to the next step
     pub should_continue: bool,
+    /// Park the execution durably instead of moving on; the engine calls
+    /// [`WorkflowStep::resume`] on this step once the suspension ends.
+    pub suspension: Option<StepSuspension>,
+}
+
+/// Why and until when a step parks its execution.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepSuspension {
+    /// Resume at this instant: the end of a delay or the timeout of a wait.
+    pub resume_at: Option<DateTime<Utc>>,
+    /// Resume early when a matching event arrives.
+    pub wait_for: Option<EventWait>,
+    /// Open (or reassign) a human approval task; a decision resumes early.
+    pub approval: Option<ApprovalRequest>,
+}
+
+/// Domain event a suspended step is waiting for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventWait {
+    pub event_type: String,
+    /// Event paths (`payload.order_id`) and the values they must equal.
+    pub correlation: serde_json::Map<String, Value>,
+}
+
+impl EventWait {
+    /// Whether `event` (shaped like the trigger context `event` object)
+    /// satisfies every correlation entry.
+    pub fn matches(&self, event: &Value) -> bool {
+        event_matches(&self.correlation, event)
+    }
+}
+
+pub(crate) fn event_matches(correlation: &serde_json::Map<String, Value>, event: &Value) -> bool {
+    correlation
+        .iter()
+        .all(|(path, expected)| lookup_template_path(path, event) == Some(expected))
+}
+
+/// Human approval task a suspended step is waiting on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApprovalRequest {
+    pub title: String,
+    pub description: Option<String>,
+    /// Users allowed to decide.
+    pub assignee_user_ids: Vec<Uuid>,
+    /// RBAC roles allowed to decide.
+    pub assignee_roles: Vec<String>,
+    /// How many times the task has been escalated.
+    pub escalation_level: i32,
+}
+
+/// A person's verdict on an approval task.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApprovalDecision {
+    pub approved: bool,
+    pub decided_by: Uuid,
+    pub comment: Option<String>,
+    pub decided_at: DateTime<Utc>,
+}
+
+/// What ended a suspension.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResumeSignal {
+    /// `resume_at` was reached.
+    Timer,
+    /// The awaited event arrived; holds the event object.
+    Event(Value),
+    /// An assignee approved or rejected the approval task.
+    Decision(ApprovalDecision),
 }
 
 impl StepOutput {
@@ -60,6 +215,7 @@ impl StepOutput {
             context,
             data,
             should_continue: true,
+            suspension: None,
         }
     }
 
@@ -68,6 +224,16 @@ impl StepOutput {
             context,
             data,
             should_continue: false,
+            suspension: None,
+        }
+    }
+
+    pub fn suspend_with(context: StepContext, data: Value, suspension: StepSuspension) -> Self {
+        Self {
+            context,
+            data,
+            should_continue: true,
+            suspension: Some(suspension),
         }
     }
 }
@@ -80,4 +246,46 @@ pub trait WorkflowStep: Send + Sync {
 
     /// Execute the step, returning updated context or an error.
     async fn execute(&self, config: &Value, context: StepContext) -> WorkflowResult<StepOutput>;
+
+    /// Finish a step that suspended its execution. The default continues
+    /// with the context unchanged.
+    async fn resume(
+        &self,
+        _config: &Value,
+        context: StepContext,
+        _signal: ResumeSignal,
+    ) -> WorkflowResult<StepOutput> {
+        Ok(StepOutput::continue_with(context, Value::Null))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn resolves_whole_and_embedded_placeholders() {
+        let data = json!({ "order": { "id": 42, "lines": [{ "sku": "A-1" }] }, "name": "Ann" });
+        let resolved = resolve_templates(
+            &json!({
+                "order_id": "{{context.order.id}}",
+                "sku": "{{ order.lines.0.sku }}",
+                "greeting": "Hi {{context.name}}, order #{{context.order.id}}{{context.missing}}",
+                "missing": "{{context.missing}}",
+                "fixed": 7
+            }),
+            &data,
+        );
+        assert_eq!(
+            resolved,
+            json!({
+                "order_id": 42,
+                "sku": "A-1",
+                "greeting": "Hi Ann, order #42",
+                "missing": null,
+                "fixed": 7
+            })
+        );
+    }
 }
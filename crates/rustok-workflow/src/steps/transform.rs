@@ -0,0 +1,978 @@
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Duration, NaiveDate, SecondsFormat, Utc};
+use serde::Deserialize;
+use serde_json::{Map, Number, Value};
+use tracing::debug;
+
+use super::{lookup_template_path, resolve_template_string, StepContext, StepOutput, WorkflowStep};
+use crate::error::{WorkflowError, WorkflowResult};
+
+/// Maximum nesting of `fields` / `map` mappings in one config.
+const MAX_MAPPING_DEPTH: usize = 8;
+/// Maximum number of array items a single `filter` / `map` may process.
+const MAX_ARRAY_ITEMS: usize = 10_000;
+
+/// Transform step — declaratively reshapes the execution context.
+///
+/// Config format:
+/// ```json
+/// {
+///   "rename": { "event.customer_email": "email" },
+///   "fields": {
+///     "customer.email": { "path": "event.email", "default": "", "apply": ["trim", "lowercase"] },
+///     "customer.greeting": { "template": "Hi {{event.first_name}}" },
+///     "order.total": { "path": "event.total", "apply": [{ "fn": "round", "digits": 2 }] },
+///     "order.skus": {
+///       "path": "event.lines",
+///       "filter": [{ "field": "qty", "operator": "gt", "value": 0 }],
+///       "map": { "path": "sku", "apply": ["uppercase"] }
+///     },
+///     "order.paid_at": { "path": "event.paid_at", "apply": [{ "fn": "date_format", "format": "%Y-%m-%d" }] },
+///     "source": { "value": "checkout" }
+///   },
+///   "remove": ["event.card"]
+/// }
+/// ```
+///
+/// Every mapping has exactly one source: `path`, `value`, `template` or nested
+/// `fields`. `default` replaces a missing or `null` result, `filter` / `map`
+/// operate on arrays (with each item as the scope), `apply` runs functions in
+/// order. All mappings read the context as it was before the step; then
+/// `rename`, the mapped `fields` and `remove` are applied to it, in that order.
+///
+/// The step is pure: no I/O, no clock, bounded nesting and array sizes.
+pub struct TransformStep;
+
+impl TransformStep {
+    /// Save-time validation: the config parses, every mapping has a single
+    /// source and every function and argument is known.
+    pub fn validate_config(config: &Value) -> WorkflowResult<()> {
+        TransformPlan::compile(config).map(|_| ())
+    }
+}
+
+#[async_trait]
+impl WorkflowStep for TransformStep {
+    fn step_type(&self) -> &'static str {
+        "transform"
+    }
+
+    async fn execute(&self, config: &Value, context: StepContext) -> WorkflowResult<StepOutput> {
+        let plan = TransformPlan::compile(config)?;
+        let mut new_context = context;
+        let output = plan.apply(&mut new_context.data)?;
+        debug!(fields = plan.fields.len(), "Transform applied");
+        Ok(StepOutput::continue_with(new_context, output))
+    }
+}
+
+// ── Config ─────────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TransformConfig {
+    #[serde(default)]
+    rename: BTreeMap<String, String>,
+    #[serde(default)]
+    fields: BTreeMap<String, MappingConfig>,
+    #[serde(default)]
+    remove: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MappingConfig {
+    path: Option<String>,
+    value: Option<Value>,
+    template: Option<String>,
+    fields: Option<BTreeMap<String, MappingConfig>>,
+    default: Option<Value>,
+    #[serde(default)]
+    filter: Vec<FilterConfig>,
+    map: Option<Box<MappingConfig>>,
+    #[serde(default)]
+    apply: Vec<FunctionConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FilterConfig {
+    field: Option<String>,
+    #[serde(default = "default_operator")]
+    operator: String,
+    value: Option<Value>,
+}
+
+fn default_operator() -> String {
+    "eq".to_string()
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum FunctionConfig {
+    Name(String),
+    Call(Map<String, Value>),
+}
+
+// ── Compiled plan ──────────────────────────────────────────────────────────────
+
+struct TransformPlan {
+    rename: Vec<(String, String)>,
+    fields: Vec<(String, Mapping)>,
+    remove: Vec<String>,
+}
+
+enum Source {
+    Path(String),
+    Literal(Value),
+    Template(String),
+    Object(Vec<(String, Mapping)>),
+}
+
+struct Mapping {
+    source: Source,
+    default: Option<Value>,
+    filter: Vec<Filter>,
+    map: Option<Box<Mapping>>,
+    apply: Vec<Function>,
+}
+
+struct Filter {
+    field: Option<String>,
+    operator: FilterOperator,
+    value: Value,
+}
+
+#[derive(Clone, Copy)]
+enum FilterOperator {
+    Eq,
+    Ne,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    Exists,
+    NotExists,
+    Contains,
+    In,
+}
+
+enum Function {
+    Lowercase,
+    Uppercase,
+    Trim,
+    ToString,
+    Split(String),
+    Join(String),
+    Replace { from: String, to: String },
+    Substring { start: usize, length: Option<usize> },
+    Length,
+    ToNumber,
+    Round(u32),
+    Floor,
+    Ceil,
+    Abs,
+    Add(f64),
+    Multiply(f64),
+    ToDate,
+    DateFormat(String),
+    DateAdd(Duration),
+    First,
+    Last,
+    Sum,
+    Unique,
+    Compact,
+}
+
+fn invalid(message: impl std::fmt::Display) -> WorkflowError {
+    WorkflowError::InvalidStepConfig(format!("transform: {message}"))
+}
+
+fn failed(message: impl std::fmt::Display) -> WorkflowError {
+    WorkflowError::StepFailed(format!("transform: {message}"))
+}
+
+impl TransformPlan {
+    fn compile(config: &Value) -> WorkflowResult<Self> {
+        let config: TransformConfig = serde_json::from_value(config.clone()).map_err(invalid)?;
+        if config.fields.is_empty() && config.rename.is_empty() && config.remove.is_empty() {
+            return Err(invalid("config must define 'fields', 'rename' or 'remove'"));
+        }
+
+        for path in config
+            .rename
+            .iter()
+            .flat_map(|(from, to)| [from, to])
+            .chain(config.fields.keys())
+            .chain(config.remove.iter())
+        {
+            check_target_path(path)?;
+        }
+
+        Ok(Self {
+            rename: config.rename.into_iter().collect(),
+            fields: compile_fields(config.fields, 1)?,
+            remove: config.remove,
+        })
+    }
+
+    fn apply(&self, data: &mut Value) -> WorkflowResult<Value> {
+        let snapshot = data.clone();
+        let mut mapped = Vec::with_capacity(self.fields.len());
+        for (target, mapping) in &self.fields {
+            let value = mapping
+                .evaluate(&snapshot)
+                .map_err(|error| failed(format!("field '{target}': {error}")))?;
+            mapped.push((target.as_str(), value));
+        }
+
+        if !data.is_object() {
+            *data = Value::Object(Map::new());
+        }
+        let mut renamed = 0;
+        for (from, to) in &self.rename {
+            if let Some(value) = remove_path(data, from) {
+                set_path(data, to, value);
+                renamed += 1;
+            }
+        }
+        for (target, value) in mapped {
+            set_path(data, target, value);
+        }
+        let removed = self
+            .remove
+            .iter()
+            .filter(|path| remove_path(data, path).is_some())
+            .count();
+
+        Ok(serde_json::json!({
+            "fields": self.fields.iter().map(|(target, _)| target).collect::<Vec<_>>(),
+            "renamed": renamed,
+            "removed": removed,
+        }))
+    }
+}
+
+fn check_target_path(path: &str) -> WorkflowResult<()> {
+    if path.split('.').any(|segment| segment.trim().is_empty()) {
+        return Err(invalid(format!("invalid path '{path}'")));
+    }
+    Ok(())
+}
+
+fn compile_fields(
+    fields: BTreeMap<String, MappingConfig>,
+    depth: usize,
+) -> WorkflowResult<Vec<(String, Mapping)>> {
+    fields
+        .into_iter()
+        .map(|(target, config)| {
+            check_target_path(&target)?;
+            let mapping = compile_mapping(config, depth)
+                .map_err(|error| invalid(format!("field '{target}': {}", strip(error))))?;
+            Ok((target, mapping))
+        })
+        .collect()
+}
+
+/// Drops the `transform: ` prefix of nested errors so paths read naturally.
+fn strip(error: WorkflowError) -> String {
+    match error {
+        WorkflowError::InvalidStepConfig(message) => message
+            .strip_prefix("transform: ")
+            .map(str::to_string)
+            .unwrap_or(message),
+        other => other.to_string(),
+    }
+}
+
+fn compile_mapping(config: MappingConfig, depth: usize) -> WorkflowResult<Mapping> {
+    if depth > MAX_MAPPING_DEPTH {
+        return Err(invalid(format!(
+            "mappings are nested deeper than {MAX_MAPPING_DEPTH} levels"
+        )));
+    }
+
+    let sources = [
+        config.path.is_some(),
+        config.value.is_some(),
+        config.template.is_some(),
+        config.fields.is_some(),
+    ]
+    .into_iter()
+    .filter(|present| *present)
+    .count();
+    if sources != 1 {
+        return Err(invalid(
+            "exactly one of 'path', 'value', 'template' or 'fields' is required",
+        ));
+    }
+
+    let source = if let Some(path) = config.path {
+        Source::Path(path)
+    } else if let Some(value) = config.value {
+        Source::Literal(value)
+    } else if let Some(template) = config.template {
+        Source::Template(template)
+    } else {
+        Source::Object(compile_fields(
+            config.fields.unwrap_or_default(),
+            depth + 1,
+        )?)
+    };
+
+    Ok(Mapping {
+        source,
+        default: config.default,
+        filter: config
+            .filter
+            .into_iter()
+            .map(compile_filter)
+            .collect::<WorkflowResult<_>>()?,
+        map: config
+            .map
+            .map(|map| compile_mapping(*map, depth + 1).map(Box::new))
+            .transpose()?,
+        apply: config
+            .apply
+            .into_iter()
+            .map(compile_function)
+            .collect::<WorkflowResult<_>>()?,
+    })
+}
+
+fn compile_filter(config: FilterConfig) -> WorkflowResult<Filter> {
+    let operator = match config.operator.as_str() {
+        "eq" => FilterOperator::Eq,
+        "ne" => FilterOperator::Ne,
+        "gt" => FilterOperator::Gt,
+        "gte" => FilterOperator::Gte,
+        "lt" => FilterOperator::Lt,
+        "lte" => FilterOperator::Lte,
+        "exists" => FilterOperator::Exists,
+        "not_exists" => FilterOperator::NotExists,
+        "contains" => FilterOperator::Contains,
+        "in" => FilterOperator::In,
+        other => return Err(invalid(format!("unknown filter operator '{other}'"))),
+    };
+    let value = config.value.unwrap_or(Value::Null);
+    if matches!(operator, FilterOperator::In) && !value.is_array() {
+        return Err(invalid("filter operator 'in' requires an array 'value'"));
+    }
+    Ok(Filter {
+        field: config.field,
+        operator,
+        value,
+    })
+}
+
+fn compile_function(config: FunctionConfig) -> WorkflowResult<Function> {
+    let (name, args) = match config {
+        FunctionConfig::Name(name) => (name, Map::new()),
+        FunctionConfig::Call(mut args) => {
+            let name = args
+                .remove("fn")
+                .and_then(|name| name.as_str().map(str::to_string))
+                .ok_or_else(|| invalid("function call requires 'fn'"))?;
+            (name, args)
+        }
+    };
+    let string_arg = |key: &str| -> WorkflowResult<String> {
+        args.get(key)
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .ok_or_else(|| invalid(format!("function '{name}' requires string '{key}'")))
+    };
+    let number_arg = |key: &str| -> WorkflowResult<f64> {
+        args.get(key)
+            .and_then(Value::as_f64)
+            .ok_or_else(|| invalid(format!("function '{name}' requires number '{key}'")))
+    };
+    let count_arg = |key: &str| -> WorkflowResult<Option<u64>> {
+        match args.get(key) {
+            None => Ok(None),
+            Some(value) => value.as_u64().map(Some).ok_or_else(|| {
+                invalid(format!(
+                    "function '{name}' requires non-negative integer '{key}'"
+                ))
+            }),
+        }
+    };
+    let int_arg = |key: &str| -> WorkflowResult<i64> {
+        match args.get(key) {
+            None => Ok(0),
+            Some(value) => value
+                .as_i64()
+                .ok_or_else(|| invalid(format!("function '{name}' requires integer '{key}'"))),
+        }
+    };
+
+    let function = match name.as_str() {
+        "lowercase" => Function::Lowercase,
+        "uppercase" => Function::Uppercase,
+        "trim" => Function::Trim,
+        "to_string" => Function::ToString,
+        "split" => Function::Split(string_arg("separator")?),
+        "join" => Function::Join(string_arg("separator").unwrap_or_default()),
+        "replace" => {
+            let from = string_arg("from")?;
+            if from.is_empty() {
+                return Err(invalid("function 'replace' requires non-empty 'from'"));
+            }
+            Function::Replace {
+                from,
+                to: string_arg("to")?,
+            }
+        }
+        "substring" => Function::Substring {
+            start: count_arg("start")?.unwrap_or(0) as usize,
+            length: count_arg("length")?.map(|length| length as usize),
+        },
+        "length" => Function::Length,
+        "to_number" => Function::ToNumber,
+        "round" => Function::Round(count_arg("digits")?.unwrap_or(0).min(12) as u32),
+        "floor" => Function::Floor,
+        "ceil" => Function::Ceil,
+        "abs" => Function::Abs,
+        "add" => Function::Add(number_arg("value")?),
+        "multiply" => Function::Multiply(number_arg("value")?),
+        "to_date" => Function::ToDate,
+        "date_format" => {
+            let format = string_arg("format")?;
+            if StrftimeItems::new(&format).any(|item| matches!(item, Item::Error)) {
+                return Err(invalid(format!("invalid date format '{format}'")));
+            }
+            Function::DateFormat(format)
+        }
+        "date_add" => {
+            let (days, hours, minutes, seconds) = (
+                int_arg("days")?,
+                int_arg("hours")?,
+                int_arg("minutes")?,
+                int_arg("seconds")?,
+            );
+            let offset = Duration::try_days(days)
+                .zip(Duration::try_hours(hours))
+                .zip(Duration::try_minutes(minutes))
+                .zip(Duration::try_seconds(seconds))
+                .and_then(|(((days, hours), minutes), seconds)| {
+                    days.checked_add(&hours)?
+                        .checked_add(&minutes)?
+                        .checked_add(&seconds)
+                })
+                .ok_or_else(|| invalid("function 'date_add' offset is out of range"))?;
+            Function::DateAdd(offset)
+        }
+        "first" => Function::First,
+        "last" => Function::Last,
+        "sum" => Function::Sum,
+        "unique" => Function::Unique,
+        "compact" => Function::Compact,
+        other => return Err(invalid(format!("unknown function '{other}'"))),
+    };
+    Ok(function)
+}
+
+// ── Evaluation ─────────────────────────────────────────────────────────────────
+
+impl Mapping {
+    fn evaluate(&self, scope: &Value) -> Result<Value, String> {
+        let mut value = match &self.source {
+            Source::Path(path) => lookup_scope(path, scope).cloned().unwrap_or(Value::Null),
+            Source::Literal(value) => value.clone(),
+            Source::Template(template) => resolve_template_string(template, scope),
+            Source::Object(fields) => {
+                let mut object = Value::Object(Map::new());
+                for (target, mapping) in fields {
+                    let value = mapping
+                        .evaluate(scope)
+                        .map_err(|error| format!("{target}: {error}"))?;
+                    set_path(&mut object, target, value);
+                }
+                object
+            }
+        };
+
+        if value.is_null() {
+            if let Some(default) = &self.default {
+                value = default.clone();
+            }
+        }
+
+        if !self.filter.is_empty() || self.map.is_some() {
+            if let Value::Array(items) = value {
+                if items.len() > MAX_ARRAY_ITEMS {
+                    return Err(format!("array exceeds {MAX_ARRAY_ITEMS} items"));
+                }
+                let mut result = Vec::with_capacity(items.len());
+                for item in items {
+                    if !self.filter.iter().all(|filter| filter.matches(&item)) {
+                        continue;
+                    }
+                    result.push(match &self.map {
+                        Some(map) => map.evaluate(&item)?,
+                        None => item,
+                    });
+                }
+                value = Value::Array(result);
+            } else if !value.is_null() {
+                return Err("'filter' and 'map' require an array".to_string());
+            }
+        }
+
+        for function in &self.apply {
+            value = function.call(value)?;
+        }
+        Ok(value)
+    }
+}
+
+/// Resolves `path` inside `scope`; `$` is the scope itself.
+fn lookup_scope<'a>(path: &str, scope: &'a Value) -> Option<&'a Value> {
+    if path.trim() == "$" {
+        Some(scope)
+    } else {
+        lookup_template_path(path, scope)
+    }
+}
+
+impl Filter {
+    fn matches(&self, item: &Value) -> bool {
+        let actual = match &self.field {
+            Some(field) => lookup_scope(field, item),
+            None => Some(item),
+        };
+        match self.operator {
+            FilterOperator::Exists => actual.is_some_and(|value| !value.is_null()),
+            FilterOperator::NotExists => actual.is_none_or(Value::is_null),
+            FilterOperator::Eq => actual.unwrap_or(&Value::Null) == &self.value,
+            FilterOperator::Ne => actual.unwrap_or(&Value::Null) != &self.value,
+            FilterOperator::Gt => compare(actual, &self.value).is_some_and(|ord| ord.is_gt()),
+            FilterOperator::Gte => compare(actual, &self.value).is_some_and(|ord| ord.is_ge()),
+            FilterOperator::Lt => compare(actual, &self.value).is_some_and(|ord| ord.is_lt()),
+            FilterOperator::Lte => compare(actual, &self.value).is_some_and(|ord| ord.is_le()),
+            FilterOperator::Contains => match (actual, &self.value) {
+                (Some(Value::String(text)), Value::String(needle)) => {
+                    text.contains(needle.as_str())
+                }
+                (Some(Value::Array(items)), needle) => items.contains(needle),
+                _ => false,
+            },
+            FilterOperator::In => self
+                .value
+                .as_array()
+                .is_some_and(|allowed| allowed.contains(actual.unwrap_or(&Value::Null))),
+        }
+    }
+}
+
+fn compare(actual: Option<&Value>, expected: &Value) -> Option<std::cmp::Ordering> {
+    match (actual?, expected) {
+        (Value::Number(left), Value::Number(right)) => left.as_f64()?.partial_cmp(&right.as_f64()?),
+        (Value::String(left), Value::String(right)) => Some(left.cmp(right)),
+        _ => None,
+    }
+}
+
+impl Function {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Lowercase => "lowercase",
+            Self::Uppercase => "uppercase",
+            Self::Trim => "trim",
+            Self::ToString => "to_string",
+            Self::Split(_) => "split",
+            Self::Join(_) => "join",
+            Self::Replace { .. } => "replace",
+            Self::Substring { .. } => "substring",
+            Self::Length => "length",
+            Self::ToNumber => "to_number",
+            Self::Round(_) => "round",
+            Self::Floor => "floor",
+            Self::Ceil => "ceil",
+            Self::Abs => "abs",
+            Self::Add(_) => "add",
+            Self::Multiply(_) => "multiply",
+            Self::ToDate => "to_date",
+            Self::DateFormat(_) => "date_format",
+            Self::DateAdd(_) => "date_add",
+            Self::First => "first",
+            Self::Last => "last",
+            Self::Sum => "sum",
+            Self::Unique => "unique",
+            Self::Compact => "compact",
+        }
+    }
+
+    /// Applies the function; `null` passes through every function unchanged.
+    fn call(&self, value: Value) -> Result<Value, String> {
+        if value.is_null() {
+            return Ok(value);
+        }
+        let mismatch = |expected: &str| {
+            format!(
+                "function '{}' expects {expected}, got {}",
+                self.name(),
+                type_name(&value)
+            )
+        };
+
+        let result = match self {
+            Self::Lowercase => Value::String(
+                as_str(&value)
+                    .ok_or_else(|| mismatch("string"))?
+                    .to_lowercase(),
+            ),
+            Self::Uppercase => Value::String(
+                as_str(&value)
+                    .ok_or_else(|| mismatch("string"))?
+                    .to_uppercase(),
+            ),
+            Self::Trim => Value::String(
+                as_str(&value)
+                    .ok_or_else(|| mismatch("string"))?
+                    .trim()
+                    .to_string(),
+            ),
+            Self::ToString => Value::String(stringify(&value)),
+            Self::Split(separator) => Value::Array(
+                as_str(&value)
+                    .ok_or_else(|| mismatch("string"))?
+                    .split(separator.as_str())
+                    .map(|part| Value::String(part.to_string()))
+                    .collect(),
+            ),
+            Self::Join(separator) => Value::String(
+                value
+                    .as_array()
+                    .ok_or_else(|| mismatch("array"))?
+                    .iter()
+                    .filter(|item| !item.is_null())
+                    .map(stringify)
+                    .collect::<Vec<_>>()
+                    .join(separator),
+            ),
+            Self::Replace { from, to } => Value::String(
+                as_str(&value)
+                    .ok_or_else(|| mismatch("string"))?
+                    .replace(from.as_str(), to),
+            ),
+            Self::Substring { start, length } => {
+                let chars = as_str(&value)
+                    .ok_or_else(|| mismatch("string"))?
+                    .chars()
+                    .skip(*start);
+                Value::String(match length {
+                    Some(length) => chars.take(*length).collect(),
+                    None => chars.collect(),
+                })
+            }
+            Self::Length => Value::from(match &value {
+                Value::String(text) => text.chars().count(),
+                Value::Array(items) => items.len(),
+                Value::Object(fields) => fields.len(),
+                _ => return Err(mismatch("string, array or object")),
+            }),
+            Self::ToNumber => match &value {
+                Value::Number(_) => value,
+                Value::String(text) => number_value(
+                    text.trim()
+                        .parse::<f64>()
+                        .map_err(|_| format!("function 'to_number' cannot parse '{text}'"))?,
+                ),
+                Value::Bool(flag) => Value::from(u8::from(*flag)),
+                _ => return Err(mismatch("number, string or boolean")),
+            },
+            Self::Round(digits) => {
+                let factor = 10f64.powi(*digits as i32);
+                number_value(
+                    (as_f64(&value).ok_or_else(|| mismatch("number"))? * factor).round() / factor,
+                )
+            }
+            Self::Floor => number_value(as_f64(&value).ok_or_else(|| mismatch("number"))?.floor()),
+            Self::Ceil => number_value(as_f64(&value).ok_or_else(|| mismatch("number"))?.ceil()),
+            Self::Abs => number_value(as_f64(&value).ok_or_else(|| mismatch("number"))?.abs()),
+            Self::Add(operand) => {
+                number_value(as_f64(&value).ok_or_else(|| mismatch("number"))? + operand)
+            }
+            Self::Multiply(operand) => {
+                number_value(as_f64(&value).ok_or_else(|| mismatch("number"))? * operand)
+            }
+            Self::ToDate => date_value(parse_date(&value).ok_or_else(|| mismatch("date"))?),
+            Self::DateFormat(format) => Value::String(
+                parse_date(&value)
+                    .ok_or_else(|| mismatch("date"))?
+                    .format(format)
+                    .to_string(),
+            ),
+            Self::DateAdd(offset) => date_value(
+                parse_date(&value)
+                    .ok_or_else(|| mismatch("date"))?
+                    .checked_add_signed(*offset)
+                    .ok_or_else(|| "function 'date_add' overflowed".to_string())?,
+            ),
+            Self::First => value
+                .as_array()
+                .ok_or_else(|| mismatch("array"))?
+                .first()
+                .cloned()
+                .unwrap_or(Value::Null),
+            Self::Last => value
+                .as_array()
+                .ok_or_else(|| mismatch("array"))?
+                .last()
+                .cloned()
+                .unwrap_or(Value::Null),
+            Self::Sum => {
+                let items = value.as_array().ok_or_else(|| mismatch("array"))?;
+                let mut total = 0.0;
+                for item in items.iter().filter(|item| !item.is_null()) {
+                    total += as_f64(item).ok_or_else(|| {
+                        format!("function 'sum' expects numbers, got {}", type_name(item))
+                    })?;
+                }
+                number_value(total)
+            }
+            Self::Unique => {
+                let items = value.as_array().ok_or_else(|| mismatch("array"))?;
+                let mut unique: Vec<Value> = Vec::with_capacity(items.len());
+                for item in items {
+                    if !unique.contains(item) {
+                        unique.push(item.clone());
+                    }
+                }
+                Value::Array(unique)
+            }
+            Self::Compact => Value::Array(
+                value
+                    .as_array()
+                    .ok_or_else(|| mismatch("array"))?
+                    .iter()
+                    .filter(|item| !item.is_null())
+                    .cloned()
+                    .collect(),
+            ),
+        };
+        Ok(result)
+    }
+}
+
+fn as_str(value: &Value) -> Option<&str> {
+    value.as_str()
+}
+
+/// Numbers and numeric strings.
+fn as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(text) => text.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+/// Integral results stay integers so `{"qty": 2}` does not become `2.0`.
+fn number_value(number: f64) -> Value {
+    if number.fract() == 0.0 && number.abs() < i64::MAX as f64 {
+        Value::from(number as i64)
+    } else {
+        Number::from_f64(number).map_or(Value::Null, Value::Number)
+    }
+}
+
+fn stringify(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// RFC 3339 timestamps, `YYYY-MM-DD` dates and unix seconds.
+pub(super) fn parse_date(value: &Value) -> Option<DateTime<Utc>> {
+    match value {
+        Value::String(text) => DateTime::parse_from_rfc3339(text.trim())
+            .map(|date| date.with_timezone(&Utc))
+            .ok()
+            .or_else(|| {
+                NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d")
+                    .ok()
+                    .and_then(|date| date.and_hms_opt(0, 0, 0))
+                    .map(|date| date.and_utc())
+            }),
+        Value::Number(number) => DateTime::from_timestamp(number.as_i64()?, 0),
+        _ => None,
+    }
+}
+
+fn date_value(date: DateTime<Utc>) -> Value {
+    Value::String(date.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Writes `value` at a dot path, creating (or replacing non-object) parents.
+fn set_path(data: &mut Value, path: &str, value: Value) {
+    let mut current = data;
+    let mut segments = path.split('.').peekable();
+    while let Some(segment) = segments.next() {
+        if !current.is_object() {
+            *current = Value::Object(Map::new());
+        }
+        let Value::Object(map) = current else {
+            return;
+        };
+        if segments.peek().is_none() {
+            map.insert(segment.to_string(), value);
+            return;
+        }
+        current = map
+            .entry(segment.to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+    }
+}
+
+/// Removes and returns the value at a dot path.
+fn remove_path(data: &mut Value, path: &str) -> Option<Value> {
+    let (parent, key) = match path.rsplit_once('.') {
+        Some((parent, key)) => (
+            parent
+                .split('.')
+                .try_fold(data, |current, segment| current.get_mut(segment))?,
+            key,
+        ),
+        None => (data, path),
+    };
+    parent.as_object_mut()?.remove(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    async fn run(config: Value, data: Value) -> WorkflowResult<Value> {
+        TransformStep
+            .execute(&config, StepContext::new(data))
+            .await
+            .map(|output| output.context.data)
+    }
+
+    #[tokio::test]
+    async fn maps_renames_and_removes_fields() {
+        let data = json!({
+            "event": {
+                "email": "  Ann@Example.COM ",
+                "first_name": "Ann",
+                "total": "41.456",
+                "paid_at": "2026-03-01T10:15:00+02:00",
+                "card": "4242",
+                "lines": [
+                    { "sku": "a-1", "qty": 2, "price": 10 },
+                    { "sku": "b-2", "qty": 0, "price": 5 },
+                    { "sku": "c-3", "qty": 1, "price": 7.5 }
+                ]
+            }
+        });
+        let config = json!({
+            "rename": { "event.first_name": "first_name" },
+            "fields": {
+                "customer.email": { "path": "event.email", "apply": ["trim", "lowercase"] },
+                "customer.greeting": { "template": "Hi {{context.event.first_name}}!" },
+                "customer.tier": { "path": "event.tier", "default": "standard" },
+                "order.total": { "path": "event.total", "apply": [{ "fn": "round", "digits": 2 }] },
+                "order.paid_on": {
+                    "path": "event.paid_at",
+                    "apply": [{ "fn": "date_add", "days": 30 }, { "fn": "date_format", "format": "%Y-%m-%d" }]
+                },
+                "order.skus": {
+                    "path": "event.lines",
+                    "filter": [{ "field": "qty", "operator": "gt", "value": 0 }],
+                    "map": { "path": "sku", "apply": ["uppercase"] }
+                },
+                "order.lines": {
+                    "path": "event.lines",
+                    "filter": [{ "field": "qty", "operator": "gte", "value": 1 }],
+                    "map": { "fields": { "id": { "path": "sku" }, "count": { "path": "qty" } } }
+                },
+                "order.units": {
+                    "path": "event.lines",
+                    "map": { "path": "qty" },
+                    "apply": ["sum"]
+                },
+                "source": { "value": "checkout" }
+            },
+            "remove": ["event.card"]
+        });
+
+        let data = run(config, data).await.expect("transform should succeed");
+
+        assert_eq!(data["first_name"], "Ann");
+        assert!(data["event"].get("first_name").is_none());
+        assert!(data["event"].get("card").is_none());
+        assert_eq!(
+            data["customer"],
+            json!({ "email": "ann@example.com", "greeting": "Hi Ann!", "tier": "standard" })
+        );
+        assert_eq!(
+            data["order"],
+            json!({
+                "total": 41.46,
+                "paid_on": "2026-03-31",
+                "skus": ["A-1", "C-3"],
+                "lines": [{ "id": "a-1", "count": 2 }, { "id": "c-3", "count": 1 }],
+                "units": 3
+            })
+        );
+        assert_eq!(data["source"], "checkout");
+    }
+
+    #[tokio::test]
+    async fn fails_on_type_mismatch_at_run_time() {
+        let result = run(
+            json!({ "fields": { "x": { "path": "name", "apply": ["sum"] } } }),
+            json!({ "name": "Ann" }),
+        )
+        .await;
+        assert!(
+            matches!(result, Err(WorkflowError::StepFailed(message)) if message.contains("'sum'"))
+        );
+    }
+
+    #[test]
+    fn validates_config_at_save_time() {
+        assert!(TransformStep::validate_config(&json!({
+            "fields": { "a.b": { "path": "x", "apply": ["trim", { "fn": "join", "separator": "," }] } }
+        }))
+        .is_ok());
+
+        for config in [
+            json!({}),
+            json!({ "fields": { "a": {} } }),
+            json!({ "fields": { "a": { "path": "x", "value": 1 } } }),
+            json!({ "fields": { "a": { "path": "x", "apply": ["eval"] } } }),
+            json!({ "fields": { "a": { "path": "x", "apply": [{ "fn": "date_format", "format": "%Q" }] } } }),
+            json!({ "fields": { "a": { "path": "x", "filter": [{ "operator": "like" }] } } }),
+            json!({ "fields": { "a..b": { "path": "x" } } }),
+            json!({ "fields": { "a": { "path": "x" } }, "script": "1 + 1" }),
+        ] {
+            assert!(
+                matches!(
+                    TransformStep::validate_config(&config),
+                    Err(WorkflowError::InvalidStepConfig(_))
+                ),
+                "{config} should be rejected"
+            );
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use serde_json::{json, Map, Value};
+use tracing::info;
+
+use super::{
+    resolve_templates, EventWait, ResumeSignal, StepContext, StepOutput, StepSuspension,
+    WorkflowStep,
+};
+use crate::error::{WorkflowError, WorkflowResult};
+
+/// Context key used for the wait result when the config has no `output_key`.
+pub const DEFAULT_WAIT_OUTPUT_KEY: &str = "wait_result";
+const MAX_WAIT_TIMEOUT_MS: u64 = 366 * 24 * 60 * 60 * 1000; // one year
+
+/// Wait-for-event step — suspends the execution until a correlated domain
+/// event arrives or the timeout passes.
+///
+/// Step config format:
+/// ```json
+/// {
+///   "event_type": "order.paid",
+///   "correlate": { "payload.order_id": "{{context.order_id}}" },
+///   "timeout_ms": 86400000,
+///   "on_timeout": "continue",
+///   "output_key": "payment"
+/// }
+/// ```
+///
+/// `correlate` maps paths of the incoming event (same shape as the trigger
+/// context `event` object) to the values they must equal. The result is
+/// stored under `output_key` as `{"received": true, "event": {...}}` or
+/// `{"received": false, "timed_out": true}`; `on_timeout` is `continue`
+/// (default), `stop` or `fail`.
+pub struct WaitForEventStep;
+
+impl WaitForEventStep {
+    /// Save-time validation of the config shape.
+    pub fn validate_config(config: &Value) -> WorkflowResult<()> {
+        event_type(config)?;
+        correlation(config)?;
+        timeout_ms(config)?;
+        on_timeout(config)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl WorkflowStep for WaitForEventStep {
+    fn step_type(&self) -> &'static str {
+        "wait_for_event"
+    }
+
+    async fn execute(&self, config: &Value, context: StepContext) -> WorkflowResult<StepOutput> {
+        Self::validate_config(config)?;
+        let event_type = event_type(config)?;
+
+        let correlation = resolve_templates(&Value::Object(correlation(config)?), &context.data)
+            .as_object()
+            .cloned()
+            .unwrap_or_default();
+        if let Some((path, _)) = correlation.iter().find(|(_, value)| value.is_null()) {
+            return Err(WorkflowError::StepFailed(format!(
+                "wait_for_event: correlation '{path}' resolved to null"
+            )));
+        }
+
+        let resume_at = timeout_ms(config)?
+            .map(|timeout_ms| Utc::now() + chrono::Duration::milliseconds(timeout_ms as i64));
+        info!(event_type = event_type, timeout_at = ?resume_at, "Waiting for event");
+
+        Ok(StepOutput::suspend_with(
+            context,
+            json!({
+                "event_type": event_type,
+                "correlation": correlation,
+                "timeout_at": resume_at,
+            }),
+            StepSuspension {
+                resume_at,
+                wait_for: Some(EventWait {
+                    event_type: event_type.to_string(),
+                    correlation,
+                }),
+                approval: None,
+            },
+        ))
+    }
+
+    async fn resume(
+        &self,
+        config: &Value,
+        context: StepContext,
+        signal: ResumeSignal,
+    ) -> WorkflowResult<StepOutput> {
+        let output_key = config
+            .get("output_key")
+            .and_then(Value::as_str)
+            .unwrap_or(DEFAULT_WAIT_OUTPUT_KEY);
+        let mut new_context = context;
+
+        match signal {
+            ResumeSignal::Event(event) => {
+                let result = json!({ "received": true, "timed_out": false, "event": event });
+                new_context.set(output_key, result.clone());
+                Ok(StepOutput::continue_with(new_context, result))
+            }
+            ResumeSignal::Timer => {
+                let result = json!({ "received": false, "timed_out": true });
+                new_context.set(output_key, result.clone());
+                match on_timeout(config)? {
+                    "stop" => Ok(StepOutput::stop_with(new_context, result)),
+                    "fail" => Err(WorkflowError::StepFailed(format!(
+                        "wait_for_event: timed out waiting for '{}'",
+                        event_type(config)?
+                    ))),
+                    _ => Ok(StepOutput::continue_with(new_context, result)),
+                }
+            }
+            ResumeSignal::Decision(_) => Err(WorkflowError::StepFailed(
+                "wait_for_event: cannot be resumed by an approval decision".into(),
+            )),
+        }
+    }
+}
+
+fn event_type(config: &Value) -> WorkflowResult<&str> {
+    config
+        .get("event_type")
+        .and_then(Value::as_str)
+        .filter(|event_type| !event_type.trim().is_empty())
+        .ok_or_else(|| {
+            WorkflowError::InvalidStepConfig("wait_for_event: missing 'event_type'".into())
+        })
+}
+
+fn correlation(config: &Value) -> WorkflowResult<Map<String, Value>> {
+    match config.get("correlate") {
+        None => Ok(Map::new()),
+        Some(Value::Object(correlation)) => Ok(correlation.clone()),
+        Some(_) => Err(WorkflowError::InvalidStepConfig(
+            "wait_for_event: 'correlate' must be an object".into(),
+        )),
+    }
+}
+
+fn timeout_ms(config: &Value) -> WorkflowResult<Option<u64>> {
+    match config.get("timeout_ms") {
+        None => Ok(None),
+        Some(value) => value
+            .as_u64()
+            .filter(|timeout_ms| *timeout_ms <= MAX_WAIT_TIMEOUT_MS)
+            .map(Some)
+            .ok_or_else(|| {
+                WorkflowError::InvalidStepConfig(format!(
+                    "wait_for_event: 'timeout_ms' must be an integer up to {MAX_WAIT_TIMEOUT_MS}"
+                ))
+            }),
+    }
+}
+
+fn on_timeout(config: &Value) -> WorkflowResult<&str> {
+    match config.get("on_timeout").map(Value::as_str) {
+        None => Ok("continue"),
+        Some(Some(mode @ ("continue" | "stop" | "fail"))) => Ok(mode),
+        Some(_) => Err(WorkflowError::InvalidStepConfig(
+            "wait_for_event: 'on_timeout' must be 'continue', 'stop' or 'fail'".into(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn suspends_with_resolved_correlation_and_records_result() {
+        let config = json!({
+            "event_type": "order.paid",
+            "correlate": { "payload.order_id": "{{context.order_id}}" },
+            "timeout_ms": 3_600_000,
+            "output_key": "payment"
+        });
+        let output = WaitForEventStep
+            .execute(&config, StepContext::new(json!({ "order_id": "o-1" })))
+            .await
+            .expect("wait should suspend");
+
+        let suspension = output.suspension.expect("step should suspend");
+        let wait = suspension.wait_for.expect("step should wait for an event");
+        assert_eq!(wait.event_type, "order.paid");
+        assert!(suspension.resume_at.is_some());
+        assert!(wait.matches(&json!({ "type": "order.paid", "payload": { "order_id": "o-1" } })));
+        assert!(!wait.matches(&json!({ "type": "order.paid", "payload": { "order_id": "o-2" } })));
+
+        let resumed = WaitForEventStep
+            .resume(
+                &config,
+                output.context,
+                ResumeSignal::Event(json!({ "payload": { "order_id": "o-1" } })),
+            )
+            .await
+            .expect("resume should succeed");
+        assert_eq!(resumed.context.get("payment").unwrap()["received"], true);
+    }
+
+    #[tokio::test]
+    async fn timeout_follows_on_timeout_mode() {
+        let config = json!({ "event_type": "order.paid", "on_timeout": "stop" });
+        let output = WaitForEventStep
+            .resume(&config, StepContext::new(json!({})), ResumeSignal::Timer)
+            .await
+            .expect("stop mode should not fail");
+        assert!(!output.should_continue);
+        assert_eq!(
+            output.context.get(DEFAULT_WAIT_OUTPUT_KEY).unwrap()["timed_out"],
+            true
+        );
+
+        let config = json!({ "event_type": "order.paid", "on_timeout": "fail" });
+        assert!(matches!(
+            WaitForEventStep
+                .resume(&config, StepContext::new(json!({})), ResumeSignal::Timer)
+                .await,
+            Err(WorkflowError::StepFailed(_))
+        ));
+    }
+
+    #[test]
+    fn validates_config() {
+        assert!(WaitForEventStep::validate_config(&json!({ "event_type": "order.paid" })).is_ok());
+        for config in [
+            json!({}),
+            json!({ "event_type": "order.paid", "correlate": "order_id" }),
+            json!({ "event_type": "order.paid", "timeout_ms": -1 }),
+            json!({ "event_type": "order.paid", "on_timeout": "retry" }),
+        ] {
+            assert!(WaitForEventStep::validate_config(&config).is_err());
+        }
+    }
+}
@@ -69,9 +69,9 @@ fn order_paid_fulfillment() -> WorkflowTemplate {
             TemplateStep {
                 step_type: StepType::Action,
                 config: json!({
-                    "service": "commerce",
-                    "action": "fulfill_order",
-                    "params": { "order_id": "{{context.order_id}}" }
+                    "action": "fulfillment.create",
+                    "input": { "order_id": "{{context.order_id}}" },
+                    "output_key": "fulfillment"
                 }),
                 on_error: OnError::Stop,
                 timeout_ms: Some(30_000),
@@ -113,7 +113,7 @@ fn new_user_onboarding() -> WorkflowTemplate {
             },
             TemplateStep {
                 step_type: StepType::Delay,
-                config: json!({ "duration_seconds": 86400 }),
+                config: json!({ "delay_ms": 86_400_000 }),
                 on_error: OnError::Skip,
                 timeout_ms: None,
             },
@@ -175,10 +175,8 @@ fn webhook_crm_sync() -> WorkflowTemplate {
             TemplateStep {
                 step_type: StepType::Condition,
                 config: json!({
-                    "field": "webhook.payload.event",
-                    "operator": "equals",
-                    "value": "contact.updated",
-                    "on_false": "stop"
+                    "expression": "webhook.payload.event == 'contact.updated'",
+                    "stop_on_false": true
                 }),
                 on_error: OnError::Stop,
                 timeout_ms: None,
@@ -0,0 +1,202 @@
+use chrono::{Duration, Utc};
+use rustok_core::UserRole;
+use rustok_workflow::entities::{ApprovalStatus, ExecutionStatus, StepType};
+use rustok_workflow::steps::ResumeSignal;
+use rustok_workflow::{WorkflowEngine, WorkflowError, WorkflowService};
+use serde_json::json;
+use uuid::Uuid;
+
+mod support;
+
+use support::{execution_state, insert_step, insert_workflow, set_field, setup_workflow_db};
+
+#[tokio::test]
+async fn assignee_decision_resumes_execution_and_is_recorded() {
+    let db = setup_workflow_db().await;
+    let engine = WorkflowEngine::new(db.clone());
+    let service = WorkflowService::new(db.clone());
+    let tenant_id = Uuid::new_v4();
+    let approver = Uuid::new_v4();
+    let workflow_id = insert_workflow(&db, tenant_id, json!({ "type": "manual" })).await;
+    let steps = vec![
+        insert_step(
+            &db,
+            workflow_id,
+            None,
+            0,
+            StepType::Approval,
+            json!({
+                "title": "Refund {{context.order}}",
+                "assignees": { "users": [approver], "roles": ["admin"] },
+                "deadline_ms": 3_600_000,
+                "output_key": "refund"
+            }),
+        )
+        .await,
+        insert_step(
+            &db,
+            workflow_id,
+            None,
+            1,
+            StepType::Transform,
+            set_field("refunded", json!(true)),
+        )
+        .await,
+    ];
+
+    let execution_id = engine
+        .execute(
+            workflow_id,
+            tenant_id,
+            None,
+            steps,
+            json!({ "order": "A-1" }),
+        )
+        .await
+        .expect("execution should start");
+    assert_eq!(
+        execution_state(&db, execution_id).await.0,
+        ExecutionStatus::Suspended
+    );
+
+    let mine = service
+        .list_approvals(
+            tenant_id,
+            Some(ApprovalStatus::Pending),
+            Some((approver, UserRole::Customer)),
+        )
+        .await
+        .expect("approval query");
+    assert_eq!(mine.len(), 1);
+    assert_eq!(mine[0].title, "Refund A-1");
+    let approval_id = mine[0].id;
+
+    let outsider = service
+        .decide_approval(
+            tenant_id,
+            approval_id,
+            Uuid::new_v4(),
+            &UserRole::Manager,
+            true,
+            None,
+        )
+        .await;
+    assert!(matches!(outsider, Err(WorkflowError::NotApprover(_))));
+
+    let approval = service
+        .decide_approval(
+            tenant_id,
+            approval_id,
+            approver,
+            &UserRole::Customer,
+            true,
+            Some("within policy".into()),
+        )
+        .await
+        .expect("assignee should decide");
+    assert_eq!(approval.status, ApprovalStatus::Approved);
+    assert_eq!(approval.decided_by, Some(approver));
+    assert_eq!(approval.comment.as_deref(), Some("within policy"));
+
+    let (status, context, _) = execution_state(&db, execution_id).await;
+    assert_eq!(status, ExecutionStatus::Completed);
+    assert_eq!(context["refund"]["status"], "approved");
+    assert_eq!(context["refund"]["comment"], "within policy");
+    assert_eq!(context["refunded"], true);
+
+    let again = service
+        .decide_approval(
+            tenant_id,
+            approval_id,
+            approver,
+            &UserRole::Customer,
+            false,
+            None,
+        )
+        .await;
+    assert!(matches!(again, Err(WorkflowError::ApprovalClosed(_))));
+}
+
+#[tokio::test]
+async fn deadline_escalates_then_auto_rejects() {
+    let db = setup_workflow_db().await;
+    let engine = WorkflowEngine::new(db.clone());
+    let service = WorkflowService::new(db.clone());
+    let tenant_id = Uuid::new_v4();
+    let workflow_id = insert_workflow(&db, tenant_id, json!({ "type": "manual" })).await;
+    let steps = vec![
+        insert_step(
+            &db,
+            workflow_id,
+            None,
+            0,
+            StepType::Approval,
+            json!({
+                "title": "Publish to main channel",
+                "assignees": { "roles": ["manager"] },
+                "deadline_ms": 3_600_000,
+                "on_deadline": "escalate",
+                "escalate_to": { "roles": ["admin"] }
+            }),
+        )
+        .await,
+        insert_step(
+            &db,
+            workflow_id,
+            None,
+            1,
+            StepType::Transform,
+            set_field("published", json!(true)),
+        )
+        .await,
+    ];
+
+    let execution_id = engine
+        .execute(workflow_id, tenant_id, None, steps, json!({}))
+        .await
+        .expect("execution should start");
+
+    let later = Utc::now() + Duration::hours(2);
+    let due = engine
+        .due_suspensions(later, 10)
+        .await
+        .expect("due query")
+        .remove(0);
+    assert!(engine
+        .resume(due, ResumeSignal::Timer)
+        .await
+        .expect("escalation should resume"));
+
+    let approvals = service
+        .list_approvals(tenant_id, None, None)
+        .await
+        .expect("approval query");
+    assert_eq!(approvals.len(), 1);
+    assert_eq!(approvals[0].status, ApprovalStatus::Pending);
+    assert_eq!(approvals[0].escalation_level, 1);
+    assert_eq!(approvals[0].assignee_roles, vec!["admin"]);
+    assert_eq!(
+        execution_state(&db, execution_id).await.0,
+        ExecutionStatus::Suspended
+    );
+
+    let due = engine
+        .due_suspensions(later + Duration::hours(2), 10)
+        .await
+        .expect("due query")
+        .remove(0);
+    assert!(engine
+        .resume(due, ResumeSignal::Timer)
+        .await
+        .expect("auto-reject should resume"));
+
+    let approval = service
+        .get_approval(tenant_id, approvals[0].id)
+        .await
+        .expect("approval");
+    assert_eq!(approval.status, ApprovalStatus::Expired);
+    let (status, context, _) = execution_state(&db, execution_id).await;
+    assert_eq!(status, ExecutionStatus::Completed);
+    assert_eq!(context["approval"]["status"], "expired");
+    assert!(context.get("published").is_none());
+}
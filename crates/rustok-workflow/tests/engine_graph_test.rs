@@ -0,0 +1,424 @@
+use std::collections::HashMap;
+
+use chrono::{Duration, Utc};
+use rustok_workflow::entities::{
+    workflow_step_execution, ExecutionStatus, StepExecutionStatus, StepType,
+    WorkflowStepExecutionEntity,
+};
+use rustok_workflow::steps::ResumeSignal;
+use rustok_workflow::WorkflowEngine;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use serde_json::json;
+use uuid::Uuid;
+
+mod support;
+
+use support::{execution_state, insert_step, insert_workflow, set_field, setup_workflow_db};
+
+/// Step id → (branch label, status) of every step execution of `execution_id`.
+async fn step_runs(
+    db: &DatabaseConnection,
+    execution_id: Uuid,
+) -> HashMap<Uuid, Vec<(Option<String>, StepExecutionStatus)>> {
+    let mut runs: HashMap<_, Vec<_>> = HashMap::new();
+    for run in WorkflowStepExecutionEntity::find()
+        .filter(workflow_step_execution::Column::ExecutionId.eq(execution_id))
+        .all(db)
+        .await
+        .expect("step executions")
+    {
+        runs.entry(run.step_id)
+            .or_default()
+            .push((run.branch, run.status));
+    }
+    runs
+}
+
+#[tokio::test]
+async fn branch_runs_only_the_selected_case_and_records_it() {
+    let db = setup_workflow_db().await;
+    let engine = WorkflowEngine::new(db.clone());
+    let tenant_id = Uuid::new_v4();
+    let workflow_id = insert_workflow(&db, tenant_id, json!({ "type": "manual" })).await;
+
+    let branch = insert_step(
+        &db,
+        workflow_id,
+        None,
+        0,
+        StepType::Branch,
+        json!({ "switch": "order.status", "cases": ["paid", "refunded"] }),
+    )
+    .await;
+    let paid = insert_step(
+        &db,
+        workflow_id,
+        Some((branch.id, Some("paid"))),
+        1,
+        StepType::Transform,
+        set_field("route", json!("fulfil")),
+    )
+    .await;
+    let refunded = insert_step(
+        &db,
+        workflow_id,
+        Some((branch.id, Some("refunded"))),
+        2,
+        StepType::Transform,
+        set_field("route", json!("restock")),
+    )
+    .await;
+    let after = insert_step(
+        &db,
+        workflow_id,
+        None,
+        3,
+        StepType::Transform,
+        set_field("done", json!(true)),
+    )
+    .await;
+    let steps = vec![
+        branch.clone(),
+        paid.clone(),
+        refunded.clone(),
+        after.clone(),
+    ];
+
+    let execution_id = engine
+        .execute(
+            workflow_id,
+            tenant_id,
+            None,
+            steps,
+            json!({ "order": { "status": "refunded" } }),
+        )
+        .await
+        .expect("execution should run");
+
+    let (status, context, _) = execution_state(&db, execution_id).await;
+    assert_eq!(status, ExecutionStatus::Completed);
+    assert_eq!(context["route"], "restock");
+    assert_eq!(context["done"], true);
+
+    let runs = step_runs(&db, execution_id).await;
+    assert!(!runs.contains_key(&paid.id));
+    assert_eq!(
+        runs[&refunded.id],
+        vec![(Some("refunded".to_string()), StepExecutionStatus::Completed)]
+    );
+    assert_eq!(runs[&after.id][0].0, None);
+}
+
+#[tokio::test]
+async fn parallel_merges_lanes_and_applies_join() {
+    let db = setup_workflow_db().await;
+    let engine = WorkflowEngine::new(db.clone());
+    let tenant_id = Uuid::new_v4();
+    let workflow_id = insert_workflow(&db, tenant_id, json!({ "type": "manual" })).await;
+
+    let any = insert_step(
+        &db,
+        workflow_id,
+        None,
+        0,
+        StepType::Parallel,
+        json!({ "join": "any" }),
+    )
+    .await;
+    let steps = vec![
+        any.clone(),
+        insert_step(
+            &db,
+            workflow_id,
+            Some((any.id, Some("crm"))),
+            1,
+            StepType::Transform,
+            set_field("crm_synced", json!(true)),
+        )
+        .await,
+        insert_step(
+            &db,
+            workflow_id,
+            Some((any.id, Some("erp"))),
+            2,
+            StepType::Condition,
+            json!({ "field": "order", "operator": "unknown" }),
+        )
+        .await,
+        insert_step(
+            &db,
+            workflow_id,
+            Some((any.id, Some("erp"))),
+            3,
+            StepType::Transform,
+            set_field("erp_synced", json!(true)),
+        )
+        .await,
+    ];
+
+    let execution_id = engine
+        .execute(workflow_id, tenant_id, None, steps.clone(), json!({}))
+        .await
+        .expect("execution should run");
+    let (status, context, _) = execution_state(&db, execution_id).await;
+    assert_eq!(status, ExecutionStatus::Completed);
+    assert_eq!(context["crm_synced"], true);
+    assert!(context.get("erp_synced").is_none());
+    let runs = step_runs(&db, execution_id).await;
+    assert_eq!(
+        runs[&steps[2].id],
+        vec![(Some("erp".to_string()), StepExecutionStatus::Failed)]
+    );
+
+    // The same lanes under `join: all` fail the execution.
+    let mut strict = steps;
+    strict[0].config = json!({ "join": "all" });
+    let execution_id = engine
+        .execute(workflow_id, tenant_id, None, strict, json!({}))
+        .await
+        .expect("execution should run");
+    let (status, _, error) = execution_state(&db, execution_id).await;
+    assert_eq!(status, ExecutionStatus::Failed);
+    assert!(error.unwrap_or_default().contains("join requires 2"));
+}
+
+#[tokio::test]
+async fn for_each_runs_body_per_item_and_collects_results() {
+    let db = setup_workflow_db().await;
+    let engine = WorkflowEngine::new(db.clone());
+    let tenant_id = Uuid::new_v4();
+    let workflow_id = insert_workflow(&db, tenant_id, json!({ "type": "manual" })).await;
+
+    let each = insert_step(
+        &db,
+        workflow_id,
+        None,
+        0,
+        StepType::ForEach,
+        json!({
+            "items": "{{context.order.lines}}",
+            "item_key": "line",
+            "concurrency": 2,
+            "collect": "line_total",
+            "output_key": "totals"
+        }),
+    )
+    .await;
+    let body = insert_step(
+        &db,
+        workflow_id,
+        Some((each.id, None)),
+        1,
+        StepType::Transform,
+        json!({ "fields": { "line_total": { "path": "line.price", "apply": [{ "fn": "multiply", "value": 2 }] } } }),
+    )
+    .await;
+
+    let execution_id = engine
+        .execute(
+            workflow_id,
+            tenant_id,
+            None,
+            vec![each, body.clone()],
+            json!({ "order": { "lines": [{ "price": 5 }, { "price": 7 }, { "price": 1 }] } }),
+        )
+        .await
+        .expect("execution should run");
+
+    let (status, context, _) = execution_state(&db, execution_id).await;
+    assert_eq!(status, ExecutionStatus::Completed);
+    assert_eq!(context["totals"], json!([10, 14, 2]));
+    assert!(context.get("line").is_none());
+
+    let mut labels: Vec<_> = step_runs(&db, execution_id).await[&body.id]
+        .iter()
+        .filter_map(|(branch, _)| branch.clone())
+        .collect();
+    labels.sort();
+    assert_eq!(labels, vec!["item[0]", "item[1]", "item[2]"]);
+}
+
+#[tokio::test]
+async fn sub_workflow_passes_typed_input_and_output() {
+    let db = setup_workflow_db().await;
+    let engine = WorkflowEngine::new(db.clone());
+    let tenant_id = Uuid::new_v4();
+
+    let child_id = insert_workflow(
+        &db,
+        tenant_id,
+        json!({
+            "type": "manual",
+            "input_schema": {
+                "type": "object",
+                "required": ["email"],
+                "properties": { "email": { "type": "string" } }
+            },
+            "output_schema": { "type": "object", "required": ["score"] }
+        }),
+    )
+    .await;
+    insert_step(
+        &db,
+        child_id,
+        None,
+        0,
+        StepType::Transform,
+        json!({ "fields": { "output.score": { "value": 42 }, "output.email": { "path": "input.email" } } }),
+    )
+    .await;
+
+    let parent_id = insert_workflow(&db, tenant_id, json!({ "type": "manual" })).await;
+    let call = insert_step(
+        &db,
+        parent_id,
+        None,
+        0,
+        StepType::SubWorkflow,
+        json!({
+            "workflow_id": child_id,
+            "input": { "email": "{{context.customer.email}}" },
+            "output_key": "risk"
+        }),
+    )
+    .await;
+
+    let execution_id = engine
+        .execute(
+            parent_id,
+            tenant_id,
+            None,
+            vec![call.clone()],
+            json!({ "customer": { "email": "ann@example.com" } }),
+        )
+        .await
+        .expect("execution should run");
+    let (status, context, _) = execution_state(&db, execution_id).await;
+    assert_eq!(status, ExecutionStatus::Completed);
+    assert_eq!(
+        context["risk"],
+        json!({ "score": 42, "email": "ann@example.com" })
+    );
+
+    // Input that violates the child's input_schema fails the calling step.
+    let execution_id = engine
+        .execute(parent_id, tenant_id, None, vec![call], json!({}))
+        .await
+        .expect("execution should run");
+    let (status, _, error) = execution_state(&db, execution_id).await;
+    assert_eq!(status, ExecutionStatus::Failed);
+    assert!(error.unwrap_or_default().contains("input_schema"));
+}
+
+#[tokio::test]
+async fn suspension_inside_branch_resumes_and_continues_after_it() {
+    let db = setup_workflow_db().await;
+    let engine = WorkflowEngine::new(db.clone());
+    let tenant_id = Uuid::new_v4();
+    let workflow_id = insert_workflow(&db, tenant_id, json!({ "type": "manual" })).await;
+
+    let branch = insert_step(
+        &db,
+        workflow_id,
+        None,
+        0,
+        StepType::Branch,
+        json!({ "when": { "field": "vip", "operator": "eq", "value": true } }),
+    )
+    .await;
+    let steps = vec![
+        branch.clone(),
+        insert_step(
+            &db,
+            workflow_id,
+            Some((branch.id, Some("then"))),
+            1,
+            StepType::Delay,
+            json!({ "delay_ms": 60 * 60 * 1000 }),
+        )
+        .await,
+        insert_step(
+            &db,
+            workflow_id,
+            Some((branch.id, Some("then"))),
+            2,
+            StepType::Transform,
+            set_field("followed_up", json!(true)),
+        )
+        .await,
+        insert_step(
+            &db,
+            workflow_id,
+            None,
+            3,
+            StepType::Transform,
+            set_field("done", json!(true)),
+        )
+        .await,
+    ];
+
+    let execution_id = engine
+        .execute(workflow_id, tenant_id, None, steps, json!({ "vip": true }))
+        .await
+        .expect("execution should run");
+    assert_eq!(
+        execution_state(&db, execution_id).await.0,
+        ExecutionStatus::Suspended
+    );
+
+    let suspension = engine
+        .due_suspensions(Utc::now() + Duration::hours(2), 10)
+        .await
+        .expect("due query")
+        .remove(0);
+    assert!(engine
+        .resume(suspension, ResumeSignal::Timer)
+        .await
+        .expect("resume should succeed"));
+
+    let (status, context, _) = execution_state(&db, execution_id).await;
+    assert_eq!(status, ExecutionStatus::Completed);
+    assert_eq!(context["followed_up"], true);
+    assert_eq!(context["done"], true);
+}
+
+#[tokio::test]
+async fn durable_waits_are_rejected_inside_for_each() {
+    let db = setup_workflow_db().await;
+    let engine = WorkflowEngine::new(db.clone());
+    let tenant_id = Uuid::new_v4();
+    let workflow_id = insert_workflow(&db, tenant_id, json!({ "type": "manual" })).await;
+
+    let each = insert_step(
+        &db,
+        workflow_id,
+        None,
+        0,
+        StepType::ForEach,
+        json!({ "items": "ids" }),
+    )
+    .await;
+    let wait = insert_step(
+        &db,
+        workflow_id,
+        Some((each.id, None)),
+        1,
+        StepType::Delay,
+        json!({ "delay_ms": 60 * 60 * 1000 }),
+    )
+    .await;
+
+    let execution_id = engine
+        .execute(
+            workflow_id,
+            tenant_id,
+            None,
+            vec![each, wait],
+            json!({ "ids": [1] }),
+        )
+        .await
+        .expect("execution should run");
+    let (status, _, error) = execution_state(&db, execution_id).await;
+    assert_eq!(status, ExecutionStatus::Failed);
+    assert!(error.unwrap_or_default().contains("cannot suspend"));
+}
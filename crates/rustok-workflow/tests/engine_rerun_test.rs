@@ -0,0 +1,299 @@
+use std::collections::HashMap;
+
+use rustok_workflow::entities::{
+    workflow_step_execution, ExecutionStatus, StepExecutionStatus, StepType,
+    WorkflowExecutionEntity, WorkflowStepExecutionEntity,
+};
+use rustok_workflow::{WorkflowEngine, WorkflowError};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel,
+    PaginatorTrait, QueryFilter, QueryOrder, Set,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+mod support;
+
+use support::{execution_state, insert_step, insert_workflow, set_field, setup_workflow_db};
+
+/// Statuses of every run of `step_id` in `execution_id`, oldest first.
+async fn step_statuses(
+    db: &DatabaseConnection,
+    execution_id: Uuid,
+    step_id: Uuid,
+) -> Vec<StepExecutionStatus> {
+    WorkflowStepExecutionEntity::find()
+        .filter(workflow_step_execution::Column::ExecutionId.eq(execution_id))
+        .filter(workflow_step_execution::Column::StepId.eq(step_id))
+        .order_by_asc(workflow_step_execution::Column::StartedAt)
+        .all(db)
+        .await
+        .expect("step executions")
+        .into_iter()
+        .map(|run| run.status)
+        .collect()
+}
+
+async fn load_execution(
+    db: &DatabaseConnection,
+    execution_id: Uuid,
+) -> rustok_workflow::entities::WorkflowExecution {
+    WorkflowExecutionEntity::find_by_id(execution_id)
+        .one(db)
+        .await
+        .expect("execution query")
+        .expect("execution should exist")
+}
+
+#[tokio::test]
+async fn retry_resumes_from_the_failed_step_with_its_context() {
+    let db = setup_workflow_db().await;
+    let engine = WorkflowEngine::new(db.clone());
+    let tenant_id = Uuid::new_v4();
+    let workflow_id = insert_workflow(&db, tenant_id, json!({ "type": "manual" })).await;
+
+    let first = insert_step(
+        &db,
+        workflow_id,
+        None,
+        0,
+        StepType::Transform,
+        set_field("prepared", json!(true)),
+    )
+    .await;
+    let broken = insert_step(
+        &db,
+        workflow_id,
+        None,
+        1,
+        StepType::Condition,
+        json!({ "field": "prepared", "operator": "bogus" }),
+    )
+    .await;
+    let last = insert_step(
+        &db,
+        workflow_id,
+        None,
+        2,
+        StepType::Transform,
+        set_field("done", json!(true)),
+    )
+    .await;
+
+    let execution_id = engine
+        .execute(
+            workflow_id,
+            tenant_id,
+            None,
+            vec![first.clone(), broken.clone(), last.clone()],
+            json!({ "payload": {} }),
+        )
+        .await
+        .expect("execution should run");
+    assert_eq!(
+        execution_state(&db, execution_id).await.0,
+        ExecutionStatus::Failed
+    );
+
+    // Fix the step, then resume the failed execution.
+    let mut fixed = broken.clone().into_active_model();
+    fixed.config = Set(json!({ "expression": "prepared == true" }));
+    fixed.update(&db).await.expect("step should update");
+
+    let claimed = engine
+        .retry(load_execution(&db, execution_id).await)
+        .await
+        .expect("retry should run");
+    assert!(claimed);
+
+    let (status, context, error) = execution_state(&db, execution_id).await;
+    assert_eq!(status, ExecutionStatus::Completed);
+    assert_eq!(error, None);
+    assert_eq!(context["prepared"], json!(true));
+    assert_eq!(context["done"], json!(true));
+    assert_eq!(
+        step_statuses(&db, execution_id, first.id).await,
+        vec![StepExecutionStatus::Completed]
+    );
+    assert_eq!(
+        step_statuses(&db, execution_id, broken.id).await,
+        vec![StepExecutionStatus::Failed, StepExecutionStatus::Completed]
+    );
+
+    // A completed execution has nothing to resume.
+    let err = engine
+        .retry(load_execution(&db, execution_id).await)
+        .await
+        .expect_err("completed execution cannot be retried");
+    assert!(matches!(err, WorkflowError::NotRerunnable(..)));
+}
+
+#[tokio::test]
+async fn rerun_from_step_starts_from_its_recorded_input() {
+    let db = setup_workflow_db().await;
+    let engine = WorkflowEngine::new(db.clone());
+    let tenant_id = Uuid::new_v4();
+    let workflow_id = insert_workflow(&db, tenant_id, json!({ "type": "manual" })).await;
+
+    let first = insert_step(
+        &db,
+        workflow_id,
+        None,
+        0,
+        StepType::Transform,
+        set_field("a", json!(1)),
+    )
+    .await;
+    let second = insert_step(
+        &db,
+        workflow_id,
+        None,
+        1,
+        StepType::Transform,
+        json!({ "fields": { "b": { "path": "a" } } }),
+    )
+    .await;
+    let for_each = insert_step(
+        &db,
+        workflow_id,
+        None,
+        2,
+        StepType::ForEach,
+        json!({ "items": "payload.items" }),
+    )
+    .await;
+    let body = insert_step(
+        &db,
+        workflow_id,
+        Some((for_each.id, None)),
+        3,
+        StepType::Transform,
+        set_field("seen", json!(true)),
+    )
+    .await;
+
+    let execution_id = engine
+        .execute(
+            workflow_id,
+            tenant_id,
+            None,
+            vec![
+                first.clone(),
+                second.clone(),
+                for_each.clone(),
+                body.clone(),
+            ],
+            json!({ "payload": { "items": [1] } }),
+        )
+        .await
+        .expect("execution should run");
+    assert_eq!(
+        execution_state(&db, execution_id).await.0,
+        ExecutionStatus::Completed
+    );
+
+    let err = engine
+        .rerun_from(load_execution(&db, execution_id).await, body.id)
+        .await
+        .expect_err("steps inside for_each cannot be re-run alone");
+    assert!(matches!(err, WorkflowError::NotRerunnable(..)));
+
+    let claimed = engine
+        .rerun_from(load_execution(&db, execution_id).await, second.id)
+        .await
+        .expect("re-run should run");
+    assert!(claimed);
+
+    let (status, context, _) = execution_state(&db, execution_id).await;
+    assert_eq!(status, ExecutionStatus::Completed);
+    assert_eq!(context["b"], json!(1));
+    assert_eq!(
+        step_statuses(&db, execution_id, first.id).await.len(),
+        1,
+        "steps before the re-run step do not run again"
+    );
+    assert_eq!(step_statuses(&db, execution_id, second.id).await.len(), 2);
+    assert_eq!(step_statuses(&db, execution_id, for_each.id).await.len(), 2);
+}
+
+#[tokio::test]
+async fn dry_run_mocks_side_effects_and_persists_nothing() {
+    let db = setup_workflow_db().await;
+    let engine = WorkflowEngine::new(db.clone());
+    let tenant_id = Uuid::new_v4();
+    let workflow_id = insert_workflow(&db, tenant_id, json!({ "type": "manual" })).await;
+
+    let http = insert_step(
+        &db,
+        workflow_id,
+        None,
+        0,
+        StepType::Http,
+        json!({ "url": "https://crm.example.com/orders/{{payload.order_id}}" }),
+    )
+    .await;
+    let transform = insert_step(
+        &db,
+        workflow_id,
+        None,
+        1,
+        StepType::Transform,
+        json!({ "fields": { "approved": { "path": "http_response.approved" } } }),
+    )
+    .await;
+    let notify = insert_step(
+        &db,
+        workflow_id,
+        None,
+        2,
+        StepType::Notify,
+        json!({ "recipient": "{{payload.email}}" }),
+    )
+    .await;
+
+    let response = engine
+        .dry_run(
+            workflow_id,
+            tenant_id,
+            vec![http.clone(), transform.clone(), notify.clone()],
+            json!({ "payload": { "order_id": 42, "email": "a@example.com" } }),
+            HashMap::from([(http.id, json!({ "approved": true }))]),
+        )
+        .await
+        .expect("dry run should run");
+
+    assert_eq!(response.status, ExecutionStatus::Completed);
+    assert_eq!(response.context["approved"], json!(true));
+    let steps: Vec<_> = response
+        .steps
+        .iter()
+        .map(|step| (step.step_id, step.mocked))
+        .collect();
+    assert_eq!(
+        steps,
+        vec![(http.id, true), (transform.id, false), (notify.id, true)]
+    );
+    assert_eq!(
+        response.steps[0].output["request"]["url"],
+        json!("https://crm.example.com/orders/42")
+    );
+    assert_eq!(
+        response.steps[2].output["request"]["recipient"],
+        json!("a@example.com")
+    );
+
+    assert_eq!(
+        WorkflowExecutionEntity::find()
+            .count(&db)
+            .await
+            .expect("count"),
+        0
+    );
+    assert_eq!(
+        WorkflowStepExecutionEntity::find()
+            .count(&db)
+            .await
+            .expect("count"),
+        0
+    );
+}
@@ -0,0 +1,166 @@
+use chrono::{Duration, Utc};
+use rustok_workflow::entities::{
+    ExecutionStatus, StepExecutionStatus, StepType, WorkflowStepExecutionEntity,
+    WorkflowSuspensionEntity,
+};
+use rustok_workflow::steps::ResumeSignal;
+use rustok_workflow::WorkflowEngine;
+use sea_orm::EntityTrait;
+use serde_json::json;
+use uuid::Uuid;
+
+mod support;
+
+use support::{execution_state, insert_step, insert_workflow, set_field, setup_workflow_db};
+
+#[tokio::test]
+async fn wait_for_event_suspends_and_resumes_on_correlated_event() {
+    let db = setup_workflow_db().await;
+    let engine = WorkflowEngine::new(db.clone());
+    let tenant_id = Uuid::new_v4();
+    let workflow_id = insert_workflow(&db, tenant_id, json!({ "type": "manual" })).await;
+    let steps = vec![
+        insert_step(
+            &db,
+            workflow_id,
+            None,
+            0,
+            StepType::WaitForEvent,
+            json!({
+                "event_type": "order.paid",
+                "correlate": { "payload.order_id": "{{context.order_id}}" },
+                "timeout_ms": 3_600_000,
+                "output_key": "payment"
+            }),
+        )
+        .await,
+        insert_step(
+            &db,
+            workflow_id,
+            None,
+            1,
+            StepType::Transform,
+            set_field("reminded", json!(false)),
+        )
+        .await,
+    ];
+
+    let execution_id = engine
+        .execute(
+            workflow_id,
+            tenant_id,
+            None,
+            steps,
+            json!({ "order_id": "o-1" }),
+        )
+        .await
+        .expect("execution should start");
+
+    let (status, _, _) = execution_state(&db, execution_id).await;
+    assert_eq!(status, ExecutionStatus::Suspended);
+    let step_executions = WorkflowStepExecutionEntity::find()
+        .all(&db)
+        .await
+        .expect("step executions");
+    assert_eq!(step_executions.len(), 1);
+    assert_eq!(step_executions[0].status, StepExecutionStatus::Waiting);
+
+    let other_order = json!({ "type": "order.paid", "payload": { "order_id": "o-2" } });
+    assert!(engine
+        .event_suspensions(tenant_id, "order.paid", &other_order)
+        .await
+        .expect("suspension query")
+        .is_empty());
+    assert!(engine
+        .event_suspensions(Uuid::new_v4(), "order.paid", &other_order)
+        .await
+        .expect("suspension query")
+        .is_empty());
+
+    let event = json!({ "type": "order.paid", "payload": { "order_id": "o-1" } });
+    let mut waiting = engine
+        .event_suspensions(tenant_id, "order.paid", &event)
+        .await
+        .expect("suspension query");
+    assert_eq!(waiting.len(), 1);
+    let suspension = waiting.remove(0);
+
+    assert!(engine
+        .resume(suspension.clone(), ResumeSignal::Event(event.clone()))
+        .await
+        .expect("resume should succeed"));
+    assert!(
+        !engine
+            .resume(suspension, ResumeSignal::Event(event))
+            .await
+            .expect("second resume should be a no-op"),
+        "a suspension must only be resumed once"
+    );
+
+    let (status, context, _) = execution_state(&db, execution_id).await;
+    assert_eq!(status, ExecutionStatus::Completed);
+    assert_eq!(context["payment"]["received"], true);
+    assert_eq!(context["payment"]["event"]["payload"]["order_id"], "o-1");
+    assert_eq!(context["reminded"], false);
+    assert!(WorkflowSuspensionEntity::find()
+        .all(&db)
+        .await
+        .expect("suspensions")
+        .is_empty());
+}
+
+#[tokio::test]
+async fn long_delay_resumes_when_due() {
+    let db = setup_workflow_db().await;
+    let engine = WorkflowEngine::new(db.clone());
+    let tenant_id = Uuid::new_v4();
+    let workflow_id = insert_workflow(&db, tenant_id, json!({ "type": "manual" })).await;
+    let steps = vec![
+        insert_step(
+            &db,
+            workflow_id,
+            None,
+            0,
+            StepType::Delay,
+            json!({ "delay_ms": 2 * 60 * 60 * 1000 }),
+        )
+        .await,
+        insert_step(
+            &db,
+            workflow_id,
+            None,
+            1,
+            StepType::Transform,
+            set_field("reminder_sent", json!(true)),
+        )
+        .await,
+    ];
+
+    let execution_id = engine
+        .execute(workflow_id, tenant_id, None, steps, json!({}))
+        .await
+        .expect("execution should start");
+    assert_eq!(
+        execution_state(&db, execution_id).await.0,
+        ExecutionStatus::Suspended
+    );
+
+    assert!(engine
+        .due_suspensions(Utc::now(), 10)
+        .await
+        .expect("due query")
+        .is_empty());
+    let mut due = engine
+        .due_suspensions(Utc::now() + Duration::hours(3), 10)
+        .await
+        .expect("due query");
+    assert_eq!(due.len(), 1);
+
+    assert!(engine
+        .resume(due.remove(0), ResumeSignal::Timer)
+        .await
+        .expect("resume should succeed"));
+    let (status, context, _) = execution_state(&db, execution_id).await;
+    assert_eq!(status, ExecutionStatus::Completed);
+    assert_eq!(context["reminder_sent"], true);
+}
@@ -0,0 +1,137 @@
+#![allow(dead_code)]
+
+use chrono::Utc;
+use rustok_workflow::entities::{
+    webhook_subscription_delivery, workflow_webhook_delivery, ExecutionStatus, OnError, StepType,
+    WebhookSubscriptionDeliveryEntity, WebhookSubscriptionEntity, WorkflowActiveModel,
+    WorkflowApprovalEntity, WorkflowEntity, WorkflowExecutionEntity, WorkflowStatus, WorkflowStep,
+    WorkflowStepActiveModel, WorkflowStepEntity, WorkflowStepExecutionEntity,
+    WorkflowSuspensionEntity, WorkflowWebhookDeliveryEntity,
+};
+use sea_orm::sea_query::Index;
+use sea_orm::{
+    ActiveModelTrait, ConnectOptions, ConnectionTrait, Database, DatabaseConnection, EntityTrait,
+    Schema, Set,
+};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+pub async fn setup_workflow_db() -> DatabaseConnection {
+    let mut options = ConnectOptions::new("sqlite::memory:");
+    options.max_connections(1).min_connections(1);
+    let db = Database::connect(options)
+        .await
+        .expect("in-memory sqlite should connect");
+
+    let builder = db.get_database_backend();
+    let schema = Schema::new(builder);
+    for statement in [
+        schema.create_table_from_entity(WorkflowEntity),
+        schema.create_table_from_entity(WorkflowStepEntity),
+        schema.create_table_from_entity(WorkflowExecutionEntity),
+        schema.create_table_from_entity(WorkflowStepExecutionEntity),
+        schema.create_table_from_entity(WorkflowSuspensionEntity),
+        schema.create_table_from_entity(WorkflowApprovalEntity),
+        schema.create_table_from_entity(WorkflowWebhookDeliveryEntity),
+        schema.create_table_from_entity(WebhookSubscriptionEntity),
+        schema.create_table_from_entity(WebhookSubscriptionDeliveryEntity),
+    ] {
+        db.execute(builder.build(&statement))
+            .await
+            .expect("failed to create workflow test table");
+    }
+    // Composite unique index that the migration adds for replay protection
+    let replay_index = Index::create()
+        .name("uidx_workflow_webhook_deliveries_replay_key")
+        .table(WorkflowWebhookDeliveryEntity)
+        .col(workflow_webhook_delivery::Column::WorkflowId)
+        .col(workflow_webhook_delivery::Column::ReplayKey)
+        .unique()
+        .to_owned();
+    db.execute(builder.build(&replay_index))
+        .await
+        .expect("failed to create replay index");
+    // One delivery per subscription and event, as in the migration
+    let event_index = Index::create()
+        .name("uidx_webhook_subscription_deliveries_event")
+        .table(WebhookSubscriptionDeliveryEntity)
+        .col(webhook_subscription_delivery::Column::SubscriptionId)
+        .col(webhook_subscription_delivery::Column::EventId)
+        .unique()
+        .to_owned();
+    db.execute(builder.build(&event_index))
+        .await
+        .expect("failed to create delivery event index");
+    db
+}
+
+pub async fn insert_workflow(
+    db: &DatabaseConnection,
+    tenant_id: Uuid,
+    trigger_config: Value,
+) -> Uuid {
+    let workflow_id = Uuid::new_v4();
+    let now = Utc::now().into();
+    WorkflowActiveModel {
+        id: Set(workflow_id),
+        tenant_id: Set(tenant_id),
+        name: Set("Test workflow".to_string()),
+        description: Set(None),
+        status: Set(WorkflowStatus::Active),
+        trigger_config: Set(trigger_config),
+        created_by: Set(None),
+        created_at: Set(now),
+        updated_at: Set(now),
+        failure_count: Set(0),
+        auto_disabled_at: Set(None),
+        webhook_slug: Set(None),
+        webhook_secret: Set(None),
+        webhook_previous_secret: Set(None),
+        webhook_secret_rotated_at: Set(None),
+    }
+    .insert(db)
+    .await
+    .expect("workflow should insert");
+    workflow_id
+}
+
+pub async fn insert_step(
+    db: &DatabaseConnection,
+    workflow_id: Uuid,
+    parent: Option<(Uuid, Option<&str>)>,
+    position: i32,
+    step_type: StepType,
+    config: Value,
+) -> WorkflowStep {
+    WorkflowStepActiveModel {
+        id: Set(Uuid::new_v4()),
+        workflow_id: Set(workflow_id),
+        parent_step_id: Set(parent.map(|(id, _)| id)),
+        branch: Set(parent.and_then(|(_, branch)| branch.map(str::to_string))),
+        position: Set(position),
+        step_type: Set(step_type),
+        config: Set(config),
+        on_error: Set(OnError::Stop),
+        timeout_ms: Set(None),
+    }
+    .insert(db)
+    .await
+    .expect("step should insert")
+}
+
+/// Transform config that sets `key` to `value`.
+pub fn set_field(key: &str, value: Value) -> Value {
+    json!({ "fields": { key: { "value": value } } })
+}
+
+pub async fn execution_state(
+    db: &DatabaseConnection,
+    execution_id: Uuid,
+) -> (ExecutionStatus, Value, Option<String>) {
+    let execution = WorkflowExecutionEntity::find_by_id(execution_id)
+        .one(db)
+        .await
+        .expect("execution query")
+        .expect("execution should exist");
+    (execution.status, execution.context, execution.error)
+}
@@ -0,0 +1,403 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::{body::Bytes, extract::State, routing::post};
+use chrono::Utc;
+use rustok_core::events::{DomainEvent, EventEnvelope};
+use rustok_core::SsrfProtection;
+use rustok_workflow::entities::{
+    SubscriptionDeliveryStatus, WebhookSubscriptionDelivery, WebhookSubscriptionDeliveryEntity,
+    WebhookSubscriptionStatus,
+};
+use rustok_workflow::services::webhook;
+use rustok_workflow::{
+    CreateWebhookSubscriptionInput, UpdateWebhookSubscriptionInput, WebhookDispatcher,
+    WebhookDispatcherConfig, WebhookSubscriptionHandler, WebhookSubscriptionResponse,
+    WebhookSubscriptionService, WorkflowError,
+};
+use sea_orm::{DatabaseConnection, EntityTrait};
+use uuid::Uuid;
+
+mod support;
+
+use support::setup_workflow_db;
+
+/// Requests received by the test endpoint, answered with `status`.
+#[derive(Clone)]
+struct Endpoint {
+    status: Arc<Mutex<StatusCode>>,
+    received: Arc<Mutex<Vec<(HeaderMap, Bytes)>>>,
+}
+
+async fn receive(State(endpoint): State<Endpoint>, headers: HeaderMap, body: Bytes) -> StatusCode {
+    endpoint.received.lock().unwrap().push((headers, body));
+    *endpoint.status.lock().unwrap()
+}
+
+/// Serves `endpoint` on a local port and returns its URL. `/redirect`
+/// answers with a redirect to it and `/large` with an oversized body.
+async fn serve(endpoint: Endpoint) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("listener should bind");
+    let url = format!("http://{}/hooks", listener.local_addr().unwrap());
+    let app = axum::Router::new()
+        .route("/hooks", post(receive))
+        .route(
+            "/redirect",
+            post(|| async {
+                (
+                    StatusCode::TEMPORARY_REDIRECT,
+                    [(header::LOCATION, "/hooks")],
+                )
+            }),
+        )
+        .route("/large", post(|| async { "x".repeat(64 * 1024) }))
+        .with_state(endpoint);
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    url
+}
+
+/// The test endpoints listen on loopback.
+fn local_network() -> SsrfProtection {
+    SsrfProtection::new().allow_private_networks()
+}
+
+fn dispatcher(db: &DatabaseConnection, config: WebhookDispatcherConfig) -> WebhookDispatcher {
+    WebhookDispatcher::with_config(db.clone(), config).with_ssrf_protection(local_network())
+}
+
+fn endpoint(status: StatusCode) -> Endpoint {
+    Endpoint {
+        status: Arc::new(Mutex::new(status)),
+        received: Arc::default(),
+    }
+}
+
+async fn subscribe(
+    db: &DatabaseConnection,
+    tenant_id: Uuid,
+    url: &str,
+    event_types: &[&str],
+) -> WebhookSubscriptionResponse {
+    WebhookSubscriptionService::new(db.clone())
+        .with_ssrf_protection(local_network())
+        .create(
+            tenant_id,
+            None,
+            CreateWebhookSubscriptionInput {
+                name: "ERP".to_string(),
+                url: url.to_string(),
+                event_types: event_types.iter().map(|t| t.to_string()).collect(),
+            },
+        )
+        .await
+        .expect("subscription should be created")
+}
+
+fn order_paid(tenant_id: Uuid) -> EventEnvelope {
+    EventEnvelope::new(
+        tenant_id,
+        None,
+        DomainEvent::OrderStatusChanged {
+            order_id: Uuid::new_v4(),
+            old_status: "pending".to_string(),
+            new_status: "paid".to_string(),
+        },
+    )
+}
+
+async fn deliveries(db: &DatabaseConnection) -> Vec<WebhookSubscriptionDelivery> {
+    WebhookSubscriptionDeliveryEntity::find()
+        .all(db)
+        .await
+        .expect("deliveries")
+}
+
+#[tokio::test]
+async fn matching_events_are_queued_once_per_subscription() {
+    let db = setup_workflow_db().await;
+    let tenant_id = Uuid::new_v4();
+    let orders = subscribe(
+        &db,
+        tenant_id,
+        "https://erp.example.com/hooks",
+        &["order.*"],
+    )
+    .await;
+    subscribe(
+        &db,
+        tenant_id,
+        "https://crm.example.com/hooks",
+        &["product.created"],
+    )
+    .await;
+    subscribe(&db, Uuid::new_v4(), "https://other.example.com", &["*"]).await;
+
+    let handler = WebhookSubscriptionHandler::new(db.clone());
+    let envelope = order_paid(tenant_id);
+    assert_eq!(handler.enqueue(&envelope).await.expect("enqueue"), 1);
+    // Re-dispatching the same outbox event does not queue it again.
+    assert_eq!(handler.enqueue(&envelope).await.expect("enqueue"), 0);
+
+    let queued = deliveries(&db).await;
+    assert_eq!(queued.len(), 1);
+    assert_eq!(queued[0].subscription_id, orders.id);
+    assert_eq!(queued[0].status, SubscriptionDeliveryStatus::Pending);
+    assert_eq!(queued[0].payload["type"], "order.status_changed");
+    assert_eq!(queued[0].payload["id"], envelope.id.to_string());
+    assert_eq!(queued[0].payload["data"]["new_status"], "paid");
+}
+
+#[tokio::test]
+async fn deliveries_are_signed_and_logged() {
+    let db = setup_workflow_db().await;
+    let tenant_id = Uuid::new_v4();
+    let endpoint = endpoint(StatusCode::OK);
+    let url = serve(endpoint.clone()).await;
+    let subscription = subscribe(&db, tenant_id, &url, &["order.status_changed"]).await;
+    let secret = subscription.secret.expect("secret is returned on create");
+
+    WebhookSubscriptionHandler::new(db.clone())
+        .enqueue(&order_paid(tenant_id))
+        .await
+        .expect("enqueue");
+    let sent = dispatcher(&db, WebhookDispatcherConfig::default())
+        .deliver_due(Utc::now())
+        .await
+        .expect("dispatch");
+    assert_eq!(sent, 1);
+
+    let (headers, body) = endpoint.received.lock().unwrap().remove(0);
+    let header = |name: &str| headers[name].to_str().unwrap().to_string();
+    let timestamp: i64 = header("x-webhook-timestamp").parse().unwrap();
+    assert!(webhook::signature_matches(
+        &secret,
+        timestamp,
+        &body,
+        &header("x-webhook-signature")
+    ));
+    assert_eq!(header("x-webhook-event"), "order.status_changed");
+
+    let delivery = deliveries(&db).await.remove(0);
+    assert_eq!(header("x-webhook-id"), delivery.id.to_string());
+    assert_eq!(delivery.status, SubscriptionDeliveryStatus::Delivered);
+    assert_eq!(delivery.attempts, 1);
+    assert_eq!(delivery.response_status, Some(200));
+    assert_eq!(
+        delivery.request_body.as_deref(),
+        std::str::from_utf8(&body).ok()
+    );
+    assert!(delivery.delivered_at.is_some());
+}
+
+#[tokio::test]
+async fn failing_endpoint_is_dead_lettered_disabled_and_redelivered() {
+    let db = setup_workflow_db().await;
+    let tenant_id = Uuid::new_v4();
+    let endpoint = endpoint(StatusCode::INTERNAL_SERVER_ERROR);
+    let url = serve(endpoint.clone()).await;
+    let subscription = subscribe(&db, tenant_id, &url, &["order.*"]).await;
+    let service = WebhookSubscriptionService::new(db.clone());
+    let dispatcher = dispatcher(
+        &db,
+        WebhookDispatcherConfig {
+            max_attempts: 2,
+            auto_disable_after: 2,
+            backoff_base: Duration::from_secs(60),
+            ..Default::default()
+        },
+    );
+
+    WebhookSubscriptionHandler::new(db.clone())
+        .enqueue(&order_paid(tenant_id))
+        .await
+        .expect("enqueue");
+
+    let now = Utc::now();
+    assert_eq!(dispatcher.deliver_due(now).await.expect("dispatch"), 1);
+    let delivery = deliveries(&db).await.remove(0);
+    assert_eq!(delivery.status, SubscriptionDeliveryStatus::Pending);
+    assert_eq!(delivery.response_status, Some(500));
+    assert!(delivery.error.is_some());
+    // Not due again until the backoff has passed.
+    assert_eq!(dispatcher.deliver_due(now).await.expect("dispatch"), 0);
+
+    let later = now + chrono::Duration::minutes(5);
+    assert_eq!(dispatcher.deliver_due(later).await.expect("dispatch"), 1);
+    let delivery = deliveries(&db).await.remove(0);
+    assert_eq!(delivery.status, SubscriptionDeliveryStatus::DeadLetter);
+    assert_eq!(delivery.attempts, 2);
+    assert_eq!(delivery.next_attempt_at, None);
+
+    let disabled = service
+        .get(tenant_id, subscription.id)
+        .await
+        .expect("subscription");
+    assert_eq!(disabled.status, WebhookSubscriptionStatus::Disabled);
+    assert_eq!(disabled.consecutive_failures, 2);
+    assert!(disabled.disabled_reason.is_some());
+
+    // Once the endpoint is fixed, re-enable it and replay the dead letter.
+    *endpoint.status.lock().unwrap() = StatusCode::NO_CONTENT;
+    let enabled = service
+        .update(
+            tenant_id,
+            subscription.id,
+            UpdateWebhookSubscriptionInput {
+                status: Some(WebhookSubscriptionStatus::Active),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("subscription should be re-enabled");
+    assert_eq!(enabled.consecutive_failures, 0);
+    assert_eq!(enabled.disabled_at, None);
+
+    let requeued = service
+        .redeliver(tenant_id, delivery.id)
+        .await
+        .expect("redeliver");
+    assert_eq!(requeued.status, SubscriptionDeliveryStatus::Pending);
+    assert_eq!(requeued.attempts, 0);
+
+    assert_eq!(
+        dispatcher
+            .deliver_due(Utc::now() + chrono::Duration::seconds(1))
+            .await
+            .expect("dispatch"),
+        1
+    );
+    let delivery = deliveries(&db).await.remove(0);
+    assert_eq!(delivery.status, SubscriptionDeliveryStatus::Delivered);
+    assert_eq!(delivery.response_status, Some(204));
+    assert_eq!(delivery.error, None);
+    assert_eq!(endpoint.received.lock().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn subscriptions_to_internal_addresses_are_rejected() {
+    let db = setup_workflow_db().await;
+    let tenant_id = Uuid::new_v4();
+    let service = WebhookSubscriptionService::new(db.clone());
+    let input = |url: &str| CreateWebhookSubscriptionInput {
+        name: "Internal".to_string(),
+        url: url.to_string(),
+        event_types: vec!["*".to_string()],
+    };
+
+    for url in [
+        "http://127.0.0.1:8080/hooks",
+        "http://localhost/hooks",
+        "http://169.254.169.254/latest/meta-data",
+        "http://192.168.1.10/hooks",
+        "http://[::ffff:10.0.0.1]/hooks",
+    ] {
+        assert!(
+            matches!(
+                service.create(tenant_id, None, input(url)).await,
+                Err(WorkflowError::InvalidSubscription(_))
+            ),
+            "{url} should be rejected"
+        );
+    }
+
+    let subscription = service
+        .create(tenant_id, None, input("https://erp.example.com/hooks"))
+        .await
+        .expect("public URL should be accepted");
+    let moved = service
+        .update(
+            tenant_id,
+            subscription.id,
+            UpdateWebhookSubscriptionInput {
+                url: Some("http://10.0.0.5/hooks".to_string()),
+                ..Default::default()
+            },
+        )
+        .await;
+    assert!(matches!(moved, Err(WorkflowError::InvalidSubscription(_))));
+}
+
+#[tokio::test]
+async fn dispatcher_checks_the_resolved_address_before_sending() {
+    let db = setup_workflow_db().await;
+    let tenant_id = Uuid::new_v4();
+    let endpoint = endpoint(StatusCode::OK);
+    let url = serve(endpoint.clone()).await;
+    // Saved while loopback was allowed, e.g. a host that now resolves inward
+    subscribe(&db, tenant_id, &url, &["order.*"]).await;
+    WebhookSubscriptionHandler::new(db.clone())
+        .enqueue(&order_paid(tenant_id))
+        .await
+        .expect("enqueue");
+
+    let attempted = WebhookDispatcher::new(db.clone())
+        .deliver_due(Utc::now())
+        .await
+        .expect("dispatch");
+    assert_eq!(attempted, 1);
+    assert!(endpoint.received.lock().unwrap().is_empty());
+
+    let delivery = deliveries(&db).await.remove(0);
+    assert_eq!(delivery.status, SubscriptionDeliveryStatus::Pending);
+    assert_eq!(delivery.attempts, 1);
+    assert_eq!(delivery.response_status, None);
+    assert!(delivery
+        .error
+        .as_deref()
+        .is_some_and(|error| error.contains("Private IP addresses are not allowed")));
+    assert!(delivery.next_attempt_at.is_some());
+}
+
+#[tokio::test]
+async fn redirects_are_not_followed_and_response_bodies_are_capped() {
+    let db = setup_workflow_db().await;
+    let tenant_id = Uuid::new_v4();
+    let endpoint = endpoint(StatusCode::OK);
+    let url = serve(endpoint.clone()).await;
+    subscribe(
+        &db,
+        tenant_id,
+        &url.replace("/hooks", "/redirect"),
+        &["order.*"],
+    )
+    .await;
+    subscribe(
+        &db,
+        tenant_id,
+        &url.replace("/hooks", "/large"),
+        &["order.*"],
+    )
+    .await;
+    WebhookSubscriptionHandler::new(db.clone())
+        .enqueue(&order_paid(tenant_id))
+        .await
+        .expect("enqueue");
+
+    let attempted = dispatcher(&db, WebhookDispatcherConfig::default())
+        .deliver_due(Utc::now())
+        .await
+        .expect("dispatch");
+    assert_eq!(attempted, 2);
+    assert!(endpoint.received.lock().unwrap().is_empty());
+
+    let deliveries = deliveries(&db).await;
+    let redirected = deliveries
+        .iter()
+        .find(|delivery| delivery.response_status == Some(307))
+        .expect("redirect response should be logged");
+    assert_eq!(redirected.status, SubscriptionDeliveryStatus::Pending);
+    assert!(redirected.error.is_some());
+
+    let large = deliveries
+        .iter()
+        .find(|delivery| delivery.response_status == Some(200))
+        .expect("large response should be logged");
+    assert_eq!(large.status, SubscriptionDeliveryStatus::Delivered);
+    assert!(large
+        .response_body
+        .as_deref()
+        .is_some_and(|body| body.len() <= 4096));
+}
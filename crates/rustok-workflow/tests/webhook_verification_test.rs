@@ -0,0 +1,288 @@
+use chrono::Utc;
+use rustok_workflow::entities::{
+    workflow_execution, StepType, WebhookDeliveryStatus, WorkflowActiveModel,
+    WorkflowExecutionEntity, WorkflowWebhookDeliveryActiveModel, WorkflowWebhookDeliveryEntity,
+};
+use rustok_workflow::services::webhook::{sign, WebhookRequest, DELIVERY_RETENTION_DAYS};
+use rustok_workflow::{CreateWorkflowInput, UpdateWorkflowInput, WorkflowError, WorkflowService};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
+    Set,
+};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+mod support;
+
+use support::{insert_step, insert_workflow, set_field, setup_workflow_db};
+
+async fn webhook_workflow(db: &DatabaseConnection, tenant_id: Uuid, trigger_config: Value) -> Uuid {
+    let workflow_id = insert_workflow(db, tenant_id, trigger_config).await;
+    WorkflowActiveModel {
+        id: Set(workflow_id),
+        webhook_slug: Set(Some("orders".to_string())),
+        ..Default::default()
+    }
+    .update(db)
+    .await
+    .expect("webhook slug should be set");
+    insert_step(
+        db,
+        workflow_id,
+        None,
+        0,
+        StepType::Transform,
+        set_field("received", json!(true)),
+    )
+    .await;
+    workflow_id
+}
+
+fn signed(secret: &str, delivery_id: &str, body: &str) -> WebhookRequest {
+    let timestamp = Utc::now().timestamp();
+    WebhookRequest {
+        timestamp: Some(timestamp.to_string()),
+        delivery_id: Some(delivery_id.to_string()),
+        signature: Some(format!(
+            "sha256={}",
+            sign(secret, timestamp, body.as_bytes())
+        )),
+        body: body.as_bytes().to_vec(),
+    }
+}
+
+#[tokio::test]
+async fn signed_delivery_triggers_once_and_replay_is_rejected() {
+    let db = setup_workflow_db().await;
+    let service = WorkflowService::new(db.clone());
+    let tenant_id = Uuid::new_v4();
+    let workflow_id = webhook_workflow(&db, tenant_id, json!({ "type": "webhook" })).await;
+
+    let unsigned = service
+        .receive_webhook(tenant_id, "orders", signed("any", "evt_0", "{}"))
+        .await;
+    assert!(matches!(unsigned, Err(WorkflowError::WebhookRejected(_))));
+
+    let secret = service
+        .rotate_webhook_secret(tenant_id, workflow_id)
+        .await
+        .expect("secret should rotate");
+    let request = signed(&secret, "evt_1", r#"{"order":"A-1"}"#);
+    let executions = service
+        .receive_webhook(tenant_id, "orders", request.clone())
+        .await
+        .expect("signed delivery should be accepted");
+    assert_eq!(executions.len(), 1);
+
+    let replay = service.receive_webhook(tenant_id, "orders", request).await;
+    assert!(matches!(replay, Err(WorkflowError::WebhookRejected(_))));
+
+    // Rejections reach the table only with the periodic flush
+    let deliveries = service
+        .list_webhook_deliveries(tenant_id, workflow_id)
+        .await
+        .expect("deliveries should list");
+    assert_eq!(deliveries.len(), 1);
+    service.flush_webhook_deliveries().await.unwrap();
+
+    let deliveries = service
+        .list_webhook_deliveries(tenant_id, workflow_id)
+        .await
+        .expect("deliveries should list");
+    let accepted: Vec<_> = deliveries
+        .iter()
+        .filter(|delivery| delivery.status == WebhookDeliveryStatus::Accepted)
+        .collect();
+    assert_eq!(deliveries.len(), 3);
+    assert_eq!(accepted.len(), 1);
+    assert_eq!(accepted[0].execution_ids, executions);
+    assert_eq!(WorkflowExecutionEntity::find().count(&db).await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn rotation_keeps_previous_secret_and_schema_guards_payload() {
+    let db = setup_workflow_db().await;
+    let service = WorkflowService::new(db.clone());
+    let tenant_id = Uuid::new_v4();
+    let workflow_id = webhook_workflow(
+        &db,
+        tenant_id,
+        json!({
+            "type": "webhook",
+            "payload_schema": {
+                "type": "object",
+                "required": ["order"],
+                "properties": { "order": { "type": "string" } }
+            }
+        }),
+    )
+    .await;
+
+    let old_secret = service
+        .rotate_webhook_secret(tenant_id, workflow_id)
+        .await
+        .unwrap();
+    let new_secret = service
+        .rotate_webhook_secret(tenant_id, workflow_id)
+        .await
+        .unwrap();
+    assert_ne!(old_secret, new_secret);
+
+    let invalid = service
+        .receive_webhook(
+            tenant_id,
+            "orders",
+            signed(&new_secret, "evt_1", r#"{"order":1}"#),
+        )
+        .await;
+    assert!(matches!(
+        invalid,
+        Err(WorkflowError::InvalidWebhookPayload(_))
+    ));
+
+    // The rejected delivery id is not burned and the old secret still works
+    let executions = service
+        .receive_webhook(
+            tenant_id,
+            "orders",
+            signed(&old_secret, "evt_1", r#"{"order":"A-1"}"#),
+        )
+        .await
+        .expect("previous secret should verify during the grace period");
+    assert_eq!(executions.len(), 1);
+
+    service.flush_webhook_deliveries().await.unwrap();
+    let deliveries = service
+        .list_webhook_deliveries(tenant_id, workflow_id)
+        .await
+        .unwrap();
+    let rejected = deliveries
+        .iter()
+        .find(|delivery| delivery.status == WebhookDeliveryStatus::Rejected)
+        .expect("rejected attempt should be recorded");
+    assert!(rejected
+        .reason
+        .as_deref()
+        .is_some_and(|reason| reason.contains("Invalid webhook payload")));
+}
+
+#[tokio::test]
+async fn only_the_workflow_whose_secret_verified_runs() {
+    let db = setup_workflow_db().await;
+    let service = WorkflowService::new(db.clone());
+    let tenant_id = Uuid::new_v4();
+    // Two rows sharing a slug, as saved before slugs were unique
+    let first = webhook_workflow(&db, tenant_id, json!({ "type": "webhook" })).await;
+    let second = webhook_workflow(&db, tenant_id, json!({ "type": "webhook" })).await;
+    service
+        .rotate_webhook_secret(tenant_id, first)
+        .await
+        .unwrap();
+    let secret = service
+        .rotate_webhook_secret(tenant_id, second)
+        .await
+        .unwrap();
+
+    let executions = service
+        .receive_webhook(tenant_id, "orders", signed(&secret, "evt_1", "{}"))
+        .await
+        .expect("delivery signed for the second workflow should be accepted");
+    assert_eq!(executions.len(), 1);
+    let runs = |workflow_id: Uuid| {
+        WorkflowExecutionEntity::find()
+            .filter(workflow_execution::Column::WorkflowId.eq(workflow_id))
+            .count(&db)
+    };
+    assert_eq!(runs(first).await.unwrap(), 0);
+    assert_eq!(runs(second).await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn webhook_slugs_are_unique_per_tenant() {
+    let db = setup_workflow_db().await;
+    let service = WorkflowService::new(db.clone());
+    let tenant_id = Uuid::new_v4();
+    let input = |name: &str| CreateWorkflowInput {
+        name: name.to_string(),
+        description: None,
+        trigger_config: json!({ "type": "webhook" }),
+        webhook_slug: Some("orders".to_string()),
+    };
+
+    service
+        .create(tenant_id, None, input("first"))
+        .await
+        .unwrap();
+    let taken = service.create(tenant_id, None, input("second")).await;
+    assert!(matches!(taken, Err(WorkflowError::InvalidTriggerConfig(_))));
+    service
+        .create(Uuid::new_v4(), None, input("other tenant"))
+        .await
+        .expect("slugs are scoped to the tenant");
+
+    let other = service
+        .create(
+            tenant_id,
+            None,
+            CreateWorkflowInput {
+                webhook_slug: None,
+                ..input("second")
+            },
+        )
+        .await
+        .unwrap();
+    let update = |slug: &str| UpdateWorkflowInput {
+        webhook_slug: Some(slug.to_string()),
+        ..Default::default()
+    };
+    let moved = service
+        .update(tenant_id, other, None, update("orders"))
+        .await;
+    assert!(matches!(moved, Err(WorkflowError::InvalidTriggerConfig(_))));
+}
+
+#[tokio::test]
+async fn flush_purges_expired_deliveries() {
+    let db = setup_workflow_db().await;
+    let service = WorkflowService::new(db.clone());
+    let tenant_id = Uuid::new_v4();
+    let delivery = |age_days: i64| WorkflowWebhookDeliveryActiveModel {
+        id: Set(Uuid::new_v4()),
+        tenant_id: Set(tenant_id),
+        workflow_id: Set(None),
+        webhook_slug: Set("orders".to_string()),
+        delivery_id: Set(None),
+        replay_key: Set(None),
+        status: Set(WebhookDeliveryStatus::Rejected),
+        reason: Set(None),
+        execution_ids: Set(json!([])),
+        received_at: Set((Utc::now() - chrono::Duration::days(age_days)).fixed_offset()),
+    };
+    delivery(DELIVERY_RETENTION_DAYS + 1)
+        .insert(&db)
+        .await
+        .unwrap();
+    delivery(1).insert(&db).await.unwrap();
+
+    let unknown = service
+        .receive_webhook(tenant_id, "missing", signed("any", "evt_1", "{}"))
+        .await;
+    assert!(matches!(unknown, Err(WorkflowError::WebhookRejected(_))));
+    assert_eq!(
+        WorkflowWebhookDeliveryEntity::find()
+            .count(&db)
+            .await
+            .unwrap(),
+        2
+    );
+
+    service.flush_webhook_deliveries().await.unwrap();
+    let remaining = WorkflowWebhookDeliveryEntity::find()
+        .all(&db)
+        .await
+        .unwrap();
+    assert_eq!(remaining.len(), 2);
+    assert!(remaining
+        .iter()
+        .any(|delivery| delivery.webhook_slug == "missing"));
+}